@@ -49,6 +49,7 @@ fn main() {
         .insert_resource(BallisticsEnvironment {
             gravity: Vec3::new(0.0, -9.81, 0.0),
             air_density: 1.225,
+            water_density: 1000.0,
             wind: Vec3::ZERO,
         })
         .add_systems(Startup, setup)
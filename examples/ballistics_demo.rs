@@ -17,6 +17,7 @@ fn main() {
         .insert_resource(BallisticsEnvironment {
             gravity: Vec3::new(0.0, -9.81, 0.0),
             air_density: 1.225,
+            water_density: 1000.0,
             wind: Vec3::new(2.0, 0.0, 0.0), // Wind blows to the right
         })
         .add_systems(Startup, setup)
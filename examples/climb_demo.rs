@@ -32,7 +32,7 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_plugins(PhysicsPlugins::default())
-        .add_plugins(GameControllerPlugin)
+        .add_plugins(GameControllerPlugin::default())
         .add_systems(Startup, setup)
         .add_systems(Update, (
             spawn_climbable_surfaces,
@@ -449,6 +449,7 @@ fn update_climb_demo_ui(
                 ClimbState::ClimbingLeft => "Climbing Left",
                 ClimbState::ClimbingRight => "Climbing Right",
                 ClimbState::Vaulting => "Vaulting",
+                ClimbState::FreeClimbing => "Free Climbing",
                 ClimbState::Falling => "Falling",
             };
 
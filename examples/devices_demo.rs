@@ -8,19 +8,20 @@
 
 use bevy::prelude::*;
 use bevy_allinone::prelude::*;
-use bevy_allinone::devices::simple_switch::{SimpleSwitch, SimpleSwitchEvent, SimpleSwitchEventType, SimpleSwitchPlugin, SimpleSwitchEventQueue};
-use bevy_allinone::devices::pressure_plate::{PressurePlate, PressurePlateActivated, PressurePlateDeactivated, PressurePlatePlugin, PressurePlateActivatedQueue, PressurePlateDeactivatedQueue};
-use bevy_allinone::devices::recharger_station::{RechargerStation, RechargerStationHealingStarted, RechargerStationFullyHealed, RechargerStationPlugin, RechargerStationHealingStartedQueue, RechargerStationFullyHealedQueue};
-use bevy_allinone::devices::examine_object::{ExamineObject, ExamineObjectEvent, ExamineObjectEventType, ExamineObjectPlugin, ExamineObjectEventQueue};
+use bevy_allinone::devices::simple_switch::{SimpleSwitch, SimpleSwitchEvent, SimpleSwitchEventType, SimpleSwitchPlugin};
+use bevy_allinone::devices::pressure_plate::{PressurePlate, PressurePlateActivated, PressurePlateDeactivated, PressurePlatePlugin};
+use bevy_allinone::devices::recharger_station::{RechargerStation, RechargerStationHealingStarted, RechargerStationFullyHealed, RechargerStationPlugin};
+use bevy_allinone::devices::examine_object::{ExamineObject, ExamineObjectEvent, ExamineObjectEventType, ExamineObjectPlugin};
 use bevy_allinone::devices::DevicesPlugin;
 use bevy_allinone::interaction::{Interactable, InteractionType};
 use bevy_allinone::camera::{CameraController, CameraMode, CameraState};
 use avian3d::prelude::*;
+use bevy_allinone::utils::QueueReader;
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(GameControllerPlugin)
+        .add_plugins(GameControllerPlugin::default())
         .add_plugins(DevicesPlugin)
         .add_plugins(SimpleSwitchPlugin)
         .add_plugins(PressurePlatePlugin)
@@ -192,10 +193,10 @@ fn setup(
 
 /// Handle simple switch events
 fn handle_switch_events(
-    mut event_queue: ResMut<SimpleSwitchEventQueue>,
+    mut event_queue: QueueReader<SimpleSwitchEvent>,
     mut ui_query: Query<&mut Text, With<UiState>>,
 ) {
-    let events: Vec<_> = event_queue.0.drain(..).collect();
+    let events: Vec<_> = event_queue.read().cloned().collect();
     for event in events {
         let status_text = match event.event_type {
             SimpleSwitchEventType::SingleSwitch => "Switch activated (single)",
@@ -216,18 +217,18 @@ fn handle_switch_events(
 
 /// Handle pressure plate events
 fn handle_pressure_plate_events(
-    mut activated_queue: ResMut<PressurePlateActivatedQueue>,
-    mut deactivated_queue: ResMut<PressurePlateDeactivatedQueue>,
+    mut activated_queue: QueueReader<PressurePlateActivated>,
+    mut deactivated_queue: QueueReader<PressurePlateDeactivated>,
     mut ui_query: Query<&mut Text, With<UiState>>,
 ) {
-    for event in activated_queue.0.drain(..) {
+    for event in activated_queue.read().cloned() {
          let status_text = format!( "Pressure plate activated ({} objects)", event.objects_on_plate.len() );
          if let Some(mut text) = ui_query.iter_mut().next() {
              text.0 = format!("Devices Demo\n...\nStatus: {}", status_text);
          }
     }
     
-    for _ in deactivated_queue.0.drain(..) {
+    for _ in deactivated_queue.read() {
          if let Some(mut text) = ui_query.iter_mut().next() {
              text.0 = format!("Devices Demo\n...\nStatus: Pressure plate deactivated");
          }
@@ -236,17 +237,17 @@ fn handle_pressure_plate_events(
 
 /// Handle recharger station events
 fn handle_recharger_station_events(
-    mut started_queue: ResMut<RechargerStationHealingStartedQueue>,
-    mut healed_queue: ResMut<RechargerStationFullyHealedQueue>,
+    mut started_queue: QueueReader<RechargerStationHealingStarted>,
+    mut healed_queue: QueueReader<RechargerStationFullyHealed>,
     mut ui_query: Query<&mut Text, With<UiState>>,
 ) {
-    for _ in started_queue.0.drain(..) {
+    for _ in started_queue.read() {
         if let Some(mut text) = ui_query.iter_mut().next() {
             text.0 = format!("Devices Demo\n...\nStatus: Healing started");
         }
     }
     
-    for _ in healed_queue.0.drain(..) {
+    for _ in healed_queue.read() {
         if let Some(mut text) = ui_query.iter_mut().next() {
             text.0 = format!("Devices Demo\n...\nStatus: Fully healed");
         }
@@ -255,10 +256,10 @@ fn handle_recharger_station_events(
 
 /// Handle examine events
 fn handle_examine_events(
-    mut event_queue: ResMut<ExamineObjectEventQueue>,
+    mut event_queue: QueueReader<ExamineObjectEvent>,
     mut ui_query: Query<&mut Text, With<UiState>>,
 ) {
-    let events: Vec<_> = event_queue.0.drain(..).collect();
+    let events: Vec<_> = event_queue.read().cloned().collect();
     for event in events {
         let status_text = match event.event_type {
             ExamineObjectEventType::Start => "Examine started",
@@ -10,7 +10,7 @@ use bevy_allinone::interaction::{Interactable, InteractionType, InteractionPlugi
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(GameControllerPlugin)
+        .add_plugins(GameControllerPlugin::default())
         .add_plugins(DialogPlugin)
         .add_plugins(InteractionPlugin)
         .add_systems(Startup, setup)
@@ -4,7 +4,7 @@ use bevy_allinone::prelude::*;
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(GameControllerPlugin)
+        .add_plugins(GameControllerPlugin::default())
         .add_systems(Startup, setup)
         .run();
 }
@@ -5,7 +5,7 @@ use bevy_allinone::prelude::*;
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(GameControllerPlugin)
+        .add_plugins(GameControllerPlugin::default())
         .add_systems(Startup, setup_systems)
         .add_systems(Update, (update_skills, handle_input, display_skills))
         .run();
@@ -6,7 +6,7 @@ use bevy_allinone::tutorial::TutorialEventQueue;
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(GameControllerPlugin)
+        .add_plugins(GameControllerPlugin::default())
         .add_systems(Startup, setup_tutorial_demo)
         .add_systems(Update, trigger_tutorial)
         .run();
@@ -57,6 +57,6 @@ fn trigger_tutorial(
     mut events: ResMut<TutorialEventQueue>,
 ) {
     if keys.just_pressed(KeyCode::KeyT) {
-        events.0.push(TutorialEvent::Open(1));
+        events.send(TutorialEvent::Open(1));
     }
 }
@@ -8,7 +8,7 @@ use bevy_allinone::prelude::*;
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(GameControllerPlugin)
+        .add_plugins(GameControllerPlugin::default())
         .add_systems(Startup, setup)
         .add_systems(Update, (
             handle_vendor_interactions,
@@ -49,6 +49,7 @@ fn setup(
         Currency {
             amount: 500.0,
             currency_type: CurrencyType::Gold,
+            repair_multiplier: 1.0,
         },
         Inventory::default(),
     ));
@@ -134,11 +135,12 @@ fn handle_vendor_interactions(
             for (index, shop_item) in vendor_inventory.items.iter().enumerate() {
                 if shop_item.is_available(player_level) {
                     if player_currency.amount >= shop_item.buy_price {
-                        purchase_events.0.push(PurchaseItemEvent {
+                        purchase_events.send(PurchaseItemEvent {
                             vendor_entity,
                             item_index: index,
                             amount: 1,
                             buyer_entity: player_entity,
+                            transaction_id: None,
                         });
                         info!("Buying {} for {} gold", shop_item.item.name, shop_item.buy_price);
                         break;
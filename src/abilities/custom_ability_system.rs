@@ -1,30 +1,30 @@
 use bevy::prelude::*;
 use super::ability_info::AbilityInfo;
+use crate::utils::EventQueue;
+
+/// Which custom ability callback fired
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CustomAbilityEventKind {
+    PressDown,
+    PressHold,
+    PressUp,
+    PressUpBefore,
+    PressUpAfter,
+    Update,
+    Enable,
+    Disable,
+    Deactivate,
+}
 
 #[derive(Debug, Clone)]
 pub struct CustomAbilityEvent {
     pub entity: Entity,
     pub ability_name: String,
+    pub kind: CustomAbilityEventKind,
 }
 
-#[derive(Resource, Default)]
-pub struct CustomAbilityPressDownEventQueue(pub Vec<CustomAbilityEvent>);
-#[derive(Resource, Default)]
-pub struct CustomAbilityPressHoldEventQueue(pub Vec<CustomAbilityEvent>);
-#[derive(Resource, Default)]
-pub struct CustomAbilityPressUpEventQueue(pub Vec<CustomAbilityEvent>);
-#[derive(Resource, Default)]
-pub struct CustomAbilityPressUpBeforeEventQueue(pub Vec<CustomAbilityEvent>);
-#[derive(Resource, Default)]
-pub struct CustomAbilityPressUpAfterEventQueue(pub Vec<CustomAbilityEvent>);
-#[derive(Resource, Default)]
-pub struct CustomAbilityUpdateEventQueue(pub Vec<CustomAbilityEvent>);
-#[derive(Resource, Default)]
-pub struct CustomAbilityEnableEventQueue(pub Vec<CustomAbilityEvent>);
-#[derive(Resource, Default)]
-pub struct CustomAbilityDisableEventQueue(pub Vec<CustomAbilityEvent>);
-#[derive(Resource, Default)]
-pub struct CustomAbilityDeactivateEventQueue(pub Vec<CustomAbilityEvent>);
+/// Queue for custom ability callbacks
+pub type CustomAbilityEventQueue = EventQueue<CustomAbilityEvent>;
 
 /// Generic custom ability behavior.
 ///
@@ -92,42 +92,38 @@ impl Default for CustomAbilitySystem {
 pub fn update_custom_ability_system(
     time: Res<Time>,
     mut query: Query<(Entity, &AbilityInfo, &mut CustomAbilitySystem)>,
-    mut press_down_events: ResMut<CustomAbilityPressDownEventQueue>,
-    mut press_hold_events: ResMut<CustomAbilityPressHoldEventQueue>,
-    mut press_up_events: ResMut<CustomAbilityPressUpEventQueue>,
-    mut press_up_before_events: ResMut<CustomAbilityPressUpBeforeEventQueue>,
-    mut press_up_after_events: ResMut<CustomAbilityPressUpAfterEventQueue>,
-    mut update_events: ResMut<CustomAbilityUpdateEventQueue>,
-    mut enable_events: ResMut<CustomAbilityEnableEventQueue>,
-    mut disable_events: ResMut<CustomAbilityDisableEventQueue>,
-    mut deactivate_events: ResMut<CustomAbilityDeactivateEventQueue>,
+    mut events: ResMut<CustomAbilityEventQueue>,
 ) {
     for (entity, ability, mut system) in query.iter_mut() {
         if system.use_event_on_update_ability_state {
-            update_events.0.push(CustomAbilityEvent {
+            events.send(CustomAbilityEvent {
                 entity,
                 ability_name: ability.name.clone(),
+                kind: CustomAbilityEventKind::Update,
             });
         }
 
         if ability.enabled && !system.prev_enabled && system.use_event_on_enable_ability {
-            enable_events.0.push(CustomAbilityEvent {
+            events.send(CustomAbilityEvent {
                 entity,
                 ability_name: ability.name.clone(),
+                kind: CustomAbilityEventKind::Enable,
             });
         }
 
         if !ability.enabled && system.prev_enabled && system.use_event_on_disable_ability {
-            disable_events.0.push(CustomAbilityEvent {
+            events.send(CustomAbilityEvent {
                 entity,
                 ability_name: ability.name.clone(),
+                kind: CustomAbilityEventKind::Disable,
             });
         }
 
         if system.prev_active && !ability.active && system.use_event_on_deactivate_ability {
-            deactivate_events.0.push(CustomAbilityEvent {
+            events.send(CustomAbilityEvent {
                 entity,
                 ability_name: ability.name.clone(),
+                kind: CustomAbilityEventKind::Deactivate,
             });
         }
 
@@ -135,9 +131,10 @@ pub fn update_custom_ability_system(
             system.last_time_press_down_used = time.elapsed_secs();
             system.event_triggered_on_press_hold = false;
             if system.use_event_on_press_down {
-                press_down_events.0.push(CustomAbilityEvent {
+                events.send(CustomAbilityEvent {
                     entity,
                     ability_name: ability.name.clone(),
+                    kind: CustomAbilityEventKind::PressDown,
                 });
             }
         }
@@ -146,9 +143,10 @@ pub fn update_custom_ability_system(
             if system.use_delay_time_to_use_event_on_press_hold {
                 if (!system.use_event_on_press_hold_just_once || !system.event_triggered_on_press_hold) {
                     if time.elapsed_secs() - system.last_time_press_down_used >= system.delay_time_to_use_event_on_press_hold {
-                        press_hold_events.0.push(CustomAbilityEvent {
+                        events.send(CustomAbilityEvent {
                             entity,
                             ability_name: ability.name.clone(),
+                            kind: CustomAbilityEventKind::PressHold,
                         });
                         if system.use_event_on_press_hold_just_once {
                             system.event_triggered_on_press_hold = true;
@@ -156,9 +154,10 @@ pub fn update_custom_ability_system(
                     }
                 }
             } else {
-                press_hold_events.0.push(CustomAbilityEvent {
+                events.send(CustomAbilityEvent {
                     entity,
                     ability_name: ability.name.clone(),
+                    kind: CustomAbilityEventKind::PressHold,
                 });
             }
         }
@@ -168,27 +167,31 @@ pub fn update_custom_ability_system(
                 if system.use_event_on_press_up_before_and_after {
                     let elapsed = time.elapsed_secs() - system.last_time_press_down_used;
                     if elapsed < system.delay_time_to_use_event_on_press_up_before {
-                        press_up_before_events.0.push(CustomAbilityEvent {
+                        events.send(CustomAbilityEvent {
                             entity,
                             ability_name: ability.name.clone(),
+                            kind: CustomAbilityEventKind::PressUpBefore,
                         });
                     } else if elapsed > system.delay_time_to_use_event_on_press_up_after {
-                        press_up_after_events.0.push(CustomAbilityEvent {
+                        events.send(CustomAbilityEvent {
                             entity,
                             ability_name: ability.name.clone(),
+                            kind: CustomAbilityEventKind::PressUpAfter,
                         });
                     }
                 } else if system.use_delay_time_to_use_event_on_press_up {
                     if time.elapsed_secs() - system.last_time_press_down_used >= system.delay_time_to_use_event_on_press_up {
-                        press_up_events.0.push(CustomAbilityEvent {
+                        events.send(CustomAbilityEvent {
                             entity,
                             ability_name: ability.name.clone(),
+                            kind: CustomAbilityEventKind::PressUp,
                         });
                     }
                 } else {
-                    press_up_events.0.push(CustomAbilityEvent {
+                    events.send(CustomAbilityEvent {
                         entity,
                         ability_name: ability.name.clone(),
+                        kind: CustomAbilityEventKind::PressUp,
                     });
                 }
             }
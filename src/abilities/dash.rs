@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use avian3d::prelude::LinearVelocity;
 use super::ability_info::AbilityInfo;
+use crate::character::Player;
+use crate::input::{InputAction, InputBuffer};
 
 /// Dash ability controller.
 ///
@@ -27,16 +29,19 @@ impl Default for DashAbility {
     }
 }
 
-/// Start a dash when the matching ability is active.
+/// Start a dash when the matching ability is active, or when the player pressed
+/// dash. A press during a dash is buffered and dashes again right after it.
 pub fn start_dash_from_ability(
-    mut query: Query<(&AbilityInfo, &mut DashAbility, &GlobalTransform, &mut LinearVelocity)>,
+    mut input_buffer: ResMut<InputBuffer>,
+    mut query: Query<(&AbilityInfo, &mut DashAbility, &GlobalTransform, &mut LinearVelocity, Has<Player>)>,
 ) {
-    for (ability, mut dash, transform, mut velocity) in query.iter_mut() {
-        if ability.name != dash.ability_name {
+    for (ability, mut dash, transform, mut velocity, is_player) in query.iter_mut() {
+        if ability.name != dash.ability_name || dash.active {
             continue;
         }
 
-        if ability.active && !dash.active {
+        let dash_pressed = is_player && ability.enabled && input_buffer.consume(InputAction::Dash);
+        if ability.active || dash_pressed {
             dash.active = true;
             dash.timer = dash.dash_duration;
             let forward = transform.forward().as_vec3();
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use avian3d::prelude::{LayerMask, SpatialQuery, SpatialQueryFilter};
+use crate::utils::EventQueue;
 
 #[derive(Debug, Clone)]
 pub struct LaserVisionSliceEvent {
@@ -8,11 +9,15 @@ pub struct LaserVisionSliceEvent {
     pub direction: Vec3,
 }
 
-#[derive(Resource, Default)]
-pub struct LaserVisionSliceEventQueue(pub Vec<LaserVisionSliceEvent>);
+pub type LaserVisionSliceEventQueue = EventQueue<LaserVisionSliceEvent>;
 
-#[derive(Resource, Default)]
-pub struct LaserVisionToggleEventQueue(pub Vec<(Entity, bool)>);
+#[derive(Debug, Clone)]
+pub struct LaserVisionToggleEvent {
+    pub entity: Entity,
+    pub enabled: bool,
+}
+
+pub type LaserVisionToggleEventQueue = EventQueue<LaserVisionToggleEvent>;
 
 /// Laser vision ability system.
 ///
@@ -61,7 +66,7 @@ pub fn update_laser_vision_system(
 ) {
     for (entity, mut system, system_ref) in query.iter_mut() {
         if system_ref.is_changed() {
-            toggle_events.0.push((entity, system.active));
+            toggle_events.send(LaserVisionToggleEvent { entity, enabled: system.active });
             system.last_time_slice_active = 0.0;
             system.previous_laser_position = Vec3::ZERO;
         }
@@ -88,7 +93,7 @@ pub fn update_laser_vision_system(
                 let distance = system.current_laser_position.distance(system.previous_laser_position);
                 if distance >= system.min_movement_to_slice {
                     let slice_dir = (system.current_laser_position - system.previous_laser_position).normalize_or_zero();
-                    slice_events.0.push(LaserVisionSliceEvent {
+                    slice_events.send(LaserVisionSliceEvent {
                         entity,
                         position: system.current_laser_position,
                         direction: slice_dir,
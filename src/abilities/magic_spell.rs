@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use super::ability_info::AbilityInfo;
 use super::types::*;
+use crate::vfx::{VfxKey, VfxRequestQueue};
 
 /// Magic spell ability settings.
 ///
@@ -12,6 +13,8 @@ pub struct MagicSpellAbility {
     pub cast_time: f32,
     pub cast_timer: f32,
     pub active: bool,
+    /// Effect played on the caster when the cast completes
+    pub cast_vfx: Option<VfxKey>,
 }
 
 impl Default for MagicSpellAbility {
@@ -21,6 +24,7 @@ impl Default for MagicSpellAbility {
             cast_time: 0.5,
             cast_timer: 0.0,
             active: false,
+            cast_vfx: None,
         }
     }
 }
@@ -45,9 +49,10 @@ pub fn start_magic_spell_cast(
 pub fn update_magic_spell_cast(
     time: Res<Time>,
     mut events: ResMut<MagicSpellCastEventQueue>,
-    mut query: Query<(Entity, &mut MagicSpellAbility)>,
+    mut vfx_queue: ResMut<VfxRequestQueue>,
+    mut query: Query<(Entity, &mut MagicSpellAbility, Option<&GlobalTransform>)>,
 ) {
-    for (entity, mut spell) in query.iter_mut() {
+    for (entity, mut spell, transform) in query.iter_mut() {
         if !spell.active {
             continue;
         }
@@ -55,10 +60,14 @@ pub fn update_magic_spell_cast(
         spell.cast_timer -= time.delta_secs();
         if spell.cast_timer <= 0.0 {
             spell.active = false;
-            events.0.push(MagicSpellCastEvent {
+            events.send(MagicSpellCastEvent {
                 caster: entity,
                 ability_name: spell.ability_name.clone(),
             });
+
+            if let (Some(key), Some(transform)) = (&spell.cast_vfx, transform) {
+                vfx_queue.spawn(key.clone(), transform.translation());
+            }
         }
     }
 }
@@ -1,12 +1,12 @@
 use bevy::prelude::*;
+use crate::utils::{EventQueue, QueueReader};
 
 #[derive(Debug, Clone)]
 pub struct PlaceMineEvent {
     pub owner: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct PlaceMineEventQueue(pub Vec<PlaceMineEvent>);
+pub type PlaceMineEventQueue = EventQueue<PlaceMineEvent>;
 
 /// Mine object that can be activated manually.
 #[derive(Component, Debug, Reflect)]
@@ -49,7 +49,7 @@ pub fn place_new_mine(
 ) {
     for (entity, mut system) in query.iter_mut() {
         if system.current_mine.is_none() {
-            events.0.push(PlaceMineEvent { owner: entity });
+            events.send(PlaceMineEvent { owner: entity });
             system.last_time_mine_placed = time.elapsed_secs();
         }
     }
@@ -58,10 +58,10 @@ pub fn place_new_mine(
 pub fn set_current_mine(
     mut query: Query<&mut ManualDetonationMineSystem>,
     mut mine_query: Query<&mut ManualDetonationMineObject>,
-    mut events: ResMut<PlaceMineEventQueue>,
+    mut events: QueueReader<PlaceMineEvent>,
     time: Res<Time>,
 ) {
-    for event in events.0.drain(..) {
+    for event in events.read().cloned() {
         if let Ok(mut system) = query.get_mut(event.owner) {
             if let Some(mine_entity) = system.current_mine {
                 if let Ok(mut mine) = mine_query.get_mut(mine_entity) {
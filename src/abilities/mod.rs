@@ -25,9 +25,11 @@ pub mod laser_vision_system;
 pub mod manual_detonation_mine_system;
 pub mod player_gravity_system;
 pub mod player_shield_system;
+pub mod projectile_catch;
 pub mod player_stealth_system;
 pub mod remove_gravity_from_character_system;
 pub mod grappling_hook_rope;
+pub mod radar_pulse;
 
 use bevy::prelude::*;
 use types::*;
@@ -57,9 +59,13 @@ use laser_vision_system::*;
 use manual_detonation_mine_system::*;
 use player_gravity_system::*;
 use player_shield_system::*;
+use projectile_catch::*;
 use player_stealth_system::*;
 use remove_gravity_from_character_system::*;
 use grappling_hook_rope::*;
+use radar_pulse::*;
+use crate::utils::EventQueuePlugin;
+use crate::simulation::SimulationAppExt;
 
 // Re-export specific types for cleaner imports
 pub use types::AbilityStatus;
@@ -102,73 +108,102 @@ pub use object_to_attract_with_grappling_hook::ObjectToAttractWithGrapplingHook;
 pub use custom_ability_system::{
     CustomAbilitySystem,
     CustomAbilityEvent,
-    CustomAbilityPressDownEventQueue,
-    CustomAbilityPressHoldEventQueue,
-    CustomAbilityPressUpEventQueue,
-    CustomAbilityPressUpBeforeEventQueue,
-    CustomAbilityPressUpAfterEventQueue,
-    CustomAbilityUpdateEventQueue,
-    CustomAbilityEnableEventQueue,
-    CustomAbilityDisableEventQueue,
-    CustomAbilityDeactivateEventQueue,
+    CustomAbilityEventKind,
+    CustomAbilityEventQueue,
 };
 pub use drain_stat_system::{DrainStatSystem, DrainStatInfo};
 pub use laser_vision_system::{
     LaserVisionSystem,
     LaserVisionSliceEvent,
     LaserVisionSliceEventQueue,
+    LaserVisionToggleEvent,
     LaserVisionToggleEventQueue,
 };
 pub use manual_detonation_mine_system::{
     ManualDetonationMineSystem,
     ManualDetonationMineObject,
+    PlaceMineEvent,
     PlaceMineEventQueue,
 };
 pub use player_gravity_system::PlayerGravityAbility;
 pub use player_shield_system::PlayerShieldSystem;
-pub use player_stealth_system::{PlayerStealthSystem, PlayerStealthEventQueue};
-pub use remove_gravity_from_character_system::{RemoveGravityFromCharacterSystem, RemoveGravityEventQueue};
+pub use projectile_catch::ProjectileCatch;
+pub use player_stealth_system::{PlayerStealthSystem, PlayerStealthEvent, PlayerStealthEventQueue};
+pub use remove_gravity_from_character_system::{RemoveGravityFromCharacterSystem, RemoveGravityEvent, RemoveGravityEventQueue};
 pub use grappling_hook_rope::GrapplingHookRope;
+pub use radar_pulse::{
+    RadarPulseAbility,
+    RadarPulseCategory,
+    RadarPulseFilter,
+    RadarPulseFilters,
+    RadarPulseReveal,
+};
 
 /// Plugin for the abilities system
 pub struct AbilitiesPlugin;
 
 impl Plugin for AbilitiesPlugin {
     fn build(&self, app: &mut App) {
+        let simulation = app.simulation_schedule();
+
         app
             // Register types
             .register_type::<AbilityInfo>()
             .register_type::<PlayerAbilitiesSystem>()
+            .register_type::<RadarPulseAbility>()
+            .register_type::<CustomAbilitySystem>()
+            .register_type::<GrapplingHookTarget>()
+            .register_type::<PlayerShieldSystem>()
+            .register_type::<ProjectileCatch>()
+            .register_type::<ObjectToAttractWithGrapplingHook>()
+            .register_type::<WallRunningZone>()
+            .register_type::<WallRunningZoneTracker>()
+            .register_type::<ParticleCollisionDetector>()
+            .register_type::<ParticleTriggerDetector>()
+            .register_type::<PlayerTeleportAbility>()
+            .register_type::<PlayerStealthSystem>()
+            .register_type::<AbilityWeaponIntegration>()
+            .register_type::<DashAbility>()
+            .register_type::<GrapplingHookTargetsSystem>()
+            .register_type::<GrapplingHookEffect>()
+            .register_type::<AbilityWheelUI>()
+            .register_type::<AbilitySlotElement>()
+            .register_type::<LaserVisionSystem>()
+            .register_type::<PlayerGravityAbility>()
+            .register_type::<OxygenSystem>()
+            .register_type::<RemoveGravityFromCharacterSystem>()
+            .register_type::<GrapplingHookSystem>()
+            .register_type::<DrainStatSystem>()
+            .register_type::<AbilityPickup>()
+            .register_type::<TemplateAbilitySystem>()
+            .register_type::<GrapplingHookRope>()
+            .register_type::<ManualDetonationMineObject>()
+            .register_type::<ManualDetonationMineSystem>()
+            .register_type::<StaminaSystem>()
+            .register_type::<ThrowObjectTrajectory>()
+            .register_type::<MagicSpellAbility>()
             // Events (Resource Queues)
-            .init_resource::<ActivateAbilityEventQueue>()
-            .init_resource::<DeactivateAbilityEventQueue>()
-            .init_resource::<SetAbilityEnabledEventQueue>()
-            .init_resource::<AbilityCooldownEventQueue>()
-            .init_resource::<AbilityTimeLimitEventQueue>()
-            .init_resource::<MagicSpellCastEventQueue>()
-            .init_resource::<TeleportStartEventQueue>()
-            .init_resource::<TeleportEndEventQueue>()
-            .init_resource::<CustomAbilityPressDownEventQueue>()
-            .init_resource::<CustomAbilityPressHoldEventQueue>()
-            .init_resource::<CustomAbilityPressUpEventQueue>()
-            .init_resource::<CustomAbilityPressUpBeforeEventQueue>()
-            .init_resource::<CustomAbilityPressUpAfterEventQueue>()
-            .init_resource::<CustomAbilityUpdateEventQueue>()
-            .init_resource::<CustomAbilityEnableEventQueue>()
-            .init_resource::<CustomAbilityDisableEventQueue>()
-            .init_resource::<CustomAbilityDeactivateEventQueue>()
-            .init_resource::<LaserVisionSliceEventQueue>()
-            .init_resource::<LaserVisionToggleEventQueue>()
-            .init_resource::<PlaceMineEventQueue>()
-            .init_resource::<PlayerStealthEventQueue>()
-            .init_resource::<RemoveGravityEventQueue>()
-            .init_resource::<ParticleCollisionEventQueue>()
-            .init_resource::<ParticleTriggerEventQueue>()
+            .add_plugins(EventQueuePlugin::<ActivateAbilityEvent>::default())
+            .add_plugins(EventQueuePlugin::<DeactivateAbilityEvent>::default())
+            .add_plugins(EventQueuePlugin::<SetAbilityEnabledEvent>::default())
+            .add_plugins(EventQueuePlugin::<AbilityCooldownEvent>::default())
+            .add_plugins(EventQueuePlugin::<AbilityTimeLimitEvent>::default())
+            .add_plugins(EventQueuePlugin::<MagicSpellCastEvent>::default())
+            .add_plugins(EventQueuePlugin::<TeleportStartEvent>::default())
+            .add_plugins(EventQueuePlugin::<TeleportEndEvent>::default())
+            .add_plugins(EventQueuePlugin::<CustomAbilityEvent>::default())
+            .add_plugins(EventQueuePlugin::<LaserVisionSliceEvent>::default())
+            .add_plugins(EventQueuePlugin::<LaserVisionToggleEvent>::default())
+            .add_plugins(EventQueuePlugin::<PlaceMineEvent>::default())
+            .add_plugins(EventQueuePlugin::<PlayerStealthEvent>::default())
+            .add_plugins(EventQueuePlugin::<RemoveGravityEvent>::default())
+            .add_plugins(EventQueuePlugin::<ParticleCollisionEvent>::default())
+            .add_plugins(EventQueuePlugin::<ParticleTriggerEvent>::default())
             // Add systems
-            .add_systems(Update, (
+            .add_systems(simulation, (
                 update_player_abilities_context,
                 update_abilities,
-                update_ability_wheel_ui,
+                update_ability_wheel_ui.in_set(crate::headless::PresentationSet),
                 update_ability_slot_elements,
                 handle_ability_input,
                 start_dash_from_ability,
@@ -204,6 +239,11 @@ impl Plugin for AbilitiesPlugin {
                 handle_ability_activation,
                 handle_ability_deactivation,
                 handle_ability_enabled_events,
-            ));
+            ))
+            .add_systems(simulation, update_projectile_catch_ability)
+            .add_systems(simulation, (
+                start_radar_pulse,
+                update_radar_pulse,
+            ).chain());
     }
 }
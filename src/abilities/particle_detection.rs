@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Event sent when a particle collision occurs.
 #[derive(Debug, Clone)]
@@ -15,11 +16,9 @@ pub struct ParticleTriggerEvent {
     pub entered: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct ParticleCollisionEventQueue(pub Vec<ParticleCollisionEvent>);
+pub type ParticleCollisionEventQueue = EventQueue<ParticleCollisionEvent>;
 
-#[derive(Resource, Default)]
-pub struct ParticleTriggerEventQueue(pub Vec<ParticleTriggerEvent>);
+pub type ParticleTriggerEventQueue = EventQueue<ParticleTriggerEvent>;
 
 /// Helper component for particle collision detection.
 ///
@@ -42,10 +41,10 @@ pub struct ParticleTriggerDetector {
 
 /// Update collision detector state from events.
 pub fn handle_particle_collision_events(
-    mut events: ResMut<ParticleCollisionEventQueue>,
+    mut events: QueueReader<ParticleCollisionEvent>,
     mut detectors: Query<&mut ParticleCollisionDetector>,
 ) {
-    for event in events.0.drain(..) {
+    for event in events.read().cloned() {
         if let Ok(mut detector) = detectors.get_mut(event.detector) {
             detector.last_hit = Some(event.other);
         }
@@ -54,10 +53,10 @@ pub fn handle_particle_collision_events(
 
 /// Update trigger detector state from events.
 pub fn handle_particle_trigger_events(
-    mut events: ResMut<ParticleTriggerEventQueue>,
+    mut events: QueueReader<ParticleTriggerEvent>,
     mut detectors: Query<&mut ParticleTriggerDetector>,
 ) {
-    for event in events.0.drain(..) {
+    for event in events.read().cloned() {
         if let Ok(mut detector) = detectors.get_mut(event.detector) {
             detector.last_trigger = Some(event.other);
             detector.is_inside = event.entered;
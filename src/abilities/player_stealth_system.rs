@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use super::ability_info::AbilityInfo;
+use crate::utils::EventQueue;
 
 #[derive(Debug, Clone)]
 pub struct PlayerStealthEvent {
@@ -7,8 +8,7 @@ pub struct PlayerStealthEvent {
     pub active: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct PlayerStealthEventQueue(pub Vec<PlayerStealthEvent>);
+pub type PlayerStealthEventQueue = EventQueue<PlayerStealthEvent>;
 
 /// Player stealth ability system.
 ///
@@ -46,7 +46,7 @@ pub fn update_player_stealth_system(
         if !ability.enabled {
             if stealth.active {
                 stealth.active = false;
-                events.0.push(PlayerStealthEvent { entity, active: false });
+                events.send(PlayerStealthEvent { entity, active: false });
             }
             stealth.enabled = false;
             continue;
@@ -55,19 +55,19 @@ pub fn update_player_stealth_system(
         if ability.active_from_press_down && !stealth.active {
             stealth.active = true;
             stealth.time_limit_timer = if ability.use_time_limit { ability.time_limit } else { 0.0 };
-            events.0.push(PlayerStealthEvent { entity, active: true });
+            events.send(PlayerStealthEvent { entity, active: true });
         }
 
         if !ability.active_from_press_down && stealth.active {
             stealth.active = false;
-            events.0.push(PlayerStealthEvent { entity, active: false });
+            events.send(PlayerStealthEvent { entity, active: false });
         }
 
         if stealth.active && ability.use_time_limit {
             stealth.time_limit_timer -= time.delta_secs();
             if stealth.time_limit_timer <= 0.0 {
                 stealth.active = false;
-                events.0.push(PlayerStealthEvent { entity, active: false });
+                events.send(PlayerStealthEvent { entity, active: false });
             }
         }
     }
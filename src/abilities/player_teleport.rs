@@ -6,6 +6,7 @@ use crate::camera::{CameraController, CameraState};
 use crate::character::CharacterController;
 use crate::input::InputState;
 use crate::actions::types::{ActivateCustomActionEvent, ActivateCustomActionEventQueue, StopCustomActionEvent, StopCustomActionEventQueue};
+use crate::utils::EventQueue;
 
 #[derive(Debug, Clone)]
 pub struct TeleportStartEvent {
@@ -18,11 +19,9 @@ pub struct TeleportEndEvent {
     pub entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct TeleportStartEventQueue(pub Vec<TeleportStartEvent>);
+pub type TeleportStartEventQueue = EventQueue<TeleportStartEvent>;
 
-#[derive(Resource, Default)]
-pub struct TeleportEndEventQueue(pub Vec<TeleportEndEvent>);
+pub type TeleportEndEventQueue = EventQueue<TeleportEndEvent>;
 
 /// Teleport ability controller.
 ///
@@ -254,12 +253,12 @@ pub fn handle_teleport_input(
                     teleport.smooth_camera_follow_timer = 0.0;
                 }
                 if teleport.use_action_system_on_teleport && !teleport.action_name_used_on_teleport.is_empty() {
-                    stop_action_queue.0.push(StopCustomActionEvent {
+                    stop_action_queue.send(StopCustomActionEvent {
                         player_entity: entity,
                         action_name: teleport.action_name_used_on_teleport.clone(),
                     });
                 }
-                end_events.0.push(TeleportEndEvent { entity });
+                end_events.send(TeleportEndEvent { entity });
             }
             if teleport.use_teleport_mark {
                 if let Some(mark_entity) = teleport.teleport_mark {
@@ -306,13 +305,13 @@ pub fn handle_teleport_input(
                     }
 
                     if teleport.use_action_system_on_teleport && !teleport.action_name_used_on_teleport.is_empty() {
-                        activate_action_queue.0.push(ActivateCustomActionEvent {
+                        activate_action_queue.send(ActivateCustomActionEvent {
                             player_entity: entity,
                             action_name: teleport.action_name_used_on_teleport.clone(),
                         });
                     }
 
-                    start_events.0.push(TeleportStartEvent {
+                    start_events.send(TeleportStartEvent {
                         entity,
                         position: teleport.current_teleport_position,
                     });
@@ -340,12 +339,12 @@ pub fn handle_teleport_input(
                             teleport.smooth_camera_follow_timer = 0.0;
                         }
                         if teleport.use_action_system_on_teleport && !teleport.action_name_used_on_teleport.is_empty() {
-                            stop_action_queue.0.push(StopCustomActionEvent {
+                            stop_action_queue.send(StopCustomActionEvent {
                                 player_entity: entity,
                                 action_name: teleport.action_name_used_on_teleport.clone(),
                             });
                         }
-                        end_events.0.push(TeleportEndEvent { entity });
+                        end_events.send(TeleportEndEvent { entity });
                     } else {
                         teleport.teleport_in_process = true;
                     }
@@ -396,12 +395,12 @@ pub fn handle_teleport_input(
                     teleport.smooth_camera_follow_timer = 0.0;
                 }
                 if teleport.use_action_system_on_teleport && !teleport.action_name_used_on_teleport.is_empty() {
-                    stop_action_queue.0.push(StopCustomActionEvent {
+                    stop_action_queue.send(StopCustomActionEvent {
                         player_entity: entity,
                         action_name: teleport.action_name_used_on_teleport.clone(),
                     });
                 }
-                end_events.0.push(TeleportEndEvent { entity });
+                end_events.send(TeleportEndEvent { entity });
             } else {
                 let dir = to_target / distance;
                 transform.translation += dir * teleport.teleport_speed * time.delta_secs();
@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use super::ability_info::AbilityInfo;
+use crate::weapons::{ReturnProjectilesEvent, ReturnProjectilesQueue};
+
+/// Projectile catch ability.
+///
+/// While the ability is active, projectiles flying close by are caught and
+/// held instead of hitting. Releasing it throws them back along the aim.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ProjectileCatch {
+    pub ability_name: String,
+    pub catching: bool,
+    pub catch_radius: f32,
+    pub max_stored: usize,
+    pub throw_speed: f32,
+    /// Caught projectiles, hidden until thrown
+    pub stored: Vec<Entity>,
+}
+
+impl Default for ProjectileCatch {
+    fn default() -> Self {
+        Self {
+            ability_name: "Projectile Catch".to_string(),
+            catching: false,
+            catch_radius: 1.5,
+            max_stored: 3,
+            throw_speed: 25.0,
+            stored: Vec::new(),
+        }
+    }
+}
+
+impl ProjectileCatch {
+    pub fn can_catch(&self) -> bool {
+        self.catching && self.stored.len() < self.max_stored
+    }
+}
+
+/// Catch while the ability is active and throw what was caught once it ends.
+pub fn update_projectile_catch_ability(
+    mut returns: ResMut<ReturnProjectilesQueue>,
+    mut query: Query<(Entity, &AbilityInfo, &mut ProjectileCatch, &GlobalTransform)>,
+) {
+    for (entity, ability, mut catch, transform) in query.iter_mut() {
+        if ability.name != catch.ability_name {
+            continue;
+        }
+
+        let catching = ability.enabled && ability.active;
+        if catch.catching && !catching && !catch.stored.is_empty() {
+            returns.send(ReturnProjectilesEvent {
+                armor_entity: entity,
+                direction: transform.forward().as_vec3(),
+                speed: Some(catch.throw_speed),
+            });
+        }
+        if catch.catching != catching {
+            catch.catching = catching;
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Radar pulse ("detective vision")
+//!
+//! When the matching [`AbilityInfo`] turns active, a pulse expands from the
+//! caster and highlights the interactables, pickups and enemies its front
+//! passes over, walls or not, for a few seconds. Energy cost and cooldown are
+//! the ability's (`use_energy`, `use_cooldown`). Which categories are revealed,
+//! how far and in what color comes from [`RadarPulseFilters`], which can be
+//! written in RON:
+//!
+//! ```ron
+//! (filters: [
+//!     (category: Enemies, enabled: true, color: (1.0, 0.2, 0.2), max_distance: Some(20.0)),
+//!     (category: Pickups, enabled: false, color: (0.3, 1.0, 0.4), max_distance: None),
+//! ])
+//! ```
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use super::ability_info::AbilityInfo;
+use crate::ai::AiController;
+use crate::combat::Health;
+use crate::highlight::{HighlightCommandQueue, HighlightKind};
+use crate::interaction::Interactable;
+use crate::pickups::PickUpObject;
+
+/// Kind of entity a radar pulse can reveal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum RadarPulseCategory {
+    Interactables,
+    Pickups,
+    /// Living AI characters
+    Enemies,
+}
+
+/// Whether and how a radar pulse reveals one category
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+pub struct RadarPulseFilter {
+    pub category: RadarPulseCategory,
+    pub enabled: bool,
+    /// sRGB highlight color
+    pub color: (f32, f32, f32),
+    /// Reveal range of this category; the pulse radius when `None`
+    pub max_distance: Option<f32>,
+}
+
+/// Per-category filters of a radar pulse; categories left out are not revealed
+#[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RadarPulseFilters {
+    pub filters: Vec<RadarPulseFilter>,
+}
+
+impl Default for RadarPulseFilters {
+    fn default() -> Self {
+        Self {
+            filters: vec![
+                RadarPulseFilter {
+                    category: RadarPulseCategory::Interactables,
+                    enabled: true,
+                    color: (0.2, 0.9, 1.0),
+                    max_distance: None,
+                },
+                RadarPulseFilter {
+                    category: RadarPulseCategory::Pickups,
+                    enabled: true,
+                    color: (1.0, 0.85, 0.2),
+                    max_distance: None,
+                },
+                RadarPulseFilter {
+                    category: RadarPulseCategory::Enemies,
+                    enabled: true,
+                    color: (1.0, 0.2, 0.2),
+                    max_distance: None,
+                },
+            ],
+        }
+    }
+}
+
+impl RadarPulseFilters {
+    pub fn from_ron(text: &str) -> Result<Self, String> {
+        ron::from_str(text).map_err(|e| format!("Invalid radar pulse filters: {}", e))
+    }
+
+    /// Enabled filter of `category`
+    pub fn get(&self, category: RadarPulseCategory) -> Option<&RadarPulseFilter> {
+        self.filters.iter().find(|filter| filter.category == category && filter.enabled)
+    }
+}
+
+/// Entity revealed by the current pulse
+#[derive(Debug, Clone, Reflect)]
+pub struct RadarPulseReveal {
+    pub entity: Entity,
+    pub timer: f32,
+}
+
+/// Radar pulse ability controller.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct RadarPulseAbility {
+    pub ability_name: String,
+    pub max_radius: f32,
+    /// Growth of the pulse radius per second
+    pub expand_speed: f32,
+    /// Seconds a revealed entity stays highlighted
+    pub highlight_duration: f32,
+    pub filters: RadarPulseFilters,
+
+    /// Whether the pulse is expanding
+    pub expanding: bool,
+    pub radius: f32,
+    /// Where the pulse was sent from
+    pub origin: Vec3,
+    pub revealed: Vec<RadarPulseReveal>,
+    pub ability_was_active: bool,
+}
+
+impl Default for RadarPulseAbility {
+    fn default() -> Self {
+        Self {
+            ability_name: "RadarPulse".to_string(),
+            max_radius: 30.0,
+            expand_speed: 40.0,
+            highlight_duration: 4.0,
+            filters: RadarPulseFilters::default(),
+            expanding: false,
+            radius: 0.0,
+            origin: Vec3::ZERO,
+            revealed: Vec::new(),
+            ability_was_active: false,
+        }
+    }
+}
+
+/// Send a pulse when the matching ability turns active.
+pub fn start_radar_pulse(
+    mut query: Query<(&AbilityInfo, &mut RadarPulseAbility, &GlobalTransform)>,
+) {
+    for (ability, mut pulse, transform) in query.iter_mut() {
+        if ability.name != pulse.ability_name {
+            continue;
+        }
+
+        if ability.active && !pulse.ability_was_active {
+            pulse.expanding = true;
+            pulse.radius = 0.0;
+            pulse.origin = transform.translation();
+        }
+        pulse.ability_was_active = ability.active;
+    }
+}
+
+/// Expand pulses, highlight what they reach and release expired highlights.
+pub fn update_radar_pulse(
+    time: Res<Time>,
+    mut highlights: ResMut<HighlightCommandQueue>,
+    mut query: Query<(Entity, &mut RadarPulseAbility)>,
+    interactables: Query<(Entity, &GlobalTransform), With<Interactable>>,
+    pickups: Query<(Entity, &GlobalTransform), With<PickUpObject>>,
+    enemies: Query<(Entity, &GlobalTransform, &Health), With<AiController>>,
+) {
+    let delta = time.delta_secs();
+
+    for (caster, mut pulse) in query.iter_mut() {
+        let pulse = pulse.as_mut();
+
+        pulse.revealed.retain_mut(|reveal| {
+            reveal.timer -= delta;
+            if reveal.timer > 0.0 {
+                return true;
+            }
+            highlights.release(reveal.entity, HighlightKind::RadarPulse);
+            false
+        });
+
+        if !pulse.expanding {
+            continue;
+        }
+
+        let inner = pulse.radius;
+        pulse.radius = (pulse.radius + pulse.expand_speed * delta).min(pulse.max_radius);
+        if pulse.radius >= pulse.max_radius {
+            pulse.expanding = false;
+        }
+
+        let candidates = interactables
+            .iter()
+            .map(|(entity, transform)| (entity, transform, RadarPulseCategory::Interactables))
+            .chain(pickups.iter().map(|(entity, transform)| (entity, transform, RadarPulseCategory::Pickups)))
+            .chain(
+                enemies
+                    .iter()
+                    .filter(|(_, _, health)| !health.is_dead)
+                    .map(|(entity, transform, _)| (entity, transform, RadarPulseCategory::Enemies)),
+            );
+
+        for (entity, transform, category) in candidates {
+            if entity == caster {
+                continue;
+            }
+            let Some(filter) = pulse.filters.get(category) else { continue };
+
+            // Only what the pulse front crossed this frame; no line of sight check
+            let distance = transform.translation().distance(pulse.origin);
+            let reach = filter.max_distance.map_or(pulse.radius, |max| max.min(pulse.radius));
+            if distance > reach || (distance < inner && inner > 0.0) {
+                continue;
+            }
+
+            let (r, g, b) = filter.color;
+            highlights.request_with_color(entity, HighlightKind::RadarPulse, Color::srgb(r, g, b));
+            match pulse.revealed.iter_mut().find(|reveal| reveal.entity == entity) {
+                Some(reveal) => reveal.timer = pulse.highlight_duration,
+                None => pulse.revealed.push(RadarPulseReveal {
+                    entity,
+                    timer: pulse.highlight_duration,
+                }),
+            }
+        }
+    }
+}
@@ -1,14 +1,14 @@
 use bevy::prelude::*;
 use crate::physics::CustomGravity;
 use avian3d::prelude::LinearVelocity;
+use crate::utils::{EventQueue, QueueReader};
 
 #[derive(Debug, Clone)]
 pub struct RemoveGravityEvent {
     pub entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct RemoveGravityEventQueue(pub Vec<RemoveGravityEvent>);
+pub type RemoveGravityEventQueue = EventQueue<RemoveGravityEvent>;
 
 /// Temporarily remove gravity from character.
 ///
@@ -44,10 +44,10 @@ impl Default for RemoveGravityFromCharacterSystem {
 }
 
 pub fn activate_remove_gravity(
-    mut events: ResMut<RemoveGravityEventQueue>,
+    mut events: QueueReader<RemoveGravityEvent>,
     mut query: Query<(&mut RemoveGravityFromCharacterSystem, Option<&mut CustomGravity>, Option<&mut LinearVelocity>)>,
 ) {
-    for event in events.0.drain(..) {
+    for event in events.read().cloned() {
         if let Ok((mut system, custom_gravity, velocity)) = query.get_mut(event.entity) {
             system.active = true;
             system.active_timer = system.remove_gravity_duration;
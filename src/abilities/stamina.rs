@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use crate::stats::{StatsSystem};
 use crate::stats::types::DerivedStat;
+use crate::game_manager::types::FeatureToggles;
 
 /// Stamina management system.
 ///
@@ -29,9 +30,29 @@ impl Default for StaminaSystem {
     }
 }
 
+impl StaminaSystem {
+    /// Takes `amount` stamina if there is enough, keeping the stats system in sync
+    pub fn try_spend(&mut self, amount: f32, stats: Option<&mut StatsSystem>) -> bool {
+        if self.use_stats {
+            if let Some(current) = stats.as_deref().and_then(|stats| stats.get_derived_stat(DerivedStat::CurrentStamina)) {
+                self.current_stamina = *current;
+            }
+        }
+        if self.current_stamina < amount {
+            return false;
+        }
+        self.current_stamina -= amount;
+        if let (true, Some(stats)) = (self.use_stats, stats) {
+            stats.set_derived_stat(DerivedStat::CurrentStamina, self.current_stamina);
+        }
+        true
+    }
+}
+
 /// Update stamina values and optionally sync to the stats system.
 pub fn update_stamina_system(
     time: Res<Time>,
+    toggles: Res<FeatureToggles>,
     mut query: Query<(&mut StaminaSystem, Option<&mut StatsSystem>)>,
 ) {
     let dt = time.delta_secs();
@@ -47,7 +68,9 @@ pub fn update_stamina_system(
             }
         }
 
-        if stamina.is_exerting {
+        if !toggles.stamina {
+            stamina.current_stamina = stamina.max_stamina;
+        } else if stamina.is_exerting {
             stamina.current_stamina = (stamina.current_stamina - stamina.drain_rate * dt).max(0.0);
         } else {
             stamina.current_stamina = (stamina.current_stamina + stamina.regen_rate * dt)
@@ -62,3 +85,42 @@ pub fn update_stamina_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    fn stamina(app: &App, entity: Entity) -> f32 {
+        app.world().get::<StaminaSystem>(entity).unwrap().current_stamina
+    }
+
+    #[test]
+    fn test_stamina_toggle_keeps_stamina_full() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<FeatureToggles>()
+            .add_systems(Update, update_stamina_system);
+        let runner = app
+            .world_mut()
+            .spawn(StaminaSystem { is_exerting: true, use_stats: false, ..default() })
+            .id();
+
+        app.update();
+        let start = stamina(&app, runner);
+        app.update();
+        assert!((stamina(&app, runner) - (start - 2.0)).abs() < 1e-3);
+
+        // Sprinting costs nothing while stamina is off
+        app.world_mut().resource_mut::<FeatureToggles>().stamina = false;
+        app.update();
+        app.update();
+        assert_eq!(stamina(&app, runner), 100.0);
+
+        app.world_mut().resource_mut::<FeatureToggles>().stamina = true;
+        app.update();
+        assert!((stamina(&app, runner) - 98.0).abs() < 1e-3);
+    }
+}
@@ -6,6 +6,7 @@ use crate::camera::{CameraController, CameraMode};
 use crate::input::InputState;
 use crate::physics::GroundDetection;
 use crate::stats::StatsSystem;
+use crate::utils::QueueReader;
 
 /// System to update ability timers
 pub fn update_abilities(
@@ -23,7 +24,7 @@ pub fn update_abilities(
         ability.update(delta_time);
 
         if ability.cooldown_in_process != prev_cooldown {
-            cooldown_events.0.push(AbilityCooldownEvent {
+            cooldown_events.send(AbilityCooldownEvent {
                 ability_name: ability.name.clone(),
                 started: ability.cooldown_in_process,
             });
@@ -31,7 +32,7 @@ pub fn update_abilities(
         }
 
         if ability.time_limit_in_process != prev_time_limit {
-            time_limit_events.0.push(AbilityTimeLimitEvent {
+            time_limit_events.send(AbilityTimeLimitEvent {
                 ability_name: ability.name.clone(),
                 started: ability.time_limit_in_process,
             });
@@ -42,10 +43,10 @@ pub fn update_abilities(
 
 /// System to handle ability activation events
 pub fn handle_ability_activation(
-    mut events: ResMut<ActivateAbilityEventQueue>,
+    mut events: QueueReader<ActivateAbilityEvent>,
     mut abilities: Query<&mut AbilityInfo>,
 ) {
-    for event in events.0.drain(..) {
+    for event in events.read().cloned() {
         if let Some(mut ability) = abilities.iter_mut().find(|a| a.name == event.ability_name) {
             if !ability.enabled {
                 continue;
@@ -154,10 +155,10 @@ pub fn handle_ability_input(
 
 /// System to handle ability deactivation events
 pub fn handle_ability_deactivation(
-    mut events: ResMut<DeactivateAbilityEventQueue>,
+    mut events: QueueReader<DeactivateAbilityEvent>,
     mut abilities: Query<&mut AbilityInfo>,
 ) {
-    for event in events.0.drain(..) {
+    for event in events.read().cloned() {
         if let Some(mut ability) = abilities.iter_mut().find(|a| a.name == event.ability_name) {
             ability.deactivate();
             ability.status = if ability.enabled {
@@ -171,10 +172,10 @@ pub fn handle_ability_deactivation(
 
 /// System to handle ability enable/disable events
 pub fn handle_ability_enabled_events(
-    mut events: ResMut<SetAbilityEnabledEventQueue>,
+    mut events: QueueReader<SetAbilityEnabledEvent>,
     mut abilities: Query<&mut AbilityInfo>,
 ) {
-    for event in events.0.drain(..) {
+    for event in events.read().cloned() {
         if let Some(mut ability) = abilities.iter_mut().find(|a| a.name == event.ability_name) {
             if event.enabled {
                 ability.enable();
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bevy::ecs::query::QueryFilter;
 use serde::{Deserialize, Serialize};
+use crate::utils::EventQueue;
 
 /// The status of an ability.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect)]
@@ -42,53 +43,48 @@ pub enum EnergyConsumptionType {
 
 
 /// Event for activating an ability
-#[derive(Event)]
+#[derive(Event, Debug, Clone)]
 pub struct ActivateAbilityEvent {
     pub ability_name: String,
     pub input_type: AbilityInputType,
 }
 
 /// Event for deactivating an ability
-#[derive(Event)]
+#[derive(Event, Debug, Clone)]
 pub struct DeactivateAbilityEvent {
     pub ability_name: String,
 }
 
 /// Event for enabling/disabling an ability
-#[derive(Event)]
+#[derive(Event, Debug, Clone)]
 pub struct SetAbilityEnabledEvent {
     pub ability_name: String,
     pub enabled: bool,
 }
 
 /// Event for ability cooldown state changes
-#[derive(Event)]
+#[derive(Event, Debug, Clone)]
 pub struct AbilityCooldownEvent {
     pub ability_name: String,
     pub started: bool,
 }
 
 /// Event for ability time limit state changes
-#[derive(Event)]
+#[derive(Event, Debug, Clone)]
 pub struct AbilityTimeLimitEvent {
     pub ability_name: String,
     pub started: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct ActivateAbilityEventQueue(pub Vec<ActivateAbilityEvent>);
+pub type ActivateAbilityEventQueue = EventQueue<ActivateAbilityEvent>;
 
-#[derive(Resource, Default)]
-pub struct DeactivateAbilityEventQueue(pub Vec<DeactivateAbilityEvent>);
+pub type DeactivateAbilityEventQueue = EventQueue<DeactivateAbilityEvent>;
 
-#[derive(Resource, Default)]
-pub struct SetAbilityEnabledEventQueue(pub Vec<SetAbilityEnabledEvent>);
+pub type SetAbilityEnabledEventQueue = EventQueue<SetAbilityEnabledEvent>;
 
-#[derive(Resource, Default)]
-pub struct AbilityCooldownEventQueue(pub Vec<AbilityCooldownEvent>);
+pub type AbilityCooldownEventQueue = EventQueue<AbilityCooldownEvent>;
 
-#[derive(Resource, Default)]
-pub struct AbilityTimeLimitEventQueue(pub Vec<AbilityTimeLimitEvent>);
+pub type AbilityTimeLimitEventQueue = EventQueue<AbilityTimeLimitEvent>;
 
 /// Event emitted when a magic spell is cast.
 #[derive(Event, Debug, Clone)]
@@ -97,5 +93,4 @@ pub struct MagicSpellCastEvent {
     pub ability_name: String,
 }
 
-#[derive(Resource, Default)]
-pub struct MagicSpellCastEventQueue(pub Vec<MagicSpellCastEvent>);
+pub type MagicSpellCastEventQueue = EventQueue<MagicSpellCastEvent>;
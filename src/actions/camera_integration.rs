@@ -1,14 +1,15 @@
 use bevy::prelude::*;
 use crate::camera::{CameraController, CameraMode};
 use super::types::*;
+use crate::utils::QueueReader;
 
 /// Process camera events triggered by actions
 pub fn process_camera_events(
-    mut camera_queue: ResMut<CameraEventQueue>,
+    mut camera_queue: QueueReader<CameraEventTriggered>,
     mut camera_query: Query<&mut CameraController>,
     action_query: Query<&ActionSystem>,
 ) {
-    for event in camera_queue.0.drain(..) {
+    for event in camera_queue.read().cloned() {
         // Find cameras following this player
         for mut camera in camera_query.iter_mut() {
             if camera.follow_target == Some(event.player_entity) {
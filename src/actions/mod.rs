@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use bevy::app::App;
+use crate::utils::EventQueuePlugin;
 
 pub mod types;
 pub mod systems;
@@ -22,19 +23,19 @@ impl Plugin for ActionSystemPlugin {
             .register_type::<types::CustomActionInfo>()
             
             // Register resources
-            .init_resource::<types::StartActionEventQueue>()
-            .init_resource::<types::EndActionEventQueue>()
-            .init_resource::<types::ActivateCustomActionEventQueue>()
-            .init_resource::<types::StopCustomActionEventQueue>()
-            .init_resource::<types::ActionInterruptedEventQueue>()
-            .init_resource::<types::ActionEventTriggeredQueue>()
-            .init_resource::<types::RemoteActionEventQueue>()
-            .init_resource::<types::CameraEventQueue>()
-            .init_resource::<types::PhysicsEventQueue>()
-            .init_resource::<types::StateChangeEventQueue>()
-            .init_resource::<types::WeaponEventQueue>()
-            .init_resource::<types::PowerEventQueue>()
-            .init_resource::<types::ParentingEventQueue>()
+            .add_plugins(EventQueuePlugin::<types::StartActionEvent>::default())
+            .add_plugins(EventQueuePlugin::<types::EndActionEvent>::default())
+            .add_plugins(EventQueuePlugin::<types::ActivateCustomActionEvent>::default())
+            .add_plugins(EventQueuePlugin::<types::StopCustomActionEvent>::default())
+            .add_plugins(EventQueuePlugin::<types::ActionInterruptedEvent>::default())
+            .add_plugins(EventQueuePlugin::<types::ActionEventTriggered>::default())
+            .add_plugins(EventQueuePlugin::<types::RemoteActionEvent>::default())
+            .add_plugins(EventQueuePlugin::<types::CameraEventTriggered>::default())
+            .add_plugins(EventQueuePlugin::<types::PhysicsEventTriggered>::default())
+            .add_plugins(EventQueuePlugin::<types::StateChangeEventTriggered>::default())
+            .add_plugins(EventQueuePlugin::<types::WeaponEventTriggered>::default())
+            .add_plugins(EventQueuePlugin::<types::PowerEventTriggered>::default())
+            .add_plugins(EventQueuePlugin::<types::ParentingEventTriggered>::default())
             .init_resource::<types::CustomActionManager>()
             
             // Register systems
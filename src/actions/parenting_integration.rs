@@ -1,15 +1,16 @@
 use bevy::prelude::*;
 use super::types::*;
+use crate::utils::QueueReader;
 
 /// Process parenting events triggered by actions
 pub fn process_parenting_events(
-    mut parenting_queue: ResMut<ParentingEventQueue>,
+    mut parenting_queue: QueueReader<ParentingEventTriggered>,
     mut player_query: Query<(Entity, &mut PlayerActionSystem, &Children)>,
     name_query: Query<&Name>,
     children_query: Query<&Children>,
     mut commands: Commands,
 ) {
-    for event in parenting_queue.0.drain(..) {
+    for event in parenting_queue.read().cloned() {
         if let Ok((player_entity, mut player_action, children)) = player_query.get_mut(event.player_entity) {
             match event.event_type {
                 BoneParentingEventType::ParentToBone { object_entity, bone_name, config } => {
@@ -14,11 +14,15 @@ use bevy::ecs::system::SystemParam;
 use crate::character::types::{FootIk, HandIk};
 use crate::head_track::types::HeadTrack;
 use crate::weapons::WeaponIkState;
+use crate::utils::{QueueCursor, QueueReader};
 
 #[derive(SystemParam)]
 pub struct ActionSystemParams<'w, 's> {
     pub start_queue: ResMut<'w, StartActionEventQueue>,
     pub end_queue: ResMut<'w, EndActionEventQueue>,
+    /// The action system both sends and reads start/end events, so it reads through cursors
+    pub start_cursor: Local<'s, QueueCursor<StartActionEvent>>,
+    pub end_cursor: Local<'s, QueueCursor<EndActionEvent>>,
     pub player_query: Query<'w, 's, (Entity, &'static mut PlayerActionSystem, &'static mut Transform)>,
     pub action_query: Query<'w, 's, (Entity, &'static mut ActionSystem, &'static GlobalTransform)>,
     pub movement_query: Query<'w, 's, &'static mut CharacterMovementState>,
@@ -113,7 +117,8 @@ pub fn update_action_system(
     mut commands: Commands,
 ) {
     // 1. Handle Start Action
-    for event in params.start_queue.0.drain(..) {
+    let started: Vec<StartActionEvent> = params.start_cursor.read(&params.start_queue).copied().collect();
+    for event in started {
         if let Ok((_player_entity, mut player_action, _)) = params.player_query.get_mut(event.player_entity) {
              // Only start if not already active
             if player_action.is_action_active {
@@ -368,7 +373,7 @@ pub fn update_action_system(
                             if action.player_state_control.drop_held_object {
                                 if let Ok(mut grabber) = params.grabber_query.get_mut(player_entity) {
                                     if let Some(held) = grabber.held_object {
-                                        params.grab_events.0.push(GrabEvent::Drop(player_entity, held));
+                                        params.grab_events.send(GrabEvent::Drop(player_entity, held));
                                         grabber.held_object = None;
                                         info!("Action System: Force-dropped held object {:?}", held);
                                     }
@@ -383,7 +388,7 @@ pub fn update_action_system(
                             player_action.state = ActionState::Interrupted;
                             info!("Action Interrupted by Jump");
                             // Trigger end-action logic via queue
-                            params.end_queue.0.push(EndActionEvent {
+                            params.end_queue.send(EndActionEvent {
                                 player_entity,
                                 action_entity,
                             });
@@ -465,7 +470,7 @@ pub fn update_action_system(
         }
         
         if action_ended {
-            params.end_queue.0.push(EndActionEvent {
+            params.end_queue.send(EndActionEvent {
                 player_entity,
                 action_entity: player_action.current_action.unwrap_or(Entity::PLACEHOLDER),
             });
@@ -473,7 +478,8 @@ pub fn update_action_system(
     }
     
     // 3. Handle End Action
-    for event in params.end_queue.0.drain(..) {
+    let ended: Vec<EndActionEvent> = params.end_cursor.read(&params.end_queue).copied().collect();
+    for event in ended {
         if let Ok((_ent, mut player_action, player_transform)) = params.player_query.get_mut(event.player_entity) {
             // Validate it's the correct action ending
             let is_matching_action = player_action.current_action == Some(event.action_entity) || event.action_entity == Entity::PLACEHOLDER;
@@ -688,7 +694,7 @@ pub fn update_action_system(
                      let angle_threshold = if action.use_min_angle { action.min_angle.to_radians().cos() } else { -1.0 };
                      
                      if dot >= angle_threshold {
-                         params.start_queue.0.push(StartActionEvent {
+                         params.start_queue.send(StartActionEvent {
                              player_entity,
                              action_entity,
                          });
@@ -822,7 +828,7 @@ pub fn apply_match_target_system(
 
 /// System to handle custom action activation requests
 pub fn handle_custom_action_activation_system(
-    mut activate_queue: ResMut<ActivateCustomActionEventQueue>,
+    mut activate_queue: QueueReader<ActivateCustomActionEvent>,
     mut start_action_queue: ResMut<StartActionEventQueue>,
     mut interrupted_queue: ResMut<ActionInterruptedEventQueue>,
     custom_action_manager: Res<CustomActionManager>,
@@ -836,7 +842,7 @@ pub fn handle_custom_action_activation_system(
     weapon_manager_query: Query<&WeaponManager>,
     time: Res<Time>,
 ) {
-    for event in activate_queue.0.drain(..) {
+    for event in activate_queue.read().cloned() {
         let action_name_lower = event.action_name.to_lowercase();
         
         // Look up action by name
@@ -933,7 +939,7 @@ pub fn handle_custom_action_activation_system(
                                         
                                         if can_interrupt || custom_action_info.can_force_interrupt {
                                             // Interrupt the current action
-                                            interrupted_queue.0.push(ActionInterruptedEvent {
+                                            interrupted_queue.send(ActionInterruptedEvent {
                                                 player_entity: event.player_entity,
                                                 interrupted_action_entity: current_action_entity,
                                                 new_action_entity: action_entity_to_use,
@@ -968,7 +974,7 @@ pub fn handle_custom_action_activation_system(
                         player_action.current_action_category = Some(custom_action_info.category.name.clone());
                         
                         // Start the action
-                        start_action_queue.0.push(StartActionEvent {
+                        start_action_queue.send(StartActionEvent {
                             player_entity: event.player_entity,
                             action_entity: action_entity_to_use,
                         });
@@ -1031,12 +1037,12 @@ pub fn update_walk_to_target_system(
                 // Initialize walk if needed
                 if player_action.walk_state == WalkToTargetState::Idle {
                     // Enable NavMesh override
-                    enable_queue.0.push(crate::player::navmesh_override::EnableNavMeshOverrideEvent {
+                    enable_queue.send(crate::player::navmesh_override::EnableNavMeshOverrideEvent {
                         entity: player_entity,
                     });
                     
                     // Set target
-                    target_queue.0.push(crate::player::navmesh_override::SetNavMeshTargetEvent {
+                    target_queue.send(crate::player::navmesh_override::SetNavMeshTargetEvent {
                         entity: player_entity,
                         target_position: action.walk_target_position,
                         target_entity: action.walk_target_entity,
@@ -1246,7 +1252,7 @@ fn fire_action_event(
     power_multiplier: f32,
 ) {
     if event.use_bevy_event {
-        event_queue.0.push(ActionEventTriggered {
+        event_queue.send(ActionEventTriggered {
             action_entity,
             player_entity,
             event_name: event.bevy_event_name.clone(),
@@ -1255,7 +1261,7 @@ fn fire_action_event(
     }
     
     if event.use_remote_event {
-        remote_queue.0.push(RemoteActionEvent {
+        remote_queue.send(RemoteActionEvent {
             event_name: event.remote_event_name.clone(),
             player_entity: if event.send_player_entity { Some(player_entity) } else { None },
         });
@@ -1263,7 +1269,7 @@ fn fire_action_event(
     }
     
     if event.use_camera_event {
-        camera_queue.0.push(CameraEventTriggered {
+        camera_queue.send(CameraEventTriggered {
             event_type: event.camera_event_type.clone(),
             player_entity,
             action_entity,
@@ -1278,7 +1284,7 @@ fn fire_action_event(
             action_entity  // Target is the action entity
         };
         
-        physics_queue.0.push(PhysicsEventTriggered {
+        physics_queue.send(PhysicsEventTriggered {
             event_type: event.physics_event_type.clone(),
             target_entity,
             source_entity: player_entity,
@@ -1287,7 +1293,7 @@ fn fire_action_event(
     }
     
     if event.use_state_change_event {
-        state_change_queue.0.push(StateChangeEventTriggered {
+        state_change_queue.send(StateChangeEventTriggered {
             event_type: event.state_change_event_type.clone(),
             player_entity,
         });
@@ -1295,7 +1301,7 @@ fn fire_action_event(
     }
 
     if event.use_weapon_event {
-        weapon_queue.0.push(WeaponEventTriggered {
+        weapon_queue.send(WeaponEventTriggered {
             event_type: event.weapon_event_type.clone(),
             player_entity,
             target_entity: event.weapon_target_entity,
@@ -1320,7 +1326,7 @@ fn fire_action_event(
             _ => {}
         }
         
-        power_queue.0.push(PowerEventTriggered {
+        power_queue.send(PowerEventTriggered {
             event_type: event.power_event_type.clone(),
             player_entity,
             amount,
@@ -1329,7 +1335,7 @@ fn fire_action_event(
     }
 
     if event.use_parenting_event {
-        parenting_queue.0.push(ParentingEventTriggered {
+        parenting_queue.send(ParentingEventTriggered {
             event_type: event.parenting_event_type.clone(),
             player_entity,
         });
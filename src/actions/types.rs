@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
+use crate::utils::EventQueue;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Reflect)]
 pub enum ActionState {
@@ -787,8 +788,7 @@ pub struct StartActionEvent {
     pub player_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct StartActionEventQueue(pub Vec<StartActionEvent>);
+pub type StartActionEventQueue = EventQueue<StartActionEvent>;
 
 /// Event when an action ends
 #[derive(Debug, Clone, Copy, Event)]
@@ -797,8 +797,7 @@ pub struct EndActionEvent {
     pub player_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct EndActionEventQueue(pub Vec<EndActionEvent>);
+pub type EndActionEventQueue = EventQueue<EndActionEvent>;
 
 /// Event to activate a custom action by name
 #[derive(Debug, Clone)]
@@ -807,8 +806,7 @@ pub struct ActivateCustomActionEvent {
     pub action_name: String,
 }
 
-#[derive(Resource, Default)]
-pub struct ActivateCustomActionEventQueue(pub Vec<ActivateCustomActionEvent>);
+pub type ActivateCustomActionEventQueue = EventQueue<ActivateCustomActionEvent>;
 
 /// Event to stop a custom action by name
 #[derive(Debug, Clone)]
@@ -817,8 +815,7 @@ pub struct StopCustomActionEvent {
     pub action_name: String,
 }
 
-#[derive(Resource, Default)]
-pub struct StopCustomActionEventQueue(pub Vec<StopCustomActionEvent>);
+pub type StopCustomActionEventQueue = EventQueue<StopCustomActionEvent>;
 
 /// Event when an action is interrupted
 #[derive(Debug, Clone, Copy)]
@@ -828,8 +825,7 @@ pub struct ActionInterruptedEvent {
     pub new_action_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct ActionInterruptedEventQueue(pub Vec<ActionInterruptedEvent>);
+pub type ActionInterruptedEventQueue = EventQueue<ActionInterruptedEvent>;
 
 // ============================================================================
 // Event System
@@ -843,8 +839,7 @@ pub struct ActionEventTriggered {
     pub event_name: String,
 }
 
-#[derive(Resource, Default)]
-pub struct ActionEventTriggeredQueue(pub Vec<ActionEventTriggered>);
+pub type ActionEventTriggeredQueue = EventQueue<ActionEventTriggered>;
 
 /// Remote event for cross-system communication
 #[derive(Debug, Clone)]
@@ -853,8 +848,7 @@ pub struct RemoteActionEvent {
     pub player_entity: Option<Entity>,
 }
 
-#[derive(Resource, Default)]
-pub struct RemoteActionEventQueue(pub Vec<RemoteActionEvent>);
+pub type RemoteActionEventQueue = EventQueue<RemoteActionEvent>;
 
 /// Camera event triggered during action
 #[derive(Debug, Clone)]
@@ -864,8 +858,7 @@ pub struct CameraEventTriggered {
     pub action_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct CameraEventQueue(pub Vec<CameraEventTriggered>);
+pub type CameraEventQueue = EventQueue<CameraEventTriggered>;
 
 /// Physics event triggered during action
 #[derive(Debug, Clone)]
@@ -875,8 +868,7 @@ pub struct PhysicsEventTriggered {
     pub source_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct PhysicsEventQueue(pub Vec<PhysicsEventTriggered>);
+pub type PhysicsEventQueue = EventQueue<PhysicsEventTriggered>;
 
 /// State change event triggered during action
 #[derive(Debug, Clone)]
@@ -885,8 +877,7 @@ pub struct StateChangeEventTriggered {
     pub player_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct StateChangeEventQueue(pub Vec<StateChangeEventTriggered>);
+pub type StateChangeEventQueue = EventQueue<StateChangeEventTriggered>;
 
 /// Weapon event triggered during an action
 #[derive(Debug, Clone)]
@@ -897,8 +888,7 @@ pub struct WeaponEventTriggered {
 }
 
 /// Queue for weapon events triggered during actions
-#[derive(Resource, Default)]
-pub struct WeaponEventQueue(pub Vec<WeaponEventTriggered>);
+pub type WeaponEventQueue = EventQueue<WeaponEventTriggered>;
 
 /// Power event triggered during an action
 #[derive(Debug, Clone)]
@@ -908,8 +898,7 @@ pub struct PowerEventTriggered {
     pub amount: f32,
 }
 
-#[derive(Resource, Default)]
-pub struct PowerEventQueue(pub Vec<PowerEventTriggered>);
+pub type PowerEventQueue = EventQueue<PowerEventTriggered>;
 
 /// Event triggered when a parenting event occurs
 #[derive(Debug, Clone)]
@@ -918,5 +907,4 @@ pub struct ParentingEventTriggered {
     pub player_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct ParentingEventQueue(pub Vec<ParentingEventTriggered>);
+pub type ParentingEventQueue = EventQueue<ParentingEventTriggered>;
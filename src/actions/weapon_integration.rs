@@ -3,11 +3,12 @@ use crate::weapons::{WeaponManager, Weapon, GrenadeState, throw_grenade};
 use crate::devices::types::Power;
 use avian3d::prelude::SpatialQuery;
 use super::types::*;
+use crate::utils::QueueReader;
 
 /// Process weapon events triggered by actions
 pub fn process_weapon_events(
     mut commands: Commands,
-    mut weapon_queue: ResMut<WeaponEventQueue>,
+    mut weapon_queue: QueueReader<WeaponEventTriggered>,
     mut weapon_manager_query: Query<&mut WeaponManager>,
     mut weapon_query: Query<(&mut Weapon, &mut Visibility)>,
     mut grenade_query: Query<&mut GrenadeState>,
@@ -15,7 +16,7 @@ pub fn process_weapon_events(
     spatial_query: SpatialQuery,
     time: Res<Time>,
 ) {
-    for event in weapon_queue.0.drain(..) {
+    for event in weapon_queue.read().cloned() {
         if let Ok(mut manager) = weapon_manager_query.get_mut(event.player_entity) {
             match event.event_type {
                 WeaponEventType::Fire { burst_count, delay_between_shots } => {
@@ -76,10 +77,10 @@ pub fn process_weapon_events(
 
 /// Process power events triggered by actions
 pub fn process_power_events(
-    mut power_queue: ResMut<PowerEventQueue>,
+    mut power_queue: QueueReader<PowerEventTriggered>,
     mut power_query: Query<&mut Power>,
 ) {
-    for event in power_queue.0.drain(..) {
+    for event in power_queue.read().cloned() {
         if let Ok(mut power) = power_query.get_mut(event.player_entity) {
             match event.event_type {
                 PowerEventType::ConsumePower { amount } => {
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use crate::ai::types::*;
 use rand::Rng;
+use crate::weapons::{LeadAim, WeaponManager};
 
 #[derive(Component, Debug, Reflect, Default)]
 #[reflect(Component)]
@@ -259,3 +260,27 @@ fn update_powers_combat(
         input.ability_use_pressed = false;
     }
 }
+
+/// Points the lead solver of ranged AI at their combat target, so projectile
+/// weapons fire where the target will be
+pub fn update_ai_lead_targets(
+    mut commands: Commands,
+    mut query: Query<(Entity, &AiController, &AiRangedCombatSettings, Option<&mut LeadAim>), With<WeaponManager>>,
+) {
+    for (entity, ai, ranged, lead) in query.iter_mut() {
+        let in_combat = matches!(ai.state, AiBehaviorState::Combat | AiBehaviorState::Attack);
+        let target = if in_combat && ranged.lead_targets { ai.target } else { None };
+
+        match lead {
+            Some(mut lead) => {
+                if lead.target != target {
+                    lead.target = target;
+                }
+            }
+            None if ranged.lead_targets => {
+                commands.entity(entity).insert(LeadAim { target, ..default() });
+            }
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,486 @@
+//! Crowd layer for ambient NPCs
+//!
+//! [`CrowdAgent`]s are cheap background characters for populating cities: no
+//! physics body, perception or combat brain, just kinematic wandering with
+//! separation from their neighbours. They flee from loud noises
+//! ([`NoiseEvent`]), gather around [`CrowdGatherPoint`]s, and are upgraded to a
+//! full [`AiController`] character when the player interacts with or hurts them.
+
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+
+use super::types::*;
+use crate::character::{character_physics, CharacterAnimationState, CharacterController, CharacterMovementState};
+use crate::combat::{DamageEvent, Health};
+use crate::input::InputState;
+use crate::interaction::InteractionEvent;
+use crate::utils::{EventQueue, QueueReader};
+
+/// What a crowd agent is doing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum CrowdAgentState {
+    #[default]
+    Idle,
+    Wander,
+    Flee,
+    Gather,
+}
+
+/// Lightweight ambient NPC
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CrowdAgent {
+    pub state: CrowdAgentState,
+    pub walk_speed: f32,
+    pub run_speed: f32,
+    /// Center of wandering; the spawn position when `None`
+    pub home: Option<Vec3>,
+    pub wander_radius: f32,
+    pub destination: Option<Vec3>,
+    /// Pause between wander destinations (seconds, min/max)
+    pub idle_time: Vec2,
+    /// Multiplier of the noise hearing range
+    pub hearing_multiplier: f32,
+    /// Entity of the gathering joined
+    pub gather_point: Option<Entity>,
+    pub upgrade_on_interaction: bool,
+    pub upgrade_on_damage: bool,
+    /// Time left in the current state (idle pause or flight)
+    pub timer: f32,
+    pub velocity: Vec3,
+}
+
+impl Default for CrowdAgent {
+    fn default() -> Self {
+        Self {
+            state: CrowdAgentState::Idle,
+            walk_speed: 1.4,
+            run_speed: 4.5,
+            home: None,
+            wander_radius: 15.0,
+            destination: None,
+            idle_time: Vec2::new(1.0, 5.0),
+            hearing_multiplier: 1.0,
+            gather_point: None,
+            upgrade_on_interaction: true,
+            upgrade_on_damage: true,
+            timer: 0.0,
+            velocity: Vec3::ZERO,
+        }
+    }
+}
+
+/// Place crowd agents gather around (street performer, accident, fight...)
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CrowdGatherPoint {
+    pub enabled: bool,
+    /// Distance from which agents are drawn in
+    pub attraction_radius: f32,
+    /// Distance agents stand at
+    pub ring_radius: f32,
+    pub max_agents: usize,
+    /// Seconds before the gathering disperses; `None` lasts until removed
+    pub lifetime: Option<f32>,
+}
+
+impl Default for CrowdGatherPoint {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            attraction_radius: 20.0,
+            ring_radius: 3.0,
+            max_agents: 12,
+            lifetime: None,
+        }
+    }
+}
+
+/// Global crowd tuning
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct CrowdSettings {
+    pub enabled: bool,
+    /// Hearing range of a noise of volume 1 (scales with the volume)
+    pub noise_hearing_range: f32,
+    pub min_noise_volume: f32,
+    pub flee_duration: f32,
+    /// Distance kept from other agents
+    pub separation_radius: f32,
+    pub separation_weight: f32,
+    pub turn_speed: f32,
+    pub acceleration: f32,
+}
+
+impl Default for CrowdSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            noise_hearing_range: 25.0,
+            min_noise_volume: 0.5,
+            flee_duration: 6.0,
+            separation_radius: 0.8,
+            separation_weight: 1.5,
+            turn_speed: 8.0,
+            acceleration: 6.0,
+        }
+    }
+}
+
+/// Sent when a crowd agent becomes a full AI character, to add game-specific
+/// components (weapons, combat settings, dialog...)
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct CrowdAgentUpgradedEvent {
+    pub entity: Entity,
+    /// Entity whose interaction or damage caused the upgrade
+    pub cause: Option<Entity>,
+}
+
+pub type CrowdAgentUpgradedEventQueue = EventQueue<CrowdAgentUpgradedEvent>;
+
+/// System to send crowd agents running from loud noises
+pub fn react_crowd_to_noise(
+    settings: Res<CrowdSettings>,
+    mut noise_events: QueueReader<NoiseEvent>,
+    mut agents: Query<(&mut CrowdAgent, &GlobalTransform)>,
+) {
+    for event in noise_events.read() {
+        if !settings.enabled || event.volume < settings.min_noise_volume {
+            continue;
+        }
+
+        for (mut agent, transform) in agents.iter_mut() {
+            let range = settings.noise_hearing_range * event.volume * agent.hearing_multiplier;
+            let offset = transform.translation() - event.position;
+            if offset.length_squared() > range * range {
+                continue;
+            }
+
+            let mut away = Vec3::new(offset.x, 0.0, offset.z).normalize_or_zero();
+            if away == Vec3::ZERO {
+                away = Vec3::X;
+            }
+            agent.state = CrowdAgentState::Flee;
+            agent.gather_point = None;
+            agent.destination = Some(transform.translation() + away * agent.run_speed * settings.flee_duration);
+            agent.timer = settings.flee_duration;
+        }
+    }
+}
+
+/// System to draw idle and wandering agents into gatherings and disperse expired ones
+pub fn update_crowd_gatherings(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut points: Query<(Entity, &mut CrowdGatherPoint, &GlobalTransform)>,
+    mut agents: Query<(Entity, &mut CrowdAgent, &GlobalTransform)>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut point, _) in points.iter_mut() {
+        let Some(lifetime) = point.lifetime.as_mut() else { continue };
+        *lifetime -= delta;
+        if *lifetime <= 0.0 {
+            commands.entity(entity).remove::<CrowdGatherPoint>();
+        }
+    }
+
+    let mut members: HashMap<Entity, usize> = HashMap::new();
+    for (_, mut agent, _) in agents.iter_mut() {
+        if agent.state != CrowdAgentState::Gather {
+            continue;
+        }
+        let active = agent
+            .gather_point
+            .and_then(|entity| points.get(entity).ok())
+            .is_some_and(|(_, point, _)| point.enabled && point.lifetime.is_none_or(|lifetime| lifetime > 0.0));
+        match agent.gather_point {
+            Some(entity) if active => *members.entry(entity).or_default() += 1,
+            _ => {
+                agent.state = CrowdAgentState::Idle;
+                agent.gather_point = None;
+                agent.destination = None;
+                agent.timer = 0.0;
+            }
+        }
+    }
+
+    for (agent_entity, mut agent, transform) in agents.iter_mut() {
+        if !matches!(agent.state, CrowdAgentState::Idle | CrowdAgentState::Wander) {
+            continue;
+        }
+
+        let position = transform.translation();
+        for (point_entity, point, point_transform) in points.iter() {
+            let count = members.entry(point_entity).or_default();
+            if !point.enabled || *count >= point.max_agents {
+                continue;
+            }
+            let center = point_transform.translation();
+            if position.distance_squared(center) > point.attraction_radius * point.attraction_radius {
+                continue;
+            }
+
+            // Spread the onlookers around the ring by entity index
+            let angle = (agent_entity.to_bits() % 64) as f32 * 2.399_963;
+            agent.state = CrowdAgentState::Gather;
+            agent.gather_point = Some(point_entity);
+            agent.destination = Some(center + Vec3::new(angle.cos(), 0.0, angle.sin()) * point.ring_radius);
+            *count += 1;
+            break;
+        }
+    }
+}
+
+/// System to steer crowd agents toward their destination while keeping apart from each other
+pub fn steer_crowd_agents(
+    time: Res<Time>,
+    settings: Res<CrowdSettings>,
+    mut agents: Query<(Entity, &mut CrowdAgent, &mut Transform)>,
+) {
+    if !settings.enabled {
+        return;
+    }
+
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+
+    // Neighbours are looked up in a grid of cells the size of the separation radius
+    let cell_size = settings.separation_radius.max(0.1);
+    let cell = |position: Vec3| IVec2::new((position.x / cell_size).floor() as i32, (position.z / cell_size).floor() as i32);
+    let mut grid: HashMap<IVec2, Vec<(Entity, Vec3)>> = HashMap::new();
+    for (entity, _, transform) in agents.iter() {
+        grid.entry(cell(transform.translation)).or_default().push((entity, transform.translation));
+    }
+
+    let mut rng = rand::rng();
+    for (entity, mut agent, mut transform) in agents.iter_mut() {
+        let position = transform.translation;
+        let home = *agent.home.get_or_insert(position);
+        agent.timer -= delta;
+
+        match agent.state {
+            CrowdAgentState::Idle if agent.timer <= 0.0 => {
+                let angle = rng.random_range(0.0..std::f32::consts::TAU);
+                let distance = rng.random_range(0.0..agent.wander_radius.max(0.1));
+                agent.destination = Some(home + Vec3::new(angle.cos(), 0.0, angle.sin()) * distance);
+                agent.state = CrowdAgentState::Wander;
+            }
+            CrowdAgentState::Flee if agent.timer <= 0.0 => {
+                agent.state = CrowdAgentState::Idle;
+                agent.destination = None;
+                agent.timer = rng.random_range(agent.idle_time.x..=agent.idle_time.y.max(agent.idle_time.x));
+            }
+            _ => {}
+        }
+
+        let speed = if agent.state == CrowdAgentState::Flee { agent.run_speed } else { agent.walk_speed };
+        let mut desired = Vec3::ZERO;
+        if let Some(destination) = agent.destination {
+            let to_destination = Vec3::new(destination.x - position.x, 0.0, destination.z - position.z);
+            let distance = to_destination.length();
+            if distance > 0.3 {
+                desired = to_destination / distance * speed;
+            } else if agent.state == CrowdAgentState::Wander {
+                agent.state = CrowdAgentState::Idle;
+                agent.destination = None;
+                agent.timer = rng.random_range(agent.idle_time.x..=agent.idle_time.y.max(agent.idle_time.x));
+            }
+        }
+
+        let home_cell = cell(position);
+        let mut separation = Vec3::ZERO;
+        for x in -1..=1 {
+            for y in -1..=1 {
+                let Some(neighbours) = grid.get(&(home_cell + IVec2::new(x, y))) else { continue };
+                for (other, other_position) in neighbours {
+                    if *other == entity {
+                        continue;
+                    }
+                    let offset = Vec3::new(position.x - other_position.x, 0.0, position.z - other_position.z);
+                    let distance = offset.length();
+                    if distance > 1e-4 && distance < settings.separation_radius {
+                        separation += offset / distance * (1.0 - distance / settings.separation_radius);
+                    }
+                }
+            }
+        }
+        desired += separation * settings.separation_weight * agent.walk_speed;
+
+        let velocity = agent.velocity.lerp(desired, (settings.acceleration * delta).min(1.0));
+        agent.velocity = velocity;
+        transform.translation += velocity * delta;
+
+        if velocity.length_squared() > 0.01 {
+            let target = Quat::from_rotation_y(f32::atan2(-velocity.x, -velocity.z));
+            transform.rotation = transform.rotation.slerp(target, (settings.turn_speed * delta).min(1.0));
+        }
+    }
+}
+
+/// System to turn crowd agents the player interacts with, or that get hurt, into full AI characters
+pub fn upgrade_crowd_agents(
+    mut commands: Commands,
+    mut interaction_events: QueueReader<InteractionEvent>,
+    mut damage_events: QueueReader<DamageEvent>,
+    agents: Query<(&CrowdAgent, &Transform)>,
+    mut upgraded_events: ResMut<CrowdAgentUpgradedEventQueue>,
+) {
+    let interactions = interaction_events
+        .read()
+        .filter(|event| agents.get(event.target).is_ok_and(|(agent, _)| agent.upgrade_on_interaction))
+        .map(|event| (event.target, Some(event.source)));
+    let damage = damage_events
+        .read()
+        .filter(|event| agents.get(event.target).is_ok_and(|(agent, _)| agent.upgrade_on_damage))
+        .map(|event| (event.target, event.source));
+    let requests: Vec<(Entity, Option<Entity>)> = interactions.chain(damage).collect();
+
+    let mut upgraded = Vec::new();
+    for (entity, cause) in requests {
+        if upgraded.contains(&entity) {
+            continue;
+        }
+        let Ok((agent, transform)) = agents.get(entity) else { continue };
+        upgraded.push(entity);
+
+        commands
+            .entity(entity)
+            .remove::<CrowdAgent>()
+            .insert((
+                CharacterController {
+                    walk_speed: agent.walk_speed,
+                    ..default()
+                },
+                CharacterMovementState::default(),
+                CharacterAnimationState::default(),
+                InputState::default(),
+                AiController {
+                    state: AiBehaviorState::Wander,
+                    wander_radius: agent.wander_radius,
+                    wander_center: agent.home.unwrap_or(transform.translation),
+                    ..default()
+                },
+                AiMovement {
+                    speed: agent.walk_speed,
+                    stop_distance: 0.5,
+                    ..default()
+                },
+                AiPerception::default(),
+                AIPerceptionSettings {
+                    fov: 90.0,
+                    range: 20.0,
+                    hearing_range: 15.0,
+                    layer_mask: u32::MAX,
+                },
+            ))
+            .insert(character_physics());
+        commands.entity(entity).insert_if_new(Health::default());
+
+        upgraded_events.send(CrowdAgentUpgradedEvent { entity, cause });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::DamageType;
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[derive(Resource, Default)]
+    struct Upgrades(Vec<(Entity, Option<Entity>)>);
+
+    fn log_upgrades(mut upgrades: QueueReader<CrowdAgentUpgradedEvent>, mut log: ResMut<Upgrades>) {
+        log.0.extend(upgrades.read().map(|event| (event.entity, event.cause)));
+    }
+
+    fn spawn_agent(app: &mut App, position: Vec3) -> Entity {
+        app.world_mut()
+            .spawn((CrowdAgent::default(), Transform::from_translation(position), GlobalTransform::from_translation(position)))
+            .id()
+    }
+
+    fn state(app: &App, agent: Entity) -> CrowdAgentState {
+        app.world().get::<CrowdAgent>(agent).unwrap().state
+    }
+
+    fn position(app: &App, agent: Entity) -> Vec3 {
+        app.world().get::<Transform>(agent).unwrap().translation
+    }
+
+    #[test]
+    fn test_crowd_gathers_flees_and_upgrades() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            TransformPlugin,
+            EventQueuePlugin::<NoiseEvent>::default(),
+            EventQueuePlugin::<InteractionEvent>::default(),
+            EventQueuePlugin::<DamageEvent>::default(),
+            EventQueuePlugin::<CrowdAgentUpgradedEvent>::default(),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .init_resource::<CrowdSettings>()
+        .init_resource::<Upgrades>()
+        .add_systems(Update, (
+            upgrade_crowd_agents,
+            react_crowd_to_noise,
+            update_crowd_gatherings,
+            steer_crowd_agents,
+            log_upgrades,
+        ).chain());
+
+        let center = Vec3::new(10.0, 0.0, 0.0);
+        app.world_mut().spawn((
+            CrowdGatherPoint { max_agents: 1, ..default() },
+            Transform::from_translation(center),
+            GlobalTransform::from_translation(center),
+        ));
+        let near = [spawn_agent(&mut app, Vec3::new(5.0, 0.0, 0.0)), spawn_agent(&mut app, Vec3::new(6.0, 0.0, 0.0))];
+        let far = spawn_agent(&mut app, Vec3::new(50.0, 0.0, 0.0));
+
+        // One onlooker fits, and it walks over to the ring
+        app.update();
+        let gathered: Vec<Entity> = near.into_iter().filter(|agent| state(&app, *agent) == CrowdAgentState::Gather).collect();
+        assert_eq!(gathered.len(), 1);
+        let onlooker = gathered[0];
+        assert_ne!(state(&app, far), CrowdAgentState::Gather);
+        for _ in 0..80 {
+            app.update();
+        }
+        let ring_distance = position(&app, onlooker).distance(center);
+        assert!((ring_distance - 3.0).abs() < 0.5, "ring distance {}", ring_distance);
+
+        // A gunshot at the gathering scatters it; the far agent doesn't hear it
+        let shooter = app.world_mut().spawn_empty().id();
+        app.world_mut().resource_mut::<NoiseEventQueue>().send(NoiseEvent { position: center, volume: 1.0, source: shooter });
+        app.update();
+        assert_eq!(state(&app, onlooker), CrowdAgentState::Flee);
+        assert_eq!(app.world().get::<CrowdAgent>(onlooker).unwrap().gather_point, None);
+        assert_ne!(state(&app, far), CrowdAgentState::Flee);
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(position(&app, onlooker).distance(center) > ring_distance + 2.0);
+
+        app.world_mut().resource_mut::<EventQueue<DamageEvent>>().send(DamageEvent {
+            amount: 5.0,
+            damage_type: DamageType::Melee,
+            source: Some(shooter),
+            target: far,
+            position: None,
+            direction: None,
+            ignore_shield: false,
+        });
+        app.update();
+        assert!(app.world().get::<CrowdAgent>(far).is_none());
+        assert_eq!(app.world().get::<AiController>(far).unwrap().state, AiBehaviorState::Wander);
+        assert!(app.world().get::<Health>(far).is_some());
+        assert_eq!(app.world().resource::<Upgrades>().0, [(far, Some(shooter))]);
+    }
+}
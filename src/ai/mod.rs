@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 use bevy::app::App;
+#[cfg(feature = "vehicles")]
 use crate::vehicles::types::{VehicleAI, WaypointPath};
+use crate::utils::EventQueuePlugin;
+use crate::simulation::SimulationAppExt;
+use crate::input::{ConsoleAppExt, ConsoleCommand};
 
 pub mod types;
 mod systems;
@@ -13,6 +17,9 @@ mod behavior;
 mod hiding;
 mod movement;
 mod navigation;
+mod obstacles;
+mod crowd;
+#[cfg(feature = "vehicles")]
 mod vehicle_ai;
 
 pub use types::*;
@@ -26,13 +33,24 @@ pub use behavior::*;
 pub use hiding::*;
 pub use movement::*;
 pub use navigation::*;
+pub use obstacles::*;
+pub use crowd::*;
+#[cfg(feature = "vehicles")]
 pub use vehicle_ai::*;
 
 pub struct AiPlugin;
 
 impl Plugin for AiPlugin {
     fn build(&self, app: &mut App) {
+        let simulation = app.simulation_schedule();
+
         app
+            .init_resource::<AiSettings>()
+            .register_type::<AiSettings>()
+            .add_console_command(
+                ConsoleCommand::new("toggle_ai", "Stops or restarts every AI").with_usage("[on|off]"),
+                toggle_ai_command,
+            )
             .register_type::<AiController>()
             .register_type::<AiPerception>()
             .register_type::<FriendManager>()
@@ -57,39 +75,75 @@ impl Plugin for AiPlugin {
             .register_type::<AiMovement>()
             .register_type::<AiNavWaypoint>()
             .register_type::<AiPath>()
+            .register_type::<AiNavLink>()
             .register_type::<AiNavigationSettings>()
+            .register_type::<NavObstacle>()
             .init_resource::<AiNavGraph>()
-            .register_type::<VehicleAI>()
-            .register_type::<WaypointPath>()
             .register_type::<AiHearingSettings>()
             .register_type::<AiCombatRangeSettings>()
             .register_type::<AiAlertSettings>()
             .register_type::<AiAvoidanceSettings>()
             .init_resource::<FactionSystem>()
             .init_resource::<FriendSystem>()
-            .init_resource::<NoiseEventQueue>()
-            .add_systems(Update, (
+            .register_type::<CrowdAgent>()
+            .register_type::<CrowdGatherPoint>()
+            .register_type::<CrowdSettings>()
+            .init_resource::<CrowdSettings>()
+            .add_plugins(EventQueuePlugin::<NoiseEvent>::default())
+            .add_plugins(EventQueuePlugin::<CrowdAgentUpgradedEvent>::default())
+            .configure_sets(simulation, (
+                AiSet::Perception,
+                AiSet::Decision,
+                AiSet::Navigation,
+                AiSet::Combat,
+                AiSet::Visuals,
+            ).chain().in_set(crate::GameControllerSet::Ai).run_if(ai_enabled))
+            .add_systems(simulation, (
                 update_ai_perception,
                 update_ai_hearing,
                 update_ai_alert_allies,
+                update_faction_relations,
+                alert_faction_members,
+            ).in_set(AiSet::Perception))
+            .add_systems(simulation, (
                 handle_friend_commands,
                 update_ai_behavior,
                 update_ai_suspicion,
-                rebuild_nav_graph,
-                update_ai_navigation,
+                upgrade_crowd_agents,
+                (react_crowd_to_noise, update_crowd_gatherings).chain(),
+            ).in_set(AiSet::Decision))
+            .add_systems(simulation, (
+                (
+                    rebuild_nav_graph,
+                    update_door_obstacles,
+                    update_carried_obstacles,
+                    sync_nav_obstacles,
+                    update_ai_navigation,
+                ).chain(),
                 update_ai_movement,
                 update_ai_avoidance,
                 update_patrol,
+                steer_crowd_agents,
+            ).in_set(AiSet::Navigation))
+            .add_systems(simulation, (
                 update_turrets,
                 update_turret_firing,
                 update_turret_lasers,
                 update_ai_combat,
+                update_ai_lead_targets.after(update_ai_combat),
                 update_ai_hiding,
+            ).in_set(AiSet::Combat))
+            .add_systems(simulation, (
                 draw_ai_vision_cones,
                 update_ai_state_visuals,
-                update_faction_relations,
-                alert_faction_members,
-                update_vehicle_ai,
-            ));
+                draw_turret_lasers,
+            ).in_set(AiSet::Visuals).in_set(crate::headless::PresentationSet));
+
+        #[cfg(feature = "vehicles")]
+        app
+            .register_type::<VehicleAI>()
+            .register_type::<HidePositionsManager>()
+            .register_type::<WaypointPath>()
+            .add_systems(simulation, update_vehicle_ai.in_set(AiSet::Navigation));
     }
 }
@@ -38,7 +38,12 @@ pub fn update_ai_movement(
             if let Some(destination) = movement.destination {
                 let current_pos = transform.translation();
                 let to_dest = destination - current_pos;
-                let horizontal_dist = Vec3::new(to_dest.x, 0.0, to_dest.z).length();
+                // Link ends (a ladder's top and bottom) can sit right above each other
+                let horizontal_dist = if path.current_link().is_some() {
+                    to_dest.length()
+                } else {
+                    Vec3::new(to_dest.x, 0.0, to_dest.z).length()
+                };
 
                 if horizontal_dist > movement.stop_distance {
                     let move_dir = to_dest.normalize_or_zero();
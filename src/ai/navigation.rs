@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap};
 
+use super::obstacles::NavObstacleVolume;
 use super::types::*;
 
 #[derive(Resource, Debug)]
@@ -9,12 +10,36 @@ pub struct AiNavGraph {
     pub nodes: Vec<(Entity, Vec3)>,
     pub last_build_time: f32,
     pub rebuild_interval: f32,
+    /// Blocking `NavObstacle`s, kept by `sync_nav_obstacles`
+    pub obstacles: Vec<(Entity, NavObstacleVolume)>,
+    /// Bumped whenever the obstacles change
+    pub obstacle_version: u64,
+    /// Enabled `AiNavLink`s as (link, from waypoint, to waypoint), kept by `rebuild_nav_graph`
+    pub links: Vec<(Entity, Entity, Entity)>,
+}
+
+impl AiNavGraph {
+    /// Whether `point` is inside an obstacle
+    pub fn is_point_blocked(&self, point: Vec3) -> bool {
+        self.obstacles.iter().any(|(_, volume)| volume.contains(point))
+    }
+
+    /// Whether the straight line from `from` to `to` passes through an obstacle
+    pub fn is_segment_blocked(&self, from: Vec3, to: Vec3) -> bool {
+        self.obstacles.iter().any(|(_, volume)| volume.intersects_segment(from, to))
+    }
+
+    /// Links leaving waypoint `node`, as (link, other end)
+    fn links_from(&self, node: Entity) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.links.iter().filter_map(move |&(link, from, to)| (from == node).then_some((link, to)))
+    }
 }
 
 pub fn rebuild_nav_graph(
     time: Res<Time>,
     mut graph: ResMut<AiNavGraph>,
     waypoint_query: Query<(Entity, &GlobalTransform, &AiNavWaypoint)>,
+    link_query: Query<(Entity, &AiNavLink)>,
 ) {
     let now = time.elapsed_secs();
     if now - graph.last_build_time < graph.rebuild_interval {
@@ -26,9 +51,40 @@ pub fn rebuild_nav_graph(
         .filter(|(_, _, w)| w.is_active)
         .map(|(e, gt, _)| (e, gt.translation()))
         .collect();
+
+    let mut links = Vec::new();
+    for (entity, link) in link_query.iter() {
+        let connected = [link.from, link.to].iter().all(|end| graph.nodes.iter().any(|(node, _)| node == end));
+        if !link.enabled || !connected {
+            continue;
+        }
+        links.push((entity, link.from, link.to));
+        if link.bidirectional {
+            links.push((entity, link.to, link.from));
+        }
+    }
+    graph.links = links;
     graph.last_build_time = now;
 }
 
+/// Connects two waypoints that can't be walked between, such as the bottom and
+/// top of a ladder. Paths may route through it whatever the distance; the system
+/// owning the link entity (e.g. `update_ai_ladder_usage`) moves the agent across.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct AiNavLink {
+    pub from: Entity,
+    pub to: Entity,
+    pub bidirectional: bool,
+    pub enabled: bool,
+}
+
+impl AiNavLink {
+    pub fn new(from: Entity, to: Entity) -> Self {
+        Self { from, to, bidirectional: true, enabled: true }
+    }
+}
+
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
 pub struct AiNavWaypoint {
@@ -46,6 +102,20 @@ impl Default for AiNavWaypoint {
 pub struct AiPath {
     pub points: Vec<Vec3>,
     pub current_index: usize,
+    /// `AiNavLink`s on the path, as (index of the point the link leads to, link)
+    pub links: Vec<(usize, Entity)>,
+}
+
+impl AiPath {
+    /// Link crossed to reach the point at `index`, if that stretch isn't walked
+    pub fn link_to(&self, index: usize) -> Option<Entity> {
+        self.links.iter().find(|(to_index, _)| *to_index == index).map(|(_, link)| *link)
+    }
+
+    /// Link the agent is heading through, if any
+    pub fn current_link(&self) -> Option<Entity> {
+        self.link_to(self.current_index)
+    }
 }
 
 #[derive(Component, Debug, Reflect)]
@@ -76,6 +146,9 @@ impl Default for AiNavGraph {
             nodes: Vec::new(),
             last_build_time: -999.0,
             rebuild_interval: 2.0,
+            obstacles: Vec::new(),
+            obstacle_version: 0,
+            links: Vec::new(),
         }
     }
 }
@@ -119,14 +192,14 @@ pub fn update_ai_navigation(
             continue;
         }
 
-        let start = find_closest_waypoint(transform.translation(), &graph.nodes);
-        let goal = find_closest_waypoint(destination, &graph.nodes);
+        let start = find_closest_waypoint(transform.translation(), &graph);
+        let goal = find_closest_waypoint(destination, &graph);
         nav_settings.last_repath_time = now;
 
         let Some((start_ent, _)) = start else { continue };
         let Some((goal_ent, _)) = goal else { continue };
 
-        let path = compute_path(start_ent, goal_ent, &graph.nodes, nav_settings.waypoint_connection_radius);
+        let path = compute_path(start_ent, goal_ent, &graph, nav_settings.waypoint_connection_radius);
         if path.is_empty() {
             if nav_settings.accept_partial_path {
                 continue;
@@ -136,8 +209,12 @@ pub fn update_ai_navigation(
         }
 
         let mut points = Vec::new();
-        for node in path {
+        let mut links = Vec::new();
+        for (node, link) in path {
             if let Some((_e, pos)) = graph.nodes.iter().find(|(e, _)| *e == node) {
+                if let Some(link) = link {
+                    links.push((points.len(), link));
+                }
                 points.push(*pos);
             }
         }
@@ -146,23 +223,47 @@ pub fn update_ai_navigation(
         if let Some(mut path_component) = path_opt {
             path_component.points = points;
             path_component.current_index = 0;
+            path_component.links = links;
         } else {
             movement.destination = points.first().copied();
             commands.entity(entity).insert(AiPath {
                 points,
                 current_index: 0,
+                links,
             });
         }
     }
 }
 
+/// Waypoint path from `from` to `to` (destination included), or empty if unreachable
+pub fn find_nav_path(graph: &AiNavGraph, from: Vec3, to: Vec3, radius: f32) -> Vec<Vec3> {
+    let Some((start_ent, _)) = find_closest_waypoint(from, graph) else { return Vec::new() };
+    let Some((goal_ent, _)) = find_closest_waypoint(to, graph) else { return Vec::new() };
+
+    let path = compute_path(start_ent, goal_ent, graph, radius);
+    if path.is_empty() {
+        return Vec::new();
+    }
+
+    let mut points: Vec<Vec3> = path
+        .into_iter()
+        .filter_map(|(node, _)| graph.nodes.iter().find(|(e, _)| *e == node).map(|(_, pos)| *pos))
+        .collect();
+    points.push(to);
+    points
+}
+
+/// Closest waypoint that isn't inside an obstacle
 fn find_closest_waypoint(
     position: Vec3,
-    waypoints: &[(Entity, Vec3)],
+    graph: &AiNavGraph,
 ) -> Option<(Entity, Vec3)> {
     let mut best = None;
     let mut best_dist = f32::MAX;
-    for (entity, pos) in waypoints.iter() {
+    for (entity, pos) in graph.nodes.iter() {
+        if graph.is_point_blocked(*pos) {
+            continue;
+        }
         let dist = position.distance(*pos);
         if dist < best_dist {
             best_dist = dist;
@@ -172,14 +273,16 @@ fn find_closest_waypoint(
     best
 }
 
+/// Waypoints from `start` to `goal`, each with the link crossed to reach it
 fn compute_path(
     start: Entity,
     goal: Entity,
-    waypoints: &[(Entity, Vec3)],
+    graph: &AiNavGraph,
     radius: f32,
-) -> Vec<Entity> {
+) -> Vec<(Entity, Option<Entity>)> {
+    let waypoints = &graph.nodes;
     let mut frontier = BinaryHeap::new();
-    let mut came_from: HashMap<Entity, Entity> = HashMap::new();
+    let mut came_from: HashMap<Entity, (Entity, Option<Entity>)> = HashMap::new();
     let mut cost_so_far: HashMap<Entity, u32> = HashMap::new();
 
     frontier.push(QueueNode { cost: 0, node: start });
@@ -202,12 +305,31 @@ fn compute_path(
             if current_pos.distance(*neighbor_pos) > radius {
                 continue;
             }
+            // Links through obstacles are cut, and so are waypoints inside them
+            if graph.is_point_blocked(*neighbor_pos) || graph.is_segment_blocked(current_pos, *neighbor_pos) {
+                continue;
+            }
             let new_cost = cost_so_far.get(&node).copied().unwrap_or(0) + 1;
             if cost_so_far.get(neighbor).map_or(true, |&c| new_cost < c) {
                 cost_so_far.insert(*neighbor, new_cost);
                 let priority = new_cost + heuristic(*neighbor_pos, goal, waypoints);
                 frontier.push(QueueNode { cost: priority, node: *neighbor });
-                came_from.insert(*neighbor, node);
+                came_from.insert(*neighbor, (node, None));
+            }
+        }
+
+        // Links ignore the connection radius and obstacles between their ends
+        for (link, neighbor) in graph.links_from(node) {
+            let Some((_, neighbor_pos)) = waypoints.iter().find(|(e, _)| *e == neighbor) else { continue };
+            if graph.is_point_blocked(*neighbor_pos) {
+                continue;
+            }
+            let new_cost = cost_so_far.get(&node).copied().unwrap_or(0) + 1;
+            if cost_so_far.get(&neighbor).map_or(true, |&c| new_cost < c) {
+                cost_so_far.insert(neighbor, new_cost);
+                let priority = new_cost + heuristic(*neighbor_pos, goal, waypoints);
+                frontier.push(QueueNode { cost: priority, node: neighbor });
+                came_from.insert(neighbor, (node, Some(link)));
             }
         }
     }
@@ -217,15 +339,16 @@ fn compute_path(
     }
 
     let mut current = goal;
-    let mut path = vec![current];
+    let mut path = Vec::new();
     while current != start {
-        if let Some(prev) = came_from.get(&current).copied() {
+        if let Some((prev, link)) = came_from.get(&current).copied() {
+            path.push((current, link));
             current = prev;
-            path.push(current);
         } else {
             break;
         }
     }
+    path.push((start, None));
     path.reverse();
     path
 }
@@ -238,3 +361,43 @@ fn heuristic(pos: Vec3, goal: Entity, waypoints: &[(Entity, Vec3)]) -> u32 {
         .unwrap_or(pos);
     pos.distance(goal_pos) as u32
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_routes_through_nav_link() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<AiNavGraph>()
+            .add_systems(Update, (rebuild_nav_graph, update_ai_navigation).chain());
+
+        // The roof is out of the connection radius, so only the ladder link reaches it
+        let waypoint = |position: Vec3| (GlobalTransform::from_translation(position), AiNavWaypoint::default());
+        let ground = app.world_mut().spawn(waypoint(Vec3::ZERO)).id();
+        let roof = app.world_mut().spawn(waypoint(Vec3::new(0.0, 20.0, 0.0))).id();
+        let ladder = app.world_mut().spawn(AiNavLink::new(ground, roof)).id();
+
+        let destination = Vec3::new(1.0, 20.0, 0.0);
+        let npc = app.world_mut().spawn((
+            GlobalTransform::from_translation(Vec3::new(1.0, 0.0, 0.0)),
+            AiMovement { destination: Some(destination), ..default() },
+            AiNavigationSettings { use_pathfinding: true, ..default() },
+        )).id();
+        app.update();
+
+        let path = app.world().get::<AiPath>(npc).unwrap();
+        assert_eq!(path.points, vec![Vec3::ZERO, Vec3::new(0.0, 20.0, 0.0), destination]);
+        assert_eq!(path.link_to(1), Some(ladder));
+        assert_eq!(path.current_link(), None);
+
+        // Without the link there is no way up
+        app.world_mut().get_mut::<AiNavLink>(ladder).unwrap().enabled = false;
+        app.world_mut().resource_mut::<AiNavGraph>().last_build_time = -999.0;
+        app.update();
+        let graph = app.world().resource::<AiNavGraph>();
+        assert!(graph.links.is_empty());
+        assert!(find_nav_path(graph, Vec3::ZERO, destination, 10.0).is_empty());
+    }
+}
@@ -0,0 +1,247 @@
+//! Dynamic obstacles for AI pathfinding
+//!
+//! A [`NavObstacle`] blocks the waypoint graph where it stands: waypoints
+//! inside it can't be used and links that pass through it are cut. When an
+//! obstacle appears, moves or goes away, the agents whose path it touches
+//! re-path on the next navigation update instead of waiting for their repath
+//! interval.
+//!
+//! Closed doors with a `NavObstacle` block, open ones don't. Grabbable
+//! obstacles stop blocking while they are carried and block again where they
+//! are dropped. Walls and doors placed by the building system get one of their
+//! own size.
+//!
+//! ```rust,ignore
+//! commands.spawn((barricade_mesh, Transform::from_xyz(4.0, 0.5, 0.0), NavObstacle::cuboid(Vec3::new(2.0, 0.5, 0.3))));
+//! ```
+
+use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+use super::navigation::{AiNavGraph, AiNavigationSettings, AiPath};
+use crate::devices::{DoorCurrentState, DoorSystem};
+use crate::grab::{GrabPowerer, Grabbable, Grabber};
+
+/// Shape of a navigation obstacle, in the entity's space
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum NavObstacleShape {
+    Sphere { radius: f32 },
+    Box { half_extents: Vec3 },
+}
+
+/// Blocks AI pathfinding where the entity stands
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct NavObstacle {
+    pub shape: NavObstacleShape,
+    /// Extra clearance around the shape
+    pub padding: f32,
+    pub enabled: bool,
+    /// Set while the object is held by a grabber
+    pub carried: bool,
+}
+
+impl NavObstacle {
+    pub fn sphere(radius: f32) -> Self {
+        Self { shape: NavObstacleShape::Sphere { radius }, padding: 0.25, enabled: true, carried: false }
+    }
+
+    pub fn cuboid(half_extents: Vec3) -> Self {
+        Self { shape: NavObstacleShape::Box { half_extents }, padding: 0.25, enabled: true, carried: false }
+    }
+
+    pub fn with_padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn is_blocking(&self) -> bool {
+        self.enabled && !self.carried
+    }
+
+    /// Space blocked with the entity at `transform`
+    pub fn volume(&self, transform: &GlobalTransform) -> NavObstacleVolume {
+        let (scale, rotation, center) = transform.to_scale_rotation_translation();
+        let shape = match self.shape {
+            NavObstacleShape::Sphere { radius } => NavObstacleShape::Sphere { radius: radius * scale.max_element() + self.padding },
+            NavObstacleShape::Box { half_extents } => NavObstacleShape::Box { half_extents: half_extents * scale + Vec3::splat(self.padding) },
+        };
+        NavObstacleVolume { center, rotation, shape }
+    }
+}
+
+/// World-space volume blocked by an obstacle, padding included
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct NavObstacleVolume {
+    pub center: Vec3,
+    pub rotation: Quat,
+    pub shape: NavObstacleShape,
+}
+
+impl NavObstacleVolume {
+    pub fn contains(&self, point: Vec3) -> bool {
+        let local = self.rotation.inverse() * (point - self.center);
+        match self.shape {
+            NavObstacleShape::Sphere { radius } => local.length_squared() <= radius * radius,
+            NavObstacleShape::Box { half_extents } => local.abs().cmple(half_extents).all(),
+        }
+    }
+
+    /// Whether the segment from `a` to `b` passes through the volume
+    pub fn intersects_segment(&self, a: Vec3, b: Vec3) -> bool {
+        let inverse = self.rotation.inverse();
+        let start = inverse * (a - self.center);
+        let delta = inverse * (b - a);
+        match self.shape {
+            NavObstacleShape::Sphere { radius } => {
+                let length_squared = delta.length_squared();
+                let t = if length_squared > 0.0 { (-start.dot(delta) / length_squared).clamp(0.0, 1.0) } else { 0.0 };
+                (start + delta * t).length_squared() <= radius * radius
+            }
+            NavObstacleShape::Box { half_extents } => {
+                // Slab test, clipping the segment against each axis in turn
+                let (mut t_min, mut t_max) = (0.0f32, 1.0f32);
+                for axis in 0..3 {
+                    let (origin, direction, extent) = (start[axis], delta[axis], half_extents[axis]);
+                    if direction.abs() < 1e-6 {
+                        if origin.abs() > extent {
+                            return false;
+                        }
+                        continue;
+                    }
+                    let t1 = (-extent - origin) / direction;
+                    let t2 = (extent - origin) / direction;
+                    t_min = t_min.max(t1.min(t2));
+                    t_max = t_max.min(t1.max(t2));
+                    if t_min > t_max {
+                        return false;
+                    }
+                }
+                true
+            }
+        }
+    }
+}
+
+/// System to block doors while they are closed
+pub fn update_door_obstacles(mut door_query: Query<(&DoorSystem, &mut NavObstacle), Changed<DoorSystem>>) {
+    for (door, mut obstacle) in door_query.iter_mut() {
+        let closed = door.door_state == DoorCurrentState::Closed;
+        if obstacle.enabled != closed {
+            obstacle.enabled = closed;
+        }
+    }
+}
+
+/// System to lift obstacles while they are carried
+pub fn update_carried_obstacles(
+    grabber_query: Query<&Grabber>,
+    powerer_query: Query<&GrabPowerer>,
+    mut obstacle_query: Query<(Entity, &mut NavObstacle), With<Grabbable>>,
+) {
+    let held: HashSet<Entity> = grabber_query
+        .iter()
+        .filter_map(|grabber| grabber.held_object)
+        .chain(powerer_query.iter().flat_map(|powerer| powerer.held_objects.iter().copied()))
+        .collect();
+    for (entity, mut obstacle) in obstacle_query.iter_mut() {
+        let carried = held.contains(&entity);
+        if obstacle.carried != carried {
+            obstacle.carried = carried;
+        }
+    }
+}
+
+type MovedNavObstacle = (With<NavObstacle>, Or<(Changed<NavObstacle>, Changed<GlobalTransform>)>);
+
+/// System to carve obstacles into the navigation graph and re-path the
+/// agents they affect
+pub fn sync_nav_obstacles(
+    mut graph: ResMut<AiNavGraph>,
+    obstacle_query: Query<(Entity, &NavObstacle, &GlobalTransform)>,
+    changed_query: Query<(), MovedNavObstacle>,
+    mut removed: RemovedComponents<NavObstacle>,
+    mut agent_query: Query<(&GlobalTransform, &AiPath, &mut AiNavigationSettings)>,
+) {
+    let removed: Vec<Entity> = removed.read().collect();
+    if removed.is_empty() && changed_query.is_empty() {
+        return;
+    }
+
+    let previous: HashMap<Entity, NavObstacleVolume> = graph.obstacles.iter().copied().collect();
+    let current: Vec<(Entity, NavObstacleVolume)> = obstacle_query
+        .iter()
+        .filter(|(_, obstacle, _)| obstacle.is_blocking())
+        .map(|(entity, obstacle, transform)| (entity, obstacle.volume(transform)))
+        .collect();
+
+    // New blockers re-path the agents walking through them; anything that
+    // opens up may offer a shorter way, so it re-paths everyone
+    let mut blocked = Vec::new();
+    let mut opened = removed.iter().any(|entity| previous.contains_key(entity));
+    for (entity, volume) in current.iter() {
+        match previous.get(entity) {
+            Some(old) if old == volume => {}
+            Some(_) => {
+                blocked.push(*volume);
+                opened = true;
+            }
+            None => blocked.push(*volume),
+        }
+    }
+    opened |= previous.keys().any(|entity| !current.iter().any(|(e, _)| e == entity));
+
+    if blocked.is_empty() && !opened {
+        return;
+    }
+    graph.obstacles = current;
+    graph.obstacle_version += 1;
+
+    for (transform, path, mut nav_settings) in agent_query.iter_mut() {
+        if !nav_settings.use_pathfinding || path.current_index >= path.points.len() {
+            continue;
+        }
+        let affected = opened || {
+            let legs: Vec<Vec3> = std::iter::once(transform.translation())
+                .chain(path.points[path.current_index..].iter().copied())
+                .collect();
+            legs.windows(2).any(|leg| blocked.iter().any(|volume| volume.intersects_segment(leg[0], leg[1])))
+        };
+        if affected {
+            nav_settings.last_repath_time = f32::NEG_INFINITY;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_nav_obstacles_cut_paths() {
+        use crate::ai::{find_nav_path, AiNavGraph, NavObstacle};
+        let wall = NavObstacle::cuboid(Vec3::new(0.5, 1.0, 1.0)).volume(&GlobalTransform::from_translation(Vec3::new(5.0, 0.0, 0.0)));
+        assert!(wall.contains(Vec3::new(5.0, 0.0, 1.2)));
+        assert!(wall.intersects_segment(Vec3::ZERO, Vec3::new(10.0, 0.0, 0.0)));
+        assert!(!wall.intersects_segment(Vec3::new(0.0, 0.0, 2.0), Vec3::new(10.0, 0.0, 2.0)));
+        let ball = NavObstacle::sphere(1.0).with_padding(0.0).volume(&GlobalTransform::IDENTITY);
+        assert!(ball.intersects_segment(Vec3::new(-3.0, 0.5, 0.0), Vec3::new(3.0, 0.5, 0.0)));
+        assert!(!ball.intersects_segment(Vec3::new(2.0, 0.0, 0.0), Vec3::new(3.0, 0.0, 0.0)));
+
+        let mut world = World::new();
+        let [a, b, c, d] = [(); 4].map(|_| world.spawn_empty().id());
+        let mut graph = AiNavGraph {
+            nodes: vec![(a, Vec3::ZERO), (b, Vec3::new(5.0, 0.0, 0.0)), (c, Vec3::new(10.0, 0.0, 0.0)), (d, Vec3::new(5.0, 0.0, 4.0))],
+            ..default()
+        };
+        let to = Vec3::new(10.0, 0.0, 0.0);
+        assert_eq!(find_nav_path(&graph, Vec3::ZERO, to, 6.5)[1], Vec3::new(5.0, 0.0, 0.0));
+
+        // The wall over the middle waypoint sends the path around it
+        graph.obstacles.push((world.spawn_empty().id(), wall));
+        assert!(graph.is_segment_blocked(Vec3::ZERO, Vec3::new(5.0, 0.0, 0.0)));
+        assert_eq!(find_nav_path(&graph, Vec3::ZERO, to, 6.5), vec![Vec3::ZERO, Vec3::new(5.0, 0.0, 4.0), to, to]);
+        assert!(find_nav_path(&graph, Vec3::ZERO, to, 5.0).is_empty());
+    }
+}
@@ -1,14 +1,17 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
 use crate::ai::types::*;
+use crate::combat::{Submerged, UnderwaterCombatRules};
+use crate::utils::QueueReader;
 
 pub fn update_ai_perception(
-    mut ai_query: Query<(Entity, &GlobalTransform, &mut AiController, &AiPerception, Option<&CharacterFaction>, &AIPerceptionSettings)>,
-    target_query: Query<(Entity, &GlobalTransform, Option<&CharacterFaction>)>,
+    mut ai_query: Query<(Entity, &GlobalTransform, &mut AiController, &AiPerception, Option<&CharacterFaction>, &AIPerceptionSettings, Has<Submerged>)>,
+    target_query: Query<(Entity, &GlobalTransform, Option<&CharacterFaction>, Has<Submerged>)>,
     faction_system: Res<FactionSystem>,
+    underwater_rules: Res<UnderwaterCombatRules>,
     spatial_query: SpatialQuery,
 ) {
-    for (entity, transform, mut ai, _perception, ai_faction, settings) in ai_query.iter_mut() {
+    for (entity, transform, mut ai, _perception, ai_faction, settings, ai_submerged) in ai_query.iter_mut() {
         if ai.is_paused { continue; }
         if ai.state == AiBehaviorState::Flee || ai.state == AiBehaviorState::Dead { continue; }
 
@@ -18,7 +21,7 @@ pub fn update_ai_perception(
         let forward = transform.forward();
         let ai_faction_name = ai_faction.map(|f| f.name.as_str()).unwrap_or("Default");
 
-        for (target_entity, target_transform, target_faction) in target_query.iter() {
+        for (target_entity, target_transform, target_faction, target_submerged) in target_query.iter() {
             if target_entity == entity { continue; }
 
             let target_faction_name = target_faction.map(|f| f.name.as_str()).unwrap_or("Default");
@@ -29,6 +32,7 @@ pub fn update_ai_perception(
             let to_target = target_transform.translation() - current_pos;
             let dist = to_target.length();
             if dist > settings.range { continue; }
+            if !underwater_rules.can_detect(ai_submerged, target_submerged, dist) { continue; }
 
             let dir_to_target = to_target.normalize();
             if forward.angle_between(dir_to_target).to_degrees() > settings.fov / 2.0 {
@@ -71,7 +75,7 @@ pub fn update_ai_perception(
 }
 
 pub fn update_ai_hearing(
-    mut queue: ResMut<NoiseEventQueue>,
+    mut queue: QueueReader<NoiseEvent>,
     mut ai_query: Query<(
         &GlobalTransform,
         &mut AiController,
@@ -79,7 +83,7 @@ pub fn update_ai_hearing(
         Option<&AiHearingSettings>,
     )>,
 ) {
-    for event in queue.0.iter() {
+    for event in queue.read() {
         for (transform, mut ai, settings, hearing) in ai_query.iter_mut() {
             if ai.is_paused { continue; }
             if let Some(hearing) = hearing {
@@ -107,7 +111,6 @@ pub fn update_ai_hearing(
             }
         }
     }
-    queue.0.clear();
 }
 
 pub fn draw_ai_vision_cones(
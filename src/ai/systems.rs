@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use super::types::*;
+use crate::input::{ConsoleArgs, ConsoleResult, InputState};
 
 pub fn update_ai_state_visuals(
     mut gizmos: Gizmos,
@@ -23,3 +24,21 @@ pub fn update_ai_state_visuals(
         }
     }
 }
+
+/// Console command `toggle_ai [on|off]`: stops or restarts every AI, releasing the inputs they held
+pub fn toggle_ai_command(
+    In(args): In<ConsoleArgs>,
+    mut settings: ResMut<AiSettings>,
+    mut input_query: Query<&mut InputState, With<AiController>>,
+) -> ConsoleResult {
+    settings.enabled = match args.first().map(String::as_str) {
+        None => !settings.enabled,
+        Some("on") => true,
+        Some("off") => false,
+        Some(other) => return Err(format!("Expected on or off, got '{}'", other)),
+    };
+    for mut input in input_query.iter_mut() {
+        input.set_input_enabled(settings.enabled);
+    }
+    Ok(format!("AI {}", if settings.enabled { "on" } else { "off" }))
+}
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use crate::ai::types::*;
 use crate::combat::{Health, DamageEventQueue, DamageEvent, DamageType};
 use avian3d::prelude::*;
+use crate::weapons::{solve_lead, BallisticArc, BallisticShot, BallisticsEnvironment};
 
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
@@ -11,6 +12,9 @@ pub struct Turret {
     pub target: Option<Entity>,
     pub base_entity: Option<Entity>,
     pub cannon_entity: Option<Entity>,
+    /// Projectile speed used to lead moving targets; 0 aims straight at them
+    pub lead_speed: f32,
+    pub lead_arc: BallisticArc,
 }
 
 #[derive(Component, Debug, Reflect)]
@@ -45,6 +49,8 @@ pub struct TurretLaser {
     pub max_distance: f32,
     pub color: Color,
     pub require_line_of_sight: bool,
+    /// Point the beam is hitting, if it fires this frame (read-only)
+    pub beam_end: Option<Vec3>,
 }
 
 impl Default for TurretLaser {
@@ -55,6 +61,7 @@ impl Default for TurretLaser {
             max_distance: 25.0,
             color: Color::srgb(1.0, 0.1, 0.1),
             require_line_of_sight: true,
+            beam_end: None,
         }
     }
 }
@@ -67,14 +74,17 @@ impl Default for Turret {
             target: None,
             base_entity: None,
             cannon_entity: None,
+            lead_speed: 0.0,
+            lead_arc: BallisticArc::Low,
         }
     }
 }
 
 pub fn update_turrets(
     time: Res<Time>,
+    environment: Option<Res<BallisticsEnvironment>>,
     mut query: Query<(&mut Turret, &GlobalTransform, Option<&AiPerception>)>,
-    target_query: Query<(Entity, &GlobalTransform, &Health)>,
+    target_query: Query<(Entity, &GlobalTransform, &Health, Option<&LinearVelocity>)>,
     mut transform_query: Query<&mut Transform>,
 ) {
     let delta = time.delta_secs();
@@ -83,7 +93,7 @@ pub fn update_turrets(
         let my_pos = turret_transform.translation();
 
         if let Some(target_entity) = turret.target {
-            if let Ok((_entity, target_transform, health)) = target_query.get(target_entity) {
+            if let Ok((_entity, target_transform, health, _)) = target_query.get(target_entity) {
                 if health.current <= 0.0 {
                     turret.target = None;
                 } else if my_pos.distance(target_transform.translation()) > turret.max_range {
@@ -97,7 +107,7 @@ pub fn update_turrets(
             let mut best_dist = turret.max_range;
 
             for &candidate in &perception.visible_targets {
-                if let Ok((entity, target_gt, health)) = target_query.get(candidate) {
+                if let Ok((entity, target_gt, health, _)) = target_query.get(candidate) {
                     if health.current <= 0.0 {
                         continue;
                     }
@@ -113,8 +123,20 @@ pub fn update_turrets(
         }
 
         if let Some(target_entity) = turret.target {
-            if let Ok((_entity, target_transform, _health)) = target_query.get(target_entity) {
-                let target_pos = target_transform.translation();
+            if let Ok((_entity, target_transform, _health, target_velocity)) = target_query.get(target_entity) {
+                let mut target_pos = target_transform.translation();
+
+                // Aim where the target will be when the shot arrives
+                if turret.lead_speed > 0.0 {
+                    let mut shot = BallisticShot::new(my_pos, turret.lead_speed);
+                    if let Some(environment) = environment.as_deref() {
+                        shot = shot.with_gravity(environment.gravity);
+                    }
+                    let velocity = target_velocity.map(|velocity| velocity.0).unwrap_or(Vec3::ZERO);
+                    if let Some(solution) = solve_lead(&shot, target_pos, velocity, turret.lead_arc) {
+                        target_pos = solution.aim_point;
+                    }
+                }
 
                 // Base rotates only on Y
                 if let Some(base_entity) = turret.base_entity {
@@ -183,7 +205,7 @@ pub fn update_turret_firing(
         }
 
         if can_fire {
-            damage_queue.0.push(DamageEvent {
+            damage_queue.send(DamageEvent {
                 amount: combat.damage,
                 damage_type: combat.damage_type,
                 source: Some(entity),
@@ -201,13 +223,13 @@ pub fn update_turret_lasers(
     time: Res<Time>,
     spatial_query: SpatialQuery,
     mut damage_queue: ResMut<DamageEventQueue>,
-    turret_query: Query<(Entity, &Turret, &GlobalTransform, &TurretLaser)>,
+    mut turret_query: Query<(Entity, &Turret, &GlobalTransform, &mut TurretLaser)>,
     target_query: Query<&GlobalTransform>,
-    mut gizmos: Gizmos,
 ) {
     let dt = time.delta_secs();
 
-    for (entity, turret, turret_transform, laser) in turret_query.iter() {
+    for (entity, turret, turret_transform, mut laser) in turret_query.iter_mut() {
+        laser.beam_end = None;
         if !laser.enabled {
             continue;
         }
@@ -235,8 +257,8 @@ pub fn update_turret_lasers(
         }
 
         if can_fire {
-            gizmos.line(origin, target_pos, laser.color);
-            damage_queue.0.push(DamageEvent {
+            laser.beam_end = Some(target_pos);
+            damage_queue.send(DamageEvent {
                 amount: laser.damage_per_second * dt,
                 damage_type: DamageType::Electric,
                 source: Some(entity),
@@ -248,3 +270,14 @@ pub fn update_turret_lasers(
         }
     }
 }
+
+pub fn draw_turret_lasers(
+    mut gizmos: Gizmos,
+    turret_query: Query<(&GlobalTransform, &TurretLaser)>,
+) {
+    for (turret_transform, laser) in turret_query.iter() {
+        if let Some(beam_end) = laser.beam_end {
+            gizmos.line(turret_transform.translation(), beam_end, laser.color);
+        }
+    }
+}
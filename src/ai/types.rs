@@ -1,4 +1,38 @@
 use bevy::prelude::*;
+use crate::utils::EventQueue;
+
+/// AI phases, run in order inside `GameControllerSet::Ai`
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AiSet {
+    /// Sight, hearing, alerts and faction relations
+    Perception,
+    /// Behavior state and suspicion updates
+    Decision,
+    /// Path finding, steering and patrols
+    Navigation,
+    /// Turrets, attacks and hiding
+    Combat,
+    /// Debug cones and state indicators
+    Visuals,
+}
+
+/// Global AI switch; the `toggle_ai` console command flips it
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct AiSettings {
+    pub enabled: bool,
+}
+
+impl Default for AiSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// Run condition: AI systems are enabled
+pub fn ai_enabled(settings: Option<Res<AiSettings>>) -> bool {
+    settings.is_none_or(|settings| settings.enabled)
+}
 
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
@@ -196,6 +230,8 @@ pub struct AiRangedCombatSettings {
     pub accuracy: f32,
     pub aim_time: f32,
     pub aim_timer: f32,
+    /// Lead moving targets with projectile weapons (see `LeadAim`)
+    pub lead_targets: bool,
 }
 
 impl Default for AiRangedCombatSettings {
@@ -213,6 +249,7 @@ impl Default for AiRangedCombatSettings {
             accuracy: 0.85,
             aim_time: 0.15,
             aim_timer: 0.0,
+            lead_targets: true,
         }
     }
 }
@@ -441,5 +478,4 @@ pub struct NoiseEvent {
     pub source: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct NoiseEventQueue(pub Vec<NoiseEvent>);
+pub type NoiseEventQueue = EventQueue<NoiseEvent>;
@@ -0,0 +1,74 @@
+//! Character appearance and creation
+//!
+//! A [`CharacterAppearance`] holds palette choices for color slots (skin, hair,
+//! outfit), the selected option of each swappable body part and blend-shape
+//! weights, all defined in the [`AppearanceCatalog`]. Models opt in by tagging
+//! entities with [`AppearanceMaterialSlot`] and [`AppearancePartAnchor`]; morph
+//! targets are matched to blend shapes by name. The appearance is re-applied when
+//! it changes, when the model's scenes finish spawning and when a character
+//! becomes the player, and it is stored with the player in saves.
+//!
+//! The character creator is driven through the [`AppearanceCommandQueue`]:
+//!
+//! ```rust,ignore
+//! // Before spawning: the confirmed appearance goes to the next player spawned
+//! appearance_commands.send(AppearanceCommand::OpenCreator { target: None });
+//! // Or edit an existing character with a live preview
+//! appearance_commands.send(AppearanceCommand::OpenCreator { target: Some(player) });
+//! ```
+
+pub mod types;
+pub mod systems;
+pub mod ui;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use ui::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    ColorSlotDefinition, BodyPartOption, BodyPartDefinition, BlendShapeDefinition, AppearanceCatalog,
+    CharacterAppearance, AppearanceMaterialSlot, AppearancePartAnchor, AppearancePartInstance,
+    AppearanceMaterial, AppearanceDirty, AppearanceSettings, CharacterCreatorState, PendingAppearance,
+    AppearanceCommand, AppearanceCommandQueue, AppearanceEvent, AppearanceEventQueue,
+};
+
+pub struct AppearancePlugin;
+
+impl Plugin for AppearancePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<AppearanceCatalog>()
+            .init_resource::<AppearanceSettings>()
+            .init_resource::<CharacterCreatorState>()
+            .init_resource::<PendingAppearance>()
+            .add_plugins((
+                EventQueuePlugin::<AppearanceCommand>::default(),
+                EventQueuePlugin::<AppearanceEvent>::default(),
+            ))
+            .register_type::<AppearanceCatalog>()
+            .register_type::<AppearanceSettings>()
+            .register_type::<CharacterAppearance>()
+            .register_type::<AppearanceMaterialSlot>()
+            .register_type::<AppearancePartAnchor>()
+            .register_type::<AppearancePartInstance>()
+            .add_systems(Startup, (
+                setup_character_creator_ui,
+                open_creator_on_startup,
+            ))
+            .add_systems(Update, (
+                handle_character_creator_buttons,
+                process_appearance_commands,
+                attach_player_appearance,
+                restore_appearance_from_save,
+                store_appearance_in_save,
+                update_character_creator_ui,
+            ).chain())
+            // After this frame's spawns and highlight changes
+            .add_systems(PostUpdate, (
+                mark_appearance_dirty,
+                apply_appearance,
+            ).chain().after(crate::highlight::systems::apply_highlights));
+    }
+}
@@ -0,0 +1,475 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::mesh::morph::MorphWeights;
+use super::types::*;
+use crate::character::Player;
+use crate::game_manager::types::CursorState;
+use crate::highlight::HighlightMaterial;
+use crate::save::SaveCustomData;
+use crate::utils::QueueReader;
+
+/// System to open the creator before the player is spawned
+pub fn open_creator_on_startup(
+    settings: Res<AppearanceSettings>,
+    mut appearance_commands: ResMut<AppearanceCommandQueue>,
+) {
+    if settings.open_creator_on_startup {
+        appearance_commands.send(AppearanceCommand::OpenCreator { target: None });
+    }
+}
+
+/// System to give new players the confirmed (or default) appearance
+pub fn attach_player_appearance(
+    mut commands: Commands,
+    settings: Res<AppearanceSettings>,
+    catalog: Res<AppearanceCatalog>,
+    mut pending: ResMut<PendingAppearance>,
+    player_query: Query<(Entity, Has<CharacterAppearance>), Added<Player>>,
+) {
+    for (entity, has_appearance) in player_query.iter() {
+        if let Some(appearance) = pending.0.take() {
+            commands.entity(entity).insert(appearance);
+        } else if !has_appearance && settings.auto_attach_player {
+            commands.entity(entity).insert(catalog.default_appearance());
+        }
+    }
+}
+
+/// Creator state edited by appearance commands
+#[derive(SystemParam)]
+pub struct CreatorSession<'w> {
+    pub creator: ResMut<'w, CharacterCreatorState>,
+    pub pending: ResMut<'w, PendingAppearance>,
+    pub cursor: ResMut<'w, CursorState>,
+}
+
+pub fn process_appearance_commands(
+    mut commands: Commands,
+    mut appearance_commands: QueueReader<AppearanceCommand>,
+    mut events: ResMut<AppearanceEventQueue>,
+    catalog: Res<AppearanceCatalog>,
+    session: CreatorSession,
+    mut appearance_query: Query<&mut CharacterAppearance>,
+) {
+    let CreatorSession { mut creator, mut pending, mut cursor } = session;
+    for command in appearance_commands.read() {
+        match command {
+            AppearanceCommand::OpenCreator { target } => {
+                creator.original = target.and_then(|target| appearance_query.get(target).ok().cloned());
+                creator.draft = creator
+                    .original
+                    .clone()
+                    .or_else(|| pending.0.clone())
+                    .unwrap_or_else(|| catalog.default_appearance());
+                creator.target = *target;
+                creator.open = true;
+                cursor.visible_override = Some(true);
+                events.send(AppearanceEvent::CreatorOpened { target: *target });
+                continue;
+            }
+            AppearanceCommand::Apply { entity, appearance } => {
+                set_appearance(&mut commands, &mut appearance_query, *entity, appearance);
+                continue;
+            }
+            _ => {}
+        }
+
+        if !creator.open {
+            continue;
+        }
+
+        match command {
+            AppearanceCommand::SetColor { slot, index } => {
+                creator.draft.colors.insert(slot.clone(), *index);
+            }
+            AppearanceCommand::CycleColor { slot, step } => {
+                let Some(definition) = catalog.color_slot(slot) else { continue };
+                if definition.palette.is_empty() {
+                    continue;
+                }
+                let current = creator.draft.color_index(&catalog, slot).unwrap_or(0);
+                let next = (current as i32 + step).rem_euclid(definition.palette.len() as i32) as usize;
+                creator.draft.colors.insert(slot.clone(), next);
+            }
+            AppearanceCommand::SetPart { part, option } => {
+                creator.draft.parts.insert(part.clone(), option.clone());
+            }
+            AppearanceCommand::CyclePart { part, step } => {
+                let Some(definition) = catalog.body_part(part) else { continue };
+                if definition.options.is_empty() {
+                    continue;
+                }
+                let next = match creator.draft.part_index(&catalog, part) {
+                    Some(current) => (current as i32 + step).rem_euclid(definition.options.len() as i32) as usize,
+                    None => 0,
+                };
+                creator.draft.parts.insert(part.clone(), definition.options[next].id.clone());
+            }
+            AppearanceCommand::SetBlendShape { id, value } => {
+                let Some(definition) = catalog.blend_shape(id) else { continue };
+                let value = value.clamp(definition.min.min(definition.max), definition.max.max(definition.min));
+                creator.draft.blend_shapes.insert(id.clone(), value);
+            }
+            AppearanceCommand::AdjustBlendShape { id, steps } => {
+                let Some(definition) = catalog.blend_shape(id) else { continue };
+                let current = creator.draft.blend_shape(&catalog, id).unwrap_or(definition.default);
+                let value = (current + definition.step * steps)
+                    .clamp(definition.min.min(definition.max), definition.max.max(definition.min));
+                creator.draft.blend_shapes.insert(id.clone(), value);
+            }
+            AppearanceCommand::Randomize => {
+                creator.draft = catalog.random_appearance();
+            }
+            AppearanceCommand::Reset => {
+                creator.draft = catalog.default_appearance();
+            }
+            AppearanceCommand::Confirm => {
+                let appearance = creator.draft.clone();
+                match creator.target {
+                    Some(target) => set_appearance(&mut commands, &mut appearance_query, target, &appearance),
+                    None => pending.0 = Some(appearance.clone()),
+                }
+                events.send(AppearanceEvent::CreationConfirmed { target: creator.target, appearance });
+                close_creator(&mut creator, &mut cursor);
+                continue;
+            }
+            AppearanceCommand::Cancel => {
+                if let Some(target) = creator.target {
+                    match creator.original.clone() {
+                        Some(original) => set_appearance(&mut commands, &mut appearance_query, target, &original),
+                        None => {
+                            commands.entity(target).remove::<CharacterAppearance>();
+                        }
+                    }
+                }
+                events.send(AppearanceEvent::CreationCancelled { target: creator.target });
+                close_creator(&mut creator, &mut cursor);
+                continue;
+            }
+            AppearanceCommand::OpenCreator { .. } | AppearanceCommand::Apply { .. } => continue,
+        }
+
+        // Live preview on the target
+        if let Some(target) = creator.target {
+            let draft = creator.draft.clone();
+            set_appearance(&mut commands, &mut appearance_query, target, &draft);
+        }
+    }
+}
+
+fn set_appearance(
+    commands: &mut Commands,
+    appearance_query: &mut Query<&mut CharacterAppearance>,
+    entity: Entity,
+    appearance: &CharacterAppearance,
+) {
+    match appearance_query.get_mut(entity) {
+        Ok(mut current) => {
+            current.set_if_neq(appearance.clone());
+        }
+        Err(_) => {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.insert(appearance.clone());
+            }
+        }
+    }
+}
+
+fn close_creator(creator: &mut CharacterCreatorState, cursor: &mut CursorState) {
+    creator.open = false;
+    creator.target = None;
+    creator.original = None;
+    cursor.visible_override = None;
+}
+
+/// System to restore the appearance stored in a loaded save
+pub fn restore_appearance_from_save(
+    mut commands: Commands,
+    settings: Res<AppearanceSettings>,
+    mut query: Query<(Entity, &SaveCustomData, Option<&mut CharacterAppearance>), Changed<SaveCustomData>>,
+) {
+    for (entity, custom_data, current) in query.iter_mut() {
+        let Some(value) = custom_data.0.get(&settings.save_key) else { continue };
+        let saved: CharacterAppearance = match serde_json::from_value(value.clone()) {
+            Ok(saved) => saved,
+            Err(err) => {
+                warn!("Invalid saved appearance on {:?}: {}", entity, err);
+                continue;
+            }
+        };
+
+        match current {
+            Some(mut current) => {
+                current.set_if_neq(saved);
+            }
+            None => {
+                commands.entity(entity).insert(saved);
+            }
+        }
+    }
+}
+
+type ChangedPlayerAppearance = (With<Player>, Or<(Changed<CharacterAppearance>, Changed<SaveCustomData>)>);
+
+/// System to keep the player's appearance in the data written to saves
+pub fn store_appearance_in_save(
+    mut commands: Commands,
+    settings: Res<AppearanceSettings>,
+    mut query: Query<(Entity, &CharacterAppearance, Option<&mut SaveCustomData>), ChangedPlayerAppearance>,
+) {
+    for (entity, appearance, custom_data) in query.iter_mut() {
+        let Ok(value) = serde_json::to_value(appearance) else { continue };
+
+        match custom_data {
+            Some(mut custom_data) => {
+                if custom_data.0.get(&settings.save_key) != Some(&value) {
+                    custom_data.0.insert(settings.save_key.clone(), value);
+                }
+            }
+            None => {
+                let mut custom_data = SaveCustomData::default();
+                custom_data.0.insert(settings.save_key.clone(), value);
+                commands.entity(entity).insert(custom_data);
+            }
+        }
+    }
+}
+
+type ChangedAppearance = (With<CharacterAppearance>, Or<(Changed<CharacterAppearance>, Added<Player>)>);
+
+type NewAppearanceMesh = Or<(Added<MeshMaterial3d<StandardMaterial>>, Added<MorphWeights>)>;
+
+/// System to flag characters whose appearance or model changed
+pub fn mark_appearance_dirty(
+    mut commands: Commands,
+    catalog: Res<AppearanceCatalog>,
+    appearance_query: Query<Entity, With<CharacterAppearance>>,
+    changed_query: Query<Entity, ChangedAppearance>,
+    new_mesh_query: Query<Entity, NewAppearanceMesh>,
+    parent_query: Query<&ChildOf>,
+) {
+    if catalog.is_changed() {
+        for entity in appearance_query.iter() {
+            commands.entity(entity).insert(AppearanceDirty);
+        }
+        return;
+    }
+
+    for entity in changed_query.iter() {
+        commands.entity(entity).insert(AppearanceDirty);
+    }
+
+    // Model scenes and part scenes finish spawning after the appearance was set
+    for mesh in new_mesh_query.iter() {
+        let root = std::iter::once(mesh)
+            .chain(parent_query.iter_ancestors(mesh))
+            .find(|entity| appearance_query.contains(*entity));
+        if let Some(root) = root {
+            commands.entity(root).insert(AppearanceDirty);
+        }
+    }
+}
+
+/// Slots, anchors and meshes found under a character
+#[derive(SystemParam)]
+pub struct AppearanceHierarchy<'w, 's> {
+    pub children_query: Query<'w, 's, &'static Children>,
+    pub slot_query: Query<'w, 's, &'static AppearanceMaterialSlot>,
+    pub anchor_query: Query<'w, 's, &'static AppearancePartAnchor>,
+    pub instance_query: Query<'w, 's, &'static AppearancePartInstance>,
+    pub mesh_query: Query<
+        'w,
+        's,
+        (&'static mut MeshMaterial3d<StandardMaterial>, Option<&'static AppearanceMaterial>, Has<HighlightMaterial>),
+    >,
+    pub morph_query: Query<'w, 's, &'static mut MorphWeights>,
+}
+
+/// Applies colors, body parts and blend shapes to flagged characters.
+/// Characters stay flagged until every mesh and material is loaded.
+pub fn apply_appearance(
+    mut commands: Commands,
+    catalog: Res<AppearanceCatalog>,
+    asset_server: Option<Res<AssetServer>>,
+    dirty_query: Query<(Entity, &CharacterAppearance), With<AppearanceDirty>>,
+    hierarchy: AppearanceHierarchy,
+    meshes: Option<Res<Assets<Mesh>>>,
+    materials: Option<ResMut<Assets<StandardMaterial>>>,
+) {
+    let AppearanceHierarchy { children_query, slot_query, anchor_query, instance_query, mut mesh_query, mut morph_query } =
+        hierarchy;
+    let (Some(meshes), Some(mut materials)) = (meshes, materials) else { return };
+
+    for (root, appearance) in dirty_query.iter() {
+        let mut complete = true;
+        // Parents first, so nested slots override their ancestors
+        let entities: Vec<Entity> = std::iter::once(root).chain(children_query.iter_descendants(root)).collect();
+
+        // Material colors
+        for slot_entity in entities.iter().copied() {
+            let Ok(slot) = slot_query.get(slot_entity) else { continue };
+            let Some(color) = appearance.color(&catalog, &slot.slot) else { continue };
+
+            for mesh in std::iter::once(slot_entity).chain(children_query.iter_descendants(slot_entity)) {
+                let Ok((mut material, owned, highlighted)) = mesh_query.get_mut(mesh) else { continue };
+                if highlighted {
+                    // The highlight holds the original material; retry once it is released
+                    complete = false;
+                    continue;
+                }
+
+                match owned {
+                    Some(owned) if owned.0 == material.0 => {
+                        if let Some(owned) = materials.get_mut(&owned.0) {
+                            owned.base_color = color;
+                        }
+                    }
+                    _ => {
+                        let Some(original) = materials.get(&material.0) else {
+                            complete = false;
+                            continue;
+                        };
+                        let mut copy = original.clone();
+                        copy.base_color = color;
+                        let copy = materials.add(copy);
+                        commands.entity(mesh).insert(AppearanceMaterial(copy.clone()));
+                        material.0 = copy;
+                    }
+                }
+            }
+        }
+
+        // Body parts
+        for anchor_entity in entities.iter().copied() {
+            let Ok(anchor) = anchor_query.get(anchor_entity) else { continue };
+            let wanted = appearance.part(&catalog, &anchor.part);
+
+            let mut present = false;
+            for child in children_query.get(anchor_entity).into_iter().flat_map(|children| children.iter()) {
+                let Ok(instance) = instance_query.get(child) else { continue };
+                if instance.part != anchor.part {
+                    continue;
+                }
+                if wanted.is_some_and(|option| option.id == instance.option) {
+                    present = true;
+                } else {
+                    commands.entity(child).despawn();
+                }
+            }
+
+            let Some(option) = wanted else { continue };
+            if present || option.scene.is_empty() {
+                continue;
+            }
+            let Some(asset_server) = asset_server.as_ref() else { continue };
+
+            let mut part = commands.spawn((
+                SceneRoot(asset_server.load(option.scene.clone())),
+                Transform::default(),
+                AppearancePartInstance {
+                    part: anchor.part.clone(),
+                    option: option.id.clone(),
+                },
+                Name::new(format!("{} ({})", anchor.part, option.label)),
+                ChildOf(anchor_entity),
+            ));
+            if let Some(slot) = catalog.body_part(&anchor.part).and_then(|definition| definition.color_slot.clone()) {
+                part.insert(AppearanceMaterialSlot { slot });
+            }
+        }
+
+        // Blend shapes
+        for entity in entities.iter().copied() {
+            let Ok(mut weights) = morph_query.get_mut(entity) else { continue };
+            let Some(names) = weights
+                .first_mesh()
+                .and_then(|mesh| meshes.get(mesh))
+                .map(|mesh| mesh.morph_target_names().map(|names| names.to_vec()))
+            else {
+                complete = false;
+                continue;
+            };
+
+            for (index, name) in names.unwrap_or_default().iter().enumerate() {
+                let Some(value) = appearance.blend_shape(&catalog, name) else { continue };
+                if let Some(weight) = weights.weights_mut().get_mut(index) {
+                    *weight = value;
+                }
+            }
+        }
+
+        if complete {
+            commands.entity(root).remove::<AppearanceDirty>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::appearance::AppearancePlugin;
+    use crate::highlight::HighlightPlugin;
+
+    fn skin_color(app: &App, mesh: Entity) -> Color {
+        let handle = &app.world().get::<MeshMaterial3d<StandardMaterial>>(mesh).unwrap().0;
+        app.world().resource::<Assets<StandardMaterial>>().get(handle).unwrap().base_color
+    }
+
+    #[test]
+    fn test_creator_appearance_goes_to_player_and_save() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, HighlightPlugin, AppearancePlugin))
+            .init_resource::<CursorState>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<StandardMaterial>>();
+        app.update();
+
+        {
+            let mut appearance_commands = app.world_mut().resource_mut::<AppearanceCommandQueue>();
+            appearance_commands.send(AppearanceCommand::OpenCreator { target: None });
+            appearance_commands.send(AppearanceCommand::SetColor { slot: "skin".to_string(), index: 2 });
+            appearance_commands.send(AppearanceCommand::CycleColor { slot: "hair".to_string(), step: -2 });
+        }
+        app.update();
+        assert!(app.world().resource::<CharacterCreatorState>().open);
+        assert_eq!(app.world().resource::<CursorState>().visible_override, Some(true));
+
+        app.world_mut().resource_mut::<AppearanceCommandQueue>().send(AppearanceCommand::Confirm);
+        app.update();
+        assert!(!app.world().resource::<CharacterCreatorState>().open);
+        assert_eq!(app.world().resource::<CursorState>().visible_override, None);
+        let confirmed = app.world().resource::<PendingAppearance>().0.clone().expect("confirmed appearance should wait for the player");
+        assert_eq!((confirmed.colors["skin"], confirmed.colors["hair"]), (2, 5));
+        assert!(app.world().resource::<AppearanceEventQueue>().iter().any(|event| matches!(
+            event,
+            AppearanceEvent::CreationConfirmed { target: None, .. }
+        )));
+
+        // The next player spawned wears it, and it is written to the save data
+        let original = app.world_mut().resource_mut::<Assets<StandardMaterial>>().add(StandardMaterial::default());
+        let player = app.world_mut().spawn((Player, AppearanceMaterialSlot { slot: "skin".to_string() })).id();
+        let mesh = app.world_mut().spawn((MeshMaterial3d(original.clone()), ChildOf(player))).id();
+        app.update();
+
+        let catalog = AppearanceCatalog::default();
+        let palette = &catalog.color_slot("skin").unwrap().palette;
+        assert_eq!(app.world().get::<CharacterAppearance>(player), Some(&confirmed));
+        assert!(app.world().get::<AppearanceDirty>(player).is_none());
+        assert_ne!(app.world().get::<MeshMaterial3d<StandardMaterial>>(mesh).unwrap().0, original);
+        assert_eq!(skin_color(&app, mesh), palette[2]);
+        let saved = app.world().get::<SaveCustomData>(player).unwrap().0["appearance"].clone();
+        assert_eq!(serde_json::from_value::<CharacterAppearance>(saved).unwrap(), confirmed);
+
+        // Loading a save re-applies the stored appearance on the same material copy
+        let owned = app.world().get::<MeshMaterial3d<StandardMaterial>>(mesh).unwrap().0.clone();
+        let mut loaded = confirmed.clone();
+        loaded.colors.insert("skin".to_string(), 4);
+        app.world_mut().get_mut::<SaveCustomData>(player).unwrap().0
+            .insert("appearance".to_string(), serde_json::to_value(&loaded).unwrap());
+        app.update();
+
+        assert_eq!(app.world().get::<CharacterAppearance>(player), Some(&loaded));
+        assert_eq!(app.world().get::<MeshMaterial3d<StandardMaterial>>(mesh).unwrap().0, owned);
+        assert_eq!(skin_color(&app, mesh), palette[4]);
+    }
+}
@@ -0,0 +1,331 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::utils::EventQueue;
+
+/// Material color the player can customize (skin, hair, outfit...)
+#[derive(Debug, Clone, Reflect)]
+pub struct ColorSlotDefinition {
+    pub id: String,
+    pub label: String,
+    pub palette: Vec<Color>,
+    pub default_index: usize,
+}
+
+/// One choice of a body part
+#[derive(Debug, Clone, Reflect)]
+pub struct BodyPartOption {
+    pub id: String,
+    pub label: String,
+    /// Scene spawned under the part anchor; empty for nothing
+    pub scene: String,
+}
+
+/// Swappable body part (hair style, beard, outfit...)
+#[derive(Debug, Clone, Reflect)]
+pub struct BodyPartDefinition {
+    pub id: String,
+    pub label: String,
+    pub options: Vec<BodyPartOption>,
+    pub default_index: usize,
+    /// Color slot tinting the spawned part (e.g. "hair")
+    pub color_slot: Option<String>,
+}
+
+/// Blend-shape parameter, driving the morph target of the same name
+#[derive(Debug, Clone, Reflect)]
+pub struct BlendShapeDefinition {
+    pub id: String,
+    pub label: String,
+    pub min: f32,
+    pub max: f32,
+    pub default: f32,
+    /// Increment of the creator's -/+ buttons
+    pub step: f32,
+}
+
+/// Everything the character creator offers
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct AppearanceCatalog {
+    pub color_slots: Vec<ColorSlotDefinition>,
+    pub body_parts: Vec<BodyPartDefinition>,
+    pub blend_shapes: Vec<BlendShapeDefinition>,
+}
+
+impl Default for AppearanceCatalog {
+    fn default() -> Self {
+        let outfit_palette = vec![
+            Color::srgb(0.15, 0.2, 0.35),
+            Color::srgb(0.45, 0.1, 0.1),
+            Color::srgb(0.2, 0.35, 0.15),
+            Color::srgb(0.3, 0.3, 0.3),
+            Color::srgb(0.85, 0.8, 0.7),
+            Color::srgb(0.1, 0.1, 0.1),
+        ];
+
+        Self {
+            color_slots: vec![
+                ColorSlotDefinition {
+                    id: "skin".to_string(),
+                    label: "Skin".to_string(),
+                    palette: vec![
+                        Color::srgb(0.96, 0.8, 0.69),
+                        Color::srgb(0.89, 0.68, 0.53),
+                        Color::srgb(0.78, 0.56, 0.4),
+                        Color::srgb(0.6, 0.4, 0.27),
+                        Color::srgb(0.43, 0.28, 0.18),
+                        Color::srgb(0.3, 0.19, 0.12),
+                    ],
+                    default_index: 1,
+                },
+                ColorSlotDefinition {
+                    id: "hair".to_string(),
+                    label: "Hair".to_string(),
+                    palette: vec![
+                        Color::srgb(0.08, 0.06, 0.05),
+                        Color::srgb(0.3, 0.18, 0.1),
+                        Color::srgb(0.55, 0.35, 0.2),
+                        Color::srgb(0.85, 0.7, 0.45),
+                        Color::srgb(0.6, 0.2, 0.1),
+                        Color::srgb(0.75, 0.75, 0.75),
+                    ],
+                    default_index: 1,
+                },
+                ColorSlotDefinition {
+                    id: "outfit_primary".to_string(),
+                    label: "Outfit".to_string(),
+                    palette: outfit_palette.clone(),
+                    default_index: 0,
+                },
+                ColorSlotDefinition {
+                    id: "outfit_secondary".to_string(),
+                    label: "Outfit Trim".to_string(),
+                    palette: outfit_palette,
+                    default_index: 3,
+                },
+            ],
+            // Parts depend on the game's models
+            body_parts: Vec::new(),
+            blend_shapes: vec![
+                BlendShapeDefinition {
+                    id: "Weight".to_string(),
+                    label: "Weight".to_string(),
+                    min: 0.0,
+                    max: 1.0,
+                    default: 0.0,
+                    step: 0.1,
+                },
+                BlendShapeDefinition {
+                    id: "Muscle".to_string(),
+                    label: "Muscle".to_string(),
+                    min: 0.0,
+                    max: 1.0,
+                    default: 0.0,
+                    step: 0.1,
+                },
+            ],
+        }
+    }
+}
+
+impl AppearanceCatalog {
+    pub fn color_slot(&self, id: &str) -> Option<&ColorSlotDefinition> {
+        self.color_slots.iter().find(|slot| slot.id == id)
+    }
+
+    pub fn body_part(&self, id: &str) -> Option<&BodyPartDefinition> {
+        self.body_parts.iter().find(|part| part.id == id)
+    }
+
+    pub fn blend_shape(&self, id: &str) -> Option<&BlendShapeDefinition> {
+        self.blend_shapes.iter().find(|shape| shape.id == id)
+    }
+
+    /// Appearance made of every default choice
+    pub fn default_appearance(&self) -> CharacterAppearance {
+        CharacterAppearance {
+            colors: self.color_slots.iter().map(|slot| (slot.id.clone(), slot.default_index)).collect(),
+            parts: self
+                .body_parts
+                .iter()
+                .filter_map(|part| part.options.get(part.default_index).map(|option| (part.id.clone(), option.id.clone())))
+                .collect(),
+            blend_shapes: self.blend_shapes.iter().map(|shape| (shape.id.clone(), shape.default)).collect(),
+        }
+    }
+
+    pub fn random_appearance(&self) -> CharacterAppearance {
+        let mut rng = rand::rng();
+        CharacterAppearance {
+            colors: self
+                .color_slots
+                .iter()
+                .filter(|slot| !slot.palette.is_empty())
+                .map(|slot| (slot.id.clone(), rng.random_range(0..slot.palette.len())))
+                .collect(),
+            parts: self
+                .body_parts
+                .iter()
+                .filter(|part| !part.options.is_empty())
+                .map(|part| (part.id.clone(), part.options[rng.random_range(0..part.options.len())].id.clone()))
+                .collect(),
+            blend_shapes: self
+                .blend_shapes
+                .iter()
+                .map(|shape| (shape.id.clone(), rng.random_range(shape.min.min(shape.max)..=shape.max.max(shape.min))))
+                .collect(),
+        }
+    }
+}
+
+/// Appearance of one character; persisted in saves
+#[derive(Component, Debug, Clone, Default, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Component)]
+pub struct CharacterAppearance {
+    /// Palette index per color slot
+    pub colors: HashMap<String, usize>,
+    /// Option id per body part
+    pub parts: HashMap<String, String>,
+    /// Weight per blend shape
+    pub blend_shapes: HashMap<String, f32>,
+}
+
+impl CharacterAppearance {
+    pub fn color_index(&self, catalog: &AppearanceCatalog, slot: &str) -> Option<usize> {
+        let definition = catalog.color_slot(slot)?;
+        let index = self.colors.get(slot).copied().unwrap_or(definition.default_index);
+        (!definition.palette.is_empty()).then(|| index.min(definition.palette.len() - 1))
+    }
+
+    pub fn color(&self, catalog: &AppearanceCatalog, slot: &str) -> Option<Color> {
+        let index = self.color_index(catalog, slot)?;
+        catalog.color_slot(slot)?.palette.get(index).copied()
+    }
+
+    pub fn part_index(&self, catalog: &AppearanceCatalog, part: &str) -> Option<usize> {
+        let definition = catalog.body_part(part)?;
+        match self.parts.get(part) {
+            Some(option) => definition.options.iter().position(|candidate| candidate.id == *option),
+            None => (definition.default_index < definition.options.len()).then_some(definition.default_index),
+        }
+    }
+
+    pub fn part<'a>(&self, catalog: &'a AppearanceCatalog, part: &str) -> Option<&'a BodyPartOption> {
+        let index = self.part_index(catalog, part)?;
+        catalog.body_part(part)?.options.get(index)
+    }
+
+    pub fn blend_shape(&self, catalog: &AppearanceCatalog, id: &str) -> Option<f32> {
+        let definition = catalog.blend_shape(id)?;
+        Some(self.blend_shapes.get(id).copied().unwrap_or(definition.default).clamp(
+            definition.min.min(definition.max),
+            definition.max.max(definition.min),
+        ))
+    }
+}
+
+/// Tints the meshes of this entity and its descendants with a color slot
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct AppearanceMaterialSlot {
+    pub slot: String,
+}
+
+/// Where the scene of a body part is spawned
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct AppearancePartAnchor {
+    pub part: String,
+}
+
+/// Spawned body part scene
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct AppearancePartInstance {
+    pub part: String,
+    pub option: String,
+}
+
+/// Per-character copy of a mesh material owned by the appearance systems
+#[derive(Component, Debug)]
+pub struct AppearanceMaterial(pub Handle<StandardMaterial>);
+
+/// Character whose appearance has to be (re)applied
+#[derive(Component, Debug, Default)]
+pub struct AppearanceDirty;
+
+/// Global appearance settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct AppearanceSettings {
+    /// Give players without an appearance the catalog defaults
+    pub auto_attach_player: bool,
+    /// Open the creator at startup; the result goes to the first player spawned
+    pub open_creator_on_startup: bool,
+    /// Save key under `SaveData::custom_data`
+    pub save_key: String,
+}
+
+impl Default for AppearanceSettings {
+    fn default() -> Self {
+        Self {
+            auto_attach_player: true,
+            open_creator_on_startup: false,
+            save_key: "appearance".to_string(),
+        }
+    }
+}
+
+/// Character creator state
+#[derive(Resource, Debug, Default)]
+pub struct CharacterCreatorState {
+    pub open: bool,
+    /// Character previewing the draft; `None` while creating before spawn
+    pub target: Option<Entity>,
+    pub draft: CharacterAppearance,
+    /// Target's appearance when the creator opened, restored on cancel
+    pub original: Option<CharacterAppearance>,
+}
+
+/// Appearance confirmed before the player existed, applied to the next player spawned
+#[derive(Resource, Debug, Default)]
+pub struct PendingAppearance(pub Option<CharacterAppearance>);
+
+/// Appearance and character creator control
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppearanceCommand {
+    /// Opens the creator on `target`, or for a character not spawned yet
+    OpenCreator { target: Option<Entity> },
+    SetColor { slot: String, index: usize },
+    /// Moves through the palette of `slot`
+    CycleColor { slot: String, step: i32 },
+    SetPart { part: String, option: String },
+    CyclePart { part: String, step: i32 },
+    SetBlendShape { id: String, value: f32 },
+    /// Adds `steps` times the blend shape's step
+    AdjustBlendShape { id: String, steps: f32 },
+    Randomize,
+    /// Back to the catalog defaults
+    Reset,
+    Confirm,
+    Cancel,
+    /// Replaces an entity's appearance outside the creator
+    Apply { entity: Entity, appearance: CharacterAppearance },
+}
+
+/// Queue for appearance commands
+pub type AppearanceCommandQueue = EventQueue<AppearanceCommand>;
+
+/// Creator notifications
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppearanceEvent {
+    CreatorOpened { target: Option<Entity> },
+    /// The game spawns the player here when `target` is `None`
+    CreationConfirmed { target: Option<Entity>, appearance: CharacterAppearance },
+    CreationCancelled { target: Option<Entity> },
+}
+
+/// Queue for appearance events
+pub type AppearanceEventQueue = EventQueue<AppearanceEvent>;
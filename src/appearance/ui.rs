@@ -0,0 +1,266 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use super::types::*;
+use crate::input::{FocusCancel, FocusScope, UiCancelButton};
+
+/// Character creator panel
+#[derive(Component)]
+pub struct CharacterCreatorRoot;
+
+/// Rows built from the catalog
+#[derive(Component)]
+pub struct CharacterCreatorContent;
+
+/// Creator row shown by a value text
+#[derive(Debug, Clone, PartialEq)]
+pub enum CreatorRow {
+    Color(String),
+    Part(String),
+    BlendShape(String),
+}
+
+#[derive(Component)]
+pub struct CreatorValueText(pub CreatorRow);
+
+/// Preview of a color slot
+#[derive(Component)]
+pub struct CreatorSwatch(pub String);
+
+/// Button sending an appearance command
+#[derive(Component)]
+pub struct CreatorButton(pub AppearanceCommand);
+
+/// System to setup the character creator UI (hidden by default)
+pub fn setup_character_creator_ui(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            width: Val::Px(380.0),
+            height: Val::Percent(90.0),
+            position_type: PositionType::Absolute,
+            left: Val::Percent(5.0),
+            top: Val::Percent(5.0),
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Val::Px(20.0)),
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.08, 0.08, 0.1, 0.92)),
+        CharacterCreatorRoot,
+        FocusScope { priority: 0, cancel: FocusCancel::None },
+        Visibility::Hidden,
+    )).with_children(|parent| {
+        parent.spawn((
+            Text::new("CHARACTER CREATION"),
+            TextFont { font_size: 26.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+    });
+}
+
+/// Value texts and color swatches of the creator rows
+#[derive(SystemParam)]
+pub struct CreatorRowWidgets<'w, 's> {
+    pub text_query: Query<'w, 's, (&'static mut Text, &'static CreatorValueText)>,
+    pub swatch_query: Query<'w, 's, (&'static mut BackgroundColor, &'static CreatorSwatch)>,
+}
+
+/// System to show the creator and rebuild its rows when the catalog changes
+pub fn update_character_creator_ui(
+    mut commands: Commands,
+    creator: Res<CharacterCreatorState>,
+    catalog: Res<AppearanceCatalog>,
+    mut root_query: Query<(Entity, &mut Visibility), With<CharacterCreatorRoot>>,
+    content_query: Query<Entity, With<CharacterCreatorContent>>,
+    widgets: CreatorRowWidgets,
+    mut rebuilt: Local<bool>,
+) {
+    let CreatorRowWidgets { mut text_query, mut swatch_query } = widgets;
+    let Ok((root, mut visibility)) = root_query.single_mut() else { return };
+
+    let wanted = if creator.open { Visibility::Visible } else { Visibility::Hidden };
+    visibility.set_if_neq(wanted);
+    if !creator.open {
+        return;
+    }
+
+    let content = content_query.iter().next();
+    if content.is_none() || catalog.is_changed() {
+        if let Some(content) = content {
+            commands.entity(content).despawn();
+        }
+        commands.entity(root).with_children(|root| build_creator_rows(root, &catalog));
+        // Texts are filled in once the rows exist
+        *rebuilt = true;
+        return;
+    }
+
+    if !creator.is_changed() && !*rebuilt {
+        return;
+    }
+    *rebuilt = false;
+
+    for (mut text, value) in text_query.iter_mut() {
+        text.0 = match &value.0 {
+            CreatorRow::Color(slot) => {
+                let count = catalog.color_slot(slot).map(|definition| definition.palette.len()).unwrap_or(0);
+                let index = creator.draft.color_index(&catalog, slot).unwrap_or(0);
+                format!("{}/{}", index + 1, count)
+            }
+            CreatorRow::Part(part) => creator
+                .draft
+                .part(&catalog, part)
+                .map(|option| option.label.clone())
+                .unwrap_or_else(|| "-".to_string()),
+            CreatorRow::BlendShape(id) => format!("{:.2}", creator.draft.blend_shape(&catalog, id).unwrap_or(0.0)),
+        };
+    }
+
+    for (mut background, swatch) in swatch_query.iter_mut() {
+        if let Some(color) = creator.draft.color(&catalog, &swatch.0) {
+            background.0 = color;
+        }
+    }
+}
+
+fn build_creator_rows(root: &mut ChildSpawnerCommands, catalog: &AppearanceCatalog) {
+    root.spawn((
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(6.0),
+            ..default()
+        },
+        CharacterCreatorContent,
+    )).with_children(|content| {
+        for slot in catalog.color_slots.iter() {
+            spawn_row(
+                content,
+                &slot.label,
+                CreatorRow::Color(slot.id.clone()),
+                AppearanceCommand::CycleColor { slot: slot.id.clone(), step: -1 },
+                AppearanceCommand::CycleColor { slot: slot.id.clone(), step: 1 },
+                Some(slot.id.clone()),
+            );
+        }
+
+        for part in catalog.body_parts.iter() {
+            spawn_row(
+                content,
+                &part.label,
+                CreatorRow::Part(part.id.clone()),
+                AppearanceCommand::CyclePart { part: part.id.clone(), step: -1 },
+                AppearanceCommand::CyclePart { part: part.id.clone(), step: 1 },
+                None,
+            );
+        }
+
+        for shape in catalog.blend_shapes.iter() {
+            spawn_row(
+                content,
+                &shape.label,
+                CreatorRow::BlendShape(shape.id.clone()),
+                AppearanceCommand::AdjustBlendShape { id: shape.id.clone(), steps: -1.0 },
+                AppearanceCommand::AdjustBlendShape { id: shape.id.clone(), steps: 1.0 },
+                None,
+            );
+        }
+
+        // Actions
+        content.spawn(Node {
+            margin: UiRect::top(Val::Px(16.0)),
+            column_gap: Val::Px(8.0),
+            ..default()
+        }).with_children(|actions| {
+            spawn_button(actions, "Random", AppearanceCommand::Randomize, 80.0);
+            spawn_button(actions, "Reset", AppearanceCommand::Reset, 80.0);
+            spawn_button(actions, "Cancel", AppearanceCommand::Cancel, 80.0);
+            spawn_button(actions, "Confirm", AppearanceCommand::Confirm, 80.0);
+        });
+    });
+}
+
+fn spawn_row(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    row: CreatorRow,
+    previous: AppearanceCommand,
+    next: AppearanceCommand,
+    swatch: Option<String>,
+) {
+    parent.spawn(Node {
+        width: Val::Percent(100.0),
+        height: Val::Px(32.0),
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(8.0),
+        ..default()
+    }).with_children(|row_node| {
+        row_node.spawn((
+            Text::new(label),
+            TextFont { font_size: 16.0, ..default() },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+            Node { width: Val::Px(120.0), ..default() },
+        ));
+
+        spawn_button(row_node, "<", previous, 32.0);
+
+        if let Some(slot) = swatch {
+            row_node.spawn((
+                Node { width: Val::Px(24.0), height: Val::Px(24.0), ..default() },
+                BackgroundColor(Color::WHITE),
+                CreatorSwatch(slot),
+            ));
+        }
+
+        row_node.spawn((
+            Text::new(""),
+            TextFont { font_size: 16.0, ..default() },
+            TextColor(Color::WHITE),
+            Node { width: Val::Px(90.0), justify_content: JustifyContent::Center, ..default() },
+            CreatorValueText(row),
+        ));
+
+        spawn_button(row_node, ">", next, 32.0);
+    });
+}
+
+fn spawn_button(parent: &mut ChildSpawnerCommands, label: &str, command: AppearanceCommand, width: f32) {
+    let is_cancel = matches!(command, AppearanceCommand::Cancel);
+    let mut button = parent.spawn((
+        Button,
+        Node {
+            width: Val::Px(width),
+            height: Val::Px(28.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.25, 0.25, 0.3)),
+        CreatorButton(command),
+    ));
+    if is_cancel {
+        button.insert(UiCancelButton);
+    }
+    button.with_children(|button| {
+        button.spawn((
+            Text::new(label),
+            TextFont { font_size: 16.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+    });
+}
+
+/// System to send the commands of pressed creator buttons
+pub fn handle_character_creator_buttons(
+    mut button_query: Query<(&Interaction, &CreatorButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut appearance_commands: ResMut<AppearanceCommandQueue>,
+) {
+    for (interaction, button, mut background) in button_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => {
+                appearance_commands.send(button.0.clone());
+                background.0 = Color::srgb(0.4, 0.4, 0.5);
+            }
+            Interaction::Hovered => background.0 = Color::srgb(0.32, 0.32, 0.38),
+            Interaction::None => background.0 = Color::srgb(0.25, 0.25, 0.3),
+        }
+    }
+}
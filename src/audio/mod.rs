@@ -0,0 +1,59 @@
+//! Audio cues, subtitles and voice ducking
+//!
+//! Dialog, barks and cutscenes play sounds through the [`AudioCueQueue`]. A
+//! cue names its sound, its mix channel and who it comes from, and can carry a
+//! subtitle: a key into the [`SubtitleLibrary`] or a line of its own. Voice
+//! cues show their subtitle with the speaker's name and lower music, effects
+//! and ambience until they end. A speaker says one line at a time: their next
+//! voice cue stops the previous one.
+//!
+//! ```rust,ignore
+//! library.insert("guard_halt", SubtitleLine::new(Some("Guard"), "Halt! Who goes there?"));
+//! cues.send(AudioCue::voice("voice/guard_halt.ogg", guard).with_subtitle("guard_halt"));
+//! cues.send(AudioCue::sound("sfx/alarm.ogg", AudioChannel::Sfx).with_line(SubtitleLine::new(None, "[Alarm]")));
+//! ```
+//!
+//! [`SubtitleSettings`] holds the accessibility options (text size, background
+//! opacity, speaker names, captions for sounds) and [`AudioMixSettings`] the
+//! channel volumes and ducking. Music and other long-lived sounds follow them
+//! when they carry a [`MixedAudio`].
+
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    AudioChannel, MixedAudio, VoicePlayback, SubtitleLine, SubtitleSource, AudioCue, AudioCueQueue,
+    SubtitleLibrary, SubtitleSettings, AudioMixSettings, ActiveSubtitle, ActiveSubtitles, DuckingState,
+};
+
+pub struct AudioCuePlugin;
+
+impl Plugin for AudioCuePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SubtitleLibrary>()
+            .init_resource::<SubtitleSettings>()
+            .init_resource::<AudioMixSettings>()
+            .init_resource::<ActiveSubtitles>()
+            .init_resource::<DuckingState>()
+            .add_plugins(EventQueuePlugin::<AudioCue>::default())
+            .register_type::<MixedAudio>()
+            .register_type::<VoicePlayback>()
+            .register_type::<SubtitleSettings>()
+            .register_type::<AudioMixSettings>()
+            .register_type::<ActiveSubtitles>()
+            .register_type::<DuckingState>()
+            .add_systems(Startup, setup_subtitle_ui.in_set(crate::headless::PresentationSet))
+            .add_systems(Update, (
+                update_subtitles,
+                play_audio_cues,
+                apply_audio_mix,
+                update_subtitle_ui.in_set(crate::headless::PresentationSet),
+            ).chain());
+    }
+}
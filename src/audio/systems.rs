@@ -0,0 +1,158 @@
+use bevy::audio::{AudioSinkPlayback, Volume};
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::utils::QueueReader;
+use super::types::*;
+
+/// Asset access for cue sounds; missing without the audio plugin
+#[derive(SystemParam)]
+pub struct CueSoundAssets<'w> {
+    pub asset_server: Option<Res<'w, AssetServer>>,
+    pub audio_assets: Option<Res<'w, Assets<AudioSource>>>,
+}
+
+/// System to play audio cues and show their subtitles
+pub fn play_audio_cues(
+    mut commands: Commands,
+    mut cues: QueueReader<AudioCue>,
+    sound_assets: CueSoundAssets,
+    library: Res<SubtitleLibrary>,
+    settings: Res<SubtitleSettings>,
+    mut subtitles: ResMut<ActiveSubtitles>,
+    voice_query: Query<(Entity, &VoicePlayback)>,
+) {
+    let CueSoundAssets { asset_server, audio_assets } = sound_assets;
+    for cue in cues.read() {
+        let voice = cue.channel == AudioChannel::Voice;
+
+        // A new line cuts the emitter's previous one short
+        if let (true, Some(emitter)) = (voice, cue.emitter) {
+            for (entity, playback) in voice_query.iter() {
+                if playback.emitter == Some(emitter) {
+                    commands.entity(entity).despawn();
+                }
+            }
+        }
+
+        // Without the audio plugin (headless) only the subtitles remain
+        if let (Some(path), Some(asset_server), Some(_)) = (&cue.sound, &asset_server, &audio_assets) {
+            let mut sound = commands.spawn((
+                AudioPlayer::<AudioSource>(asset_server.load(path.clone())),
+                PlaybackSettings::DESPAWN,
+                MixedAudio { channel: cue.channel, volume: cue.volume },
+            ));
+            if voice {
+                sound.insert(VoicePlayback { emitter: cue.emitter });
+            }
+        }
+
+        if !settings.enabled || !(voice || settings.caption_sounds) {
+            continue;
+        }
+        let line = match &cue.subtitle {
+            Some(SubtitleSource::Key(key)) => match library.get(key) {
+                Some(line) => line.clone(),
+                None => {
+                    warn!("Subtitle '{}' not found", key);
+                    continue;
+                }
+            },
+            Some(SubtitleSource::Line(line)) => line.clone(),
+            None => continue,
+        };
+        let remaining = settings.duration(&line);
+        subtitles.push(ActiveSubtitle { line, emitter: cue.emitter, remaining, voice }, settings.max_lines);
+    }
+}
+
+/// System to take expired subtitles off the screen
+pub fn update_subtitles(time: Res<Time>, mut subtitles: ResMut<ActiveSubtitles>) {
+    // Only expiring lines change what is shown
+    if subtitles.bypass_change_detection().tick(time.delta_secs()) {
+        subtitles.set_changed();
+    }
+}
+
+/// System to fade the other channels under voices and apply the channel volumes
+pub fn apply_audio_mix(
+    time: Res<Time>,
+    settings: Res<AudioMixSettings>,
+    subtitles: Res<ActiveSubtitles>,
+    mut ducking: ResMut<DuckingState>,
+    voice_query: Query<(), With<VoicePlayback>>,
+    mut sink_query: Query<(&MixedAudio, &mut AudioSink)>,
+) {
+    let voice_playing = !voice_query.is_empty() || subtitles.has_voice();
+    ducking.update(voice_playing, &settings, time.delta_secs());
+
+    for (mixed, mut sink) in sink_query.iter_mut() {
+        let duck = if mixed.channel == AudioChannel::Voice { 1.0 } else { ducking.level };
+        sink.set_volume(Volume::Linear(mixed.volume * settings.channel_volume(mixed.channel) * duck));
+    }
+}
+
+/// System to setup the subtitle panel
+pub fn setup_subtitle_ui(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            bottom: Val::Px(48.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(6.0),
+            ..default()
+        },
+        GlobalZIndex(50),
+        SubtitleRoot,
+    ));
+}
+
+/// System to rebuild the subtitle lines when they or the settings change
+pub fn update_subtitle_ui(
+    mut commands: Commands,
+    subtitles: Res<ActiveSubtitles>,
+    settings: Res<SubtitleSettings>,
+    root_query: Query<Entity, With<SubtitleRoot>>,
+    line_query: Query<Entity, With<SubtitleLineNode>>,
+) {
+    if !subtitles.is_changed() && !settings.is_changed() {
+        return;
+    }
+    let Ok(root) = root_query.single() else { return };
+
+    for line in line_query.iter() {
+        commands.entity(line).despawn();
+    }
+    if !settings.enabled {
+        return;
+    }
+
+    commands.entity(root).with_children(|root| {
+        for subtitle in subtitles.lines.iter() {
+            root.spawn((
+                Node {
+                    max_width: Val::Percent(70.0),
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                    column_gap: Val::Px(8.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, settings.background_opacity.clamp(0.0, 1.0))),
+                SubtitleLineNode,
+            )).with_children(|line| {
+                if let Some(speaker) = subtitle.line.speaker.as_ref().filter(|_| settings.show_speaker_names) {
+                    line.spawn((
+                        Text::new(format!("{}:", speaker)),
+                        TextFont { font_size: settings.font_size, ..default() },
+                        TextColor(settings.speaker_color),
+                    ));
+                }
+                line.spawn((
+                    Text::new(subtitle.line.text.clone()),
+                    TextFont { font_size: settings.font_size, ..default() },
+                    TextColor(settings.text_color),
+                ));
+            });
+        }
+    });
+}
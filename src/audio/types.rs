@@ -0,0 +1,358 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::utils::EventQueue;
+
+/// Mix channel of a sound
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect, Serialize, Deserialize)]
+pub enum AudioChannel {
+    Music,
+    #[default]
+    Sfx,
+    Ambient,
+    /// Dialog, barks and narration; ducks the other channels while it plays
+    Voice,
+}
+
+/// Sound whose volume follows its channel's volume and ducking
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct MixedAudio {
+    pub channel: AudioChannel,
+    /// Volume before the channel's
+    pub volume: f32,
+}
+
+impl MixedAudio {
+    pub fn new(channel: AudioChannel) -> Self {
+        Self { channel, volume: 1.0 }
+    }
+}
+
+/// Voice line playing for `emitter`; despawned with its sound
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct VoicePlayback {
+    pub emitter: Option<Entity>,
+}
+
+/// Text of a subtitle
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, Reflect)]
+#[serde(default)]
+pub struct SubtitleLine {
+    pub speaker: Option<String>,
+    pub text: String,
+    /// Seconds on screen; `None` reads it from the text length
+    pub duration: Option<f32>,
+}
+
+impl SubtitleLine {
+    pub fn new(speaker: Option<&str>, text: impl Into<String>) -> Self {
+        Self {
+            speaker: speaker.map(str::to_string),
+            text: text.into(),
+            duration: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubtitleSource {
+    /// Key in the [`SubtitleLibrary`]
+    Key(String),
+    Line(SubtitleLine),
+}
+
+/// A sound to play, with an optional subtitle
+#[derive(Debug, Clone)]
+pub struct AudioCue {
+    /// Asset path; cues without one only show their subtitle
+    pub sound: Option<String>,
+    pub channel: AudioChannel,
+    /// Who speaks; a new voice cue from the same emitter cuts the previous one short
+    pub emitter: Option<Entity>,
+    pub subtitle: Option<SubtitleSource>,
+    pub volume: f32,
+}
+
+impl AudioCue {
+    pub fn sound(path: impl Into<String>, channel: AudioChannel) -> Self {
+        Self {
+            sound: Some(path.into()),
+            channel,
+            emitter: None,
+            subtitle: None,
+            volume: 1.0,
+        }
+    }
+
+    /// Voice line of `emitter`
+    pub fn voice(path: impl Into<String>, emitter: Entity) -> Self {
+        Self::sound(path, AudioChannel::Voice).from(emitter)
+    }
+
+    /// Cue without a sound, for text-only barks and unvoiced lines
+    pub fn silent(channel: AudioChannel) -> Self {
+        Self {
+            sound: None,
+            channel,
+            emitter: None,
+            subtitle: None,
+            volume: 1.0,
+        }
+    }
+
+    pub fn from(mut self, emitter: Entity) -> Self {
+        self.emitter = Some(emitter);
+        self
+    }
+
+    pub fn with_subtitle(mut self, key: impl Into<String>) -> Self {
+        self.subtitle = Some(SubtitleSource::Key(key.into()));
+        self
+    }
+
+    pub fn with_line(mut self, line: SubtitleLine) -> Self {
+        self.subtitle = Some(SubtitleSource::Line(line));
+        self
+    }
+}
+
+/// Queue for AudioCue
+pub type AudioCueQueue = EventQueue<AudioCue>;
+
+/// Subtitles by key
+#[derive(Resource, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubtitleLibrary {
+    pub lines: HashMap<String, SubtitleLine>,
+}
+
+impl SubtitleLibrary {
+    /// Reads `{ "key": (speaker: Some("Guard"), text: "Halt!") }` maps
+    pub fn from_ron(text: &str) -> Result<Self, String> {
+        let lines = ron::from_str(text).map_err(|e| format!("Invalid subtitles: {}", e))?;
+        Ok(Self { lines })
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, line: SubtitleLine) -> &mut Self {
+        self.lines.insert(key.into(), line);
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&SubtitleLine> {
+        self.lines.get(key)
+    }
+}
+
+/// Subtitle display and accessibility options
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct SubtitleSettings {
+    pub enabled: bool,
+    pub show_speaker_names: bool,
+    /// Also show the subtitles of music, effects and ambience (`[Explosion]`)
+    pub caption_sounds: bool,
+    /// Shortest time a line stays up
+    pub min_duration: f32,
+    /// Reading time per character, for lines without a duration
+    pub seconds_per_character: f32,
+    /// Lines shown at once; the oldest makes room, 1 replaces the current line
+    pub max_lines: usize,
+    pub font_size: f32,
+    /// Opacity of the box behind each line, 0 for none
+    pub background_opacity: f32,
+    pub text_color: Color,
+    pub speaker_color: Color,
+}
+
+impl Default for SubtitleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            show_speaker_names: true,
+            caption_sounds: false,
+            min_duration: 1.5,
+            seconds_per_character: 0.06,
+            max_lines: 2,
+            font_size: 22.0,
+            background_opacity: 0.6,
+            text_color: Color::WHITE,
+            speaker_color: Color::srgb(1.0, 0.85, 0.4),
+        }
+    }
+}
+
+impl SubtitleSettings {
+    /// Seconds `line` stays on screen
+    pub fn duration(&self, line: &SubtitleLine) -> f32 {
+        line.duration
+            .unwrap_or_else(|| (line.text.chars().count() as f32 * self.seconds_per_character).max(self.min_duration))
+    }
+}
+
+/// Channel volumes and voice ducking
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct AudioMixSettings {
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub ambient_volume: f32,
+    pub voice_volume: f32,
+    /// Lower music, effects and ambience while a voice plays
+    pub ducking: bool,
+    /// Volume of the ducked channels, 0-1
+    pub duck_volume: f32,
+    /// Seconds to fade down when a voice starts
+    pub duck_attack: f32,
+    /// Seconds to fade back up after the last voice
+    pub duck_release: f32,
+}
+
+impl Default for AudioMixSettings {
+    fn default() -> Self {
+        Self {
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            ambient_volume: 1.0,
+            voice_volume: 1.0,
+            ducking: true,
+            duck_volume: 0.35,
+            duck_attack: 0.15,
+            duck_release: 0.6,
+        }
+    }
+}
+
+impl AudioMixSettings {
+    pub fn channel_volume(&self, channel: AudioChannel) -> f32 {
+        match channel {
+            AudioChannel::Music => self.music_volume,
+            AudioChannel::Sfx => self.sfx_volume,
+            AudioChannel::Ambient => self.ambient_volume,
+            AudioChannel::Voice => self.voice_volume,
+        }
+    }
+}
+
+/// Subtitle on screen
+#[derive(Debug, Clone, Reflect)]
+pub struct ActiveSubtitle {
+    pub line: SubtitleLine,
+    pub emitter: Option<Entity>,
+    pub remaining: f32,
+    /// Spoken; keeps the other channels ducked while it is up
+    pub voice: bool,
+}
+
+/// Subtitles on screen, oldest first
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ActiveSubtitles {
+    pub lines: Vec<ActiveSubtitle>,
+}
+
+impl ActiveSubtitles {
+    pub fn push(&mut self, subtitle: ActiveSubtitle, max_lines: usize) {
+        // A speaker says one thing at a time
+        if let Some(emitter) = subtitle.emitter {
+            self.lines.retain(|line| line.emitter != Some(emitter));
+        }
+        self.lines.push(subtitle);
+        let excess = self.lines.len().saturating_sub(max_lines.max(1));
+        self.lines.drain(..excess);
+    }
+
+    /// Counts the lines down; returns whether any expired
+    pub fn tick(&mut self, dt: f32) -> bool {
+        let count = self.lines.len();
+        for line in self.lines.iter_mut() {
+            line.remaining -= dt;
+        }
+        self.lines.retain(|line| line.remaining > 0.0);
+        self.lines.len() != count
+    }
+
+    pub fn has_voice(&self) -> bool {
+        self.lines.iter().any(|line| line.voice)
+    }
+}
+
+/// Volume of the ducked channels, moving toward its target
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct DuckingState {
+    pub level: f32,
+}
+
+impl Default for DuckingState {
+    fn default() -> Self {
+        Self { level: 1.0 }
+    }
+}
+
+impl DuckingState {
+    pub fn update(&mut self, voice_playing: bool, settings: &AudioMixSettings, dt: f32) {
+        let ducked = settings.duck_volume.clamp(0.0, 1.0);
+        let target = if voice_playing && settings.ducking { ducked } else { 1.0 };
+        let fade = if target < self.level { settings.duck_attack } else { settings.duck_release };
+        let step = (1.0 - ducked).max(0.01) * dt / fade.max(0.001);
+        self.level = if target < self.level {
+            (self.level - step).max(target)
+        } else {
+            (self.level + step).min(target)
+        };
+    }
+}
+
+/// Subtitle panel
+#[derive(Component)]
+pub struct SubtitleRoot;
+
+/// A line of the subtitle panel
+#[derive(Component)]
+pub struct SubtitleLineNode;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_subtitle_stacking_and_ducking() {
+        let settings = SubtitleSettings::default();
+        let short = SubtitleLine::new(Some("Guard"), "Halt!");
+        assert_eq!(settings.duration(&short), settings.min_duration);
+        let long = SubtitleLine::new(None, "x".repeat(100));
+        assert!((settings.duration(&long) - 6.0).abs() < 1e-4);
+
+        let mut world = World::new();
+        let guard = world.spawn_empty().id();
+        let line = |text: &str, emitter, remaining| ActiveSubtitle {
+            line: SubtitleLine::new(None, text),
+            emitter,
+            remaining,
+            voice: true,
+        };
+        let mut subtitles = ActiveSubtitles::default();
+        subtitles.push(line("a", Some(guard), 1.0), 2);
+        subtitles.push(line("b", None, 3.0), 2);
+        subtitles.push(line("c", Some(guard), 2.0), 2);
+        assert_eq!(subtitles.lines.iter().map(|l| l.line.text.as_str()).collect::<Vec<_>>(), vec!["b", "c"]);
+        subtitles.push(line("d", None, 1.0), 2);
+        assert_eq!(subtitles.lines.iter().map(|l| l.line.text.as_str()).collect::<Vec<_>>(), vec!["c", "d"]);
+        assert!(subtitles.tick(1.5));
+        assert_eq!(subtitles.lines.len(), 1);
+        assert!(!subtitles.tick(0.1));
+
+        let mix = AudioMixSettings { duck_volume: 0.5, duck_attack: 0.5, duck_release: 1.0, ..default() };
+        let mut ducking = DuckingState::default();
+        ducking.update(true, &mix, 0.25);
+        assert!((ducking.level - 0.75).abs() < 1e-4);
+        ducking.update(true, &mix, 1.0);
+        assert_eq!(ducking.level, 0.5);
+        ducking.update(false, &mix, 0.5);
+        assert!((ducking.level - 0.75).abs() < 1e-4);
+        ducking.update(false, &mix, 5.0);
+        assert_eq!(ducking.level, 1.0);
+    }
+}
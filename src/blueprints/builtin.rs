@@ -0,0 +1,79 @@
+//! Built-in example blueprints
+
+/// Enemy, pickup and door templates registered when
+/// `BlueprintSettings::register_builtin` is set
+pub const BUILTIN_BLUEPRINTS: &str = r#"[
+    {
+        "name": "enemy_grunt",
+        "description": "Melee enemy that patrols, chases and drops loot",
+        "parameters": {
+            "health": 100.0,
+            "damage": 10.0,
+            "detection_range": 15.0,
+            "loot": "health_pickup"
+        },
+        "components": [
+            { "type": "Name", "name": "Enemy Grunt" },
+            { "type": "Tags", "tags": ["Enemy"] },
+            { "type": "Health", "maximum": "$health" },
+            { "type": "Character", "walk_speed": 3.0 },
+            { "type": "Ai", "detection_range": "$detection_range", "attack_range": 2.0 },
+            { "type": "MeleeCombat", "damage": "$damage" },
+            { "type": "Loot", "drops": [{ "blueprint": "$loot", "chance": 0.5 }] },
+            { "type": "Mesh", "shape": "Capsule", "radius": 0.4, "height": 1.0, "color": [0.7, 0.2, 0.2] }
+        ]
+    },
+    {
+        "name": "health_pickup",
+        "description": "Restores health on pickup",
+        "parameters": { "amount": 25 },
+        "components": [
+            { "type": "Name", "name": "Health Pickup" },
+            { "type": "Tags", "tags": ["Pickup"] },
+            { "type": "Pickup", "kind": "Health", "amount": "$amount" },
+            { "type": "Interactable", "text": "Pick up", "interaction_type": "Pickup" },
+            { "type": "Collider", "shape": "Sphere", "radius": 0.3 },
+            { "type": "Mesh", "shape": "Sphere", "radius": 0.3, "color": [0.2, 0.8, 0.2] }
+        ]
+    },
+    {
+        "name": "door",
+        "description": "Trigger door with a frame",
+        "parameters": { "locked": false },
+        "components": [
+            { "type": "Name", "name": "Door" },
+            { "type": "Tags", "tags": ["Door"] },
+            { "type": "Door", "locked": "$locked", "close_after": 5.0, "tags_to_open": ["Player"] },
+            { "type": "Interactable", "text": "Open", "interaction_type": "Open" },
+            { "type": "RigidBody", "body": "Kinematic" },
+            { "type": "Collider", "shape": "Box", "size": [1.2, 2.2, 0.1] },
+            { "type": "Mesh", "shape": "Box", "size": [1.2, 2.2, 0.1], "color": [0.5, 0.35, 0.2] }
+        ],
+        "children": [
+            { "blueprint": "door_frame_post", "translation": [-0.7, 0.0, 0.0] },
+            { "blueprint": "door_frame_post", "translation": [0.7, 0.0, 0.0] }
+        ]
+    },
+    {
+        "name": "door_frame_post",
+        "components": [
+            { "type": "RigidBody", "body": "Static" },
+            { "type": "Collider", "shape": "Box", "size": [0.2, 2.4, 0.2] },
+            { "type": "Mesh", "shape": "Box", "size": [0.2, 2.4, 0.2], "color": [0.3, 0.3, 0.3] }
+        ]
+    }
+]"#;
+
+/// Vehicle templates, registered alongside [`BUILTIN_BLUEPRINTS`]
+#[cfg(feature = "vehicles")]
+pub const BUILTIN_VEHICLE_BLUEPRINTS: &str = r#"[
+    {
+        "name": "car",
+        "description": "Drivable preset car",
+        "parameters": { "name": "Car" },
+        "components": [
+            { "type": "Vehicle", "vehicle_type": "Car", "name": "$name" },
+            { "type": "Tags", "tags": ["Vehicle"] }
+        ]
+    }
+]"#;
@@ -0,0 +1,35 @@
+pub mod types;
+pub mod builtin;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    BlueprintShape, BlueprintBody, BlueprintLootEntry, BlueprintComponent, BlueprintChild,
+    BlueprintDefinition, BlueprintRegistry, BlueprintSettings, BlueprintSpawnRequest, BlueprintSpawnQueue,
+    BlueprintInstance, BlueprintLootTable, BlueprintSpawnEvent, BlueprintSpawnEventQueue,
+};
+pub use systems::BlueprintSpawner;
+
+pub struct BlueprintsPlugin;
+
+impl Plugin for BlueprintsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<BlueprintRegistry>()
+            .init_resource::<BlueprintSettings>()
+            .add_plugins(EventQueuePlugin::<BlueprintSpawnRequest>::default())
+            .add_plugins(EventQueuePlugin::<BlueprintSpawnEvent>::default())
+            .register_type::<BlueprintSettings>()
+            .register_type::<BlueprintInstance>()
+            .register_type::<BlueprintLootTable>()
+            .add_systems(Startup, load_blueprints)
+            .add_systems(Update, (
+                drop_blueprint_loot,
+                process_blueprint_spawn_requests,
+            ).chain());
+    }
+}
@@ -0,0 +1,481 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use avian3d::prelude::*;
+use rand::Rng;
+use serde_json::Value;
+use std::collections::HashMap;
+use crate::ai::AiController;
+use crate::character::{CharacterAnimationState, CharacterController, CharacterMovementState};
+use crate::combat::{Health, MeleeCombat};
+use crate::devices::DoorSystem;
+use crate::game_manager::types::PrefabRegistry;
+use crate::input::InputState;
+use crate::interaction::Interactable;
+use crate::physics::{CustomGravity, GroundDetection, GroundDetectionSettings};
+use crate::pickups::PickUpObject;
+use crate::tags::GameTag;
+use crate::utils::QueueReader;
+#[cfg(feature = "vehicles")]
+use crate::vehicles::{spawn_vehicle, Vehicle};
+use super::builtin::BUILTIN_BLUEPRINTS;
+#[cfg(feature = "vehicles")]
+use super::builtin::BUILTIN_VEHICLE_BLUEPRINTS;
+use super::types::*;
+
+/// Everything needed to spawn blueprints from a system
+pub struct BlueprintSpawner<'a, 'w, 's> {
+    pub commands: &'a mut Commands<'w, 's>,
+    pub registry: &'a BlueprintRegistry,
+    pub prefabs: &'a PrefabRegistry,
+    pub asset_server: &'a AssetServer,
+    pub meshes: &'a mut ResMut<'w, Assets<Mesh>>,
+    pub materials: &'a mut ResMut<'w, Assets<StandardMaterial>>,
+    pub max_depth: u32,
+}
+
+impl BlueprintSpawner<'_, '_, '_> {
+    /// Spawns `name` and its child blueprints, returning the root entity
+    pub fn spawn(
+        &mut self,
+        name: &str,
+        transform: Transform,
+        overrides: &HashMap<String, Value>,
+    ) -> Result<Entity, String> {
+        self.spawn_nested(name, transform, overrides, 0)
+    }
+
+    /// Root entity of a blueprint; vehicles come with their own body, wheels and seats
+    fn spawn_root(&mut self, components: &[BlueprintComponent], transform: Transform) -> Entity {
+        #[cfg(feature = "vehicles")]
+        if let Some((vehicle_type, vehicle_name)) = components.iter().find_map(|component| match component {
+            BlueprintComponent::Vehicle { vehicle_type, name } => Some((vehicle_type.clone(), name.clone())),
+            _ => None,
+        }) {
+            let entity = spawn_vehicle(self.commands, self.meshes.reborrow(), self.materials.reborrow(), transform.translation, vehicle_type);
+            self.commands.entity(entity).insert(transform);
+            if let Some(vehicle_name) = vehicle_name {
+                self.commands.queue(move |world: &mut World| {
+                    if let Some(mut vehicle) = world.get_mut::<Vehicle>(entity) {
+                        vehicle.vehicle_name = vehicle_name;
+                    }
+                });
+            }
+            return entity;
+        }
+        #[cfg(not(feature = "vehicles"))]
+        let _ = components;
+
+        self.commands.spawn((transform, Visibility::default())).id()
+    }
+
+    fn spawn_nested(
+        &mut self,
+        name: &str,
+        transform: Transform,
+        overrides: &HashMap<String, Value>,
+        depth: u32,
+    ) -> Result<Entity, String> {
+        if depth > self.max_depth {
+            return Err(format!("Blueprint '{}' nests deeper than {} levels", name, self.max_depth));
+        }
+
+        let definition = self
+            .registry
+            .get(name)
+            .ok_or_else(|| format!("Blueprint '{}' not found", name))?;
+        let parameters = definition.parameters_with(overrides);
+        let components = definition.resolve_components(&parameters)?;
+
+        let entity = self.spawn_root(&components, transform);
+
+        self.commands.entity(entity).insert((
+            BlueprintInstance {
+                blueprint: name.to_string(),
+            },
+            Name::new(name.to_string()),
+        ));
+
+        for component in components {
+            self.insert_component(entity, component);
+        }
+
+        for child in definition.children.iter() {
+            // Children may forward the parent's parameters
+            let child_overrides: HashMap<String, Value> = child
+                .overrides
+                .iter()
+                .map(|(key, value)| (key.clone(), substitute_parameters(value, &parameters)))
+                .collect();
+            let rotation = Quat::from_euler(
+                EulerRot::YXZ,
+                child.rotation.y.to_radians(),
+                child.rotation.x.to_radians(),
+                child.rotation.z.to_radians(),
+            );
+            let child_transform = Transform::from_translation(child.translation).with_rotation(rotation);
+
+            let child_entity = self.spawn_nested(&child.blueprint, child_transform, &child_overrides, depth + 1)?;
+            self.commands.entity(entity).add_child(child_entity);
+        }
+
+        Ok(entity)
+    }
+
+    fn insert_component(&mut self, entity: Entity, component: BlueprintComponent) {
+        let mut entity_commands = self.commands.entity(entity);
+
+        match component {
+            BlueprintComponent::Name { name } => {
+                entity_commands.insert(Name::new(name));
+            }
+            BlueprintComponent::Tags { tags } => {
+                entity_commands.insert(GameTag::new(tags));
+            }
+            BlueprintComponent::Health { maximum, can_regenerate } => {
+                entity_commands.insert(Health {
+                    current: maximum,
+                    maximum,
+                    can_regenerate,
+                    ..default()
+                });
+            }
+            BlueprintComponent::Ai { detection_range, attack_range, wander_radius, patrol_path } => {
+                let mut ai = AiController::default();
+                if let Some(detection_range) = detection_range {
+                    ai.detection_range = detection_range;
+                }
+                if let Some(attack_range) = attack_range {
+                    ai.attack_range = attack_range;
+                }
+                if let Some(wander_radius) = wander_radius {
+                    ai.wander_radius = wander_radius;
+                }
+                ai.patrol_path = patrol_path;
+                entity_commands.insert(ai);
+            }
+            BlueprintComponent::Character { walk_speed, run_speed } => {
+                let mut controller = CharacterController::default();
+                if let Some(walk_speed) = walk_speed {
+                    controller.walk_speed = walk_speed;
+                }
+                if let Some(run_speed) = run_speed {
+                    controller.run_speed = run_speed;
+                }
+                entity_commands
+                    .insert((
+                        controller,
+                        CharacterMovementState::default(),
+                        CharacterAnimationState::default(),
+                        InputState::default(),
+                    ))
+                    .insert((
+                        RigidBody::Dynamic,
+                        Collider::capsule(0.4, 1.0),
+                        LockedAxes::ROTATION_LOCKED,
+                        GravityScale(1.0),
+                        Friction::new(0.0),
+                        Restitution::new(0.0),
+                        LinearVelocity::default(),
+                        AngularVelocity::default(),
+                        CustomGravity::default(),
+                        GroundDetection::default(),
+                        GroundDetectionSettings::default(),
+                    ));
+            }
+            BlueprintComponent::MeleeCombat { damage, range } => {
+                let mut combat = MeleeCombat {
+                    damage,
+                    ..default()
+                };
+                if let Some(range) = range {
+                    combat.range = range;
+                }
+                entity_commands.insert(combat);
+            }
+            BlueprintComponent::Loot { drops } => {
+                entity_commands.insert(BlueprintLootTable {
+                    drops,
+                    scatter_radius: 1.0,
+                    dropped: false,
+                });
+            }
+            BlueprintComponent::Pickup { kind, amount } => {
+                entity_commands.insert(PickUpObject {
+                    amount,
+                    pickup_kind: kind,
+                    ..default()
+                });
+            }
+            BlueprintComponent::Interactable { text, distance, interaction_type } => {
+                let mut interactable = Interactable {
+                    interaction_text: text,
+                    interaction_type,
+                    ..default()
+                };
+                if let Some(distance) = distance {
+                    interactable.interaction_distance = distance;
+                }
+                entity_commands.insert(interactable);
+            }
+            BlueprintComponent::Door { locked, open_speed, close_after, tags_to_open } => {
+                let mut door = DoorSystem {
+                    locked,
+                    close_after_time: close_after.is_some(),
+                    ..default()
+                };
+                if let Some(open_speed) = open_speed {
+                    door.open_speed = open_speed;
+                }
+                if let Some(close_after) = close_after {
+                    door.time_to_close = close_after;
+                }
+                if !tags_to_open.is_empty() {
+                    door.tag_list_to_open = tags_to_open;
+                }
+                entity_commands.insert(door);
+            }
+            // Handled when the root entity is created
+            #[cfg(feature = "vehicles")]
+            BlueprintComponent::Vehicle { .. } => {}
+            BlueprintComponent::RigidBody { body } => {
+                entity_commands.insert(match body {
+                    BlueprintBody::Dynamic => RigidBody::Dynamic,
+                    BlueprintBody::Static => RigidBody::Static,
+                    BlueprintBody::Kinematic => RigidBody::Kinematic,
+                });
+            }
+            BlueprintComponent::Collider(shape) => {
+                entity_commands.insert(match shape {
+                    BlueprintShape::Box { size } => Collider::cuboid(size.x, size.y, size.z),
+                    BlueprintShape::Sphere { radius } => Collider::sphere(radius),
+                    BlueprintShape::Capsule { radius, height } => Collider::capsule(radius, height),
+                    BlueprintShape::Cylinder { radius, height } => Collider::cylinder(radius, height),
+                });
+            }
+            BlueprintComponent::Mesh { shape, color } => {
+                let mesh = match shape {
+                    BlueprintShape::Box { size } => Mesh::from(Cuboid::new(size.x, size.y, size.z)),
+                    BlueprintShape::Sphere { radius } => Mesh::from(Sphere::new(radius)),
+                    BlueprintShape::Capsule { radius, height } => Mesh::from(Capsule3d::new(radius, height)),
+                    BlueprintShape::Cylinder { radius, height } => Mesh::from(Cylinder::new(radius, height)),
+                };
+                let material = StandardMaterial {
+                    base_color: Color::srgb(color[0], color[1], color[2]),
+                    ..default()
+                };
+                entity_commands.insert((
+                    Mesh3d(self.meshes.add(mesh)),
+                    MeshMaterial3d(self.materials.add(material)),
+                ));
+            }
+            BlueprintComponent::Scene { scene } => {
+                let handle = self
+                    .prefabs
+                    .prefabs
+                    .get(&scene)
+                    .cloned()
+                    .unwrap_or_else(|| {
+                        if scene.ends_with(".gltf") || scene.ends_with(".glb") {
+                            self.asset_server.load(GltfAssetLabel::Scene(0).from_asset(scene))
+                        } else {
+                            self.asset_server.load(scene)
+                        }
+                    });
+                entity_commands.insert(SceneRoot(handle));
+            }
+        }
+    }
+}
+
+/// System to register the built-in blueprints and the blueprint folder
+pub fn load_blueprints(
+    settings: Res<BlueprintSettings>,
+    mut registry: ResMut<BlueprintRegistry>,
+) {
+    if settings.register_builtin {
+        if let Err(error) = registry.register_json(BUILTIN_BLUEPRINTS) {
+            error!("Failed to register built-in blueprints: {}", error);
+        }
+        #[cfg(feature = "vehicles")]
+        if let Err(error) = registry.register_json(BUILTIN_VEHICLE_BLUEPRINTS) {
+            error!("Failed to register built-in vehicle blueprints: {}", error);
+        }
+    }
+
+    if settings.directory.is_empty() {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(&settings.directory) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+            continue;
+        }
+
+        let result = std::fs::read_to_string(&path)
+            .map_err(|error| error.to_string())
+            .and_then(|json| registry.register_json(&json));
+        match result {
+            Ok(count) => info!("Loaded {} blueprint(s) from {:?}", count, path),
+            Err(error) => error!("Failed to load blueprints from {:?}: {}", path, error),
+        }
+    }
+}
+
+/// Assets blueprint components load or create
+#[derive(SystemParam)]
+pub struct BlueprintAssets<'w> {
+    pub asset_server: Res<'w, AssetServer>,
+    pub meshes: ResMut<'w, Assets<Mesh>>,
+    pub materials: ResMut<'w, Assets<StandardMaterial>>,
+}
+
+/// System to spawn queued blueprints
+pub fn process_blueprint_spawn_requests(
+    mut commands: Commands,
+    settings: Res<BlueprintSettings>,
+    registry: Res<BlueprintRegistry>,
+    prefabs: Res<PrefabRegistry>,
+    assets: BlueprintAssets,
+    mut spawn_queue: QueueReader<BlueprintSpawnRequest>,
+    mut event_queue: ResMut<BlueprintSpawnEventQueue>,
+) {
+    if spawn_queue.is_empty() {
+        return;
+    }
+    let BlueprintAssets { asset_server, mut meshes, mut materials } = assets;
+
+    let mut spawner = BlueprintSpawner {
+        commands: &mut commands,
+        registry: &registry,
+        prefabs: &prefabs,
+        asset_server: &asset_server,
+        meshes: &mut meshes,
+        materials: &mut materials,
+        max_depth: settings.max_child_depth,
+    };
+
+    for request in spawn_queue.read() {
+        match spawner.spawn(&request.blueprint, request.transform, &request.overrides) {
+            Ok(entity) => {
+                if let Some(parent) = request.parent {
+                    spawner.commands.entity(parent).add_child(entity);
+                }
+                event_queue.send(BlueprintSpawnEvent::Spawned {
+                    blueprint: request.blueprint,
+                    entity,
+                });
+            }
+            Err(error) => {
+                warn!("Blueprint spawn failed: {}", error);
+                event_queue.send(BlueprintSpawnEvent::Failed {
+                    blueprint: request.blueprint,
+                    error,
+                });
+            }
+        }
+    }
+}
+
+/// System to roll blueprint loot when its owner dies
+pub fn drop_blueprint_loot(
+    mut spawn_queue: ResMut<BlueprintSpawnQueue>,
+    mut query: Query<(&GlobalTransform, &Health, &mut BlueprintLootTable), Changed<Health>>,
+) {
+    let mut rng = rand::rng();
+
+    for (transform, health, mut loot) in query.iter_mut() {
+        if !health.is_dead {
+            // Allow another drop after a respawn
+            loot.dropped = false;
+            continue;
+        }
+        if loot.dropped {
+            continue;
+        }
+        loot.dropped = true;
+
+        for entry in loot.drops.iter() {
+            for _ in 0..entry.count {
+                if rng.random::<f32>() > entry.chance {
+                    continue;
+                }
+                let angle = rng.random::<f32>() * std::f32::consts::TAU;
+                let offset = Vec3::new(angle.cos(), 0.5, angle.sin()) * loot.scatter_radius * rng.random::<f32>();
+                spawn_queue.send(BlueprintSpawnRequest::new(
+                    entry.blueprint.clone(),
+                    Transform::from_translation(transform.translation() + offset),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blueprints::BlueprintsPlugin;
+
+    const LOOT_CRATE: &str = r#"{
+        "name": "loot_crate",
+        "parameters": { "drops": 2 },
+        "components": [
+            { "type": "Health", "maximum": 10.0 },
+            { "type": "Loot", "drops": [{ "blueprint": "health_pickup", "count": "$drops" }] }
+        ]
+    }"#;
+
+    fn spawned(app: &App, name: &str) -> Vec<Entity> {
+        app.world()
+            .resource::<BlueprintSpawnEventQueue>()
+            .iter()
+            .filter_map(|event| match event {
+                BlueprintSpawnEvent::Spawned { blueprint, entity } if blueprint == name => Some(*entity),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_blueprint_requests_spawn_overridden_entities_and_loot() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), BlueprintsPlugin))
+            .insert_resource(BlueprintSettings { directory: String::new(), ..default() })
+            .init_resource::<PrefabRegistry>()
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<StandardMaterial>>();
+        app.world_mut().resource_mut::<BlueprintRegistry>().register_json(LOOT_CRATE).unwrap();
+
+        app.world_mut().resource_mut::<BlueprintSpawnQueue>().send_batch([
+            BlueprintSpawnRequest::new("enemy_grunt", Transform::default()).with_override("health", 250.0),
+            BlueprintSpawnRequest::new("door", Transform::default()),
+            BlueprintSpawnRequest::new("loot_crate", Transform::default()),
+            BlueprintSpawnRequest::new("missing", Transform::default()),
+        ]);
+        app.update();
+
+        let grunt = spawned(&app, "enemy_grunt")[0];
+        assert_eq!(app.world().get::<Health>(grunt).unwrap().maximum, 250.0);
+        assert!(app.world().get::<GameTag>(grunt).unwrap().has("Enemy"));
+        assert_eq!(app.world().get::<MeleeCombat>(grunt).unwrap().damage, 10.0);
+
+        let door = spawned(&app, "door")[0];
+        let posts = app.world().get::<Children>(door).unwrap();
+        assert_eq!(posts.len(), 2);
+        assert!(posts.iter().all(|post| app.world().get::<BlueprintInstance>(post).unwrap().blueprint == "door_frame_post"));
+
+        assert!(app.world().resource::<BlueprintSpawnEventQueue>().iter().any(|event| {
+            matches!(event, BlueprintSpawnEvent::Failed { blueprint, .. } if blueprint == "missing")
+        }));
+
+        // Breaking the crate queues its loot for the next frame
+        let loot_crate = spawned(&app, "loot_crate")[0];
+        app.world_mut().get_mut::<Health>(loot_crate).unwrap().is_dead = true;
+        app.update();
+        app.update();
+        assert_eq!(spawned(&app, "health_pickup").len(), 2);
+    }
+}
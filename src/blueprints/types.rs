@@ -0,0 +1,344 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use crate::interaction::InteractionType;
+use crate::pickups::PickupKind;
+#[cfg(feature = "vehicles")]
+use crate::vehicles::VehicleType;
+use crate::utils::EventQueue;
+
+/// Primitive shape used by blueprint meshes and colliders
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "shape")]
+pub enum BlueprintShape {
+    Box { size: Vec3 },
+    Sphere { radius: f32 },
+    Capsule { radius: f32, height: f32 },
+    Cylinder { radius: f32, height: f32 },
+}
+
+/// Physics body kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum BlueprintBody {
+    #[default]
+    Dynamic,
+    Static,
+    Kinematic,
+}
+
+/// One loot roll of a `Loot` component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct BlueprintLootEntry {
+    /// Blueprint spawned when the roll succeeds
+    pub blueprint: String,
+    #[serde(default = "default_loot_chance")]
+    pub chance: f32,
+    #[serde(default = "default_loot_count")]
+    pub count: u32,
+}
+
+fn default_loot_chance() -> f32 {
+    1.0
+}
+
+fn default_loot_count() -> u32 {
+    1
+}
+
+/// Component template. Every numeric or string field may be written as
+/// `"$parameter"` in JSON and is resolved from the blueprint parameters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum BlueprintComponent {
+    Name { name: String },
+    Tags { tags: Vec<String> },
+    Health {
+        maximum: f32,
+        #[serde(default)]
+        can_regenerate: bool,
+    },
+    /// AI brain (`AiController`)
+    Ai {
+        detection_range: Option<f32>,
+        attack_range: Option<f32>,
+        wander_radius: Option<f32>,
+        #[serde(default)]
+        patrol_path: Vec<Vec3>,
+    },
+    /// Character controller with its physics body
+    Character {
+        walk_speed: Option<f32>,
+        run_speed: Option<f32>,
+    },
+    MeleeCombat {
+        damage: f32,
+        range: Option<f32>,
+    },
+    /// Blueprints dropped when the entity dies
+    Loot { drops: Vec<BlueprintLootEntry> },
+    Pickup {
+        kind: PickupKind,
+        #[serde(default = "default_pickup_amount")]
+        amount: i32,
+    },
+    Interactable {
+        text: String,
+        distance: Option<f32>,
+        interaction_type: InteractionType,
+    },
+    Door {
+        #[serde(default)]
+        locked: bool,
+        open_speed: Option<f32>,
+        /// Close automatically after this many seconds
+        close_after: Option<f32>,
+        #[serde(default)]
+        tags_to_open: Vec<String>,
+    },
+    /// Builds the entity as a preset vehicle (wheels, seats and body included)
+    #[cfg(feature = "vehicles")]
+    Vehicle {
+        vehicle_type: VehicleType,
+        name: Option<String>,
+    },
+    RigidBody { body: BlueprintBody },
+    Collider(BlueprintShape),
+    Mesh {
+        #[serde(flatten)]
+        shape: BlueprintShape,
+        #[serde(default = "default_mesh_color")]
+        color: [f32; 3],
+    },
+    /// Prefab name from `PrefabRegistry`, or a scene asset path
+    Scene { scene: String },
+}
+
+fn default_pickup_amount() -> i32 {
+    1
+}
+
+fn default_mesh_color() -> [f32; 3] {
+    [0.8, 0.8, 0.8]
+}
+
+/// Nested blueprint spawned as a child
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlueprintChild {
+    pub blueprint: String,
+    #[serde(default)]
+    pub translation: Vec3,
+    /// Euler angles in degrees (YXZ)
+    #[serde(default)]
+    pub rotation: Vec3,
+    /// Parameter overrides; values may reference the parent's `"$parameters"`
+    #[serde(default)]
+    pub overrides: HashMap<String, Value>,
+}
+
+/// Serde-defined entity template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlueprintDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Default parameter values, overridable at spawn time
+    #[serde(default)]
+    pub parameters: HashMap<String, Value>,
+    /// `BlueprintComponent`s kept as JSON until parameters are resolved
+    #[serde(default)]
+    pub components: Vec<Value>,
+    #[serde(default)]
+    pub children: Vec<BlueprintChild>,
+}
+
+impl BlueprintDefinition {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|error| format!("Invalid blueprint: {}", error))
+    }
+
+    /// Merged parameters: defaults, then `overrides`
+    pub fn parameters_with(&self, overrides: &HashMap<String, Value>) -> HashMap<String, Value> {
+        let mut parameters = self.parameters.clone();
+        for (key, value) in overrides {
+            parameters.insert(key.clone(), value.clone());
+        }
+        parameters
+    }
+
+    /// Components with `"$parameter"` references substituted
+    pub fn resolve_components(&self, parameters: &HashMap<String, Value>) -> Result<Vec<BlueprintComponent>, String> {
+        self.components
+            .iter()
+            .map(|component| {
+                let resolved = substitute_parameters(component, parameters);
+                serde_json::from_value(resolved)
+                    .map_err(|error| format!("Blueprint '{}': invalid component {}: {}", self.name, component, error))
+            })
+            .collect()
+    }
+}
+
+/// Replaces every `"$name"` string in `value` with the parameter `name`
+pub fn substitute_parameters(value: &Value, parameters: &HashMap<String, Value>) -> Value {
+    match value {
+        Value::String(text) => text
+            .strip_prefix('$')
+            .and_then(|name| parameters.get(name))
+            .cloned()
+            .unwrap_or_else(|| value.clone()),
+        Value::Array(items) => Value::Array(items.iter().map(|item| substitute_parameters(item, parameters)).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, item)| (key.clone(), substitute_parameters(item, parameters)))
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+/// Registered blueprints by name
+#[derive(Resource, Debug, Default)]
+pub struct BlueprintRegistry {
+    pub definitions: HashMap<String, BlueprintDefinition>,
+}
+
+impl BlueprintRegistry {
+    /// Registers a blueprint after checking that its default parameters resolve
+    pub fn register(&mut self, definition: BlueprintDefinition) -> Result<(), String> {
+        definition.resolve_components(&definition.parameters)?;
+        self.definitions.insert(definition.name.clone(), definition);
+        Ok(())
+    }
+
+    /// Registers one blueprint object or an array of them
+    pub fn register_json(&mut self, json: &str) -> Result<usize, String> {
+        let value: Value = serde_json::from_str(json).map_err(|error| format!("Invalid blueprint JSON: {}", error))?;
+        let values = match value {
+            Value::Array(values) => values,
+            value => vec![value],
+        };
+
+        let count = values.len();
+        for value in values {
+            let definition: BlueprintDefinition =
+                serde_json::from_value(value).map_err(|error| format!("Invalid blueprint: {}", error))?;
+            self.register(definition)?;
+        }
+        Ok(count)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&BlueprintDefinition> {
+        self.definitions.get(name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.definitions.keys().map(String::as_str).collect();
+        names.sort();
+        names
+    }
+}
+
+/// Blueprint settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct BlueprintSettings {
+    /// Folder scanned for `*.json` blueprints at startup (empty = none)
+    pub directory: String,
+    pub register_builtin: bool,
+    /// Guards against blueprints that contain themselves
+    pub max_child_depth: u32,
+}
+
+impl Default for BlueprintSettings {
+    fn default() -> Self {
+        Self {
+            directory: "assets/blueprints".to_string(),
+            register_builtin: true,
+            max_child_depth: 8,
+        }
+    }
+}
+
+/// Request to spawn a blueprint
+#[derive(Debug, Clone)]
+pub struct BlueprintSpawnRequest {
+    pub blueprint: String,
+    pub transform: Transform,
+    pub overrides: HashMap<String, Value>,
+    pub parent: Option<Entity>,
+}
+
+impl BlueprintSpawnRequest {
+    pub fn new(blueprint: impl Into<String>, transform: Transform) -> Self {
+        Self {
+            blueprint: blueprint.into(),
+            transform,
+            overrides: HashMap::new(),
+            parent: None,
+        }
+    }
+
+    pub fn with_override(mut self, parameter: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.overrides.insert(parameter.into(), value.into());
+        self
+    }
+
+    /// Parses a console line: `spawn <blueprint> [x y z] [parameter=value ...]`.
+    /// Values are read as JSON when possible (`health=250`, `locked=true`), else as strings.
+    pub fn from_console(line: &str) -> Result<Self, String> {
+        let mut words = line.split_whitespace();
+        if words.next() != Some("spawn") {
+            return Err("Usage: spawn <blueprint> [x y z] [parameter=value ...]".to_string());
+        }
+        let blueprint = words.next().ok_or("Missing blueprint name")?;
+
+        let mut request = Self::new(blueprint, Transform::default());
+        let mut coordinates = Vec::new();
+        for word in words {
+            if let Some((key, value)) = word.split_once('=') {
+                let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+                request.overrides.insert(key.to_string(), value);
+            } else {
+                let coordinate: f32 = word.parse().map_err(|_| format!("Invalid coordinate '{}'", word))?;
+                coordinates.push(coordinate);
+            }
+        }
+
+        match coordinates.as_slice() {
+            [] => {}
+            [x, y, z] => request.transform.translation = Vec3::new(*x, *y, *z),
+            _ => return Err("Expected three coordinates".to_string()),
+        }
+        Ok(request)
+    }
+}
+
+/// Custom queue for blueprint spawn requests
+pub type BlueprintSpawnQueue = EventQueue<BlueprintSpawnRequest>;
+
+/// Root entity spawned from a blueprint
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BlueprintInstance {
+    pub blueprint: String,
+}
+
+/// Loot dropped by a blueprint entity on death
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct BlueprintLootTable {
+    pub drops: Vec<BlueprintLootEntry>,
+    pub scatter_radius: f32,
+    pub dropped: bool,
+}
+
+/// Blueprint spawn result
+#[derive(Debug, Clone)]
+pub enum BlueprintSpawnEvent {
+    Spawned { blueprint: String, entity: Entity },
+    Failed { blueprint: String, error: String },
+}
+
+/// Custom queue for blueprint spawn results
+pub type BlueprintSpawnEventQueue = EventQueue<BlueprintSpawnEvent>;
@@ -0,0 +1,67 @@
+//! Boss encounters
+//!
+//! A [`Boss`] fights in [`BossPhase`]s: when its health drops to a phase's
+//! threshold it switches AI strategy, attack range and telegraphed attacks
+//! (see `combat::AttackTelegraphs`), briefly invulnerable while it does.
+//! Damage fills its posture bar; a full bar staggers the boss and opens a
+//! finisher window in which a player pressing interact nearby deals a large
+//! share of its health.
+//!
+//! A [`BossArena`] on the boss starts the encounter when a player walks in,
+//! locks its doors and keeps the players (and the boss) inside until one side
+//! dies. Every step is sent as a [`BossEvent`], for music, doors and cutscenes:
+//!
+//! ```rust,ignore
+//! commands.spawn((
+//!     Boss::new("Stone Warden", vec![
+//!         BossPhase::new("Guard", 1.0),
+//!         BossPhase::new("Fury", 0.5).with_strategy(AiCombatStrategy::Powers),
+//!     ]),
+//!     BossArena::new(Vec3::new(0.0, 0.0, 40.0), 18.0).with_doors(vec![gate]),
+//! ));
+//!
+//! for event in boss_events.read() {
+//!     if let BossEvent::EncounterStarted { .. } = event {
+//!         // Boss music...
+//!     }
+//! }
+//! ```
+
+pub mod types;
+pub mod systems;
+pub mod ui;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use ui::*;
+use crate::simulation::SimulationAppExt;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    BossPhase, Boss, BossArena, BossSettings, BossCommand, BossEvent, BossCommandQueue, BossEventQueue,
+};
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        let simulation = app.simulation_schedule();
+
+        app
+            .init_resource::<BossSettings>()
+            .add_plugins(EventQueuePlugin::<BossCommand>::default())
+            .add_plugins(EventQueuePlugin::<BossEvent>::default())
+            .register_type::<Boss>()
+            .register_type::<BossArena>()
+            .register_type::<BossSettings>()
+            .add_systems(Startup, setup_boss_hud.in_set(crate::headless::PresentationSet))
+            .add_systems(simulation, (
+                update_boss_encounters,
+                update_boss_combat,
+                trigger_boss_finishers,
+                enforce_boss_arenas,
+            ).chain().in_set(crate::combat::CombatSet::Aftermath))
+            .add_systems(Update, update_boss_hud.in_set(crate::headless::PresentationSet));
+    }
+}
@@ -0,0 +1,300 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::ai::{AiCombatBrain, AiController};
+use crate::character::Player;
+use crate::combat::{AttackTelegraphs, DamageEvent, DamageEventQueue, DamageResultEvent, DamageType, Health, Staggered};
+use crate::devices::types::DoorSystem;
+use crate::input::InputState;
+use crate::utils::QueueReader;
+use super::types::*;
+
+type BossParts<'a> = (
+    &'a mut Boss,
+    &'a mut Health,
+    Option<&'a mut AiCombatBrain>,
+    Option<&'a mut AttackTelegraphs>,
+    Option<&'a mut AiController>,
+);
+
+fn apply_phase(
+    boss_entity: Entity,
+    (boss, health, brain, telegraphs, controller): BossParts,
+    phase: usize,
+    events: &mut BossEventQueue,
+) {
+    boss.current_phase = phase;
+    let Some(phase) = boss.phases.get(phase).cloned() else { return };
+    if let (Some(strategy), Some(brain)) = (phase.strategy, brain) {
+        brain.strategy = strategy;
+    }
+    if let Some(telegraphs) = telegraphs {
+        if !phase.telegraphs.is_empty() {
+            telegraphs.telegraphs = phase.telegraphs.clone();
+            telegraphs.next = 0;
+        }
+    }
+    if let (Some(range), Some(controller)) = (phase.attack_range, controller) {
+        controller.attack_range = range;
+    }
+    health.temporal_invincibility_timer = health.temporal_invincibility_timer.max(phase.transition_invulnerability);
+    events.send(BossEvent::PhaseChanged {
+        boss: boss_entity,
+        phase: boss.current_phase,
+        name: phase.name,
+    });
+}
+
+fn set_doors_locked(arena: &BossArena, door_query: &mut Query<&mut DoorSystem>, locked: bool) {
+    for door in arena.doors.iter() {
+        if let Ok(mut door) = door_query.get_mut(*door) {
+            door.locked = locked;
+        }
+    }
+}
+
+/// Queues written by boss commands
+#[derive(SystemParam)]
+pub struct BossCommandOutputs<'w> {
+    pub events: ResMut<'w, BossEventQueue>,
+    pub damage_queue: ResMut<'w, DamageEventQueue>,
+}
+
+type EncounterBoss<'a> = (
+    Entity,
+    &'a GlobalTransform,
+    &'a mut Boss,
+    &'a mut Health,
+    Option<&'a mut BossArena>,
+    Option<&'a mut AiCombatBrain>,
+    Option<&'a mut AttackTelegraphs>,
+    Option<&'a mut AiController>,
+);
+type PlayerNotBoss = (With<Player>, Without<Boss>);
+
+/// System to start encounters when a living player walks into an arena, and to run boss commands
+pub fn update_boss_encounters(
+    settings: Res<BossSettings>,
+    mut commands: Commands,
+    mut boss_commands: QueueReader<BossCommand>,
+    outputs: BossCommandOutputs,
+    mut boss_query: Query<EncounterBoss>,
+    player_query: Query<(&GlobalTransform, Option<&Health>), PlayerNotBoss>,
+    mut door_query: Query<&mut DoorSystem>,
+) {
+    let BossCommandOutputs { mut events, mut damage_queue } = outputs;
+    let mut pending: Vec<BossCommand> = boss_commands.read().cloned().collect();
+
+    for (entity, _, _, health, arena, ..) in boss_query.iter() {
+        let Some(arena) = arena else { continue };
+        if arena.active || health.is_dead {
+            continue;
+        }
+        let entered = player_query.iter().any(|(transform, player_health)| {
+            !player_health.is_some_and(|health| health.is_dead)
+                && arena.is_inside(transform.translation(), arena.trigger_radius)
+        });
+        if entered {
+            pending.push(BossCommand::StartEncounter { boss: entity });
+        }
+    }
+
+    for command in pending {
+        match command {
+            BossCommand::StartEncounter { boss } => {
+                let Ok((_, _, _, _, arena, ..)) = boss_query.get_mut(boss) else { continue };
+                if let Some(mut arena) = arena {
+                    if arena.active {
+                        continue;
+                    }
+                    arena.active = true;
+                    set_doors_locked(&arena, &mut door_query, true);
+                }
+                events.send(BossEvent::EncounterStarted { boss });
+            }
+            BossCommand::EndEncounter { boss, victory } => {
+                let Ok((_, _, mut boss_data, mut health, arena, brain, telegraphs, controller)) = boss_query.get_mut(boss) else { continue };
+                if let Some(mut arena) = arena {
+                    if !arena.active {
+                        continue;
+                    }
+                    arena.active = false;
+                    set_doors_locked(&arena, &mut door_query, false);
+                }
+                if !victory && settings.reset_on_defeat && !health.is_dead {
+                    health.current = health.maximum;
+                    boss_data.reset();
+                    commands.entity(boss).remove::<Staggered>();
+                    apply_phase(
+                        boss,
+                        (
+                            &mut *boss_data,
+                            &mut *health,
+                            brain.map(Mut::into_inner),
+                            telegraphs.map(Mut::into_inner),
+                            controller.map(Mut::into_inner),
+                        ),
+                        0,
+                        &mut events,
+                    );
+                }
+                events.send(BossEvent::EncounterEnded { boss, victory });
+            }
+            BossCommand::Finisher { boss, source } => {
+                let Ok((_, transform, mut boss_data, health, ..)) = boss_query.get_mut(boss) else { continue };
+                if !boss_data.is_finisher_open() || health.is_dead {
+                    continue;
+                }
+                boss_data.finisher_remaining = 0.0;
+                let damage = health.maximum * boss_data.finisher_damage;
+                commands.entity(boss).remove::<Staggered>();
+                damage_queue.send(DamageEvent {
+                    amount: damage,
+                    damage_type: DamageType::Melee,
+                    source: Some(source),
+                    target: boss,
+                    position: Some(transform.translation()),
+                    direction: None,
+                    ignore_shield: true,
+                });
+                events.send(BossEvent::Finisher { boss, source, damage });
+            }
+        }
+    }
+}
+
+type CombatBoss<'a> = (
+    Entity,
+    &'a mut Boss,
+    &'a mut Health,
+    Option<&'a mut AiCombatBrain>,
+    Option<&'a mut AttackTelegraphs>,
+    Option<&'a mut AiController>,
+);
+
+/// System to build posture from the damage bosses take, stagger them when it
+/// breaks and move them through their phases
+pub fn update_boss_combat(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut damage_results: QueueReader<DamageResultEvent>,
+    mut events: ResMut<BossEventQueue>,
+    mut boss_query: Query<CombatBoss>,
+) {
+    for result in damage_results.read() {
+        let Ok((entity, mut boss, health, ..)) = boss_query.get_mut(result.target) else { continue };
+        if health.is_dead {
+            continue;
+        }
+        let posture = if result.is_block {
+            result.original_amount * boss.posture_per_blocked
+        } else {
+            result.final_amount * boss.posture_per_damage
+        };
+        if boss.add_posture(posture) {
+            commands.entity(entity).insert(Staggered { remaining: boss.finisher_window });
+            events.send(BossEvent::PostureBroken { boss: entity });
+        }
+    }
+
+    let dt = time.delta_secs();
+    for (entity, mut boss, mut health, brain, telegraphs, controller) in boss_query.iter_mut() {
+        if boss.tick(dt) {
+            events.send(BossEvent::FinisherWindowClosed { boss: entity });
+        }
+        if health.is_dead || health.maximum <= 0.0 {
+            continue;
+        }
+        let phase = boss.phase_for(health.current / health.maximum);
+        // Healing never sends a boss back to an earlier phase
+        if phase > boss.current_phase {
+            apply_phase(
+                entity,
+                (
+                    &mut *boss,
+                    &mut *health,
+                    brain.map(Mut::into_inner),
+                    telegraphs.map(Mut::into_inner),
+                    controller.map(Mut::into_inner),
+                ),
+                phase,
+                &mut events,
+            );
+        }
+    }
+}
+
+/// System to let players finish a staggered boss with the interact input
+pub fn trigger_boss_finishers(
+    settings: Res<BossSettings>,
+    mut boss_commands: ResMut<BossCommandQueue>,
+    player_query: Query<(Entity, &GlobalTransform, &InputState), With<Player>>,
+    boss_query: Query<(Entity, &GlobalTransform, &Boss)>,
+) {
+    for (player, player_transform, input) in player_query.iter() {
+        if !input.interact_pressed {
+            continue;
+        }
+        let in_reach = boss_query.iter().find(|(_, transform, boss)| {
+            boss.is_finisher_open()
+                && transform.translation().distance(player_transform.translation()) <= settings.finisher_range
+        });
+        if let Some((boss, ..)) = in_reach {
+            boss_commands.send(BossCommand::Finisher { boss, source: player });
+        }
+    }
+}
+
+type ArenaBoss<'a> = (Entity, &'a BossArena, &'a Health, &'a mut Transform, Option<&'a mut LinearVelocity>);
+type ArenaPlayer<'a> = (&'a mut Transform, Option<&'a mut LinearVelocity>, Option<&'a Health>);
+
+/// System to keep players and leashed bosses inside active arenas, and to end
+/// encounters when the boss or every player in the arena dies
+pub fn enforce_boss_arenas(
+    mut boss_commands: ResMut<BossCommandQueue>,
+    mut boss_query: Query<ArenaBoss, With<Boss>>,
+    mut player_query: Query<ArenaPlayer, PlayerNotBoss>,
+) {
+    for (boss, arena, boss_health, mut boss_transform, boss_velocity) in boss_query.iter_mut() {
+        if !arena.active {
+            continue;
+        }
+        if boss_health.is_dead {
+            boss_commands.send(BossCommand::EndEncounter { boss, victory: true });
+            continue;
+        }
+
+        let mut any_alive = false;
+        for (mut transform, velocity, health) in player_query.iter_mut() {
+            if health.is_some_and(|health| health.is_dead) {
+                continue;
+            }
+            // Players outside the wall when the fight starts are not part of it
+            if !arena.is_inside(transform.translation, arena.radius + 2.0) {
+                continue;
+            }
+            any_alive = true;
+            keep_inside(arena, &mut transform, velocity);
+        }
+        if !any_alive {
+            boss_commands.send(BossCommand::EndEncounter { boss, victory: false });
+            continue;
+        }
+
+        if arena.leash_boss {
+            keep_inside(arena, &mut boss_transform, boss_velocity);
+        }
+    }
+}
+
+fn keep_inside(arena: &BossArena, transform: &mut Transform, velocity: Option<Mut<LinearVelocity>>) {
+    let Some(clamped) = arena.clamp(transform.translation) else { return };
+    transform.translation = clamped;
+    if let Some(mut velocity) = velocity {
+        let outward = Vec3::new(clamped.x - arena.center.x, 0.0, clamped.z - arena.center.z).normalize_or_zero();
+        let speed = velocity.0.dot(outward);
+        if speed > 0.0 {
+            velocity.0 -= outward * speed;
+        }
+    }
+}
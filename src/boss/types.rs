@@ -0,0 +1,302 @@
+use bevy::prelude::*;
+use crate::ai::AiCombatStrategy;
+use crate::combat::TelegraphDefinition;
+use crate::utils::EventQueue;
+
+/// Stage of a boss fight, entered once health drops to its threshold
+#[derive(Debug, Clone, Reflect)]
+pub struct BossPhase {
+    pub name: String,
+    /// Fraction of maximum health (0-1) at or below which the phase starts
+    pub health_threshold: f32,
+    /// Combat strategy the boss's AI switches to
+    pub strategy: Option<AiCombatStrategy>,
+    /// Telegraphed attacks of the phase; replaces the boss's `AttackTelegraphs` when not empty
+    pub telegraphs: Vec<TelegraphDefinition>,
+    pub attack_range: Option<f32>,
+    /// Seconds of invulnerability while the boss changes phase
+    pub transition_invulnerability: f32,
+}
+
+impl Default for BossPhase {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            health_threshold: 1.0,
+            strategy: None,
+            telegraphs: Vec::new(),
+            attack_range: None,
+            transition_invulnerability: 1.5,
+        }
+    }
+}
+
+impl BossPhase {
+    pub fn new(name: &str, health_threshold: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            health_threshold,
+            ..default()
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: AiCombatStrategy) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+
+    pub fn with_telegraphs(mut self, telegraphs: Vec<TelegraphDefinition>) -> Self {
+        self.telegraphs = telegraphs;
+        self
+    }
+}
+
+/// Boss with phases and a posture bar. Damage fills the posture; a full bar
+/// staggers the boss and opens a finisher window.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Boss {
+    pub name: String,
+    /// Ordered by decreasing `health_threshold`, the first one at 1.0
+    pub phases: Vec<BossPhase>,
+    pub current_phase: usize,
+    pub posture: f32,
+    pub max_posture: f32,
+    /// Posture per point of damage taken
+    pub posture_per_damage: f32,
+    /// Extra posture per point of damage the boss blocks
+    pub posture_per_blocked: f32,
+    /// Posture lost per second once `posture_decay_delay` passed without a hit
+    pub posture_decay: f32,
+    pub posture_decay_delay: f32,
+    pub since_posture_hit: f32,
+    /// Seconds the boss stays staggered and open to a finisher
+    pub finisher_window: f32,
+    pub finisher_remaining: f32,
+    /// Damage of a finisher, as a fraction of maximum health
+    pub finisher_damage: f32,
+}
+
+impl Default for Boss {
+    fn default() -> Self {
+        Self {
+            name: "Boss".to_string(),
+            phases: vec![BossPhase::new("Phase 1", 1.0)],
+            current_phase: 0,
+            posture: 0.0,
+            max_posture: 100.0,
+            posture_per_damage: 1.0,
+            posture_per_blocked: 1.5,
+            posture_decay: 10.0,
+            posture_decay_delay: 2.0,
+            since_posture_hit: 0.0,
+            finisher_window: 3.0,
+            finisher_remaining: 0.0,
+            finisher_damage: 0.2,
+        }
+    }
+}
+
+impl Boss {
+    pub fn new(name: &str, phases: Vec<BossPhase>) -> Self {
+        Self {
+            name: name.to_string(),
+            phases,
+            ..default()
+        }
+    }
+
+    /// Last phase whose threshold `health_fraction` has reached
+    pub fn phase_for(&self, health_fraction: f32) -> usize {
+        self.phases
+            .iter()
+            .rposition(|phase| health_fraction <= phase.health_threshold)
+            .unwrap_or(0)
+    }
+
+    pub fn phase(&self) -> Option<&BossPhase> {
+        self.phases.get(self.current_phase)
+    }
+
+    pub fn is_finisher_open(&self) -> bool {
+        self.finisher_remaining > 0.0
+    }
+
+    /// Adds posture, returning true when it breaks. A broken posture resets and
+    /// opens the finisher window; no posture builds while the window is open.
+    pub fn add_posture(&mut self, amount: f32) -> bool {
+        if amount <= 0.0 || self.is_finisher_open() {
+            return false;
+        }
+        self.since_posture_hit = 0.0;
+        self.posture = (self.posture + amount).min(self.max_posture);
+        if self.posture < self.max_posture {
+            return false;
+        }
+        self.posture = 0.0;
+        self.finisher_remaining = self.finisher_window;
+        true
+    }
+
+    /// Ticks posture decay and the finisher window, returning true when the window closes
+    pub fn tick(&mut self, dt: f32) -> bool {
+        self.since_posture_hit += dt;
+        if self.since_posture_hit >= self.posture_decay_delay {
+            self.posture = (self.posture - self.posture_decay * dt).max(0.0);
+        }
+        if !self.is_finisher_open() {
+            return false;
+        }
+        self.finisher_remaining = (self.finisher_remaining - dt).max(0.0);
+        self.finisher_remaining <= 0.0
+    }
+
+    /// Back to the first phase with an empty posture bar
+    pub fn reset(&mut self) {
+        self.current_phase = 0;
+        self.posture = 0.0;
+        self.since_posture_hit = 0.0;
+        self.finisher_remaining = 0.0;
+    }
+}
+
+/// Area a boss fight takes place in, on the boss entity. Players coming within
+/// `trigger_radius` of the center start the encounter; while it runs, players
+/// (and the boss, when leashed) are kept inside `radius` and the doors are locked.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BossArena {
+    pub center: Vec3,
+    pub radius: f32,
+    pub trigger_radius: f32,
+    /// `DoorSystem` entities locked for the fight
+    pub doors: Vec<Entity>,
+    pub leash_boss: bool,
+    pub active: bool,
+}
+
+impl Default for BossArena {
+    fn default() -> Self {
+        Self {
+            center: Vec3::ZERO,
+            radius: 20.0,
+            trigger_radius: 15.0,
+            doors: Vec::new(),
+            leash_boss: true,
+            active: false,
+        }
+    }
+}
+
+impl BossArena {
+    pub fn new(center: Vec3, radius: f32) -> Self {
+        Self {
+            center,
+            radius,
+            trigger_radius: radius * 0.75,
+            ..default()
+        }
+    }
+
+    pub fn with_doors(mut self, doors: Vec<Entity>) -> Self {
+        self.doors = doors;
+        self
+    }
+
+    pub fn is_inside(&self, position: Vec3, radius: f32) -> bool {
+        let offset = position - self.center;
+        Vec2::new(offset.x, offset.z).length() <= radius
+    }
+
+    /// Position pulled back onto the boundary, or None when already inside
+    pub fn clamp(&self, position: Vec3) -> Option<Vec3> {
+        let offset = Vec2::new(position.x - self.center.x, position.z - self.center.z);
+        if offset.length() <= self.radius {
+            return None;
+        }
+        let inside = offset.normalize() * self.radius;
+        Some(Vec3::new(self.center.x + inside.x, position.y, self.center.z + inside.y))
+    }
+}
+
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct BossSettings {
+    /// Players pressing interact within this distance of a staggered boss perform the finisher
+    pub finisher_range: f32,
+    /// Restore the boss when every player in the arena dies
+    pub reset_on_defeat: bool,
+}
+
+impl Default for BossSettings {
+    fn default() -> Self {
+        Self {
+            finisher_range: 3.0,
+            reset_on_defeat: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BossCommand {
+    StartEncounter { boss: Entity },
+    EndEncounter { boss: Entity, victory: bool },
+    Finisher { boss: Entity, source: Entity },
+}
+
+/// Boss fight progress, for music, doors, cutscenes and achievements
+#[derive(Debug, Clone, PartialEq)]
+pub enum BossEvent {
+    EncounterStarted { boss: Entity },
+    EncounterEnded { boss: Entity, victory: bool },
+    PhaseChanged { boss: Entity, phase: usize, name: String },
+    PostureBroken { boss: Entity },
+    FinisherWindowClosed { boss: Entity },
+    Finisher { boss: Entity, source: Entity, damage: f32 },
+}
+
+pub type BossCommandQueue = EventQueue<BossCommand>;
+pub type BossEventQueue = EventQueue<BossEvent>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_boss_phases_and_posture() {
+        let mut warden = Boss::new("Warden", vec![
+            BossPhase::new("Guard", 1.0),
+            BossPhase::new("Fury", 0.6),
+            BossPhase::new("Desperation", 0.25),
+        ]);
+        assert_eq!(warden.phase_for(1.0), 0);
+        assert_eq!(warden.phase_for(0.6), 1);
+        assert_eq!(warden.phase_for(0.4), 1);
+        assert_eq!(warden.phase_for(0.1), 2);
+
+        // A full posture bar opens the finisher window and stops building until it closes
+        warden.max_posture = 50.0;
+        assert!(!warden.add_posture(30.0));
+        assert!(warden.add_posture(30.0));
+        assert!(warden.is_finisher_open());
+        assert_eq!(warden.posture, 0.0);
+        assert!(!warden.add_posture(100.0));
+        assert!(!warden.tick(warden.finisher_window - 0.5));
+        assert!(warden.tick(1.0));
+        assert!(!warden.is_finisher_open());
+
+        // Posture drains once the boss goes a while without being hit
+        warden.add_posture(20.0);
+        warden.tick(1.0);
+        assert_eq!(warden.posture, 20.0);
+        warden.tick(1.0);
+        assert!(warden.posture < 20.0);
+
+        let arena = BossArena::new(Vec3::ZERO, 10.0);
+        assert!(arena.clamp(Vec3::new(5.0, 1.0, 0.0)).is_none());
+        assert_eq!(arena.clamp(Vec3::new(0.0, 2.0, -15.0)), Some(Vec3::new(0.0, 2.0, -10.0)));
+        assert!(arena.is_inside(Vec3::new(7.0, 0.0, 0.0), arena.trigger_radius));
+        assert!(!arena.is_inside(Vec3::new(8.0, 0.0, 0.0), arena.trigger_radius));
+    }
+}
@@ -0,0 +1,176 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use crate::combat::Health;
+use super::types::*;
+
+/// Boss health bar across the top of the screen, shown during an encounter
+#[derive(Component)]
+pub struct BossHudRoot;
+
+#[derive(Component)]
+pub struct BossHudName;
+
+#[derive(Component)]
+pub struct BossHealthFill;
+
+#[derive(Component)]
+pub struct BossPostureFill;
+
+/// Track the phase pips are placed in, rebuilt for each boss
+#[derive(Component)]
+pub struct BossPhasePipTrack;
+
+/// Marker at a phase's health threshold, lit once the phase is reached
+#[derive(Component)]
+pub struct BossPhasePip(pub usize);
+
+const PIP_DIM: Color = Color::srgba(1.0, 1.0, 1.0, 0.35);
+const PIP_LIT: Color = Color::srgb(1.0, 0.75, 0.2);
+
+/// System to setup the boss health bar (hidden by default)
+pub fn setup_boss_hud(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(24.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            BossHudRoot,
+            Visibility::Hidden,
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Text::new(""),
+                TextFont { font_size: 22.0, ..default() },
+                TextColor(Color::WHITE),
+                BossHudName,
+            ));
+            root.spawn((
+                Node {
+                    width: Val::Px(560.0),
+                    height: Val::Px(16.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.1, 0.1, 0.12, 0.85)),
+            ))
+            .with_children(|bar| {
+                bar.spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.75, 0.12, 0.1)),
+                    BossHealthFill,
+                ));
+                bar.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BossPhasePipTrack,
+                ));
+            });
+            root.spawn((
+                Node {
+                    width: Val::Px(280.0),
+                    height: Val::Px(6.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.1, 0.1, 0.12, 0.85)),
+            ))
+            .with_children(|bar| {
+                bar.spawn((
+                    Node {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.95, 0.8, 0.3)),
+                    BossPostureFill,
+                ));
+            });
+        });
+}
+
+type BossPosturePart = (With<BossPostureFill>, Without<BossPhasePip>);
+
+/// Name, bars and phase pips of the boss HUD
+#[derive(SystemParam)]
+pub struct BossHudWidgets<'w, 's> {
+    pub name_query: Query<'w, 's, &'static mut Text, With<BossHudName>>,
+    pub health_query: Query<'w, 's, &'static mut Node, (With<BossHealthFill>, Without<BossPostureFill>)>,
+    pub posture_query: Query<'w, 's, (&'static mut Node, &'static mut BackgroundColor), BossPosturePart>,
+    pub track_query: Query<'w, 's, Entity, With<BossPhasePipTrack>>,
+    pub pip_query: Query<'w, 's, (Entity, &'static BossPhasePip, &'static mut BackgroundColor), Without<BossPostureFill>>,
+}
+
+/// System to show the health, phase and posture of the boss being fought
+pub fn update_boss_hud(
+    mut commands: Commands,
+    mut shown: Local<Option<Entity>>,
+    boss_query: Query<(Entity, &Boss, &Health, &BossArena)>,
+    mut root_query: Query<&mut Visibility, With<BossHudRoot>>,
+    widgets: BossHudWidgets,
+) {
+    let BossHudWidgets { mut name_query, mut health_query, mut posture_query, track_query, mut pip_query } = widgets;
+    let Ok(mut visibility) = root_query.single_mut() else { return };
+    let Some((entity, boss, health, _)) = boss_query.iter().find(|(_, _, _, arena)| arena.active) else {
+        visibility.set_if_neq(Visibility::Hidden);
+        *shown = None;
+        return;
+    };
+    visibility.set_if_neq(Visibility::Visible);
+
+    if *shown != Some(entity) {
+        *shown = Some(entity);
+        for mut text in name_query.iter_mut() {
+            text.0 = boss.name.clone();
+        }
+        for (pip, ..) in pip_query.iter() {
+            commands.entity(pip).despawn();
+        }
+        // The first phase starts at full health and needs no pip
+        for track in track_query.iter() {
+            commands.entity(track).with_children(|track| {
+                for (index, phase) in boss.phases.iter().enumerate().skip(1) {
+                    track.spawn((
+                        Node {
+                            position_type: PositionType::Absolute,
+                            left: Val::Percent(phase.health_threshold.clamp(0.0, 1.0) * 100.0),
+                            width: Val::Px(3.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(PIP_DIM),
+                        BossPhasePip(index),
+                    ));
+                }
+            });
+        }
+    }
+
+    let fraction = if health.maximum > 0.0 { (health.current / health.maximum).clamp(0.0, 1.0) } else { 0.0 };
+    for mut node in health_query.iter_mut() {
+        node.width = Val::Percent(fraction * 100.0);
+    }
+    for (mut node, mut background) in posture_query.iter_mut() {
+        if boss.is_finisher_open() {
+            node.width = Val::Percent(100.0);
+            background.0 = Color::srgb(1.0, 0.35, 0.2);
+        } else {
+            node.width = Val::Percent((boss.posture / boss.max_posture.max(0.01)).clamp(0.0, 1.0) * 100.0);
+            background.0 = Color::srgb(0.95, 0.8, 0.3);
+        }
+    }
+    for (_, pip, mut background) in pip_query.iter_mut() {
+        background.0 = if pip.0 <= boss.current_phase { PIP_LIT } else { PIP_DIM };
+    }
+}
@@ -0,0 +1,97 @@
+//! Base building
+//!
+//! Construction mode shows a ghost of the selected [`BuildPieceDefinition`]
+//! where the player aims. Pieces snap to the [`BuildSocket`]s of what is
+//! already built: walls and doors on the edges of foundations and floors,
+//! floors on top of walls, foundations and floors next to their own kind.
+//! Only foundations stand on the ground, on a grid. Every piece keeps a
+//! stability that drops with each piece between it and the ground; pieces
+//! too far out can't be built.
+//!
+//! Building takes the [`BuildCost`] materials from the builder's inventory and
+//! dismantling gives back `BuildSettings::refund_ratio` of them, top pieces
+//! first. Door pieces carry a `DoorSystem` and swing their panel as it opens
+//! and closes. Built structures are stored in the player's save data.
+//!
+//! ```rust,ignore
+//! build_commands.send(BuildCommand::Enter);
+//! catalog.register(BuildPieceDefinition::wall("stone_wall", "Stone Wall").with_cost("stone", 8));
+//!
+//! for event in build_events.read() {
+//!     if let BuildEvent::Placed { piece_id, .. } = event {
+//!         // Quest objective...
+//!     }
+//! }
+//! ```
+
+pub mod types;
+pub mod systems;
+pub mod ui;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use ui::*;
+use crate::save::SaveAppExt;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    BuildPieceKind, BuildSocket, BuildCost, BuildPieceDefinition, BuildCatalog, PlacementError,
+    PlacementCandidate, BuiltPiece, BuiltDoorHinge, BuiltPieceRecord, BuildingSaveData, BuiltStructures,
+    BuildModeState, BuildSettings, BuildCommand, BuildCommandQueue, BuildEvent, BuildEventQueue,
+    missing_cost, GRID_SIZE,
+};
+pub use systems::spawn_built_piece;
+
+pub struct BuildingPlugin;
+
+impl Plugin for BuildingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<BuildCatalog>()
+            .init_resource::<BuildSettings>()
+            .init_resource::<BuildModeState>()
+            .init_resource::<BuiltStructures>()
+            .add_plugins(EventQueuePlugin::<BuildCommand>::default())
+            .add_plugins(EventQueuePlugin::<BuildEvent>::default())
+            .register_type::<BuildCatalog>()
+            .register_type::<BuildSettings>()
+            .register_type::<BuiltPiece>()
+            .register_type::<BuiltDoorHinge>()
+            .add_systems(Startup, setup_build_hud.in_set(crate::headless::PresentationSet))
+            .add_systems(Update, (
+                handle_build_commands,
+                build_mode_input,
+            ).chain()
+                .in_set(crate::input::InputSet::Gather)
+                .after(crate::input::player_input_sync_system))
+            .add_systems(Update, (
+                update_build_placement,
+                apply_build_actions,
+                prune_built_structures,
+                restore_buildings_from_save,
+                store_buildings_in_save,
+                animate_built_doors,
+            ).chain().after(build_mode_input))
+            .add_systems(Update, (
+                update_build_ghost,
+                update_build_hud,
+            ).after(update_build_placement).in_set(crate::headless::PresentationSet));
+
+        // Saved pieces refer to each other by uid
+        let save_key = app.world().resource::<BuildSettings>().save_key.clone();
+        app.add_save_reference_check("building", move |save| {
+            let Some(value) = save.get_mut("custom_data").and_then(|custom_data| custom_data.get_mut(&save_key)) else {
+                return Vec::new();
+            };
+            let Ok(mut saved) = serde_json::from_value::<BuildingSaveData>(value.clone()) else { return Vec::new() };
+            let removed = saved.remove_orphan_references();
+            if !removed.is_empty() {
+                if let Ok(repaired) = serde_json::to_value(&saved) {
+                    *value = repaired;
+                }
+            }
+            removed
+        });
+    }
+}
@@ -0,0 +1,544 @@
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use std::f32::consts::FRAC_PI_2;
+use crate::ai::NavObstacle;
+use crate::camera::CameraController;
+use crate::character::Player;
+use crate::devices::types::{DoorCurrentState, DoorMovementType, DoorSystem, SingleDoorInfo};
+use crate::input::InputState;
+use crate::inventory::Inventory;
+use crate::save::{ChangedPlayerSaveData, SaveCustomData};
+use crate::utils::QueueReader;
+use super::types::*;
+
+/// Width and height of the opening in a door piece
+const DOOR_OPENING: Vec2 = Vec2::new(1.2, 2.4);
+
+/// Construction settings and the pieces that can be built
+#[derive(SystemParam)]
+pub struct BuildConfig<'w> {
+    pub settings: Res<'w, BuildSettings>,
+    pub catalog: Res<'w, BuildCatalog>,
+}
+
+/// Commands and render assets for spawning built pieces; assets are missing headless
+#[derive(SystemParam)]
+pub struct PieceSpawner<'w, 's> {
+    pub commands: Commands<'w, 's>,
+    pub meshes: Option<ResMut<'w, Assets<Mesh>>>,
+    pub materials: Option<ResMut<'w, Assets<StandardMaterial>>>,
+}
+
+/// System to enter and leave construction mode from the toggle key and `BuildCommand`s
+pub fn handle_build_commands(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    config: BuildConfig,
+    mut state: ResMut<BuildModeState>,
+    mut requests: QueueReader<BuildCommand>,
+    mut events: ResMut<BuildEventQueue>,
+    player_query: Query<Entity, With<Player>>,
+) {
+    let BuildConfig { settings, catalog } = config;
+    let mut toggle = settings.toggle_key.is_some_and(|key| keyboard.just_pressed(key));
+    let mut enter = false;
+    let mut exit = !settings.enabled || state.builder.is_some_and(|builder| player_query.get(builder).is_err());
+    for request in requests.read() {
+        match request {
+            BuildCommand::Toggle => toggle = !toggle,
+            BuildCommand::Enter => enter = true,
+            BuildCommand::Exit => exit = true,
+            BuildCommand::Select(id) => {
+                if let Some(index) = catalog.pieces.iter().position(|piece| &piece.id == id) {
+                    state.selected = index;
+                }
+            }
+        }
+    }
+    if toggle {
+        if state.active { exit = true } else { enter = true }
+    }
+
+    if state.active && exit {
+        if let Some(ghost) = state.ghost.take() {
+            commands.entity(ghost).despawn();
+        }
+        if let Some(builder) = state.builder {
+            events.send(BuildEvent::ModeExited { builder });
+        }
+        let selected = state.selected;
+        *state = BuildModeState { selected, ..default() };
+    } else if !state.active && enter && settings.enabled {
+        let Some(builder) = player_query.iter().next() else { return };
+        state.active = true;
+        state.builder = Some(builder);
+        state.rotation_steps = 0;
+        events.send(BuildEvent::ModeEntered { builder });
+    }
+}
+
+/// System to turn the builder's input into construction actions while in construction mode.
+/// Runs after the player input sync so weapons stay quiet while building.
+pub fn build_mode_input(
+    catalog: Res<BuildCatalog>,
+    mut state: ResMut<BuildModeState>,
+    mut was_blocking: Local<bool>,
+    mut input_query: Query<&mut InputState, With<Player>>,
+) {
+    let Some(builder) = state.builder.filter(|_| state.active) else {
+        *was_blocking = false;
+        return;
+    };
+    let Ok(mut input) = input_query.get_mut(builder) else { return };
+
+    let count = catalog.pieces.len().max(1);
+    if input.next_weapon_pressed {
+        state.selected = (state.selected + 1) % count;
+    }
+    if input.prev_weapon_pressed {
+        state.selected = (state.selected + count - 1) % count;
+    }
+    if input.reload_pressed {
+        state.rotation_steps = (state.rotation_steps + 1) % 4;
+    }
+    if input.attack_pressed {
+        state.place_requested = true;
+    }
+    if input.block_pressed && !*was_blocking {
+        state.dismantle_requested = true;
+    }
+    *was_blocking = input.block_pressed;
+
+    // Construction mode owns these inputs
+    input.attack_pressed = false;
+    input.attack_held = false;
+    input.attack_released = false;
+    input.block_pressed = false;
+    input.fire_pressed = false;
+    input.fire_just_pressed = false;
+    input.reload_pressed = false;
+    input.next_weapon_pressed = false;
+    input.prev_weapon_pressed = false;
+    input.select_weapon = None;
+}
+
+/// Ground under `point`: (hit point, surface normal)
+fn ground_below(spatial_query: &SpatialQuery, filter: &SpatialQueryFilter, point: Vec3, max_gap: f32) -> Option<(Vec3, Vec3)> {
+    let origin = point + Vec3::Y;
+    spatial_query
+        .cast_ray(origin, Dir3::NEG_Y, 1.0 + max_gap, true, filter)
+        .map(|hit| (origin - Vec3::Y * hit.distance, hit.normal))
+}
+
+/// System to find where the selected piece would go and whether it can be built there
+pub fn update_build_placement(
+    config: BuildConfig,
+    mut state: ResMut<BuildModeState>,
+    spatial_query: SpatialQuery,
+    builder_query: Query<(&GlobalTransform, Option<&Inventory>), With<Player>>,
+    camera_query: Query<&GlobalTransform, With<CameraController>>,
+    piece_query: Query<(Entity, &Transform, &BuiltPiece)>,
+    parent_query: Query<&ChildOf>,
+) {
+    let BuildConfig { settings, catalog } = config;
+    let Some(builder) = state.builder.filter(|_| state.active) else { return };
+    let Ok((builder_transform, inventory)) = builder_query.get(builder) else { return };
+    let Some(piece) = catalog.pieces.get(state.selected).or(catalog.pieces.first()) else { return };
+
+    let mut filter = SpatialQueryFilter::from_excluded_entities([builder]);
+    if let Some(ghost) = state.ghost {
+        filter.excluded_entities.insert(ghost);
+    }
+
+    // Aim along the camera, or straight ahead without one
+    let builder_position = builder_transform.translation();
+    let (origin, direction, reach) = match camera_query.iter().next() {
+        Some(camera) => {
+            let origin = camera.translation();
+            (origin, camera.forward(), origin.distance(builder_position) + settings.place_distance)
+        }
+        None => {
+            let origin = builder_position + Vec3::Y * 1.6;
+            let forward = Dir3::new(builder_transform.forward().as_vec3() - Vec3::Y * 0.3).unwrap_or(Dir3::NEG_Z);
+            (origin, forward, settings.place_distance)
+        }
+    };
+    let hit = spatial_query.cast_ray(origin, direction, reach, true, &filter);
+    let aim = hit.map_or(origin + direction * reach, |hit| origin + direction * hit.distance);
+    // Door panels are children of their piece
+    state.target = hit.and_then(|hit| {
+        std::iter::once(hit.entity)
+            .chain(parent_query.iter_ancestors(hit.entity))
+            .find(|entity| piece_query.contains(*entity))
+    });
+
+    // Closest free socket that accepts the piece
+    let snap = piece_query
+        .iter()
+        .filter_map(|(entity, transform, built)| {
+            let definition = catalog.get(&built.piece_id)?;
+            definition
+                .sockets_for(transform, piece.kind)
+                .map(|socket| (socket.translation.distance(aim), socket))
+                .min_by(|a, b| a.0.total_cmp(&b.0))
+                .map(|(distance, socket)| (distance, socket, entity, built.stability))
+        })
+        .filter(|(distance, ..)| *distance <= settings.snap_distance)
+        .min_by(|a, b| a.0.total_cmp(&b.0));
+
+    let yaw = Quat::from_rotation_y(state.rotation_steps as f32 * FRAC_PI_2);
+    let max_slope_cos = settings.max_slope.to_radians().cos();
+    let mut candidate = match snap {
+        Some((_, transform, support, support_stability)) => {
+            // Foundations next to foundations are back to full strength when they touch the ground
+            let grounded = piece.kind == BuildPieceKind::Foundation
+                && ground_below(&spatial_query, &filter, transform.translation - Vec3::Y * piece.size.y / 2.0, settings.max_ground_gap)
+                    .is_some();
+            PlacementCandidate {
+                transform,
+                support: Some(support),
+                stability: if grounded { 1.0 } else { support_stability - piece.stability_loss },
+                result: Ok(()),
+            }
+        }
+        None if piece.kind == BuildPieceKind::Foundation => {
+            let mut position = aim;
+            if settings.grid_snap {
+                position.x = (position.x / GRID_SIZE).round() * GRID_SIZE;
+                position.z = (position.z / GRID_SIZE).round() * GRID_SIZE;
+            }
+            let ground = ground_below(&spatial_query, &filter, position, settings.max_ground_gap);
+            let result = match ground {
+                None => Err(PlacementError::NoGround),
+                Some((_, normal)) if normal.y < max_slope_cos => Err(PlacementError::TooSteep),
+                Some(_) => Ok(()),
+            };
+            if let Some((point, _)) = ground {
+                // Sink a little so uneven ground doesn't leave gaps
+                position.y = point.y + piece.size.y / 2.0 - 0.1;
+            }
+            PlacementCandidate {
+                transform: Transform::from_translation(position).with_rotation(yaw),
+                support: None,
+                stability: 1.0,
+                result,
+            }
+        }
+        None => PlacementCandidate {
+            transform: Transform::from_translation(aim + Vec3::Y * piece.size.y / 2.0).with_rotation(yaw),
+            support: None,
+            stability: 0.0,
+            result: Err(PlacementError::NoSupport),
+        },
+    };
+
+    if candidate.result.is_ok() {
+        let center = candidate.transform.translation;
+        let occupied = piece_query.iter().any(|(_, transform, built)| {
+            let same_spot = transform.translation.distance(center) < 0.3;
+            // Free-standing foundations can't overlap others
+            let overlapping = candidate.support.is_none()
+                && catalog.get(&built.piece_id).is_some_and(|other| other.kind == BuildPieceKind::Foundation)
+                && transform.translation.xz().distance(center.xz()) < GRID_SIZE * 0.9
+                && (transform.translation.y - center.y).abs() < piece.size.y;
+            same_spot || overlapping
+        });
+        candidate.result = if occupied {
+            Err(PlacementError::Occupied)
+        } else if candidate.stability < settings.min_stability {
+            Err(PlacementError::Unstable)
+        } else if let Some(cost) = missing_cost(piece, inventory) {
+            Err(PlacementError::NotEnoughMaterials { item_id: cost.item_id.clone(), needed: cost.amount })
+        } else {
+            Ok(())
+        };
+    }
+
+    state.candidate = Some(candidate);
+}
+
+/// Spawns a built piece, with its collider, meshes and door
+pub fn spawn_built_piece(
+    commands: &mut Commands,
+    definition: &BuildPieceDefinition,
+    transform: Transform,
+    built: BuiltPiece,
+    mut meshes: Option<&mut Assets<Mesh>>,
+    mut materials: Option<&mut Assets<StandardMaterial>>,
+) -> Entity {
+    let size = definition.size;
+    let material = materials.as_mut().map(|materials| {
+        materials.add(StandardMaterial { base_color: definition.color, perceptual_roughness: 0.9, ..default() })
+    });
+    let mut entity_commands = commands.spawn((
+        Name::new(format!("Built {}", definition.name)),
+        transform,
+        Visibility::default(),
+        RigidBody::Static,
+        built,
+    ));
+
+    // Walls and doors split the floor for AI; a door only while it's closed
+    if matches!(definition.kind, BuildPieceKind::Wall | BuildPieceKind::Door) {
+        entity_commands.insert(NavObstacle::cuboid(size / 2.0));
+    }
+
+    if definition.kind != BuildPieceKind::Door {
+        entity_commands.insert(Collider::cuboid(size.x, size.y, size.z));
+        if let (Some(meshes), Some(material)) = (meshes.as_mut(), material.clone()) {
+            entity_commands.insert((Mesh3d(meshes.add(Cuboid::new(size.x, size.y, size.z))), MeshMaterial3d(material)));
+        }
+        return entity_commands.id();
+    }
+
+    // Frame: two posts and a lintel around the opening
+    let post_width = (size.x - DOOR_OPENING.x) / 2.0;
+    let lintel_height = size.y - DOOR_OPENING.y;
+    let parts = [
+        (Vec3::new(-(DOOR_OPENING.x + post_width) / 2.0, 0.0, 0.0), Vec3::new(post_width, size.y, size.z)),
+        (Vec3::new((DOOR_OPENING.x + post_width) / 2.0, 0.0, 0.0), Vec3::new(post_width, size.y, size.z)),
+        (Vec3::new(0.0, (size.y - lintel_height) / 2.0, 0.0), Vec3::new(DOOR_OPENING.x, lintel_height, size.z)),
+    ];
+    entity_commands.insert(Collider::compound(
+        parts
+            .iter()
+            .map(|(offset, part)| (*offset, Quat::IDENTITY, Collider::cuboid(part.x, part.y, part.z)))
+            .collect(),
+    ));
+    let frame = entity_commands.id();
+    if let (Some(meshes), Some(material)) = (meshes.as_mut(), material.clone()) {
+        for (offset, part) in parts {
+            let mesh = meshes.add(Cuboid::new(part.x, part.y, part.z));
+            commands.spawn((Transform::from_translation(offset), Mesh3d(mesh), MeshMaterial3d(material.clone()), ChildOf(frame)));
+        }
+    }
+
+    // The panel swings on a hinge at the edge of the opening
+    let hinge_position = Vec3::new(-DOOR_OPENING.x / 2.0, (DOOR_OPENING.y - size.y) / 2.0, 0.0);
+    let hinge = commands
+        .spawn((Transform::from_translation(hinge_position), Visibility::default(), BuiltDoorHinge, ChildOf(frame)))
+        .id();
+    let mut panel = commands.spawn((
+        Transform::from_xyz(DOOR_OPENING.x / 2.0, 0.0, 0.0),
+        Visibility::default(),
+        Collider::cuboid(DOOR_OPENING.x - 0.05, DOOR_OPENING.y - 0.05, 0.08),
+        ChildOf(hinge),
+    ));
+    if let (Some(meshes), Some(materials)) = (meshes.as_mut(), materials.as_mut()) {
+        let panel_material = materials.add(StandardMaterial { base_color: Color::srgb(0.45, 0.3, 0.18), ..default() });
+        panel.insert((Mesh3d(meshes.add(Cuboid::new(DOOR_OPENING.x - 0.05, DOOR_OPENING.y - 0.05, 0.08))), MeshMaterial3d(panel_material)));
+    }
+
+    commands.entity(frame).insert(DoorSystem {
+        movement_type: DoorMovementType::Rotate,
+        doors_info: vec![SingleDoorInfo {
+            door_mesh_entity: Some(hinge),
+            rotated_position_found: true,
+            ..default()
+        }],
+        doors_number: 1,
+        close_after_time: true,
+        time_to_close: 4.0,
+        trigger_zone_offset: Vec3::new(0.0, hinge_position.y, 0.0),
+        trigger_zone_size: Vec3::new(DOOR_OPENING.x, DOOR_OPENING.y, 3.0),
+        ..default()
+    });
+    frame
+}
+
+/// System to build the placement candidate and dismantle the targeted piece
+pub fn apply_build_actions(
+    spawner: PieceSpawner,
+    config: BuildConfig,
+    mut state: ResMut<BuildModeState>,
+    mut structures: ResMut<BuiltStructures>,
+    mut events: ResMut<BuildEventQueue>,
+    mut inventory_query: Query<&mut Inventory>,
+    piece_query: Query<&BuiltPiece>,
+) {
+    let PieceSpawner { mut commands, mut meshes, mut materials } = spawner;
+    let BuildConfig { settings, catalog } = config;
+    let place = std::mem::take(&mut state.place_requested);
+    let dismantle = std::mem::take(&mut state.dismantle_requested);
+    let Some(builder) = state.builder.filter(|_| state.active) else { return };
+
+    if place {
+        let Some(piece) = catalog.pieces.get(state.selected) else { return };
+        let Some(candidate) = state.candidate.clone() else { return };
+        match candidate.result {
+            Err(error) => {
+                events.send(BuildEvent::PlacementFailed { piece_id: piece.id.clone(), error });
+            }
+            Ok(()) => {
+                if let Ok(mut inventory) = inventory_query.get_mut(builder) {
+                    for cost in piece.cost.iter() {
+                        inventory.remove_quantity(&cost.item_id, cost.amount);
+                    }
+                }
+                let uid = structures.allocate_uid();
+                let built = BuiltPiece {
+                    uid,
+                    piece_id: piece.id.clone(),
+                    support: candidate.support.and_then(|support| piece_query.get(support).ok()).map(|support| support.uid),
+                    stability: candidate.stability,
+                };
+                let entity = spawn_built_piece(
+                    &mut commands,
+                    piece,
+                    candidate.transform,
+                    built,
+                    meshes.as_deref_mut(),
+                    materials.as_deref_mut(),
+                );
+                structures.pieces.insert(uid, entity);
+                events.send(BuildEvent::Placed { entity, piece_id: piece.id.clone() });
+            }
+        }
+    }
+
+    if dismantle {
+        let Some(target) = state.target else { return };
+        let Ok(built) = piece_query.get(target) else { return };
+        if piece_query.iter().any(|other| other.support == Some(built.uid)) {
+            events.send(BuildEvent::DismantleBlocked { entity: target });
+            return;
+        }
+
+        let mut refunded = Vec::new();
+        if let (Some(definition), Ok(mut inventory)) = (catalog.get(&built.piece_id), inventory_query.get_mut(builder)) {
+            for cost in definition.cost.iter() {
+                let amount = (cost.amount as f32 * settings.refund_ratio).floor() as i32;
+                if amount > 0 {
+                    // Whatever doesn't fit is lost
+                    let _leftover = inventory.add_item(cost.to_item(amount));
+                    refunded.push((cost.item_id.clone(), amount));
+                }
+            }
+        }
+        structures.pieces.remove(&built.uid);
+        commands.entity(target).despawn();
+        state.target = None;
+        events.send(BuildEvent::Dismantled { piece_id: built.piece_id.clone(), refunded });
+    }
+}
+
+/// System to forget pieces despawned by other code
+pub fn prune_built_structures(mut structures: ResMut<BuiltStructures>, mut removed: RemovedComponents<BuiltPiece>) {
+    let removed: Vec<Entity> = removed.read().collect();
+    if !removed.is_empty() {
+        structures.pieces.retain(|_, entity| !removed.contains(entity));
+    }
+}
+
+/// System to swing built door panels as their `DoorSystem` opens and closes
+pub fn animate_built_doors(
+    time: Res<Time>,
+    door_query: Query<&DoorSystem, With<BuiltPiece>>,
+    mut hinge_query: Query<&mut Transform, With<BuiltDoorHinge>>,
+) {
+    for door in door_query.iter() {
+        let open = door.enter || (door.door_state == DoorCurrentState::Opened && !door.exit);
+        for info in door.doors_info.iter() {
+            let Some(mut hinge) = info.door_mesh_entity.and_then(|hinge| hinge_query.get_mut(hinge).ok()) else { continue };
+            // Same open rotation and speed as the door system expects
+            let target = if open { Quat::from_rotation_y(FRAC_PI_2) } else { info.original_rotation };
+            hinge.rotation = hinge.rotation.rotate_towards(target, door.open_speed * 10.0 * time.delta_secs());
+        }
+    }
+}
+
+/// Current structures as save data
+fn building_save_data(structures: &BuiltStructures, piece_query: &Query<(&Transform, &BuiltPiece)>) -> BuildingSaveData {
+    let mut pieces: Vec<BuiltPieceRecord> = structures
+        .pieces
+        .values()
+        .filter_map(|entity| piece_query.get(*entity).ok())
+        .map(|(transform, built)| BuiltPieceRecord {
+            uid: built.uid,
+            piece_id: built.piece_id.clone(),
+            translation: transform.translation,
+            rotation: transform.rotation,
+            support: built.support,
+            stability: built.stability,
+        })
+        .collect();
+    pieces.sort_by_key(|record| record.uid);
+    BuildingSaveData { next_uid: structures.next_uid, pieces }
+}
+
+/// System to keep the built structures in the data written to saves
+pub fn store_buildings_in_save(
+    mut commands: Commands,
+    settings: Res<BuildSettings>,
+    structures: Res<BuiltStructures>,
+    piece_query: Query<(&Transform, &BuiltPiece)>,
+    mut player_query: Query<(Entity, Option<&mut SaveCustomData>), With<Player>>,
+) {
+    if !structures.is_changed() {
+        return;
+    }
+    let Some((player, custom_data)) = player_query.iter_mut().next() else { return };
+    let Ok(value) = serde_json::to_value(building_save_data(&structures, &piece_query)) else { return };
+
+    match custom_data {
+        Some(mut custom_data) => {
+            if custom_data.0.get(&settings.save_key) != Some(&value) {
+                custom_data.0.insert(settings.save_key.clone(), value);
+            }
+        }
+        None => {
+            let mut custom_data = SaveCustomData::default();
+            custom_data.0.insert(settings.save_key.clone(), value);
+            commands.entity(player).insert(custom_data);
+        }
+    }
+}
+
+/// System to rebuild the structures stored in a loaded save
+pub fn restore_buildings_from_save(
+    spawner: PieceSpawner,
+    config: BuildConfig,
+    mut structures: ResMut<BuiltStructures>,
+    piece_query: Query<(&Transform, &BuiltPiece)>,
+    save_query: Query<(Entity, &SaveCustomData), ChangedPlayerSaveData>,
+) {
+    let PieceSpawner { mut commands, mut meshes, mut materials } = spawner;
+    let BuildConfig { settings, catalog } = config;
+    for (entity, custom_data) in save_query.iter() {
+        let Some(value) = custom_data.0.get(&settings.save_key) else { continue };
+        let saved: BuildingSaveData = match serde_json::from_value(value.clone()) {
+            Ok(saved) => saved,
+            Err(err) => {
+                warn!("Invalid saved structures on {:?}: {}", entity, err);
+                continue;
+            }
+        };
+        // Our own write-back
+        if saved == building_save_data(&structures, &piece_query) {
+            continue;
+        }
+
+        for piece in structures.pieces.values() {
+            commands.entity(*piece).despawn();
+        }
+        structures.pieces.clear();
+        structures.next_uid = saved.next_uid;
+        for record in saved.pieces {
+            let Some(definition) = catalog.get(&record.piece_id) else {
+                warn!("Saved building piece {} is not in the catalog", record.piece_id);
+                continue;
+            };
+            let built = BuiltPiece {
+                uid: record.uid,
+                piece_id: record.piece_id.clone(),
+                support: record.support,
+                stability: record.stability,
+            };
+            let transform = Transform::from_translation(record.translation).with_rotation(record.rotation);
+            let piece = spawn_built_piece(&mut commands, definition, transform, built, meshes.as_deref_mut(), materials.as_deref_mut());
+            structures.next_uid = structures.next_uid.max(record.uid);
+            structures.pieces.insert(record.uid, piece);
+        }
+    }
+}
@@ -0,0 +1,456 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::f32::consts::FRAC_PI_2;
+use crate::inventory::{Inventory, InventoryItem, ItemType};
+use crate::utils::EventQueue;
+
+/// Side of a foundation or floor tile, in meters
+pub const GRID_SIZE: f32 = 4.0;
+pub const FOUNDATION_HEIGHT: f32 = 0.5;
+pub const WALL_HEIGHT: f32 = 3.0;
+pub const WALL_THICKNESS: f32 = 0.2;
+pub const FLOOR_THICKNESS: f32 = 0.2;
+
+/// Role of a building piece; sockets accept pieces by kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum BuildPieceKind {
+    /// Sits on the ground or next to another foundation
+    Foundation,
+    Wall,
+    /// Wall-sized frame with a door (a `DoorSystem`)
+    Door,
+    /// Ceiling/upper floor on top of walls
+    Floor,
+}
+
+/// Attachment point on a piece where another piece snaps.
+///
+/// `transform` is where the attached piece's center goes, relative to this
+/// piece's center.
+#[derive(Debug, Clone, Reflect)]
+pub struct BuildSocket {
+    pub transform: Transform,
+    pub accepts: Vec<BuildPieceKind>,
+}
+
+impl BuildSocket {
+    pub fn new(translation: Vec3, yaw: f32, accepts: &[BuildPieceKind]) -> Self {
+        Self {
+            transform: Transform::from_translation(translation).with_rotation(Quat::from_rotation_y(yaw)),
+            accepts: accepts.to_vec(),
+        }
+    }
+}
+
+/// Material needed to build a piece
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct BuildCost {
+    pub item_id: String,
+    pub amount: i32,
+}
+
+impl BuildCost {
+    pub fn new(item_id: &str, amount: i32) -> Self {
+        Self { item_id: item_id.to_string(), amount }
+    }
+
+    /// Inventory item for refunds
+    pub fn to_item(&self, amount: i32) -> InventoryItem {
+        InventoryItem {
+            item_id: self.item_id.clone(),
+            name: self.item_id.replace('_', " "),
+            quantity: amount,
+            max_stack: 99,
+            weight: 0.5,
+            item_type: ItemType::Material,
+            icon_path: String::new(),
+            value: 1.0,
+            category: "Materials".to_string(),
+            min_level: 0,
+            info: String::new(),
+            is_infinite: false,
+        }
+    }
+}
+
+/// Buildable piece in the catalog
+#[derive(Debug, Clone, Reflect)]
+pub struct BuildPieceDefinition {
+    pub id: String,
+    pub name: String,
+    pub kind: BuildPieceKind,
+    /// Box size; pieces are centered on their transform
+    pub size: Vec3,
+    pub cost: Vec<BuildCost>,
+    pub sockets: Vec<BuildSocket>,
+    /// Stability lost from the piece it rests on
+    pub stability_loss: f32,
+    pub color: Color,
+}
+
+impl BuildPieceDefinition {
+    pub fn new(id: &str, name: &str, kind: BuildPieceKind, size: Vec3) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind,
+            size,
+            cost: Vec::new(),
+            sockets: Vec::new(),
+            stability_loss: 0.1,
+            color: Color::srgb(0.6, 0.45, 0.3),
+        }
+    }
+
+    pub fn with_cost(mut self, item_id: &str, amount: i32) -> Self {
+        self.cost.push(BuildCost::new(item_id, amount));
+        self
+    }
+
+    pub fn with_socket(mut self, socket: BuildSocket) -> Self {
+        self.sockets.push(socket);
+        self
+    }
+
+    pub fn with_stability_loss(mut self, loss: f32) -> Self {
+        self.stability_loss = loss;
+        self
+    }
+
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Adds the wall sockets on the four edges of a tile whose top is at `top`
+    fn with_edge_sockets(mut self, top: f32) -> Self {
+        let half = GRID_SIZE / 2.0;
+        let y = top + WALL_HEIGHT / 2.0;
+        let walls = [BuildPieceKind::Wall, BuildPieceKind::Door];
+        self.sockets.push(BuildSocket::new(Vec3::new(half, y, 0.0), FRAC_PI_2, &walls));
+        self.sockets.push(BuildSocket::new(Vec3::new(-half, y, 0.0), FRAC_PI_2, &walls));
+        self.sockets.push(BuildSocket::new(Vec3::new(0.0, y, half), 0.0, &walls));
+        self.sockets.push(BuildSocket::new(Vec3::new(0.0, y, -half), 0.0, &walls));
+        self
+    }
+
+    /// Adds sockets for the same kind of tile on the four sides
+    fn with_neighbour_sockets(mut self) -> Self {
+        for offset in [Vec3::X, Vec3::NEG_X, Vec3::Z, Vec3::NEG_Z] {
+            self.sockets.push(BuildSocket::new(offset * GRID_SIZE, 0.0, &[self.kind]));
+        }
+        self
+    }
+
+    /// Adds the sockets on top of a wall: another wall, and a floor on either side
+    fn with_wall_top_sockets(mut self) -> Self {
+        let top = WALL_HEIGHT / 2.0;
+        let half = GRID_SIZE / 2.0;
+        self.sockets.push(BuildSocket::new(Vec3::new(0.0, WALL_HEIGHT, 0.0), 0.0, &[BuildPieceKind::Wall, BuildPieceKind::Door]));
+        for side in [half, -half] {
+            self.sockets.push(BuildSocket::new(Vec3::new(0.0, top + FLOOR_THICKNESS / 2.0, side), 0.0, &[BuildPieceKind::Floor]));
+        }
+        self
+    }
+
+    pub fn foundation(id: &str, name: &str) -> Self {
+        Self::new(id, name, BuildPieceKind::Foundation, Vec3::new(GRID_SIZE, FOUNDATION_HEIGHT, GRID_SIZE))
+            .with_edge_sockets(FOUNDATION_HEIGHT / 2.0)
+            .with_neighbour_sockets()
+            .with_stability_loss(0.25)
+            .with_color(Color::srgb(0.55, 0.55, 0.55))
+    }
+
+    pub fn wall(id: &str, name: &str) -> Self {
+        Self::new(id, name, BuildPieceKind::Wall, Vec3::new(GRID_SIZE, WALL_HEIGHT, WALL_THICKNESS))
+            .with_wall_top_sockets()
+    }
+
+    pub fn door(id: &str, name: &str) -> Self {
+        Self::new(id, name, BuildPieceKind::Door, Vec3::new(GRID_SIZE, WALL_HEIGHT, WALL_THICKNESS))
+            .with_wall_top_sockets()
+    }
+
+    pub fn floor(id: &str, name: &str) -> Self {
+        Self::new(id, name, BuildPieceKind::Floor, Vec3::new(GRID_SIZE, FLOOR_THICKNESS, GRID_SIZE))
+            .with_edge_sockets(FLOOR_THICKNESS / 2.0)
+            .with_neighbour_sockets()
+            .with_stability_loss(0.2)
+    }
+
+    /// World transforms of the sockets accepting `kind`, for a piece at `transform`
+    pub fn sockets_for<'a>(&'a self, transform: &'a Transform, kind: BuildPieceKind) -> impl Iterator<Item = Transform> + 'a {
+        self.sockets
+            .iter()
+            .filter(move |socket| socket.accepts.contains(&kind))
+            .map(move |socket| transform.mul_transform(socket.transform))
+    }
+}
+
+/// Buildable pieces, in menu order
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct BuildCatalog {
+    pub pieces: Vec<BuildPieceDefinition>,
+}
+
+impl Default for BuildCatalog {
+    fn default() -> Self {
+        Self {
+            pieces: vec![
+                BuildPieceDefinition::foundation("foundation", "Foundation").with_cost("stone", 6).with_cost("wood", 2),
+                BuildPieceDefinition::wall("wall", "Wall").with_cost("wood", 6),
+                BuildPieceDefinition::door("door", "Door").with_cost("wood", 8),
+                BuildPieceDefinition::floor("floor", "Floor").with_cost("wood", 5),
+            ],
+        }
+    }
+}
+
+impl BuildCatalog {
+    pub fn get(&self, id: &str) -> Option<&BuildPieceDefinition> {
+        self.pieces.iter().find(|piece| piece.id == id)
+    }
+
+    pub fn register(&mut self, piece: BuildPieceDefinition) {
+        self.pieces.retain(|existing| existing.id != piece.id);
+        self.pieces.push(piece);
+    }
+}
+
+/// Missing material for a piece, or `None` when `inventory` can pay for it
+pub fn missing_cost<'a>(piece: &'a BuildPieceDefinition, inventory: Option<&Inventory>) -> Option<&'a BuildCost> {
+    piece
+        .cost
+        .iter()
+        .find(|cost| inventory.map_or(0, |inventory| inventory.item_count(&cost.item_id)) < cost.amount)
+}
+
+/// Why a piece can't go where the ghost is
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlacementError {
+    NotEnoughMaterials { item_id: String, needed: i32 },
+    /// Nothing to attach to (only foundations stand on the ground)
+    NoSupport,
+    /// No ground under a foundation, or too far below it
+    NoGround,
+    TooSteep,
+    Occupied,
+    /// Too far from the foundations to hold
+    Unstable,
+}
+
+impl PlacementError {
+    pub fn message(&self) -> String {
+        match self {
+            PlacementError::NotEnoughMaterials { item_id, needed } => format!("Needs {} {}", needed, item_id.replace('_', " ")),
+            PlacementError::NoSupport => "Must be attached to the structure".to_string(),
+            PlacementError::NoGround => "Needs solid ground".to_string(),
+            PlacementError::TooSteep => "Ground is too steep".to_string(),
+            PlacementError::Occupied => "Something is already there".to_string(),
+            PlacementError::Unstable => "Not enough support".to_string(),
+        }
+    }
+}
+
+/// Where the selected piece would go this frame
+#[derive(Debug, Clone)]
+pub struct PlacementCandidate {
+    pub transform: Transform,
+    /// Piece it snapped to
+    pub support: Option<Entity>,
+    pub stability: f32,
+    pub result: Result<(), PlacementError>,
+}
+
+/// Placed building piece
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BuiltPiece {
+    /// Id stable across saves
+    pub uid: u64,
+    pub piece_id: String,
+    /// `uid` of the piece this one rests on
+    pub support: Option<u64>,
+    /// 1 on the ground, lower further from it
+    pub stability: f32,
+}
+
+/// Hinge of a built door; swings its panel toward the `DoorSystem` target
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct BuiltDoorHinge;
+
+/// Built piece as stored in saves
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuiltPieceRecord {
+    pub uid: u64,
+    pub piece_id: String,
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub support: Option<u64>,
+    pub stability: f32,
+}
+
+/// Everything built, as stored under `BuildSettings::save_key`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BuildingSaveData {
+    pub next_uid: u64,
+    pub pieces: Vec<BuiltPieceRecord>,
+}
+
+impl BuildingSaveData {
+    /// Clears supports pointing at pieces that aren't saved and moves
+    /// `next_uid` past every saved uid; returns what was fixed
+    pub fn remove_orphan_references(&mut self) -> Vec<String> {
+        let uids: HashSet<u64> = self.pieces.iter().map(|piece| piece.uid).collect();
+        let mut removed = Vec::new();
+        for piece in self.pieces.iter_mut() {
+            if let Some(support) = piece.support.filter(|support| !uids.contains(support)) {
+                removed.push(format!("piece {} supported by missing piece {}", piece.uid, support));
+                piece.support = None;
+            }
+        }
+        if let Some(max_uid) = uids.iter().max().filter(|max_uid| **max_uid >= self.next_uid) {
+            removed.push(format!("next uid {} already in use", self.next_uid));
+            self.next_uid = max_uid + 1;
+        }
+        removed
+    }
+}
+
+/// Built pieces by uid
+#[derive(Resource, Debug, Default)]
+pub struct BuiltStructures {
+    pub next_uid: u64,
+    pub pieces: HashMap<u64, Entity>,
+}
+
+impl BuiltStructures {
+    pub fn allocate_uid(&mut self) -> u64 {
+        self.next_uid += 1;
+        self.next_uid
+    }
+}
+
+/// Construction mode state of the local player
+#[derive(Resource, Debug, Default)]
+pub struct BuildModeState {
+    pub active: bool,
+    pub builder: Option<Entity>,
+    /// Index into `BuildCatalog::pieces`
+    pub selected: usize,
+    /// Quarter turns applied to free-standing pieces
+    pub rotation_steps: i32,
+    pub ghost: Option<Entity>,
+    /// Piece the ghost was built for
+    pub ghost_piece: Option<String>,
+    pub valid_material: Option<Handle<StandardMaterial>>,
+    pub invalid_material: Option<Handle<StandardMaterial>>,
+    pub candidate: Option<PlacementCandidate>,
+    /// Piece under the ghost, target of a dismantle
+    pub target: Option<Entity>,
+    pub place_requested: bool,
+    pub dismantle_requested: bool,
+}
+
+/// Construction tuning
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct BuildSettings {
+    pub enabled: bool,
+    /// Key toggling construction mode (`None` = commands only)
+    pub toggle_key: Option<KeyCode>,
+    /// Distance in front of the builder where the ghost goes
+    pub place_distance: f32,
+    /// Sockets this close to the ghost snap it
+    pub snap_distance: f32,
+    /// Free-standing foundations snap to a `GRID_SIZE` grid
+    pub grid_snap: bool,
+    /// Largest gap between a foundation and the ground
+    pub max_ground_gap: f32,
+    /// Steepest ground for a foundation, in degrees
+    pub max_slope: f32,
+    /// Pieces below this stability can't be built
+    pub min_stability: f32,
+    /// Share of the cost given back when dismantling
+    pub refund_ratio: f32,
+    /// Key of the built structures in `SaveCustomData`
+    pub save_key: String,
+}
+
+impl Default for BuildSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            toggle_key: Some(KeyCode::KeyN),
+            place_distance: 5.0,
+            snap_distance: 1.5,
+            grid_snap: true,
+            max_ground_gap: 1.0,
+            max_slope: 30.0,
+            min_stability: 0.2,
+            refund_ratio: 0.5,
+            save_key: "building".to_string(),
+        }
+    }
+}
+
+/// Requests to construction mode, for menus and gameplay code
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildCommand {
+    Toggle,
+    Enter,
+    Exit,
+    /// Select a piece by id
+    Select(String),
+}
+
+/// Queue for construction commands
+pub type BuildCommandQueue = EventQueue<BuildCommand>;
+
+/// Construction notifications
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildEvent {
+    ModeEntered { builder: Entity },
+    ModeExited { builder: Entity },
+    Placed { entity: Entity, piece_id: String },
+    PlacementFailed { piece_id: String, error: PlacementError },
+    Dismantled { piece_id: String, refunded: Vec<(String, i32)> },
+    /// Other pieces rest on it
+    DismantleBlocked { entity: Entity },
+}
+
+/// Queue for construction events
+pub type BuildEventQueue = EventQueue<BuildEvent>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::Inventory;
+
+    #[test]
+    fn test_building_sockets_and_costs() {
+        let catalog = BuildCatalog::default();
+        let foundation = catalog.get("foundation").unwrap();
+        let at = Transform::from_xyz(8.0, 0.25, 0.0);
+
+        // Walls stand on the edges, centered on top of the foundation
+        let walls: Vec<Transform> = foundation.sockets_for(&at, BuildPieceKind::Wall).collect();
+        assert_eq!(walls.len(), 4);
+        assert!(walls.iter().any(|socket| socket.translation.abs_diff_eq(Vec3::new(10.0, 2.0, 0.0), 1e-4)));
+        let neighbours: Vec<Transform> = foundation.sockets_for(&at, BuildPieceKind::Foundation).collect();
+        assert!(neighbours.iter().any(|socket| socket.translation.abs_diff_eq(Vec3::new(12.0, 0.25, 0.0), 1e-4)));
+        assert_eq!(foundation.sockets_for(&at, BuildPieceKind::Floor).count(), 0);
+
+        let mut inventory = Inventory::default();
+        let wall = catalog.get("wall").unwrap();
+        assert_eq!(missing_cost(wall, Some(&inventory)).unwrap().item_id, "wood");
+        inventory.add_item(wall.cost[0].to_item(10));
+        assert!(missing_cost(wall, Some(&inventory)).is_none());
+        assert_eq!(inventory.remove_quantity("wood", 6), 6);
+        assert_eq!(inventory.item_count("wood"), 4);
+        assert_eq!(inventory.remove_quantity("wood", 6), 4);
+    }
+}
@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+use crate::input::{GlyphProvider, InputMap};
+use crate::inventory::Inventory;
+use super::types::*;
+
+/// Translucent preview of the selected piece
+#[derive(Component)]
+pub struct BuildGhost;
+
+/// Construction mode panel
+#[derive(Component)]
+pub struct BuildHudRoot;
+
+#[derive(Component)]
+pub struct BuildHudLabel;
+
+/// System to keep the ghost on the placement candidate, green when it can be built
+pub fn update_build_ghost(
+    mut commands: Commands,
+    catalog: Res<BuildCatalog>,
+    mut state: ResMut<BuildModeState>,
+    mut meshes: Option<ResMut<Assets<Mesh>>>,
+    mut materials: Option<ResMut<Assets<StandardMaterial>>>,
+    mut ghost_query: Query<(&mut Transform, &mut Visibility, &mut MeshMaterial3d<StandardMaterial>), With<BuildGhost>>,
+) {
+    if !state.active {
+        return;
+    }
+    let Some(piece) = catalog.pieces.get(state.selected) else { return };
+
+    // Rebuild the ghost for a newly selected piece
+    if state.ghost_piece.as_ref() != Some(&piece.id) {
+        if let Some(ghost) = state.ghost.take() {
+            commands.entity(ghost).despawn();
+        }
+        let (Some(meshes), Some(materials)) = (meshes.as_mut(), materials.as_mut()) else { return };
+        let mut ghost_material = |color: Color| {
+            materials.add(StandardMaterial { base_color: color, alpha_mode: AlphaMode::Blend, unlit: true, ..default() })
+        };
+        let valid = state.valid_material.clone().unwrap_or_else(|| ghost_material(Color::srgba(0.3, 0.9, 0.4, 0.4)));
+        let invalid = state.invalid_material.clone().unwrap_or_else(|| ghost_material(Color::srgba(0.95, 0.25, 0.2, 0.4)));
+        let ghost = commands
+            .spawn((
+                Name::new("Build Ghost"),
+                Transform::default(),
+                Visibility::Hidden,
+                Mesh3d(meshes.add(Cuboid::new(piece.size.x, piece.size.y, piece.size.z))),
+                MeshMaterial3d(invalid.clone()),
+                BuildGhost,
+            ))
+            .id();
+        state.ghost = Some(ghost);
+        state.ghost_piece = Some(piece.id.clone());
+        state.valid_material = Some(valid);
+        state.invalid_material = Some(invalid);
+        return;
+    }
+
+    let Some(ghost) = state.ghost else { return };
+    let Ok((mut transform, mut visibility, mut material)) = ghost_query.get_mut(ghost) else { return };
+    let Some(candidate) = state.candidate.as_ref() else { return };
+    *transform = candidate.transform;
+    let wanted = if candidate.result.is_ok() { &state.valid_material } else { &state.invalid_material };
+    if let Some(wanted) = wanted {
+        if material.0 != *wanted {
+            material.0 = wanted.clone();
+        }
+    }
+    visibility.set_if_neq(Visibility::Inherited);
+}
+
+/// System to setup the construction mode panel (hidden outside construction mode)
+pub fn setup_build_hud(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(20.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BuildHudRoot,
+            Visibility::Hidden,
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.05, 0.05, 0.08, 0.75)),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    Text::new(""),
+                    TextFont { font_size: 16.0, ..default() },
+                    TextColor(Color::WHITE),
+                    TextLayout::new_with_justify(Justify::Center),
+                    BuildHudLabel,
+                ));
+            });
+        });
+}
+
+/// System to show the selected piece, its cost and why it can't be built
+pub fn update_build_hud(
+    catalog: Res<BuildCatalog>,
+    state: Res<BuildModeState>,
+    glyphs: Res<GlyphProvider>,
+    input_map: Res<InputMap>,
+    inventory_query: Query<&Inventory>,
+    mut root_query: Query<&mut Visibility, With<BuildHudRoot>>,
+    mut label_query: Query<&mut Text, With<BuildHudLabel>>,
+) {
+    let Ok(mut visibility) = root_query.single_mut() else { return };
+    let piece = catalog.pieces.get(state.selected).filter(|_| state.active);
+    let Some(piece) = piece else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    visibility.set_if_neq(Visibility::Visible);
+
+    let inventory = state.builder.and_then(|builder| inventory_query.get(builder).ok());
+    let cost = piece
+        .cost
+        .iter()
+        .map(|cost| {
+            let have = inventory.map_or(0, |inventory| inventory.item_count(&cost.item_id));
+            format!("{} {}/{}", cost.item_id.replace('_', " "), have, cost.amount)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let status = match state.candidate.as_ref().map(|candidate| &candidate.result) {
+        Some(Err(error)) => error.message(),
+        _ => String::new(),
+    };
+    let controls = glyphs.format_prompt(
+        "{Attack} build   {Block} dismantle   {Reload} rotate   {PrevWeapon}/{NextWeapon} piece",
+        &input_map,
+    );
+    let label = format!("{} ({})\n{}\n{}", piece.name, cost, status, controls);
+
+    for mut text in label_query.iter_mut() {
+        if text.0 != label {
+            text.0 = label.clone();
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::interaction::{Interactable, InteractionType};
+
+/// Builder of an object the player can aim at and interact with: the
+/// [`Interactable`], a collider for the interaction ray and an optional mesh
+pub struct InteractableBundle {
+    pub name: String,
+    pub transform: Transform,
+    pub interactable: Interactable,
+    pub collider: Collider,
+    pub mesh: Option<(Handle<Mesh>, Handle<StandardMaterial>)>,
+}
+
+impl InteractableBundle {
+    pub fn builder(interaction_type: InteractionType) -> Self {
+        Self {
+            name: format!("{:?} Interactable", interaction_type),
+            transform: Transform::default(),
+            interactable: Interactable {
+                interaction_type,
+                ..default()
+            },
+            collider: Collider::cuboid(1.0, 1.0, 1.0),
+            mesh: None,
+        }
+    }
+
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn at(mut self, position: Vec3) -> Self {
+        self.transform.translation = position;
+        self
+    }
+
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.interactable.interaction_text = text.into();
+        self
+    }
+
+    pub fn with_distance(mut self, distance: f32) -> Self {
+        self.interactable.interaction_distance = distance;
+        self
+    }
+
+    pub fn with_collider(mut self, collider: Collider) -> Self {
+        self.collider = collider;
+        self
+    }
+
+    pub fn with_mesh(mut self, mesh: Handle<Mesh>, material: Handle<StandardMaterial>) -> Self {
+        self.mesh = Some((mesh, material));
+        self
+    }
+
+    /// Spawns the interactable, returning its entity; add the behavior
+    /// components (door, switch, quest station...) to it
+    pub fn spawn(self, commands: &mut Commands) -> Entity {
+        let entity = commands
+            .spawn((
+                Name::new(self.name),
+                self.interactable,
+                self.transform,
+                Visibility::default(),
+                RigidBody::Static,
+                self.collider,
+            ))
+            .id();
+
+        if let Some((mesh, material)) = self.mesh {
+            commands.entity(entity).insert((Mesh3d(mesh), MeshMaterial3d(material)));
+        }
+
+        entity
+    }
+}
@@ -0,0 +1,120 @@
+//! Spawn builders
+//!
+//! One-call spawning of complete, correctly parented entities: a player with
+//! input, physics, inventory, weapons, stats and a follow camera; an AI
+//! character; a vehicle with its wheels and seats; an interactable object.
+//!
+//! ```rust,ignore
+//! fn setup(mut commands: Commands) {
+//!     let player = PlayerBundle::builder()
+//!         .at(Vec3::new(0.0, 1.0, 0.0))
+//!         .with_inventory(Inventory::default())
+//!         .with_weapon_manager(WeaponManager::default())
+//!         .with_weapon(Weapon { weapon_name: "Pistol".to_string(), ..default() })
+//!         .spawn(&mut commands);
+//!
+//!     NpcBundle::builder()
+//!         .named("Guard")
+//!         .at(Vec3::new(10.0, 1.0, 0.0))
+//!         .with_faction("Enemies")
+//!         .with_patrol(vec![Vec3::new(10.0, 0.0, 0.0), Vec3::new(10.0, 0.0, 10.0)], true)
+//!         .with_combat()
+//!         .spawn(&mut commands);
+//!
+//!     InteractableBundle::builder(InteractionType::Open)
+//!         .with_text("Open door")
+//!         .at(Vec3::new(0.0, 1.0, 5.0))
+//!         .spawn(&mut commands);
+//! }
+//! ```
+
+mod player;
+mod npc;
+mod interactable;
+#[cfg(feature = "vehicles")]
+mod vehicle;
+
+pub use player::PlayerBundle;
+pub use npc::NpcBundle;
+pub use interactable::InteractableBundle;
+#[cfg(feature = "vehicles")]
+pub use vehicle::VehicleBundle;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use avian3d::prelude::RigidBody;
+    use crate::ai::{AiBehaviorState, AiCombatBrain, AiController, CharacterFaction};
+    use crate::camera::CameraController;
+    use crate::character::Player;
+    use crate::interaction::{Interactable, InteractionType};
+    use crate::weapons::{Weapon, WeaponManager};
+
+    #[derive(Resource)]
+    struct Spawned {
+        player: Entity,
+        npc: Entity,
+        door: Entity,
+    }
+
+    fn spawn_level(mut commands: Commands) {
+        let player = PlayerBundle::builder()
+            .named("Hero")
+            .at(Vec3::new(2.0, 1.0, 0.0))
+            .with_weapon(Weapon { weapon_name: "Pistol".to_string(), ..default() })
+            .with_weapon(Weapon { weapon_name: "Rifle".to_string(), ..default() })
+            .spawn(&mut commands);
+        let npc = NpcBundle::builder()
+            .named("Guard")
+            .at(Vec3::new(10.0, 1.0, 0.0))
+            .with_faction("Enemies")
+            .with_patrol(vec![Vec3::new(10.0, 0.0, 0.0), Vec3::new(10.0, 0.0, 10.0)], true)
+            .with_combat()
+            .spawn(&mut commands);
+        let door = InteractableBundle::builder(InteractionType::Open)
+            .with_text("Open door")
+            .at(Vec3::new(0.0, 1.0, 5.0))
+            .spawn(&mut commands);
+        commands.insert_resource(Spawned { player, npc, door });
+    }
+
+    #[test]
+    fn test_builders_spawn_complete_parented_entities() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin)).add_systems(Startup, spawn_level);
+        app.update();
+
+        let &Spawned { player, npc, door } = app.world().resource::<Spawned>();
+        let world = app.world();
+        assert!(world.get::<Player>(player).is_some());
+        assert_eq!(world.get::<Name>(player).unwrap().as_str(), "Hero");
+        #[cfg(feature = "quest")]
+        assert!(world.get::<crate::quest::QuestLog>(player).is_some());
+
+        // The first weapon is drawn, both ride along with the player
+        let manager = world.get::<WeaponManager>(player).unwrap();
+        assert_eq!(manager.weapons_list.len(), 2);
+        assert!(manager.any_weapon_available);
+        let [pistol, rifle] = [manager.weapons_list[0], manager.weapons_list[1]];
+        assert!(world.get::<Weapon>(pistol).unwrap().equipped);
+        assert_eq!(*world.get::<Visibility>(rifle).unwrap(), Visibility::Hidden);
+        assert_eq!(world.get::<ChildOf>(rifle).unwrap().parent(), player);
+        assert_eq!(world.get::<GlobalTransform>(pistol).unwrap().translation(), Vec3::new(2.0, 1.0, 0.0));
+
+        let mut cameras = app.world_mut().query::<&CameraController>();
+        let follow_targets: Vec<_> = cameras.iter(app.world()).map(|camera| camera.follow_target).collect();
+        assert_eq!(follow_targets, [Some(player)]);
+
+        let world = app.world();
+        let ai = world.get::<AiController>(npc).unwrap();
+        assert_eq!(ai.state, AiBehaviorState::Patrol);
+        assert_eq!(ai.wander_center, Vec3::new(10.0, 1.0, 0.0));
+        assert_eq!(world.get::<CharacterFaction>(npc).unwrap().name, "Enemies");
+        assert!(world.get::<AiCombatBrain>(npc).is_some());
+        assert!(world.get::<WeaponManager>(npc).is_none());
+
+        assert_eq!(world.get::<Interactable>(door).unwrap().interaction_text, "Open door");
+        assert_eq!(world.get::<RigidBody>(door), Some(&RigidBody::Static));
+        assert_eq!(world.get::<GlobalTransform>(door).unwrap().translation(), Vec3::new(0.0, 1.0, 5.0));
+    }
+}
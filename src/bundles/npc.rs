@@ -0,0 +1,176 @@
+use bevy::prelude::*;
+use super::player::spawn_weapons;
+use crate::ai::{
+    AIPerceptionSettings, AiCombatBrain, AiCombatRangeSettings, AiCombatSettings, AiController, AiMovement,
+    AiPerception, CharacterFaction, PatrolPath,
+};
+use crate::character::{character_physics, CharacterAnimationState, CharacterController, CharacterMovementState};
+use crate::combat::Health;
+use crate::input::InputState;
+use crate::inventory::Inventory;
+use crate::weapons::{Weapon, WeaponManager};
+
+/// Builder of an AI character: character, physics, perception, movement and
+/// optionally faction, patrol route, combat brain and weapons
+pub struct NpcBundle {
+    pub name: String,
+    pub position: Vec3,
+    pub controller: CharacterController,
+    pub health: Health,
+    pub ai: AiController,
+    pub movement: AiMovement,
+    pub perception: AIPerceptionSettings,
+    pub faction: Option<String>,
+    pub patrol: Option<PatrolPath>,
+    pub combat: bool,
+    pub inventory: Option<Inventory>,
+    pub weapons: Vec<Weapon>,
+}
+
+impl Default for NpcBundle {
+    fn default() -> Self {
+        Self {
+            name: "NPC".to_string(),
+            position: Vec3::new(0.0, 1.0, 0.0),
+            controller: CharacterController::default(),
+            health: Health::default(),
+            ai: AiController::default(),
+            movement: AiMovement {
+                speed: 3.0,
+                acceleration: 10.0,
+                stop_distance: 0.5,
+                ..default()
+            },
+            perception: AIPerceptionSettings {
+                fov: 90.0,
+                range: 20.0,
+                hearing_range: 15.0,
+                layer_mask: u32::MAX,
+            },
+            faction: None,
+            patrol: None,
+            combat: false,
+            inventory: None,
+            weapons: Vec::new(),
+        }
+    }
+}
+
+impl NpcBundle {
+    pub fn builder() -> Self {
+        Self::default()
+    }
+
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn at(mut self, position: Vec3) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn with_controller(mut self, controller: CharacterController) -> Self {
+        self.controller = controller;
+        self
+    }
+
+    pub fn with_health(mut self, health: Health) -> Self {
+        self.health = health;
+        self
+    }
+
+    pub fn with_ai(mut self, ai: AiController) -> Self {
+        self.ai = ai;
+        self
+    }
+
+    pub fn with_movement(mut self, movement: AiMovement) -> Self {
+        self.movement = movement;
+        self
+    }
+
+    pub fn with_perception(mut self, perception: AIPerceptionSettings) -> Self {
+        self.perception = perception;
+        self
+    }
+
+    pub fn with_faction(mut self, faction: impl Into<String>) -> Self {
+        self.faction = Some(faction.into());
+        self
+    }
+
+    /// Patrols `waypoints`, starting in the patrol state
+    pub fn with_patrol(mut self, waypoints: Vec<Vec3>, loop_path: bool) -> Self {
+        self.ai.state = crate::ai::AiBehaviorState::Patrol;
+        self.ai.patrol_path = waypoints.clone();
+        self.patrol = Some(PatrolPath { waypoints, loop_path });
+        self
+    }
+
+    /// Adds the combat brain and range settings
+    pub fn with_combat(mut self) -> Self {
+        self.combat = true;
+        self
+    }
+
+    pub fn with_inventory(mut self, inventory: Inventory) -> Self {
+        self.inventory = Some(inventory);
+        self
+    }
+
+    pub fn with_weapon(mut self, weapon: Weapon) -> Self {
+        self.weapons.push(weapon);
+        self
+    }
+
+    /// Spawns the NPC (and its weapons), returning the NPC entity
+    pub fn spawn(self, commands: &mut Commands) -> Entity {
+        let mut ai = self.ai;
+        if ai.wander_center == Vec3::ZERO {
+            ai.wander_center = self.position;
+        }
+
+        let npc = commands
+            .spawn((
+                Name::new(self.name),
+                self.controller,
+                CharacterMovementState::default(),
+                CharacterAnimationState::default(),
+                self.health,
+                InputState::default(),
+                ai,
+                self.movement,
+                AiPerception::default(),
+                self.perception,
+                Transform::from_translation(self.position),
+                Visibility::default(),
+            ))
+            .insert(character_physics())
+            .id();
+
+        if let Some(faction) = self.faction {
+            commands.entity(npc).insert(CharacterFaction { name: faction });
+        }
+        if let Some(patrol) = self.patrol {
+            commands.entity(npc).insert(patrol);
+        }
+        if self.combat {
+            commands.entity(npc).insert((
+                AiCombatBrain::default(),
+                AiCombatSettings::default(),
+                AiCombatRangeSettings::default(),
+            ));
+        }
+        if let Some(inventory) = self.inventory {
+            commands.entity(npc).insert(inventory);
+        }
+        if !self.weapons.is_empty() {
+            let manager = spawn_weapons(commands, npc, WeaponManager::default(), self.weapons);
+            commands.entity(npc).insert(manager);
+        }
+
+        npc
+    }
+}
@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+use crate::camera::spawn_camera;
+use crate::character::{spawn_character, CharacterController, Player};
+use crate::combat::Health;
+use crate::inventory::{Equipment, Inventory};
+use crate::stats::StatsSystem;
+use crate::weapons::{Weapon, WeaponBundle, WeaponManager};
+
+/// Builder of a complete player: character, input, physics, interaction,
+/// inventory, weapons, stats and a follow camera
+pub struct PlayerBundle {
+    pub name: String,
+    pub position: Vec3,
+    pub controller: CharacterController,
+    pub health: Health,
+    pub inventory: Option<Inventory>,
+    pub weapon_manager: Option<WeaponManager>,
+    /// Weapons spawned as children of the player; the first one is equipped
+    pub weapons: Vec<Weapon>,
+    pub stats: Option<StatsSystem>,
+    pub camera: bool,
+}
+
+impl Default for PlayerBundle {
+    fn default() -> Self {
+        Self {
+            name: "Player".to_string(),
+            position: Vec3::new(0.0, 1.0, 0.0),
+            controller: CharacterController::default(),
+            health: Health::default(),
+            inventory: Some(Inventory::default()),
+            weapon_manager: None,
+            weapons: Vec::new(),
+            stats: Some(StatsSystem::default()),
+            camera: true,
+        }
+    }
+}
+
+impl PlayerBundle {
+    pub fn builder() -> Self {
+        Self::default()
+    }
+
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn at(mut self, position: Vec3) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn with_controller(mut self, controller: CharacterController) -> Self {
+        self.controller = controller;
+        self
+    }
+
+    pub fn with_health(mut self, health: Health) -> Self {
+        self.health = health;
+        self
+    }
+
+    pub fn with_inventory(mut self, inventory: Inventory) -> Self {
+        self.inventory = Some(inventory);
+        self
+    }
+
+    pub fn without_inventory(mut self) -> Self {
+        self.inventory = None;
+        self
+    }
+
+    pub fn with_weapon_manager(mut self, weapon_manager: WeaponManager) -> Self {
+        self.weapon_manager = Some(weapon_manager);
+        self
+    }
+
+    pub fn with_weapon(mut self, weapon: Weapon) -> Self {
+        self.weapons.push(weapon);
+        self
+    }
+
+    pub fn with_stats(mut self, stats: StatsSystem) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    pub fn without_stats(mut self) -> Self {
+        self.stats = None;
+        self
+    }
+
+    pub fn without_camera(mut self) -> Self {
+        self.camera = false;
+        self
+    }
+
+    /// Spawns the player (and its camera and weapons), returning the player entity
+    pub fn spawn(self, commands: &mut Commands) -> Entity {
+        let player = spawn_character(commands, self.position);
+        commands.entity(player).insert((
+            Player,
+            Name::new(self.name),
+            self.controller,
+            self.health,
+            Equipment::default(),
+        ));
+
+        if let Some(inventory) = self.inventory {
+            commands.entity(player).insert(inventory);
+        }
+        if let Some(stats) = self.stats {
+            commands.entity(player).insert(stats);
+        }
+
+        #[cfg(feature = "quest")]
+        commands.entity(player).insert(crate::quest::QuestLog::default());
+
+        if self.weapon_manager.is_some() || !self.weapons.is_empty() {
+            let manager = spawn_weapons(commands, player, self.weapon_manager.unwrap_or_default(), self.weapons);
+            commands.entity(player).insert(manager);
+        }
+
+        if self.camera {
+            spawn_camera(commands, player);
+        }
+
+        player
+    }
+}
+
+/// Spawns `weapons` under `owner` and lists them in `manager`, equipping the first one
+pub(crate) fn spawn_weapons(
+    commands: &mut Commands,
+    owner: Entity,
+    mut manager: WeaponManager,
+    weapons: Vec<Weapon>,
+) -> WeaponManager {
+    for mut weapon in weapons {
+        let equipped = manager.weapons_list.is_empty();
+        weapon.equipped = equipped;
+        let name = Name::new(weapon.weapon_name.clone());
+        let entity = commands
+            .spawn((
+                WeaponBundle {
+                    weapon,
+                    name,
+                    visibility: if equipped { Visibility::Inherited } else { Visibility::Hidden },
+                    ..default()
+                },
+                ChildOf(owner),
+            ))
+            .id();
+        manager.weapons_list.push(entity);
+    }
+
+    if !manager.weapons_list.is_empty() {
+        manager.current_index = 0;
+        manager.any_weapon_available = true;
+        manager.weapon_list_count = manager.weapons_list.len();
+    }
+    manager
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use crate::vehicles::{VehicleConfig, VehicleType};
+
+/// Builder of a drivable vehicle with its wheels and seats as children.
+/// Starts from the type's preset; `with_seat`/`with_wheel` replace the preset's
+/// seats and wheels.
+pub struct VehicleBundle {
+    pub config: VehicleConfig,
+    custom_seats: bool,
+    custom_wheels: bool,
+}
+
+impl VehicleBundle {
+    pub fn builder(vehicle_type: VehicleType) -> Self {
+        Self {
+            config: VehicleConfig::preset(vehicle_type, Vec3::ZERO),
+            custom_seats: false,
+            custom_wheels: false,
+        }
+    }
+
+    pub fn named(mut self, name: impl Into<String>) -> Self {
+        self.config.name = name.into();
+        self
+    }
+
+    pub fn at(mut self, position: Vec3) -> Self {
+        self.config.position = position;
+        self
+    }
+
+    pub fn with_body(mut self, size: Vec3, color: Color) -> Self {
+        self.config.mesh_size = size;
+        self.config.color = color;
+        self
+    }
+
+    pub fn with_seat(mut self, name: impl Into<String>, offset: Vec3, is_driver: bool) -> Self {
+        if !self.custom_seats {
+            self.config.seats.clear();
+            self.custom_seats = true;
+        }
+        self.config.seats.push((name.into(), offset, is_driver));
+        self
+    }
+
+    pub fn with_wheel(mut self, name: impl Into<String>, offset: Vec3, steerable: bool, powered: bool) -> Self {
+        if !self.custom_wheels {
+            self.config.wheels.clear();
+            self.custom_wheels = true;
+        }
+        self.config.wheels.push((name.into(), offset, steerable, powered, offset.x < 0.0));
+        self
+    }
+
+    /// Spawns the vehicle, returning its root entity
+    pub fn spawn(
+        self,
+        commands: &mut Commands,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<StandardMaterial>>,
+    ) -> Entity {
+        self.config.build(commands, meshes, materials)
+    }
+}
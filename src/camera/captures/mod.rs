@@ -1,26 +1,57 @@
 use bevy::prelude::*;
+use bevy::render::view::screenshot::{save_to_disk, Screenshot};
 use crate::camera::types::CameraController;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 pub struct CameraCapturesPlugin;
 
 impl Plugin for CameraCapturesPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<ScreenshotEventQueue>()
+        app.add_plugins(EventQueuePlugin::<TakeScreenshotEvent>::default())
+           .add_plugins(EventQueuePlugin::<PhotoTakenEvent>::default())
            .register_type::<CameraPerspective>()
+           // Framing is measured before the shot is taken
            .add_systems(Update, (
-               handle_screenshot_requests,
                update_camera_perspective,
-           ));
+               handle_screenshot_requests,
+           ).chain());
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct TakeScreenshotEvent {
     pub path: Option<String>,
     pub metadata: Option<CaptureSlot>,
 }
 
-#[derive(Resource, Default)]
-pub struct ScreenshotEventQueue(pub Vec<TakeScreenshotEvent>);
+pub type ScreenshotEventQueue = EventQueue<TakeScreenshotEvent>;
+
+/// A `CameraPerspective` framed when a photo was taken
+#[derive(Debug, Clone, Reflect)]
+pub struct PhotoSubject {
+    pub entity: Entity,
+    pub name: String,
+    pub distance: f32,
+    /// Degrees off the camera's forward
+    pub angle: f32,
+}
+
+/// Sent for every screenshot, with the perspectives it framed
+#[derive(Debug, Clone)]
+pub struct PhotoTakenEvent {
+    pub slot: CaptureSlot,
+    /// File the screenshot is saved to, if any
+    pub path: Option<String>,
+    pub subjects: Vec<PhotoSubject>,
+}
+
+impl PhotoTakenEvent {
+    pub fn frames(&self, name: &str) -> bool {
+        self.subjects.iter().any(|subject| subject.name == name)
+    }
+}
+
+pub type PhotoTakenEventQueue = EventQueue<PhotoTakenEvent>;
 
 /// Stored information about a taken capture/screenshot
 #[derive(Debug, Clone, Reflect)]
@@ -40,8 +71,12 @@ pub struct CameraPerspective {
     pub max_distance: f32,
     pub max_angle: f32,
     pub active: bool,
+    /// Set once a photo framed this perspective
     pub capture_taken: bool,
+    /// Within distance and angle, and inside the viewport
     pub target_on_screen: bool,
+    /// Distance and angle from the camera while on screen
+    pub framing: Option<(f32, f32)>,
 }
 
 impl Default for CameraPerspective {
@@ -53,15 +88,50 @@ impl Default for CameraPerspective {
             active: true,
             capture_taken: false,
             target_on_screen: false,
+            framing: None,
         }
     }
 }
 
+/// System to take requested screenshots and report the perspectives they framed
 pub fn handle_screenshot_requests(
-    mut events: ResMut<ScreenshotEventQueue>,
+    mut commands: Commands,
+    mut events: QueueReader<TakeScreenshotEvent>,
+    mut photos: ResMut<PhotoTakenEventQueue>,
+    camera_query: Query<(&GlobalTransform, Option<&Projection>), With<CameraController>>,
+    mut perspective_query: Query<(Entity, &mut CameraPerspective)>,
 ) {
-    for _event in events.0.drain(..) {
-        info!("Screenshot requested (using Bevy's built-in screenshot API logic)");
+    for event in events.read() {
+        if let Some(path) = &event.path {
+            commands.spawn(Screenshot::primary_window()).observe(save_to_disk(path.clone()));
+        }
+
+        let mut subjects = Vec::new();
+        for (entity, mut perspective) in perspective_query.iter_mut() {
+            let Some((distance, angle)) = perspective.framing.filter(|_| perspective.active && perspective.target_on_screen) else {
+                continue;
+            };
+            perspective.capture_taken = true;
+            subjects.push(PhotoSubject { entity, name: perspective.name.clone(), distance, angle });
+        }
+
+        let slot = event.metadata.clone().unwrap_or_else(|| {
+            let (camera_pos, camera_rot, fov) = camera_query.iter().next().map_or((Vec3::ZERO, Quat::IDENTITY, 0.0), |(transform, projection)| {
+                let fov = match projection {
+                    Some(Projection::Perspective(perspective)) => perspective.fov.to_degrees(),
+                    _ => 0.0,
+                };
+                (transform.translation(), transform.rotation(), fov)
+            });
+            CaptureSlot {
+                name: subjects.first().map_or_else(|| "Photo".to_string(), |subject| subject.name.clone()),
+                date: chrono::Utc::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                camera_pos,
+                camera_rot,
+                fov,
+            }
+        });
+        photos.send(PhotoTakenEvent { slot, path: event.path.clone(), subjects });
     }
 }
 
@@ -76,34 +146,27 @@ pub fn update_camera_perspective(
 
     let camera_pos = camera_gt.translation();
     let camera_fwd = camera_gt.forward();
+    let viewport = camera.logical_viewport_size();
 
     for (mut perspective, target_gt) in perspective_query.iter_mut() {
-        if !perspective.active || perspective.capture_taken { continue; }
+        perspective.framing = None;
+        if !perspective.active {
+            perspective.target_on_screen = false;
+            continue;
+        }
 
         let target_pos = target_gt.translation();
         let dist = camera_pos.distance(target_pos);
+        let angle = camera_fwd.dot((target_pos - camera_pos).normalize_or_zero()).clamp(-1.0, 1.0).acos().to_degrees();
 
-        if dist <= perspective.max_distance {
-            let dir_to_target = (target_pos - camera_pos).normalize();
-            let dot = camera_fwd.dot(dir_to_target);
-            let angle = dot.acos().to_degrees();
-
-            if angle <= perspective.max_angle {
-                // Check if on screen
-                if let Ok(_viewport_pos) = camera.world_to_viewport(camera_gt, target_pos) {
-                    // Check if within viewport bounds [0, 1]
-                    // (Assuming viewport covers full screen or we check against specific camera size)
-                    // For now, world_to_viewport returns screen coordinates.
-                    // We need to normalize or check against viewport size.
-                    perspective.target_on_screen = true; // Placeholder for exact bounds check
-                } else {
-                    perspective.target_on_screen = false;
-                }
-            } else {
-                perspective.target_on_screen = false;
-            }
-        } else {
-            perspective.target_on_screen = false;
+        let on_screen = dist <= perspective.max_distance
+            && angle <= perspective.max_angle
+            && camera.world_to_viewport(camera_gt, target_pos).is_ok_and(|position| {
+                viewport.is_none_or(|size| position.cmpge(Vec2::ZERO).all() && position.cmple(size).all())
+            });
+        perspective.target_on_screen = on_screen;
+        if on_screen {
+            perspective.framing = Some((dist, angle));
         }
     }
 }
@@ -1,21 +1,116 @@
+//! Camera collision.
+//!
+//! A sphere of `collision_radius` is cast from the pivot toward the camera,
+//! and the camera stops where it would touch geometry. Whisker rays fanned out
+//! to the sides find walls the camera is about to swing into, so it starts
+//! moving in before contact instead of snapping. Once clear, it eases back out
+//! at `collision_recovery_speed`.
+
 use bevy::prelude::*;
 use avian3d::prelude::*;
 use super::types::*;
 
+/// Distance a whisker hit lets the camera keep. Whiskers closer to the view
+/// direction (`index` 1) pull harder than outer ones.
+pub fn whisker_limit(full: f32, hit: f32, index: u32, count: u32) -> f32 {
+    let weight = 1.0 - index as f32 / (count + 1) as f32;
+    full - (full - hit.min(full)) * weight
+}
+
+/// Steps the collision distance toward `target`, never past `hard`.
+/// Returns `None` once the camera is back at its full distance.
+pub fn smooth_collision_distance(
+    camera: &CameraController,
+    current: Option<f32>,
+    full: f32,
+    hard: f32,
+    target: f32,
+    dt: f32,
+) -> Option<f32> {
+    let distance = current.unwrap_or(full).min(full);
+    let speed = if target < distance { camera.collision_pull_in_speed } else { camera.collision_recovery_speed };
+    let alpha = 1.0 - (-speed * dt).exp();
+    let distance = (distance + (target - distance) * alpha).min(hard).max(0.0);
+    if distance >= full - 1e-3 { None } else { Some(distance) }
+}
+
 pub fn handle_camera_collision(
+    time: Res<Time>,
     spatial_query: SpatialQuery,
-    mut query: Query<(&CameraController, &CameraState, &mut Transform)>,
+    mut query: Query<(&CameraController, &mut CameraState, &mut Transform)>,
 ) {
-    for (camera, state, mut transform) in query.iter_mut() {
-        if !camera.use_collision { continue; }
+    let dt = time.delta_secs();
+    for (camera, mut state, mut transform) in query.iter_mut() {
+        if !camera.use_collision {
+            state.collision_distance = None;
+            continue;
+        }
 
         let start = state.current_pivot;
-        let direction = transform.back();
-        let max_dist = state.current_distance;
-        let filter = SpatialQueryFilter::default();
+        let offset = transform.translation - start;
+        let full = offset.length();
+        let Ok(direction) = Dir3::new(offset) else {
+            state.collision_distance = None;
+            continue;
+        };
+        let mut filter = SpatialQueryFilter::default().with_mask(camera.collision_layers);
+        if let Some(target) = camera.follow_target {
+            filter = filter.with_excluded_entities([target]);
+        }
+
+        let hard = spatial_query
+            .cast_shape(
+                &Collider::sphere(camera.collision_radius),
+                start,
+                Quat::IDENTITY,
+                direction,
+                &ShapeCastConfig::default().with_max_distance(full),
+                &filter,
+            )
+            .map_or(full, |hit| hit.distance);
+
+        let mut target = hard;
+        let count = camera.collision_whiskers;
+        for index in 1..=count {
+            let angle = (camera.collision_whisker_angle * index as f32 / count as f32).to_radians();
+            for side in [-1.0, 1.0] {
+                let whisker = Quat::from_axis_angle(*transform.up(), angle * side) * direction;
+                if let Some(hit) = spatial_query.cast_ray(start, whisker, full, true, &filter) {
+                    target = target.min(whisker_limit(full, hit.distance, index, count));
+                }
+            }
+        }
 
-        if let Some(hit) = spatial_query.cast_ray(start, direction, max_dist, true, &filter) {
-            transform.translation = start + direction * (hit.distance - camera.collision_radius);
+        state.collision_distance = smooth_collision_distance(camera, state.collision_distance, full, hard, target, dt);
+        if let Some(distance) = state.collision_distance {
+            transform.translation = start + direction * distance;
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_camera_collision_smoothing() {
+        use crate::camera::{smooth_collision_distance, whisker_limit, CameraController};
+        let camera = CameraController { collision_pull_in_speed: 10.0, collision_recovery_speed: 2.0, ..default() };
+
+        // Inner whiskers pull harder, and a miss-length hit doesn't pull at all
+        assert_eq!(whisker_limit(4.0, 1.0, 1, 3), 1.75);
+        assert_eq!(whisker_limit(4.0, 1.0, 2, 3), 2.5);
+        assert_eq!(whisker_limit(4.0, 6.0, 1, 3), 4.0);
+
+        // A direct hit clamps at once, whiskers ease in
+        assert_eq!(smooth_collision_distance(&camera, None, 4.0, 1.5, 1.5, 0.01), Some(1.5));
+        let easing = smooth_collision_distance(&camera, None, 4.0, 4.0, 2.0, 0.05).unwrap();
+        assert!(easing > 2.0 && easing < 4.0);
+
+        // Recovery is slower than pull-in and ends unobstructed
+        let recovering = smooth_collision_distance(&camera, Some(1.0), 4.0, 4.0, 4.0, 0.05).unwrap();
+        assert!(recovering - 1.0 < 4.0 - easing);
+        assert_eq!(smooth_collision_distance(&camera, Some(3.9995), 4.0, 4.0, 4.0, 0.1), None);
+    }
+}
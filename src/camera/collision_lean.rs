@@ -3,6 +3,78 @@ use avian3d::prelude::*;
 use crate::input::InputState;
 use super::types::*;
 
+/// Picks the shoulder the orbit camera sits over. Leaning puts it over the
+/// lean side; otherwise, when a wall crowds the chosen shoulder and the other
+/// one is open, it moves across until the chosen side has been clear for
+/// `shoulder_return_delay`. When neither side has room the side offset shrinks.
+pub fn update_camera_shoulder(
+    time: Res<Time>,
+    input: Res<InputState>,
+    spatial_query: SpatialQuery,
+    mut camera_query: Query<(&CameraController, &mut CameraState, &Transform)>,
+    target_query: Query<&GlobalTransform, Without<CameraController>>,
+) {
+    let dt = time.delta_secs();
+
+    for (camera, mut state, transform) in camera_query.iter_mut() {
+        let target = camera
+            .follow_target
+            .filter(|_| camera.mode == CameraMode::ThirdPerson)
+            .and_then(|target| target_query.get(target).ok().map(|target_transform| (target, target_transform)));
+        let Some((target, target_transform)) = target else {
+            state.auto_side = None;
+            state.side_offset_squeeze = 0.0;
+            continue;
+        };
+
+        let origin = target_transform.translation() + Vec3::Y * camera.default_pivot_offset.y;
+        let right = Vec3::new(transform.right().x, 0.0, transform.right().z).normalize_or_zero();
+        let reach = camera.side_offset + camera.collision_radius + camera.shoulder_wall_margin;
+        let filter = SpatialQueryFilter::default().with_mask(camera.collision_layers).with_excluded_entities([target]);
+        let room = |side: CameraSide| {
+            let Ok(direction) = Dir3::new(right * side.sign()) else { return reach };
+            spatial_query
+                .cast_ray(origin, direction, reach, true, &filter)
+                .map_or(reach, |hit| hit.distance)
+        };
+
+        let chosen = camera.current_side;
+        let chosen_room = room(chosen);
+        let other_room = room(chosen.opposite());
+
+        let lean_side = if input.lean_left {
+            Some(CameraSide::Left)
+        } else if input.lean_right {
+            Some(CameraSide::Right)
+        } else {
+            None
+        };
+        if let Some(lean_side) = lean_side {
+            state.auto_side = (lean_side != chosen).then_some(lean_side);
+            state.auto_side_timer = 0.0;
+        } else if !camera.auto_shoulder_swap {
+            state.auto_side = None;
+        } else if state.auto_side.is_none() {
+            if chosen_room < reach && other_room > chosen_room + camera.shoulder_wall_margin {
+                state.auto_side = Some(chosen.opposite());
+                state.auto_side_timer = 0.0;
+            }
+        } else if chosen_room >= reach {
+            state.auto_side_timer += dt;
+            if state.auto_side_timer >= camera.shoulder_return_delay {
+                state.auto_side = None;
+            }
+        } else {
+            state.auto_side_timer = 0.0;
+        }
+
+        let side_room = if state.auto_side.is_some_and(|side| side != chosen) { other_room } else { chosen_room };
+        let fits = ((side_room - camera.collision_radius) / camera.side_offset.max(0.01)).clamp(0.0, 1.0);
+        let alpha = 1.0 - (-camera.shoulder_swap_speed * dt).exp();
+        state.side_offset_squeeze += (1.0 - fits - state.side_offset_squeeze) * alpha;
+    }
+}
+
 pub fn update_camera_lean_collision(
     time: Res<Time>,
     input: Res<InputState>,
@@ -26,12 +98,17 @@ pub fn update_camera_lean_collision(
             // Cast from current pivot (which is smoothed target eye pos)
             let ray_origin = state.current_pivot;
 
+            // The character's own collider would stop every lean
+            let mut filter = SpatialQueryFilter::default().with_mask(camera.collision_layers);
+            if let Some(target) = camera.follow_target {
+                filter = filter.with_excluded_entities([target]);
+            }
             if let Some(hit) = spatial_query.cast_ray(
                 ray_origin,
                 ray_dir,
                 camera.lean_raycast_dist,
                 true,
-                &SpatialQueryFilter::default(),
+                &filter,
             ) {
                 // If hit, reduce target lean based on distance
                 let t = (hit.distance / camera.lean_raycast_dist).clamp(0.0, 1.0);
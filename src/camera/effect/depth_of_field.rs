@@ -0,0 +1,218 @@
+//! Gameplay-driven depth of field.
+//!
+//! A camera with a [`DofDriver`] gets a `DepthOfField` whose focus follows
+//! what matters on screen, in this order:
+//!
+//! 1. the speaker of the dialog in progress,
+//! 2. the lock-on target,
+//! 3. whatever the crosshair is on while aiming,
+//!
+//! and `default_distance` otherwise. The focal distance eases toward the new
+//! subject instead of snapping, and the aperture opens up while something is
+//! in focus so the background falls away behind a scope or a conversation.
+
+use bevy::post_process::dof::{DepthOfField, DepthOfFieldMode};
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::camera::types::{CameraController, CameraState, CameraTargetState};
+
+/// What the depth of field is focused on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum DofFocusSource {
+    #[default]
+    Default,
+    DialogSpeaker,
+    LockedTarget,
+    AimPoint,
+}
+
+/// Drives the camera's depth of field from gameplay
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct DofDriver {
+    pub enabled: bool,
+    pub mode: DepthOfFieldMode,
+    /// Focal distance with nothing to focus on
+    pub default_distance: f32,
+    /// Aperture with nothing to focus on; high enough to keep the scene sharp
+    pub idle_f_stops: f32,
+    /// Aperture while focused on a subject
+    pub focus_f_stops: f32,
+    /// How far the aim ray looks for a surface
+    pub max_aim_distance: f32,
+    /// Height above a target's origin to focus on (roughly its face)
+    pub subject_height: f32,
+    /// How fast focus and aperture follow a change of subject
+    pub smoothing: f32,
+    /// Dialog speaker, set while a conversation is running
+    pub speaker: Option<Entity>,
+    pub source: DofFocusSource,
+    pub focal_distance: f32,
+    pub aperture_f_stops: f32,
+}
+
+impl Default for DofDriver {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mode: DepthOfFieldMode::Gaussian,
+            default_distance: 10.0,
+            idle_f_stops: 16.0,
+            focus_f_stops: 2.8,
+            max_aim_distance: 300.0,
+            subject_height: 1.6,
+            smoothing: 6.0,
+            speaker: None,
+            source: DofFocusSource::Default,
+            focal_distance: 10.0,
+            aperture_f_stops: 16.0,
+        }
+    }
+}
+
+impl DofDriver {
+    /// Eases focus and aperture toward a subject `depth` meters in front of
+    /// the camera, or back to the defaults with `None`
+    pub fn step(&mut self, depth: Option<f32>, dt: f32) {
+        let (target_distance, target_f_stops) = match depth {
+            Some(depth) => (depth.max(0.1), self.focus_f_stops),
+            None => (self.default_distance, self.idle_f_stops),
+        };
+        let alpha = 1.0 - (-self.smoothing * dt).exp();
+        self.focal_distance += (target_distance - self.focal_distance) * alpha;
+        self.aperture_f_stops += (target_f_stops - self.aperture_f_stops) * alpha;
+    }
+}
+
+/// Depth of `point` along the view axis of `camera`, as depth of field measures it
+pub fn focus_depth(camera: &GlobalTransform, point: Vec3) -> f32 {
+    (point - camera.translation()).dot(camera.forward().as_vec3())
+}
+
+/// Camera parts the depth of field driver reads and focuses
+type DofCamera<'a> = (
+    Entity,
+    &'a mut DofDriver,
+    &'a CameraController,
+    &'a CameraState,
+    Option<&'a CameraTargetState>,
+    &'a GlobalTransform,
+    Option<&'a mut DepthOfField>,
+);
+
+/// System to focus the depth of field on the speaker, the locked target or the aim point
+pub fn update_dof_driver(
+    mut commands: Commands,
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    mut camera_query: Query<DofCamera>,
+    subject_query: Query<&GlobalTransform, Without<DofDriver>>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut driver, controller, state, target_state, camera_transform, dof) in camera_query.iter_mut() {
+        if !driver.enabled {
+            if dof.is_some() {
+                commands.entity(entity).remove::<DepthOfField>();
+            }
+            continue;
+        }
+
+        let subject = |target: Entity| {
+            subject_query
+                .get(target)
+                .ok()
+                .map(|transform| focus_depth(camera_transform, transform.translation() + Vec3::Y * driver.subject_height))
+                .filter(|depth| *depth > 0.0)
+        };
+        let speaker = driver.speaker.and_then(subject);
+        let locked = target_state.and_then(|target_state| target_state.locked_target).and_then(subject);
+        let aimed = state
+            .is_aiming
+            .then(|| {
+                let filter = SpatialQueryFilter::from_excluded_entities(controller.follow_target);
+                spatial_query.cast_ray(camera_transform.translation(), camera_transform.forward(), driver.max_aim_distance, true, &filter)
+            })
+            .flatten()
+            .map(|hit| hit.distance);
+
+        let (source, depth) = if let Some(depth) = speaker {
+            (DofFocusSource::DialogSpeaker, Some(depth))
+        } else if let Some(depth) = locked {
+            (DofFocusSource::LockedTarget, Some(depth))
+        } else if let Some(distance) = aimed {
+            (DofFocusSource::AimPoint, Some(distance))
+        } else {
+            (DofFocusSource::Default, None)
+        };
+        driver.source = source;
+        driver.step(depth, dt);
+
+        match dof {
+            Some(mut dof) => {
+                dof.mode = driver.mode;
+                dof.focal_distance = driver.focal_distance;
+                dof.aperture_f_stops = driver.aperture_f_stops;
+            }
+            None => {
+                commands.entity(entity).insert(DepthOfField {
+                    mode: driver.mode,
+                    focal_distance: driver.focal_distance,
+                    aperture_f_stops: driver.aperture_f_stops,
+                    ..default()
+                });
+            }
+        }
+    }
+}
+
+/// System to hand the driver the speaker of the running dialog
+#[cfg(feature = "dialog")]
+pub fn update_dof_dialog_speaker(
+    dialog_query: Query<&crate::dialog::DialogSystem>,
+    speaker_query: Query<(Entity, &crate::dialog::DialogContent)>,
+    mut driver_query: Query<&mut DofDriver>,
+) {
+    let speaker = dialog_query
+        .iter()
+        .filter(|dialog| dialog.dialog_active)
+        .filter_map(|dialog| dialog.current_dialog_content.as_ref())
+        .find_map(|content| {
+            speaker_query
+                .iter()
+                .find(|(_, speaker)| speaker.id == content.id && speaker.scene_id == content.scene_id)
+                .map(|(entity, _)| entity)
+        });
+    for mut driver in driver_query.iter_mut() {
+        if driver.speaker != speaker {
+            driver.speaker = speaker;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_dof_driver_focus() {
+        use crate::camera::effect::{focus_depth, DofDriver};
+        // Depth is measured along the view axis, not straight-line distance
+        let camera = GlobalTransform::from(Transform::from_xyz(0.0, 2.0, 0.0).looking_to(Vec3::NEG_Z, Vec3::Y));
+        assert!((focus_depth(&camera, Vec3::new(3.0, 2.0, -4.0)) - 4.0).abs() < 1e-5);
+
+        let mut driver = DofDriver { smoothing: 10.0, ..default() };
+        driver.step(Some(40.0), 0.1);
+        assert!(driver.focal_distance > 10.0 && driver.focal_distance < 40.0);
+        assert!(driver.aperture_f_stops < driver.idle_f_stops);
+        for _ in 0..100 {
+            driver.step(Some(40.0), 0.1);
+        }
+        assert!((driver.focal_distance - 40.0).abs() < 1e-3);
+        assert!((driver.aperture_f_stops - driver.focus_f_stops).abs() < 1e-3);
+
+        // Losing the subject eases back to the defaults
+        driver.step(None, 0.1);
+        assert!(driver.focal_distance < 40.0 && driver.aperture_f_stops > driver.focus_f_stops);
+    }
+}
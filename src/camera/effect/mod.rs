@@ -1,5 +1,9 @@
 use bevy::prelude::*;
-pub mod photo_mode;
+pub mod photo_filters;
+pub mod depth_of_field;
+
+pub use photo_filters::*;
+pub use depth_of_field::*;
 
 pub struct CameraEffectPlugin;
 
@@ -7,9 +11,15 @@ impl Plugin for CameraEffectPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<CameraEffectManager>()
            .register_type::<PixelEffectSettings>()
+           .register_type::<OverlayEffectSettings>()
            .register_type::<SolidEffectSettings>()
-           .add_plugins(photo_mode::PhotoModePlugin)
-           .add_systems(Update, update_camera_effects);
+           .register_type::<DofDriver>()
+           .init_resource::<PhotoFilterLibrary>()
+           .register_type::<PhotoFilterLibrary>()
+           .add_systems(Update, update_camera_effects)
+           .add_systems(Update, update_dof_driver.after(crate::camera::CameraSet::Follow));
+        #[cfg(feature = "dialog")]
+        app.add_systems(Update, update_dof_dialog_speaker.before(update_dof_driver));
     }
 }
 
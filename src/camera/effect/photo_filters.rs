@@ -0,0 +1,121 @@
+//! Photo filters
+//!
+//! Color grading presets for photo mode. A [`PhotoFilter`] becomes the
+//! camera's `ColorGrading`, plus chromatic aberration for the filters that
+//! want it; the [`PhotoFilterLibrary`] keeps them in the order photo mode
+//! cycles through them.
+
+use bevy::post_process::effect_stack::ChromaticAberration;
+use bevy::prelude::*;
+use bevy::render::view::{ColorGrading, ColorGradingGlobal, ColorGradingSection};
+
+/// A color grading preset
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct PhotoFilter {
+    pub name: String,
+    /// Exposure offset, in stops
+    pub exposure: f32,
+    /// Positive is warmer, negative colder
+    pub temperature: f32,
+    /// Positive is more magenta, negative greener
+    pub tint: f32,
+    /// Hue shift, in radians
+    pub hue: f32,
+    /// 0 is grayscale, 1 leaves the colors as they are
+    pub saturation: f32,
+    /// 1 leaves the contrast as it is
+    pub contrast: f32,
+    /// Size of the color fringes; 0 leaves chromatic aberration off
+    pub chromatic_aberration: f32,
+}
+
+impl Default for PhotoFilter {
+    fn default() -> Self {
+        Self {
+            name: "None".to_string(),
+            exposure: 0.0,
+            temperature: 0.0,
+            tint: 0.0,
+            hue: 0.0,
+            saturation: 1.0,
+            contrast: 1.0,
+            chromatic_aberration: 0.0,
+        }
+    }
+}
+
+impl PhotoFilter {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), ..default() }
+    }
+
+    /// Color grading of the filter, with `exposure` more stops on top
+    pub fn color_grading(&self, exposure: f32) -> ColorGrading {
+        ColorGrading::with_identical_sections(
+            ColorGradingGlobal {
+                exposure: self.exposure + exposure,
+                temperature: self.temperature,
+                tint: self.tint,
+                hue: self.hue,
+                post_saturation: self.saturation,
+                ..default()
+            },
+            ColorGradingSection {
+                contrast: self.contrast,
+                ..default()
+            },
+        )
+    }
+
+    pub fn chromatic_aberration(&self) -> Option<ChromaticAberration> {
+        (self.chromatic_aberration > 0.0).then(|| ChromaticAberration {
+            intensity: self.chromatic_aberration,
+            ..default()
+        })
+    }
+}
+
+/// Filters offered by photo mode, in order; the first one is "no filter"
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct PhotoFilterLibrary {
+    pub filters: Vec<PhotoFilter>,
+}
+
+impl Default for PhotoFilterLibrary {
+    fn default() -> Self {
+        Self {
+            filters: vec![
+                PhotoFilter::default(),
+                PhotoFilter { saturation: 0.0, contrast: 1.3, ..PhotoFilter::new("Noir") },
+                PhotoFilter { saturation: 0.3, temperature: 0.3, tint: 0.05, ..PhotoFilter::new("Sepia") },
+                PhotoFilter { saturation: 1.4, contrast: 1.15, ..PhotoFilter::new("Vivid") },
+                PhotoFilter { temperature: 0.15, exposure: 0.1, ..PhotoFilter::new("Warm") },
+                PhotoFilter { temperature: -0.15, saturation: 0.85, ..PhotoFilter::new("Cold") },
+                PhotoFilter { exposure: 0.3, saturation: 0.8, contrast: 0.85, chromatic_aberration: 0.01, ..PhotoFilter::new("Dreamy") },
+                PhotoFilter { hue: 0.1, saturation: 0.7, contrast: 1.2, chromatic_aberration: 0.02, ..PhotoFilter::new("Retro") },
+            ],
+        }
+    }
+}
+
+impl PhotoFilterLibrary {
+    pub fn register(&mut self, filter: PhotoFilter) {
+        match self.filters.iter_mut().find(|existing| existing.name == filter.name) {
+            Some(existing) => *existing = filter,
+            None => self.filters.push(filter),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&PhotoFilter> {
+        self.filters.get(index)
+    }
+
+    /// Index `steps` filters away from `index`, wrapping around
+    pub fn step(&self, index: usize, steps: i32) -> usize {
+        if self.filters.is_empty() {
+            return 0;
+        }
+        (index as i32 + steps).rem_euclid(self.filters.len() as i32) as usize
+    }
+}
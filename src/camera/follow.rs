@@ -1,17 +1,66 @@
 use bevy::prelude::*;
-use crate::input::InputState;
+use crate::input::{InputState, PinchEvent, TouchGestureSettings};
+use crate::physics::SurfaceAnchor;
+use crate::utils::QueueReader;
 use super::types::*;
 
 // Character movement state sync and pivot logic removed - handled in state_offsets.rs
 
+/// Turns the camera with the surface its target is anchored to. Only the yaw
+/// is followed; a surface pitching or rolling doesn't tip the view.
+pub fn follow_anchored_surface_yaw(
+    mut query: Query<(&CameraController, &mut CameraState)>,
+    mut anchor_query: Query<&mut SurfaceAnchor>,
+) {
+    for (camera, mut state) in query.iter_mut() {
+        let Some(mut anchor) = camera.follow_target.and_then(|target| anchor_query.get_mut(target).ok()) else { continue };
+        if anchor.pending_yaw == 0.0 {
+            continue;
+        }
+        if camera.follow_surface_rotation {
+            state.yaw += anchor.pending_yaw.to_degrees();
+        }
+        anchor.pending_yaw = 0.0;
+    }
+}
+
+/// Steps free look for this frame. `yaw_error` is how far (degrees) the
+/// camera is from behind the character. Returns how fast the camera may
+/// recenter, or `None` while it keeps its own yaw.
+pub fn step_free_look(camera: &CameraController, state: &mut CameraState, held: bool, locked: bool, yaw_error: f32, dt: f32) -> Option<f32> {
+    // A lock-on takes the camera over at once, with no hold before recentering
+    if locked || !camera.free_look_enabled || camera.mode != CameraMode::ThirdPerson {
+        state.free_look = FreeLookPhase::Off;
+        return Some(camera.smooth_rotation_speed * 0.1);
+    }
+    if held {
+        state.free_look = FreeLookPhase::Looking;
+        return None;
+    }
+    match state.free_look {
+        FreeLookPhase::Off => return Some(camera.smooth_rotation_speed * 0.1),
+        FreeLookPhase::Looking => state.free_look = FreeLookPhase::Holding(camera.free_look_recenter_delay),
+        FreeLookPhase::Holding(remaining) => {
+            state.free_look = if remaining > dt { FreeLookPhase::Holding(remaining - dt) } else { FreeLookPhase::Recentering };
+        }
+        FreeLookPhase::Recentering => {
+            if yaw_error.abs() < 1.0 {
+                state.free_look = FreeLookPhase::Off;
+            }
+            return Some(camera.free_look_recenter_speed);
+        }
+    }
+    None
+}
+
 pub fn update_camera_rotation(
     input: Res<InputState>,
     time: Res<Time>,
-    mut query: Query<(&CameraController, &mut CameraState)>,
+    mut query: Query<(&CameraController, &mut CameraState, Option<&CameraTargetState>)>,
     target_query: Query<&Transform, Without<CameraController>>,
 ) {
     let dt = time.delta_secs();
-    for (camera, mut state) in query.iter_mut() {
+    for (camera, mut state, target_state) in query.iter_mut() {
         if !camera.enabled || camera.mode == CameraMode::Locked { continue; }
 
         let target_xf = if let Some(target) = camera.follow_target {
@@ -20,18 +69,20 @@ pub fn update_camera_rotation(
             None
         };
 
+        // Wrap-around aware yaw from the camera to behind the character
+        let yaw_error = target_xf.map_or(0.0, |target_xf| {
+            let (target_yaw, _, _) = target_xf.rotation.to_euler(EulerRot::YXZ);
+            (target_yaw.to_degrees() - state.yaw + 180.0).rem_euclid(360.0) - 180.0
+        });
+        let locked = target_state.is_some_and(|target_state| target_state.locked_target.is_some());
+        let recenter_speed = step_free_look(camera, &mut state, input.free_look_held, locked, yaw_error, dt);
+
         // Auto-center logic (Look in player direction)
         if !state.is_aiming && camera.mode == CameraMode::ThirdPerson {
-            if let Some(target_xf) = target_xf {
+            if let (Some(_), Some(recenter_speed)) = (target_xf, recenter_speed) {
                 if input.look.length() < 0.01 {
-                    let target_rot = target_xf.rotation;
-                    let (target_yaw, _, _) = target_rot.to_euler(EulerRot::YXZ);
-                    let target_yaw_deg = target_yaw.to_degrees();
-                    
-                    // Simple wrap-around aware lerp for yaw
-                    let diff = (target_yaw_deg - state.yaw + 180.0) % 360.0 - 180.0;
-                    let alpha = 1.0 - (-camera.smooth_rotation_speed * 0.1 * dt).exp();
-                    state.yaw += diff * alpha;
+                    let alpha = 1.0 - (-recenter_speed * dt).exp();
+                    state.yaw += yaw_error * alpha;
                 }
             }
         }
@@ -51,11 +102,35 @@ pub fn update_camera_rotation(
             state.yaw -= input.look.x * sensitivity;
             state.pitch -= input.look.y * sensitivity;
         }
+        // Gyro and flick stick turns are already in degrees
+        state.yaw -= input.aim_turn.x;
+        state.pitch -= input.aim_turn.y;
 
         state.pitch = state.pitch.clamp(camera.min_vertical_angle, camera.max_vertical_angle);
     }
 }
 
+/// Pinching out brings the camera closer, pinching in moves it back.
+/// Zones blend from and back to `base_distance`, so that is what changes.
+pub fn apply_pinch_zoom(
+    settings: Res<TouchGestureSettings>,
+    mut pinches: QueueReader<PinchEvent>,
+    mut query: Query<&mut CameraController>,
+) {
+    let delta: f32 = pinches.read().map(|pinch| pinch.delta).sum();
+    if delta == 0.0 || settings.pinch_zoom_speed == 0.0 {
+        return;
+    }
+    for mut camera in query.iter_mut() {
+        if !camera.enabled || matches!(camera.mode, CameraMode::FirstPerson | CameraMode::Locked) {
+            continue;
+        }
+        let distance = (camera.base_distance - delta * settings.pinch_zoom_speed).clamp(camera.min_distance, camera.max_distance);
+        camera.base_distance = distance;
+        camera.distance = distance;
+    }
+}
+
 pub fn update_camera_follow(
     time: Res<Time>,
     mut camera_query: Query<(&CameraController, &mut CameraState, &mut Transform)>,
@@ -66,7 +141,9 @@ pub fn update_camera_follow(
         let Some(target_entity) = camera.follow_target else { continue };
         let Ok(_target_transform) = target_query.get(target_entity) else { continue };
 
-        let lean_pivot_offset = transform.right() * state.current_lean * camera.lean_amount;
+        // The shake goes over the smoothed rotation, unsmoothed; take last frame's off first
+        let smoothed = transform.rotation * state.applied_shake.inverse();
+        let lean_pivot_offset = smoothed * Vec3::X * state.current_lean * camera.lean_amount;
         let final_pivot = state.current_pivot + lean_pivot_offset;
 
         // Rotation smoothing with mode-specific speeds
@@ -76,14 +153,22 @@ pub fn update_camera_follow(
         let lean_rotation = Quat::from_rotation_z(-state.current_lean * camera.lean_angle.to_radians());
         
         let rot_alpha = 1.0 - (-camera.smooth_rotation_speed * time.delta_secs()).exp();
-        transform.rotation = transform.rotation.slerp(rotation * lean_rotation, rot_alpha);
+        let smoothed = smoothed.slerp(rotation * lean_rotation, rot_alpha);
+        let shake = Quat::from_euler(
+            EulerRot::YXZ,
+            state.shake_rotation.y.to_radians(),
+            state.shake_rotation.x.to_radians(),
+            state.shake_rotation.z.to_radians(),
+        );
+        transform.rotation = smoothed * shake;
+        state.applied_shake = shake;
 
         // Position/Distance smoothing
         let dist_alpha = 1.0 - (-camera.distance_smooth_speed * time.delta_secs()).exp();
         state.current_distance = state.current_distance + (camera.distance - state.current_distance) * dist_alpha;
         
         // Final position
-        let direction = transform.back();
+        let direction = smoothed * Vec3::Z;
         transform.translation = final_pivot + direction * state.current_distance + state.bob_offset;
     }
 }
@@ -106,3 +191,35 @@ pub fn handle_camera_mode_switch(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_free_look_recenter_lag() {
+        use crate::camera::{step_free_look, CameraController, CameraState, FreeLookPhase};
+        let camera = CameraController { free_look_recenter_delay: 0.45, free_look_recenter_speed: 8.0, ..default() };
+        let normal = camera.smooth_rotation_speed * 0.1;
+        let mut state = CameraState::default();
+
+        assert_eq!(step_free_look(&camera, &mut state, false, false, 30.0, 0.1), Some(normal));
+        assert_eq!(step_free_look(&camera, &mut state, true, false, 30.0, 0.1), None);
+        assert_eq!(state.free_look, FreeLookPhase::Looking);
+
+        // Released: the view holds for the delay, then recenters at its own speed until behind
+        assert_eq!(step_free_look(&camera, &mut state, false, false, 30.0, 0.1), None);
+        for _ in 0..5 {
+            assert_eq!(step_free_look(&camera, &mut state, false, false, 30.0, 0.1), None);
+        }
+        assert_eq!(step_free_look(&camera, &mut state, false, false, 30.0, 0.1), Some(8.0));
+        assert_eq!(step_free_look(&camera, &mut state, false, false, 0.5, 0.1), Some(8.0));
+        assert_eq!(state.free_look, FreeLookPhase::Off);
+
+        // Locking on breaks free look, and it can't start while locked
+        step_free_look(&camera, &mut state, true, false, 30.0, 0.1);
+        assert_eq!(step_free_look(&camera, &mut state, true, true, 30.0, 0.1), Some(normal));
+        assert_eq!(state.free_look, FreeLookPhase::Off);
+    }
+}
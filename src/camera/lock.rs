@@ -11,6 +11,12 @@ pub struct LookAtPoint {
     pub active: bool,
 }
 
+/// Lock-on candidate score from distance and angle off the view center (lower is better)
+pub fn lock_on_score(distance: f32, angle_degrees: f32) -> f32 {
+    // Prefer the center of the screen over proximity
+    distance * 0.5 + angle_degrees * 2.0
+}
+
 pub fn update_target_marking(
     mut camera_query: Query<(&CameraController, &CameraState, &mut CameraTargetState, &Transform)>,
     target_query: Query<(Entity, &GlobalTransform, &Health, Option<&Name>)>,
@@ -46,8 +52,7 @@ pub fn update_target_marking(
             
             if angle > controller.target_lock.fov_threshold { continue; }
             
-            // Score based on distance and angle (prefer center)
-            let score = dist * 0.5 + angle * 2.0; 
+            let score = lock_on_score(dist, angle);
             
             if score < min_score {
                 min_score = score;
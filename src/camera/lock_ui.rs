@@ -2,6 +2,8 @@ use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 
 use super::types::CameraTargetState;
+use crate::highlight::{HighlightCommandQueue, HighlightKind};
+use crate::hud::{HudElement, HudPriority};
 
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
@@ -27,6 +29,7 @@ pub fn setup_lock_on_reticle_ui(mut commands: Commands) {
             BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
             Visibility::Hidden,
             LockOnReticleRoot,
+            HudElement::new("lock_on_reticle", HudPriority::Essential),
         ))
         .with_children(|parent| {
             parent.spawn((
@@ -80,3 +83,25 @@ pub fn update_lock_on_reticle_ui(
         *visibility = Visibility::Hidden;
     }
 }
+
+/// Highlights the locked (or else marked) target
+pub fn highlight_camera_target(
+    camera_query: Query<&CameraTargetState>,
+    mut highlights: ResMut<HighlightCommandQueue>,
+    mut previous: Local<Option<Entity>>,
+) {
+    let target = camera_query
+        .iter()
+        .find_map(|state| state.locked_target.or(state.marked_target));
+    if *previous == target {
+        return;
+    }
+
+    if let Some(entity) = *previous {
+        highlights.release(entity, HighlightKind::EnemyMarked);
+    }
+    if let Some(entity) = target {
+        highlights.request(entity, HighlightKind::EnemyMarked);
+    }
+    *previous = target;
+}
@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use crate::input::{ConsoleAppExt, ConsoleCommand};
+use crate::utils::EventQueuePlugin;
 
 mod types;
 mod follow;
@@ -14,6 +16,9 @@ mod zones;
 mod bounds;
 mod waypoints;
 mod photo_mode;
+mod underwater;
+mod profiles;
+mod virtual_camera;
 
 // New Submodules
 pub mod effect;
@@ -36,19 +41,29 @@ pub use zones::*;
 pub use bounds::*;
 pub use waypoints::*;
 pub use photo_mode::*;
+pub use underwater::*;
+pub use profiles::*;
+pub use virtual_camera::*;
 
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
         app
-            .init_resource::<ShakeQueue>()
+            .add_plugins(EventQueuePlugin::<ShakeRequest>::default())
+            .add_plugins(EventQueuePlugin::<CameraWaterEvent>::default())
+            .add_plugins(EventQueuePlugin::<CameraProfileEvent>::default())
             .register_type::<CameraController>()
             .register_type::<CameraState>()
+            .register_type::<CameraProfileState>()
+            .register_type::<VirtualCamera>()
+            .register_type::<CameraBrain>()
             .register_type::<CameraWaypoint>()
             .register_type::<CameraWaypointTrack>()
             .register_type::<CameraWaypointFollower>()
-            .register_type::<CameraShakeInstance>()
+            .register_type::<CameraShake>()
+            .register_type::<ShakeLibrary>()
+            .register_type::<ShakeSettings>()
             .register_type::<PointShake>()
             .register_type::<CameraBobState>()
             .register_type::<CameraTargetState>()
@@ -57,8 +72,24 @@ impl Plugin for CameraPlugin {
             .register_type::<CameraZone>()
             .register_type::<CameraZoneTracker>()
             .register_type::<CameraBounds>()
+            .register_type::<UnderwaterCameraSettings>()
+            .register_type::<UnderwaterCameraState>()
+            .register_type::<LensDroplet>()
+            .register_type::<LookAtPoint>()
+            .register_type::<UnderwaterTintOverlay>()
+            .init_resource::<ShakeLibrary>()
+            .init_resource::<ShakeSettings>()
+            .init_resource::<UnderwaterCameraSettings>()
+            .init_resource::<UnderwaterAudioSnapshot>()
             .init_resource::<PhotoModeSettings>()
             .init_resource::<PhotoModeState>()
+            .init_resource::<PhotoGallery>()
+            .add_plugins(EventQueuePlugin::<PhotoModeCommand>::default())
+            .init_resource::<CameraProfiles>()
+            .add_console_command(
+                ConsoleCommand::new("camera_profile", "Blends the camera to a named profile").with_usage("<name> [seconds]"),
+                camera_profile_command,
+            )
             .add_plugins((
                 effect::CameraEffectPlugin,
                 captures::CameraCapturesPlugin,
@@ -69,18 +100,36 @@ impl Plugin for CameraPlugin {
             ))
             .add_systems(Startup, (
                 setup_lock_on_reticle_ui,
-            ))
+                setup_underwater_overlay,
+                setup_photo_mode_ui,
+            ).in_set(crate::headless::PresentationSet))
+            .add_systems(Startup, load_photo_gallery)
+            .add_systems(Update, (
+                handle_photo_mode_buttons,
+                update_photo_mode_ui,
+            ).chain().after(CameraSet::Rotation).in_set(crate::headless::PresentationSet))
+            .add_systems(Update, collect_photo_gallery.after(captures::handle_screenshot_requests))
+            .configure_sets(Update, (
+                CameraSet::Rotation,
+                CameraSet::Follow,
+            ).chain().in_set(crate::GameControllerSet::Camera))
             .add_systems(Update, (
+                update_camera_shoulder,
                 update_camera_state_offsets,
                 update_target_marking,
                 update_target_lock,
+                apply_pinch_zoom,
+                handle_camera_profile_events,
+                update_camera_profiles,
                 update_camera_zones,
                 apply_camera_zone_settings,
+                follow_anchored_surface_yaw,
                 update_camera_rotation,
-                handle_photo_mode_toggle,
+                handle_photo_mode_commands,
                 update_photo_mode,
-            ).chain())
+            ).chain().in_set(CameraSet::Rotation))
             .add_systems(Update, (
+                shake_on_landing,
                 update_camera_shake,
                 update_camera_bob,
                 update_camera_lean_collision,
@@ -89,8 +138,20 @@ impl Plugin for CameraPlugin {
                 update_camera_fov,
                 update_camera_waypoint_follow,
                 handle_camera_mode_switch,
-                update_lock_on_reticle_ui,
-            ).chain());
+                update_lock_on_reticle_ui.in_set(crate::headless::PresentationSet),
+                highlight_camera_target,
+            ).chain().in_set(CameraSet::Follow))
+            .add_systems(Update, restore_camera_rig_pose.before(CameraSet::Rotation).in_set(crate::GameControllerSet::Camera))
+            .add_systems(Update, (
+                update_zone_virtual_cameras,
+                update_virtual_cameras,
+                update_camera_brain,
+            ).chain().after(CameraSet::Follow).before(update_underwater_camera_state).in_set(crate::GameControllerSet::Camera))
+            .add_systems(Update, (
+                update_underwater_camera_state,
+                update_underwater_audio,
+                (apply_underwater_effects, update_wet_lens).in_set(crate::headless::PresentationSet),
+            ).chain().after(CameraSet::Follow).in_set(crate::GameControllerSet::Camera));
     }
 }
 
@@ -108,6 +169,8 @@ pub fn spawn_camera(
             current_distance: 4.0,
             ..default()
         },
+        CameraProfileState::default(),
+        CameraBrain::default(),
         CameraBobState::default(),
         CameraTargetState::default(),
         Transform::from_xyz(0.0, 5.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y),
@@ -7,7 +7,7 @@ pub struct PlayerCullingPlugin;
 impl Plugin for PlayerCullingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PlayerCullingSettings>()
-           .add_systems(Update, update_player_culling);
+           .add_systems(Update, update_player_culling.in_set(crate::headless::PresentationSet));
     }
 }
 
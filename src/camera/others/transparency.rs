@@ -12,7 +12,7 @@ impl Plugin for TransparencyPlugin {
            .add_systems(Update, (
                update_transparent_surfaces,
                apply_surface_transparency,
-           ).chain());
+           ).chain().in_set(crate::headless::PresentationSet));
     }
 }
 
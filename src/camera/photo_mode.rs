@@ -1,8 +1,30 @@
+//! Photo mode
+//!
+//! The reset-camera action pauses the game and detaches the camera. It then
+//! flies freely within `max_camera_radius` of where it started: move to fly,
+//! zoom in and out to rise and sink, lean to roll. A panel sets the field of
+//! view and exposure, picks a filter from the [`PhotoFilterLibrary`], hides the
+//! HUD and the player, and takes photos (also on the interact action).
+//!
+//! Photos are written to `capture_directory` as PNG files and collected in the
+//! [`PhotoGallery`], together with the ones already there from earlier
+//! sessions. The panel browses them. Games can drive all of it through the
+//! [`PhotoModeCommandQueue`].
+
+use std::path::Path;
+use bevy::asset::RenderAssetUsages;
+use bevy::image::{CompressedImageFormats, ImageSampler, ImageType};
+use bevy::post_process::effect_stack::ChromaticAberration;
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use bevy::render::view::ColorGrading;
 
+use crate::character::Player;
 use crate::input::InputState;
-use super::captures::ScreenshotEventQueue;
-use super::types::CameraController;
+use crate::utils::{EventQueue, QueueReader};
+use super::captures::{CaptureSlot, PhotoTakenEvent, ScreenshotEventQueue, TakeScreenshotEvent};
+use super::effect::PhotoFilterLibrary;
+use super::types::{CameraController, CameraState};
 
 #[derive(Resource, Debug, Clone)]
 pub struct PhotoModeSettings {
@@ -15,6 +37,18 @@ pub struct PhotoModeSettings {
     pub roll_speed: f32,
     pub clamp_camera_distance: bool,
     pub max_camera_radius: f32,
+    /// Freeze game time while in photo mode
+    pub pause_game: bool,
+    /// Whether the HUD starts hidden
+    pub hide_hud_on_enter: bool,
+    /// Field of view slider range, in degrees
+    pub fov_range: Vec2,
+    pub fov_step: f32,
+    /// Exposure slider range, in stops
+    pub exposure_range: Vec2,
+    pub exposure_step: f32,
+    /// Directory photos are written to and the gallery is read from
+    pub capture_directory: String,
 }
 
 impl Default for PhotoModeSettings {
@@ -29,10 +63,27 @@ impl Default for PhotoModeSettings {
             roll_speed: 1.0,
             clamp_camera_distance: true,
             max_camera_radius: 15.0,
+            pause_game: true,
+            hide_hud_on_enter: true,
+            fov_range: Vec2::new(20.0, 110.0),
+            fov_step: 5.0,
+            exposure_range: Vec2::new(-3.0, 3.0),
+            exposure_step: 0.25,
+            capture_directory: "screenshots".to_string(),
         }
     }
 }
 
+impl PhotoModeSettings {
+    /// File a photo taken now is written to
+    pub fn capture_path(&self) -> String {
+        format!("{}/photo_{}.png", self.capture_directory, chrono::Local::now().format("%Y%m%d_%H%M%S_%3f"))
+    }
+}
+
+/// Frames the panel stays hidden around a capture, so it isn't in the photo
+const CAPTURE_HIDE_FRAMES: u8 = 4;
+
 #[derive(Resource, Debug, Default)]
 pub struct PhotoModeState {
     pub active: bool,
@@ -41,67 +92,347 @@ pub struct PhotoModeState {
     pub roll_angle: f32,
     pub last_camera_pos: Vec3,
     pub stored_enabled: bool,
+    /// Field of view, in degrees
+    pub fov: f32,
+    /// Exposure on top of the filter's, in stops
+    pub exposure: f32,
+    /// Index in the `PhotoFilterLibrary`
+    pub filter: usize,
+    pub hide_hud: bool,
+    pub hide_player: bool,
+    pub gallery_open: bool,
+    pub gallery_index: usize,
+    /// Frames until the panel shows again after a capture
+    pub capture_countdown: u8,
+    /// Filter or exposure changed since they were applied to the camera
+    pub grading_dirty: bool,
+    /// Whether the filter added chromatic aberration to the camera
+    pub aberration_applied: bool,
+    pub paused: bool,
+    pub stored_time_speed: f32,
+    pub stored_fov_override: Option<f32>,
+    pub stored_grading: Option<ColorGrading>,
+    pub hidden_players: Vec<(Entity, Visibility)>,
 }
 
-pub fn handle_photo_mode_toggle(
-    input: Res<InputState>,
-    settings: Res<PhotoModeSettings>,
+/// Requests to photo mode, from the panel or the game
+#[derive(Debug, Clone, PartialEq)]
+pub enum PhotoModeCommand {
+    Toggle,
+    /// Degrees added to the field of view
+    AdjustFov(f32),
+    /// Stops added to the exposure
+    AdjustExposure(f32),
+    /// Moves through the filters, wrapping around
+    StepFilter(i32),
+    SetFilter(usize),
+    ToggleHud,
+    TogglePlayer,
+    Capture,
+    ShowGallery(bool),
+    /// Moves through the gallery, wrapping around
+    StepGallery(i32),
+}
+
+pub type PhotoModeCommandQueue = EventQueue<PhotoModeCommand>;
+
+/// A photo on disk
+#[derive(Debug, Clone)]
+pub struct PhotoGalleryEntry {
+    pub slot: CaptureSlot,
+    pub path: String,
+    /// Perspectives the photo framed; unknown for photos of earlier sessions
+    pub subjects: Vec<String>,
+}
+
+/// Photos taken, oldest first
+#[derive(Resource, Debug, Default)]
+pub struct PhotoGallery {
+    pub entries: Vec<PhotoGalleryEntry>,
+}
+
+impl PhotoGallery {
+    pub fn add(&mut self, entry: PhotoGalleryEntry) {
+        if !self.entries.iter().any(|existing| existing.path == entry.path) {
+            self.entries.push(entry);
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&PhotoGalleryEntry> {
+        self.entries.get(index)
+    }
+
+    /// Index `steps` photos away from `index`, wrapping around
+    pub fn step(&self, index: usize, steps: i32) -> usize {
+        if self.entries.is_empty() {
+            return 0;
+        }
+        (index as i32 + steps).rem_euclid(self.entries.len() as i32) as usize
+    }
+
+    /// Adds the PNG files of `directory`, in name order
+    pub fn scan(&mut self, directory: &Path) {
+        let Ok(files) = std::fs::read_dir(directory) else { return };
+        let mut paths: Vec<_> = files
+            .flatten()
+            .map(|file| file.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "png"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let date = std::fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .map(|modified| chrono::DateTime::<chrono::Local>::from(modified).format("%Y-%m-%d %H:%M:%S").to_string())
+                .unwrap_or_default();
+            let name = path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+            self.add(PhotoGalleryEntry {
+                slot: CaptureSlot { name, date, camera_pos: Vec3::ZERO, camera_rot: Quat::IDENTITY, fov: 0.0 },
+                path: path.to_string_lossy().into_owned(),
+                subjects: Vec::new(),
+            });
+        }
+    }
+}
+
+fn set_players_hidden(state: &mut PhotoModeState, hidden: bool, player_query: &mut Query<(Entity, &mut Visibility), With<Player>>) {
+    if hidden {
+        for (entity, mut visibility) in player_query.iter_mut() {
+            state.hidden_players.push((entity, *visibility));
+            *visibility = Visibility::Hidden;
+        }
+    } else {
+        for (entity, previous) in state.hidden_players.drain(..) {
+            if let Ok((_, mut visibility)) = player_query.get_mut(entity) {
+                *visibility = previous;
+            }
+        }
+    }
+}
+
+/// Camera parts stored on entering photo mode and restored on leaving it
+type PhotoCameraRestore<'a> = (
+    Entity,
+    &'a Transform,
+    &'a mut CameraController,
+    &'a mut CameraState,
+    Option<&'a Projection>,
+    Option<&'a ColorGrading>,
+);
+
+/// Camera parts photo mode flies and sets the lens of
+type PhotoCameraLens<'a> = (Entity, &'a mut Transform, &'a mut CameraState, Option<&'a mut Projection>);
+
+/// Photo mode settings, filters and taken photos
+#[derive(SystemParam)]
+pub struct PhotoModeConfig<'w> {
+    pub settings: Res<'w, PhotoModeSettings>,
+    pub filters: Res<'w, PhotoFilterLibrary>,
+    pub gallery: Res<'w, PhotoGallery>,
+}
+
+/// Player input and queued commands driving photo mode
+#[derive(SystemParam)]
+pub struct PhotoModeRequests<'w, 's> {
+    pub input: Res<'w, InputState>,
+    pub requests: QueueReader<'w, 's, PhotoModeCommand>,
+}
+
+/// System to enter and leave photo mode and apply its commands
+pub fn handle_photo_mode_commands(
+    mut commands: Commands,
+    requests: PhotoModeRequests,
+    config: PhotoModeConfig,
     mut state: ResMut<PhotoModeState>,
-    mut camera_query: Query<(&mut Transform, &mut CameraController)>,
+    mut time: ResMut<Time<Virtual>>,
+    mut camera_query: Query<PhotoCameraRestore>,
+    mut player_query: Query<(Entity, &mut Visibility), With<Player>>,
 ) {
+    let PhotoModeConfig { settings, filters, gallery } = config;
+    let PhotoModeRequests { input, mut requests } = requests;
     if !settings.enabled {
         return;
     }
 
-    if !input.reset_camera_pressed {
+    let mut pending: Vec<PhotoModeCommand> = requests.read().cloned().collect();
+    if input.reset_camera_pressed {
+        pending.push(PhotoModeCommand::Toggle);
+    }
+    if state.active && input.interact_pressed {
+        pending.push(PhotoModeCommand::Capture);
+    }
+    if pending.is_empty() {
         return;
     }
 
-    let Ok((mut transform, mut controller)) = camera_query.single_mut() else {
+    let Ok((camera, transform, mut controller, mut camera_state, projection, grading)) = camera_query.single_mut() else {
         return;
     };
 
-    state.active = !state.active;
+    for command in pending {
+        match command {
+            PhotoModeCommand::Toggle if !state.active => {
+                state.active = true;
+                state.stored_enabled = controller.enabled;
+                controller.enabled = false;
+                state.last_camera_pos = transform.translation;
+                let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+                state.look_angle = Vec2::new(yaw.to_degrees(), pitch.to_degrees());
+                state.roll_angle = 0.0;
+                state.rotation_active = true;
 
-    if state.active {
-        state.stored_enabled = controller.enabled;
-        controller.enabled = false;
-        state.last_camera_pos = transform.translation;
-        state.look_angle = Vec2::ZERO;
-        state.roll_angle = 0.0;
-        state.rotation_active = true;
-    } else {
-        controller.enabled = state.stored_enabled;
+                state.stored_fov_override = camera_state.fov_override;
+                state.fov = match projection {
+                    Some(Projection::Perspective(perspective)) => perspective.fov.to_degrees(),
+                    _ => controller.default_fov,
+                };
+                state.exposure = 0.0;
+                state.filter = 0;
+                state.stored_grading = grading.cloned();
+                state.grading_dirty = true;
+                state.hide_hud = settings.hide_hud_on_enter;
+                state.hide_player = false;
+                state.gallery_open = false;
+                state.capture_countdown = 0;
+
+                state.paused = settings.pause_game;
+                if state.paused {
+                    state.stored_time_speed = time.relative_speed();
+                    time.set_relative_speed(0.0);
+                }
+            }
+            PhotoModeCommand::Toggle => {
+                state.active = false;
+                controller.enabled = state.stored_enabled;
+                camera_state.fov_override = state.stored_fov_override;
+                if state.paused {
+                    time.set_relative_speed(state.stored_time_speed);
+                    state.paused = false;
+                }
+                match state.stored_grading.take() {
+                    Some(grading) => {
+                        commands.entity(camera).insert(grading);
+                    }
+                    None => {
+                        commands.entity(camera).remove::<ColorGrading>();
+                    }
+                }
+                if state.aberration_applied {
+                    commands.entity(camera).remove::<ChromaticAberration>();
+                    state.aberration_applied = false;
+                }
+                set_players_hidden(&mut state, false, &mut player_query);
+                state.hide_player = false;
+                state.gallery_open = false;
+            }
+            _ if !state.active => {}
+            PhotoModeCommand::AdjustFov(delta) => {
+                state.fov = (state.fov + delta).clamp(settings.fov_range.x, settings.fov_range.y);
+            }
+            PhotoModeCommand::AdjustExposure(delta) => {
+                state.exposure = (state.exposure + delta).clamp(settings.exposure_range.x, settings.exposure_range.y);
+                state.grading_dirty = true;
+            }
+            PhotoModeCommand::StepFilter(steps) => {
+                state.filter = filters.step(state.filter, steps);
+                state.grading_dirty = true;
+            }
+            PhotoModeCommand::SetFilter(index) => {
+                if filters.get(index).is_some() {
+                    state.filter = index;
+                    state.grading_dirty = true;
+                }
+            }
+            PhotoModeCommand::ToggleHud => state.hide_hud = !state.hide_hud,
+            PhotoModeCommand::TogglePlayer => {
+                state.hide_player = !state.hide_player;
+                let hidden = state.hide_player;
+                set_players_hidden(&mut state, hidden, &mut player_query);
+            }
+            PhotoModeCommand::Capture => {
+                if state.capture_countdown == 0 {
+                    state.capture_countdown = CAPTURE_HIDE_FRAMES;
+                }
+            }
+            PhotoModeCommand::ShowGallery(open) => {
+                state.gallery_open = open;
+                state.gallery_index = gallery.entries.len().saturating_sub(1);
+            }
+            PhotoModeCommand::StepGallery(steps) => {
+                state.gallery_index = gallery.step(state.gallery_index, steps);
+            }
+        }
     }
 }
 
+/// System to fly the photo mode camera, apply its lens settings and filter,
+/// and take the photos
 pub fn update_photo_mode(
-    time: Res<Time>,
+    mut commands: Commands,
+    time: Res<Time<Real>>,
     input: Res<InputState>,
-    settings: Res<PhotoModeSettings>,
+    config: PhotoModeConfig,
     mut state: ResMut<PhotoModeState>,
-    mut camera_query: Query<&mut Transform, With<CameraController>>,
+    mut camera_query: Query<PhotoCameraLens, With<CameraController>>,
     mut screenshot_queue: ResMut<ScreenshotEventQueue>,
 ) {
+    let PhotoModeConfig { settings, filters, .. } = config;
     if !state.active || !settings.enabled {
         return;
     }
 
-    let Ok(mut transform) = camera_query.single_mut() else {
+    let Ok((camera, mut transform, mut camera_state, projection)) = camera_query.single_mut() else {
         return;
     };
 
-    if input.interact_pressed {
-        screenshot_queue.0.push(super::captures::TakeScreenshotEvent {
-            path: None,
-            metadata: None,
-        });
+    // The panel is hidden from the first countdown frame; shoot once it's gone
+    if state.capture_countdown > 0 {
+        state.capture_countdown -= 1;
+        if state.capture_countdown == CAPTURE_HIDE_FRAMES / 2 {
+            if let Err(error) = std::fs::create_dir_all(&settings.capture_directory) {
+                warn!("Could not create the photo directory {}: {}", settings.capture_directory, error);
+            }
+            screenshot_queue.send(TakeScreenshotEvent {
+                path: Some(settings.capture_path()),
+                metadata: None,
+            });
+        }
+    }
+
+    // Held against the FOV system once photo mode ends, and applied directly
+    // since that system doesn't move while the game is paused
+    camera_state.fov_override = Some(state.fov);
+    if let Some(mut projection) = projection {
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = state.fov.to_radians();
+        }
+    }
+
+    if state.grading_dirty {
+        state.grading_dirty = false;
+        let filter = filters.get(state.filter).cloned().unwrap_or_default();
+        commands.entity(camera).insert(filter.color_grading(state.exposure));
+        match filter.chromatic_aberration() {
+            Some(aberration) => {
+                commands.entity(camera).insert(aberration);
+                state.aberration_applied = true;
+            }
+            None if state.aberration_applied => {
+                commands.entity(camera).remove::<ChromaticAberration>();
+                state.aberration_applied = false;
+            }
+            None => {}
+        }
+    }
+
+    if state.gallery_open {
+        return;
     }
 
     let dt = time.delta_secs();
 
     if state.rotation_active {
-        state.look_angle.x += input.look.x * settings.rotation_speed;
+        state.look_angle.x -= input.look.x * settings.rotation_speed;
         state.look_angle.y -= input.look.y * settings.rotation_speed;
     }
 
@@ -147,3 +478,310 @@ pub fn update_photo_mode(
         }
     }
 }
+
+/// System to add the photos already in the capture directory to the gallery
+pub fn load_photo_gallery(settings: Res<PhotoModeSettings>, mut gallery: ResMut<PhotoGallery>) {
+    gallery.scan(Path::new(&settings.capture_directory));
+}
+
+/// System to add the photos written to disk to the gallery
+pub fn collect_photo_gallery(mut photos: QueueReader<PhotoTakenEvent>, mut gallery: ResMut<PhotoGallery>) {
+    for photo in photos.read() {
+        let Some(path) = &photo.path else { continue };
+        gallery.add(PhotoGalleryEntry {
+            slot: photo.slot.clone(),
+            path: path.clone(),
+            subjects: photo.subjects.iter().map(|subject| subject.name.clone()).collect(),
+        });
+    }
+}
+
+// ============================================================================
+// UI
+// ============================================================================
+
+const PHOTO_BUTTON_COLOR: Color = Color::srgba(0.15, 0.15, 0.15, 0.9);
+const PHOTO_BUTTON_HOVER_COLOR: Color = Color::srgba(0.3, 0.3, 0.3, 0.9);
+
+#[derive(Component)]
+pub struct PhotoModeRoot;
+
+#[derive(Component)]
+pub struct PhotoGalleryRoot;
+
+#[derive(Component)]
+pub struct PhotoGalleryImage;
+
+#[derive(Component)]
+pub struct PhotoGalleryCaption;
+
+/// Panel value shown by a text
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhotoModeValue {
+    Fov,
+    Exposure,
+    Filter,
+    Hud,
+    Player,
+}
+
+/// Panel button sending a command
+#[derive(Component, Debug, Clone)]
+pub struct PhotoModeButton(pub PhotoModeCommand);
+
+fn spawn_photo_button(parent: &mut ChildSpawnerCommands, label: &str, command: PhotoModeCommand) {
+    parent.spawn((
+        Button,
+        Node {
+            min_width: Val::Px(28.0),
+            height: Val::Px(26.0),
+            padding: UiRect::horizontal(Val::Px(8.0)),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor(PHOTO_BUTTON_COLOR),
+        PhotoModeButton(command),
+    )).with_children(|button| {
+        button.spawn((
+            Text::new(label),
+            TextFont { font_size: 15.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+    });
+}
+
+fn spawn_photo_value(parent: &mut ChildSpawnerCommands, value: PhotoModeValue) {
+    parent.spawn((
+        Text::new(""),
+        TextFont { font_size: 15.0, ..default() },
+        TextColor(Color::WHITE),
+        Node { width: Val::Px(130.0), ..default() },
+        value,
+    ));
+}
+
+fn spawn_photo_row(parent: &mut ChildSpawnerCommands, build: impl FnOnce(&mut ChildSpawnerCommands)) {
+    parent.spawn(Node {
+        flex_direction: FlexDirection::Row,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(6.0),
+        ..default()
+    }).with_children(build);
+}
+
+/// System to setup the photo mode panel and gallery (hidden by default)
+pub fn setup_photo_mode_ui(mut commands: Commands, settings: Res<PhotoModeSettings>) {
+    let fov_step = settings.fov_step;
+    let exposure_step = settings.exposure_step;
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(20.0),
+            bottom: Val::Px(20.0),
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Val::Px(12.0)),
+            row_gap: Val::Px(6.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        PhotoModeRoot,
+        Visibility::Hidden,
+    )).with_children(|panel| {
+        panel.spawn((
+            Text::new("PHOTO MODE"),
+            TextFont { font_size: 18.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+        spawn_photo_row(panel, |row| {
+            spawn_photo_button(row, "-", PhotoModeCommand::AdjustFov(-fov_step));
+            spawn_photo_value(row, PhotoModeValue::Fov);
+            spawn_photo_button(row, "+", PhotoModeCommand::AdjustFov(fov_step));
+        });
+        spawn_photo_row(panel, |row| {
+            spawn_photo_button(row, "-", PhotoModeCommand::AdjustExposure(-exposure_step));
+            spawn_photo_value(row, PhotoModeValue::Exposure);
+            spawn_photo_button(row, "+", PhotoModeCommand::AdjustExposure(exposure_step));
+        });
+        spawn_photo_row(panel, |row| {
+            spawn_photo_button(row, "<", PhotoModeCommand::StepFilter(-1));
+            spawn_photo_value(row, PhotoModeValue::Filter);
+            spawn_photo_button(row, ">", PhotoModeCommand::StepFilter(1));
+        });
+        spawn_photo_row(panel, |row| {
+            spawn_photo_button(row, "HUD", PhotoModeCommand::ToggleHud);
+            spawn_photo_value(row, PhotoModeValue::Hud);
+        });
+        spawn_photo_row(panel, |row| {
+            spawn_photo_button(row, "Player", PhotoModeCommand::TogglePlayer);
+            spawn_photo_value(row, PhotoModeValue::Player);
+        });
+        spawn_photo_row(panel, |row| {
+            spawn_photo_button(row, "Capture", PhotoModeCommand::Capture);
+            spawn_photo_button(row, "Gallery", PhotoModeCommand::ShowGallery(true));
+        });
+    });
+
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(10.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        PhotoGalleryRoot,
+        Visibility::Hidden,
+    )).with_children(|gallery| {
+        gallery.spawn((
+            ImageNode::default(),
+            Node { width: Val::Percent(70.0), height: Val::Percent(70.0), ..default() },
+            PhotoGalleryImage,
+        ));
+        gallery.spawn((
+            Text::new(""),
+            TextFont { font_size: 16.0, ..default() },
+            TextColor(Color::WHITE),
+            PhotoGalleryCaption,
+        ));
+        spawn_photo_row(gallery, |row| {
+            spawn_photo_button(row, "<", PhotoModeCommand::StepGallery(-1));
+            spawn_photo_button(row, "Close", PhotoModeCommand::ShowGallery(false));
+            spawn_photo_button(row, ">", PhotoModeCommand::StepGallery(1));
+        });
+    });
+}
+
+/// System to send the commands of clicked panel buttons
+pub fn handle_photo_mode_buttons(
+    mut button_query: Query<(&Interaction, &PhotoModeButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut requests: ResMut<PhotoModeCommandQueue>,
+) {
+    for (interaction, button, mut background) in button_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => requests.send(button.0.clone()),
+            Interaction::Hovered => background.0 = PHOTO_BUTTON_HOVER_COLOR,
+            Interaction::None => background.0 = PHOTO_BUTTON_COLOR,
+        }
+    }
+}
+
+/// Panel, gallery and their texts
+#[derive(SystemParam)]
+pub struct PhotoModeWidgets<'w, 's> {
+    pub panel_query: Query<'w, 's, &'static mut Visibility, (With<PhotoModeRoot>, Without<PhotoGalleryRoot>)>,
+    pub gallery_query: Query<'w, 's, &'static mut Visibility, (With<PhotoGalleryRoot>, Without<PhotoModeRoot>)>,
+    pub value_query: Query<'w, 's, (&'static PhotoModeValue, &'static mut Text), Without<PhotoGalleryCaption>>,
+    pub caption_query: Query<'w, 's, &'static mut Text, With<PhotoGalleryCaption>>,
+    pub image_query: Query<'w, 's, &'static mut ImageNode, With<PhotoGalleryImage>>,
+}
+
+/// System to show the panel or the gallery and keep their values current
+pub fn update_photo_mode_ui(
+    state: Res<PhotoModeState>,
+    config: PhotoModeConfig,
+    mut images: ResMut<Assets<Image>>,
+    widgets: PhotoModeWidgets,
+    mut shown_photo: Local<Option<String>>,
+) {
+    let PhotoModeConfig { filters, gallery, .. } = config;
+    let PhotoModeWidgets { mut panel_query, mut gallery_query, mut value_query, mut caption_query, mut image_query } = widgets;
+    let panel_visible = state.active && !state.gallery_open && state.capture_countdown == 0;
+    let gallery_visible = state.active && state.gallery_open;
+    for mut visibility in panel_query.iter_mut() {
+        visibility.set_if_neq(if panel_visible { Visibility::Visible } else { Visibility::Hidden });
+    }
+    for mut visibility in gallery_query.iter_mut() {
+        visibility.set_if_neq(if gallery_visible { Visibility::Visible } else { Visibility::Hidden });
+    }
+
+    if panel_visible && state.is_changed() {
+        let on_off = |on: bool| if on { "Hidden" } else { "Shown" };
+        for (value, mut text) in value_query.iter_mut() {
+            text.0 = match value {
+                PhotoModeValue::Fov => format!("FOV {:.0}°", state.fov),
+                PhotoModeValue::Exposure => format!("Exposure {:+.2}", state.exposure),
+                PhotoModeValue::Filter => filters.get(state.filter).map_or_else(String::new, |filter| filter.name.clone()),
+                PhotoModeValue::Hud => on_off(state.hide_hud).to_string(),
+                PhotoModeValue::Player => on_off(state.hide_player).to_string(),
+            };
+        }
+    }
+
+    if !gallery_visible {
+        *shown_photo = None;
+        return;
+    }
+    let entry = gallery.get(state.gallery_index);
+    if entry.is_some() && entry.map(|entry| &entry.path) == shown_photo.as_ref() {
+        return;
+    }
+
+    for mut caption in caption_query.iter_mut() {
+        caption.0 = match entry {
+            Some(entry) => format!("{} / {}   {}   {}", state.gallery_index + 1, gallery.entries.len(), entry.slot.name, entry.slot.date),
+            None => "No photos yet".to_string(),
+        };
+    }
+
+    // Photos live outside the asset folder, so they are decoded here
+    let image = entry.and_then(|entry| {
+        let bytes = std::fs::read(&entry.path).ok()?;
+        Image::from_buffer(
+            &bytes,
+            ImageType::Extension("png"),
+            CompressedImageFormats::NONE,
+            true,
+            ImageSampler::Default,
+            RenderAssetUsages::default(),
+        )
+        .ok()
+    });
+    // A photo just taken may not be written yet; try again next frame
+    if entry.is_some() && image.is_none() {
+        return;
+    }
+    *shown_photo = entry.map(|entry| entry.path.clone());
+
+    // The previous photo is freed with its handle
+    for mut node in image_query.iter_mut() {
+        node.image = image.clone().map_or_else(Handle::default, |image| images.add(image));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_photo_filters_and_gallery() {
+        let filters = PhotoFilterLibrary::default();
+        assert_eq!(filters.get(0).unwrap().name, "None");
+        assert_eq!(filters.step(0, -1), filters.filters.len() - 1);
+        assert_eq!(filters.step(filters.filters.len() - 1, 1), 0);
+
+        let noir = filters.filters.iter().find(|filter| filter.name == "Noir").unwrap();
+        let grading = noir.color_grading(1.5);
+        assert_eq!(grading.global.post_saturation, 0.0);
+        assert_eq!(grading.global.exposure, 1.5);
+        assert_eq!(grading.midtones.contrast, noir.contrast);
+        assert!(noir.chromatic_aberration().is_none());
+
+        let mut gallery = PhotoGallery::default();
+        let entry = |path: &str| PhotoGalleryEntry {
+            slot: CaptureSlot { name: "Photo".into(), date: String::new(), camera_pos: Vec3::ZERO, camera_rot: Quat::IDENTITY, fov: 60.0 },
+            path: path.to_string(),
+            subjects: Vec::new(),
+        };
+        gallery.add(entry("screenshots/a.png"));
+        gallery.add(entry("screenshots/b.png"));
+        gallery.add(entry("screenshots/a.png"));
+        assert_eq!(gallery.entries.len(), 2);
+        assert_eq!(gallery.step(1, 1), 0);
+        assert_eq!(gallery.step(0, -1), 1);
+    }
+}
@@ -0,0 +1,371 @@
+//! Named camera profiles and blended transitions between them.
+//!
+//! A profile is a full set of framing values (mode, distance, FOV, pivot
+//! offset and smoothing). Gameplay requests one by name with a
+//! [`CameraProfileEvent`], and the camera eases from wherever it is to the new
+//! profile over the requested time, so a transition that interrupts another
+//! starts from the in-between framing instead of snapping:
+//!
+//! ```rust,ignore
+//! profile_events.send(CameraProfileEvent::new("Dialogue").with_blend(CameraBlendCurve::EaseInOut, 0.8));
+//! profile_events.send(CameraProfileEvent::new("Exploration"));
+//! ```
+//!
+//! Profiles set the camera's base values, so camera zones still override them
+//! and return to the active profile when the player leaves.
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::input::{console_arg, ConsoleArgs, ConsoleResult};
+use crate::utils::{EventQueue, QueueReader};
+use super::types::*;
+
+/// Easing of a profile transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum CameraBlendCurve {
+    Linear,
+    EaseIn,
+    EaseOut,
+    #[default]
+    EaseInOut,
+}
+
+impl CameraBlendCurve {
+    /// Blend weight at `t` in 0-1
+    pub fn sample(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            CameraBlendCurve::Linear => t,
+            CameraBlendCurve::EaseIn => t * t,
+            CameraBlendCurve::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            CameraBlendCurve::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// How a transition eases; a duration of 0 cuts
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct CameraBlend {
+    pub curve: CameraBlendCurve,
+    pub duration: f32,
+}
+
+impl Default for CameraBlend {
+    fn default() -> Self {
+        Self { curve: CameraBlendCurve::EaseInOut, duration: 0.5 }
+    }
+}
+
+impl CameraBlend {
+    pub fn cut() -> Self {
+        Self { curve: CameraBlendCurve::Linear, duration: 0.0 }
+    }
+}
+
+/// Framing values of a named camera profile
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct CameraProfile {
+    /// Switched at the start of the transition
+    pub mode: CameraMode,
+    pub distance: f32,
+    pub fov: f32,
+    pub pivot_offset: Vec3,
+    pub side_offset: f32,
+    /// Smoothing speeds of the camera; lower values are more damped
+    pub smooth_rotation_speed: f32,
+    pub pivot_smooth_speed: f32,
+    pub distance_smooth_speed: f32,
+}
+
+impl Default for CameraProfile {
+    fn default() -> Self {
+        Self::from_controller(&CameraController::default())
+    }
+}
+
+impl CameraProfile {
+    /// The profile the camera is framed by now
+    pub fn from_controller(controller: &CameraController) -> Self {
+        Self {
+            mode: controller.base_mode,
+            distance: controller.base_distance,
+            fov: controller.base_fov,
+            pivot_offset: controller.base_pivot_offset,
+            side_offset: controller.side_offset,
+            smooth_rotation_speed: controller.smooth_rotation_speed,
+            pivot_smooth_speed: controller.pivot_smooth_speed,
+            distance_smooth_speed: controller.distance_smooth_speed,
+        }
+    }
+
+    /// Values `t` of the way to `other`; the mode is `other`'s
+    pub fn lerp(&self, other: &CameraProfile, t: f32) -> Self {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        Self {
+            mode: other.mode,
+            distance: mix(self.distance, other.distance),
+            fov: mix(self.fov, other.fov),
+            pivot_offset: self.pivot_offset.lerp(other.pivot_offset, t),
+            side_offset: mix(self.side_offset, other.side_offset),
+            smooth_rotation_speed: mix(self.smooth_rotation_speed, other.smooth_rotation_speed),
+            pivot_smooth_speed: mix(self.pivot_smooth_speed, other.pivot_smooth_speed),
+            distance_smooth_speed: mix(self.distance_smooth_speed, other.distance_smooth_speed),
+        }
+    }
+
+    /// Sets the camera's base and current values
+    pub fn apply(&self, controller: &mut CameraController) {
+        controller.base_mode = self.mode;
+        controller.mode = self.mode;
+        controller.base_distance = self.distance;
+        controller.distance = self.distance;
+        controller.base_fov = self.fov;
+        controller.default_fov = self.fov;
+        controller.base_pivot_offset = self.pivot_offset;
+        controller.default_pivot_offset = self.pivot_offset;
+        controller.side_offset = self.side_offset;
+        controller.smooth_rotation_speed = self.smooth_rotation_speed;
+        controller.pivot_smooth_speed = self.pivot_smooth_speed;
+        controller.distance_smooth_speed = self.distance_smooth_speed;
+    }
+}
+
+/// Camera profiles by name
+#[derive(Resource, Debug, Clone)]
+pub struct CameraProfiles {
+    pub profiles: HashMap<String, CameraProfile>,
+    /// Blend of requests that don't name one
+    pub default_blend: CameraBlend,
+}
+
+impl Default for CameraProfiles {
+    fn default() -> Self {
+        let exploration = CameraProfile::default();
+        let profiles = HashMap::from([
+            ("Combat".to_string(), CameraProfile {
+                distance: 3.2,
+                fov: 65.0,
+                pivot_offset: Vec3::new(0.0, 1.5, 0.0),
+                smooth_rotation_speed: 25.0,
+                pivot_smooth_speed: 14.0,
+                ..exploration.clone()
+            }),
+            ("Aiming".to_string(), CameraProfile {
+                distance: 1.8,
+                fov: 45.0,
+                pivot_offset: Vec3::new(0.0, 1.5, 0.0),
+                side_offset: 0.7,
+                smooth_rotation_speed: 30.0,
+                pivot_smooth_speed: 18.0,
+                ..exploration.clone()
+            }),
+            ("Dialogue".to_string(), CameraProfile {
+                distance: 2.2,
+                fov: 45.0,
+                pivot_offset: Vec3::new(0.0, 1.65, 0.0),
+                side_offset: 0.6,
+                smooth_rotation_speed: 6.0,
+                pivot_smooth_speed: 4.0,
+                distance_smooth_speed: 3.0,
+                ..exploration.clone()
+            }),
+            ("Vehicle".to_string(), CameraProfile {
+                distance: 7.0,
+                fov: 70.0,
+                pivot_offset: Vec3::new(0.0, 2.0, 0.0),
+                side_offset: 0.0,
+                smooth_rotation_speed: 10.0,
+                pivot_smooth_speed: 20.0,
+                ..exploration.clone()
+            }),
+            ("Exploration".to_string(), exploration),
+        ]);
+        Self { profiles, default_blend: CameraBlend::default() }
+    }
+}
+
+impl CameraProfiles {
+    pub fn insert(&mut self, name: impl Into<String>, profile: CameraProfile) -> &mut Self {
+        self.profiles.insert(name.into(), profile);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CameraProfile> {
+        self.profiles.get(name)
+    }
+}
+
+/// Request to move a camera to a named profile
+#[derive(Debug, Clone)]
+pub struct CameraProfileEvent {
+    /// `None` moves every camera
+    pub camera: Option<Entity>,
+    pub profile: String,
+    /// `None` uses `CameraProfiles::default_blend`
+    pub blend: Option<CameraBlend>,
+}
+
+impl CameraProfileEvent {
+    pub fn new(profile: impl Into<String>) -> Self {
+        Self { camera: None, profile: profile.into(), blend: None }
+    }
+
+    pub fn for_camera(mut self, camera: Entity) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    pub fn with_blend(mut self, curve: CameraBlendCurve, duration: f32) -> Self {
+        self.blend = Some(CameraBlend { curve, duration });
+        self
+    }
+}
+
+/// Queue for CameraProfileEvent
+pub type CameraProfileQueue = EventQueue<CameraProfileEvent>;
+
+/// Transition in progress
+#[derive(Debug, Clone, Reflect)]
+pub struct CameraProfileTransition {
+    pub from: CameraProfile,
+    pub to: CameraProfile,
+    pub blend: CameraBlend,
+    pub elapsed: f32,
+}
+
+/// Active profile of a camera
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct CameraProfileState {
+    pub current: String,
+    pub transition: Option<CameraProfileTransition>,
+}
+
+impl Default for CameraProfileState {
+    fn default() -> Self {
+        Self { current: "Exploration".to_string(), transition: None }
+    }
+}
+
+impl CameraProfileState {
+    /// Starts a transition from the camera's current framing
+    pub fn begin(&mut self, name: &str, from: CameraProfile, to: CameraProfile, blend: CameraBlend) {
+        self.current = name.to_string();
+        self.transition = Some(CameraProfileTransition { from, to, blend, elapsed: 0.0 });
+    }
+
+    /// Steps the transition; returns the framing to apply while one runs
+    pub fn advance(&mut self, dt: f32) -> Option<CameraProfile> {
+        let transition = self.transition.as_mut()?;
+        transition.elapsed += dt;
+        let t = if transition.blend.duration > 0.0 { transition.elapsed / transition.blend.duration } else { 1.0 };
+        if t >= 1.0 {
+            return self.transition.take().map(|transition| transition.to);
+        }
+        Some(transition.from.lerp(&transition.to, transition.blend.curve.sample(t)))
+    }
+}
+
+/// System to start profile transitions on request
+pub fn handle_camera_profile_events(
+    mut commands: Commands,
+    mut events: QueueReader<CameraProfileEvent>,
+    profiles: Res<CameraProfiles>,
+    mut camera_query: Query<(Entity, &CameraController, Option<&mut CameraProfileState>)>,
+) {
+    for event in events.read() {
+        let Some(profile) = profiles.get(&event.profile) else {
+            warn!("Unknown camera profile '{}'", event.profile);
+            continue;
+        };
+        let blend = event.blend.unwrap_or(profiles.default_blend);
+
+        for (entity, controller, state) in camera_query.iter_mut() {
+            if event.camera.is_some_and(|camera| camera != entity) {
+                continue;
+            }
+            let from = CameraProfile::from_controller(controller);
+            match state {
+                Some(mut state) => state.begin(&event.profile, from, profile.clone(), blend),
+                None => {
+                    let mut state = CameraProfileState::default();
+                    state.begin(&event.profile, from, profile.clone(), blend);
+                    commands.entity(entity).insert(state);
+                }
+            }
+        }
+    }
+}
+
+/// System to blend cameras toward their requested profile
+pub fn update_camera_profiles(
+    time: Res<Time>,
+    mut camera_query: Query<(&mut CameraController, &mut CameraProfileState)>,
+) {
+    let dt = time.delta_secs();
+    for (mut controller, mut state) in camera_query.iter_mut() {
+        if let Some(profile) = state.advance(dt) {
+            profile.apply(&mut controller);
+        }
+    }
+}
+
+/// Console `camera_profile <name> [seconds]`
+pub fn camera_profile_command(
+    In(args): In<ConsoleArgs>,
+    profiles: Res<CameraProfiles>,
+    mut events: ResMut<CameraProfileQueue>,
+) -> ConsoleResult {
+    let name: String = console_arg(&args, 0, "profile")?;
+    if profiles.get(&name).is_none() {
+        let mut names: Vec<&str> = profiles.profiles.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        return Err(format!("Unknown profile '{}', expected one of: {}", name, names.join(", ")));
+    }
+    let mut event = CameraProfileEvent::new(name.clone());
+    if args.len() > 1 {
+        let seconds: f32 = console_arg(&args, 1, "seconds")?;
+        event = event.with_blend(profiles.default_blend.curve, seconds);
+    }
+    events.send(event);
+    Ok(format!("Camera profile {}", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::CameraController;
+
+    #[test]
+    fn test_camera_profile_blending() {
+        use crate::camera::{CameraBlendCurve, CameraProfile, CameraProfileState, CameraProfiles};
+        assert_eq!(CameraBlendCurve::EaseInOut.sample(0.5), 0.5);
+        assert_eq!(CameraBlendCurve::EaseIn.sample(0.5), 0.25);
+        assert_eq!(CameraBlendCurve::EaseOut.sample(2.0), 1.0);
+
+        let profiles = CameraProfiles::default();
+        for name in ["Exploration", "Combat", "Aiming", "Dialogue", "Vehicle"] {
+            assert!(profiles.get(name).is_some(), "{}", name);
+        }
+        let from = CameraProfile::default();
+        let to = profiles.get("Vehicle").unwrap().clone();
+
+        let mut state = CameraProfileState::default();
+        state.begin("Vehicle", from.clone(), to.clone(), CameraBlend { curve: CameraBlendCurve::Linear, duration: 1.0 });
+        let halfway = state.advance(0.5).unwrap();
+        assert!((halfway.distance - (from.distance + to.distance) / 2.0).abs() < 1e-4);
+        assert!(state.transition.is_some());
+        assert_eq!(state.advance(0.6), Some(to.clone()));
+        assert!(state.transition.is_none());
+        assert_eq!(state.advance(0.1), None);
+        assert_eq!(state.current, "Vehicle");
+
+        let mut controller = CameraController::default();
+        to.apply(&mut controller);
+        assert_eq!(controller.base_distance, to.distance);
+        assert_eq!(CameraProfile::from_controller(&controller), to);
+
+        state.begin("Exploration", to.clone(), from.clone(), CameraBlend::cut());
+        assert_eq!(state.advance(0.0), Some(from));
+    }
+}
@@ -1,189 +1,415 @@
+//! Trauma-based camera shake
+//!
+//! Send a [`ShakeRequest`] naming a [`ShakePreset`] on the [`ShakeQueue`] and
+//! the preset's [`ShakeProfile`] from the [`ShakeLibrary`] adds trauma (0-1)
+//! to the cameras. A request with a `source` position adds less the farther
+//! the camera is from it, and nothing past the profile's `radius`. Trauma of
+//! the same preset stacks up to 1 and decays linearly; the camera turns by
+//! trauma squared times the profile's `max_angles`, following Perlin noise.
+//! Explosions, weapon recoil and hard landings shake the camera on their own.
+//!
+//! ```rust,ignore
+//! fn stomp(mut shakes: ResMut<ShakeQueue>, boss: Single<&GlobalTransform, With<Boss>>) {
+//!     shakes.shake_at(ShakePreset::FootstepHeavy, boss.translation());
+//! }
+//! ```
+
 use bevy::prelude::*;
+use bevy::platform::collections::HashMap;
+use avian3d::prelude::LinearVelocity;
+use bevy::ecs::entity::EntityHashMap;
 use super::types::*;
+use crate::character::Player;
+use crate::physics::GroundDetection;
+use crate::utils::{EventQueue, QueueReader};
+
+/// Name of a shake in the [`ShakeLibrary`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+pub enum ShakePreset {
+    Explosion,
+    /// Stomps of something big
+    FootstepHeavy,
+    Landing,
+    WeaponRecoilLight,
+    WeaponRecoilHeavy,
+    /// Game-specific shake registered under a name
+    Custom(String),
+}
 
-/// Trigger for a camera shake effect
+/// How a preset shakes the camera
 #[derive(Debug, Clone, Reflect)]
-pub struct ShakeRequest {
-    /// Name of the shake preset
-    pub name: String,
-    /// Multiplier for intensity
-    pub intensity: f32,
-    /// Custom duration (optional, overrides preset)
-    pub duration: Option<f32>,
+pub struct ShakeProfile {
+    /// Trauma added by a request of intensity 1 at the source
+    pub trauma: f32,
+    /// Trauma lost per second
+    pub decay: f32,
+    /// Pitch, yaw and roll at full trauma, in degrees
+    pub max_angles: Vec3,
+    /// Noise frequency, in Hz
+    pub frequency: f32,
+    /// Distance from the source at which the shake fades out
+    pub radius: f32,
 }
 
-/// Resource for queuing camera shakes from any system
-#[derive(Resource, Default)]
-pub struct ShakeQueue(pub Vec<ShakeRequest>);
+impl Default for ShakeProfile {
+    fn default() -> Self {
+        Self {
+            trauma: 0.5,
+            decay: 1.5,
+            max_angles: Vec3::new(3.0, 3.0, 2.0),
+            frequency: 15.0,
+            radius: 20.0,
+        }
+    }
+}
 
-#[derive(Component, Debug, Reflect)]
-#[reflect(Component)]
-pub struct CameraShakeInstance {
-    pub camera_entity: Option<Entity>,
-    pub name: String,
-    pub timer: f32,
-    pub duration: f32,
+/// Shake profiles by preset
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct ShakeLibrary {
+    pub profiles: HashMap<ShakePreset, ShakeProfile>,
+}
+
+impl Default for ShakeLibrary {
+    fn default() -> Self {
+        let mut profiles = HashMap::new();
+        profiles.insert(ShakePreset::Explosion, ShakeProfile {
+            trauma: 1.0,
+            decay: 0.8,
+            max_angles: Vec3::new(6.0, 6.0, 8.0),
+            frequency: 18.0,
+            radius: 30.0,
+        });
+        profiles.insert(ShakePreset::FootstepHeavy, ShakeProfile {
+            trauma: 0.35,
+            decay: 2.5,
+            max_angles: Vec3::new(3.0, 1.0, 1.5),
+            frequency: 10.0,
+            radius: 20.0,
+        });
+        profiles.insert(ShakePreset::Landing, ShakeProfile {
+            trauma: 0.6,
+            decay: 2.0,
+            max_angles: Vec3::new(5.0, 1.0, 2.0),
+            frequency: 12.0,
+            radius: 10.0,
+        });
+        profiles.insert(ShakePreset::WeaponRecoilLight, ShakeProfile {
+            trauma: 0.2,
+            decay: 6.0,
+            max_angles: Vec3::new(1.5, 0.8, 0.8),
+            frequency: 25.0,
+            radius: 5.0,
+        });
+        profiles.insert(ShakePreset::WeaponRecoilHeavy, ShakeProfile {
+            trauma: 0.5,
+            decay: 4.0,
+            max_angles: Vec3::new(4.0, 2.0, 2.0),
+            frequency: 20.0,
+            radius: 5.0,
+        });
+        Self { profiles }
+    }
+}
+
+impl ShakeLibrary {
+    /// Adds or replaces the profile of `preset`
+    pub fn register(&mut self, preset: ShakePreset, profile: ShakeProfile) {
+        self.profiles.insert(preset, profile);
+    }
+
+    pub fn get(&self, preset: &ShakePreset) -> Option<&ShakeProfile> {
+        self.profiles.get(preset)
+    }
+}
+
+/// Camera shake settings, including the landing hook
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct ShakeSettings {
+    pub enabled: bool,
+    /// Scales every shake
     pub intensity: f32,
-    pub pos_amount: Vec3,
-    pub rot_amount: Vec3,
-    pub pos_speed: Vec3,
-    pub rot_speed: Vec3,
-    pub pos_smooth: f32,
-    pub rot_smooth: f32,
-    pub current_pos: Vec3,
-    pub current_rot: Vec3,
-    pub decrease_in_time: bool,
-}
-
-impl Default for CameraShakeInstance {
+    /// Power the trauma is raised to; 2 keeps small trauma subtle
+    pub trauma_exponent: f32,
+    /// Scales the shake while aiming
+    pub aim_multiplier: f32,
+    /// Fall speed that starts to shake on landing
+    pub landing_min_speed: f32,
+    /// Fall speed for a full `Landing` shake
+    pub landing_full_speed: f32,
+}
+
+impl Default for ShakeSettings {
     fn default() -> Self {
         Self {
-            camera_entity: None,
-            name: "Default Shake".to_string(),
-            timer: 0.0,
-            duration: 0.5,
+            enabled: true,
             intensity: 1.0,
-            pos_amount: Vec3::new(0.05, 0.05, 0.05),
-            rot_amount: Vec3::new(2.0, 2.0, 2.0),
-            pos_speed: Vec3::new(15.0, 15.0, 15.0),
-            rot_speed: Vec3::new(15.0, 15.0, 15.0),
-            pos_smooth: 8.0,
-            rot_smooth: 8.0,
-            current_pos: Vec3::ZERO,
-            current_rot: Vec3::ZERO,
-            decrease_in_time: true,
+            trauma_exponent: 2.0,
+            aim_multiplier: 0.5,
+            landing_min_speed: 6.0,
+            landing_full_speed: 20.0,
+        }
+    }
+}
+
+/// Trigger for a camera shake
+#[derive(Debug, Clone, Reflect)]
+pub struct ShakeRequest {
+    pub preset: ShakePreset,
+    /// Multiplier for the preset's trauma
+    pub intensity: f32,
+    /// Where the shake comes from; cameras farther away shake less
+    pub source: Option<Vec3>,
+    /// Only cameras following this entity shake; every camera when `None`
+    pub target: Option<Entity>,
+}
+
+/// Resource for queuing camera shakes from any system
+pub type ShakeQueue = EventQueue<ShakeRequest>;
+
+impl EventQueue<ShakeRequest> {
+    /// Shakes every camera with `preset`
+    pub fn shake(&mut self, preset: ShakePreset) {
+        self.send(ShakeRequest { preset, intensity: 1.0, source: None, target: None });
+    }
+
+    /// Shakes cameras with `preset`, fading with their distance to `position`
+    pub fn shake_at(&mut self, preset: ShakePreset, position: Vec3) {
+        self.send(ShakeRequest { preset, intensity: 1.0, source: Some(position), target: None });
+    }
+
+    /// Shakes the cameras following `target` with `preset`
+    pub fn shake_for(&mut self, preset: ShakePreset, target: Entity, intensity: f32) {
+        self.send(ShakeRequest { preset, intensity, source: None, target: Some(target) });
+    }
+}
+
+/// Share of a shake left `distance` away from its source
+pub fn shake_attenuation(distance: f32, radius: f32) -> f32 {
+    if radius <= 0.0 {
+        return 1.0;
+    }
+    let falloff = (1.0 - distance / radius).clamp(0.0, 1.0);
+    falloff * falloff
+}
+
+/// 1D Perlin noise in [-1, 1]; 0 at whole numbers, a different curve per `seed`
+pub fn perlin_noise(x: f32, seed: u32) -> f32 {
+    let gradient = |cell: i32| {
+        let mut hash = (cell as u32).wrapping_mul(0x27d4_eb2d) ^ seed.wrapping_mul(0x1656_67b1);
+        hash ^= hash >> 15;
+        hash = hash.wrapping_mul(0x85eb_ca6b);
+        hash ^= hash >> 13;
+        hash as f32 / u32::MAX as f32 * 2.0 - 1.0
+    };
+    let cell = x.floor();
+    let t = x - cell;
+    let from = gradient(cell as i32) * t;
+    let to = gradient(cell as i32 + 1) * (t - 1.0);
+    let fade = t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+    // Plain 1D Perlin noise stays within ±0.5
+    ((from + (to - from) * fade) * 2.0).clamp(-1.0, 1.0)
+}
+
+/// Trauma of one preset on a camera
+#[derive(Debug, Clone, Reflect)]
+pub struct ShakeLayer {
+    pub preset: ShakePreset,
+    pub trauma: f32,
+}
+
+/// Trauma stacked on a camera, per preset
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct CameraShake {
+    pub layers: Vec<ShakeLayer>,
+    /// Noise time
+    pub time: f32,
+}
+
+impl CameraShake {
+    /// Stacks `amount` onto the trauma of `preset`, up to 1
+    pub fn add_trauma(&mut self, preset: ShakePreset, amount: f32) {
+        match self.layers.iter_mut().find(|layer| layer.preset == preset) {
+            Some(layer) => layer.trauma = (layer.trauma + amount).min(1.0),
+            None => self.layers.push(ShakeLayer { preset, trauma: amount.min(1.0) }),
         }
     }
+
+    /// Highest trauma of any preset
+    pub fn trauma(&self) -> f32 {
+        self.layers.iter().map(|layer| layer.trauma).fold(0.0, f32::max)
+    }
+
+    /// Advances the noise and lets every preset's trauma decay
+    pub fn step(&mut self, library: &ShakeLibrary, dt: f32) {
+        self.time += dt;
+        self.layers.retain_mut(|layer| {
+            let Some(profile) = library.get(&layer.preset) else { return false };
+            layer.trauma -= profile.decay * dt;
+            layer.trauma > 0.0
+        });
+    }
+
+    /// Pitch, yaw and roll of the shake, in degrees
+    pub fn rotation(&self, library: &ShakeLibrary, trauma_exponent: f32) -> Vec3 {
+        let mut rotation = Vec3::ZERO;
+        for (index, layer) in self.layers.iter().enumerate() {
+            let Some(profile) = library.get(&layer.preset) else { continue };
+            let shake = layer.trauma.clamp(0.0, 1.0).powf(trauma_exponent);
+            let x = self.time * profile.frequency;
+            let seed = index as u32 * 3;
+            rotation += profile.max_angles
+                * shake
+                * Vec3::new(perlin_noise(x, seed), perlin_noise(x, seed + 1), perlin_noise(x, seed + 2));
+        }
+        rotation
+    }
 }
 
-/// A world-space shake source that affects cameras within range
+/// A world-space shake source; while active, cameras in range keep gaining
+/// its preset's trauma every second
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
 pub struct PointShake {
-    pub name: String,
-    pub radius: f32,
+    pub preset: ShakePreset,
     pub intensity: f32,
     pub active: bool,
-    pub shake_using_distance: bool,
 }
 
 impl Default for PointShake {
     fn default() -> Self {
         Self {
-            name: "Explosion".to_string(),
-            radius: 10.0,
+            preset: ShakePreset::Explosion,
             intensity: 1.0,
             active: false,
-            shake_using_distance: true,
         }
     }
 }
 
-/// Apply noise and active shakes to the camera state
+/// Camera parts shake reads and writes
+type ShakenCamera<'a> = (Entity, &'a CameraController, &'a mut CameraState, &'a GlobalTransform, Option<&'a mut CameraShake>);
+
+/// Turn shake requests into camera trauma and trauma into shake, and apply
+/// the handheld noise
 pub fn update_camera_shake(
-    time: Res<Time>,
-    mut shakes_queue: ResMut<ShakeQueue>,
-    mut camera_query: Query<(&CameraController, &mut CameraState, &GlobalTransform, Entity)>,
     mut commands: Commands,
-    mut shakes_instances: Query<(Entity, &mut CameraShakeInstance)>,
+    time: Res<Time>,
+    settings: Res<ShakeSettings>,
+    library: Res<ShakeLibrary>,
+    mut shakes_queue: QueueReader<ShakeRequest>,
+    mut camera_query: Query<ShakenCamera>,
     point_shakes: Query<(&PointShake, &GlobalTransform)>,
 ) {
     let dt = time.delta_secs();
     let elapsed = time.elapsed_secs();
 
-    // 1. Trigger new shakes from queue
-    for request in shakes_queue.0.drain(..) {
-        if let Some((_, _, _, camera_ent)) = camera_query.iter_mut().next() {
-            commands.spawn(CameraShakeInstance {
-                camera_entity: Some(camera_ent),
-                name: request.name,
-                intensity: request.intensity,
-                duration: request.duration.unwrap_or(0.5),
-                ..default()
-            });
+    let mut requests: Vec<ShakeRequest> = shakes_queue.read().cloned().collect();
+    requests.extend(point_shakes.iter().filter(|(point, _)| point.active).map(|(point, point_transform)| ShakeRequest {
+        preset: point.preset.clone(),
+        intensity: point.intensity * dt,
+        source: Some(point_transform.translation()),
+        target: None,
+    }));
+
+    for (entity, camera, mut state, camera_transform, shake) in camera_query.iter_mut() {
+        let t = elapsed * 2.0;
+        let noise_x = (t * 0.5).sin() * 0.05;
+        let noise_y = (t * 0.8).cos() * 0.05;
+        let noise_mult = if state.is_aiming { 0.3 } else { 1.0 };
+        state.noise_offset = Vec2::new(noise_x * noise_mult, noise_y * noise_mult);
+
+        let mut added = Vec::new();
+        for request in requests.iter() {
+            if !settings.enabled || request.target.is_some_and(|target| camera.follow_target != Some(target)) {
+                continue;
+            }
+            let Some(profile) = library.get(&request.preset) else { continue };
+            let attenuation = request
+                .source
+                .map_or(1.0, |source| shake_attenuation(source.distance(camera_transform.translation()), profile.radius));
+            let amount = profile.trauma * request.intensity * attenuation;
+            if amount > 0.0 {
+                added.push((request.preset.clone(), amount));
+            }
         }
-    }
 
-    // 2. Proximity-based Point Shakes
-    for (point, point_gt) in point_shakes.iter() {
-        if !point.active { continue; }
-        
-        for (_, _, camera_gt, cam_ent) in camera_query.iter() {
-            let dist = point_gt.translation().distance(camera_gt.translation());
-            if dist <= point.radius {
-                let mut int_mult = 1.0;
-                if point.shake_using_distance {
-                    int_mult = 1.0 - (dist / point.radius);
+        let Some(mut shake) = shake else {
+            if !added.is_empty() {
+                let mut shake = CameraShake::default();
+                for (preset, amount) in added {
+                    shake.add_trauma(preset, amount);
                 }
-                
-                // Trigger an instance if not already shaking from this point?
-                // For simplicity, let's just spawn a one-shot instance.
-                // In a real system we might want a cooldown.
-                commands.spawn(CameraShakeInstance {
-                    camera_entity: Some(cam_ent),
-                    name: point.name.clone(),
-                    intensity: point.intensity * int_mult,
-                    duration: 0.3,
-                    ..default()
-                });
+                commands.entity(entity).insert(shake);
             }
+            state.shake_rotation = Vec3::ZERO;
+            continue;
+        };
+        if !settings.enabled {
+            shake.layers.clear();
+        }
+        for (preset, amount) in added {
+            shake.add_trauma(preset, amount);
         }
+        let aim_mult = if state.is_aiming { settings.aim_multiplier } else { 1.0 };
+        state.shake_rotation = shake.rotation(&library, settings.trauma_exponent) * settings.intensity * aim_mult;
+        shake.step(&library, dt);
     }
+}
 
-    // 3. Accumulate all active shakes
-    let mut camera_offsets: std::collections::HashMap<Entity, (Vec3, Vec3)> = std::collections::HashMap::new();
-
-    for (ent, mut shake) in shakes_instances.iter_mut() {
-        let Some(cam_ent) = shake.camera_entity else {
-            commands.entity(ent).despawn();
-            continue;
-        };
-        shake.timer += dt;
-        
-        if shake.timer >= shake.duration {
-            commands.entity(ent).despawn();
+/// System to shake the camera of players landing from a high fall
+pub fn shake_on_landing(
+    settings: Res<ShakeSettings>,
+    mut shakes: ResMut<ShakeQueue>,
+    mut fall_speeds: Local<EntityHashMap<f32>>,
+    player_query: Query<(Entity, &GroundDetection, &LinearVelocity), With<Player>>,
+) {
+    for (entity, ground, velocity) in player_query.iter() {
+        if !ground.is_grounded {
+            fall_speeds.insert(entity, (-velocity.y).max(0.0));
             continue;
         }
-
-        let phase = elapsed + shake.timer; // Add timer to phase to make multiple instances unique
-        
-        // Calculate target positions
-        let target_pos = Vec3::new(
-            (phase * shake.pos_speed.x).sin() * shake.pos_amount.x,
-            (phase * shake.pos_speed.y).sin() * shake.pos_amount.y,
-            (phase * shake.pos_speed.z).cos() * shake.pos_amount.z,
-        ) * shake.intensity;
-
-        let target_rot = Vec3::new(
-            (phase * shake.rot_speed.x).sin() * shake.rot_amount.x,
-            (phase * shake.rot_speed.y).sin() * shake.rot_amount.y,
-            (phase * shake.rot_speed.z).cos() * shake.rot_amount.z,
-        ) * shake.intensity;
-
-        let mut multiplier = 1.0;
-        if shake.decrease_in_time {
-            multiplier = 1.0 - (shake.timer / shake.duration);
+        let Some(fall_speed) = fall_speeds.remove(&entity) else { continue };
+        if fall_speed >= settings.landing_min_speed {
+            let range = (settings.landing_full_speed - settings.landing_min_speed).max(0.001);
+            let intensity = ((fall_speed - settings.landing_min_speed) / range).clamp(0.2, 1.0);
+            shakes.shake_for(ShakePreset::Landing, entity, intensity);
         }
+    }
+}
 
-        shake.current_pos = shake.current_pos.lerp(target_pos * multiplier, shake.pos_smooth * dt);
-        shake.current_rot = shake.current_rot.lerp(target_rot * multiplier, shake.rot_smooth * dt);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        let offsets = camera_offsets.entry(cam_ent).or_insert((Vec3::ZERO, Vec3::ZERO));
-        offsets.0 += shake.current_pos;
-        offsets.1 += shake.current_rot;
-    }
 
-    // 4. Apply to CameraState
-    for (_, mut state, _, cam_ent) in camera_query.iter_mut() {
-        let t = elapsed * 2.0;
-        let noise_x = (t * 0.5).sin() * 0.05;
-        let noise_y = (t * 0.8).cos() * 0.05;
-        let noise_mult = if state.is_aiming { 0.3 } else { 1.0 };
-        state.noise_offset = Vec2::new(noise_x * noise_mult, noise_y * noise_mult);
+    #[test]
+    fn test_camera_shake_trauma() {
+        let library = ShakeLibrary::default();
+        assert_eq!(shake_attenuation(0.0, 30.0), 1.0);
+        assert_eq!(shake_attenuation(15.0, 30.0), 0.25);
+        assert_eq!(shake_attenuation(40.0, 30.0), 0.0);
+        assert_eq!(perlin_noise(3.0, 7), 0.0);
+        assert!((0..200).all(|step| perlin_noise(step as f32 * 0.137, 1).abs() <= 1.0));
 
-        if let Some((_total_pos, total_rot)) = camera_offsets.get(&cam_ent) {
-            state.noise_offset.x += total_rot.y * 0.1;
-            state.noise_offset.y += total_rot.x * 0.1;
-        }
+        // Same presets stack up to 1, others get their own layer
+        let mut shake = CameraShake::default();
+        shake.add_trauma(ShakePreset::WeaponRecoilLight, 0.2);
+        shake.add_trauma(ShakePreset::WeaponRecoilLight, 0.2);
+        shake.add_trauma(ShakePreset::Explosion, 0.7);
+        shake.add_trauma(ShakePreset::Explosion, 0.7);
+        assert_eq!(shake.layers.len(), 2);
+        assert!((shake.layers[0].trauma - 0.4).abs() < 1e-6);
+        assert_eq!(shake.trauma(), 1.0);
+
+        shake.step(&library, 0.125);
+        assert!(shake.rotation(&library, 2.0).length() > 0.0);
+        // Recoil decays away long before the explosion
+        shake.step(&library, 0.1);
+        assert_eq!(shake.layers.len(), 1);
+        shake.step(&library, 2.0);
+        assert!(shake.layers.is_empty());
+        assert_eq!(shake.rotation(&library, 2.0), Vec3::ZERO);
     }
 }
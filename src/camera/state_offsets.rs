@@ -15,20 +15,17 @@ pub fn update_camera_state_offsets(
         let Some(target_ent) = controller.follow_target else { continue };
         let Ok((target_transform, movement)) = target_query.get(target_ent) else { continue };
 
-        // 1. Handle Side Switching Input
+        // 1. Handle Side Switching Input: swap away from the shoulder in use,
+        // which also overrides the automatic pick
         if input.side_switch_pressed {
-            controller.current_side = match controller.current_side {
-                CameraSide::Right => CameraSide::Left,
-                CameraSide::Left => CameraSide::Right,
-            };
+            controller.current_side = state.auto_side.unwrap_or(controller.current_side).opposite();
+            state.auto_side = None;
         }
 
-        // 2. Interpolate Side
-        let target_side_val = match controller.current_side {
-            CameraSide::Right => 1.0,
-            CameraSide::Left => -1.0,
-        };
-        let side_alpha = 1.0 - (-10.0 * dt).exp();
+        // 2. Interpolate Side, mirroring the offset across the character
+        let side = state.auto_side.unwrap_or(controller.current_side);
+        let target_side_val = side.sign() * (1.0 - state.side_offset_squeeze);
+        let side_alpha = 1.0 - (-controller.shoulder_swap_speed * dt).exp();
         state.current_side_interpolator = state.current_side_interpolator + (target_side_val - state.current_side_interpolator) * side_alpha;
 
         // 3. Determine Target Pivot Offset based on state
@@ -51,7 +48,7 @@ pub fn update_camera_state_offsets(
         
         if state.is_aiming {
             target_pivot_offset = controller.aim_pivot_offset;
-            target_pivot_offset.x *= target_side_val;
+            target_pivot_offset.x *= state.current_side_interpolator;
         } else {
             if controller.mode == CameraMode::ThirdPerson {
                 target_pivot_offset.x += controller.side_offset * state.current_side_interpolator;
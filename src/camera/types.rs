@@ -1,5 +1,15 @@
+use avian3d::prelude::LayerMask;
 use bevy::prelude::*;
 
+/// Camera phases, run in order inside `GameControllerSet::Camera`
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CameraSet {
+    /// State offsets, targeting, zones and orbit rotation
+    Rotation,
+    /// Shake, bob, follow, collision and FOV
+    Follow,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
 pub enum WaypointRotationMode {
     #[default]
@@ -120,6 +130,23 @@ pub enum CameraSide {
     Left,
 }
 
+impl CameraSide {
+    pub fn opposite(self) -> Self {
+        match self {
+            CameraSide::Right => CameraSide::Left,
+            CameraSide::Left => CameraSide::Right,
+        }
+    }
+
+    /// 1 on the right, -1 on the left
+    pub fn sign(self) -> f32 {
+        match self {
+            CameraSide::Right => 1.0,
+            CameraSide::Left => -1.0,
+        }
+    }
+}
+
 /// Camera side preference
 
 #[derive(Debug, Clone, Reflect)]
@@ -225,6 +252,16 @@ pub struct CameraController {
     pub default_pivot_offset: Vec3,
     pub aim_pivot_offset: Vec3,
     pub crouch_pivot_offset: Vec3,
+
+    // Shoulder
+    /// How fast the camera crosses over when swapping shoulders
+    pub shoulder_swap_speed: f32,
+    /// Move to the open shoulder while a wall crowds the current one
+    pub auto_shoulder_swap: bool,
+    /// Room kept beyond the side offset before a wall counts as crowding
+    pub shoulder_wall_margin: f32,
+    /// Seconds the chosen shoulder must stay clear before moving back to it
+    pub shoulder_return_delay: f32,
     
     // Leaning
     pub lean_amount: f32,
@@ -240,7 +277,29 @@ pub struct CameraController {
     
     // Collision
     pub use_collision: bool,
+    /// Radius of the sphere cast from the pivot to the camera
     pub collision_radius: f32,
+    /// Layers the camera collides with
+    pub collision_layers: LayerMask,
+    /// Whisker rays on each side of the view direction
+    pub collision_whiskers: u32,
+    /// Angle of the outermost whisker, in degrees
+    pub collision_whisker_angle: f32,
+    /// How fast the camera moves in ahead of walls the whiskers find
+    pub collision_pull_in_speed: f32,
+    /// How fast the camera moves back out once clear
+    pub collision_recovery_speed: f32,
+    /// Turn with the surface the target is climbing on, so the view stays
+    /// steady relative to it
+    pub follow_surface_rotation: bool,
+
+    // Free Look
+    /// Holding `InputAction::FreeLook` orbits the camera without recentering it
+    /// behind the character
+    pub free_look_enabled: bool,
+    /// Seconds after free look ends before the camera starts recentering
+    pub free_look_recenter_delay: f32,
+    pub free_look_recenter_speed: f32,
 
     // Target Lock
     pub target_lock: TargetLockSettings,
@@ -285,6 +344,11 @@ impl Default for CameraController {
             default_pivot_offset: Vec3::new(0.0, 1.6, 0.0),
             aim_pivot_offset: Vec3::new(0.5, 1.5, 0.0),
             crouch_pivot_offset: Vec3::new(0.0, 1.0, 0.0),
+
+            shoulder_swap_speed: 10.0,
+            auto_shoulder_swap: true,
+            shoulder_wall_margin: 0.3,
+            shoulder_return_delay: 0.75,
             
             lean_amount: 0.4,
             lean_angle: 15.0,
@@ -298,6 +362,16 @@ impl Default for CameraController {
             
             use_collision: true,
             collision_radius: 0.2,
+            collision_layers: LayerMask::ALL,
+            collision_whiskers: 2,
+            collision_whisker_angle: 30.0,
+            collision_pull_in_speed: 12.0,
+            collision_recovery_speed: 4.0,
+            follow_surface_rotation: true,
+
+            free_look_enabled: true,
+            free_look_recenter_delay: 0.6,
+            free_look_recenter_speed: 4.0,
 
             target_lock: TargetLockSettings::default(),
 
@@ -333,13 +407,39 @@ pub struct CameraState {
     pub current_distance: f32,
     pub current_pivot: Vec3,
     pub current_side_interpolator: f32, // -1.0 (Left) to 1.0 (Right)
+    /// Shoulder picked by leaning or wall avoidance, over `CameraController::current_side`
+    pub auto_side: Option<CameraSide>,
+    pub auto_side_timer: f32,
+    /// Part of the side offset given up because walls leave no room (0-1)
+    pub side_offset_squeeze: f32,
     pub current_lean: f32,
     pub noise_offset: Vec2,
+    /// Pitch, yaw and roll of the camera shake, in degrees
+    pub shake_rotation: Vec3,
+    /// Shake laid over the camera rotation last frame
+    pub applied_shake: Quat,
     pub bob_offset: Vec3,
     pub is_aiming: bool,
     pub is_crouching: bool,
     pub fov_override: Option<f32>,
     pub fov_override_speed: Option<f32>,
+    /// Distance the camera is held at by collision; `None` when unobstructed
+    pub collision_distance: Option<f32>,
+    pub free_look: FreeLookPhase,
+}
+
+/// Free-look progress of the orbit camera
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect)]
+pub enum FreeLookPhase {
+    /// The camera drifts back behind the character as usual
+    #[default]
+    Off,
+    /// Orbiting freely while the button is held
+    Looking,
+    /// Released; holding the view for the remaining seconds
+    Holding(f32),
+    /// Swinging back behind the character
+    Recentering,
 }
 #[derive(Resource, Reflect, Clone)]
 #[reflect(Resource)]
@@ -398,3 +498,18 @@ impl Default for TransparentSurface {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_camera_side_mirrors() {
+        use crate::camera::CameraSide;
+
+        assert_eq!(CameraSide::Right.opposite(), CameraSide::Left);
+        assert_eq!(CameraSide::Left.opposite().sign(), 1.0);
+        assert_eq!(CameraSide::Left.sign(), -CameraSide::Right.sign());
+    }
+}
@@ -0,0 +1,396 @@
+//! Underwater camera state
+//!
+//! When the camera goes below the surface of a [`WaterZone`] (the same surface
+//! test the swim system uses), it gets distance fog, a screen tint and
+//! chromatic distortion, and playing sounds are ducked to a muffled snapshot of
+//! their volumes. Coming out of the water restores the previous fog and volumes
+//! and runs droplets down the lens for a moment. A [`CameraWaterEvent`] is sent
+//! on each transition (splash sounds, breath meters...).
+
+use bevy::audio::{AudioSinkPlayback, Volume};
+use bevy::pbr::{DistanceFog, FogFalloff};
+use bevy::post_process::effect_stack::ChromaticAberration;
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+
+use super::types::CameraController;
+use crate::player::extra_movements::swim::{water_surface_at, WaterZone};
+use crate::utils::EventQueue;
+
+/// Look of the underwater camera state
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct UnderwaterCameraSettings {
+    pub enabled: bool,
+    pub fog_color: Color,
+    pub fog_start: f32,
+    pub fog_end: f32,
+    /// Full screen tint (alpha is its strength)
+    pub tint: Color,
+    /// Chromatic aberration intensity
+    pub distortion: f32,
+    /// Volume factor of sounds while submerged
+    pub muffled_volume: f32,
+    /// How far past the surface the camera must go to switch state (avoids flicker)
+    pub surface_margin: f32,
+    /// Seconds droplets stay on the lens after surfacing
+    pub wet_lens_duration: f32,
+    pub droplet_count: usize,
+}
+
+impl Default for UnderwaterCameraSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            fog_color: Color::srgb(0.05, 0.25, 0.35),
+            fog_start: 1.0,
+            fog_end: 25.0,
+            tint: Color::srgba(0.0, 0.3, 0.45, 0.35),
+            distortion: 0.04,
+            muffled_volume: 0.35,
+            surface_margin: 0.05,
+            wet_lens_duration: 1.5,
+            droplet_count: 14,
+        }
+    }
+}
+
+/// Water state of a camera; added to cameras with a [`CameraController`]
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct UnderwaterCameraState {
+    pub submerged: bool,
+    /// Surface height of the water zone around the camera
+    pub surface_height: Option<f32>,
+    pub wet_lens_timer: f32,
+    /// Whether the fog, tint and distortion are on
+    pub effects_applied: bool,
+    /// Fog the camera had before diving, restored on surfacing
+    #[reflect(ignore)]
+    pub previous_fog: Option<DistanceFog>,
+}
+
+/// Sent when a camera goes below or above a water surface
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct CameraWaterEvent {
+    pub camera: Entity,
+    pub submerged: bool,
+}
+
+pub type CameraWaterEventQueue = EventQueue<CameraWaterEvent>;
+
+/// Volumes of the sounds playing when the camera went underwater
+#[derive(Resource, Debug, Default)]
+pub struct UnderwaterAudioSnapshot {
+    pub active: bool,
+    pub volumes: HashMap<Entity, Volume>,
+}
+
+/// Full screen tint shown underwater
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct UnderwaterTintOverlay;
+
+/// Drop of water running down the lens
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct LensDroplet {
+    pub lifetime: f32,
+    pub age: f32,
+    /// Slide speed (percent of the screen height per second)
+    pub speed: f32,
+}
+
+/// System to spawn the (hidden) underwater tint overlay
+pub fn setup_underwater_overlay(mut commands: Commands, settings: Res<UnderwaterCameraSettings>) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(settings.tint),
+        GlobalZIndex(-5),
+        Visibility::Hidden,
+        UnderwaterTintOverlay,
+        Name::new("UnderwaterTint"),
+    ));
+}
+
+/// System to detect cameras crossing a water surface
+pub fn update_underwater_camera_state(
+    mut commands: Commands,
+    settings: Res<UnderwaterCameraSettings>,
+    zone_query: Query<(&WaterZone, &GlobalTransform)>,
+    mut camera_query: Query<(Entity, &Transform, Option<&mut UnderwaterCameraState>), With<CameraController>>,
+    mut water_events: ResMut<CameraWaterEventQueue>,
+) {
+    for (entity, transform, state) in camera_query.iter_mut() {
+        let Some(mut state) = state else {
+            commands.entity(entity).insert(UnderwaterCameraState::default());
+            continue;
+        };
+
+        let position = transform.translation;
+        state.surface_height = water_surface_at(zone_query.iter(), position);
+
+        let submerged = settings.enabled
+            && state.surface_height.is_some_and(|surface| {
+                // Stay in the current state until clearly past the surface
+                if state.submerged {
+                    position.y < surface + settings.surface_margin
+                } else {
+                    position.y < surface - settings.surface_margin
+                }
+            });
+        if submerged == state.submerged {
+            continue;
+        }
+
+        state.submerged = submerged;
+        if !submerged {
+            state.wet_lens_timer = settings.wet_lens_duration;
+        }
+        water_events.send(CameraWaterEvent { camera: entity, submerged });
+    }
+}
+
+/// System to switch the fog, tint and distortion with the camera's water state
+pub fn apply_underwater_effects(
+    mut commands: Commands,
+    settings: Res<UnderwaterCameraSettings>,
+    mut camera_query: Query<(Entity, &mut UnderwaterCameraState, Option<&DistanceFog>), Changed<UnderwaterCameraState>>,
+    mut overlay_query: Query<(&mut Visibility, &mut BackgroundColor), With<UnderwaterTintOverlay>>,
+) {
+    for (entity, mut state, fog) in camera_query.iter_mut() {
+        if state.submerged == state.effects_applied {
+            continue;
+        }
+        let state = state.bypass_change_detection();
+        state.effects_applied = state.submerged;
+
+        if state.submerged {
+            state.previous_fog = fog.cloned();
+            commands.entity(entity).insert((
+                DistanceFog {
+                    color: settings.fog_color,
+                    falloff: FogFalloff::Linear {
+                        start: settings.fog_start,
+                        end: settings.fog_end,
+                    },
+                    ..default()
+                },
+                ChromaticAberration {
+                    intensity: settings.distortion,
+                    ..default()
+                },
+            ));
+        } else {
+            commands.entity(entity).remove::<ChromaticAberration>();
+            match state.previous_fog.take() {
+                Some(previous) => {
+                    commands.entity(entity).insert(previous);
+                }
+                None => {
+                    commands.entity(entity).remove::<DistanceFog>();
+                }
+            }
+        }
+
+        for (mut visibility, mut color) in overlay_query.iter_mut() {
+            *visibility = if state.submerged { Visibility::Inherited } else { Visibility::Hidden };
+            color.0 = settings.tint;
+        }
+    }
+}
+
+/// System to duck playing sounds while the camera is underwater and restore them after
+pub fn update_underwater_audio(
+    settings: Res<UnderwaterCameraSettings>,
+    mut snapshot: ResMut<UnderwaterAudioSnapshot>,
+    camera_query: Query<&UnderwaterCameraState>,
+    mut sinks: Query<(Entity, &mut AudioSink)>,
+    mut spatial_sinks: Query<(Entity, &mut SpatialAudioSink)>,
+) {
+    let submerged = camera_query.iter().any(|state| state.submerged);
+    if !submerged && !snapshot.active {
+        return;
+    }
+
+    if submerged {
+        // Sounds started underwater are ducked too
+        snapshot.active = true;
+        for (entity, mut sink) in sinks.iter_mut() {
+            duck_sink(&mut snapshot, entity, sink.as_mut(), settings.muffled_volume);
+        }
+        for (entity, mut sink) in spatial_sinks.iter_mut() {
+            duck_sink(&mut snapshot, entity, sink.as_mut(), settings.muffled_volume);
+        }
+        snapshot.volumes.retain(|entity, _| sinks.contains(*entity) || spatial_sinks.contains(*entity));
+        return;
+    }
+
+    for (entity, volume) in snapshot.volumes.drain() {
+        if let Ok((_, mut sink)) = sinks.get_mut(entity) {
+            sink.set_volume(volume);
+        } else if let Ok((_, mut sink)) = spatial_sinks.get_mut(entity) {
+            sink.set_volume(volume);
+        }
+    }
+    snapshot.active = false;
+}
+
+fn duck_sink(snapshot: &mut UnderwaterAudioSnapshot, entity: Entity, sink: &mut impl AudioSinkPlayback, factor: f32) {
+    if snapshot.volumes.contains_key(&entity) {
+        return;
+    }
+    let volume = sink.volume();
+    snapshot.volumes.insert(entity, volume);
+    sink.set_volume(Volume::Linear(volume.to_linear() * factor));
+}
+
+/// System to run droplets down the lens after surfacing
+pub fn update_wet_lens(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<UnderwaterCameraSettings>,
+    mut camera_query: Query<&mut UnderwaterCameraState>,
+    mut droplet_query: Query<(Entity, &mut LensDroplet, &mut Node, &mut BackgroundColor)>,
+) {
+    let delta = time.delta_secs();
+
+    for mut state in camera_query.iter_mut() {
+        if state.wet_lens_timer <= 0.0 {
+            continue;
+        }
+        // Droplets are spawned once, when the timer starts
+        if state.wet_lens_timer >= settings.wet_lens_duration {
+            let mut rng = rand::rng();
+            for _ in 0..settings.droplet_count {
+                let size = rng.random_range(6.0..22.0);
+                let lifetime = settings.wet_lens_duration * rng.random_range(0.5..1.0);
+                commands.spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Percent(rng.random_range(0.0..100.0)),
+                        top: Val::Percent(rng.random_range(0.0..90.0)),
+                        width: Val::Px(size),
+                        height: Val::Px(size * 1.3),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.8, 0.9, 1.0, 0.35)),
+                    BorderRadius::MAX,
+                    GlobalZIndex(-4),
+                    LensDroplet {
+                        lifetime,
+                        age: 0.0,
+                        speed: rng.random_range(2.0..10.0),
+                    },
+                    Name::new("LensDroplet"),
+                ));
+            }
+        }
+        state.wet_lens_timer -= delta;
+    }
+
+    for (entity, mut droplet, mut node, mut color) in droplet_query.iter_mut() {
+        droplet.age += delta;
+        if droplet.age >= droplet.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        if let Val::Percent(top) = node.top {
+            node.top = Val::Percent(top + droplet.speed * delta);
+        }
+        color.0.set_alpha(0.35 * (1.0 - droplet.age / droplet.lifetime));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{EventQueuePlugin, QueueReader};
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[derive(Resource, Default)]
+    struct Transitions(Vec<bool>);
+
+    fn log_transitions(mut events: QueueReader<CameraWaterEvent>, mut log: ResMut<Transitions>) {
+        log.0.extend(events.read().map(|event| event.submerged));
+    }
+
+    fn move_camera(app: &mut App, camera: Entity, height: f32) {
+        app.world_mut().get_mut::<Transform>(camera).unwrap().translation.y = height;
+        app.update();
+    }
+
+    fn overlay_visibility(app: &mut App) -> Visibility {
+        let mut query = app.world_mut().query_filtered::<&Visibility, With<UnderwaterTintOverlay>>();
+        *query.single(app.world()).unwrap()
+    }
+
+    fn droplet_count(app: &mut App) -> usize {
+        app.world_mut().query::<&LensDroplet>().iter(app.world()).count()
+    }
+
+    #[test]
+    fn test_diving_camera_fogs_muffles_and_drips() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, EventQueuePlugin::<CameraWaterEvent>::default()))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<UnderwaterCameraSettings>()
+            .init_resource::<UnderwaterAudioSnapshot>()
+            .init_resource::<Transitions>()
+            .add_systems(Startup, setup_underwater_overlay)
+            .add_systems(Update, (
+                update_underwater_camera_state,
+                update_underwater_audio,
+                apply_underwater_effects,
+                update_wet_lens,
+                log_transitions,
+            ).chain());
+
+        app.world_mut().spawn((WaterZone { surface_height: 0.0, radius: 20.0 }, GlobalTransform::default()));
+        let haze = Color::srgb(0.6, 0.6, 0.6);
+        let camera = app
+            .world_mut()
+            .spawn((
+                CameraController::default(),
+                Transform::from_xyz(0.0, 1.0, 0.0),
+                DistanceFog { color: haze, ..default() },
+            ))
+            .id();
+        app.update();
+        app.update();
+        assert!(!app.world().get::<UnderwaterCameraState>(camera).unwrap().submerged);
+
+        move_camera(&mut app, camera, -1.0);
+        let state = app.world().get::<UnderwaterCameraState>(camera).unwrap();
+        assert!(state.submerged && state.effects_applied);
+        assert_eq!(app.world().get::<DistanceFog>(camera).unwrap().color, UnderwaterCameraSettings::default().fog_color);
+        assert!(app.world().get::<ChromaticAberration>(camera).is_some());
+        assert_eq!(overlay_visibility(&mut app), Visibility::Inherited);
+        assert!(app.world().resource::<UnderwaterAudioSnapshot>().active);
+
+        // Bobbing just under the surface doesn't flicker
+        move_camera(&mut app, camera, -0.02);
+        assert!(app.world().get::<UnderwaterCameraState>(camera).unwrap().submerged);
+
+        // Surfacing restores the old fog and leaves droplets that run off
+        move_camera(&mut app, camera, 1.0);
+        assert_eq!(app.world().get::<DistanceFog>(camera).unwrap().color, haze);
+        assert!(app.world().get::<ChromaticAberration>(camera).is_none());
+        assert_eq!(overlay_visibility(&mut app), Visibility::Hidden);
+        assert!(!app.world().resource::<UnderwaterAudioSnapshot>().active);
+        assert_eq!(droplet_count(&mut app), UnderwaterCameraSettings::default().droplet_count);
+        for _ in 0..20 {
+            app.update();
+        }
+        assert_eq!(droplet_count(&mut app), 0);
+
+        assert_eq!(app.world().resource::<Transitions>().0, [true, false]);
+    }
+}
@@ -0,0 +1,298 @@
+//! Priority-based virtual cameras.
+//!
+//! A [`VirtualCamera`] is a camera placement that doesn't render: a fixed
+//! shot, a follow shot or a shot tied to a camera zone. The [`CameraBrain`] on
+//! the real camera shows the live virtual camera with the highest priority,
+//! blending from what was on screen. With no live virtual camera it hands back
+//! to the gameplay rig (`CameraController`, waypoint tracks and vehicle
+//! cameras), so the rig is the lowest-priority shot:
+//!
+//! ```rust,ignore
+//! commands.spawn((
+//!     VirtualCamera {
+//!         priority: 20,
+//!         body: VirtualCameraBody::Follow { target: boss, offset: Vec3::new(0.0, 4.0, 9.0), damping: 4.0 },
+//!         aim: VirtualCameraAim::LookAt { target: boss, offset: Vec3::Y * 2.0 },
+//!         blend_in: Some(CameraBlend { curve: CameraBlendCurve::EaseInOut, duration: 1.2 }),
+//!         ..default()
+//!     },
+//!     Transform::default(),
+//! ));
+//! ```
+//!
+//! Cutscenes switch shots by turning cameras `live` or raising their
+//! priority. A camera with a `zone` is live while the player is inside that
+//! `CameraZone`. Virtual cameras are read from their `Transform`, so keep them
+//! at the root of the hierarchy.
+
+use bevy::prelude::*;
+use crate::character::Player;
+use super::profiles::CameraBlend;
+use super::types::*;
+
+/// Where a virtual camera stands
+#[derive(Debug, Clone, PartialEq, Default, Reflect)]
+pub enum VirtualCameraBody {
+    /// Its own transform, placed or animated by other systems
+    #[default]
+    Fixed,
+    /// `offset` in the target's space, reached with exponential `damping`
+    Follow { target: Entity, offset: Vec3, damping: f32 },
+}
+
+/// Where a virtual camera looks
+#[derive(Debug, Clone, PartialEq, Default, Reflect)]
+pub enum VirtualCameraAim {
+    /// Its own rotation
+    #[default]
+    Fixed,
+    LookAt { target: Entity, offset: Vec3 },
+}
+
+/// A camera shot the `CameraBrain` can show
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct VirtualCamera {
+    /// The live camera with the highest priority is shown
+    pub priority: i32,
+    pub live: bool,
+    /// Vertical field of view in degrees
+    pub fov: f32,
+    pub body: VirtualCameraBody,
+    pub aim: VirtualCameraAim,
+    /// Blend used when this camera takes over; `None` uses the brain's
+    pub blend_in: Option<CameraBlend>,
+    /// `CameraZone` that makes the camera live while the player is inside
+    pub zone: Option<Entity>,
+}
+
+impl Default for VirtualCamera {
+    fn default() -> Self {
+        Self {
+            priority: 10,
+            live: true,
+            fov: 60.0,
+            body: VirtualCameraBody::Fixed,
+            aim: VirtualCameraAim::Fixed,
+            blend_in: None,
+            zone: None,
+        }
+    }
+}
+
+/// Position, rotation and FOV (degrees) of a shot
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct CameraPose {
+    pub translation: Vec3,
+    pub rotation: Quat,
+    pub fov: f32,
+}
+
+impl CameraPose {
+    pub fn lerp(&self, other: &CameraPose, t: f32) -> Self {
+        Self {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.slerp(other.rotation, t),
+            fov: self.fov + (other.fov - self.fov) * t,
+        }
+    }
+}
+
+/// Blend between shots in progress
+#[derive(Debug, Clone, Reflect)]
+pub struct CameraBrainBlend {
+    pub from: CameraPose,
+    pub blend: CameraBlend,
+    pub elapsed: f32,
+}
+
+/// Shows the best virtual camera through the real camera
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct CameraBrain {
+    /// Blend of cameras without `blend_in`, and back to the gameplay rig
+    pub default_blend: CameraBlend,
+    /// Virtual camera shown; `None` for the gameplay rig
+    pub active: Option<Entity>,
+    pub blend: Option<CameraBrainBlend>,
+    /// Pose written last frame, while a virtual camera or blend was shown
+    pub output: Option<CameraPose>,
+    /// Where the gameplay rig had the camera before it was overridden
+    pub rig_pose: Option<CameraPose>,
+}
+
+impl CameraBrain {
+    /// Live camera with the highest priority; the active one keeps ties
+    pub fn select(&self, candidates: impl IntoIterator<Item = (Entity, i32)>) -> Option<Entity> {
+        candidates
+            .into_iter()
+            .max_by_key(|(entity, priority)| (*priority, Some(*entity) == self.active))
+            .map(|(entity, _)| entity)
+    }
+
+    /// Steps toward `chosen` (camera, its pose, its blend) or back to the rig.
+    /// Returns the pose to show, or `None` when the rig has the camera.
+    pub fn update(&mut self, rig: CameraPose, chosen: Option<(Entity, CameraPose, Option<CameraBlend>)>, dt: f32) -> Option<CameraPose> {
+        let chosen_entity = chosen.map(|(entity, _, _)| entity);
+        if chosen_entity != self.active {
+            // Start from what is on screen, which may be mid-blend
+            let from = self.output.unwrap_or(rig);
+            let blend = chosen.and_then(|(_, _, blend)| blend).unwrap_or(self.default_blend);
+            self.active = chosen_entity;
+            self.blend = Some(CameraBrainBlend { from, blend, elapsed: 0.0 });
+        }
+
+        let target = chosen.map_or(rig, |(_, pose, _)| pose);
+        let pose = match self.blend.as_mut() {
+            Some(blend) => {
+                blend.elapsed += dt;
+                let t = if blend.blend.duration > 0.0 { blend.elapsed / blend.blend.duration } else { 1.0 };
+                if t >= 1.0 {
+                    self.blend = None;
+                    target
+                } else {
+                    blend.from.lerp(&target, blend.blend.curve.sample(t))
+                }
+            }
+            None => target,
+        };
+
+        if self.active.is_none() && self.blend.is_none() {
+            self.output = None;
+            return None;
+        }
+        self.output = Some(pose);
+        Some(pose)
+    }
+}
+
+/// System to make zone cameras live while the player is in their zone
+pub fn update_zone_virtual_cameras(
+    tracker_query: Query<&CameraZoneTracker, With<Player>>,
+    mut vcam_query: Query<&mut VirtualCamera>,
+) {
+    let active_zones: Vec<Entity> = tracker_query.iter().flat_map(|tracker| tracker.active_zones.iter().copied()).collect();
+    for mut vcam in vcam_query.iter_mut() {
+        let Some(zone) = vcam.zone else { continue };
+        let live = active_zones.contains(&zone);
+        if vcam.live != live {
+            vcam.live = live;
+        }
+    }
+}
+
+/// System to move follow and look-at virtual cameras
+pub fn update_virtual_cameras(
+    time: Res<Time>,
+    mut vcam_query: Query<(&VirtualCamera, &mut Transform), Without<CameraBrain>>,
+    target_query: Query<&GlobalTransform>,
+) {
+    let dt = time.delta_secs();
+    for (vcam, mut transform) in vcam_query.iter_mut() {
+        if let VirtualCameraBody::Follow { target, offset, damping } = vcam.body {
+            if let Ok(target) = target_query.get(target) {
+                let desired = target.translation() + target.rotation() * offset;
+                // Cameras waiting off screen are kept in place, so they cut in cleanly
+                let alpha = if vcam.live { 1.0 - (-damping * dt).exp() } else { 1.0 };
+                transform.translation = transform.translation.lerp(desired, alpha);
+            }
+        }
+        if let VirtualCameraAim::LookAt { target, offset } = vcam.aim {
+            if let Ok(target) = target_query.get(target) {
+                let look_at = target.translation() + offset;
+                if look_at.distance_squared(transform.translation) > 1e-6 {
+                    transform.look_at(look_at, Vec3::Y);
+                }
+            }
+        }
+    }
+}
+
+/// System to give the camera back to the gameplay rig before it runs
+pub fn restore_camera_rig_pose(
+    mut camera_query: Query<(&CameraBrain, &mut Transform, Option<&mut Projection>)>,
+) {
+    for (brain, mut transform, projection) in camera_query.iter_mut() {
+        let (Some(_), Some(rig)) = (brain.output, brain.rig_pose) else { continue };
+        transform.translation = rig.translation;
+        transform.rotation = rig.rotation;
+        if let Some(mut projection) = projection {
+            if let Projection::Perspective(ref mut perspective) = *projection {
+                perspective.fov = rig.fov.to_radians();
+            }
+        }
+    }
+}
+
+/// System to show the best live virtual camera, blending between shots
+pub fn update_camera_brain(
+    time: Res<Time>,
+    mut camera_query: Query<(&mut CameraBrain, &mut Transform, Option<&mut Projection>)>,
+    vcam_query: Query<(Entity, &VirtualCamera, &Transform), Without<CameraBrain>>,
+) {
+    let dt = time.delta_secs();
+    for (mut brain, mut transform, mut projection) in camera_query.iter_mut() {
+        let rig_fov = match projection.as_deref() {
+            Some(Projection::Perspective(perspective)) => perspective.fov.to_degrees(),
+            _ => 60.0,
+        };
+        let rig = CameraPose { translation: transform.translation, rotation: transform.rotation, fov: rig_fov };
+
+        let chosen = brain
+            .select(vcam_query.iter().filter(|(_, vcam, _)| vcam.live).map(|(entity, vcam, _)| (entity, vcam.priority)))
+            .and_then(|entity| vcam_query.get(entity).ok())
+            .map(|(entity, vcam, vcam_transform)| {
+                let pose = CameraPose { translation: vcam_transform.translation, rotation: vcam_transform.rotation, fov: vcam.fov };
+                (entity, pose, vcam.blend_in)
+            });
+
+        let Some(pose) = brain.update(rig, chosen, dt) else {
+            brain.rig_pose = None;
+            continue;
+        };
+        brain.rig_pose = Some(rig);
+        transform.translation = pose.translation;
+        transform.rotation = pose.rotation;
+        if let Some(Projection::Perspective(perspective)) = projection.as_deref_mut() {
+            perspective.fov = pose.fov.to_radians();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_virtual_camera_brain() {
+        use crate::camera::{CameraBlend, CameraBlendCurve, CameraBrain, CameraPose};
+        let mut world = World::new();
+        let (wide, close) = (world.spawn_empty().id(), world.spawn_empty().id());
+        let pose = |x: f32, fov: f32| CameraPose { translation: Vec3::new(x, 0.0, 0.0), rotation: Quat::IDENTITY, fov };
+        let rig = pose(0.0, 60.0);
+
+        let mut brain = CameraBrain { default_blend: CameraBlend { curve: CameraBlendCurve::Linear, duration: 1.0 }, ..default() };
+        assert_eq!(brain.select([(wide, 5), (close, 10)]), Some(close));
+        assert_eq!(brain.select([]), None);
+        assert_eq!(brain.update(rig, None, 0.1), None);
+
+        // Rig to the wide shot over a second
+        let halfway = brain.update(rig, Some((wide, pose(10.0, 40.0), None)), 0.5).unwrap();
+        assert_eq!(halfway.translation.x, 5.0);
+        assert_eq!(halfway.fov, 50.0);
+        assert_eq!(brain.active, Some(wide));
+        assert_eq!(brain.select([(close, 5), (wide, 5)]), Some(wide));
+
+        // A cut to the close shot interrupts the blend
+        let cut = Some(CameraBlend::cut());
+        assert_eq!(brain.update(rig, Some((close, pose(20.0, 30.0), cut)), 0.1), Some(pose(20.0, 30.0)));
+        assert!(brain.blend.is_none());
+
+        // Back to the rig from the close shot, then the rig has the camera again
+        let back = brain.update(rig, None, 0.25).unwrap();
+        assert_eq!(back.translation.x, 15.0);
+        assert_eq!(brain.update(rig, None, 1.0), None);
+        assert!(brain.output.is_none());
+    }
+}
@@ -8,11 +8,15 @@ pub use types::*;
 pub use spawn::*;
 
 use systems::*;
+use crate::simulation::SimulationAppExt;
+use crate::input::{ConsoleAppExt, ConsoleCommand};
 
 pub struct CharacterPlugin;
 
 impl Plugin for CharacterPlugin {
     fn build(&self, app: &mut App) {
+        let simulation = app.simulation_schedule();
+
         app
             .register_type::<Player>()
             .register_type::<CharacterController>()
@@ -20,11 +24,20 @@ impl Plugin for CharacterPlugin {
             .register_type::<CharacterAnimationState>()
             .register_type::<FootIk>()
             .register_type::<HandIk>()
-            .add_systems(Update, (
-                movement::update_character_movement,
-                rotation::update_character_rotation,
-                animation::update_character_animation,
-            ).chain())
+            .add_console_command(
+                ConsoleCommand::new("teleport", "Moves the player").with_usage("<x> <y> <z>"),
+                teleport_command,
+            )
+            .configure_sets(simulation, (
+                CharacterSet::Movement,
+                CharacterSet::Rotation,
+                CharacterSet::Animation,
+            ).chain().in_set(crate::GameControllerSet::Movement))
+            .add_systems(simulation, (
+                movement::update_character_movement.in_set(CharacterSet::Movement),
+                rotation::update_character_rotation.in_set(CharacterSet::Rotation),
+                animation::update_character_animation.in_set(CharacterSet::Animation),
+            ))
             .add_systems(FixedUpdate, (
                 movement::apply_character_physics,
                 detection::check_ground_state,
@@ -33,6 +46,6 @@ impl Plugin for CharacterPlugin {
                 movement::handle_crouch_sliding,
                 detection::handle_obstacle_detection,
                 detection::handle_wall_running_detection,
-            ));
+            ).in_set(CharacterSet::Physics));
     }
 }
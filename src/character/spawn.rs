@@ -21,7 +21,21 @@ pub fn spawn_character(
         GlobalTransform::default(),
     ))
     .insert((
-        // Physics components
+        character_physics(),
+        crate::interaction::InteractionDetector::default(),
+    ))
+    .insert((
+        // Visibility
+        Visibility::default(),
+        InheritedVisibility::default(),
+        ViewVisibility::default(),
+    ))
+    .id()
+}
+
+/// Capsule body, gravity and ground detection shared by players and NPCs
+pub fn character_physics() -> impl Bundle {
+    (
         RigidBody::Dynamic,
         Collider::capsule(0.4, 1.0),
         LockedAxes::ROTATION_LOCKED,
@@ -33,13 +47,5 @@ pub fn spawn_character(
         CustomGravity::default(),
         GroundDetection::default(),
         GroundDetectionSettings::default(),
-        crate::interaction::InteractionDetector::default(),
-    ))
-    .insert((
-        // Visibility
-        Visibility::default(),
-        InheritedVisibility::default(),
-        ViewVisibility::default(),
-    ))
-    .id()
+    )
 }
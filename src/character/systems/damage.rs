@@ -2,15 +2,17 @@ use bevy::prelude::*;
 use crate::character::types::*;
 use crate::physics::GroundDetection;
 use crate::combat::{DamageEventQueue, DamageEvent, DamageType}; // Import combat types
+use crate::game_manager::types::FeatureToggles;
 use avian3d::prelude::*;
 
 pub fn handle_falling_damage(
     time: Res<Time>,
+    toggles: Res<FeatureToggles>,
     mut damage_queue: ResMut<DamageEventQueue>, // Use Queue instead of EventWriter
     mut query: Query<(Entity, &CharacterController, &mut CharacterMovementState, &LinearVelocity, &GroundDetection)>,
 ) {
     for (entity, controller, mut state, velocity, ground) in query.iter_mut() {
-        if !controller.fall_damage_enabled { continue; }
+        if !controller.fall_damage_enabled || !toggles.fall_damage { continue; }
 
         if !ground.is_grounded {
             state.last_vertical_velocity = velocity.y;
@@ -21,7 +23,7 @@ pub fn handle_falling_damage(
             let damage = (impact_speed - controller.min_velocity_for_damage + state.air_time * 2.0) * controller.falling_damage_multiplier;
             
             // Push to Damage Queue
-            damage_queue.0.push(DamageEvent {
+            damage_queue.send(DamageEvent {
                 target: entity,
                 amount: damage,
                 damage_type: DamageType::Fall,
@@ -39,3 +41,53 @@ pub fn handle_falling_damage(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{EventQueuePlugin, QueueReader};
+
+    #[derive(Resource, Default)]
+    struct FallHits(Vec<f32>);
+
+    fn log_fall_hits(mut damage: QueueReader<DamageEvent>, mut log: ResMut<FallHits>) {
+        log.0.extend(damage.read().filter(|event| event.damage_type == DamageType::Fall).map(|event| event.amount));
+    }
+
+    fn fall(app: &mut App, character: Entity) {
+        app.world_mut().get_mut::<GroundDetection>(character).unwrap().is_grounded = false;
+        app.world_mut().get_mut::<LinearVelocity>(character).unwrap().0 = Vec3::new(0.0, -20.0, 0.0);
+        app.update();
+        app.world_mut().get_mut::<GroundDetection>(character).unwrap().is_grounded = true;
+        app.world_mut().get_mut::<LinearVelocity>(character).unwrap().0 = Vec3::ZERO;
+        app.update();
+    }
+
+    #[test]
+    fn test_fall_damage_toggle_skips_landings() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, EventQueuePlugin::<DamageEvent>::default()))
+            .init_resource::<FeatureToggles>()
+            .init_resource::<FallHits>()
+            .add_systems(Update, (handle_falling_damage, log_fall_hits).chain());
+        let character = app
+            .world_mut()
+            .spawn((
+                CharacterController::default(),
+                CharacterMovementState::default(),
+                LinearVelocity::default(),
+                GroundDetection::default(),
+            ))
+            .id();
+
+        app.world_mut().resource_mut::<FeatureToggles>().fall_damage = false;
+        fall(&mut app, character);
+        assert!(app.world().resource::<FallHits>().0.is_empty());
+
+        app.world_mut().resource_mut::<FeatureToggles>().fall_damage = true;
+        fall(&mut app, character);
+        let hits = &app.world().resource::<FallHits>().0;
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0] >= (20.0 - 12.0) * 5.0);
+    }
+}
@@ -4,12 +4,13 @@ use crate::input::InputState;
 use crate::physics::{GroundDetection, GroundDetectionSettings};
 use avian3d::prelude::*;
 use crate::input::InputBuffer;
-use crate::input::InputAction;
+use crate::input::{console_arg, ConsoleArgs, ConsoleResult, InputAction};
+use crate::combat::{SpecialMoveState, Staggered};
 
 pub fn update_character_movement(
-    mut query: Query<(&CharacterController, &mut CharacterMovementState)>,
+    mut query: Query<(&CharacterController, &mut CharacterMovementState, Option<&SpecialMoveState>, Has<Staggered>)>,
 ) {
-    for (controller, mut state) in query.iter_mut() {
+    for (controller, mut state, special, staggered) in query.iter_mut() {
         let mut base_speed = if state.is_crouching {
             controller.crouch_speed
         } else if state.is_sprinting {
@@ -22,6 +23,14 @@ pub fn update_character_movement(
             base_speed = controller.crouch_sliding_speed;
         }
 
+        // Charging a heavy attack slows down, a stagger stops
+        if let Some(special) = special {
+            base_speed *= special.move_speed_multiplier();
+        }
+        if staggered {
+            base_speed = 0.0;
+        }
+
         state.current_speed = base_speed;
     }
 }
@@ -39,9 +48,10 @@ pub fn apply_character_physics(
         &CharacterController,
         &mut Transform,
         &InputState,
+        Has<crate::character::Player>,
     )>,
 ) {
-    for (entity, mut movement, mut ground, settings, mut velocity, controller, mut transform, input) in query.iter_mut() {
+    for (entity, mut movement, mut ground, settings, mut velocity, controller, mut transform, input, is_player) in query.iter_mut() {
         // Horizontal movement
         let move_dir = if controller.use_tank_controls {
              Vec3::new(0.0, 0.0, movement.lerped_move_dir.z)
@@ -84,7 +94,7 @@ pub fn apply_character_physics(
             if input.jump_pressed {
                 free_vel.y += movement.current_speed;
             }
-            if input.crouch_pressed {
+            if input.intends(InputAction::Crouch) {
                 free_vel.y -= movement.current_speed;
             }
 
@@ -158,14 +168,21 @@ pub fn apply_character_physics(
             }
         }
 
-        // Jump logic with buffering
-        let jump_requested = movement.wants_to_jump || input_buffer.consume(InputAction::Jump);
-        
-        if jump_requested && ground.is_grounded {
+        // Jump logic with buffering and coyote time. The buffer holds the local
+        // player's presses, and is only used up by a jump that happens.
+        movement.update_coyote_time(ground.is_grounded, velocity.y > 0.1, controller.coyote_time, time.delta_secs());
+        let jump_requested = movement.wants_to_jump || (is_player && input_buffer.is_buffered(InputAction::Jump));
+
+        if jump_requested && movement.can_jump(ground.is_grounded) {
+            if is_player {
+                input_buffer.consume(InputAction::Jump);
+            }
             // Apply jump impulse directly to velocity
             velocity.y = controller.jump_power;
             movement.jump_hold_timer = controller.max_jump_hold_time;
             movement.wants_to_jump = false;
+            movement.jump_used = true;
+            movement.coyote_timer = 0.0;
         }
 
         // Variable Jump Bonus
@@ -213,3 +230,21 @@ pub fn update_friction_material(
         }
     }
 }
+
+/// Console command `teleport <x> <y> <z>`: moves the player, dropping their velocity
+pub fn teleport_command(
+    In(args): In<ConsoleArgs>,
+    mut player_query: Query<(&mut Transform, Option<&mut LinearVelocity>), With<Player>>,
+) -> ConsoleResult {
+    let position = Vec3::new(
+        console_arg(&args, 0, "x")?,
+        console_arg(&args, 1, "y")?,
+        console_arg(&args, 2, "z")?,
+    );
+    let (mut transform, velocity) = player_query.iter_mut().next().ok_or("No player")?;
+    transform.translation = position;
+    if let Some(mut velocity) = velocity {
+        velocity.0 = Vec3::ZERO;
+    }
+    Ok(format!("Teleported to {:.1} {:.1} {:.1}", position.x, position.y, position.z))
+}
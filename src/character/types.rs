@@ -1,6 +1,17 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
 
+/// Character phases. `Movement`, `Rotation` and `Animation` run in order inside
+/// `GameControllerSet::Movement`; `Physics` runs in `FixedUpdate`.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CharacterSet {
+    Movement,
+    Rotation,
+    Animation,
+    /// Velocity, ground checks and obstacle detection
+    Physics,
+}
+
 #[derive(Component, Debug, Default, Reflect)]
 #[reflect(Component)]
 pub struct Player;
@@ -25,6 +36,8 @@ pub struct CharacterController {
     pub jump_power: f32,
     pub jump_hold_bonus: f32,
     pub max_jump_hold_time: f32,
+    /// Seconds after walking off a ledge during which a jump still works
+    pub coyote_time: f32,
     
     // Feedback/State
     pub can_move: bool,
@@ -75,6 +88,7 @@ impl Default for CharacterController {
             jump_power: 6.0,
             jump_hold_bonus: 2.0,
             max_jump_hold_time: 0.25,
+            coyote_time: 0.12,
             
             can_move: true,
             is_dead: false,
@@ -119,6 +133,10 @@ pub struct CharacterMovementState {
     pub last_vertical_velocity: f32,
     pub air_time: f32,
     pub jump_hold_timer: f32,
+    /// Coyote time left since the character was last grounded
+    pub coyote_timer: f32,
+    /// Jumped since last landing; no coyote jump until grounded again
+    pub jump_used: bool,
     pub crouch_sliding_active: bool,
     pub crouch_sliding_timer: f32,
     pub obstacle_found: bool,
@@ -141,6 +159,26 @@ pub struct CharacterMovementState {
     pub slope_slide_active: bool,
 }
 
+impl CharacterMovementState {
+    /// Refills coyote time while grounded and runs it down in the air. Landing
+    /// (grounded and not rising) allows jumping again.
+    pub fn update_coyote_time(&mut self, grounded: bool, rising: bool, coyote_time: f32, dt: f32) {
+        if grounded {
+            self.coyote_timer = coyote_time;
+            if !rising {
+                self.jump_used = false;
+            }
+        } else {
+            self.coyote_timer = (self.coyote_timer - dt).max(0.0);
+        }
+    }
+
+    /// Grounded, or just walked off a ledge without jumping
+    pub fn can_jump(&self, grounded: bool) -> bool {
+        !self.jump_used && (grounded || self.coyote_timer > 0.0)
+    }
+}
+
 /// Character animation modes
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 pub enum CharacterAnimationMode {
@@ -199,3 +237,36 @@ impl Default for HandIk {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{InputAction, InputConfig};
+
+    #[test]
+    fn test_jump_buffer_and_coyote_time() {
+        let config = InputConfig::default();
+        assert_eq!(config.buffer_window(InputAction::Jump), 0.15);
+        assert_eq!(config.buffer_window(InputAction::Dash), 0.15);
+        assert_eq!(config.buffer_window(InputAction::Reload), 0.0);
+
+        let mut movement = CharacterMovementState::default();
+        movement.update_coyote_time(true, false, 0.12, 0.016);
+        assert!(movement.can_jump(true));
+
+        // Walked off a ledge: a jump still works for the coyote time, then not
+        movement.update_coyote_time(false, false, 0.12, 0.1);
+        assert!(movement.can_jump(false));
+        movement.update_coyote_time(false, false, 0.12, 0.1);
+        assert!(!movement.can_jump(false));
+
+        // A jump uses up the coyote time until the character lands again
+        movement.update_coyote_time(true, false, 0.12, 0.016);
+        movement.jump_used = true;
+        movement.update_coyote_time(true, true, 0.12, 0.016);
+        movement.update_coyote_time(false, true, 0.12, 0.05);
+        assert!(!movement.can_jump(false));
+        movement.update_coyote_time(true, false, 0.12, 0.016);
+        assert!(movement.can_jump(true));
+    }
+}
@@ -94,6 +94,8 @@ pub struct ClimbLedgeSystem {
     // Ledge detection state
     pub ledge_position: Vec3,
     pub ledge_normal: Vec3,
+    /// Collider the ledge belongs to, so moving ledges can carry the player
+    pub ledge_surface: Option<Entity>,
 }
 
 impl Default for ClimbLedgeSystem {
@@ -178,6 +180,7 @@ impl Default for ClimbLedgeSystem {
             
             ledge_position: Vec3::ZERO,
             ledge_normal: Vec3::ZERO,
+            ledge_surface: None,
         }
     }
 }
@@ -0,0 +1,602 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::character::{CharacterController, Player};
+use crate::input::InputState;
+use crate::ladder::{LadderSystem, PlayerLadderSystem, LadderMovementState};
+use crate::ladder::types::LadderMovementTracker;
+use super::types::*;
+use super::climb_ledge_system::ClimbLedgeSystem;
+
+/// Marks a collider as a free-climbable surface (rock walls, vines, grates...)
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct FreeClimbSurface {
+    pub active: bool,
+    pub surface_type: SurfaceType,
+    /// Multiplier applied to the stamina drained while moving on this surface
+    pub stamina_drain_multiplier: f32,
+    /// Multiplier applied to the free climb movement speed on this surface
+    pub speed_multiplier: f32,
+}
+
+impl Default for FreeClimbSurface {
+    fn default() -> Self {
+        Self {
+            active: true,
+            surface_type: SurfaceType::Stone,
+            stamina_drain_multiplier: 1.0,
+            speed_multiplier: 1.0,
+        }
+    }
+}
+
+/// Hand/foot anchor points sampled against the climbed surface
+#[derive(Debug, Clone, Copy, Reflect, Default)]
+pub struct ClimbIkAnchor {
+    pub valid: bool,
+    pub position: Vec3,
+    pub normal: Vec3,
+}
+
+/// Per-player free climb settings and runtime state
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct FreeClimb {
+    // Main Settings
+    pub enabled: bool,
+    pub climb_speed: f32,
+    pub surface_check_distance: f32,
+    pub distance_to_surface: f32,
+    pub align_speed: f32,
+    pub max_surface_up_dot: f32,
+    pub min_surface_up_dot: f32,
+
+    // Stamina Settings
+    /// Extra stamina drained per second while actively moving (on top of the hanging drain)
+    pub moving_stamina_drain_rate: f32,
+    pub fall_on_exhaustion: bool,
+    pub push_off_force: f32,
+
+    // IK Settings
+    pub sample_ik_anchors: bool,
+    pub hand_offset: Vec2,
+    pub foot_offset: Vec2,
+    pub ik_ray_distance: f32,
+
+    // Transition Settings
+    pub top_out_to_ledge: bool,
+    pub top_check_height: f32,
+    pub transition_to_ladder: bool,
+
+    // Buffered Input (set in `Update`, consumed by the next fixed step)
+    pub grab_requested: bool,
+    pub push_off_requested: bool,
+    pub release_requested: bool,
+
+    // Debug State
+    pub surface_found: bool,
+    pub is_climbing: bool,
+    pub current_surface: Option<Entity>,
+    pub surface_point: Vec3,
+    pub surface_normal: Vec3,
+    pub climb_input: Vec2,
+    pub left_hand: ClimbIkAnchor,
+    pub right_hand: ClimbIkAnchor,
+    pub left_foot: ClimbIkAnchor,
+    pub right_foot: ClimbIkAnchor,
+}
+
+impl Default for FreeClimb {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            climb_speed: 1.5,
+            surface_check_distance: 1.0,
+            distance_to_surface: 0.4,
+            align_speed: 8.0,
+            max_surface_up_dot: 0.5,
+            min_surface_up_dot: -0.3,
+
+            moving_stamina_drain_rate: 8.0,
+            fall_on_exhaustion: true,
+            push_off_force: 4.0,
+
+            sample_ik_anchors: true,
+            hand_offset: Vec2::new(0.25, 1.6),
+            foot_offset: Vec2::new(0.2, 0.3),
+            ik_ray_distance: 1.0,
+
+            top_out_to_ledge: true,
+            top_check_height: 1.8,
+            transition_to_ladder: true,
+
+            grab_requested: false,
+            push_off_requested: false,
+            release_requested: false,
+
+            surface_found: false,
+            is_climbing: false,
+            current_surface: None,
+            surface_point: Vec3::ZERO,
+            surface_normal: Vec3::ZERO,
+            climb_input: Vec2::ZERO,
+            left_hand: ClimbIkAnchor::default(),
+            right_hand: ClimbIkAnchor::default(),
+            left_foot: ClimbIkAnchor::default(),
+            right_foot: ClimbIkAnchor::default(),
+        }
+    }
+}
+
+/// Reason a free climb ended
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum FreeClimbEndReason {
+    ManualRelease,
+    JumpedOff,
+    StaminaDepleted,
+    SurfaceLost,
+    ToppedOutToLedge,
+    TransitionedToLadder,
+    /// The surface moved too violently to hold on
+    ShakenOff,
+}
+
+/// Event for when free climbing starts
+#[derive(Event, Debug, Reflect)]
+pub struct FreeClimbStartedEvent {
+    pub entity: Entity,
+    pub surface: Entity,
+    pub surface_type: SurfaceType,
+}
+
+/// Event for when free climbing ends
+#[derive(Event, Debug, Reflect)]
+pub struct FreeClimbEndedEvent {
+    pub entity: Entity,
+    pub reason: FreeClimbEndReason,
+}
+
+/// Returns true when the surface normal is steep enough to climb
+fn is_climbable_normal(free_climb: &FreeClimb, normal: Vec3) -> bool {
+    let up_dot = normal.dot(Vec3::Y);
+    up_dot > free_climb.min_surface_up_dot && up_dot < free_climb.max_surface_up_dot
+}
+
+/// Resets climb state when the player leaves a free-climb surface
+pub(super) fn end_free_climb(
+    commands: &mut Commands,
+    entity: Entity,
+    free_climb: &mut FreeClimb,
+    state_tracker: &mut ClimbStateTracker,
+    character: &mut CharacterController,
+    reason: FreeClimbEndReason,
+) {
+    free_climb.is_climbing = false;
+    free_climb.current_surface = None;
+    free_climb.climb_input = Vec2::ZERO;
+    character.zero_gravity_mode = false;
+
+    state_tracker.previous_state = state_tracker.current_state;
+    state_tracker.current_state = match reason {
+        FreeClimbEndReason::ToppedOutToLedge => ClimbState::Hanging,
+        FreeClimbEndReason::TransitionedToLadder | FreeClimbEndReason::ManualRelease => ClimbState::None,
+        _ => ClimbState::Falling,
+    };
+    state_tracker.state_timer = 0.0;
+
+    commands.trigger(FreeClimbEndedEvent { entity, reason });
+}
+
+/// System to detect free-climbable surfaces in front of the player
+pub fn detect_free_climb_surface(
+    spatial_query: SpatialQuery,
+    mut query: Query<(Entity, &mut FreeClimb, &CharacterController, &Transform), With<Player>>,
+    surface_query: Query<&FreeClimbSurface>,
+) {
+    for (entity, mut free_climb, character, transform) in query.iter_mut() {
+        if !free_climb.enabled || character.is_dead {
+            free_climb.surface_found = false;
+            continue;
+        }
+
+        // While climbing, probe along the inverted surface normal so we stay attached around bends
+        let ray_origin = transform.translation + Vec3::Y * 1.0;
+        let ray_dir_vec = if free_climb.is_climbing && free_climb.surface_normal != Vec3::ZERO {
+            -free_climb.surface_normal
+        } else {
+            transform.forward().as_vec3()
+        };
+
+        let Ok(ray_dir) = Dir3::new(ray_dir_vec) else {
+            continue;
+        };
+
+        let filter = SpatialQueryFilter::from_excluded_entities([entity]);
+        let hit = spatial_query.cast_ray(
+            ray_origin,
+            ray_dir,
+            free_climb.surface_check_distance,
+            true,
+            &filter,
+        );
+
+        let mut found = false;
+        if let Some(hit) = hit {
+            if let Ok(surface) = surface_query.get(hit.entity) {
+                if surface.active && is_climbable_normal(&free_climb, hit.normal) {
+                    found = true;
+                    free_climb.surface_point = ray_origin + ray_dir_vec * hit.distance;
+                    free_climb.surface_normal = hit.normal;
+                    if !free_climb.is_climbing {
+                        free_climb.current_surface = Some(hit.entity);
+                    }
+                }
+            }
+        }
+
+        free_climb.surface_found = found;
+    }
+}
+
+/// System to buffer free climb input until the next fixed step reads it
+pub fn buffer_free_climb_input(
+    input_state: Res<InputState>,
+    mut query: Query<&mut FreeClimb, With<Player>>,
+) {
+    for mut free_climb in query.iter_mut() {
+        if !free_climb.enabled {
+            continue;
+        }
+        if free_climb.is_climbing {
+            free_climb.climb_input = input_state.movement;
+        }
+        free_climb.grab_requested |= input_state.interact_pressed;
+        free_climb.push_off_requested |= input_state.jump_pressed;
+        free_climb.release_requested |= input_state.crouch_pressed;
+    }
+}
+
+type FreeClimber<'a> = (
+    Entity,
+    &'a mut FreeClimb,
+    &'a mut ClimbStateTracker,
+    &'a ClimbLedgeSystem,
+    &'a mut CharacterController,
+    &'a mut LinearVelocity,
+);
+
+/// System to start/stop free climbing from the buffered player input
+pub fn handle_free_climb_input(
+    mut commands: Commands,
+    mut query: Query<FreeClimber, With<Player>>,
+    surface_query: Query<&FreeClimbSurface>,
+) {
+    for (
+        entity,
+        mut free_climb,
+        mut state_tracker,
+        climb_system,
+        mut character,
+        mut velocity,
+    ) in query.iter_mut() {
+        let grab = std::mem::take(&mut free_climb.grab_requested);
+        let push_off = std::mem::take(&mut free_climb.push_off_requested);
+        let release = std::mem::take(&mut free_climb.release_requested);
+
+        if !free_climb.enabled || character.is_dead {
+            continue;
+        }
+
+        if free_climb.is_climbing {
+            if push_off {
+                // Push off the wall
+                velocity.0 = free_climb.surface_normal * free_climb.push_off_force + Vec3::Y * free_climb.push_off_force * 0.5;
+                end_free_climb(&mut commands, entity, &mut free_climb, &mut state_tracker, &mut character, FreeClimbEndReason::JumpedOff);
+            } else if release {
+                end_free_climb(&mut commands, entity, &mut free_climb, &mut state_tracker, &mut character, FreeClimbEndReason::ManualRelease);
+            }
+            continue;
+        }
+
+        // Ledge climbing has priority over free climbing
+        if climb_system.climbing_ledge || climb_system.grabbing_surface {
+            continue;
+        }
+
+        if !free_climb.surface_found || !grab || state_tracker.is_stamina_depleted {
+            continue;
+        }
+
+        let Some(surface_entity) = free_climb.current_surface else {
+            continue;
+        };
+
+        let surface_type = surface_query
+            .get(surface_entity)
+            .map(|surface| surface.surface_type)
+            .unwrap_or_default();
+
+        free_climb.is_climbing = true;
+        character.zero_gravity_mode = true;
+        velocity.0 = Vec3::ZERO;
+
+        state_tracker.previous_state = state_tracker.current_state;
+        state_tracker.current_state = ClimbState::FreeClimbing;
+        state_tracker.state_timer = 0.0;
+
+        commands.trigger(FreeClimbStartedEvent {
+            entity,
+            surface: surface_entity,
+            surface_type,
+        });
+    }
+}
+
+type MovingFreeClimber<'a> = (
+    Entity,
+    &'a mut FreeClimb,
+    &'a mut ClimbStateTracker,
+    &'a mut CharacterController,
+    &'a mut Transform,
+    &'a mut LinearVelocity,
+);
+
+/// System to move the player across the climbed surface and drain stamina
+pub fn update_free_climb_movement(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<MovingFreeClimber, With<Player>>,
+    surface_query: Query<&FreeClimbSurface>,
+) {
+    let delta = time.delta_secs();
+
+    for (
+        entity,
+        mut free_climb,
+        mut state_tracker,
+        mut character,
+        mut transform,
+        mut velocity,
+    ) in query.iter_mut() {
+        if !free_climb.is_climbing {
+            continue;
+        }
+
+        // Exhaustion is handled by update_climb_state, which flips the state to Falling
+        if state_tracker.current_state == ClimbState::Falling {
+            if free_climb.fall_on_exhaustion {
+                end_free_climb(&mut commands, entity, &mut free_climb, &mut state_tracker, &mut character, FreeClimbEndReason::StaminaDepleted);
+            } else {
+                state_tracker.current_state = ClimbState::FreeClimbing;
+            }
+            continue;
+        }
+
+        if !free_climb.surface_found {
+            end_free_climb(&mut commands, entity, &mut free_climb, &mut state_tracker, &mut character, FreeClimbEndReason::SurfaceLost);
+            continue;
+        }
+
+        let (speed_multiplier, drain_multiplier, surface_type) = free_climb
+            .current_surface
+            .and_then(|surface| surface_query.get(surface).ok())
+            .map(|surface| (surface.speed_multiplier, surface.stamina_drain_multiplier, surface.surface_type))
+            .unwrap_or((1.0, 1.0, SurfaceType::Default));
+
+        // Build a 2D movement basis on the surface plane
+        let normal = free_climb.surface_normal.normalize_or_zero();
+        let right = Vec3::Y.cross(normal).normalize_or_zero();
+        let up = normal.cross(right).normalize_or_zero();
+
+        let input = free_climb.climb_input.clamp_length_max(1.0);
+        let speed = free_climb.climb_speed * speed_multiplier * surface_type.climb_speed_multiplier();
+
+        let mut movement = (right * -input.x + up * input.y) * speed * delta;
+
+        // Keep a constant distance to the surface
+        let desired_position = free_climb.surface_point + normal * free_climb.distance_to_surface - Vec3::Y * 1.0;
+        let plane_error = (desired_position - transform.translation).dot(normal);
+        movement += normal * plane_error * (free_climb.align_speed * delta).min(1.0);
+
+        transform.translation += movement;
+        velocity.0 = Vec3::ZERO;
+
+        // Face the wall
+        let target_rotation = Transform::IDENTITY.looking_to(-normal, Vec3::Y).rotation;
+        transform.rotation = transform.rotation.slerp(target_rotation, (free_climb.align_speed * delta).min(1.0));
+
+        // Moving costs extra stamina on top of the base climb drain
+        if input.length_squared() > 0.01 {
+            state_tracker.stamina -= free_climb.moving_stamina_drain_rate * drain_multiplier * input.length() * delta;
+            state_tracker.stamina = state_tracker.stamina.max(0.0);
+        }
+    }
+}
+
+/// System to sample hand/foot IK anchors against the climbed surface
+pub fn sample_free_climb_ik_anchors(
+    spatial_query: SpatialQuery,
+    mut query: Query<(Entity, &mut FreeClimb, &Transform), With<Player>>,
+) {
+    for (entity, mut free_climb, transform) in query.iter_mut() {
+        if !free_climb.is_climbing || !free_climb.sample_ik_anchors {
+            continue;
+        }
+
+        let normal = free_climb.surface_normal.normalize_or_zero();
+        let Ok(ray_dir) = Dir3::new(-normal) else {
+            continue;
+        };
+        let right = Vec3::Y.cross(normal).normalize_or_zero();
+        let filter = SpatialQueryFilter::from_excluded_entities([entity]);
+
+        let sample = |offset: Vec2| -> ClimbIkAnchor {
+            let origin = transform.translation + right * offset.x + Vec3::Y * offset.y;
+            match spatial_query.cast_ray(origin, ray_dir, free_climb.ik_ray_distance, true, &filter) {
+                Some(hit) => ClimbIkAnchor {
+                    valid: true,
+                    position: origin + ray_dir.as_vec3() * hit.distance,
+                    normal: hit.normal,
+                },
+                None => ClimbIkAnchor::default(),
+            }
+        };
+
+        let hand = free_climb.hand_offset;
+        let foot = free_climb.foot_offset;
+        let left_hand = sample(Vec2::new(hand.x, hand.y));
+        let right_hand = sample(Vec2::new(-hand.x, hand.y));
+        let left_foot = sample(Vec2::new(foot.x, foot.y));
+        let right_foot = sample(Vec2::new(-foot.x, foot.y));
+
+        free_climb.left_hand = left_hand;
+        free_climb.right_hand = right_hand;
+        free_climb.left_foot = left_foot;
+        free_climb.right_foot = right_foot;
+    }
+}
+
+type TransitioningFreeClimber<'a> = (
+    Entity,
+    &'a mut FreeClimb,
+    &'a mut ClimbStateTracker,
+    &'a mut ClimbLedgeSystem,
+    &'a mut CharacterController,
+    &'a Transform,
+    Option<&'a mut PlayerLadderSystem>,
+    Option<&'a mut LadderMovementTracker>,
+    Option<&'a CollidingEntities>,
+);
+
+/// System to hand off from free climbing into ledge hanging or ladder climbing
+pub fn handle_free_climb_transitions(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    mut query: Query<TransitioningFreeClimber, With<Player>>,
+    ladder_query: Query<&LadderSystem>,
+) {
+    for (
+        entity,
+        mut free_climb,
+        mut state_tracker,
+        mut climb_system,
+        mut character,
+        transform,
+        player_ladder,
+        ladder_tracker,
+        colliding_entities,
+    ) in query.iter_mut() {
+        if !free_climb.is_climbing {
+            continue;
+        }
+
+        // Ladder hand-off: touching an active ladder while climbing
+        if free_climb.transition_to_ladder {
+            if let (Some(mut player_ladder), Some(mut ladder_tracker), Some(colliding)) = (player_ladder, ladder_tracker, colliding_entities) {
+                let ladder_entity = colliding.iter().copied().find(|colliding_entity| {
+                    ladder_query.get(*colliding_entity).map(|ladder| ladder.ladder_active).unwrap_or(false)
+                });
+
+                if let Some(ladder_entity) = ladder_entity {
+                    end_free_climb(&mut commands, entity, &mut free_climb, &mut state_tracker, &mut character, FreeClimbEndReason::TransitionedToLadder);
+                    player_ladder.ladder_found = true;
+                    player_ladder.current_ladder_system = Some(ladder_entity);
+                    ladder_tracker.current_state = LadderMovementState::Approaching;
+                    continue;
+                }
+            }
+        }
+
+        // Ledge hand-off: wall ends above the hands while climbing upward
+        if !free_climb.top_out_to_ledge || free_climb.climb_input.y <= 0.1 {
+            continue;
+        }
+
+        let normal = free_climb.surface_normal.normalize_or_zero();
+        let Ok(forward_dir) = Dir3::new(-normal) else {
+            continue;
+        };
+        let filter = SpatialQueryFilter::from_excluded_entities([entity]);
+        let top_origin = transform.translation + Vec3::Y * free_climb.top_check_height;
+
+        if spatial_query.cast_ray(top_origin, forward_dir, free_climb.surface_check_distance, true, &filter).is_some() {
+            // Still wall above the hands
+            continue;
+        }
+
+        // Find the top of the wall just beyond the surface
+        let down_origin = top_origin + forward_dir.as_vec3() * (free_climb.distance_to_surface + 0.2);
+        if let Some(down_hit) = spatial_query.cast_ray(down_origin, Dir3::NEG_Y, free_climb.top_check_height, true, &filter) {
+            if down_hit.normal.dot(Vec3::Y) > 0.7 {
+                let ledge_position = down_origin - Vec3::Y * down_hit.distance;
+
+                climb_system.ledge_position = ledge_position;
+                climb_system.ledge_normal = normal;
+                climb_system.ledge_surface = Some(down_hit.entity);
+                climb_system.ledge_zone_found = true;
+                climb_system.can_start_to_climb_ledge = true;
+                climb_system.grabbing_surface = true;
+
+                end_free_climb(&mut commands, entity, &mut free_climb, &mut state_tracker, &mut character, FreeClimbEndReason::ToppedOutToLedge);
+            }
+        }
+    }
+}
+
+/// Utility function to check if the player is currently free climbing
+pub fn is_free_climbing(free_climb: &FreeClimb) -> bool {
+    free_climb.is_climbing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn test_free_climb_grab_drain_and_release() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<InputState>()
+            .add_systems(Update, (buffer_free_climb_input, handle_free_climb_input, update_free_climb_movement).chain());
+
+        let wall = app.world_mut().spawn(FreeClimbSurface { stamina_drain_multiplier: 2.0, ..default() }).id();
+        let player = app.world_mut().spawn((
+            Player,
+            FreeClimb {
+                surface_found: true,
+                current_surface: Some(wall),
+                surface_point: Vec3::new(0.0, 1.0, 0.0),
+                surface_normal: Vec3::Z,
+                ..default()
+            },
+            ClimbStateTracker::default(),
+            ClimbLedgeSystem::default(),
+            CharacterController::default(),
+            Transform::from_xyz(0.0, 0.0, 0.4),
+            LinearVelocity::default(),
+        )).id();
+
+        app.insert_resource(InputState { interact_pressed: true, ..default() });
+        app.update();
+        assert!(app.world().get::<FreeClimb>(player).unwrap().is_climbing);
+        assert_eq!(app.world().get::<ClimbStateTracker>(player).unwrap().current_state, ClimbState::FreeClimbing);
+        assert!(app.world().get::<CharacterController>(player).unwrap().zero_gravity_mode);
+
+        // Climbing up the wall moves the player and drains stamina
+        let stamina = app.world().get::<ClimbStateTracker>(player).unwrap().stamina;
+        app.insert_resource(InputState { movement: Vec2::Y, ..default() });
+        app.update();
+        app.update();
+        assert!(app.world().get::<ClimbStateTracker>(player).unwrap().stamina < stamina);
+        assert!(app.world().get::<Transform>(player).unwrap().translation.y > 0.0);
+
+        app.insert_resource(InputState { crouch_pressed: true, ..default() });
+        app.update();
+        assert!(!app.world().get::<FreeClimb>(player).unwrap().is_climbing);
+        assert_eq!(app.world().get::<ClimbStateTracker>(player).unwrap().current_state, ClimbState::None);
+        assert!(!app.world().get::<CharacterController>(player).unwrap().zero_gravity_mode);
+    }
+}
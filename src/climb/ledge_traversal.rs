@@ -0,0 +1,521 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::character::{CharacterController, Player};
+use crate::input::InputState;
+use super::types::*;
+use super::climb_ledge_system::ClimbLedgeSystem;
+
+/// Corner type encountered while shimmying
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum LedgeCornerType {
+    /// Wall turns toward the player (concave)
+    Inner,
+    /// Wall turns away from the player (convex)
+    Outer,
+}
+
+/// Direction of a ledge-to-ledge hop
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum LedgeHopDirection {
+    Up,
+    Left,
+    Right,
+    Down,
+}
+
+/// Component for shimmying around corners and hopping between ledges while hanging
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct LedgeTraversal {
+    // Shimmy Settings
+    pub enabled: bool,
+    pub shimmy_speed: f32,
+    pub side_probe_distance: f32,
+    pub allow_inner_corners: bool,
+    pub allow_outer_corners: bool,
+    pub corner_turn_speed: f32,
+
+    // Hang Settings
+    pub hang_height_offset: f32,
+    pub hang_wall_offset: f32,
+
+    // Hop Settings
+    pub allow_hops: bool,
+    pub hop_reach_up: f32,
+    pub hop_reach_side: f32,
+    pub hop_reach_down: f32,
+    pub hop_speed: f32,
+    pub hop_cooldown: f32,
+    pub min_input_for_hop: f32,
+
+    // Buffered Input (set in `Update`, consumed by the next fixed step)
+    pub hop_requested: Option<LedgeHopDirection>,
+
+    // Debug State
+    pub is_shimmying: bool,
+    pub is_turning_corner: bool,
+    pub is_hopping: bool,
+    pub hop_timer: f32,
+    pub last_corner: Option<LedgeCornerType>,
+}
+
+impl Default for LedgeTraversal {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            shimmy_speed: 1.2,
+            side_probe_distance: 0.5,
+            allow_inner_corners: true,
+            allow_outer_corners: true,
+            corner_turn_speed: 6.0,
+
+            hang_height_offset: 1.6,
+            hang_wall_offset: 0.35,
+
+            allow_hops: true,
+            hop_reach_up: 1.8,
+            hop_reach_side: 1.6,
+            hop_reach_down: 2.2,
+            hop_speed: 6.0,
+            hop_cooldown: 0.4,
+            min_input_for_hop: 0.5,
+
+            hop_requested: None,
+
+            is_shimmying: false,
+            is_turning_corner: false,
+            is_hopping: false,
+            hop_timer: 0.0,
+            last_corner: None,
+        }
+    }
+}
+
+impl LedgeTraversal {
+    /// Hop asked for by this frame's input: jump + up/left/right, or crouch + down.
+    /// A plain jump, or a jump while holding down, is a regular ledge jump.
+    pub fn hop_direction(&self, input_state: &InputState) -> Option<LedgeHopDirection> {
+        if !self.enabled || !self.allow_hops {
+            return None;
+        }
+        let input = input_state.movement;
+        if input_state.crouch_pressed && input.y < -self.min_input_for_hop {
+            Some(LedgeHopDirection::Down)
+        } else if !input_state.jump_pressed {
+            None
+        } else if input.y > self.min_input_for_hop {
+            Some(LedgeHopDirection::Up)
+        } else if input.x > self.min_input_for_hop {
+            Some(LedgeHopDirection::Right)
+        } else if input.x < -self.min_input_for_hop {
+            Some(LedgeHopDirection::Left)
+        } else {
+            None
+        }
+    }
+}
+
+/// Event for when the player turns a corner while hanging
+#[derive(Event, Debug, Reflect)]
+pub struct LedgeCornerTurnEvent {
+    pub entity: Entity,
+    pub corner: LedgeCornerType,
+    pub new_ledge_position: Vec3,
+    pub new_ledge_normal: Vec3,
+}
+
+/// Event for when the player hops (or drops) to another ledge
+#[derive(Event, Debug, Reflect)]
+pub struct LedgeHopEvent {
+    pub entity: Entity,
+    pub direction: LedgeHopDirection,
+    pub from_position: Vec3,
+    pub to_position: Vec3,
+    pub to_normal: Vec3,
+}
+
+/// Finds a ledge by casting toward a wall and then down from above its edge.
+///
+/// `wall_dir` points into the wall. Returns the ledge top position and the wall normal.
+fn probe_ledge(
+    spatial_query: &SpatialQuery,
+    filter: &SpatialQueryFilter,
+    origin: Vec3,
+    wall_dir: Vec3,
+    reach: f32,
+    max_drop: f32,
+) -> Option<(Vec3, Vec3)> {
+    let wall_dir = Dir3::new(wall_dir).ok()?;
+    let wall_hit = spatial_query.cast_ray(origin, wall_dir, reach, true, filter)?;
+
+    let up_dot = wall_hit.normal.dot(Vec3::Y);
+    if up_dot < -0.3 || up_dot > 0.7 {
+        return None;
+    }
+
+    let wall_point = origin + wall_dir.as_vec3() * wall_hit.distance;
+    let down_origin = wall_point + wall_dir.as_vec3() * 0.1 + Vec3::Y * max_drop;
+    let down_hit = spatial_query.cast_ray(down_origin, Dir3::NEG_Y, max_drop * 2.0, true, filter)?;
+
+    if down_hit.normal.dot(Vec3::Y) < 0.7 {
+        return None;
+    }
+
+    let ledge_top = down_origin - Vec3::Y * down_hit.distance;
+    // The top must be near the probe height, otherwise we hit the floor far below
+    if (ledge_top.y - origin.y).abs() > max_drop {
+        return None;
+    }
+
+    Some((ledge_top, wall_hit.normal))
+}
+
+/// Computes the hanging position/rotation for a ledge
+fn hang_pose(traversal: &LedgeTraversal, ledge_position: Vec3, ledge_normal: Vec3) -> (Vec3, Quat) {
+    let normal = Vec3::new(ledge_normal.x, 0.0, ledge_normal.z).normalize_or_zero();
+    let position = ledge_position + normal * traversal.hang_wall_offset - Vec3::Y * traversal.hang_height_offset;
+    let rotation = Transform::IDENTITY.looking_to(-normal, Vec3::Y).rotation;
+    (position, rotation)
+}
+
+type LedgeTraverser<'a> = (
+    Entity,
+    &'a mut LedgeTraversal,
+    &'a mut ClimbLedgeSystem,
+    &'a mut ClimbStateTracker,
+    &'a mut ClimbMovement,
+    &'a CharacterController,
+    &'a Transform,
+);
+
+/// System to shimmy along ledges, including inner and outer corners
+pub fn handle_ledge_shimmy(
+    mut commands: Commands,
+    time: Res<Time>,
+    input_state: Res<InputState>,
+    spatial_query: SpatialQuery,
+    mut query: Query<LedgeTraverser, With<Player>>,
+) {
+    for (
+        entity,
+        mut traversal,
+        mut climb_system,
+        mut state_tracker,
+        mut climb_movement,
+        character,
+        transform,
+    ) in query.iter_mut() {
+        if !traversal.enabled || character.is_dead || traversal.is_hopping {
+            continue;
+        }
+
+        let hanging = matches!(
+            state_tracker.current_state,
+            ClimbState::Hanging | ClimbState::ClimbingLeft | ClimbState::ClimbingRight
+        );
+        if !hanging {
+            traversal.is_shimmying = false;
+            continue;
+        }
+
+        // Wait for a corner turn to finish before moving again
+        if traversal.is_turning_corner {
+            if !climb_movement.is_active {
+                traversal.is_turning_corner = false;
+            }
+            continue;
+        }
+
+        let horizontal = input_state.movement.x;
+        if horizontal.abs() < 0.1 || input_state.movement.y.abs() > horizontal.abs() {
+            traversal.is_shimmying = false;
+            if state_tracker.current_state != ClimbState::Hanging {
+                state_tracker.current_state = ClimbState::Hanging;
+            }
+            continue;
+        }
+
+        let normal = Vec3::new(climb_system.ledge_normal.x, 0.0, climb_system.ledge_normal.z).normalize_or_zero();
+        if normal == Vec3::ZERO {
+            continue;
+        }
+
+        // Right while facing the wall (facing direction is -normal)
+        let wall_right = normal.cross(Vec3::Y).normalize_or_zero();
+        let side = wall_right * horizontal.signum();
+        let filter = SpatialQueryFilter::from_excluded_entities([entity]);
+        let ledge_y = climb_system.ledge_position.y;
+        let hand_origin = Vec3::new(transform.translation.x, ledge_y - 0.2, transform.translation.z);
+
+        state_tracker.current_state = if horizontal > 0.0 { ClimbState::ClimbingRight } else { ClimbState::ClimbingLeft };
+
+        // Inner corner: a wall blocks the sideways movement
+        if traversal.allow_inner_corners {
+            if let Some((corner_ledge, corner_normal)) = probe_ledge(
+                &spatial_query,
+                &filter,
+                hand_origin,
+                side,
+                traversal.side_probe_distance,
+                0.5,
+            ) {
+                let (target_position, target_rotation) = hang_pose(&traversal, corner_ledge, corner_normal);
+                climb_movement.is_active = true;
+                climb_movement.target_position = target_position;
+                climb_movement.target_rotation = target_rotation;
+                climb_movement.rotation_speed = traversal.corner_turn_speed;
+
+                climb_system.ledge_position = corner_ledge;
+                climb_system.ledge_normal = corner_normal;
+                traversal.is_turning_corner = true;
+                traversal.last_corner = Some(LedgeCornerType::Inner);
+
+                commands.trigger(LedgeCornerTurnEvent {
+                    entity,
+                    corner: LedgeCornerType::Inner,
+                    new_ledge_position: corner_ledge,
+                    new_ledge_normal: corner_normal,
+                });
+                continue;
+            }
+        }
+
+        // Check the ledge continues beside the hands
+        let ahead_origin = hand_origin + side * traversal.side_probe_distance + normal * traversal.hang_wall_offset;
+        if let Some((next_ledge, next_normal)) = probe_ledge(
+            &spatial_query,
+            &filter,
+            ahead_origin,
+            -normal,
+            traversal.hang_wall_offset + 0.5,
+            0.5,
+        ) {
+            // Straight shimmy along the current ledge
+            traversal.is_shimmying = true;
+            let step = side * traversal.shimmy_speed * time.delta_secs();
+            let (target_position, target_rotation) = hang_pose(&traversal, next_ledge, next_normal);
+            let along = (target_position - transform.translation).dot(side).max(0.0);
+
+            climb_movement.is_active = true;
+            climb_movement.target_position = transform.translation + step.clamp_length_max(along);
+            climb_movement.target_position.y = target_position.y;
+            climb_movement.target_rotation = target_rotation;
+            climb_movement.move_speed = traversal.shimmy_speed;
+
+            climb_system.ledge_position.y = next_ledge.y;
+            climb_system.ledge_normal = next_normal;
+            continue;
+        }
+
+        // Outer corner: wall ends, look for the wall wrapping around the edge
+        if traversal.allow_outer_corners {
+            let around_origin = hand_origin + side * traversal.side_probe_distance - normal * (traversal.hang_wall_offset + 0.3);
+            if let Some((corner_ledge, corner_normal)) = probe_ledge(
+                &spatial_query,
+                &filter,
+                around_origin,
+                -side,
+                traversal.side_probe_distance + 0.3,
+                0.5,
+            ) {
+                let (target_position, target_rotation) = hang_pose(&traversal, corner_ledge, corner_normal);
+                climb_movement.is_active = true;
+                climb_movement.target_position = target_position;
+                climb_movement.target_rotation = target_rotation;
+                climb_movement.rotation_speed = traversal.corner_turn_speed;
+
+                climb_system.ledge_position = corner_ledge;
+                climb_system.ledge_normal = corner_normal;
+                traversal.is_turning_corner = true;
+                traversal.last_corner = Some(LedgeCornerType::Outer);
+
+                commands.trigger(LedgeCornerTurnEvent {
+                    entity,
+                    corner: LedgeCornerType::Outer,
+                    new_ledge_position: corner_ledge,
+                    new_ledge_normal: corner_normal,
+                });
+                continue;
+            }
+        }
+
+        // End of the ledge with no corner to take
+        traversal.is_shimmying = false;
+        climb_movement.is_active = false;
+    }
+}
+
+/// System to buffer hop and drop presses until the next fixed step reads them
+pub fn buffer_ledge_hop_input(
+    input_state: Res<InputState>,
+    mut query: Query<&mut LedgeTraversal, With<Player>>,
+) {
+    for mut traversal in query.iter_mut() {
+        if let Some(direction) = traversal.hop_direction(&input_state) {
+            traversal.hop_requested = Some(direction);
+        }
+    }
+}
+
+/// System to hop up/sideways to nearby ledges or drop to a lower ledge
+pub fn handle_ledge_hops(
+    mut commands: Commands,
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    mut query: Query<LedgeTraverser, With<Player>>,
+) {
+    for (
+        entity,
+        mut traversal,
+        mut climb_system,
+        mut state_tracker,
+        mut climb_movement,
+        character,
+        transform,
+    ) in query.iter_mut() {
+        if traversal.hop_timer > 0.0 {
+            traversal.hop_timer -= time.delta_secs();
+        }
+
+        // Finish an in-flight hop once the movement interpolation lands
+        if traversal.is_hopping {
+            if !climb_movement.is_active {
+                traversal.is_hopping = false;
+                state_tracker.current_state = ClimbState::Hanging;
+            }
+            continue;
+        }
+
+        // A hop pressed during the cooldown stays buffered until it ends
+        if traversal.hop_timer > 0.0 {
+            continue;
+        }
+        let hop_request = traversal.hop_requested.take();
+
+        if !traversal.enabled || !traversal.allow_hops || character.is_dead {
+            continue;
+        }
+
+        if state_tracker.current_state != ClimbState::Hanging &&
+           state_tracker.current_state != ClimbState::ClimbingLeft &&
+           state_tracker.current_state != ClimbState::ClimbingRight {
+            continue;
+        }
+
+        let Some(direction) = hop_request else {
+            continue;
+        };
+
+        let normal = Vec3::new(climb_system.ledge_normal.x, 0.0, climb_system.ledge_normal.z).normalize_or_zero();
+        if normal == Vec3::ZERO {
+            continue;
+        }
+        let wall_right = normal.cross(Vec3::Y).normalize_or_zero();
+        let filter = SpatialQueryFilter::from_excluded_entities([entity]);
+        let ledge = climb_system.ledge_position;
+        let from_wall = ledge + normal * traversal.hang_wall_offset;
+
+        // Sample a few points along the reach and take the closest valid ledge
+        let (offset_dir, reach) = match direction {
+            LedgeHopDirection::Up => (Vec3::Y, traversal.hop_reach_up),
+            LedgeHopDirection::Down => (-Vec3::Y, traversal.hop_reach_down),
+            LedgeHopDirection::Right => (wall_right, traversal.hop_reach_side),
+            LedgeHopDirection::Left => (-wall_right, traversal.hop_reach_side),
+        };
+
+        let samples = 4;
+        let mut target = None;
+        for i in 1..=samples {
+            let distance = reach * i as f32 / samples as f32;
+            let origin = from_wall + offset_dir * distance - Vec3::Y * 0.2;
+            if let Some(found) = probe_ledge(
+                &spatial_query,
+                &filter,
+                origin,
+                -normal,
+                traversal.hang_wall_offset + 0.6,
+                0.4,
+            ) {
+                // Ignore the ledge we are already hanging on
+                if (found.0 - ledge).length() > 0.4 {
+                    target = Some(found);
+                    break;
+                }
+            }
+        }
+
+        let Some((target_ledge, target_normal)) = target else {
+            continue;
+        };
+
+        let (target_position, target_rotation) = hang_pose(&traversal, target_ledge, target_normal);
+        climb_movement.is_active = true;
+        climb_movement.target_position = target_position;
+        climb_movement.target_rotation = target_rotation;
+        climb_movement.move_speed = traversal.hop_speed;
+
+        climb_system.ledge_position = target_ledge;
+        climb_system.ledge_normal = target_normal;
+
+        state_tracker.previous_state = state_tracker.current_state;
+        state_tracker.current_state = match direction {
+            LedgeHopDirection::Up => ClimbState::ClimbingUp,
+            LedgeHopDirection::Down => ClimbState::ClimbingDown,
+            LedgeHopDirection::Left => ClimbState::ClimbingLeft,
+            LedgeHopDirection::Right => ClimbState::ClimbingRight,
+        };
+        state_tracker.state_timer = 0.0;
+
+        traversal.is_hopping = true;
+        traversal.is_shimmying = false;
+        traversal.hop_timer = traversal.hop_cooldown;
+
+        commands.trigger(LedgeHopEvent {
+            entity,
+            direction,
+            from_position: transform.translation,
+            to_position: target_position,
+            to_normal: target_normal,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hop_input_buffered_through_cooldown() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin, PhysicsPlugins::default()))
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<InputState>()
+            .add_systems(Update, (buffer_ledge_hop_input, handle_ledge_hops).chain());
+
+        let player = app.world_mut().spawn((
+            Player,
+            LedgeTraversal { hop_timer: 10.0, ..default() },
+            ClimbLedgeSystem::default(),
+            ClimbStateTracker { current_state: ClimbState::Hanging, ..default() },
+            ClimbMovement::default(),
+            CharacterController::default(),
+            Transform::default(),
+        )).id();
+
+        // Jumping while holding down is a regular ledge jump, not a hop
+        app.insert_resource(InputState { jump_pressed: true, movement: Vec2::new(0.0, -1.0), ..default() });
+        app.update();
+        assert_eq!(app.world().get::<LedgeTraversal>(player).unwrap().hop_requested, None);
+
+        app.insert_resource(InputState { jump_pressed: true, movement: Vec2::new(0.0, 1.0), ..default() });
+        app.update();
+        app.insert_resource(InputState::default());
+        app.update();
+        assert_eq!(app.world().get::<LedgeTraversal>(player).unwrap().hop_requested, Some(LedgeHopDirection::Up));
+
+        // Consumed once the cooldown is over
+        app.world_mut().get_mut::<LedgeTraversal>(player).unwrap().hop_timer = 0.0;
+        app.update();
+        assert_eq!(app.world().get::<LedgeTraversal>(player).unwrap().hop_requested, None);
+    }
+}
@@ -1,11 +1,17 @@
 pub mod types;
 pub mod climb_ledge_system;
 pub mod systems;
+pub mod free_climb;
+pub mod ledge_traversal;
+pub mod moving_surfaces;
 
 use bevy::prelude::*;
 use types::*;
 use climb_ledge_system::*;
 use systems::*;
+use free_climb::*;
+use ledge_traversal::*;
+use moving_surfaces::*;
 
 // Re-export specific types for cleaner imports
 pub use types::ClimbState;
@@ -25,6 +31,15 @@ pub use types::LedgeClimbedEvent;
 pub use types::LedgeLostEvent;
 pub use types::LedgeJumpEvent;
 pub use climb_ledge_system::ClimbLedgeSystem;
+pub use free_climb::{
+    FreeClimb, FreeClimbSurface, ClimbIkAnchor, FreeClimbEndReason,
+    FreeClimbStartedEvent, FreeClimbEndedEvent, is_free_climbing,
+};
+pub use ledge_traversal::{
+    LedgeTraversal, LedgeCornerType, LedgeHopDirection,
+    LedgeCornerTurnEvent, LedgeHopEvent,
+};
+pub use moving_surfaces::ClimbSurfaceAnchor;
 
 pub struct ClimbPlugin;
 
@@ -33,16 +48,42 @@ impl Plugin for ClimbPlugin {
         app
             .register_type::<ClimbLedgeSystem>()
             .register_type::<LedgeZone>()
+            .register_type::<FreeClimb>()
+            .register_type::<FreeClimbSurface>()
+            .register_type::<LedgeTraversal>()
+            .register_type::<ClimbStateTracker>()
+            .register_type::<LedgeDetection>()
+            .register_type::<AutoHang>()
+            .register_type::<ClimbAnimation>()
+            .register_type::<ClimbMovement>()
+            .register_type::<LedgeJump>()
+            .register_type::<GrabSurfaceOnAir>()
+            .register_type::<ClimbSurfaceAnchor>()
             .add_systems(Update, (
                 handle_climb_input,
+                buffer_free_climb_input,
+                buffer_ledge_hop_input,
                 update_climb_state,
                 update_climb_visuals,
             ).chain())
+            .add_systems(FixedUpdate, anchor_climbers_to_surfaces
+                .after(crate::physics::follow_surface_anchors)
+                .before(detect_ledge)
+                .before(detect_free_climb_surface))
             .add_systems(FixedUpdate, (
                 detect_ledge,
                 detect_ledge_below,
+                handle_ledge_shimmy,
+                handle_ledge_hops,
                 update_climb_movement,
                 handle_auto_hang,
-            ));
+            ).chain())
+            .add_systems(FixedUpdate, (
+                detect_free_climb_surface,
+                handle_free_climb_input,
+                update_free_climb_movement,
+                sample_free_climb_ik_anchors,
+                handle_free_climb_transitions,
+            ).chain());
     }
 }
@@ -0,0 +1,124 @@
+//! Climbing on moving and rotating objects.
+//!
+//! While the player hangs from a ledge or free climbs, the collider they hold
+//! gets a `SurfaceAnchor` (see `crate::physics::surface_anchor`), which carries
+//! them along with it. The ledge being held and any climb or auto-hang target
+//! are moved with the surface, so shimmying and climbing up keep working while
+//! it moves. Letting go keeps the surface's momentum, and a surface that
+//! shakes the player off drops them with `LedgeLostReason::ShakenOff` or
+//! `FreeClimbEndReason::ShakenOff`.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::character::{CharacterController, Player};
+use crate::physics::{release_surface_anchor, SurfaceAnchor, SurfaceAnchorSettings, SurfaceDetachedEvent};
+use crate::utils::QueueReader;
+use super::types::*;
+use super::climb_ledge_system::ClimbLedgeSystem;
+use super::free_climb::{end_free_climb, FreeClimb, FreeClimbEndReason};
+
+/// Marks a `SurfaceAnchor` placed by climbing, as opposed to a ladder
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct ClimbSurfaceAnchor;
+
+/// Surface the player holds, with the world grip point and normal
+fn held_surface(climb_system: &ClimbLedgeSystem, state: ClimbState, free_climb: Option<&FreeClimb>) -> Option<(Entity, Vec3, Vec3)> {
+    if let Some(free_climb) = free_climb.filter(|free_climb| free_climb.is_climbing) {
+        return free_climb.current_surface.map(|surface| (surface, free_climb.surface_point, free_climb.surface_normal));
+    }
+    let holding = (climb_system.grabbing_surface || climb_system.climbing_ledge)
+        && !matches!(state, ClimbState::None | ClimbState::Falling);
+    if !holding {
+        return None;
+    }
+    climb_system.ledge_surface.map(|surface| (surface, climb_system.ledge_position, climb_system.ledge_normal))
+}
+
+/// Climber parts anchored to and dropped from moving surfaces
+type AnchoredClimber<'a> = (
+    Entity,
+    &'a mut ClimbLedgeSystem,
+    &'a mut ClimbStateTracker,
+    &'a mut ClimbMovement,
+    &'a mut AutoHang,
+    &'a mut CharacterController,
+    Option<&'a mut FreeClimb>,
+    Option<&'a mut SurfaceAnchor>,
+    Option<&'a mut LinearVelocity>,
+    Has<ClimbSurfaceAnchor>,
+);
+
+/// System to anchor climbers to the surface they hold, and drop them when it
+/// shakes them off
+pub fn anchor_climbers_to_surfaces(
+    mut commands: Commands,
+    settings: Res<SurfaceAnchorSettings>,
+    mut detached: QueueReader<SurfaceDetachedEvent>,
+    mut query: Query<AnchoredClimber, With<Player>>,
+    surface_query: Query<&GlobalTransform>,
+) {
+    let mut shaken_off = Vec::new();
+    for event in detached.read() {
+        let Ok((entity, mut climb_system, mut state_tracker, mut climb_movement, mut auto_hang, mut character, free_climb, _, _, climbing)) =
+            query.get_mut(event.entity) else { continue };
+        if !climbing {
+            continue;
+        }
+        shaken_off.push(entity);
+        commands.entity(entity).remove::<ClimbSurfaceAnchor>();
+        climb_movement.is_active = false;
+        auto_hang.active = false;
+        auto_hang.moving_toward_ledge = false;
+
+        if let Some(mut free_climb) = free_climb.filter(|free_climb| free_climb.is_climbing) {
+            end_free_climb(&mut commands, entity, &mut free_climb, &mut state_tracker, &mut character, FreeClimbEndReason::ShakenOff);
+        } else if climb_system.grabbing_surface || climb_system.climbing_ledge {
+            state_tracker.previous_state = state_tracker.current_state;
+            state_tracker.current_state = ClimbState::Falling;
+            state_tracker.state_timer = 0.0;
+            climb_system.grabbing_surface = false;
+            climb_system.climbing_ledge = false;
+            climb_system.activate_climb_action = false;
+            commands.trigger(LedgeLostEvent { entity, reason: LedgeLostReason::ShakenOff });
+        }
+    }
+
+    for (entity, mut climb_system, state_tracker, mut climb_movement, mut auto_hang, _, free_climb, anchor, mut velocity, climbing) in query.iter_mut() {
+        if shaken_off.contains(&entity) {
+            continue;
+        }
+        let held = held_surface(&climb_system, state_tracker.current_state, free_climb.as_deref());
+        match (held, anchor) {
+            (Some((surface, _, _)), Some(mut anchor)) if climbing && anchor.surface == surface => {
+                // The anchor carried the player; carry what they are reaching for too
+                if climb_movement.is_active {
+                    climb_movement.target_position = anchor.carry_point(climb_movement.target_position);
+                    climb_movement.target_rotation = anchor.carry_rotation(climb_movement.target_rotation);
+                }
+                if auto_hang.moving_toward_ledge {
+                    auto_hang.target_ledge_position = anchor.carry_point(auto_hang.target_ledge_position);
+                    auto_hang.target_ledge_normal = anchor.carry_direction(auto_hang.target_ledge_normal);
+                }
+                if free_climb.as_ref().is_some_and(|free_climb| free_climb.is_climbing) {
+                    continue;
+                }
+                let ledge_position = anchor.carry_point(climb_system.ledge_position);
+                let ledge_normal = anchor.carry_direction(climb_system.ledge_normal);
+                climb_system.ledge_position = ledge_position;
+                climb_system.ledge_normal = ledge_normal;
+                anchor.regrip(ledge_position, ledge_normal);
+            }
+            (Some((surface, point, normal)), _) => {
+                if let Ok(surface_transform) = surface_query.get(surface) {
+                    commands.entity(entity).insert((SurfaceAnchor::new(surface, surface_transform, point, normal), ClimbSurfaceAnchor));
+                }
+            }
+            (None, Some(anchor)) if climbing => {
+                release_surface_anchor(&mut commands, entity, &anchor, velocity.as_deref_mut(), &settings);
+                commands.entity(entity).remove::<ClimbSurfaceAnchor>();
+            }
+            (None, _) => {}
+        }
+    }
+}
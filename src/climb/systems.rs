@@ -1,10 +1,12 @@
 use bevy::prelude::*;
 use crate::character::{CharacterController, Player};
 use crate::input::InputState;
+use crate::game_manager::types::FeatureToggles;
 use crate::physics::{GroundDetection, GroundDetectionSettings};
 use avian3d::prelude::*;
 use super::types::*;
 use super::climb_ledge_system::ClimbLedgeSystem;
+use super::ledge_traversal::LedgeTraversal;
 
 /// System to handle climb input
 pub fn handle_climb_input(
@@ -21,6 +23,7 @@ pub fn handle_climb_input(
         &CharacterController,
         &Transform,
         &mut LinearVelocity,
+        Option<&LedgeTraversal>,
     ), With<Player>>,
     ground_query: Query<(&GroundDetection, &GroundDetectionSettings)>,
 ) {
@@ -34,6 +37,7 @@ pub fn handle_climb_input(
         character,
         _transform,
         mut velocity,
+        ledge_traversal,
     ) in query.iter_mut() {
         if !climb_system.climb_ledge_active || !climb_system.can_use_climb_ledge {
             continue;
@@ -48,7 +52,10 @@ pub fn handle_climb_input(
         if climb_system.can_jump_when_hold_ledge &&
            (state_tracker.current_state == ClimbState::Hanging || climb_system.grabbing_surface) &&
            !climb_system.activate_climb_action {
-            if input_state.jump_pressed {
+            // Directional jumps are ledge-to-ledge hops, handled by the ledge traversal system
+            let wants_hop = ledge_traversal.is_some_and(|traversal| traversal.hop_direction(&input_state).is_some());
+
+            if input_state.jump_pressed && !wants_hop {
                 // Trigger jump from ledge
                 // Apply jump force to the player's rigidbody
                 ledge_jump.is_jumping = true;
@@ -113,6 +120,7 @@ pub fn handle_climb_input(
                             // Calculate hit point: origin + dir * distance
                             climb_system.ledge_position = ray_origin + ray_dir_vec * hit.distance;
                             climb_system.ledge_normal = surface_normal;
+                            climb_system.ledge_surface = Some(hit.entity);
                         
                         // Set state to hanging
                         state_tracker.current_state = ClimbState::Hanging;
@@ -154,6 +162,7 @@ pub fn handle_climb_input(
 /// System to update climb state
 pub fn update_climb_state(
     time: Res<Time>,
+    toggles: Res<FeatureToggles>,
     input_state: Res<InputState>,
     mut query: Query<(
         &mut ClimbLedgeSystem,
@@ -180,7 +189,10 @@ pub fn update_climb_state(
         state_tracker.state_timer += time.delta_secs();
 
         // Update stamina
-        if state_tracker.current_state != ClimbState::None &&
+        if !toggles.stamina {
+            state_tracker.stamina = state_tracker.max_stamina;
+            state_tracker.is_stamina_depleted = false;
+        } else if state_tracker.current_state != ClimbState::None &&
            state_tracker.current_state != ClimbState::Falling {
             // Drain stamina while climbing
             state_tracker.stamina -= state_tracker.stamina_drain_rate * time.delta_secs();
@@ -454,6 +466,7 @@ pub fn detect_ledge(
                         climb_system.surface_to_hang_on_ground_found = true;
                         climb_system.ledge_position = down_hit_point;
                         climb_system.ledge_normal = surface_normal;
+                        climb_system.ledge_surface = Some(forward_hit.entity);
                         
                         // Update ledge detection results
                         _ledge_detection.ledge_found = true;
@@ -548,6 +561,7 @@ pub fn detect_ledge_below(
                     // Calculate hit point manualy
                     climb_system.ledge_position = down_ray_origin + (-Vec3::Y) * distance_to_surface;
                     climb_system.ledge_normal = surface_normal;
+                    climb_system.ledge_surface = Some(down_hit.entity);
                     
                     // Additional checks for ledge validity
                     if distance_to_surface < 1.5 {
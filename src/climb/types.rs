@@ -53,6 +53,7 @@ pub enum ClimbState {
     ClimbingLeft,
     ClimbingRight,
     Vaulting,
+    FreeClimbing,
     Falling,
 }
 
@@ -335,6 +336,8 @@ pub enum LedgeLostReason {
     SurfaceBecameUnclimbable,
     ManualRelease,
     JumpedOff,
+    /// The surface moved too violently to hold on
+    ShakenOff,
 }
 
 /// Event for when a ledge jump occurs
@@ -73,7 +73,7 @@ pub fn handle_area_effects(
 
                 if distance <= effect.radius {
                     // Apply Effect
-                    damage_queue.0.push(DamageEvent {
+                    damage_queue.send(DamageEvent {
                         amount: effect.amount,
                         damage_type: effect.damage_type,
                         source: effect.source, // The effect entity itself could be source, or the original caster
@@ -44,7 +44,7 @@ pub fn update_damage_over_time(
             dot.last_tick_time = dot.time_elapsed;
 
             // Apply damage
-            damage_queue.0.push(DamageEvent {
+            damage_queue.send(DamageEvent {
                 amount: dot.damage_per_tick,
                 damage_type: dot.damage_type,
                 source: dot.source,
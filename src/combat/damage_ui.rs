@@ -1,7 +1,12 @@
 use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
 use super::types::*;
+use super::damage_over_time::DamageOverTime;
 use super::result_queue::*;
+use crate::utils::QueueReader;
 use crate::character::Player;
+use crate::hud::{HudElement, HudPriority};
+use crate::input::{HapticsCurve, HapticsEvent, HapticsQueue};
 
 /// Component for the full-screen damage tint effect.
 #[derive(Component, Reflect)]
@@ -25,7 +30,17 @@ impl Default for DamageScreenEffect {
     }
 }
 
-/// Component for a directional damage indicator UI element.
+/// Look of a damage indicator, picked from the damage that spawned it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum DamageIndicatorStyle {
+    Direct,
+    Explosive,
+    /// Ticks of a `DamageOverTime` effect (burning, poison...)
+    DamageOverTime,
+}
+
+/// Component for a directional damage indicator UI element: an arc on a ring
+/// around the screen center, turned toward the damage source.
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct DamageIndicator {
@@ -33,6 +48,13 @@ pub struct DamageIndicator {
     pub lifetime: f32,
     /// World position of the damage source.
     pub source_position: Vec3,
+    /// Entity that dealt the damage, followed while it exists.
+    pub source: Option<Entity>,
+    /// Lifetime the indicator started with, for the fade.
+    pub duration: f32,
+    /// Hits from the same source stacked on this indicator.
+    pub stacks: u32,
+    pub style: DamageIndicatorStyle,
 }
 
 /// Resource to hold settings for damage feedback.
@@ -42,6 +64,20 @@ pub struct DamageFeedbackSettings {
     pub flash_enabled: bool,
     pub indicators_enabled: bool,
     pub indicator_lifetime: f32,
+    /// Radius of the indicator ring, in pixels.
+    pub indicator_radius: f32,
+    /// Arc thickness of a single hit; each stack adds to it.
+    pub indicator_thickness: f32,
+    pub max_indicator_stacks: u32,
+    pub direct_color: Color,
+    pub explosive_color: Color,
+    pub damage_over_time_color: Color,
+    /// Also rumble gamepads when the player is hit.
+    pub rumble_enabled: bool,
+    /// Rumble strength for a hit of `rumble_full_damage` or more.
+    pub rumble_intensity: f32,
+    pub rumble_full_damage: f32,
+    pub rumble_duration: f32,
 }
 
 impl Default for DamageFeedbackSettings {
@@ -50,10 +86,47 @@ impl Default for DamageFeedbackSettings {
             flash_enabled: true,
             indicators_enabled: true,
             indicator_lifetime: 2.0,
+            indicator_radius: 140.0,
+            indicator_thickness: 6.0,
+            max_indicator_stacks: 4,
+            direct_color: Color::srgba(0.9, 0.1, 0.1, 0.8),
+            explosive_color: Color::srgba(1.0, 0.55, 0.1, 0.9),
+            damage_over_time_color: Color::srgba(0.6, 0.9, 0.2, 0.6),
+            rumble_enabled: false,
+            rumble_intensity: 0.6,
+            rumble_full_damage: 50.0,
+            rumble_duration: 0.2,
         }
     }
 }
 
+impl DamageFeedbackSettings {
+    pub fn indicator_color(&self, style: DamageIndicatorStyle) -> Color {
+        match style {
+            DamageIndicatorStyle::Direct => self.direct_color,
+            DamageIndicatorStyle::Explosive => self.explosive_color,
+            DamageIndicatorStyle::DamageOverTime => self.damage_over_time_color,
+        }
+    }
+
+    /// Ring radius of a style; explosions sit further out
+    pub fn indicator_ring_radius(&self, style: DamageIndicatorStyle) -> f32 {
+        match style {
+            DamageIndicatorStyle::Explosive => self.indicator_radius * 1.25,
+            _ => self.indicator_radius,
+        }
+    }
+
+    pub fn indicator_arc_thickness(&self, style: DamageIndicatorStyle, stacks: u32) -> f32 {
+        let base = match style {
+            DamageIndicatorStyle::Explosive => self.indicator_thickness * 2.0,
+            DamageIndicatorStyle::DamageOverTime => self.indicator_thickness * 0.5,
+            DamageIndicatorStyle::Direct => self.indicator_thickness,
+        };
+        base * (1.0 + 0.5 * stacks.saturating_sub(1) as f32)
+    }
+}
+
 /// System to spawn Damage UI for the player.
 /// Should be called once during setup or player spawn.
 pub fn setup_damage_ui(mut commands: Commands) {
@@ -68,6 +141,7 @@ pub fn setup_damage_ui(mut commands: Commands) {
         BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
         GlobalZIndex(100), // Ensure it's on top
         DamageScreenEffect::default(),
+        HudElement::new("damage_overlay", HudPriority::Essential),
         // PickingBehavior::IGNORE removed due to potential import issue
     ));
 }
@@ -76,14 +150,14 @@ pub fn setup_damage_ui(mut commands: Commands) {
 pub fn update_damage_ui(
     mut commands: Commands,
     time: Res<Time>,
+    settings: Res<DamageFeedbackSettings>,
     // Query for the effect overlay
     mut effect_query: Query<(&mut DamageScreenEffect, &mut BackgroundColor)>,
     // Query for indicators
-    mut indicator_query: Query<(Entity, &mut DamageIndicator, &mut Transform, &mut Node)>,
+    mut indicator_query: Query<(Entity, &mut DamageIndicator, &mut Transform, &mut Node, &mut BorderColor)>,
     // Query for player camera to calculate directions
     camera_query: Query<(&GlobalTransform, &Camera), With<Camera3d>>,
-    // Query for player entity check (to ensure we only show for player)
-    player_query: Query<Entity, With<Player>>,
+    source_query: Query<&GlobalTransform, Without<DamageIndicator>>,
 ) {
     let dt = time.delta_secs();
 
@@ -107,19 +181,22 @@ pub fn update_damage_ui(
     // Using camera position usually feels better for FPS/TPS UI.
     let cam_pos = cam_xf.translation();
     let cam_forward = cam_xf.forward();
-    let cam_right = cam_xf.right();
 
     // flattened to XZ plane for 2D UI comparison
     let flat_forward = Vec3::new(cam_forward.x, 0.0, cam_forward.z).normalize_or_zero();
-    let flat_right = Vec3::new(cam_right.x, 0.0, cam_right.z).normalize_or_zero();
 
-    for (entity, mut indicator, mut transform, _) in indicator_query.iter_mut() {
+    for (entity, mut indicator, mut transform, mut node, mut border_color) in indicator_query.iter_mut() {
         indicator.lifetime -= dt;
         if indicator.lifetime <= 0.0 {
             commands.entity(entity).despawn();
             continue;
         }
 
+        // Follow the source while it's alive
+        if let Some(source_transform) = indicator.source.and_then(|source| source_query.get(source).ok()) {
+            indicator.source_position = source_transform.translation();
+        }
+
         // Calculate Angle
         let direction_to_source = (indicator.source_position - cam_pos).normalize_or_zero();
         let flat_dir = Vec3::new(direction_to_source.x, 0.0, direction_to_source.z).normalize_or_zero();
@@ -131,30 +208,51 @@ pub fn update_damage_ui(
         let is_right = flat_forward.cross(flat_dir).y < 0.0;
         let final_angle = if is_right { -angle } else { angle };
 
-        // Rotate the indicator UI element
-        // Assuming the indicator sprite points UP by default. 
-        // We rotate it around Z axis.
+        // The arc sits at the top of its ring; turning the ring points it at the source
         transform.rotation = Quat::from_rotation_z(final_angle);
+
+        let thickness = settings.indicator_arc_thickness(indicator.style, indicator.stacks);
+        node.border = UiRect::top(Val::Px(thickness));
+
+        let fade = (indicator.lifetime / indicator.duration.max(0.001)).clamp(0.0, 1.0);
+        let pulse = match indicator.style {
+            DamageIndicatorStyle::DamageOverTime => 0.6 + 0.4 * (indicator.lifetime * 8.0).sin().abs(),
+            _ => 1.0,
+        };
+        let mut color = settings.indicator_color(indicator.style);
+        color.set_alpha(color.alpha() * fade * pulse);
+        border_color.0 = color;
     }
 }
 
+/// Screen tint and direction indicators driven by damage feedback.
+#[derive(SystemParam)]
+pub struct DamageFeedbackUi<'w, 's> {
+    pub effect_query: Query<'w, 's, &'static mut DamageScreenEffect>,
+    pub indicator_query: Query<'w, 's, &'static mut DamageIndicator>,
+}
+
 /// System to listen for damage events and trigger UI effects.
 pub fn trigger_damage_ui(
     mut commands: Commands,
-    damage_queue: Res<DamageResultQueue>,
-    mut effect_query: Query<&mut DamageScreenEffect>,
-    player_query: Query<Entity, With<Player>>,
+    mut damage_queue: QueueReader<DamageResultEvent>,
+    feedback_ui: DamageFeedbackUi,
+    player_query: Query<(Entity, Option<&DamageOverTime>), With<Player>>,
+    mut haptics: ResMut<HapticsQueue>,
     settings: Res<DamageFeedbackSettings>,
-    asset_server: Res<AssetServer>,
     transform_query: Query<&GlobalTransform>,
 ) {
+    let DamageFeedbackUi { mut effect_query, mut indicator_query } = feedback_ui;
     // Only process if player exists
-    let player_entity = match player_query.iter().next() {
+    let (player_entity, player_dot) = match player_query.iter().next() {
         Some(p) => p,
         None => return,
     };
 
-    for event in damage_queue.0.iter() {
+    // Indicators spawned this frame, so further hits from the same source stack on them
+    let mut spawned: Vec<DamageIndicator> = Vec::new();
+
+    for event in damage_queue.read() {
         if event.target == player_entity && (event.final_amount > 0.0 || event.shielded_amount > 0.0) {
             // Trigger Flash
             if settings.flash_enabled {
@@ -163,41 +261,193 @@ pub fn trigger_damage_ui(
                 }
             }
 
+            let style = if event.damage_type == DamageType::Explosion {
+                DamageIndicatorStyle::Explosive
+            } else if player_dot.is_some_and(|dot| dot.damage_type == event.damage_type && dot.source == event.source) {
+                DamageIndicatorStyle::DamageOverTime
+            } else {
+                DamageIndicatorStyle::Direct
+            };
+
+            if settings.rumble_enabled {
+                let strength = (event.final_amount / settings.rumble_full_damage.max(0.001)).clamp(0.2, 1.0);
+                let strength = match style {
+                    DamageIndicatorStyle::Explosive => 1.0,
+                    DamageIndicatorStyle::DamageOverTime => strength * 0.5,
+                    DamageIndicatorStyle::Direct => strength,
+                } * settings.rumble_intensity;
+                haptics.send(
+                    HapticsEvent::new(strength, settings.rumble_duration)
+                        .for_player(player_entity)
+                        .with_curve(HapticsCurve::FadeOut),
+                );
+            }
+
             // Trigger Indicator
             if settings.indicators_enabled {
                 // Determine source position for indicator
-                let source_pos = if let Some(source) = event.source {
-                    if let Ok(transform) = transform_query.get(source) {
-                        Some(transform.translation())
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-
-                if let Some(pos) = source_pos {
-                    // Spawn Indicator
-                    commands.spawn((
-                        ImageNode::new(asset_server.load("ui/damage_indicator.png")),
-                        Node {
-                            position_type: PositionType::Absolute,
-                            top: Val::Percent(50.0),
-                            left: Val::Percent(50.0),
-                            width: Val::Px(100.0),
-                            height: Val::Px(100.0),
-                            margin: UiRect::all(Val::Auto), 
-                             ..default()
-                        },
-                        Transform::from_translation(Vec3::new(0.0, 0.0, 0.0)),
-                        GlobalZIndex(101),
-                        DamageIndicator {
-                            lifetime: settings.indicator_lifetime,
-                            source_position: pos,
-                        },
-                    ));
+                let Some(source) = event.source else { continue };
+                let Ok(source_transform) = transform_query.get(source) else { continue };
+
+                // Stack on the source's current indicator
+                let same_source = |indicator: &DamageIndicator| indicator.source == Some(source) && indicator.style == style;
+                if let Some(mut indicator) = indicator_query.iter_mut().find(|indicator| same_source(indicator)) {
+                    stack_indicator(&mut indicator, &settings);
+                    continue;
+                }
+                if let Some(indicator) = spawned.iter_mut().find(|indicator| same_source(indicator)) {
+                    stack_indicator(indicator, &settings);
+                    continue;
                 }
+
+                spawned.push(DamageIndicator {
+                    lifetime: settings.indicator_lifetime,
+                    source_position: source_transform.translation(),
+                    source: Some(source),
+                    duration: settings.indicator_lifetime,
+                    stacks: 1,
+                    style,
+                });
             }
         }
     }
+
+    for indicator in spawned {
+        let radius = settings.indicator_ring_radius(indicator.style);
+        let thickness = settings.indicator_arc_thickness(indicator.style, indicator.stacks);
+        // Ring centered on the screen with only its top border drawn: an arc
+        commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(50.0),
+                left: Val::Percent(50.0),
+                width: Val::Px(radius * 2.0),
+                height: Val::Px(radius * 2.0),
+                margin: UiRect {
+                    left: Val::Px(-radius),
+                    top: Val::Px(-radius),
+                    ..default()
+                },
+                border: UiRect::top(Val::Px(thickness)),
+                ..default()
+            },
+            BorderColor(settings.indicator_color(indicator.style)),
+            BorderRadius::MAX,
+            Transform::default(),
+            GlobalZIndex(101),
+            indicator,
+            Name::new("DamageIndicator"),
+        ));
+    }
+}
+
+fn stack_indicator(indicator: &mut DamageIndicator, settings: &DamageFeedbackSettings) {
+    indicator.stacks = (indicator.stacks + 1).min(settings.max_indicator_stacks.max(1));
+    indicator.lifetime = settings.indicator_lifetime;
+    indicator.duration = settings.indicator_lifetime;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[derive(Resource, Default)]
+    struct Rumbles(Vec<f32>);
+
+    fn log_rumbles(mut haptics: QueueReader<HapticsEvent>, mut log: ResMut<Rumbles>) {
+        log.0.extend(haptics.read().map(|event| event.strength));
+    }
+
+    fn hit(player: Entity, source: Entity, damage_type: DamageType) -> DamageResultEvent {
+        DamageResultEvent {
+            target: player,
+            part: None,
+            source: Some(source),
+            original_amount: 10.0,
+            final_amount: 10.0,
+            damage_type,
+            shielded_amount: 0.0,
+            is_crit: false,
+            is_block: false,
+        }
+    }
+
+    fn indicator_from(app: &mut App, source: Entity) -> (DamageIndicatorStyle, u32, Quat, Node) {
+        let mut query = app.world_mut().query::<(&DamageIndicator, &Transform, &Node)>();
+        query
+            .iter(app.world())
+            .find(|(indicator, ..)| indicator.source == Some(source))
+            .map(|(indicator, transform, node)| (indicator.style, indicator.stacks, transform.rotation, node.clone()))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_hits_stack_arcs_by_source_and_style() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            EventQueuePlugin::<DamageResultEvent>::default(),
+            EventQueuePlugin::<HapticsEvent>::default(),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .insert_resource(DamageFeedbackSettings { rumble_enabled: true, ..default() })
+        .init_resource::<Rumbles>()
+        .add_systems(Startup, setup_damage_ui)
+        .add_systems(Update, (trigger_damage_ui, update_damage_ui, log_rumbles).chain());
+
+        app.world_mut().spawn((Camera3d::default(), GlobalTransform::default()));
+        let bandit = app.world_mut().spawn(GlobalTransform::from_translation(Vec3::new(5.0, 0.0, 0.0))).id();
+        let grenadier = app.world_mut().spawn(GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 10.0))).id();
+        let torch = app.world_mut().spawn(GlobalTransform::from_translation(Vec3::new(-3.0, 0.0, -3.0))).id();
+        let player = app
+            .world_mut()
+            .spawn((Player, DamageOverTime { damage_type: DamageType::Fire, source: Some(torch), ..default() }))
+            .id();
+        app.update();
+
+        app.world_mut().resource_mut::<DamageResultQueue>().send_batch([
+            hit(player, bandit, DamageType::Melee),
+            hit(player, bandit, DamageType::Melee),
+            hit(player, grenadier, DamageType::Explosion),
+            hit(player, torch, DamageType::Fire),
+        ]);
+        app.update();
+
+        let settings = DamageFeedbackSettings::default();
+        // Both bandit hits share one arc on the right
+        let (style, stacks, rotation, node) = indicator_from(&mut app, bandit);
+        assert_eq!((style, stacks), (DamageIndicatorStyle::Direct, 2));
+        assert!(rotation.angle_between(Quat::from_rotation_z(-std::f32::consts::FRAC_PI_2)) < 1e-3);
+        assert_eq!(node.border.top, Val::Px(settings.indicator_thickness * 1.5));
+
+        let (style, stacks, _, node) = indicator_from(&mut app, grenadier);
+        assert_eq!((style, stacks), (DamageIndicatorStyle::Explosive, 1));
+        assert_eq!(node.width, Val::Px(settings.indicator_radius * 2.5));
+        let (style, ..) = indicator_from(&mut app, torch);
+        assert_eq!(style, DamageIndicatorStyle::DamageOverTime);
+
+        let (effect_intensity, flash) = {
+            let mut query = app.world_mut().query::<(&DamageScreenEffect, &BackgroundColor)>();
+            let (effect, background) = query.single(app.world()).unwrap();
+            (effect.intensity, background.0.alpha())
+        };
+        assert!((effect_intensity - 0.3).abs() < 1e-4 && (flash - 0.3).abs() < 1e-4);
+
+        let rumbles = &app.world().resource::<Rumbles>().0;
+        let expected = [0.12, 0.12, 0.6, 0.06];
+        assert_eq!(rumbles.len(), expected.len());
+        for (rumble, expected) in rumbles.iter().zip(expected) {
+            assert!((rumble - expected).abs() < 1e-4, "rumble {} != {}", rumble, expected);
+        }
+
+        // Arcs fade out after their lifetime
+        for _ in 0..20 {
+            app.update();
+        }
+        let remaining = app.world_mut().query::<&DamageIndicator>().iter(app.world()).count();
+        assert_eq!(remaining, 0);
+    }
 }
@@ -1,9 +1,11 @@
 use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
 use std::collections::HashMap;
 
-use crate::combat::types::DamageEventQueue;
+use crate::combat::types::DamageEvent;
 use crate::combat::types::DamageType;
 use crate::combat::impact::SurfaceType;
+use crate::utils::{EntityPool, Poolable, QueueReader};
 
 #[derive(Debug, Clone, Reflect)]
 pub struct DecalInfo {
@@ -97,22 +99,37 @@ pub struct Decal {
     pub lifetime: f32,
 }
 
+impl Poolable for Decal {
+    fn on_release(entity: &mut EntityCommands) {
+        // Detach so the pooled decal survives its old target being despawned
+        entity.remove_parent();
+    }
+}
+
+/// Assets decal meshes and materials are made from
+#[derive(SystemParam)]
+pub struct DecalAssets<'w> {
+    pub meshes: ResMut<'w, Assets<Mesh>>,
+    pub materials: ResMut<'w, Assets<StandardMaterial>>,
+    pub asset_server: Res<'w, AssetServer>,
+}
+
 pub fn spawn_decals_from_damage(
     mut commands: Commands,
-    damage_queue: Res<DamageEventQueue>,
+    mut damage_queue: QueueReader<DamageEvent>,
     registry: Res<DecalRegistry>,
     settings: Res<DecalSettings>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    asset_server: Res<AssetServer>,
+    mut decal_pool: ResMut<EntityPool<Decal>>,
+    assets: DecalAssets,
     target_query: Query<(&GlobalTransform, Option<&SurfaceType>)>,
 ) {
     if !settings.enabled {
         return;
     }
+    let DecalAssets { mut meshes, mut materials, asset_server } = assets;
 
     let mut spawned = 0;
-    for event in damage_queue.0.iter() {
+    for event in damage_queue.read() {
         if spawned >= settings.max_per_frame {
             break;
         }
@@ -154,19 +171,16 @@ pub fn spawn_decals_from_damage(
         }
         let material = materials.add(material);
 
-        let decal_entity = commands
-            .spawn((
-                Mesh3d(mesh),
-                MeshMaterial3d(material),
-                Transform::from_translation(world_pos).with_rotation(rotation),
-                GlobalTransform::default(),
-                Visibility::default(),
-                Decal {
-                    lifetime: info.lifetime,
-                },
-                Name::new("Decal"),
-            ))
-            .id();
+        let decal_entity = decal_pool.acquire(&mut commands, (
+            Mesh3d(mesh),
+            MeshMaterial3d(material),
+            Transform::from_translation(world_pos).with_rotation(rotation),
+            GlobalTransform::default(),
+            Decal {
+                lifetime: info.lifetime,
+            },
+            Name::new("Decal"),
+        ));
 
         if settings.attach_to_target {
             let parent_rot = target_transform.rotation();
@@ -191,13 +205,14 @@ pub fn spawn_decals_from_damage(
 pub fn update_decals(
     time: Res<Time>,
     mut commands: Commands,
+    mut decal_pool: ResMut<EntityPool<Decal>>,
     mut query: Query<(Entity, &mut Decal)>,
 ) {
     let dt = time.delta_secs();
     for (entity, mut decal) in query.iter_mut() {
         decal.lifetime -= dt;
         if decal.lifetime <= 0.0 {
-            commands.entity(entity).despawn_recursive();
+            decal_pool.release(&mut commands, entity);
         }
     }
 }
@@ -1,23 +1,44 @@
 use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
 use avian3d::prelude::*;
+use crate::physics::{PhysicsWakeEvent, PhysicsWakeEventQueue};
 use super::types::*;
+use crate::utils::QueueReader;
+use crate::camera::{ShakePreset, ShakeQueue};
+use crate::vfx::{VfxKey, VfxRequestQueue};
+
+/// Wake, effect and shake requests sent by explosions.
+#[derive(SystemParam)]
+pub struct ExplosionOutputs<'w> {
+    pub wake_queue: ResMut<'w, PhysicsWakeEventQueue>,
+    pub vfx_queue: ResMut<'w, VfxRequestQueue>,
+    pub shakes: ResMut<'w, ShakeQueue>,
+}
 
 /// System to handle death of destroyable objects.
 pub fn handle_destroyable_death(
     mut commands: Commands,
-    mut death_queue: ResMut<DeathEventQueue>,
+    mut death_queue: QueueReader<DeathEvent>,
     query: Query<(Entity, &GlobalTransform, &DestroyableObject)>,
     spatial_query: SpatialQuery,
     mut damage_queue: ResMut<DamageEventQueue>,
     mut velocity_query: Query<(Entity, &mut LinearVelocity, &GlobalTransform)>,
+    outputs: ExplosionOutputs,
 ) {
-    let death_events = death_queue.0.drain(..).collect::<Vec<_>>();
-    
-    for event in death_events {
+    let ExplosionOutputs { mut wake_queue, mut vfx_queue, mut shakes } = outputs;
+    for event in death_queue.read() {
         if let Ok((entity, transform, destroyable)) = query.get(event.entity) {
             info!("Destroyable object {:?} destroyed!", entity);
 
             if destroyable.explosion_enabled {
+                // Wake sleeping props first so they can react to the blast
+                wake_queue.send(PhysicsWakeEvent {
+                    position: transform.translation(),
+                    radius: destroyable.explosion_settings.radius * 1.5,
+                });
+                vfx_queue.spawn(VfxKey::Explosion, transform.translation());
+                shakes.shake_at(ShakePreset::Explosion, transform.translation());
+
                 trigger_explosion(
                     &mut commands,
                     transform.translation(),
@@ -65,7 +86,7 @@ pub fn trigger_explosion(
     // 2. Apply effects
     for (entity, hit_pos, distance) in affected {
         // Apply Damage
-        damage_queue.0.push(DamageEvent {
+        damage_queue.send(DamageEvent {
             amount: settings.damage,
             damage_type: settings.damage_type,
             source: Some(source_entity),
@@ -0,0 +1,806 @@
+//! Elemental environment interactions
+//!
+//! Fire, water, ice and shock act on the world as well as on health. Fire
+//! damage heats [`Flammable`] objects (or anything with the
+//! [`ElementSettings::flammable_tag`] game tag) and [`GrassVolume`]s; once hot
+//! enough they start [`Burning`], heating what is within reach until the fire
+//! spreads, and hurting characters in the flames with a fire
+//! `DamageOverTime`. Water puts fires out and leaves things [`Wet`]; ice does
+//! too, and freezes water zones and wet targets. Electric damage landing in a
+//! water zone conducts to everyone standing in it.
+//!
+//! Every hit goes through the damage pipeline, so damage events carry
+//! elements on their own. Spells and props that apply an element without a
+//! target send an [`ElementPulse`]:
+//!
+//! ```rust,ignore
+//! commands.spawn((Transform::from_xyz(0.0, 0.0, 8.0), GrassVolume::new(Vec2::new(20.0, 20.0))));
+//! commands.spawn((crate_mesh, GameTag::single("Flammable"), DestroyableObject::default()));
+//!
+//! pulses.send(ElementPulse::new(Element::Water, position, 3.0));
+//! ```
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use crate::player::extra_movements::swim::{water_surface_at, WaterZone};
+use crate::tags::GameTag;
+use crate::utils::{EventQueue, QueueReader};
+use super::damage_over_time::DamageOverTime;
+use super::types::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum Element {
+    Fire,
+    Water,
+    Ice,
+    Shock,
+}
+
+impl Element {
+    /// Element a damage type carries, if any
+    pub fn from_damage_type(damage_type: DamageType) -> Option<Self> {
+        match damage_type {
+            DamageType::Fire => Some(Element::Fire),
+            DamageType::Electric => Some(Element::Shock),
+            _ => None,
+        }
+    }
+}
+
+/// An element applied to an area, with no damage event of its own
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElementPulse {
+    pub element: Element,
+    pub position: Vec3,
+    pub radius: f32,
+    /// Damage for fire and shock pulses
+    pub amount: f32,
+    pub source: Option<Entity>,
+}
+
+impl ElementPulse {
+    pub fn new(element: Element, position: Vec3, radius: f32) -> Self {
+        Self { element, position, radius, amount: 20.0, source: None }
+    }
+
+    pub fn with_amount(mut self, amount: f32) -> Self {
+        self.amount = amount;
+        self
+    }
+}
+
+pub type ElementPulseQueue = EventQueue<ElementPulse>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElementEvent {
+    Ignited { entity: Entity },
+    Extinguished { entity: Entity },
+    BurntOut { entity: Entity },
+    GrassIgnited { volume: Entity, position: Vec3 },
+    Froze { entity: Entity },
+    Thawed { entity: Entity },
+    /// A shock spread through a water zone to `targets` characters
+    Conducted { water: Entity, targets: usize },
+}
+
+pub type ElementEventQueue = EventQueue<ElementEvent>;
+
+/// Object that catches fire once heated enough
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Flammable {
+    pub ignition_heat: f32,
+    pub heat: f32,
+    /// Seconds it burns before burning out
+    pub burn_time: f32,
+    /// Reach of its flames
+    pub spread_radius: f32,
+    /// Fire damage per second it deals itself while burning, so destroyables burn down
+    pub self_damage_per_second: f32,
+    /// Burnt out objects don't catch fire again
+    pub burnt: bool,
+}
+
+impl Default for Flammable {
+    fn default() -> Self {
+        Self {
+            ignition_heat: 1.0,
+            heat: 0.0,
+            burn_time: 10.0,
+            spread_radius: 2.5,
+            self_damage_per_second: 5.0,
+            burnt: false,
+        }
+    }
+}
+
+impl Flammable {
+    /// Adds (or removes) heat; returns whether the object is hot enough to ignite
+    pub fn add_heat(&mut self, heat: f32) -> bool {
+        if self.burnt {
+            return false;
+        }
+        self.heat = (self.heat + heat).max(0.0);
+        self.heat >= self.ignition_heat
+    }
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Burning {
+    pub remaining: f32,
+    pub damage_timer: f32,
+}
+
+/// Doesn't heat up, and conducts shocks
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Wet {
+    pub remaining: f32,
+}
+
+/// Frozen water zone (no conduction, fire can't spread on it) or frozen character
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Frozen {
+    pub remaining: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect)]
+pub enum GrassState {
+    #[default]
+    Fresh,
+    Burning { remaining: f32 },
+    /// Grows back once `regrow` reaches zero
+    Burnt { regrow: f32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect)]
+pub struct GrassCell {
+    pub heat: f32,
+    pub state: GrassState,
+}
+
+/// Grass field centered on the entity, burning cell by cell
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct GrassVolume {
+    /// Width (x) and depth (z)
+    pub size: Vec2,
+    pub cell_size: f32,
+    pub ignition_heat: f32,
+    pub burn_time: f32,
+    /// Seconds before burnt cells grow back; `None` to stay burnt
+    pub regrow_time: Option<f32>,
+    /// Row-major, filled on first use
+    pub cells: Vec<GrassCell>,
+}
+
+impl Default for GrassVolume {
+    fn default() -> Self {
+        Self::new(Vec2::splat(10.0))
+    }
+}
+
+impl GrassVolume {
+    pub fn new(size: Vec2) -> Self {
+        Self {
+            size,
+            cell_size: 1.0,
+            ignition_heat: 0.5,
+            burn_time: 4.0,
+            regrow_time: None,
+            cells: Vec::new(),
+        }
+    }
+
+    pub fn dimensions(&self) -> (usize, usize) {
+        let cell_size = self.cell_size.max(0.01);
+        let columns = (self.size.x / cell_size).ceil().max(1.0) as usize;
+        let rows = (self.size.y / cell_size).ceil().max(1.0) as usize;
+        (columns, rows)
+    }
+
+    pub fn ensure_cells(&mut self) {
+        let (columns, rows) = self.dimensions();
+        if self.cells.len() != columns * rows {
+            self.cells = vec![GrassCell::default(); columns * rows];
+        }
+    }
+
+    /// Cell under a point local to the volume
+    pub fn cell_at(&self, local: Vec3) -> Option<usize> {
+        let (columns, rows) = self.dimensions();
+        let x = local.x + self.size.x * 0.5;
+        let z = local.z + self.size.y * 0.5;
+        if x < 0.0 || z < 0.0 {
+            return None;
+        }
+        let column = (x / self.cell_size) as usize;
+        let row = (z / self.cell_size) as usize;
+        (column < columns && row < rows).then_some(row * columns + column)
+    }
+
+    /// Center of a cell, local to the volume
+    pub fn cell_center(&self, index: usize) -> Vec3 {
+        let (columns, _) = self.dimensions();
+        let column = (index % columns) as f32;
+        let row = (index / columns) as f32;
+        Vec3::new(
+            (column + 0.5) * self.cell_size - self.size.x * 0.5,
+            0.0,
+            (row + 0.5) * self.cell_size - self.size.y * 0.5,
+        )
+    }
+
+    pub fn is_burning(&self, index: usize) -> bool {
+        matches!(self.cells.get(index).map(|cell| cell.state), Some(GrassState::Burning { .. }))
+    }
+
+    /// Whether a local point is close enough above or below the grass to reach it
+    fn reaches(&self, local: Vec3, radius: f32) -> bool {
+        local.y.abs() <= radius + 1.0
+    }
+
+    /// Heats the cells within `radius` of a local point; returns the ones that caught fire
+    pub fn heat_area(&mut self, local: Vec3, radius: f32, heat: f32) -> Vec<usize> {
+        self.ensure_cells();
+        let mut ignited = Vec::new();
+        if !self.reaches(local, radius) {
+            return ignited;
+        }
+        for index in 0..self.cells.len() {
+            let offset = self.cell_center(index) - local;
+            if Vec2::new(offset.x, offset.z).length() > radius.max(self.cell_size * 0.5) {
+                continue;
+            }
+            if self.heat_cell(index, heat) {
+                ignited.push(index);
+            }
+        }
+        ignited
+    }
+
+    /// Puts out and cools the cells within `radius`; returns how many were burning
+    pub fn extinguish_area(&mut self, local: Vec3, radius: f32) -> usize {
+        self.ensure_cells();
+        let mut extinguished = 0;
+        if !self.reaches(local, radius) {
+            return extinguished;
+        }
+        for index in 0..self.cells.len() {
+            let offset = self.cell_center(index) - local;
+            if Vec2::new(offset.x, offset.z).length() > radius.max(self.cell_size * 0.5) {
+                continue;
+            }
+            let cell = &mut self.cells[index];
+            cell.heat = 0.0;
+            if let GrassState::Burning { .. } = cell.state {
+                cell.state = GrassState::Fresh;
+                extinguished += 1;
+            }
+        }
+        extinguished
+    }
+
+    fn heat_cell(&mut self, index: usize, heat: f32) -> bool {
+        let (ignition_heat, burn_time) = (self.ignition_heat, self.burn_time);
+        let cell = &mut self.cells[index];
+        if cell.state != GrassState::Fresh {
+            return false;
+        }
+        cell.heat = (cell.heat + heat).max(0.0);
+        if cell.heat < ignition_heat {
+            return false;
+        }
+        cell.state = GrassState::Burning { remaining: burn_time };
+        true
+    }
+
+    /// Burns and regrows cells and spreads fire to neighbours; returns the cells that caught fire
+    pub fn step(&mut self, dt: f32, spread_heat_per_second: f32, cooling_per_second: f32) -> Vec<usize> {
+        self.ensure_cells();
+        let (columns, rows) = self.dimensions();
+        let burning: Vec<usize> = (0..self.cells.len()).filter(|index| self.is_burning(*index)).collect();
+
+        let mut heated = vec![false; self.cells.len()];
+        for index in &burning {
+            let (column, row) = (index % columns, index / columns);
+            if column > 0 { heated[index - 1] = true; }
+            if column + 1 < columns { heated[index + 1] = true; }
+            if row > 0 { heated[index - columns] = true; }
+            if row + 1 < rows { heated[index + columns] = true; }
+        }
+
+        let regrow_time = self.regrow_time;
+        for cell in self.cells.iter_mut() {
+            match &mut cell.state {
+                GrassState::Burning { remaining } => {
+                    *remaining -= dt;
+                    if *remaining <= 0.0 {
+                        cell.heat = 0.0;
+                        cell.state = GrassState::Burnt { regrow: regrow_time.unwrap_or(f32::INFINITY) };
+                    }
+                }
+                GrassState::Burnt { regrow } => {
+                    *regrow -= dt;
+                    if *regrow <= 0.0 {
+                        cell.state = GrassState::Fresh;
+                    }
+                }
+                GrassState::Fresh => {}
+            }
+        }
+
+        let mut ignited = Vec::new();
+        for index in 0..self.cells.len() {
+            let heat = if heated[index] { spread_heat_per_second * dt } else { -cooling_per_second * dt };
+            if self.heat_cell(index, heat) {
+                ignited.push(index);
+            }
+        }
+        ignited
+    }
+}
+
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct ElementSettings {
+    pub enabled: bool,
+    /// `GameTag` that makes an object `Flammable`
+    pub flammable_tag: String,
+    /// Heat per second gained within a fire's reach
+    pub spread_heat_per_second: f32,
+    /// Heat per second lost away from fire
+    pub cooling_per_second: f32,
+    /// Heat per point of fire damage taken
+    pub heat_per_fire_damage: f32,
+    /// Fire status given to characters in the flames
+    pub burn_damage_per_tick: f32,
+    pub burn_tick: f32,
+    pub burn_duration: f32,
+    pub wet_duration: f32,
+    pub freeze_duration: f32,
+    /// Share of a shock's damage dealt to everyone in the same water
+    pub conduction_multiplier: f32,
+    /// Height above a water surface still counted as standing in the water
+    pub wading_depth: f32,
+}
+
+impl Default for ElementSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            flammable_tag: "Flammable".to_string(),
+            spread_heat_per_second: 0.5,
+            cooling_per_second: 0.25,
+            heat_per_fire_damage: 0.05,
+            burn_damage_per_tick: 4.0,
+            burn_tick: 0.5,
+            burn_duration: 3.0,
+            wet_duration: 10.0,
+            freeze_duration: 8.0,
+            conduction_multiplier: 1.0,
+            wading_depth: 1.0,
+        }
+    }
+}
+
+/// Whether `point` is in the water of a zone, or wading in it
+fn in_water(zone: &WaterZone, zone_transform: &GlobalTransform, point: Vec3, wading_depth: f32) -> bool {
+    water_surface_at([(zone, zone_transform)], point).is_some_and(|surface| point.y < surface + wading_depth)
+}
+
+fn local_point(transform: &GlobalTransform, point: Vec3) -> Vec3 {
+    transform.affine().inverse().transform_point3(point)
+}
+
+type RetaggedNonFlammable = (Changed<GameTag>, Without<Flammable>);
+
+/// System to make objects with the flammable tag `Flammable`
+pub fn tag_flammable_objects(
+    mut commands: Commands,
+    settings: Res<ElementSettings>,
+    query: Query<(Entity, &GameTag), RetaggedNonFlammable>,
+) {
+    for (entity, tag) in query.iter() {
+        if tag.has(&settings.flammable_tag) {
+            commands.entity(entity).insert(Flammable::default());
+        }
+    }
+}
+
+/// Flammable parts heated, burnt and put out by elements
+type FlammableParts<'a> = (Entity, &'a mut Flammable, &'a GlobalTransform, Has<Burning>, Has<Wet>);
+
+/// Character parts elements wet, freeze, thaw and put out
+type ElementalCharacter<'a> = (Entity, &'a GlobalTransform, Has<Wet>, Has<Frozen>, Option<&'a DamageOverTime>);
+
+/// Characters fire can set alight
+type IgnitableCharacter = (With<Health>, Without<Flammable>, Without<Wet>);
+
+/// Damage events and pulses carrying elements, and where targeted hits land
+#[derive(SystemParam)]
+pub struct ElementHits<'w, 's> {
+    pub damage_events: QueueReader<'w, 's, DamageEvent>,
+    pub pulses: QueueReader<'w, 's, ElementPulse>,
+    pub transform_query: Query<'w, 's, &'static GlobalTransform>,
+}
+
+/// Queues written by element reactions
+#[derive(SystemParam)]
+pub struct ElementOutputs<'w> {
+    pub damage_queue: ResMut<'w, DamageEventQueue>,
+    pub events: ResMut<'w, ElementEventQueue>,
+}
+
+/// Flammable objects and grass fire spreads through
+#[derive(SystemParam)]
+pub struct FireFuel<'w, 's> {
+    pub flammable_query: Query<'w, 's, FlammableParts<'static>>,
+    pub grass_query: Query<'w, 's, (Entity, &'static mut GrassVolume, &'static GlobalTransform)>,
+}
+
+/// Timed element states: burning, wet and frozen
+#[derive(SystemParam)]
+pub struct ElementStates<'w, 's> {
+    pub burning_query:
+        Query<'w, 's, (Entity, &'static mut Burning, &'static mut Flammable, &'static GlobalTransform)>,
+    pub wet_query: Query<'w, 's, (Entity, &'static mut Wet)>,
+    pub frozen_query: Query<'w, 's, (Entity, &'static mut Frozen)>,
+}
+
+/// System to apply the elements carried by damage events and pulses
+pub fn apply_elemental_hits(
+    mut commands: Commands,
+    settings: Res<ElementSettings>,
+    hits: ElementHits,
+    outputs: ElementOutputs,
+    fuel: FireFuel,
+    water_query: Query<(Entity, &WaterZone, &GlobalTransform, Has<Frozen>)>,
+    character_query: Query<ElementalCharacter, With<Health>>,
+) {
+    let ElementHits { mut damage_events, mut pulses, transform_query } = hits;
+    let ElementOutputs { mut damage_queue, mut events } = outputs;
+    let FireFuel { mut flammable_query, mut grass_query } = fuel;
+    if !settings.enabled {
+        damage_events.clear();
+        pulses.clear();
+        return;
+    }
+
+    let mut hits: Vec<(ElementPulse, Option<Entity>)> = Vec::new();
+    for event in damage_events.read() {
+        let Some(element) = Element::from_damage_type(event.damage_type) else { continue };
+        // Conducted shocks come from the water itself and don't conduct again
+        if event.source.is_some_and(|source| water_query.contains(source)) {
+            continue;
+        }
+        let Some(position) = event.position.or_else(|| transform_query.get(event.target).ok().map(|t| t.translation())) else {
+            continue;
+        };
+        hits.push((
+            ElementPulse { element, position, radius: 0.0, amount: event.amount, source: event.source },
+            Some(event.target),
+        ));
+    }
+    hits.extend(pulses.read().map(|pulse| (*pulse, None)));
+
+    for (hit, target) in hits {
+        let reaches = |entity: Entity, position: Vec3| target == Some(entity) || position.distance(hit.position) <= hit.radius;
+
+        match hit.element {
+            Element::Fire => {
+                let heat = hit.amount * settings.heat_per_fire_damage;
+                for (entity, mut flammable, transform, burning, wet) in flammable_query.iter_mut() {
+                    if burning || wet || !reaches(entity, transform.translation()) {
+                        continue;
+                    }
+                    if flammable.add_heat(heat) {
+                        commands.entity(entity).insert(Burning { remaining: flammable.burn_time, damage_timer: 0.0 });
+                        events.send(ElementEvent::Ignited { entity });
+                    }
+                }
+                for (volume, mut grass, transform) in grass_query.iter_mut() {
+                    let local = local_point(transform, hit.position);
+                    for index in grass.heat_area(local, hit.radius, heat) {
+                        let position = transform.transform_point(grass.cell_center(index));
+                        events.send(ElementEvent::GrassIgnited { volume, position });
+                    }
+                }
+                // Fire thaws
+                for (entity, zone, transform, frozen) in water_query.iter() {
+                    if frozen && in_water(zone, transform, hit.position, settings.wading_depth + hit.radius) {
+                        commands.entity(entity).remove::<Frozen>();
+                        events.send(ElementEvent::Thawed { entity });
+                    }
+                }
+                for (entity, transform, _, frozen, _) in character_query.iter() {
+                    if frozen && reaches(entity, transform.translation()) {
+                        commands.entity(entity).remove::<Frozen>();
+                        events.send(ElementEvent::Thawed { entity });
+                    }
+                }
+            }
+            Element::Water | Element::Ice => {
+                let freezing = hit.element == Element::Ice;
+                for (entity, mut flammable, transform, burning, _) in flammable_query.iter_mut() {
+                    if !reaches(entity, transform.translation()) {
+                        continue;
+                    }
+                    flammable.heat = 0.0;
+                    if burning {
+                        commands.entity(entity).remove::<Burning>();
+                        events.send(ElementEvent::Extinguished { entity });
+                    }
+                    if !freezing {
+                        commands.entity(entity).insert(Wet { remaining: settings.wet_duration });
+                    }
+                }
+                for (_, mut grass, transform) in grass_query.iter_mut() {
+                    let local = local_point(transform, hit.position);
+                    grass.extinguish_area(local, hit.radius);
+                }
+                for (entity, transform, wet, frozen, dot) in character_query.iter() {
+                    if !reaches(entity, transform.translation()) {
+                        continue;
+                    }
+                    if dot.is_some_and(|dot| dot.damage_type == DamageType::Fire) {
+                        commands.entity(entity).remove::<DamageOverTime>();
+                        events.send(ElementEvent::Extinguished { entity });
+                    }
+                    if !freezing {
+                        commands.entity(entity).insert(Wet { remaining: settings.wet_duration });
+                    } else if wet && !frozen {
+                        // Wet targets freeze solid
+                        commands.entity(entity).remove::<Wet>().insert(Frozen { remaining: settings.freeze_duration });
+                        events.send(ElementEvent::Froze { entity });
+                    }
+                }
+                if freezing {
+                    for (entity, zone, transform, frozen) in water_query.iter() {
+                        if !frozen && in_water(zone, transform, hit.position, settings.wading_depth + hit.radius) {
+                            commands.entity(entity).insert(Frozen { remaining: settings.freeze_duration });
+                            events.send(ElementEvent::Froze { entity });
+                        }
+                    }
+                }
+            }
+            Element::Shock => {
+                let amount = hit.amount * settings.conduction_multiplier;
+                let mut shocked: Vec<Entity> = target.into_iter().collect();
+                for (water, zone, transform, frozen) in water_query.iter() {
+                    if frozen || !in_water(zone, transform, hit.position, settings.wading_depth + hit.radius) {
+                        continue;
+                    }
+                    let mut targets = 0;
+                    for (entity, character_transform, _, _, _) in character_query.iter() {
+                        let position = character_transform.translation();
+                        if shocked.contains(&entity) || !in_water(zone, transform, position, settings.wading_depth) {
+                            continue;
+                        }
+                        shocked.push(entity);
+                        targets += 1;
+                        damage_queue.send(DamageEvent {
+                            amount,
+                            damage_type: DamageType::Electric,
+                            source: Some(water),
+                            target: entity,
+                            position: Some(position),
+                            direction: None,
+                            ignore_shield: false,
+                        });
+                    }
+                    if targets > 0 {
+                        events.send(ElementEvent::Conducted { water, targets });
+                    }
+                }
+                // Pulses also hit whoever is within their radius
+                if target.is_none() {
+                    for (entity, transform, _, _, _) in character_query.iter() {
+                        let position = transform.translation();
+                        if shocked.contains(&entity) || position.distance(hit.position) > hit.radius {
+                            continue;
+                        }
+                        shocked.push(entity);
+                        damage_queue.send(DamageEvent {
+                            amount: hit.amount,
+                            damage_type: DamageType::Electric,
+                            source: hit.source,
+                            target: entity,
+                            position: Some(position),
+                            direction: Some((position - hit.position).normalize_or_zero()),
+                            ignore_shield: false,
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// System to spread fire between flammables and grass, and set characters in the flames alight
+pub fn spread_fire(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<ElementSettings>,
+    mut events: ResMut<ElementEventQueue>,
+    fuel: FireFuel,
+    water_query: Query<(&WaterZone, &GlobalTransform), Without<Frozen>>,
+    mut character_query: Query<(Entity, &GlobalTransform, Option<&mut DamageOverTime>), IgnitableCharacter>,
+) {
+    let FireFuel { mut flammable_query, mut grass_query } = fuel;
+    if !settings.enabled {
+        return;
+    }
+    let dt = time.delta_secs();
+
+    // Everything on fire, with the reach of its flames
+    let mut fires: Vec<(Vec3, f32)> = flammable_query
+        .iter()
+        .filter(|(_, _, _, burning, _)| *burning)
+        .map(|(_, flammable, transform, _, _)| (transform.translation(), flammable.spread_radius))
+        .collect();
+    let entity_fires = fires.len();
+    for (_, grass, transform) in grass_query.iter() {
+        fires.extend(
+            (0..grass.cells.len())
+                .filter(|index| grass.is_burning(*index))
+                .map(|index| (transform.transform_point(grass.cell_center(index)), grass.cell_size)),
+        );
+    }
+    if fires.is_empty() {
+        for (_, mut flammable, _, _, _) in flammable_query.iter_mut() {
+            flammable.add_heat(-settings.cooling_per_second * dt);
+        }
+    }
+
+    for (entity, mut flammable, transform, burning, wet) in flammable_query.iter_mut() {
+        if burning || fires.is_empty() {
+            continue;
+        }
+        let position = transform.translation();
+        let near_fire = fires.iter().any(|(fire, radius)| fire.distance(position) <= *radius);
+        let heat = if near_fire && !wet { settings.spread_heat_per_second } else { -settings.cooling_per_second };
+        if flammable.add_heat(heat * dt) {
+            commands.entity(entity).insert(Burning { remaining: flammable.burn_time, damage_timer: 0.0 });
+            events.send(ElementEvent::Ignited { entity });
+        }
+    }
+
+    for (volume, mut grass, transform) in grass_query.iter_mut() {
+        let mut ignited = grass.step(dt, settings.spread_heat_per_second, settings.cooling_per_second);
+        // Burning objects set the grass around them alight
+        for (fire, radius) in &fires[..entity_fires] {
+            let local = local_point(transform, *fire);
+            ignited.extend(grass.heat_area(local, *radius, settings.spread_heat_per_second * dt));
+        }
+        for index in ignited {
+            let position = transform.transform_point(grass.cell_center(index));
+            events.send(ElementEvent::GrassIgnited { volume, position });
+        }
+    }
+
+    for (entity, transform, dot) in character_query.iter_mut() {
+        let position = transform.translation();
+        let in_flames = fires.iter().any(|(fire, radius)| fire.distance(position) <= *radius);
+        if !in_flames || water_query.iter().any(|(zone, zone_transform)| in_water(zone, zone_transform, position, 0.0)) {
+            continue;
+        }
+        match dot {
+            Some(mut dot) if dot.damage_type == DamageType::Fire => {
+                dot.total_duration = dot.time_elapsed + settings.burn_duration;
+            }
+            _ => {
+                commands.entity(entity).insert(DamageOverTime {
+                    damage_per_tick: settings.burn_damage_per_tick,
+                    tick_frequency: settings.burn_tick,
+                    total_duration: settings.burn_duration,
+                    damage_type: DamageType::Fire,
+                    ..default()
+                });
+            }
+        }
+    }
+}
+
+/// System to burn down flammables, douse fires in water and dry and thaw things over time
+pub fn update_burning(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<ElementSettings>,
+    outputs: ElementOutputs,
+    states: ElementStates,
+    character_query: Query<(Entity, &GlobalTransform, &DamageOverTime), With<Health>>,
+    water_query: Query<(&WaterZone, &GlobalTransform), Without<Frozen>>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let ElementOutputs { mut damage_queue, mut events } = outputs;
+    let ElementStates { mut burning_query, mut wet_query, mut frozen_query } = states;
+    let dt = time.delta_secs();
+    let in_any_water = |position: Vec3| {
+        water_query.iter().any(|(zone, transform)| in_water(zone, transform, position, 0.0))
+    };
+
+    for (entity, mut burning, mut flammable, transform) in burning_query.iter_mut() {
+        if in_any_water(transform.translation()) {
+            flammable.heat = 0.0;
+            commands.entity(entity).remove::<Burning>();
+            events.send(ElementEvent::Extinguished { entity });
+            continue;
+        }
+
+        burning.remaining -= dt;
+        burning.damage_timer += dt;
+        if burning.damage_timer >= 1.0 && flammable.self_damage_per_second > 0.0 {
+            burning.damage_timer -= 1.0;
+            damage_queue.send(DamageEvent {
+                amount: flammable.self_damage_per_second,
+                damage_type: DamageType::Fire,
+                source: Some(entity),
+                target: entity,
+                position: None,
+                direction: None,
+                ignore_shield: true,
+            });
+        }
+        if burning.remaining <= 0.0 {
+            flammable.burnt = true;
+            flammable.heat = 0.0;
+            commands.entity(entity).remove::<Burning>();
+            events.send(ElementEvent::BurntOut { entity });
+        }
+    }
+
+    // Diving in puts a burning character out
+    for (entity, transform, dot) in character_query.iter() {
+        if dot.damage_type == DamageType::Fire && in_any_water(transform.translation()) {
+            commands.entity(entity).remove::<DamageOverTime>();
+            events.send(ElementEvent::Extinguished { entity });
+        }
+    }
+
+    for (entity, mut wet) in wet_query.iter_mut() {
+        wet.remaining -= dt;
+        if wet.remaining <= 0.0 {
+            commands.entity(entity).remove::<Wet>();
+        }
+    }
+
+    for (entity, mut frozen) in frozen_query.iter_mut() {
+        frozen.remaining -= dt;
+        if frozen.remaining <= 0.0 {
+            commands.entity(entity).remove::<Frozen>();
+            events.send(ElementEvent::Thawed { entity });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::DamageType;
+
+    #[test]
+    fn test_fire_spreads_through_grass() {
+        let mut grass = GrassVolume::new(Vec2::new(3.0, 1.0));
+        assert_eq!(grass.cell_at(Vec3::new(-1.4, 0.0, 0.0)), Some(0));
+        assert_eq!(grass.cell_at(Vec3::new(2.0, 0.0, 0.0)), None);
+
+        assert_eq!(grass.heat_area(grass.cell_center(0), 0.0, 1.0), vec![0]);
+        // Heat reaches the next cell only
+        assert_eq!(grass.step(1.0, 0.5, 0.25), vec![1]);
+        assert!(!grass.is_burning(2));
+        assert_eq!(grass.extinguish_area(grass.cell_center(1), 0.0), 1);
+        assert!(grass.is_burning(0) && !grass.is_burning(1));
+
+        let mut crate_box = Flammable::default();
+        assert!(!crate_box.add_heat(0.6));
+        assert!(crate_box.add_heat(0.6));
+        crate_box.burnt = true;
+        assert!(!crate_box.add_heat(5.0));
+        assert_eq!(Element::from_damage_type(DamageType::Electric), Some(Element::Shock));
+    }
+}
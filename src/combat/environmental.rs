@@ -0,0 +1,422 @@
+//! Environmental kills
+//!
+//! Deaths caused by the world rather than by a weapon: kill volumes, falling
+//! out of the world, deep water, fire and long falls. Characters get an
+//! [`EnvironmentalKillTracker`] remembering who last sent them flying (a hit
+//! from another character, a grab throw or a power throw) and the last hazard
+//! that hurt them. When such a character dies to a hazard, an
+//! [`EnvironmentalKillEvent`] names the [`EnvironmentalCause`] and credits the
+//! instigator, so achievements and quests can reward throwing a guard off the
+//! dam like any other kill.
+//!
+//! Ragdolled characters don't run their controller, so the world acts on them
+//! here: a ragdoll landing after a long fall takes fall damage, one lying in
+//! deep water drowns, and its root bone entering a kill volume counts as the
+//! character entering it. Throwing a character with a [`Ragdoll`] goes limp with
+//! the throw's impulse.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use avian3d::prelude::LinearVelocity;
+use crate::character::{CharacterController, Player};
+use crate::grab::GrabEvent;
+use crate::player::extra_movements::swim::{water_surface_at, WaterZone};
+use crate::player::ragdoll::{ActivateRagdollEvent, ActivateRagdollQueue, Ragdoll, RagdollState};
+use crate::utils::{EventQueue, QueueReader};
+#[cfg(feature = "world_bounds")]
+use crate::world_bounds::{KillVolume, KillVolumeEffect, WorldBounds};
+use super::result_queue::DamageResultEvent;
+use super::underwater::Submerged;
+use super::types::*;
+
+/// What killed a character, when it wasn't a weapon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum EnvironmentalCause {
+    KillVolume,
+    /// Below the world's kill-Z, or off a ledge high enough to die from the landing
+    Fall,
+    DeepWater,
+    Fire,
+}
+
+impl EnvironmentalCause {
+    /// Cause behind damage of `damage_type`, if it's a hazard's
+    pub fn from_damage(damage_type: DamageType, in_deep_water: bool) -> Option<Self> {
+        match damage_type {
+            DamageType::Fire => Some(Self::Fire),
+            DamageType::Fall => Some(Self::Fall),
+            DamageType::Environmental if in_deep_water => Some(Self::DeepWater),
+            DamageType::Environmental => Some(Self::KillVolume),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::KillVolume => "Killed by the environment",
+            Self::Fall => "Fell to their death",
+            Self::DeepWater => "Drowned",
+            Self::Fire => "Burned to death",
+        }
+    }
+}
+
+/// Tuning for environmental kills
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct EnvironmentalKillSettings {
+    pub enabled: bool,
+    /// Seconds a hit or throw keeps crediting the instigator
+    pub credit_window: f32,
+    /// Seconds a hazard's damage still counts as the cause of death
+    pub hazard_window: f32,
+    /// Ragdolls falling less than this (meters) land unhurt
+    pub ragdoll_safe_fall_height: f32,
+    pub ragdoll_fall_damage_per_meter: f32,
+    /// Vertical speed under which a falling ragdoll counts as landed
+    pub ragdoll_landing_speed: f32,
+    /// Depth below the surface at which a ragdoll starts drowning
+    pub deep_water_depth: f32,
+    pub drowning_damage_per_second: f32,
+    /// Make thrown characters with a `Ragdoll` go limp
+    pub ragdoll_thrown_characters: bool,
+}
+
+impl Default for EnvironmentalKillSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            credit_window: 6.0,
+            hazard_window: 2.0,
+            ragdoll_safe_fall_height: 4.0,
+            ragdoll_fall_damage_per_meter: 12.0,
+            ragdoll_landing_speed: 0.5,
+            deep_water_depth: 1.2,
+            drowning_damage_per_second: 25.0,
+            ragdoll_thrown_characters: true,
+        }
+    }
+}
+
+/// Who last sent a character flying, and the last hazard that hurt it
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct EnvironmentalKillTracker {
+    pub instigator: Option<Entity>,
+    pub credit_remaining: f32,
+    pub cause: Option<EnvironmentalCause>,
+    pub cause_remaining: f32,
+    /// Highest point of the current ragdoll fall
+    pub fall_peak: Option<f32>,
+}
+
+impl EnvironmentalKillTracker {
+    pub fn credit(&mut self, instigator: Entity, window: f32) {
+        self.instigator = Some(instigator);
+        self.credit_remaining = window;
+    }
+
+    pub fn hazard(&mut self, cause: EnvironmentalCause, window: f32) {
+        self.cause = Some(cause);
+        self.cause_remaining = window;
+    }
+
+    pub fn tick(&mut self, delta: f32) {
+        self.credit_remaining = (self.credit_remaining - delta).max(0.0);
+        self.cause_remaining = (self.cause_remaining - delta).max(0.0);
+        if self.credit_remaining <= 0.0 {
+            self.instigator = None;
+        }
+        if self.cause_remaining <= 0.0 {
+            self.cause = None;
+        }
+    }
+
+    /// Instigator still within its credit window
+    pub fn credited(&self) -> Option<Entity> {
+        self.instigator.filter(|_| self.credit_remaining > 0.0)
+    }
+
+    /// Hazard still within its window
+    pub fn recent_cause(&self) -> Option<EnvironmentalCause> {
+        self.cause.filter(|_| self.cause_remaining > 0.0)
+    }
+}
+
+/// A character died to a hazard
+#[derive(Debug, Clone, Copy)]
+pub struct EnvironmentalKillEvent {
+    pub victim: Entity,
+    pub cause: EnvironmentalCause,
+    /// Character who threw, pushed or hit the victim into the hazard
+    pub instigator: Option<Entity>,
+    pub position: Vec3,
+}
+
+pub type EnvironmentalKillEventQueue = EventQueue<EnvironmentalKillEvent>;
+
+/// Depth of `point` below the surface of the water zone containing it
+fn water_depth<'a>(zones: impl IntoIterator<Item = (&'a WaterZone, &'a GlobalTransform)>, point: Vec3) -> Option<f32> {
+    water_surface_at(zones, point).map(|surface| surface - point.y).filter(|depth| *depth > 0.0)
+}
+
+type UntrackedVictim = (With<Health>, Or<(With<CharacterController>, With<Ragdoll>)>, Without<EnvironmentalKillTracker>);
+
+/// System to give characters an `EnvironmentalKillTracker`
+pub fn add_environmental_kill_trackers(
+    mut commands: Commands,
+    query: Query<Entity, UntrackedVictim>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(EnvironmentalKillTracker::default());
+    }
+}
+
+/// System to credit throwers, and send thrown characters limp
+pub fn credit_thrown_characters(
+    settings: Res<EnvironmentalKillSettings>,
+    mut grab_events: QueueReader<GrabEvent>,
+    mut ragdoll_queue: ResMut<ActivateRagdollQueue>,
+    mut tracker_query: Query<(&mut EnvironmentalKillTracker, Option<&Ragdoll>)>,
+) {
+    for event in grab_events.read() {
+        let GrabEvent::Throw(grabber, thrown, direction, force) = *event else { continue };
+        if !settings.enabled || grabber == thrown {
+            continue;
+        }
+        let Ok((mut tracker, ragdoll)) = tracker_query.get_mut(thrown) else { continue };
+        tracker.credit(grabber, settings.credit_window);
+
+        if settings.ragdoll_thrown_characters
+            && ragdoll.is_some_and(|ragdoll| ragdoll.active && ragdoll.current_state != RagdollState::Ragdolled)
+        {
+            ragdoll_queue.send(ActivateRagdollEvent {
+                entity: thrown,
+                force_direction: Some(direction),
+                force_magnitude: force,
+            });
+        }
+    }
+}
+
+/// Ragdolled character parts hurt by hazards
+type RagdollVictim<'a> = (
+    Entity,
+    &'a Ragdoll,
+    &'a mut EnvironmentalKillTracker,
+    &'a mut Health,
+    &'a GlobalTransform,
+    Has<Player>,
+);
+
+/// Damage and death queues written by hazards
+#[derive(SystemParam)]
+pub struct HazardOutputs<'w> {
+    pub damage_queue: ResMut<'w, DamageEventQueue>,
+    pub death_queue: ResMut<'w, DeathEventQueue>,
+}
+
+/// Damage results and deaths environmental kills are told from
+#[derive(SystemParam)]
+pub struct EnvironmentalKillReports<'w, 's> {
+    pub results: QueueReader<'w, 's, DamageResultEvent>,
+    pub deaths: QueueReader<'w, 's, DeathEvent>,
+}
+
+/// System to apply falls, drowning and kill volumes to ragdolled characters
+pub fn apply_ragdoll_hazards(
+    time: Res<Time>,
+    settings: Res<EnvironmentalKillSettings>,
+    outputs: HazardOutputs,
+    mut ragdoll_query: Query<RagdollVictim>,
+    bone_query: Query<(&GlobalTransform, Option<&LinearVelocity>)>,
+    water_query: Query<(&WaterZone, &GlobalTransform)>,
+    #[cfg(feature = "world_bounds")] volume_query: Query<(&GlobalTransform, &KillVolume)>,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let HazardOutputs { mut damage_queue, mut death_queue } = outputs;
+    let delta = time.delta_secs();
+
+    for (entity, ragdoll, mut tracker, mut health, transform, is_player) in ragdoll_query.iter_mut() {
+        if ragdoll.current_state != RagdollState::Ragdolled || health.is_dead {
+            tracker.fall_peak = None;
+            continue;
+        }
+        let (position, vertical_speed) = ragdoll
+            .root_bone
+            .and_then(|bone| bone_query.get(bone).ok())
+            .map(|(bone, velocity)| (bone.translation(), velocity.map_or(0.0, |velocity| velocity.y)))
+            .unwrap_or((transform.translation(), 0.0));
+        let source = tracker.credited();
+
+        // Falls: remember the peak, hurt on landing
+        let peak = tracker.fall_peak.map_or(position.y, |peak| peak.max(position.y));
+        let fallen = peak - position.y;
+        let landed = ragdoll.on_ground || (fallen > 0.1 && vertical_speed.abs() < settings.ragdoll_landing_speed);
+        if landed {
+            tracker.fall_peak = None;
+            if fallen > settings.ragdoll_safe_fall_height {
+                tracker.hazard(EnvironmentalCause::Fall, settings.hazard_window);
+                damage_queue.send(DamageEvent {
+                    amount: (fallen - settings.ragdoll_safe_fall_height) * settings.ragdoll_fall_damage_per_meter,
+                    damage_type: DamageType::Fall,
+                    source,
+                    target: entity,
+                    position: Some(position),
+                    direction: Some(Vec3::Y),
+                    ignore_shield: true,
+                });
+            }
+        } else {
+            tracker.fall_peak = Some(peak);
+        }
+
+        // Deep water: limp bodies don't swim
+        if water_depth(water_query.iter(), position).is_some_and(|depth| depth >= settings.deep_water_depth) {
+            tracker.hazard(EnvironmentalCause::DeepWater, settings.hazard_window);
+            damage_queue.send(DamageEvent {
+                amount: settings.drowning_damage_per_second * delta,
+                damage_type: DamageType::Environmental,
+                source,
+                target: entity,
+                position: Some(position),
+                direction: None,
+                ignore_shield: true,
+            });
+        }
+
+        // Kill volumes: the root bone counts as the character. When the
+        // character's own origin is inside, the world bounds systems handle it
+        #[cfg(feature = "world_bounds")]
+        for (volume_transform, volume) in volume_query.iter() {
+            let affected = if is_player { volume.affect_players } else { volume.affect_npcs };
+            if !volume.active
+                || !affected
+                || !volume.contains(volume_transform, position)
+                || volume.contains(volume_transform, transform.translation())
+            {
+                continue;
+            }
+            let cause = EnvironmentalCause::from_damage(volume.damage_type, false).unwrap_or(EnvironmentalCause::KillVolume);
+            tracker.hazard(cause, settings.hazard_window);
+            match volume.effect {
+                KillVolumeEffect::Kill => {
+                    health.current = 0.0;
+                    health.is_dead = true;
+                    death_queue.send(DeathEvent { entity });
+                }
+                KillVolumeEffect::Damage { per_second } => {
+                    damage_queue.send(DamageEvent {
+                        amount: per_second * delta,
+                        damage_type: volume.damage_type,
+                        source,
+                        target: entity,
+                        position: Some(position),
+                        direction: None,
+                        ignore_shield: true,
+                    });
+                }
+                // Nothing to recover to while limp
+                KillVolumeEffect::Recover { .. } => {}
+            }
+            break;
+        }
+    }
+}
+
+/// System to credit hits, record hazards and report environmental deaths
+pub fn report_environmental_kills(
+    time: Res<Time>,
+    settings: Res<EnvironmentalKillSettings>,
+    reports: EnvironmentalKillReports,
+    mut kill_queue: ResMut<EnvironmentalKillEventQueue>,
+    mut tracker_query: Query<(&mut EnvironmentalKillTracker, &GlobalTransform, Has<Submerged>)>,
+    #[cfg(feature = "world_bounds")] bounds: Option<Res<WorldBounds>>,
+    #[cfg(feature = "world_bounds")] volume_query: Query<(&GlobalTransform, &KillVolume)>,
+) {
+    let EnvironmentalKillReports { mut results, mut deaths } = reports;
+    if !settings.enabled {
+        results.clear();
+        deaths.clear();
+        return;
+    }
+    let delta = time.delta_secs();
+    for (mut tracker, _, _) in tracker_query.iter_mut() {
+        tracker.tick(delta);
+    }
+
+    for result in results.read() {
+        if result.damage_type == DamageType::Heal || result.final_amount <= 0.0 {
+            continue;
+        }
+        // Only characters get credit; burning crates and traps don't
+        if let Some(source) = result.source.filter(|source| *source != result.target) {
+            if tracker_query.contains(source) {
+                if let Ok((mut tracker, _, _)) = tracker_query.get_mut(result.target) {
+                    tracker.credit(source, settings.credit_window);
+                }
+            }
+        }
+        if let Ok((mut tracker, _, submerged)) = tracker_query.get_mut(result.target) {
+            let in_deep_water = submerged || tracker.recent_cause() == Some(EnvironmentalCause::DeepWater);
+            if let Some(cause) = EnvironmentalCause::from_damage(result.damage_type, in_deep_water) {
+                tracker.hazard(cause, settings.hazard_window);
+            }
+        }
+    }
+
+    for death in deaths.read() {
+        let Ok((tracker, transform, _)) = tracker_query.get(death.entity) else { continue };
+        let position = transform.translation();
+
+        // Kills that skip the damage pipeline (kill-Z, instant kill volumes)
+        // leave no hazard behind, so look at where the body is
+        let mut cause = tracker.recent_cause();
+        #[cfg(feature = "world_bounds")]
+        if cause.is_none() {
+            if bounds.as_ref().is_some_and(|bounds| bounds.enabled && bounds.kill_z.is_some_and(|kill_z| position.y < kill_z)) {
+                cause = Some(EnvironmentalCause::Fall);
+            } else if let Some((_, volume)) = volume_query
+                .iter()
+                .find(|(volume_transform, volume)| volume.active && volume.contains(volume_transform, position))
+            {
+                cause = EnvironmentalCause::from_damage(volume.damage_type, false).or(Some(EnvironmentalCause::KillVolume));
+            }
+        }
+
+        let Some(cause) = cause else { continue };
+        kill_queue.send(EnvironmentalKillEvent {
+            victim: death.entity,
+            cause,
+            instigator: tracker.credited(),
+            position,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_environmental_kill_tracker() {
+        use crate::combat::{DamageType, EnvironmentalCause, EnvironmentalKillTracker};
+
+        assert_eq!(EnvironmentalCause::from_damage(DamageType::Fire, false), Some(EnvironmentalCause::Fire));
+        assert_eq!(EnvironmentalCause::from_damage(DamageType::Environmental, true), Some(EnvironmentalCause::DeepWater));
+        assert_eq!(EnvironmentalCause::from_damage(DamageType::Ranged, false), None);
+
+        let thrower = Entity::from_raw_u32(7).unwrap();
+        let mut tracker = EnvironmentalKillTracker::default();
+        tracker.credit(thrower, 3.0);
+        tracker.hazard(EnvironmentalCause::Fall, 1.0);
+        tracker.tick(1.5);
+        assert_eq!(tracker.credited(), Some(thrower));
+        assert_eq!(tracker.recent_cause(), None);
+        tracker.tick(2.0);
+        assert_eq!(tracker.credited(), None);
+    }
+}
@@ -1,8 +1,10 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
 
-use crate::combat::result_queue::DamageResultQueue;
-use crate::combat::types::DamageType;
+use crate::combat::result_queue::DamageResultEvent;
+use crate::utils::QueueReader;
+use crate::combat::types::{DamageType, Health};
+use crate::vfx::{VfxKey, VfxRequestQueue};
 
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
@@ -20,6 +22,7 @@ impl Default for SurfaceType {
 
 #[derive(Debug, Clone, Reflect)]
 pub struct SurfaceFxDefinition {
+    /// Hit effect played as `VfxKey::Custom`; empty for tinted sparks
     pub particles: String,
     pub sound: String,
     pub decal: String,
@@ -72,49 +75,39 @@ impl Default for SurfaceFxDatabase {
     }
 }
 
-#[derive(Component, Debug, Reflect)]
-#[reflect(Component)]
-pub struct SurfaceFxMarker {
-    pub lifetime: f32,
-}
-
 #[derive(Resource, Debug, Reflect)]
 #[reflect(Resource)]
 pub struct SurfaceFxSettings {
     pub enable_melee_fx: bool,
-    pub marker_lifetime: f32,
-    pub marker_radius: f32,
 }
 
 impl Default for SurfaceFxSettings {
     fn default() -> Self {
         Self {
             enable_melee_fx: true,
-            marker_lifetime: 1.2,
-            marker_radius: 0.08,
         }
     }
 }
 
+/// Plays the hit effect of the target's surface for melee hits: blood on
+/// characters, the surface's own effect or tinted sparks elsewhere
 pub fn spawn_surface_fx_from_damage(
-    mut commands: Commands,
-    damage_queue: Res<DamageResultQueue>,
+    mut damage_queue: QueueReader<DamageResultEvent>,
     fx_db: Res<SurfaceFxDatabase>,
     settings: Res<SurfaceFxSettings>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    surface_query: Query<(&GlobalTransform, Option<&SurfaceType>)>,
+    mut vfx_queue: ResMut<VfxRequestQueue>,
+    surface_query: Query<(&GlobalTransform, Option<&SurfaceType>, Has<Health>)>,
 ) {
     if !settings.enable_melee_fx {
         return;
     }
 
-    for event in damage_queue.0.iter() {
+    for event in damage_queue.read() {
         if event.damage_type != DamageType::Melee || event.final_amount <= 0.0 {
             continue;
         }
 
-        let Ok((transform, surface_opt)) = surface_query.get(event.target) else {
+        let Ok((transform, surface_opt, has_health)) = surface_query.get(event.target) else {
             continue;
         };
 
@@ -128,37 +121,13 @@ pub fn spawn_surface_fx_from_damage(
             .cloned()
             .unwrap_or_default();
 
-        let mesh = meshes.add(Mesh::from(Sphere::new(settings.marker_radius)));
-        let material = materials.add(StandardMaterial {
-            base_color: fx.color,
-            unlit: true,
-            ..default()
-        });
-
-        commands.spawn((
-            Mesh3d(mesh),
-            MeshMaterial3d(material),
-            Transform::from_translation(transform.translation()),
-            GlobalTransform::default(),
-            Visibility::default(),
-            SurfaceFxMarker {
-                lifetime: settings.marker_lifetime,
-            },
-            Name::new("SurfaceFxMarker"),
-        ));
-    }
-}
-
-pub fn update_surface_fx_markers(
-    time: Res<Time>,
-    mut commands: Commands,
-    mut query: Query<(Entity, &mut SurfaceFxMarker)>,
-) {
-    let dt = time.delta_secs();
-    for (entity, mut marker) in query.iter_mut() {
-        marker.lifetime -= dt;
-        if marker.lifetime <= 0.0 {
-            commands.entity(entity).despawn_recursive();
+        let position = transform.translation();
+        if surface_name == "Flesh" || (surface_opt.is_none() && has_health) {
+            vfx_queue.spawn(VfxKey::BloodHit, position);
+        } else if !fx.particles.is_empty() {
+            vfx_queue.spawn(VfxKey::Custom(fx.particles), position);
+        } else {
+            vfx_queue.spawn_colored(VfxKey::Spark, position, Vec3::Y, fx.color);
         }
     }
 }
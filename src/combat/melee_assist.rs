@@ -0,0 +1,168 @@
+//! Melee assist
+//!
+//! When an attack starts without a camera lock, an attacker with
+//! [`MeleeAssist`] picks the best living target inside an arc in front of it
+//! (ranked like lock-on candidates, [`lock_on_score`]), snaps its facing to it
+//! so the swing connects, and lunges toward it for a moment. Pushing the
+//! movement stick centers the arc on the input direction instead, and with no
+//! target there the attack is simply turned that way.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use super::types::*;
+use crate::camera::{lock_on_score, CameraTargetState};
+use crate::character::{CharacterMovementState, Player};
+use crate::input::InputState;
+
+/// Soft-lock and facing correction of melee attacks
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct MeleeAssist {
+    pub enabled: bool,
+    /// Half angle of the search arc, in degrees
+    pub max_angle: f32,
+    pub max_distance: f32,
+    /// The lunge stops this close to the target
+    pub stop_distance: f32,
+    pub lunge_speed: f32,
+    pub lunge_duration: f32,
+    /// Movement input above this length centers the arc on the input direction
+    pub input_override_threshold: f32,
+
+    /// Current soft-locked target
+    pub target: Option<Entity>,
+    pub lunge_timer: f32,
+}
+
+impl Default for MeleeAssist {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_angle: 60.0,
+            max_distance: 4.0,
+            stop_distance: 1.2,
+            lunge_speed: 6.0,
+            lunge_duration: 0.15,
+            input_override_threshold: 0.3,
+            target: None,
+            lunge_timer: 0.0,
+        }
+    }
+}
+
+/// Attacker parts melee assist aims and lunges
+type AssistedAttacker<'a> = (
+    Entity,
+    &'a mut MeleeAssist,
+    &'a MeleeCombat,
+    Option<&'a MeleeAttackState>,
+    &'a mut Transform,
+    Option<&'a CharacterMovementState>,
+    Has<Player>,
+);
+
+/// System to pick a target and correct the facing when a melee attack starts.
+/// Runs before the attack systems so their hit checks use the corrected facing.
+pub fn start_melee_assist(
+    input: Res<InputState>,
+    camera_query: Query<&CameraTargetState>,
+    mut attackers: Query<AssistedAttacker>,
+    targets: Query<(Entity, &GlobalTransform, &Health)>,
+) {
+    if !input.attack_pressed {
+        return;
+    }
+    let camera_locked = camera_query.iter().any(|state| state.locked_target.is_some());
+
+    for (entity, mut assist, combat, attack_state, mut transform, movement, is_player) in attackers.iter_mut() {
+        let starts_attack = !combat.is_attacking && (attack_state.is_some() || combat.attack_timer <= 0.0);
+        if !assist.enabled || !starts_attack || (is_player && camera_locked) {
+            continue;
+        }
+
+        let origin = transform.translation;
+        let input_direction = movement
+            .map(|movement| Vec3::new(movement.raw_move_dir.x, 0.0, movement.raw_move_dir.z))
+            .filter(|direction| direction.length() > assist.input_override_threshold)
+            .map(|direction| direction.normalize());
+        let forward = transform.forward();
+        let reference = input_direction.unwrap_or_else(|| Vec3::new(forward.x, 0.0, forward.z).normalize_or_zero());
+
+        let mut best_target = None;
+        let mut best_score = f32::MAX;
+        for (target, target_transform, health) in targets.iter() {
+            if target == entity || health.is_dead {
+                continue;
+            }
+            let offset = target_transform.translation() - origin;
+            let flat = Vec3::new(offset.x, 0.0, offset.z);
+            let distance = flat.length();
+            if distance > assist.max_distance || distance < 0.001 {
+                continue;
+            }
+            let angle = reference.angle_between(flat / distance).to_degrees();
+            if angle > assist.max_angle {
+                continue;
+            }
+            let score = lock_on_score(distance, angle);
+            if score < best_score {
+                best_score = score;
+                best_target = Some((target, flat / distance));
+            }
+        }
+
+        assist.target = best_target.map(|(target, _)| target);
+        assist.lunge_timer = if best_target.is_some() { assist.lunge_duration } else { 0.0 };
+
+        let facing = best_target.map(|(_, direction)| direction).or(input_direction);
+        if let Some(direction) = facing {
+            transform.look_to(direction, Vec3::Y);
+        }
+    }
+}
+
+/// System to lunge toward the soft-locked target and keep facing it
+pub fn update_melee_assist(
+    time: Res<Time>,
+    mut attackers: Query<(&mut MeleeAssist, &mut Transform, Option<&mut LinearVelocity>)>,
+    targets: Query<(&GlobalTransform, &Health)>,
+) {
+    let dt = time.delta_secs();
+
+    for (mut assist, mut transform, velocity) in attackers.iter_mut() {
+        if assist.lunge_timer <= 0.0 {
+            continue;
+        }
+        assist.lunge_timer -= dt;
+
+        let Some((target_transform, health)) = assist.target.and_then(|target| targets.get(target).ok()) else {
+            assist.target = None;
+            assist.lunge_timer = 0.0;
+            continue;
+        };
+        if health.is_dead {
+            assist.target = None;
+            assist.lunge_timer = 0.0;
+            continue;
+        }
+
+        let offset = target_transform.translation() - transform.translation;
+        let flat = Vec3::new(offset.x, 0.0, offset.z);
+        let distance = flat.length();
+        if distance < 0.001 {
+            continue;
+        }
+        let direction = flat / distance;
+        transform.look_to(direction, Vec3::Y);
+
+        let Some(mut velocity) = velocity else { continue };
+        if distance > assist.stop_distance {
+            let speed = assist.lunge_speed.min((distance - assist.stop_distance) / dt.max(0.001));
+            velocity.x = direction.x * speed;
+            velocity.z = direction.z * speed;
+        } else {
+            velocity.x = 0.0;
+            velocity.z = 0.0;
+        }
+    }
+}
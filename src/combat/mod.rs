@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+use crate::utils::{EventQueuePlugin, PoolPlugin};
+use crate::simulation::SimulationAppExt;
+use crate::input::{ConsoleAppExt, ConsoleCommand};
 
 pub mod types;
 pub mod systems;
@@ -11,6 +14,12 @@ pub mod result_queue;
 pub mod slice;
 pub mod impact;
 pub mod decals;
+pub mod underwater;
+pub mod special_moves;
+pub mod melee_assist;
+pub mod telegraph;
+pub mod elements;
+pub mod environmental;
 
 pub use types::*;
 pub use systems::*;
@@ -23,18 +32,41 @@ pub use result_queue::*;
 pub use slice::*;
 pub use impact::*;
 pub use decals::*;
+pub use underwater::*;
+pub use special_moves::*;
+pub use melee_assist::*;
+pub use telegraph::*;
+pub use elements::*;
+pub use environmental::*;
 
 pub struct CombatPlugin;
 
 impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
+        let simulation = app.simulation_schedule();
+
         app
-            .init_resource::<DamageEventQueue>()
-            .init_resource::<DeathEventQueue>()
-            .init_resource::<DamageResultQueue>()
-            .init_resource::<MeleeHitboxEventQueue>()
-            .init_resource::<SliceEventQueue>()
-            .init_resource::<SliceResultQueue>()
+            .add_plugins((
+                PoolPlugin::<DamageNumber>::new(64),
+                PoolPlugin::<Decal>::new(128),
+                EventQueuePlugin::<DamageEvent>::default(),
+                EventQueuePlugin::<DeathEvent>::default(),
+                EventQueuePlugin::<DamageResultEvent>::default(),
+                EventQueuePlugin::<HitReaction>::default(),
+                EventQueuePlugin::<SpecialMoveEvent>::default(),
+            ))
+            .add_plugins(EventQueuePlugin::<MeleeHitboxEvent>::default())
+            .add_console_command(
+                ConsoleCommand::new("set_health", "Sets the player's health").with_usage("<amount|max>"),
+                set_health_command,
+            )
+            .add_plugins(EventQueuePlugin::<SliceEvent>::default())
+            .add_plugins(EventQueuePlugin::<SliceResultEvent>::default())
+            .add_plugins(EventQueuePlugin::<TelegraphRequest>::default())
+            .add_plugins(EventQueuePlugin::<TelegraphEvent>::default())
+            .add_plugins(EventQueuePlugin::<ElementPulse>::default())
+            .add_plugins(EventQueuePlugin::<ElementEvent>::default())
+            .add_plugins(EventQueuePlugin::<EnvironmentalKillEvent>::default())
             .init_resource::<SliceFxSettings>()
             .init_resource::<SurfaceFxDatabase>()
             .init_resource::<SurfaceFxSettings>()
@@ -42,13 +74,22 @@ impl Plugin for CombatPlugin {
             .init_resource::<DecalSettings>()
             .init_resource::<DamageFeedbackSettings>()
             .init_resource::<AttackDatabase>()
+            .init_resource::<UnderwaterCombatRules>()
+            .init_resource::<TelegraphSettings>()
+            .init_resource::<ElementSettings>()
+            .init_resource::<EnvironmentalKillSettings>()
             .register_type::<Health>()
             .register_type::<Shield>()
             .register_type::<DamageReceiver>()
             .register_type::<MeleeCombat>()
             .register_type::<AttackDefinition>()
             .register_type::<AttackChain>()
+            .register_type::<AttackTrigger>()
+            .register_type::<AttackCharge>()
+            .register_type::<SpecialMoveState>()
+            .register_type::<Staggered>()
             .register_type::<MeleeAttackState>()
+            .register_type::<MeleeAssist>()
             .register_type::<DamageZone>()
             .register_type::<MeleeRangedWeaponSettings>()
             .register_type::<MeleeRangedAimState>()
@@ -68,27 +109,82 @@ impl Plugin for CombatPlugin {
             .register_type::<SliceFxMarker>()
             .register_type::<SliceChunk>()
             .register_type::<SurfaceType>()
-            .register_type::<SurfaceFxMarker>()
             .register_type::<Decal>()
-            .add_systems(Startup, damage_ui::setup_damage_ui)
-            .add_systems(Update, (
-                systems::clear_damage_results, // Clear results at start of frame/update
+            .register_type::<Submerged>()
+            .register_type::<UnderwaterWeaponSwap>()
+            .register_type::<UnderwaterCombatRules>()
+            .register_type::<AttackTelegraphs>()
+            .register_type::<ActiveTelegraph>()
+            .register_type::<DodgeableWindow>()
+            .register_type::<TelegraphSettings>()
+            .register_type::<Flammable>()
+            .register_type::<Burning>()
+            .register_type::<Wet>()
+            .register_type::<Frozen>()
+            .register_type::<GrassVolume>()
+            .register_type::<ElementSettings>()
+            .register_type::<EnvironmentalKillTracker>()
+            .register_type::<EnvironmentalKillSettings>()
+            .add_systems(Startup, damage_ui::setup_damage_ui.in_set(crate::headless::PresentationSet))
+            .configure_sets(simulation, (
+                CombatSet::Prepare,
+                CombatSet::Effects,
+                CombatSet::Attacks,
+                CombatSet::DamageResolution,
+                CombatSet::Aftermath,
+            ).chain().in_set(crate::GameControllerSet::Combat))
+            // Telegraphs hold back AI attack input, so they run inside the AI phase
+            .add_systems(simulation, (
+                telegraph::start_requested_telegraphs,
+                telegraph::update_attack_telegraphs,
+            ).chain().in_set(crate::ai::AiSet::Combat).before(crate::ai::update_ai_combat))
+            .add_systems(simulation, telegraph::telegraph_ai_attacks
+                .in_set(crate::ai::AiSet::Combat)
+                .after(crate::ai::update_ai_combat)
+                .before(crate::ai::update_ai_lead_targets))
+            .add_systems(simulation, telegraph::render_attack_telegraphs
+                .in_set(crate::headless::PresentationSet)
+                .after(telegraph::telegraph_ai_attacks))
+            .add_systems(simulation, (
+                underwater::update_submerged_state,
+                underwater::swap_weapons_underwater,
+                underwater::lose_submerged_targets,
                 systems::update_timers,
+            ).chain().in_set(CombatSet::Prepare))
+            .add_systems(simulation, (
                 decals::spawn_decals_from_damage,
                 decals::update_decals,
                 impact::spawn_surface_fx_from_damage,
-                impact::update_surface_fx_markers,
                 slice::queue_slice_events_from_laser,
                 slice::queue_slice_events_from_damage_results,
                 slice::apply_slice_events,
                 slice::handle_slice_results,
                 slice::update_slice_fx_markers,
                 slice::update_slice_chunks,
+            ).chain().in_set(CombatSet::Effects))
+            .add_systems(simulation, (
+                elements::tag_flammable_objects,
+                elements::apply_elemental_hits,
+                elements::spread_fire,
+                elements::update_burning,
+            ).chain().in_set(CombatSet::Effects))
+            .add_systems(simulation, (
+                environmental::add_environmental_kill_trackers,
+                environmental::credit_thrown_characters,
+                environmental::apply_ragdoll_hazards,
+            ).chain().in_set(CombatSet::Effects))
+            .add_systems(simulation, (
                 systems::handle_air_attack_to_land,
-                systems::update_melee_attack_state,
-                systems::apply_melee_hitbox_events,
-                systems::update_melee_hitboxes,
-                systems::perform_melee_hitbox_damage,
+                special_moves::attach_special_move_state,
+                special_moves::update_special_moves,
+                special_moves::perform_special_move_hits,
+                (
+                    melee_assist::start_melee_assist,
+                    systems::update_melee_attack_state,
+                    systems::apply_melee_hitbox_events,
+                    systems::update_melee_hitboxes,
+                    systems::perform_melee_hitbox_damage,
+                ).chain(),
                 systems::update_melee_ranged_aim,
                 systems::update_melee_ranged_camera,
                 systems::perform_melee_ranged_attacks,
@@ -97,22 +193,28 @@ impl Plugin for CombatPlugin {
                 systems::regenerate_health,
                 systems::regenerate_shields,
                 systems::perform_melee_attacks,
+                melee_assist::update_melee_assist,
                 systems::perform_blocking,
-                
+                special_moves::apply_hit_reactions,
+                special_moves::update_staggered,
+            ).chain().in_set(CombatSet::Attacks))
+            .add_systems(simulation, (
                 // Sync Stats <-> Combat
                 sync::sync_stats_to_combat, // Push Max from Stats to Health
                 sync::sync_combat_to_stats, // Push Current from Health to Stats
                 
                 // Damage Logic Chain
-                damage_ui::trigger_damage_ui, // Read events before drain
+                damage_ui::trigger_damage_ui.in_set(crate::headless::PresentationSet), // Read events before drain
                 systems::process_damage_events, // Drains events
-                
+            ).chain().in_set(CombatSet::DamageResolution))
+            .add_systems(simulation, (
                 systems::update_damage_numbers,
-                damage_ui::update_damage_ui,
+                damage_ui::update_damage_ui.in_set(crate::headless::PresentationSet),
                 damage_over_time::update_damage_over_time,
                 destroyable::handle_destroyable_death,
                 systems::handle_character_death, // Character Death -> Ragdoll
                 area_effect::handle_area_effects,
-            ).chain());
+                environmental::report_environmental_kills,
+            ).chain().in_set(CombatSet::Aftermath));
     }
 }
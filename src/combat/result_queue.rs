@@ -1,13 +1,15 @@
 use bevy::prelude::*;
 use super::types::*;
+use crate::utils::EventQueue;
 
-/// Queue for result events (Workaround for Bevy 0.18 EventReader issues)
-#[derive(Resource, Default)]
-pub struct DamageResultQueue(pub Vec<DamageResultEvent>);
+/// Queue for damage results (final amounts after shields, blocks and crits)
+pub type DamageResultQueue = EventQueue<DamageResultEvent>;
 
 #[derive(Debug, Clone, Copy, Event)]
 pub struct DamageResultEvent {
     pub target: Entity,
+    /// `DamageReceiver` collider that was hit, when the hit landed on a body part
+    pub part: Option<Entity>,
     pub source: Option<Entity>,
     pub original_amount: f32,
     pub final_amount: f32,
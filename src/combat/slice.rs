@@ -1,8 +1,9 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
 
-use crate::abilities::LaserVisionSliceEventQueue;
-use crate::combat::result_queue::DamageResultQueue;
+use crate::abilities::{LaserVisionSliceEvent, LaserVisionSliceEventQueue};
+use crate::combat::result_queue::DamageResultEvent;
+use crate::utils::{EventQueue, QueueCursor, QueueReader};
 use crate::combat::types::DamageType;
 
 #[derive(Resource, Debug, Reflect)]
@@ -98,8 +99,7 @@ pub struct SliceEvent {
     pub radius: f32,
 }
 
-#[derive(Resource, Default)]
-pub struct SliceEventQueue(pub Vec<SliceEvent>);
+pub type SliceEventQueue = EventQueue<SliceEvent>;
 
 #[derive(Debug, Clone)]
 pub struct SliceResultEvent {
@@ -110,17 +110,18 @@ pub struct SliceResultEvent {
     pub sliced: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct SliceResultQueue(pub Vec<SliceResultEvent>);
+pub type SliceResultQueue = EventQueue<SliceResultEvent>;
 
 pub fn queue_slice_events_from_laser(
     mut slice_queue: ResMut<SliceEventQueue>,
-    mut laser_queue: Option<ResMut<LaserVisionSliceEventQueue>>,
+    laser_queue: Option<Res<LaserVisionSliceEventQueue>>,
+    mut laser_cursor: Local<QueueCursor<LaserVisionSliceEvent>>,
 ) {
-    let Some(mut laser_queue) = laser_queue else { return };
+    // Laser vision belongs to the abilities plugin, which may not be added
+    let Some(laser_queue) = laser_queue else { return };
 
-    for event in laser_queue.0.drain(..) {
-        slice_queue.0.push(SliceEvent {
+    for event in laser_cursor.read(&laser_queue) {
+        slice_queue.send(SliceEvent {
             source: Some(event.entity),
             position: event.position,
             normal: event.direction,
@@ -131,11 +132,11 @@ pub fn queue_slice_events_from_laser(
 }
 
 pub fn queue_slice_events_from_damage_results(
-    damage_queue: Res<DamageResultQueue>,
+    mut damage_queue: QueueReader<DamageResultEvent>,
     mut slice_queue: ResMut<SliceEventQueue>,
     query: Query<(&GlobalTransform, &SliceOnDamage, &Sliceable)>,
 ) {
-    for event in damage_queue.0.iter() {
+    for event in damage_queue.read() {
         let Ok((transform, settings, sliceable)) = query.get(event.target) else {
             continue;
         };
@@ -160,7 +161,7 @@ pub fn queue_slice_events_from_damage_results(
 
         let radius = settings.radius_override.unwrap_or(sliceable.slice_radius);
 
-        slice_queue.0.push(SliceEvent {
+        slice_queue.send(SliceEvent {
             source: event.source,
             position: transform.translation(),
             normal: Vec3::Y,
@@ -172,13 +173,13 @@ pub fn queue_slice_events_from_damage_results(
 
 pub fn apply_slice_events(
     time: Res<Time>,
-    mut slice_queue: ResMut<SliceEventQueue>,
+    mut slice_queue: QueueReader<SliceEvent>,
     mut result_queue: ResMut<SliceResultQueue>,
     mut sliceables: Query<(Entity, &GlobalTransform, &mut Sliceable)>,
 ) {
     let now = time.elapsed_secs();
 
-    for event in slice_queue.0.drain(..) {
+    for event in slice_queue.read().cloned() {
         for (entity, transform, mut sliceable) in sliceables.iter_mut() {
             if !sliceable.enabled {
                 continue;
@@ -195,7 +196,7 @@ pub fn apply_slice_events(
 
             sliceable.last_slice_time = now;
 
-            result_queue.0.push(SliceResultEvent {
+            result_queue.send(SliceResultEvent {
                 target: entity,
                 position: event.position,
                 normal: event.normal,
@@ -211,18 +212,18 @@ pub fn apply_slice_events(
 pub fn handle_slice_results(
     mut commands: Commands,
     time: Res<Time>,
-    mut results: ResMut<SliceResultQueue>,
+    mut results: QueueReader<SliceResultEvent>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     settings: Res<SliceFxSettings>,
     target_query: Query<(&GlobalTransform, &Sliceable, Option<&Handle<StandardMaterial>>)>,
 ) {
     if !settings.spawn_debug_marker {
-        results.0.clear();
+        results.clear();
         return;
     }
 
-    for result in results.0.drain(..) {
+    for result in results.read() {
         if !result.sliced {
             continue;
         }
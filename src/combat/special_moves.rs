@@ -0,0 +1,462 @@
+//! Special moves
+//!
+//! Shield bashes, charged heavy attacks and weapon arts are [`AttackDefinition`]s
+//! with a non-combo [`AttackTrigger`], listed in the `special_moves` of the
+//! equipped weapon's [`AttackChain`] (chain id = weapon id) or of the
+//! [`AttackDatabase`] for moves shared by every weapon:
+//!
+//! - `ShieldBash`: attack while blocking (shields, grabbed objects that block)
+//! - `Charged`: hold attack, release after `charge.min_time`; a shorter press still
+//!   plays the combo. The character keeps moving at `charge.move_speed_multiplier`.
+//! - `WeaponArt`: the special-move input, usually with a `cooldown`
+//! - `InputCombo`: a chord or sequence binding (quarter-circle + attack) equal to
+//!   the move's `input`, bound to any action in the `InputMap`
+//!
+//! Hits apply the attack's `stagger` and `knockback` through [`HitReaction`]s,
+//! which games can also send for their own moves:
+//!
+//! ```rust,ignore
+//! attack_db.chains.push(AttackChain {
+//!     id: "greatsword".to_string(),
+//!     attacks: vec![AttackDefinition::default()],
+//!     special_moves: vec![
+//!         AttackDefinition::heavy("Overhead Slam"),
+//!         AttackDefinition {
+//!             name: "Whirlwind".to_string(),
+//!             trigger: AttackTrigger::WeaponArt,
+//!             damage_multiplier: 2.0,
+//!             range: 3.0,
+//!             cooldown: 8.0,
+//!             knockback: 4.0,
+//!             ..default()
+//!         },
+//!     ],
+//! });
+//! ```
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use std::collections::HashMap;
+use super::types::*;
+use super::underwater::{Submerged, UnderwaterCombatRules};
+use crate::input::{InputBuffer, InputState};
+use crate::inventory::MeleeWeaponEquipmentState;
+use crate::utils::{EventQueue, QueueReader};
+
+/// Special move runtime state; added to characters with `MeleeCombat` or `Blocking`
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct SpecialMoveState {
+    /// Move being performed
+    pub active: Option<AttackDefinition>,
+    pub timer: f32,
+    /// Damage multiplier of the active move (charge power)
+    pub power: f32,
+    /// Charged move being held
+    pub charging: Option<AttackDefinition>,
+    pub charge_time: f32,
+    /// Attack press released before the charge threshold, passed on to the combo this frame
+    pub released_tap: bool,
+    /// Remaining cooldown per move name
+    pub cooldowns: HashMap<String, f32>,
+    /// Targets already hit by the active move
+    pub hit_entities: Vec<Entity>,
+}
+
+impl SpecialMoveState {
+    pub fn is_busy(&self) -> bool {
+        self.active.is_some() || self.charging.is_some()
+    }
+
+    /// Attack input left for the combo systems
+    pub fn combo_attack_input(&self, attack_pressed: bool) -> bool {
+        if self.released_tap {
+            return true;
+        }
+        attack_pressed && !self.is_busy()
+    }
+
+    /// Movement speed factor from charging
+    pub fn move_speed_multiplier(&self) -> f32 {
+        self.charging
+            .as_ref()
+            .and_then(|attack| attack.charge.as_ref())
+            .map_or(1.0, |charge| charge.move_speed_multiplier)
+    }
+
+    pub fn cooldown_remaining(&self, name: &str) -> f32 {
+        self.cooldowns.get(name).copied().unwrap_or(0.0)
+    }
+
+    fn start(&mut self, attack: AttackDefinition, power: f32) {
+        if attack.cooldown > 0.0 {
+            self.cooldowns.insert(attack.name.clone(), attack.cooldown);
+        }
+        self.active = Some(attack);
+        self.timer = 0.0;
+        self.power = power;
+        self.hit_entities.clear();
+    }
+}
+
+/// Character briefly unable to move, attack or block
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Staggered {
+    pub remaining: f32,
+}
+
+/// Stagger and knockback applied to a hit target
+#[derive(Debug, Clone, PartialEq)]
+pub struct HitReaction {
+    pub target: Entity,
+    pub source: Option<Entity>,
+    pub direction: Vec3,
+    /// Seconds of stagger; 0 for none
+    pub stagger: f32,
+    /// Impulse along `direction`
+    pub knockback: f32,
+}
+
+/// Queue for hit reactions
+pub type HitReactionQueue = EventQueue<HitReaction>;
+
+/// Special move notifications
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpecialMoveEvent {
+    Started { entity: Entity, name: String, trigger: AttackTrigger },
+    Hit { entity: Entity, target: Entity, name: String },
+}
+
+/// Queue for special move events
+pub type SpecialMoveEventQueue = EventQueue<SpecialMoveEvent>;
+
+type NewSpecialMover = (Or<(With<MeleeCombat>, With<Blocking>)>, Without<SpecialMoveState>);
+
+/// System to add special move state to melee characters and blockers
+pub fn attach_special_move_state(
+    mut commands: Commands,
+    query: Query<Entity, NewSpecialMover>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(SpecialMoveState::default());
+    }
+}
+
+/// Character parts special moves read and drive
+type SpecialMover<'a> = (
+    Entity,
+    &'a mut SpecialMoveState,
+    Option<&'a mut MeleeCombat>,
+    Option<&'a MeleeAttackState>,
+    Option<&'a MeleeWeaponEquipmentState>,
+    Option<&'a Blocking>,
+    Has<Staggered>,
+    Has<Submerged>,
+);
+
+/// System to start, charge and finish special moves from the attack, block and special-move input
+pub fn update_special_moves(
+    time: Res<Time>,
+    input: Res<InputState>,
+    mut input_buffer: ResMut<InputBuffer>,
+    attack_db: Res<AttackDatabase>,
+    underwater_rules: Res<UnderwaterCombatRules>,
+    mut events: ResMut<SpecialMoveEventQueue>,
+    mut query: Query<SpecialMover>,
+) {
+    for (entity, mut state, combat, attack_state, equipment, blocking, staggered, submerged) in query.iter_mut() {
+        let dt = time.delta_secs() * underwater_rules.melee_time_scale(submerged);
+        state.released_tap = false;
+        state.cooldowns.retain(|_, remaining| {
+            *remaining -= dt;
+            *remaining > 0.0
+        });
+
+        if staggered {
+            state.active = None;
+            state.charging = None;
+            continue;
+        }
+
+        if let Some(active) = &state.active {
+            let duration = active.duration;
+            state.timer += dt;
+            if state.timer >= duration {
+                state.active = None;
+                if let Some(mut combat) = combat {
+                    combat.is_attacking = false;
+                }
+            }
+            continue;
+        }
+
+        let chain_ids = [
+            equipment.and_then(|equipment| equipment.equipped_weapon_id.as_deref()),
+            attack_state.map(|attack_state| attack_state.chain_id.as_str()),
+        ];
+        let find = |state: &SpecialMoveState, trigger| {
+            attack_db
+                .special_move(chain_ids.iter().flatten().copied(), trigger)
+                .filter(|attack| state.cooldown_remaining(&attack.name) <= 0.0)
+                .cloned()
+        };
+
+        let combo_move = input_buffer.actions.iter().enumerate().find_map(|(index, buffered)| {
+            let input = buffered.combo.as_ref()?;
+            attack_db
+                .combo_special_move(chain_ids.iter().flatten().copied(), input)
+                .filter(|attack| state.cooldown_remaining(&attack.name) <= 0.0)
+                .map(|attack| (index, attack.clone()))
+        });
+
+        let mut started = None;
+        if let Some(charging) = state.charging.clone() {
+            let charge = charging.charge.clone().unwrap_or_default();
+            if input.attack_held {
+                state.charge_time += dt;
+                continue;
+            }
+            let held = state.charge_time;
+            state.charging = None;
+            if held >= charge.min_time {
+                started = Some((charging, charge.power(held)));
+            } else {
+                state.released_tap = true;
+            }
+        } else if let Some((index, attack)) = combo_move {
+            input_buffer.actions.remove(index);
+            started = Some((attack, 1.0));
+        } else if input.attack_pressed && blocking.is_some_and(|blocking| blocking.is_blocking) {
+            started = find(&state, AttackTrigger::ShieldBash).map(|attack| (attack, 1.0));
+        } else if input.special_move_pressed {
+            started = find(&state, AttackTrigger::WeaponArt).map(|attack| (attack, 1.0));
+        } else if input.attack_pressed && combat.as_ref().is_none_or(|combat| !combat.is_attacking) {
+            if let Some(charged) = find(&state, AttackTrigger::Charged) {
+                state.charging = Some(charged);
+                state.charge_time = 0.0;
+            }
+        }
+
+        let Some((attack, power)) = started else { continue };
+        events.send(SpecialMoveEvent::Started { entity, name: attack.name.clone(), trigger: attack.trigger });
+        if let Some(mut combat) = combat {
+            combat.is_attacking = true;
+            combat.attack_timer = attack.duration;
+        }
+        state.start(attack, power);
+    }
+}
+
+type SpecialMoveTarget = Or<(With<Health>, With<DamageReceiver>)>;
+
+/// System to damage targets in front of characters during a special move's hit window
+pub fn perform_special_move_hits(
+    mut damage_queue: ResMut<DamageEventQueue>,
+    mut reactions: ResMut<HitReactionQueue>,
+    mut events: ResMut<SpecialMoveEventQueue>,
+    spatial_query: SpatialQuery,
+    mut attackers: Query<(Entity, &GlobalTransform, &mut SpecialMoveState, Option<&MeleeCombat>)>,
+    targets: Query<Entity, SpecialMoveTarget>,
+) {
+    for (attacker_entity, transform, mut state, combat) in attackers.iter_mut() {
+        let Some(attack) = &state.active else { continue };
+        if state.timer < attack.hitbox_start || state.timer > attack.hitbox_end {
+            continue;
+        }
+
+        let origin = transform.translation();
+        let forward = transform.forward();
+        let Some(hit) = spatial_query.cast_shape(
+            &Collider::sphere(0.5),
+            origin,
+            transform.rotation(),
+            forward.into(),
+            &ShapeCastConfig::default().with_max_distance(attack.range),
+            &SpatialQueryFilter::default().with_excluded_entities([attacker_entity]),
+        ) else {
+            continue;
+        };
+        if targets.get(hit.entity).is_err() || state.hit_entities.contains(&hit.entity) {
+            continue;
+        }
+
+        let base_damage = combat.map_or(MeleeCombat::default().damage, |combat| combat.damage);
+        let amount = base_damage * attack.damage_multiplier * state.power;
+        if amount > 0.0 {
+            damage_queue.send(DamageEvent {
+                amount,
+                damage_type: DamageType::Melee,
+                source: Some(attacker_entity),
+                target: hit.entity,
+                position: Some(origin + *forward * hit.distance),
+                direction: Some(*forward),
+                ignore_shield: false,
+            });
+        }
+        if attack.stagger > 0.0 || attack.knockback > 0.0 {
+            reactions.send(HitReaction {
+                target: hit.entity,
+                source: Some(attacker_entity),
+                direction: *forward,
+                stagger: attack.stagger,
+                knockback: attack.knockback * state.power,
+            });
+        }
+        events.send(SpecialMoveEvent::Hit { entity: attacker_entity, target: hit.entity, name: attack.name.clone() });
+        state.hit_entities.push(hit.entity);
+    }
+}
+
+type HitReactor<'a> = (
+    Option<&'a mut LinearVelocity>,
+    Option<&'a mut Staggered>,
+    Option<&'a mut MeleeCombat>,
+    Option<&'a mut Blocking>,
+    Option<&'a Health>,
+);
+
+/// System to stagger and push back hit targets
+pub fn apply_hit_reactions(
+    mut commands: Commands,
+    mut reactions: QueueReader<HitReaction>,
+    receiver_query: Query<&DamageReceiver>,
+    mut target_query: Query<HitReactor>,
+) {
+    for reaction in reactions.read() {
+        let target = receiver_query
+            .get(reaction.target)
+            .map_or(reaction.target, |receiver| receiver.health_root);
+        let Ok((velocity, staggered, combat, blocking, health)) = target_query.get_mut(target) else { continue };
+        if health.is_some_and(|health| health.is_dead || health.is_invulnerable) {
+            continue;
+        }
+
+        if reaction.knockback > 0.0 {
+            if let Some(mut velocity) = velocity {
+                let push = Vec3::new(reaction.direction.x, 0.0, reaction.direction.z).normalize_or_zero();
+                velocity.0 += push * reaction.knockback + Vec3::Y * reaction.knockback * 0.2;
+            }
+        }
+
+        if reaction.stagger > 0.0 {
+            match staggered {
+                Some(mut staggered) => staggered.remaining = staggered.remaining.max(reaction.stagger),
+                None => {
+                    commands.entity(target).insert(Staggered { remaining: reaction.stagger });
+                }
+            }
+            if let Some(mut combat) = combat {
+                combat.is_attacking = false;
+                combat.attack_timer = 0.0;
+            }
+            if let Some(mut blocking) = blocking {
+                blocking.is_blocking = false;
+                blocking.current_block_time = 0.0;
+            }
+        }
+    }
+}
+
+/// System to end staggers
+pub fn update_staggered(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Staggered, Option<&mut Blocking>)>,
+) {
+    for (entity, mut staggered, blocking) in query.iter_mut() {
+        // No blocking until the stagger ends
+        if let Some(mut blocking) = blocking {
+            blocking.is_blocking = false;
+        }
+        staggered.remaining -= time.delta_secs();
+        if staggered.remaining <= 0.0 {
+            commands.entity(entity).remove::<Staggered>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn test_shield_bash_staggers_and_cools_down() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin, PhysicsPlugins::default()))
+            .add_plugins((
+                EventQueuePlugin::<SpecialMoveEvent>::default(),
+                EventQueuePlugin::<HitReaction>::default(),
+                EventQueuePlugin::<DamageEvent>::default(),
+            ))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<InputState>()
+            .init_resource::<InputBuffer>()
+            .init_resource::<AttackDatabase>()
+            .init_resource::<UnderwaterCombatRules>()
+            .add_systems(Update, (
+                attach_special_move_state,
+                update_special_moves,
+                perform_special_move_hits,
+                apply_hit_reactions,
+                update_staggered,
+            ).chain());
+
+        let knight = app.world_mut().spawn((
+            MeleeCombat::default(),
+            Blocking { is_blocking: true, ..default() },
+            Transform::default(),
+        )).id();
+        let bandit = app.world_mut().spawn((
+            Health::default(),
+            RigidBody::Kinematic,
+            Collider::sphere(0.5),
+            Transform::from_xyz(0.0, 0.0, -1.2),
+        )).id();
+        for _ in 0..2 {
+            app.update();
+        }
+
+        // Attacking while blocking bashes instead of swinging
+        app.insert_resource(InputState { attack_pressed: true, ..default() });
+        app.update();
+        app.insert_resource(InputState::default());
+        assert!(app.world().resource::<SpecialMoveEventQueue>().iter().any(|event| *event == SpecialMoveEvent::Started {
+            entity: knight,
+            name: "Shield Bash".to_string(),
+            trigger: AttackTrigger::ShieldBash,
+        }));
+        assert!(app.world().get::<MeleeCombat>(knight).unwrap().is_attacking);
+
+        // The hit window opens on the next frame
+        app.update();
+        let bash = AttackDefinition::shield_bash();
+        let damage = app.world().resource::<DamageEventQueue>().iter().find(|event| event.target == bandit).cloned().unwrap();
+        assert_eq!(damage.source, Some(knight));
+        assert_eq!(damage.amount, MeleeCombat::default().damage * bash.damage_multiplier);
+        let stagger = app.world().get::<Staggered>(bandit).unwrap().remaining;
+        assert!(stagger > 0.0 && stagger <= bash.stagger);
+        assert!(app.world().get::<LinearVelocity>(bandit).unwrap().z < 0.0);
+
+        // Once the bash is over it is still on cooldown
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(app.world().get::<SpecialMoveState>(knight).unwrap().active.is_none());
+        app.insert_resource(InputState { attack_pressed: true, ..default() });
+        app.update();
+        app.insert_resource(InputState::default());
+        assert!(app.world().get::<SpecialMoveState>(knight).unwrap().active.is_none());
+        assert!(app.world().get::<SpecialMoveState>(knight).unwrap().cooldown_remaining("Shield Bash") > 0.0);
+
+        // The stagger wears off
+        for _ in 0..10 {
+            app.update();
+        }
+        assert!(app.world().get::<Staggered>(bandit).is_none());
+    }
+}
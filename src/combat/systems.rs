@@ -1,25 +1,31 @@
 use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
 use avian3d::prelude::*;
 use super::types::*;
-use crate::input::InputState;
+use crate::input::{console_arg, ConsoleArgs, ConsoleResult, InputAction, InputBuffer, InputState};
 use crate::stats::{StatsSystem, types::DerivedStat};
 use crate::player::ragdoll::{ActivateRagdollQueue, ActivateRagdollEvent};
 use super::result_queue::*;
+use super::underwater::{Submerged, UnderwaterCombatRules};
+use super::special_moves::SpecialMoveState;
 use crate::camera::types::{CameraController, CameraState};
 use crate::weapons::types::Projectile;
-use crate::inventory::MeleeWeaponEquipmentState;
+use crate::inventory::{Equipment, MeleeWeaponEquipmentState};
 use crate::character::types::CharacterMovementState;
 use crate::physics::GroundDetection;
+use crate::utils::{EntityPool, QueueReader};
+use crate::game_manager::types::FeatureToggles;
 
 pub fn update_melee_attack_state(
     time: Res<Time>,
     input: Res<InputState>,
+    mut input_buffer: ResMut<InputBuffer>,
     attack_db: Res<AttackDatabase>,
-    mut query: Query<(&mut MeleeCombat, &mut MeleeAttackState)>,
+    underwater_rules: Res<UnderwaterCombatRules>,
+    mut query: Query<(&mut MeleeCombat, &mut MeleeAttackState, Option<&SpecialMoveState>, Has<Submerged>)>,
 ) {
-    let dt = time.delta_secs();
-
-    for (mut combat, mut state) in query.iter_mut() {
+    for (mut combat, mut state, special, submerged) in query.iter_mut() {
+        let dt = time.delta_secs() * underwater_rules.melee_time_scale(submerged);
         state.timer += dt;
         if state.combo_timer > 0.0 {
             state.combo_timer = (state.combo_timer - dt).max(0.0);
@@ -40,7 +46,11 @@ pub fn update_melee_attack_state(
             combat.is_attacking = false;
         }
 
-        if input.attack_pressed && !combat.is_attacking {
+        // A press buffered during the previous swing starts the next one as soon as it ends
+        let pressed = input.attack_pressed || input_buffer.is_buffered(InputAction::Attack);
+        let attack_pressed = special.map_or(pressed, |special| special.combo_attack_input(pressed));
+        if attack_pressed && !combat.is_attacking {
+            input_buffer.consume(InputAction::Attack);
             if state.combo_timer > 0.0 {
                 state.current_attack_index = (state.current_attack_index + 1) % chain.attacks.len();
             } else {
@@ -49,7 +59,7 @@ pub fn update_melee_attack_state(
 
             combat.is_attacking = true;
             combat.attack_timer = attack.duration;
-            combat.last_attack_finish_time = time.elapsed_secs() + attack.duration;
+            combat.last_attack_finish_time = time.elapsed_secs() + attack.duration / underwater_rules.melee_time_scale(submerged);
             combat.combo_count = state.current_attack_index + 1;
 
             state.timer = 0.0;
@@ -81,10 +91,10 @@ pub fn update_melee_hitboxes(
 }
 
 pub fn apply_melee_hitbox_events(
-    mut event_queue: ResMut<MeleeHitboxEventQueue>,
+    mut event_queue: QueueReader<MeleeHitboxEvent>,
     mut hitboxes: Query<&mut DamageZone>,
 ) {
-    for event in event_queue.0.drain(..) {
+    for event in event_queue.read().cloned() {
         for mut zone in hitboxes.iter_mut() {
             if zone.owner == event.owner {
                 zone.active = event.active;
@@ -131,7 +141,7 @@ pub fn perform_melee_hitbox_damage(
                 &SpatialQueryFilter::default().with_excluded_entities([attacker_entity]),
             ) {
                 if targets.get(hit.entity).is_ok() {
-                    damage_queue.0.push(DamageEvent {
+                    damage_queue.send(DamageEvent {
                         amount: base_damage * zone.damage_multiplier,
                         damage_type: DamageType::Melee,
                         source: Some(attacker_entity),
@@ -180,16 +190,24 @@ pub fn update_melee_ranged_camera(
     }
 }
 
+/// Projectile pool and render assets for thrown and fired melee projectiles
+#[derive(SystemParam)]
+pub struct MeleeProjectileSpawner<'w> {
+    pub projectile_pool: ResMut<'w, EntityPool<Projectile>>,
+    pub meshes: ResMut<'w, Assets<Mesh>>,
+    pub materials: ResMut<'w, Assets<StandardMaterial>>,
+}
+
 pub fn perform_melee_ranged_attacks(
     mut commands: Commands,
     time: Res<Time>,
     input: Res<InputState>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    spawner: MeleeProjectileSpawner,
     mut query: Query<(Entity, &GlobalTransform, &MeleeCombat, &mut MeleeRangedWeaponSettings, &MeleeRangedAimState)>,
     equipment_query: Query<&MeleeWeaponEquipmentState>,
     weapon_query: Query<(&GlobalTransform, &MeleeWeaponRangedAttack)>,
 ) {
+    let MeleeProjectileSpawner { mut projectile_pool, mut meshes, mut materials } = spawner;
     let now = time.elapsed_secs();
 
     for (owner, transform, combat, mut settings, aim_state) in query.iter_mut() {
@@ -218,7 +236,7 @@ pub fn perform_melee_ranged_attacks(
         let velocity = forward * settings.projectile_speed;
         let damage = combat.damage * settings.damage_multiplier;
 
-        let projectile_entity = commands.spawn((
+        let projectile_entity = projectile_pool.acquire(&mut commands, (
             Transform::from_translation(spawn_pos),
             GlobalTransform::default(),
             Projectile {
@@ -234,7 +252,7 @@ pub fn perform_melee_ranged_attacks(
                 rotate_to_velocity: true,
             },
             Name::new("MeleeRangedProjectile"),
-        )).id();
+        ));
 
         if settings.returnable {
             commands.entity(projectile_entity).insert(ReturnToOwner {
@@ -274,6 +292,7 @@ pub fn perform_melee_ranged_attacks(
 pub fn update_returning_projectiles(
     time: Res<Time>,
     mut commands: Commands,
+    mut projectile_pool: ResMut<EntityPool<Projectile>>,
     mut query: Query<(Entity, &mut Projectile, &mut Transform, &mut ReturnToOwner)>,
     owner_query: Query<&GlobalTransform>,
 ) {
@@ -286,7 +305,7 @@ pub fn update_returning_projectiles(
         }
 
         let Ok(owner_transform) = owner_query.get(return_state.owner) else {
-            commands.entity(entity).despawn();
+            projectile_pool.release(&mut commands, entity);
             continue;
         };
 
@@ -294,7 +313,7 @@ pub fn update_returning_projectiles(
         let to_owner = owner_pos - transform.translation;
         let dist = to_owner.length();
         if dist < 0.6 {
-            commands.entity(entity).despawn();
+            projectile_pool.release(&mut commands, entity);
             continue;
         }
 
@@ -368,7 +387,7 @@ pub fn handle_air_attack_to_land(
             &SpatialQueryFilter::default().with_excluded_entities([entity]),
         ) {
             if targets.get(hit.entity).is_ok() {
-                damage_queue.0.push(DamageEvent {
+                damage_queue.send(DamageEvent {
                     amount: air_attack.damage,
                     damage_type: air_attack.damage_type,
                     source: Some(entity),
@@ -386,32 +405,58 @@ pub fn handle_air_attack_to_land(
     }
 }
 
+/// Parts of a damage target that soak, block and take the damage
+type DamageableParts<'a> = (
+    &'a mut Health,
+    Option<&'a mut Shield>,
+    Option<&'a Blocking>,
+    Option<&'a StatsSystem>,
+    &'a GlobalTransform,
+    Option<&'a mut Equipment>,
+);
+
+/// Deaths, results and damage numbers produced by applied damage
+#[derive(SystemParam)]
+pub struct DamageOutcomes<'w> {
+    pub death_queue: ResMut<'w, DeathEventQueue>,
+    pub result_queue: ResMut<'w, DamageResultQueue>,
+    pub damage_number_pool: ResMut<'w, EntityPool<DamageNumber>>,
+}
+
 /// System to process damage events, reduce health/shields, and show feedback.
 pub fn process_damage_events(
     mut commands: Commands,
-    mut damage_queue: ResMut<DamageEventQueue>,
-    mut death_queue: ResMut<DeathEventQueue>,
-    mut result_queue: ResMut<DamageResultQueue>,
-    mut health_query: Query<(&mut Health, Option<&mut Shield>, Option<&Blocking>, Option<&StatsSystem>, &GlobalTransform)>,
+    mut damage_queue: QueueReader<DamageEvent>,
+    outcomes: DamageOutcomes,
+    mut health_query: Query<DamageableParts>,
     receiver_query: Query<&DamageReceiver>,
     time: Res<Time>,
+    toggles: Res<FeatureToggles>,
 ) {
+    let DamageOutcomes { mut death_queue, mut result_queue, mut damage_number_pool } = outcomes;
     let now = time.elapsed_secs();
 
-    for event in damage_queue.0.drain(..) {
+    for event in damage_queue.read().copied() {
+        // Fall damage from any source (falls, crashes, dropped from climbs...)
+        if event.damage_type == DamageType::Fall && !toggles.fall_damage {
+            continue;
+        }
+
         // 1. Resolve Target and Multipliers
         let mut target_root = event.target;
         let mut part_multiplier = 1.0;
         let mut is_weak_spot = false;
+        let mut part = None;
 
         if let Ok(receiver) = receiver_query.get(event.target) {
+            part = Some(event.target);
             target_root = receiver.health_root;
             part_multiplier = receiver.damage_multiplier;
             is_weak_spot = receiver.is_weak_spot;
         }
 
         // 2. Apply Damage to Root Health
-        if let Ok((mut health, shield_opt, blocking_opt, stats_opt, transform)) = health_query.get_mut(target_root) {
+        if let Ok((mut health, shield_opt, blocking_opt, stats_opt, transform, equipment_opt)) = health_query.get_mut(target_root) {
             if health.is_invulnerable || health.temporal_invincibility_timer > 0.0 || health.is_dead {
                 continue;
             }
@@ -447,6 +492,15 @@ pub fn process_damage_events(
                 final_damage *= (1.0 - resistance).max(0.0);
             }
 
+            // Apply Worn Armor (Physical), wearing it down
+            if let Some(mut equipment) = equipment_opt {
+                if matches!(event.damage_type, DamageType::Melee | DamageType::Ranged | DamageType::Explosion)
+                    && !equipment.armor_pieces.is_empty()
+                {
+                    final_damage = equipment.absorb_damage(final_damage);
+                }
+            }
+
             final_damage *= health.general_damage_multiplier * part_multiplier;
             let mut is_parry = false;
             let mut is_block = false;
@@ -457,7 +511,7 @@ pub fn process_damage_events(
                 health.current = (health.current + event.amount).min(health.maximum);
                 
                 // Show Feedback for Heal
-                commands.spawn((
+                damage_number_pool.acquire(&mut commands, (
                     Text::new(format!("+{}", event.amount as i32)),
                     TextFont { font_size: 20.0, ..default() },
                     TextColor(Color::srgb(0.0, 1.0, 0.0)), // Green
@@ -507,8 +561,9 @@ pub fn process_damage_events(
                 }
             }
             // 5. Emit Result Event (Hook for Audio/UI)
-            result_queue.0.push(DamageResultEvent {
+            result_queue.send(DamageResultEvent {
                 target: target_root,
+                part,
                 source: event.source,
                 original_amount: event.amount,
                 final_amount: final_damage,
@@ -548,7 +603,7 @@ pub fn process_damage_events(
                 format!("-{}", (shield_dmg + final_damage) as i32)
             };
 
-            commands.spawn((
+            damage_number_pool.acquire(&mut commands, (
                 Text::new(label),
                 TextFont { font_size: 20.0, ..default() },
                 TextColor(text_color),
@@ -565,7 +620,7 @@ pub fn process_damage_events(
             if health.current <= 0.0 {
                 health.current = 0.0;
                 health.is_dead = true;
-                death_queue.0.push(DeathEvent { entity: target_root });
+                death_queue.send(DeathEvent { entity: target_root });
             }
         }
     }
@@ -612,7 +667,8 @@ pub fn regenerate_shields(
 /// System to update various combat timers.
 pub fn update_timers(
     mut health_query: Query<&mut Health>,
-    mut melee_query: Query<&mut MeleeCombat>,
+    mut melee_query: Query<(&mut MeleeCombat, Has<Submerged>)>,
+    underwater_rules: Res<UnderwaterCombatRules>,
     time: Res<Time>,
 ) {
     let dt = time.delta_secs();
@@ -623,9 +679,9 @@ pub fn update_timers(
         }
     }
 
-    for mut combat in melee_query.iter_mut() {
+    for (mut combat, submerged) in melee_query.iter_mut() {
         if combat.attack_timer > 0.0 {
-            combat.attack_timer -= dt;
+            combat.attack_timer -= dt * underwater_rules.melee_time_scale(submerged);
             if combat.attack_timer <= 0.0 {
                 combat.is_attacking = false;
             }
@@ -633,20 +689,42 @@ pub fn update_timers(
     }
 }
 
+/// Attacker parts basic melee attacks read and drive
+type MeleeAttacker<'a> = (
+    Entity,
+    &'a GlobalTransform,
+    &'a mut MeleeCombat,
+    Option<&'a MeleeAttackState>,
+    Option<&'a SpecialMoveState>,
+    Has<Submerged>,
+);
+
+/// Attack input, live and buffered
+#[derive(SystemParam)]
+pub struct MeleeAttackInput<'w> {
+    pub input: Res<'w, InputState>,
+    pub input_buffer: ResMut<'w, InputBuffer>,
+}
+
 /// System to perform basic melee attacks with combo support and spatial hit detection.
 pub fn perform_melee_attacks(
     time: Res<Time>,
-    input: Res<InputState>,
+    attack_input: MeleeAttackInput,
     mut damage_queue: ResMut<DamageEventQueue>,
     spatial_query: SpatialQuery,
-    mut attackers: Query<(Entity, &GlobalTransform, &mut MeleeCombat, Option<&MeleeAttackState>)>,
+    underwater_rules: Res<UnderwaterCombatRules>,
+    mut attackers: Query<MeleeAttacker>,
     targets: Query<Entity, Or<(With<Health>, With<DamageReceiver>)>>,
 ) {
-    for (attacker_entity, transform, mut combat, attack_state) in attackers.iter_mut() {
+    let MeleeAttackInput { input, mut input_buffer } = attack_input;
+    for (attacker_entity, transform, mut combat, attack_state, special, submerged) in attackers.iter_mut() {
         if attack_state.is_some() {
             continue;
         }
-        if input.attack_pressed && combat.attack_timer <= 0.0 {
+        let pressed = input.attack_pressed || input_buffer.is_buffered(InputAction::Attack);
+        let attack_pressed = special.map_or(pressed, |special| special.combo_attack_input(pressed));
+        if attack_pressed && combat.attack_timer <= 0.0 {
+            input_buffer.consume(InputAction::Attack);
             let now = time.elapsed_secs();
             
             // Combo logic
@@ -662,7 +740,7 @@ pub fn perform_melee_attacks(
 
             combat.is_attacking = true;
             combat.attack_timer = combat.attack_speed;
-            combat.last_attack_finish_time = now + combat.attack_speed; // Estimate finish
+            combat.last_attack_finish_time = now + combat.attack_speed / underwater_rules.melee_time_scale(submerged); // Estimate finish
 
             let combo_multiplier = 1.0 + (combat.combo_count as f32 - 1.0) * 0.2;
             let current_damage = combat.damage * combo_multiplier;
@@ -680,7 +758,7 @@ pub fn perform_melee_attacks(
                 &SpatialQueryFilter::default().with_excluded_entities([attacker_entity]),
             ) {
                 if targets.get(hit.entity).is_ok() {
-                    damage_queue.0.push(DamageEvent {
+                    damage_queue.send(DamageEvent {
                         amount: current_damage,
                         damage_type: DamageType::Melee,
                         source: Some(attacker_entity),
@@ -720,6 +798,7 @@ pub fn perform_blocking(
 pub fn update_damage_numbers(
     mut commands: Commands,
     time: Res<Time>,
+    mut damage_number_pool: ResMut<EntityPool<DamageNumber>>,
     mut query: Query<(Entity, &mut Transform, &mut DamageNumber)>,
 ) {
     for (entity, mut transform, mut damage_number) in query.iter_mut() {
@@ -727,22 +806,22 @@ pub fn update_damage_numbers(
         transform.translation += damage_number.velocity * time.delta_secs();
 
         if damage_number.lifetime <= 0.0 {
-            commands.entity(entity).despawn();
+            damage_number_pool.release(&mut commands, entity);
         }
     }
 }
 
 /// System to handle character death events (trigger ragdoll, etc.)
 pub fn handle_character_death(
-    mut death_queue: ResMut<DeathEventQueue>,
+    mut death_queue: QueueReader<DeathEvent>,
     mut ragdoll_queue: ResMut<ActivateRagdollQueue>,
     query: Query<&Health>, // Just to verify? Or maybe just pass through.
     ragdoll_query: Query<Entity, With<crate::player::ragdoll::Ragdoll>>,
 ) {
-    for event in death_queue.0.drain(..) {
+    for event in death_queue.read() {
         // Trigger Ragdoll if component exists
         if ragdoll_query.contains(event.entity) {
-            ragdoll_queue.0.push(ActivateRagdollEvent {
+            ragdoll_queue.send(ActivateRagdollEvent {
                 entity: event.entity,
                 force_direction: None, // Could pass this from DamageEvent if we tracked it
                 force_magnitude: 0.0,
@@ -756,3 +835,21 @@ pub fn handle_character_death(
         }
     }
 }
+
+/// Console command `set_health <amount|max>`: sets the player's health, reviving them if needed
+pub fn set_health_command(
+    In(args): In<ConsoleArgs>,
+    mut player_query: Query<&mut Health, With<crate::character::Player>>,
+) -> ConsoleResult {
+    let mut health = player_query.iter_mut().next().ok_or("No player with health")?;
+    let amount = match args.first().map(String::as_str) {
+        Some("max") => health.maximum,
+        _ => console_arg::<f32>(&args, 0, "amount")?,
+    };
+    if amount <= 0.0 {
+        return Err("Amount must be above 0".to_string());
+    }
+    health.current = amount.min(health.maximum);
+    health.is_dead = false;
+    Ok(format!("Health {}/{}", health.current, health.maximum))
+}
@@ -0,0 +1,563 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::f32::consts::FRAC_PI_2;
+
+use crate::combat::types::Health;
+use crate::input::InputState;
+use crate::utils::{EventQueue, QueueReader};
+
+/// Area a telegraph marks on the ground
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum TelegraphShape {
+    /// Flash and audio only
+    None,
+    Circle { radius: f32 },
+    /// Wedge in front of the attacker, `angle` being the full opening in degrees
+    Cone { radius: f32, angle: f32 },
+    /// Strip in front of the attacker
+    Line { length: f32, width: f32 },
+}
+
+/// Which AI input a telegraph holds back until its wind-up is over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum TelegraphedInput {
+    Attack,
+    Fire,
+    AbilityUse,
+}
+
+impl TelegraphedInput {
+    fn get(self, input: &InputState) -> bool {
+        match self {
+            Self::Attack => input.attack_pressed,
+            Self::Fire => input.fire_pressed,
+            Self::AbilityUse => input.ability_use_pressed,
+        }
+    }
+
+    fn set(self, input: &mut InputState, pressed: bool) {
+        match self {
+            Self::Attack => input.attack_pressed = pressed,
+            Self::Fire => input.fire_pressed = pressed,
+            Self::AbilityUse => input.ability_use_pressed = pressed,
+        }
+    }
+}
+
+/// Wind-up warning shown before an attack lands
+#[derive(Debug, Clone, Reflect)]
+pub struct TelegraphDefinition {
+    pub id: String,
+    pub shape: TelegraphShape,
+    /// Center the shape on the target instead of the attacker (ground slams, mortars)
+    pub on_target: bool,
+    /// Seconds from the start of the telegraph to the hit
+    pub wind_up: f32,
+    /// Seconds before the hit during which a dodge avoids it
+    pub dodge_window: f32,
+    /// Seconds after the hit the dodge window stays open
+    pub dodge_grace: f32,
+    pub decal_color: Color,
+    pub flash_color: Color,
+    /// Sound played at the attacker when the wind-up starts, empty for none
+    pub audio_cue: String,
+    pub input: TelegraphedInput,
+}
+
+impl Default for TelegraphDefinition {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            shape: TelegraphShape::Cone { radius: 3.0, angle: 90.0 },
+            on_target: false,
+            wind_up: 0.6,
+            dodge_window: 0.3,
+            dodge_grace: 0.1,
+            decal_color: Color::srgba(0.9, 0.15, 0.1, 0.45),
+            flash_color: Color::srgb(1.0, 0.3, 0.2),
+            audio_cue: String::new(),
+            input: TelegraphedInput::Attack,
+        }
+    }
+}
+
+impl TelegraphDefinition {
+    /// Seconds into the telegraph the dodge window opens
+    pub fn dodge_window_start(&self) -> f32 {
+        (self.wind_up - self.dodge_window).max(0.0)
+    }
+
+    /// Seconds into the telegraph the dodge window closes and the telegraph ends
+    pub fn end(&self) -> f32 {
+        self.wind_up + self.dodge_grace.max(0.0)
+    }
+}
+
+/// Telegraphs an attacker cycles through, one per telegraphed attack
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct AttackTelegraphs {
+    pub telegraphs: Vec<TelegraphDefinition>,
+    /// Let `telegraph_ai_attacks` hold back the AI's attack input for the wind-up
+    pub telegraph_ai_attacks: bool,
+    pub next: usize,
+}
+
+impl Default for AttackTelegraphs {
+    fn default() -> Self {
+        Self {
+            telegraphs: vec![TelegraphDefinition::default()],
+            telegraph_ai_attacks: true,
+            next: 0,
+        }
+    }
+}
+
+impl AttackTelegraphs {
+    pub fn get(&self, id: &str) -> Option<&TelegraphDefinition> {
+        self.telegraphs.iter().find(|telegraph| telegraph.id == id)
+    }
+
+    /// Next telegraph of the cycle
+    pub fn advance(&mut self) -> Option<TelegraphDefinition> {
+        if self.telegraphs.is_empty() {
+            return None;
+        }
+        let telegraph = self.telegraphs[self.next % self.telegraphs.len()].clone();
+        self.next = (self.next + 1) % self.telegraphs.len();
+        Some(telegraph)
+    }
+}
+
+/// Telegraph in progress on an attacker
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct ActiveTelegraph {
+    pub definition: TelegraphDefinition,
+    pub target: Option<Entity>,
+    /// Ground point the shape is drawn at
+    pub origin: Vec3,
+    /// Flat facing of the shape
+    pub direction: Vec3,
+    pub elapsed: f32,
+    /// Set on the frame the wind-up ends and the attack goes out
+    pub just_released: bool,
+    pub released: bool,
+    pub dodge_window_opened: bool,
+}
+
+impl Default for ActiveTelegraph {
+    fn default() -> Self {
+        Self {
+            definition: TelegraphDefinition::default(),
+            target: None,
+            origin: Vec3::ZERO,
+            direction: Vec3::NEG_Z,
+            elapsed: 0.0,
+            just_released: false,
+            released: false,
+            dodge_window_opened: false,
+        }
+    }
+}
+
+impl ActiveTelegraph {
+    /// 0 when the wind-up starts, 1 when the attack lands
+    pub fn progress(&self) -> f32 {
+        if self.definition.wind_up <= 0.0 {
+            1.0
+        } else {
+            (self.elapsed / self.definition.wind_up).clamp(0.0, 1.0)
+        }
+    }
+
+    pub fn is_dodge_window_open(&self) -> bool {
+        self.elapsed >= self.definition.dodge_window_start() && self.elapsed <= self.definition.end()
+    }
+}
+
+/// Standard flag put on the target of a telegraphed attack while dodging it counts.
+/// Dodge/i-frame systems and tutorials read this instead of the attacker's timings.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct DodgeableWindow {
+    pub attacker: Entity,
+    pub telegraph_id: String,
+    /// Seconds until the window closes
+    pub remaining: f32,
+}
+
+impl Default for DodgeableWindow {
+    fn default() -> Self {
+        Self {
+            attacker: Entity::PLACEHOLDER,
+            telegraph_id: String::new(),
+            remaining: 0.0,
+        }
+    }
+}
+
+/// Grants `iframes` seconds of invincibility if a dodge window is open, returning whether it was
+pub fn try_dodge(window: Option<&DodgeableWindow>, health: &mut Health, iframes: f32) -> bool {
+    let Some(window) = window else { return false };
+    if window.remaining <= 0.0 {
+        return false;
+    }
+    health.temporal_invincibility_timer = health.temporal_invincibility_timer.max(iframes);
+    true
+}
+
+/// Starts one of the attacker's telegraphs by id, for scripted and boss attacks
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelegraphRequest {
+    pub attacker: Entity,
+    pub telegraph_id: String,
+    pub target: Option<Entity>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TelegraphEvent {
+    Started { attacker: Entity, target: Option<Entity>, telegraph_id: String, wind_up: f32 },
+    DodgeWindowOpened { attacker: Entity, target: Entity, telegraph_id: String },
+    Released { attacker: Entity, target: Option<Entity>, telegraph_id: String },
+    Finished { attacker: Entity, telegraph_id: String },
+}
+
+pub type TelegraphRequestQueue = EventQueue<TelegraphRequest>;
+pub type TelegraphEventQueue = EventQueue<TelegraphEvent>;
+
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct TelegraphSettings {
+    pub enabled: bool,
+    pub show_decals: bool,
+    /// Peak intensity of the light flashed on the attacker, 0 for none
+    pub flash_intensity: f32,
+    pub play_audio: bool,
+    /// Invincibility a dodge inside the window grants through `try_dodge`
+    pub dodge_iframes: f32,
+}
+
+impl Default for TelegraphSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            show_decals: true,
+            flash_intensity: 60_000.0,
+            play_audio: true,
+            dodge_iframes: 0.4,
+        }
+    }
+}
+
+/// Ground marking of an attacker's telegraph
+#[derive(Component, Debug)]
+pub struct TelegraphDecal {
+    pub attacker: Entity,
+}
+
+/// Light flashed on an attacker winding up
+#[derive(Component, Debug)]
+pub struct TelegraphFlash;
+
+fn begin_telegraph(
+    definition: TelegraphDefinition,
+    attacker_transform: &GlobalTransform,
+    target: Option<Entity>,
+    target_position: Option<Vec3>,
+) -> ActiveTelegraph {
+    let position = attacker_transform.translation();
+    let facing = target_position
+        .map(|target_position| target_position - position)
+        .unwrap_or_else(|| attacker_transform.forward().as_vec3());
+    let direction = Vec3::new(facing.x, 0.0, facing.z).try_normalize().unwrap_or(Vec3::NEG_Z);
+    let origin = match target_position {
+        Some(target_position) if definition.on_target => target_position,
+        _ => position,
+    };
+    ActiveTelegraph {
+        definition,
+        target,
+        origin,
+        direction,
+        ..default()
+    }
+}
+
+/// System to start telegraphs requested by id
+pub fn start_requested_telegraphs(
+    mut commands: Commands,
+    settings: Res<TelegraphSettings>,
+    mut requests: QueueReader<TelegraphRequest>,
+    mut events: ResMut<TelegraphEventQueue>,
+    attacker_query: Query<(&GlobalTransform, &AttackTelegraphs), Without<ActiveTelegraph>>,
+    transform_query: Query<&GlobalTransform>,
+) {
+    for request in requests.read() {
+        if !settings.enabled {
+            continue;
+        }
+        let Ok((transform, telegraphs)) = attacker_query.get(request.attacker) else { continue };
+        let Some(definition) = telegraphs.get(&request.telegraph_id) else {
+            warn!("Attacker {:?} has no telegraph '{}'", request.attacker, request.telegraph_id);
+            continue;
+        };
+        let target_position = request.target.and_then(|target| transform_query.get(target).ok()).map(|t| t.translation());
+        events.send(TelegraphEvent::Started {
+            attacker: request.attacker,
+            target: request.target,
+            telegraph_id: definition.id.clone(),
+            wind_up: definition.wind_up,
+        });
+        commands
+            .entity(request.attacker)
+            .insert(begin_telegraph(definition.clone(), transform, request.target, target_position));
+    }
+}
+
+/// System to run telegraph timings and open/close the target's dodge window
+pub fn update_attack_telegraphs(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut events: ResMut<TelegraphEventQueue>,
+    mut query: Query<(Entity, &mut ActiveTelegraph)>,
+    mut window_query: Query<&mut DodgeableWindow>,
+) {
+    let dt = time.delta_secs();
+    for (attacker, mut telegraph) in query.iter_mut() {
+        telegraph.just_released = false;
+        telegraph.elapsed += dt;
+        let id = telegraph.definition.id.clone();
+
+        if let Some(target) = telegraph.target {
+            let remaining = telegraph.definition.end() - telegraph.elapsed;
+            if telegraph.is_dodge_window_open() {
+                if let Ok(mut window) = window_query.get_mut(target) {
+                    if window.attacker == attacker {
+                        window.remaining = remaining;
+                    }
+                } else if !telegraph.dodge_window_opened {
+                    telegraph.dodge_window_opened = true;
+                    commands.entity(target).insert(DodgeableWindow {
+                        attacker,
+                        telegraph_id: id.clone(),
+                        remaining,
+                    });
+                    events.send(TelegraphEvent::DodgeWindowOpened { attacker, target, telegraph_id: id.clone() });
+                }
+            }
+        }
+
+        if !telegraph.released && telegraph.elapsed >= telegraph.definition.wind_up {
+            telegraph.released = true;
+            telegraph.just_released = true;
+            events.send(TelegraphEvent::Released { attacker, target: telegraph.target, telegraph_id: id.clone() });
+        }
+
+        if telegraph.released && !telegraph.just_released && telegraph.elapsed >= telegraph.definition.end() {
+            if let Some(target) = telegraph.target {
+                if window_query.get(target).is_ok_and(|window| window.attacker == attacker) {
+                    commands.entity(target).remove::<DodgeableWindow>();
+                }
+            }
+            commands.entity(attacker).remove::<ActiveTelegraph>();
+            events.send(TelegraphEvent::Finished { attacker, telegraph_id: id });
+        }
+    }
+}
+
+type TelegraphingAi<'a> = (
+    Entity,
+    &'a GlobalTransform,
+    &'a mut AttackTelegraphs,
+    &'a mut InputState,
+    &'a crate::ai::AiController,
+    Option<&'a ActiveTelegraph>,
+);
+
+/// System to turn AI attack presses into telegraphs, holding the press back until the wind-up ends
+pub fn telegraph_ai_attacks(
+    mut commands: Commands,
+    settings: Res<TelegraphSettings>,
+    mut events: ResMut<TelegraphEventQueue>,
+    mut query: Query<TelegraphingAi>,
+    transform_query: Query<&GlobalTransform>,
+) {
+    for (attacker, transform, mut telegraphs, mut input, ai, active) in query.iter_mut() {
+        if !telegraphs.telegraph_ai_attacks {
+            continue;
+        }
+        if let Some(active) = active {
+            let telegraphed = active.definition.input;
+            telegraphed.set(&mut input, active.just_released);
+            continue;
+        }
+        if !settings.enabled {
+            continue;
+        }
+        let pressed = telegraphs
+            .telegraphs
+            .get(telegraphs.next % telegraphs.telegraphs.len().max(1))
+            .is_some_and(|next| next.input.get(&input));
+        if !pressed {
+            continue;
+        }
+        let Some(definition) = telegraphs.advance() else { continue };
+        definition.input.set(&mut input, false);
+
+        let target_position = ai.target.and_then(|target| transform_query.get(target).ok()).map(|t| t.translation());
+        events.send(TelegraphEvent::Started {
+            attacker,
+            target: ai.target,
+            telegraph_id: definition.id.clone(),
+            wind_up: definition.wind_up,
+        });
+        commands.entity(attacker).insert(begin_telegraph(definition, transform, ai.target, target_position));
+    }
+}
+
+/// Assets telegraph decals and cues are made from
+#[derive(SystemParam)]
+pub struct TelegraphAssets<'w> {
+    pub asset_server: Res<'w, AssetServer>,
+    pub meshes: ResMut<'w, Assets<Mesh>>,
+    pub materials: ResMut<'w, Assets<StandardMaterial>>,
+}
+
+/// System to draw telegraph decals, flash attackers and play wind-up cues
+pub fn render_attack_telegraphs(
+    mut commands: Commands,
+    settings: Res<TelegraphSettings>,
+    assets: TelegraphAssets,
+    started_query: Query<(Entity, &ActiveTelegraph), Added<ActiveTelegraph>>,
+    telegraph_query: Query<&ActiveTelegraph>,
+    decal_query: Query<(Entity, &TelegraphDecal, &MeshMaterial3d<StandardMaterial>)>,
+    mut flash_query: Query<(Entity, &ChildOf, &mut PointLight), With<TelegraphFlash>>,
+) {
+    let TelegraphAssets { asset_server, mut meshes, mut materials } = assets;
+    for (attacker, telegraph) in started_query.iter() {
+        let definition = &telegraph.definition;
+
+        if settings.show_decals {
+            let flat = Quat::from_rotation_x(-FRAC_PI_2);
+            let facing = Quat::from_rotation_arc(Vec3::NEG_Z, telegraph.direction);
+            let shape = match definition.shape {
+                TelegraphShape::None => None,
+                TelegraphShape::Circle { radius } => Some((Mesh::from(Circle::new(radius)), telegraph.origin)),
+                TelegraphShape::Cone { radius, angle } => Some((
+                    Mesh::from(CircularSector::new(radius, (angle * 0.5).to_radians())),
+                    telegraph.origin,
+                )),
+                TelegraphShape::Line { length, width } => Some((
+                    Mesh::from(Rectangle::new(width, length)),
+                    telegraph.origin + telegraph.direction * length * 0.5,
+                )),
+            };
+            if let Some((mesh, center)) = shape {
+                commands.spawn((
+                    Mesh3d(meshes.add(mesh)),
+                    MeshMaterial3d(materials.add(StandardMaterial {
+                        base_color: definition.decal_color.with_alpha(0.0),
+                        alpha_mode: AlphaMode::Blend,
+                        unlit: true,
+                        ..default()
+                    })),
+                    Transform::from_translation(center + Vec3::Y * 0.02).with_rotation(facing * flat),
+                    TelegraphDecal { attacker },
+                    Name::new("Telegraph Decal"),
+                ));
+            }
+        }
+
+        if settings.flash_intensity > 0.0 {
+            commands.entity(attacker).with_children(|parent| {
+                parent.spawn((
+                    PointLight {
+                        color: definition.flash_color,
+                        intensity: 0.0,
+                        range: 4.0,
+                        shadows_enabled: false,
+                        ..default()
+                    },
+                    Transform::from_xyz(0.0, 1.2, 0.0),
+                    TelegraphFlash,
+                ));
+            });
+        }
+
+        if settings.play_audio && !definition.audio_cue.is_empty() {
+            commands.spawn((
+                AudioPlayer::<AudioSource>(asset_server.load(definition.audio_cue.clone())),
+                PlaybackSettings::DESPAWN.with_spatial(true),
+                Transform::from_translation(telegraph.origin),
+            ));
+        }
+    }
+
+    for (decal, owner, material) in decal_query.iter() {
+        let Ok(telegraph) = telegraph_query.get(owner.attacker) else {
+            commands.entity(decal).despawn();
+            continue;
+        };
+        if let Some(material) = materials.get_mut(&material.0) {
+            // Fades in over the wind-up so the last moment before the hit reads clearly
+            let alpha = telegraph.definition.decal_color.alpha() * (0.3 + 0.7 * telegraph.progress());
+            material.base_color = telegraph.definition.decal_color.with_alpha(alpha);
+        }
+    }
+
+    for (flash, parent, mut light) in flash_query.iter_mut() {
+        let Ok(telegraph) = telegraph_query.get(parent.parent()) else {
+            commands.entity(flash).despawn();
+            continue;
+        };
+        // Pulses faster as the hit gets closer
+        let progress = telegraph.progress();
+        let pulse = (telegraph.elapsed * (6.0 + 18.0 * progress)).sin() * 0.5 + 0.5;
+        light.intensity = settings.flash_intensity * pulse * progress.max(0.2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::Health;
+
+    #[test]
+    fn test_attack_telegraph_dodge_window() {
+        let slam = TelegraphDefinition {
+            id: "slam".to_string(),
+            shape: TelegraphShape::Circle { radius: 4.0 },
+            on_target: true,
+            wind_up: 1.0,
+            dodge_window: 0.25,
+            dodge_grace: 0.15,
+            ..Default::default()
+        };
+        let mut telegraphs = AttackTelegraphs {
+            telegraphs: vec![slam.clone(), TelegraphDefinition { id: "swipe".to_string(), ..Default::default() }],
+            ..Default::default()
+        };
+        assert_eq!(telegraphs.get("slam").map(|t| t.wind_up), Some(1.0));
+        assert_eq!(telegraphs.advance().map(|t| t.id), Some("slam".to_string()));
+        assert_eq!(telegraphs.advance().map(|t| t.id), Some("swipe".to_string()));
+        assert_eq!(telegraphs.advance().map(|t| t.id), Some("slam".to_string()));
+
+        // The window opens a quarter second before the hit and stays open briefly after it
+        let mut active = ActiveTelegraph { definition: slam, elapsed: 0.5, ..Default::default() };
+        assert_eq!(active.progress(), 0.5);
+        assert!(!active.is_dodge_window_open());
+        active.elapsed = 0.8;
+        assert!(active.is_dodge_window_open());
+        active.elapsed = 1.1;
+        assert!(active.is_dodge_window_open());
+        active.elapsed = 1.2;
+        assert!(!active.is_dodge_window_open());
+
+        let mut health = Health::default();
+        assert!(!try_dodge(None, &mut health, 0.4));
+        let window = DodgeableWindow { remaining: 0.2, ..Default::default() };
+        assert!(try_dodge(Some(&window), &mut health, 0.4));
+        assert_eq!(health.temporal_invincibility_timer, 0.4);
+    }
+}
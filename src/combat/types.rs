@@ -1,4 +1,21 @@
 use bevy::prelude::*;
+use crate::input::InputBinding;
+use crate::utils::EventQueue;
+
+/// Combat phases, run in order inside `GameControllerSet::Combat`
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CombatSet {
+    /// Combat timers ticked
+    Prepare,
+    /// Decals, impact FX and slicing driven by damage results
+    Effects,
+    /// Melee, blocking, regeneration and thrown weapons
+    Attacks,
+    /// Stats sync and `DamageEventQueue` processing
+    DamageResolution,
+    /// Damage UI, damage over time, deaths and area effects
+    Aftermath,
+}
 
 /// Health component enhanced with professional features.
 #[derive(Component, Debug, Reflect)]
@@ -139,9 +156,8 @@ pub struct DamageEvent {
     pub ignore_shield: bool,
 }
 
-/// Custom queue for damage events.
-#[derive(Resource, Default)]
-pub struct DamageEventQueue(pub Vec<DamageEvent>);
+/// Queue for damage events, read by `process_damage_events` and effect systems.
+pub type DamageEventQueue = EventQueue<DamageEvent>;
 
 /// Death event data.
 #[derive(Debug, Clone, Copy, Event)]
@@ -149,9 +165,8 @@ pub struct DeathEvent {
     pub entity: Entity,
 }
 
-/// Custom queue for death events.
-#[derive(Resource, Default)]
-pub struct DeathEventQueue(pub Vec<DeathEvent>);
+/// Queue for death events; every death handler sees each death.
+pub type DeathEventQueue = EventQueue<DeathEvent>;
 
 #[derive(Debug, Clone)]
 pub struct MeleeHitboxEvent {
@@ -159,8 +174,7 @@ pub struct MeleeHitboxEvent {
     pub active: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct MeleeHitboxEventQueue(pub Vec<MeleeHitboxEvent>);
+pub type MeleeHitboxEventQueue = EventQueue<MeleeHitboxEvent>;
 
 
 /// Damage type enumeration.
@@ -351,6 +365,55 @@ impl Default for MeleeWeaponTransformData {
     }
 }
 
+/// How an attack is started
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum AttackTrigger {
+    /// Next step of the combo on attack press
+    #[default]
+    Combo,
+    /// Attack input held, released to strike
+    Charged,
+    /// Attack press while blocking
+    ShieldBash,
+    /// Special-move input
+    WeaponArt,
+    /// Chord or sequence input matching the attack's `input` (quarter-circle + attack)
+    InputCombo,
+}
+
+/// Charge-up of a held attack
+#[derive(Debug, Clone, Reflect)]
+pub struct AttackCharge {
+    /// Hold time before a release strikes with this attack instead of the combo
+    pub min_time: f32,
+    /// Hold time for full power
+    pub max_time: f32,
+    /// Damage multiplier at full charge, from 1.0 at `min_time`
+    pub max_damage_multiplier: f32,
+    /// Movement speed factor while charging
+    pub move_speed_multiplier: f32,
+}
+
+impl Default for AttackCharge {
+    fn default() -> Self {
+        Self {
+            min_time: 0.35,
+            max_time: 1.2,
+            max_damage_multiplier: 2.5,
+            move_speed_multiplier: 0.4,
+        }
+    }
+}
+
+impl AttackCharge {
+    /// Damage multiplier after holding for `held` seconds
+    pub fn power(&self, held: f32) -> f32 {
+        let span = (self.max_time - self.min_time).max(0.001);
+        let t = ((held - self.min_time) / span).clamp(0.0, 1.0);
+        1.0 + (self.max_damage_multiplier - 1.0) * t
+    }
+}
+
 #[derive(Debug, Clone, Reflect)]
 pub struct AttackDefinition {
     pub name: String,
@@ -361,6 +424,17 @@ pub struct AttackDefinition {
     pub hitbox_end: f32,
     pub combo_window: f32,
     pub animation_clip: String,
+    pub trigger: AttackTrigger,
+    /// Charge-up for `AttackTrigger::Charged` attacks
+    pub charge: Option<AttackCharge>,
+    /// Seconds hit targets are staggered
+    pub stagger: f32,
+    /// Impulse pushing hit targets away
+    pub knockback: f32,
+    /// Seconds before the attack can be used again
+    pub cooldown: f32,
+    /// Chord or sequence of `AttackTrigger::InputCombo` attacks, bound to an action in the `InputMap`
+    pub input: Option<InputBinding>,
 }
 
 impl Default for AttackDefinition {
@@ -374,6 +448,50 @@ impl Default for AttackDefinition {
             hitbox_end: 0.35,
             combo_window: 0.25,
             animation_clip: String::new(),
+            trigger: AttackTrigger::Combo,
+            charge: None,
+            stagger: 0.0,
+            knockback: 0.0,
+            cooldown: 0.0,
+            input: None,
+        }
+    }
+}
+
+impl AttackDefinition {
+    /// Shield bash: short range, little damage, staggers and pushes back
+    pub fn shield_bash() -> Self {
+        Self {
+            name: "Shield Bash".to_string(),
+            damage_multiplier: 0.5,
+            range: 1.5,
+            duration: 0.5,
+            hitbox_start: 0.1,
+            hitbox_end: 0.25,
+            combo_window: 0.0,
+            trigger: AttackTrigger::ShieldBash,
+            stagger: 1.0,
+            knockback: 6.0,
+            cooldown: 2.0,
+            ..default()
+        }
+    }
+
+    /// Heavy attack charged by holding the attack input
+    pub fn heavy(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            damage_multiplier: 1.5,
+            range: 2.2,
+            duration: 0.9,
+            hitbox_start: 0.3,
+            hitbox_end: 0.5,
+            combo_window: 0.0,
+            trigger: AttackTrigger::Charged,
+            charge: Some(AttackCharge::default()),
+            stagger: 0.6,
+            knockback: 3.0,
+            ..default()
         }
     }
 }
@@ -382,6 +500,8 @@ impl Default for AttackDefinition {
 pub struct AttackChain {
     pub id: String,
     pub attacks: Vec<AttackDefinition>,
+    /// Charged attacks, shield bashes and weapon arts of this weapon
+    pub special_moves: Vec<AttackDefinition>,
 }
 
 impl Default for AttackChain {
@@ -389,20 +509,52 @@ impl Default for AttackChain {
         Self {
             id: "Default".to_string(),
             attacks: Vec::new(),
+            special_moves: Vec::new(),
         }
     }
 }
 
-#[derive(Resource, Debug, Default, Reflect)]
+#[derive(Resource, Debug, Reflect)]
 #[reflect(Resource)]
 pub struct AttackDatabase {
     pub chains: Vec<AttackChain>,
+    /// Special moves available with any weapon whose chain lacks one for the trigger
+    pub special_moves: Vec<AttackDefinition>,
+}
+
+impl Default for AttackDatabase {
+    fn default() -> Self {
+        Self {
+            chains: Vec::new(),
+            special_moves: vec![AttackDefinition::shield_bash()],
+        }
+    }
 }
 
 impl AttackDatabase {
     pub fn get_chain(&self, id: &str) -> Option<&AttackChain> {
         self.chains.iter().find(|chain| chain.id == id)
     }
+
+    /// Special move for `trigger`, from the first listed chain that has one, else the shared ones
+    pub fn special_move<'a>(&self, chain_ids: impl IntoIterator<Item = &'a str>, trigger: AttackTrigger) -> Option<&AttackDefinition> {
+        chain_ids
+            .into_iter()
+            .filter_map(|id| self.get_chain(id))
+            .flat_map(|chain| chain.special_moves.iter())
+            .chain(self.special_moves.iter())
+            .find(|attack| attack.trigger == trigger)
+    }
+
+    /// `AttackTrigger::InputCombo` special move performed with `input`
+    pub fn combo_special_move<'a>(&self, chain_ids: impl IntoIterator<Item = &'a str>, input: &InputBinding) -> Option<&AttackDefinition> {
+        chain_ids
+            .into_iter()
+            .filter_map(|id| self.get_chain(id))
+            .flat_map(|chain| chain.special_moves.iter())
+            .chain(self.special_moves.iter())
+            .find(|attack| attack.trigger == AttackTrigger::InputCombo && attack.input.as_ref() == Some(input))
+    }
 }
 
 #[derive(Component, Debug, Reflect)]
@@ -478,3 +630,5 @@ pub struct DamageNumber {
     pub lifetime: f32,
     pub velocity: Vec3,
 }
+
+impl crate::utils::Poolable for DamageNumber {}
@@ -0,0 +1,240 @@
+//! Underwater combat rules
+//!
+//! Characters whose head is below a water surface (`Swim::is_underwater`) get
+//! [`Submerged`]. While submerged, weapon types listed in
+//! [`UnderwaterCombatRules::blocked_weapon_types`] can't fire unless the weapon is
+//! `usable_underwater` (harpoons), and the weapon manager switches to such a
+//! weapon when there is one. Melee attacks run slower, projectiles slow down in
+//! water through `BallisticsEnvironment::water_density`, and AI out of the water
+//! loses sight of submerged targets beyond
+//! [`UnderwaterCombatRules::underwater_detection_range`], searching where they dove.
+
+use bevy::prelude::*;
+use crate::ai::{AiBehaviorState, AiController};
+use crate::player::extra_movements::swim::Swim;
+use crate::weapons::{Weapon, WeaponManager, WeaponType};
+
+/// Character with its head below a water surface
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Submerged;
+
+/// Weapon selection to restore when the character surfaces
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct UnderwaterWeaponSwap {
+    pub previous_index: usize,
+}
+
+/// Combat restrictions applied to submerged characters
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct UnderwaterCombatRules {
+    pub enabled: bool,
+    /// Weapon types that can't fire underwater, unless `Weapon::usable_underwater`
+    pub blocked_weapon_types: Vec<WeaponType>,
+    /// Switch to a weapon usable underwater when submerging, back when surfacing
+    pub switch_to_underwater_weapon: bool,
+    /// Melee attack speed factor (0.5 = attacks take twice as long)
+    pub melee_speed_multiplier: f32,
+    /// Distance within which AI out of the water still sees submerged targets
+    pub underwater_detection_range: f32,
+}
+
+impl Default for UnderwaterCombatRules {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            blocked_weapon_types: vec![
+                WeaponType::Pistol,
+                WeaponType::Rifle,
+                WeaponType::Shotgun,
+                WeaponType::Bow,
+                WeaponType::Thrown,
+            ],
+            switch_to_underwater_weapon: true,
+            melee_speed_multiplier: 0.6,
+            underwater_detection_range: 6.0,
+        }
+    }
+}
+
+impl UnderwaterCombatRules {
+    pub fn allows_weapon(&self, weapon: &Weapon, submerged: bool) -> bool {
+        !self.enabled || !submerged || weapon.usable_underwater || !self.blocked_weapon_types.contains(&weapon.weapon_type)
+    }
+
+    /// Factor applied to melee timers
+    pub fn melee_time_scale(&self, submerged: bool) -> f32 {
+        if self.enabled && submerged {
+            self.melee_speed_multiplier.max(0.01)
+        } else {
+            1.0
+        }
+    }
+
+    /// Whether an observer sees a target, given who is underwater
+    pub fn can_detect(&self, observer_submerged: bool, target_submerged: bool, distance: f32) -> bool {
+        !self.enabled || observer_submerged || !target_submerged || distance <= self.underwater_detection_range
+    }
+}
+
+/// Keeps `Submerged` in sync with the swim state
+pub fn update_submerged_state(
+    mut commands: Commands,
+    query: Query<(Entity, &Swim, Has<Submerged>)>,
+) {
+    for (entity, swim, submerged) in query.iter() {
+        if swim.is_underwater && !submerged {
+            commands.entity(entity).insert(Submerged);
+        } else if !swim.is_underwater && submerged {
+            commands.entity(entity).remove::<Submerged>();
+        }
+    }
+}
+
+/// Switches to a weapon usable underwater on submerging and back on surfacing
+pub fn swap_weapons_underwater(
+    mut commands: Commands,
+    rules: Res<UnderwaterCombatRules>,
+    mut manager_query: Query<(Entity, &mut WeaponManager, Has<Submerged>, Option<&UnderwaterWeaponSwap>)>,
+    weapon_query: Query<&Weapon>,
+) {
+    for (entity, mut manager, submerged, swap) in manager_query.iter_mut() {
+        match swap {
+            None if submerged && rules.enabled && rules.switch_to_underwater_weapon => {
+                let usable = |index: usize| {
+                    manager
+                        .weapons_list
+                        .get(index)
+                        .and_then(|weapon| weapon_query.get(*weapon).ok())
+                        .is_some_and(|weapon| weapon.enabled && rules.allows_weapon(weapon, true))
+                };
+                if manager.weapons_list.is_empty() || usable(manager.current_index) {
+                    continue;
+                }
+
+                // Prefer dedicated underwater weapons over allowed types (melee)
+                let replacement = (0..manager.weapons_list.len())
+                    .filter(|index| usable(*index))
+                    .max_by_key(|index| {
+                        weapon_query
+                            .get(manager.weapons_list[*index])
+                            .is_ok_and(|weapon| weapon.usable_underwater)
+                    });
+                let Some(replacement) = replacement else { continue };
+
+                commands.entity(entity).insert(UnderwaterWeaponSwap { previous_index: manager.current_index });
+                select_weapon(&mut manager, replacement);
+            }
+            Some(swap) if !submerged => {
+                let previous_index = swap.previous_index;
+                commands.entity(entity).remove::<UnderwaterWeaponSwap>();
+                if previous_index < manager.weapons_list.len() && previous_index != manager.current_index {
+                    select_weapon(&mut manager, previous_index);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn select_weapon(manager: &mut WeaponManager, index: usize) {
+    manager.current_index = index;
+    manager.choosed_weapon = index;
+    if manager.carrying_weapon_in_third_person || manager.carrying_weapon_in_first_person {
+        manager.changing_weapon = true;
+        manager.keeping_weapon = false;
+    }
+}
+
+/// AI out of the water loses targets that dive out of reach and searches where they went under
+pub fn lose_submerged_targets(
+    rules: Res<UnderwaterCombatRules>,
+    submerged_query: Query<(Entity, &GlobalTransform), Added<Submerged>>,
+    mut ai_query: Query<(&GlobalTransform, &mut AiController, Has<Submerged>)>,
+) {
+    if !rules.enabled {
+        return;
+    }
+
+    for (target, target_transform) in submerged_query.iter() {
+        let target_position = target_transform.translation();
+        for (transform, mut ai, ai_submerged) in ai_query.iter_mut() {
+            if ai.target != Some(target) || ai.state == AiBehaviorState::Dead {
+                continue;
+            }
+            let distance = transform.translation().distance(target_position);
+            if rules.can_detect(ai_submerged, true, distance) {
+                continue;
+            }
+
+            // Perception reacquires the target once it is visible again
+            ai.target = None;
+            ai.target_last_position = Some(target_position);
+            ai.suspicion_timer = ai.max_suspicion_time;
+            ai.state = AiBehaviorState::Suspect;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weapon(app: &mut App, weapon_type: WeaponType, usable_underwater: bool) -> Entity {
+        app.world_mut().spawn(Weapon { weapon_type, usable_underwater, enabled: true, ..default() }).id()
+    }
+
+    #[test]
+    fn test_diving_swaps_weapons_and_shakes_distant_ai() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<UnderwaterCombatRules>()
+            .add_systems(Update, (
+                update_submerged_state,
+                swap_weapons_underwater,
+                lose_submerged_targets,
+            ).chain());
+
+        let weapons_list = vec![
+            weapon(&mut app, WeaponType::Rifle, false),
+            weapon(&mut app, WeaponType::Melee, false),
+            weapon(&mut app, WeaponType::Rifle, true),
+        ];
+        let diver = app.world_mut().spawn((
+            Swim::default(),
+            WeaponManager { weapons_list, current_index: 0, ..default() },
+            GlobalTransform::from_xyz(0.0, -2.0, 0.0),
+        )).id();
+        let far_ai = app.world_mut().spawn((
+            AiController { target: Some(diver), state: AiBehaviorState::Chase, ..default() },
+            GlobalTransform::from_xyz(20.0, 0.0, 0.0),
+        )).id();
+        let near_ai = app.world_mut().spawn((
+            AiController { target: Some(diver), state: AiBehaviorState::Chase, ..default() },
+            GlobalTransform::from_xyz(3.0, 0.0, 0.0),
+        )).id();
+        app.update();
+        assert!(app.world().get::<Submerged>(diver).is_none());
+
+        // Diving: the rifle is blocked, the harpoon beats the knife
+        app.world_mut().get_mut::<Swim>(diver).unwrap().is_underwater = true;
+        app.update();
+        assert!(app.world().get::<Submerged>(diver).is_some());
+        assert_eq!(app.world().get::<WeaponManager>(diver).unwrap().current_index, 2);
+        assert_eq!(app.world().get::<UnderwaterWeaponSwap>(diver).unwrap().previous_index, 0);
+
+        let far = app.world().get::<AiController>(far_ai).unwrap();
+        assert_eq!((far.target, far.state), (None, AiBehaviorState::Suspect));
+        assert_eq!(far.target_last_position, Some(Vec3::new(0.0, -2.0, 0.0)));
+        assert_eq!(app.world().get::<AiController>(near_ai).unwrap().target, Some(diver));
+
+        // Surfacing restores the rifle
+        app.world_mut().get_mut::<Swim>(diver).unwrap().is_underwater = false;
+        app.update();
+        assert!(app.world().get::<Submerged>(diver).is_none());
+        assert!(app.world().get::<UnderwaterWeaponSwap>(diver).is_none());
+        assert_eq!(app.world().get::<WeaponManager>(diver).unwrap().current_index, 0);
+    }
+}
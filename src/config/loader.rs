@@ -0,0 +1,51 @@
+use std::fmt;
+use bevy::asset::{io::Reader, AssetLoader, LoadContext};
+use super::types::GameControllerConfig;
+
+/// Reads [`GameControllerConfig`] assets from `.controller.ron` files
+#[derive(Default)]
+pub struct GameControllerConfigLoader;
+
+#[derive(Debug)]
+pub enum GameControllerConfigError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for GameControllerConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not read game controller config: {}", error),
+            Self::Ron(error) => write!(f, "Invalid game controller config: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for GameControllerConfigError {}
+
+impl From<std::io::Error> for GameControllerConfigError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl AssetLoader for GameControllerConfigLoader {
+    type Asset = GameControllerConfig;
+    type Settings = ();
+    type Error = GameControllerConfigError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        ron::de::from_bytes(&bytes).map_err(GameControllerConfigError::Ron)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["controller.ron"]
+    }
+}
@@ -0,0 +1,52 @@
+//! Data-driven configuration
+//!
+//! Tuning for the camera, player movement, stealth, weapons and AI read from a
+//! [`GameControllerConfig`] RON asset instead of the `Default` impls. Only the
+//! fields present in the file are changed. The config is applied once loaded, to
+//! controllers spawned later, and again whenever the file changes on disk (with
+//! the `config_hot_reload` feature).
+//!
+//! ```rust,ignore
+//! App::new()
+//!     .add_plugins(DefaultPlugins)
+//!     .add_plugins(GameControllerPlugin::default().with_config("game.controller.ron"));
+//! ```
+//!
+//! `assets/game.controller.ron`:
+//!
+//! ```ron
+//! (
+//!     camera: (distance: Some(5.0), default_fov: Some(70.0)),
+//!     movement: (walk_speed: Some(3.0), sprint_speed: Some(9.0)),
+//!     weapons: (wind: Some((2.0, 0.0, 0.0))),
+//!     ai: (vision_range: Some(30.0)),
+//! )
+//! ```
+
+pub mod types;
+pub mod loader;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use loader::*;
+use systems::*;
+
+pub use types::{
+    GameControllerConfig, CameraConfig, MovementConfig, StealthConfig, WeaponsConfig, AiConfig,
+    ActiveGameControllerConfig,
+};
+pub use loader::{GameControllerConfigLoader, GameControllerConfigError};
+
+pub struct GameControllerConfigPlugin;
+
+impl Plugin for GameControllerConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<GameControllerConfig>()
+            .init_asset_loader::<GameControllerConfigLoader>()
+            .init_resource::<ActiveGameControllerConfig>()
+            .add_systems(Startup, load_game_controller_config)
+            .add_systems(Update, apply_game_controller_config.before(crate::GameControllerSet::Input));
+    }
+}
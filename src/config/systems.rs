@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use super::types::*;
+use crate::ai::{AiController, AiPerception};
+use crate::camera::CameraController;
+use crate::character::{CharacterController, Player};
+use crate::weapons::{AimAssistSettings, BallisticsEnvironment};
+
+/// System to start loading the config file set on the plugin
+pub fn load_game_controller_config(
+    asset_server: Res<AssetServer>,
+    mut active: ResMut<ActiveGameControllerConfig>,
+) {
+    if active.handle.is_some() {
+        return;
+    }
+    if let Some(path) = active.path.clone() {
+        active.handle = Some(asset_server.load(path));
+    }
+}
+
+/// Camera, player and AI components the config applies to
+#[derive(SystemParam)]
+pub struct ConfiguredControllers<'w, 's> {
+    pub cameras: Query<'w, 's, &'static mut CameraController>,
+    pub players: Query<'w, 's, &'static mut CharacterController, With<Player>>,
+    pub ai_controllers: Query<'w, 's, &'static mut AiController>,
+    pub ai_perceptions: Query<'w, 's, &'static mut AiPerception>,
+}
+
+/// Applies the active config once loaded, again on every hot reload, and to
+/// controllers spawned afterwards
+pub fn apply_game_controller_config(
+    mut asset_events: MessageReader<AssetEvent<GameControllerConfig>>,
+    active: Res<ActiveGameControllerConfig>,
+    configs: Res<Assets<GameControllerConfig>>,
+    ballistics: Option<ResMut<BallisticsEnvironment>>,
+    aim_assist: Option<ResMut<AimAssistSettings>>,
+    controllers: ConfiguredControllers,
+    #[cfg(feature = "stealth")]
+    mut stealth_controllers: Query<&mut crate::stealth::StealthController>,
+) {
+    let ConfiguredControllers { mut cameras, mut players, mut ai_controllers, mut ai_perceptions } = controllers;
+    let Some(handle) = active.handle.as_ref() else {
+        asset_events.clear();
+        return;
+    };
+
+    let mut reload = active.is_changed();
+    for event in asset_events.read() {
+        if event.is_loaded_with_dependencies(handle.id()) || event.is_modified(handle.id()) {
+            reload = true;
+        }
+    }
+
+    let Some(config) = configs.get(handle) else { return };
+
+    if reload {
+        if let Some(mut ballistics) = ballistics {
+            config.weapons.apply_ballistics(&mut ballistics);
+        }
+        if let Some(mut aim_assist) = aim_assist {
+            config.weapons.apply_aim_assist(&mut aim_assist);
+        }
+        info!("Applied game controller config {:?}", active.path);
+    }
+
+    for mut camera in cameras.iter_mut() {
+        if reload || camera.is_added() {
+            config.camera.apply(&mut camera);
+        }
+    }
+    for mut controller in players.iter_mut() {
+        if reload || controller.is_added() {
+            config.movement.apply(&mut controller);
+        }
+    }
+    for mut controller in ai_controllers.iter_mut() {
+        if reload || controller.is_added() {
+            config.ai.apply_controller(&mut controller);
+        }
+    }
+    for mut perception in ai_perceptions.iter_mut() {
+        if reload || perception.is_added() {
+            config.ai.apply_perception(&mut perception);
+        }
+    }
+    #[cfg(feature = "stealth")]
+    for mut stealth in stealth_controllers.iter_mut() {
+        if reload || stealth.is_added() {
+            config.stealth.apply(&mut stealth);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameControllerConfigPlugin;
+
+    const CONFIG: &str = r#"(
+        camera: (distance: Some(5.0), default_fov: Some(70.0)),
+        movement: (walk_speed: Some(3.0), sprint_speed: Some(9.0)),
+        weapons: (wind: Some((2.0, 0.0, 0.0))),
+    )"#;
+
+    #[test]
+    fn test_config_applies_to_new_controllers_and_on_reload() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default(), GameControllerConfigPlugin))
+            .init_resource::<BallisticsEnvironment>();
+        let camera = app.world_mut().spawn(CameraController::default()).id();
+        app.update();
+        assert_eq!(app.world().get::<CameraController>(camera).unwrap().distance, CameraController::default().distance);
+
+        let config: GameControllerConfig = ron::de::from_str(CONFIG).unwrap();
+        let handle = app.world_mut().resource_mut::<Assets<GameControllerConfig>>().add(config);
+        app.world_mut().resource_mut::<ActiveGameControllerConfig>().handle = Some(handle.clone());
+        app.update();
+
+        let controller = app.world().get::<CameraController>(camera).unwrap();
+        assert_eq!((controller.distance, controller.base_distance), (5.0, 5.0));
+        assert_eq!((controller.default_fov, controller.base_fov), (70.0, 70.0));
+        let ballistics = app.world().resource::<BallisticsEnvironment>();
+        assert_eq!(ballistics.wind, Vec3::new(2.0, 0.0, 0.0));
+        // Fields missing from the file keep their values
+        assert_eq!(ballistics.gravity, BallisticsEnvironment::default().gravity);
+
+        // Controllers spawned later get the config too, once
+        let player = app.world_mut().spawn((Player, CharacterController::default())).id();
+        app.update();
+        assert_eq!(app.world().get::<CharacterController>(player).unwrap().sprint_speed, 9.0);
+        app.world_mut().get_mut::<CharacterController>(player).unwrap().walk_speed = 1.0;
+        app.update();
+        assert_eq!(app.world().get::<CharacterController>(player).unwrap().walk_speed, 1.0);
+
+        // Editing the asset (as a hot reload does) applies it everywhere again
+        app.world_mut()
+            .resource_mut::<Assets<GameControllerConfig>>()
+            .get_mut(&handle)
+            .unwrap()
+            .camera
+            .distance = Some(8.0);
+        app.update();
+        app.update();
+        assert_eq!(app.world().get::<CameraController>(camera).unwrap().distance, 8.0);
+        assert_eq!(app.world().get::<CharacterController>(player).unwrap().walk_speed, 3.0);
+    }
+}
@@ -0,0 +1,203 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::ai::{AiController, AiPerception};
+use crate::camera::CameraController;
+use crate::character::CharacterController;
+use crate::weapons::{AimAssistSettings, BallisticsEnvironment};
+
+/// Overwrites `field` when the config sets a value
+fn set<T: Clone>(field: &mut T, value: &Option<T>) {
+    if let Some(value) = value {
+        *field = value.clone();
+    }
+}
+
+/// Game tuning loaded from a `.controller.ron` asset.
+///
+/// Every section and field is optional; anything left out keeps its current
+/// (`Default`) value, so a file only lists what it changes.
+#[derive(Asset, TypePath, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameControllerConfig {
+    pub camera: CameraConfig,
+    pub movement: MovementConfig,
+    pub stealth: StealthConfig,
+    pub weapons: WeaponsConfig,
+    pub ai: AiConfig,
+}
+
+/// Applied to every [`CameraController`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CameraConfig {
+    pub rot_sensitivity_3p: Option<f32>,
+    pub rot_sensitivity_1p: Option<f32>,
+    pub aim_zoom_sensitivity_mult: Option<f32>,
+    pub min_vertical_angle: Option<f32>,
+    pub max_vertical_angle: Option<f32>,
+    pub distance: Option<f32>,
+    pub min_distance: Option<f32>,
+    pub max_distance: Option<f32>,
+    pub smooth_follow_speed: Option<f32>,
+    pub smooth_rotation_speed: Option<f32>,
+    pub default_fov: Option<f32>,
+    pub aim_fov: Option<f32>,
+    pub use_collision: Option<bool>,
+    pub collision_radius: Option<f32>,
+}
+
+impl CameraConfig {
+    pub fn apply(&self, camera: &mut CameraController) {
+        set(&mut camera.rot_sensitivity_3p, &self.rot_sensitivity_3p);
+        set(&mut camera.rot_sensitivity_1p, &self.rot_sensitivity_1p);
+        set(&mut camera.aim_zoom_sensitivity_mult, &self.aim_zoom_sensitivity_mult);
+        set(&mut camera.min_vertical_angle, &self.min_vertical_angle);
+        set(&mut camera.max_vertical_angle, &self.max_vertical_angle);
+        set(&mut camera.min_distance, &self.min_distance);
+        set(&mut camera.max_distance, &self.max_distance);
+        set(&mut camera.smooth_follow_speed, &self.smooth_follow_speed);
+        set(&mut camera.smooth_rotation_speed, &self.smooth_rotation_speed);
+        set(&mut camera.aim_fov, &self.aim_fov);
+        set(&mut camera.use_collision, &self.use_collision);
+        set(&mut camera.collision_radius, &self.collision_radius);
+
+        // Zones restore the baseline values when the camera leaves them
+        set(&mut camera.distance, &self.distance);
+        set(&mut camera.base_distance, &self.distance);
+        set(&mut camera.default_fov, &self.default_fov);
+        set(&mut camera.base_fov, &self.default_fov);
+    }
+}
+
+/// Applied to the player's [`CharacterController`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MovementConfig {
+    pub walk_speed: Option<f32>,
+    pub run_speed: Option<f32>,
+    pub sprint_speed: Option<f32>,
+    pub crouch_speed: Option<f32>,
+    pub turn_speed: Option<f32>,
+    pub jump_power: Option<f32>,
+    pub acceleration: Option<f32>,
+    pub deceleration: Option<f32>,
+    pub fall_damage_enabled: Option<bool>,
+    pub falling_damage_multiplier: Option<f32>,
+}
+
+impl MovementConfig {
+    pub fn apply(&self, controller: &mut CharacterController) {
+        set(&mut controller.walk_speed, &self.walk_speed);
+        set(&mut controller.run_speed, &self.run_speed);
+        set(&mut controller.sprint_speed, &self.sprint_speed);
+        set(&mut controller.crouch_speed, &self.crouch_speed);
+        set(&mut controller.turn_speed, &self.turn_speed);
+        set(&mut controller.jump_power, &self.jump_power);
+        set(&mut controller.acceleration, &self.acceleration);
+        set(&mut controller.deceleration, &self.deceleration);
+        set(&mut controller.fall_damage_enabled, &self.fall_damage_enabled);
+        set(&mut controller.falling_damage_multiplier, &self.falling_damage_multiplier);
+    }
+}
+
+/// Applied to every `StealthController` (ignored without the `stealth` feature)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StealthConfig {
+    pub cover_detection_distance: Option<f32>,
+    pub cover_detection_angle: Option<f32>,
+    pub character_need_to_crouch: Option<bool>,
+    pub hidden_for_a_time: Option<bool>,
+    pub hidden_for_a_time_amount: Option<f32>,
+    pub time_delay_to_hide_again_if_discovered: Option<f32>,
+    pub hidden_fov: Option<f32>,
+}
+
+#[cfg(feature = "stealth")]
+impl StealthConfig {
+    pub fn apply(&self, stealth: &mut crate::stealth::StealthController) {
+        set(&mut stealth.cover_detection_distance, &self.cover_detection_distance);
+        set(&mut stealth.cover_detection_angle, &self.cover_detection_angle);
+        set(&mut stealth.character_need_to_crouch, &self.character_need_to_crouch);
+        set(&mut stealth.hidden_for_a_time, &self.hidden_for_a_time);
+        set(&mut stealth.hidden_for_a_time_amount, &self.hidden_for_a_time_amount);
+        set(&mut stealth.time_delay_to_hide_again_if_discovered, &self.time_delay_to_hide_again_if_discovered);
+        set(&mut stealth.hidden_fov, &self.hidden_fov);
+    }
+}
+
+/// Applied to the [`BallisticsEnvironment`] and [`AimAssistSettings`] resources
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WeaponsConfig {
+    pub gravity: Option<Vec3>,
+    pub air_density: Option<f32>,
+    pub wind: Option<Vec3>,
+    pub aim_assist_enabled: Option<bool>,
+    pub aim_assist_max_distance: Option<f32>,
+    pub magnetism_angle: Option<f32>,
+    pub magnetism_slowdown: Option<f32>,
+    pub bullet_magnetism_angle: Option<f32>,
+    pub snap_angle: Option<f32>,
+}
+
+impl WeaponsConfig {
+    pub fn apply_ballistics(&self, environment: &mut BallisticsEnvironment) {
+        set(&mut environment.gravity, &self.gravity);
+        set(&mut environment.air_density, &self.air_density);
+        set(&mut environment.wind, &self.wind);
+    }
+
+    pub fn apply_aim_assist(&self, settings: &mut AimAssistSettings) {
+        set(&mut settings.enabled, &self.aim_assist_enabled);
+        set(&mut settings.max_distance, &self.aim_assist_max_distance);
+        set(&mut settings.magnetism_angle, &self.magnetism_angle);
+        set(&mut settings.magnetism_slowdown, &self.magnetism_slowdown);
+        set(&mut settings.bullet_magnetism_angle, &self.bullet_magnetism_angle);
+        set(&mut settings.snap_angle, &self.snap_angle);
+    }
+}
+
+/// Applied to every [`AiController`] and [`AiPerception`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AiConfig {
+    pub detection_range: Option<f32>,
+    pub attack_range: Option<f32>,
+    pub patrol_speed_mult: Option<f32>,
+    pub chase_speed_mult: Option<f32>,
+    pub wait_time_between_waypoints: Option<f32>,
+    pub max_suspicion_time: Option<f32>,
+    pub fov: Option<f32>,
+    pub vision_range: Option<f32>,
+}
+
+impl AiConfig {
+    pub fn apply_controller(&self, controller: &mut AiController) {
+        set(&mut controller.detection_range, &self.detection_range);
+        set(&mut controller.attack_range, &self.attack_range);
+        set(&mut controller.patrol_speed_mult, &self.patrol_speed_mult);
+        set(&mut controller.chase_speed_mult, &self.chase_speed_mult);
+        set(&mut controller.wait_time_between_waypoints, &self.wait_time_between_waypoints);
+        set(&mut controller.max_suspicion_time, &self.max_suspicion_time);
+    }
+
+    pub fn apply_perception(&self, perception: &mut AiPerception) {
+        set(&mut perception.fov, &self.fov);
+        set(&mut perception.vision_range, &self.vision_range);
+    }
+}
+
+/// Config the game runs with; set through `GameControllerPlugin::with_config`
+/// or by inserting a handle at runtime
+#[derive(Resource, Debug, Default)]
+pub struct ActiveGameControllerConfig {
+    pub path: Option<String>,
+    pub handle: Option<Handle<GameControllerConfig>>,
+}
+
+impl ActiveGameControllerConfig {
+    pub fn from_path(path: impl Into<String>) -> Self {
+        Self { path: Some(path.into()), handle: None }
+    }
+}
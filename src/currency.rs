@@ -7,6 +7,8 @@
 //! - **Currency Component**: Track player's money amount
 //! - **Multiple Currency Types**: Support for different currencies (gold, silver, etc.)
 //! - **Transaction Events**: Events for money changes
+//! - **Transaction Log**: Every currency change and vendor trade, for debugging the economy
+//! - **Duplicate Guard**: Events carrying a `transaction_id` are applied once, however often they fire
 //! - **Integration**: Works with Vendor System and Inventory System
 //!
 //! ## Usage
@@ -27,11 +29,17 @@
 //! }
 //! ```
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use crate::input::{console_arg, ConsoleAppExt, ConsoleArgs, ConsoleCommand, ConsoleResult};
 use crate::stats::{StatsSystem, StatValue};
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 /// Component representing currency/money
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
 pub struct Currency {
     /// Current amount of currency
     pub amount: f32,
@@ -49,7 +57,7 @@ impl Default for Currency {
 }
 
 /// Types of currency
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Reflect)]
 pub enum CurrencyType {
     /// Gold coins (main currency)
     Gold,
@@ -70,6 +78,8 @@ pub struct AddCurrencyEvent {
     pub amount: f32,
     /// Currency type
     pub currency_type: CurrencyType,
+    /// Applied only the first time this id is seen
+    pub transaction_id: Option<u64>,
 }
 
 /// Event for removing currency from an entity
@@ -81,6 +91,8 @@ pub struct RemoveCurrencyEvent {
     pub amount: f32,
     /// Currency type
     pub currency_type: CurrencyType,
+    /// Applied only the first time this id is seen
+    pub transaction_id: Option<u64>,
 }
 
 /// Event for when currency removal fails
@@ -94,15 +106,12 @@ pub struct CurrencyRemovalFailedEvent {
     pub current_amount: f32,
 }
 
-/// Custom queues for currency events (Workaround for Bevy 0.18 EventReader issues)
-#[derive(Resource, Default)]
-pub struct AddCurrencyEventQueue(pub Vec<AddCurrencyEvent>);
+/// Queues for currency events
+pub type AddCurrencyEventQueue = EventQueue<AddCurrencyEvent>;
 
-#[derive(Resource, Default)]
-pub struct RemoveCurrencyEventQueue(pub Vec<RemoveCurrencyEvent>);
+pub type RemoveCurrencyEventQueue = EventQueue<RemoveCurrencyEvent>;
 
-#[derive(Resource, Default)]
-pub struct CurrencyRemovalFailedEventQueue(pub Vec<CurrencyRemovalFailedEvent>);
+pub type CurrencyRemovalFailedEventQueue = EventQueue<CurrencyRemovalFailedEvent>;
 
 #[derive(Debug, Clone)]
 pub struct CurrencyChangeEvent {
@@ -112,8 +121,7 @@ pub struct CurrencyChangeEvent {
     pub currency_type: CurrencyType,
 }
 
-#[derive(Resource, Default)]
-pub struct CurrencyChangeEventQueue(pub Vec<CurrencyChangeEvent>);
+pub type CurrencyChangeEventQueue = EventQueue<CurrencyChangeEvent>;
 
 #[derive(Component)]
 pub struct CurrencyNotification {
@@ -142,17 +150,217 @@ impl Default for CurrencyNotificationSettings {
     }
 }
 
+/// What a logged transaction did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionKind {
+    CurrencyAdded,
+    CurrencyRemoved,
+    Purchase,
+    Sale,
+    Repair,
+}
+
+/// How a logged transaction ended
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionOutcome {
+    Completed,
+    /// Refused before anything changed
+    Failed(String),
+    /// A step failed and the earlier steps were undone
+    RolledBack(String),
+    /// Ignored as a repeat of a transaction already handled
+    Duplicate,
+}
+
+/// Entry of the [`TransactionLog`]
+#[derive(Debug, Clone)]
+pub struct TransactionRecord {
+    /// Sequence number in the log
+    pub id: u64,
+    /// Seconds since startup
+    pub time: f64,
+    pub kind: TransactionKind,
+    /// Entity whose money or items changed
+    pub customer: Entity,
+    pub vendor: Option<Entity>,
+    pub item: Option<String>,
+    pub quantity: u32,
+    /// Change of the customer's money, negative when paying
+    pub money: f32,
+    pub outcome: TransactionOutcome,
+}
+
+impl std::fmt::Display for TransactionRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{} {:.1}s {:?} {:?}", self.id, self.time, self.kind, self.customer)?;
+        if let Some(vendor) = self.vendor {
+            write!(f, " at {:?}", vendor)?;
+        }
+        if let Some(item) = &self.item {
+            write!(f, " {}x {}", self.quantity, item)?;
+        }
+        write!(f, " {:+.2}: {:?}", self.money, self.outcome)
+    }
+}
+
+/// Recent currency changes and vendor trades, oldest first
+#[derive(Resource, Debug, Clone)]
+pub struct TransactionLog {
+    /// Entries kept; the oldest are dropped past it
+    pub capacity: usize,
+    entries: VecDeque<TransactionRecord>,
+    next_id: u64,
+}
+
+impl Default for TransactionLog {
+    fn default() -> Self {
+        Self { capacity: 256, entries: VecDeque::new(), next_id: 1 }
+    }
+}
+
+impl TransactionLog {
+    /// Adds an entry, filling in its sequence number, and returns that number
+    pub fn record(&mut self, mut record: TransactionRecord) -> u64 {
+        record.id = self.next_id;
+        self.next_id += 1;
+        match &record.outcome {
+            TransactionOutcome::Completed => debug!("Transaction {}", record),
+            _ => warn!("Transaction {}", record),
+        }
+        self.entries.push_back(record);
+        while self.entries.len() > self.capacity.max(1) {
+            self.entries.pop_front();
+        }
+        self.next_id - 1
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &TransactionRecord> {
+        self.entries.iter()
+    }
+
+    /// The last `count` entries, oldest first
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &TransactionRecord> {
+        self.entries.iter().skip(self.entries.len().saturating_sub(count))
+    }
+
+    /// Entries where `entity` was the customer or the vendor
+    pub fn for_entity(&self, entity: Entity) -> impl Iterator<Item = &TransactionRecord> {
+        self.entries.iter().filter(move |record| record.customer == entity || record.vendor == Some(entity))
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Keeps double-fired currency and trade events from being applied twice
+///
+/// Events with a `transaction_id` are applied once per id. Trades without one
+/// go through, unless `dedupe_requests` is set: then they are compared by their
+/// content, and the same request again within `duplicate_window` seconds is
+/// treated as the same click.
+#[derive(Resource, Debug, Clone)]
+pub struct TransactionGuard {
+    pub dedupe_requests: bool,
+    pub duplicate_window: f64,
+    /// Transaction ids remembered
+    pub id_capacity: usize,
+    seen_ids: HashSet<u64>,
+    id_order: VecDeque<u64>,
+    recent_requests: VecDeque<(u64, f64)>,
+}
+
+impl Default for TransactionGuard {
+    fn default() -> Self {
+        Self {
+            dedupe_requests: false,
+            duplicate_window: 0.05,
+            id_capacity: 1024,
+            seen_ids: HashSet::new(),
+            id_order: VecDeque::new(),
+            recent_requests: VecDeque::new(),
+        }
+    }
+}
+
+impl TransactionGuard {
+    /// Whether `id` is new; remembers it
+    pub fn admit_id(&mut self, id: u64) -> bool {
+        if !self.seen_ids.insert(id) {
+            return false;
+        }
+        self.id_order.push_back(id);
+        while self.id_order.len() > self.id_capacity.max(1) {
+            if let Some(oldest) = self.id_order.pop_front() {
+                self.seen_ids.remove(&oldest);
+            }
+        }
+        true
+    }
+
+    /// Whether a request with this content wasn't just handled; remembers it
+    pub fn admit_request(&mut self, request: impl Hash, now: f64) -> bool {
+        let mut hasher = DefaultHasher::new();
+        request.hash(&mut hasher);
+        let fingerprint = hasher.finish();
+
+        let window = self.duplicate_window;
+        self.recent_requests.retain(|(_, time)| now - time <= window);
+        if self.recent_requests.iter().any(|(seen, _)| *seen == fingerprint) {
+            return false;
+        }
+        self.recent_requests.push_back((fingerprint, now));
+        true
+    }
+
+    /// `admit_id` for events with an id, `admit_request` for the others when
+    /// `dedupe_requests` is set
+    pub fn admit(&mut self, transaction_id: Option<u64>, request: impl Hash, now: f64) -> bool {
+        match transaction_id {
+            Some(id) => self.admit_id(id),
+            None => !self.dedupe_requests || self.admit_request(request, now),
+        }
+    }
+}
+
+/// Clock, duplicate guard and log trades are recorded with
+#[derive(SystemParam)]
+pub struct TransactionLedger<'w> {
+    pub time: Res<'w, Time>,
+    pub guard: ResMut<'w, TransactionGuard>,
+    pub log: ResMut<'w, TransactionLog>,
+}
+
+/// Transaction id for the action `source` stands for, such as a pickup entity
+/// or a button press. The same source always gives the same id.
+pub fn transaction_id(source: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
 /// System to handle adding currency
 pub fn handle_add_currency(
-    mut events: ResMut<AddCurrencyEventQueue>,
+    time: Res<Time>,
+    mut events: QueueReader<AddCurrencyEvent>,
     mut currency_query: Query<&mut Currency>,
     mut change_events: ResMut<CurrencyChangeEventQueue>,
+    mut guard: ResMut<TransactionGuard>,
+    mut log: ResMut<TransactionLog>,
 ) {
-    for event in events.0.drain(..) {
+    for event in events.read().cloned() {
+        let mut record = currency_record(&time, TransactionKind::CurrencyAdded, event.entity, event.amount);
+        // Pickups of equal value can land in the same frame, so only ids mark repeats
+        if event.transaction_id.is_some_and(|id| !guard.admit_id(id)) {
+            record.outcome = TransactionOutcome::Duplicate;
+            log.record(record);
+            continue;
+        }
         if let Ok(mut currency) = currency_query.get_mut(event.entity) {
             if currency.currency_type == event.currency_type {
                 currency.amount += event.amount;
-                change_events.0.push(CurrencyChangeEvent {
+                log.record(record);
+                change_events.send(CurrencyChangeEvent {
                     entity: event.entity,
                     delta: event.amount,
                     new_total: currency.amount,
@@ -169,17 +377,27 @@ pub fn handle_add_currency(
 
 /// System to handle removing currency
 pub fn handle_remove_currency(
-    mut events: ResMut<RemoveCurrencyEventQueue>,
+    time: Res<Time>,
+    mut events: QueueReader<RemoveCurrencyEvent>,
     mut currency_query: Query<&mut Currency>,
     mut failed_events: ResMut<CurrencyRemovalFailedEventQueue>,
     mut change_events: ResMut<CurrencyChangeEventQueue>,
+    mut guard: ResMut<TransactionGuard>,
+    mut log: ResMut<TransactionLog>,
 ) {
-    for event in events.0.drain(..) {
+    for event in events.read().cloned() {
+        let mut record = currency_record(&time, TransactionKind::CurrencyRemoved, event.entity, -event.amount);
+        if event.transaction_id.is_some_and(|id| !guard.admit_id(id)) {
+            record.outcome = TransactionOutcome::Duplicate;
+            log.record(record);
+            continue;
+        }
         if let Ok(mut currency) = currency_query.get_mut(event.entity) {
             if currency.currency_type == event.currency_type {
                 if currency.amount >= event.amount {
                     currency.amount -= event.amount;
-                    change_events.0.push(CurrencyChangeEvent {
+                    log.record(record);
+                    change_events.send(CurrencyChangeEvent {
                         entity: event.entity,
                         delta: -event.amount,
                         new_total: currency.amount,
@@ -190,7 +408,9 @@ pub fn handle_remove_currency(
                         event.amount, event.currency_type, event.entity, currency.amount
                     );
                 } else {
-                    failed_events.0.push(CurrencyRemovalFailedEvent {
+                    record.outcome = TransactionOutcome::Failed("not enough money".to_string());
+                    log.record(record);
+                    failed_events.send(CurrencyRemovalFailedEvent {
                         entity: event.entity,
                         requested_amount: event.amount,
                         current_amount: currency.amount,
@@ -201,6 +421,30 @@ pub fn handle_remove_currency(
     }
 }
 
+fn currency_record(time: &Time, kind: TransactionKind, entity: Entity, money: f32) -> TransactionRecord {
+    TransactionRecord {
+        id: 0,
+        time: time.elapsed_secs_f64(),
+        kind,
+        customer: entity,
+        vendor: None,
+        item: None,
+        quantity: 0,
+        money,
+        outcome: TransactionOutcome::Completed,
+    }
+}
+
+/// Console `transactions [count]`
+pub fn transactions_command(In(args): In<ConsoleArgs>, log: Res<TransactionLog>) -> ConsoleResult {
+    let count = if args.is_empty() { 10 } else { console_arg(&args, 0, "count")? };
+    let lines: Vec<String> = log.recent(count).map(ToString::to_string).collect();
+    if lines.is_empty() {
+        return Ok("No transactions".to_string());
+    }
+    Ok(lines.join("\n"))
+}
+
 /// System to check currency balance
 pub fn check_currency_balance(
     currency_query: Query<(Entity, &Currency)>,
@@ -229,10 +473,10 @@ pub fn sync_currency_to_stats(
 
 pub fn spawn_currency_notifications(
     mut commands: Commands,
-    mut events: ResMut<CurrencyChangeEventQueue>,
+    mut events: QueueReader<CurrencyChangeEvent>,
     settings: Res<CurrencyNotificationSettings>,
 ) {
-    for event in events.0.drain(..) {
+    for event in events.read().cloned() {
         let color = if event.delta >= 0.0 {
             settings.color_gain
         } else {
@@ -298,12 +542,19 @@ pub struct CurrencyPlugin;
 impl Plugin for CurrencyPlugin {
     fn build(&self, app: &mut App) {
         app
+            .register_type::<Currency>()
             // Add custom event queues
-            .init_resource::<AddCurrencyEventQueue>()
-            .init_resource::<RemoveCurrencyEventQueue>()
-            .init_resource::<CurrencyRemovalFailedEventQueue>()
-            .init_resource::<CurrencyChangeEventQueue>()
+            .add_plugins(EventQueuePlugin::<AddCurrencyEvent>::default())
+            .add_plugins(EventQueuePlugin::<RemoveCurrencyEvent>::default())
+            .add_plugins(EventQueuePlugin::<CurrencyRemovalFailedEvent>::default())
+            .add_plugins(EventQueuePlugin::<CurrencyChangeEvent>::default())
             .init_resource::<CurrencyNotificationSettings>()
+            .init_resource::<TransactionLog>()
+            .init_resource::<TransactionGuard>()
+            .add_console_command(
+                ConsoleCommand::new("transactions", "Lists the last currency changes and trades").with_usage("[count]"),
+                transactions_command,
+            )
             // Add systems
             .add_systems(Update, (
                 handle_add_currency,
@@ -315,3 +566,41 @@ impl Plugin for CurrencyPlugin {
             ));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_guard_and_log() {
+        let mut guard = TransactionGuard::default();
+        let mut world = World::new();
+        let (player, merchant) = (world.spawn_empty().id(), world.spawn_empty().id());
+        assert!(guard.admit(None, (player, merchant, 0usize, 1u32), 1.0));
+        assert!(guard.admit(None, (player, merchant, 0usize, 1u32), 1.0));
+        guard.dedupe_requests = true;
+        assert!(guard.admit(None, (player, merchant, 0usize, 1u32), 1.0));
+        assert!(!guard.admit(None, (player, merchant, 0usize, 1u32), 1.02));
+        assert!(guard.admit(None, (player, merchant, 0usize, 1u32), 1.5));
+        assert!(guard.admit(Some(7), (), 1.5));
+        assert!(!guard.admit(Some(7), (), 9.0));
+
+        let mut log = TransactionLog::default();
+        log.capacity = 2;
+        for money in [1.0, 2.0, 3.0] {
+            log.record(TransactionRecord {
+                id: 0,
+                time: 0.0,
+                kind: TransactionKind::CurrencyAdded,
+                customer: player,
+                vendor: None,
+                item: None,
+                quantity: 0,
+                money,
+                outcome: TransactionOutcome::Completed,
+            });
+        }
+        assert_eq!(log.entries().map(|record| (record.id, record.money)).collect::<Vec<_>>(), vec![(2, 2.0), (3, 3.0)]);
+        assert_eq!(log.for_entity(merchant).count(), 0);
+    }
+}
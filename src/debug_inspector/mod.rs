@@ -0,0 +1,38 @@
+//! In-game debug overlay
+//!
+//! Press F10 to show the overlay and F9/F11 to cycle its tabs: AI state, combat damage
+//! log, input actions, camera rig, ability cooldowns and save contents. Tabs can be
+//! hidden through [`DebugInspectorSettings::enabled_tabs`].
+
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+
+pub use types::{
+    DebugInspectorTab, DebugInspectorSettings, DebugInspectorState, DebugInspectorRoot,
+    DebugInspectorHeader, DebugInspectorBody,
+};
+
+pub struct DebugInspectorPlugin;
+
+impl Plugin for DebugInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<DebugInspectorSettings>()
+            .init_resource::<DebugInspectorState>()
+            .register_type::<DebugInspectorSettings>()
+            .register_type::<DebugInspectorRoot>()
+            .register_type::<DebugInspectorHeader>()
+            .register_type::<DebugInspectorBody>()
+            .add_systems(Startup, setup_debug_inspector)
+            .add_systems(Update, handle_debug_inspector_input)
+            // Damage results are cleared at the start of the combat update
+            .add_systems(PostUpdate, (
+                record_damage_log,
+                update_debug_inspector,
+            ).chain());
+    }
+}
@@ -0,0 +1,411 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use std::fmt::Write;
+use super::types::*;
+use crate::abilities::AbilityInfo;
+use crate::ai::AiController;
+use crate::camera::types::{CameraController, CameraState};
+use crate::combat::DamageResultEvent;
+use crate::utils::QueueReader;
+use crate::input::{ActionState, InputContextStack, InputState};
+use crate::save::SaveManager;
+
+/// Spawns the hidden overlay
+pub fn setup_debug_inspector(mut commands: Commands, settings: Res<DebugInspectorSettings>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                left: Val::Px(10.0),
+                width: Val::Px(420.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+            GlobalZIndex(100),
+            Visibility::Hidden,
+            DebugInspectorRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont { font_size: settings.font_size, ..default() },
+                TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                DebugInspectorHeader,
+            ));
+            parent.spawn((
+                Text::new(""),
+                TextFont { font_size: settings.font_size, ..default() },
+                TextColor(Color::WHITE),
+                DebugInspectorBody,
+            ));
+        });
+}
+
+/// Toggles the overlay and cycles through the enabled tabs
+pub fn handle_debug_inspector_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<DebugInspectorSettings>,
+    mut state: ResMut<DebugInspectorState>,
+) {
+    if keyboard.just_pressed(settings.toggle_key) {
+        state.visible = !state.visible;
+        state.refresh_timer = 0.0;
+    }
+
+    if settings.enabled_tabs.is_empty() {
+        return;
+    }
+
+    let current_index = settings.enabled_tabs.iter().position(|tab| *tab == state.current_tab);
+    let Some(index) = current_index else {
+        // Current tab was disabled at runtime
+        state.current_tab = settings.enabled_tabs[0];
+        return;
+    };
+
+    if !state.visible {
+        return;
+    }
+
+    let count = settings.enabled_tabs.len();
+    if keyboard.just_pressed(settings.next_tab_key) {
+        state.current_tab = settings.enabled_tabs[(index + 1) % count];
+        state.refresh_timer = 0.0;
+    } else if keyboard.just_pressed(settings.previous_tab_key) {
+        state.current_tab = settings.enabled_tabs[(index + count - 1) % count];
+        state.refresh_timer = 0.0;
+    }
+}
+
+/// Appends this frame's damage results to the combat log
+pub fn record_damage_log(
+    mut results: QueueReader<DamageResultEvent>,
+    settings: Res<DebugInspectorSettings>,
+    mut state: ResMut<DebugInspectorState>,
+    names: Query<&Name>,
+    time: Res<Time>,
+) {
+    let entity_label = |entity: Entity| {
+        names
+            .get(entity)
+            .map(|name| name.as_str().to_string())
+            .unwrap_or_else(|_| format!("{:?}", entity))
+    };
+
+    for result in results.read() {
+        let source = result.source.map(entity_label).unwrap_or_else(|| "world".to_string());
+        let mut line = format!(
+            "[{:.1}] {} -> {}: {:.1} {:?} (raw {:.1})",
+            time.elapsed_secs(),
+            source,
+            entity_label(result.target),
+            result.final_amount,
+            result.damage_type,
+            result.original_amount,
+        );
+        if result.shielded_amount > 0.0 {
+            let _ = write!(line, " shield {:.1}", result.shielded_amount);
+        }
+        if result.is_crit {
+            line.push_str(" CRIT");
+        }
+        if result.is_block {
+            line.push_str(" BLOCK");
+        }
+        state.damage_log.push_back(line);
+    }
+
+    while state.damage_log.len() > settings.damage_log_size {
+        state.damage_log.pop_front();
+    }
+}
+
+/// Everything the inspector tabs show
+#[derive(SystemParam)]
+pub struct DebugInspectorSources<'w, 's> {
+    pub ai_query: Query<'w, 's, (Entity, &'static AiController, Option<&'static Name>)>,
+    pub camera_query: Query<'w, 's, (&'static CameraController, &'static CameraState)>,
+    pub ability_query: Query<'w, 's, &'static AbilityInfo>,
+    pub input_state: Res<'w, InputState>,
+    pub action_state: Res<'w, ActionState>,
+    pub context_stack: Res<'w, InputContextStack>,
+    pub save_manager: Res<'w, SaveManager>,
+}
+
+/// Refreshes the overlay text for the current tab
+pub fn update_debug_inspector(
+    time: Res<Time>,
+    settings: Res<DebugInspectorSettings>,
+    mut state: ResMut<DebugInspectorState>,
+    mut root_query: Query<&mut Visibility, With<DebugInspectorRoot>>,
+    mut header_query: Query<&mut Text, (With<DebugInspectorHeader>, Without<DebugInspectorBody>)>,
+    mut body_query: Query<&mut Text, (With<DebugInspectorBody>, Without<DebugInspectorHeader>)>,
+    sources: DebugInspectorSources,
+) {
+    let DebugInspectorSources { ai_query, camera_query, ability_query, input_state, action_state, context_stack, save_manager } =
+        sources;
+    let visible = state.visible && !settings.enabled_tabs.is_empty();
+    for mut visibility in root_query.iter_mut() {
+        *visibility = if visible { Visibility::Visible } else { Visibility::Hidden };
+    }
+    if !visible {
+        return;
+    }
+
+    state.refresh_timer -= time.delta_secs();
+    if state.refresh_timer > 0.0 {
+        return;
+    }
+    state.refresh_timer = settings.refresh_interval;
+
+    let header = settings
+        .enabled_tabs
+        .iter()
+        .map(|tab| {
+            if *tab == state.current_tab {
+                format!("[{}]", tab.label())
+            } else {
+                tab.label().to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
+
+    let body = match state.current_tab {
+        DebugInspectorTab::Ai => ai_tab(&ai_query),
+        DebugInspectorTab::Combat => combat_tab(&state),
+        DebugInspectorTab::Input => input_tab(&input_state, &action_state, &context_stack),
+        DebugInspectorTab::Camera => camera_tab(&camera_query),
+        DebugInspectorTab::Abilities => abilities_tab(&ability_query),
+        DebugInspectorTab::Save => save_tab(&save_manager),
+    };
+
+    for mut text in header_query.iter_mut() {
+        text.0 = format!("{}\n{:?}/{:?} tabs  {:?} hide", header, settings.previous_tab_key, settings.next_tab_key, settings.toggle_key);
+    }
+    for mut text in body_query.iter_mut() {
+        text.0 = body.clone();
+    }
+}
+
+fn ai_tab(ai_query: &Query<(Entity, &AiController, Option<&Name>)>) -> String {
+    let mut text = String::new();
+    for (entity, ai, name) in ai_query.iter() {
+        let label = name.map(|name| name.as_str().to_string()).unwrap_or_else(|| format!("{:?}", entity));
+        let _ = writeln!(
+            text,
+            "{}: {:?}{} target {:?} waypoint {}/{} suspicion {:.1}/{:.1}",
+            label,
+            ai.state,
+            if ai.is_paused { " (paused)" } else { "" },
+            ai.target,
+            ai.current_waypoint_index,
+            ai.patrol_path.len(),
+            ai.suspicion_timer,
+            ai.max_suspicion_time,
+        );
+    }
+    if text.is_empty() {
+        text.push_str("No AI controllers");
+    }
+    text
+}
+
+fn combat_tab(state: &DebugInspectorState) -> String {
+    if state.damage_log.is_empty() {
+        return "No damage dealt yet".to_string();
+    }
+    state.damage_log.iter().rev().cloned().collect::<Vec<_>>().join("\n")
+}
+
+fn input_tab(input_state: &InputState, action_state: &ActionState, context_stack: &InputContextStack) -> String {
+    let mut text = String::new();
+    let _ = writeln!(text, "Context: {:?}", context_stack.stack);
+    let _ = writeln!(text, "Movement: ({:.2}, {:.2})", input_state.movement.x, input_state.movement.y);
+    let _ = writeln!(text, "Look: ({:.2}, {:.2})", input_state.look.x, input_state.look.y);
+
+    let mut pressed: Vec<String> = action_state
+        .actions
+        .iter()
+        .filter(|(_, value)| value.pressed || value.just_released)
+        .map(|(action, value)| {
+            if value.just_pressed {
+                format!("{:?} (just pressed)", action)
+            } else if value.just_released {
+                format!("{:?} (released)", action)
+            } else {
+                format!("{:?} {:.2}", action, value.value)
+            }
+        })
+        .collect();
+    pressed.sort();
+
+    if pressed.is_empty() {
+        text.push_str("No actions held");
+    } else {
+        text.push_str(&pressed.join("\n"));
+    }
+    text
+}
+
+fn camera_tab(camera_query: &Query<(&CameraController, &CameraState)>) -> String {
+    let mut text = String::new();
+    for (controller, state) in camera_query.iter() {
+        let _ = writeln!(text, "Mode: {:?} side {:?}", controller.mode, controller.current_side);
+        let _ = writeln!(text, "Follow: {:?}", controller.follow_target);
+        let _ = writeln!(text, "Yaw {:.1} Pitch {:.1}", state.yaw, state.pitch);
+        let _ = writeln!(
+            text,
+            "Distance {:.2} (target {:.2}, {:.1}-{:.1})",
+            state.current_distance, controller.distance, controller.min_distance, controller.max_distance
+        );
+        let _ = writeln!(text, "Pivot {:.2} lean {:.2}", state.current_pivot, state.current_lean);
+        let _ = writeln!(text, "Aiming {} crouching {}", state.is_aiming, state.is_crouching);
+        if let Some(fov) = state.fov_override {
+            let _ = writeln!(text, "FOV override {:.1}", fov);
+        }
+    }
+    if text.is_empty() {
+        text.push_str("No camera controller");
+    }
+    text
+}
+
+fn abilities_tab(ability_query: &Query<&AbilityInfo>) -> String {
+    let mut lines: Vec<String> = ability_query
+        .iter()
+        .map(|ability| {
+            let mut line = format!(
+                "{}{}: {:?}",
+                if ability.is_current { "> " } else { "" },
+                ability.name,
+                ability.status,
+            );
+            if ability.cooldown_in_process {
+                let _ = write!(line, " cooldown {:.1}/{:.1}s", ability.cooldown_timer.max(0.0), ability.cooldown_duration);
+            }
+            if ability.time_limit_in_process {
+                let _ = write!(line, " limit {:.1}/{:.1}s", ability.time_limit_timer.max(0.0), ability.time_limit);
+            }
+            line
+        })
+        .collect();
+    lines.sort();
+
+    if lines.is_empty() {
+        return "No abilities".to_string();
+    }
+    lines.join("\n")
+}
+
+fn save_tab(save_manager: &SaveManager) -> String {
+    let mut text = String::new();
+    let _ = writeln!(
+        text,
+        "Slot {} / {}  autosave {} ({:.0}/{:.0}s)",
+        save_manager.current_save_slot,
+        save_manager.max_save_slots,
+        save_manager.auto_save_enabled,
+        save_manager.time_since_last_save,
+        save_manager.auto_save_interval,
+    );
+
+    match &save_manager.current_save_data {
+        Some(data) => {
+            let _ = writeln!(text, "Position {:.1}", data.player_position);
+            let _ = writeln!(text, "Health {:.0} Stamina {:.0}", data.player_health, data.player_stamina);
+            let _ = writeln!(text, "Items {}", data.inventory_items.len());
+            let _ = writeln!(text, "Scene {} chapter {}", data.scene_index, data.game_progress.chapter);
+            let _ = writeln!(text, "Play time {:.0}s", data.play_time);
+            let _ = writeln!(text, "Checkpoint {}", data.is_checkpoint);
+        }
+        None => text.push_str("No save data loaded\n"),
+    }
+
+    let mut slots: Vec<_> = save_manager.save_slots_cache.values().collect();
+    slots.sort_by_key(|slot| slot.slot_number);
+    for slot in slots {
+        let _ = writeln!(
+            text,
+            "#{} {} scene {} {:.0}s{}{}",
+            slot.slot_number,
+            slot.save_date.format("%Y-%m-%d %H:%M"),
+            slot.scene_index,
+            slot.play_time,
+            if slot.is_autosave { " auto" } else { "" },
+            if slot.is_valid { "" } else { " (invalid)" },
+        );
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::{DamageResultQueue, DamageType};
+    use crate::debug_inspector::DebugInspectorPlugin;
+    use crate::utils::EventQueuePlugin;
+
+    fn tap(app: &mut App, key: KeyCode) {
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(key);
+        app.update();
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.release(key);
+        keyboard.clear();
+    }
+
+    fn body_text(app: &mut App) -> String {
+        let mut query = app.world_mut().query_filtered::<&Text, With<DebugInspectorBody>>();
+        query.single(app.world()).unwrap().0.clone()
+    }
+
+    #[test]
+    fn test_inspector_toggles_cycles_and_logs_damage() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, DebugInspectorPlugin))
+            .add_plugins(EventQueuePlugin::<DamageResultEvent>::default())
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<InputState>()
+            .init_resource::<ActionState>()
+            .init_resource::<InputContextStack>()
+            .init_resource::<SaveManager>();
+
+        app.update();
+        let mut root = app.world_mut().query_filtered::<&Visibility, With<DebugInspectorRoot>>();
+        assert_eq!(root.single(app.world()).unwrap(), &Visibility::Hidden);
+
+        tap(&mut app, KeyCode::F10);
+        assert!(app.world().resource::<DebugInspectorState>().visible);
+        assert_eq!(root.single(app.world()).unwrap(), &Visibility::Visible);
+
+        tap(&mut app, KeyCode::F11);
+        assert_eq!(app.world().resource::<DebugInspectorState>().current_tab, DebugInspectorTab::Combat);
+
+        let guard = app.world_mut().spawn(Name::new("Guard")).id();
+        app.world_mut().resource_mut::<DamageResultQueue>().send(DamageResultEvent {
+            target: guard,
+            part: None,
+            source: None,
+            original_amount: 20.0,
+            final_amount: 15.0,
+            damage_type: DamageType::Melee,
+            shielded_amount: 5.0,
+            is_crit: true,
+            is_block: false,
+        });
+        app.world_mut().resource_mut::<DebugInspectorState>().refresh_timer = 0.0;
+        app.update();
+
+        let body = body_text(&mut app);
+        assert!(body.contains("world -> Guard: 15.0 Melee (raw 20.0) shield 5.0 CRIT"), "{body}");
+
+        // Disabling the current tab falls back to the first enabled one
+        app.world_mut().resource_mut::<DebugInspectorSettings>().set_tab_enabled(DebugInspectorTab::Combat, false);
+        app.update();
+        assert_eq!(app.world().resource::<DebugInspectorState>().current_tab, DebugInspectorTab::Ai);
+    }
+}
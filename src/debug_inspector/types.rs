@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// Overlay tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum DebugInspectorTab {
+    Ai,
+    Combat,
+    Input,
+    Camera,
+    Abilities,
+    Save,
+}
+
+impl DebugInspectorTab {
+    pub const ALL: [DebugInspectorTab; 6] = [
+        DebugInspectorTab::Ai,
+        DebugInspectorTab::Combat,
+        DebugInspectorTab::Input,
+        DebugInspectorTab::Camera,
+        DebugInspectorTab::Abilities,
+        DebugInspectorTab::Save,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DebugInspectorTab::Ai => "AI",
+            DebugInspectorTab::Combat => "Combat",
+            DebugInspectorTab::Input => "Input",
+            DebugInspectorTab::Camera => "Camera",
+            DebugInspectorTab::Abilities => "Abilities",
+            DebugInspectorTab::Save => "Save",
+        }
+    }
+}
+
+/// Debug overlay settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct DebugInspectorSettings {
+    pub toggle_key: KeyCode,
+    pub next_tab_key: KeyCode,
+    pub previous_tab_key: KeyCode,
+    /// Tabs shown in the overlay, in cycling order
+    pub enabled_tabs: Vec<DebugInspectorTab>,
+    /// Seconds between text refreshes
+    pub refresh_interval: f32,
+    /// Damage results kept in the combat log
+    pub damage_log_size: usize,
+    pub font_size: f32,
+}
+
+impl Default for DebugInspectorSettings {
+    fn default() -> Self {
+        Self {
+            toggle_key: KeyCode::F10,
+            next_tab_key: KeyCode::F11,
+            previous_tab_key: KeyCode::F9,
+            enabled_tabs: DebugInspectorTab::ALL.to_vec(),
+            refresh_interval: 0.1,
+            damage_log_size: 20,
+            font_size: 14.0,
+        }
+    }
+}
+
+impl DebugInspectorSettings {
+    pub fn is_tab_enabled(&self, tab: DebugInspectorTab) -> bool {
+        self.enabled_tabs.contains(&tab)
+    }
+
+    /// Shows or hides a single tab
+    pub fn set_tab_enabled(&mut self, tab: DebugInspectorTab, enabled: bool) {
+        if enabled {
+            if !self.is_tab_enabled(tab) {
+                self.enabled_tabs.push(tab);
+            }
+        } else {
+            self.enabled_tabs.retain(|enabled_tab| *enabled_tab != tab);
+        }
+    }
+}
+
+/// Runtime overlay state
+#[derive(Resource, Debug)]
+pub struct DebugInspectorState {
+    pub visible: bool,
+    pub current_tab: DebugInspectorTab,
+    pub refresh_timer: f32,
+    /// Most recent damage results, newest last
+    pub damage_log: VecDeque<String>,
+}
+
+impl Default for DebugInspectorState {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            current_tab: DebugInspectorTab::Ai,
+            refresh_timer: 0.0,
+            damage_log: VecDeque::new(),
+        }
+    }
+}
+
+/// Overlay root node
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct DebugInspectorRoot;
+
+/// Tab bar text
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct DebugInspectorHeader;
+
+/// Current tab contents
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct DebugInspectorBody;
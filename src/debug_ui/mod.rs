@@ -0,0 +1,42 @@
+//! egui tuning panel
+//!
+//! With the `debug_ui` feature, F8 opens a [`bevy_egui`] window to tune the
+//! ballistics environment and, for a selected entity, its `CharacterController`,
+//! `CameraController`, `Accuracy`, `StealthController`, AI perception and
+//! health while the game runs. Buttons inject damage and noise events at the
+//! selected entity.
+//!
+//! ```rust,ignore
+//! App::new()
+//!     .add_plugins(DefaultPlugins)
+//!     .add_plugins(GameControllerPlugin::default())
+//!     .insert_resource(DebugUiSettings { visible: true, ..default() });
+//! ```
+
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use bevy_egui::{EguiPlugin, EguiPrimaryContextPass};
+use types::*;
+use systems::*;
+
+pub use types::{DebugUiSettings, DebugUiState};
+
+pub struct DebugUiPlugin;
+
+impl Plugin for DebugUiPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin::default());
+        }
+
+        app
+            .init_resource::<DebugUiSettings>()
+            .init_resource::<DebugUiState>()
+            .register_type::<DebugUiSettings>()
+            .register_type::<DebugUiState>()
+            .add_systems(Update, toggle_debug_ui)
+            .add_systems(EguiPrimaryContextPass, draw_debug_ui.run_if(debug_ui_visible));
+    }
+}
@@ -0,0 +1,368 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy_egui::{egui, EguiContexts};
+use super::types::*;
+use crate::ai::{AIPerceptionSettings, AiPerception, NoiseEvent, NoiseEventQueue};
+use crate::camera::CameraController;
+use crate::character::CharacterController;
+use crate::combat::{DamageEvent, DamageEventQueue, DamageType, Health};
+use crate::game_manager::types::FeatureToggles;
+use crate::weapons::{Accuracy, BallisticsEnvironment};
+#[cfg(feature = "stealth")]
+use crate::stealth::StealthController;
+
+/// System to show or hide the tuning panel
+pub fn toggle_debug_ui(keyboard: Res<ButtonInput<KeyCode>>, mut settings: ResMut<DebugUiSettings>) {
+    if keyboard.just_pressed(settings.toggle_key) {
+        settings.visible = !settings.visible;
+    }
+}
+
+pub fn debug_ui_visible(settings: Res<DebugUiSettings>) -> bool {
+    settings.visible
+}
+
+/// Entities listed in the panel
+type TunableEntity = Or<(
+    With<CharacterController>,
+    With<CameraController>,
+    With<Accuracy>,
+    With<AiPerception>,
+    With<AIPerceptionSettings>,
+)>;
+
+/// World-wide values edited in the panel
+#[derive(SystemParam)]
+pub struct DebugWorldSettings<'w> {
+    pub environment: ResMut<'w, BallisticsEnvironment>,
+    pub toggles: ResMut<'w, FeatureToggles>,
+}
+
+/// Components of the selected entity edited in the panel
+#[derive(SystemParam)]
+pub struct TunableComponents<'w, 's> {
+    pub characters: Query<'w, 's, &'static mut CharacterController>,
+    pub cameras: Query<'w, 's, &'static mut CameraController>,
+    pub accuracies: Query<'w, 's, &'static mut Accuracy>,
+    pub perceptions: Query<'w, 's, (Option<&'static mut AiPerception>, Option<&'static mut AIPerceptionSettings>)>,
+    pub healths: Query<'w, 's, &'static mut Health>,
+    pub transforms: Query<'w, 's, &'static GlobalTransform>,
+}
+
+/// Queues the event buttons send to
+#[derive(SystemParam)]
+pub struct DebugEventQueues<'w> {
+    pub damage_queue: ResMut<'w, DamageEventQueue>,
+    pub noise_queue: ResMut<'w, NoiseEventQueue>,
+}
+
+/// Draws the tuning panel: world ballistics, the tunable entities, the selected
+/// entity's components and the event buttons
+pub fn draw_debug_ui(
+    mut contexts: EguiContexts,
+    mut state: ResMut<DebugUiState>,
+    world_settings: DebugWorldSettings,
+    entity_query: Query<(Entity, Option<&Name>), TunableEntity>,
+    tunables: TunableComponents,
+    #[cfg(feature = "stealth")] mut stealth_controllers: Query<&mut StealthController>,
+    events: DebugEventQueues,
+) -> Result {
+    let DebugWorldSettings { mut environment, mut toggles } = world_settings;
+    let TunableComponents { mut characters, mut cameras, mut accuracies, mut perceptions, mut healths, transforms } = tunables;
+    let DebugEventQueues { mut damage_queue, mut noise_queue } = events;
+    let ctx = contexts.ctx_mut()?;
+
+    egui::Window::new("Controller Tuning").default_width(320.0).show(ctx, |ui| {
+        let changed = ui.collapsing("Ballistics", |ui| {
+            let values = environment.bypass_change_detection();
+            let mut changed = false;
+            changed |= drag_vec3(ui, "Gravity", &mut values.gravity, 0.1);
+            changed |= drag(ui, "Air density", &mut values.air_density, 0.01);
+            changed |= drag(ui, "Water density", &mut values.water_density, 1.0);
+            changed |= drag_vec3(ui, "Wind", &mut values.wind, 0.1);
+            changed
+        });
+        if changed.body_returned.unwrap_or(false) {
+            environment.set_changed();
+        }
+
+        let changed = ui.collapsing("Feature toggles", |ui| {
+            let values = toggles.bypass_change_detection();
+            let mut changed = false;
+            changed |= ui.checkbox(&mut values.fall_damage, "Fall damage").changed();
+            changed |= ui.checkbox(&mut values.stamina, "Stamina").changed();
+            changed |= ui.checkbox(&mut values.weapon_sway, "Weapon sway").changed();
+            changed
+        });
+        if changed.body_returned.unwrap_or(false) {
+            toggles.set_changed();
+        }
+
+        ui.separator();
+        ui.label("Entities");
+        let mut entities: Vec<(Entity, String)> = entity_query
+            .iter()
+            .map(|(entity, name)| (entity, name.map(|name| name.to_string()).unwrap_or_else(|| format!("Entity {}", entity))))
+            .collect();
+        entities.sort_by_key(|(entity, _)| *entity);
+        egui::ScrollArea::vertical().max_height(140.0).show(ui, |ui| {
+            for (entity, label) in entities.iter() {
+                if ui.selectable_label(state.selected == Some(*entity), label).clicked() {
+                    state.selected = Some(*entity);
+                }
+            }
+        });
+
+        let Some(selected) = state.selected.filter(|entity| entity_query.contains(*entity)) else {
+            return;
+        };
+        ui.separator();
+
+        if let Ok(mut controller) = characters.get_mut(selected) {
+            let changed = ui.collapsing("CharacterController", |ui| {
+                edit_character_controller(ui, controller.bypass_change_detection())
+            });
+            if changed.body_returned.unwrap_or(false) {
+                controller.set_changed();
+            }
+        }
+
+        if let Ok(mut camera) = cameras.get_mut(selected) {
+            let changed = ui.collapsing("CameraController", |ui| {
+                edit_camera_controller(ui, camera.bypass_change_detection())
+            });
+            if changed.body_returned.unwrap_or(false) {
+                camera.set_changed();
+            }
+        }
+
+        if let Ok(mut accuracy) = accuracies.get_mut(selected) {
+            let changed = ui.collapsing("Accuracy", |ui| edit_accuracy(ui, accuracy.bypass_change_detection()));
+            if changed.body_returned.unwrap_or(false) {
+                accuracy.set_changed();
+            }
+        }
+
+        #[cfg(feature = "stealth")]
+        if let Ok(mut stealth) = stealth_controllers.get_mut(selected) {
+            let changed = ui.collapsing("StealthController", |ui| {
+                edit_stealth_controller(ui, stealth.bypass_change_detection())
+            });
+            if changed.body_returned.unwrap_or(false) {
+                stealth.set_changed();
+            }
+        }
+
+        if let Ok((perception, perception_settings)) = perceptions.get_mut(selected) {
+            if perception.is_some() || perception_settings.is_some() {
+                ui.collapsing("AI perception", |ui| {
+                    if let Some(mut perception) = perception {
+                        let values = perception.bypass_change_detection();
+                        let mut changed = false;
+                        changed |= drag(ui, "Field of view", &mut values.fov, 1.0);
+                        changed |= drag(ui, "Vision range", &mut values.vision_range, 0.5);
+                        if changed {
+                            perception.set_changed();
+                        }
+                    }
+                    if let Some(mut perception_settings) = perception_settings {
+                        let values = perception_settings.bypass_change_detection();
+                        let mut changed = false;
+                        changed |= drag(ui, "Settings FOV", &mut values.fov, 1.0);
+                        changed |= drag(ui, "Settings range", &mut values.range, 0.5);
+                        changed |= drag(ui, "Hearing range", &mut values.hearing_range, 0.5);
+                        if changed {
+                            perception_settings.set_changed();
+                        }
+                    }
+                });
+            }
+        }
+
+        if let Ok(mut health) = healths.get_mut(selected) {
+            ui.collapsing("Health", |ui| {
+                let values = health.bypass_change_detection();
+                let mut changed = false;
+                changed |= drag(ui, "Current", &mut values.current, 1.0);
+                changed |= drag(ui, "Maximum", &mut values.maximum, 1.0);
+                changed |= ui.checkbox(&mut values.is_invulnerable, "Invulnerable").changed();
+                if changed {
+                    health.set_changed();
+                }
+            });
+        }
+
+        ui.separator();
+        ui.label("Events");
+        let position = transforms.get(selected).ok().map(|transform| transform.translation());
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut state.damage_amount).speed(1.0).range(0.0..=10_000.0));
+            if ui.button("Damage").clicked() {
+                damage_queue.send(DamageEvent {
+                    amount: state.damage_amount,
+                    damage_type: DamageType::Environmental,
+                    source: None,
+                    target: selected,
+                    position,
+                    direction: None,
+                    ignore_shield: false,
+                });
+            }
+            if ui.button("Kill").clicked() {
+                // Well past the remaining health so armor and multipliers don't save the target
+                let amount = healths.get(selected).map(|health| health.maximum.max(health.current) * 10.0).unwrap_or(1000.0);
+                damage_queue.send(DamageEvent {
+                    amount,
+                    damage_type: DamageType::Environmental,
+                    source: None,
+                    target: selected,
+                    position,
+                    direction: None,
+                    ignore_shield: true,
+                });
+            }
+            if ui.button("Heal").clicked() {
+                if let Ok(mut health) = healths.get_mut(selected) {
+                    health.current = health.maximum;
+                }
+            }
+        });
+        if let Some(position) = position {
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut state.noise_volume).speed(0.05).range(0.0..=10.0));
+                if ui.button("Make noise").clicked() {
+                    noise_queue.send(NoiseEvent {
+                        position,
+                        volume: state.noise_volume,
+                        source: selected,
+                    });
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
+
+fn edit_character_controller(ui: &mut egui::Ui, controller: &mut CharacterController) -> bool {
+    let mut changed = false;
+    changed |= drag(ui, "Walk speed", &mut controller.walk_speed, 0.05);
+    changed |= drag(ui, "Run speed", &mut controller.run_speed, 0.05);
+    changed |= drag(ui, "Sprint speed", &mut controller.sprint_speed, 0.05);
+    changed |= drag(ui, "Crouch speed", &mut controller.crouch_speed, 0.05);
+    changed |= drag(ui, "Turn speed", &mut controller.turn_speed, 0.1);
+    changed |= drag(ui, "Jump power", &mut controller.jump_power, 0.1);
+    changed |= drag(ui, "Acceleration", &mut controller.acceleration, 0.1);
+    changed |= drag(ui, "Deceleration", &mut controller.deceleration, 0.1);
+    changed |= ui.checkbox(&mut controller.can_move, "Can move").changed();
+    changed |= ui.checkbox(&mut controller.fall_damage_enabled, "Fall damage").changed();
+    changed
+}
+
+fn edit_camera_controller(ui: &mut egui::Ui, camera: &mut CameraController) -> bool {
+    let mut changed = false;
+    changed |= drag(ui, "Sensitivity (3rd person)", &mut camera.rot_sensitivity_3p, 0.01);
+    changed |= drag(ui, "Sensitivity (1st person)", &mut camera.rot_sensitivity_1p, 0.01);
+    changed |= drag(ui, "Min vertical angle", &mut camera.min_vertical_angle, 0.5);
+    changed |= drag(ui, "Max vertical angle", &mut camera.max_vertical_angle, 0.5);
+    changed |= drag(ui, "Distance", &mut camera.distance, 0.05);
+    changed |= drag(ui, "Min distance", &mut camera.min_distance, 0.05);
+    changed |= drag(ui, "Max distance", &mut camera.max_distance, 0.05);
+    changed |= drag(ui, "Follow smoothing", &mut camera.smooth_follow_speed, 0.1);
+    changed |= drag(ui, "Rotation smoothing", &mut camera.smooth_rotation_speed, 0.1);
+    changed |= drag(ui, "Side offset", &mut camera.side_offset, 0.01);
+    changed |= drag_vec3(ui, "Pivot offset", &mut camera.default_pivot_offset, 0.01);
+    changed |= drag_vec3(ui, "Aim pivot offset", &mut camera.aim_pivot_offset, 0.01);
+    changed |= drag(ui, "FOV", &mut camera.default_fov, 0.5);
+    changed |= drag(ui, "Aim FOV", &mut camera.aim_fov, 0.5);
+    changed |= ui.checkbox(&mut camera.use_collision, "Collision").changed();
+    changed
+}
+
+fn edit_accuracy(ui: &mut egui::Ui, accuracy: &mut Accuracy) -> bool {
+    let mut changed = false;
+    changed |= drag(ui, "Base spread", &mut accuracy.base_spread, 0.01);
+    changed |= drag(ui, "Max spread", &mut accuracy.max_spread, 0.01);
+    changed |= drag(ui, "Bloom per shot", &mut accuracy.bloom_per_shot, 0.01);
+    changed |= drag(ui, "Recovery rate", &mut accuracy.recovery_rate, 0.01);
+    changed |= drag(ui, "Movement penalty", &mut accuracy.movement_penalty, 0.01);
+    changed |= drag(ui, "ADS modifier", &mut accuracy.ads_modifier, 0.01);
+    changed |= drag(ui, "Airborne multiplier", &mut accuracy.airborne_multiplier, 0.01);
+    changed
+}
+
+#[cfg(feature = "stealth")]
+fn edit_stealth_controller(ui: &mut egui::Ui, stealth: &mut StealthController) -> bool {
+    let mut changed = false;
+    changed |= drag(ui, "Cover detection distance", &mut stealth.cover_detection_distance, 0.05);
+    changed |= drag(ui, "Cover detection angle", &mut stealth.cover_detection_angle, 0.5);
+    changed |= ui.checkbox(&mut stealth.character_need_to_crouch, "Needs to crouch").changed();
+    changed |= ui.checkbox(&mut stealth.character_cant_move, "Can't move while hidden").changed();
+    changed |= drag(ui, "Max move amount", &mut stealth.max_move_amount, 0.01);
+    changed |= ui.checkbox(&mut stealth.hidden_for_a_time, "Hidden for a time").changed();
+    changed |= drag(ui, "Hidden time", &mut stealth.hidden_for_a_time_amount, 0.1);
+    changed
+}
+
+fn drag(ui: &mut egui::Ui, label: &str, value: &mut f32, speed: f32) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        ui.add(egui::DragValue::new(value).speed(speed)).changed()
+    })
+    .inner
+}
+
+fn drag_vec3(ui: &mut egui::Ui, label: &str, value: &mut Vec3, speed: f32) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let x = ui.add(egui::DragValue::new(&mut value.x).speed(speed)).changed();
+        let y = ui.add(egui::DragValue::new(&mut value.y).speed(speed)).changed();
+        let z = ui.add(egui::DragValue::new(&mut value.z).speed(speed)).changed();
+        x || y || z
+    })
+    .inner
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct PanelFrames(u32);
+
+    fn count_panel_frames(mut frames: ResMut<PanelFrames>) {
+        frames.0 += 1;
+    }
+
+    fn tap(app: &mut App, key: KeyCode) {
+        app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(key);
+        app.update();
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.release(key);
+        keyboard.clear();
+    }
+
+    #[test]
+    fn test_toggle_key_opens_and_closes_panel() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<DebugUiSettings>()
+            .init_resource::<PanelFrames>()
+            .add_systems(Update, (toggle_debug_ui, count_panel_frames.run_if(debug_ui_visible)).chain());
+
+        app.update();
+        tap(&mut app, KeyCode::F7);
+        assert_eq!(app.world().resource::<PanelFrames>().0, 0);
+
+        tap(&mut app, KeyCode::F8);
+        app.update();
+        assert!(app.world().resource::<DebugUiSettings>().visible);
+        assert_eq!(app.world().resource::<PanelFrames>().0, 2);
+
+        tap(&mut app, KeyCode::F8);
+        app.update();
+        assert!(!app.world().resource::<DebugUiSettings>().visible);
+        assert_eq!(app.world().resource::<PanelFrames>().0, 2);
+    }
+}
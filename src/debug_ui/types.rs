@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+/// Tuning panel settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct DebugUiSettings {
+    pub visible: bool,
+    pub toggle_key: KeyCode,
+}
+
+impl Default for DebugUiSettings {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            toggle_key: KeyCode::F8,
+        }
+    }
+}
+
+/// Entity being edited and the parameters of the injected events
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct DebugUiState {
+    pub selected: Option<Entity>,
+    pub damage_amount: f32,
+    pub noise_volume: f32,
+}
+
+impl Default for DebugUiState {
+    fn default() -> Self {
+        Self {
+            selected: None,
+            damage_amount: 10.0,
+            noise_volume: 1.0,
+        }
+    }
+}
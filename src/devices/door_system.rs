@@ -19,6 +19,7 @@ use crate::character;
 use crate::devices::types::{
     DoorSystem, SingleDoorInfo, DoorMovementType, DoorType, DoorCurrentState
 };
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 // ============================================================================
 // COMPONENTS
@@ -37,17 +38,13 @@ pub struct DoorOpenCloseEvent {
     pub open: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct DoorOpenCloseEventQueue(pub Vec<DoorOpenCloseEvent>);
+pub type DoorOpenCloseEventQueue = EventQueue<DoorOpenCloseEvent>;
 
-#[derive(Resource, Default)]
-pub struct DoorLockEventQueue(pub Vec<DoorLockEvent>);
+pub type DoorLockEventQueue = EventQueue<DoorLockEvent>;
 
-#[derive(Resource, Default)]
-pub struct DoorFoundEventQueue(pub Vec<DoorFoundEvent>);
+pub type DoorFoundEventQueue = EventQueue<DoorFoundEvent>;
 
-#[derive(Resource, Default)]
-pub struct DoorActivationEventQueue(pub Vec<DoorActivationEvent>);
+pub type DoorActivationEventQueue = EventQueue<DoorActivationEvent>;
 
 /// Event for door lock/unlock
 #[derive(Debug, Clone, Event)]
@@ -228,7 +225,7 @@ pub fn handle_door_collisions(
 
         // Check if door was found (first time interaction available)
         if door.use_event_on_door_found && !door.door_found {
-            door_found_queue.0.push(DoorFoundEvent {
+            door_found_queue.send(DoorFoundEvent {
                 door_entity: entity,
                 locked: door.locked,
             });
@@ -240,10 +237,10 @@ pub fn handle_door_collisions(
 /// System to handle door activation
 pub fn handle_door_activation(
     mut door_query: Query<&mut DoorSystem>,
-    mut activation_queue: ResMut<DoorActivationEventQueue>,
+    mut activation_queue: QueueReader<DoorActivationEvent>,
     mut open_close_queue: ResMut<DoorOpenCloseEventQueue>,
 ) {
-    for event in activation_queue.0.drain(..) {
+    for event in activation_queue.read().cloned() {
         if let Ok(mut door) = door_query.get_mut(event.door_entity) {
             change_doors_state_by_button(event.door_entity, &mut door, &mut open_close_queue);
         }
@@ -328,7 +325,7 @@ fn open_doors(
         }
     }
 
-    open_close_queue.0.push(DoorOpenCloseEvent {
+    open_close_queue.send(DoorOpenCloseEvent {
         door_entity,
         open: true,
     });
@@ -372,7 +369,7 @@ fn close_doors(
         }
     }
 
-    open_close_queue.0.push(DoorOpenCloseEvent {
+    open_close_queue.send(DoorOpenCloseEvent {
         door_entity,
         open: false,
     });
@@ -393,7 +390,7 @@ fn lock_door(
         door.enter = false;
     }
 
-    lock_queue.0.push(DoorLockEvent {
+    lock_queue.send(DoorLockEvent {
         door_entity,
         locked: true,
     });
@@ -412,7 +409,7 @@ fn unlock_door(
         change_doors_state_by_button(door_entity, door, open_close_queue);
     }
 
-    lock_queue.0.push(DoorLockEvent {
+    lock_queue.send(DoorLockEvent {
         door_entity,
         locked: false,
     });
@@ -513,11 +510,11 @@ impl DoorSystem {
 
 /// System to handle door events
 pub fn handle_door_events(
-    mut open_close_queue: ResMut<DoorOpenCloseEventQueue>,
-    mut lock_queue: ResMut<DoorLockEventQueue>,
-    mut door_found_queue: ResMut<DoorFoundEventQueue>,
+    mut open_close_queue: QueueReader<DoorOpenCloseEvent>,
+    mut lock_queue: QueueReader<DoorLockEvent>,
+    mut door_found_queue: QueueReader<DoorFoundEvent>,
 ) {
-    for event in open_close_queue.0.drain(..) {
+    for event in open_close_queue.read().cloned() {
         info!(
             "Door {:?} {}",
             event.door_entity,
@@ -525,7 +522,7 @@ pub fn handle_door_events(
         );
     }
     
-    for event in lock_queue.0.drain(..) {
+    for event in lock_queue.read().cloned() {
         info!(
             "Door {:?} {}",
             event.door_entity,
@@ -533,7 +530,7 @@ pub fn handle_door_events(
         );
     }
     
-    for event in door_found_queue.0.drain(..) {
+    for event in door_found_queue.read().cloned() {
         info!(
             "Door {:?} found (locked: {})",
             event.door_entity, event.locked
@@ -556,10 +553,10 @@ impl Plugin for DoorSystemPlugin {
             .register_type::<DoorMovementType>()
             .register_type::<DoorType>()
             .register_type::<DoorCurrentState>()
-            .init_resource::<DoorOpenCloseEventQueue>()
-            .init_resource::<DoorLockEventQueue>()
-            .init_resource::<DoorFoundEventQueue>()
-            .init_resource::<DoorActivationEventQueue>()
+            .add_plugins(EventQueuePlugin::<DoorOpenCloseEvent>::default())
+            .add_plugins(EventQueuePlugin::<DoorLockEvent>::default())
+            .add_plugins(EventQueuePlugin::<DoorFoundEvent>::default())
+            .add_plugins(EventQueuePlugin::<DoorActivationEvent>::default())
             .add_systems(Update, (
                 update_door_movement,
                 handle_door_collisions,
@@ -11,6 +11,7 @@ use std::collections::HashSet;
 // ============================================================================
 
 use crate::devices::types::ElectronicDevice;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 // ============================================================================
 // COMPONENTS
@@ -71,26 +72,19 @@ pub struct ElectronicDeviceStopUsingEvent {
     pub player_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct ElectronicDeviceActivationEventQueue(pub Vec<ElectronicDeviceActivationEvent>);
+pub type ElectronicDeviceActivationEventQueue = EventQueue<ElectronicDeviceActivationEvent>;
 
-#[derive(Resource, Default)]
-pub struct ElectronicDeviceTriggerEnterEventQueue(pub Vec<ElectronicDeviceTriggerEnterEvent>);
+pub type ElectronicDeviceTriggerEnterEventQueue = EventQueue<ElectronicDeviceTriggerEnterEvent>;
 
-#[derive(Resource, Default)]
-pub struct ElectronicDeviceTriggerExitEventQueue(pub Vec<ElectronicDeviceTriggerExitEvent>);
+pub type ElectronicDeviceTriggerExitEventQueue = EventQueue<ElectronicDeviceTriggerExitEvent>;
 
-#[derive(Resource, Default)]
-pub struct ElectronicDeviceTriggerStayEventQueue(pub Vec<ElectronicDeviceTriggerStayEvent>);
+pub type ElectronicDeviceTriggerStayEventQueue = EventQueue<ElectronicDeviceTriggerStayEvent>;
 
-#[derive(Resource, Default)]
-pub struct ElectronicDeviceUnableToUseEventQueue(pub Vec<ElectronicDeviceUnableToUseEvent>);
+pub type ElectronicDeviceUnableToUseEventQueue = EventQueue<ElectronicDeviceUnableToUseEvent>;
 
-#[derive(Resource, Default)]
-pub struct ElectronicDeviceStartUsingEventQueue(pub Vec<ElectronicDeviceStartUsingEvent>);
+pub type ElectronicDeviceStartUsingEventQueue = EventQueue<ElectronicDeviceStartUsingEvent>;
 
-#[derive(Resource, Default)]
-pub struct ElectronicDeviceStopUsingEventQueue(pub Vec<ElectronicDeviceStopUsingEvent>);
+pub type ElectronicDeviceStopUsingEventQueue = EventQueue<ElectronicDeviceStopUsingEvent>;
 
 // ============================================================================
 // SYSTEMS
@@ -106,7 +100,7 @@ pub fn update_electronic_device(
         if device.player_inside && device.activate_event_on_trigger_stay {
             if time.elapsed_secs() > device.last_time_event_on_trigger_stay + device.event_on_trigger_stay_rate {
                 if let Some(player_entity) = device.current_player {
-                    trigger_stay_queue.0.push(ElectronicDeviceTriggerStayEvent {
+                    trigger_stay_queue.send(ElectronicDeviceTriggerStayEvent {
                         device_entity: entity,
                         player_entity,
                     });
@@ -120,12 +114,12 @@ pub fn update_electronic_device(
 /// System to handle electronic device activation
 pub fn handle_electronic_device_activation(
     mut device_query: Query<&mut ElectronicDevice>,
-    mut activation_queue: ResMut<ElectronicDeviceActivationEventQueue>,
+    mut activation_queue: QueueReader<ElectronicDeviceActivationEvent>,
     mut start_using_queue: ResMut<ElectronicDeviceStartUsingEventQueue>,
     mut stop_using_queue: ResMut<ElectronicDeviceStopUsingEventQueue>,
     mut unable_to_use_queue: ResMut<ElectronicDeviceUnableToUseEventQueue>,
 ) {
-    for event in activation_queue.0.drain(..) {
+    for event in activation_queue.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             activate_device(
                 &mut device,
@@ -150,7 +144,7 @@ fn activate_device(
 ) {
     if !device.device_can_be_used {
         if device.activate_event_if_unable_to_use_device {
-            unable_to_use_queue.0.push(ElectronicDeviceUnableToUseEvent {
+            unable_to_use_queue.send(ElectronicDeviceUnableToUseEvent {
                 device_entity,
                 player_entity,
             });
@@ -200,7 +194,7 @@ fn set_device_state(
     if device.using_device {
         if device.use_event_on_start_using_device {
             if let Some(player_entity) = device.current_player {
-                start_using_queue.0.push(ElectronicDeviceStartUsingEvent {
+                start_using_queue.send(ElectronicDeviceStartUsingEvent {
                     device_entity,
                     player_entity,
                 });
@@ -209,7 +203,7 @@ fn set_device_state(
     } else {
         if device.use_event_on_stop_using_device {
             if let Some(player_entity) = device.current_player {
-                stop_using_queue.0.push(ElectronicDeviceStopUsingEvent {
+                stop_using_queue.send(ElectronicDeviceStopUsingEvent {
                     device_entity,
                     player_entity,
                 });
@@ -227,12 +221,12 @@ fn move_camera(_device: &mut ElectronicDevice, _state: bool) {
 /// System to handle trigger enter
 pub fn handle_trigger_enter(
     mut device_query: Query<&mut ElectronicDevice>,
-    mut trigger_enter_queue: ResMut<ElectronicDeviceTriggerEnterEventQueue>,
+    mut trigger_enter_queue: QueueReader<ElectronicDeviceTriggerEnterEvent>,
     mut start_using_queue: ResMut<ElectronicDeviceStartUsingEventQueue>,
     mut unable_to_use_queue: ResMut<ElectronicDeviceUnableToUseEventQueue>, 
     mut stop_using_queue: ResMut<ElectronicDeviceStopUsingEventQueue>,
 ) {
-    for event in trigger_enter_queue.0.drain(..) {
+    for event in trigger_enter_queue.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             // Check if device is already being used
             if device.using_device {
@@ -270,11 +264,11 @@ pub fn handle_trigger_enter(
 /// System to handle trigger exit
 pub fn handle_trigger_exit(
     mut device_query: Query<&mut ElectronicDevice>,
-    mut trigger_exit_queue: ResMut<ElectronicDeviceTriggerExitEventQueue>,
+    mut trigger_exit_queue: QueueReader<ElectronicDeviceTriggerExitEvent>,
     mut stop_using_queue: ResMut<ElectronicDeviceStopUsingEventQueue>,
     mut start_using_queue: ResMut<ElectronicDeviceStartUsingEventQueue>,
 ) {
-    for event in trigger_exit_queue.0.drain(..) {
+    for event in trigger_exit_queue.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             // Check if using device and not free interaction
             if (device.using_device && !device.use_free_interaction) ||
@@ -392,57 +386,57 @@ impl ElectronicDevice {
 
 /// System to handle electronic device events
 pub fn handle_electronic_device_events(
-    mut activation_queue: ResMut<ElectronicDeviceActivationEventQueue>,
-    mut trigger_enter_queue: ResMut<ElectronicDeviceTriggerEnterEventQueue>,
-    mut trigger_exit_queue: ResMut<ElectronicDeviceTriggerExitEventQueue>,
-    mut trigger_stay_queue: ResMut<ElectronicDeviceTriggerStayEventQueue>,
-    mut unable_to_use_queue: ResMut<ElectronicDeviceUnableToUseEventQueue>,
-    mut start_using_queue: ResMut<ElectronicDeviceStartUsingEventQueue>,
-    mut stop_using_queue: ResMut<ElectronicDeviceStopUsingEventQueue>,
+    mut activation_queue: QueueReader<ElectronicDeviceActivationEvent>,
+    mut trigger_enter_queue: QueueReader<ElectronicDeviceTriggerEnterEvent>,
+    mut trigger_exit_queue: QueueReader<ElectronicDeviceTriggerExitEvent>,
+    mut trigger_stay_queue: QueueReader<ElectronicDeviceTriggerStayEvent>,
+    mut unable_to_use_queue: QueueReader<ElectronicDeviceUnableToUseEvent>,
+    mut start_using_queue: QueueReader<ElectronicDeviceStartUsingEvent>,
+    mut stop_using_queue: QueueReader<ElectronicDeviceStopUsingEvent>,
 ) {
-    for event in activation_queue.0.drain(..) {
+    for event in activation_queue.read().cloned() {
         info!(
             "Device {:?} activated by player {:?}",
             event.device_entity, event.player_entity
         );
     }
     
-    for event in trigger_enter_queue.0.drain(..) {
+    for event in trigger_enter_queue.read().cloned() {
         info!(
             "Player {:?} entered trigger of device {:?}",
             event.player_entity, event.device_entity
         );
     }
     
-    for event in trigger_exit_queue.0.drain(..) {
+    for event in trigger_exit_queue.read().cloned() {
         info!(
             "Player {:?} exited trigger of device {:?}",
             event.player_entity, event.device_entity
         );
     }
     
-    for event in trigger_stay_queue.0.drain(..) {
+    for event in trigger_stay_queue.read().cloned() {
         info!(
             "Player {:?} staying in trigger of device {:?}",
             event.player_entity, event.device_entity
         );
     }
     
-    for event in unable_to_use_queue.0.drain(..) {
+    for event in unable_to_use_queue.read().cloned() {
         info!(
             "Unable to use device {:?} by player {:?}",
             event.device_entity, event.player_entity
         );
     }
     
-    for event in start_using_queue.0.drain(..) {
+    for event in start_using_queue.read().cloned() {
         info!(
             "Player {:?} started using device {:?}",
             event.player_entity, event.device_entity
         );
     }
     
-    for event in stop_using_queue.0.drain(..) {
+    for event in stop_using_queue.read().cloned() {
         info!(
             "Player {:?} stopped using device {:?}",
             event.player_entity, event.device_entity
@@ -461,13 +455,13 @@ impl Plugin for ElectronicDevicePlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<ElectronicDevice>()
-            .init_resource::<ElectronicDeviceActivationEventQueue>()
-            .init_resource::<ElectronicDeviceTriggerEnterEventQueue>()
-            .init_resource::<ElectronicDeviceTriggerExitEventQueue>()
-            .init_resource::<ElectronicDeviceTriggerStayEventQueue>()
-            .init_resource::<ElectronicDeviceUnableToUseEventQueue>()
-            .init_resource::<ElectronicDeviceStartUsingEventQueue>()
-            .init_resource::<ElectronicDeviceStopUsingEventQueue>()
+            .add_plugins(EventQueuePlugin::<ElectronicDeviceActivationEvent>::default())
+            .add_plugins(EventQueuePlugin::<ElectronicDeviceTriggerEnterEvent>::default())
+            .add_plugins(EventQueuePlugin::<ElectronicDeviceTriggerExitEvent>::default())
+            .add_plugins(EventQueuePlugin::<ElectronicDeviceTriggerStayEvent>::default())
+            .add_plugins(EventQueuePlugin::<ElectronicDeviceUnableToUseEvent>::default())
+            .add_plugins(EventQueuePlugin::<ElectronicDeviceStartUsingEvent>::default())
+            .add_plugins(EventQueuePlugin::<ElectronicDeviceStopUsingEvent>::default())
             .add_systems(Update, (
                 update_electronic_device,
                 handle_electronic_device_activation,
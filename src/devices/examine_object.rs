@@ -7,6 +7,7 @@ use bevy::prelude::*;
 use bevy::input::mouse::{MouseMotion, MouseWheel};
 use bevy::ui::{PositionType, Val, AlignSelf, JustifyContent, AlignItems, UiRect};
 use crate::input::InputState;
+use crate::utils::{QueueCursor, QueueReader};
 // use bevy::ecs::event::Events;
 use std::collections::HashMap;
 use crate::devices::types::*;
@@ -75,7 +76,7 @@ pub fn handle_examine_input(
         // Cancel examine (Escape key)
         if input.just_pressed(KeyCode::Escape) {
             if examine.use_secondary_cancel_examine_function {
-                event_queue.0.push(ExamineObjectEvent {
+                event_queue.send(ExamineObjectEvent {
                     examine_entity: entity,
                     event_type: ExamineObjectEventType::Cancel,
                 });
@@ -85,7 +86,7 @@ pub fn handle_examine_input(
         // Show/hide examine message (Tab key)
         if input.just_pressed(KeyCode::Tab) {
             if examine.use_examine_message {
-                event_queue.0.push(ExamineObjectEvent {
+                event_queue.send(ExamineObjectEvent {
                     examine_entity: entity,
                     event_type: ExamineObjectEventType::ShowMessage(
                         examine.examine_message.clone(),
@@ -98,7 +99,7 @@ pub fn handle_examine_input(
         // Reset rotation (R key)
         if input.just_pressed(KeyCode::KeyR) {
             if examine.zoom_can_be_used {
-                event_queue.0.push(ExamineObjectEvent {
+                event_queue.send(ExamineObjectEvent {
                     examine_entity: entity,
                     event_type: ExamineObjectEventType::Start,
                 });
@@ -110,14 +111,11 @@ pub fn handle_examine_input(
 /// System to handle examine events
 pub fn handle_examine_events(
     mut event_queue: ResMut<ExamineObjectEventQueue>,
+    mut cursor: Local<QueueCursor<ExamineObjectEvent>>,
     mut examine_query: Query<&mut ExamineObject>,
 ) {
-    // We need to drain events to process them, but we might re-enqueue some?
-    // Bevy events are read-only-ish for readers. Custom queue we drain.
-    // But here we want to process and maybe trigger new ones.
-    // We should iterate a snapshot or handle carefully.
-    // For now, drain.
-    let events: Vec<ExamineObjectEvent> = event_queue.0.drain(..).collect();
+    // Snapshot the unread events; events sent while handling them are read next frame
+    let events: Vec<ExamineObjectEvent> = cursor.read(&event_queue).cloned().collect();
     
     for event in events {
         if let Ok(mut examine) = examine_query.get_mut(event.examine_entity) {
@@ -167,7 +165,7 @@ pub fn handle_examine_events(
                                     if place_entity == place.examine_place_transform.unwrap() {
                                         // Correct place
                                         if place.show_message_on_press {
-                                            event_queue.0.push(ExamineObjectEvent {
+                                            event_queue.send(ExamineObjectEvent {
                                                 examine_entity: event.examine_entity,
                                                 event_type: ExamineObjectEventType::ShowMessage(
                                                     place.message_on_press.clone(),
@@ -177,7 +175,7 @@ pub fn handle_examine_events(
                                         }
                                         
                                         if place.stop_use_object_on_press {
-                                            event_queue.0.push(ExamineObjectEvent {
+                                            event_queue.send(ExamineObjectEvent {
                                                 examine_entity: event.examine_entity,
                                                 event_type: ExamineObjectEventType::Stop,
                                             });
@@ -323,9 +321,9 @@ impl ExamineObject {
 
 /// System to handle examine object events
 pub fn handle_examine_object_events(
-    event_queue: Res<ExamineObjectEventQueue>,
+    mut event_queue: QueueReader<ExamineObjectEvent>,
 ) {
-    for event in event_queue.0.iter() {
+    for event in event_queue.read() {
         match &event.event_type {
             ExamineObjectEventType::Start => {
                 info!("Examine object {:?} started", event.examine_entity);
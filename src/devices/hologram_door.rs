@@ -8,6 +8,7 @@ use bevy::app::App;
 use bevy::ui::{Val, AlignSelf, JustifyContent, AlignItems, UiRect};
 use std::collections::HashSet;
 use std::time::Duration;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 // ============================================================================
 // COMPONENTS
@@ -178,26 +179,19 @@ pub struct HologramDoorOpenByExternalInputEvent {
     pub door_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct HologramDoorActivationEventQueue(pub Vec<HologramDoorActivationEvent>);
+pub type HologramDoorActivationEventQueue = EventQueue<HologramDoorActivationEvent>;
 
-#[derive(Resource, Default)]
-pub struct HologramDoorOpenEventQueue(pub Vec<HologramDoorOpenEvent>);
+pub type HologramDoorOpenEventQueue = EventQueue<HologramDoorOpenEvent>;
 
-#[derive(Resource, Default)]
-pub struct HologramDoorUnlockEventQueue(pub Vec<HologramDoorUnlockEvent>);
+pub type HologramDoorUnlockEventQueue = EventQueue<HologramDoorUnlockEvent>;
 
-#[derive(Resource, Default)]
-pub struct HologramDoorLockEventQueue(pub Vec<HologramDoorLockEvent>);
+pub type HologramDoorLockEventQueue = EventQueue<HologramDoorLockEvent>;
 
-#[derive(Resource, Default)]
-pub struct HologramDoorEnterEventQueue(pub Vec<HologramDoorEnterEvent>);
+pub type HologramDoorEnterEventQueue = EventQueue<HologramDoorEnterEvent>;
 
-#[derive(Resource, Default)]
-pub struct HologramDoorExitEventQueue(pub Vec<HologramDoorExitEvent>);
+pub type HologramDoorExitEventQueue = EventQueue<HologramDoorExitEvent>;
 
-#[derive(Resource, Default)]
-pub struct HologramDoorOpenByExternalInputEventQueue(pub Vec<HologramDoorOpenByExternalInputEvent>);
+pub type HologramDoorOpenByExternalInputEventQueue = EventQueue<HologramDoorOpenByExternalInputEvent>;
 
 // ============================================================================
 // SYSTEMS
@@ -233,10 +227,10 @@ pub fn update_hologram_door(
 /// System to handle hologram door activation
 pub fn handle_hologram_door_activation(
     mut door_query: Query<&mut HologramDoor>,
-    mut activation_queue: ResMut<HologramDoorActivationEventQueue>,
+    mut activation_queue: QueueReader<HologramDoorActivationEvent>,
     mut open_queue: ResMut<HologramDoorOpenEventQueue>,
 ) {
-    for event in activation_queue.0.drain(..) {
+    for event in activation_queue.read().cloned() {
         if let Ok(mut door) = door_query.get_mut(event.door_entity) {
             activate_device(&mut door, event.door_entity, &mut open_queue);
         }
@@ -296,7 +290,7 @@ fn start_open_door_coroutine(
     info!("Starting open door coroutine for hologram door {:?}", door_entity);
     
     // Send open event
-    open_queue.0.push(HologramDoorOpenEvent {
+    open_queue.send(HologramDoorOpenEvent {
         door_entity,
     });
 }
@@ -309,9 +303,9 @@ fn stop_open_door(door: &mut HologramDoor) {
 /// System to handle unlock hologram door
 pub fn handle_unlock_hologram_door(
     mut door_query: Query<&mut HologramDoor>,
-    mut unlock_queue: ResMut<HologramDoorUnlockEventQueue>,
+    mut unlock_queue: QueueReader<HologramDoorUnlockEvent>,
 ) {
-    for event in unlock_queue.0.drain(..) {
+    for event in unlock_queue.read().cloned() {
         if let Ok(mut door) = door_query.get_mut(event.door_entity) {
             unlock_hologram(event.door_entity, &mut door);
         }
@@ -330,9 +324,9 @@ fn unlock_hologram(door_entity: Entity, door: &mut HologramDoor) {
 /// System to handle lock hologram door
 pub fn handle_lock_hologram_door(
     mut door_query: Query<&mut HologramDoor>,
-    mut lock_queue: ResMut<HologramDoorLockEventQueue>,
+    mut lock_queue: QueueReader<HologramDoorLockEvent>,
 ) {
-    for event in lock_queue.0.drain(..) {
+    for event in lock_queue.read().cloned() {
         if let Ok(mut door) = door_query.get_mut(event.door_entity) {
             lock_hologram(event.door_entity, &mut door);
         }
@@ -373,10 +367,10 @@ fn set_hologram_text(door_entity: Entity, door: &mut HologramDoor, text: &str) {
 /// System to handle enter hologram door
 pub fn handle_enter_hologram_door(
     mut door_query: Query<&mut HologramDoor>,
-    mut enter_queue: ResMut<HologramDoorEnterEventQueue>,
+    mut enter_queue: QueueReader<HologramDoorEnterEvent>,
     mut open_queue: ResMut<HologramDoorOpenEventQueue>,
 ) {
-    for event in enter_queue.0.drain(..) {
+    for event in enter_queue.read().cloned() {
         if let Ok(mut door) = door_query.get_mut(event.door_entity) {
             entering_door(&mut door, event.door_entity, &mut open_queue);
         }
@@ -416,9 +410,9 @@ fn entering_door(
 /// System to handle exit hologram door
 pub fn handle_exit_hologram_door(
     mut door_query: Query<&mut HologramDoor>,
-    mut exit_queue: ResMut<HologramDoorExitEventQueue>,
+    mut exit_queue: QueueReader<HologramDoorExitEvent>,
 ) {
-    for event in exit_queue.0.drain(..) {
+    for event in exit_queue.read().cloned() {
         if let Ok(mut door) = door_query.get_mut(event.door_entity) {
             exiting_door(event.door_entity, &mut door);
         }
@@ -448,10 +442,10 @@ fn exiting_door(door_entity: Entity, door: &mut HologramDoor) {
 /// System to handle open hologram door by external input
 pub fn handle_open_hologram_door_by_external_input(
     mut door_query: Query<&mut HologramDoor>,
-    mut open_by_external_queue: ResMut<HologramDoorOpenByExternalInputEventQueue>,
+    mut open_by_external_queue: QueueReader<HologramDoorOpenByExternalInputEvent>,
     mut open_queue: ResMut<HologramDoorOpenEventQueue>,
 ) {
-    for event in open_by_external_queue.0.drain(..) {
+    for event in open_by_external_queue.read().cloned() {
         if let Ok(mut door) = door_query.get_mut(event.door_entity) {
             open_hologram_door_by_external_input(&mut door, event.door_entity, &mut open_queue);
         }
@@ -471,7 +465,7 @@ fn open_hologram_door_by_external_input(
     );
     
     // Send open event
-    open_queue.0.push(HologramDoorOpenEvent {
+    open_queue.send(HologramDoorOpenEvent {
         door_entity,
     });
 }
@@ -501,48 +495,48 @@ impl HologramDoor {
 
 /// System to handle hologram door events
 pub fn handle_hologram_door_events(
-    mut activation_queue: ResMut<HologramDoorActivationEventQueue>,
-    mut open_queue: ResMut<HologramDoorOpenEventQueue>,
-    mut unlock_queue: ResMut<HologramDoorUnlockEventQueue>,
-    mut lock_queue: ResMut<HologramDoorLockEventQueue>,
-    mut enter_queue: ResMut<HologramDoorEnterEventQueue>,
-    mut exit_queue: ResMut<HologramDoorExitEventQueue>,
-    mut open_by_external_queue: ResMut<HologramDoorOpenByExternalInputEventQueue>,
+    mut activation_queue: QueueReader<HologramDoorActivationEvent>,
+    mut open_queue: QueueReader<HologramDoorOpenEvent>,
+    mut unlock_queue: QueueReader<HologramDoorUnlockEvent>,
+    mut lock_queue: QueueReader<HologramDoorLockEvent>,
+    mut enter_queue: QueueReader<HologramDoorEnterEvent>,
+    mut exit_queue: QueueReader<HologramDoorExitEvent>,
+    mut open_by_external_queue: QueueReader<HologramDoorOpenByExternalInputEvent>,
 ) {
-    for event in activation_queue.0.drain(..) {
+    for event in activation_queue.read().cloned() {
         info!(
             "Hologram door {:?} activated by player {:?}",
             event.door_entity, event.player_entity
         );
     }
     
-    for event in open_queue.0.drain(..) {
+    for event in open_queue.read().cloned() {
         info!("Hologram door {:?} opened", event.door_entity);
     }
     
-    for event in unlock_queue.0.drain(..) {
+    for event in unlock_queue.read().cloned() {
         info!("Hologram door {:?} unlocked", event.door_entity);
     }
     
-    for event in lock_queue.0.drain(..) {
+    for event in lock_queue.read().cloned() {
         info!("Hologram door {:?} locked", event.door_entity);
     }
     
-    for event in enter_queue.0.drain(..) {
+    for event in enter_queue.read().cloned() {
         info!(
             "Player {:?} entered hologram door {:?}",
             event.player_entity, event.door_entity
         );
     }
     
-    for event in exit_queue.0.drain(..) {
+    for event in exit_queue.read().cloned() {
         info!(
             "Player {:?} exited hologram door {:?}",
             event.player_entity, event.door_entity
         );
     }
     
-    for event in open_by_external_queue.0.drain(..) {
+    for event in open_by_external_queue.read().cloned() {
         info!(
             "Hologram door {:?} opened by external input",
             event.door_entity
@@ -561,13 +555,13 @@ impl Plugin for HologramDoorPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<HologramDoor>()
-            .init_resource::<HologramDoorActivationEventQueue>()
-            .init_resource::<HologramDoorOpenEventQueue>()
-            .init_resource::<HologramDoorUnlockEventQueue>()
-            .init_resource::<HologramDoorLockEventQueue>()
-            .init_resource::<HologramDoorEnterEventQueue>()
-            .init_resource::<HologramDoorExitEventQueue>()
-            .init_resource::<HologramDoorOpenByExternalInputEventQueue>()
+            .add_plugins(EventQueuePlugin::<HologramDoorActivationEvent>::default())
+            .add_plugins(EventQueuePlugin::<HologramDoorOpenEvent>::default())
+            .add_plugins(EventQueuePlugin::<HologramDoorUnlockEvent>::default())
+            .add_plugins(EventQueuePlugin::<HologramDoorLockEvent>::default())
+            .add_plugins(EventQueuePlugin::<HologramDoorEnterEvent>::default())
+            .add_plugins(EventQueuePlugin::<HologramDoorExitEvent>::default())
+            .add_plugins(EventQueuePlugin::<HologramDoorOpenByExternalInputEvent>::default())
             .add_systems(Update, (
                 update_hologram_door,
                 handle_hologram_door_activation,
@@ -14,6 +14,7 @@ pub mod simple_switch;
 pub mod pressure_plate;
 pub mod recharger_station;
 pub mod examine_object;
+pub mod terminal;
 
 pub use types::*;
 pub use systems::*;
@@ -23,17 +24,23 @@ pub struct DevicesPlugin;
 impl Plugin for DevicesPlugin {
     fn build(&self, app: &mut App) {
         app
+            .register_type::<InteractionActionInfo>()
+            .register_type::<IconButtonInfo>()
+            .register_type::<RechargerStation>()
+            .register_type::<Power>()
+            .register_type::<ExamineObject>()
+            .register_type::<MoveCameraToDevice>()
             .init_resource::<DeviceList>()
             .init_resource::<DeviceUIState>()
             .init_resource::<DeviceDebugSettings>()
             .add_systems(Update, (
                 systems::detect_devices,
-                systems::update_device_ui,
+                systems::update_device_ui.in_set(crate::headless::PresentationSet),
                 systems::process_device_interaction,
                 systems::update_device_icons,
-                systems::debug_draw_device_info,
+                systems::debug_draw_device_info.in_set(crate::headless::PresentationSet),
             ).chain())
-            .add_systems(Startup, systems::setup_device_ui)
+            .add_systems(Startup, systems::setup_device_ui.in_set(crate::headless::PresentationSet))
             // Add subplugins
             .add_plugins(door_system::DoorSystemPlugin)
             .add_plugins(electronic_device::ElectronicDevicePlugin)
@@ -43,6 +50,7 @@ impl Plugin for DevicesPlugin {
             .add_plugins(simple_switch::SimpleSwitchPlugin)
             .add_plugins(pressure_plate::PressurePlatePlugin)
             .add_plugins(recharger_station::RechargerStationPlugin)
-            .add_plugins(examine_object::ExamineObjectPlugin);
+            .add_plugins(examine_object::ExamineObjectPlugin)
+            .add_plugins(terminal::TerminalPlugin);
     }
 }
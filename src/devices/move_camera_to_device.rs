@@ -8,6 +8,7 @@ use bevy::app::App;
 use std::collections::HashSet;
 use crate::devices::types::*;
 use crate::weapons::Weapon;
+use crate::utils::{EventQueuePlugin, QueueReader};
 
 // ============================================================================
 // SYSTEMS
@@ -16,11 +17,11 @@ use crate::weapons::Weapon;
 /// System to handle move camera to device
 pub fn handle_move_camera_to_device(
     mut device_query: Query<&mut MoveCameraToDevice>,
-    mut move_events: ResMut<MoveCameraToDeviceEventQueue>,
+    mut move_events: QueueReader<MoveCameraToDeviceEvent>,
     weapon_query: Query<&Weapon>,
     time: Res<Time>,
 ) {
-    for event in move_events.0.drain(..) {
+    for event in move_events.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             move_camera(&mut device, event.state, &weapon_query, &time);
         }
@@ -472,9 +473,9 @@ fn check_camera_position(device: &mut MoveCameraToDevice) {
 /// System to handle has second move camera to device
 pub fn handle_has_second_move_camera_to_device(
     mut device_query: Query<&mut MoveCameraToDevice>,
-    mut has_second_events: ResMut<HasSecondMoveCameraToDeviceEventQueue>,
+    mut has_second_events: QueueReader<HasSecondMoveCameraToDeviceEvent>,
 ) {
-    for event in has_second_events.0.drain(..) {
+    for event in has_second_events.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             has_second_move_camera_to_device(&mut device);
         }
@@ -489,9 +490,9 @@ fn has_second_move_camera_to_device(device: &mut MoveCameraToDevice) {
 /// System to handle enable free interaction state
 pub fn handle_enable_free_interaction_state(
     mut device_query: Query<&mut MoveCameraToDevice>,
-    mut enable_events: ResMut<EnableFreeInteractionStateEventQueue>,
+    mut enable_events: QueueReader<EnableFreeInteractionStateEvent>,
 ) {
-    for event in enable_events.0.drain(..) {
+    for event in enable_events.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             enable_free_interaction_state(&mut device);
         }
@@ -517,9 +518,9 @@ fn enable_free_interaction_state(device: &mut MoveCameraToDevice) {
 /// System to handle disable free interaction state
 pub fn handle_disable_free_interaction_state(
     mut device_query: Query<&mut MoveCameraToDevice>,
-    mut disable_events: ResMut<DisableFreeInteractionStateEventQueue>,
+    mut disable_events: QueueReader<DisableFreeInteractionStateEvent>,
 ) {
-    for event in disable_events.0.drain(..) {
+    for event in disable_events.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             disable_free_interaction_state(&mut device);
         }
@@ -545,9 +546,9 @@ fn disable_free_interaction_state(device: &mut MoveCameraToDevice) {
 /// System to handle stop movement
 pub fn handle_stop_movement(
     mut device_query: Query<&mut MoveCameraToDevice>,
-    mut stop_events: ResMut<StopMovementEventQueue>,
+    mut stop_events: QueueReader<StopMovementEvent>,
 ) {
-    for event in stop_events.0.drain(..) {
+    for event in stop_events.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             stop_movement(&mut device);
         }
@@ -563,9 +564,9 @@ fn stop_movement(device: &mut MoveCameraToDevice) {
 /// System to handle set current player use device button enabled state
 pub fn handle_set_current_player_use_device_button_enabled_state(
     mut device_query: Query<&mut MoveCameraToDevice>,
-    mut set_events: ResMut<SetCurrentPlayerUseDeviceButtonEnabledStateEventQueue>,
+    mut set_events: QueueReader<SetCurrentPlayerUseDeviceButtonEnabledStateEvent>,
 ) {
-    for event in set_events.0.drain(..) {
+    for event in set_events.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             set_current_player_use_device_button_enabled_state(&mut device, event.state);
         }
@@ -665,12 +666,12 @@ pub struct MoveCameraToDevicePlugin;
 impl Plugin for MoveCameraToDevicePlugin {
     fn build(&self, app: &mut bevy::app::App) {
         app
-            .init_resource::<MoveCameraToDeviceEventQueue>()
-            .init_resource::<HasSecondMoveCameraToDeviceEventQueue>()
-            .init_resource::<EnableFreeInteractionStateEventQueue>()
-            .init_resource::<DisableFreeInteractionStateEventQueue>()
-            .init_resource::<StopMovementEventQueue>()
-            .init_resource::<SetCurrentPlayerUseDeviceButtonEnabledStateEventQueue>()
+            .add_plugins(EventQueuePlugin::<MoveCameraToDeviceEvent>::default())
+            .add_plugins(EventQueuePlugin::<HasSecondMoveCameraToDeviceEvent>::default())
+            .add_plugins(EventQueuePlugin::<EnableFreeInteractionStateEvent>::default())
+            .add_plugins(EventQueuePlugin::<DisableFreeInteractionStateEvent>::default())
+            .add_plugins(EventQueuePlugin::<StopMovementEvent>::default())
+            .add_plugins(EventQueuePlugin::<SetCurrentPlayerUseDeviceButtonEnabledStateEvent>::default())
             .add_systems(Update, (
                 handle_move_camera_to_device,
                 handle_has_second_move_camera_to_device,
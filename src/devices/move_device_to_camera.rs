@@ -8,6 +8,7 @@ use bevy::app::App;
 use bevy::ui::{PositionType, Val, AlignSelf, JustifyContent, AlignItems, UiRect};
 use std::collections::HashSet;
 use crate::weapons::Weapon;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 // ============================================================================
 // COMPONENTS
@@ -304,20 +305,16 @@ pub struct ResetRotationAndPositionEvent {
 }
 
 /// Queue for MoveCameraToDeviceEvent
-#[derive(Resource, Default)]
-pub struct MoveCameraToDeviceEventQueue(pub Vec<MoveCameraToDeviceEvent>);
+pub type MoveCameraToDeviceEventQueue = EventQueue<MoveCameraToDeviceEvent>;
 
 /// Queue for ChangeDeviceZoomEvent
-#[derive(Resource, Default)]
-pub struct ChangeDeviceZoomEventQueue(pub Vec<ChangeDeviceZoomEvent>);
+pub type ChangeDeviceZoomEventQueue = EventQueue<ChangeDeviceZoomEvent>;
 
 /// Queue for ResetRotationEvent
-#[derive(Resource, Default)]
-pub struct ResetRotationEventQueue(pub Vec<ResetRotationEvent>);
+pub type ResetRotationEventQueue = EventQueue<ResetRotationEvent>;
 
 /// Queue for ResetRotationAndPositionEvent
-#[derive(Resource, Default)]
-pub struct ResetRotationAndPositionEventQueue(pub Vec<ResetRotationAndPositionEvent>);
+pub type ResetRotationAndPositionEventQueue = EventQueue<ResetRotationAndPositionEvent>;
 
 // ============================================================================
 // SYSTEMS
@@ -326,11 +323,11 @@ pub struct ResetRotationAndPositionEventQueue(pub Vec<ResetRotationAndPositionEv
 /// System to handle move camera to device
 pub fn handle_move_camera_to_device(
     mut device_query: Query<&mut MoveDeviceToCamera>,
-    mut move_events: ResMut<MoveCameraToDeviceEventQueue>,
+    mut move_events: QueueReader<MoveCameraToDeviceEvent>,
     weapon_query: Query<&Weapon>,
     time: Res<Time>,
 ) {
-    for event in move_events.0.drain(..) {
+    for event in move_events.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             move_camera(&mut device, event.state, &weapon_query, &time);
         }
@@ -636,9 +633,9 @@ fn set_layer_list_state(device: &mut MoveDeviceToCamera, state: bool) {
 /// System to handle change device zoom
 pub fn handle_change_device_zoom(
     mut device_query: Query<&mut MoveDeviceToCamera>,
-    mut zoom_events: ResMut<ChangeDeviceZoomEventQueue>,
+    mut zoom_events: QueueReader<ChangeDeviceZoomEvent>,
 ) {
-    for event in zoom_events.0.drain(..) {
+    for event in zoom_events.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             change_device_zoom(&mut device, event.zoom_in);
         }
@@ -672,9 +669,9 @@ fn change_device_zoom(device: &mut MoveDeviceToCamera, zoom_in: bool) {
 /// System to handle reset rotation
 pub fn handle_reset_rotation(
     mut device_query: Query<&mut MoveDeviceToCamera>,
-    mut reset_events: ResMut<ResetRotationEventQueue>,
+    mut reset_events: QueueReader<ResetRotationEvent>,
 ) {
-    for event in reset_events.0.drain(..) {
+    for event in reset_events.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             reset_rotation(&mut device);
         }
@@ -692,9 +689,9 @@ fn reset_rotation(device: &mut MoveDeviceToCamera) {
 /// System to handle reset rotation and position
 pub fn handle_reset_rotation_and_position(
     mut device_query: Query<&mut MoveDeviceToCamera>,
-    mut reset_events: ResMut<ResetRotationAndPositionEventQueue>,
+    mut reset_events: QueueReader<ResetRotationAndPositionEvent>,
 ) {
-    for event in reset_events.0.drain(..) {
+    for event in reset_events.read().cloned() {
         if let Ok(mut device) = device_query.get_mut(event.device_entity) {
             reset_rotation_and_position(&mut device);
         }
@@ -800,10 +797,10 @@ impl Plugin for MoveDeviceToCameraPlugin {
         app
             .register_type::<MoveDeviceToCamera>()
             .register_type::<LayerInfo>()
-            .init_resource::<MoveCameraToDeviceEventQueue>()
-            .init_resource::<ChangeDeviceZoomEventQueue>()
-            .init_resource::<ResetRotationEventQueue>()
-            .init_resource::<ResetRotationAndPositionEventQueue>()
+            .add_plugins(EventQueuePlugin::<MoveCameraToDeviceEvent>::default())
+            .add_plugins(EventQueuePlugin::<ChangeDeviceZoomEvent>::default())
+            .add_plugins(EventQueuePlugin::<ResetRotationEvent>::default())
+            .add_plugins(EventQueuePlugin::<ResetRotationAndPositionEvent>::default())
             .add_systems(Update, (
                 handle_move_camera_to_device,
                 handle_change_device_zoom,
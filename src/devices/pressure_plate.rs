@@ -8,12 +8,14 @@ use bevy::prelude::*;
 use avian3d::prelude::*;
 use std::collections::HashSet;
 use std::time::Duration;
+use crate::tags::TagIndex;
 
 // ============================================================================
 // COMPONENTS
 // ============================================================================
 
 use crate::devices::types::PressurePlate;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 // ============================================================================
 // COMPONENTS
@@ -38,20 +40,26 @@ pub struct PressurePlateDeactivated {
     pub plate_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct PressurePlateActivatedQueue(pub Vec<PressurePlateActivated>);
+pub type PressurePlateActivatedQueue = EventQueue<PressurePlateActivated>;
 
-#[derive(Resource, Default)]
-pub struct PressurePlateDeactivatedQueue(pub Vec<PressurePlateDeactivated>);
+pub type PressurePlateDeactivatedQueue = EventQueue<PressurePlateDeactivated>;
 
 // ============================================================================
 // SYSTEMS
 // ============================================================================
 
+fn is_ignored_by_plate(plate: &PressurePlate, entity: Entity, name: Option<&Name>, tag_index: &TagIndex) -> bool {
+    if name.is_some_and(|name| plate.tags_to_ignore.contains(name.as_str())) {
+        return true;
+    }
+    tag_index.tags_of(entity).iter().any(|tag| plate.tags_to_ignore.contains(tag))
+}
+
 /// System to handle collision detection for pressure plates
 pub fn handle_pressure_plate_collisions(
     mut plate_query: Query<(Entity, &mut PressurePlate, &CollidingEntities)>,
     name_query: Query<&Name>,
+    tag_index: Res<TagIndex>,
     mut activated_queue: ResMut<PressurePlateActivatedQueue>,
     mut deactivated_queue: ResMut<PressurePlateDeactivatedQueue>,
 ) {
@@ -60,15 +68,9 @@ pub fn handle_pressure_plate_collisions(
         let mut objects_on_plate = HashSet::new();
         
         for &colliding_entity in colliding_entities.iter() {
-            // Check if we should ignore this entity based on tags (Name)
-            if let Ok(name) = name_query.get(colliding_entity) {
-                if plate.tags_to_ignore.contains(&name.to_string()) {
-                    continue;
-                }
-            } else {
-                 // If no name, check if we ignore "Untagged" or similar default? 
-                 // For now, if no name, we assume it's valid unless explicitly ignored which requires a name.
-                 // Or we could check for specific components if tags aren't sufficient.
+            // Check if we should ignore this entity based on tags (GameTag, or Name for older setups)
+            if is_ignored_by_plate(&plate, colliding_entity, name_query.get(colliding_entity).ok(), &tag_index) {
+                continue;
             }
 
             objects_on_plate.insert(colliding_entity);
@@ -85,7 +87,7 @@ pub fn handle_pressure_plate_collisions(
                     plate.disable_function_called = true;
                     plate.active_function_called = false;
                     
-                    deactivated_queue.0.push(PressurePlateDeactivated {
+                    deactivated_queue.send(PressurePlateDeactivated {
                         plate_entity: entity,
                     });
                     
@@ -102,7 +104,7 @@ pub fn handle_pressure_plate_collisions(
                 plate.disable_function_called = false;
                 plate.using_plate = true;
                 
-                activated_queue.0.push(PressurePlateActivated {
+                activated_queue.send(PressurePlateActivated {
                     plate_entity: entity,
                     objects_on_plate: objects_on_plate.clone(),
                 });
@@ -117,6 +119,7 @@ pub fn handle_pressure_plate_collisions(
 pub fn update_pressure_plate_position(
     mut plate_query: Query<(Entity, &mut PressurePlate, &Transform)>,
     object_query: Query<(Entity, &Transform, &Collider, Option<&Name>)>,
+    tag_index: Res<TagIndex>,
     mut activated_queue: ResMut<PressurePlateActivatedQueue>,
     mut deactivated_queue: ResMut<PressurePlateDeactivatedQueue>,
 ) {
@@ -126,10 +129,8 @@ pub fn update_pressure_plate_position(
         // Check distance to objects
         for (obj_entity, object_transform, _collider, maybe_name) in object_query.iter() {
             // Skip ignored tags
-            if let Some(name) = maybe_name {
-                if plate.tags_to_ignore.contains(&name.to_string()) {
-                    continue;
-                }
+            if is_ignored_by_plate(&plate, obj_entity, maybe_name, &tag_index) {
+                continue;
             }
             
             let distance = plate_transform
@@ -151,7 +152,7 @@ pub fn update_pressure_plate_position(
                     plate.disable_function_called = true;
                     plate.active_function_called = false;
                     
-                    deactivated_queue.0.push(PressurePlateDeactivated {
+                    deactivated_queue.send(PressurePlateDeactivated {
                         plate_entity: entity,
                     });
                     
@@ -167,7 +168,7 @@ pub fn update_pressure_plate_position(
                 plate.disable_function_called = false;
                 plate.using_plate = true;
                 
-                activated_queue.0.push(PressurePlateActivated {
+                activated_queue.send(PressurePlateActivated {
                     plate_entity: entity,
                     objects_on_plate: objects_on_plate.clone(),
                 });
@@ -258,10 +259,10 @@ impl PressurePlate {
 
 /// System to handle pressure plate events
 pub fn handle_pressure_plate_events(
-    mut activated_queue: ResMut<PressurePlateActivatedQueue>,
-    mut deactivated_queue: ResMut<PressurePlateDeactivatedQueue>,
+    mut activated_queue: QueueReader<PressurePlateActivated>,
+    mut deactivated_queue: QueueReader<PressurePlateDeactivated>,
 ) {
-    for event in activated_queue.0.drain(..) {
+    for event in activated_queue.read().cloned() {
         info!(
             "Pressure plate {:?} activated with {} objects",
             event.plate_entity,
@@ -269,7 +270,7 @@ pub fn handle_pressure_plate_events(
         );
     }
     
-    for event in deactivated_queue.0.drain(..) {
+    for event in deactivated_queue.read().cloned() {
         info!("Pressure plate {:?} deactivated", event.plate_entity);
     }
 }
@@ -285,8 +286,8 @@ impl Plugin for PressurePlatePlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<PressurePlate>()
-            .init_resource::<PressurePlateActivatedQueue>()
-            .init_resource::<PressurePlateDeactivatedQueue>()
+            .add_plugins(EventQueuePlugin::<PressurePlateActivated>::default())
+            .add_plugins(EventQueuePlugin::<PressurePlateDeactivated>::default())
             .add_systems(Update, (
                 handle_pressure_plate_collisions,
                 update_pressure_plate_position,
@@ -7,6 +7,8 @@ use bevy::prelude::*;
 use bevy::audio::{AudioSource, PlaybackSettings};
 use std::time::Duration;
 use crate::devices::types::*;
+use crate::vfx::{VfxKey, VfxRequestQueue};
+use crate::utils::QueueReader;
 
 // ============================================================================
 // SYSTEMS
@@ -88,10 +90,12 @@ pub fn update_recharger_station(
 /// System to handle button activation
 pub fn handle_recharger_station_activation(
     mut station_query: Query<&mut RechargerStation>,
-    mut activation_queue: ResMut<RechargerStationActivationQueue>,
+    mut activation_queue: QueueReader<RechargerStationActivation>,
     mut healing_started_queue: ResMut<RechargerStationHealingStartedQueue>,
+    mut vfx_queue: ResMut<VfxRequestQueue>,
+    transform_query: Query<&GlobalTransform>,
 ) {
-    for event in activation_queue.0.drain(..) {
+    for event in activation_queue.read().cloned() {
         if let Ok(mut station) = station_query.get_mut(event.station_entity) {
             // Check if player is inside and not fully healed
             if station.inside && !station.fully_healed {
@@ -113,8 +117,12 @@ pub fn handle_recharger_station_activation(
                 
                 // Disable button collider
                 station.button_collider = None;
+
+                if let Some(transform) = station.player.and_then(|player| transform_query.get(player).ok()) {
+                    vfx_queue.spawn(VfxKey::Heal, transform.translation());
+                }
                 
-                healing_started_queue.0.push(RechargerStationHealingStarted {
+                healing_started_queue.send(RechargerStationHealingStarted {
                     station_entity: event.station_entity,
                     player_entity: station.player.unwrap_or(Entity::PLACEHOLDER),
                 });
@@ -135,7 +143,7 @@ fn stop_healing(
     // Stop audio loop
     // In Bevy, we'd stop the audio source loop
     
-    fully_healed_queue.0.push(RechargerStationFullyHealed {
+    fully_healed_queue.send(RechargerStationFullyHealed {
         station_entity: Entity::PLACEHOLDER, // station.entities() not valid on component
         player_entity,
     });
@@ -201,41 +209,41 @@ impl RechargerStation {
 
 /// System to handle recharger station events
 pub fn handle_recharger_station_events(
-    mut entered_queue: ResMut<RechargerStationEnteredQueue>,
-    mut exited_queue: ResMut<RechargerStationExitedQueue>,
-    mut healing_started_queue: ResMut<RechargerStationHealingStartedQueue>,
-    mut healing_stopped_queue: ResMut<RechargerStationHealingStoppedQueue>,
-    mut fully_healed_queue: ResMut<RechargerStationFullyHealedQueue>,
+    mut entered_queue: QueueReader<RechargerStationEntered>,
+    mut exited_queue: QueueReader<RechargerStationExited>,
+    mut healing_started_queue: QueueReader<RechargerStationHealingStarted>,
+    mut healing_stopped_queue: QueueReader<RechargerStationHealingStopped>,
+    mut fully_healed_queue: QueueReader<RechargerStationFullyHealed>,
 ) {
-    for event in entered_queue.0.drain(..) {
+    for event in entered_queue.read().cloned() {
         info!(
             "Player {:?} entered recharger station {:?}",
             event.player_entity, event.station_entity
         );
     }
     
-    for event in exited_queue.0.drain(..) {
+    for event in exited_queue.read().cloned() {
         info!(
             "Player {:?} exited recharger station {:?}",
             event.player_entity, event.station_entity
         );
     }
     
-    for event in healing_started_queue.0.drain(..) {
+    for event in healing_started_queue.read().cloned() {
         info!(
             "Healing started for player {:?} at station {:?}",
             event.player_entity, event.station_entity
         );
     }
     
-    for event in healing_stopped_queue.0.drain(..) {
+    for event in healing_stopped_queue.read().cloned() {
         info!(
             "Healing stopped for player {:?} at station {:?}",
             event.player_entity, event.station_entity
         );
     }
     
-    for event in fully_healed_queue.0.drain(..) {
+    for event in fully_healed_queue.read().cloned() {
         info!(
             "Player {:?} fully healed at station {:?}",
             event.player_entity, event.station_entity
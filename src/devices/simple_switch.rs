@@ -10,7 +10,7 @@ use bevy::app::App;
 
 use crate::input::{InputState, InputAction};
 use crate::character::CharacterController;
-use crate::interaction::{Interactable, InteractionType, InteractionEvent, InteractionEventQueue};
+use crate::interaction::{Interactable, InteractionType, InteractionEvent};
 use crate::devices::DeviceStringAction;
 
 use std::time::Duration;
@@ -20,6 +20,7 @@ use std::time::Duration;
 // ============================================================================
 
 use crate::devices::types::{SimpleSwitch, SimpleSwitchEventType};
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 // ============================================================================
 // COMPONENTS
@@ -41,8 +42,7 @@ pub struct SimpleSwitchEvent {
 }
 
 /// Queue for SimpleSwitchEvent
-#[derive(Resource, Default)]
-pub struct SimpleSwitchEventQueue(pub Vec<SimpleSwitchEvent>);
+pub type SimpleSwitchEventQueue = EventQueue<SimpleSwitchEvent>;
 
 // ============================================================================
 // SYSTEM PARAMETERS
@@ -68,9 +68,9 @@ pub struct SimpleSwitchSystemParams<'w, 's> {
 /// System to handle simple switch activation
 pub fn handle_simple_switch_activation(
     mut params: SimpleSwitchSystemParams,
-    mut interaction_events: ResMut<InteractionEventQueue>,
+    mut interaction_events: QueueReader<InteractionEvent>,
 ) {
-    for event in interaction_events.0.drain(..) {
+    for event in interaction_events.read().copied() {
         if let InteractionType::Activate = event.interaction_type {
             if let Ok(mut switch) = params.switch_query.get_mut(event.target) {
                 activate_switch(
@@ -172,7 +172,7 @@ fn activate_switch(
     if switch.send_current_user {
         if let Some(target) = switch.object_to_active {
             // Send message to target
-            event_writer.0.push(SimpleSwitchEvent {
+            event_writer.send(SimpleSwitchEvent {
                 switch_entity,
                 event_type: SimpleSwitchEventType::SingleSwitch,
                 target_entity: Some(target),
@@ -184,7 +184,7 @@ fn activate_switch(
     // Use Unity-style events
     if switch.use_unity_events {
         if switch.use_single_switch {
-            event_writer.0.push(SimpleSwitchEvent {
+            event_writer.send(SimpleSwitchEvent {
                 switch_entity,
                 event_type: SimpleSwitchEventType::SingleSwitch,
                 target_entity: switch.object_to_active,
@@ -196,7 +196,7 @@ fn activate_switch(
             } else {
                 SimpleSwitchEventType::TurnOff
             };
-            event_writer.0.push(SimpleSwitchEvent {
+            event_writer.send(SimpleSwitchEvent {
                 switch_entity,
                 event_type,
                 target_entity: switch.object_to_active,
@@ -207,14 +207,14 @@ fn activate_switch(
         // Use function name approach
         if let Some(target) = switch.object_to_active {
             if switch.send_this_button {
-                event_writer.0.push(SimpleSwitchEvent {
+                event_writer.send(SimpleSwitchEvent {
                     switch_entity,
                     event_type: SimpleSwitchEventType::SingleSwitch,
                     target_entity: Some(target),
                     parameter: Some(switch_entity),
                 });
             } else {
-                event_writer.0.push(SimpleSwitchEvent {
+                event_writer.send(SimpleSwitchEvent {
                     switch_entity,
                     event_type: SimpleSwitchEventType::SingleSwitch,
                     target_entity: Some(target),
@@ -319,9 +319,9 @@ impl SimpleSwitch {
 
 /// System to handle simple switch events
 pub fn handle_simple_switch_events(
-    mut event_reader: ResMut<SimpleSwitchEventQueue>,
+    mut event_reader: QueueReader<SimpleSwitchEvent>,
 ) {
-    for event in event_reader.0.drain(..) {
+    for event in event_reader.read().cloned() {
         match event.event_type {
             SimpleSwitchEventType::SingleSwitch => {
                 info!("Single switch event triggered for entity {:?}", event.switch_entity);
@@ -347,7 +347,7 @@ impl Plugin for SimpleSwitchPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<SimpleSwitch>()
-            .init_resource::<SimpleSwitchEventQueue>()
+            .add_plugins(EventQueuePlugin::<SimpleSwitchEvent>::default())
             .add_systems(Update, (
                 handle_simple_switch_activation,
                 handle_simple_switch_events,
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use bevy::ui::{PositionType, Val, AlignSelf, JustifyContent, AlignItems, UiRect};
 use avian3d::prelude::*;
-use crate::input::{InputState, InputAction, InputBuffer};
+use crate::input::{GlyphProvider, InputState, InputAction, InputBuffer, InputMap};
 use crate::character::CharacterController;
 use crate::camera::{CameraController, CameraMode};
 use super::types::*;
@@ -42,6 +42,8 @@ pub fn setup_device_ui(mut commands: Commands) {
 /// System to update device UI
 pub fn update_device_ui(
     device_ui_state: Res<DeviceUIState>,
+    glyphs: Res<GlyphProvider>,
+    input_map: Res<InputMap>,
     mut ui_query: Query<(&mut Visibility, &Children), With<DevicePrompt>>,
     mut text_query: Query<(&mut Text, &mut TextColor)>,
 ) {
@@ -52,8 +54,14 @@ pub fn update_device_ui(
             for child in children.iter() {
                 if let Ok((mut text, mut text_color)) = text_query.get_mut(child) {
                     text_color.0 = Color::WHITE;
+                    // An explicit key text wins over the device's Interact glyph
+                    let key_text = if device_ui_state.current_key_text.is_empty() {
+                        glyphs.text(InputAction::Interact, &input_map)
+                    } else {
+                        device_ui_state.current_key_text.clone()
+                    };
                     text.0 = format!("Press {} to {} {}",
-                        device_ui_state.current_key_text,
+                        key_text,
                         device_ui_state.current_action_text,
                         device_ui_state.current_object_name
                     );
@@ -0,0 +1,807 @@
+//! Terminal Device
+//!
+//! In-world computers. Interacting with a [`Terminal`] opens its content, a
+//! `.terminal.ron` asset of pages with selectable entries, in a full-screen
+//! panel or in one pinned over the terminal. Entries read logs, open other
+//! pages, activate devices by name (what a switch wired to them does), transfer
+//! currency and accept the quest of a quest station. The panel is a
+//! [`FocusScope`], so mouse, keyboard and gamepad drive it like any menu, and
+//! the `Terminal` input context holds the rest of the game while it is open.
+//!
+//! A locked terminal starts with the hacking minigame: find the password among
+//! the content's words, each wrong guess telling how many letters sit in the
+//! right place. Running out of attempts locks the terminal out for a while.
+//!
+//! ```ron
+//! (
+//!     title: "SECURITY OFFICE",
+//!     pages: [
+//!         (id: "home", entries: [
+//!             (label: "Shift log", action: ReadLog(title: "Shift log", text: "Nobody goes below deck 3.")),
+//!             (label: "Doors", action: OpenPage("doors")),
+//!             (label: "Withdraw 50", action: TransferCurrency(50.0)),
+//!         ]),
+//!         (id: "doors", entries: [
+//!             (label: "Cargo bay door", action: ToggleDevice("Cargo Door")),
+//!         ]),
+//!     ],
+//!     hack: Some((words: ["LATCH", "MATCH", "PATCH", "WATCH", "BATCH"], attempts: 4)),
+//! )
+//! ```
+
+use std::fmt;
+use bevy::asset::{io::Reader, AssetLoader, LoadContext};
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::currency::{transaction_id, AddCurrencyEvent, AddCurrencyEventQueue, CurrencyType, RemoveCurrencyEvent, RemoveCurrencyEventQueue};
+use crate::input::{FocusCancel, FocusScope, InputContext, InputContextStack, UiCancelButton};
+use crate::interaction::{InteractionEvent, InteractionEventQueue, InteractionType};
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
+
+// ============================================================================
+// CONTENT
+// ============================================================================
+
+/// Pages of a terminal, read from `.terminal.ron` files
+#[derive(Asset, TypePath, Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TerminalContent {
+    pub title: String,
+    /// The first page is the home page
+    pub pages: Vec<TerminalPage>,
+    /// Minigame of locked terminals
+    #[serde(default)]
+    pub hack: Option<TerminalHack>,
+}
+
+impl TerminalContent {
+    pub fn from_ron(text: &str) -> Result<Self, String> {
+        ron::from_str(text).map_err(|e| format!("Invalid terminal content: {}", e))
+    }
+
+    /// Index of the page `id`
+    pub fn page(&self, id: &str) -> Option<usize> {
+        self.pages.iter().position(|page| page.id == id)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TerminalPage {
+    pub id: String,
+    /// Text above the entries
+    #[serde(default)]
+    pub header: String,
+    pub entries: Vec<TerminalEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalEntry {
+    pub label: String,
+    pub action: TerminalAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TerminalAction {
+    ReadLog { title: String, text: String },
+    OpenPage(String),
+    /// Activates the devices with this `Name`, like a switch wired to them
+    ToggleDevice(String),
+    /// Gold given to the user; negative amounts are taken from them
+    TransferCurrency(f32),
+    /// Accepts the quest of the quest station with this `Name`
+    StartQuest(String),
+}
+
+/// Password minigame of a locked terminal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalHack {
+    /// Candidates; one of them is picked as the password
+    pub words: Vec<String>,
+    #[serde(default = "default_hack_attempts")]
+    pub attempts: u32,
+    /// Seconds the terminal stays locked out after a failed hack
+    #[serde(default = "default_hack_lockout")]
+    pub lockout: f32,
+}
+
+fn default_hack_attempts() -> u32 {
+    4
+}
+
+fn default_hack_lockout() -> f32 {
+    30.0
+}
+
+/// Reads [`TerminalContent`] assets from `.terminal.ron` files
+#[derive(Default)]
+pub struct TerminalContentLoader;
+
+#[derive(Debug)]
+pub enum TerminalContentError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for TerminalContentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not read terminal content: {}", error),
+            Self::Ron(error) => write!(f, "Invalid terminal content: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for TerminalContentError {}
+
+impl From<std::io::Error> for TerminalContentError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl AssetLoader for TerminalContentLoader {
+    type Asset = TerminalContent;
+    type Settings = ();
+    type Error = TerminalContentError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        ron::de::from_bytes(&bytes).map_err(TerminalContentError::Ron)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["terminal.ron"]
+    }
+}
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect)]
+pub enum TerminalDisplay {
+    #[default]
+    FullScreen,
+    /// A panel over the terminal's screen, at `offset` from its origin
+    InWorld { offset: Vec3 },
+}
+
+/// Computer the player can log into
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Terminal {
+    pub content: Handle<TerminalContent>,
+    pub display: TerminalDisplay,
+    pub enabled: bool,
+    /// Needs the content's hacking minigame before the pages open
+    pub locked: bool,
+    /// Seconds left before a failed hack can be retried
+    pub lockout_timer: f32,
+}
+
+impl Terminal {
+    pub fn new(content: Handle<TerminalContent>) -> Self {
+        Self {
+            content,
+            display: TerminalDisplay::FullScreen,
+            enabled: true,
+            locked: false,
+            lockout_timer: 0.0,
+        }
+    }
+
+    pub fn locked(mut self) -> Self {
+        self.locked = true;
+        self
+    }
+
+    pub fn in_world(mut self, offset: Vec3) -> Self {
+        self.display = TerminalDisplay::InWorld { offset };
+        self
+    }
+}
+
+// ============================================================================
+// SESSION
+// ============================================================================
+
+/// Outcome of a hacking guess
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HackGuess {
+    Granted,
+    /// Wrong word, with its letters in the right place
+    Denied { likeness: usize },
+    /// Wrong word and no attempts left
+    Failed,
+}
+
+/// Letters of `a` and `b` in the same place
+pub fn word_likeness(a: &str, b: &str) -> usize {
+    a.chars().zip(b.chars()).filter(|(a, b)| a.eq_ignore_ascii_case(b)).count()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HackSession {
+    pub words: Vec<String>,
+    password: usize,
+    pub attempts_left: u32,
+    /// Wrong guesses with their likeness
+    pub guesses: Vec<(String, usize)>,
+}
+
+impl HackSession {
+    pub fn new(hack: &TerminalHack, password: usize) -> Self {
+        Self {
+            words: hack.words.clone(),
+            password: password.min(hack.words.len().saturating_sub(1)),
+            attempts_left: hack.attempts.max(1),
+            guesses: Vec::new(),
+        }
+    }
+
+    pub fn guess(&mut self, index: usize) -> HackGuess {
+        let (Some(word), Some(password)) = (self.words.get(index), self.words.get(self.password)) else {
+            return HackGuess::Denied { likeness: 0 };
+        };
+        if index == self.password {
+            return HackGuess::Granted;
+        }
+
+        let likeness = word_likeness(word, password);
+        self.guesses.push((word.clone(), likeness));
+        self.attempts_left = self.attempts_left.saturating_sub(1);
+        if self.attempts_left == 0 {
+            HackGuess::Failed
+        } else {
+            HackGuess::Denied { likeness }
+        }
+    }
+
+    pub fn was_guessed(&self, index: usize) -> bool {
+        self.words.get(index).is_some_and(|word| self.guesses.iter().any(|(guess, _)| guess == word))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TerminalView {
+    Page(usize),
+    Log { page: usize, title: String, text: String },
+    Hack(HackSession),
+    /// Locked without a minigame, or locked out after a failed hack
+    Denied,
+}
+
+/// Choice made in the terminal panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalCommand {
+    Entry(usize),
+    Guess(usize),
+    Back,
+    Close,
+}
+
+/// What a command asks of the world
+#[derive(Debug, Clone, PartialEq)]
+pub enum TerminalOutcome {
+    Stay,
+    Close,
+    Run(TerminalAction),
+    Hacked,
+    HackFailed,
+}
+
+/// Terminal the player is logged into
+#[derive(Debug, Clone)]
+pub struct ActiveTerminal {
+    pub terminal: Entity,
+    pub user: Entity,
+    pub view: TerminalView,
+    /// Pages `Back` returns to
+    pub history: Vec<usize>,
+}
+
+impl ActiveTerminal {
+    pub fn new(terminal: Entity, user: Entity, view: TerminalView) -> Self {
+        Self { terminal, user, view, history: Vec::new() }
+    }
+
+    pub fn apply(&mut self, command: TerminalCommand, content: &TerminalContent) -> TerminalOutcome {
+        match command {
+            TerminalCommand::Close => TerminalOutcome::Close,
+            TerminalCommand::Back => match &self.view {
+                TerminalView::Log { page, .. } => {
+                    self.view = TerminalView::Page(*page);
+                    TerminalOutcome::Stay
+                }
+                TerminalView::Page(_) => match self.history.pop() {
+                    Some(page) => {
+                        self.view = TerminalView::Page(page);
+                        TerminalOutcome::Stay
+                    }
+                    None => TerminalOutcome::Close,
+                },
+                TerminalView::Hack(_) | TerminalView::Denied => TerminalOutcome::Close,
+            },
+            TerminalCommand::Entry(index) => {
+                let TerminalView::Page(page) = self.view else { return TerminalOutcome::Stay };
+                let Some(entry) = content.pages.get(page).and_then(|page| page.entries.get(index)) else {
+                    return TerminalOutcome::Stay;
+                };
+                match &entry.action {
+                    TerminalAction::ReadLog { title, text } => {
+                        self.view = TerminalView::Log { page, title: title.clone(), text: text.clone() };
+                        TerminalOutcome::Run(entry.action.clone())
+                    }
+                    TerminalAction::OpenPage(id) => {
+                        if let Some(next) = content.page(id) {
+                            self.history.push(page);
+                            self.view = TerminalView::Page(next);
+                        } else {
+                            warn!("Terminal page '{}' not found", id);
+                        }
+                        TerminalOutcome::Stay
+                    }
+                    action => TerminalOutcome::Run(action.clone()),
+                }
+            }
+            TerminalCommand::Guess(index) => {
+                let TerminalView::Hack(session) = &mut self.view else { return TerminalOutcome::Stay };
+                match session.guess(index) {
+                    HackGuess::Granted => {
+                        self.view = TerminalView::Page(0);
+                        TerminalOutcome::Hacked
+                    }
+                    HackGuess::Denied { .. } => TerminalOutcome::Stay,
+                    HackGuess::Failed => {
+                        self.view = TerminalView::Denied;
+                        TerminalOutcome::HackFailed
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Terminal session state
+#[derive(Resource, Debug, Default)]
+pub struct TerminalState {
+    pub active: Option<ActiveTerminal>,
+}
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+#[derive(Debug, Clone)]
+pub enum TerminalEvent {
+    Opened { terminal: Entity, user: Entity },
+    Closed { terminal: Entity, user: Entity },
+    LogRead { terminal: Entity, title: String },
+    DeviceToggled { terminal: Entity, device: Entity },
+    CurrencyTransferred { terminal: Entity, user: Entity, amount: f32 },
+    QuestStarted { terminal: Entity, station: Entity },
+    Hacked { terminal: Entity },
+    HackFailed { terminal: Entity },
+}
+
+/// Queue for TerminalEvent
+pub type TerminalEventQueue = EventQueue<TerminalEvent>;
+
+// ============================================================================
+// UI COMPONENTS
+// ============================================================================
+
+/// Terminal panel
+#[derive(Component)]
+pub struct TerminalRoot;
+
+/// Texts and buttons of the current view
+#[derive(Component)]
+pub struct TerminalViewNode;
+
+#[derive(Component)]
+pub struct TerminalButton(pub TerminalCommand);
+
+const TERMINAL_TEXT_COLOR: Color = Color::srgb(0.35, 1.0, 0.45);
+const TERMINAL_BUTTON_COLOR: Color = Color::srgb(0.05, 0.16, 0.07);
+const TERMINAL_BUTTON_HOVER_COLOR: Color = Color::srgb(0.08, 0.26, 0.11);
+const TERMINAL_PANEL_WIDTH: f32 = 420.0;
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// System to log into terminals the player interacts with
+pub fn open_terminals(
+    mut interaction_events: QueueReader<InteractionEvent>,
+    terminal_query: Query<&Terminal>,
+    contents: Res<Assets<TerminalContent>>,
+    mut state: ResMut<TerminalState>,
+    mut terminal_events: ResMut<TerminalEventQueue>,
+) {
+    for event in interaction_events.read() {
+        if state.active.is_some() {
+            continue;
+        }
+        let Ok(terminal) = terminal_query.get(event.target) else { continue };
+        if !terminal.enabled {
+            continue;
+        }
+        let Some(content) = contents.get(&terminal.content) else {
+            warn!("Terminal content is not loaded yet");
+            continue;
+        };
+        if content.pages.is_empty() {
+            continue;
+        }
+
+        let view = if terminal.lockout_timer > 0.0 {
+            TerminalView::Denied
+        } else if terminal.locked {
+            match &content.hack {
+                Some(hack) if !hack.words.is_empty() => {
+                    let password = rand::rng().random_range(0..hack.words.len());
+                    TerminalView::Hack(HackSession::new(hack, password))
+                }
+                _ => TerminalView::Denied,
+            }
+        } else {
+            TerminalView::Page(0)
+        };
+
+        state.active = Some(ActiveTerminal::new(event.target, event.source, view));
+        terminal_events.send(TerminalEvent::Opened { terminal: event.target, user: event.source });
+    }
+}
+
+/// Queues written by the entries run from the terminal panel
+#[derive(SystemParam)]
+pub struct TerminalOutputs<'w> {
+    pub interaction_events: ResMut<'w, InteractionEventQueue>,
+    pub add_currency_events: ResMut<'w, AddCurrencyEventQueue>,
+    pub remove_currency_events: ResMut<'w, RemoveCurrencyEventQueue>,
+    pub terminal_events: ResMut<'w, TerminalEventQueue>,
+}
+
+/// System to run the entries pressed in the terminal panel
+pub fn handle_terminal_buttons(
+    time: Res<Time>,
+    mut button_query: Query<(Entity, &Interaction, &TerminalButton, &mut BackgroundColor), Changed<Interaction>>,
+    contents: Res<Assets<TerminalContent>>,
+    mut state: ResMut<TerminalState>,
+    mut terminal_query: Query<&mut Terminal>,
+    name_query: Query<(Entity, &Name)>,
+    mut outputs: TerminalOutputs,
+) {
+    let mut pressed = Vec::new();
+    for (button_entity, interaction, button, mut background) in button_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => pressed.push((button_entity, button.0)),
+            Interaction::Hovered => background.0 = TERMINAL_BUTTON_HOVER_COLOR,
+            Interaction::None => background.0 = TERMINAL_BUTTON_COLOR,
+        }
+    }
+
+    for (button_entity, command) in pressed {
+        let Some(active) = state.active.as_mut() else { return };
+        let (terminal_entity, user) = (active.terminal, active.user);
+        let Ok(mut terminal) = terminal_query.get_mut(terminal_entity) else { continue };
+        let Some(content) = contents.get(&terminal.content) else { continue };
+
+        match active.apply(command, content) {
+            TerminalOutcome::Stay => {}
+            TerminalOutcome::Close => {
+                state.active = None;
+                outputs.terminal_events.send(TerminalEvent::Closed { terminal: terminal_entity, user });
+            }
+            TerminalOutcome::Hacked => {
+                terminal.locked = false;
+                outputs.terminal_events.send(TerminalEvent::Hacked { terminal: terminal_entity });
+            }
+            TerminalOutcome::HackFailed => {
+                terminal.lockout_timer = content.hack.as_ref().map_or(0.0, |hack| hack.lockout);
+                outputs.terminal_events.send(TerminalEvent::HackFailed { terminal: terminal_entity });
+            }
+            TerminalOutcome::Run(TerminalAction::ReadLog { title, .. }) => {
+                outputs.terminal_events.send(TerminalEvent::LogRead { terminal: terminal_entity, title });
+            }
+            TerminalOutcome::Run(TerminalAction::OpenPage(_)) => {}
+            TerminalOutcome::Run(TerminalAction::ToggleDevice(device_name)) => {
+                for (device, _) in name_query.iter().filter(|(_, name)| name.as_str() == device_name) {
+                    outputs.interaction_events.send(InteractionEvent { source: user, target: device, interaction_type: InteractionType::Activate });
+                    outputs.terminal_events.send(TerminalEvent::DeviceToggled { terminal: terminal_entity, device });
+                }
+            }
+            TerminalOutcome::Run(TerminalAction::StartQuest(station_name)) => {
+                // Quest stations accept their quest on any interaction
+                for (station, _) in name_query.iter().filter(|(_, name)| name.as_str() == station_name) {
+                    outputs.interaction_events.send(InteractionEvent { source: user, target: station, interaction_type: InteractionType::Use });
+                    outputs.terminal_events.send(TerminalEvent::QuestStarted { terminal: terminal_entity, station });
+                }
+            }
+            TerminalOutcome::Run(TerminalAction::TransferCurrency(amount)) => {
+                // One transfer per press of the button
+                let transaction_id = Some(transaction_id(("terminal", button_entity, time.elapsed().as_nanos())));
+                if amount >= 0.0 {
+                    outputs.add_currency_events.send(AddCurrencyEvent { entity: user, amount, currency_type: CurrencyType::Gold, transaction_id });
+                } else {
+                    outputs.remove_currency_events.send(RemoveCurrencyEvent { entity: user, amount: -amount, currency_type: CurrencyType::Gold, transaction_id });
+                }
+                outputs.terminal_events.send(TerminalEvent::CurrencyTransferred { terminal: terminal_entity, user, amount });
+            }
+        }
+    }
+}
+
+/// System to tick lockouts, log out of removed terminals and hold the input context
+pub fn update_terminals(
+    time: Res<Time>,
+    mut terminal_query: Query<&mut Terminal>,
+    mut state: ResMut<TerminalState>,
+    mut context_stack: ResMut<InputContextStack>,
+    mut terminal_events: ResMut<TerminalEventQueue>,
+) {
+    for mut terminal in terminal_query.iter_mut() {
+        if terminal.lockout_timer > 0.0 {
+            terminal.lockout_timer = (terminal.lockout_timer - time.delta_secs()).max(0.0);
+        }
+    }
+
+    if let Some(active) = &state.active {
+        if !terminal_query.get(active.terminal).is_ok_and(|terminal| terminal.enabled) {
+            terminal_events.send(TerminalEvent::Closed { terminal: active.terminal, user: active.user });
+            state.active = None;
+        }
+    }
+
+    let open = state.active.is_some();
+    if open != context_stack.contains(InputContext::Terminal) {
+        context_stack.set_active(InputContext::Terminal, open);
+    }
+}
+
+/// System to setup the terminal panel (hidden by default)
+pub fn setup_terminal_ui(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Val::Px(24.0)),
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.01, 0.05, 0.02, 0.95)),
+        TerminalRoot,
+        FocusScope { priority: 25, cancel: FocusCancel::None },
+        Visibility::Hidden,
+    ));
+}
+
+/// System to show the terminal panel and rebuild it when the view changes
+pub fn update_terminal_ui(
+    mut commands: Commands,
+    state: Res<TerminalState>,
+    contents: Res<Assets<TerminalContent>>,
+    terminal_query: Query<&Terminal>,
+    mut root_query: Query<(Entity, &mut Node, &mut Visibility), With<TerminalRoot>>,
+    view_query: Query<Entity, With<TerminalViewNode>>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    let Ok((root, mut node, mut visibility)) = root_query.single_mut() else { return };
+
+    for view in view_query.iter() {
+        commands.entity(view).despawn();
+    }
+
+    let session = state.active.as_ref().and_then(|active| {
+        let terminal = terminal_query.get(active.terminal).ok()?;
+        Some((active, terminal, contents.get(&terminal.content)?))
+    });
+    let Some((active, terminal, content)) = session else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+
+    *visibility = Visibility::Visible;
+    match terminal.display {
+        TerminalDisplay::FullScreen => {
+            node.width = Val::Percent(100.0);
+            node.height = Val::Percent(100.0);
+            node.left = Val::Px(0.0);
+            node.top = Val::Px(0.0);
+        }
+        // Placed by `position_terminal_ui`
+        TerminalDisplay::InWorld { .. } => {
+            node.width = Val::Px(TERMINAL_PANEL_WIDTH);
+            node.height = Val::Auto;
+        }
+    }
+
+    commands.entity(root).with_children(|root| build_terminal_view(root, active, content));
+}
+
+fn build_terminal_view(root: &mut ChildSpawnerCommands, active: &ActiveTerminal, content: &TerminalContent) {
+    root.spawn((
+        Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(6.0),
+            ..default()
+        },
+        TerminalViewNode,
+    )).with_children(|view| {
+        spawn_text(view, &content.title, 24.0);
+
+        match &active.view {
+            TerminalView::Page(page) => {
+                let Some(page) = content.pages.get(*page) else { return };
+                if !page.header.is_empty() {
+                    spawn_text(view, &page.header, 16.0);
+                }
+                for (index, entry) in page.entries.iter().enumerate() {
+                    spawn_button(view, &entry.label, TerminalCommand::Entry(index));
+                }
+                let back = if active.history.is_empty() { "Log off" } else { "Back" };
+                spawn_button(view, back, TerminalCommand::Back);
+            }
+            TerminalView::Log { title, text, .. } => {
+                spawn_text(view, title, 18.0);
+                spawn_text(view, text, 16.0);
+                spawn_button(view, "Back", TerminalCommand::Back);
+            }
+            TerminalView::Hack(session) => {
+                spawn_text(view, &format!("ENTER PASSWORD - {} ATTEMPT(S) LEFT", session.attempts_left), 18.0);
+                for (word, likeness) in session.guesses.iter() {
+                    spawn_text(view, &format!("> {}: likeness {}", word, likeness), 16.0);
+                }
+                for (index, word) in session.words.iter().enumerate() {
+                    if !session.was_guessed(index) {
+                        spawn_button(view, word, TerminalCommand::Guess(index));
+                    }
+                }
+                spawn_button(view, "Cancel", TerminalCommand::Back);
+            }
+            TerminalView::Denied => {
+                spawn_text(view, "ACCESS DENIED", 18.0);
+                spawn_button(view, "Log off", TerminalCommand::Back);
+            }
+        }
+    });
+}
+
+fn spawn_text(parent: &mut ChildSpawnerCommands, text: &str, font_size: f32) {
+    parent.spawn((
+        Text::new(text),
+        TextFont { font_size, ..default() },
+        TextColor(TERMINAL_TEXT_COLOR),
+    ));
+}
+
+fn spawn_button(parent: &mut ChildSpawnerCommands, label: &str, command: TerminalCommand) {
+    let mut button = parent.spawn((
+        Button,
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Px(30.0),
+            padding: UiRect::horizontal(Val::Px(10.0)),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(TERMINAL_BUTTON_COLOR),
+        TerminalButton(command),
+    ));
+    if command == TerminalCommand::Back {
+        button.insert(UiCancelButton);
+    }
+    button.with_children(|button| {
+        button.spawn((
+            Text::new(label),
+            TextFont { font_size: 16.0, ..default() },
+            TextColor(TERMINAL_TEXT_COLOR),
+        ));
+    });
+}
+
+/// System to keep in-world terminal panels over their terminal
+pub fn position_terminal_ui(
+    state: Res<TerminalState>,
+    terminal_query: Query<(&Terminal, &GlobalTransform)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut root_query: Query<&mut Node, With<TerminalRoot>>,
+) {
+    let Some(active) = &state.active else { return };
+    let Ok((terminal, transform)) = terminal_query.get(active.terminal) else { return };
+    let TerminalDisplay::InWorld { offset } = terminal.display else { return };
+    let Some((camera, camera_transform)) = camera_query.iter().find(|(camera, _)| camera.is_active) else { return };
+    let Ok(mut node) = root_query.single_mut() else { return };
+
+    if let Ok(position) = camera.world_to_viewport(camera_transform, transform.transform_point(offset)) {
+        node.left = Val::Px(position.x - TERMINAL_PANEL_WIDTH * 0.5);
+        node.top = Val::Px(position.y);
+    }
+}
+
+// ============================================================================
+// PLUGIN
+// ============================================================================
+
+pub struct TerminalPlugin;
+
+impl Plugin for TerminalPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_asset::<TerminalContent>()
+            .init_asset_loader::<TerminalContentLoader>()
+            .init_resource::<TerminalState>()
+            .register_type::<Terminal>()
+            .add_plugins(EventQueuePlugin::<TerminalEvent>::default())
+            .add_systems(Startup, setup_terminal_ui.in_set(crate::headless::PresentationSet))
+            .add_systems(Update, (
+                open_terminals,
+                handle_terminal_buttons,
+                update_terminals,
+                (update_terminal_ui, position_terminal_ui).chain().in_set(crate::headless::PresentationSet),
+            ).chain());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_terminal_pages_and_hack() {
+        use crate::devices::terminal::*;
+
+        let content = TerminalContent::from_ron(r#"(
+            title: "OFFICE",
+            pages: [
+                (id: "home", entries: [
+                    (label: "Log", action: ReadLog(title: "Log", text: "Hello")),
+                    (label: "Doors", action: OpenPage("doors")),
+                ]),
+                (id: "doors", entries: [(label: "Cargo", action: ToggleDevice("Cargo Door"))]),
+            ],
+            hack: Some((words: ["LATCH", "MATCH", "PARTY"], attempts: 2)),
+        )"#).unwrap();
+
+        let mut world = World::new();
+        let (terminal, user) = (world.spawn_empty().id(), world.spawn_empty().id());
+        let mut session = ActiveTerminal::new(terminal, user, TerminalView::Page(0));
+        session.apply(TerminalCommand::Entry(1), &content);
+        assert_eq!(session.view, TerminalView::Page(1));
+        assert_eq!(
+            session.apply(TerminalCommand::Entry(0), &content),
+            TerminalOutcome::Run(TerminalAction::ToggleDevice("Cargo Door".into()))
+        );
+        assert_eq!(session.apply(TerminalCommand::Back, &content), TerminalOutcome::Stay);
+        assert!(matches!(session.apply(TerminalCommand::Entry(0), &content), TerminalOutcome::Run(TerminalAction::ReadLog { .. })));
+        session.apply(TerminalCommand::Back, &content);
+        assert_eq!(session.view, TerminalView::Page(0));
+        assert_eq!(session.apply(TerminalCommand::Back, &content), TerminalOutcome::Close);
+
+        // The password is MATCH; LATCH shares four letters with it
+        let hack = content.hack.as_ref().unwrap();
+        let mut session = ActiveTerminal::new(terminal, user, TerminalView::Hack(HackSession::new(hack, 1)));
+        assert_eq!(session.apply(TerminalCommand::Guess(0), &content), TerminalOutcome::Stay);
+        let TerminalView::Hack(hack_session) = &session.view else { panic!("hack ended early") };
+        assert_eq!(hack_session.guesses, vec![("LATCH".to_string(), 4)]);
+        assert_eq!(session.apply(TerminalCommand::Guess(1), &content), TerminalOutcome::Hacked);
+        assert_eq!(session.view, TerminalView::Page(0));
+
+        let mut session = ActiveTerminal::new(terminal, user, TerminalView::Hack(HackSession::new(hack, 1)));
+        session.apply(TerminalCommand::Guess(0), &content);
+        assert_eq!(session.apply(TerminalCommand::Guess(2), &content), TerminalOutcome::HackFailed);
+        assert_eq!(session.view, TerminalView::Denied);
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use std::collections::HashSet;
+use crate::utils::EventQueue;
 
 
 /// Main component for managing device interactions
@@ -933,23 +934,17 @@ pub struct RechargerStationActivation {
     pub player_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct RechargerStationEnteredQueue(pub Vec<RechargerStationEntered>);
+pub type RechargerStationEnteredQueue = EventQueue<RechargerStationEntered>;
 
-#[derive(Resource, Default)]
-pub struct RechargerStationExitedQueue(pub Vec<RechargerStationExited>);
+pub type RechargerStationExitedQueue = EventQueue<RechargerStationExited>;
 
-#[derive(Resource, Default)]
-pub struct RechargerStationHealingStartedQueue(pub Vec<RechargerStationHealingStarted>);
+pub type RechargerStationHealingStartedQueue = EventQueue<RechargerStationHealingStarted>;
 
-#[derive(Resource, Default)]
-pub struct RechargerStationHealingStoppedQueue(pub Vec<RechargerStationHealingStopped>);
+pub type RechargerStationHealingStoppedQueue = EventQueue<RechargerStationHealingStopped>;
 
-#[derive(Resource, Default)]
-pub struct RechargerStationFullyHealedQueue(pub Vec<RechargerStationFullyHealed>);
+pub type RechargerStationFullyHealedQueue = EventQueue<RechargerStationFullyHealed>;
 
-#[derive(Resource, Default)]
-pub struct RechargerStationActivationQueue(pub Vec<RechargerStationActivation>);
+pub type RechargerStationActivationQueue = EventQueue<RechargerStationActivation>;
 
 /// Health component (for player detection mock)
 #[derive(Component, Debug, Reflect)]
@@ -1214,8 +1209,7 @@ pub enum ExamineObjectEventType {
     HideMessage,
 }
 
-#[derive(Resource, Default)]
-pub struct ExamineObjectEventQueue(pub Vec<ExamineObjectEvent>);
+pub type ExamineObjectEventQueue = EventQueue<ExamineObjectEvent>;
 
 /// Simple switch component
 #[derive(Component, Debug, Reflect)]
@@ -1655,23 +1649,17 @@ pub struct SetCurrentPlayerUseDeviceButtonEnabledStateEvent {
     pub state: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct MoveCameraToDeviceEventQueue(pub Vec<MoveCameraToDeviceEvent>);
+pub type MoveCameraToDeviceEventQueue = EventQueue<MoveCameraToDeviceEvent>;
 
-#[derive(Resource, Default)]
-pub struct HasSecondMoveCameraToDeviceEventQueue(pub Vec<HasSecondMoveCameraToDeviceEvent>);
+pub type HasSecondMoveCameraToDeviceEventQueue = EventQueue<HasSecondMoveCameraToDeviceEvent>;
 
-#[derive(Resource, Default)]
-pub struct EnableFreeInteractionStateEventQueue(pub Vec<EnableFreeInteractionStateEvent>);
+pub type EnableFreeInteractionStateEventQueue = EventQueue<EnableFreeInteractionStateEvent>;
 
-#[derive(Resource, Default)]
-pub struct DisableFreeInteractionStateEventQueue(pub Vec<DisableFreeInteractionStateEvent>);
+pub type DisableFreeInteractionStateEventQueue = EventQueue<DisableFreeInteractionStateEvent>;
 
-#[derive(Resource, Default)]
-pub struct StopMovementEventQueue(pub Vec<StopMovementEvent>);
+pub type StopMovementEventQueue = EventQueue<StopMovementEvent>;
 
-#[derive(Resource, Default)]
-pub struct SetCurrentPlayerUseDeviceButtonEnabledStateEventQueue(pub Vec<SetCurrentPlayerUseDeviceButtonEnabledStateEvent>);
+pub type SetCurrentPlayerUseDeviceButtonEnabledStateEventQueue = EventQueue<SetCurrentPlayerUseDeviceButtonEnabledStateEvent>;
 
 // ----------------------------------------------------------------------------
 // Move Device To Camera Types
@@ -1956,14 +1944,11 @@ pub struct ResetRotationAndPositionEvent {
     pub device_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct ChangeDeviceZoomEventQueue(pub Vec<ChangeDeviceZoomEvent>);
+pub type ChangeDeviceZoomEventQueue = EventQueue<ChangeDeviceZoomEvent>;
 
-#[derive(Resource, Default)]
-pub struct ResetRotationEventQueue(pub Vec<ResetRotationEvent>);
+pub type ResetRotationEventQueue = EventQueue<ResetRotationEvent>;
 
-#[derive(Resource, Default)]
-pub struct ResetRotationAndPositionEventQueue(pub Vec<ResetRotationAndPositionEvent>);
+pub type ResetRotationAndPositionEventQueue = EventQueue<ResetRotationAndPositionEvent>;
 
 // ----------------------------------------------------------------------------
 // Hologram Door Types
@@ -2130,24 +2115,17 @@ pub struct HologramDoorOpenByExternalInputEvent {
     pub door_entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct HologramDoorActivationEventQueue(pub Vec<HologramDoorActivationEvent>);
+pub type HologramDoorActivationEventQueue = EventQueue<HologramDoorActivationEvent>;
 
-#[derive(Resource, Default)]
-pub struct HologramDoorOpenEventQueue(pub Vec<HologramDoorOpenEvent>);
+pub type HologramDoorOpenEventQueue = EventQueue<HologramDoorOpenEvent>;
 
-#[derive(Resource, Default)]
-pub struct HologramDoorUnlockEventQueue(pub Vec<HologramDoorUnlockEvent>);
+pub type HologramDoorUnlockEventQueue = EventQueue<HologramDoorUnlockEvent>;
 
-#[derive(Resource, Default)]
-pub struct HologramDoorLockEventQueue(pub Vec<HologramDoorLockEvent>);
+pub type HologramDoorLockEventQueue = EventQueue<HologramDoorLockEvent>;
 
-#[derive(Resource, Default)]
-pub struct HologramDoorEnterEventQueue(pub Vec<HologramDoorEnterEvent>);
+pub type HologramDoorEnterEventQueue = EventQueue<HologramDoorEnterEvent>;
 
-#[derive(Resource, Default)]
-pub struct HologramDoorExitEventQueue(pub Vec<HologramDoorExitEvent>);
+pub type HologramDoorExitEventQueue = EventQueue<HologramDoorExitEvent>;
 
-#[derive(Resource, Default)]
-pub struct HologramDoorOpenByExternalInputEventQueue(pub Vec<HologramDoorOpenByExternalInputEvent>);
+pub type HologramDoorOpenByExternalInputEventQueue = EventQueue<HologramDoorOpenByExternalInputEvent>;
 
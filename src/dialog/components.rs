@@ -4,6 +4,7 @@ use super::types::CompleteDialog;
 /// Component representing a dialog content system.
 /// This is attached to entities that can trigger dialogs (NPCs, objects, etc.).
 #[derive(Debug, Component, Reflect, Clone)]
+#[reflect(Component)]
 pub struct DialogContent {
     /// Unique identifier for this dialog content
     pub id: u32,
@@ -59,6 +60,7 @@ impl Default for DialogContent {
 
 /// Component representing the dialog system on a player or entity.
 #[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
 pub struct DialogSystem {
     /// Whether the dialog system is enabled
     pub enabled: bool,
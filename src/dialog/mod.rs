@@ -42,6 +42,8 @@ impl Plugin for DialogPlugin {
                 handle_next_dialog,
                 handle_select_dialog_choice,
                 handle_close_dialog,
-            ));
+            ))
+            .add_systems(Update, sync_dialog_input_context.before(crate::input::InputSet::Gather))
+            .add_systems(Update, speak_dialog_lines.before(crate::audio::systems::play_audio_cues));
     }
 }
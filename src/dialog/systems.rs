@@ -1,5 +1,8 @@
 use bevy::prelude::*;
 use super::components::DialogSystem;
+use std::collections::HashMap;
+use crate::input::{InputContext, InputContextStack};
+use crate::audio::{AudioCue, AudioCueQueue, AudioChannel, SubtitleLine};
 
 /// System to handle starting dialogs.
 ///
@@ -141,3 +144,49 @@ pub fn close_dialog(dialog_system: &mut DialogSystem) {
     // Clear current dialog content
     dialog_system.current_dialog_content = None;
 }
+
+/// System to put the exclusive dialog layer on the input stack while a dialog is open
+pub fn sync_dialog_input_context(
+    dialog_systems: Query<&DialogSystem>,
+    mut context_stack: ResMut<InputContextStack>,
+) {
+    let active = dialog_systems.iter().any(|dialog_system| dialog_system.dialog_active);
+    if active != context_stack.contains(InputContext::Dialog) {
+        context_stack.set_active(InputContext::Dialog, active);
+    }
+}
+
+/// System to voice and subtitle each dialog line as it comes up
+pub fn speak_dialog_lines(
+    dialog_systems: Query<(Entity, &DialogSystem)>,
+    mut cues: ResMut<AudioCueQueue>,
+    mut spoken: Local<HashMap<Entity, (usize, usize)>>,
+) {
+    spoken.retain(|entity, _| dialog_systems.get(*entity).is_ok_and(|(_, dialog_system)| dialog_system.dialog_active));
+
+    for (entity, dialog_system) in dialog_systems.iter() {
+        if !dialog_system.dialog_active {
+            continue;
+        }
+        let Some(content) = &dialog_system.current_dialog_content else { continue };
+        let line = (content.current_dialog_index, dialog_system.current_dialog_index);
+        if spoken.get(&entity) == Some(&line) {
+            continue;
+        }
+        spoken.insert(entity, line);
+
+        let Some(node) = content
+            .complete_dialogs
+            .get(line.0)
+            .and_then(|complete_dialog| complete_dialog.nodes.get(line.1))
+        else {
+            continue;
+        };
+        let speaker = Some(node.speaker_name.as_str()).filter(|name| !name.is_empty());
+        let cue = match node.sound_path.as_ref().filter(|_| node.use_sound) {
+            Some(path) => AudioCue::voice(path.clone(), entity),
+            None => AudioCue::silent(AudioChannel::Voice).from(entity),
+        };
+        cues.send(cue.with_line(SubtitleLine::new(speaker, node.content.clone())));
+    }
+}
@@ -0,0 +1,72 @@
+//! Emotes and gestures
+//!
+//! Emotes are defined in the [`EmoteLibrary`] (animation clip, optional sound
+//! and particle effect, duration and what the emote means to NPCs). Players pick
+//! one from a radial wheel opened with `InputAction::EmoteWheel`; the wheel item
+//! under the pointer (or the look stick on gamepads) plays when the input is
+//! released. Damage, combat input, running actions and, for most emotes,
+//! movement interrupt the emote, and emotes can't start right after taking damage.
+//!
+//! NPCs within range that see a player's emote react: friendly ones greet back,
+//! companions follow, wait or join in, hostile ones go after a taunting player.
+//! Every reaction is also sent as an [`EmoteReactionEvent`] for game-specific
+//! behavior:
+//!
+//! ```rust,ignore
+//! emote_commands.send(EmoteCommand::Play { entity: player, emote: "wave".to_string() });
+//!
+//! for reaction in reactions.read() {
+//!     if reaction.reaction == EmoteReaction::Responded {
+//!         // Start a conversation...
+//!     }
+//! }
+//! ```
+
+pub mod types;
+pub mod systems;
+pub mod ui;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use ui::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    EmoteIntent, EmoteDefinition, EmoteLibrary, EmoteSettings, Emoting, EmoteCharacter, EmoteReactor,
+    EmoteInterruption, EmoteReaction, EmoteCommand, EmoteCommandQueue, EmoteEvent, EmoteEventQueue,
+    EmoteReactionEvent, EmoteReactionQueue, EmoteWheelState,
+};
+
+pub struct EmotesPlugin;
+
+impl Plugin for EmotesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<EmoteLibrary>()
+            .init_resource::<EmoteSettings>()
+            .init_resource::<EmoteWheelState>()
+            .init_resource::<EmoteAnimations>()
+            .add_plugins((
+                EventQueuePlugin::<EmoteCommand>::default(),
+                EventQueuePlugin::<EmoteEvent>::default(),
+                EventQueuePlugin::<EmoteReactionEvent>::default(),
+            ))
+            .register_type::<EmoteLibrary>()
+            .register_type::<EmoteSettings>()
+            .register_type::<Emoting>()
+            .register_type::<EmoteCharacter>()
+            .register_type::<EmoteReactor>()
+            .add_systems(Startup, setup_emote_wheel_ui.in_set(crate::headless::PresentationSet))
+            .add_systems(Update, update_emote_wheel
+                .in_set(crate::input::InputSet::Gather)
+                .after(crate::input::player_input_sync_system))
+            .add_systems(Update, (
+                attach_emote_components,
+                process_emote_commands,
+                update_emotes,
+                react_to_emotes,
+                update_emote_wheel_ui.in_set(crate::headless::PresentationSet),
+            ).chain().after(crate::GameControllerSet::Input));
+    }
+}
@@ -0,0 +1,535 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use std::collections::HashSet;
+use super::types::*;
+use crate::actions::types::PlayerActionSystem;
+use crate::ai::{AIPerceptionSettings, AiBehaviorState, AiCommand, AiController, CharacterFaction, FactionSystem, FriendManager};
+use crate::character::Player;
+use crate::combat::DamageResultEvent;
+use crate::game_manager::types::CursorState;
+use crate::input::InputState;
+use crate::utils::QueueReader;
+use crate::vfx::VfxRequestQueue;
+
+/// Everything needed to start and stop emote playback
+#[derive(SystemParam)]
+pub struct EmotePlayback<'w, 's> {
+    pub commands: Commands<'w, 's>,
+    pub asset_server: Res<'w, AssetServer>,
+    pub graphs: Option<ResMut<'w, Assets<AnimationGraph>>>,
+    pub animations: ResMut<'w, EmoteAnimations>,
+    pub children_query: Query<'w, 's, &'static Children>,
+    pub player_query: Query<'w, 's, (&'static mut AnimationPlayer, Option<&'static AnimationGraphHandle>)>,
+    pub vfx: ResMut<'w, VfxRequestQueue>,
+    pub events: ResMut<'w, EmoteEventQueue>,
+}
+
+impl EmotePlayback<'_, '_> {
+    /// Character entity or the first descendant with an `AnimationPlayer`
+    fn animation_player(&self, entity: Entity) -> Option<Entity> {
+        std::iter::once(entity)
+            .chain(self.children_query.iter_descendants(entity))
+            .find(|candidate| self.player_query.contains(*candidate))
+    }
+
+    /// Plays the emote; `replaced` is the emote it cuts off, whose original graph is kept
+    fn start(&mut self, entity: Entity, definition: &EmoteDefinition, position: Option<Vec3>, replaced: Option<&Emoting>) {
+        let mut previous_graph = replaced.and_then(|replaced| replaced.previous_graph.clone());
+
+        if !definition.animation.is_empty() {
+            if let (Some(player_entity), Some(graphs)) = (self.animation_player(entity), self.graphs.as_mut()) {
+                let (graph, node) = self
+                    .animations
+                    .graphs
+                    .entry(definition.id.clone())
+                    .or_insert_with(|| {
+                        let (graph, node) = AnimationGraph::from_clip(self.asset_server.load(definition.animation.clone()));
+                        (graphs.add(graph), node)
+                    })
+                    .clone();
+
+                if let Ok((mut player, current_graph)) = self.player_query.get_mut(player_entity) {
+                    if previous_graph.is_none() {
+                        previous_graph = current_graph
+                            .filter(|current| current.0 != graph)
+                            .map(|current| (player_entity, current.0.clone()));
+                    }
+                    player.stop_all();
+                    let animation = player.play(node);
+                    if definition.looping {
+                        animation.repeat();
+                    }
+                    self.commands.entity(player_entity).insert(AnimationGraphHandle(graph));
+                }
+            }
+        }
+
+        if let Some(position) = position {
+            if !definition.sound.is_empty() {
+                self.commands.spawn((
+                    AudioPlayer::<AudioSource>(self.asset_server.load(definition.sound.clone())),
+                    PlaybackSettings::DESPAWN.with_spatial(true),
+                    Transform::from_translation(position),
+                    GlobalTransform::default(),
+                ));
+            }
+            if let Some(key) = definition.vfx.clone() {
+                self.vfx.spawn(key, position + Vec3::Y * 1.8);
+            }
+        }
+
+        self.commands.entity(entity).insert(Emoting {
+            emote: definition.id.clone(),
+            elapsed: 0.0,
+            duration: definition.duration,
+            looping: definition.looping,
+            interrupted_by_movement: definition.interrupted_by_movement,
+            previous_graph,
+        });
+        self.events.send(EmoteEvent::Started { entity, emote: definition.id.clone() });
+    }
+
+    /// Ends the emote; `None` means it ran to completion
+    fn stop(&mut self, entity: Entity, emoting: &Emoting, reason: Option<EmoteInterruption>) {
+        if let Some(player_entity) = self.animation_player(entity) {
+            if let Ok((mut player, _)) = self.player_query.get_mut(player_entity) {
+                player.stop_all();
+            }
+        }
+        if let Some((player_entity, graph)) = emoting.previous_graph.clone() {
+            if let Ok(mut player_commands) = self.commands.get_entity(player_entity) {
+                player_commands.insert(AnimationGraphHandle(graph));
+            }
+        }
+
+        self.commands.entity(entity).remove::<Emoting>();
+        let emote = emoting.emote.clone();
+        self.events.send(match reason {
+            Some(reason) => EmoteEvent::Interrupted { entity, emote, reason },
+            None => EmoteEvent::Finished { entity, emote },
+        });
+    }
+}
+
+/// Gives players and AI characters the emote components
+pub fn attach_emote_components(
+    mut commands: Commands,
+    player_query: Query<Entity, (Added<Player>, Without<EmoteCharacter>)>,
+    ai_query: Query<(Entity, Has<EmoteCharacter>, Has<EmoteReactor>), Added<AiController>>,
+) {
+    for entity in player_query.iter() {
+        commands.entity(entity).insert(EmoteCharacter::default());
+    }
+
+    for (entity, has_character, has_reactor) in ai_query.iter() {
+        if !has_character {
+            commands.entity(entity).insert(EmoteCharacter::default());
+        }
+        if !has_reactor {
+            commands.entity(entity).insert(EmoteReactor::default());
+        }
+    }
+}
+
+type EmotingCharacter<'a> = (
+    Option<&'a GlobalTransform>,
+    Option<&'a EmoteCharacter>,
+    Option<&'a PlayerActionSystem>,
+    Option<&'a Emoting>,
+);
+
+/// Starts and stops emotes on request, refusing them during combat or actions
+pub fn process_emote_commands(
+    time: Res<Time>,
+    mut emote_commands: QueueReader<EmoteCommand>,
+    settings: Res<EmoteSettings>,
+    library: Res<EmoteLibrary>,
+    mut playback: EmotePlayback,
+    character_query: Query<EmotingCharacter>,
+    ai_query: Query<&AiController>,
+) {
+    let now = time.elapsed_secs();
+    // Emotes started this frame, whose `Emoting` is not inserted yet
+    let mut started: Vec<Entity> = Vec::new();
+
+    for command in emote_commands.read() {
+        match command {
+            EmoteCommand::Play { entity, emote } => {
+                if !settings.enabled {
+                    continue;
+                }
+                let Ok((transform, character, action, current)) = character_query.get(*entity) else { continue };
+
+                let in_combat = character
+                    .and_then(|character| character.last_damage_time)
+                    .is_some_and(|last| now - last < settings.combat_lockout);
+                let busy = action.is_some_and(|action| action.is_action_active);
+                let dead = ai_query.get(*entity).is_ok_and(|ai| ai.state == AiBehaviorState::Dead);
+
+                let Some(definition) = library.get(emote).filter(|_| !in_combat && !busy && !dead && !started.contains(entity)) else {
+                    playback.events.send(EmoteEvent::Rejected { entity: *entity, emote: emote.clone() });
+                    continue;
+                };
+
+                if let Some(current) = current {
+                    playback.stop(*entity, current, Some(EmoteInterruption::Command));
+                }
+                playback.start(*entity, definition, transform.map(|transform| transform.translation()), current);
+                started.push(*entity);
+            }
+            EmoteCommand::Stop { entity } => {
+                if let Ok((_, _, _, Some(current))) = character_query.get(*entity) {
+                    playback.stop(*entity, current, Some(EmoteInterruption::Command));
+                }
+            }
+        }
+    }
+}
+
+/// Advances emotes and interrupts them on damage, movement, combat input or actions
+pub fn update_emotes(
+    time: Res<Time>,
+    mut damage_results: QueueReader<DamageResultEvent>,
+    mut playback: EmotePlayback,
+    mut character_query: Query<&mut EmoteCharacter>,
+    mut emoting_query: Query<(
+        Entity,
+        &mut Emoting,
+        Option<&InputState>,
+        Option<&PlayerActionSystem>,
+    )>,
+) {
+    let now = time.elapsed_secs();
+
+    let mut damaged = HashSet::new();
+    for result in damage_results.read() {
+        if result.final_amount <= 0.0 {
+            continue;
+        }
+        if let Ok(mut character) = character_query.get_mut(result.target) {
+            character.last_damage_time = Some(now);
+        }
+        damaged.insert(result.target);
+    }
+
+    for (entity, mut emoting, input, action) in emoting_query.iter_mut() {
+        emoting.elapsed += time.delta_secs();
+
+        let combat_input = input.is_some_and(|input| {
+            input.attack_pressed || input.special_move_pressed || input.fire_pressed || input.block_pressed || input.aim_pressed || input.jump_pressed
+        });
+        let moving = emoting.interrupted_by_movement && input.is_some_and(|input| input.movement.length() > 0.1);
+
+        let reason = if damaged.contains(&entity) {
+            Some(EmoteInterruption::Damage)
+        } else if combat_input {
+            Some(EmoteInterruption::Combat)
+        } else if action.is_some_and(|action| action.is_action_active) {
+            Some(EmoteInterruption::Action)
+        } else if moving {
+            Some(EmoteInterruption::Movement)
+        } else {
+            None
+        };
+
+        if reason.is_some() {
+            playback.stop(entity, &emoting, reason);
+        } else if !emoting.looping && emoting.elapsed >= emoting.duration {
+            playback.stop(entity, &emoting, None);
+        }
+    }
+}
+
+/// Emote settings and the emotes there are
+#[derive(SystemParam)]
+pub struct EmoteConfig<'w> {
+    pub settings: Res<'w, EmoteSettings>,
+    pub library: Res<'w, EmoteLibrary>,
+}
+
+/// Input the wheel reads its pointer from and takes over while open
+#[derive(SystemParam)]
+pub struct EmoteWheelInput<'w, 's> {
+    pub global_input: ResMut<'w, InputState>,
+    pub player_query: Query<'w, 's, (Entity, Option<&'static mut InputState>), With<Player>>,
+    pub window_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
+/// Opens the emote wheel while its input is held and plays the selection on release.
+/// Runs after the player input sync so it can keep the wheel's pointer from aiming or firing.
+pub fn update_emote_wheel(
+    time: Res<Time>,
+    config: EmoteConfig,
+    mut wheel: ResMut<EmoteWheelState>,
+    mut cursor: ResMut<CursorState>,
+    mut emote_commands: ResMut<EmoteCommandQueue>,
+    wheel_input: EmoteWheelInput,
+) {
+    let EmoteConfig { settings, library } = config;
+    let EmoteWheelInput { mut global_input, mut player_query, window_query } = wheel_input;
+    let Some((player, mut player_input)) = player_query.iter_mut().next() else { return };
+
+    let held = settings.enabled
+        && (global_input.emote_wheel_held || player_input.as_ref().is_some_and(|input| input.emote_wheel_held));
+
+    if !held {
+        if wheel.open {
+            wheel.open = false;
+            cursor.visible_override = None;
+            if let Some(emote) = wheel.selected.and_then(|index| library.emotes.get(index)) {
+                emote_commands.send(EmoteCommand::Play { entity: player, emote: emote.id.clone() });
+            }
+            wheel.selected = None;
+        }
+        return;
+    }
+
+    if !wheel.open {
+        wheel.open = true;
+        wheel.pointer = Vec2::ZERO;
+        cursor.visible_override = Some(true);
+    }
+
+    let cursor_offset = window_query.single().ok().and_then(|window| {
+        let position = window.cursor_position()?;
+        Some(Vec2::new(position.x - window.width() * 0.5, window.height() * 0.5 - position.y))
+    });
+    wheel.pointer = match cursor_offset {
+        Some(offset) => offset,
+        None => {
+            // Gamepads steer the pointer with the look stick
+            let look = player_input.as_ref().map_or(global_input.look, |input| input.look);
+            (wheel.pointer + look * settings.stick_sensitivity * time.delta_secs()).clamp_length_max(settings.wheel_radius)
+        }
+    };
+
+    let count = library.emotes.len();
+    wheel.selected = if count == 0 || wheel.pointer.length() < settings.wheel_dead_zone {
+        None
+    } else {
+        // Slot 0 at the top, clockwise
+        let angle = wheel.pointer.x.atan2(wheel.pointer.y).rem_euclid(std::f32::consts::TAU);
+        let sector = std::f32::consts::TAU / count as f32;
+        Some(((angle + sector * 0.5) / sector) as usize % count)
+    };
+
+    suppress_wheel_input(&mut global_input);
+    if let Some(input) = player_input.as_mut() {
+        suppress_wheel_input(input);
+    }
+}
+
+/// The wheel owns the pointer: no looking, aiming or attacking while it is open
+fn suppress_wheel_input(input: &mut InputState) {
+    input.look = Vec2::ZERO;
+    input.aim_pressed = false;
+    input.attack_pressed = false;
+    input.attack_held = false;
+    input.special_move_pressed = false;
+    input.fire_pressed = false;
+    input.fire_just_pressed = false;
+    input.block_pressed = false;
+}
+
+/// NPC parts that notice and answer emotes
+type EmoteReactingNpc<'a> = (
+    Entity,
+    &'a GlobalTransform,
+    &'a mut AiController,
+    &'a mut EmoteReactor,
+    Option<&'a AIPerceptionSettings>,
+    Option<&'a CharacterFaction>,
+    Option<&'a mut FriendManager>,
+);
+
+/// Emotes played and reactions reported by NPCs
+#[derive(SystemParam)]
+pub struct EmoteReactionOutputs<'w> {
+    pub emote_commands: ResMut<'w, EmoteCommandQueue>,
+    pub reactions: ResMut<'w, EmoteReactionQueue>,
+}
+
+/// Lets NPCs that see a player's emote react to it
+pub fn react_to_emotes(
+    time: Res<Time>,
+    mut emote_events: QueueReader<EmoteEvent>,
+    outputs: EmoteReactionOutputs,
+    config: EmoteConfig,
+    factions: Res<FactionSystem>,
+    emoter_query: Query<(&GlobalTransform, Option<&CharacterFaction>), With<Player>>,
+    mut npc_query: Query<EmoteReactingNpc, Without<Player>>,
+) {
+    let EmoteReactionOutputs { mut emote_commands, mut reactions } = outputs;
+    let EmoteConfig { settings, library } = config;
+    let now = time.elapsed_secs();
+
+    for event in emote_events.read() {
+        let EmoteEvent::Started { entity: emoter, emote } = event else { continue };
+        let Some(definition) = library.get(emote) else { continue };
+        if definition.intent == EmoteIntent::Neutral {
+            continue;
+        }
+        // Only players' emotes, so NPC responses don't set each other off
+        let Ok((emoter_transform, emoter_faction)) = emoter_query.get(*emoter) else { continue };
+        let emoter_position = emoter_transform.translation();
+
+        for (npc, transform, mut ai, mut reactor, perception, faction, friend_manager) in npc_query.iter_mut() {
+            if !reactor.enabled
+                || ai.state == AiBehaviorState::Dead
+                || reactor.last_reaction.is_some_and(|last| now - last < reactor.cooldown)
+                || transform.translation().distance(emoter_position) > settings.reaction_radius
+            {
+                continue;
+            }
+            // NPCs with a vision cone only notice emotes in front of them
+            let to_emoter = (emoter_position - transform.translation()).normalize_or_zero();
+            if perception.is_some_and(|perception| transform.forward().angle_between(to_emoter).to_degrees() > perception.fov / 2.0) {
+                continue;
+            }
+
+            let hostile = match (faction, emoter_faction) {
+                (Some(faction), Some(emoter_faction)) => factions.is_enemy(&faction.name, &emoter_faction.name),
+                _ => false,
+            };
+            let companion = !hostile && friend_manager.is_some();
+
+            let reaction = match definition.intent {
+                EmoteIntent::Greeting if !hostile => {
+                    let response = reactor
+                        .greeting_response
+                        .clone()
+                        .or_else(|| library.first_with_intent(EmoteIntent::Greeting).map(|emote| emote.id.clone()));
+                    match response {
+                        Some(response) => {
+                            emote_commands.send(EmoteCommand::Play { entity: npc, emote: response });
+                            EmoteReaction::Responded
+                        }
+                        None => EmoteReaction::Noticed,
+                    }
+                }
+                EmoteIntent::Celebrate if companion => {
+                    emote_commands.send(EmoteCommand::Play { entity: npc, emote: emote.clone() });
+                    EmoteReaction::Responded
+                }
+                EmoteIntent::FollowMe if companion => {
+                    if let Some(mut friend_manager) = friend_manager {
+                        friend_manager.current_command = AiCommand::Follow;
+                    }
+                    ai.target = Some(*emoter);
+                    EmoteReaction::Follow
+                }
+                EmoteIntent::Wait if companion => {
+                    if let Some(mut friend_manager) = friend_manager {
+                        friend_manager.current_command = AiCommand::Wait;
+                    }
+                    EmoteReaction::Wait
+                }
+                EmoteIntent::Taunt if hostile => {
+                    ai.target = Some(*emoter);
+                    ai.target_last_position = Some(emoter_position);
+                    ai.state = AiBehaviorState::Chase;
+                    EmoteReaction::Provoked
+                }
+                _ => EmoteReaction::Noticed,
+            };
+
+            reactor.last_reaction = Some(now);
+            reactions.send(EmoteReactionEvent {
+                npc,
+                emoter: *emoter,
+                emote: emote.clone(),
+                reaction,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::{DamageResultQueue, DamageType};
+    use crate::utils::EventQueuePlugin;
+    use crate::vfx::VfxRequest;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[derive(Resource, Default)]
+    struct EmoteLog(Vec<EmoteEvent>);
+
+    fn log_emote_events(mut events: QueueReader<EmoteEvent>, mut log: ResMut<EmoteLog>) {
+        log.0.extend(events.read().cloned());
+    }
+
+    #[test]
+    fn test_emotes_react_interrupt_and_finish() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_plugins((
+                EventQueuePlugin::<EmoteCommand>::default(),
+                EventQueuePlugin::<EmoteEvent>::default(),
+                EventQueuePlugin::<EmoteReactionEvent>::default(),
+                EventQueuePlugin::<DamageResultEvent>::default(),
+                EventQueuePlugin::<VfxRequest>::default(),
+            ))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<EmoteLibrary>()
+            .init_resource::<EmoteSettings>()
+            .init_resource::<EmoteAnimations>()
+            .init_resource::<FactionSystem>()
+            .init_resource::<EmoteLog>()
+            .add_systems(Update, (
+                attach_emote_components,
+                process_emote_commands,
+                update_emotes,
+                react_to_emotes,
+                log_emote_events,
+            ).chain());
+
+        let player = app.world_mut().spawn((Player, GlobalTransform::default())).id();
+        let npc = app.world_mut().spawn((AiController::default(), GlobalTransform::from_xyz(0.0, 0.0, 3.0))).id();
+        app.update();
+
+        // A friendly NPC nearby waves back
+        app.world_mut()
+            .resource_mut::<EmoteCommandQueue>()
+            .send(EmoteCommand::Play { entity: player, emote: "wave".to_string() });
+        app.update();
+        let reaction = app.world().resource::<EmoteReactionQueue>().iter().next().cloned().unwrap();
+        assert_eq!((reaction.npc, reaction.reaction), (npc, EmoteReaction::Responded));
+        app.update();
+        assert_eq!(app.world().get::<Emoting>(npc).unwrap().emote, "wave");
+
+        // Getting hit cuts the player's emote and locks emotes out for a while
+        app.world_mut().resource_mut::<DamageResultQueue>().send(DamageResultEvent {
+            target: player,
+            part: None,
+            source: None,
+            original_amount: 5.0,
+            final_amount: 5.0,
+            damage_type: DamageType::Melee,
+            shielded_amount: 0.0,
+            is_crit: false,
+            is_block: false,
+        });
+        app.update();
+        assert!(app.world().get::<Emoting>(player).is_none());
+        app.world_mut()
+            .resource_mut::<EmoteCommandQueue>()
+            .send(EmoteCommand::Play { entity: player, emote: "dance".to_string() });
+        app.update();
+        assert!(app.world().get::<Emoting>(player).is_none());
+
+        for _ in 0..20 {
+            app.update();
+        }
+        assert!(app.world().get::<Emoting>(npc).is_none());
+        assert_eq!(app.world().resource::<EmoteLog>().0, vec![
+            EmoteEvent::Started { entity: player, emote: "wave".to_string() },
+            EmoteEvent::Started { entity: npc, emote: "wave".to_string() },
+            EmoteEvent::Interrupted { entity: player, emote: "wave".to_string(), reason: EmoteInterruption::Damage },
+            EmoteEvent::Rejected { entity: player, emote: "dance".to_string() },
+            EmoteEvent::Finished { entity: npc, emote: "wave".to_string() },
+        ]);
+    }
+}
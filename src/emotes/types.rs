@@ -0,0 +1,248 @@
+use bevy::prelude::*;
+use crate::utils::EventQueue;
+use crate::vfx::VfxKey;
+
+/// What an emote means to the NPCs that see it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum EmoteIntent {
+    /// Purely cosmetic, NPCs ignore it
+    #[default]
+    Neutral,
+    /// Friendly NPCs greet back
+    Greeting,
+    /// Companions join in
+    Celebrate,
+    /// Companions follow the emoter
+    FollowMe,
+    /// Companions hold position
+    Wait,
+    /// Hostile NPCs go after the emoter
+    Taunt,
+}
+
+/// Data-defined emote
+#[derive(Debug, Clone, Reflect)]
+pub struct EmoteDefinition {
+    pub id: String,
+    pub label: String,
+    /// Animation clip asset path (e.g. "models/character.glb#Animation4"); empty for none
+    pub animation: String,
+    /// Sound played at the emoter; empty for none
+    pub sound: String,
+    pub vfx: Option<VfxKey>,
+    /// Seconds before the emote ends; ignored while looping
+    pub duration: f32,
+    /// Plays until interrupted or stopped
+    pub looping: bool,
+    /// Movement input cancels the emote
+    pub interrupted_by_movement: bool,
+    pub intent: EmoteIntent,
+}
+
+impl EmoteDefinition {
+    pub fn new(id: &str, label: &str, duration: f32, intent: EmoteIntent) -> Self {
+        Self {
+            id: id.to_string(),
+            label: label.to_string(),
+            animation: String::new(),
+            sound: String::new(),
+            vfx: None,
+            duration,
+            looping: false,
+            interrupted_by_movement: true,
+            intent,
+        }
+    }
+}
+
+/// Emotes available to characters, in wheel order
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct EmoteLibrary {
+    pub emotes: Vec<EmoteDefinition>,
+}
+
+impl Default for EmoteLibrary {
+    fn default() -> Self {
+        let mut cheer = EmoteDefinition::new("cheer", "Cheer", 2.5, EmoteIntent::Celebrate);
+        cheer.vfx = Some(VfxKey::LevelUp);
+        let mut sit = EmoteDefinition::new("sit", "Sit", 0.0, EmoteIntent::Neutral);
+        sit.looping = true;
+
+        Self {
+            emotes: vec![
+                EmoteDefinition::new("wave", "Wave", 2.0, EmoteIntent::Greeting),
+                cheer,
+                EmoteDefinition::new("follow_me", "Follow Me", 1.5, EmoteIntent::FollowMe),
+                EmoteDefinition::new("halt", "Halt", 1.5, EmoteIntent::Wait),
+                EmoteDefinition::new("taunt", "Taunt", 2.0, EmoteIntent::Taunt),
+                EmoteDefinition::new("point", "Point", 1.5, EmoteIntent::Neutral),
+                sit,
+                EmoteDefinition::new("dance", "Dance", 6.0, EmoteIntent::Celebrate),
+            ],
+        }
+    }
+}
+
+impl EmoteLibrary {
+    pub fn get(&self, id: &str) -> Option<&EmoteDefinition> {
+        self.emotes.iter().find(|emote| emote.id == id)
+    }
+
+    /// First emote with the given intent, used for NPC responses
+    pub fn first_with_intent(&self, intent: EmoteIntent) -> Option<&EmoteDefinition> {
+        self.emotes.iter().find(|emote| emote.intent == intent)
+    }
+}
+
+/// Global emote settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct EmoteSettings {
+    pub enabled: bool,
+    /// Distance within which NPCs notice emotes
+    pub reaction_radius: f32,
+    /// Emotes can't start for this long after taking damage
+    pub combat_lockout: f32,
+    /// Wheel radius in pixels
+    pub wheel_radius: f32,
+    /// Pointer offset (pixels, or stick magnitude for gamepads) below which nothing is selected
+    pub wheel_dead_zone: f32,
+    /// Gamepad stick offset accumulated per second while the wheel is open
+    pub stick_sensitivity: f32,
+}
+
+impl Default for EmoteSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            reaction_radius: 12.0,
+            combat_lockout: 3.0,
+            wheel_radius: 160.0,
+            wheel_dead_zone: 40.0,
+            stick_sensitivity: 600.0,
+        }
+    }
+}
+
+/// Emote being played by a character
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Emoting {
+    pub emote: String,
+    pub elapsed: f32,
+    pub duration: f32,
+    pub looping: bool,
+    pub interrupted_by_movement: bool,
+    /// Animation graph the character used before the emote, restored when it ends
+    #[reflect(ignore)]
+    pub previous_graph: Option<(Entity, Handle<AnimationGraph>)>,
+}
+
+/// Emote state of a character able to emote
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct EmoteCharacter {
+    /// Time of the last damage taken, for the combat lockout
+    pub last_damage_time: Option<f32>,
+}
+
+/// How an NPC answers emotes; AI characters get the default one automatically
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct EmoteReactor {
+    pub enabled: bool,
+    /// Seconds between two reactions
+    pub cooldown: f32,
+    pub last_reaction: Option<f32>,
+    /// Emote answering greetings; the library's first greeting when `None`
+    pub greeting_response: Option<String>,
+}
+
+impl Default for EmoteReactor {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            cooldown: 5.0,
+            last_reaction: None,
+            greeting_response: None,
+        }
+    }
+}
+
+/// Why an emote stopped early
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum EmoteInterruption {
+    Movement,
+    Combat,
+    Action,
+    Damage,
+    /// Replaced by another emote or stopped by a command
+    Command,
+}
+
+/// How an NPC reacted to an emote
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum EmoteReaction {
+    /// Noticed it but did nothing
+    Noticed,
+    /// Answered with an emote
+    Responded,
+    /// Companion switched to following the emoter
+    Follow,
+    /// Companion holds position
+    Wait,
+    /// Hostile NPC targets the emoter
+    Provoked,
+}
+
+/// Emote control
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmoteCommand {
+    Play { entity: Entity, emote: String },
+    Stop { entity: Entity },
+}
+
+/// Queue for emote commands
+pub type EmoteCommandQueue = EventQueue<EmoteCommand>;
+
+/// Emote notifications
+#[derive(Debug, Clone, PartialEq)]
+pub enum EmoteEvent {
+    Started { entity: Entity, emote: String },
+    Finished { entity: Entity, emote: String },
+    Interrupted { entity: Entity, emote: String, reason: EmoteInterruption },
+    /// Start refused (combat lockout, running action, unknown emote)
+    Rejected { entity: Entity, emote: String },
+}
+
+/// Queue for emote events
+pub type EmoteEventQueue = EventQueue<EmoteEvent>;
+
+/// NPC reaction to a player's emote; the hook for game-specific behavior
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmoteReactionEvent {
+    pub npc: Entity,
+    pub emoter: Entity,
+    pub emote: String,
+    pub reaction: EmoteReaction,
+}
+
+/// Queue for emote reactions
+pub type EmoteReactionQueue = EventQueue<EmoteReactionEvent>;
+
+/// Emote wheel state
+#[derive(Resource, Debug, Default)]
+pub struct EmoteWheelState {
+    pub open: bool,
+    /// Wheel slot under the pointer
+    pub selected: Option<usize>,
+    /// Pointer offset from the wheel center, in pixels (y up)
+    pub pointer: Vec2,
+}
+
+/// Cached animation graphs, one per emote
+#[derive(Resource, Default)]
+pub struct EmoteAnimations {
+    pub graphs: std::collections::HashMap<String, (Handle<AnimationGraph>, AnimationNodeIndex)>,
+}
@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use super::systems::EmoteConfig;
+use super::types::*;
+
+const SLOT_WIDTH: f32 = 96.0;
+const SLOT_HEIGHT: f32 = 36.0;
+
+/// Emote wheel overlay
+#[derive(Component)]
+pub struct EmoteWheelRoot;
+
+/// Slots built from the library
+#[derive(Component)]
+pub struct EmoteWheelContent;
+
+#[derive(Component)]
+pub struct EmoteWheelSlot(pub usize);
+
+/// Label of the selected emote in the wheel's center
+#[derive(Component)]
+pub struct EmoteWheelLabel;
+
+/// System to setup the emote wheel UI (hidden by default)
+pub fn setup_emote_wheel_ui(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        EmoteWheelRoot,
+        Visibility::Hidden,
+    ));
+}
+
+/// System to show the wheel, rebuild its slots when the library changes and highlight the selection
+pub fn update_emote_wheel_ui(
+    mut commands: Commands,
+    wheel: Res<EmoteWheelState>,
+    config: EmoteConfig,
+    mut root_query: Query<(Entity, &mut Visibility), With<EmoteWheelRoot>>,
+    content_query: Query<Entity, With<EmoteWheelContent>>,
+    mut slot_query: Query<(&mut BackgroundColor, &EmoteWheelSlot)>,
+    mut label_query: Query<&mut Text, With<EmoteWheelLabel>>,
+) {
+    let EmoteConfig { settings, library } = config;
+    let Ok((root, mut visibility)) = root_query.single_mut() else { return };
+
+    let wanted = if wheel.open { Visibility::Visible } else { Visibility::Hidden };
+    visibility.set_if_neq(wanted);
+    if !wheel.open {
+        return;
+    }
+
+    let content = content_query.iter().next();
+    if content.is_none() || library.is_changed() || settings.is_changed() {
+        if let Some(content) = content {
+            commands.entity(content).despawn();
+        }
+        commands.entity(root).with_children(|root| build_wheel_slots(root, &library, settings.wheel_radius));
+        return;
+    }
+
+    for (mut background, slot) in slot_query.iter_mut() {
+        background.0 = if wheel.selected == Some(slot.0) {
+            Color::srgba(0.85, 0.65, 0.2, 0.95)
+        } else {
+            Color::srgba(0.1, 0.1, 0.12, 0.85)
+        };
+    }
+
+    let label = wheel
+        .selected
+        .and_then(|index| library.emotes.get(index))
+        .map(|emote| emote.label.clone())
+        .unwrap_or_default();
+    for mut text in label_query.iter_mut() {
+        if text.0 != label {
+            text.0 = label.clone();
+        }
+    }
+}
+
+fn build_wheel_slots(root: &mut ChildSpawnerCommands, library: &EmoteLibrary, radius: f32) {
+    let size = radius * 2.0 + SLOT_WIDTH;
+    let center = size * 0.5;
+    let count = library.emotes.len().max(1);
+
+    root.spawn((
+        Node {
+            width: Val::Px(size),
+            height: Val::Px(size),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        EmoteWheelContent,
+    )).with_children(|content| {
+        content.spawn((
+            Text::new(""),
+            TextFont { font_size: 22.0, ..default() },
+            TextColor(Color::WHITE),
+            EmoteWheelLabel,
+        ));
+
+        for (index, emote) in library.emotes.iter().enumerate() {
+            // Slot 0 at the top, clockwise
+            let angle = index as f32 / count as f32 * std::f32::consts::TAU;
+            content.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(center + angle.sin() * radius - SLOT_WIDTH * 0.5),
+                    top: Val::Px(center - angle.cos() * radius - SLOT_HEIGHT * 0.5),
+                    width: Val::Px(SLOT_WIDTH),
+                    height: Val::Px(SLOT_HEIGHT),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.1, 0.1, 0.12, 0.85)),
+                EmoteWheelSlot(index),
+            )).with_children(|slot| {
+                slot.spawn((
+                    Text::new(emote.label.clone()),
+                    TextFont { font_size: 16.0, ..default() },
+                    TextColor(Color::WHITE),
+                ));
+            });
+        }
+    });
+}
@@ -1,7 +1,9 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
 use super::types::*;
+use crate::tags::TagIndex;
 use std::collections::HashSet;
+use crate::utils::QueueReader;
 
 pub fn update_event_triggers(
     mut triggers: Query<(
@@ -12,6 +14,7 @@ pub fn update_event_triggers(
     )>,
     mut commands: Commands,
     mut remote_event_queue: ResMut<RemoteEventQueue>,
+    tag_index: Res<TagIndex>,
 ) {
     for (entity, mut trigger, colliding_entities, prev_collisions_opt) in triggers.iter_mut() {
         // Ensure PreviousCollisions component exists
@@ -22,8 +25,12 @@ pub fn update_event_triggers(
              continue; // Wait for next frame to have the component
         };
 
-        // Current set of colliding entities
-        let current_set: HashSet<Entity> = colliding_entities.0.iter().cloned().collect();
+        // Current set of colliding entities that pass the tag filter
+        let current_set: HashSet<Entity> = colliding_entities.0
+            .iter()
+            .cloned()
+            .filter(|other| trigger.tags.is_empty() || tag_index.has_any_tag(*other, &trigger.tags))
+            .collect();
         
         // ENTER EVENTS
         if trigger.on_enter && trigger.is_active {
@@ -68,9 +75,10 @@ fn fire_trigger_event(
     queue: &mut RemoteEventQueue
 ) {
     if info.use_remote_event {
-        queue.0.push(RemoteEvent {
+        queue.send(RemoteEvent {
             name: info.event_name.clone(),
             target: Some(target), // We send it TO the entity that entered
+            target_tag: None,
             source: None,
             parameter: info.parameter.clone(),
         });
@@ -81,11 +89,12 @@ fn fire_trigger_event(
 
 
 pub fn handle_remote_events(
-    mut event_queue: ResMut<RemoteEventQueue>,
+    mut event_queue: QueueReader<RemoteEvent>,
     receivers: Query<(Entity, &RemoteEventReceiver)>,
+    tag_index: Res<TagIndex>,
     mut commands: Commands,
 ) {
-     for event in event_queue.0.drain(..) {
+     for event in event_queue.read().cloned() {
          // 1. If target is specified, check against it
          if let Some(target) = event.target {
              if let Ok((_, receiver)) = receivers.get(target) {
@@ -95,6 +104,15 @@ pub fn handle_remote_events(
                      info!("Remote Event '{}' received by {:?}", event.name, target);
                  }
              }
+         } else if let Some(tag) = event.target_tag.as_deref() {
+             // Deliver to receivers with the tag
+             for entity in tag_index.entities_with(tag) {
+                 if let Ok((_, receiver)) = receivers.get(entity) {
+                     if receiver.events.contains(&event.name) {
+                         info!("Remote Event '{}' received by {:?} (Tag '{}')", event.name, entity, tag);
+                     }
+                 }
+             }
          } else {
              // Broadcast to all receivers matching name
              for (entity, receiver) in receivers.iter() {
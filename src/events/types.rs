@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::EventQueue;
 
 /// Parameters that can be passed with an event
 #[derive(Debug, Clone, Reflect)]
@@ -32,12 +33,13 @@ impl Default for RemoteEventReceiver {
 pub struct RemoteEvent {
     pub name: String,
     pub target: Option<Entity>,
+    /// Deliver to every receiver with this tag (ignored when `target` is set)
+    pub target_tag: Option<String>,
     pub source: Option<Entity>,
     pub parameter: EventParameter,
 }
 
-#[derive(Resource, Default)]
-pub struct RemoteEventQueue(pub Vec<RemoteEvent>);
+pub type RemoteEventQueue = EventQueue<RemoteEvent>;
 
 /// Defines a trigger area that fires events on intersection
 #[derive(Component, Debug, Reflect)]
@@ -47,7 +49,7 @@ pub struct EventTrigger {
     pub on_exit: bool,
     pub on_stay: bool,
     
-    pub tags: Vec<String>, // Tags required to trigger
+    pub tags: Vec<String>, // Tags required to trigger (GameTag, any of; empty = everything)
     pub target_entity: Option<Entity>, // Only specific entity triggers
     
     // Actions to perform
@@ -61,7 +63,8 @@ pub struct EventTrigger {
     pub delay: f32,
 }
 
-#[derive(Component, Debug, Default)]
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
 pub struct PreviousCollisions(pub std::collections::HashSet<Entity>);
 
 impl Default for EventTrigger {
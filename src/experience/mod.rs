@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use bevy::app::App;
+use crate::utils::EventQueuePlugin;
 
 pub mod types;
 pub mod systems;
@@ -12,8 +13,8 @@ impl Plugin for ExperiencePlugin {
             .register_type::<types::ExperienceSettings>()
             .register_type::<types::ObjectExperience>()
             .init_resource::<types::ExperienceSettings>()
-            .init_resource::<types::ExperienceObtainedQueue>()
-            .init_resource::<types::LevelUpQueue>()
+            .add_plugins(EventQueuePlugin::<types::ExperienceObtainedEvent>::default())
+            .add_plugins(EventQueuePlugin::<types::LevelUpEvent>::default())
             .add_systems(Startup, systems::initialize_experience_settings)
             .add_systems(Update, (
                 systems::handle_experience_gain,
@@ -2,6 +2,8 @@ use bevy::prelude::*;
 use super::types::*;
 use rand::Rng;
 use crate::stats::{stats_system::StatsSystem, types::{CoreAttribute, DerivedStat, StatValue}};
+use crate::vfx::{VfxKey, VfxRequestQueue};
+use crate::utils::QueueReader;
 
 pub fn initialize_experience_settings(
     mut settings: ResMut<ExperienceSettings>,
@@ -28,14 +30,15 @@ pub fn initialize_experience_settings(
 }
 
 pub fn handle_experience_gain(
-    mut xp_queue: ResMut<ExperienceObtainedQueue>,
+    mut xp_queue: QueueReader<ExperienceObtainedEvent>,
     mut level_up_queue: ResMut<LevelUpQueue>,
-    mut query: Query<&mut PlayerExperience>,
+    mut vfx_queue: ResMut<VfxRequestQueue>,
+    mut query: Query<(&mut PlayerExperience, Option<&GlobalTransform>)>,
     settings: Res<ExperienceSettings>,
 ) {
     // Drain the queue
-    for event in xp_queue.0.drain(..) {
-        if let Ok(mut player_xp) = query.get_mut(event.entity) {
+    for event in xp_queue.read().cloned() {
+        if let Ok((mut player_xp, transform)) = query.get_mut(event.entity) {
             let mut gain = event.amount as f32;
             if player_xp.xp_multiplier_timer > 0.0 {
                 gain *= player_xp.xp_multiplier;
@@ -54,10 +57,13 @@ pub fn handle_experience_gain(
                         player_xp.current_level += 1;
                         player_xp.skill_points += level_info.skill_points_reward;
 
-                        level_up_queue.0.push(LevelUpEvent {
+                        level_up_queue.send(LevelUpEvent {
                             entity: event.entity,
                             new_level: player_xp.current_level,
                         });
+                        if let Some(transform) = transform {
+                            vfx_queue.spawn(VfxKey::LevelUp, transform.translation());
+                        }
 
                         // Check if we hit max level
                         if let Some(max) = settings.max_level {
@@ -77,11 +83,11 @@ pub fn handle_experience_gain(
 }
 
 pub fn handle_level_up_rewards(
-    mut level_up_queue: ResMut<LevelUpQueue>,
+    mut level_up_queue: QueueReader<LevelUpEvent>,
     settings: Res<ExperienceSettings>,
     mut stats_query: Query<&mut StatsSystem>,
 ) {
-    for event in level_up_queue.0.drain(..) {
+    for event in level_up_queue.read().cloned() {
         let level_idx = (event.new_level as usize).saturating_sub(1);
         let Some(level_info) = settings.levels.get(level_idx) else { continue };
 
@@ -192,7 +198,7 @@ pub fn grant_xp_from_object(
         object_experience.xp_amount
     };
 
-    xp_queue.0.push(ExperienceObtainedEvent {
+    xp_queue.send(ExperienceObtainedEvent {
         entity: player_entity,
         amount,
         source_position,
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use crate::utils::EventQueue;
 
 #[derive(Component, Debug, Reflect, Clone, Default)]
 #[reflect(Component)]
@@ -52,8 +53,7 @@ pub struct ExperienceObtainedEvent {
     pub source_position: Option<Vec3>,
 }
 
-#[derive(Resource, Default)]
-pub struct ExperienceObtainedQueue(pub Vec<ExperienceObtainedEvent>);
+pub type ExperienceObtainedQueue = EventQueue<ExperienceObtainedEvent>;
 
 #[derive(Event, Debug, Clone)]
 pub struct LevelUpEvent {
@@ -61,5 +61,4 @@ pub struct LevelUpEvent {
     pub new_level: u32,
 }
 
-#[derive(Resource, Default)]
-pub struct LevelUpQueue(pub Vec<LevelUpEvent>);
+pub type LevelUpQueue = EventQueue<LevelUpEvent>;
@@ -0,0 +1,70 @@
+//! Fishing minigame
+//!
+//! Characters with a [`FishingRod`] fish at [`FishingSpot`]s. Holding the attack
+//! input in reach of a spot runs a power meter that swings up and down; releasing
+//! it casts, further with more power. After a random wait the fish bites and
+//! the attack input hooks it within a short window. Reeling is a tension
+//! minigame: holding attack reels the line in and builds tension as the fish
+//! surges, releasing gives slack. Too much tension snaps the line, too much
+//! slack lets the fish go. Landed fish are picked from the spot's
+//! [`FishingLootTable`] and go into the inventory.
+//!
+//! [`FishingSkill`] levels up with catches: longer casts, quicker bites, a
+//! wider hook window, less tension and rarer fish. Every step is sent as a
+//! [`FishingEvent`], so the module also serves as a template for other
+//! minigames:
+//!
+//! ```rust,ignore
+//! commands.spawn((Transform::from_xyz(0.0, 0.0, 20.0), FishingSpot { loot_table: "sea".to_string(), ..default() }.with_stock(8)));
+//! commands.entity(player).insert(FishingRod::default());
+//!
+//! for event in fishing_events.read() {
+//!     if let FishingEvent::Caught { fish, rarity: FishRarity::Legendary, .. } = event {
+//!         // Achievement...
+//!     }
+//! }
+//! ```
+
+pub mod types;
+pub mod systems;
+pub mod ui;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use ui::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    FishRarity, FishDefinition, FishingLootTable, FishingLootTables, FishingSpot, FishingRod,
+    FishingSkill, FishingSettings, FishingPhase, Fisher, FishingEvent, FishingEventQueue,
+};
+
+pub struct FishingPlugin;
+
+impl Plugin for FishingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<FishingLootTables>()
+            .init_resource::<FishingSettings>()
+            .add_plugins(EventQueuePlugin::<FishingEvent>::default())
+            .register_type::<FishingLootTables>()
+            .register_type::<FishingSettings>()
+            .register_type::<FishingSpot>()
+            .register_type::<FishingRod>()
+            .register_type::<FishingSkill>()
+            .register_type::<Fisher>()
+            .add_systems(Startup, setup_fishing_hud.in_set(crate::headless::PresentationSet))
+            .add_systems(Update, (
+                attach_fisher,
+                update_fishing,
+            ).chain()
+                .in_set(crate::input::InputSet::Gather)
+                .after(crate::input::player_input_sync_system))
+            .add_systems(Update, (
+                restock_fishing_spots,
+                draw_fishing_lines.in_set(crate::headless::PresentationSet),
+                update_fishing_hud.in_set(crate::headless::PresentationSet),
+            ));
+    }
+}
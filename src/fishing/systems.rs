@@ -0,0 +1,281 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::character::Player;
+use crate::experience::types::{ExperienceObtainedEvent, ExperienceObtainedQueue};
+use crate::input::InputState;
+use crate::inventory::Inventory;
+use super::types::*;
+
+type NewFisher = (With<FishingRod>, Without<Fisher>);
+
+/// System to add fishing state and skill to characters with a rod
+pub fn attach_fisher(
+    mut commands: Commands,
+    query: Query<(Entity, Has<FishingSkill>), NewFisher>,
+) {
+    for (entity, has_skill) in query.iter() {
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(Fisher::default());
+        if !has_skill {
+            entity_commands.insert(FishingSkill::default());
+        }
+    }
+}
+
+/// Nearest spot in reach of `position` that still has fish
+fn spot_in_reach(position: Vec3, spot_query: &Query<(Entity, &GlobalTransform, &mut FishingSpot)>) -> Option<(Entity, Vec3)> {
+    spot_query
+        .iter()
+        .filter(|(_, _, spot)| !spot.is_fished_out())
+        .map(|(entity, transform, spot)| (entity, transform.translation(), spot.radius))
+        .filter(|(_, center, radius)| center.distance(position) <= *radius)
+        .min_by(|a, b| a.1.distance_squared(position).total_cmp(&b.1.distance_squared(position)))
+        .map(|(entity, center, _)| (entity, center))
+}
+
+fn bite_delay(spot: &FishingSpot, skill: &FishingSkill, rng: &mut impl Rng) -> f32 {
+    let (min, max) = (spot.bite_delay.0.min(spot.bite_delay.1), spot.bite_delay.0.max(spot.bite_delay.1));
+    rng.random_range(min..=max) * skill.bite_delay_multiplier()
+}
+
+type FishingPlayer<'a> = (
+    Entity,
+    &'a GlobalTransform,
+    &'a FishingRod,
+    &'a mut Fisher,
+    &'a mut FishingSkill,
+    &'a mut InputState,
+    Option<&'a mut Inventory>,
+);
+
+/// Casting, bites and the reel tension minigame, driven by the player's attack input.
+/// Runs after the player input sync so combat doesn't swing while the rod is out.
+pub fn update_fishing(
+    time: Res<Time>,
+    settings: Res<FishingSettings>,
+    loot_tables: Res<FishingLootTables>,
+    mut events: ResMut<FishingEventQueue>,
+    mut experience: ResMut<ExperienceObtainedQueue>,
+    mut spot_query: Query<(Entity, &GlobalTransform, &mut FishingSpot)>,
+    mut fisher_query: Query<FishingPlayer, With<Player>>,
+) {
+    let dt = time.delta_secs();
+    let mut rng = rand::rng();
+
+    for (entity, transform, rod, mut fisher, mut skill, mut input, inventory) in fisher_query.iter_mut() {
+        let position = transform.translation();
+
+        if !settings.enabled {
+            if fisher.is_fishing() {
+                *fisher = Fisher::default();
+                events.send(FishingEvent::Cancelled { entity });
+            }
+            continue;
+        }
+
+        // Walking off, jumping or blocking reels the line in
+        let cancel = fisher.is_fishing()
+            && (input.jump_pressed
+                || input.block_pressed
+                || (settings.cancel_on_move && input.movement.length() > 0.1 && !matches!(fisher.phase, FishingPhase::Charging { .. })));
+        let spot = fisher.spot.and_then(|spot| spot_query.get(spot).ok()).map(|(_, transform, spot)| (transform.translation(), spot.radius));
+        let line_out = matches!(fisher.phase, FishingPhase::Waiting { .. } | FishingPhase::Bite { .. } | FishingPhase::Reeling { .. });
+        let left_spot = line_out && spot.is_none_or(|(center, radius)| center.distance(position) > radius + rod.max_cast_distance);
+        if cancel || left_spot {
+            if let Some(fish) = fisher.hooked.take().filter(|_| matches!(fisher.phase, FishingPhase::Reeling { .. })) {
+                events.send(FishingEvent::Escaped { entity, fish: fish.item_id });
+            } else {
+                events.send(FishingEvent::Cancelled { entity });
+            }
+            *fisher = Fisher::default();
+            continue;
+        }
+
+        let attack_pressed = input.attack_pressed;
+        let attack_held = input.attack_held;
+        // The rod owns the attack input while it is out, or about to be
+        if fisher.is_fishing() || (attack_pressed && spot_in_reach(position, &spot_query).is_some()) {
+            input.attack_pressed = false;
+            input.attack_held = false;
+            input.attack_released = false;
+        }
+
+        let phase = fisher.phase.clone();
+        match phase {
+            FishingPhase::Idle => {
+                if !attack_pressed {
+                    continue;
+                }
+                let Some((spot_entity, _)) = spot_in_reach(position, &spot_query) else { continue };
+                fisher.spot = Some(spot_entity);
+                fisher.phase = FishingPhase::Charging { meter: 0.0 };
+            }
+            FishingPhase::Charging { meter } => {
+                if attack_held {
+                    fisher.phase = FishingPhase::Charging { meter: meter + dt / settings.charge_time.max(0.01) };
+                } else {
+                    let Some(Ok((spot_entity, spot_transform, spot))) = fisher.spot.map(|spot| spot_query.get(spot)) else {
+                        *fisher = Fisher::default();
+                        continue;
+                    };
+                    let power = Fisher::cast_power(meter);
+                    let max_distance = rod.max_cast_distance * skill.cast_multiplier();
+                    let distance = settings.min_cast_distance + (max_distance - settings.min_cast_distance).max(0.0) * power;
+                    let forward = transform.forward().as_vec3().with_y(0.0).normalize_or_zero();
+                    let mut bobber = position + forward * distance;
+                    bobber.y = spot_transform.translation().y;
+
+                    // Longer casts reach calmer water and get bites sooner
+                    let bite_in = bite_delay(spot, &skill, &mut rng) * (1.25 - 0.5 * power);
+                    fisher.bobber = bobber;
+                    fisher.phase = FishingPhase::Waiting { bite_in };
+                    events.send(FishingEvent::Cast { entity, spot: spot_entity, power, position: bobber });
+                }
+            }
+            FishingPhase::Waiting { bite_in } => {
+                if attack_pressed {
+                    // Reeled in too early
+                    *fisher = Fisher::default();
+                    events.send(FishingEvent::Cancelled { entity });
+                    continue;
+                }
+                let bite_in = bite_in - dt;
+                if bite_in > 0.0 {
+                    fisher.phase = FishingPhase::Waiting { bite_in };
+                    continue;
+                }
+
+                let Some(Ok((_, _, spot))) = fisher.spot.map(|spot| spot_query.get(spot)) else { continue };
+                let fish = loot_tables
+                    .get(&spot.loot_table)
+                    .and_then(|table| table.pick(skill.level, skill.rare_bonus(), rng.random::<f32>()))
+                    .cloned();
+                match fish {
+                    Some(fish) => {
+                        events.send(FishingEvent::Bite { entity, fish: fish.item_id.clone() });
+                        fisher.hooked = Some(fish);
+                        fisher.phase = FishingPhase::Bite { window: settings.hook_window + skill.hook_window_bonus() };
+                    }
+                    None => {
+                        fisher.phase = FishingPhase::Waiting { bite_in: bite_delay(spot, &skill, &mut rng) };
+                    }
+                }
+            }
+            FishingPhase::Bite { window } => {
+                if attack_pressed {
+                    let fish = fisher.hooked.as_ref().map(|fish| fish.item_id.clone()).unwrap_or_default();
+                    let distance = fisher.bobber.with_y(position.y).distance(position);
+                    fisher.fight_time = 0.0;
+                    fisher.phase = FishingPhase::Reeling { tension: 0.3, distance, slack_time: 0.0 };
+                    events.send(FishingEvent::Hooked { entity, fish });
+                    continue;
+                }
+                let window = window - dt;
+                if window > 0.0 {
+                    fisher.phase = FishingPhase::Bite { window };
+                    continue;
+                }
+
+                fisher.hooked = None;
+                events.send(FishingEvent::BiteMissed { entity });
+                let bite_in = fisher
+                    .spot
+                    .and_then(|spot| spot_query.get(spot).ok())
+                    .map_or(5.0, |(_, _, spot)| bite_delay(spot, &skill, &mut rng));
+                fisher.phase = FishingPhase::Waiting { bite_in };
+            }
+            FishingPhase::Reeling { mut tension, mut distance, mut slack_time } => {
+                let Some(fish) = fisher.hooked.clone() else {
+                    *fisher = Fisher::default();
+                    continue;
+                };
+                fisher.fight_time += dt;
+
+                // The fish surges and tires in waves; stronger fish surge faster
+                let surge = 0.6 + 0.4 * (fisher.fight_time * (1.5 + 2.5 * fish.strength)).sin();
+                let pull = fish.strength * surge;
+
+                if attack_held {
+                    distance -= rod.reel_speed * dt * (1.0 - 0.5 * pull);
+                    tension += (settings.base_tension_rise + pull * settings.tension_rise) * skill.tension_multiplier() * dt;
+                    slack_time = 0.0;
+                } else {
+                    distance += pull * settings.fish_pull_speed * dt;
+                    tension = (tension - settings.tension_fall * dt).max(0.0);
+                    if tension <= 0.0 && fish.strength > 0.0 {
+                        slack_time += dt;
+                    }
+                }
+
+                if tension >= rod.line_strength {
+                    *fisher = Fisher::default();
+                    events.send(FishingEvent::LineSnapped { entity, fish: fish.item_id });
+                    continue;
+                }
+                if slack_time >= settings.slack_escape_time || distance > rod.max_cast_distance * skill.cast_multiplier() * 1.5 {
+                    *fisher = Fisher::default();
+                    events.send(FishingEvent::Escaped { entity, fish: fish.item_id });
+                    continue;
+                }
+
+                // The bobber follows the line in
+                let toward = (fisher.bobber - position).with_y(0.0).normalize_or_zero();
+                fisher.bobber = (position + toward * distance).with_y(fisher.bobber.y);
+
+                if distance > settings.landing_distance {
+                    fisher.phase = FishingPhase::Reeling { tension, distance, slack_time };
+                    continue;
+                }
+
+                // Landed
+                let (min, max) = (fish.size.0.min(fish.size.1), fish.size.0.max(fish.size.1));
+                let size = rng.random_range(min..=max);
+                let stored = inventory.is_some_and(|mut inventory| inventory.add_item(fish.to_item(size)).is_none());
+                if let Some(Ok((_, _, mut spot))) = fisher.spot.map(|spot| spot_query.get_mut(spot)) {
+                    if let Some(stock) = spot.stock.as_mut() {
+                        *stock = stock.saturating_sub(1);
+                    }
+                }
+                events.send(FishingEvent::Caught { entity, fish: fish.item_id.clone(), rarity: fish.rarity, size, stored });
+
+                if let Some(level) = skill.add_experience(fish.experience) {
+                    events.send(FishingEvent::LevelUp { entity, level });
+                }
+                if settings.grant_player_experience && fish.experience > 0 {
+                    experience.send(ExperienceObtainedEvent { entity, amount: fish.experience, source_position: Some(fisher.bobber) });
+                }
+                *fisher = Fisher::default();
+            }
+        }
+    }
+}
+
+/// System to restock fished-out spots over time
+pub fn restock_fishing_spots(time: Res<Time>, mut spot_query: Query<&mut FishingSpot>) {
+    for mut spot in spot_query.iter_mut() {
+        let max_stock = spot.max_stock;
+        let Some(stock) = spot.stock.filter(|stock| *stock < max_stock) else { continue };
+        spot.restock_timer += time.delta_secs();
+        if spot.restock_timer >= spot.restock_time {
+            spot.restock_timer = 0.0;
+            spot.stock = Some(stock + 1);
+        }
+    }
+}
+
+/// System to draw fishing lines
+pub fn draw_fishing_lines(mut gizmos: Gizmos, fisher_query: Query<(&GlobalTransform, &Fisher)>) {
+    for (transform, fisher) in fisher_query.iter() {
+        if !matches!(fisher.phase, FishingPhase::Waiting { .. } | FishingPhase::Bite { .. } | FishingPhase::Reeling { .. }) {
+            continue;
+        }
+        let rod_tip = transform.translation() + Vec3::Y * 1.6 + transform.forward().as_vec3() * 1.2;
+        let color = match fisher.phase {
+            FishingPhase::Bite { .. } => Color::srgb(1.0, 0.8, 0.2),
+            FishingPhase::Reeling { .. } => Color::srgb(0.9, 0.9, 0.9),
+            _ => Color::srgb(0.7, 0.7, 0.7),
+        };
+        gizmos.line(rod_tip, fisher.bobber, color);
+        gizmos.sphere(Isometry3d::from_translation(fisher.bobber), 0.08, Color::srgb(0.9, 0.2, 0.2));
+    }
+}
@@ -0,0 +1,432 @@
+use bevy::prelude::*;
+use crate::inventory::{InventoryItem, ItemType};
+use crate::utils::EventQueue;
+
+/// How rare a catch is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum FishRarity {
+    /// Boots, cans and weeds; doesn't fight
+    Junk,
+    #[default]
+    Common,
+    Uncommon,
+    Rare,
+    Legendary,
+}
+
+/// Catch in a fishing loot table
+#[derive(Debug, Clone, Reflect)]
+pub struct FishDefinition {
+    /// Inventory item id of the catch
+    pub item_id: String,
+    pub name: String,
+    pub rarity: FishRarity,
+    /// Relative chance among the entries of the table
+    pub chance: f32,
+    /// Fishing level needed to hook it
+    pub min_level: u32,
+    /// Size range, in kg
+    pub size: (f32, f32),
+    /// Value per kg
+    pub value: f32,
+    /// How hard it fights on the line (0 = dead weight, 1 = legendary)
+    pub strength: f32,
+    /// Fishing experience for landing it
+    pub experience: u32,
+}
+
+impl FishDefinition {
+    pub fn new(item_id: &str, name: &str, rarity: FishRarity, chance: f32) -> Self {
+        let (strength, experience, value) = match rarity {
+            FishRarity::Junk => (0.0, 2, 0.5),
+            FishRarity::Common => (0.3, 10, 4.0),
+            FishRarity::Uncommon => (0.5, 25, 8.0),
+            FishRarity::Rare => (0.75, 60, 20.0),
+            FishRarity::Legendary => (1.0, 200, 80.0),
+        };
+        Self {
+            item_id: item_id.to_string(),
+            name: name.to_string(),
+            rarity,
+            chance,
+            min_level: 0,
+            size: (0.3, 2.0),
+            value,
+            strength,
+            experience,
+        }
+    }
+
+    pub fn with_min_level(mut self, level: u32) -> Self {
+        self.min_level = level;
+        self
+    }
+
+    pub fn with_size(mut self, min: f32, max: f32) -> Self {
+        self.size = (min, max);
+        self
+    }
+
+    /// Inventory item for a catch of `size` kg
+    pub fn to_item(&self, size: f32) -> InventoryItem {
+        let junk = self.rarity == FishRarity::Junk;
+        InventoryItem {
+            item_id: self.item_id.clone(),
+            name: self.name.clone(),
+            quantity: 1,
+            max_stack: if junk { 10 } else { 20 },
+            weight: size,
+            item_type: if junk { ItemType::Material } else { ItemType::Consumable },
+            icon_path: String::new(),
+            value: (self.value * size).max(0.0),
+            category: "Fish".to_string(),
+            min_level: 0,
+            info: if junk { String::new() } else { format!("{:.2} kg", size) },
+            is_infinite: false,
+        }
+    }
+}
+
+/// Weighted catches of a fishing spot
+#[derive(Debug, Clone, Reflect)]
+pub struct FishingLootTable {
+    pub id: String,
+    pub entries: Vec<FishDefinition>,
+}
+
+impl FishingLootTable {
+    /// Picks a catch for a fisher of `level` with `roll` in 0-1.
+    /// Entries above the level are skipped; `rare_bonus` raises the chance of uncommon and rarer fish.
+    pub fn pick(&self, level: u32, rare_bonus: f32, roll: f32) -> Option<&FishDefinition> {
+        let chance = |fish: &FishDefinition| -> f32 {
+            if fish.min_level > level {
+                return 0.0;
+            }
+            match fish.rarity {
+                FishRarity::Junk | FishRarity::Common => fish.chance.max(0.0),
+                _ => fish.chance.max(0.0) * (1.0 + rare_bonus),
+            }
+        };
+
+        let total: f32 = self.entries.iter().map(chance).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut remaining = roll.clamp(0.0, 1.0) * total;
+        for fish in self.entries.iter() {
+            let weight = chance(fish);
+            if weight <= 0.0 {
+                continue;
+            }
+            if remaining < weight {
+                return Some(fish);
+            }
+            remaining -= weight;
+        }
+        self.entries.iter().rev().find(|fish| chance(fish) > 0.0)
+    }
+}
+
+/// Loot tables referenced by `FishingSpot::loot_table`
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct FishingLootTables {
+    pub tables: Vec<FishingLootTable>,
+}
+
+impl Default for FishingLootTables {
+    fn default() -> Self {
+        Self {
+            tables: vec![
+                FishingLootTable {
+                    id: "freshwater".to_string(),
+                    entries: vec![
+                        FishDefinition::new("old_boot", "Old Boot", FishRarity::Junk, 8.0).with_size(0.5, 0.8),
+                        FishDefinition::new("perch", "Perch", FishRarity::Common, 40.0).with_size(0.2, 1.2),
+                        FishDefinition::new("trout", "Trout", FishRarity::Common, 30.0).with_size(0.5, 3.0),
+                        FishDefinition::new("pike", "Pike", FishRarity::Uncommon, 14.0).with_size(2.0, 8.0).with_min_level(2),
+                        FishDefinition::new("sturgeon", "Sturgeon", FishRarity::Rare, 6.0).with_size(8.0, 30.0).with_min_level(5),
+                        FishDefinition::new("golden_carp", "Golden Carp", FishRarity::Legendary, 1.0).with_size(4.0, 12.0).with_min_level(10),
+                    ],
+                },
+                FishingLootTable {
+                    id: "sea".to_string(),
+                    entries: vec![
+                        FishDefinition::new("seaweed", "Seaweed", FishRarity::Junk, 10.0).with_size(0.1, 0.4),
+                        FishDefinition::new("mackerel", "Mackerel", FishRarity::Common, 45.0).with_size(0.3, 1.5),
+                        FishDefinition::new("sea_bass", "Sea Bass", FishRarity::Uncommon, 20.0).with_size(1.0, 6.0).with_min_level(2),
+                        FishDefinition::new("tuna", "Tuna", FishRarity::Rare, 6.0).with_size(20.0, 80.0).with_min_level(6),
+                        FishDefinition::new("swordfish", "Swordfish", FishRarity::Legendary, 1.0).with_size(50.0, 150.0).with_min_level(12),
+                    ],
+                },
+            ],
+        }
+    }
+}
+
+impl FishingLootTables {
+    pub fn get(&self, id: &str) -> Option<&FishingLootTable> {
+        self.tables.iter().find(|table| table.id == id)
+    }
+}
+
+/// Water characters with a rod can fish in, around the entity transform
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct FishingSpot {
+    /// Id in `FishingLootTables`
+    pub loot_table: String,
+    /// Characters within this distance can cast here
+    pub radius: f32,
+    /// Seconds before a bite, picked in this range
+    pub bite_delay: (f32, f32),
+    /// Catches left before the spot is fished out; `None` for endless
+    pub stock: Option<u32>,
+    pub max_stock: u32,
+    /// Seconds to restock one catch
+    pub restock_time: f32,
+
+    // Debug State
+    pub restock_timer: f32,
+}
+
+impl Default for FishingSpot {
+    fn default() -> Self {
+        Self {
+            loot_table: "freshwater".to_string(),
+            radius: 8.0,
+            bite_delay: (3.0, 9.0),
+            stock: None,
+            max_stock: 5,
+            restock_time: 120.0,
+            restock_timer: 0.0,
+        }
+    }
+}
+
+impl FishingSpot {
+    pub fn with_stock(mut self, stock: u32) -> Self {
+        self.stock = Some(stock);
+        self.max_stock = stock;
+        self
+    }
+
+    pub fn is_fished_out(&self) -> bool {
+        self.stock == Some(0)
+    }
+}
+
+/// Fishing rod held by a character; needed to fish
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct FishingRod {
+    /// Cast distance at full power
+    pub max_cast_distance: f32,
+    /// Tension (0-1 scale) the line holds before snapping
+    pub line_strength: f32,
+    /// Line reeled in per second
+    pub reel_speed: f32,
+}
+
+impl Default for FishingRod {
+    fn default() -> Self {
+        Self {
+            max_cast_distance: 15.0,
+            line_strength: 1.0,
+            reel_speed: 2.5,
+        }
+    }
+}
+
+/// Fishing level and experience; added to characters with a rod
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct FishingSkill {
+    pub level: u32,
+    pub experience: u32,
+    pub max_level: u32,
+}
+
+impl Default for FishingSkill {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            experience: 0,
+            max_level: 20,
+        }
+    }
+}
+
+impl FishingSkill {
+    /// Experience needed to reach the next level
+    pub fn experience_to_next_level(&self) -> u32 {
+        self.level * 100
+    }
+
+    /// Adds experience, returning the new level if it went up
+    pub fn add_experience(&mut self, amount: u32) -> Option<u32> {
+        let start = self.level;
+        self.experience += amount;
+        while self.level < self.max_level && self.experience >= self.experience_to_next_level() {
+            self.experience -= self.experience_to_next_level();
+            self.level += 1;
+        }
+        (self.level > start).then_some(self.level)
+    }
+
+    /// Cast distance multiplier
+    pub fn cast_multiplier(&self) -> f32 {
+        1.0 + 0.02 * self.level as f32
+    }
+
+    /// Bite delay multiplier
+    pub fn bite_delay_multiplier(&self) -> f32 {
+        (1.0 - 0.025 * self.level as f32).max(0.5)
+    }
+
+    /// Extra seconds to hook a bite
+    pub fn hook_window_bonus(&self) -> f32 {
+        0.03 * self.level as f32
+    }
+
+    /// Tension gain multiplier while reeling
+    pub fn tension_multiplier(&self) -> f32 {
+        (1.0 - 0.025 * self.level as f32).max(0.5)
+    }
+
+    /// Extra chance for uncommon and rarer fish
+    pub fn rare_bonus(&self) -> f32 {
+        0.05 * self.level as f32
+    }
+}
+
+/// Fishing tuning
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct FishingSettings {
+    pub enabled: bool,
+    /// Seconds for the power meter to fill; it swings back down when held longer
+    pub charge_time: f32,
+    pub min_cast_distance: f32,
+    /// Seconds to hook a bite
+    pub hook_window: f32,
+    /// Tension gained per second reeling against a full strength fish
+    pub tension_rise: f32,
+    /// Tension gained per second reeling in dead weight
+    pub base_tension_rise: f32,
+    /// Tension lost per second with the line slack
+    pub tension_fall: f32,
+    /// Line a full strength fish pulls out per second while slack
+    pub fish_pull_speed: f32,
+    /// Seconds of slack line before the fish gets away
+    pub slack_escape_time: f32,
+    /// Line distance at which the catch is landed
+    pub landing_distance: f32,
+    /// Movement, jumping or blocking reels the line in
+    pub cancel_on_move: bool,
+    /// Also grant player experience for catches
+    pub grant_player_experience: bool,
+}
+
+impl Default for FishingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            charge_time: 1.2,
+            min_cast_distance: 3.0,
+            hook_window: 0.8,
+            tension_rise: 0.6,
+            base_tension_rise: 0.1,
+            tension_fall: 0.8,
+            fish_pull_speed: 1.5,
+            slack_escape_time: 2.5,
+            landing_distance: 1.5,
+            cancel_on_move: true,
+            grant_player_experience: true,
+        }
+    }
+}
+
+/// Fishing phase of a character
+#[derive(Debug, Clone, PartialEq, Reflect, Default)]
+pub enum FishingPhase {
+    #[default]
+    Idle,
+    /// Power meter running while the attack input is held
+    Charging { meter: f32 },
+    /// Bobber in the water, waiting for a bite
+    Waiting { bite_in: f32 },
+    /// Fish nibbling; attack hooks it before the window closes
+    Bite { window: f32 },
+    /// Fish on the line: hold attack to reel, release to give slack
+    Reeling { tension: f32, distance: f32, slack_time: f32 },
+}
+
+/// Fishing state; added to characters with a `FishingRod`
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Fisher {
+    pub phase: FishingPhase,
+    pub spot: Option<Entity>,
+    /// Where the line is in the water
+    pub bobber: Vec3,
+    /// Catch picked at the bite
+    pub hooked: Option<FishDefinition>,
+    /// Seconds into the fight, drives the fish's surges
+    pub fight_time: f32,
+}
+
+impl Fisher {
+    pub fn is_fishing(&self) -> bool {
+        self.phase != FishingPhase::Idle
+    }
+
+    /// Cast power (0-1) of a charging meter: rises over `charge_time`, then swings back down
+    pub fn cast_power(meter: f32) -> f32 {
+        let phase = meter.rem_euclid(2.0);
+        if phase <= 1.0 { phase } else { 2.0 - phase }
+    }
+}
+
+/// Fishing notifications
+#[derive(Debug, Clone, PartialEq)]
+pub enum FishingEvent {
+    Cast { entity: Entity, spot: Entity, power: f32, position: Vec3 },
+    Bite { entity: Entity, fish: String },
+    Hooked { entity: Entity, fish: String },
+    /// The bite wasn't hooked in time
+    BiteMissed { entity: Entity },
+    LineSnapped { entity: Entity, fish: String },
+    Escaped { entity: Entity, fish: String },
+    /// `stored` is false when the inventory was full
+    Caught { entity: Entity, fish: String, rarity: FishRarity, size: f32, stored: bool },
+    LevelUp { entity: Entity, level: u32 },
+    /// Line reeled in without a catch
+    Cancelled { entity: Entity },
+}
+
+/// Queue for fishing events
+pub type FishingEventQueue = EventQueue<FishingEvent>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_fishing_loot_and_skill() {
+        let tables = FishingLootTables::default();
+        let freshwater = tables.get("freshwater").unwrap();
+        // Low levels never hook fish above their level
+        for roll in [0.0, 0.25, 0.5, 0.75, 0.999] {
+            assert!(freshwater.pick(1, 0.0, roll).unwrap().min_level <= 1);
+        }
+        assert_eq!(freshwater.pick(1, 0.0, 0.0).unwrap().item_id, "old_boot");
+
+        let mut skill = FishingSkill::default();
+        assert_eq!(skill.add_experience(250), Some(2));
+        assert_eq!(skill.experience, 150);
+        assert_eq!(Fisher::cast_power(0.5), 0.5);
+        assert_eq!(Fisher::cast_power(1.5), 0.5);
+    }
+}
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use crate::character::Player;
+use crate::input::{GlyphProvider, InputMap};
+use super::types::*;
+
+/// Fishing meter overlay (cast power, then line tension)
+#[derive(Component)]
+pub struct FishingHudRoot;
+
+#[derive(Component)]
+pub struct FishingMeterFill;
+
+#[derive(Component)]
+pub struct FishingHudLabel;
+
+/// System to setup the fishing meter (hidden by default)
+pub fn setup_fishing_hud(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(140.0),
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            FishingHudRoot,
+            Visibility::Hidden,
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Text::new(""),
+                TextFont { font_size: 18.0, ..default() },
+                TextColor(Color::WHITE),
+                FishingHudLabel,
+            ));
+            root.spawn((
+                Node {
+                    width: Val::Px(240.0),
+                    height: Val::Px(14.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.1, 0.1, 0.12, 0.85)),
+            ))
+            .with_children(|bar| {
+                bar.spawn((
+                    Node {
+                        width: Val::Percent(0.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.3, 0.7, 0.9)),
+                    FishingMeterFill,
+                ));
+            });
+        });
+}
+
+/// System to show the local player's cast power or line tension
+pub fn update_fishing_hud(
+    glyphs: Res<GlyphProvider>,
+    input_map: Res<InputMap>,
+    fisher_query: Query<(&Fisher, Option<&FishingRod>), With<Player>>,
+    mut root_query: Query<&mut Visibility, With<FishingHudRoot>>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<FishingMeterFill>>,
+    mut label_query: Query<&mut Text, With<FishingHudLabel>>,
+) {
+    let Ok(mut visibility) = root_query.single_mut() else { return };
+    let fisher = fisher_query.iter().find(|(fisher, _)| fisher.is_fishing());
+
+    let (fill, color, label) = match fisher {
+        Some((fisher, rod)) => match &fisher.phase {
+            FishingPhase::Charging { meter } => {
+                (Fisher::cast_power(*meter), Color::srgb(0.3, 0.7, 0.9), "Release {Attack} to cast".to_string())
+            }
+            FishingPhase::Waiting { .. } => (0.0, Color::srgb(0.3, 0.7, 0.9), "Waiting for a bite...".to_string()),
+            FishingPhase::Bite { .. } => (1.0, Color::srgb(1.0, 0.8, 0.2), "Bite! Press {Attack}".to_string()),
+            FishingPhase::Reeling { tension, .. } => {
+                let strength = rod.map_or(1.0, |rod| rod.line_strength).max(0.01);
+                let ratio = (tension / strength).clamp(0.0, 1.0);
+                let color = Color::srgb(0.3 + 0.7 * ratio, 0.8 - 0.6 * ratio, 0.3);
+                (ratio, color, "Hold {Attack} to reel, release when the line is tight".to_string())
+            }
+            FishingPhase::Idle => (0.0, Color::NONE, String::new()),
+        },
+        None => {
+            visibility.set_if_neq(Visibility::Hidden);
+            return;
+        }
+    };
+
+    visibility.set_if_neq(Visibility::Visible);
+    let label = glyphs.format_prompt(&label, &input_map);
+    for (mut node, mut background) in fill_query.iter_mut() {
+        node.width = Val::Percent(fill * 100.0);
+        background.0 = color;
+    }
+    for mut text in label_query.iter_mut() {
+        if text.0 != label {
+            text.0 = label.clone();
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use bevy::app::App;
+use crate::utils::EventQueuePlugin;
 
 pub mod types;
 pub mod systems;
@@ -15,11 +16,14 @@ impl Plugin for FootstepPlugin {
             .register_type::<types::FootstepAssets>()
             .register_type::<types::FootstepDecal>()
             .init_resource::<types::FootstepAssets>()
-            .init_resource::<types::FootstepEventQueue>()
+            .add_plugins(EventQueuePlugin::<types::FootstepEvent>::default())
             .init_resource::<types::FootstepDecalSettings>()
+            .init_resource::<types::FootstepNoiseSettings>()
+            .register_type::<types::FootstepNoiseSettings>()
             .add_systems(Update, (
                 systems::update_footsteps,
                 systems::handle_footstep_audio,
+                systems::publish_footstep_noise,
                 systems::update_footstep_decals,
             ));
     }
@@ -1,27 +1,35 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
+use crate::ai::{AiController, NoiseEvent, NoiseEventQueue};
 use crate::physics::GroundDetection;
 use crate::character::CharacterMovementState;
+use crate::inventory::{Equipment, Inventory};
 use super::types::*;
 use rand::Rng;
+use crate::utils::QueueReader;
+
+type FootstepWalker<'a> = (
+    Entity,
+    &'a mut FootstepController,
+    &'a GlobalTransform,
+    &'a LinearVelocity,
+    &'a GroundDetection,
+    &'a CharacterMovementState,
+    Option<&'a Inventory>,
+    Option<&'a Equipment>,
+);
 
 pub fn update_footsteps(
     time: Res<Time>,
     spatial_query: SpatialQuery,
-    mut query: Query<(
-        Entity,
-        &mut FootstepController,
-        &GlobalTransform,
-        &LinearVelocity,
-        &GroundDetection,
-        &CharacterMovementState,
-    )>,
+    noise_settings: Res<FootstepNoiseSettings>,
+    mut query: Query<FootstepWalker>,
     surface_query: Query<&FootstepSurface>,
     mut event_queue: ResMut<FootstepEventQueue>,
 ) {
     let dt = time.delta_secs();
 
-    for (entity, mut footstep, transform, velocity, ground, movement) in query.iter_mut() {
+    for (entity, mut footstep, transform, velocity, ground, movement, inventory, equipment) in query.iter_mut() {
         if !footstep.is_enabled || !ground.is_grounded {
             footstep.accumulated_distance = 0.0;
             continue;
@@ -69,16 +77,28 @@ pub fn update_footsteps(
             let mut rng = rand::rng();
             let volume = rng.random_range(footstep.volume_range.0..=footstep.volume_range.1);
 
+            // Louder when moving fast, on hard surfaces and carrying a lot
+            let carried_weight = inventory.map_or(0.0, |inventory| inventory.current_weight)
+                + equipment.map_or(0.0, |equipment| {
+                    [&equipment.main_hand, &equipment.armor].into_iter().flatten().map(|item| item.weight).sum()
+                });
+            let noise_radius = noise_settings.noise_radius(
+                footstep.noise_radius,
+                FootstepGait::from_movement(movement),
+                &surface_id,
+                carried_weight,
+            );
+
             // Toggle foot
             footstep.last_foot_left = !footstep.last_foot_left;
 
-            event_queue.0.push(FootstepEvent {
+            event_queue.send(FootstepEvent {
                 entity,
                 surface_id,
                 position: hit_pos,
                 normal: hit_normal,
                 volume,
-                noise_radius: footstep.noise_radius,
+                noise_radius,
                 is_left: footstep.last_foot_left,
             });
         }
@@ -86,14 +106,14 @@ pub fn update_footsteps(
 }
 
 pub fn handle_footstep_audio(
-    mut event_queue: ResMut<FootstepEventQueue>,
+    mut event_queue: QueueReader<FootstepEvent>,
     assets: Res<FootstepAssets>,
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     settings: Res<FootstepDecalSettings>,
 ) {
-    for event in event_queue.0.drain(..) {
+    for event in event_queue.read().cloned() {
         let sound_pool = assets.surface_sounds.get(&event.surface_id)
             .or_else(|| assets.surface_sounds.get(&assets.default_surface_id));
 
@@ -142,9 +162,25 @@ pub fn handle_footstep_audio(
                 Name::new("FootstepDecal"),
             ));
         }
+    }
+}
 
-        // Note: Noise signal for AI would be sent here as well
-        // apply_damage::send_noise_signal(event.noise_radius, event.position, ...)
+/// Sends each footstep to AI hearing; AI don't investigate each other's steps
+pub fn publish_footstep_noise(
+    settings: Res<FootstepNoiseSettings>,
+    mut footsteps: QueueReader<FootstepEvent>,
+    mut noise_queue: ResMut<NoiseEventQueue>,
+    ai_query: Query<(), With<AiController>>,
+) {
+    for event in footsteps.read() {
+        if !settings.enabled || event.noise_radius <= 0.0 || ai_query.contains(event.entity) {
+            continue;
+        }
+        noise_queue.send(NoiseEvent {
+            position: event.position,
+            volume: settings.loudness(event.noise_radius),
+            source: event.entity,
+        });
     }
 }
 
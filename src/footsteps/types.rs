@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
+use crate::character::CharacterMovementState;
+use crate::utils::EventQueue;
 
 #[derive(Component, Debug, Reflect, Clone)]
 #[reflect(Component)]
@@ -13,7 +15,8 @@ pub struct FootstepController {
     pub accumulated_distance: f32,
     /// Audio volume range (min, max)
     pub volume_range: (f32, f32),
-    /// Radius of the noise signal for AI detection
+    /// Radius of the noise signal for AI detection, walking on a plain surface
+    /// unencumbered; `FootstepNoiseSettings` scales it for each step
     pub noise_radius: f32,
     /// Layer mask for ground detection raycast
     pub floor_mask: u32,
@@ -52,6 +55,102 @@ pub struct FootstepAssets {
     pub default_surface_id: String,
 }
 
+/// How a character is moving when a foot lands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum FootstepGait {
+    Sneak,
+    #[default]
+    Walk,
+    Run,
+    Sprint,
+}
+
+impl FootstepGait {
+    pub fn from_movement(movement: &CharacterMovementState) -> Self {
+        if movement.is_crouching {
+            Self::Sneak
+        } else if movement.is_sprinting {
+            Self::Sprint
+        } else if movement.is_running {
+            Self::Run
+        } else {
+            Self::Walk
+        }
+    }
+}
+
+/// How loud footsteps are, for AI hearing and the stealth sound level
+#[derive(Resource, Debug, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct FootstepNoiseSettings {
+    pub enabled: bool,
+    pub sneak_multiplier: f32,
+    pub walk_multiplier: f32,
+    pub run_multiplier: f32,
+    pub sprint_multiplier: f32,
+    /// Per `FootstepSurface::surface_id`; unlisted surfaces count as 1
+    pub surface_multipliers: HashMap<String, f32>,
+    /// Extra radius fraction per kilogram carried or equipped
+    pub weight_multiplier_per_kg: f32,
+    pub max_weight_multiplier: f32,
+    /// Noise radius AI with this hearing range hear a step from, at full
+    /// stealth sound level. Noise events carry `radius / reference_hearing_range`
+    pub reference_hearing_range: f32,
+}
+
+impl Default for FootstepNoiseSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sneak_multiplier: 0.3,
+            walk_multiplier: 1.0,
+            run_multiplier: 1.6,
+            sprint_multiplier: 2.4,
+            surface_multipliers: [
+                ("Metal", 1.5),
+                ("Water", 1.4),
+                ("Gravel", 1.3),
+                ("Ladder", 1.2),
+                ("Wood", 1.15),
+                ("Concrete", 1.0),
+                ("Sand", 0.8),
+                ("Grass", 0.7),
+                ("Snow", 0.6),
+                ("Carpet", 0.5),
+            ]
+            .into_iter()
+            .map(|(surface, multiplier)| (surface.to_string(), multiplier))
+            .collect(),
+            weight_multiplier_per_kg: 0.01,
+            max_weight_multiplier: 1.75,
+            reference_hearing_range: 15.0,
+        }
+    }
+}
+
+impl FootstepNoiseSettings {
+    pub fn gait_multiplier(&self, gait: FootstepGait) -> f32 {
+        match gait {
+            FootstepGait::Sneak => self.sneak_multiplier,
+            FootstepGait::Walk => self.walk_multiplier,
+            FootstepGait::Run => self.run_multiplier,
+            FootstepGait::Sprint => self.sprint_multiplier,
+        }
+    }
+
+    /// Noise radius of a step, from the controller's base radius
+    pub fn noise_radius(&self, base_radius: f32, gait: FootstepGait, surface_id: &str, carried_weight: f32) -> f32 {
+        let surface = self.surface_multipliers.get(surface_id).copied().unwrap_or(1.0);
+        let weight = (1.0 + carried_weight.max(0.0) * self.weight_multiplier_per_kg).min(self.max_weight_multiplier);
+        base_radius * self.gait_multiplier(gait) * surface * weight
+    }
+
+    /// Loudness of a step (0-1 and up), as AI noise volume and stealth sound level
+    pub fn loudness(&self, noise_radius: f32) -> f32 {
+        noise_radius / self.reference_hearing_range.max(0.01)
+    }
+}
+
 #[derive(Event, Debug, Clone)]
 pub struct FootstepEvent {
     pub entity: Entity,
@@ -63,8 +162,7 @@ pub struct FootstepEvent {
     pub is_left: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct FootstepEventQueue(pub Vec<FootstepEvent>);
+pub type FootstepEventQueue = EventQueue<FootstepEvent>;
 
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
@@ -93,3 +191,23 @@ impl Default for FootstepDecalSettings {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_footstep_noise_radius() {
+        use crate::footsteps::{FootstepGait, FootstepNoiseSettings};
+
+        let settings = FootstepNoiseSettings::default();
+        let walk = settings.noise_radius(5.0, FootstepGait::Walk, "Concrete", 0.0);
+        assert_eq!(walk, 5.0);
+        assert!(settings.noise_radius(5.0, FootstepGait::Sneak, "Concrete", 0.0) < walk);
+        assert!(settings.noise_radius(5.0, FootstepGait::Sprint, "Metal", 0.0) > settings.noise_radius(5.0, FootstepGait::Sprint, "Carpet", 0.0));
+        // Carried weight caps at the max multiplier
+        assert_eq!(settings.noise_radius(5.0, FootstepGait::Walk, "Unknown", 1000.0), 5.0 * settings.max_weight_multiplier);
+        assert!((settings.loudness(settings.reference_hearing_range) - 1.0).abs() < 1e-6);
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use bevy::app::App;
+use crate::utils::EventQueuePlugin;
 
 pub mod types;
 pub mod systems;
@@ -10,16 +11,18 @@ impl Plugin for GameManagerPlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<types::GameState>()
             .register_type::<types::GameManagerSettings>()
+            .register_type::<types::FeatureToggles>()
             .register_type::<types::PrefabRegistry>()
             .register_type::<types::PlayerManager>()
             .register_type::<types::CursorManagerSettings>()
             .register_type::<types::CursorState>()
             .init_resource::<types::GameManagerSettings>()
+            .init_resource::<types::FeatureToggles>()
             .init_resource::<types::PrefabRegistry>()
             .init_resource::<types::PlayerManager>()
             .init_resource::<types::CursorManagerSettings>()
             .init_resource::<types::CursorState>()
-            .init_resource::<types::SwitchPlayerQueue>()
+            .add_plugins(EventQueuePlugin::<types::SwitchPlayerEvent>::default())
             .add_systems(Update, (
                 systems::update_play_time,
                 systems::toggle_pause,
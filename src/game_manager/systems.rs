@@ -6,6 +6,7 @@ use crate::camera::types::CameraController;
 use crate::game_manager::types::{GameState, CursorState};
 use crate::input::InputState;
 use crate::inventory::InventoryUIRoot;
+use crate::utils::QueueReader;
 
 pub fn update_play_time(
     time: Res<Time>,
@@ -17,12 +18,13 @@ pub fn update_play_time(
     }
 }
 
+/// Pausing goes through the input contexts, so it works over exclusive layers like the map
 pub fn toggle_pause(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: Res<InputState>,
     current_state: Res<State<GameState>>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if keyboard.just_pressed(KeyCode::Escape) {
+    if input.pause_pressed {
         match current_state.get() {
             GameState::Playing => next_state.set(GameState::Paused),
             GameState::Paused => next_state.set(GameState::Playing),
@@ -39,7 +41,7 @@ pub fn switch_player_input(
     if keyboard.just_pressed(KeyCode::Tab) {
         if !player_manager.players.is_empty() {
             let next_index = (player_manager.current_player_index + 1) % player_manager.players.len();
-            switch_queue.0.push(SwitchPlayerEvent {
+            switch_queue.send(SwitchPlayerEvent {
                 target_index: Some(next_index),
                 target_entity: None,
             });
@@ -50,11 +52,11 @@ pub fn switch_player_input(
 pub fn handle_switch_player(
     mut commands: Commands,
     mut player_manager: ResMut<PlayerManager>,
-    mut switch_queue: ResMut<SwitchPlayerQueue>,
+    mut switch_queue: QueueReader<SwitchPlayerEvent>,
     mut camera_query: Query<&mut CameraController>,
     player_query: Query<Entity, With<Player>>,
 ) {
-    for event in switch_queue.0.drain(..) {
+    for event in switch_queue.read().copied() {
         if player_manager.players.is_empty() {
             continue;
         }
@@ -104,7 +106,8 @@ pub fn handle_switch_player(
 }
 
 pub fn handle_cursor_state(
-    mut windows: Query<&mut Window>,
+    mut commands: Commands,
+    mut windows: Query<(Entity, &mut Window)>,
     settings: Res<CursorManagerSettings>,
     state: Res<State<GameState>>,
     inventory_query: Query<&Visibility, With<InventoryUIRoot>>,
@@ -118,7 +121,7 @@ pub fn handle_cursor_state(
     let show_cursor = (paused && settings.show_cursor_when_paused)
         || (inventory_open && settings.show_cursor_when_inventory_open);
 
-    for mut window in windows.iter_mut() {
+    for (window_entity, mut window) in windows.iter_mut() {
         let mut visible = if show_cursor {
             true
         } else if settings.lock_in_game {
@@ -141,12 +144,15 @@ pub fn handle_cursor_state(
         if let Some(override_grab) = cursor_state.grab_mode_override {
             grab_mode = override_grab;
         }
-        // TODO: Fix Cursor API for Bevy 0.18
-        /*
-        if let Some(icon) = cursor_state.icon_override {
-            window.cursor.icon = icon;
+        // The cursor icon lives in its own component on the window entity
+        if cursor_state.is_changed() {
+            commands
+                .entity(window_entity)
+                .insert(cursor_state.icon_override.clone().unwrap_or_default());
         }
 
+        // TODO: Fix Cursor API for Bevy 0.18
+        /*
         window.cursor.visible = visible;
         window.cursor.grab_mode = grab_mode;
         */
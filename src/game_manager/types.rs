@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use bevy::window::{CursorGrabMode, CursorIcon};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use crate::utils::EventQueue;
 
 #[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default, Reflect)]
 pub enum GameState {
@@ -21,6 +22,10 @@ pub struct GameManagerSettings {
     pub save_folder: String,
     pub save_file_extension: String,
     pub play_time: f32,
+    /// Roguelike mode: dying ends the run instead of respawning
+    pub permadeath: bool,
+    /// Delete every save slot when a permadeath run ends
+    pub permadeath_wipe_saves: bool,
 }
 
 impl Default for GameManagerSettings {
@@ -32,6 +37,31 @@ impl Default for GameManagerSettings {
             save_folder: "saves".to_string(),
             save_file_extension: ".json".to_string(),
             play_time: 0.0,
+            permadeath: false,
+            permadeath_wipe_saves: false,
+        }
+    }
+}
+
+/// Runtime switches for whole gameplay behaviors (cheats, debug builds).
+/// Everything is on by default.
+#[derive(Resource, Debug, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct FeatureToggles {
+    /// Falls hurt (`CharacterController::fall_damage_enabled` still applies per character)
+    pub fall_damage: bool,
+    /// Stamina drains; when off, stamina stays full and costs are free
+    pub stamina: bool,
+    /// Weapons sway with the look input
+    pub weapon_sway: bool,
+}
+
+impl Default for FeatureToggles {
+    fn default() -> Self {
+        Self {
+            fall_damage: true,
+            stamina: true,
+            weapon_sway: true,
         }
     }
 }
@@ -109,8 +139,7 @@ pub struct SwitchPlayerEvent {
     pub target_entity: Option<Entity>,
 }
 
-#[derive(Resource, Default)]
-pub struct SwitchPlayerQueue(pub Vec<SwitchPlayerEvent>);
+pub type SwitchPlayerQueue = EventQueue<SwitchPlayerEvent>;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SaveData {
@@ -0,0 +1,61 @@
+//! Gore
+//!
+//! Optional blood and dismemberment. A limb collider with a [`Severable`]
+//! next to its `DamageReceiver` is cut off by a single hit that crosses one of
+//! its [`SeveranceThreshold`]s (damage type and amount, defaulting per
+//! [`BodyPart`]). The part swaps to its stump mesh (or hides), a cap covers the
+//! wound and the limb is thrown as a physics gib. Hurt characters with
+//! [`Bleeds`] leave blood decals on the ground, capped by a budget that
+//! recycles the oldest.
+//!
+//! [`GoreSettings::policy`] picks what is shown, so one build can ship
+//! age-appropriate configurations:
+//!
+//! ```rust,ignore
+//! commands.spawn((
+//!     DamageReceiver { health_root: character, ..default() },
+//!     Severable::new(BodyPart::LeftArm).with_threshold(DamageType::Ranged, 90.0),
+//! ));
+//!
+//! // Blood only, no severed limbs
+//! gore_settings.policy = GorePolicy::Reduced;
+//! ```
+
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use crate::simulation::SimulationAppExt;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    GorePolicy, BodyPart, SeveranceThreshold, Severable, Severed, Bleeds, GoreSettings, BloodDecalBudget,
+    BloodDecal, GoreGib, GoreEvent, GoreEventQueue,
+};
+
+pub struct GorePlugin;
+
+impl Plugin for GorePlugin {
+    fn build(&self, app: &mut App) {
+        let simulation = app.simulation_schedule();
+
+        app
+            .init_resource::<GoreSettings>()
+            .init_resource::<BloodDecalBudget>()
+            .add_plugins(EventQueuePlugin::<GoreEvent>::default())
+            .register_type::<Severable>()
+            .register_type::<Severed>()
+            .register_type::<Bleeds>()
+            .register_type::<GoreSettings>()
+            .register_type::<BloodDecal>()
+            .register_type::<GoreGib>()
+            .add_systems(simulation, sever_body_parts.in_set(crate::combat::CombatSet::Aftermath))
+            .add_systems(simulation, (
+                spawn_severed_limbs,
+                spawn_blood_decals,
+                update_gore_lifetimes,
+            ).chain().after(sever_body_parts).in_set(crate::combat::CombatSet::Aftermath).in_set(crate::headless::PresentationSet));
+    }
+}
@@ -0,0 +1,228 @@
+use avian3d::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use crate::combat::{DamageReceiver, DamageResultEvent, Health};
+use crate::utils::QueueReader;
+use super::types::*;
+
+/// System to sever parts hit hard enough, under `GorePolicy::Full`
+pub fn sever_body_parts(
+    mut commands: Commands,
+    settings: Res<GoreSettings>,
+    mut results: QueueReader<DamageResultEvent>,
+    mut events: ResMut<GoreEventQueue>,
+    part_query: Query<(&Severable, &GlobalTransform, &DamageReceiver), Without<Severed>>,
+    health_query: Query<&Health>,
+) {
+    if !settings.policy.allows_severance() {
+        results.clear();
+        return;
+    }
+
+    let mut severed: Vec<Entity> = Vec::new();
+    for result in results.read() {
+        let Some(part_entity) = result.part else { continue };
+        if result.is_block || severed.contains(&part_entity) {
+            continue;
+        }
+        let Ok((severable, transform, receiver)) = part_query.get(part_entity) else { continue };
+        if !severable.is_severed_by(result.damage_type, result.final_amount) {
+            continue;
+        }
+        if severable.lethal_only && !health_query.get(receiver.health_root).is_ok_and(|health| health.is_dead) {
+            continue;
+        }
+
+        severed.push(part_entity);
+        commands.entity(part_entity).insert(Severed { damage_type: result.damage_type });
+        events.send(GoreEvent::Severed {
+            root: receiver.health_root,
+            part_entity,
+            part: severable.part,
+            damage_type: result.damage_type,
+            position: transform.translation(),
+        });
+    }
+}
+
+/// Severed part data a limb, stump and wound cap are made from
+type SeveredPart<'a> = (
+    &'a Severable,
+    &'a GlobalTransform,
+    &'a Transform,
+    Option<&'a ChildOf>,
+    Option<&'a MeshMaterial3d<StandardMaterial>>,
+);
+
+/// Mesh and material assets gore creates
+#[derive(SystemParam)]
+pub struct GoreMeshes<'w> {
+    pub meshes: ResMut<'w, Assets<Mesh>>,
+    pub materials: ResMut<'w, Assets<StandardMaterial>>,
+}
+
+/// System to swap severed parts for their stump, cap the wound and throw the limb
+pub fn spawn_severed_limbs(
+    mut commands: Commands,
+    mut events: QueueReader<GoreEvent>,
+    asset_server: Res<AssetServer>,
+    gore_meshes: GoreMeshes,
+    settings: Res<GoreSettings>,
+    part_query: Query<SeveredPart>,
+    root_query: Query<&GlobalTransform>,
+) {
+    let GoreMeshes { mut meshes, mut materials } = gore_meshes;
+    for event in events.read() {
+        let GoreEvent::Severed { root, part_entity, position, .. } = event;
+        let Ok((severable, transform, local, parent, material)) = part_query.get(*part_entity) else { continue };
+
+        let material = material.map(|material| material.0.clone()).unwrap_or_else(|| {
+            materials.add(StandardMaterial { base_color: Color::srgb(0.8, 0.65, 0.55), ..default() })
+        });
+        let wound = materials.add(StandardMaterial { base_color: settings.blood_color, ..default() });
+
+        // Thrown away from the body's center
+        let away = root_query
+            .get(*root)
+            .map(|root| (*position - root.translation()).normalize_or_zero())
+            .unwrap_or(Vec3::ZERO);
+        let limb_mesh = if severable.limb_mesh.is_empty() {
+            meshes.add(Capsule3d::new(0.08, 0.5))
+        } else {
+            asset_server.load(severable.limb_mesh.as_str())
+        };
+        commands.spawn((
+            Mesh3d(limb_mesh),
+            MeshMaterial3d(material),
+            Transform::from_translation(*position).with_rotation(transform.rotation()),
+            Visibility::default(),
+            RigidBody::Dynamic,
+            Collider::capsule(0.08, 0.5),
+            LinearVelocity((away + Vec3::Y) * severable.limb_impulse),
+            GoreGib { lifetime: severable.limb_lifetime },
+            Name::new("SeveredLimb"),
+        ));
+
+        if severable.stump_mesh.is_empty() {
+            commands.entity(*part_entity).insert(Visibility::Hidden);
+        } else {
+            commands.entity(*part_entity).insert(Mesh3d(asset_server.load(severable.stump_mesh.as_str())));
+        }
+
+        // The cap sits where the part was, on its parent so it stays visible
+        let cap_mesh = if severable.cap_mesh.is_empty() {
+            meshes.add(Cylinder::new(0.09, 0.02))
+        } else {
+            asset_server.load(severable.cap_mesh.as_str())
+        };
+        let cap = commands.spawn((
+            Mesh3d(cap_mesh),
+            MeshMaterial3d(wound),
+            *local,
+            Visibility::default(),
+            Name::new("WoundCap"),
+        )).id();
+        if let Some(parent) = parent {
+            commands.entity(cap).insert(ChildOf(parent.parent()));
+        } else {
+            commands.entity(cap).insert(Transform::from_translation(*position));
+        }
+    }
+}
+
+/// System to leave blood under bleeding characters and severable parts, within the policy's budget
+pub fn spawn_blood_decals(
+    mut commands: Commands,
+    settings: Res<GoreSettings>,
+    mut budget: ResMut<BloodDecalBudget>,
+    mut results: QueueReader<DamageResultEvent>,
+    gore_meshes: GoreMeshes,
+    spatial_query: SpatialQuery,
+    bleed_query: Query<(&GlobalTransform, Option<&Bleeds>, Has<Severable>)>,
+) {
+    if !settings.policy.allows_blood() {
+        results.clear();
+        return;
+    }
+    let GoreMeshes { mut meshes, mut materials } = gore_meshes;
+
+    for result in results.read() {
+        if result.is_block || result.final_amount < settings.min_blood_damage {
+            continue;
+        }
+        let hit = result.part.unwrap_or(result.target);
+        let Ok((transform, bleeds, severable)) = bleed_query.get(hit).or_else(|_| bleed_query.get(result.target)) else {
+            continue;
+        };
+        let root_bleeds = bleed_query.get(result.target).ok().and_then(|(_, bleeds, _)| bleeds);
+        let bleeds = bleeds.or(root_bleeds);
+        if bleeds.is_none() && !severable {
+            continue;
+        }
+
+        let origin = transform.translation();
+        let Some(ground) = spatial_query.cast_ray(
+            origin,
+            Dir3::NEG_Y,
+            3.0,
+            true,
+            &SpatialQueryFilter::from_excluded_entities([result.target, hit]),
+        ) else {
+            continue;
+        };
+
+        let color = bleeds.and_then(|bleeds| bleeds.color).unwrap_or(settings.blood_color);
+        let size = settings.blood_decal_size * (0.6 + (result.final_amount / 100.0).min(0.8));
+        let decal = commands.spawn((
+            Mesh3d(meshes.add(Circle::new(size * 0.5))),
+            MeshMaterial3d(materials.add(StandardMaterial {
+                base_color: color,
+                alpha_mode: AlphaMode::Blend,
+                unlit: true,
+                ..default()
+            })),
+            Transform::from_translation(origin - Vec3::Y * (ground.distance - 0.01))
+                .with_rotation(Quat::from_rotation_arc(Vec3::Z, ground.normal)),
+            Visibility::default(),
+            BloodDecal { lifetime: settings.blood_decal_lifetime },
+            Name::new("BloodDecal"),
+        )).id();
+
+        for oldest in budget.push(decal, settings.decal_budget()) {
+            commands.entity(oldest).despawn();
+        }
+    }
+}
+
+/// System to expire blood and limbs, and to clear blood the policy no longer allows
+pub fn update_gore_lifetimes(
+    time: Res<Time>,
+    mut commands: Commands,
+    settings: Res<GoreSettings>,
+    mut budget: ResMut<BloodDecalBudget>,
+    mut decal_query: Query<(Entity, &mut BloodDecal)>,
+    mut gib_query: Query<(Entity, &mut GoreGib)>,
+) {
+    let dt = time.delta_secs();
+
+    if settings.is_changed() {
+        for decal in budget.trim(settings.decal_budget()) {
+            commands.entity(decal).despawn();
+        }
+    }
+
+    for (entity, mut decal) in decal_query.iter_mut() {
+        decal.lifetime -= dt;
+        if decal.lifetime <= 0.0 {
+            budget.remove(entity);
+            commands.entity(entity).despawn();
+        }
+    }
+
+    for (entity, mut gib) in gib_query.iter_mut() {
+        gib.lifetime -= dt;
+        if gib.lifetime <= 0.0 || !settings.policy.allows_severance() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
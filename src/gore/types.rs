@@ -0,0 +1,266 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use crate::combat::DamageType;
+use crate::utils::EventQueue;
+
+/// How much gore the game shows, so one build can ship age-appropriate settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum GorePolicy {
+    /// No blood and no severed parts
+    Disabled,
+    /// Blood decals within `GoreSettings::reduced_decal_budget`, no severed parts
+    Reduced,
+    #[default]
+    Full,
+}
+
+impl GorePolicy {
+    pub fn allows_blood(self) -> bool {
+        self != GorePolicy::Disabled
+    }
+
+    pub fn allows_severance(self) -> bool {
+        self == GorePolicy::Full
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum BodyPart {
+    Head,
+    Torso,
+    LeftArm,
+    RightArm,
+    LeftLeg,
+    RightLeg,
+}
+
+impl BodyPart {
+    /// Thresholds `Severable::new` starts from; the torso can't be severed
+    pub fn default_thresholds(self) -> Vec<SeveranceThreshold> {
+        match self {
+            BodyPart::Head => vec![
+                SeveranceThreshold::new(DamageType::Melee, 40.0),
+                SeveranceThreshold::new(DamageType::Ranged, 80.0),
+                SeveranceThreshold::new(DamageType::Explosion, 60.0),
+            ],
+            BodyPart::Torso => Vec::new(),
+            BodyPart::LeftArm | BodyPart::RightArm | BodyPart::LeftLeg | BodyPart::RightLeg => vec![
+                SeveranceThreshold::new(DamageType::Melee, 30.0),
+                SeveranceThreshold::new(DamageType::Explosion, 40.0),
+            ],
+        }
+    }
+}
+
+/// A single hit of `damage_type` dealing at least `min_damage` severs the part
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct SeveranceThreshold {
+    pub damage_type: DamageType,
+    pub min_damage: f32,
+}
+
+impl SeveranceThreshold {
+    pub fn new(damage_type: DamageType, min_damage: f32) -> Self {
+        Self { damage_type, min_damage }
+    }
+}
+
+/// Put on a limb collider next to its `DamageReceiver` to let hits sever it
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Severable {
+    pub part: BodyPart,
+    pub thresholds: Vec<SeveranceThreshold>,
+    /// Only the hit that kills the character severs the part
+    pub lethal_only: bool,
+    /// Mesh of the detached limb, empty for a plain capsule
+    pub limb_mesh: String,
+    /// Mesh swapped onto the part once severed, empty to hide the part
+    pub stump_mesh: String,
+    /// Mesh capping the wound, empty for a flat disc
+    pub cap_mesh: String,
+    pub limb_impulse: f32,
+    /// Seconds the detached limb stays in the world
+    pub limb_lifetime: f32,
+}
+
+impl Default for Severable {
+    fn default() -> Self {
+        Self::new(BodyPart::LeftArm)
+    }
+}
+
+impl Severable {
+    pub fn new(part: BodyPart) -> Self {
+        Self {
+            part,
+            thresholds: part.default_thresholds(),
+            lethal_only: part == BodyPart::Head,
+            limb_mesh: String::new(),
+            stump_mesh: String::new(),
+            cap_mesh: String::new(),
+            limb_impulse: 4.0,
+            limb_lifetime: 20.0,
+        }
+    }
+
+    pub fn with_threshold(mut self, damage_type: DamageType, min_damage: f32) -> Self {
+        self.thresholds.retain(|threshold| threshold.damage_type != damage_type);
+        self.thresholds.push(SeveranceThreshold::new(damage_type, min_damage));
+        self
+    }
+
+    /// Whether one hit of `amount` and `damage_type` is enough to sever the part
+    pub fn is_severed_by(&self, damage_type: DamageType, amount: f32) -> bool {
+        self.thresholds
+            .iter()
+            .any(|threshold| threshold.damage_type == damage_type && amount >= threshold.min_damage)
+    }
+}
+
+/// Added to a part once it has been severed
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Severed {
+    pub damage_type: DamageType,
+}
+
+impl Default for Severed {
+    fn default() -> Self {
+        Self { damage_type: DamageType::Melee }
+    }
+}
+
+/// Characters and props that leave blood decals when hurt. Severable parts bleed without it.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct Bleeds {
+    /// Overrides `GoreSettings::blood_color`
+    pub color: Option<Color>,
+}
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct GoreSettings {
+    pub policy: GorePolicy,
+    /// Most blood decals alive at once under `GorePolicy::Full`; the oldest make room
+    pub blood_decal_budget: usize,
+    /// Budget under `GorePolicy::Reduced`
+    pub reduced_decal_budget: usize,
+    pub blood_decal_lifetime: f32,
+    pub blood_decal_size: f32,
+    pub blood_color: Color,
+    /// Hits dealing less leave no blood
+    pub min_blood_damage: f32,
+}
+
+impl Default for GoreSettings {
+    fn default() -> Self {
+        Self {
+            policy: GorePolicy::Full,
+            blood_decal_budget: 64,
+            reduced_decal_budget: 16,
+            blood_decal_lifetime: 30.0,
+            blood_decal_size: 0.6,
+            blood_color: Color::srgba(0.4, 0.02, 0.02, 0.9),
+            min_blood_damage: 5.0,
+        }
+    }
+}
+
+impl GoreSettings {
+    /// Blood decals allowed at once under the current policy
+    pub fn decal_budget(&self) -> usize {
+        match self.policy {
+            GorePolicy::Disabled => 0,
+            GorePolicy::Reduced => self.reduced_decal_budget.min(self.blood_decal_budget),
+            GorePolicy::Full => self.blood_decal_budget,
+        }
+    }
+}
+
+/// Blood decals alive, oldest first
+#[derive(Resource, Debug, Default)]
+pub struct BloodDecalBudget {
+    pub decals: VecDeque<Entity>,
+}
+
+impl BloodDecalBudget {
+    /// Records a new decal and returns the oldest ones over `budget`, to despawn
+    pub fn push(&mut self, decal: Entity, budget: usize) -> Vec<Entity> {
+        self.decals.push_back(decal);
+        self.trim(budget)
+    }
+
+    pub fn trim(&mut self, budget: usize) -> Vec<Entity> {
+        let excess = self.decals.len().saturating_sub(budget);
+        self.decals.drain(..excess).collect()
+    }
+
+    pub fn remove(&mut self, decal: Entity) {
+        self.decals.retain(|entity| *entity != decal);
+    }
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BloodDecal {
+    pub lifetime: f32,
+}
+
+/// Detached limb, despawned after its lifetime
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct GoreGib {
+    pub lifetime: f32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GoreEvent {
+    Severed {
+        /// Character the part belonged to
+        root: Entity,
+        part_entity: Entity,
+        part: BodyPart,
+        damage_type: DamageType,
+        position: Vec3,
+    },
+}
+
+pub type GoreEventQueue = EventQueue<GoreEvent>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::DamageType;
+
+    #[test]
+    fn test_gore_severance_and_decal_budget() {
+        let arm = Severable::new(BodyPart::LeftArm);
+        assert!(arm.is_severed_by(DamageType::Melee, 30.0));
+        assert!(!arm.is_severed_by(DamageType::Melee, 29.0));
+        assert!(!arm.is_severed_by(DamageType::Ranged, 500.0));
+        let arm = arm.with_threshold(DamageType::Melee, 50.0);
+        assert!(!arm.is_severed_by(DamageType::Melee, 30.0));
+        assert!(Severable::new(BodyPart::Head).lethal_only);
+        assert!(BodyPart::Torso.default_thresholds().is_empty());
+
+        let mut settings = GoreSettings { blood_decal_budget: 3, reduced_decal_budget: 1, ..default() };
+        assert_eq!(settings.decal_budget(), 3);
+        settings.policy = GorePolicy::Reduced;
+        assert!(settings.policy.allows_blood() && !settings.policy.allows_severance());
+        assert_eq!(settings.decal_budget(), 1);
+        settings.policy = GorePolicy::Disabled;
+        assert_eq!(settings.decal_budget(), 0);
+
+        let mut budget = BloodDecalBudget::default();
+        let mut world = World::new();
+        let decals: Vec<Entity> = (0..4).map(|_| world.spawn_empty().id()).collect();
+        for decal in &decals[..3] {
+            assert!(budget.push(*decal, 3).is_empty());
+        }
+        // The oldest decal makes room
+        assert_eq!(budget.push(decals[3], 3), vec![decals[0]]);
+        assert_eq!(budget.trim(1), vec![decals[1], decals[2]]);
+    }
+}
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::EventQueuePlugin;
 
 pub mod types;
 pub mod systems;
@@ -10,7 +11,7 @@ pub struct GrabPlugin;
 impl Plugin for GrabPlugin {
     fn build(&self, app: &mut App) {
         app
-            .init_resource::<GrabEventQueue>()
+            .add_plugins(EventQueuePlugin::<GrabEvent>::default())
             .register_type::<Grabbable>()
             .register_type::<Grabber>()
             .register_type::<GrabObjectParent>()
@@ -29,7 +30,7 @@ impl Plugin for GrabPlugin {
             .register_type::<GrabPowerFx>()
             .register_type::<GrabPhysicalObjectSettings>()
             .register_type::<PlacementSlot>()
-            .init_resource::<PlacementEventQueue>()
+            .add_plugins(EventQueuePlugin::<PlacementEvent>::default())
             .add_systems(Update, (
                 systems::handle_grab_input,
                 systems::process_grab_events,
@@ -2,12 +2,16 @@ use bevy::prelude::*;
 
 use avian3d::prelude::*;
 use crate::input::{InputState, InputAction};
-use crate::combat::{DamageEventQueue, DamageEvent, DamageType, DamageZone, Blocking, AreaEffect};
+use crate::combat::{DamageEventQueue, DamageEvent, DamageType, DamageZone, Blocking, AreaEffect, SpecialMoveState};
 use crate::stats::stats_system::StatsSystem;
 use crate::stats::types::DerivedStat;
 use crate::abilities::types::{SetAbilityEnabledEventQueue, SetAbilityEnabledEvent};
+use crate::highlight::{HighlightCommandQueue, HighlightKind};
+use crate::interaction::CurrentInteractable;
 use super::types::*;
 use bevy::audio::{AudioSource, PlaybackSettings};
+use crate::utils::QueueReader;
+use crate::game_manager::types::FeatureToggles;
 
 /// System to handle grab/drop input.
 pub fn handle_grab_input(
@@ -19,7 +23,7 @@ pub fn handle_grab_input(
     for (entity, mut grabber) in grabber_query.iter_mut() {
         if let Some(held) = grabber.held_object {
             if input.interact_pressed {
-                 event_queue.0.push(GrabEvent::Drop(entity, held));
+                 event_queue.send(GrabEvent::Drop(entity, held));
             }
         }
     }
@@ -28,7 +32,7 @@ pub fn handle_grab_input(
         if !powerer.held_objects.is_empty() {
             if input.interact_pressed {
                 for held in powerer.held_objects.clone() {
-                    event_queue.0.push(GrabEvent::Drop(entity, held));
+                    event_queue.send(GrabEvent::Drop(entity, held));
                 }
                 powerer.held_objects.clear();
             }
@@ -39,7 +43,7 @@ pub fn handle_grab_input(
 /// System to process Grab events.
 pub fn process_grab_events(
     mut commands: Commands,
-    mut event_queue: ResMut<GrabEventQueue>,
+    mut event_queue: QueueReader<GrabEvent>,
     mut grabber_query: Query<&mut Grabber>,
     mut powerer_query: Query<&mut GrabPowerer>,
     mut grabbable_query: Query<(&Grabbable, &mut LinearVelocity, Option<&mut Mass>, Option<&mut Collider>, Option<&mut GravityScale>, (Option<&mut LinearDamping>, Option<&mut AngularDamping>))>,
@@ -49,7 +53,7 @@ pub fn process_grab_events(
     physical_settings_query: Query<&GrabPhysicalObjectSettings>,
     mut ability_queue: ResMut<SetAbilityEnabledEventQueue>,
 ) {
-    let events: Vec<GrabEvent> = event_queue.0.drain(..).collect();
+    let events: Vec<GrabEvent> = event_queue.read().cloned().collect();
 
     for event in events {
         match event {
@@ -143,7 +147,7 @@ pub fn process_grab_events(
                     }
 
                     for ability in &weapon.unlock_abilities {
-                        ability_queue.0.push(SetAbilityEnabledEvent {
+                        ability_queue.send(SetAbilityEnabledEvent {
                             player_entity: grabber_entity,
                             ability_name: ability.clone(),
                             enabled: true,
@@ -193,7 +197,7 @@ pub fn process_grab_events(
 
                 if let Ok(weapon) = weapon_query.get(target_entity) {
                     for ability in &weapon.unlock_abilities {
-                        ability_queue.0.push(SetAbilityEnabledEvent {
+                        ability_queue.send(SetAbilityEnabledEvent {
                             player_entity: grabber_entity,
                             ability_name: ability.clone(),
                             enabled: false,
@@ -237,7 +241,7 @@ pub fn process_grab_events(
 
                 if let Ok(weapon) = weapon_query.get(target_entity) {
                     for ability in &weapon.unlock_abilities {
-                        ability_queue.0.push(SetAbilityEnabledEvent {
+                        ability_queue.send(SetAbilityEnabledEvent {
                             player_entity: grabber_entity,
                             ability_name: ability.clone(),
                             enabled: false,
@@ -318,7 +322,7 @@ pub fn handle_throwing(
             grabber.throw_force = (grabber.throw_force + 10.0).min(grabber.max_throw_force);
         } else if grabber.is_charging_throw {
             let dir = transform.forward();
-            event_queue.0.push(GrabEvent::Throw(entity, held, *dir, grabber.throw_force));
+            event_queue.send(GrabEvent::Throw(entity, held, *dir, grabber.throw_force));
             grabber.is_charging_throw = false;
             grabber.throw_force = 500.0;
         }
@@ -411,7 +415,7 @@ pub fn handle_power_grabbing(
              let center = transform.translation();
              for (target_entity, target_xf) in grabbable_query.iter() {
                  if target_xf.translation().distance(center) < powerer.grab_radius {
-                     event_queue.0.push(GrabEvent::Grab(entity, target_entity));
+                     event_queue.send(GrabEvent::Grab(entity, target_entity));
                  }
              }
         }
@@ -447,25 +451,48 @@ pub fn update_power_held_objects(
     }
 }
 
-/// System to update object outlines.
+/// System to highlight the grabbable object under the crosshair.
 pub fn update_outlines(
-    mut _objects: Query<(&mut OutlineSettings, &Children)>,
-    mut _materials: Query<&mut MeshMaterial3d<StandardMaterial>>,
+    current_interactable: Res<CurrentInteractable>,
+    mut highlights: ResMut<HighlightCommandQueue>,
+    grabber_query: Query<&Grabber>,
+    grabbable_query: Query<Option<&OutlineSettings>, With<Grabbable>>,
+    mut previous: Local<Option<Entity>>,
 ) {
-    // Placeholder for visual outline logic
+    let held = |entity: Entity| grabber_query.iter().any(|grabber| grabber.held_object == Some(entity));
+    let target = current_interactable
+        .entity
+        .filter(|entity| grabbable_query.contains(*entity) && !held(*entity));
+    if *previous == target {
+        return;
+    }
+
+    if let Some(entity) = *previous {
+        highlights.release(entity, HighlightKind::Grabbable);
+    }
+    if let Some(entity) = target {
+        match grabbable_query.get(entity) {
+            Ok(Some(outline)) => highlights.request_with_color(entity, HighlightKind::Grabbable, outline.color),
+            _ => highlights.request(entity, HighlightKind::Grabbable),
+        }
+    }
+    *previous = target;
 }
 
 /// System to handle melee attack input for grabbed objects.
 pub fn handle_grab_melee(
     input: Res<InputState>,
-    mut grabber_query: Query<(Entity, &Grabber, Option<&mut StatsSystem>)>,
+    toggles: Res<FeatureToggles>,
+    mut grabber_query: Query<(Entity, &Grabber, Option<&mut StatsSystem>, Option<&SpecialMoveState>)>,
     mut weapon_query: Query<(&GrabMeleeWeapon, &mut GrabMeleeAttackState, Option<&ImprovisedWeapon>, &GlobalTransform, Option<&ImprovisedWeaponStats>)>,
     spatial_query: SpatialQuery,
 ) {
-    for (grabber_entity, grabber, stats_opt) in grabber_query.iter_mut() {
+    for (grabber_entity, grabber, stats_opt, special) in grabber_query.iter_mut() {
         let Some(held) = grabber.held_object else { continue };
         let Ok((weapon, mut state, _improv, weapon_transform, improvised_stats)) = weapon_query.get_mut(held) else { continue };
-        if !input.attack_pressed || state.cooldown_timer > 0.0 || state.recoil_timer > 0.0 {
+        // Attacks while blocking are shield bashes
+        let attack_pressed = special.map_or(input.attack_pressed, |special| special.combo_attack_input(input.attack_pressed));
+        if !attack_pressed || state.cooldown_timer > 0.0 || state.recoil_timer > 0.0 {
             continue;
         }
 
@@ -477,7 +504,7 @@ pub fn handle_grab_melee(
         };
 
         // Check stamina
-        if let Some(mut stats) = stats_opt {
+        if let Some(mut stats) = stats_opt.filter(|_| toggles.stamina) {
             if let Some(current) = stats.get_derived_stat(DerivedStat::CurrentStamina).copied() {
                 if current < stamina_cost {
                     continue;
@@ -568,7 +595,7 @@ pub fn handle_placement_slots(
             slot.current_object = Some(object_entity);
 
             if slot.use_events {
-                placement_events.0.push(PlacementEvent {
+                placement_events.send(PlacementEvent {
                     slot: slot_entity,
                     placed_object: object_entity,
                 });
@@ -599,7 +626,7 @@ pub fn handle_power_throwing(
 
         let dir = transform.forward();
         let force = grabber.throw_force * power_throw.force_multiplier;
-        event_queue.0.push(GrabEvent::Throw(entity, held, *dir, force));
+        event_queue.send(GrabEvent::Throw(entity, held, *dir, force));
         power_throw.last_throw_time = now;
 
         commands.entity(held).insert(PowerThrown {
@@ -694,7 +721,7 @@ pub fn perform_grab_melee_damage(
             &SpatialQueryFilter::default().with_excluded_entities([weapon_entity]),
         ) {
             if targets.get(hit.entity).is_ok() {
-                damage_queue.0.push(DamageEvent {
+                damage_queue.send(DamageEvent {
                     amount: state.damage,
                     damage_type,
                     source: owner_entity,
@@ -710,7 +737,7 @@ pub fn perform_grab_melee_damage(
 
 pub fn apply_throw_damage_on_collision(
     mut damage_queue: ResMut<DamageEventQueue>,
-    collision_events: EventReader<CollisionStarted>,
+    mut collision_events: MessageReader<CollisionStart>,
     settings_query: Query<&GrabPhysicalObjectSettings>,
     mut commands: Commands,
     power_query: Query<&PowerThrown>,
@@ -719,10 +746,10 @@ pub fn apply_throw_damage_on_collision(
     transform_query: Query<&GlobalTransform>,
 ) {
     for event in collision_events.read() {
-        let (a, b) = (event.entity1, event.entity2);
+        let (a, b) = (event.collider1, event.collider2);
         if let Ok(settings) = settings_query.get(a) {
             if settings.throw_damage > 0.0 {
-                damage_queue.0.push(DamageEvent {
+                damage_queue.send(DamageEvent {
                     amount: settings.throw_damage,
                     damage_type: DamageType::Melee,
                     source: Some(a),
@@ -778,7 +805,7 @@ pub fn apply_throw_damage_on_collision(
         }
         if let Ok(settings) = settings_query.get(b) {
             if settings.throw_damage > 0.0 {
-                damage_queue.0.push(DamageEvent {
+                damage_queue.send(DamageEvent {
                     amount: settings.throw_damage,
                     damage_type: DamageType::Melee,
                     source: Some(b),
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use crate::utils::EventQueue;
 
 /// Component for objects that can be grabbed.
 #[derive(Component, Debug, Clone, Reflect, Serialize, Deserialize)]
@@ -89,8 +90,7 @@ pub enum GrabEvent {
     Throw(Entity, Entity, Vec3, f32), // Grabber, Grabbable, Direction, Force
 }
 
-#[derive(Resource, Default)]
-pub struct GrabEventQueue(pub Vec<GrabEvent>);
+pub type GrabEventQueue = EventQueue<GrabEvent>;
 
 /// Component to redirect grab action to a parent or another entity.
 #[derive(Component, Debug, Clone, Reflect)]
@@ -191,26 +191,8 @@ impl Default for GrabPowerer {
     }
 }
 
-/// Settings for object outlining.
-#[derive(Component, Debug, Clone, Reflect)]
-#[reflect(Component)]
-pub struct OutlineSettings {
-    pub enabled: bool,
-    pub width: f32,
-    pub color: Color,
-    pub active: bool,
-}
-
-impl Default for OutlineSettings {
-    fn default() -> Self {
-        Self {
-            enabled: true,
-            width: 0.05,
-            color: Color::srgba(1.0, 1.0, 0.0, 1.0), // srgba for Yellow
-            active: false,
-        }
-    }
-}
+/// Highlight options, shared with the other highlight users
+pub use crate::highlight::OutlineSettings;
 
 /// Information for a specific melee attack.
 #[derive(Debug, Clone, Reflect, Serialize, Deserialize)]
@@ -287,8 +269,7 @@ pub struct PlacementEvent {
     pub placed_object: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct PlacementEventQueue(pub Vec<PlacementEvent>);
+pub type PlacementEventQueue = EventQueue<PlacementEvent>;
 
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component)]
@@ -0,0 +1,148 @@
+//! Headless mode
+//!
+//! Runs the gameplay systems (AI, combat, physics, inventory, quests...) without
+//! a window, renderer or UI, for dedicated servers and automated simulation tests:
+//!
+//! ```rust,ignore
+//! App::new()
+//!     .add_plugins(MinimalPlugins)
+//!     .add_plugins(GameControllerPlugin::default().headless())
+//!     .run();
+//! ```
+//!
+//! [`HeadlessPlugin`] provides the engine resources that gameplay systems expect
+//! from `DefaultPlugins` (assets, meshes, materials, input, states, gizmos), so
+//! systems that spawn visuals keep working on plain data, and turns off purely
+//! cosmetic effects (decals, particle effects, screen flashes). UI-only subsystems
+//! are skipped by `GameControllerPlugin`, and the UI and material systems of the
+//! other modules (HUD, damage UI, map, inventory, camera transparency...) sit in
+//! [`PresentationSet`], which does not run in headless mode.
+
+use bevy::prelude::*;
+use bevy::gizmos::GizmoPlugin;
+use bevy::state::app::StatesPlugin;
+use crate::combat::{DamageFeedbackSettings, DecalSettings, SliceFxSettings, SurfaceFxSettings};
+use crate::input::HapticsSettings;
+use crate::vfx::VfxSettings;
+
+/// Present while the crate runs without rendering
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub struct HeadlessMode;
+
+/// UI, material and cosmetic systems, skipped in headless mode.
+///
+/// Configured in `Startup`, `Update` and `PostUpdate` (and `FixedUpdate` in
+/// fixed simulation mode). Gameplay state must not
+/// depend on these systems; add your own presentation systems to it to keep
+/// headless runs lean.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PresentationSet;
+
+/// Run condition: false in headless mode
+pub fn presentation_enabled(headless: Option<Res<HeadlessMode>>) -> bool {
+    headless.is_none()
+}
+
+/// Engine prerequisites and settings for running without rendering
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(HeadlessMode)
+            .configure_sets(Startup, PresentationSet.run_if(presentation_enabled))
+            .configure_sets(Update, PresentationSet.run_if(presentation_enabled))
+            .configure_sets(PostUpdate, PresentationSet.run_if(presentation_enabled));
+
+        if !app.is_plugin_added::<AssetPlugin>() {
+            app.add_plugins(AssetPlugin::default());
+        }
+        if !app.is_plugin_added::<TransformPlugin>() {
+            app.add_plugins(TransformPlugin);
+        }
+        if !app.is_plugin_added::<bevy::input::InputPlugin>() {
+            app.add_plugins(bevy::input::InputPlugin);
+        }
+        if !app.is_plugin_added::<StatesPlugin>() {
+            app.add_plugins(StatesPlugin);
+        }
+        if !app.is_plugin_added::<GizmoPlugin>() {
+            app.add_plugins(GizmoPlugin);
+        }
+
+        // Normally registered by the render plugins
+        if !app.world().contains_resource::<Assets<Mesh>>() {
+            app.init_asset::<Mesh>();
+        }
+        if !app.world().contains_resource::<Assets<StandardMaterial>>() {
+            app.init_asset::<StandardMaterial>();
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        // Cosmetic effects have nobody to show them to
+        let world = app.world_mut();
+        if let Some(mut settings) = world.get_resource_mut::<DecalSettings>() {
+            settings.enabled = false;
+        }
+        if let Some(mut settings) = world.get_resource_mut::<SurfaceFxSettings>() {
+            settings.enable_melee_fx = false;
+        }
+        if let Some(mut settings) = world.get_resource_mut::<SliceFxSettings>() {
+            settings.spawn_debug_marker = false;
+        }
+        if let Some(mut settings) = world.get_resource_mut::<DamageFeedbackSettings>() {
+            settings.flash_enabled = false;
+            settings.indicators_enabled = false;
+            settings.rumble_enabled = false;
+        }
+        if let Some(mut settings) = world.get_resource_mut::<HapticsSettings>() {
+            settings.enabled = false;
+        }
+        if let Some(mut settings) = world.get_resource_mut::<VfxSettings>() {
+            settings.enabled = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hud::HudElement;
+    use crate::GameControllerPlugin;
+
+    #[derive(Resource, Default)]
+    struct Runs {
+        presentation: u32,
+        gameplay: u32,
+    }
+
+    fn count_presentation(mut runs: ResMut<Runs>) {
+        runs.presentation += 1;
+    }
+
+    fn count_gameplay(mut runs: ResMut<Runs>) {
+        runs.gameplay += 1;
+    }
+
+    #[test]
+    fn test_headless_app_skips_presentation() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, GameControllerPlugin::default().headless()))
+            .init_resource::<Runs>()
+            .add_systems(Startup, count_presentation.in_set(PresentationSet))
+            .add_systems(Update, (count_presentation.in_set(PresentationSet), count_gameplay))
+            .add_systems(PostUpdate, count_presentation.in_set(PresentationSet));
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        let runs = app.world().resource::<Runs>();
+        assert_eq!((runs.presentation, runs.gameplay), (0, 3));
+        // No HUD was built, and cosmetic feedback is off
+        let hud_elements = app.world_mut().query::<&HudElement>().iter(app.world()).count();
+        assert_eq!(hud_elements, 0);
+        let feedback = app.world().resource::<DamageFeedbackSettings>();
+        assert!(!feedback.flash_enabled && !feedback.indicators_enabled);
+    }
+}
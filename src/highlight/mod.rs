@@ -0,0 +1,46 @@
+//! Highlight service
+//!
+//! Modules request and release highlights per entity through the
+//! [`HighlightCommandQueue`]; when several requests target one entity the highest
+//! priority wins. A single system applies the result as an emissive tint on copies
+//! of the entity's materials, so interaction hover, quest targets, grabbables and
+//! marked enemies no longer fight over materials.
+//!
+//! ```rust,ignore
+//! fn mark(mut highlights: ResMut<HighlightCommandQueue>) {
+//!     highlights.request(chest, HighlightKind::QuestTarget);
+//!     highlights.release(door, HighlightKind::InteractableHover);
+//! }
+//! ```
+
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    HighlightKind, HighlightStyle, HighlightSettings, OutlineSettings, HighlightRequest,
+    HighlightCommand, HighlightCommandQueue, HighlightRegistry, Highlighted, HighlightMaterial,
+};
+
+pub struct HighlightPlugin;
+
+impl Plugin for HighlightPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<HighlightSettings>()
+            .init_resource::<HighlightRegistry>()
+            .add_plugins(EventQueuePlugin::<HighlightCommand>::default())
+            .register_type::<HighlightSettings>()
+            .register_type::<OutlineSettings>()
+            .register_type::<Highlighted>()
+            // Requests are made during Update
+            .add_systems(PostUpdate, (
+                process_highlight_commands,
+                apply_highlights,
+            ).chain());
+    }
+}
@@ -0,0 +1,167 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use bevy::platform::collections::HashMap;
+use super::types::*;
+use crate::utils::QueueReader;
+
+pub fn process_highlight_commands(
+    mut highlight_commands: QueueReader<HighlightCommand>,
+    mut registry: ResMut<HighlightRegistry>,
+) {
+    for command in highlight_commands.read() {
+        match command {
+            HighlightCommand::Request { entity, request } => {
+                let requests = registry.requests.entry(*entity).or_default();
+                requests.retain(|existing| existing.kind != request.kind);
+                requests.push(request.clone());
+            }
+            HighlightCommand::Release { entity, kind } => {
+                if let Some(requests) = registry.requests.get_mut(entity) {
+                    requests.retain(|existing| existing.kind != *kind);
+                }
+            }
+            HighlightCommand::Clear { entity } => {
+                registry.requests.remove(entity);
+            }
+        }
+    }
+}
+
+/// Meshes under highlighted entities and the materials they are tinted with
+#[derive(SystemParam)]
+pub struct HighlightMeshes<'w, 's> {
+    pub children_query: Query<'w, 's, &'static Children>,
+    pub mesh_query: Query<'w, 's, (&'static mut MeshMaterial3d<StandardMaterial>, Option<&'static HighlightMaterial>)>,
+    pub materials: Option<ResMut<'w, Assets<StandardMaterial>>>,
+}
+
+/// Tints the meshes of highlighted entities and restores the others.
+/// The only system that swaps materials for highlighting.
+pub fn apply_highlights(
+    mut commands: Commands,
+    settings: Res<HighlightSettings>,
+    mut registry: ResMut<HighlightRegistry>,
+    entity_query: Query<()>,
+    mut outline_query: Query<&mut OutlineSettings>,
+    highlighted_query: Query<(Entity, &Highlighted)>,
+    meshes: HighlightMeshes,
+) {
+    let HighlightMeshes { children_query, mut mesh_query, materials } = meshes;
+    let Some(mut materials) = materials else { return };
+
+    // Forget despawned entities
+    registry.requests.retain(|entity, requests| !requests.is_empty() && entity_query.contains(*entity));
+
+    let wanted: HashMap<Entity, (HighlightKind, Color)> = registry
+        .requests
+        .keys()
+        .filter(|entity| settings.enabled && outline_query.get(**entity).map(|outline| outline.enabled).unwrap_or(true))
+        .filter_map(|entity| registry.resolve(*entity, &settings).map(|resolved| (*entity, resolved)))
+        .collect();
+
+    for (entity, _) in highlighted_query.iter() {
+        if wanted.contains_key(&entity) {
+            continue;
+        }
+
+        for mesh in std::iter::once(entity).chain(children_query.iter_descendants(entity)) {
+            let Ok((mut material, Some(tint))) = mesh_query.get_mut(mesh) else { continue };
+            material.0 = tint.original.clone();
+            materials.remove(&tint.tinted);
+            commands.entity(mesh).remove::<HighlightMaterial>();
+        }
+        commands.entity(entity).remove::<Highlighted>();
+        if let Ok(mut outline) = outline_query.get_mut(entity) {
+            outline.active = false;
+        }
+    }
+
+    for (entity, (kind, color)) in wanted {
+        let current = highlighted_query.get(entity).ok().map(|(_, highlighted)| (highlighted.kind, highlighted.color));
+        if current == Some((kind, color)) {
+            continue;
+        }
+
+        let tint = color.to_linear() * settings.intensity;
+        for mesh in std::iter::once(entity).chain(children_query.iter_descendants(entity)) {
+            let Ok((mut material, existing)) = mesh_query.get_mut(mesh) else { continue };
+            match existing {
+                Some(existing) => {
+                    let base = materials.get(&existing.original).map(|original| original.emissive).unwrap_or_default();
+                    if let Some(tinted) = materials.get_mut(&existing.tinted) {
+                        tinted.emissive = base + tint;
+                    }
+                }
+                None => {
+                    let Some(original) = materials.get(&material.0) else { continue };
+                    let mut tinted = original.clone();
+                    tinted.emissive = original.emissive + tint;
+                    let tinted = materials.add(tinted);
+                    commands.entity(mesh).insert(HighlightMaterial {
+                        original: material.0.clone(),
+                        tinted: tinted.clone(),
+                    });
+                    material.0 = tinted;
+                }
+            }
+        }
+
+        commands.entity(entity).insert(Highlighted { kind, color });
+        if let Ok(mut outline) = outline_query.get_mut(entity) {
+            outline.active = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::highlight::HighlightPlugin;
+
+    fn emissive_of(app: &App, mesh: Entity) -> LinearRgba {
+        let handle = app.world().get::<MeshMaterial3d<StandardMaterial>>(mesh).unwrap().0.clone();
+        app.world().resource::<Assets<StandardMaterial>>().get(&handle).unwrap().emissive
+    }
+
+    #[test]
+    fn test_highest_priority_highlight_wins_and_restores() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, HighlightPlugin))
+            .init_resource::<Assets<StandardMaterial>>();
+
+        let original = app.world_mut().resource_mut::<Assets<StandardMaterial>>().add(StandardMaterial::default());
+        let chest = app.world_mut().spawn(OutlineSettings::default()).id();
+        let mesh = app.world_mut().spawn((MeshMaterial3d(original.clone()), ChildOf(chest))).id();
+        let settings = HighlightSettings::default();
+        let tint = |kind: HighlightKind| LinearRgba::BLACK + settings.style(kind).color.to_linear() * settings.intensity;
+
+        app.world_mut().resource_mut::<HighlightCommandQueue>().request(chest, HighlightKind::QuestTarget);
+        app.update();
+        assert_eq!(app.world().get::<Highlighted>(chest).unwrap().kind, HighlightKind::QuestTarget);
+        assert!(app.world().get::<OutlineSettings>(chest).unwrap().active);
+        assert_ne!(app.world().get::<MeshMaterial3d<StandardMaterial>>(mesh).unwrap().0, original);
+        assert_eq!(emissive_of(&app, mesh), tint(HighlightKind::QuestTarget));
+
+        // Grabbable outranks the quest marker and reuses the tinted copy
+        let tinted = app.world().get::<MeshMaterial3d<StandardMaterial>>(mesh).unwrap().0.clone();
+        app.world_mut().resource_mut::<HighlightCommandQueue>().request(chest, HighlightKind::Grabbable);
+        app.update();
+        assert_eq!(app.world().get::<Highlighted>(chest).unwrap().kind, HighlightKind::Grabbable);
+        assert_eq!(app.world().get::<MeshMaterial3d<StandardMaterial>>(mesh).unwrap().0, tinted);
+        assert_eq!(emissive_of(&app, mesh), tint(HighlightKind::Grabbable));
+
+        app.world_mut().resource_mut::<HighlightCommandQueue>().release(chest, HighlightKind::Grabbable);
+        app.update();
+        assert_eq!(app.world().get::<Highlighted>(chest).unwrap().kind, HighlightKind::QuestTarget);
+        assert_eq!(emissive_of(&app, mesh), tint(HighlightKind::QuestTarget));
+
+        // Releasing the last request puts the original material back
+        app.world_mut().resource_mut::<HighlightCommandQueue>().release(chest, HighlightKind::QuestTarget);
+        app.update();
+        assert!(app.world().get::<Highlighted>(chest).is_none());
+        assert!(app.world().get::<HighlightMaterial>(mesh).is_none());
+        assert!(!app.world().get::<OutlineSettings>(chest).unwrap().active);
+        assert_eq!(app.world().get::<MeshMaterial3d<StandardMaterial>>(mesh).unwrap().0, original);
+        assert!(app.world().resource::<Assets<StandardMaterial>>().get(&tinted).is_none());
+    }
+}
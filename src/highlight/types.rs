@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+use bevy::platform::collections::HashMap;
+use crate::utils::EventQueue;
+
+/// Why an entity is highlighted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum HighlightKind {
+    /// Interactable under the player's crosshair
+    InteractableHover,
+    /// Objective of an active quest
+    QuestTarget,
+    /// Object the player can pick up
+    Grabbable,
+    /// Enemy marked by the camera targeting
+    EnemyMarked,
+    /// Revealed by a radar pulse
+    RadarPulse,
+    /// Game-specific highlight
+    Custom(u8),
+}
+
+/// Color and priority of one highlight kind
+#[derive(Debug, Clone, Reflect)]
+pub struct HighlightStyle {
+    pub color: Color,
+    /// Higher priorities win when an entity has several requests
+    pub priority: i32,
+}
+
+/// Global highlight settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct HighlightSettings {
+    pub enabled: bool,
+    /// Emissive strength of the highlight tint
+    pub intensity: f32,
+    pub interactable_hover: HighlightStyle,
+    pub quest_target: HighlightStyle,
+    pub grabbable: HighlightStyle,
+    pub enemy_marked: HighlightStyle,
+    pub radar_pulse: HighlightStyle,
+    pub custom: HighlightStyle,
+}
+
+impl Default for HighlightSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            intensity: 0.6,
+            interactable_hover: HighlightStyle { color: Color::srgb(1.0, 1.0, 1.0), priority: 20 },
+            quest_target: HighlightStyle { color: Color::srgb(1.0, 0.8, 0.2), priority: 10 },
+            grabbable: HighlightStyle { color: Color::srgb(1.0, 1.0, 0.0), priority: 30 },
+            enemy_marked: HighlightStyle { color: Color::srgb(1.0, 0.2, 0.2), priority: 40 },
+            radar_pulse: HighlightStyle { color: Color::srgb(0.2, 0.9, 1.0), priority: 5 },
+            custom: HighlightStyle { color: Color::srgb(0.3, 0.7, 1.0), priority: 0 },
+        }
+    }
+}
+
+impl HighlightSettings {
+    pub fn style(&self, kind: HighlightKind) -> &HighlightStyle {
+        match kind {
+            HighlightKind::InteractableHover => &self.interactable_hover,
+            HighlightKind::QuestTarget => &self.quest_target,
+            HighlightKind::Grabbable => &self.grabbable,
+            HighlightKind::EnemyMarked => &self.enemy_marked,
+            HighlightKind::RadarPulse => &self.radar_pulse,
+            HighlightKind::Custom(_) => &self.custom,
+        }
+    }
+}
+
+/// Per-entity highlight options
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct OutlineSettings {
+    /// Whether the entity may be highlighted at all
+    pub enabled: bool,
+    /// Outline width for custom renderers reading `Highlighted`
+    pub width: f32,
+    /// Color used for `HighlightKind::Grabbable`
+    pub color: Color,
+    /// Set while the entity is highlighted
+    pub active: bool,
+}
+
+impl Default for OutlineSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            width: 0.05,
+            color: Color::srgba(1.0, 1.0, 0.0, 1.0), // srgba for Yellow
+            active: false,
+        }
+    }
+}
+
+/// One module's highlight request
+#[derive(Debug, Clone, PartialEq)]
+pub struct HighlightRequest {
+    pub kind: HighlightKind,
+    /// Overrides the style color
+    pub color: Option<Color>,
+    /// Overrides the style priority
+    pub priority: Option<i32>,
+}
+
+/// Highlight control
+#[derive(Debug, Clone, PartialEq)]
+pub enum HighlightCommand {
+    /// Adds or replaces the request of `request.kind` on `entity`
+    Request { entity: Entity, request: HighlightRequest },
+    Release { entity: Entity, kind: HighlightKind },
+    /// Drops every request on `entity`
+    Clear { entity: Entity },
+}
+
+/// Queue for highlight commands
+pub type HighlightCommandQueue = EventQueue<HighlightCommand>;
+
+impl EventQueue<HighlightCommand> {
+    /// Highlights `entity` with the style of `kind`
+    pub fn request(&mut self, entity: Entity, kind: HighlightKind) {
+        self.send(HighlightCommand::Request {
+            entity,
+            request: HighlightRequest { kind, color: None, priority: None },
+        });
+    }
+
+    pub fn request_with_color(&mut self, entity: Entity, kind: HighlightKind, color: Color) {
+        self.send(HighlightCommand::Request {
+            entity,
+            request: HighlightRequest { kind, color: Some(color), priority: None },
+        });
+    }
+
+    pub fn release(&mut self, entity: Entity, kind: HighlightKind) {
+        self.send(HighlightCommand::Release { entity, kind });
+    }
+}
+
+/// Active requests per entity
+#[derive(Resource, Debug, Default)]
+pub struct HighlightRegistry {
+    pub requests: HashMap<Entity, Vec<HighlightRequest>>,
+}
+
+impl HighlightRegistry {
+    /// Winning request and its resolved color
+    pub fn resolve(&self, entity: Entity, settings: &HighlightSettings) -> Option<(HighlightKind, Color)> {
+        self.requests
+            .get(&entity)?
+            .iter()
+            .enumerate()
+            // Latest request wins ties
+            .max_by_key(|(index, request)| {
+                (request.priority.unwrap_or(settings.style(request.kind).priority), *index)
+            })
+            .map(|(_, request)| {
+                (request.kind, request.color.unwrap_or(settings.style(request.kind).color))
+            })
+    }
+}
+
+/// Currently displayed highlight
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Highlighted {
+    pub kind: HighlightKind,
+    pub color: Color,
+}
+
+/// Mesh whose material was swapped for a tinted copy
+#[derive(Component, Debug)]
+pub struct HighlightMaterial {
+    pub original: Handle<StandardMaterial>,
+    pub tinted: Handle<StandardMaterial>,
+}
@@ -0,0 +1,58 @@
+//! HUD manager
+//!
+//! HUD pieces register by adding a [`HudElement`] to their root node. The
+//! [`HudManager`] then decides what is on screen:
+//!
+//! - the toggle-HUD input (`InputAction::ToggleHud`) hides every element that
+//!   does not `ignore_toggle`
+//! - elements hide during camera cutscenes, photo mode or menus per their
+//!   [`HudVisibilityRules`], and can fade out while inactive
+//! - layout profiles (`full`, `minimal`, `custom`) pick elements by
+//!   [`HudPriority`]; `custom` places draggable elements where the `ui_edit`
+//!   layout editor saved them
+//!
+//! ```rust,ignore
+//! commands.spawn((
+//!     Node { position_type: PositionType::Absolute, ..default() },
+//!     HudElement::new("quest_tracker", HudPriority::Detail).fading(),
+//! ));
+//!
+//! fn minimal_hud(mut hud: ResMut<HudCommandQueue>) {
+//!     hud.send(HudCommand::SetProfile("minimal".to_string()));
+//! }
+//! ```
+
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use bevy::camera::visibility::VisibilitySystems;
+use types::*;
+use systems::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    HudPriority, HudVisibilityRules, HudElement, HudLayoutProfile, HudManager, HudCommand,
+    HudCommandQueue,
+};
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<HudManager>()
+            .add_plugins(EventQueuePlugin::<HudCommand>::default())
+            .register_type::<HudManager>()
+            .register_type::<HudElement>()
+            .add_systems(Update, (
+                handle_hud_commands,
+                update_hud_conditions,
+                apply_hud_layout,
+            ).chain().after(crate::input::InputSet::Gather))
+            // After the owning systems, before visibility propagates
+            .add_systems(PostUpdate, update_hud_elements
+                .in_set(crate::headless::PresentationSet)
+                .before(VisibilitySystems::VisibilityPropagate));
+    }
+}
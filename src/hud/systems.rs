@@ -0,0 +1,261 @@
+use bevy::prelude::*;
+use super::types::*;
+use crate::camera::{CameraWaypointFollower, PhotoModeState};
+use crate::input::types::InputContext;
+use crate::input::{DraggableUi, InputContextStack, InputState, UiLayoutStore};
+use crate::utils::QueueReader;
+
+/// System to apply HUD commands and the toggle-HUD input
+pub fn handle_hud_commands(
+    input: Res<InputState>,
+    mut commands: QueueReader<HudCommand>,
+    mut manager: ResMut<HudManager>,
+    mut elements: Query<&mut HudElement>,
+) {
+    if input.toggle_hud_pressed && manager.toggle_enabled {
+        manager.visible = !manager.visible;
+    }
+
+    for command in commands.read() {
+        match command {
+            HudCommand::Toggle => manager.visible = !manager.visible,
+            HudCommand::SetVisible(visible) => manager.visible = *visible,
+            HudCommand::SetProfile(name) => {
+                if let Err(error) = manager.set_profile(name) {
+                    warn!("{}", error);
+                }
+            }
+            HudCommand::Ping(id) => {
+                for mut element in elements.iter_mut().filter(|element| element.id == *id) {
+                    element.inactive_time = 0.0;
+                }
+            }
+        }
+    }
+}
+
+/// System to track the cutscene, photo mode and menu conditions
+pub fn update_hud_conditions(
+    mut manager: ResMut<HudManager>,
+    photo_mode: Res<PhotoModeState>,
+    contexts: Res<InputContextStack>,
+    followers: Query<&CameraWaypointFollower>,
+) {
+    manager.in_cutscene = followers.iter().any(|follower| follower.current_track.is_some());
+    manager.in_photo_mode = photo_mode.active && photo_mode.hide_hud;
+    manager.in_menu = contexts.contains(InputContext::Menu);
+}
+
+/// System to place draggable HUD elements for the active profile
+pub fn apply_hud_layout(
+    manager: Res<HudManager>,
+    store: Res<UiLayoutStore>,
+    mut applied_profile: Local<Option<String>>,
+    mut query: Query<(Ref<HudElement>, &DraggableUi, &mut Node)>,
+) {
+    let profile_changed = applied_profile.as_deref() != Some(manager.active_profile.as_str());
+    let custom = manager.profile().is_some_and(|profile| profile.custom_layout);
+
+    for (element, draggable, mut node) in query.iter_mut() {
+        if !profile_changed && !element.is_added() {
+            continue;
+        }
+        let position = custom
+            .then(|| store.positions.get(&draggable.save_key))
+            .flatten()
+            .map_or(draggable.default_position, |position| Vec2::new(position.x, position.y));
+        node.left = Val::Px(position.x);
+        node.top = Val::Px(position.y);
+    }
+
+    *applied_profile = Some(manager.active_profile.clone());
+}
+
+/// Colored parts of a HUD element, faded together
+type HudElementColors<'a> = (
+    Option<&'a mut BackgroundColor>,
+    Option<&'a mut TextColor>,
+    Option<&'a mut ImageNode>,
+    Option<Ref<'a, Text>>,
+);
+
+/// System to show, hide and fade HUD elements
+pub fn update_hud_elements(
+    time: Res<Time>,
+    manager: Res<HudManager>,
+    mut elements: Query<(Entity, &mut HudElement, &mut Visibility)>,
+    children_query: Query<&Children>,
+    mut colors: Query<HudElementColors>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut element, mut visibility) in elements.iter_mut() {
+        let element = &mut *element;
+        element.shown = manager.should_show(element);
+        if element.shown {
+            if let Some(previous) = element.hidden_visibility.take() {
+                *visibility = previous;
+            }
+        } else {
+            // Owning systems may show the element again during Update
+            element.hidden_visibility.get_or_insert(*visibility);
+            *visibility = Visibility::Hidden;
+        }
+
+        let tree: Vec<Entity> = std::iter::once(entity).chain(children_query.iter_descendants(entity)).collect();
+        let text_changed = tree.iter().any(|part| {
+            colors
+                .get(*part)
+                .is_ok_and(|(.., text)| text.is_some_and(|text| text.is_changed()))
+        });
+        element.inactive_time = if text_changed { 0.0 } else { element.inactive_time + dt };
+
+        let rules = &element.rules;
+        let target = if rules.fade_when_inactive && element.inactive_time > rules.inactive_delay {
+            rules.inactive_opacity
+        } else {
+            1.0
+        };
+        let step = rules.fade_speed * dt;
+        element.opacity += (target - element.opacity).clamp(-step, step);
+
+        if element.opacity >= 1.0 && element.base_alphas.is_empty() {
+            continue;
+        }
+        let opacity = element.opacity;
+        for part in tree {
+            let Ok((background, text_color, image, _)) = colors.get_mut(part) else { continue };
+            let bases = element.base_alphas.entry(part).or_default();
+            if let Some(mut background) = background {
+                let changed = background.is_changed();
+                fade_color(&mut background.bypass_change_detection().0, &mut bases[0], changed, opacity);
+            }
+            if let Some(mut text_color) = text_color {
+                let changed = text_color.is_changed();
+                fade_color(&mut text_color.bypass_change_detection().0, &mut bases[1], changed, opacity);
+            }
+            if let Some(mut image) = image {
+                let changed = image.is_changed();
+                fade_color(&mut image.bypass_change_detection().color, &mut bases[2], changed, opacity);
+            }
+        }
+        if opacity >= 1.0 {
+            element.base_alphas.clear();
+        }
+    }
+}
+
+/// Scales `color`'s alpha by `opacity`, keeping the unfaded alpha in `base`.
+/// Fading bypasses change detection, so a change means the owner set a new color.
+fn fade_color(color: &mut Color, base: &mut Option<f32>, changed: bool, opacity: f32) {
+    if changed || base.is_none() {
+        *base = Some(color.alpha());
+    }
+    color.set_alpha(base.unwrap_or(1.0) * opacity);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hud::HudPlugin;
+    use crate::input::UiPosition;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    fn visibility(app: &App, entity: Entity) -> Visibility {
+        *app.world().get::<Visibility>(entity).unwrap()
+    }
+
+    fn send(app: &mut App, command: HudCommand) {
+        app.world_mut().resource_mut::<HudCommandQueue>().send(command);
+        app.update();
+    }
+
+    #[test]
+    fn test_hud_profiles_toggle_and_fade() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, HudPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<InputState>()
+            .init_resource::<PhotoModeState>()
+            .init_resource::<InputContextStack>()
+            .init_resource::<UiLayoutStore>();
+        app.world_mut()
+            .resource_mut::<UiLayoutStore>()
+            .positions
+            .insert("minimap".to_string(), UiPosition { x: 300.0, y: 40.0 });
+
+        let crosshair = app
+            .world_mut()
+            .spawn((Node::default(), Visibility::Inherited, HudElement::new("crosshair", HudPriority::Essential)))
+            .id();
+        let minimap = app
+            .world_mut()
+            .spawn((
+                Node::default(),
+                Visibility::Visible,
+                HudElement::new("minimap", HudPriority::Detail),
+                DraggableUi { save_key: "minimap".to_string(), default_position: Vec2::new(20.0, 20.0) },
+            ))
+            .id();
+        let overlay = app
+            .world_mut()
+            .spawn((
+                Node::default(),
+                Visibility::Inherited,
+                HudElement::new("fps", HudPriority::Detail)
+                    .with_rules(HudVisibilityRules { ignore_toggle: true, ..default() }),
+            ))
+            .id();
+        let mut tracker = HudElement::new("quest_tracker", HudPriority::Standard).fading();
+        tracker.rules.inactive_delay = 0.3;
+        tracker.rules.fade_speed = 10.0;
+        let quest_text = app.world_mut().spawn((Text::new("Find the key"), TextColor(Color::WHITE))).id();
+        app.world_mut().spawn((Node::default(), Visibility::Inherited, tracker)).add_child(quest_text);
+
+        app.update();
+        assert_eq!(app.world().get::<Node>(minimap).unwrap().left, Val::Px(20.0));
+        assert_eq!(visibility(&app, minimap), Visibility::Visible);
+
+        // The minimal profile hides details; going back restores their own visibility
+        send(&mut app, HudCommand::SetProfile("minimal".to_string()));
+        assert_eq!(visibility(&app, minimap), Visibility::Hidden);
+        assert_eq!(visibility(&app, crosshair), Visibility::Inherited);
+        send(&mut app, HudCommand::SetProfile("custom".to_string()));
+        assert_eq!(visibility(&app, minimap), Visibility::Visible);
+        assert_eq!(app.world().get::<Node>(minimap).unwrap().left, Val::Px(300.0));
+        send(&mut app, HudCommand::SetProfile("cinematic".to_string()));
+        assert_eq!(app.world().resource::<HudManager>().active_profile, "custom");
+
+        // The toggle input hides everything but elements that ignore it
+        app.world_mut().resource_mut::<InputState>().toggle_hud_pressed = true;
+        app.update();
+        app.world_mut().resource_mut::<InputState>().toggle_hud_pressed = false;
+        assert!(!app.world().resource::<HudManager>().visible);
+        assert_eq!(visibility(&app, crosshair), Visibility::Hidden);
+        assert_eq!(visibility(&app, overlay), Visibility::Inherited);
+        send(&mut app, HudCommand::Toggle);
+        assert_eq!(visibility(&app, crosshair), Visibility::Inherited);
+
+        {
+            let mut photo_mode = app.world_mut().resource_mut::<PhotoModeState>();
+            photo_mode.active = true;
+            photo_mode.hide_hud = true;
+        }
+        app.update();
+        assert_eq!(visibility(&app, crosshair), Visibility::Hidden);
+        app.world_mut().resource_mut::<PhotoModeState>().active = false;
+        app.update();
+        assert_eq!(visibility(&app, crosshair), Visibility::Inherited);
+
+        // The idle tracker fades, and new text brings it back
+        for _ in 0..5 {
+            app.update();
+        }
+        let alpha = app.world().get::<TextColor>(quest_text).unwrap().0.alpha();
+        assert!((alpha - 0.25).abs() < 1e-4, "faded alpha {}", alpha);
+        app.world_mut().get_mut::<Text>(quest_text).unwrap().0 = "Open the gate".to_string();
+        app.update();
+        assert_eq!(app.world().get::<TextColor>(quest_text).unwrap().0.alpha(), 1.0);
+    }
+}
@@ -0,0 +1,215 @@
+use bevy::prelude::*;
+use bevy::platform::collections::HashMap;
+use crate::utils::EventQueue;
+
+/// How important a HUD element is; layout profiles show elements up to a priority
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect, Default)]
+pub enum HudPriority {
+    /// Always part of the HUD (health, crosshair)
+    Essential,
+    #[default]
+    Standard,
+    /// Extra information (minimap, pickup info)
+    Detail,
+}
+
+/// When a HUD element hides or fades
+#[derive(Debug, Clone, Reflect)]
+pub struct HudVisibilityRules {
+    pub hide_in_cutscene: bool,
+    pub hide_in_photo_mode: bool,
+    pub hide_in_menu: bool,
+    /// Stays visible when the player toggles the HUD off
+    pub ignore_toggle: bool,
+    /// Fade out after `inactive_delay` seconds without activity
+    pub fade_when_inactive: bool,
+    pub inactive_delay: f32,
+    /// Opacity once faded (0-1)
+    pub inactive_opacity: f32,
+    /// Opacity change per second
+    pub fade_speed: f32,
+}
+
+impl Default for HudVisibilityRules {
+    fn default() -> Self {
+        Self {
+            hide_in_cutscene: true,
+            hide_in_photo_mode: true,
+            hide_in_menu: false,
+            ignore_toggle: false,
+            fade_when_inactive: false,
+            inactive_delay: 4.0,
+            inactive_opacity: 0.25,
+            fade_speed: 3.0,
+        }
+    }
+}
+
+/// HUD element managed by the [`HudManager`]; add it to the element's root node.
+///
+/// Text changes anywhere in the element count as activity; other activity is
+/// reported with [`HudCommand::Ping`].
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct HudElement {
+    pub id: String,
+    pub priority: HudPriority,
+    pub rules: HudVisibilityRules,
+    /// Shown this frame (read-only)
+    pub shown: bool,
+    /// Current opacity (read-only)
+    pub opacity: f32,
+    /// Seconds since the last activity
+    pub inactive_time: f32,
+    /// Visibility to restore once the manager stops hiding the element
+    #[reflect(ignore)]
+    pub(crate) hidden_visibility: Option<Visibility>,
+    /// Unfaded alpha of the background, text and image colors in the element's tree
+    #[reflect(ignore)]
+    pub(crate) base_alphas: HashMap<Entity, [Option<f32>; 3]>,
+}
+
+impl Default for HudElement {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            priority: HudPriority::Standard,
+            rules: HudVisibilityRules::default(),
+            shown: true,
+            opacity: 1.0,
+            inactive_time: 0.0,
+            hidden_visibility: None,
+            base_alphas: HashMap::default(),
+        }
+    }
+}
+
+impl HudElement {
+    pub fn new(id: impl Into<String>, priority: HudPriority) -> Self {
+        Self { id: id.into(), priority, ..default() }
+    }
+
+    pub fn with_rules(mut self, rules: HudVisibilityRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Fade out while inactive
+    pub fn fading(mut self) -> Self {
+        self.rules.fade_when_inactive = true;
+        self
+    }
+}
+
+/// Named set of HUD elements and their layout
+#[derive(Debug, Clone, Reflect)]
+pub struct HudLayoutProfile {
+    pub name: String,
+    /// Elements up to this priority are shown
+    pub max_priority: HudPriority,
+    /// Element ids hidden regardless of priority
+    pub hidden: Vec<String>,
+    /// Element ids shown regardless of priority
+    pub shown: Vec<String>,
+    /// Place draggable elements where the `ui_edit` layout editor saved them
+    pub custom_layout: bool,
+}
+
+impl HudLayoutProfile {
+    pub fn new(name: impl Into<String>, max_priority: HudPriority) -> Self {
+        Self {
+            name: name.into(),
+            max_priority,
+            hidden: Vec::new(),
+            shown: Vec::new(),
+            custom_layout: false,
+        }
+    }
+
+    pub fn includes(&self, element: &HudElement) -> bool {
+        if self.hidden.contains(&element.id) {
+            return false;
+        }
+        self.shown.contains(&element.id) || element.priority <= self.max_priority
+    }
+}
+
+/// Central HUD state: the toggle, layout profiles and screen conditions
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct HudManager {
+    /// Cleared by the toggle-HUD input
+    pub visible: bool,
+    pub toggle_enabled: bool,
+    pub profiles: Vec<HudLayoutProfile>,
+    pub active_profile: String,
+    /// A camera cutscene is playing (updated each frame)
+    pub in_cutscene: bool,
+    /// Photo mode is active (updated each frame)
+    pub in_photo_mode: bool,
+    /// The input context is a menu (updated each frame)
+    pub in_menu: bool,
+}
+
+impl Default for HudManager {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            toggle_enabled: true,
+            profiles: vec![
+                HudLayoutProfile::new("full", HudPriority::Detail),
+                HudLayoutProfile::new("minimal", HudPriority::Essential),
+                HudLayoutProfile {
+                    custom_layout: true,
+                    ..HudLayoutProfile::new("custom", HudPriority::Detail)
+                },
+            ],
+            active_profile: "full".to_string(),
+            in_cutscene: false,
+            in_photo_mode: false,
+            in_menu: false,
+        }
+    }
+}
+
+impl HudManager {
+    pub fn profile(&self) -> Option<&HudLayoutProfile> {
+        self.profiles.iter().find(|profile| profile.name == self.active_profile)
+    }
+
+    pub fn set_profile(&mut self, name: &str) -> Result<(), String> {
+        if !self.profiles.iter().any(|profile| profile.name == name) {
+            return Err(format!("Unknown HUD profile '{}'", name));
+        }
+        self.active_profile = name.to_string();
+        Ok(())
+    }
+
+    /// Whether `element` is shown under the current profile and conditions
+    pub fn should_show(&self, element: &HudElement) -> bool {
+        let rules = &element.rules;
+        if !self.visible && !rules.ignore_toggle {
+            return false;
+        }
+        if (self.in_cutscene && rules.hide_in_cutscene)
+            || (self.in_photo_mode && rules.hide_in_photo_mode)
+            || (self.in_menu && rules.hide_in_menu)
+        {
+            return false;
+        }
+        self.profile().is_none_or(|profile| profile.includes(element))
+    }
+}
+
+/// HUD requests from gameplay code and menus
+#[derive(Debug, Clone, PartialEq)]
+pub enum HudCommand {
+    Toggle,
+    SetVisible(bool),
+    SetProfile(String),
+    /// Activity on an element, which resets its fade
+    Ping(String),
+}
+
+/// Queue for HUD commands
+pub type HudCommandQueue = EventQueue<HudCommand>;
@@ -0,0 +1,57 @@
+//! Off-screen indicators
+//!
+//! Edge arrows toward world targets (quest objectives, waypoints, pinged enemies,
+//! downed allies, vehicles). Tag an entity with an [`OffScreenTarget`]; while it is
+//! off screen a pooled indicator is clamped to the screen edge, pointing at it with
+//! its kind's icon or glyph and the distance to the player. Kinds are styled through
+//! [`OffScreenIndicatorSettings::styles`].
+//!
+//! ```rust,ignore
+//! // Ping an enemy for 8 seconds
+//! commands.entity(enemy).insert(
+//!     OffScreenTarget::new(IndicatorKind::PingedEnemy)
+//!         .with_offset(Vec3::Y * 2.0)
+//!         .with_lifetime(8.0),
+//! );
+//! ```
+
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use bevy::transform::TransformSystems;
+use crate::utils::PoolPlugin;
+use types::*;
+use systems::*;
+
+pub use types::{
+    IndicatorKind, IndicatorStyle, OffScreenIndicatorSettings, OffScreenTarget,
+    OffScreenIndicatorRoot, OffScreenIndicator, IndicatorParts,
+};
+
+pub struct OffScreenIndicatorPlugin;
+
+impl Plugin for OffScreenIndicatorPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<OffScreenIndicatorSettings>()
+            .register_type::<OffScreenIndicatorSettings>()
+            .register_type::<OffScreenTarget>()
+            .register_type::<OffScreenIndicator>()
+            .register_type::<OffScreenIndicatorRoot>()
+            .register_type::<IndicatorParts>()
+            .add_plugins(PoolPlugin::<OffScreenIndicator>::new(32))
+            .add_systems(Startup, setup_indicator_root.in_set(crate::headless::PresentationSet))
+            .add_systems(Update, (
+                update_target_lifetimes,
+                assign_indicators.in_set(crate::headless::PresentationSet),
+            ).chain())
+            // After cameras and targets have moved for this frame
+            .add_systems(PostUpdate, position_indicators
+                .in_set(crate::headless::PresentationSet)
+                .after(TransformSystems::Propagate));
+
+        #[cfg(feature = "map")]
+        app.add_systems(Update, attach_objective_indicators.before(assign_indicators));
+    }
+}
@@ -0,0 +1,300 @@
+use bevy::prelude::*;
+use super::types::*;
+use crate::character::Player;
+use crate::hud::{HudElement, HudPriority};
+use crate::utils::EntityPool;
+
+/// System to spawn the full screen container of the indicators
+pub fn setup_indicator_root(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        GlobalZIndex(15),
+        OffScreenIndicatorRoot,
+        HudElement::new("offscreen_indicators", HudPriority::Standard),
+        Name::new("OffScreenIndicators"),
+    ));
+}
+
+/// System to remove targets whose lifetime ran out
+pub fn update_target_lifetimes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut OffScreenTarget)>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut target) in query.iter_mut() {
+        let Some(lifetime) = target.lifetime.as_mut() else { continue };
+        *lifetime -= delta;
+        if *lifetime <= 0.0 {
+            commands.entity(entity).remove::<OffScreenTarget>();
+        }
+    }
+}
+
+/// Hooks objectives that ask for an off-screen arrow up to the indicators
+#[cfg(feature = "map")]
+pub fn attach_objective_indicators(
+    mut commands: Commands,
+    query: Query<(Entity, &crate::map::types::ObjectiveIcon, Option<&OffScreenTarget>), Changed<crate::map::types::ObjectiveIcon>>,
+) {
+    for (entity, icon, target) in query.iter() {
+        match (icon.off_screen_arrow, target) {
+            (true, None) => {
+                commands.entity(entity).insert(OffScreenTarget::new(IndicatorKind::Objective));
+            }
+            (false, Some(target)) if target.kind == IndicatorKind::Objective => {
+                commands.entity(entity).remove::<OffScreenTarget>();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// System to take indicators from the pool for new targets and return those of removed ones
+pub fn assign_indicators(
+    mut commands: Commands,
+    settings: Res<OffScreenIndicatorSettings>,
+    mut pool: ResMut<EntityPool<OffScreenIndicator>>,
+    root_query: Query<Entity, With<OffScreenIndicatorRoot>>,
+    mut target_query: Query<(Entity, &mut OffScreenTarget)>,
+    indicator_query: Query<(Entity, &OffScreenIndicator)>,
+) {
+    for (entity, indicator) in indicator_query.iter() {
+        let in_use = settings.enabled
+            && target_query
+                .get(indicator.target)
+                .is_ok_and(|(_, target)| target.enabled && target.indicator == Some(entity));
+        if in_use {
+            continue;
+        }
+
+        pool.release(&mut commands, entity);
+        if let Ok((_, mut target)) = target_query.get_mut(indicator.target) {
+            if target.indicator == Some(entity) {
+                target.indicator = None;
+            }
+        }
+    }
+
+    if !settings.enabled {
+        return;
+    }
+    let Some(root) = root_query.iter().next() else { return };
+
+    for (target_entity, mut target) in target_query.iter_mut() {
+        if !target.enabled || target.indicator.is_some_and(|indicator| indicator_query.contains(indicator)) {
+            continue;
+        }
+
+        let recycled = !pool.available.is_empty();
+        let indicator = pool.acquire(&mut commands, (
+            Node {
+                position_type: PositionType::Absolute,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            OffScreenIndicator { target: target_entity },
+            ChildOf(root),
+        ));
+
+        // Recycled indicators still have their parts
+        if !recycled {
+            let text = |commands: &mut Commands| {
+                commands.spawn((
+                    Text::new(""),
+                    TextFont { font_size: settings.font_size, ..default() },
+                    TextColor(Color::WHITE),
+                    TextLayout::new_with_justify(Justify::Center),
+                )).id()
+            };
+            let arrow = text(&mut commands);
+            let icon = commands.spawn((Node::default(), ImageNode::default(), Visibility::Hidden)).id();
+            let glyph = text(&mut commands);
+            let distance = text(&mut commands);
+
+            commands
+                .entity(indicator)
+                .add_children(&[arrow, icon, glyph, distance])
+                .insert(IndicatorParts { arrow, icon, glyph, distance });
+        }
+
+        target.indicator = Some(indicator);
+    }
+}
+
+/// Icon part of an indicator: neither the indicator itself nor one of its texts
+type IndicatorIconPart = (Without<OffScreenIndicator>, Without<Text>);
+
+/// Places indicators over on-screen targets or clamped to the screen edge toward off-screen ones
+pub fn position_indicators(
+    settings: Res<OffScreenIndicatorSettings>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+    target_query: Query<(&OffScreenTarget, &GlobalTransform)>,
+    mut indicator_query: Query<(&OffScreenIndicator, &IndicatorParts, &mut Node, &mut Visibility)>,
+    mut text_query: Query<(&mut Text, &mut TextColor, &mut Visibility), Without<OffScreenIndicator>>,
+    mut icon_query: Query<(&mut ImageNode, &mut Node, &mut Visibility), IndicatorIconPart>,
+) {
+    let camera = camera_query.iter().find(|(camera, _)| camera.is_active);
+    let player_position = player_query.iter().next().map(|transform| transform.translation());
+
+    for (indicator, parts, mut node, mut visibility) in indicator_query.iter_mut() {
+        let Some((camera, camera_transform)) = camera else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let (Some(viewport), Ok((target, transform))) = (camera.logical_viewport_size(), target_query.get(indicator.target)) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        let style = settings.style(target.kind);
+        let point = transform.translation() + target.offset;
+        let distance = player_position.unwrap_or(camera_transform.translation()).distance(point);
+        if distance > style.max_distance {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+
+        let margin = settings.edge_margin.clamp(0.0, viewport.min_element() * 0.5 - 1.0);
+        let on_screen = camera
+            .world_to_viewport(camera_transform, point)
+            .ok()
+            .filter(|position| {
+                position.cmpge(Vec2::splat(margin)).all() && position.cmple(viewport - margin).all()
+            });
+
+        let (position, arrow) = match on_screen {
+            Some(position) if style.show_on_screen => (position, ""),
+            Some(_) => {
+                *visibility = Visibility::Hidden;
+                continue;
+            }
+            None => {
+                // Direction in screen space (y down), from the camera's point of view;
+                // straight behind points at the bottom edge
+                let local = camera_transform.affine().inverse().transform_point3(point);
+                let mut direction = Vec2::new(local.x, -local.y);
+                if direction.length_squared() < 1e-6 {
+                    direction = Vec2::Y;
+                }
+
+                let center = viewport * 0.5;
+                let half = center - Vec2::splat(margin);
+                let scale = (half.x / direction.x.abs().max(1e-6)).min(half.y / direction.y.abs().max(1e-6));
+                let arrow = if direction.x.abs() * half.y >= direction.y.abs() * half.x {
+                    if direction.x > 0.0 { ">" } else { "<" }
+                } else if direction.y > 0.0 {
+                    "v"
+                } else {
+                    "^"
+                };
+                (center + direction * scale, arrow)
+            }
+        };
+
+        let width = style.icon_size * 3.0;
+        node.width = Val::Px(width);
+        node.left = Val::Px(position.x - width * 0.5);
+        node.top = Val::Px(position.y - style.icon_size * 0.5 - if arrow.is_empty() { 0.0 } else { settings.font_size });
+        *visibility = Visibility::Inherited;
+
+        let icon = target.icon.clone().or(style.icon.clone());
+        if let Ok((mut image, mut icon_node, mut icon_visibility)) = icon_query.get_mut(parts.icon) {
+            match &icon {
+                Some(icon) => {
+                    if image.image != *icon {
+                        image.image = icon.clone();
+                    }
+                    image.color = style.color;
+                    icon_node.width = Val::Px(style.icon_size);
+                    icon_node.height = Val::Px(style.icon_size);
+                    *icon_visibility = Visibility::Inherited;
+                }
+                None => *icon_visibility = Visibility::Hidden,
+            }
+        }
+
+        let glyph = if icon.is_some() { "" } else { target.glyph.as_deref().unwrap_or(&style.glyph) };
+        let distance_label = if style.show_distance { format!("{:.0}m", distance) } else { String::new() };
+        for (part, label) in [(parts.arrow, arrow), (parts.glyph, glyph), (parts.distance, distance_label.as_str())] {
+            let Ok((mut text, mut color, mut text_visibility)) = text_query.get_mut(part) else { continue };
+            if text.0 != label {
+                text.0 = label.to_string();
+            }
+            color.0 = style.color;
+            *text_visibility = if label.is_empty() { Visibility::Hidden } else { Visibility::Inherited };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::indicators::OffScreenIndicatorPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    fn spawn_target(app: &mut App, target: OffScreenTarget) -> Entity {
+        app.world_mut().spawn((Transform::default(), GlobalTransform::default(), target)).id()
+    }
+
+    fn indicator_of(app: &App, target: Entity) -> Option<Entity> {
+        app.world().get::<OffScreenTarget>(target).and_then(|target| target.indicator)
+    }
+
+    #[test]
+    fn test_indicators_follow_targets_through_the_pool() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, OffScreenIndicatorPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+        app.update();
+        let root = app
+            .world_mut()
+            .query_filtered::<Entity, With<OffScreenIndicatorRoot>>()
+            .single(app.world())
+            .unwrap();
+
+        let ping = spawn_target(&mut app, OffScreenTarget::new(IndicatorKind::PingedEnemy).with_lifetime(0.25));
+        let ally = spawn_target(&mut app, OffScreenTarget::new(IndicatorKind::DownedAlly));
+        app.update();
+        let ping_indicator = indicator_of(&app, ping).unwrap();
+        let ally_indicator = indicator_of(&app, ally).unwrap();
+        assert_ne!(ping_indicator, ally_indicator);
+        assert_eq!(app.world().get::<ChildOf>(ping_indicator).unwrap().parent(), root);
+        assert_eq!(app.world().get::<Children>(ping_indicator).unwrap().len(), 4);
+        // No camera to project through
+        assert_eq!(*app.world().get::<Visibility>(ping_indicator).unwrap(), Visibility::Hidden);
+        assert_eq!(app.world().resource::<EntityPool<OffScreenIndicator>>().stats.created, 2);
+
+        // The ping runs out and its indicator goes back to the pool
+        for _ in 0..3 {
+            app.update();
+        }
+        assert!(app.world().get::<OffScreenTarget>(ping).is_none());
+        assert!(app.world().get::<OffScreenIndicator>(ping_indicator).is_none());
+        assert_eq!(app.world().resource::<EntityPool<OffScreenIndicator>>().available, vec![ping_indicator]);
+
+        // A new target reuses it with its parts
+        let vehicle = spawn_target(&mut app, OffScreenTarget::new(IndicatorKind::Vehicle));
+        app.update();
+        assert_eq!(indicator_of(&app, vehicle), Some(ping_indicator));
+        assert_eq!(app.world().get::<OffScreenIndicator>(ping_indicator).unwrap().target, vehicle);
+        assert_eq!(app.world().get::<Children>(ping_indicator).unwrap().len(), 4);
+        let pool = app.world().resource::<EntityPool<OffScreenIndicator>>();
+        assert_eq!((pool.stats.created, pool.stats.reused), (2, 1));
+
+        app.world_mut().resource_mut::<OffScreenIndicatorSettings>().enabled = false;
+        app.update();
+        assert_eq!(indicator_of(&app, vehicle), None);
+        assert_eq!(indicator_of(&app, ally), None);
+        assert_eq!(app.world().resource::<EntityPool<OffScreenIndicator>>().available.len(), 2);
+    }
+}
@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+use bevy::platform::collections::HashMap;
+use crate::utils::Poolable;
+
+/// What an off-screen indicator points at; picks the [`IndicatorStyle`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum IndicatorKind {
+    #[default]
+    Objective,
+    Waypoint,
+    PingedEnemy,
+    DownedAlly,
+    Vehicle,
+    Custom,
+}
+
+/// Look of the indicators of one [`IndicatorKind`]
+#[derive(Debug, Clone, Reflect)]
+pub struct IndicatorStyle {
+    pub color: Color,
+    /// Icon image; `glyph` is shown when there is none
+    pub icon: Option<Handle<Image>>,
+    pub glyph: String,
+    pub icon_size: f32,
+    pub show_distance: bool,
+    /// Keep a marker over the target while it is on screen
+    pub show_on_screen: bool,
+    /// Targets farther than this are not indicated
+    pub max_distance: f32,
+}
+
+impl IndicatorStyle {
+    pub fn new(color: Color, glyph: impl Into<String>) -> Self {
+        Self {
+            color,
+            icon: None,
+            glyph: glyph.into(),
+            icon_size: 22.0,
+            show_distance: true,
+            show_on_screen: false,
+            max_distance: 500.0,
+        }
+    }
+}
+
+impl Default for IndicatorStyle {
+    fn default() -> Self {
+        Self::new(Color::WHITE, "?")
+    }
+}
+
+/// Global off-screen indicator settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct OffScreenIndicatorSettings {
+    pub enabled: bool,
+    /// Distance (pixels) kept between clamped indicators and the screen edge
+    pub edge_margin: f32,
+    pub font_size: f32,
+    pub styles: HashMap<IndicatorKind, IndicatorStyle>,
+}
+
+impl Default for OffScreenIndicatorSettings {
+    fn default() -> Self {
+        let mut styles = HashMap::default();
+        styles.insert(IndicatorKind::Objective, IndicatorStyle {
+            show_on_screen: true,
+            max_distance: 2000.0,
+            ..IndicatorStyle::new(Color::srgb(1.0, 0.8, 0.2), "!")
+        });
+        styles.insert(IndicatorKind::Waypoint, IndicatorStyle {
+            show_on_screen: true,
+            max_distance: 2000.0,
+            ..IndicatorStyle::new(Color::srgb(0.4, 0.8, 1.0), "o")
+        });
+        styles.insert(IndicatorKind::PingedEnemy, IndicatorStyle {
+            max_distance: 150.0,
+            ..IndicatorStyle::new(Color::srgb(0.95, 0.3, 0.25), "x")
+        });
+        styles.insert(IndicatorKind::DownedAlly, IndicatorStyle {
+            show_on_screen: true,
+            ..IndicatorStyle::new(Color::srgb(0.3, 0.9, 0.4), "+")
+        });
+        styles.insert(IndicatorKind::Vehicle, IndicatorStyle::new(Color::srgb(0.7, 0.7, 0.95), "V"));
+
+        Self {
+            enabled: true,
+            edge_margin: 40.0,
+            font_size: 13.0,
+            styles,
+        }
+    }
+}
+
+impl OffScreenIndicatorSettings {
+    /// Style of `kind`, falling back to the default style
+    pub fn style(&self, kind: IndicatorKind) -> IndicatorStyle {
+        self.styles.get(&kind).cloned().unwrap_or_default()
+    }
+}
+
+/// World target shown with an edge arrow while it is off screen
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct OffScreenTarget {
+    pub kind: IndicatorKind,
+    pub enabled: bool,
+    /// Offset from the entity origin of the point indicated
+    pub offset: Vec3,
+    /// Overrides the style's glyph
+    pub glyph: Option<String>,
+    /// Overrides the style's icon
+    pub icon: Option<Handle<Image>>,
+    /// Seconds before the target removes itself (pings)
+    pub lifetime: Option<f32>,
+    /// Pooled indicator UI (read-only)
+    pub indicator: Option<Entity>,
+}
+
+impl Default for OffScreenTarget {
+    fn default() -> Self {
+        Self {
+            kind: IndicatorKind::Objective,
+            enabled: true,
+            offset: Vec3::ZERO,
+            glyph: None,
+            icon: None,
+            lifetime: None,
+            indicator: None,
+        }
+    }
+}
+
+impl OffScreenTarget {
+    pub fn new(kind: IndicatorKind) -> Self {
+        Self { kind, ..default() }
+    }
+
+    pub fn with_offset(mut self, offset: Vec3) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_glyph(mut self, glyph: impl Into<String>) -> Self {
+        self.glyph = Some(glyph.into());
+        self
+    }
+
+    pub fn with_icon(mut self, icon: Handle<Image>) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Removes the target after `seconds`
+    pub fn with_lifetime(mut self, seconds: f32) -> Self {
+        self.lifetime = Some(seconds);
+        self
+    }
+}
+
+/// Full screen container of the indicators
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct OffScreenIndicatorRoot;
+
+/// Pooled indicator UI of one target
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct OffScreenIndicator {
+    pub target: Entity,
+}
+
+impl Poolable for OffScreenIndicator {}
+
+/// Children of an indicator; kept while it waits in the pool
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct IndicatorParts {
+    pub arrow: Entity,
+    pub icon: Entity,
+    pub glyph: Entity,
+    pub distance: Entity,
+}
@@ -0,0 +1,192 @@
+//! Accessibility options per action
+//!
+//! `InputConfig::accessibility` can turn hold actions (aim, sprint, crouch...)
+//! into toggles, give an action's double-tap and other sequence bindings a
+//! longer window, and repeat an action while its button is held.
+//! `InputConfig::ui_repeat` does the same for menu navigation.
+//!
+//! The options are applied while `InputState` is built, so everything
+//! downstream reads what the player means rather than which button is down:
+//!
+//! ```rust,ignore
+//! config.accessibility.insert(InputAction::Aim, ActionAccessibility::toggle());
+//! config.accessibility.insert(InputAction::Dash, ActionAccessibility { double_tap_window: Some(0.6), ..default() });
+//!
+//! if input.intends(InputAction::Aim) { /* aiming, held or toggled */ }
+//! ```
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use super::components::InputState;
+use super::types::InputAction;
+
+/// Hold actions that can be switched to toggles
+pub const TOGGLEABLE_ACTIONS: [InputAction; 7] = [
+    InputAction::Aim,
+    InputAction::Sprint,
+    InputAction::Crouch,
+    InputAction::Block,
+    InputAction::LeanLeft,
+    InputAction::LeanRight,
+    InputAction::FreeLook,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
+pub enum ActionMode {
+    /// Active while the button is held
+    #[default]
+    Hold,
+    /// Each press switches the action on or off
+    Toggle,
+}
+
+/// Held presses fire again after `delay`, then every `interval` seconds
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct InputRepeat {
+    pub delay: f32,
+    pub interval: f32,
+}
+
+impl Default for InputRepeat {
+    fn default() -> Self {
+        Self { delay: 0.4, interval: 0.12 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActionAccessibility {
+    /// Only used for `TOGGLEABLE_ACTIONS`
+    pub mode: ActionMode,
+    /// Longest gap between the presses of this action's sequence bindings; `None` uses `InputConfig::combo_window`
+    pub double_tap_window: Option<f32>,
+    pub repeat: Option<InputRepeat>,
+}
+
+impl ActionAccessibility {
+    pub fn toggle() -> Self {
+        Self { mode: ActionMode::Toggle, ..default() }
+    }
+
+    pub fn repeating(repeat: InputRepeat) -> Self {
+        Self { repeat: Some(repeat), ..default() }
+    }
+}
+
+/// Repeat timing of one held input
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RepeatTimer {
+    held_for: f32,
+    next_fire: f32,
+}
+
+impl RepeatTimer {
+    /// Advances while held; returns whether the held input fires again this frame
+    pub fn tick(&mut self, repeat: &InputRepeat, dt: f32) -> bool {
+        if self.held_for == 0.0 {
+            self.next_fire = repeat.delay;
+        }
+        self.held_for += dt;
+        if self.held_for < self.next_fire {
+            return false;
+        }
+        self.next_fire += repeat.interval.max(0.01);
+        true
+    }
+}
+
+/// Toggle and repeat state of one input source: the global `InputState`, or a
+/// player on their own gamepad
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ActionIntents {
+    /// Toggle actions switched on
+    pub toggled: HashSet<InputAction>,
+    /// Raw held state of the toggle actions last frame
+    held: HashSet<InputAction>,
+    repeats: HashMap<InputAction, RepeatTimer>,
+}
+
+impl ActionIntents {
+    /// Turns the raw input in `state` into intent; `held` reads whether an action's buttons are down
+    pub fn apply(
+        &mut self,
+        state: &mut InputState,
+        accessibility: &HashMap<InputAction, ActionAccessibility>,
+        held: impl Fn(InputAction) -> bool,
+        dt: f32,
+    ) {
+        for (action, options) in accessibility.iter() {
+            let is_held = held(*action);
+
+            if options.mode == ActionMode::Toggle && TOGGLEABLE_ACTIONS.contains(action) {
+                if is_held && !self.held.contains(action) && !self.toggled.remove(action) {
+                    self.toggled.insert(*action);
+                }
+                if is_held {
+                    self.held.insert(*action);
+                } else {
+                    self.held.remove(action);
+                }
+                state.set_held(*action, self.toggled.contains(action));
+            }
+
+            if let Some(repeat) = options.repeat {
+                if is_held {
+                    // The first press comes through as usual
+                    if self.repeats.entry(*action).or_default().tick(&repeat, dt) {
+                        state.press_action(*action);
+                    }
+                } else {
+                    self.repeats.remove(action);
+                }
+            }
+        }
+
+        // Toggles stay on only while they are configured
+        self.toggled.retain(|action| accessibility.get(action).is_some_and(|options| options.mode == ActionMode::Toggle));
+    }
+
+    /// Switches every toggle off, for respawns and cutscenes
+    pub fn clear_toggles(&mut self) {
+        self.toggled.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{InputConfig, InputState};
+
+    #[test]
+    fn test_accessibility_toggle_and_repeat() {
+        use crate::input::{ActionAccessibility, InputAction, InputRepeat};
+
+        let mut config = InputConfig::default();
+        config.accessibility.insert(InputAction::Aim, ActionAccessibility::toggle());
+        config.accessibility.insert(InputAction::NextWeapon, ActionAccessibility::repeating(InputRepeat { delay: 0.4, interval: 0.1 }));
+        config.accessibility.insert(InputAction::Dash, ActionAccessibility { double_tap_window: Some(0.6), ..default() });
+        assert_eq!(config.sequence_window(InputAction::Dash), 0.6);
+        assert_eq!(config.sequence_window(InputAction::Jump), config.combo_window);
+
+        let mut intents = ActionIntents::default();
+        let mut frame = |held: &[InputAction], dt: f32| {
+            let mut state = InputState::default();
+            intents.apply(&mut state, &config.accessibility, |action| held.contains(&action), dt);
+            state
+        };
+
+        // A press switches aiming on until the next press
+        assert!(frame(&[InputAction::Aim], 0.1).intends(InputAction::Aim));
+        assert!(frame(&[InputAction::Aim], 0.1).intends(InputAction::Aim));
+        assert!(frame(&[], 0.1).intends(InputAction::Aim));
+        assert!(!frame(&[InputAction::Aim], 0.1).intends(InputAction::Aim));
+        assert!(!frame(&[], 0.1).intends(InputAction::Aim));
+
+        // Held, the next weapon repeats after the delay
+        let repeats: Vec<bool> = (0..3).map(|_| frame(&[InputAction::NextWeapon], 0.25).intends(InputAction::NextWeapon)).collect();
+        assert_eq!(repeats, vec![false, true, true]);
+        assert!(!frame(&[], 0.25).intends(InputAction::NextWeapon));
+        assert!(!frame(&[InputAction::NextWeapon], 0.25).intends(InputAction::NextWeapon));
+    }
+}
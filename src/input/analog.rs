@@ -0,0 +1,173 @@
+//! Analog stick shaping: dead zones, response curves and inversion.
+//!
+//! [`StickSettings`] turn the raw stick position into the value gameplay sees.
+//! `InputConfig::move_stick` and `InputConfig::look_stick` are applied when the
+//! gamepad input state is built, so movement and camera systems only ever read
+//! shaped values.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How stick deflection (past the dead zone) maps to output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum ResponseCurve {
+    #[default]
+    Linear,
+    /// `x^exponent`: above 1 gives finer control near the center
+    Exponential { exponent: f32 },
+    /// Smooth curve through `(input, output)` points in 0-1, sorted by input.
+    /// (0, 0) and (1, 1) are implied when left out.
+    Spline { points: Vec<Vec2> },
+}
+
+impl ResponseCurve {
+    /// Maps a deflection in 0-1 to an output in 0-1
+    pub fn evaluate(&self, x: f32) -> f32 {
+        let x = x.clamp(0.0, 1.0);
+        match self {
+            ResponseCurve::Linear => x,
+            ResponseCurve::Exponential { exponent } => x.powf(exponent.max(0.01)),
+            ResponseCurve::Spline { points } => evaluate_spline(points, x),
+        }
+    }
+}
+
+/// Catmull-Rom interpolation of the output between the control points
+fn evaluate_spline(points: &[Vec2], x: f32) -> f32 {
+    let mut knots = Vec::with_capacity(points.len() + 2);
+    if points.first().is_none_or(|point| point.x > 0.0) {
+        knots.push(Vec2::ZERO);
+    }
+    knots.extend(points.iter().map(|point| point.clamp(Vec2::ZERO, Vec2::ONE)));
+    if points.last().is_none_or(|point| point.x < 1.0) {
+        knots.push(Vec2::ONE);
+    }
+
+    let segment = knots.windows(2).position(|pair| x <= pair[1].x).unwrap_or(knots.len() - 2);
+    let (p1, p2) = (knots[segment], knots[segment + 1]);
+    let p0 = if segment > 0 { knots[segment - 1] } else { p1 };
+    let p3 = knots.get(segment + 2).copied().unwrap_or(p2);
+
+    let width = p2.x - p1.x;
+    if width <= f32::EPSILON {
+        return p2.y;
+    }
+    let t = (x - p1.x) / width;
+    let (t2, t3) = (t * t, t * t * t);
+    let y = 0.5
+        * (2.0 * p1.y
+            + (p2.y - p0.y) * t
+            + (2.0 * p0.y - 5.0 * p1.y + 4.0 * p2.y - p3.y) * t2
+            + (3.0 * p1.y - p0.y - 3.0 * p2.y + p3.y) * t3);
+    y.clamp(0.0, 1.0)
+}
+
+/// How the dead zone is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DeadZoneShape {
+    /// On the stick's distance from center; keeps diagonals smooth
+    #[default]
+    Radial,
+    /// On each axis separately; makes it easy to hold a straight line
+    Axial,
+}
+
+/// Shaping of one analog stick
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StickSettings {
+    /// Deflection below which the stick reads zero
+    pub dead_zone: f32,
+    /// Deflection above which the stick reads full
+    pub outer_dead_zone: f32,
+    pub dead_zone_shape: DeadZoneShape,
+    pub curve: ResponseCurve,
+    pub invert_x: bool,
+    pub invert_y: bool,
+}
+
+impl Default for StickSettings {
+    fn default() -> Self {
+        Self {
+            dead_zone: 0.15,
+            outer_dead_zone: 0.95,
+            dead_zone_shape: DeadZoneShape::Radial,
+            curve: ResponseCurve::Linear,
+            invert_x: false,
+            invert_y: false,
+        }
+    }
+}
+
+impl StickSettings {
+    /// Look stick defaults: a squared curve for precise aiming
+    pub fn look() -> Self {
+        Self {
+            curve: ResponseCurve::Exponential { exponent: 2.0 },
+            ..default()
+        }
+    }
+
+    /// Rescales a deflection between the dead zones to 0-1, then applies the curve
+    fn shape(&self, deflection: f32) -> f32 {
+        let outer = self.outer_dead_zone.max(self.dead_zone + 0.01);
+        if deflection <= self.dead_zone {
+            return 0.0;
+        }
+        let scaled = ((deflection - self.dead_zone) / (outer - self.dead_zone)).min(1.0);
+        self.curve.evaluate(scaled)
+    }
+
+    /// Shaped stick value, with a length of at most 1
+    pub fn apply(&self, raw: Vec2) -> Vec2 {
+        let mut value = match self.dead_zone_shape {
+            DeadZoneShape::Radial => {
+                let length = raw.length();
+                if length <= f32::EPSILON {
+                    Vec2::ZERO
+                } else {
+                    raw / length * self.shape(length)
+                }
+            }
+            DeadZoneShape::Axial => Vec2::new(
+                raw.x.signum() * self.shape(raw.x.abs()),
+                raw.y.signum() * self.shape(raw.y.abs()),
+            )
+            .clamp_length_max(1.0),
+        };
+        if self.invert_x {
+            value.x = -value.x;
+        }
+        if self.invert_y {
+            value.y = -value.y;
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_stick_dead_zones_and_curves() {
+        let stick = StickSettings { dead_zone: 0.2, outer_dead_zone: 1.0, ..Default::default() };
+        assert_eq!(stick.apply(Vec2::new(0.1, 0.1)), Vec2::ZERO);
+        assert!((stick.apply(Vec2::new(0.6, 0.0)).x - 0.5).abs() < 1e-5);
+        assert!((stick.apply(Vec2::new(1.0, 1.0)).length() - 1.0).abs() < 1e-5);
+
+        // Axial keeps a straight line straight despite a little drift
+        let axial = StickSettings { dead_zone_shape: DeadZoneShape::Axial, invert_y: true, ..stick.clone() };
+        let value = axial.apply(Vec2::new(0.1, 0.6));
+        assert_eq!(value.x, 0.0);
+        assert!((value.y + 0.5).abs() < 1e-5);
+
+        let squared = ResponseCurve::Exponential { exponent: 2.0 };
+        assert!((squared.evaluate(0.5) - 0.25).abs() < 1e-5);
+        let spline = ResponseCurve::Spline { points: vec![Vec2::new(0.5, 0.2)] };
+        assert!((spline.evaluate(0.5) - 0.2).abs() < 1e-5);
+        assert_eq!(spline.evaluate(0.0), 0.0);
+        assert_eq!(spline.evaluate(1.0), 1.0);
+        assert!(spline.evaluate(0.25) < spline.evaluate(0.75));
+    }
+}
@@ -0,0 +1,396 @@
+//! Input binding profiles and persistence
+//!
+//! [`InputProfiles`] keeps named binding sets ("default", "left_handed",
+//! "player_2"...) and writes them to a RON file in the user config directory.
+//! The active profile is applied to the [`InputMap`], rebinds made through
+//! `RebindState` are stored back into it and saved when `auto_save` is on, and
+//! the bindings are loaded again at startup.
+//!
+//! [`InputMap::conflicts`] lists the bindings shared by actions that are live
+//! in the same [`InputContext`], so a settings menu can warn before the player
+//! leaves it.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use super::resources::{InputContextRules, InputMap};
+use super::types::{InputAction, InputBinding, InputContext, ALL_INPUT_ACTIONS};
+use crate::utils::{EventQueue, QueueReader};
+
+const BINDINGS_VERSION: u32 = 1;
+const ALL_INPUT_CONTEXTS: [InputContext; 7] = [
+    InputContext::Gameplay,
+    InputContext::Menu,
+    InputContext::Vehicle,
+    InputContext::Map,
+    InputContext::Dialog,
+    InputContext::Console,
+    InputContext::Terminal,
+];
+
+/// Bindings of one action
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionBindings {
+    pub action: InputAction,
+    pub bindings: Vec<InputBinding>,
+}
+
+/// Named binding set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputProfile {
+    pub name: String,
+    pub bindings: Vec<ActionBindings>,
+}
+
+impl InputProfile {
+    /// Captures the bindings of a map, in action order
+    pub fn from_map(name: impl Into<String>, map: &InputMap) -> Self {
+        let bindings = ALL_INPUT_ACTIONS
+            .iter()
+            .filter_map(|action| {
+                map.bindings.get(action).map(|bindings| ActionBindings {
+                    action: *action,
+                    bindings: bindings.clone(),
+                })
+            })
+            .collect();
+        Self { name: name.into(), bindings }
+    }
+
+    /// Builds a map from the profile; actions it leaves out keep their default bindings
+    pub fn to_map(&self) -> InputMap {
+        let mut map = InputMap::default();
+        for entry in self.bindings.iter() {
+            map.bindings.insert(entry.action, entry.bindings.clone());
+        }
+        map
+    }
+}
+
+/// Binding file contents
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InputBindingsFile {
+    pub version: u32,
+    pub active_profile: String,
+    pub profiles: Vec<InputProfile>,
+}
+
+impl InputBindingsFile {
+    pub fn to_ron(&self) -> Result<String, String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| format!("Failed to serialize input bindings: {}", e))
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, String> {
+        let file: Self = ron::from_str(text).map_err(|e| format!("Invalid input bindings: {}", e))?;
+        if file.version > BINDINGS_VERSION {
+            return Err(format!("Unsupported input bindings version {}", file.version));
+        }
+        Ok(file)
+    }
+}
+
+/// Binding profiles and where they are saved
+#[derive(Resource, Debug)]
+pub struct InputProfiles {
+    pub active: String,
+    pub profiles: Vec<InputProfile>,
+    /// Binding file; relative paths are resolved against the working directory
+    pub path: String,
+    /// Save after every completed rebind
+    pub auto_save: bool,
+}
+
+impl Default for InputProfiles {
+    fn default() -> Self {
+        Self {
+            active: "default".to_string(),
+            profiles: vec![InputProfile::from_map("default", &InputMap::default())],
+            path: "config/input_bindings.ron".to_string(),
+            auto_save: true,
+        }
+    }
+}
+
+impl InputProfiles {
+    pub fn get(&self, name: &str) -> Option<&InputProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    pub fn active_profile(&self) -> Option<&InputProfile> {
+        self.get(&self.active)
+    }
+
+    /// Stores a map as `name`, replacing a profile of that name
+    pub fn store(&mut self, name: &str, map: &InputMap) {
+        let profile = InputProfile::from_map(name, map);
+        match self.profiles.iter_mut().find(|profile| profile.name == name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    pub fn to_file(&self) -> InputBindingsFile {
+        InputBindingsFile {
+            version: BINDINGS_VERSION,
+            active_profile: self.active.clone(),
+            profiles: self.profiles.clone(),
+        }
+    }
+}
+
+/// Two or more actions sharing a binding in a context where all of them are live
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingConflict {
+    pub binding: InputBinding,
+    pub actions: Vec<InputAction>,
+    pub contexts: Vec<InputContext>,
+}
+
+impl InputMap {
+    /// Every binding shared by actions live in the same context
+    pub fn conflicts(&self, rules: &InputContextRules) -> Vec<BindingConflict> {
+        let mut by_binding: HashMap<&InputBinding, Vec<InputAction>> = HashMap::new();
+        for action in ALL_INPUT_ACTIONS {
+            for binding in self.bindings.get(&action).into_iter().flatten() {
+                by_binding.entry(binding).or_default().push(action);
+            }
+        }
+
+        let mut conflicts: Vec<BindingConflict> = by_binding
+            .into_iter()
+            .filter_map(|(binding, actions)| conflict(binding, &actions, rules))
+            .collect();
+        // Stable order for settings menus
+        conflicts.sort_by_key(|conflict| ALL_INPUT_ACTIONS.iter().position(|action| *action == conflict.actions[0]));
+        conflicts
+    }
+
+    /// Conflict `binding` would create if it were added to `action`, to warn before rebinding
+    pub fn conflicts_with(&self, action: InputAction, binding: &InputBinding, rules: &InputContextRules) -> Option<BindingConflict> {
+        let mut actions = vec![action];
+        for other in ALL_INPUT_ACTIONS {
+            if other != action && self.bindings.get(&other).is_some_and(|bindings| bindings.contains(binding)) {
+                actions.push(other);
+            }
+        }
+        conflict(binding, &actions, rules)
+    }
+}
+
+fn conflict(binding: &InputBinding, actions: &[InputAction], rules: &InputContextRules) -> Option<BindingConflict> {
+    if actions.len() < 2 {
+        return None;
+    }
+    let live = |context: &InputContext, action: &InputAction| rules.is_live(*context, *action);
+
+    let mut contexts = Vec::new();
+    let mut colliding: Vec<InputAction> = Vec::new();
+    for context in ALL_INPUT_CONTEXTS {
+        let live_actions: Vec<InputAction> = actions.iter().filter(|action| live(&context, action)).copied().collect();
+        if live_actions.len() >= 2 {
+            contexts.push(context);
+            for action in live_actions {
+                if !colliding.contains(&action) {
+                    colliding.push(action);
+                }
+            }
+        }
+    }
+
+    if contexts.is_empty() {
+        return None;
+    }
+    Some(BindingConflict {
+        binding: binding.clone(),
+        actions: colliding,
+        contexts,
+    })
+}
+
+/// Binding profile control
+#[derive(Debug, Clone)]
+pub enum InputBindingsCommand {
+    Save,
+    Load,
+    /// Applies a profile to the `InputMap`
+    SwitchProfile { name: String },
+    /// Adds a profile with the current bindings
+    CreateProfile { name: String },
+    DeleteProfile { name: String },
+    /// Restores the default bindings in the active profile
+    ResetToDefaults,
+}
+
+/// Reported by the binding profiles
+#[derive(Debug, Clone)]
+pub enum InputBindingsStatus {
+    Saved { path: String },
+    Loaded { path: String, profiles: usize },
+    ProfileSwitched { name: String },
+    Failed { error: String },
+}
+
+pub type InputBindingsCommandQueue = EventQueue<InputBindingsCommand>;
+pub type InputBindingsStatusQueue = EventQueue<InputBindingsStatus>;
+
+fn save_bindings(profiles: &InputProfiles) -> Result<String, String> {
+    let path = PathBuf::from(&profiles.path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create input config directory: {}", e))?;
+    }
+    let text = profiles.to_file().to_ron()?;
+    std::fs::write(&path, text)
+        .map_err(|e| format!("Failed to write input bindings {}: {}", path.display(), e))?;
+    Ok(path.display().to_string())
+}
+
+fn load_bindings(profiles: &mut InputProfiles) -> Result<usize, String> {
+    let text = std::fs::read_to_string(&profiles.path)
+        .map_err(|e| format!("Failed to read input bindings {}: {}", profiles.path, e))?;
+    let file = InputBindingsFile::from_ron(&text)?;
+    if file.profiles.is_empty() {
+        return Err(format!("No binding profiles in {}", profiles.path));
+    }
+    profiles.profiles = file.profiles;
+    profiles.active = if profiles.get(&file.active_profile).is_some() {
+        file.active_profile
+    } else {
+        profiles.profiles[0].name.clone()
+    };
+    Ok(profiles.profiles.len())
+}
+
+/// System to load the saved bindings at startup
+pub fn load_input_bindings_on_startup(mut profiles: ResMut<InputProfiles>, mut input_map: ResMut<InputMap>) {
+    if !std::path::Path::new(&profiles.path).exists() {
+        return;
+    }
+    match load_bindings(&mut profiles) {
+        Ok(_) => {
+            if let Some(profile) = profiles.active_profile() {
+                *input_map = profile.to_map();
+            }
+        }
+        Err(error) => warn!("{}", error),
+    }
+}
+
+/// System to apply binding profile commands
+pub fn handle_input_bindings_commands(
+    mut commands: QueueReader<InputBindingsCommand>,
+    mut profiles: ResMut<InputProfiles>,
+    mut input_map: ResMut<InputMap>,
+    mut status: ResMut<InputBindingsStatusQueue>,
+) {
+    for command in commands.read() {
+        match command {
+            InputBindingsCommand::Save => {
+                let active = profiles.active.clone();
+                profiles.store(&active, &input_map);
+                match save_bindings(&profiles) {
+                    Ok(path) => status.send(InputBindingsStatus::Saved { path }),
+                    Err(error) => status.send(InputBindingsStatus::Failed { error }),
+                }
+            }
+            InputBindingsCommand::Load => match load_bindings(&mut profiles) {
+                Ok(count) => {
+                    if let Some(profile) = profiles.active_profile() {
+                        *input_map = profile.to_map();
+                    }
+                    status.send(InputBindingsStatus::Loaded { path: profiles.path.clone(), profiles: count });
+                }
+                Err(error) => status.send(InputBindingsStatus::Failed { error }),
+            },
+            InputBindingsCommand::SwitchProfile { name } => {
+                let Some(map) = profiles.get(name).map(InputProfile::to_map) else {
+                    status.send(InputBindingsStatus::Failed { error: format!("Unknown binding profile {}", name) });
+                    continue;
+                };
+                // Unsaved rebinds stay with the profile they were made in
+                let active = profiles.active.clone();
+                profiles.store(&active, &input_map);
+                profiles.active = name.clone();
+                *input_map = map;
+                status.send(InputBindingsStatus::ProfileSwitched { name: name.clone() });
+            }
+            InputBindingsCommand::CreateProfile { name } => {
+                if profiles.get(name).is_some() {
+                    status.send(InputBindingsStatus::Failed { error: format!("Binding profile {} already exists", name) });
+                    continue;
+                }
+                profiles.store(name, &input_map);
+            }
+            InputBindingsCommand::DeleteProfile { name } => {
+                if *name == profiles.active || profiles.profiles.len() <= 1 {
+                    status.send(InputBindingsStatus::Failed { error: format!("Cannot delete binding profile {}", name) });
+                    continue;
+                }
+                profiles.profiles.retain(|profile| profile.name != *name);
+            }
+            InputBindingsCommand::ResetToDefaults => {
+                *input_map = InputMap::default();
+                let active = profiles.active.clone();
+                profiles.store(&active, &input_map);
+            }
+        }
+    }
+}
+
+/// System to store finished rebinds in the active profile and save them
+pub fn persist_rebinds(
+    rebind_state: Res<super::resources::RebindState>,
+    input_map: Res<InputMap>,
+    mut profiles: ResMut<InputProfiles>,
+    mut status: ResMut<InputBindingsStatusQueue>,
+    mut was_rebinding: Local<bool>,
+) {
+    let rebinding = rebind_state.action.is_some();
+    let finished = *was_rebinding && !rebinding;
+    *was_rebinding = rebinding;
+    if !finished {
+        return;
+    }
+
+    let active = profiles.active.clone();
+    profiles.store(&active, &input_map);
+    if profiles.auto_save {
+        if let Err(error) = save_bindings(&profiles) {
+            status.send(InputBindingsStatus::Failed { error });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{InputAction, InputBinding, InputContextRules, InputMap};
+
+    #[test]
+    fn test_input_bindings_roundtrip_and_conflicts() {
+        let mut map = InputMap::default();
+        map.bindings.insert(InputAction::Jump, vec![InputBinding::Mouse(MouseButton::Middle)]);
+        let mut profiles = InputProfiles::default();
+        profiles.store("left_handed", &map);
+
+        let text = profiles.to_file().to_ron().unwrap();
+        let file = InputBindingsFile::from_ron(&text).unwrap();
+        assert_eq!(file.profiles.len(), 2);
+        let restored = file.profiles[1].to_map();
+        assert_eq!(restored.bindings[&InputAction::Jump], vec![InputBinding::Mouse(MouseButton::Middle)]);
+
+        let rules = InputContextRules::default();
+        let conflicts = map.conflicts(&rules);
+        let lean = conflicts
+            .iter()
+            .find(|conflict| conflict.binding == InputBinding::Key(KeyCode::KeyE))
+            .unwrap();
+        assert!(lean.actions.contains(&InputAction::Interact));
+        assert!(lean.actions.contains(&InputAction::LeanRight));
+        assert!(map
+            .conflicts_with(InputAction::Reload, &InputBinding::Key(KeyCode::F12), &rules)
+            .is_none());
+    }
+}
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use super::types::InputAction;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
 pub enum InputDevice {
     KeyboardMouse,
     Gamepad { id: usize },
@@ -13,7 +13,7 @@ impl Default for InputDevice {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Reflect)]
 pub struct InputLocks {
     pub movement: bool,
     pub camera: bool,
@@ -30,7 +30,8 @@ impl Default for InputLocks {
     }
 }
 
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
 pub struct PlayerInputSettings {
     pub player_id: u8,
     pub device: InputDevice,
@@ -50,11 +51,14 @@ impl Default for PlayerInputSettings {
 }
 
 /// Global input state resource and per-entity input component
-#[derive(Component, Resource, Debug, Reflect, Clone)]
+#[derive(Component, Resource, Debug, Reflect, Clone, serde::Serialize, serde::Deserialize)]
 #[reflect(Component, Resource)]
+#[serde(default)]
 pub struct InputState {
     pub movement: Vec2,
     pub look: Vec2,
+    /// Camera turn in degrees from gyro aiming and flick stick, in the directions of `look`
+    pub aim_turn: Vec2,
     pub jump_pressed: bool,
     pub crouch_pressed: bool,
     pub sprint_pressed: bool,
@@ -64,6 +68,11 @@ pub struct InputState {
     pub lean_right: bool,
     pub lock_on_pressed: bool,
     pub attack_pressed: bool,
+    pub attack_held: bool,
+    pub attack_released: bool,
+    pub special_move_pressed: bool,
+    pub dash_pressed: bool,
+    pub take_cover_pressed: bool,
     pub block_pressed: bool,
     pub switch_camera_mode_pressed: bool,
     pub fire_pressed: bool,
@@ -72,7 +81,9 @@ pub struct InputState {
     pub next_weapon_pressed: bool,
     pub prev_weapon_pressed: bool,
     pub toggle_inventory_pressed: bool,
+    pub toggle_hud_pressed: bool,
     pub side_switch_pressed: bool,
+    pub pause_pressed: bool,
     
     // Stealth/Utility
     pub hide_pressed: bool,
@@ -86,6 +97,12 @@ pub struct InputState {
     pub ability_use_released: bool,
     pub ability_use_held: bool,
     pub select_ability: Option<usize>,
+
+    pub emote_wheel_held: bool,
+    pub emote_wheel_released: bool,
+
+    /// Orbit the camera without turning the character
+    pub free_look_held: bool,
     
     pub select_weapon: Option<usize>,
     pub enabled: bool,
@@ -96,6 +113,7 @@ impl Default for InputState {
         Self {
             movement: Vec2::ZERO,
             look: Vec2::ZERO,
+            aim_turn: Vec2::ZERO,
             jump_pressed: false,
             crouch_pressed: false,
             sprint_pressed: false,
@@ -105,6 +123,11 @@ impl Default for InputState {
             lean_right: false,
             lock_on_pressed: false,
             attack_pressed: false,
+            attack_held: false,
+            attack_released: false,
+            special_move_pressed: false,
+            dash_pressed: false,
+            take_cover_pressed: false,
             block_pressed: false,
             switch_camera_mode_pressed: false,
             fire_pressed: false,
@@ -113,7 +136,9 @@ impl Default for InputState {
             next_weapon_pressed: false,
             prev_weapon_pressed: false,
             toggle_inventory_pressed: false,
+            toggle_hud_pressed: false,
             side_switch_pressed: false,
+            pause_pressed: false,
             hide_pressed: false,
             peek_pressed: false,
             corner_lean_pressed: false,
@@ -124,6 +149,9 @@ impl Default for InputState {
             ability_use_released: false,
             ability_use_held: false,
             select_ability: None,
+            emote_wheel_held: false,
+            emote_wheel_released: false,
+            free_look_held: false,
             select_weapon: None,
             enabled: true,
         }
@@ -136,6 +164,7 @@ impl InputState {
         if !enabled {
             self.movement = Vec2::ZERO;
             self.look = Vec2::ZERO;
+            self.aim_turn = Vec2::ZERO;
             self.jump_pressed = false;
             self.crouch_pressed = false;
             self.sprint_pressed = false;
@@ -145,6 +174,11 @@ impl InputState {
             self.lean_right = false;
             self.lock_on_pressed = false;
             self.attack_pressed = false;
+            self.attack_held = false;
+            self.attack_released = false;
+            self.special_move_pressed = false;
+            self.dash_pressed = false;
+            self.take_cover_pressed = false;
             self.block_pressed = false;
             self.switch_camera_mode_pressed = false;
             self.fire_pressed = false;
@@ -153,7 +187,9 @@ impl InputState {
             self.next_weapon_pressed = false;
             self.prev_weapon_pressed = false;
             self.toggle_inventory_pressed = false;
+            self.toggle_hud_pressed = false;
             self.side_switch_pressed = false;
+            self.pause_pressed = false;
             self.hide_pressed = false;
             self.peek_pressed = false;
             self.corner_lean_pressed = false;
@@ -164,10 +200,102 @@ impl InputState {
             self.ability_use_released = false;
             self.ability_use_held = false;
             self.select_ability = None;
+            self.emote_wheel_held = false;
+            self.emote_wheel_released = false;
+            self.free_look_held = false;
             self.select_weapon = None;
         }
     }
 
+    /// Merges a newer frame into input no fixed step has read yet: held buttons
+    /// and the movement axis take the newer values, look deltas add up, and
+    /// just pressed/released buttons and selections stay set until read
+    pub fn latch(&mut self, newer: &InputState) {
+        self.movement = newer.movement;
+        self.look += newer.look;
+        self.aim_turn += newer.aim_turn;
+        self.enabled = newer.enabled;
+
+        // Continuous Input
+        self.crouch_pressed = newer.crouch_pressed;
+        self.sprint_pressed = newer.sprint_pressed;
+        self.aim_pressed = newer.aim_pressed;
+        self.lean_left = newer.lean_left;
+        self.lean_right = newer.lean_right;
+        self.block_pressed = newer.block_pressed;
+        self.fire_pressed = newer.fire_pressed;
+        self.attack_held = newer.attack_held;
+        self.ability_use_held = newer.ability_use_held;
+        self.emote_wheel_held = newer.emote_wheel_held;
+        self.free_look_held = newer.free_look_held;
+
+        // Just Pressed Input
+        self.jump_pressed |= newer.jump_pressed;
+        self.interact_pressed |= newer.interact_pressed;
+        self.lock_on_pressed |= newer.lock_on_pressed;
+        self.attack_pressed |= newer.attack_pressed;
+        self.attack_released |= newer.attack_released;
+        self.special_move_pressed |= newer.special_move_pressed;
+        self.dash_pressed |= newer.dash_pressed;
+        self.take_cover_pressed |= newer.take_cover_pressed;
+        self.switch_camera_mode_pressed |= newer.switch_camera_mode_pressed;
+        self.fire_just_pressed |= newer.fire_just_pressed;
+        self.reload_pressed |= newer.reload_pressed;
+        self.next_weapon_pressed |= newer.next_weapon_pressed;
+        self.prev_weapon_pressed |= newer.prev_weapon_pressed;
+        self.toggle_inventory_pressed |= newer.toggle_inventory_pressed;
+        self.toggle_hud_pressed |= newer.toggle_hud_pressed;
+        self.side_switch_pressed |= newer.side_switch_pressed;
+        self.pause_pressed |= newer.pause_pressed;
+        self.hide_pressed |= newer.hide_pressed;
+        self.peek_pressed |= newer.peek_pressed;
+        self.corner_lean_pressed |= newer.corner_lean_pressed;
+        self.reset_camera_pressed |= newer.reset_camera_pressed;
+        self.zoom_in_pressed |= newer.zoom_in_pressed;
+        self.zoom_out_pressed |= newer.zoom_out_pressed;
+        self.ability_use_pressed |= newer.ability_use_pressed;
+        self.ability_use_released |= newer.ability_use_released;
+        self.emote_wheel_released |= newer.emote_wheel_released;
+        self.select_weapon = newer.select_weapon.or(self.select_weapon);
+        self.select_ability = newer.select_ability.or(self.select_ability);
+    }
+
+    /// Clears look deltas, just pressed/released buttons and selections,
+    /// keeping the movement axis and held buttons; for fixed steps after the
+    /// first one that read a frame's input
+    pub fn clear_edges(&mut self) {
+        self.look = Vec2::ZERO;
+        self.aim_turn = Vec2::ZERO;
+        self.jump_pressed = false;
+        self.interact_pressed = false;
+        self.lock_on_pressed = false;
+        self.attack_pressed = false;
+        self.attack_released = false;
+        self.special_move_pressed = false;
+        self.dash_pressed = false;
+        self.take_cover_pressed = false;
+        self.switch_camera_mode_pressed = false;
+        self.fire_just_pressed = false;
+        self.reload_pressed = false;
+        self.next_weapon_pressed = false;
+        self.prev_weapon_pressed = false;
+        self.toggle_inventory_pressed = false;
+        self.toggle_hud_pressed = false;
+        self.side_switch_pressed = false;
+        self.pause_pressed = false;
+        self.hide_pressed = false;
+        self.peek_pressed = false;
+        self.corner_lean_pressed = false;
+        self.reset_camera_pressed = false;
+        self.zoom_in_pressed = false;
+        self.zoom_out_pressed = false;
+        self.ability_use_pressed = false;
+        self.ability_use_released = false;
+        self.emote_wheel_released = false;
+        self.select_weapon = None;
+        self.select_ability = None;
+    }
+
     /// Check if an action was just pressed (dynamic check)
     pub fn is_action_just_pressed(&self, action: InputAction) -> bool {
         match action {
@@ -183,10 +311,164 @@ impl InputState {
             InputAction::CornerLean => self.corner_lean_pressed,
             InputAction::ZoomIn => self.zoom_in_pressed,
             InputAction::ZoomOut => self.zoom_out_pressed,
+            InputAction::Pause => self.pause_pressed,
+            InputAction::Dash => self.dash_pressed,
+            InputAction::TakeCover => self.take_cover_pressed,
             _ => false,
         }
     }
 
+    /// Whether the player means `action` this frame: held (or toggled on) for
+    /// held actions, just pressed for the rest. Gameplay reads this rather than
+    /// the raw fields so `InputConfig::accessibility` applies everywhere
+    pub fn intends(&self, action: InputAction) -> bool {
+        match action {
+            InputAction::MoveForward => self.movement.y > 0.1,
+            InputAction::MoveBackward => self.movement.y < -0.1,
+            InputAction::MoveLeft => self.movement.x < -0.1,
+            InputAction::MoveRight => self.movement.x > 0.1,
+            InputAction::Sprint => self.sprint_pressed,
+            InputAction::Crouch => self.crouch_pressed,
+            InputAction::Aim => self.aim_pressed,
+            InputAction::LeanLeft => self.lean_left,
+            InputAction::LeanRight => self.lean_right,
+            InputAction::Block => self.block_pressed,
+            InputAction::Attack => self.attack_held || self.attack_pressed,
+            InputAction::Fire => self.fire_pressed,
+            InputAction::AbilityUse => self.ability_use_held || self.ability_use_pressed,
+            InputAction::EmoteWheel => self.emote_wheel_held,
+            InputAction::FreeLook => self.free_look_held,
+            InputAction::SpecialMove => self.special_move_pressed,
+            InputAction::NextWeapon => self.next_weapon_pressed,
+            InputAction::PrevWeapon => self.prev_weapon_pressed,
+            InputAction::ToggleInventory => self.toggle_inventory_pressed,
+            InputAction::ToggleHud => self.toggle_hud_pressed,
+            _ => self.is_action_just_pressed(action),
+        }
+    }
+
+    /// Sets a held action, e.g. from a toggle
+    pub fn set_held(&mut self, action: InputAction, held: bool) {
+        match action {
+            InputAction::Sprint => self.sprint_pressed = held,
+            InputAction::Crouch => self.crouch_pressed = held,
+            InputAction::Aim => self.aim_pressed = held,
+            InputAction::LeanLeft => self.lean_left = held,
+            InputAction::LeanRight => self.lean_right = held,
+            InputAction::Block => self.block_pressed = held,
+            InputAction::FreeLook => self.free_look_held = held,
+            _ => {}
+        }
+    }
+
+    /// Presses `action` again this frame, e.g. from an input repeat
+    pub fn press_action(&mut self, action: InputAction) {
+        match action {
+            InputAction::Jump => self.jump_pressed = true,
+            InputAction::Interact => self.interact_pressed = true,
+            InputAction::LockOn => self.lock_on_pressed = true,
+            InputAction::Attack => self.attack_pressed = true,
+            InputAction::SpecialMove => self.special_move_pressed = true,
+            InputAction::Dash => self.dash_pressed = true,
+            InputAction::TakeCover => self.take_cover_pressed = true,
+            InputAction::SwitchCameraMode => self.switch_camera_mode_pressed = true,
+            InputAction::Fire => self.fire_just_pressed = true,
+            InputAction::Reload => self.reload_pressed = true,
+            InputAction::ResetCamera => self.reset_camera_pressed = true,
+            InputAction::NextWeapon => self.next_weapon_pressed = true,
+            InputAction::PrevWeapon => self.prev_weapon_pressed = true,
+            InputAction::ToggleInventory => self.toggle_inventory_pressed = true,
+            InputAction::ToggleHud => self.toggle_hud_pressed = true,
+            InputAction::SideSwitch => self.side_switch_pressed = true,
+            InputAction::Pause => self.pause_pressed = true,
+            InputAction::Hide => self.hide_pressed = true,
+            InputAction::Peek => self.peek_pressed = true,
+            InputAction::CornerLean => self.corner_lean_pressed = true,
+            InputAction::ZoomIn => self.zoom_in_pressed = true,
+            InputAction::ZoomOut => self.zoom_out_pressed = true,
+            InputAction::AbilityUse => self.ability_use_pressed = true,
+            _ => {}
+        }
+    }
+
+    /// Drops everything `action` contributes to this frame's input
+    pub fn clear_action(&mut self, action: InputAction) {
+        let clear_slot = |slot: usize, selected: &mut Option<usize>| {
+            if *selected == Some(slot) {
+                *selected = None;
+            }
+        };
+        match action {
+            InputAction::MoveForward => self.movement.y = self.movement.y.min(0.0),
+            InputAction::MoveBackward => self.movement.y = self.movement.y.max(0.0),
+            InputAction::MoveLeft => self.movement.x = self.movement.x.max(0.0),
+            InputAction::MoveRight => self.movement.x = self.movement.x.min(0.0),
+            InputAction::Jump => self.jump_pressed = false,
+            InputAction::Sprint => self.sprint_pressed = false,
+            InputAction::Crouch => self.crouch_pressed = false,
+            InputAction::Interact => self.interact_pressed = false,
+            InputAction::Aim => self.aim_pressed = false,
+            InputAction::LeanLeft => self.lean_left = false,
+            InputAction::LeanRight => self.lean_right = false,
+            InputAction::Attack => {
+                self.attack_pressed = false;
+                self.attack_held = false;
+                self.attack_released = false;
+            }
+            InputAction::Block => self.block_pressed = false,
+            InputAction::SwitchCameraMode => self.switch_camera_mode_pressed = false,
+            InputAction::Fire => {
+                self.fire_pressed = false;
+                self.fire_just_pressed = false;
+            }
+            InputAction::Reload => self.reload_pressed = false,
+            InputAction::NextWeapon => self.next_weapon_pressed = false,
+            InputAction::PrevWeapon => self.prev_weapon_pressed = false,
+            InputAction::ToggleInventory => self.toggle_inventory_pressed = false,
+            InputAction::SelectWeapon1 => clear_slot(0, &mut self.select_weapon),
+            InputAction::SelectWeapon2 => clear_slot(1, &mut self.select_weapon),
+            InputAction::SelectWeapon3 => clear_slot(2, &mut self.select_weapon),
+            InputAction::SelectWeapon4 => clear_slot(3, &mut self.select_weapon),
+            InputAction::SelectWeapon5 => clear_slot(4, &mut self.select_weapon),
+            InputAction::SelectWeapon6 => clear_slot(5, &mut self.select_weapon),
+            InputAction::SelectWeapon7 => clear_slot(6, &mut self.select_weapon),
+            InputAction::SelectWeapon8 => clear_slot(7, &mut self.select_weapon),
+            InputAction::SelectWeapon9 => clear_slot(8, &mut self.select_weapon),
+            InputAction::SelectWeapon0 => clear_slot(9, &mut self.select_weapon),
+            InputAction::Hide => self.hide_pressed = false,
+            InputAction::Peek => self.peek_pressed = false,
+            InputAction::CornerLean => self.corner_lean_pressed = false,
+            InputAction::ResetCamera => self.reset_camera_pressed = false,
+            InputAction::LockOn => self.lock_on_pressed = false,
+            InputAction::ZoomIn => self.zoom_in_pressed = false,
+            InputAction::ZoomOut => self.zoom_out_pressed = false,
+            InputAction::SideSwitch => self.side_switch_pressed = false,
+            InputAction::AbilityUse => {
+                self.ability_use_pressed = false;
+                self.ability_use_released = false;
+                self.ability_use_held = false;
+            }
+            InputAction::AbilitySelect1 => clear_slot(0, &mut self.select_ability),
+            InputAction::AbilitySelect2 => clear_slot(1, &mut self.select_ability),
+            InputAction::AbilitySelect3 => clear_slot(2, &mut self.select_ability),
+            InputAction::AbilitySelect4 => clear_slot(3, &mut self.select_ability),
+            InputAction::AbilitySelect5 => clear_slot(4, &mut self.select_ability),
+            InputAction::AbilitySelect6 => clear_slot(5, &mut self.select_ability),
+            InputAction::AbilitySelect7 => clear_slot(6, &mut self.select_ability),
+            InputAction::AbilitySelect8 => clear_slot(7, &mut self.select_ability),
+            InputAction::EmoteWheel => {
+                self.emote_wheel_held = false;
+                self.emote_wheel_released = false;
+            }
+            InputAction::SpecialMove => self.special_move_pressed = false,
+            InputAction::ToggleHud => self.toggle_hud_pressed = false,
+            InputAction::Pause => self.pause_pressed = false,
+            InputAction::Dash => self.dash_pressed = false,
+            InputAction::TakeCover => self.take_cover_pressed = false,
+            InputAction::FreeLook => self.free_look_held = false,
+        }
+    }
+
     /// Get mouse axis for camera control
     pub fn get_mouse_axis(&self) -> Vec2 {
         self.look
@@ -203,22 +485,29 @@ impl InputState {
             self.jump_pressed = false;
             self.crouch_pressed = false;
             self.sprint_pressed = false;
+            self.dash_pressed = false;
+            self.take_cover_pressed = false;
         }
 
         if locks.camera {
             self.look = Vec2::ZERO;
+            self.aim_turn = Vec2::ZERO;
             self.aim_pressed = false;
             self.lean_left = false;
             self.lean_right = false;
             self.reset_camera_pressed = false;
             self.zoom_in_pressed = false;
             self.zoom_out_pressed = false;
+            self.free_look_held = false;
         }
 
         if locks.actions {
             self.interact_pressed = false;
             self.lock_on_pressed = false;
             self.attack_pressed = false;
+            self.attack_held = false;
+            self.attack_released = false;
+            self.special_move_pressed = false;
             self.block_pressed = false;
             self.switch_camera_mode_pressed = false;
             self.fire_pressed = false;
@@ -235,6 +524,8 @@ impl InputState {
             self.ability_use_released = false;
             self.ability_use_held = false;
             self.select_ability = None;
+            self.emote_wheel_held = false;
+            self.emote_wheel_released = false;
             self.select_weapon = None;
         }
     }
@@ -0,0 +1,455 @@
+//! Developer console
+//!
+//! The backtick key drops down a console that runs commands typed as
+//! `name arg arg "quoted arg"`. Each module registers its own commands
+//! (`give_item`, `set_health`, `teleport`, `spawn_vehicle`, `toggle_ai`...)
+//! and games add theirs the same way. A command is a system that takes the
+//! arguments and returns the line to print, or an error:
+//!
+//! ```rust,ignore
+//! app.add_console_command(
+//!     ConsoleCommand::new("heal_all", "Restores every character's health"),
+//!     |In(_args): In<ConsoleArgs>, mut query: Query<&mut Health>| -> ConsoleResult {
+//!         query.iter_mut().for_each(|mut health| health.current = health.maximum);
+//!         Ok("Healed".to_string())
+//!     },
+//! );
+//! ```
+//!
+//! Scripts, cheats and tests run command lines through [`ConsoleRequestQueue`]
+//! without opening the console, and read the results from [`ConsoleOutputQueue`].
+//! While the console is open, the `Console` input context keeps the keyboard
+//! away from the game.
+
+use bevy::ecs::system::SystemId;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use super::resources::InputContextStack;
+use super::types::InputContext;
+use crate::utils::{EventQueue, QueueCursor};
+
+/// Arguments of a command, after its name
+pub type ConsoleArgs = Vec<String>;
+
+/// Text to print, or the error
+pub type ConsoleResult = Result<String, String>;
+
+/// A command the console can run
+#[derive(Debug, Clone)]
+pub struct ConsoleCommand {
+    pub name: String,
+    /// Arguments shown by `help`, e.g. `<item_id> [quantity]`
+    pub usage: String,
+    pub description: String,
+}
+
+impl ConsoleCommand {
+    pub fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        Self { name: name.into(), usage: String::new(), description: description.into() }
+    }
+
+    pub fn with_usage(mut self, usage: impl Into<String>) -> Self {
+        self.usage = usage.into();
+        self
+    }
+}
+
+/// Registered commands by name
+#[derive(Resource, Default)]
+pub struct ConsoleCommands {
+    commands: BTreeMap<String, (ConsoleCommand, SystemId<In<ConsoleArgs>, ConsoleResult>)>,
+}
+
+impl ConsoleCommands {
+    pub fn get(&self, name: &str) -> Option<&ConsoleCommand> {
+        self.commands.get(name).map(|(command, _)| command)
+    }
+
+    /// Commands sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = &ConsoleCommand> {
+        self.commands.values().map(|(command, _)| command)
+    }
+
+    /// Registered names starting with `prefix`
+    pub fn complete<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a str> {
+        self.commands.keys().filter(move |name| name.starts_with(prefix)).map(String::as_str)
+    }
+}
+
+pub trait ConsoleAppExt {
+    /// Registers `system` under `command.name`, replacing any command of that name
+    fn add_console_command<M>(
+        &mut self,
+        command: ConsoleCommand,
+        system: impl IntoSystem<In<ConsoleArgs>, ConsoleResult, M> + 'static,
+    ) -> &mut Self;
+}
+
+impl ConsoleAppExt for App {
+    fn add_console_command<M>(
+        &mut self,
+        command: ConsoleCommand,
+        system: impl IntoSystem<In<ConsoleArgs>, ConsoleResult, M> + 'static,
+    ) -> &mut Self {
+        let world = self.world_mut();
+        let id = world.register_system(system);
+        let previous = world
+            .get_resource_or_init::<ConsoleCommands>()
+            .commands
+            .insert(command.name.clone(), (command, id));
+        if let Some((_, previous)) = previous {
+            let _ = world.unregister_system(previous);
+        }
+        self
+    }
+}
+
+/// A command line to run, from the console or any other system
+#[derive(Debug, Clone)]
+pub struct ConsoleRequest {
+    pub line: String,
+}
+
+pub type ConsoleRequestQueue = EventQueue<ConsoleRequest>;
+
+/// Outcome of a [`ConsoleRequest`]
+#[derive(Debug, Clone)]
+pub struct ConsoleOutput {
+    pub line: String,
+    pub result: ConsoleResult,
+}
+
+pub type ConsoleOutputQueue = EventQueue<ConsoleOutput>;
+
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct ConsoleSettings {
+    /// Turn off to ship without the console
+    pub enabled: bool,
+    pub toggle_key: KeyCode,
+    pub max_log_lines: usize,
+    pub max_history: usize,
+    /// Share of the window height the console covers
+    pub height: f32,
+}
+
+impl Default for ConsoleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            toggle_key: KeyCode::Backquote,
+            max_log_lines: 200,
+            max_history: 50,
+            height: 0.4,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct ConsoleState {
+    pub open: bool,
+    /// Line being typed
+    pub input: String,
+    pub log: Vec<String>,
+    /// Submitted lines, oldest first
+    pub history: Vec<String>,
+    /// Entry of `history` shown by the up and down arrows
+    history_index: Option<usize>,
+}
+
+impl ConsoleState {
+    pub fn print(&mut self, line: impl Into<String>, max_lines: usize) {
+        self.log.push(line.into());
+        let excess = self.log.len().saturating_sub(max_lines);
+        self.log.drain(..excess);
+    }
+
+    fn browse_history(&mut self, older: bool) {
+        if self.history.is_empty() {
+            return;
+        }
+        let index = match (self.history_index, older) {
+            (None, true) => Some(self.history.len() - 1),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) => (index + 1 < self.history.len()).then_some(index + 1),
+        };
+        self.history_index = index;
+        self.input = index.map(|index| self.history[index].clone()).unwrap_or_default();
+    }
+}
+
+/// Console panel
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ConsoleRoot;
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ConsoleLogText;
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ConsoleInputText;
+
+/// Splits a command line into words; double quotes group words with spaces
+pub fn parse_console_line(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quoted = false;
+    let mut has_word = false;
+    for c in line.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                has_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if has_word {
+                    words.push(std::mem::take(&mut word));
+                    has_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                has_word = true;
+            }
+        }
+    }
+    if has_word {
+        words.push(word);
+    }
+    words
+}
+
+/// Runs one command line against the world
+pub fn run_console_command(world: &mut World, line: &str) -> ConsoleResult {
+    let mut words = parse_console_line(line);
+    if words.is_empty() {
+        return Ok(String::new());
+    }
+    let name = words.remove(0);
+    let id = world
+        .get_resource::<ConsoleCommands>()
+        .and_then(|commands| commands.commands.get(&name).map(|(_, id)| *id))
+        .ok_or_else(|| format!("Unknown command '{}', try 'help'", name))?;
+    world
+        .run_system_with(id, words)
+        .map_err(|error| format!("{}: {}", name, error))?
+}
+
+/// Parses argument `index`, naming it in the error
+pub fn console_arg<T: std::str::FromStr>(args: &[String], index: usize, name: &str) -> Result<T, String> {
+    let arg = args.get(index).ok_or_else(|| format!("Missing {}", name))?;
+    arg.parse().map_err(|_| format!("Invalid {} '{}'", name, arg))
+}
+
+/// Built-in `help [command]`
+pub fn help_command(In(args): In<ConsoleArgs>, commands: Res<ConsoleCommands>) -> ConsoleResult {
+    let line = |command: &ConsoleCommand| format!("{} {} - {}", command.name, command.usage, command.description);
+    match args.first() {
+        Some(name) => commands.get(name).map(line).ok_or_else(|| format!("Unknown command '{}'", name)),
+        None => Ok(commands.iter().map(line).collect::<Vec<_>>().join("\n")),
+    }
+}
+
+/// Built-in `clear`
+pub fn clear_command(In(_args): In<ConsoleArgs>, mut state: ResMut<ConsoleState>) -> ConsoleResult {
+    state.log.clear();
+    Ok(String::new())
+}
+
+/// System to open and close the console with its key
+pub fn toggle_console(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<ConsoleSettings>,
+    mut state: ResMut<ConsoleState>,
+    mut context_stack: ResMut<InputContextStack>,
+) {
+    let toggled = settings.enabled && keyboard.just_pressed(settings.toggle_key);
+    let close = state.open && (!settings.enabled || keyboard.just_pressed(KeyCode::Escape));
+    if toggled || close {
+        state.open = !state.open && !close;
+    }
+    if state.open != context_stack.contains(InputContext::Console) {
+        context_stack.set_active(InputContext::Console, state.open);
+    }
+}
+
+/// System to type into the open console; submitted lines become [`ConsoleRequest`]s
+pub fn edit_console_input(
+    mut keys: MessageReader<KeyboardInput>,
+    settings: Res<ConsoleSettings>,
+    commands: Option<Res<ConsoleCommands>>,
+    mut state: ResMut<ConsoleState>,
+    mut requests: ResMut<ConsoleRequestQueue>,
+) {
+    if !state.open {
+        keys.clear();
+        return;
+    }
+
+    for key in keys.read() {
+        if key.state != ButtonState::Pressed || key.key_code == settings.toggle_key {
+            continue;
+        }
+        match &key.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut state.input);
+                state.history_index = None;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                state.print(format!("> {}", line), settings.max_log_lines);
+                if state.history.last() != Some(&line) {
+                    state.history.push(line.clone());
+                    let excess = state.history.len().saturating_sub(settings.max_history);
+                    state.history.drain(..excess);
+                }
+                requests.send(ConsoleRequest { line });
+            }
+            Key::Backspace => {
+                state.input.pop();
+            }
+            Key::ArrowUp => state.browse_history(true),
+            Key::ArrowDown => state.browse_history(false),
+            Key::Tab => {
+                // Completes the command name when only one matches
+                let Some(commands) = commands.as_ref() else { continue };
+                if state.input.contains(' ') {
+                    continue;
+                }
+                let mut matches = commands.complete(&state.input);
+                if let (Some(name), None) = (matches.next(), matches.next()) {
+                    state.input = format!("{} ", name);
+                }
+            }
+            _ => {
+                if let Some(text) = &key.text {
+                    state.input.extend(text.chars().filter(|c| !c.is_control()));
+                }
+            }
+        }
+    }
+}
+
+/// System to run the requested command lines
+pub fn execute_console_requests(world: &mut World, mut cursor: Local<QueueCursor<ConsoleRequest>>) {
+    let lines: Vec<String> = match world.get_resource::<ConsoleRequestQueue>() {
+        Some(queue) => cursor.read(queue).map(|request| request.line.clone()).collect(),
+        None => return,
+    };
+
+    for line in lines {
+        let result = run_console_command(world, &line);
+        let max_lines = world.get_resource::<ConsoleSettings>().map_or(200, |settings| settings.max_log_lines);
+        if let Some(mut state) = world.get_resource_mut::<ConsoleState>() {
+            match &result {
+                Ok(text) if text.is_empty() => {}
+                Ok(text) => text.lines().for_each(|text| state.print(text, max_lines)),
+                Err(error) => state.print(format!("Error: {}", error), max_lines),
+            }
+        }
+        if let Some(mut outputs) = world.get_resource_mut::<ConsoleOutputQueue>() {
+            outputs.send(ConsoleOutput { line, result });
+        }
+    }
+}
+
+pub fn setup_console_ui(mut commands: Commands, settings: Res<ConsoleSettings>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(settings.height * 100.0),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::FlexEnd,
+                padding: UiRect::all(Val::Px(8.0)),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.02, 0.02, 0.02, 0.85)),
+            GlobalZIndex(100),
+            Visibility::Hidden,
+            ConsoleRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont { font_size: 14.0, ..default() },
+                TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                ConsoleLogText,
+            ));
+            parent.spawn((
+                Text::new("> "),
+                TextFont { font_size: 14.0, ..default() },
+                TextColor(Color::WHITE),
+                ConsoleInputText,
+            ));
+        });
+}
+
+/// System to show the console panel, its log and the typed line
+pub fn update_console_ui(
+    state: Res<ConsoleState>,
+    mut root_query: Query<&mut Visibility, With<ConsoleRoot>>,
+    mut log_query: Query<&mut Text, (With<ConsoleLogText>, Without<ConsoleInputText>)>,
+    mut input_query: Query<&mut Text, (With<ConsoleInputText>, Without<ConsoleLogText>)>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+    for mut visibility in root_query.iter_mut() {
+        *visibility = if state.open { Visibility::Visible } else { Visibility::Hidden };
+    }
+    for mut text in log_query.iter_mut() {
+        text.0 = state.log.join("\n");
+    }
+    for mut text in input_query.iter_mut() {
+        text.0 = format!("> {}_", state.input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameControllerPlugin;
+    use crate::ai::AiSettings;
+    use crate::character::Player;
+    use crate::combat::Health;
+    use crate::inventory::Inventory;
+
+    #[test]
+    fn test_console_commands() {
+        assert_eq!(parse_console_line(r#"give_item  "old key" 2"#), vec!["give_item", "old key", "2"]);
+        assert!(parse_console_line("   ").is_empty());
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, GameControllerPlugin::default().headless()));
+        let world = app.world_mut();
+        let player = world.spawn((
+            Player,
+            Health { current: 10.0, maximum: 100.0, ..default() },
+            Inventory::default(),
+            Transform::default(),
+        )).id();
+
+        assert!(run_console_command(world, "set_health 40").is_ok());
+        assert_eq!(world.get::<Health>(player).unwrap().current, 40.0);
+        assert!(run_console_command(world, "set_health lots").is_err());
+        run_console_command(world, "teleport 1 2 3").unwrap();
+        assert_eq!(world.get::<Transform>(player).unwrap().translation, Vec3::new(1.0, 2.0, 3.0));
+        run_console_command(world, "give_item potion 3").unwrap();
+        let inventory = world.get::<Inventory>(player).unwrap();
+        assert_eq!(inventory.items.iter().flatten().find(|item| item.item_id == "potion").map(|item| item.quantity), Some(3));
+        run_console_command(world, "toggle_ai").unwrap();
+        assert!(!world.resource::<AiSettings>().enabled);
+        assert!(run_console_command(world, "help teleport").unwrap().contains("<x> <y> <z>"));
+        assert!(run_console_command(world, "fly").is_err());
+    }
+}
@@ -0,0 +1,447 @@
+//! SDL game controller database
+//!
+//! Bevy reads gamepads through gilrs, which only recognises the controllers
+//! in its built-in mapping list; buttons of anything else are dropped. The
+//! community `gamecontrollerdb.txt` (the same file Steam Input and SDL use)
+//! covers hundreds more. [`install_controller_db`] hands it to gilrs, so those
+//! pads report standard buttons and drive `InputAction`s like any Xbox pad.
+//! gilrs reads the mappings once when it starts, so install them before
+//! adding `DefaultPlugins`:
+//!
+//! ```rust,ignore
+//! install_controller_db("assets/gamecontrollerdb.txt").ok();
+//! App::new().add_plugins(DefaultPlugins).add_plugins(GameControllerPlugin::default());
+//! ```
+//!
+//! Each connected gamepad also gets a [`ControllerInfo`] with its
+//! [`ControllerType`] and [`ControllerCapabilities`] (gyro, touchpad, rumble),
+//! for systems like gyro aiming or button prompts.
+
+use bevy::input::gamepad::{Gamepad, GamepadButton};
+use bevy::prelude::*;
+use super::glyphs::GlyphDevice;
+
+/// Environment variable gilrs (and SDL) read extra mappings from
+pub const SDL_MAPPINGS_ENV: &str = "SDL_GAMECONTROLLERCONFIG";
+
+/// One line of the database: a controller and how its raw inputs map to the standard layout
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControllerMapping {
+    pub guid: String,
+    pub name: String,
+    pub vendor_id: Option<u16>,
+    pub product_id: Option<u16>,
+    /// SDL platform name (`Windows`, `Mac OS X`, `Linux`, ...); `None` for any platform
+    pub platform: Option<String>,
+    /// SDL element (`a`, `leftx`, `touchpad`, ...) and the raw input it comes from (`b0`, `a1`, `h0.4`, ...)
+    pub bindings: Vec<(String, String)>,
+}
+
+impl ControllerMapping {
+    /// Parses `guid,name,element:input,...,platform:Linux,`; comments and blank lines give `None`
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let mut fields = line.split(',');
+        let guid = fields.next()?.trim().to_lowercase();
+        if guid.len() != 32 || !guid.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let name = fields.next()?.trim().to_string();
+
+        let mut platform = None;
+        let mut bindings = Vec::new();
+        for field in fields {
+            let Some((element, input)) = field.trim().split_once(':') else { continue };
+            if element == "platform" {
+                platform = Some(input.to_string());
+            } else {
+                bindings.push((element.to_string(), input.to_string()));
+            }
+        }
+
+        let (vendor_id, product_id) = guid_ids(&guid);
+        Some(Self { guid, name, vendor_id, product_id, platform, bindings })
+    }
+
+    /// The line back in database format
+    pub fn to_line(&self) -> String {
+        let mut line = format!("{},{},", self.guid, self.name);
+        for (element, input) in &self.bindings {
+            line.push_str(&format!("{}:{},", element, input));
+        }
+        if let Some(platform) = &self.platform {
+            line.push_str(&format!("platform:{},", platform));
+        }
+        line
+    }
+
+    /// Raw input the standard `button` comes from, if the controller has it
+    pub fn input_for(&self, button: GamepadButton) -> Option<&str> {
+        let element = sdl_element(button)?;
+        self.bindings
+            .iter()
+            .find(|(name, _)| name == element)
+            .map(|(_, input)| input.as_str())
+    }
+
+    pub fn has_element(&self, element: &str) -> bool {
+        self.bindings.iter().any(|(name, _)| name == element)
+    }
+
+    pub fn matches_platform(&self, platform: &str) -> bool {
+        self.platform.as_deref().is_none_or(|own| own == platform)
+    }
+}
+
+/// Vendor and product ids in an SDL2 GUID (little-endian words 2 and 4), when it carries them
+fn guid_ids(guid: &str) -> (Option<u16>, Option<u16>) {
+    let byte = |index: usize| u8::from_str_radix(&guid[index * 2..index * 2 + 2], 16).unwrap_or(0);
+    // Names-only GUIDs (XInput, some Bluetooth pads) keep text there instead
+    if byte(6) != 0 || byte(7) != 0 || byte(10) != 0 || byte(11) != 0 {
+        return (None, None);
+    }
+    let word = |index: usize| u16::from_le_bytes([byte(index), byte(index + 1)]);
+    (Some(word(4)), Some(word(8)))
+}
+
+/// SDL element name of a standard button
+fn sdl_element(button: GamepadButton) -> Option<&'static str> {
+    Some(match button {
+        GamepadButton::South => "a",
+        GamepadButton::East => "b",
+        GamepadButton::West => "x",
+        GamepadButton::North => "y",
+        GamepadButton::LeftTrigger => "leftshoulder",
+        GamepadButton::RightTrigger => "rightshoulder",
+        GamepadButton::LeftTrigger2 => "lefttrigger",
+        GamepadButton::RightTrigger2 => "righttrigger",
+        GamepadButton::Select => "back",
+        GamepadButton::Start => "start",
+        GamepadButton::Mode => "guide",
+        GamepadButton::LeftThumb => "leftstick",
+        GamepadButton::RightThumb => "rightstick",
+        GamepadButton::DPadUp => "dpup",
+        GamepadButton::DPadDown => "dpdown",
+        GamepadButton::DPadLeft => "dpleft",
+        GamepadButton::DPadRight => "dpright",
+        _ => return None,
+    })
+}
+
+/// SDL name of the platform the game runs on
+pub fn current_platform() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "Windows"
+    } else if cfg!(target_os = "macos") {
+        "Mac OS X"
+    } else if cfg!(target_os = "android") {
+        "Android"
+    } else if cfg!(target_os = "ios") {
+        "iOS"
+    } else {
+        "Linux"
+    }
+}
+
+/// Loaded controller mappings
+#[derive(Resource, Debug, Clone)]
+pub struct ControllerDatabase {
+    /// Read at startup when it exists
+    pub path: String,
+    pub mappings: Vec<ControllerMapping>,
+}
+
+impl Default for ControllerDatabase {
+    fn default() -> Self {
+        Self {
+            path: "assets/gamecontrollerdb.txt".to_string(),
+            mappings: Vec::new(),
+        }
+    }
+}
+
+impl ControllerDatabase {
+    /// Adds the mappings in `text` for the current platform; returns how many were added
+    pub fn load_str(&mut self, text: &str) -> usize {
+        let platform = current_platform();
+        let before = self.mappings.len();
+        for mapping in text.lines().filter_map(ControllerMapping::parse) {
+            if !mapping.matches_platform(platform) {
+                continue;
+            }
+            // Later lines override earlier ones, like SDL
+            self.mappings.retain(|existing| existing.guid != mapping.guid);
+            self.mappings.push(mapping);
+        }
+        self.mappings.len().saturating_sub(before)
+    }
+
+    pub fn load_file(&mut self, path: &str) -> Result<usize, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| format!("Failed to read controller database {}: {}", path, error))?;
+        Ok(self.load_str(&text))
+    }
+
+    /// Mapping of a gamepad, by vendor and product ids, then by name
+    pub fn find(&self, vendor_id: Option<u16>, product_id: Option<u16>, name: Option<&str>) -> Option<&ControllerMapping> {
+        let by_ids = vendor_id.zip(product_id).and_then(|(vendor, product)| {
+            self.mappings
+                .iter()
+                .find(|mapping| mapping.vendor_id == Some(vendor) && mapping.product_id == Some(product))
+        });
+        by_ids.or_else(|| {
+            let name = name?;
+            self.mappings.iter().find(|mapping| mapping.name.eq_ignore_ascii_case(name))
+        })
+    }
+
+    /// All mappings in database format, for `SDL_GAMECONTROLLERCONFIG`
+    pub fn to_sdl_string(&self) -> String {
+        self.mappings
+            .iter()
+            .map(ControllerMapping::to_line)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Hands the database at `path` to gilrs; call before adding `DefaultPlugins`.
+/// Mappings already in `SDL_GAMECONTROLLERCONFIG` are kept. Returns how many were installed.
+pub fn install_controller_db(path: &str) -> Result<usize, String> {
+    let mut database = ControllerDatabase::default();
+    let count = database.load_file(path)?;
+    let mut mappings = std::env::var(SDL_MAPPINGS_ENV).unwrap_or_default();
+    if !mappings.is_empty() && !mappings.ends_with('\n') {
+        mappings.push('\n');
+    }
+    mappings.push_str(&database.to_sdl_string());
+    std::env::set_var(SDL_MAPPINGS_ENV, mappings);
+    Ok(count)
+}
+
+/// Controller families, from their USB ids or name
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum ControllerType {
+    #[default]
+    Unknown,
+    Xbox360,
+    XboxOne,
+    PlayStation3,
+    PlayStation4,
+    PlayStation5,
+    SwitchPro,
+    JoyConLeft,
+    JoyConRight,
+    SteamController,
+    SteamDeck,
+    /// Any other pad the database knows
+    Generic,
+}
+
+impl ControllerType {
+    pub fn identify(vendor_id: Option<u16>, product_id: Option<u16>, name: Option<&str>) -> Self {
+        let product = product_id.unwrap_or(0);
+        match vendor_id {
+            Some(0x045E) => match product {
+                0x028E | 0x028F | 0x0719 => ControllerType::Xbox360,
+                _ => ControllerType::XboxOne,
+            },
+            Some(0x054C) => match product {
+                0x0268 => ControllerType::PlayStation3,
+                0x0CE6 | 0x0DF2 => ControllerType::PlayStation5,
+                _ => ControllerType::PlayStation4,
+            },
+            Some(0x057E) => match product {
+                0x2006 => ControllerType::JoyConLeft,
+                0x2007 => ControllerType::JoyConRight,
+                _ => ControllerType::SwitchPro,
+            },
+            Some(0x28DE) => match product {
+                0x1205 => ControllerType::SteamDeck,
+                _ => ControllerType::SteamController,
+            },
+            _ => Self::from_name(name.unwrap_or_default()),
+        }
+    }
+
+    fn from_name(name: &str) -> Self {
+        let name = name.to_lowercase();
+        if name.contains("dualsense") || name.contains("ps5") {
+            ControllerType::PlayStation5
+        } else if name.contains("dualshock 4") || name.contains("ps4") {
+            ControllerType::PlayStation4
+        } else if name.contains("ps3") || name.contains("dualshock 3") {
+            ControllerType::PlayStation3
+        } else if name.contains("steam deck") {
+            ControllerType::SteamDeck
+        } else if name.contains("steam controller") {
+            ControllerType::SteamController
+        } else if name.contains("pro controller") {
+            ControllerType::SwitchPro
+        } else if name.contains("joy-con (l)") {
+            ControllerType::JoyConLeft
+        } else if name.contains("joy-con (r)") {
+            ControllerType::JoyConRight
+        } else if name.contains("xbox 360") {
+            ControllerType::Xbox360
+        } else if name.contains("xbox") {
+            ControllerType::XboxOne
+        } else {
+            ControllerType::Unknown
+        }
+    }
+
+    /// Hardware every controller of the family has
+    pub fn capabilities(self) -> ControllerCapabilities {
+        let caps = ControllerCapabilities::default();
+        match self {
+            ControllerType::Xbox360 => ControllerCapabilities { rumble: true, ..caps },
+            ControllerType::XboxOne => ControllerCapabilities { rumble: true, trigger_rumble: true, ..caps },
+            ControllerType::PlayStation3 => ControllerCapabilities { gyro: true, rumble: true, ..caps },
+            ControllerType::PlayStation4 => ControllerCapabilities { gyro: true, touchpad: true, rumble: true, ..caps },
+            ControllerType::PlayStation5 => ControllerCapabilities {
+                gyro: true,
+                touchpad: true,
+                rumble: true,
+                adaptive_triggers: true,
+                ..caps
+            },
+            ControllerType::SwitchPro | ControllerType::JoyConLeft | ControllerType::JoyConRight => {
+                ControllerCapabilities { gyro: true, rumble: true, ..caps }
+            }
+            ControllerType::SteamController => ControllerCapabilities {
+                gyro: true,
+                touchpad: true,
+                rumble: true,
+                paddles: 2,
+                ..caps
+            },
+            ControllerType::SteamDeck => ControllerCapabilities {
+                gyro: true,
+                touchpad: true,
+                rumble: true,
+                paddles: 4,
+                ..caps
+            },
+            ControllerType::Unknown | ControllerType::Generic => caps,
+        }
+    }
+
+    /// Prompt family the controller's buttons are drawn with
+    pub fn glyph_device(self) -> GlyphDevice {
+        match self {
+            ControllerType::PlayStation3 | ControllerType::PlayStation4 | ControllerType::PlayStation5 => GlyphDevice::PlayStation,
+            ControllerType::SteamDeck => GlyphDevice::SteamDeck,
+            _ => GlyphDevice::Xbox,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub struct ControllerCapabilities {
+    pub gyro: bool,
+    pub touchpad: bool,
+    pub rumble: bool,
+    /// Rumble motors in the triggers (Xbox One and later)
+    pub trigger_rumble: bool,
+    /// Resistance in the triggers (DualSense)
+    pub adaptive_triggers: bool,
+    /// Back paddles
+    pub paddles: u8,
+}
+
+/// Added to each connected gamepad
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct ControllerInfo {
+    pub controller_type: ControllerType,
+    pub capabilities: ControllerCapabilities,
+    /// Name of the database mapping used for the pad, if one matched
+    pub mapping: Option<String>,
+}
+
+impl ControllerInfo {
+    pub fn identify(database: &ControllerDatabase, vendor_id: Option<u16>, product_id: Option<u16>, name: Option<&str>) -> Self {
+        let mapping = database.find(vendor_id, product_id, name);
+        let mut controller_type = ControllerType::identify(vendor_id, product_id, name);
+        if controller_type == ControllerType::Unknown {
+            if let Some(mapping) = mapping {
+                controller_type = ControllerType::identify(mapping.vendor_id, mapping.product_id, Some(&mapping.name));
+                if controller_type == ControllerType::Unknown {
+                    controller_type = ControllerType::Generic;
+                }
+            }
+        }
+
+        let mut capabilities = controller_type.capabilities();
+        if let Some(mapping) = mapping {
+            capabilities.touchpad |= mapping.has_element("touchpad");
+            let paddles = (1..=4).filter(|index| mapping.has_element(&format!("paddle{}", index))).count() as u8;
+            capabilities.paddles = capabilities.paddles.max(paddles);
+        }
+
+        Self {
+            controller_type,
+            capabilities,
+            mapping: mapping.map(|mapping| mapping.name.clone()),
+        }
+    }
+}
+
+/// System to load the controller database when its file exists
+pub fn load_controller_database(mut database: ResMut<ControllerDatabase>) {
+    let path = database.path.clone();
+    if !std::path::Path::new(&path).exists() {
+        return;
+    }
+    if let Err(error) = database.load_file(&path) {
+        warn!("{}", error);
+    }
+}
+
+/// System to describe newly connected gamepads
+pub fn identify_controllers(
+    mut commands: Commands,
+    database: Res<ControllerDatabase>,
+    gamepad_query: Query<(Entity, &Gamepad, Option<&Name>), Without<ControllerInfo>>,
+) {
+    for (entity, gamepad, name) in gamepad_query.iter() {
+        let info = ControllerInfo::identify(&database, gamepad.vendor_id(), gamepad.product_id(), name.map(Name::as_str));
+        commands.entity(entity).insert(info);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::GlyphDevice;
+
+    #[test]
+    fn test_controller_db_mapping_and_capabilities() {
+        use crate::input::controller_db::*;
+        let line = "030000004c050000e60c000000010000,PS5 Controller,a:b1,b:b2,x:b0,y:b3,touchpad:b13,leftx:a0,dpup:h0.1,";
+        let mapping = ControllerMapping::parse(line).unwrap();
+        assert_eq!(mapping.vendor_id, Some(0x054C));
+        assert_eq!(mapping.product_id, Some(0x0CE6));
+        assert_eq!(mapping.input_for(bevy::input::gamepad::GamepadButton::South), Some("b1"));
+        assert_eq!(mapping.to_line(), line);
+        assert!(ControllerMapping::parse("# comment").is_none());
+
+        let mut database = ControllerDatabase::default();
+        let exotic = "03000000de1c00000100000000000000,Retro Pad,a:b0,b:b1,paddle1:b5,";
+        assert_eq!(database.load_str(&format!("{}\n{}\n", line, exotic)), 2);
+        let info = ControllerInfo::identify(&database, Some(0x054C), Some(0x0CE6), None);
+        assert_eq!(info.controller_type, ControllerType::PlayStation5);
+        assert!(info.capabilities.gyro && info.capabilities.touchpad);
+        assert_eq!(info.controller_type.glyph_device(), GlyphDevice::PlayStation);
+
+        let info = ControllerInfo::identify(&database, Some(0x1CDE), Some(0x0001), None);
+        assert_eq!(info.controller_type, ControllerType::Generic);
+        assert_eq!(info.mapping.as_deref(), Some("Retro Pad"));
+        assert!(!info.capabilities.gyro);
+        assert_eq!(info.capabilities.paddles, 1);
+    }
+}
@@ -0,0 +1,266 @@
+use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
+use std::collections::HashMap;
+
+use super::components::InputState;
+use super::touch::TouchJoystick;
+use crate::utils::EventQueue;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// One finger moved quickly across the screen and lifted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwipeEvent {
+    pub start: Vec2,
+    pub end: Vec2,
+    pub direction: SwipeDirection,
+    /// Pixels per second
+    pub speed: f32,
+}
+
+/// Two fingers moved apart (positive `delta`) or together
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PinchEvent {
+    pub center: Vec2,
+    /// Change of the distance between the fingers since last frame, in pixels
+    pub delta: f32,
+    /// Ratio of the new distance to the previous one
+    pub scale: f32,
+}
+
+/// Two quick taps at about the same place
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DoubleTapEvent {
+    pub position: Vec2,
+}
+
+pub type SwipeEventQueue = EventQueue<SwipeEvent>;
+pub type PinchEventQueue = EventQueue<PinchEvent>;
+pub type DoubleTapEventQueue = EventQueue<DoubleTapEvent>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TouchGesture {
+    Swipe(SwipeEvent),
+    Pinch(PinchEvent),
+    DoubleTap(DoubleTapEvent),
+}
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct TouchGestureSettings {
+    pub enabled: bool,
+    /// Pixels
+    pub swipe_min_distance: f32,
+    /// Seconds
+    pub swipe_max_duration: f32,
+    /// A touch moving less than this (pixels) and lifting within `tap_max_duration` is a tap
+    pub tap_max_movement: f32,
+    pub tap_max_duration: f32,
+    /// Longest wait between the two taps of a double tap
+    pub double_tap_interval: f32,
+    pub double_tap_max_distance: f32,
+    /// Horizontal swipes cycle weapons: left for the next, right for the previous
+    pub swipe_switches_weapons: bool,
+    /// Camera distance change per pixel of pinch, 0 to leave the camera alone
+    pub pinch_zoom_speed: f32,
+}
+
+impl Default for TouchGestureSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            swipe_min_distance: 80.0,
+            swipe_max_duration: 0.5,
+            tap_max_movement: 20.0,
+            tap_max_duration: 0.25,
+            double_tap_interval: 0.3,
+            double_tap_max_distance: 40.0,
+            swipe_switches_weapons: true,
+            pinch_zoom_speed: 0.02,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TrackedTouch {
+    start: Vec2,
+    start_time: f32,
+    last: Vec2,
+    /// Part of a pinch; never a swipe or tap
+    pinching: bool,
+}
+
+/// Turns the positions of the fingers on screen, frame by frame, into gestures
+#[derive(Resource, Debug, Default)]
+pub struct TouchGestureRecognizer {
+    touches: HashMap<u64, TrackedTouch>,
+    last_tap: Option<(Vec2, f32)>,
+    pinch_distance: Option<f32>,
+}
+
+impl TouchGestureRecognizer {
+    /// Feeds the touches currently down (id and window position) at time `now`
+    pub fn update(&mut self, active: &[(u64, Vec2)], now: f32, settings: &TouchGestureSettings) -> Vec<TouchGesture> {
+        let mut gestures = Vec::new();
+
+        let ended: Vec<u64> = self.touches.keys().filter(|id| !active.iter().any(|(active_id, _)| active_id == *id)).copied().collect();
+        for id in ended {
+            let Some(touch) = self.touches.remove(&id) else { continue };
+            if touch.pinching {
+                continue;
+            }
+            let delta = touch.last - touch.start;
+            let duration = (now - touch.start_time).max(f32::EPSILON);
+            let distance = delta.length();
+
+            if distance >= settings.swipe_min_distance && duration <= settings.swipe_max_duration {
+                // Window coordinates grow downwards
+                let direction = if delta.x.abs() >= delta.y.abs() {
+                    if delta.x < 0.0 { SwipeDirection::Left } else { SwipeDirection::Right }
+                } else if delta.y < 0.0 {
+                    SwipeDirection::Up
+                } else {
+                    SwipeDirection::Down
+                };
+                gestures.push(TouchGesture::Swipe(SwipeEvent {
+                    start: touch.start,
+                    end: touch.last,
+                    direction,
+                    speed: distance / duration,
+                }));
+            } else if distance <= settings.tap_max_movement && duration <= settings.tap_max_duration {
+                let is_double = self.last_tap.is_some_and(|(position, time)| {
+                    now - time <= settings.double_tap_interval
+                        && position.distance(touch.last) <= settings.double_tap_max_distance
+                });
+                if is_double {
+                    self.last_tap = None;
+                    gestures.push(TouchGesture::DoubleTap(DoubleTapEvent { position: touch.last }));
+                } else {
+                    self.last_tap = Some((touch.last, now));
+                }
+            }
+        }
+
+        for (id, position) in active {
+            self.touches
+                .entry(*id)
+                .and_modify(|touch| touch.last = *position)
+                .or_insert(TrackedTouch { start: *position, start_time: now, last: *position, pinching: false });
+        }
+
+        if self.touches.len() == 2 {
+            let mut fingers = self.touches.values_mut();
+            let (Some(a), Some(b)) = (fingers.next(), fingers.next()) else { return gestures };
+            a.pinching = true;
+            b.pinching = true;
+            let center = (a.last + b.last) * 0.5;
+            let distance = a.last.distance(b.last);
+            if let Some(previous) = self.pinch_distance {
+                if (distance - previous).abs() > f32::EPSILON && previous > 0.0 {
+                    gestures.push(TouchGesture::Pinch(PinchEvent {
+                        center,
+                        delta: distance - previous,
+                        scale: distance / previous,
+                    }));
+                }
+            }
+            self.pinch_distance = Some(distance);
+        } else {
+            self.pinch_distance = None;
+        }
+
+        gestures
+    }
+}
+
+/// Queues recognized gestures are sent to
+#[derive(SystemParam)]
+pub struct GestureQueues<'w> {
+    pub swipes: ResMut<'w, SwipeEventQueue>,
+    pub pinches: ResMut<'w, PinchEventQueue>,
+    pub double_taps: ResMut<'w, DoubleTapEventQueue>,
+}
+
+/// System to recognize gestures from the touches the on-screen joystick doesn't hold,
+/// cycling weapons on horizontal swipes
+pub fn recognize_touch_gestures(
+    time: Res<Time>,
+    settings: Res<TouchGestureSettings>,
+    touches: Res<Touches>,
+    joystick_query: Query<&TouchJoystick>,
+    mut recognizer: ResMut<TouchGestureRecognizer>,
+    mut input_state: ResMut<InputState>,
+    queues: GestureQueues,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let GestureQueues { mut swipes, mut pinches, mut double_taps } = queues;
+    let active: Vec<(u64, Vec2)> = touches
+        .iter()
+        .filter(|touch| !joystick_query.iter().any(|joystick| joystick.active_touch == Some(touch.id())))
+        .map(|touch| (touch.id(), touch.position()))
+        .collect();
+
+    for gesture in recognizer.update(&active, time.elapsed_secs(), &settings) {
+        match gesture {
+            TouchGesture::Swipe(swipe) => {
+                if settings.swipe_switches_weapons {
+                    match swipe.direction {
+                        SwipeDirection::Left => input_state.next_weapon_pressed = true,
+                        SwipeDirection::Right => input_state.prev_weapon_pressed = true,
+                        _ => {}
+                    }
+                }
+                swipes.send(swipe);
+            }
+            TouchGesture::Pinch(pinch) => pinches.send(pinch),
+            TouchGesture::DoubleTap(double_tap) => double_taps.send(double_tap),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_touch_gestures() {
+        let settings = TouchGestureSettings::default();
+        let mut recognizer = TouchGestureRecognizer::default();
+
+        // Quick flick to the left
+        assert!(recognizer.update(&[(1, Vec2::new(300.0, 200.0))], 0.0, &settings).is_empty());
+        recognizer.update(&[(1, Vec2::new(150.0, 210.0))], 0.1, &settings);
+        match recognizer.update(&[], 0.2, &settings).as_slice() {
+            [TouchGesture::Swipe(swipe)] => assert_eq!(swipe.direction, SwipeDirection::Left),
+            other => panic!("expected a swipe, got {:?}", other),
+        }
+
+        // Two taps
+        recognizer.update(&[(2, Vec2::new(50.0, 50.0))], 1.0, &settings);
+        assert!(recognizer.update(&[], 1.1, &settings).is_empty());
+        recognizer.update(&[(3, Vec2::new(55.0, 52.0))], 1.2, &settings);
+        assert!(matches!(recognizer.update(&[], 1.25, &settings).as_slice(), [TouchGesture::DoubleTap(_)]));
+
+        // Fingers moving apart pinch out, and lifting them is no swipe
+        recognizer.update(&[(4, Vec2::new(100.0, 100.0)), (5, Vec2::new(200.0, 100.0))], 2.0, &settings);
+        match recognizer.update(&[(4, Vec2::new(50.0, 100.0)), (5, Vec2::new(250.0, 100.0))], 2.1, &settings).as_slice() {
+            [TouchGesture::Pinch(pinch)] => {
+                assert_eq!(pinch.delta, 100.0);
+                assert_eq!(pinch.scale, 2.0);
+                assert_eq!(pinch.center, Vec2::new(150.0, 100.0));
+            }
+            other => panic!("expected a pinch, got {:?}", other),
+        }
+        assert!(recognizer.update(&[], 2.2, &settings).is_empty());
+    }
+}
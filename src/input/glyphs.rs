@@ -0,0 +1,359 @@
+//! Input glyphs for UI prompts
+//!
+//! [`GlyphProvider`] turns an [`InputAction`] into the text and icon of the
+//! button that performs it on the device the player last used: keyboard and
+//! mouse, an Xbox pad, a PlayStation pad or a Steam Deck. Prompts write
+//! `{Interact}` style tokens and let the provider fill them in:
+//!
+//! ```rust,ignore
+//! text.0 = glyphs.format_prompt("Press {Interact} to open", &input_map); // "Press E to open" / "Press X to open"
+//! ```
+//!
+//! An [`InputDeviceChanged`] event is sent when the active device changes, so
+//! UI built once can swap its prompts.
+
+use bevy::input::gamepad::{Gamepad, GamepadButton};
+use bevy::input::mouse::AccumulatedMouseMotion;
+use bevy::prelude::*;
+use std::collections::HashMap;
+use super::resources::InputMap;
+use super::types::{InputAction, InputBinding, ALL_INPUT_ACTIONS};
+use crate::utils::EventQueue;
+
+/// Device family prompts are shown for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum GlyphDevice {
+    #[default]
+    KeyboardMouse,
+    Xbox,
+    PlayStation,
+    SteamDeck,
+}
+
+impl GlyphDevice {
+    pub fn is_gamepad(&self) -> bool {
+        *self != GlyphDevice::KeyboardMouse
+    }
+
+    /// Folder of the device's icons under `GlyphProvider::icon_root`
+    pub fn folder(&self) -> &'static str {
+        match self {
+            GlyphDevice::KeyboardMouse => "keyboard",
+            GlyphDevice::Xbox => "xbox",
+            GlyphDevice::PlayStation => "playstation",
+            GlyphDevice::SteamDeck => "steam_deck",
+        }
+    }
+
+    /// Family of a gamepad from its USB vendor id and name; unknown pads use the Xbox layout
+    pub fn from_gamepad(vendor_id: Option<u16>, name: Option<&str>) -> Self {
+        let name = name.unwrap_or_default().to_lowercase();
+        match vendor_id {
+            Some(0x054C) => GlyphDevice::PlayStation,
+            Some(0x28DE) => GlyphDevice::SteamDeck,
+            _ if name.contains("dualsense") || name.contains("dualshock") || name.contains("playstation") || name.contains("ps4") || name.contains("ps5") => {
+                GlyphDevice::PlayStation
+            }
+            _ if name.contains("steam deck") => GlyphDevice::SteamDeck,
+            _ => GlyphDevice::Xbox,
+        }
+    }
+}
+
+/// Text and icon of a button prompt
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InputGlyph {
+    pub text: String,
+    /// Icon asset path; `None` when the prompt is text only
+    pub icon: Option<String>,
+}
+
+/// Active device and the glyphs of each action
+#[derive(Resource, Debug)]
+pub struct GlyphProvider {
+    pub active_device: GlyphDevice,
+    /// Folder of the icon sets; icons are `<icon_root>/<device folder>/<name>.png`
+    pub icon_root: String,
+    /// Look stick travel that counts as using a gamepad
+    pub stick_threshold: f32,
+    /// Glyphs replacing the generated ones, per device and action
+    pub overrides: HashMap<(GlyphDevice, InputAction), InputGlyph>,
+}
+
+impl Default for GlyphProvider {
+    fn default() -> Self {
+        Self {
+            active_device: GlyphDevice::KeyboardMouse,
+            icon_root: "textures/glyphs".to_string(),
+            stick_threshold: 0.5,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl GlyphProvider {
+    /// Glyph of `action` on the active device
+    pub fn glyph(&self, action: InputAction, input_map: &InputMap) -> InputGlyph {
+        self.glyph_for(self.active_device, action, input_map)
+    }
+
+    /// Text of `action` on the active device
+    pub fn text(&self, action: InputAction, input_map: &InputMap) -> String {
+        self.glyph(action, input_map).text
+    }
+
+    /// Glyph of `action` on `device`
+    pub fn glyph_for(&self, device: GlyphDevice, action: InputAction, input_map: &InputMap) -> InputGlyph {
+        if let Some(glyph) = self.overrides.get(&(device, action)) {
+            return glyph.clone();
+        }
+
+        let bindings = input_map.bindings.get(&action).map(Vec::as_slice).unwrap_or_default();
+        let binding = if device.is_gamepad() {
+            bindings
+                .iter()
+                .find(|binding| binding_uses_gamepad(binding))
+                .cloned()
+                .or_else(|| default_gamepad_button(action).map(InputBinding::Gamepad))
+        } else {
+            bindings.iter().find(|binding| !binding_uses_gamepad(binding)).cloned()
+        };
+
+        let Some(binding) = binding else {
+            return InputGlyph { text: "Unbound".to_string(), icon: None };
+        };
+        let icon = match &binding {
+            InputBinding::Key(_) | InputBinding::Mouse(_) | InputBinding::Gamepad(_) => {
+                Some(format!("{}/{}/{}.png", self.icon_root, device.folder(), icon_name(&binding, device)))
+            }
+            // Combos show as text
+            InputBinding::Chord(_) | InputBinding::Sequence(_) => None,
+        };
+        InputGlyph { text: binding_text(&binding, device), icon }
+    }
+
+    /// Replaces `{Action}` tokens (`InputAction` names, e.g. `{Interact}`, `{Jump}`) with the active device's button text.
+    /// Unknown tokens are left as they are.
+    pub fn format_prompt(&self, template: &str, input_map: &InputMap) -> String {
+        let mut result = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            let after = &rest[start + 1..];
+            let Some(end) = after.find('}') else {
+                rest = &rest[start..];
+                break;
+            };
+            let name = &after[..end];
+            match ALL_INPUT_ACTIONS.iter().find(|action| format!("{:?}", action) == name) {
+                Some(action) => result.push_str(&self.text(*action, input_map)),
+                None => {
+                    result.push('{');
+                    result.push_str(name);
+                    result.push('}');
+                }
+            }
+            rest = &after[end + 1..];
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+fn binding_uses_gamepad(binding: &InputBinding) -> bool {
+    match binding {
+        InputBinding::Gamepad(_) => true,
+        InputBinding::Key(_) | InputBinding::Mouse(_) => false,
+        InputBinding::Chord(bindings) | InputBinding::Sequence(bindings) => bindings.iter().any(binding_uses_gamepad),
+    }
+}
+
+/// Gamepad button of the built-in gamepad layout (see `build_gamepad_input_state`)
+pub fn default_gamepad_button(action: InputAction) -> Option<GamepadButton> {
+    Some(match action {
+        InputAction::Jump => GamepadButton::South,
+        InputAction::Interact => GamepadButton::West,
+        InputAction::Crouch => GamepadButton::East,
+        InputAction::Sprint => GamepadButton::LeftThumb,
+        InputAction::Aim => GamepadButton::LeftTrigger2,
+        InputAction::Attack | InputAction::AbilityUse => GamepadButton::RightTrigger,
+        InputAction::SpecialMove => GamepadButton::RightThumb,
+        InputAction::Fire => GamepadButton::RightTrigger2,
+        InputAction::Reload => GamepadButton::North,
+        InputAction::Block => GamepadButton::LeftTrigger,
+        InputAction::SwitchCameraMode => GamepadButton::Select,
+        InputAction::ToggleInventory => GamepadButton::Start,
+        InputAction::ResetCamera => GamepadButton::DPadUp,
+        InputAction::ToggleHud => GamepadButton::DPadLeft,
+        InputAction::TakeCover => GamepadButton::DPadRight,
+        InputAction::EmoteWheel => GamepadButton::DPadDown,
+        _ => return None,
+    })
+}
+
+/// Display text of a binding on `device`
+pub fn binding_text(binding: &InputBinding, device: GlyphDevice) -> String {
+    match binding {
+        InputBinding::Key(code) => key_text(*code),
+        InputBinding::Mouse(button) => match button {
+            MouseButton::Left => "LMB".to_string(),
+            MouseButton::Right => "RMB".to_string(),
+            MouseButton::Middle => "MMB".to_string(),
+            MouseButton::Back => "Mouse 4".to_string(),
+            MouseButton::Forward => "Mouse 5".to_string(),
+            MouseButton::Other(index) => format!("Mouse {}", index),
+        },
+        InputBinding::Gamepad(button) => gamepad_text(*button, device).to_string(),
+        InputBinding::Chord(bindings) => bindings.iter().map(|binding| binding_text(binding, device)).collect::<Vec<_>>().join("+"),
+        InputBinding::Sequence(bindings) => bindings.iter().map(|binding| binding_text(binding, device)).collect::<Vec<_>>().join(", "),
+    }
+}
+
+fn key_text(code: KeyCode) -> String {
+    let name = format!("{:?}", code);
+    if let Some(letter) = name.strip_prefix("Key") {
+        return letter.to_string();
+    }
+    if let Some(digit) = name.strip_prefix("Digit") {
+        return digit.to_string();
+    }
+    match code {
+        KeyCode::ShiftLeft | KeyCode::ShiftRight => "Shift".to_string(),
+        KeyCode::ControlLeft | KeyCode::ControlRight => "Ctrl".to_string(),
+        KeyCode::AltLeft | KeyCode::AltRight => "Alt".to_string(),
+        KeyCode::Escape => "Esc".to_string(),
+        KeyCode::ArrowUp => "Up".to_string(),
+        KeyCode::ArrowDown => "Down".to_string(),
+        KeyCode::ArrowLeft => "Left".to_string(),
+        KeyCode::ArrowRight => "Right".to_string(),
+        _ => name,
+    }
+}
+
+fn gamepad_text(button: GamepadButton, device: GlyphDevice) -> &'static str {
+    use GamepadButton::*;
+    match device {
+        GlyphDevice::PlayStation => match button {
+            South => "Cross",
+            East => "Circle",
+            West => "Square",
+            North => "Triangle",
+            LeftTrigger => "L1",
+            RightTrigger => "R1",
+            LeftTrigger2 => "L2",
+            RightTrigger2 => "R2",
+            Select => "Create",
+            Start => "Options",
+            LeftThumb => "L3",
+            RightThumb => "R3",
+            _ => common_gamepad_text(button),
+        },
+        GlyphDevice::SteamDeck => match button {
+            South => "A",
+            East => "B",
+            West => "X",
+            North => "Y",
+            LeftTrigger => "L1",
+            RightTrigger => "R1",
+            LeftTrigger2 => "L2",
+            RightTrigger2 => "R2",
+            Select => "View",
+            Start => "Menu",
+            LeftThumb => "L3",
+            RightThumb => "R3",
+            _ => common_gamepad_text(button),
+        },
+        GlyphDevice::Xbox | GlyphDevice::KeyboardMouse => match button {
+            South => "A",
+            East => "B",
+            West => "X",
+            North => "Y",
+            LeftTrigger => "LB",
+            RightTrigger => "RB",
+            LeftTrigger2 => "LT",
+            RightTrigger2 => "RT",
+            Select => "View",
+            Start => "Menu",
+            LeftThumb => "LS",
+            RightThumb => "RS",
+            _ => common_gamepad_text(button),
+        },
+    }
+}
+
+fn common_gamepad_text(button: GamepadButton) -> &'static str {
+    match button {
+        GamepadButton::DPadUp => "D-Pad Up",
+        GamepadButton::DPadDown => "D-Pad Down",
+        GamepadButton::DPadLeft => "D-Pad Left",
+        GamepadButton::DPadRight => "D-Pad Right",
+        GamepadButton::Mode => "Home",
+        _ => "?",
+    }
+}
+
+/// Icon file name of a single button
+fn icon_name(binding: &InputBinding, device: GlyphDevice) -> String {
+    binding_text(binding, device).to_lowercase().replace([' ', '-'], "_")
+}
+
+/// Sent when the player switches between keyboard and gamepad, or to another gamepad family
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputDeviceChanged {
+    pub device: GlyphDevice,
+    pub previous: GlyphDevice,
+}
+
+pub type InputDeviceChangedQueue = EventQueue<InputDeviceChanged>;
+
+/// System to track the device the player used last
+pub fn detect_active_glyph_device(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    gamepad_query: Query<(&Gamepad, Option<&Name>)>,
+    mut provider: ResMut<GlyphProvider>,
+    mut changes: ResMut<InputDeviceChangedQueue>,
+) {
+    let mut used = None;
+    if keyboard.get_just_pressed().next().is_some()
+        || mouse_buttons.get_just_pressed().next().is_some()
+        || mouse_motion.delta.length_squared() > 4.0
+    {
+        used = Some(GlyphDevice::KeyboardMouse);
+    }
+    for (gamepad, name) in gamepad_query.iter() {
+        let active = gamepad.get_just_pressed().next().is_some()
+            || gamepad.left_stick().length() > provider.stick_threshold
+            || gamepad.right_stick().length() > provider.stick_threshold;
+        if active {
+            used = Some(GlyphDevice::from_gamepad(gamepad.vendor_id(), name.map(Name::as_str)));
+        }
+    }
+
+    let Some(device) = used else { return };
+    if device != provider.active_device {
+        let previous = provider.active_device;
+        provider.active_device = device;
+        changes.send(InputDeviceChanged { device, previous });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{InputAction, InputMap};
+
+    #[test]
+    fn test_glyph_prompts_follow_device() {
+        let input_map = InputMap::default();
+        let mut glyphs = GlyphProvider::default();
+        assert_eq!(glyphs.format_prompt("Press {Interact} to open {door}", &input_map), "Press E to open {door}");
+
+        glyphs.active_device = GlyphDevice::PlayStation;
+        assert_eq!(glyphs.text(InputAction::Interact, &input_map), "Square");
+        glyphs.active_device = GlyphDevice::from_gamepad(Some(0x28DE), None);
+        assert_eq!(glyphs.active_device, GlyphDevice::SteamDeck);
+        assert_eq!(glyphs.format_prompt("{Jump}", &input_map), "A");
+    }
+}
@@ -0,0 +1,263 @@
+//! Gyro aiming and flick stick
+//!
+//! Bevy doesn't read motion sensors, so the platform layer (SDL, Steam Input,
+//! a phone's sensors...) sends a [`GyroSample`] with the controller's angular
+//! velocity. [`GyroSettings`] turn the samples into camera rotation in degrees,
+//! `InputState::aim_turn`, either always or only while aiming down sights.
+//!
+//! With [`FlickStickSettings::enabled`] the camera stick no longer looks
+//! around: flicking it turns the camera to face the direction it points,
+//! and rotating it while held keeps turning. Pitch and fine aim are left to
+//! the gyro.
+
+use bevy::input::gamepad::Gamepad;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use super::components::{InputDevice, InputState, PlayerInputSettings};
+use super::resources::InputConfig;
+use super::systems::InputDrivenPlayer;
+use crate::utils::{EventQueue, QueueReader};
+
+/// Angular velocity read from a controller's gyroscope
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GyroSample {
+    pub gamepad: Entity,
+    /// Degrees per second, held as when playing: positive x pitches up, y yaws left, z rolls left
+    pub angular_velocity: Vec3,
+    /// Seconds the sample covers
+    pub dt: f32,
+}
+
+pub type GyroSampleQueue = EventQueue<GyroSample>;
+
+/// When the gyro turns the camera
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
+pub enum GyroActivation {
+    Never,
+    Always,
+    /// Only while `InputAction::Aim` is held
+    #[default]
+    WhileAiming,
+}
+
+/// Controller rotation that turns the camera left and right
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, Reflect)]
+pub enum GyroYawAxis {
+    /// Turning the controller like a steering wheel laid flat
+    #[default]
+    Yaw,
+    /// Tilting the controller sideways
+    Roll,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GyroSettings {
+    pub activation: GyroActivation,
+    /// Camera degrees per degree the controller turns, for yaw and pitch
+    pub sensitivity: Vec2,
+    pub yaw_axis: GyroYawAxis,
+    pub invert_pitch: bool,
+    /// Turn speed (degrees per second) below which rotation is scaled down, hiding hand tremor
+    pub tightening: f32,
+}
+
+impl Default for GyroSettings {
+    fn default() -> Self {
+        Self {
+            activation: GyroActivation::WhileAiming,
+            sensitivity: Vec2::splat(2.0),
+            yaw_axis: GyroYawAxis::Yaw,
+            invert_pitch: false,
+            tightening: 3.0,
+        }
+    }
+}
+
+impl GyroSettings {
+    pub fn is_active(&self, aiming: bool) -> bool {
+        match self.activation {
+            GyroActivation::Never => false,
+            GyroActivation::Always => true,
+            GyroActivation::WhileAiming => aiming,
+        }
+    }
+
+    /// Camera turn in degrees, in the directions of `InputState::look`
+    pub fn turn(&self, angular_velocity: Vec3, dt: f32) -> Vec2 {
+        let yaw = match self.yaw_axis {
+            GyroYawAxis::Yaw => angular_velocity.y,
+            GyroYawAxis::Roll => angular_velocity.z,
+        };
+        let pitch = if self.invert_pitch { -angular_velocity.x } else { angular_velocity.x };
+        let rate = Vec2::new(-yaw, pitch);
+
+        let speed = rate.length();
+        let tightening = if self.tightening > 0.0 && speed < self.tightening { speed / self.tightening } else { 1.0 };
+        rate * tightening * self.sensitivity * dt
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FlickStickSettings {
+    pub enabled: bool,
+    /// Deflection past which the stick points somewhere
+    pub threshold: f32,
+    /// Seconds a flick takes to turn the camera
+    pub flick_time: f32,
+    /// Camera degrees per degree the held stick rotates
+    pub rotation_sensitivity: f32,
+}
+
+impl Default for FlickStickSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold: 0.9,
+            flick_time: 0.1,
+            rotation_sensitivity: 1.0,
+        }
+    }
+}
+
+/// Flick stick state of one camera stick
+#[derive(Debug, Clone, Default)]
+pub struct FlickStick {
+    /// Stick angle last frame, while past the threshold
+    last_angle: Option<f32>,
+    flick_total: f32,
+    flick_elapsed: f32,
+}
+
+impl FlickStick {
+    /// Camera yaw turn in degrees (positive to the right) for this frame's stick position
+    pub fn update(&mut self, stick: Vec2, settings: &FlickStickSettings, dt: f32) -> f32 {
+        let mut turn = 0.0;
+        if stick.length() >= settings.threshold {
+            // 0 forward, 90 right
+            let angle = stick.x.atan2(stick.y).to_degrees();
+            match self.last_angle {
+                None => {
+                    self.flick_total = angle;
+                    self.flick_elapsed = 0.0;
+                }
+                Some(last) => {
+                    turn += wrap_degrees(angle - last) * settings.rotation_sensitivity;
+                }
+            }
+            self.last_angle = Some(angle);
+        } else {
+            self.last_angle = None;
+        }
+
+        if self.flick_total != 0.0 {
+            let before = flick_ease(self.flick_elapsed, settings.flick_time);
+            self.flick_elapsed += dt;
+            let after = flick_ease(self.flick_elapsed, settings.flick_time);
+            turn += self.flick_total * (after - before);
+            if after >= 1.0 {
+                self.flick_total = 0.0;
+            }
+        }
+        turn
+    }
+}
+
+/// Ease-out share of a flick done after `elapsed` seconds
+fn flick_ease(elapsed: f32, flick_time: f32) -> f32 {
+    if flick_time <= 0.0 {
+        return 1.0;
+    }
+    let t = (elapsed / flick_time).clamp(0.0, 1.0);
+    1.0 - (1.0 - t) * (1.0 - t)
+}
+
+fn wrap_degrees(angle: f32) -> f32 {
+    (angle + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Flick stick state per gamepad
+#[derive(Resource, Debug, Default)]
+pub struct GyroAimState {
+    pub flick_sticks: HashMap<Entity, FlickStick>,
+}
+
+/// System to turn gyro samples and flick stick into camera rotation, after the input state is built
+pub fn apply_gyro_aim(
+    time: Res<Time>,
+    config: Res<InputConfig>,
+    mut samples: QueueReader<GyroSample>,
+    mut state: ResMut<GyroAimState>,
+    gamepad_query: Query<(Entity, &Gamepad)>,
+    mut global_input: ResMut<InputState>,
+    mut player_query: Query<(&mut InputState, Option<&PlayerInputSettings>), InputDrivenPlayer>,
+) {
+    let dt = time.delta_secs();
+
+    // Gyro and flick turn of each gamepad, in `InputDevice::Gamepad` index order
+    let mut gamepads: Vec<Entity> = gamepad_query.iter().map(|(entity, _)| entity).collect();
+    gamepads.sort();
+    let mut gyro: HashMap<Entity, Vec2> = HashMap::new();
+    for sample in samples.read() {
+        *gyro.entry(sample.gamepad).or_default() += config.gyro.turn(sample.angular_velocity, sample.dt);
+    }
+    let mut flick: HashMap<Entity, f32> = HashMap::new();
+    if config.flick_stick.enabled {
+        for (entity, gamepad) in gamepad_query.iter() {
+            let turn = state.flick_sticks.entry(entity).or_default().update(gamepad.right_stick(), &config.flick_stick, dt);
+            flick.insert(entity, turn);
+        }
+    }
+    state.flick_sticks.retain(|entity, _| gamepads.contains(entity));
+
+    let turn_of = |gamepad: Entity, aiming: bool| -> Vec2 {
+        let gyro = if config.gyro.is_active(aiming) { gyro.get(&gamepad).copied().unwrap_or_default() } else { Vec2::ZERO };
+        gyro + Vec2::new(flick.get(&gamepad).copied().unwrap_or_default(), 0.0)
+    };
+
+    global_input.aim_turn = if global_input.enabled {
+        gamepads.iter().map(|gamepad| turn_of(*gamepad, global_input.aim_pressed)).sum()
+    } else {
+        Vec2::ZERO
+    };
+
+    for (mut input, settings) in player_query.iter_mut() {
+        let settings = settings.cloned().unwrap_or_default();
+        if !input.enabled || settings.locks.camera {
+            input.aim_turn = Vec2::ZERO;
+            continue;
+        }
+        input.aim_turn = match settings.device {
+            InputDevice::KeyboardMouse => global_input.aim_turn,
+            InputDevice::Gamepad { id } => gamepads.get(id).map_or(Vec2::ZERO, |gamepad| turn_of(*gamepad, input.aim_pressed)),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_gyro_turn_and_flick_stick() {
+        let gyro = GyroSettings { sensitivity: Vec2::ONE, tightening: 0.0, ..default() };
+        assert!(!gyro.is_active(false) && gyro.is_active(true));
+        // Yawing left at 90 deg/s for half a second turns the camera 45 degrees left
+        assert_eq!(gyro.turn(Vec3::new(0.0, 90.0, 0.0), 0.5), Vec2::new(-45.0, 0.0));
+        let tight = GyroSettings { tightening: 10.0, ..gyro.clone() };
+        assert_eq!(tight.turn(Vec3::new(5.0, 0.0, 0.0), 1.0), Vec2::new(0.0, 2.5));
+
+        let settings = FlickStickSettings { enabled: true, flick_time: 0.1, ..default() };
+        let mut flick = FlickStick::default();
+        // Flicking right turns 90 degrees over the flick time
+        let turned = flick.update(Vec2::X, &settings, 0.05) + flick.update(Vec2::X, &settings, 0.05);
+        assert!((turned - 90.0).abs() < 0.001);
+        // Rotating the held stick keeps turning
+        assert!((flick.update(Vec2::NEG_Y, &settings, 0.05) - 90.0).abs() < 0.001);
+        assert_eq!(flick.update(Vec2::ZERO, &settings, 0.05), 0.0);
+        assert!((flick.update(Vec2::NEG_X, &settings, 0.2) + 90.0).abs() < 0.001);
+    }
+}
@@ -0,0 +1,333 @@
+//! Gamepad haptics
+//!
+//! Send a [`HapticsEvent`] (strength, duration and a [`HapticsCurve`] for the
+//! intensity over time) on the [`HapticsQueue`] and the gamepad of the target
+//! player rumbles through Bevy's `GamepadRumbleRequest`. Overlapping effects on
+//! the same gamepad add up. Combat damage, weapon firing, vehicle impacts and
+//! hard landings send their own events, tuned in [`HapticsSettings`].
+//!
+//! ```rust,ignore
+//! fn explosion_nearby(mut haptics: ResMut<HapticsQueue>, player: Single<Entity, With<Player>>) {
+//!     haptics.send(HapticsEvent::new(0.8, 0.5).for_player(*player).with_curve(HapticsCurve::EaseOut));
+//! }
+//! ```
+
+use bevy::ecs::entity::{EntityHashMap, EntityHashSet};
+use bevy::input::gamepad::{Gamepad, GamepadRumbleIntensity, GamepadRumbleRequest};
+use bevy::prelude::*;
+use avian3d::prelude::LinearVelocity;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use super::components::{InputDevice, PlayerInputSettings};
+use crate::character::Player;
+use crate::physics::GroundDetection;
+use crate::utils::{EventQueue, QueueReader};
+
+/// Intensity of a haptics effect over its duration
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect, Serialize, Deserialize)]
+pub enum HapticsCurve {
+    #[default]
+    Constant,
+    /// Fades out linearly
+    FadeOut,
+    /// Strong start, quick fade
+    EaseOut,
+    /// Rises to full strength at the end
+    FadeIn,
+    /// On/off pulses, `frequency` per second
+    Pulse { frequency: f32 },
+}
+
+impl HapticsCurve {
+    /// Intensity (0-1) at `elapsed` seconds of a `duration` long effect
+    pub fn sample(&self, elapsed: f32, duration: f32) -> f32 {
+        let t = if duration > 0.0 { (elapsed / duration).clamp(0.0, 1.0) } else { 1.0 };
+        match self {
+            HapticsCurve::Constant => 1.0,
+            HapticsCurve::FadeOut => 1.0 - t,
+            HapticsCurve::EaseOut => (1.0 - t) * (1.0 - t),
+            HapticsCurve::FadeIn => t,
+            HapticsCurve::Pulse { frequency } => {
+                if (elapsed * frequency).fract() < 0.5 { 1.0 } else { 0.0 }
+            }
+        }
+    }
+}
+
+/// Rumble request
+#[derive(Debug, Clone)]
+pub struct HapticsEvent {
+    /// Player whose gamepad rumbles; `None` rumbles every gamepad
+    pub player: Option<Entity>,
+    /// Peak strength (0-1)
+    pub strength: f32,
+    /// In seconds
+    pub duration: f32,
+    pub curve: HapticsCurve,
+    /// Motor balance: 0 only the heavy motor, 1 only the light one, 0.5 both
+    pub sharpness: f32,
+}
+
+impl HapticsEvent {
+    pub fn new(strength: f32, duration: f32) -> Self {
+        Self {
+            player: None,
+            strength,
+            duration,
+            curve: HapticsCurve::Constant,
+            sharpness: 0.5,
+        }
+    }
+
+    pub fn for_player(mut self, player: Entity) -> Self {
+        self.player = Some(player);
+        self
+    }
+
+    pub fn with_curve(mut self, curve: HapticsCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    pub fn with_sharpness(mut self, sharpness: f32) -> Self {
+        self.sharpness = sharpness;
+        self
+    }
+
+    fn motors(&self, intensity: f32) -> (f32, f32) {
+        let sharpness = self.sharpness.clamp(0.0, 1.0);
+        let strong = self.strength * intensity * (2.0 * (1.0 - sharpness)).min(1.0);
+        let weak = self.strength * intensity * (2.0 * sharpness).min(1.0);
+        (strong, weak)
+    }
+}
+
+pub type HapticsQueue = EventQueue<HapticsEvent>;
+
+/// Haptics settings, including the built-in hooks
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct HapticsSettings {
+    pub enabled: bool,
+    /// Scales every effect
+    pub intensity: f32,
+
+    pub weapon_fire_enabled: bool,
+    pub weapon_fire_strength: f32,
+    pub weapon_fire_duration: f32,
+
+    pub vehicle_impact_enabled: bool,
+    /// Velocity change in one frame that counts as an impact
+    pub vehicle_impact_min_speed_loss: f32,
+    /// Velocity change for a full strength rumble
+    pub vehicle_impact_full_speed_loss: f32,
+    pub vehicle_impact_duration: f32,
+
+    pub landing_enabled: bool,
+    /// Fall speed that starts to rumble on landing
+    pub landing_min_speed: f32,
+    /// Fall speed for a full strength rumble
+    pub landing_full_speed: f32,
+    pub landing_duration: f32,
+}
+
+impl Default for HapticsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            intensity: 1.0,
+            weapon_fire_enabled: true,
+            weapon_fire_strength: 0.3,
+            weapon_fire_duration: 0.08,
+            vehicle_impact_enabled: true,
+            vehicle_impact_min_speed_loss: 4.0,
+            vehicle_impact_full_speed_loss: 20.0,
+            vehicle_impact_duration: 0.4,
+            landing_enabled: true,
+            landing_min_speed: 6.0,
+            landing_full_speed: 20.0,
+            landing_duration: 0.2,
+        }
+    }
+}
+
+struct ActiveHaptics {
+    event: HapticsEvent,
+    elapsed: f32,
+}
+
+/// Effects currently playing, and the gamepads they drive
+#[derive(Resource, Default)]
+pub struct HapticsState {
+    active: Vec<ActiveHaptics>,
+    rumbling: EntityHashSet,
+}
+
+/// Gamepads of a player: its configured gamepad, or any gamepad no other player claims
+fn player_gamepads(
+    player: Entity,
+    player_query: &Query<(Entity, Option<&PlayerInputSettings>), With<Player>>,
+    gamepads: &[Entity],
+) -> Vec<Entity> {
+    let Ok((_, settings)) = player_query.get(player) else {
+        return Vec::new();
+    };
+    if let Some(InputDevice::Gamepad { id }) = settings.map(|settings| settings.device) {
+        return gamepads.get(id).copied().into_iter().collect();
+    }
+    let claimed: Vec<usize> = player_query
+        .iter()
+        .filter_map(|(_, settings)| match settings.map(|settings| settings.device) {
+            Some(InputDevice::Gamepad { id }) => Some(id),
+            _ => None,
+        })
+        .collect();
+    gamepads
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !claimed.contains(index))
+        .map(|(_, gamepad)| *gamepad)
+        .collect()
+}
+
+/// System to play haptics events on the gamepads
+pub fn update_haptics(
+    time: Res<Time>,
+    settings: Res<HapticsSettings>,
+    mut events: QueueReader<HapticsEvent>,
+    mut state: ResMut<HapticsState>,
+    player_query: Query<(Entity, Option<&PlayerInputSettings>), With<Player>>,
+    gamepad_query: Query<Entity, With<Gamepad>>,
+    mut rumble_requests: MessageWriter<GamepadRumbleRequest>,
+) {
+    let delta = time.delta_secs();
+    let state = &mut *state;
+
+    for event in events.read() {
+        if settings.enabled && event.strength > 0.0 && event.duration > 0.0 {
+            state.active.push(ActiveHaptics { event: event.clone(), elapsed: 0.0 });
+        }
+    }
+    if !settings.enabled {
+        state.active.clear();
+    }
+
+    let gamepads: Vec<Entity> = gamepad_query.iter().collect();
+    let mut motors: EntityHashMap<(f32, f32)> = EntityHashMap::default();
+    for active in state.active.iter() {
+        let intensity = active.event.curve.sample(active.elapsed, active.event.duration) * settings.intensity;
+        let (strong, weak) = active.event.motors(intensity);
+        let targets = match active.event.player {
+            Some(player) => player_gamepads(player, &player_query, &gamepads),
+            None => gamepads.clone(),
+        };
+        for gamepad in targets {
+            let entry = motors.entry(gamepad).or_insert((0.0, 0.0));
+            entry.0 += strong;
+            entry.1 += weak;
+        }
+    }
+
+    for active in state.active.iter_mut() {
+        active.elapsed += delta;
+    }
+    state.active.retain(|active| active.elapsed < active.event.duration);
+
+    // Gamepads that went quiet
+    for gamepad in state.rumbling.iter() {
+        if !motors.contains_key(gamepad) {
+            rumble_requests.write(GamepadRumbleRequest::Stop { gamepad: *gamepad });
+        }
+    }
+    state.rumbling.clear();
+
+    // The mix is re-sent every frame; the short duration stops it if frames stall
+    for (gamepad, (strong, weak)) in motors {
+        rumble_requests.write(GamepadRumbleRequest::Stop { gamepad });
+        rumble_requests.write(GamepadRumbleRequest::Add {
+            gamepad,
+            intensity: GamepadRumbleIntensity {
+                strong_motor: strong.clamp(0.0, 1.0),
+                weak_motor: weak.clamp(0.0, 1.0),
+            },
+            duration: Duration::from_secs_f32(delta.max(0.0) + 0.1),
+        });
+        state.rumbling.insert(gamepad);
+    }
+}
+
+/// System to rumble players landing from a high fall
+pub fn haptics_on_landing(
+    settings: Res<HapticsSettings>,
+    mut haptics: ResMut<HapticsQueue>,
+    mut fall_speeds: Local<EntityHashMap<f32>>,
+    player_query: Query<(Entity, &GroundDetection, &LinearVelocity), With<Player>>,
+) {
+    for (entity, ground, velocity) in player_query.iter() {
+        if !ground.is_grounded {
+            fall_speeds.insert(entity, (-velocity.y).max(0.0));
+            continue;
+        }
+        let Some(fall_speed) = fall_speeds.remove(&entity) else { continue };
+        if settings.landing_enabled && fall_speed >= settings.landing_min_speed {
+            let range = (settings.landing_full_speed - settings.landing_min_speed).max(0.001);
+            let strength = ((fall_speed - settings.landing_min_speed) / range).clamp(0.2, 1.0);
+            haptics.send(
+                HapticsEvent::new(strength, settings.landing_duration)
+                    .for_player(entity)
+                    .with_curve(HapticsCurve::EaseOut)
+                    .with_sharpness(0.2),
+            );
+        }
+    }
+}
+
+/// System to rumble vehicle occupants when the vehicle hits something
+#[cfg(feature = "vehicles")]
+pub fn haptics_on_vehicle_impact(
+    settings: Res<HapticsSettings>,
+    mut haptics: ResMut<HapticsQueue>,
+    mut velocities: Local<EntityHashMap<Vec3>>,
+    vehicle_query: Query<(Entity, &LinearVelocity, &Children), With<crate::vehicles::Vehicle>>,
+    seat_query: Query<&crate::vehicles::VehicleSeat>,
+) {
+    for (entity, velocity, children) in vehicle_query.iter() {
+        let Some(previous) = velocities.insert(entity, velocity.0) else { continue };
+        if !settings.vehicle_impact_enabled {
+            continue;
+        }
+        let speed_loss = previous.length() - velocity.length();
+        if speed_loss < settings.vehicle_impact_min_speed_loss {
+            continue;
+        }
+
+        let range = (settings.vehicle_impact_full_speed_loss - settings.vehicle_impact_min_speed_loss).max(0.001);
+        let strength = ((speed_loss - settings.vehicle_impact_min_speed_loss) / range).clamp(0.3, 1.0);
+        for seat in seat_query.iter_many(children) {
+            if let Some(occupant) = seat.occupied_by {
+                haptics.send(
+                    HapticsEvent::new(strength, settings.vehicle_impact_duration)
+                        .for_player(occupant)
+                        .with_curve(HapticsCurve::FadeOut)
+                        .with_sharpness(0.3),
+                );
+            }
+        }
+    }
+    velocities.retain(|entity, _| vehicle_query.contains(*entity));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_haptics_curves() {
+        assert_eq!(HapticsCurve::Constant.sample(0.3, 1.0), 1.0);
+        assert!((HapticsCurve::FadeOut.sample(0.25, 1.0) - 0.75).abs() < 1e-6);
+        assert_eq!(HapticsCurve::EaseOut.sample(1.0, 1.0), 0.0);
+        assert_eq!(HapticsCurve::Pulse { frequency: 2.0 }.sample(0.1, 1.0), 1.0);
+        assert_eq!(HapticsCurve::Pulse { frequency: 2.0 }.sample(0.3, 1.0), 0.0);
+    }
+}
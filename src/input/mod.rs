@@ -3,19 +3,73 @@ pub mod resources;
 pub mod components;
 pub mod systems;
 pub mod touch;
+pub mod gestures;
 pub mod ui_edit;
+pub mod recorder;
+pub mod haptics;
+pub mod ui_navigation;
+pub mod bindings;
+pub mod glyphs;
+pub mod analog;
+pub mod gyro;
+pub mod mouse;
+pub mod accessibility;
+pub mod console;
+#[cfg(feature = "controller_db")]
+pub mod controller_db;
 
 use bevy::prelude::*;
 use types::*;
 use resources::*;
 use components::*;
 use systems::*;
+use recorder::*;
+use haptics::*;
+use ui_navigation::*;
+use bindings::*;
+use glyphs::*;
+use gestures::*;
+use gyro::*;
+use mouse::*;
+use accessibility::*;
+use console::*;
+use crate::utils::EventQueuePlugin;
 
-pub use types::{InputAction, InputBinding, BufferedAction};
-pub use resources::{InputMap, InputBuffer, InputConfig, RebindState, InputContextStack, InputContextRules, ActionState, ActionValue};
+pub use types::{InputAction, InputBinding, BufferedAction, InputSet, InputContext, InputLayerMode};
+pub use resources::{InputMap, InputBuffer, InputComboState, InputConfig, RebindState, InputContextStack, InputContextRules, ActionState, ActionValue};
 pub use components::{InputState, PlayerInputSettings, InputDevice, InputLocks};
 pub use touch::{TouchControlRoot, TouchActionButton, TouchJoystick, TouchJoystickThumb, TouchControlsSettings};
+pub use gestures::{
+    SwipeDirection, SwipeEvent, PinchEvent, DoubleTapEvent, SwipeEventQueue, PinchEventQueue, DoubleTapEventQueue,
+    TouchGesture, TouchGestureSettings, TouchGestureRecognizer,
+};
 pub use ui_edit::{DraggableUi, UiEditSettings, UiEditState, UiLayoutStore, UiPosition};
+pub use recorder::{
+    InputRecorder, InputRecorderMode, InputRecording, RecordedInputFrame,
+    InputRecorderCommand, InputRecorderStatus, InputRecorderCommandQueue, InputRecorderStatusQueue,
+};
+pub use haptics::{HapticsCurve, HapticsEvent, HapticsQueue, HapticsSettings, HapticsState};
+pub use ui_navigation::{
+    Focusable, FocusCancel, FocusScope, UiCancelButton, UiTab, UiFocus,
+    UiNavigationEvent, UiNavigationEventQueue, UiNavigationSettings,
+};
+pub use bindings::{
+    ActionBindings, InputProfile, InputProfiles, InputBindingsFile, BindingConflict,
+    InputBindingsCommand, InputBindingsStatus, InputBindingsCommandQueue, InputBindingsStatusQueue,
+};
+pub use analog::{ResponseCurve, DeadZoneShape, StickSettings};
+pub use gyro::{GyroSample, GyroSampleQueue, GyroActivation, GyroYawAxis, GyroSettings, FlickStickSettings, FlickStick, GyroAimState};
+pub use mouse::{MouseAcceleration, MouseLookSettings, MouseLookFilter};
+pub use accessibility::{ActionMode, InputRepeat, ActionAccessibility, ActionIntents, RepeatTimer, TOGGLEABLE_ACTIONS};
+pub use console::{
+    ConsoleArgs, ConsoleResult, ConsoleCommand, ConsoleCommands, ConsoleAppExt, ConsoleRequest, ConsoleRequestQueue,
+    ConsoleOutput, ConsoleOutputQueue, ConsoleSettings, ConsoleState, console_arg, parse_console_line, run_console_command,
+};
+pub use glyphs::{GlyphDevice, GlyphProvider, InputGlyph, InputDeviceChanged, InputDeviceChangedQueue};
+#[cfg(feature = "controller_db")]
+pub use controller_db::{
+    ControllerMapping, ControllerDatabase, ControllerType, ControllerCapabilities, ControllerInfo, install_controller_db,
+};
 pub use systems::*;
 
 pub struct InputPlugin;
@@ -27,25 +81,79 @@ impl Plugin for InputPlugin {
             .init_resource::<InputMap>()
             .init_resource::<RebindState>()
             .init_resource::<InputBuffer>()
+            .init_resource::<InputComboState>()
             .init_resource::<InputConfig>()
             .init_resource::<InputContextStack>()
             .init_resource::<InputContextRules>()
             .init_resource::<TouchControlsSettings>()
+            .init_resource::<TouchGestureSettings>()
+            .init_resource::<TouchGestureRecognizer>()
+            .add_plugins(EventQueuePlugin::<SwipeEvent>::default())
+            .add_plugins(EventQueuePlugin::<PinchEvent>::default())
+            .add_plugins(EventQueuePlugin::<DoubleTapEvent>::default())
             .init_resource::<ActionState>()
             .init_resource::<UiEditSettings>()
             .init_resource::<UiEditState>()
             .init_resource::<UiLayoutStore>()
+            .init_resource::<InputRecorder>()
+            .add_plugins(EventQueuePlugin::<InputRecorderCommand>::default())
+            .add_plugins(EventQueuePlugin::<InputRecorderStatus>::default())
+            .init_resource::<HapticsSettings>()
+            .init_resource::<HapticsState>()
+            .add_plugins(EventQueuePlugin::<HapticsEvent>::default())
+            .init_resource::<UiFocus>()
+            .init_resource::<UiNavigationSettings>()
+            .add_plugins(EventQueuePlugin::<UiNavigationEvent>::default())
+            .init_resource::<InputProfiles>()
+            .add_plugins(EventQueuePlugin::<InputBindingsCommand>::default())
+            .add_plugins(EventQueuePlugin::<InputBindingsStatus>::default())
+            .init_resource::<GlyphProvider>()
+            .add_plugins(EventQueuePlugin::<InputDeviceChanged>::default())
+            .init_resource::<GyroAimState>()
+            .init_resource::<ActionIntents>()
+            .init_resource::<MouseLookFilter>()
+            .init_resource::<ConsoleSettings>()
+            .init_resource::<ConsoleState>()
+            .init_resource::<ConsoleCommands>()
+            .add_plugins(EventQueuePlugin::<ConsoleRequest>::default())
+            .add_plugins(EventQueuePlugin::<ConsoleOutput>::default())
+            .add_plugins(EventQueuePlugin::<GyroSample>::default())
             
             // Register components
             .register_type::<InputState>()
-            
+            .register_type::<PlayerInputSettings>()
+            .register_type::<HapticsSettings>()
+            .register_type::<TouchGestureSettings>()
+            .register_type::<Focusable>()
+            .register_type::<FocusScope>()
+            .register_type::<UiCancelButton>()
+            .register_type::<UiTab>()
+            .register_type::<UiNavigationSettings>()
+            .register_type::<ConsoleSettings>()
+            .register_type::<ConsoleRoot>()
+            .register_type::<ConsoleLogText>()
+            .register_type::<ConsoleInputText>()
+            .add_console_command(ConsoleCommand::new("help", "Lists the commands").with_usage("[command]"), help_command)
+            .add_console_command(ConsoleCommand::new("clear", "Clears the console"), clear_command)
+            .add_console_command(
+                ConsoleCommand::new("mouse", "Changes mouse look options").with_usage("<smoothing|acceleration> [on|off] | sensitivity <x> [y]"),
+                mouse_command,
+            )
+
+            .configure_sets(Update, (
+                InputSet::Gather,
+                InputSet::Apply,
+            ).chain().in_set(crate::GameControllerSet::Input))
             .add_systems(Update, (
                 update_input_context,
+                update_input_combos,
                 update_input_state,
+                apply_mouse_look,
                 update_action_state,
                 touch::update_touch_controls_visibility,
                 touch::update_touch_buttons,
                 touch::update_touch_joystick,
+                recognize_touch_gestures,
                 ui_edit::apply_ui_layout,
                 ui_edit::handle_ui_drag_start,
                 ui_edit::handle_ui_drag_update,
@@ -55,11 +163,56 @@ impl Plugin for InputPlugin {
                 handle_rebinding,
                 cleanup_input_buffer,
                 player_input_sync_system,
-            ).chain())
+            ).chain().in_set(InputSet::Gather))
             .add_systems(Update, (
                 process_movement_input,
                 process_action_input,
-            ))
-            .add_systems(Startup, ui_edit::load_ui_layout);
+            ).in_set(InputSet::Apply))
+            // Recorded input replaces the devices before anything reads it
+            .add_systems(Update, (
+                handle_input_recorder_commands,
+                play_input_frame,
+                record_input_frame,
+            ).chain().after(player_input_sync_system).in_set(InputSet::Gather))
+            .add_systems(Update, (
+                handle_input_bindings_commands,
+                persist_rebinds,
+            ).chain().after(handle_rebinding).in_set(InputSet::Gather))
+            .add_systems(Update, detect_active_glyph_device.in_set(InputSet::Gather))
+            // The console layer goes up before this frame's input is read
+            .add_systems(Update, (
+                toggle_console,
+                edit_console_input,
+                execute_console_requests,
+            ).chain().before(update_input_context).in_set(InputSet::Gather))
+            .add_systems(PostUpdate, update_console_ui.in_set(crate::headless::PresentationSet))
+            // Before the recorder, so recordings keep the gyro turn and playback replaces it
+            .add_systems(Update, apply_gyro_aim
+                .after(player_input_sync_system)
+                .before(handle_input_recorder_commands)
+                .in_set(InputSet::Gather))
+            .add_systems(PostUpdate, (
+                haptics_on_landing,
+                update_haptics,
+            ).chain().in_set(crate::headless::PresentationSet))
+            // Navigation presses land after the mouse, before any menu reads `Interaction`
+            .add_systems(PreUpdate, (
+                add_focusable_to_buttons,
+                navigate_ui,
+            ).chain().after(bevy::ui::UiSystems::Focus).run_if(crate::headless::presentation_enabled))
+            .add_systems(PostUpdate, update_focus_highlight.in_set(crate::headless::PresentationSet))
+            .add_systems(Startup, (ui_edit::load_ui_layout, load_input_bindings_on_startup, setup_console_ui));
+
+        #[cfg(feature = "vehicles")]
+        app.add_systems(PostUpdate, haptics_on_vehicle_impact
+            .in_set(crate::headless::PresentationSet)
+            .before(update_haptics));
+
+        #[cfg(feature = "controller_db")]
+        app
+            .init_resource::<controller_db::ControllerDatabase>()
+            .register_type::<controller_db::ControllerInfo>()
+            .add_systems(Startup, controller_db::load_controller_database)
+            .add_systems(Update, controller_db::identify_controllers.in_set(InputSet::Gather));
     }
 }
@@ -0,0 +1,212 @@
+//! Mouse look: per-axis sensitivity, acceleration and smoothing.
+//!
+//! The mouse motion of a frame becomes `InputState::look` here, so the camera,
+//! weapon sway and photo mode all feel the same options. `InputConfig::mouse`
+//! is read every frame, which lets an options menu flip the toggles while the
+//! game runs:
+//!
+//! ```rust,ignore
+//! config.mouse.sensitivity = Vec2::new(1.0, 0.8);
+//! config.mouse.smoothing = true;
+//! config.mouse.acceleration = true;
+//! config.mouse.acceleration_curve = MouseAcceleration::Linear { rate: 0.002, max_gain: 2.5 };
+//! ```
+
+use bevy::input::mouse::AccumulatedMouseMotion;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use super::components::InputState;
+use super::console::{console_arg, ConsoleArgs, ConsoleResult};
+use super::resources::{InputConfig, InputContextRules, InputContextStack};
+use super::types::{InputContext, InputLayerMode};
+
+/// Gain applied to mouse motion by its speed, in pixels per second
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MouseAcceleration {
+    /// `1 + rate * speed`, capped at `max_gain`
+    Linear { rate: f32, max_gain: f32 },
+    /// `(speed, gain)` points sorted by speed, linear between them and flat past the ends
+    Curve { points: Vec<Vec2> },
+}
+
+impl Default for MouseAcceleration {
+    fn default() -> Self {
+        MouseAcceleration::Linear { rate: 0.001, max_gain: 2.0 }
+    }
+}
+
+impl MouseAcceleration {
+    pub fn gain(&self, speed: f32) -> f32 {
+        match self {
+            MouseAcceleration::Linear { rate, max_gain } => (1.0 + rate * speed).min(max_gain.max(1.0)),
+            MouseAcceleration::Curve { points } => {
+                let (Some(first), Some(last)) = (points.first(), points.last()) else { return 1.0 };
+                if speed <= first.x {
+                    return first.y;
+                }
+                points
+                    .windows(2)
+                    .find(|pair| speed <= pair[1].x)
+                    .map(|pair| {
+                        let t = (speed - pair[0].x) / (pair[1].x - pair[0].x).max(f32::EPSILON);
+                        pair[0].y + (pair[1].y - pair[0].y) * t
+                    })
+                    .unwrap_or(last.y)
+            }
+        }
+    }
+}
+
+/// Mouse look options, on top of `InputConfig::mouse_sensitivity` and `invert_y_axis`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MouseLookSettings {
+    /// Horizontal and vertical multipliers
+    pub sensitivity: Vec2,
+    pub invert_x: bool,
+    /// Averages the motion of the last `smoothing_frames` frames
+    pub smoothing: bool,
+    pub smoothing_frames: usize,
+    /// Speeds up fast swipes by `acceleration_curve`
+    pub acceleration: bool,
+    pub acceleration_curve: MouseAcceleration,
+}
+
+impl Default for MouseLookSettings {
+    fn default() -> Self {
+        Self {
+            sensitivity: Vec2::ONE,
+            invert_x: false,
+            smoothing: false,
+            smoothing_frames: 3,
+            acceleration: false,
+            acceleration_curve: MouseAcceleration::default(),
+        }
+    }
+}
+
+/// Recent mouse motion for smoothing
+#[derive(Resource, Debug, Default, Clone)]
+pub struct MouseLookFilter {
+    samples: VecDeque<Vec2>,
+}
+
+impl MouseLookFilter {
+    /// Turns a frame of raw mouse motion into look, before `mouse_sensitivity`
+    pub fn apply(&mut self, delta: Vec2, settings: &MouseLookSettings, dt: f32) -> Vec2 {
+        let mut look = delta;
+        if settings.acceleration && dt > 0.0 {
+            look *= settings.acceleration_curve.gain(delta.length() / dt);
+        }
+
+        if settings.smoothing && settings.smoothing_frames > 1 {
+            // Still frames count too, so the camera eases to a stop
+            self.samples.push_back(look);
+            while self.samples.len() > settings.smoothing_frames {
+                self.samples.pop_front();
+            }
+            look = self.samples.iter().sum::<Vec2>() / self.samples.len() as f32;
+        } else {
+            self.samples.clear();
+        }
+
+        look * settings.sensitivity
+    }
+
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
+
+/// Whether a layer on the stack keeps the mouse from turning the camera
+fn mouse_look_blocked(context_stack: &InputContextStack, context_rules: &InputContextRules) -> bool {
+    context_stack
+        .stack
+        .iter()
+        .any(|context| *context == InputContext::Menu || context_rules.mode(*context) == InputLayerMode::Exclusive)
+}
+
+/// System to turn mouse motion into `InputState::look`
+pub fn apply_mouse_look(
+    time: Res<Time>,
+    mouse_motion: Option<Res<AccumulatedMouseMotion>>,
+    config: Res<InputConfig>,
+    context_stack: Res<InputContextStack>,
+    context_rules: Res<InputContextRules>,
+    mut filter: ResMut<MouseLookFilter>,
+    mut input_state: ResMut<InputState>,
+) {
+    let delta = mouse_motion.map_or(Vec2::ZERO, |motion| motion.delta);
+    if !input_state.enabled || mouse_look_blocked(&context_stack, &context_rules) {
+        filter.reset();
+        input_state.look = Vec2::ZERO;
+        return;
+    }
+
+    let mut look = filter.apply(delta, &config.mouse, time.delta_secs()) * config.mouse_sensitivity;
+    if config.mouse.invert_x {
+        look.x = -look.x;
+    }
+    if config.invert_y_axis {
+        look.y = -look.y;
+    }
+    input_state.look = look;
+}
+
+/// Console `mouse <smoothing|acceleration> [on|off]` and `mouse sensitivity <x> [y]`
+pub fn mouse_command(In(args): In<ConsoleArgs>, mut config: ResMut<InputConfig>) -> ConsoleResult {
+    let toggle = |current: bool| match args.get(1).map(String::as_str) {
+        None => Ok(!current),
+        Some("on") => Ok(true),
+        Some("off") => Ok(false),
+        Some(other) => Err(format!("Expected on or off, got '{}'", other)),
+    };
+    let state = |enabled: bool| if enabled { "on" } else { "off" };
+
+    match args.first().map(String::as_str) {
+        Some("smoothing") => {
+            config.mouse.smoothing = toggle(config.mouse.smoothing)?;
+            Ok(format!("Mouse smoothing {}", state(config.mouse.smoothing)))
+        }
+        Some("acceleration") => {
+            config.mouse.acceleration = toggle(config.mouse.acceleration)?;
+            Ok(format!("Mouse acceleration {}", state(config.mouse.acceleration)))
+        }
+        Some("sensitivity") => {
+            let x: f32 = console_arg(&args, 1, "x")?;
+            let y = if args.len() > 2 { console_arg(&args, 2, "y")? } else { x };
+            config.mouse.sensitivity = Vec2::new(x, y);
+            Ok(format!("Mouse sensitivity {} {}", x, y))
+        }
+        _ => Err("Expected smoothing, acceleration or sensitivity".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_mouse_look_filter() {
+        let mut settings = MouseLookSettings { sensitivity: Vec2::new(1.0, 0.5), ..default() };
+        let mut filter = MouseLookFilter::default();
+        assert_eq!(filter.apply(Vec2::new(4.0, 4.0), &settings, 0.01), Vec2::new(4.0, 2.0));
+
+        // Smoothing averages the last frames, still ones included
+        settings.sensitivity = Vec2::ONE;
+        settings.smoothing = true;
+        settings.smoothing_frames = 2;
+        assert_eq!(filter.apply(Vec2::new(6.0, 0.0), &settings, 0.01), Vec2::new(6.0, 0.0));
+        assert_eq!(filter.apply(Vec2::ZERO, &settings, 0.01), Vec2::new(3.0, 0.0));
+        assert_eq!(filter.apply(Vec2::ZERO, &settings, 0.01), Vec2::ZERO);
+
+        settings.smoothing = false;
+        settings.acceleration = true;
+        settings.acceleration_curve = MouseAcceleration::Curve { points: vec![Vec2::new(100.0, 1.0), Vec2::new(300.0, 3.0)] };
+        assert_eq!(filter.apply(Vec2::new(50.0, 0.0), &settings, 1.0), Vec2::new(50.0, 0.0));
+        assert_eq!(filter.apply(Vec2::new(200.0, 0.0), &settings, 1.0), Vec2::new(400.0, 0.0));
+        assert_eq!(filter.apply(Vec2::new(1000.0, 0.0), &settings, 1.0), Vec2::new(3000.0, 0.0));
+    }
+}
@@ -0,0 +1,345 @@
+//! Input recording and playback
+//!
+//! The [`InputRecorder`] captures the gathered `InputState`, `ActionState` and
+//! per-player input every frame and writes it to a JSON file. Playback feeds a
+//! recording back at the end of [`InputSet::Gather`], in place of the devices,
+//! so everything downstream (movement, combat, the fixed step latch) sees the
+//! recorded input. With `lock_time_step` the frame deltas are replayed too,
+//! which makes a run reproducible for bug reports, gameplay tests and attract
+//! mode demos.
+//!
+//! ```rust,ignore
+//! fn record(mut recorder: ResMut<InputRecorderCommandQueue>) {
+//!     recorder.send(InputRecorderCommand::StartRecording);
+//!     // ...
+//!     recorder.send(InputRecorderCommand::Save { path: "bug_1234.input.json".into() });
+//! }
+//! ```
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+use super::components::{InputState, PlayerInputSettings};
+use super::resources::{ActionState, ActionValue};
+use super::systems::InputDrivenPlayer;
+use super::types::InputAction;
+use crate::utils::{EventQueue, QueueReader};
+
+const RECORDING_VERSION: u32 = 1;
+
+/// Input of one frame
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordedInputFrame {
+    /// Frame delta, in seconds
+    pub delta: f32,
+    pub input: InputState,
+    pub actions: Vec<(InputAction, ActionValue)>,
+    /// Input of each local player, by `PlayerInputSettings::player_id`
+    pub players: Vec<(u8, InputState)>,
+}
+
+/// Recorded input frames
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InputRecording {
+    pub version: u32,
+    pub frames: Vec<RecordedInputFrame>,
+}
+
+impl InputRecording {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        serde_json::to_vec(self).map_err(|e| format!("Failed to serialize input recording: {}", e))
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let recording: Self = serde_json::from_slice(bytes)
+            .map_err(|e| format!("Invalid input recording: {}", e))?;
+        if recording.version > RECORDING_VERSION {
+            return Err(format!("Unsupported input recording version {}", recording.version));
+        }
+        Ok(recording)
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.delta).sum()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum InputRecorderMode {
+    #[default]
+    Idle,
+    Recording,
+    Playback,
+}
+
+/// Input recorder state and settings
+#[derive(Resource, Debug)]
+pub struct InputRecorder {
+    pub mode: InputRecorderMode,
+    pub recording: InputRecording,
+    /// Next frame to play
+    pub frame: usize,
+    pub looping: bool,
+    /// Replay the recorded frame deltas during playback
+    pub lock_time_step: bool,
+    /// Relative paths are resolved against this directory
+    pub directory: String,
+}
+
+impl Default for InputRecorder {
+    fn default() -> Self {
+        Self {
+            mode: InputRecorderMode::Idle,
+            recording: InputRecording::default(),
+            frame: 0,
+            looping: false,
+            lock_time_step: true,
+            directory: "recordings".to_string(),
+        }
+    }
+}
+
+impl InputRecorder {
+    pub fn is_recording(&self) -> bool {
+        self.mode == InputRecorderMode::Recording
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.mode == InputRecorderMode::Playback
+    }
+}
+
+/// Input recorder control
+#[derive(Debug, Clone)]
+pub enum InputRecorderCommand {
+    /// Starts a new recording, discarding the current one
+    StartRecording,
+    StopRecording,
+    /// Writes the current recording; relative paths go to `InputRecorder::directory`
+    Save { path: String },
+    Load { path: String },
+    Play,
+    Stop,
+}
+
+/// Reported by the input recorder
+#[derive(Debug, Clone)]
+pub enum InputRecorderStatus {
+    RecordingStarted,
+    RecordingStopped { frames: usize },
+    Saved { path: String },
+    Loaded { path: String, frames: usize },
+    PlaybackStarted,
+    PlaybackFinished,
+    PlaybackStopped,
+    Failed { error: String },
+}
+
+pub type InputRecorderCommandQueue = EventQueue<InputRecorderCommand>;
+pub type InputRecorderStatusQueue = EventQueue<InputRecorderStatus>;
+
+/// System to apply input recorder commands
+pub fn handle_input_recorder_commands(
+    mut commands: QueueReader<InputRecorderCommand>,
+    mut recorder: ResMut<InputRecorder>,
+    mut status: ResMut<InputRecorderStatusQueue>,
+    mut time_strategy: ResMut<TimeUpdateStrategy>,
+) {
+    for command in commands.read() {
+        match command {
+            InputRecorderCommand::StartRecording => {
+                recorder.recording = InputRecording {
+                    version: RECORDING_VERSION,
+                    frames: Vec::new(),
+                };
+                recorder.mode = InputRecorderMode::Recording;
+                status.send(InputRecorderStatus::RecordingStarted);
+            }
+            InputRecorderCommand::StopRecording => {
+                if recorder.is_recording() {
+                    recorder.mode = InputRecorderMode::Idle;
+                    status.send(InputRecorderStatus::RecordingStopped {
+                        frames: recorder.recording.frames.len(),
+                    });
+                }
+            }
+            InputRecorderCommand::Save { path } => {
+                let path = resolve_path(&recorder, path);
+                match save_recording(&recorder.recording, &path) {
+                    Ok(path) => status.send(InputRecorderStatus::Saved { path }),
+                    Err(error) => status.send(InputRecorderStatus::Failed { error }),
+                }
+            }
+            InputRecorderCommand::Load { path } => {
+                let path = resolve_path(&recorder, path);
+                match load_recording(&path) {
+                    Ok(recording) => {
+                        let frames = recording.frames.len();
+                        recorder.recording = recording;
+                        recorder.frame = 0;
+                        status.send(InputRecorderStatus::Loaded {
+                            path: path.display().to_string(),
+                            frames,
+                        });
+                    }
+                    Err(error) => status.send(InputRecorderStatus::Failed { error }),
+                }
+            }
+            InputRecorderCommand::Play => {
+                if recorder.recording.frames.is_empty() {
+                    status.send(InputRecorderStatus::Failed {
+                        error: "No input recording to play".to_string(),
+                    });
+                    continue;
+                }
+                recorder.mode = InputRecorderMode::Playback;
+                recorder.frame = 0;
+                status.send(InputRecorderStatus::PlaybackStarted);
+            }
+            InputRecorderCommand::Stop => {
+                if recorder.is_playing() {
+                    recorder.mode = InputRecorderMode::Idle;
+                    if recorder.lock_time_step {
+                        *time_strategy = TimeUpdateStrategy::Automatic;
+                    }
+                    status.send(InputRecorderStatus::PlaybackStopped);
+                }
+            }
+        }
+    }
+}
+
+fn resolve_path(recorder: &InputRecorder, path: &str) -> PathBuf {
+    let path = PathBuf::from(path);
+    if path.is_relative() {
+        PathBuf::from(&recorder.directory).join(path)
+    } else {
+        path
+    }
+}
+
+fn save_recording(recording: &InputRecording, path: &PathBuf) -> Result<String, String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create recording directory: {}", e))?;
+    }
+    let bytes = recording.to_bytes()?;
+    std::fs::write(path, bytes)
+        .map_err(|e| format!("Failed to write input recording {}: {}", path.display(), e))?;
+    Ok(path.display().to_string())
+}
+
+fn load_recording(path: &PathBuf) -> Result<InputRecording, String> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| format!("Failed to read input recording {}: {}", path.display(), e))?;
+    InputRecording::from_bytes(&bytes)
+}
+
+/// System to overwrite the gathered input with the next recorded frame
+pub fn play_input_frame(
+    mut recorder: ResMut<InputRecorder>,
+    mut status: ResMut<InputRecorderStatusQueue>,
+    mut time_strategy: ResMut<TimeUpdateStrategy>,
+    mut input_state: ResMut<InputState>,
+    mut action_state: ResMut<ActionState>,
+    mut player_query: Query<(&mut InputState, Option<&PlayerInputSettings>), InputDrivenPlayer>,
+) {
+    if !recorder.is_playing() {
+        return;
+    }
+
+    if recorder.frame >= recorder.recording.frames.len() {
+        if recorder.looping {
+            recorder.frame = 0;
+        } else {
+            recorder.mode = InputRecorderMode::Idle;
+            if recorder.lock_time_step {
+                *time_strategy = TimeUpdateStrategy::Automatic;
+            }
+            status.send(InputRecorderStatus::PlaybackFinished);
+            return;
+        }
+    }
+
+    let frame = &recorder.recording.frames[recorder.frame];
+    *input_state = frame.input.clone();
+    for (action, value) in frame.actions.iter() {
+        action_state.actions.insert(*action, *value);
+    }
+    for (mut player_input, settings) in player_query.iter_mut() {
+        let player_id = settings.map(|settings| settings.player_id).unwrap_or(0);
+        if let Some((_, recorded)) = frame.players.iter().find(|(id, _)| *id == player_id) {
+            *player_input = recorded.clone();
+        }
+    }
+
+    // The delta of the next frame is picked when it starts
+    if recorder.lock_time_step {
+        let next = (recorder.frame + 1) % recorder.recording.frames.len();
+        let delta = recorder.recording.frames[next].delta;
+        *time_strategy = TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(delta.max(0.0)));
+    }
+    recorder.frame += 1;
+}
+
+/// System to append the gathered input to the recording
+pub fn record_input_frame(
+    time: Res<Time>,
+    mut recorder: ResMut<InputRecorder>,
+    input_state: Res<InputState>,
+    action_state: Res<ActionState>,
+    player_query: Query<(&InputState, Option<&PlayerInputSettings>), InputDrivenPlayer>,
+) {
+    if !recorder.is_recording() {
+        return;
+    }
+
+    let mut actions: Vec<(InputAction, ActionValue)> = action_state
+        .actions
+        .iter()
+        .map(|(action, value)| (*action, *value))
+        .collect();
+    // Stable order keeps recordings diffable
+    actions.sort_by_key(|(action, _)| format!("{:?}", action));
+
+    let players = player_query
+        .iter()
+        .map(|(input, settings)| (settings.map(|settings| settings.player_id).unwrap_or(0), input.clone()))
+        .collect();
+
+    recorder.recording.frames.push(RecordedInputFrame {
+        delta: time.delta_secs(),
+        input: input_state.clone(),
+        actions,
+        players,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{ActionValue, InputAction, InputState};
+
+    #[test]
+    fn test_input_recording_roundtrip() {
+        let mut input = InputState::default();
+        input.movement = Vec2::new(0.5, -1.0);
+        input.jump_pressed = true;
+        let recording = InputRecording {
+            version: 1,
+            frames: vec![RecordedInputFrame {
+                delta: 1.0 / 60.0,
+                input,
+                actions: vec![(InputAction::Jump, ActionValue { pressed: true, just_pressed: true, just_released: false, value: 1.0 })],
+                players: Vec::new(),
+            }],
+        };
+        let loaded = InputRecording::from_bytes(&recording.to_bytes().unwrap()).unwrap();
+        assert_eq!(loaded.frames.len(), 1);
+        assert_eq!(loaded.frames[0].input.movement, Vec2::new(0.5, -1.0));
+        assert!(loaded.frames[0].input.jump_pressed);
+        assert!(loaded.frames[0].actions[0].1.just_pressed);
+    }
+}
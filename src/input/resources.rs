@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
-use super::types::{InputAction, InputBinding, BufferedAction, InputContext, ALL_INPUT_ACTIONS};
+use super::types::{InputAction, InputBinding, BufferedAction, InputContext, InputLayerMode, ALL_INPUT_ACTIONS};
+use super::analog::StickSettings;
+use super::gyro::{GyroSettings, FlickStickSettings};
+use super::accessibility::{ActionAccessibility, InputRepeat};
+use super::mouse::MouseLookSettings;
 use std::collections::HashSet;
 
 /// Mapping from actions to multiple potential bindings
@@ -62,6 +66,13 @@ impl Default for InputMap {
         bindings.insert(InputAction::AbilitySelect6, vec![InputBinding::Key(KeyCode::F6)]);
         bindings.insert(InputAction::AbilitySelect7, vec![InputBinding::Key(KeyCode::F7)]);
         bindings.insert(InputAction::AbilitySelect8, vec![InputBinding::Key(KeyCode::F8)]);
+        bindings.insert(InputAction::EmoteWheel, vec![InputBinding::Key(KeyCode::KeyB)]);
+        bindings.insert(InputAction::SpecialMove, vec![InputBinding::Key(KeyCode::KeyF)]);
+        bindings.insert(InputAction::ToggleHud, vec![InputBinding::Key(KeyCode::F10)]);
+        bindings.insert(InputAction::Pause, vec![InputBinding::Key(KeyCode::Escape)]);
+        bindings.insert(InputAction::Dash, vec![InputBinding::Key(KeyCode::AltLeft)]);
+        bindings.insert(InputAction::TakeCover, vec![InputBinding::Key(KeyCode::KeyT)]);
+        bindings.insert(InputAction::FreeLook, vec![InputBinding::Key(KeyCode::CapsLock)]);
         Self { bindings }
     }
 }
@@ -86,6 +97,49 @@ impl InputBuffer {
     pub fn is_buffered(&self, action: InputAction) -> bool {
         self.actions.iter().any(|ba| ba.action == action)
     }
+
+    /// Consume an action triggered by a chord or sequence, returning that binding
+    pub fn consume_combo(&mut self, action: InputAction) -> Option<InputBinding> {
+        let index = self.actions.iter().position(|ba| ba.action == action && ba.combo.is_some())?;
+        self.actions.remove(index).combo
+    }
+}
+
+/// Recent button presses and the chords and sequences they completed
+#[derive(Resource, Debug, Default)]
+pub struct InputComboState {
+    /// Single button presses with their time, oldest first
+    pub history: Vec<(InputBinding, f32)>,
+    /// Sequences completed this frame
+    pub completed: HashSet<InputBinding>,
+    /// Completed sequences whose last button is still held
+    pub held: HashSet<InputBinding>,
+    /// Sequences whose last button was released this frame
+    pub released: HashSet<InputBinding>,
+    /// Buttons of chords pressed this frame; they don't trigger their own bindings
+    pub shadowed: HashSet<InputBinding>,
+}
+
+impl InputComboState {
+    pub const HISTORY_LEN: usize = 32;
+
+    /// Whether `steps` (minus the last one, checked by the caller) were pressed in order before `now`
+    pub fn matches_sequence(&self, steps: &[InputBinding], now: f32, window: f32) -> bool {
+        let mut time = now;
+        let mut cursor = self.history.len();
+        for step in steps.iter().rev() {
+            let Some(index) = self.history[..cursor].iter().rposition(|(binding, _)| binding == step) else {
+                return false;
+            };
+            let pressed_at = self.history[index].1;
+            if time - pressed_at > window {
+                return false;
+            }
+            time = pressed_at;
+            cursor = index;
+        }
+        true
+    }
 }
 
 /// Input configuration
@@ -94,7 +148,26 @@ pub struct InputConfig {
     pub mouse_sensitivity: f32,
     pub gamepad_sensitivity: f32,
     pub invert_y_axis: bool,
+    /// Per-axis sensitivity, smoothing and acceleration of mouse look
+    pub mouse: MouseLookSettings,
+    /// How long chord and sequence presses stay buffered
     pub buffer_ttl: f32, 
+    /// Actions buffered when pressed and how long they stay buffered, so a press
+    /// slightly before the action is possible (landing, end of an attack) still counts
+    pub buffer_windows: HashMap<InputAction, f32>,
+    /// Longest gap between the steps of a sequence binding
+    pub combo_window: f32,
+    /// Dead zones and response curve of the movement stick
+    pub move_stick: StickSettings,
+    /// Dead zones and response curve of the camera stick; `invert_y_axis` flips it too
+    pub look_stick: StickSettings,
+    pub gyro: GyroSettings,
+    /// Replaces the camera stick's look when enabled
+    pub flick_stick: FlickStickSettings,
+    /// Toggle, double-tap window and repeat options per action
+    pub accessibility: HashMap<InputAction, ActionAccessibility>,
+    /// Repeat of held menu navigation; `None` moves once per press
+    pub ui_repeat: Option<InputRepeat>,
 }
 
 impl Default for InputConfig {
@@ -103,11 +176,42 @@ impl Default for InputConfig {
             mouse_sensitivity: 0.15,
             gamepad_sensitivity: 1.0,
             invert_y_axis: false,
+            mouse: MouseLookSettings::default(),
             buffer_ttl: 0.15, 
+            buffer_windows: HashMap::from([
+                (InputAction::Jump, 0.15),
+                (InputAction::Dash, 0.15),
+                (InputAction::Attack, 0.15),
+                (InputAction::Interact, 0.15),
+                (InputAction::LockOn, 0.15),
+                (InputAction::AbilityUse, 0.15),
+            ]),
+            combo_window: 0.3,
+            move_stick: StickSettings::default(),
+            look_stick: StickSettings::look(),
+            gyro: GyroSettings::default(),
+            flick_stick: FlickStickSettings::default(),
+            accessibility: HashMap::new(),
+            ui_repeat: Some(InputRepeat::default()),
         }
     }
 }
 
+impl InputConfig {
+    /// Seconds a press of `action` stays buffered, 0 when it isn't buffered
+    pub fn buffer_window(&self, action: InputAction) -> f32 {
+        self.buffer_windows.get(&action).copied().unwrap_or(0.0)
+    }
+
+    /// Longest gap between the steps of `action`'s sequence bindings
+    pub fn sequence_window(&self, action: InputAction) -> f32 {
+        self.accessibility
+            .get(&action)
+            .and_then(|options| options.double_tap_window)
+            .unwrap_or(self.combo_window)
+    }
+}
+
 /// Resource to track if we are currently waiting for a key to rebind an action
 #[derive(Resource, Debug, Default)]
 pub struct RebindState {
@@ -115,6 +219,11 @@ pub struct RebindState {
 }
 
 /// Current input context stack (top is active).
+///
+/// The bottom layer (`Gameplay`) is the character controller. Every layer
+/// above it, from the top down, can consume an action (it handles it and
+/// the layers below never see it) or block it. Exclusive layers also stop
+/// every action they don't let through. See [`InputContextRules`].
 #[derive(Resource, Debug)]
 pub struct InputContextStack {
     pub stack: Vec<InputContext>,
@@ -132,11 +241,70 @@ impl InputContextStack {
     pub fn current(&self) -> InputContext {
         *self.stack.last().unwrap_or(&InputContext::Gameplay)
     }
+
+    pub fn contains(&self, context: InputContext) -> bool {
+        self.stack.contains(&context)
+    }
+
+    pub fn push(&mut self, context: InputContext) {
+        self.stack.push(context);
+    }
+
+    /// Removes the topmost `context` layer
+    pub fn remove(&mut self, context: InputContext) -> bool {
+        // The bottom layer stays
+        match self.stack.iter().rposition(|layer| *layer == context) {
+            Some(index) if index > 0 => {
+                self.stack.remove(index);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Pushes `context` if it isn't on the stack yet, or removes it
+    pub fn set_active(&mut self, context: InputContext, active: bool) {
+        if active && !self.contains(context) {
+            self.push(context);
+        } else if !active {
+            while self.remove(context) {}
+        }
+    }
+
+    /// Layer that gets `action`, or `None` when a layer blocks it
+    pub fn consumer(&self, action: InputAction, rules: &InputContextRules) -> Option<InputContext> {
+        for (index, layer) in self.stack.iter().enumerate().rev() {
+            if rules.blocks(*layer, action) {
+                return None;
+            }
+            if index == 0 || rules.consumes(*layer, action) {
+                return Some(*layer);
+            }
+            if rules.mode(*layer) == InputLayerMode::Exclusive && !rules.passes_through(*layer, action) {
+                return None;
+            }
+        }
+        None
+    }
+
+    /// Whether `action` gets down to the character controller
+    pub fn reaches_gameplay(&self, action: InputAction, rules: &InputContextRules) -> bool {
+        let bottom = self.stack.first().copied().unwrap_or(InputContext::Gameplay);
+        self.stack.is_empty() || self.consumer(action, rules) == Some(bottom)
+    }
 }
 
+/// What each context layer does with actions
 #[derive(Resource, Debug)]
 pub struct InputContextRules {
+    /// Actions nobody gets while the context is on the stack
     pub blocked_actions: HashMap<InputContext, HashSet<InputAction>>,
+    /// Actions the context handles itself, hidden from the layers below
+    pub consumed_actions: HashMap<InputContext, HashSet<InputAction>>,
+    /// Contexts not listed pass through
+    pub modes: HashMap<InputContext, InputLayerMode>,
+    /// Actions an exclusive context still lets through
+    pub pass_through_actions: HashMap<InputContext, HashSet<InputAction>>,
 }
 
 impl Default for InputContextRules {
@@ -157,19 +325,72 @@ impl Default for InputContextRules {
             InputAction::Reload,
             InputAction::NextWeapon,
             InputAction::PrevWeapon,
+            InputAction::EmoteWheel,
+            InputAction::SpecialMove,
+            InputAction::Dash,
+            InputAction::TakeCover,
+            InputAction::FreeLook,
         ]));
 
         blocked_actions.insert(InputContext::Vehicle, HashSet::from([
             InputAction::Jump,
             InputAction::Crouch,
             InputAction::AbilityUse,
+            InputAction::EmoteWheel,
+            InputAction::Dash,
+            InputAction::TakeCover,
         ]));
 
-        Self { blocked_actions }
+        // The map zooms itself; the rest of the game waits, except pausing
+        let mut consumed_actions = HashMap::new();
+        consumed_actions.insert(InputContext::Map, HashSet::from([InputAction::ZoomIn, InputAction::ZoomOut]));
+
+        let modes = HashMap::from([
+            (InputContext::Map, InputLayerMode::Exclusive),
+            (InputContext::Dialog, InputLayerMode::Exclusive),
+            (InputContext::Console, InputLayerMode::Exclusive),
+            (InputContext::Terminal, InputLayerMode::Exclusive),
+        ]);
+
+        let pass_through_actions = HashMap::from([
+            (InputContext::Map, HashSet::from([InputAction::Pause, InputAction::ToggleHud])),
+            (InputContext::Dialog, HashSet::from([InputAction::Pause])),
+            (InputContext::Terminal, HashSet::from([InputAction::Pause])),
+        ]);
+
+        Self { blocked_actions, consumed_actions, modes, pass_through_actions }
+    }
+}
+
+impl InputContextRules {
+    pub fn mode(&self, context: InputContext) -> InputLayerMode {
+        self.modes.get(&context).copied().unwrap_or_default()
+    }
+
+    pub fn blocks(&self, context: InputContext, action: InputAction) -> bool {
+        self.blocked_actions.get(&context).is_some_and(|actions| actions.contains(&action))
+    }
+
+    pub fn consumes(&self, context: InputContext, action: InputAction) -> bool {
+        self.consumed_actions.get(&context).is_some_and(|actions| actions.contains(&action))
+    }
+
+    pub fn passes_through(&self, context: InputContext, action: InputAction) -> bool {
+        self.pass_through_actions.get(&context).is_some_and(|actions| actions.contains(&action))
+    }
+
+    /// Whether `action` does anything while `context` is on top
+    pub fn is_live(&self, context: InputContext, action: InputAction) -> bool {
+        if self.blocks(context, action) {
+            return false;
+        }
+        self.mode(context) == InputLayerMode::PassThrough
+            || self.consumes(context, action)
+            || self.passes_through(context, action)
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
 pub struct ActionValue {
     pub pressed: bool,
     pub just_pressed: bool,
@@ -197,3 +418,58 @@ impl ActionState {
         *self.actions.get(&action).unwrap_or(&ActionValue::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{InputAction, InputBinding, InputContext, InputState};
+
+    #[test]
+    fn test_input_sequence_window() {
+        let down = InputBinding::Key(KeyCode::KeyS);
+        let forward = InputBinding::Key(KeyCode::KeyD);
+        let mut combos = InputComboState::default();
+        combos.history = vec![(down.clone(), 1.0), (forward.clone(), 1.2)];
+
+        // Quarter-circle finished by the attack press at 1.4
+        assert!(combos.matches_sequence(&[down.clone(), forward.clone()], 1.4, 0.3));
+        assert!(!combos.matches_sequence(&[down.clone(), forward.clone()], 1.6, 0.3));
+        assert!(!combos.matches_sequence(&[forward.clone(), down.clone()], 1.4, 0.3));
+
+        let quarter_circle = InputBinding::sequence([down, forward, InputBinding::Mouse(MouseButton::Left)]);
+        assert!(quarter_circle.is_combo());
+        assert!(!InputBinding::Key(KeyCode::KeyF).is_combo());
+    }
+
+    #[test]
+    fn test_input_context_layers() {
+        let rules = InputContextRules::default();
+        let mut stack = InputContextStack::default();
+        assert!(stack.reaches_gameplay(InputAction::Jump, &rules));
+
+        // Vehicle passes movement through but keeps jumping
+        stack.set_active(InputContext::Vehicle, true);
+        assert!(stack.reaches_gameplay(InputAction::MoveForward, &rules));
+        assert!(!stack.reaches_gameplay(InputAction::Jump, &rules));
+
+        // The map stops everything but pausing, and takes zooming for itself
+        stack.set_active(InputContext::Map, true);
+        assert!(!stack.reaches_gameplay(InputAction::MoveForward, &rules));
+        assert!(stack.reaches_gameplay(InputAction::Pause, &rules));
+        assert_eq!(stack.consumer(InputAction::ZoomIn, &rules), Some(InputContext::Map));
+        assert_eq!(stack.consumer(InputAction::Fire, &rules), None);
+
+        stack.set_active(InputContext::Map, false);
+        assert!(stack.reaches_gameplay(InputAction::MoveForward, &rules));
+        assert!(!stack.remove(InputContext::Gameplay));
+        assert_eq!(stack.stack, vec![InputContext::Gameplay, InputContext::Vehicle]);
+
+        let mut state = InputState { movement: Vec2::new(0.5, 1.0), fire_pressed: true, select_weapon: Some(2), ..Default::default() };
+        state.clear_action(InputAction::MoveForward);
+        state.clear_action(InputAction::Fire);
+        state.clear_action(InputAction::SelectWeapon3);
+        assert_eq!(state.movement, Vec2::new(0.5, 0.0));
+        assert!(!state.fire_pressed);
+        assert_eq!(state.select_weapon, None);
+    }
+}
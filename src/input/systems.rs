@@ -1,35 +1,171 @@
 use bevy::prelude::*;
-use super::types::{InputAction, InputBinding, BufferedAction, InputContext};
-use super::resources::{InputMap, InputBuffer, InputConfig, RebindState, InputContextStack, InputContextRules, ActionState, ActionValue};
+use bevy::ecs::system::SystemParam;
+use super::types::{InputAction, InputBinding, BufferedAction, InputContext, ALL_INPUT_ACTIONS};
+use super::resources::{InputMap, InputBuffer, InputComboState, InputConfig, RebindState, InputContextStack, InputContextRules, ActionState, ActionValue};
 use super::components::{InputState, PlayerInputSettings, InputDevice};
+use super::accessibility::ActionIntents;
+use super::glyphs::default_gamepad_button;
 use crate::game_manager::types::GameState;
-use crate::inventory::InventoryUIRoot;
+use crate::inventory::{CompanionInventoryUIRoot, InventoryUIRoot};
 use crate::character::{CharacterMovementState, Player};
 use bevy::input::axis::Axis;
 use bevy::input::gamepad::{Gamepad, GamepadAxis, GamepadButton};
+use std::collections::{HashMap, HashSet};
+
+/// Device buttons and combo state the bindings are read from
+struct BindingReader<'a> {
+    keyboard: &'a ButtonInput<KeyCode>,
+    mouse_buttons: &'a ButtonInput<MouseButton>,
+    gamepads: Vec<&'a Gamepad>,
+    combos: &'a InputComboState,
+}
 
-/// Update input state from devices based on current InputMap
-pub fn update_input_state(
+impl BindingReader<'_> {
+    fn pressed(&self, binding: &InputBinding) -> bool {
+        match binding {
+            InputBinding::Key(code) => self.keyboard.pressed(*code),
+            InputBinding::Mouse(button) => self.mouse_buttons.pressed(*button),
+            InputBinding::Gamepad(button) => self.gamepads.iter().any(|gamepad| gamepad.pressed(*button)),
+            InputBinding::Chord(buttons) => !buttons.is_empty() && buttons.iter().all(|button| self.pressed(button)),
+            InputBinding::Sequence(_) => self.combos.held.contains(binding),
+        }
+    }
+
+    /// Just pressed, ignoring chord shadowing
+    fn button_just_pressed(&self, binding: &InputBinding) -> bool {
+        match binding {
+            InputBinding::Key(code) => self.keyboard.just_pressed(*code),
+            InputBinding::Mouse(button) => self.mouse_buttons.just_pressed(*button),
+            InputBinding::Gamepad(button) => self.gamepads.iter().any(|gamepad| gamepad.just_pressed(*button)),
+            InputBinding::Chord(buttons) => {
+                self.pressed(binding) && buttons.iter().any(|button| self.button_just_pressed(button))
+            }
+            InputBinding::Sequence(_) => self.combos.completed.contains(binding),
+        }
+    }
+
+    fn just_pressed(&self, binding: &InputBinding) -> bool {
+        self.button_just_pressed(binding) && !self.combos.shadowed.contains(binding)
+    }
+
+    fn just_released(&self, binding: &InputBinding) -> bool {
+        match binding {
+            InputBinding::Key(code) => self.keyboard.just_released(*code),
+            InputBinding::Mouse(button) => self.mouse_buttons.just_released(*button),
+            InputBinding::Gamepad(button) => self.gamepads.iter().any(|gamepad| gamepad.just_released(*button)),
+            InputBinding::Chord(buttons) => {
+                buttons.iter().any(|button| self.just_released(button))
+                    && buttons.iter().all(|button| self.pressed(button) || self.just_released(button))
+            }
+            InputBinding::Sequence(_) => self.combos.released.contains(binding),
+        }
+    }
+}
+
+/// Track button presses and resolve the chord and sequence bindings of the InputMap
+pub fn update_input_combos(
     time: Res<Time>,
     keyboard: Res<ButtonInput<KeyCode>>,
     mouse_buttons: Res<ButtonInput<MouseButton>>,
+    gamepad_query: Query<&Gamepad>,
     input_map: Res<InputMap>,
+    config: Res<InputConfig>,
+    mut combo_state: ResMut<InputComboState>,
+) {
+    let now = time.elapsed_secs();
+    let mut completed = HashSet::new();
+    let mut released = HashSet::new();
+    let mut held = combo_state.held.clone();
+    let mut shadowed = HashSet::new();
+    let mut presses = Vec::new();
+
+    {
+        let reader = BindingReader {
+            keyboard: &keyboard,
+            mouse_buttons: &mouse_buttons,
+            gamepads: gamepad_query.iter().collect(),
+            combos: &combo_state,
+        };
+
+        for (action, binding) in input_map.bindings.iter().flat_map(|(action, bindings)| bindings.iter().map(move |binding| (*action, binding))) {
+            match binding {
+                InputBinding::Chord(buttons) => {
+                    if reader.button_just_pressed(binding) {
+                        shadowed.extend(buttons.iter().cloned());
+                    }
+                }
+                InputBinding::Sequence(steps) => {
+                    let Some((last, rest)) = steps.split_last() else { continue };
+                    if reader.button_just_pressed(last) && combo_state.matches_sequence(rest, now, config.sequence_window(action)) {
+                        completed.insert(binding.clone());
+                        held.insert(binding.clone());
+                    } else if held.contains(binding) && !reader.pressed(last) {
+                        held.remove(binding);
+                        released.insert(binding.clone());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        presses.extend(keyboard.get_just_pressed().map(|code| InputBinding::Key(*code)));
+        presses.extend(mouse_buttons.get_just_pressed().map(|button| InputBinding::Mouse(*button)));
+        for gamepad in reader.gamepads.iter() {
+            presses.extend(gamepad.get_just_pressed().map(|button| InputBinding::Gamepad(*button)));
+        }
+    }
+
+    let combos = &mut *combo_state;
+    combos.completed = completed;
+    combos.released = released;
+    combos.held = held;
+    combos.shadowed = shadowed;
+    // This frame's presses are only steps for the next frames' sequences
+    combos.history.extend(presses.into_iter().map(|binding| (binding, now)));
+    let excess = combos.history.len().saturating_sub(InputComboState::HISTORY_LEN);
+    combos.history.drain(..excess);
+}
+
+/// Device buttons, bindings and combo state the input state is read from
+#[derive(SystemParam)]
+pub struct BindingSources<'w, 's> {
+    pub keyboard: Res<'w, ButtonInput<KeyCode>>,
+    pub mouse_buttons: Res<'w, ButtonInput<MouseButton>>,
+    pub gamepad_query: Query<'w, 's, &'static Gamepad>,
+    pub input_map: Res<'w, InputMap>,
+    pub combo_state: Res<'w, InputComboState>,
+}
+
+/// Active input contexts and the rules for what they let through to gameplay
+#[derive(SystemParam)]
+pub struct InputContexts<'w> {
+    pub context_stack: Res<'w, InputContextStack>,
+    pub context_rules: Res<'w, InputContextRules>,
+}
+
+/// Update input state from devices based on current InputMap
+pub fn update_input_state(
+    time: Res<Time>,
+    sources: BindingSources,
+    config: Res<InputConfig>,
     mut input_state: ResMut<super::components::InputState>, // Using component as resource here since we derive Resource on it
     mut input_buffer: ResMut<InputBuffer>,
-    context_stack: Res<InputContextStack>,
-    context_rules: Res<InputContextRules>,
+    mut intents: ResMut<ActionIntents>,
+    contexts: InputContexts,
 ) {
     if !input_state.enabled {
         return;
     }
+    let BindingSources { keyboard, mouse_buttons, gamepad_query, input_map, combo_state } = sources;
+    let InputContexts { context_stack, context_rules } = contexts;
+
+    let is_blocked = |action: InputAction| -> bool { !context_stack.reaches_gameplay(action, &context_rules) };
 
-    let current_context = context_stack.current();
-    let is_blocked = |action: InputAction| -> bool {
-        context_rules
-            .blocked_actions
-            .get(&current_context)
-            .map(|set| set.contains(&action))
-            .unwrap_or(false)
+    let reader = BindingReader {
+        keyboard: &keyboard,
+        mouse_buttons: &mouse_buttons,
+        gamepads: gamepad_query.iter().collect(),
+        combos: &combo_state,
     };
 
     let check_action = |action: InputAction| -> bool {
@@ -37,10 +173,7 @@ pub fn update_input_state(
             return false;
         }
         if let Some(bindings) = input_map.bindings.get(&action) {
-            bindings.iter().any(|binding| match binding {
-                InputBinding::Key(code) => keyboard.pressed(code.clone()),
-                InputBinding::Mouse(button) => mouse_buttons.pressed(button.clone()),
-            })
+            bindings.iter().any(|binding| reader.pressed(binding))
         } else {
             false
         }
@@ -51,10 +184,7 @@ pub fn update_input_state(
             return false;
         }
         if let Some(bindings) = input_map.bindings.get(&action) {
-            bindings.iter().any(|binding| match binding {
-                InputBinding::Key(code) => keyboard.just_pressed(code.clone()),
-                InputBinding::Mouse(button) => mouse_buttons.just_pressed(button.clone()),
-            })
+            bindings.iter().any(|binding| reader.just_pressed(binding))
         } else {
             false
         }
@@ -65,28 +195,26 @@ pub fn update_input_state(
             return false;
         }
         if let Some(bindings) = input_map.bindings.get(&action) {
-            bindings.iter().any(|binding| match binding {
-                InputBinding::Key(code) => keyboard.just_released(code.clone()),
-                InputBinding::Mouse(button) => mouse_buttons.just_released(button.clone()),
-            })
+            bindings.iter().any(|binding| reader.just_released(binding))
         } else {
             false
         }
     };
 
-    // Buffer certain actions
-    let actions_to_buffer = [
-        InputAction::Jump,
-        InputAction::Interact,
-        InputAction::LockOn,
-        InputAction::AbilityUse,
-    ];
-
-    for action in actions_to_buffer {
-        if check_action_just_pressed(action) {
+    // Buffer the actions with a buffer window, and every action triggered by a chord or sequence
+    for action in ALL_INPUT_ACTIONS {
+        if is_blocked(action) {
+            continue;
+        }
+        let combo = input_map
+            .bindings
+            .get(&action)
+            .and_then(|bindings| bindings.iter().find(|binding| binding.is_combo() && reader.just_pressed(binding)));
+        if combo.is_some() || (config.buffer_window(action) > 0.0 && check_action_just_pressed(action)) {
             input_buffer.actions.push(BufferedAction {
                 action,
                 timestamp: time.elapsed_secs(),
+                combo: combo.cloned(),
             });
         }
     }
@@ -106,6 +234,7 @@ pub fn update_input_state(
     input_state.lean_left = check_action(InputAction::LeanLeft);
     input_state.lean_right = check_action(InputAction::LeanRight);
     input_state.block_pressed = check_action(InputAction::Block);
+    input_state.free_look_held = check_action(InputAction::FreeLook);
     input_state.fire_pressed = check_action(InputAction::Fire);
 
     // Just Pressed Input
@@ -113,6 +242,11 @@ pub fn update_input_state(
     input_state.interact_pressed = check_action_just_pressed(InputAction::Interact);
     input_state.lock_on_pressed = check_action_just_pressed(InputAction::LockOn);
     input_state.attack_pressed = check_action_just_pressed(InputAction::Attack);
+    input_state.attack_held = check_action(InputAction::Attack);
+    input_state.attack_released = check_action_just_released(InputAction::Attack);
+    input_state.special_move_pressed = check_action_just_pressed(InputAction::SpecialMove);
+    input_state.dash_pressed = check_action_just_pressed(InputAction::Dash);
+    input_state.take_cover_pressed = check_action_just_pressed(InputAction::TakeCover);
     input_state.switch_camera_mode_pressed = check_action_just_pressed(InputAction::SwitchCameraMode);
     input_state.fire_just_pressed = check_action_just_pressed(InputAction::Fire);
     input_state.reload_pressed = check_action_just_pressed(InputAction::Reload);
@@ -120,7 +254,9 @@ pub fn update_input_state(
     input_state.next_weapon_pressed = check_action_just_pressed(InputAction::NextWeapon);
     input_state.prev_weapon_pressed = check_action_just_pressed(InputAction::PrevWeapon);
     input_state.toggle_inventory_pressed = check_action_just_pressed(InputAction::ToggleInventory);
+    input_state.toggle_hud_pressed = check_action_just_pressed(InputAction::ToggleHud);
     input_state.side_switch_pressed = check_action_just_pressed(InputAction::SideSwitch);
+    input_state.pause_pressed = check_action_just_pressed(InputAction::Pause);
     
     // Stealth/Advanced
     input_state.hide_pressed = check_action_just_pressed(InputAction::Hide);
@@ -131,6 +267,8 @@ pub fn update_input_state(
     input_state.ability_use_pressed = check_action_just_pressed(InputAction::AbilityUse);
     input_state.ability_use_released = check_action_just_released(InputAction::AbilityUse);
     input_state.ability_use_held = check_action(InputAction::AbilityUse);
+    input_state.emote_wheel_held = check_action(InputAction::EmoteWheel);
+    input_state.emote_wheel_released = check_action_just_released(InputAction::EmoteWheel);
 
     // Weapon Selection
     input_state.select_weapon = None;
@@ -156,8 +294,10 @@ pub fn update_input_state(
     else if check_action_just_pressed(InputAction::AbilitySelect7) { input_state.select_ability = Some(6); }
     else if check_action_just_pressed(InputAction::AbilitySelect8) { input_state.select_ability = Some(7); }
 
-    // Look (handled by mouse events typically, but for this system we'll need to re-enable it if needed)
-    // input_state.look = ...
+    // Toggles and repeats on top of the raw buttons
+    intents.apply(&mut input_state, &config.accessibility, check_action, time.delta_secs());
+
+    // Look comes from `apply_mouse_look`, next in the chain
 }
 
 /// System to handle runtime remapping of actions
@@ -169,9 +309,27 @@ pub fn handle_rebinding(
 ) {
     let Some(action) = rebind_state.action else { return };
 
+    // Modifiers held with another key make a chord (Ctrl+F); alone they bind on release
+    const MODIFIERS: [KeyCode; 6] = [
+        KeyCode::ControlLeft, KeyCode::ControlRight,
+        KeyCode::ShiftLeft, KeyCode::ShiftRight,
+        KeyCode::AltLeft, KeyCode::AltRight,
+    ];
+
     let mut new_binding = None;
-    if let Some(key) = keyboard.get_just_pressed().next() {
-        new_binding = Some(InputBinding::Key(key.clone()));
+    if let Some(key) = keyboard.get_just_pressed().find(|key| !MODIFIERS.contains(key)) {
+        let held: Vec<InputBinding> = MODIFIERS
+            .iter()
+            .filter(|modifier| keyboard.pressed(**modifier))
+            .map(|modifier| InputBinding::Key(*modifier))
+            .collect();
+        new_binding = Some(if held.is_empty() {
+            InputBinding::Key(*key)
+        } else {
+            InputBinding::chord(held.into_iter().chain([InputBinding::Key(*key)]))
+        });
+    } else if let Some(modifier) = keyboard.get_just_released().find(|key| MODIFIERS.contains(key)) {
+        new_binding = Some(InputBinding::Key(*modifier));
     } else if let Some(button) = mouse_buttons.get_just_pressed().next() {
         new_binding = Some(InputBinding::Mouse(button.clone()));
     }
@@ -189,7 +347,10 @@ pub fn cleanup_input_buffer(
     mut input_buffer: ResMut<InputBuffer>,
 ) {
     let now = time.elapsed_secs();
-    input_buffer.actions.retain(|ba| now - ba.timestamp <= config.buffer_ttl);
+    input_buffer.actions.retain(|ba| {
+        let window = if ba.combo.is_some() { config.buffer_ttl.max(config.buffer_window(ba.action)) } else { config.buffer_window(ba.action) };
+        now - ba.timestamp <= window
+    });
 }
 
 /// Process movement input (Stub)
@@ -198,14 +359,30 @@ pub fn process_movement_input(_input: Res<InputState>) {}
 /// Process action input (Stub)
 pub fn process_action_input(_input: Res<InputState>) {}
 
+/// Players driven by input rather than AI
+pub type InputDrivenPlayer = (With<Player>, Without<crate::ai::AiController>);
+
+/// Gamepad buttons and sticks read per player
+#[derive(SystemParam)]
+pub struct GamepadInputs<'w> {
+    pub gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+    pub gamepad_axes: Res<'w, Axis<GamepadAxis>>,
+}
+
 /// System to sync global input state to the player entity's component
 pub fn player_input_sync_system(
+    time: Res<Time>,
     input_state: Res<InputState>,
-    gamepad_buttons: Res<ButtonInput<GamepadButton>>,
-    gamepad_axes: Res<Axis<GamepadAxis>>,
-    mut query: Query<(&mut InputState, Option<&PlayerInputSettings>), (With<crate::character::Player>, Without<crate::ai::AiController>)>,
+    config: Res<InputConfig>,
+    contexts: InputContexts,
+    gamepads: GamepadInputs,
+    mut query: Query<(Entity, &mut InputState, Option<&PlayerInputSettings>), InputDrivenPlayer>,
+    mut gamepad_intents: Local<HashMap<Entity, ActionIntents>>,
 ) {
-    for (mut player_input, settings) in query.iter_mut() {
+    let InputContexts { context_stack, context_rules } = contexts;
+    let GamepadInputs { gamepad_buttons, gamepad_axes } = gamepads;
+    gamepad_intents.retain(|entity, _| query.contains(*entity));
+    for (entity, mut player_input, settings) in query.iter_mut() {
         let settings = settings.cloned().unwrap_or_default();
         if !settings.enabled {
             player_input.set_input_enabled(false);
@@ -216,10 +393,22 @@ pub fn player_input_sync_system(
             InputDevice::KeyboardMouse => input_state.clone(),
             InputDevice::Gamepad { id } => {
                 let gamepad = Gamepad::new(id);
-                build_gamepad_input_state(gamepad, &gamepad_buttons, &gamepad_axes)
+                let mut state = build_gamepad_input_state(gamepad, &config, &gamepad_buttons, &gamepad_axes);
+                // Keyboard players get theirs from the global state
+                let held = |action: InputAction| {
+                    default_gamepad_button(action).is_some_and(|button| gamepad_buttons.pressed(GamepadButton::new(gamepad, button)))
+                };
+                gamepad_intents.entry(entity).or_default().apply(&mut state, &config.accessibility, held, time.delta_secs());
+                state
             }
         };
 
+        // Gamepad and touch input skip the keyboard's context checks
+        for action in ALL_INPUT_ACTIONS {
+            if !context_stack.reaches_gameplay(action, &context_rules) {
+                next_state.clear_action(action);
+            }
+        }
         next_state.apply_locks(&settings.locks);
         *player_input = next_state;
     }
@@ -231,14 +420,7 @@ pub fn update_action_state(
     context_rules: Res<InputContextRules>,
     mut action_state: ResMut<ActionState>,
 ) {
-    let current_context = context_stack.current();
-    let is_blocked = |action: InputAction| -> bool {
-        context_rules
-            .blocked_actions
-            .get(&current_context)
-            .map(|set| set.contains(&action))
-            .unwrap_or(false)
-    };
+    let is_blocked = |action: InputAction| -> bool { !context_stack.reaches_gameplay(action, &context_rules) };
 
     for (action, value) in action_state.actions.iter_mut() {
         if is_blocked(*action) {
@@ -279,7 +461,12 @@ fn read_action_value(action: InputAction, input_state: &InputState) -> ActionVal
         InputAction::Aim => ActionValue { pressed: input_state.aim_pressed, ..default() },
         InputAction::LeanLeft => ActionValue { pressed: input_state.lean_left, ..default() },
         InputAction::LeanRight => ActionValue { pressed: input_state.lean_right, ..default() },
-        InputAction::Attack => ActionValue { pressed: input_state.attack_pressed, just_pressed: input_state.attack_pressed, ..default() },
+        InputAction::Attack => ActionValue {
+            pressed: input_state.attack_held,
+            just_pressed: input_state.attack_pressed,
+            just_released: input_state.attack_released,
+            ..default()
+        },
         InputAction::Block => ActionValue { pressed: input_state.block_pressed, ..default() },
         InputAction::SwitchCameraMode => ActionValue { pressed: input_state.switch_camera_mode_pressed, just_pressed: input_state.switch_camera_mode_pressed, ..default() },
         InputAction::Fire => ActionValue { pressed: input_state.fire_pressed, just_pressed: input_state.fire_just_pressed, ..default() },
@@ -327,11 +514,23 @@ fn read_action_value(action: InputAction, input_state: &InputState) -> ActionVal
             just_pressed: input_state.select_ability.is_some(),
             ..default()
         },
+        InputAction::EmoteWheel => ActionValue {
+            pressed: input_state.emote_wheel_held,
+            just_released: input_state.emote_wheel_released,
+            ..default()
+        },
+        InputAction::SpecialMove => ActionValue { pressed: input_state.special_move_pressed, just_pressed: input_state.special_move_pressed, ..default() },
+        InputAction::ToggleHud => ActionValue { pressed: input_state.toggle_hud_pressed, just_pressed: input_state.toggle_hud_pressed, ..default() },
+        InputAction::Pause => ActionValue { pressed: input_state.pause_pressed, just_pressed: input_state.pause_pressed, ..default() },
+        InputAction::Dash => ActionValue { pressed: input_state.dash_pressed, just_pressed: input_state.dash_pressed, ..default() },
+        InputAction::TakeCover => ActionValue { pressed: input_state.take_cover_pressed, just_pressed: input_state.take_cover_pressed, ..default() },
+        InputAction::FreeLook => ActionValue { pressed: input_state.free_look_held, ..default() },
     }
 }
 
 fn build_gamepad_input_state(
     gamepad: Gamepad,
+    config: &InputConfig,
     buttons: &ButtonInput<GamepadButton>,
     axes: &Axis<GamepadAxis>,
 ) -> InputState {
@@ -353,9 +552,17 @@ fn build_gamepad_input_state(
         buttons.just_released(GamepadButton::new(gamepad, button_type))
     };
 
+    // Sticks are shaped here so movement and camera never see raw values
     let movement = Vec2::new(axis(GamepadAxis::LeftStickX), axis(GamepadAxis::LeftStickY));
-    state.movement = movement.normalize_or_zero();
-    state.look = Vec2::new(axis(GamepadAxis::RightStickX), axis(GamepadAxis::RightStickY));
+    state.movement = config.move_stick.apply(movement);
+    let mut look = config.look_stick.apply(Vec2::new(axis(GamepadAxis::RightStickX), axis(GamepadAxis::RightStickY)));
+    if config.invert_y_axis {
+        look.y = -look.y;
+    }
+    // Flick stick turns the camera through `aim_turn` instead
+    if !config.flick_stick.enabled {
+        state.look = look * config.gamepad_sensitivity;
+    }
 
     state.jump_pressed = button_just(GamepadButton::South);
     state.interact_pressed = button_just(GamepadButton::West);
@@ -363,6 +570,9 @@ fn build_gamepad_input_state(
     state.sprint_pressed = button(GamepadButton::LeftStick);
     state.aim_pressed = button(GamepadButton::LeftTrigger2);
     state.attack_pressed = button_just(GamepadButton::RightShoulder);
+    state.attack_held = button(GamepadButton::RightShoulder);
+    state.attack_released = button_released(GamepadButton::RightShoulder);
+    state.special_move_pressed = button_just(GamepadButton::RightStick);
     state.fire_pressed = button(GamepadButton::RightTrigger2);
     state.fire_just_pressed = button_just(GamepadButton::RightTrigger2);
     state.reload_pressed = button_just(GamepadButton::North);
@@ -371,21 +581,31 @@ fn build_gamepad_input_state(
     state.switch_camera_mode_pressed = button_just(GamepadButton::Select);
     state.toggle_inventory_pressed = button_just(GamepadButton::Start);
     state.reset_camera_pressed = button_just(GamepadButton::DPadUp);
+    state.toggle_hud_pressed = button_just(GamepadButton::DPadLeft);
+    state.take_cover_pressed = button_just(GamepadButton::DPadRight);
 
     state.ability_use_pressed = button_just(GamepadButton::RightShoulder);
     state.ability_use_released = button_released(GamepadButton::RightShoulder);
     state.ability_use_held = button(GamepadButton::RightShoulder);
 
+    state.emote_wheel_held = button(GamepadButton::DPadDown);
+    state.emote_wheel_released = button_released(GamepadButton::DPadDown);
+
     // TODO: map weapon/ability selection to D-Pad or face buttons based on user config.
 
     state
 }
 
+type InventoryPanelRoot = Or<(With<InventoryUIRoot>, With<CompanionInventoryUIRoot>)>;
+
+/// Keeps the menu and vehicle layers in step with the game, leaving the layers
+/// other modules push (loading screen, map, dialog) alone
 pub fn update_input_context(
     state: Res<State<GameState>>,
-    inventory_query: Query<&Visibility, With<InventoryUIRoot>>,
+    inventory_query: Query<&Visibility, InventoryPanelRoot>,
     player_query: Query<&CharacterMovementState, With<Player>>,
     mut context_stack: ResMut<InputContextStack>,
+    mut pushed: Local<Vec<InputContext>>,
 ) {
     let inventory_open = inventory_query
         .iter()
@@ -393,14 +613,18 @@ pub fn update_input_context(
 
     let in_vehicle = player_query.iter().any(|movement| movement.is_in_vehicle);
 
-    let desired = if *state == GameState::Paused || inventory_open {
-        InputContext::Menu
-    } else if in_vehicle {
-        InputContext::Vehicle
-    } else {
-        InputContext::Gameplay
-    };
-
-    context_stack.stack.clear();
-    context_stack.stack.push(desired);
+    let wanted = [
+        (InputContext::Vehicle, in_vehicle),
+        (InputContext::Menu, *state == GameState::Paused || inventory_open),
+    ];
+    for (context, active) in wanted {
+        let is_pushed = pushed.contains(&context);
+        if active && !is_pushed {
+            context_stack.push(context);
+            pushed.push(context);
+        } else if !active && is_pushed {
+            context_stack.remove(context);
+            pushed.retain(|layer| *layer != context);
+        }
+    }
 }
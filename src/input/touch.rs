@@ -156,9 +156,17 @@ fn apply_touch_action(
     match action {
         InputAction::Jump => input_state.jump_pressed = just_pressed,
         InputAction::Interact => input_state.interact_pressed = just_pressed,
-        InputAction::Attack => input_state.attack_pressed = just_pressed,
+        InputAction::Attack => {
+            input_state.attack_pressed = just_pressed;
+            input_state.attack_held = pressed;
+            input_state.attack_released = !pressed;
+        }
+        InputAction::SpecialMove => input_state.special_move_pressed = just_pressed,
+        InputAction::Dash => input_state.dash_pressed = just_pressed,
+        InputAction::TakeCover => input_state.take_cover_pressed = just_pressed,
         InputAction::Block => input_state.block_pressed = pressed,
         InputAction::Aim => input_state.aim_pressed = pressed,
+        InputAction::FreeLook => input_state.free_look_held = pressed,
         InputAction::Fire => {
             input_state.fire_pressed = pressed;
             input_state.fire_just_pressed = just_pressed;
@@ -1,7 +1,16 @@
 use bevy::prelude::*;
 
+/// Input phases, run inside `GameControllerSet::Input`
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputSet {
+    /// Device state read into `ActionState` and `InputState`
+    Gather,
+    /// `InputState` applied to player movement and actions
+    Apply,
+}
+
 /// Logical game actions
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, serde::Serialize, serde::Deserialize)]
 pub enum InputAction {
     MoveForward,
     MoveBackward,
@@ -51,9 +60,16 @@ pub enum InputAction {
     AbilitySelect6,
     AbilitySelect7,
     AbilitySelect8,
+    EmoteWheel,
+    SpecialMove,
+    ToggleHud,
+    Pause,
+    Dash,
+    TakeCover,
+    FreeLook,
 }
 
-pub const ALL_INPUT_ACTIONS: [InputAction; 46] = [
+pub const ALL_INPUT_ACTIONS: [InputAction; 53] = [
     InputAction::MoveForward,
     InputAction::MoveBackward,
     InputAction::MoveLeft,
@@ -100,6 +116,13 @@ pub const ALL_INPUT_ACTIONS: [InputAction; 46] = [
     InputAction::AbilitySelect6,
     InputAction::AbilitySelect7,
     InputAction::AbilitySelect8,
+    InputAction::EmoteWheel,
+    InputAction::SpecialMove,
+    InputAction::ToggleHud,
+    InputAction::Pause,
+    InputAction::Dash,
+    InputAction::TakeCover,
+    InputAction::FreeLook,
 ];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
@@ -107,13 +130,52 @@ pub enum InputContext {
     Gameplay,
     Menu,
     Vehicle,
+    Map,
+    Dialog,
+    /// Developer console; the keyboard types into it
+    Console,
+    /// In-world computer terminal
+    Terminal,
+}
+
+/// How a context layer treats the actions it doesn't consume
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum InputLayerMode {
+    /// They reach the layers below
+    #[default]
+    PassThrough,
+    /// They stop here, except the layer's pass-through actions
+    Exclusive,
 }
 
 /// Input binding types
-#[derive(Debug, Clone, Reflect)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect, serde::Serialize, serde::Deserialize)]
 pub enum InputBinding {
     Key(KeyCode),
     Mouse(MouseButton),
+    /// Button of any connected gamepad
+    Gamepad(GamepadButton),
+    /// Buttons held together (Ctrl+F, LB+X); triggers when the last of them goes down
+    Chord(Vec<InputBinding>),
+    /// Buttons pressed in order (double-tap, quarter-circle), each within
+    /// `InputConfig::combo_window` of the previous one; stays pressed while the last is held.
+    /// Steps are single buttons, except the last which may be a chord.
+    Sequence(Vec<InputBinding>),
+}
+
+impl InputBinding {
+    pub fn chord(bindings: impl IntoIterator<Item = InputBinding>) -> Self {
+        InputBinding::Chord(bindings.into_iter().collect())
+    }
+
+    pub fn sequence(bindings: impl IntoIterator<Item = InputBinding>) -> Self {
+        InputBinding::Sequence(bindings.into_iter().collect())
+    }
+
+    /// Chord or sequence
+    pub fn is_combo(&self) -> bool {
+        matches!(self, InputBinding::Chord(_) | InputBinding::Sequence(_))
+    }
 }
 
 /// A buffered action that was recently pressed
@@ -121,4 +183,6 @@ pub enum InputBinding {
 pub struct BufferedAction {
     pub action: InputAction,
     pub timestamp: f32,
+    /// Chord or sequence that triggered the action, for moves tied to a specific input
+    pub combo: Option<InputBinding>,
 }
@@ -0,0 +1,489 @@
+//! Gamepad and keyboard UI navigation
+//!
+//! Menus are [`FocusScope`] roots; navigation stays inside the top visible
+//! scope. Every `Button` is [`Focusable`] automatically, so the built-in menus
+//! work as they are: the d-pad, left stick and arrow keys move the focus to the
+//! nearest focusable node in that direction, accept (South / Enter) presses the
+//! focused node through its `Interaction` exactly like a click, cancel (East /
+//! Escape) presses the scope's [`UiCancelButton`] or closes the scope, and the
+//! bumpers (Tab / Shift+Tab) switch [`UiTab`]s, or jump between the button
+//! groups of a menu without tabs. The focused node gets an outline while the
+//! gamepad or keyboard drives the UI.
+
+use bevy::ecs::entity::EntityHashMap;
+use bevy::input::gamepad::{Gamepad, GamepadButton};
+use bevy::input::mouse::AccumulatedMouseMotion;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::ui::{ComputedNode, UiGlobalTransform};
+use crate::utils::EventQueue;
+use super::accessibility::{InputRepeat, RepeatTimer};
+use super::resources::InputConfig;
+
+/// UI node the focus can move to; added to every `Button`
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Focusable {
+    /// Skipped by navigation
+    pub disabled: bool,
+}
+
+/// What cancel does to a scope without a [`UiCancelButton`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum FocusCancel {
+    /// Only reported as [`UiNavigationEvent::Cancelled`]
+    None,
+    /// Sets the scope root `Visibility::Hidden`
+    #[default]
+    Hide,
+    Despawn,
+}
+
+/// Root of a menu; navigation stays inside the top visible scope
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct FocusScope {
+    /// Higher priority scopes (popups) take the focus from lower ones
+    pub priority: i32,
+    pub cancel: FocusCancel,
+}
+
+impl FocusScope {
+    pub fn popup(priority: i32) -> Self {
+        Self {
+            priority,
+            cancel: FocusCancel::Despawn,
+        }
+    }
+}
+
+/// Button pressed by cancel in its scope (close, back...)
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct UiCancelButton;
+
+/// Tab button pressed by the bumpers
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct UiTab;
+
+/// Current focus
+#[derive(Resource, Debug, Default)]
+pub struct UiFocus {
+    pub focused: Option<Entity>,
+    /// Scope the focus is in
+    pub scope: Option<Entity>,
+    /// True while the gamepad or keyboard drives the UI; the mouse clears it
+    pub highlight_visible: bool,
+}
+
+impl UiFocus {
+    /// Whether a menu has the focus (gameplay can ignore menu buttons meanwhile)
+    pub fn in_menu(&self) -> bool {
+        self.scope.is_some()
+    }
+}
+
+/// Reported by UI navigation
+#[derive(Debug, Clone)]
+pub enum UiNavigationEvent {
+    FocusChanged { from: Option<Entity>, to: Option<Entity> },
+    Accepted { entity: Entity },
+    Cancelled { scope: Entity },
+    TabChanged { scope: Entity, tab: Entity },
+}
+
+pub type UiNavigationEventQueue = EventQueue<UiNavigationEvent>;
+
+/// UI navigation settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct UiNavigationSettings {
+    pub enabled: bool,
+    /// Make every `Button` focusable
+    pub auto_focus_buttons: bool,
+    pub highlight_color: Color,
+    pub highlight_width: f32,
+    /// Left stick deflection that moves the focus
+    pub stick_threshold: f32,
+}
+
+impl Default for UiNavigationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            auto_focus_buttons: true,
+            highlight_color: Color::srgb(1.0, 0.85, 0.2),
+            highlight_width: 3.0,
+            stick_threshold: 0.5,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct NavigationRepeat {
+    direction: Option<IVec2>,
+    timer: RepeatTimer,
+}
+
+/// What navigation keeps between frames
+#[derive(Default)]
+pub struct NavigationMemory {
+    repeat: NavigationRepeat,
+    /// Pressed last frame, released this frame
+    pressed: Option<Entity>,
+    /// Shown scopes in the order they opened
+    scope_order: Vec<Entity>,
+    /// Active tab of each scope
+    active_tabs: EntityHashMap<usize>,
+}
+
+/// Devices and repeat settings navigation input is read from
+#[derive(SystemParam)]
+pub struct NavigationDevices<'w, 's> {
+    pub time: Res<'w, Time>,
+    pub input_config: Res<'w, InputConfig>,
+    pub keyboard: Res<'w, ButtonInput<KeyCode>>,
+    pub mouse_motion: Res<'w, AccumulatedMouseMotion>,
+    pub gamepad_query: Query<'w, 's, &'static Gamepad>,
+}
+
+/// Focusable node data navigation moves between
+type FocusableNode<'a> = (
+    Entity,
+    &'a Focusable,
+    &'a ComputedNode,
+    &'a UiGlobalTransform,
+    Option<&'a InheritedVisibility>,
+    Has<UiCancelButton>,
+    Has<UiTab>,
+);
+
+/// Scopes, the focusable nodes in them and their buttons
+#[derive(SystemParam)]
+pub struct FocusTree<'w, 's> {
+    pub scope_query: Query<'w, 's, (Entity, &'static FocusScope, &'static ComputedNode, Option<&'static InheritedVisibility>)>,
+    pub focusable_query: Query<'w, 's, FocusableNode<'static>>,
+    pub parent_query: Query<'w, 's, &'static ChildOf>,
+    pub interaction_query: Query<'w, 's, &'static mut Interaction>,
+}
+
+#[derive(Default)]
+struct NavigationInput {
+    direction: Option<IVec2>,
+    accept: bool,
+    cancel: bool,
+    tab: i32,
+}
+
+/// System to make new buttons focusable
+pub fn add_focusable_to_buttons(
+    mut commands: Commands,
+    settings: Res<UiNavigationSettings>,
+    button_query: Query<Entity, (Added<Button>, Without<Focusable>)>,
+) {
+    if !settings.auto_focus_buttons {
+        return;
+    }
+    for entity in button_query.iter() {
+        commands.entity(entity).insert(Focusable::default());
+    }
+}
+
+fn read_navigation_input(
+    time: &Time,
+    settings: &UiNavigationSettings,
+    ui_repeat: Option<InputRepeat>,
+    keyboard: &ButtonInput<KeyCode>,
+    gamepad_query: &Query<&Gamepad>,
+    repeat: &mut NavigationRepeat,
+) -> NavigationInput {
+    let mut input = NavigationInput::default();
+
+    let keys = [
+        (KeyCode::ArrowUp, IVec2::NEG_Y),
+        (KeyCode::ArrowDown, IVec2::Y),
+        (KeyCode::ArrowLeft, IVec2::NEG_X),
+        (KeyCode::ArrowRight, IVec2::X),
+    ];
+    input.direction = keys.iter().find(|(key, _)| keyboard.just_pressed(*key)).map(|(_, dir)| *dir);
+    input.accept = keyboard.just_pressed(KeyCode::Enter) || keyboard.just_pressed(KeyCode::NumpadEnter);
+    input.cancel = keyboard.just_pressed(KeyCode::Escape);
+    if keyboard.just_pressed(KeyCode::Tab) {
+        input.tab = if keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight) { -1 } else { 1 };
+    }
+
+    let mut held = None;
+    for gamepad in gamepad_query.iter() {
+        let dpad = [
+            (GamepadButton::DPadUp, IVec2::NEG_Y),
+            (GamepadButton::DPadDown, IVec2::Y),
+            (GamepadButton::DPadLeft, IVec2::NEG_X),
+            (GamepadButton::DPadRight, IVec2::X),
+        ];
+        for (button, dir) in dpad {
+            if gamepad.pressed(button) {
+                held = Some(dir);
+            }
+        }
+        let stick = gamepad.left_stick();
+        if held.is_none() && stick.length() >= settings.stick_threshold {
+            // Stick up is positive, UI down is positive
+            held = Some(if stick.x.abs() > stick.y.abs() {
+                IVec2::new(stick.x.signum() as i32, 0)
+            } else {
+                IVec2::new(0, -stick.y.signum() as i32)
+            });
+        }
+        input.accept |= gamepad.just_pressed(GamepadButton::South);
+        input.cancel |= gamepad.just_pressed(GamepadButton::East);
+        if gamepad.just_pressed(GamepadButton::RightTrigger) {
+            input.tab = 1;
+        } else if gamepad.just_pressed(GamepadButton::LeftTrigger) {
+            input.tab = -1;
+        }
+    }
+
+    // Held directions repeat after a delay
+    match held {
+        Some(dir) if repeat.direction == Some(dir) => {
+            if ui_repeat.is_some_and(|ui_repeat| repeat.timer.tick(&ui_repeat, time.delta_secs())) {
+                input.direction = input.direction.or(Some(dir));
+            }
+        }
+        Some(dir) => {
+            repeat.direction = Some(dir);
+            repeat.timer = RepeatTimer::default();
+            input.direction = input.direction.or(Some(dir));
+        }
+        None => repeat.direction = None,
+    }
+
+    input
+}
+
+fn is_shown(visibility: Option<&InheritedVisibility>, node: &ComputedNode) -> bool {
+    visibility.is_none_or(|visibility| visibility.get()) && node.size().x > 0.0 && node.size().y > 0.0
+}
+
+fn is_descendant(entity: Entity, ancestor: Entity, parent_query: &Query<&ChildOf>) -> bool {
+    let mut current = entity;
+    loop {
+        if current == ancestor {
+            return true;
+        }
+        match parent_query.get(current) {
+            Ok(child_of) => current = child_of.parent(),
+            Err(_) => return false,
+        }
+    }
+}
+
+/// Nearest candidate in `direction`, favouring nodes in line with the focus
+fn find_in_direction(from: Vec2, direction: IVec2, candidates: &[(Entity, Vec2)]) -> Option<Entity> {
+    let direction = direction.as_vec2();
+    candidates
+        .iter()
+        .filter_map(|(entity, position)| {
+            let offset = *position - from;
+            let along = offset.dot(direction);
+            if along <= 1.0 {
+                return None;
+            }
+            let across = (offset - direction * along).length();
+            Some((*entity, along + across * 2.0))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+fn press(entity: Entity, interaction_query: &mut Query<&mut Interaction>, pressed: &mut Option<Entity>) {
+    if let Ok(mut interaction) = interaction_query.get_mut(entity) {
+        *interaction = Interaction::Pressed;
+        *pressed = Some(entity);
+    }
+}
+
+/// System to move the focus and press buttons from gamepad and keyboard input
+pub fn navigate_ui(
+    mut commands: Commands,
+    settings: Res<UiNavigationSettings>,
+    devices: NavigationDevices,
+    mut focus: ResMut<UiFocus>,
+    mut events: ResMut<UiNavigationEventQueue>,
+    tree: FocusTree,
+    mut memory: Local<NavigationMemory>,
+) {
+    let NavigationDevices { time, input_config, keyboard, mouse_motion, gamepad_query } = devices;
+    let FocusTree { scope_query, focusable_query, parent_query, mut interaction_query } = tree;
+    let NavigationMemory { repeat, pressed, scope_order, active_tabs } = &mut *memory;
+    // A navigation press lasts one frame, like a click
+    if let Some(entity) = pressed.take() {
+        if let Ok(mut interaction) = interaction_query.get_mut(entity) {
+            if *interaction == Interaction::Pressed {
+                *interaction = if focus.focused == Some(entity) { Interaction::Hovered } else { Interaction::None };
+            }
+        }
+    }
+
+    if !settings.enabled {
+        focus.focused = None;
+        focus.scope = None;
+        return;
+    }
+
+    // Most recently opened scopes win ties
+    let shown: Vec<(Entity, i32, FocusCancel)> = scope_query
+        .iter()
+        .filter(|(_, _, node, visibility)| is_shown(*visibility, node))
+        .map(|(entity, scope, _, _)| (entity, scope.priority, scope.cancel))
+        .collect();
+    scope_order.retain(|entity| shown.iter().any(|(shown, _, _)| shown == entity));
+    for (entity, _, _) in shown.iter() {
+        if !scope_order.contains(entity) {
+            scope_order.push(*entity);
+        }
+    }
+    let scope = shown
+        .iter()
+        .max_by_key(|(entity, priority, _)| (*priority, scope_order.iter().position(|e| e == entity)))
+        .copied();
+    focus.scope = scope.map(|(entity, _, _)| entity);
+
+    let Some((scope_entity, _, cancel)) = scope else {
+        if focus.focused.is_some() {
+            let from = focus.focused.take();
+            events.send(UiNavigationEvent::FocusChanged { from, to: None });
+        }
+        return;
+    };
+
+    // Focusable nodes of the scope, in reading order
+    let mut candidates: Vec<(Entity, Vec2, bool, bool)> = focusable_query
+        .iter()
+        .filter(|(_, focusable, node, _, visibility, _, _)| !focusable.disabled && is_shown(*visibility, node))
+        .filter(|(entity, ..)| is_descendant(*entity, scope_entity, &parent_query))
+        .map(|(entity, _, _, transform, _, cancel_button, tab)| (entity, transform.translation, cancel_button, tab))
+        .collect();
+    candidates.sort_by(|a, b| a.1.y.total_cmp(&b.1.y).then(a.1.x.total_cmp(&b.1.x)));
+
+    let input = read_navigation_input(&time, &settings, input_config.ui_repeat, &keyboard, &gamepad_query, repeat);
+    if input.direction.is_some() || input.accept || input.cancel || input.tab != 0 {
+        focus.highlight_visible = true;
+    } else if mouse_motion.delta != Vec2::ZERO {
+        focus.highlight_visible = false;
+    }
+
+    let previous = focus.focused;
+    let mut focused = previous.filter(|entity| candidates.iter().any(|(candidate, ..)| candidate == entity));
+    if focused.is_none() {
+        focused = candidates.first().map(|(entity, ..)| *entity);
+    }
+
+    if let (Some(direction), Some(current)) = (input.direction, focused) {
+        let from = candidates.iter().find(|(entity, ..)| *entity == current).map(|(_, position, ..)| *position);
+        let positions: Vec<(Entity, Vec2)> = candidates
+            .iter()
+            .filter(|(entity, ..)| *entity != current)
+            .map(|(entity, position, ..)| (*entity, *position))
+            .collect();
+        if let Some(next) = from.and_then(|from| find_in_direction(from, direction, &positions)) {
+            focused = Some(next);
+        }
+    }
+
+    if input.tab != 0 {
+        let tabs: Vec<Entity> = candidates.iter().filter(|(.., tab)| *tab).map(|(entity, ..)| *entity).collect();
+        if !tabs.is_empty() {
+            let active = active_tabs.entry(scope_entity).or_insert(0);
+            *active = (*active as i32 + input.tab).rem_euclid(tabs.len() as i32) as usize;
+            let tab = tabs[*active];
+            press(tab, &mut interaction_query, pressed);
+            events.send(UiNavigationEvent::TabChanged { scope: scope_entity, tab });
+        } else {
+            // Without tabs, jump between the button groups (rows, panels) of the menu
+            let mut groups: Vec<(Entity, Entity)> = Vec::new();
+            for (entity, ..) in candidates.iter() {
+                let Ok(child_of) = parent_query.get(*entity) else { continue };
+                if !groups.iter().any(|(group, _)| *group == child_of.parent()) {
+                    groups.push((child_of.parent(), *entity));
+                }
+            }
+            let current_group = focused
+                .and_then(|entity| parent_query.get(entity).ok())
+                .and_then(|child_of| groups.iter().position(|(group, _)| *group == child_of.parent()));
+            if let Some(index) = current_group {
+                let next = (index as i32 + input.tab).rem_euclid(groups.len() as i32) as usize;
+                focused = Some(groups[next].1);
+            }
+        }
+    }
+
+    if focused != previous {
+        if let Some(mut interaction) = previous.and_then(|entity| interaction_query.get_mut(entity).ok()) {
+            if *interaction == Interaction::Hovered && focus.highlight_visible {
+                *interaction = Interaction::None;
+            }
+        }
+        focus.focused = focused;
+        events.send(UiNavigationEvent::FocusChanged { from: previous, to: focused });
+    }
+
+    // The focused button looks hovered while the gamepad drives the UI
+    if focus.highlight_visible && pressed.is_none() {
+        if let Some(mut interaction) = focused.and_then(|entity| interaction_query.get_mut(entity).ok()) {
+            interaction.set_if_neq(Interaction::Hovered);
+        }
+    }
+
+    if input.accept {
+        if let Some(entity) = focused {
+            press(entity, &mut interaction_query, pressed);
+            events.send(UiNavigationEvent::Accepted { entity });
+        }
+    } else if input.cancel {
+        if let Some((button, ..)) = candidates.iter().find(|(_, _, cancel_button, _)| *cancel_button) {
+            press(*button, &mut interaction_query, pressed);
+        } else {
+            match cancel {
+                FocusCancel::None => {}
+                FocusCancel::Hide => {
+                    commands.entity(scope_entity).insert(Visibility::Hidden);
+                }
+                FocusCancel::Despawn => {
+                    commands.entity(scope_entity).despawn();
+                }
+            }
+        }
+        events.send(UiNavigationEvent::Cancelled { scope: scope_entity });
+    }
+}
+
+/// System to outline the focused node
+pub fn update_focus_highlight(
+    mut commands: Commands,
+    settings: Res<UiNavigationSettings>,
+    focus: Res<UiFocus>,
+    mut highlighted: Local<Option<Entity>>,
+) {
+    let wanted = focus.focused.filter(|_| focus.highlight_visible && settings.enabled);
+    if wanted == *highlighted && !settings.is_changed() {
+        return;
+    }
+
+    if let Some(entity) = highlighted.take() {
+        if let Ok(mut entity) = commands.get_entity(entity) {
+            entity.remove::<Outline>();
+        }
+    }
+    if let Some(entity) = wanted {
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.insert(Outline::new(
+                Val::Px(settings.highlight_width),
+                Val::Px(1.0),
+                settings.highlight_color,
+            ));
+            *highlighted = Some(entity);
+        }
+    }
+}
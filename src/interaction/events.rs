@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use super::types::InteractionType;
+use crate::utils::EventQueue;
 
 /// Event to add a device to the player's list
 #[derive(Debug, Clone, Copy)]
@@ -8,8 +9,7 @@ pub struct AddDeviceEvent {
     pub device: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct AddDeviceQueue(pub Vec<AddDeviceEvent>);
+pub type AddDeviceQueue = EventQueue<AddDeviceEvent>;
 
 /// Event to remove a device from the player's list
 #[derive(Debug, Clone, Copy)]
@@ -18,10 +18,10 @@ pub struct RemoveDeviceEvent {
     pub device: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct RemoveDeviceQueue(pub Vec<RemoveDeviceEvent>);
+pub type RemoveDeviceQueue = EventQueue<RemoveDeviceEvent>;
 
 /// Event triggered when a valid interaction occurs
+#[derive(Debug, Clone, Copy)]
 pub struct InteractionEvent {
     pub source: Entity,
     pub target: Entity,
@@ -29,5 +29,4 @@ pub struct InteractionEvent {
 }
 
 /// Custom queue for interaction events
-#[derive(Resource, Default)]
-pub struct InteractionEventQueue(pub Vec<InteractionEvent>);
+pub type InteractionEventQueue = EventQueue<InteractionEvent>;
@@ -10,8 +10,10 @@ use components::*;
 use events::*;
 use resources::*;
 use systems::*;
+use crate::utils::EventQueuePlugin;
+use crate::simulation::SimulationAppExt;
 
-pub use types::{InteractionType, DeviceInfo};
+pub use types::{InteractionSet, InteractionType, DeviceInfo};
 pub use components::{
     InteractionDetector, Interactable, UsingDevicesSystem, DeviceStringAction, 
     InteractionPrompt, InteractionData, UsableDevice
@@ -27,12 +29,14 @@ pub struct InteractionPlugin;
 
 impl Plugin for InteractionPlugin {
     fn build(&self, app: &mut App) {
+        let simulation = app.simulation_schedule();
+
         app
-            .init_resource::<InteractionEventQueue>()
+            .add_plugins(EventQueuePlugin::<InteractionEvent>::default())
             .init_resource::<CurrentInteractable>()
             .init_resource::<InteractionDebugSettings>()
-            .init_resource::<AddDeviceQueue>()
-            .init_resource::<RemoveDeviceQueue>()
+            .add_plugins(EventQueuePlugin::<AddDeviceEvent>::default())
+            .add_plugins(EventQueuePlugin::<RemoveDeviceEvent>::default())
             
             // Register types
             .register_type::<InteractionDetector>()
@@ -44,16 +48,28 @@ impl Plugin for InteractionPlugin {
             .register_type::<InteractionData>()
             .register_type::<UsableDevice>()
             
-            .add_systems(Update, (
-                detect_interactables,
-                detect_devices_in_proximity,
-                update_device_list,
-                select_closest_device,
-                validate_interactions,
-                process_interactions,
-                update_interaction_ui,
-                debug_draw_interaction_rays,
-            ).chain())
-            .add_systems(Startup, setup_interaction_ui);
+            .configure_sets(simulation, (
+                InteractionSet::Detection,
+                InteractionSet::Processing,
+                InteractionSet::Ui,
+            ).chain().in_set(crate::GameControllerSet::Interaction))
+            .add_systems(simulation, (
+                (
+                    detect_interactables,
+                    highlight_current_interactable,
+                    detect_devices_in_proximity,
+                    update_device_list,
+                    select_closest_device,
+                ).chain().in_set(InteractionSet::Detection),
+                (
+                    validate_interactions,
+                    process_interactions,
+                ).chain().in_set(InteractionSet::Processing),
+                (
+                    update_interaction_ui,
+                    debug_draw_interaction_rays,
+                ).chain().in_set(InteractionSet::Ui).in_set(crate::headless::PresentationSet),
+            ))
+            .add_systems(Startup, setup_interaction_ui.in_set(crate::headless::PresentationSet));
     }
 }
@@ -1,10 +1,13 @@
 use bevy::prelude::*;
 use avian3d::prelude::*;
-use crate::input::{InputState, InputAction, InputBuffer};
+use crate::input::{GlyphProvider, InputState, InputAction, InputBuffer, InputMap};
+use crate::highlight::{HighlightCommandQueue, HighlightKind};
+use crate::hud::{HudElement, HudPriority};
 use super::types::*;
 use super::components::*;
 use super::events::*;
 use super::resources::*;
+use crate::utils::QueueReader;
 
 /// System to setup the interaction UI
 pub fn setup_interaction_ui(mut commands: Commands) {
@@ -27,6 +30,7 @@ pub fn setup_interaction_ui(mut commands: Commands) {
                 ..default()
             },
             InteractionPrompt,
+            HudElement::new("interaction_prompt", HudPriority::Standard),
             Visibility::Hidden,
         ))
         .with_children(|parent| {
@@ -42,6 +46,8 @@ pub fn setup_interaction_ui(mut commands: Commands) {
 /// System to update the interaction UI based on current detection
 pub fn update_interaction_ui(
     current_interactable: Res<CurrentInteractable>,
+    glyphs: Res<GlyphProvider>,
+    input_map: Res<InputMap>,
     interactables: Query<&Interactable>,
     player_query: Query<&UsingDevicesSystem>,
     mut ui_query: Query<(&mut Visibility, &Children), With<InteractionPrompt>>,
@@ -78,7 +84,7 @@ pub fn update_interaction_ui(
             
             for child in children.iter() {
                 if let Ok((mut text, mut text_color)) = text_query.get_mut(child) {
-                    let key_text = "E"; 
+                    let key_text = glyphs.text(InputAction::Interact, &input_map);
 
                     let (color, suffix) = if target_is_in_range {
                         (Color::WHITE, "")
@@ -143,10 +149,7 @@ pub fn detect_interactables(
     )>,
     interactables: Query<&Interactable>,
 ) {
-    // Clear current interactable at the start
-    current_interactable.entity = None;
-    current_interactable.distance = 0.0;
-    current_interactable.is_in_range = false;
+    let mut cleared = false;
 
     for (transform, mut detector) in detectors.iter_mut() {
         // Update timer
@@ -160,6 +163,14 @@ pub fn detect_interactables(
         // Reset timer
         detector.time_since_update = 0.0;
 
+        // Keep the previous result between updates so hover feedback doesn't flicker
+        if !cleared {
+            current_interactable.entity = None;
+            current_interactable.distance = 0.0;
+            current_interactable.is_in_range = false;
+            cleared = true;
+        }
+
         // Calculate ray origin and direction
         let ray_origin = transform.translation() + detector.ray_offset;
         let ray_direction = transform.forward();
@@ -185,6 +196,26 @@ pub fn detect_interactables(
     }
 }
 
+/// System to highlight the interactable under the crosshair
+pub fn highlight_current_interactable(
+    current_interactable: Res<CurrentInteractable>,
+    mut highlights: ResMut<HighlightCommandQueue>,
+    mut previous: Local<Option<Entity>>,
+) {
+    let target = current_interactable.entity.filter(|_| current_interactable.is_in_range);
+    if *previous == target {
+        return;
+    }
+
+    if let Some(entity) = *previous {
+        highlights.release(entity, HighlightKind::InteractableHover);
+    }
+    if let Some(entity) = target {
+        highlights.request(entity, HighlightKind::InteractableHover);
+    }
+    *previous = target;
+}
+
 /// System to process interaction inputs
 pub fn process_interactions(
     input: Res<InputState>,
@@ -252,28 +283,28 @@ pub fn process_interactions(
             
             // Trigger Event
             if source_entity != Entity::PLACEHOLDER {
-                events.0.push(InteractionEvent {
+                events.send(InteractionEvent {
                     source: source_entity,
                     target: entity,
                     interaction_type: interactable.interaction_type,
                 });
 
                 if interactable.interaction_type == InteractionType::Pickup {
-                    pickup_events.0.push(crate::pickups::PickupEvent {
+                    pickup_events.send(crate::pickups::PickupEvent {
                         source: source_entity,
                         target: entity,
                     });
                 }
 
                 // Specifically trigger Electronic Device activation if applicable
-                electronic_device_activation_queue.0.push(crate::devices::electronic_device::ElectronicDeviceActivationEvent {
+                electronic_device_activation_queue.send(crate::devices::electronic_device::ElectronicDeviceActivationEvent {
                     device_entity: entity,
                     player_entity: source_entity,
                 });
 
                 // Specifically trigger Grab if applicable
                 if interactable.interaction_type == InteractionType::Grab {
-                    grab_queue.0.push(crate::grab::GrabEvent::Grab(source_entity, entity));
+                    grab_queue.send(crate::grab::GrabEvent::Grab(source_entity, entity));
                 }
             }
         }
@@ -319,12 +350,12 @@ pub fn debug_draw_interaction_rays(
 
 /// System to update the player's device list based on events
 pub fn update_device_list(
-    mut add_queue: ResMut<AddDeviceQueue>,
-    mut remove_queue: ResMut<RemoveDeviceQueue>,
+    mut add_queue: QueueReader<AddDeviceEvent>,
+    mut remove_queue: QueueReader<RemoveDeviceEvent>,
     mut players: Query<&mut UsingDevicesSystem>,
     devices: Query<(&DeviceStringAction, &GlobalTransform)>,
 ) {
-    for event in add_queue.0.drain(..) {
+    for event in add_queue.read().copied() {
         if let Ok(mut player_system) = players.get_mut(event.player) {
             if let Ok((device_action, _transform)) = devices.get(event.device) {
                 // Check if already in list
@@ -347,7 +378,7 @@ pub fn update_device_list(
         }
     }
 
-    for event in remove_queue.0.drain(..) {
+    for event in remove_queue.read().copied() {
         if let Ok(mut player_system) = players.get_mut(event.player) {
             player_system.device_list.retain(|d| d.entity != event.device);
         }
@@ -408,13 +439,13 @@ pub fn detect_devices_in_proximity(
             
             if dist < player_system.raycast_distance {
                 if !is_in_list {
-                    add_queue.0.push(AddDeviceEvent {
+                    add_queue.send(AddDeviceEvent {
                         player: player_entity,
                         device: device_entity,
                     });
                 }
             } else if is_in_list {
-                remove_queue.0.push(RemoveDeviceEvent {
+                remove_queue.send(RemoveDeviceEvent {
                     player: player_entity,
                     device: device_entity,
                 });
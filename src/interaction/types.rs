@@ -1,7 +1,18 @@
 use bevy::prelude::*;
 
+/// Interaction phases, run in order inside `GameControllerSet::Interaction`
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InteractionSet {
+    /// Interactables and devices found around the player
+    Detection,
+    /// Interactions validated and `InteractionEventQueue` filled
+    Processing,
+    /// Prompts and debug rays
+    Ui,
+}
+
 /// Interaction type
-#[derive(Debug, Clone, Copy, Reflect, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Reflect, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum InteractionType {
     Pickup,
     Use,
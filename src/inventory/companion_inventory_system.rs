@@ -0,0 +1,600 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::components::{Equipment, Inventory};
+use super::types::{InventoryItem, ItemType};
+use super::weapon_equip_system::{RequestEquipWeaponEvent, RequestEquipWeaponEventQueue, WeaponSpawnRegistry};
+use crate::ai::FriendManager;
+use crate::character::Player;
+use crate::game_manager::types::CursorState;
+use crate::interaction::InteractionEvent;
+use crate::save::SaveCustomData;
+use crate::utils::{EventQueue, QueueReader};
+use crate::weapons::{Weapon, WeaponManager};
+
+/// Inventory of a companion the player can trade with and arm.
+///
+/// The companion keeps its items in its own [`Inventory`], limited by
+/// `Inventory::weight_limit`, and its weapon in `Equipment::main_hand`. The
+/// equipped weapon is spawned into the companion's `WeaponManager`, which is
+/// what AI combat fires with.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CompanionInventory {
+    /// Key of the companion in saves; companions without one are not saved
+    pub companion_id: String,
+    /// Open the transfer panel when the player interacts with the companion
+    pub open_on_interaction: bool,
+}
+
+impl Default for CompanionInventory {
+    fn default() -> Self {
+        Self {
+            companion_id: String::new(),
+            open_on_interaction: true,
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct CompanionInventorySettings {
+    /// Give every `FriendManager` NPC an inventory, named after its `Name`
+    pub auto_attach: bool,
+    pub slots: usize,
+    pub weight_limit: f32,
+    /// Key in the player's `SaveCustomData`
+    pub save_key: String,
+}
+
+impl Default for CompanionInventorySettings {
+    fn default() -> Self {
+        Self {
+            auto_attach: true,
+            slots: 12,
+            weight_limit: 40.0,
+            save_key: "companion_inventories".to_string(),
+        }
+    }
+}
+
+/// Companion whose transfer panel is open
+#[derive(Resource, Debug, Default)]
+pub struct CompanionInventoryState {
+    pub companion: Option<Entity>,
+    /// Why the last request was refused
+    pub message: String,
+}
+
+/// Requests to the companion inventory, for menus and gameplay code
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompanionInventoryCommand {
+    Open { companion: Entity },
+    Close,
+    /// Move items from the player to the companion
+    Give { companion: Entity, item_id: String, quantity: i32 },
+    /// Move items from the companion to the player
+    Take { companion: Entity, item_id: String, quantity: i32 },
+    Equip { companion: Entity, item_id: String },
+    Unequip { companion: Entity },
+}
+
+/// Queue for companion inventory commands
+pub type CompanionInventoryCommandQueue = EventQueue<CompanionInventoryCommand>;
+
+/// Companion inventory notifications
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompanionInventoryEvent {
+    Transferred { companion: Entity, item_id: String, quantity: i32, to_companion: bool },
+    Refused { companion: Entity, reason: String },
+    Equipped { companion: Entity, item_id: String },
+    Unequipped { companion: Entity, item_id: String },
+}
+
+/// Queue for companion inventory events
+pub type CompanionInventoryEventQueue = EventQueue<CompanionInventoryEvent>;
+
+/// Saved form of an [`InventoryItem`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompanionItemRecord {
+    pub item_id: String,
+    pub name: String,
+    pub quantity: i32,
+    pub max_stack: i32,
+    pub weight: f32,
+    pub item_type: ItemType,
+    pub icon_path: String,
+    pub value: f32,
+    pub category: String,
+    pub min_level: u32,
+    pub info: String,
+    pub is_infinite: bool,
+}
+
+impl From<&InventoryItem> for CompanionItemRecord {
+    fn from(item: &InventoryItem) -> Self {
+        Self {
+            item_id: item.item_id.clone(),
+            name: item.name.clone(),
+            quantity: item.quantity,
+            max_stack: item.max_stack,
+            weight: item.weight,
+            item_type: item.item_type,
+            icon_path: item.icon_path.clone(),
+            value: item.value,
+            category: item.category.clone(),
+            min_level: item.min_level,
+            info: item.info.clone(),
+            is_infinite: item.is_infinite,
+        }
+    }
+}
+
+impl CompanionItemRecord {
+    pub fn to_item(&self) -> InventoryItem {
+        InventoryItem {
+            item_id: self.item_id.clone(),
+            name: self.name.clone(),
+            quantity: self.quantity,
+            max_stack: self.max_stack,
+            weight: self.weight,
+            item_type: self.item_type,
+            icon_path: self.icon_path.clone(),
+            value: self.value,
+            category: self.category.clone(),
+            min_level: self.min_level,
+            info: self.info.clone(),
+            is_infinite: self.is_infinite,
+        }
+    }
+}
+
+/// Saved items and weapon of one companion
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompanionInventoryRecord {
+    pub items: Vec<CompanionItemRecord>,
+    pub main_hand: Option<CompanionItemRecord>,
+}
+
+impl CompanionInventoryRecord {
+    pub fn capture(inventory: &Inventory, equipment: &Equipment) -> Self {
+        Self {
+            items: inventory.items.iter().flatten().map(CompanionItemRecord::from).collect(),
+            main_hand: equipment.main_hand.as_ref().map(CompanionItemRecord::from),
+        }
+    }
+}
+
+/// Moves up to `quantity` of `item_id` between inventories, returning how many moved.
+/// With `check_weight`, only what fits under the receiver's weight limit is moved.
+pub fn transfer_inventory_items(
+    from: &mut Inventory,
+    to: &mut Inventory,
+    item_id: &str,
+    quantity: i32,
+    check_weight: bool,
+) -> Result<i32, String> {
+    let Some(template) = from.items.iter().flatten().find(|item| item.item_id == item_id).cloned() else {
+        return Err(format!("No {} to move", item_id));
+    };
+    let mut amount = quantity.min(from.item_count(item_id));
+    if check_weight && template.weight > 0.0 {
+        let free = (to.weight_limit - to.current_weight).max(0.0);
+        amount = amount.min((free / template.weight + 1e-4).floor() as i32);
+        if amount <= 0 {
+            return Err(format!("{} is too heavy to carry", template.name));
+        }
+    }
+    if amount <= 0 {
+        return Err(format!("No {} to move", template.name));
+    }
+
+    let mut item = template;
+    item.quantity = amount;
+    let moved = amount - to.add_item(item).map_or(0, |leftover| leftover.quantity);
+    if moved <= 0 {
+        return Err("No room left".to_string());
+    }
+    from.remove_quantity(item_id, moved);
+    Ok(moved)
+}
+
+/// Takes one `item_id` weapon from the inventory into the main hand, returning the
+/// weapon it replaces (which goes back into the inventory)
+pub fn equip_main_hand(
+    inventory: &mut Inventory,
+    equipment: &mut Equipment,
+    item_id: &str,
+) -> Result<Option<InventoryItem>, String> {
+    let Some(mut weapon) = inventory.items.iter().flatten().find(|item| item.item_id == item_id).cloned() else {
+        return Err(format!("No {} to equip", item_id));
+    };
+    if weapon.item_type != ItemType::Weapon {
+        return Err(format!("{} is not a weapon", weapon.name));
+    }
+    weapon.quantity = 1;
+    inventory.remove_quantity(item_id, 1);
+
+    let previous = equipment.main_hand.take();
+    if let Some(previous) = previous.clone() {
+        if let Some(leftover) = inventory.add_item(previous) {
+            // Undo: nowhere to put the weapon in hand
+            equipment.main_hand = Some(leftover);
+            inventory.add_item(weapon);
+            return Err("No room for the weapon in hand".to_string());
+        }
+    }
+    equipment.main_hand = Some(weapon);
+    Ok(previous)
+}
+
+/// Puts the main hand weapon back into the inventory
+pub fn unequip_main_hand(inventory: &mut Inventory, equipment: &mut Equipment) -> Result<InventoryItem, String> {
+    let Some(weapon) = equipment.main_hand.take() else {
+        return Err("Nothing equipped".to_string());
+    };
+    if let Some(leftover) = inventory.add_item(weapon.clone()) {
+        equipment.main_hand = Some(leftover);
+        return Err("No room left".to_string());
+    }
+    Ok(weapon)
+}
+
+/// Open transfer panel and the cursor it shows
+#[derive(SystemParam)]
+pub struct CompanionPanel<'w> {
+    pub state: ResMut<'w, CompanionInventoryState>,
+    pub cursor: ResMut<'w, CursorState>,
+}
+
+/// Weapons registry and equip requests companions are armed through
+#[derive(SystemParam)]
+pub struct CompanionWeapons<'w, 's> {
+    pub registry: Res<'w, WeaponSpawnRegistry>,
+    pub weapon_query: Query<'w, 's, &'static Weapon>,
+    pub equip_events: ResMut<'w, RequestEquipWeaponEventQueue>,
+}
+
+/// Removes the spawned weapon of `item_id` from a weapon manager
+fn holster_weapon(
+    commands: &mut Commands,
+    manager: &mut WeaponManager,
+    weapon_query: &Query<&Weapon>,
+    registry: &WeaponSpawnRegistry,
+    item_id: &str,
+) {
+    let weapon_name = registry.weapons.get(item_id).map_or(item_id, |info| info.weapon_name.as_str());
+    let Some(index) = manager.weapons_list.iter().position(|entity| {
+        weapon_query
+            .get(*entity)
+            .is_ok_and(|weapon| weapon.weapon_name == weapon_name || weapon.weapon_name == item_id)
+    }) else {
+        return;
+    };
+    let entity = manager.weapons_list.remove(index);
+    commands.entity(entity).despawn();
+    manager.current_index = 0;
+    if manager.weapons_list.is_empty() {
+        manager.weapons_mode_active = false;
+    }
+}
+
+type NewCompanion<'a> = (
+    Entity,
+    Option<&'a Name>,
+    Has<Inventory>,
+    Has<Equipment>,
+    Has<CompanionInventory>,
+    Has<WeaponManager>,
+);
+
+/// System to give new companions an inventory, equipment and a weapon manager
+pub fn attach_companion_inventories(
+    mut commands: Commands,
+    settings: Res<CompanionInventorySettings>,
+    query: Query<NewCompanion, Added<FriendManager>>,
+) {
+    if !settings.auto_attach {
+        return;
+    }
+    for (entity, name, has_inventory, has_equipment, has_companion, has_manager) in query.iter() {
+        let mut entity_commands = commands.entity(entity);
+        if !has_inventory {
+            entity_commands.insert(Inventory {
+                items: vec![None; settings.slots],
+                max_slots: settings.slots,
+                weight_limit: settings.weight_limit,
+                current_weight: 0.0,
+            });
+        }
+        if !has_equipment {
+            entity_commands.insert(Equipment::default());
+        }
+        if !has_companion {
+            entity_commands.insert(CompanionInventory {
+                companion_id: name.map(|name| name.as_str().to_string()).unwrap_or_default(),
+                ..default()
+            });
+        }
+        if !has_manager {
+            entity_commands.insert(WeaponManager::default());
+        }
+    }
+}
+
+/// System to open the transfer panel when the player interacts with a companion
+pub fn open_companion_inventory_on_interaction(
+    mut interaction_events: QueueReader<InteractionEvent>,
+    companions: Query<&CompanionInventory>,
+    players: Query<(), With<Player>>,
+    mut companion_commands: ResMut<CompanionInventoryCommandQueue>,
+) {
+    for event in interaction_events.read() {
+        if !players.contains(event.source) {
+            continue;
+        }
+        if companions.get(event.target).is_ok_and(|companion| companion.open_on_interaction) {
+            companion_commands.send(CompanionInventoryCommand::Open { companion: event.target });
+        }
+    }
+}
+
+/// System to apply companion inventory commands
+pub fn handle_companion_inventory_commands(
+    mut commands: Commands,
+    mut companion_commands: QueueReader<CompanionInventoryCommand>,
+    panel: CompanionPanel,
+    mut events: ResMut<CompanionInventoryEventQueue>,
+    weapons: CompanionWeapons,
+    mut player_query: Query<&mut Inventory, (With<Player>, Without<CompanionInventory>)>,
+    mut companion_query: Query<(&mut Inventory, &mut Equipment, Option<&mut WeaponManager>), With<CompanionInventory>>,
+) {
+    let CompanionPanel { mut state, mut cursor } = panel;
+    let CompanionWeapons { registry, weapon_query, mut equip_events } = weapons;
+    for command in companion_commands.read() {
+        let result = match command {
+            CompanionInventoryCommand::Open { companion } => {
+                if companion_query.contains(*companion) {
+                    state.companion = Some(*companion);
+                    state.message.clear();
+                    cursor.visible_override = Some(true);
+                }
+                continue;
+            }
+            CompanionInventoryCommand::Close => {
+                if state.companion.take().is_some() {
+                    cursor.visible_override = None;
+                }
+                continue;
+            }
+            CompanionInventoryCommand::Give { companion, item_id, quantity }
+            | CompanionInventoryCommand::Take { companion, item_id, quantity } => {
+                let to_companion = matches!(command, CompanionInventoryCommand::Give { .. });
+                let Ok((mut companion_inventory, _, _)) = companion_query.get_mut(*companion) else { continue };
+                let Some(mut player_inventory) = player_query.iter_mut().next() else { continue };
+                let moved = if to_companion {
+                    transfer_inventory_items(&mut player_inventory, &mut companion_inventory, item_id, *quantity, true)
+                } else {
+                    transfer_inventory_items(&mut companion_inventory, &mut player_inventory, item_id, *quantity, false)
+                };
+                moved.map(|quantity| CompanionInventoryEvent::Transferred {
+                    companion: *companion,
+                    item_id: item_id.clone(),
+                    quantity,
+                    to_companion,
+                })
+            }
+            CompanionInventoryCommand::Equip { companion, item_id } => {
+                let Ok((mut inventory, mut equipment, manager)) = companion_query.get_mut(*companion) else { continue };
+                equip_main_hand(&mut inventory, &mut equipment, item_id).map(|previous| {
+                    if let (Some(previous), Some(mut manager)) = (previous, manager) {
+                        holster_weapon(&mut commands, &mut manager, &weapon_query, &registry, &previous.item_id);
+                    }
+                    equip_events.send(RequestEquipWeaponEvent {
+                        owner: *companion,
+                        weapon_id: item_id.clone(),
+                        hand_preference: None,
+                    });
+                    CompanionInventoryEvent::Equipped { companion: *companion, item_id: item_id.clone() }
+                })
+            }
+            CompanionInventoryCommand::Unequip { companion } => {
+                let Ok((mut inventory, mut equipment, manager)) = companion_query.get_mut(*companion) else { continue };
+                unequip_main_hand(&mut inventory, &mut equipment).map(|weapon| {
+                    if let Some(mut manager) = manager {
+                        holster_weapon(&mut commands, &mut manager, &weapon_query, &registry, &weapon.item_id);
+                    }
+                    CompanionInventoryEvent::Unequipped { companion: *companion, item_id: weapon.item_id }
+                })
+            }
+        };
+
+        match result {
+            Ok(event) => {
+                state.message.clear();
+                events.send(event);
+            }
+            Err(reason) => {
+                let companion = match command {
+                    CompanionInventoryCommand::Give { companion, .. }
+                    | CompanionInventoryCommand::Take { companion, .. }
+                    | CompanionInventoryCommand::Equip { companion, .. }
+                    | CompanionInventoryCommand::Unequip { companion } => *companion,
+                    _ => continue,
+                };
+                state.message = reason.clone();
+                events.send(CompanionInventoryEvent::Refused { companion, reason });
+            }
+        }
+    }
+
+    // The companion left the world
+    if let Some(companion) = state.companion {
+        if !companion_query.contains(companion) {
+            state.companion = None;
+            cursor.visible_override = None;
+        }
+    }
+}
+
+/// Companion data restored from a save
+type SavedCompanion<'a> = (
+    Entity,
+    Ref<'a, CompanionInventory>,
+    &'a mut Inventory,
+    &'a mut Equipment,
+    Option<&'a mut WeaponManager>,
+);
+
+/// System to give companions the items and weapon stored in a loaded save,
+/// including companions spawned after the save was loaded
+pub fn restore_companion_inventories_from_save(
+    mut commands: Commands,
+    settings: Res<CompanionInventorySettings>,
+    save_query: Query<Ref<SaveCustomData>, With<Player>>,
+    mut companion_query: Query<SavedCompanion>,
+    weapons: CompanionWeapons,
+) {
+    let CompanionWeapons { registry, weapon_query, mut equip_events } = weapons;
+    let Some(custom_data) = save_query.iter().next() else { return };
+    let Some(value) = custom_data.0.get(&settings.save_key) else { return };
+    let save_changed = custom_data.is_changed();
+    if !save_changed && !companion_query.iter().any(|(_, companion, ..)| companion.is_added()) {
+        return;
+    }
+    let saved: HashMap<String, CompanionInventoryRecord> = match serde_json::from_value(value.clone()) {
+        Ok(saved) => saved,
+        Err(err) => {
+            warn!("Invalid saved companion inventories: {}", err);
+            return;
+        }
+    };
+
+    for (entity, companion, mut inventory, mut equipment, manager) in companion_query.iter_mut() {
+        if !save_changed && !companion.is_added() {
+            continue;
+        }
+        let Some(record) = saved.get(&companion.companion_id) else { continue };
+        // Our own write-back
+        if *record == CompanionInventoryRecord::capture(&inventory, &equipment) {
+            continue;
+        }
+
+        inventory.items = vec![None; inventory.max_slots];
+        for item in record.items.iter() {
+            inventory.add_item(item.to_item());
+        }
+        inventory.recalculate_weight();
+
+        let main_hand = record.main_hand.as_ref().map(CompanionItemRecord::to_item);
+        let current_id = equipment.main_hand.as_ref().map(|item| item.item_id.clone());
+        let saved_id = main_hand.as_ref().map(|item| item.item_id.clone());
+        equipment.main_hand = main_hand;
+        if current_id == saved_id {
+            continue;
+        }
+        if let (Some(current_id), Some(mut manager)) = (current_id, manager) {
+            holster_weapon(&mut commands, &mut manager, &weapon_query, &registry, &current_id);
+        }
+        if let Some(weapon_id) = saved_id {
+            equip_events.send(RequestEquipWeaponEvent { owner: entity, weapon_id, hand_preference: None });
+        }
+    }
+}
+
+type ChangedCompanionItems = (With<CompanionInventory>, Or<(Changed<Inventory>, Changed<Equipment>)>);
+
+/// System to keep companion inventories in the data written to saves
+pub fn store_companion_inventories_in_save(
+    mut commands: Commands,
+    settings: Res<CompanionInventorySettings>,
+    changed_query: Query<(), ChangedCompanionItems>,
+    companion_query: Query<(&CompanionInventory, &Inventory, &Equipment)>,
+    mut player_query: Query<(Entity, Option<&mut SaveCustomData>), With<Player>>,
+) {
+    if changed_query.is_empty() {
+        return;
+    }
+    let Some((player, custom_data)) = player_query.iter_mut().next() else { return };
+
+    // Keep companions that aren't spawned right now
+    let mut saved: HashMap<String, CompanionInventoryRecord> = custom_data
+        .as_ref()
+        .and_then(|custom_data| custom_data.0.get(&settings.save_key))
+        .and_then(|value| serde_json::from_value(value.clone()).ok())
+        .unwrap_or_default();
+    for (companion, inventory, equipment) in companion_query.iter() {
+        if companion.companion_id.is_empty() {
+            continue;
+        }
+        saved.insert(companion.companion_id.clone(), CompanionInventoryRecord::capture(inventory, equipment));
+    }
+    let Ok(value) = serde_json::to_value(&saved) else { return };
+
+    match custom_data {
+        Some(mut custom_data) => {
+            if custom_data.0.get(&settings.save_key) != Some(&value) {
+                custom_data.0.insert(settings.save_key.clone(), value);
+            }
+        }
+        None => {
+            let mut custom_data = SaveCustomData::default();
+            custom_data.0.insert(settings.save_key.clone(), value);
+            commands.entity(player).insert(custom_data);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inventory::{Equipment, Inventory, ItemType};
+
+    /// Plain stackable item
+    fn test_item(item_id: &str, quantity: i32, weight: f32, item_type: ItemType) -> InventoryItem {
+        InventoryItem {
+            item_id: item_id.to_string(),
+            name: item_id.to_string(),
+            quantity,
+            max_stack: 99,
+            weight,
+            item_type,
+            icon_path: String::new(),
+            value: 0.0,
+            category: String::new(),
+            min_level: 0,
+            info: String::new(),
+            is_infinite: false,
+        }
+    }
+
+    #[test]
+    fn test_companion_inventory_transfer_and_equip() {
+        let mut player = Inventory::default();
+        let mut companion = Inventory { weight_limit: 10.0, ..Default::default() };
+        player.add_item(test_item("stone", 8, 2.0, ItemType::Material));
+        player.add_item(test_item("rifle", 1, 4.0, ItemType::Weapon));
+
+        // Only what fits under the weight limit moves
+        assert_eq!(transfer_inventory_items(&mut player, &mut companion, "stone", 8, true), Ok(5));
+        assert_eq!(player.item_count("stone"), 3);
+        assert!(transfer_inventory_items(&mut player, &mut companion, "rifle", 1, true).is_err());
+        assert_eq!(transfer_inventory_items(&mut companion, &mut player, "stone", 3, false), Ok(3));
+        assert_eq!(transfer_inventory_items(&mut player, &mut companion, "rifle", 1, true), Ok(1));
+
+        let mut equipment = Equipment::default();
+        assert!(equip_main_hand(&mut companion, &mut equipment, "stone").is_err());
+        assert!(equip_main_hand(&mut companion, &mut equipment, "rifle").unwrap().is_none());
+        assert_eq!(companion.item_count("rifle"), 0);
+
+        let record = CompanionInventoryRecord::capture(&companion, &equipment);
+        assert_eq!(record.main_hand.as_ref().map(|weapon| weapon.item_id.as_str()), Some("rifle"));
+        assert_eq!(record.items[0].to_item().quantity, 2);
+
+        assert_eq!(unequip_main_hand(&mut companion, &mut equipment).map(|weapon| weapon.item_id), Ok("rifle".to_string()));
+        assert!(equipment.main_hand.is_none());
+        assert_eq!(companion.item_count("rifle"), 1);
+    }
+}
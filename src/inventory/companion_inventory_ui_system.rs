@@ -0,0 +1,215 @@
+use bevy::prelude::*;
+
+use super::companion_inventory_system::{CompanionInventory, CompanionInventoryCommand, CompanionInventoryCommandQueue, CompanionInventoryState};
+use super::components::{Equipment, Inventory};
+use super::types::{InventoryItem, ItemType};
+use crate::character::Player;
+use crate::input::{FocusCancel, FocusScope, UiCancelButton};
+
+/// Player/companion transfer panel
+#[derive(Component)]
+pub struct CompanionInventoryUIRoot;
+
+/// Rows rebuilt whenever either inventory changes
+#[derive(Component)]
+pub struct CompanionInventoryContent;
+
+/// Button sending a companion inventory command
+#[derive(Component)]
+pub struct CompanionInventoryButton(pub CompanionInventoryCommand);
+
+/// System to setup the transfer panel (hidden until a companion is opened)
+pub fn setup_companion_inventory_ui(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            width: Val::Px(620.0),
+            max_height: Val::Percent(80.0),
+            position_type: PositionType::Absolute,
+            left: Val::Percent(50.0),
+            top: Val::Percent(10.0),
+            margin: UiRect::left(Val::Px(-310.0)),
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Val::Px(16.0)),
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.08, 0.08, 0.1, 0.92)),
+        CompanionInventoryUIRoot,
+        FocusScope { priority: 0, cancel: FocusCancel::None },
+        Visibility::Hidden,
+    ));
+}
+
+type ShownCompanion<'a> = (Ref<'a, Inventory>, Ref<'a, Equipment>, Option<&'a Name>);
+
+/// System to show the open companion's items next to the player's
+pub fn update_companion_inventory_ui(
+    mut commands: Commands,
+    state: Res<CompanionInventoryState>,
+    mut root_query: Query<(Entity, &mut Visibility), With<CompanionInventoryUIRoot>>,
+    content_query: Query<Entity, With<CompanionInventoryContent>>,
+    player_query: Query<Ref<Inventory>, (With<Player>, Without<CompanionInventory>)>,
+    companion_query: Query<ShownCompanion, With<CompanionInventory>>,
+) {
+    let Ok((root, mut visibility)) = root_query.single_mut() else { return };
+    let open = state.companion.and_then(|companion| companion_query.get(companion).ok().map(|data| (companion, data)));
+    let Some((companion, (companion_inventory, equipment, name))) = open else {
+        visibility.set_if_neq(Visibility::Hidden);
+        for content in content_query.iter() {
+            commands.entity(content).despawn();
+        }
+        return;
+    };
+    visibility.set_if_neq(Visibility::Visible);
+
+    let Some(player_inventory) = player_query.iter().next() else { return };
+    let content = content_query.iter().next();
+    let changed = state.is_changed()
+        || player_inventory.is_changed()
+        || companion_inventory.is_changed()
+        || equipment.is_changed();
+    if content.is_some() && !changed {
+        return;
+    }
+    if let Some(content) = content {
+        commands.entity(content).despawn();
+    }
+
+    let name = name.map_or("Companion", |name| name.as_str());
+    commands.entity(root).with_children(|root| {
+        root.spawn((
+            Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(8.0),
+                ..default()
+            },
+            CompanionInventoryContent,
+        )).with_children(|content| {
+            content.spawn((
+                Text::new(format!(
+                    "{}  {:.1}/{:.1} kg",
+                    name.to_uppercase(),
+                    companion_inventory.current_weight,
+                    companion_inventory.weight_limit
+                )),
+                TextFont { font_size: 24.0, ..default() },
+                TextColor(Color::WHITE),
+            ));
+
+            content.spawn(Node { column_gap: Val::Px(8.0), align_items: AlignItems::Center, ..default() }).with_children(|row| {
+                let equipped = equipment.main_hand.as_ref().map_or("Unarmed".to_string(), |item| item.name.clone());
+                row.spawn((
+                    Text::new(format!("Weapon: {}", equipped)),
+                    TextFont { font_size: 16.0, ..default() },
+                    TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                ));
+                if equipment.main_hand.is_some() {
+                    spawn_button(row, "Unequip", CompanionInventoryCommand::Unequip { companion });
+                }
+            });
+
+            content.spawn(Node { column_gap: Val::Px(16.0), ..default() }).with_children(|columns| {
+                spawn_column(columns, "You", &player_inventory, |item| {
+                    vec![("Give", CompanionInventoryCommand::Give { companion, item_id: item.item_id.clone(), quantity: 1 })]
+                });
+                spawn_column(columns, name, &companion_inventory, |item| {
+                    let mut buttons = vec![(
+                        "Take",
+                        CompanionInventoryCommand::Take { companion, item_id: item.item_id.clone(), quantity: 1 },
+                    )];
+                    if item.item_type == ItemType::Weapon {
+                        buttons.push(("Equip", CompanionInventoryCommand::Equip { companion, item_id: item.item_id.clone() }));
+                    }
+                    buttons
+                });
+            });
+
+            if !state.message.is_empty() {
+                content.spawn((
+                    Text::new(state.message.clone()),
+                    TextFont { font_size: 16.0, ..default() },
+                    TextColor(Color::srgb(0.95, 0.4, 0.35)),
+                ));
+            }
+
+            content.spawn(Node::default()).with_children(|row| {
+                spawn_button(row, "Close", CompanionInventoryCommand::Close);
+            });
+        });
+    });
+}
+
+fn spawn_column(
+    parent: &mut ChildSpawnerCommands,
+    title: &str,
+    inventory: &Inventory,
+    buttons: impl Fn(&InventoryItem) -> Vec<(&'static str, CompanionInventoryCommand)>,
+) {
+    parent.spawn(Node {
+        width: Val::Percent(50.0),
+        flex_direction: FlexDirection::Column,
+        row_gap: Val::Px(4.0),
+        ..default()
+    }).with_children(|column| {
+        column.spawn((
+            Text::new(title),
+            TextFont { font_size: 18.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+        for item in inventory.items.iter().flatten() {
+            column.spawn(Node { column_gap: Val::Px(6.0), align_items: AlignItems::Center, ..default() }).with_children(|row| {
+                row.spawn((
+                    Text::new(format!("{} x{} ({:.1} kg)", item.name, item.quantity, item.weight * item.quantity as f32)),
+                    TextFont { font_size: 14.0, ..default() },
+                    TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                    Node { flex_grow: 1.0, ..default() },
+                ));
+                for (label, command) in buttons(item) {
+                    spawn_button(row, label, command);
+                }
+            });
+        }
+    });
+}
+
+fn spawn_button(parent: &mut ChildSpawnerCommands, label: &str, command: CompanionInventoryCommand) {
+    let is_close = command == CompanionInventoryCommand::Close;
+    let mut button = parent.spawn((
+        Button,
+        Node {
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.25, 0.25, 0.3)),
+        CompanionInventoryButton(command),
+    ));
+    if is_close {
+        button.insert(UiCancelButton);
+    }
+    button.with_children(|button| {
+        button.spawn((
+            Text::new(label),
+            TextFont { font_size: 14.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+    });
+}
+
+/// System to send the commands of pressed transfer panel buttons
+pub fn handle_companion_inventory_buttons(
+    mut button_query: Query<(&Interaction, &CompanionInventoryButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut companion_commands: ResMut<CompanionInventoryCommandQueue>,
+) {
+    for (interaction, button, mut background) in button_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => {
+                companion_commands.send(button.0.clone());
+                background.0 = Color::srgb(0.4, 0.4, 0.5);
+            }
+            Interaction::Hovered => background.0 = Color::srgb(0.32, 0.32, 0.38),
+            Interaction::None => background.0 = Color::srgb(0.25, 0.25, 0.3),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use super::types::InventoryItem;
+use crate::weapons::{ArmorPiece, ArmorSlot};
 
 /// Inventory component
 #[derive(Component, Debug, Reflect)]
@@ -61,6 +62,33 @@ impl Inventory {
     pub fn recalculate_weight(&mut self) {
         self.current_weight = self.items.iter().flatten().map(|i| i.weight * i.quantity as f32).sum();
     }
+
+    /// Total quantity of `item_id` across all slots
+    pub fn item_count(&self, item_id: &str) -> i32 {
+        self.items.iter().flatten().filter(|item| item.item_id == item_id).map(|item| item.quantity).sum()
+    }
+
+    /// Removes up to `quantity` of `item_id` across slots, returning how many were removed
+    pub fn remove_quantity(&mut self, item_id: &str, quantity: i32) -> i32 {
+        let mut remaining = quantity.max(0);
+        for slot in self.items.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+            let Some(existing) = slot else { continue };
+            if existing.item_id != item_id {
+                continue;
+            }
+            let taken = remaining.min(existing.quantity);
+            existing.quantity -= taken;
+            remaining -= taken;
+            if existing.quantity <= 0 {
+                *slot = None;
+            }
+        }
+        self.recalculate_weight();
+        quantity.max(0) - remaining
+    }
 }
 
 #[derive(Component, Debug, Default, Reflect)]
@@ -68,6 +96,72 @@ impl Inventory {
 pub struct Equipment {
     pub main_hand: Option<InventoryItem>,
     pub armor: Option<InventoryItem>,
+    /// Worn armor, at most one piece per slot
+    pub armor_pieces: Vec<ArmorPiece>,
+    /// Armor carried but not worn; broken pieces end up here until repaired
+    pub stored_armor: Vec<ArmorPiece>,
+}
+
+impl Equipment {
+    pub fn armor_in(&self, slot: ArmorSlot) -> Option<&ArmorPiece> {
+        self.armor_pieces.iter().find(|piece| piece.slot == slot)
+    }
+
+    /// Wears a piece, storing the one it replaces
+    pub fn equip_armor(&mut self, piece: ArmorPiece) -> Result<(), String> {
+        if piece.is_broken() {
+            return Err(format!("{} is broken", piece.name));
+        }
+        self.unequip_armor(piece.slot);
+        self.armor_pieces.push(piece);
+        Ok(())
+    }
+
+    /// Wears a stored piece
+    pub fn equip_stored_armor(&mut self, item_id: &str) -> Result<(), String> {
+        let index = self
+            .stored_armor
+            .iter()
+            .position(|piece| piece.item_id == item_id)
+            .ok_or_else(|| format!("No stored armor '{}'", item_id))?;
+        if self.stored_armor[index].is_broken() {
+            return Err(format!("{} is broken", self.stored_armor[index].name));
+        }
+        let piece = self.stored_armor.remove(index);
+        self.equip_armor(piece)
+    }
+
+    /// Moves the piece in `slot` to the stored armor
+    pub fn unequip_armor(&mut self, slot: ArmorSlot) -> bool {
+        let Some(index) = self.armor_pieces.iter().position(|piece| piece.slot == slot) else {
+            return false;
+        };
+        let piece = self.armor_pieces.remove(index);
+        self.stored_armor.push(piece);
+        true
+    }
+
+    /// Combined fraction of physical damage absorbed by the worn armor
+    pub fn armor_mitigation(&self) -> f32 {
+        1.0 - self
+            .armor_pieces
+            .iter()
+            .map(|piece| 1.0 - piece.effective_mitigation())
+            .product::<f32>()
+    }
+
+    /// Reduces physical damage by the worn armor and wears each piece by what it absorbed
+    pub fn absorb_damage(&mut self, amount: f32) -> f32 {
+        if self.armor_pieces.is_empty() || amount <= 0.0 {
+            return amount;
+        }
+        let remaining = amount * (1.0 - self.armor_mitigation());
+        for piece in self.armor_pieces.iter_mut() {
+            let absorbed = amount * piece.effective_mitigation();
+            piece.wear(absorbed);
+        }
+        remaining
+    }
 }
 
 /// Component for items existing in the world
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Currency balance stored on an entity.
 ///
@@ -22,8 +23,10 @@ pub struct CurrencyTransactionEvent {
     pub delta: i32,
 }
 
+pub type CurrencyTransactionEventQueue = EventQueue<CurrencyTransactionEvent>;
+
 pub fn update_currency_system(
-    mut events: EventReader<CurrencyTransactionEvent>,
+    mut events: QueueReader<CurrencyTransactionEvent>,
     mut balances: Query<&mut CurrencyBalance>,
 ) {
     for event in events.read() {
@@ -32,3 +35,48 @@ pub fn update_currency_system(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::EventQueuePlugin;
+
+    #[derive(Resource, Default)]
+    struct AuditLog(Vec<i32>);
+
+    fn audit_transactions(mut events: QueueReader<CurrencyTransactionEvent>, mut log: ResMut<AuditLog>) {
+        for event in events.read() {
+            log.0.push(event.delta);
+        }
+    }
+
+    #[test]
+    fn test_transactions_apply_once_per_reader() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(EventQueuePlugin::<CurrencyTransactionEvent>::default())
+            .init_resource::<AuditLog>()
+            .add_systems(Update, (update_currency_system, audit_transactions));
+
+        let wallet = app.world_mut().spawn(CurrencyBalance { amount: 100 }).id();
+        let mut queue = app.world_mut().resource_mut::<CurrencyTransactionEventQueue>();
+        queue.send(CurrencyTransactionEvent { entity: wallet, delta: -30 });
+        queue.send(CurrencyTransactionEvent { entity: wallet, delta: i32::MAX });
+
+        // Events stay buffered for two frames but each reader consumes them once
+        for _ in 0..4 {
+            app.update();
+        }
+
+        assert_eq!(app.world().get::<CurrencyBalance>(wallet).unwrap().amount, i32::MAX);
+        assert_eq!(app.world().resource::<AuditLog>().0, vec![-30, i32::MAX]);
+
+        app.world_mut()
+            .resource_mut::<CurrencyTransactionEventQueue>()
+            .send(CurrencyTransactionEvent { entity: wallet, delta: -5 });
+        app.update();
+
+        assert_eq!(app.world().get::<CurrencyBalance>(wallet).unwrap().amount, i32::MAX - 5);
+        assert_eq!(app.world().resource::<AuditLog>().0.len(), 3);
+    }
+}
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 
 use super::components::Inventory;
 use super::types::InventoryItem;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Event for adding an item into an inventory.
 ///
@@ -12,8 +13,10 @@ pub struct GetInventoryObjectEvent {
     pub item: InventoryItem,
 }
 
+pub type GetInventoryObjectEventQueue = EventQueue<GetInventoryObjectEvent>;
+
 pub fn update_get_inventory_object_system(
-    mut events: EventReader<GetInventoryObjectEvent>,
+    mut events: QueueReader<GetInventoryObjectEvent>,
     mut inventories: Query<&mut Inventory>,
 ) {
     for event in events.read() {
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 
 use super::components::{Inventory, PhysicalItem};
 use super::types::InventoryItem;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Event for removing an item from inventory (optionally spawning it).
 ///
@@ -15,6 +16,8 @@ pub struct GetObjectFromInventoryEvent {
     pub spawn_offset: Vec3,
 }
 
+pub type GetObjectFromInventoryEventQueue = EventQueue<GetObjectFromInventoryEvent>;
+
 impl Default for GetObjectFromInventoryEvent {
     fn default() -> Self {
         Self {
@@ -29,7 +32,7 @@ impl Default for GetObjectFromInventoryEvent {
 
 pub fn update_get_object_from_inventory_system(
     mut commands: Commands,
-    mut events: EventReader<GetObjectFromInventoryEvent>,
+    mut events: QueueReader<GetObjectFromInventoryEvent>,
     mut inventories: Query<(&mut Inventory, &Transform)>,
 ) {
     for event in events.read() {
@@ -3,6 +3,7 @@ use bevy::prelude::*;
 use super::components::Inventory;
 use super::inventory_bank_manager::InventoryBankManager;
 use super::types::InventoryItem;
+use crate::utils::EventQueue;
 
 /// Event for transferring items between player and bank.
 ///
@@ -16,8 +17,10 @@ pub struct InventoryBankTransferEvent {
     pub to_bank: bool,
 }
 
+pub type InventoryBankTransferEventQueue = EventQueue<InventoryBankTransferEvent>;
+
 pub fn update_inventory_bank_system(
-    mut events: ResMut<Events<InventoryBankTransferEvent>>,
+    mut events: ResMut<InventoryBankTransferEventQueue>,
     mut inventories: Query<&mut Inventory>,
     mut banks: Query<&mut InventoryBankManager>,
 ) {
@@ -2,8 +2,9 @@ use bevy::prelude::*;
 use std::collections::HashMap;
 
 use super::components::Inventory;
-use super::inventory_management_system::AddInventoryItemEvent;
+use super::inventory_management_system::{AddInventoryItemEvent, AddInventoryItemEventQueue};
 use super::types::InventoryItem;
+use crate::utils::{EventQueue, QueueReader};
 
 #[derive(Event, Debug, Clone)]
 pub struct CombineInventoryItemsEvent {
@@ -12,14 +13,16 @@ pub struct CombineInventoryItemsEvent {
     pub item_b: String,
 }
 
+pub type CombineInventoryItemsEventQueue = EventQueue<CombineInventoryItemsEvent>;
+
 #[derive(Resource, Default)]
 pub struct CombineRecipeRegistry {
     pub recipes: HashMap<(String, String), InventoryItem>,
 }
 
 pub fn handle_combine_inventory_items(
-    mut events: EventReader<CombineInventoryItemsEvent>,
-    mut add_events: EventWriter<AddInventoryItemEvent>,
+    mut events: QueueReader<CombineInventoryItemsEvent>,
+    mut add_events: ResMut<AddInventoryItemEventQueue>,
     mut inventories: Query<&mut Inventory>,
     registry: Res<CombineRecipeRegistry>,
 ) {
@@ -1,11 +1,12 @@
 use bevy::prelude::*;
 use super::components::{Inventory, InventoryUISlot};
 use super::types::InventoryItem;
-use super::use_inventory_object::UseInventoryObjectEvent;
-use super::inventory_drop_system::DropInventoryItemEvent;
-use super::weapon_equip_system::RequestEquipWeaponEvent;
-use super::inventory_examine_system::ExamineInventoryItemEvent;
+use super::use_inventory_object::{UseInventoryObjectEvent, UseInventoryObjectEventQueue};
+use super::inventory_drop_system::{DropInventoryItemEvent, DropInventoryItemEventQueue};
+use super::weapon_equip_system::{RequestEquipWeaponEvent, RequestEquipWeaponEventQueue};
+use super::inventory_examine_system::{ExamineInventoryItemEvent, ExamineInventoryItemEventQueue};
 use crate::interaction::InteractionDetector;
+use crate::input::FocusScope;
 
 #[derive(Component)]
 pub struct InventoryContextMenu;
@@ -88,6 +89,7 @@ fn spawn_context_menu(commands: &mut Commands, pos: Vec2, slot_index: usize, ite
         BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
         BorderColor(Color::WHITE),
         InventoryContextMenu,
+        FocusScope::popup(10),
         GlobalZIndex(100),
         Interaction::default(),
     )).with_children(|parent| {
@@ -122,10 +124,10 @@ fn spawn_context_menu(commands: &mut Commands, pos: Vec2, slot_index: usize, ite
 pub fn handle_context_button_interaction(
     mut commands: Commands,
     mut interaction_query: Query<(&Interaction, &ContextMenuButton), (Changed<Interaction>, With<Button>)>,
-    mut use_events: EventWriter<UseInventoryObjectEvent>,
-    mut drop_events: EventWriter<DropInventoryItemEvent>,
-    mut equip_events: EventWriter<RequestEquipWeaponEvent>,
-    mut examine_events: EventWriter<ExamineInventoryItemEvent>,
+    mut use_events: ResMut<UseInventoryObjectEventQueue>,
+    mut drop_events: ResMut<DropInventoryItemEventQueue>,
+    mut equip_events: ResMut<RequestEquipWeaponEventQueue>,
+    mut examine_events: ResMut<ExamineInventoryItemEventQueue>,
     inventory_query: Query<Entity, (With<Inventory>, With<InteractionDetector>)>,
     menu_query: Query<Entity, With<InventoryContextMenu>>,
 ) {
@@ -11,6 +11,8 @@ pub struct DropInventoryItemEvent {
     pub spawn_offset: Vec3,
 }
 
+pub type DropInventoryItemEventQueue = EventQueue<DropInventoryItemEvent>;
+
 impl Default for DropInventoryItemEvent {
     fn default() -> Self {
         Self {
@@ -23,10 +25,11 @@ impl Default for DropInventoryItemEvent {
 }
 
 use super::inventory_management_system::InventoryConfig;
+use crate::utils::{EventQueue, QueueReader};
 
 pub fn handle_drop_inventory_item(
     mut commands: Commands,
-    mut events: EventReader<DropInventoryItemEvent>,
+    mut events: QueueReader<DropInventoryItemEvent>,
     mut inventories: Query<(&mut Inventory, Option<&GlobalTransform>, Option<&InventoryConfig>)>,
 ) {
     for event in events.read() {
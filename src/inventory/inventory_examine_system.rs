@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use bevy::render::view::RenderLayers;
+use crate::input::{FocusCancel, FocusScope, UiCancelButton};
 use std::collections::HashMap;
+use crate::utils::EventQueue;
 
 #[derive(Event, Debug, Clone)]
 pub struct ExamineInventoryItemEvent {
@@ -9,6 +11,8 @@ pub struct ExamineInventoryItemEvent {
     pub source_entity: Option<Entity>,
 }
 
+pub type ExamineInventoryItemEventQueue = EventQueue<ExamineInventoryItemEvent>;
+
 #[derive(Resource, Default)]
 pub struct InventoryItemPreviewRegistry {
     pub previews: HashMap<String, Handle<Scene>>,
@@ -94,7 +98,7 @@ use crate::inventory::types::InventoryItem;
 
 pub fn handle_examine_item(
     mut commands: Commands,
-    mut events: ResMut<Events<ExamineInventoryItemEvent>>,
+    mut events: ResMut<ExamineInventoryItemEventQueue>,
     registry: Res<InventoryItemPreviewRegistry>,
     settings: Res<InventoryExamineSettings>,
     existing_previews: Query<Entity, With<InventoryExaminePreview>>,
@@ -148,6 +152,7 @@ pub fn handle_examine_item(
                 ..default()
             },
             ExamineUIRoot,
+            FocusScope { priority: 20, cancel: FocusCancel::None },
             GlobalZIndex(200), // Above inventory
             // Pickable?
         ))
@@ -198,6 +203,7 @@ pub fn handle_examine_item(
                     },
                     BackgroundColor(Color::srgb(0.6, 0.2, 0.2)),
                     ExamineCloseButton,
+                    UiCancelButton,
                 )).with_children(|btn| {
                     btn.spawn((
                         Text::new("Close"),
@@ -273,7 +279,7 @@ pub fn handle_examine_ui_interaction(
             if let Ok((_, preview)) = preview_query.get_single() {
                 if let Some(source) = preview.source_entity {
                     // Queue Pickup
-                    exposure_events.0.push(InteractionEvent {
+                    exposure_events.send(InteractionEvent {
                         source: player_entity,
                         target: source,
                         interaction_type: InteractionType::Pickup,
@@ -307,7 +313,7 @@ fn close_examine_mode(
 }
 
 pub fn update_examine_zoom(
-    mut mouse_wheel: EventReader<bevy::input::mouse::MouseWheel>,
+    mut mouse_wheel: MessageReader<bevy::input::mouse::MouseWheel>,
     mut settings: ResMut<InventoryExamineSettings>,
     mut camera_query: Query<&mut Transform, With<InventoryExamineCamera>>,
 ) {
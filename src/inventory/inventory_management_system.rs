@@ -2,6 +2,7 @@ use bevy::prelude::*;
 
 use super::components::Inventory;
 use super::types::InventoryItem;
+use crate::utils::EventQueue;
 
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
@@ -35,68 +36,80 @@ pub struct AddInventoryItemEvent {
     pub item: InventoryItem,
 }
 
+pub type AddInventoryItemEventQueue = EventQueue<AddInventoryItemEvent>;
+
 pub fn apply_add_inventory_item_events(
-    mut events: ResMut<Events<AddInventoryItemEvent>>,
+    mut events: ResMut<AddInventoryItemEventQueue>,
     mut inventories: Query<(&mut Inventory, Option<&InventoryConfig>)>,
 ) {
     for event in events.drain() {
         let Ok((mut inventory, config)) = inventories.get_mut(event.owner) else { continue };
+        add_item_with_config(&mut inventory, config, &event.item);
+    }
+}
 
-        let config = config.copied().unwrap_or_else(InventoryConfig::default);
-        let max_slots = if config.infinite_slots {
-            usize::MAX
-        } else {
-            config.max_slots.max(inventory.max_slots)
-        };
-        let max_weight = config.max_weight.max(inventory.weight_limit);
+/// Adds `item` within the owner's slot and weight limits, returning the quantity that didn't fit
+pub fn add_item_with_config(inventory: &mut Inventory, config: Option<&InventoryConfig>, item: &InventoryItem) -> i32 {
+    let default_config = InventoryConfig::default();
+    let config = config.unwrap_or(&default_config);
+    let max_slots = if config.infinite_slots {
+        usize::MAX
+    } else {
+        config.max_slots.max(inventory.max_slots)
+    };
+    let max_weight = config.max_weight.max(inventory.weight_limit);
 
-        let mut item = event.item.clone();
-        if item.quantity <= 0 {
-            continue;
-        }
+    if item.quantity <= 0 {
+        return 0;
+    }
 
-        let mut remaining = item.quantity;
+    let mut remaining = item.quantity;
 
-        if item.max_stack > 1 {
-            for slot in inventory.items.iter_mut() {
-                if let Some(existing) = slot {
-                    if existing.item_id == item.item_id && existing.quantity < existing.max_stack {
-                        let space = existing.max_stack - existing.quantity;
-                        let add = remaining.min(space);
-                        if add > 0 {
-                            if !can_add_weight(&inventory, max_weight, &item, add) {
-                                break;
-                            }
-                            existing.quantity += add;
-                            remaining -= add;
+    if item.max_stack > 1 {
+        for slot in inventory.items.iter_mut() {
+            if let Some(existing) = slot {
+                if existing.item_id == item.item_id && existing.quantity < existing.max_stack {
+                    let space = existing.max_stack - existing.quantity;
+                    let add = remaining.min(space);
+                    if add > 0 {
+                        if inventory.current_weight + item.weight * add as f32 > max_weight {
+                            break;
                         }
+                        existing.quantity += add;
+                        remaining -= add;
                     }
                 }
-                if remaining <= 0 {
-                    break;
-                }
             }
-        }
-
-        while remaining > 0 {
-            if inventory.items.len() >= max_slots {
+            if remaining <= 0 {
                 break;
             }
+        }
+    }
 
-            if !can_add_weight(&inventory, max_weight, &item, remaining.min(item.max_stack.max(1))) {
-                break;
-            }
+    while remaining > 0 {
+        let empty_slot = inventory.items.iter().position(Option::is_none);
+        if empty_slot.is_none() && inventory.items.len() >= max_slots {
+            break;
+        }
 
-            let add = remaining.min(item.max_stack.max(1));
-            let mut new_item = item.clone();
-            new_item.quantity = add;
-            inventory.items.push(Some(new_item));
-            remaining -= add;
+        let add = remaining.min(item.max_stack.max(1));
+        if !can_add_weight(inventory, max_weight, item, add) {
+            break;
         }
 
-        inventory.max_slots = inventory.max_slots.max(max_slots);
-        inventory.recalculate_weight();
+        let mut new_item = item.clone();
+        new_item.quantity = add;
+        match empty_slot {
+            Some(index) => inventory.items[index] = Some(new_item),
+            None => inventory.items.push(Some(new_item)),
+        }
+        inventory.current_weight += item.weight * add as f32;
+        remaining -= add;
     }
+
+    inventory.max_slots = inventory.max_slots.max(max_slots);
+    inventory.recalculate_weight();
+    remaining
 }
 
 fn can_add_weight(inventory: &Inventory, max_weight: f32, item: &InventoryItem, amount: i32) -> bool {
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::EventQueue;
 
 /// Controls inventory menu panels.
 ///
@@ -25,8 +26,10 @@ pub struct InventoryMenuPanelEvent {
     pub panel: String,
 }
 
+pub type InventoryMenuPanelEventQueue = EventQueue<InventoryMenuPanelEvent>;
+
 pub fn update_inventory_menu_panels_system(
-    mut events: ResMut<Events<InventoryMenuPanelEvent>>,
+    mut events: ResMut<InventoryMenuPanelEventQueue>,
     mut query: Query<&mut InventoryMenuPanelsSystem>,
 ) {
     for event in events.drain() {
@@ -3,6 +3,7 @@ use bevy::prelude::*;
 use super::components::Inventory;
 use super::inventory_management_system::InventoryConfig;
 use super::types::InventoryItem;
+use crate::utils::{EventQueue, QueueReader};
 
 #[derive(Event, Debug, Clone)]
 pub struct SplitStackEvent {
@@ -11,8 +12,10 @@ pub struct SplitStackEvent {
     pub quantity: i32,
 }
 
+pub type SplitStackEventQueue = EventQueue<SplitStackEvent>;
+
 pub fn handle_split_stack(
-    mut events: EventReader<SplitStackEvent>,
+    mut events: QueueReader<SplitStackEvent>,
     mut inventories: Query<(&mut Inventory, Option<&InventoryConfig>)>,
 ) {
     for event in events.read() {
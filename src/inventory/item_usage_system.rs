@@ -1,36 +1,46 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
 use crate::combat::{Health, Shield};
 use crate::stats::{DerivedStat, StatsSystem};
 use crate::abilities::OxygenSystem;
 use crate::player::extra_movements::jetpack::Jetpack;
-use crate::vehicles::{VehicleStats};
+#[cfg(feature = "vehicles")]
+use crate::vehicles::VehicleStats;
 use crate::weapons::{WeaponManager, Weapon};
 
 use super::components::Inventory;
 use super::types::{InventoryItem, ItemType, HandType};
 use super::item_effects::{ItemEffectRegistry, ItemEffect};
-use super::use_inventory_object::{UseInventoryObjectEvent, InventoryObjectUsedEvent};
-use super::melee_weapon_equipment_system::EquipMeleeWeaponEvent;
-use super::weapon_equip_system::RequestEquipWeaponEvent;
+use super::use_inventory_object::{UseInventoryObjectEvent, InventoryObjectUsedEvent, InventoryObjectUsedEventQueue};
+use super::melee_weapon_equipment_system::{EquipMeleeWeaponEvent, EquipMeleeWeaponEventQueue};
+use super::weapon_equip_system::{RequestEquipWeaponEvent, RequestEquipWeaponEventQueue};
+#[cfg(feature = "vehicles")]
 use crate::character::CharacterMovementState;
+use crate::utils::QueueReader;
+
+/// Components and event queues item effects are applied to
+#[derive(SystemParam)]
+pub struct ItemEffectTargets<'w, 's> {
+    pub health_query: Query<'w, 's, &'static mut Health>,
+    pub shield_query: Query<'w, 's, &'static mut Shield>,
+    pub stats_query: Query<'w, 's, &'static mut StatsSystem>,
+    pub oxygen_query: Query<'w, 's, &'static mut OxygenSystem>,
+    pub jetpack_query: Query<'w, 's, &'static mut Jetpack>,
+    pub weapon_manager_query: Query<'w, 's, &'static mut WeaponManager>,
+    pub weapon_query: Query<'w, 's, &'static mut Weapon>,
+    pub equip_events: ResMut<'w, EquipMeleeWeaponEventQueue>,
+    pub request_weapon_equip: ResMut<'w, RequestEquipWeaponEventQueue>,
+}
 
 pub fn apply_inventory_item_effects(
-    mut use_events: EventReader<UseInventoryObjectEvent>,
-    mut used_events: EventWriter<InventoryObjectUsedEvent>,
-    mut equip_events: EventWriter<EquipMeleeWeaponEvent>,
-    mut request_weapon_equip: EventWriter<RequestEquipWeaponEvent>,
+    mut use_events: QueueReader<UseInventoryObjectEvent>,
+    mut used_events: ResMut<InventoryObjectUsedEventQueue>,
     registry: Res<ItemEffectRegistry>,
     mut inventories: Query<&mut Inventory>,
-    mut health_query: Query<&mut Health>,
-    mut shield_query: Query<&mut Shield>,
-    mut stats_query: Query<&mut StatsSystem>,
-    mut oxygen_query: Query<&mut OxygenSystem>,
-    mut jetpack_query: Query<&mut Jetpack>,
-    mut vehicle_stats_query: Query<&mut VehicleStats>,
-    mut movement_query: Query<&CharacterMovementState>,
-    mut weapon_manager_query: Query<&mut WeaponManager>,
-    mut weapon_query: Query<&mut Weapon>,
+    mut targets: ItemEffectTargets,
+    #[cfg(feature = "vehicles")] mut vehicle_stats_query: Query<&mut VehicleStats>,
+    #[cfg(feature = "vehicles")] mut movement_query: Query<&CharacterMovementState>,
 ) {
     for event in use_events.read() {
         let Ok(mut inventory) = inventories.get_mut(event.owner) else { continue };
@@ -63,17 +73,11 @@ pub fn apply_inventory_item_effects(
             &effects,
             quantity,
             event.hand_preference,
-            &mut health_query,
-            &mut shield_query,
-            &mut stats_query,
-            &mut oxygen_query,
-            &mut jetpack_query,
+            &mut targets,
+            #[cfg(feature = "vehicles")]
             &mut vehicle_stats_query,
+            #[cfg(feature = "vehicles")]
             &mut movement_query,
-            &mut weapon_manager_query,
-            &mut weapon_query,
-            &mut equip_events,
-            &mut request_weapon_equip,
         );
 
         if let Some(slot) = inventory.items.get_mut(slot_index) {
@@ -94,24 +98,26 @@ pub fn apply_inventory_item_effects(
     }
 }
 
-#[allow(clippy::too_many_arguments)]
 fn apply_effects(
     owner: Entity,
     effects: &[ItemEffect],
     quantity: i32,
     hand_preference: Option<HandType>,
-    health_query: &mut Query<&mut Health>,
-    shield_query: &mut Query<&mut Shield>,
-    stats_query: &mut Query<&mut StatsSystem>,
-    oxygen_query: &mut Query<&mut OxygenSystem>,
-    jetpack_query: &mut Query<&mut Jetpack>,
-    vehicle_stats_query: &mut Query<&mut VehicleStats>,
-    movement_query: &mut Query<&CharacterMovementState>,
-    weapon_manager_query: &mut Query<&mut WeaponManager>,
-    weapon_query: &mut Query<&mut Weapon>,
-    equip_events: &mut EventWriter<EquipMeleeWeaponEvent>,
-    request_weapon_equip: &mut EventWriter<RequestEquipWeaponEvent>,
+    targets: &mut ItemEffectTargets,
+    #[cfg(feature = "vehicles")] vehicle_stats_query: &mut Query<&mut VehicleStats>,
+    #[cfg(feature = "vehicles")] movement_query: &mut Query<&CharacterMovementState>,
 ) {
+    let ItemEffectTargets {
+        health_query,
+        shield_query,
+        stats_query,
+        oxygen_query,
+        jetpack_query,
+        weapon_manager_query,
+        weapon_query,
+        equip_events,
+        request_weapon_equip,
+    } = targets;
     let amount_mult = quantity as f32;
     for effect in effects {
         match effect {
@@ -148,6 +154,7 @@ fn apply_effects(
                     jetpack.current_fuel = (jetpack.current_fuel + amount * amount_mult).min(jetpack.max_fuel);
                 }
             }
+            #[cfg(feature = "vehicles")]
             ItemEffect::RestoreVehicleFuel { amount } => {
                 if let Ok(movement) = movement_query.get_mut(owner) {
                     if let Some(vehicle_entity) = movement.vehicle_entity {
@@ -157,6 +164,8 @@ fn apply_effects(
                     }
                 }
             }
+            #[cfg(not(feature = "vehicles"))]
+            ItemEffect::RestoreVehicleFuel { .. } => {}
             ItemEffect::RestoreAmmo { ammo_type, amount } => {
                 if let Ok(mut manager) = weapon_manager_query.get_mut(owner) {
                     let mut remaining = amount * quantity;
@@ -3,6 +3,7 @@ use bevy::prelude::*;
 use crate::input::InputState;
 use crate::combat::{MeleeWeaponRangedAttack, MeleeWeaponTransformData};
 use super::inventory_quick_access_slots_system::InventoryQuickAccessSlotsSystem;
+use crate::utils::{EventQueue, QueueReader};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 pub enum WeaponMountType {
@@ -54,20 +55,26 @@ pub struct EquipMeleeWeaponEvent {
     pub weapon_id: String,
 }
 
+pub type EquipMeleeWeaponEventQueue = EventQueue<EquipMeleeWeaponEvent>;
+
 #[derive(Event, Debug, Clone)]
 pub struct UnequipMeleeWeaponEvent {
     pub owner: Entity,
 }
 
+pub type UnequipMeleeWeaponEventQueue = EventQueue<UnequipMeleeWeaponEvent>;
+
 #[derive(Event, Debug, Clone)]
 pub struct ToggleMeleeWeaponDrawEvent {
     pub owner: Entity,
 }
 
+pub type ToggleMeleeWeaponDrawEventQueue = EventQueue<ToggleMeleeWeaponDrawEvent>;
+
 pub fn handle_melee_weapon_quick_access_input(
     input: Res<InputState>,
     quick_access_query: Query<&InventoryQuickAccessSlotsSystem>,
-    mut equip_events: EventWriter<EquipMeleeWeaponEvent>,
+    mut equip_events: ResMut<EquipMeleeWeaponEventQueue>,
 ) {
     let Some(index) = input.select_weapon else { return };
     for system in quick_access_query.iter() {
@@ -82,8 +89,8 @@ pub fn handle_melee_weapon_quick_access_input(
 
 pub fn handle_melee_weapon_equip(
     mut commands: Commands,
-    mut equip_events: EventReader<EquipMeleeWeaponEvent>,
-    mut unequip_events: EventReader<UnequipMeleeWeaponEvent>,
+    mut equip_events: QueueReader<EquipMeleeWeaponEvent>,
+    mut unequip_events: QueueReader<UnequipMeleeWeaponEvent>,
     mut state_query: Query<&mut MeleeWeaponEquipmentState>,
     mount_query: Query<(Entity, &WeaponMountPoint, &Parent)>,
 ) {
@@ -157,7 +164,7 @@ pub fn update_melee_weapon_offsets(
 
 pub fn handle_melee_weapon_draw(
     input: Res<InputState>,
-    mut toggle_events: EventWriter<ToggleMeleeWeaponDrawEvent>,
+    mut toggle_events: ResMut<ToggleMeleeWeaponDrawEventQueue>,
     state_query: Query<(Entity, &MeleeWeaponEquipmentState)>,
 ) {
     if !input.ability_use_pressed {
@@ -173,7 +180,7 @@ pub fn handle_melee_weapon_draw(
 
 pub fn apply_melee_weapon_draw(
     mut commands: Commands,
-    mut toggle_events: EventReader<ToggleMeleeWeaponDrawEvent>,
+    mut toggle_events: QueueReader<ToggleMeleeWeaponDrawEvent>,
     mut state_query: Query<&mut MeleeWeaponEquipmentState>,
     mount_query: Query<(Entity, &WeaponMountPoint, &Parent)>,
 ) {
@@ -61,8 +61,11 @@ pub mod weapon_inventory_prefab_creation_system;
 pub mod item_effects;
 pub mod item_usage_system;
 pub mod weapon_equip_system;
+pub mod companion_inventory_system;
+pub mod companion_inventory_ui_system;
 
 use bevy::prelude::*;
+use crate::input::{ConsoleAppExt, ConsoleCommand};
 use types::*;
 use components::*;
 use systems::*;
@@ -96,12 +99,12 @@ pub use hotbar_ui_sync_system::sync_hotbar_ui;
 pub use inventory_list_manager_data::InventoryListManagerData;
 pub use carry_physically_object_from_inventory::{CarryPhysicallyObjectFromInventory, CarriedInventoryItem};
 pub use consumable_inventory_prefab_creation_system::ConsumableInventoryPrefabCreationSystem;
-pub use currency_system::{CurrencyBalance, CurrencyTransactionEvent};
+pub use currency_system::{CurrencyBalance, CurrencyTransactionEvent, CurrencyTransactionEventQueue};
 pub use general_item_on_inventory::GeneralItemOnInventory;
-pub use get_inventory_object_system::GetInventoryObjectEvent;
-pub use get_object_from_inventory_system::GetObjectFromInventoryEvent;
+pub use get_inventory_object_system::{GetInventoryObjectEvent, GetInventoryObjectEventQueue};
+pub use get_object_from_inventory_system::{GetObjectFromInventoryEvent, GetObjectFromInventoryEventQueue};
 pub use inventory_bank_manager::InventoryBankManager;
-pub use inventory_bank_system::InventoryBankTransferEvent;
+pub use inventory_bank_system::{InventoryBankTransferEvent, InventoryBankTransferEventQueue};
 pub use inventory_bank_ui_system::InventoryBankUIRoot;
 pub use inventory_capture_manager::InventoryCaptureManager;
 pub use inventory_capture_manager_transparent::InventoryCaptureManagerTransparent;
@@ -110,14 +113,14 @@ pub use inventory_info::InventoryInfo;
 pub use inventory_list_element::InventoryListElement;
 pub use inventory_list_manager::InventoryListManager;
 pub use inventory_menu_icon_element::InventoryMenuIconElement;
-pub use inventory_menu_panels_system::{InventoryMenuPanelEvent, InventoryMenuPanelsSystem};
+pub use inventory_menu_panels_system::{InventoryMenuPanelEvent, InventoryMenuPanelEventQueue, InventoryMenuPanelsSystem};
 pub use inventory_object_to_equip_info::InventoryObjectToEquipInfo;
 pub use inventory_prefab_creation_system::InventoryPrefabCreationSystem;
-pub use inventory_management_system::{InventoryConfig, AddInventoryItemEvent};
-pub use inventory_examine_system::{ExamineInventoryItemEvent, InventoryItemPreviewRegistry, InventoryExamineSettings};
-pub use inventory_drop_system::DropInventoryItemEvent;
-pub use inventory_stack_system::SplitStackEvent;
-pub use inventory_combine_system::{CombineInventoryItemsEvent, CombineRecipeRegistry};
+pub use inventory_management_system::{InventoryConfig, AddInventoryItemEvent, AddInventoryItemEventQueue, add_item_with_config};
+pub use inventory_examine_system::{ExamineInventoryItemEvent, ExamineInventoryItemEventQueue, InventoryItemPreviewRegistry, InventoryExamineSettings};
+pub use inventory_drop_system::{DropInventoryItemEvent, DropInventoryItemEventQueue};
+pub use inventory_stack_system::{SplitStackEvent, SplitStackEventQueue};
+pub use inventory_combine_system::{CombineInventoryItemsEvent, CombineInventoryItemsEventQueue, CombineRecipeRegistry};
 pub use inventory_slot_options_buttons::InventorySlotOptionsButtons;
 pub use melee_shield_inventory_prefab_creation_system::MeleeShieldInventoryPrefabCreationSystem;
 pub use melee_weapon_consumable_inventory_prefab_creation_system::MeleeWeaponConsumableInventoryPrefabCreationSystem;
@@ -127,24 +130,95 @@ pub use melee_weapon_equipment_system::{
     WeaponMountType,
     MeleeWeaponEquipmentState,
     EquipMeleeWeaponEvent,
+    EquipMeleeWeaponEventQueue,
     UnequipMeleeWeaponEvent,
+    UnequipMeleeWeaponEventQueue,
     ToggleMeleeWeaponDrawEvent,
+    ToggleMeleeWeaponDrawEventQueue,
 };
 pub use player_inventory_categories_list_manager::PlayerInventoryCategoriesListManager;
-pub use use_inventory_object::{UseInventoryObjectEvent, InventoryObjectUsedEvent};
+pub use use_inventory_object::{UseInventoryObjectEvent, UseInventoryObjectEventQueue, InventoryObjectUsedEvent, InventoryObjectUsedEventQueue};
 pub use weapon_attachment_inventory_prefab_creation_system::WeaponAttachmentInventoryPrefabCreationSystem;
 pub use weapon_inventory_prefab_creation_system::WeaponInventoryPrefabCreationSystem;
 pub use item_effects::{ItemEffectRegistry, ItemEffect};
-pub use weapon_equip_system::{RequestEquipWeaponEvent, WeaponSpawnRegistry};
+pub use weapon_equip_system::{RequestEquipWeaponEvent, RequestEquipWeaponEventQueue, WeaponSpawnRegistry};
 pub use ammo_sync_system::sync_weapon_ammo_with_inventory;
 pub use auto_equip_settings::InventoryAutoEquipSettings;
+pub use companion_inventory_system::{
+    CompanionInventory,
+    CompanionInventorySettings,
+    CompanionInventoryState,
+    CompanionInventoryCommand,
+    CompanionInventoryCommandQueue,
+    CompanionInventoryEvent,
+    CompanionInventoryEventQueue,
+    CompanionItemRecord,
+    CompanionInventoryRecord,
+    transfer_inventory_items,
+    equip_main_hand,
+    unequip_main_hand,
+};
+pub use companion_inventory_ui_system::CompanionInventoryUIRoot;
 
 /// Plugin for the Inventory System
 pub struct InventoryPlugin;
 
 impl Plugin for InventoryPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<InventoryListManagerData>()
+        app
+        .register_type::<InventoryConfig>()
+        .add_console_command(
+            ConsoleCommand::new("give_item", "Gives the player an item").with_usage("<item_id> [quantity]"),
+            give_item_command,
+        )
+        .register_type::<WeaponInventoryPrefabCreationSystem>()
+        .register_type::<JetpackFuelOnInventory>()
+        .register_type::<WeaponAttachmentOnInventory>()
+        .register_type::<InventoryListManager>()
+        .register_type::<InventoryQuickAccessSlotElement>()
+        .register_type::<OxygenOnInventory>()
+        .register_type::<MeleeShieldInventoryPrefabCreationSystem>()
+        .register_type::<InventoryMenuIconElement>()
+        .register_type::<InventoryMenuPanelsSystem>()
+        .register_type::<GeneralItemOnInventory>()
+        .register_type::<VehicleFuelOnInventory>()
+        .register_type::<MeleeWeaponOnInventory>()
+        .register_type::<AmmoOnInventory>()
+        .register_type::<InventoryCaptureManagerTransparent>()
+        .register_type::<InventoryCategoryInfo>()
+        .register_type::<StaminaOnInventory>()
+        .register_type::<CurrencyBalance>()
+        .register_type::<Inventory>()
+        .register_type::<Equipment>()
+        .register_type::<PhysicalItem>()
+        .register_type::<MeleeWeaponConsumableInventoryPrefabCreationSystem>()
+        .register_type::<InventoryObjectToEquipInfo>()
+        .register_type::<PlayerInventoryCategoriesListManager>()
+        .register_type::<ShieldOnInventory>()
+        .register_type::<MeleeWeaponInventoryPrefabCreationSystem>()
+        .register_type::<InventoryCaptureManager>()
+        .register_type::<InventoryPrefabCreationSystem>()
+        .register_type::<ObjectOnInventory>()
+        .register_type::<AmmoInventoryPrefabCreationSystem>()
+        .register_type::<InventoryBankManager>()
+        .register_type::<MeleeWeaponConsumableOnInventory>()
+        .register_type::<InventoryInfo>()
+        .register_type::<EnergyOnInventory>()
+        .register_type::<MeleeShieldOnInventory>()
+        .register_type::<WeaponAttachmentInventoryPrefabCreationSystem>()
+        .register_type::<InventorySlotOptionsButtons>()
+        .register_type::<ConsumableInventoryPrefabCreationSystem>()
+        .register_type::<WeaponOnInventory>()
+        .register_type::<InventoryListElement>()
+        .register_type::<WeaponMountPoint>()
+        .register_type::<MeleeWeaponEquipmentState>()
+        .register_type::<InventoryQuickAccessSlotsSystem>()
+        .register_type::<CarryPhysicallyObjectFromInventory>()
+        .register_type::<CarriedInventoryItem>()
+        .register_type::<CompanionInventory>()
+        .register_type::<CompanionInventorySettings>()
+        .register_type::<HealthOnInventory>()
+        .init_resource::<InventoryListManagerData>()
         .init_resource::<ItemEffectRegistry>()
         .init_resource::<InventoryItemPreviewRegistry>()
         .init_resource::<InventoryExamineSettings>()
@@ -153,29 +227,30 @@ impl Plugin for InventoryPlugin {
         .init_resource::<InventoryAutoEquipSettings>()
         .init_resource::<InventorySelection>()
         .init_resource::<InventorySlotDragState>()
-        .add_event::<CurrencyTransactionEvent>()
-        .add_event::<GetInventoryObjectEvent>()
-        .add_event::<GetObjectFromInventoryEvent>()
-        .add_event::<InventoryBankTransferEvent>()
-        .add_event::<InventoryMenuPanelEvent>()
-        .add_event::<AddInventoryItemEvent>()
-        .add_event::<ExamineInventoryItemEvent>()
-        .add_event::<DropInventoryItemEvent>()
-        .add_event::<SplitStackEvent>()
-        .add_event::<CombineInventoryItemsEvent>()
-        .add_event::<UseInventoryObjectEvent>()
-        .add_event::<InventoryObjectUsedEvent>()
-        .add_event::<EquipMeleeWeaponEvent>()
-        .add_event::<RequestEquipWeaponEvent>()
-        .add_event::<UnequipMeleeWeaponEvent>()
-        .add_event::<ToggleMeleeWeaponDrawEvent>()
+        .init_resource::<CompanionInventorySettings>()
+        .init_resource::<CompanionInventoryState>()
+        .add_plugins(crate::utils::EventQueuePlugin::<CompanionInventoryCommand>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<CompanionInventoryEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<CurrencyTransactionEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<GetInventoryObjectEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<GetObjectFromInventoryEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<InventoryBankTransferEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<InventoryMenuPanelEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<AddInventoryItemEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<ExamineInventoryItemEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<DropInventoryItemEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<SplitStackEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<CombineInventoryItemsEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<UseInventoryObjectEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<InventoryObjectUsedEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<EquipMeleeWeaponEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<RequestEquipWeaponEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<UnequipMeleeWeaponEvent>::default())
+        .add_plugins(crate::utils::EventQueuePlugin::<ToggleMeleeWeaponDrawEvent>::default())
         .add_systems(Update, (
             update_inventory,
             handle_pickup_events,
-            toggle_inventory_ui,
-            update_inventory_ui,
             handle_inventory_selection,
-            update_inventory_details_panel,
             handle_inventory_drag_and_drop,
             apply_inventory_warning_feedback,
             ammo_inventory_prefab_creation_system::update_ammo_inventory_prefab_creation_system,
@@ -187,7 +262,6 @@ impl Plugin for InventoryPlugin {
             get_object_from_inventory_system::update_get_object_from_inventory_system,
             inventory_bank_manager::update_inventory_bank_manager,
             inventory_bank_system::update_inventory_bank_system,
-            inventory_bank_ui_system::update_inventory_bank_ui,
             inventory_capture_manager::update_inventory_capture_manager,
             inventory_capture_manager_transparent::update_inventory_capture_manager_transparent,
             inventory_list_manager::update_inventory_list_manager,
@@ -216,7 +290,6 @@ impl Plugin for InventoryPlugin {
             inventory_quick_access_slots_system::update_inventory_quick_access_slots_system,
             quick_access_use_system::handle_quick_access_use,
             hotbar_sync_system::sync_hotbar_with_inventory,
-            hotbar_ui_sync_system::sync_hotbar_ui,
             inventory_examine_system::handle_examine_item,
             inventory_examine_system::rotate_examine_preview,
             inventory_examine_system::update_examine_zoom,
@@ -227,10 +300,28 @@ impl Plugin for InventoryPlugin {
             inventory_context_menu::handle_context_button_interaction,
             inventory_context_menu::handle_context_menu_outside_click,
         ))
+        .add_systems(Update, (
+            companion_inventory_system::attach_companion_inventories,
+            companion_inventory_system::open_companion_inventory_on_interaction,
+            companion_inventory_system::handle_companion_inventory_commands,
+            companion_inventory_system::restore_companion_inventories_from_save,
+            companion_inventory_system::store_companion_inventories_in_save,
+        ).chain().before(weapon_equip_system::handle_request_equip_weapon))
+        .add_systems(Update, (
+            toggle_inventory_ui,
+            update_inventory_ui,
+            update_inventory_details_panel,
+            inventory_bank_ui_system::update_inventory_bank_ui,
+            hotbar_ui_sync_system::sync_hotbar_ui,
+            companion_inventory_ui_system::handle_companion_inventory_buttons,
+            companion_inventory_ui_system::update_companion_inventory_ui
+                .after(companion_inventory_system::handle_companion_inventory_commands),
+        ).in_set(crate::headless::PresentationSet))
         .add_systems(Startup, (
             setup_inventory_ui,
             inventory_bank_ui_system::setup_inventory_bank_ui,
+            companion_inventory_ui_system::setup_companion_inventory_ui,
             inventory_examine_system::ensure_examine_camera,
-        ));
+        ).in_set(crate::headless::PresentationSet));
     }
 }
@@ -2,11 +2,11 @@ use bevy::prelude::*;
 
 use crate::input::InputState;
 use super::inventory_quick_access_slots_system::InventoryQuickAccessSlotsSystem;
-use super::use_inventory_object::UseInventoryObjectEvent;
+use super::use_inventory_object::{UseInventoryObjectEvent, UseInventoryObjectEventQueue};
 
 pub fn handle_quick_access_use(
     input: Res<InputState>,
-    mut use_events: EventWriter<UseInventoryObjectEvent>,
+    mut use_events: ResMut<UseInventoryObjectEventQueue>,
     query: Query<&InventoryQuickAccessSlotsSystem>,
 ) {
     let Some(index) = input.select_weapon else { return };
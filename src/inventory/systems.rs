@@ -1,16 +1,17 @@
 use bevy::prelude::*;
-use crate::interaction::{InteractionEvent, InteractionEventQueue, InteractionType, InteractionDetector};
+use crate::interaction::{InteractionEvent, InteractionType, InteractionDetector};
 use crate::abilities::{AbilityPickup, PlayerAbilitiesSystem, AbilityInfo};
-use crate::input::InputState;
+use crate::input::{console_arg, ConsoleArgs, ConsoleResult, FocusScope, InputState};
 use super::components::*;
 use super::types::{InventoryItem, ItemType};
 use super::inventory_management_system::InventoryConfig;
-use super::weapon_equip_system::RequestEquipWeaponEvent;
+use super::weapon_equip_system::{RequestEquipWeaponEvent, RequestEquipWeaponEventQueue};
 use crate::weapons::WeaponManager;
+use crate::utils::QueueReader;
 
 pub fn handle_pickup_events(
     mut commands: Commands,
-    mut events: ResMut<InteractionEventQueue>,
+    mut events: QueueReader<InteractionEvent>,
     mut inventory_query: Query<(&mut Inventory, Option<&InventoryConfig>, &GlobalTransform)>,
     item_query: Query<&PhysicalItem>,
     ability_pickup_query: Query<&AbilityPickup>,
@@ -18,9 +19,9 @@ pub fn handle_pickup_events(
     mut player_abilities_query: Query<&mut PlayerAbilitiesSystem>,
     mut weapon_manager_query: Query<&mut WeaponManager>,
     weapon_query: Query<&crate::weapons::Weapon>,
-    mut equip_events: EventWriter<RequestEquipWeaponEvent>,
+    mut equip_events: ResMut<RequestEquipWeaponEventQueue>,
 ) {
-    let events_to_process: Vec<InteractionEvent> = events.0.drain(..).collect();
+    let events_to_process: Vec<InteractionEvent> = events.read().copied().collect();
     
     for event in events_to_process {
         if event.interaction_type == InteractionType::Pickup {
@@ -175,6 +176,7 @@ pub fn setup_inventory_ui(mut commands: Commands) {
             },
             BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
             InventoryUIRoot,
+            FocusScope::default(),
             Visibility::Hidden,
         ))
         .with_children(|parent| {
@@ -468,3 +470,43 @@ pub fn apply_inventory_warning_feedback(
         section.value = message.unwrap_or_default().to_string();
     }
 }
+
+/// Console command `give_item <item_id> [quantity]`: copies an item the
+/// player already carries with that id, or gives a plain one
+pub fn give_item_command(
+    In(args): In<ConsoleArgs>,
+    mut player_query: Query<&mut Inventory, With<crate::character::Player>>,
+) -> ConsoleResult {
+    let item_id: String = console_arg(&args, 0, "item id")?;
+    let quantity: i32 = if args.len() > 1 { console_arg(&args, 1, "quantity")? } else { 1 };
+    if quantity < 1 {
+        return Err("Quantity must be at least 1".to_string());
+    }
+    let mut inventory = player_query.iter_mut().next().ok_or("No player with an inventory")?;
+
+    let mut item = inventory
+        .items
+        .iter()
+        .flatten()
+        .find(|item| item.item_id == item_id)
+        .cloned()
+        .unwrap_or_else(|| InventoryItem {
+            item_id: item_id.clone(),
+            name: item_id.replace('_', " "),
+            quantity: 1,
+            max_stack: 99,
+            weight: 0.1,
+            item_type: ItemType::Material,
+            icon_path: String::new(),
+            value: 0.0,
+            category: "Misc".to_string(),
+            min_level: 0,
+            info: String::new(),
+            is_infinite: false,
+        });
+    item.quantity = quantity;
+    match inventory.add_item(item) {
+        None => Ok(format!("Gave {} x{}", item_id, quantity)),
+        Some(left) => Err(format!("Inventory full, {} of {} x{} didn't fit", left.quantity, item_id, quantity)),
+    }
+}
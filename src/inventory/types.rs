@@ -30,7 +30,7 @@ pub struct InventoryItem {
 }
 
 /// Item type enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, serde::Serialize, serde::Deserialize)]
 pub enum ItemType {
     Weapon,
     Ammo,
@@ -2,6 +2,7 @@ use bevy::prelude::*;
 
 use super::components::Inventory;
 use super::types::InventoryItem;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Event to request using an inventory item.
 ///
@@ -14,6 +15,8 @@ pub struct UseInventoryObjectEvent {
     pub hand_preference: Option<crate::inventory::types::HandType>,
 }
 
+pub type UseInventoryObjectEventQueue = EventQueue<UseInventoryObjectEvent>;
+
 /// Event emitted after an item is used.
 #[derive(Event, Debug)]
 pub struct InventoryObjectUsedEvent {
@@ -22,9 +25,11 @@ pub struct InventoryObjectUsedEvent {
     pub hand_preference: Option<crate::inventory::types::HandType>,
 }
 
+pub type InventoryObjectUsedEventQueue = EventQueue<InventoryObjectUsedEvent>;
+
 pub fn update_use_inventory_object(
-    mut use_events: EventReader<UseInventoryObjectEvent>,
-    mut used_events: EventWriter<InventoryObjectUsedEvent>,
+    mut use_events: QueueReader<UseInventoryObjectEvent>,
+    mut used_events: ResMut<InventoryObjectUsedEventQueue>,
     mut inventories: Query<&mut Inventory>,
 ) {
     for event in use_events.read() {
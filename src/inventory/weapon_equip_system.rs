@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use std::collections::HashMap;
 
 use crate::weapons::{WeaponBundle, WeaponManager, Weapon, WeaponType};
+use crate::utils::{EventQueue, QueueReader};
 
 #[derive(Event, Debug, Clone)]
 pub struct RequestEquipWeaponEvent {
@@ -10,6 +11,8 @@ pub struct RequestEquipWeaponEvent {
     pub hand_preference: Option<crate::inventory::types::HandType>,
 }
 
+pub type RequestEquipWeaponEventQueue = EventQueue<RequestEquipWeaponEvent>;
+
 #[derive(Debug, Clone)]
 pub struct WeaponSpawnInfo {
     pub weapon_name: String,
@@ -44,7 +47,7 @@ pub struct WeaponSpawnRegistry {
 
 pub fn handle_request_equip_weapon(
     mut commands: Commands,
-    mut events: EventReader<RequestEquipWeaponEvent>,
+    mut events: QueueReader<RequestEquipWeaponEvent>,
     mut manager_query: Query<&mut WeaponManager>,
     mut weapon_query: Query<&mut Weapon>,
     registry: Res<WeaponSpawnRegistry>,
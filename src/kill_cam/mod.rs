@@ -0,0 +1,42 @@
+//! Kill cam and death recap
+//!
+//! When a player dies, waits [`KillCamSettings::start_delay`], replays the last
+//! seconds of the replay buffer (from the player's camera or through the killer's
+//! eyes) and shows a breakdown of the damage taken: sources, amounts and types.
+//! The victim's respawn is held until the kill cam ends; press Enter to skip.
+
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+
+pub use types::{
+    KillCamMode, KillCamSettings, DamageLogEntry, KillCamDamageLog, DamageRecapSource, DamageRecap,
+    KillCamPhase, KillCamState, KillCamRecapRoot, KillCamRecapText,
+};
+
+pub struct KillCamPlugin;
+
+impl Plugin for KillCamPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<KillCamSettings>()
+            .init_resource::<KillCamDamageLog>()
+            .init_resource::<KillCamState>()
+            .register_type::<KillCamSettings>()
+            .register_type::<KillCamRecapRoot>()
+            .register_type::<KillCamRecapText>()
+            .add_systems(Startup, setup_kill_cam_recap)
+            .add_systems(Update, (
+                record_kill_cam_damage,
+                start_kill_cam_on_death,
+                update_kill_cam,
+                update_kill_cam_recap,
+            ).chain());
+
+        #[cfg(feature = "respawn")]
+        app.add_systems(Update, hold_respawn_during_kill_cam.after(update_kill_cam));
+    }
+}
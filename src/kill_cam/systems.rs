@@ -0,0 +1,358 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::fmt::Write;
+use super::types::*;
+use crate::character::Player;
+use crate::combat::{DamageResultEvent, DeathEvent};
+use crate::replay::{ReplayCommand, ReplayCommandQueue, ReplayId, ReplayPlayback, ReplayRecorder, ReplayStatusEvent};
+use crate::utils::QueueReader;
+#[cfg(feature = "respawn")]
+use crate::respawn::Respawnable;
+
+/// Spawns the hidden recap panel
+pub fn setup_kill_cam_recap(mut commands: Commands, settings: Res<KillCamSettings>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Visibility::Hidden,
+            GlobalZIndex(110),
+            KillCamRecapRoot,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        padding: UiRect::all(Val::Px(12.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::new(""),
+                        TextFont { font_size: settings.font_size, ..default() },
+                        TextColor(Color::WHITE),
+                        KillCamRecapText,
+                    ));
+                });
+        });
+}
+
+/// Logs damage taken by players for the recap
+pub fn record_kill_cam_damage(
+    time: Res<Time>,
+    settings: Res<KillCamSettings>,
+    mut log: ResMut<KillCamDamageLog>,
+    mut damage_results: QueueReader<DamageResultEvent>,
+    players: Query<(), With<Player>>,
+    names: Query<&Name>,
+) {
+    let now = time.elapsed_secs();
+
+    for result in damage_results.read() {
+        if result.final_amount <= 0.0 || !players.contains(result.target) {
+            continue;
+        }
+
+        let source_name = match result.source {
+            Some(source) => names
+                .get(source)
+                .map(|name| name.to_string())
+                .unwrap_or_else(|_| format!("{}", source)),
+            None => "Environment".to_string(),
+        };
+
+        log.entries.push_back(DamageLogEntry {
+            time: now,
+            victim: result.target,
+            source: result.source,
+            source_name,
+            amount: result.final_amount,
+            damage_type: format!("{:?}", result.damage_type),
+            is_crit: result.is_crit,
+        });
+    }
+
+    while log.entries.front().is_some_and(|entry| entry.time < now - settings.recap_window) {
+        log.entries.pop_front();
+    }
+}
+
+/// Starts the kill cam when a player dies
+pub fn start_kill_cam_on_death(
+    settings: Res<KillCamSettings>,
+    log: Res<KillCamDamageLog>,
+    mut state: ResMut<KillCamState>,
+    mut deaths: QueueReader<DeathEvent>,
+    players: Query<(), With<Player>>,
+) {
+    for death in deaths.read() {
+        if !settings.enabled || state.is_active() || !players.contains(death.entity) {
+            continue;
+        }
+
+        state.recap = Some(DamageRecap::from_entries(death.entity, log.entries.iter()));
+        state.phase = KillCamPhase::Delay;
+        state.phase_timer = 0.0;
+    }
+}
+
+/// Replay state and queues the kill cam drives playback through
+#[derive(SystemParam)]
+pub struct KillCamReplay<'w, 's> {
+    pub recorder: Res<'w, ReplayRecorder>,
+    pub playback: Res<'w, ReplayPlayback>,
+    pub replay_status: QueueReader<'w, 's, ReplayStatusEvent>,
+    pub replay_commands: ResMut<'w, ReplayCommandQueue>,
+    pub ids: Query<'w, 's, &'static ReplayId>,
+}
+
+/// Runs the delay, replay and recap phases on real time
+pub fn update_kill_cam(
+    real_time: Res<Time<Real>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<KillCamSettings>,
+    mut state: ResMut<KillCamState>,
+    replay: KillCamReplay,
+) {
+    let KillCamReplay { recorder, playback, mut replay_status, mut replay_commands, ids } = replay;
+    let mut finished = false;
+    let mut stopped = false;
+    for status in replay_status.read() {
+        match status {
+            ReplayStatusEvent::PlaybackFinished => finished = true,
+            ReplayStatusEvent::PlaybackStopped | ReplayStatusEvent::Failed { .. } => stopped = true,
+            _ => {}
+        }
+    }
+
+    if !state.is_active() {
+        return;
+    }
+    state.phase_timer += real_time.delta_secs();
+    let skip = keyboard.just_pressed(settings.skip_key);
+
+    match state.phase {
+        KillCamPhase::Delay => {
+            if state.phase_timer < settings.start_delay && !skip {
+                return;
+            }
+
+            // A replay the user loaded or is watching takes precedence
+            let can_replay = settings.mode != KillCamMode::RecapOnly
+                && !skip
+                && !playback.active
+                && playback.loaded.is_none()
+                && !recorder.replay.frames.is_empty();
+
+            state.phase_timer = 0.0;
+            if !can_replay {
+                state.phase = KillCamPhase::Recap;
+                return;
+            }
+
+            replay_commands.send(ReplayCommand::Play);
+            replay_commands.send(ReplayCommand::Seek(recorder.replay.end_time() - settings.rewind_duration));
+            replay_commands.send(ReplayCommand::SetSpeed(settings.playback_speed));
+
+            let killer_id = state
+                .recap
+                .as_ref()
+                .and_then(|recap| recap.killer)
+                .and_then(|killer| ids.get(killer).ok());
+            if let (KillCamMode::KillerPerspective, Some(killer_id)) = (settings.mode, killer_id) {
+                replay_commands.send(ReplayCommand::SetCameraFollow {
+                    id: Some(killer_id.0),
+                    offset: settings.killer_eye_offset,
+                });
+            }
+            state.phase = KillCamPhase::Replaying;
+        }
+        KillCamPhase::Replaying => {
+            if finished || skip {
+                replay_commands.send(ReplayCommand::Stop);
+            }
+            if stopped {
+                state.phase = KillCamPhase::Recap;
+                state.phase_timer = 0.0;
+            }
+        }
+        KillCamPhase::Recap => {
+            if state.phase_timer >= settings.recap_duration || skip {
+                state.phase = KillCamPhase::Inactive;
+                state.phase_timer = 0.0;
+                state.recap = None;
+            }
+        }
+        KillCamPhase::Inactive => {}
+    }
+}
+
+/// Keeps the victim waiting to respawn until the kill cam is over
+#[cfg(feature = "respawn")]
+pub fn hold_respawn_during_kill_cam(
+    state: Res<KillCamState>,
+    mut respawnable_query: Query<(Entity, &mut Respawnable)>,
+) {
+    let victim = state.victim();
+    for (entity, mut respawnable) in respawnable_query.iter_mut() {
+        let held = victim == Some(entity);
+        if respawnable.held != held {
+            respawnable.held = held;
+        }
+    }
+}
+
+/// Shows the damage recap while the kill cam runs
+pub fn update_kill_cam_recap(
+    state: Res<KillCamState>,
+    mut root_query: Query<&mut Visibility, With<KillCamRecapRoot>>,
+    mut text_query: Query<&mut Text, With<KillCamRecapText>>,
+) {
+    if !state.is_changed() {
+        return;
+    }
+
+    for mut visibility in root_query.iter_mut() {
+        *visibility = if state.is_active() { Visibility::Inherited } else { Visibility::Hidden };
+    }
+
+    let Some(recap) = state.recap.as_ref() else { return };
+    for mut text in text_query.iter_mut() {
+        text.0 = recap_text(recap);
+    }
+}
+
+fn recap_text(recap: &DamageRecap) -> String {
+    let mut text = format!("Killed by {}\n{:.0} damage taken\n", recap.killer_name, recap.total);
+    for source in recap.sources.iter() {
+        let _ = write!(text, "\n{}: {:.0} ({} hits", source.name, source.total, source.hits);
+        if source.crits > 0 {
+            let _ = write!(text, ", {} crits", source.crits);
+        }
+        text.push(')');
+
+        let types: Vec<String> = source
+            .by_type
+            .iter()
+            .map(|(damage_type, amount)| format!("{} {:.0}", damage_type, amount))
+            .collect();
+        let _ = write!(text, "\n    {}", types.join(", "));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::{DamageResultQueue, DamageType, DeathEventQueue};
+    use crate::kill_cam::KillCamPlugin;
+    use crate::replay::{ReplayCamera, ReplayPlugin, ReplayRecordable};
+    use crate::utils::EventQueuePlugin;
+    use bevy::input::mouse::AccumulatedMouseMotion;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    fn hit(target: Entity, source: Option<Entity>, amount: f32, damage_type: DamageType, is_crit: bool) -> DamageResultEvent {
+        DamageResultEvent {
+            target,
+            part: None,
+            source,
+            original_amount: amount,
+            final_amount: amount,
+            damage_type,
+            shielded_amount: 0.0,
+            is_crit,
+            is_block: false,
+        }
+    }
+
+    fn run_until(app: &mut App, phase: KillCamPhase) {
+        for _ in 0..60 {
+            app.update();
+            if app.world().resource::<KillCamState>().phase == phase {
+                return;
+            }
+        }
+        panic!("kill cam never reached {:?}", phase);
+    }
+
+    #[test]
+    fn test_kill_cam_replays_killer_view_then_recaps() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<AccumulatedMouseMotion>()
+            .insert_resource(KillCamSettings { mode: KillCamMode::KillerPerspective, ..default() })
+            .add_plugins((
+                EventQueuePlugin::<DamageResultEvent>::default(),
+                EventQueuePlugin::<DeathEvent>::default(),
+                ReplayPlugin,
+                KillCamPlugin,
+            ));
+
+        let player = app.world_mut().spawn((
+            Name::new("Player"),
+            Player,
+            ReplayRecordable,
+            Transform::default(),
+            GlobalTransform::default(),
+        )).id();
+        #[cfg(feature = "respawn")]
+        app.world_mut().entity_mut(player).insert(Respawnable::default());
+        let bandit = app.world_mut().spawn((
+            Name::new("Bandit"),
+            ReplayRecordable,
+            Transform::from_xyz(0.0, 0.0, 4.0),
+            GlobalTransform::from_xyz(0.0, 0.0, 4.0),
+        )).id();
+
+        for _ in 0..3 {
+            app.update();
+        }
+        app.world_mut().resource_mut::<DamageResultQueue>().send_batch([
+            hit(player, None, 5.0, DamageType::Fall, false),
+            hit(player, Some(bandit), 20.0, DamageType::Melee, true),
+            hit(player, Some(bandit), 15.0, DamageType::Melee, false),
+            hit(bandit, Some(player), 50.0, DamageType::Melee, false),
+        ]);
+        app.update();
+        app.world_mut().resource_mut::<DeathEventQueue>().send(DeathEvent { entity: player });
+        app.update();
+
+        assert_eq!(app.world().resource::<KillCamState>().phase, KillCamPhase::Delay);
+        #[cfg(feature = "respawn")]
+        assert!(app.world().get::<Respawnable>(player).unwrap().held);
+        let recap = app.world_mut().query_filtered::<&Text, With<KillCamRecapText>>().single(app.world()).unwrap().0.clone();
+        assert_eq!(
+            recap,
+            "Killed by Bandit\n40 damage taken\n\nBandit: 35 (2 hits, 1 crits)\n    Melee 35\nEnvironment: 5 (1 hits)\n    Fall 5"
+        );
+
+        // After the delay the replay follows the killer
+        run_until(&mut app, KillCamPhase::Replaying);
+        app.update();
+        assert!(app.world().resource::<ReplayPlayback>().active);
+        let bandit_id = app.world().get::<ReplayId>(bandit).unwrap().0;
+        let follow = app.world_mut().query::<&ReplayCamera>().single(app.world()).unwrap().follow;
+        assert_eq!(follow, Some(bandit_id));
+
+        // The replay runs out, then the recap stays up before releasing the respawn
+        run_until(&mut app, KillCamPhase::Recap);
+        assert!(!app.world().resource::<ReplayPlayback>().active);
+        run_until(&mut app, KillCamPhase::Inactive);
+        app.update();
+        #[cfg(feature = "respawn")]
+        assert!(!app.world().get::<Respawnable>(player).unwrap().held);
+        let visibility = *app.world_mut().query_filtered::<&Visibility, With<KillCamRecapRoot>>().single(app.world()).unwrap();
+        assert_eq!(visibility, Visibility::Hidden);
+    }
+}
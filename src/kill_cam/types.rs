@@ -0,0 +1,189 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+/// What the kill cam shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum KillCamMode {
+    /// Replay the last seconds from the player's camera
+    #[default]
+    Rewind,
+    /// Replay the last seconds through the killer's eyes (falls back to `Rewind`)
+    KillerPerspective,
+    /// Only show the damage recap
+    RecapOnly,
+}
+
+/// Kill cam settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct KillCamSettings {
+    pub enabled: bool,
+    pub mode: KillCamMode,
+    /// Seconds between the death and the kill cam
+    pub start_delay: f32,
+    /// Seconds of the replay buffer shown
+    pub rewind_duration: f32,
+    pub playback_speed: f32,
+    /// Seconds the recap stays on screen after the replay
+    pub recap_duration: f32,
+    /// Damage taken within this many seconds before death counts in the recap
+    pub recap_window: f32,
+    /// Camera offset from the killer's origin in `KillerPerspective`
+    pub killer_eye_offset: Vec3,
+    pub skip_key: KeyCode,
+    pub font_size: f32,
+}
+
+impl Default for KillCamSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            mode: KillCamMode::Rewind,
+            start_delay: 1.0,
+            rewind_duration: 5.0,
+            playback_speed: 1.0,
+            recap_duration: 3.0,
+            recap_window: 10.0,
+            killer_eye_offset: Vec3::new(0.0, 1.6, 0.0),
+            skip_key: KeyCode::Enter,
+            font_size: 16.0,
+        }
+    }
+}
+
+/// Damage a player took, kept for the recap
+#[derive(Debug, Clone, PartialEq)]
+pub struct DamageLogEntry {
+    /// Virtual time of the hit
+    pub time: f32,
+    pub victim: Entity,
+    pub source: Option<Entity>,
+    pub source_name: String,
+    pub amount: f32,
+    pub damage_type: String,
+    pub is_crit: bool,
+}
+
+/// Recent damage taken by players
+#[derive(Resource, Debug, Default)]
+pub struct KillCamDamageLog {
+    pub entries: VecDeque<DamageLogEntry>,
+}
+
+/// Damage dealt by one source
+#[derive(Debug, Clone, PartialEq)]
+pub struct DamageRecapSource {
+    pub source: Option<Entity>,
+    pub name: String,
+    pub total: f32,
+    pub hits: u32,
+    pub crits: u32,
+    /// Damage per type, largest first
+    pub by_type: Vec<(String, f32)>,
+}
+
+/// Breakdown of the damage leading to a death
+#[derive(Debug, Clone, PartialEq)]
+pub struct DamageRecap {
+    pub victim: Entity,
+    /// Source of the last hit
+    pub killer: Option<Entity>,
+    pub killer_name: String,
+    pub total: f32,
+    /// Largest contributor first
+    pub sources: Vec<DamageRecapSource>,
+}
+
+impl DamageRecap {
+    /// Summarizes the log entries of `victim`
+    pub fn from_entries<'a>(victim: Entity, entries: impl IntoIterator<Item = &'a DamageLogEntry>) -> Self {
+        let mut sources: Vec<DamageRecapSource> = Vec::new();
+        let mut last_hit: Option<&DamageLogEntry> = None;
+
+        for entry in entries.into_iter().filter(|entry| entry.victim == victim) {
+            last_hit = Some(entry);
+
+            let index = match sources.iter().position(|source| source.source == entry.source && source.name == entry.source_name) {
+                Some(index) => index,
+                None => {
+                    sources.push(DamageRecapSource {
+                        source: entry.source,
+                        name: entry.source_name.clone(),
+                        total: 0.0,
+                        hits: 0,
+                        crits: 0,
+                        by_type: Vec::new(),
+                    });
+                    sources.len() - 1
+                }
+            };
+
+            let source = &mut sources[index];
+            source.total += entry.amount;
+            source.hits += 1;
+            if entry.is_crit {
+                source.crits += 1;
+            }
+            match source.by_type.iter_mut().find(|(damage_type, _)| *damage_type == entry.damage_type) {
+                Some((_, amount)) => *amount += entry.amount,
+                None => source.by_type.push((entry.damage_type.clone(), entry.amount)),
+            }
+        }
+
+        for source in sources.iter_mut() {
+            source.by_type.sort_by(|a, b| b.1.total_cmp(&a.1));
+        }
+        sources.sort_by(|a, b| b.total.total_cmp(&a.total));
+
+        Self {
+            victim,
+            killer: last_hit.and_then(|entry| entry.source),
+            killer_name: last_hit.map(|entry| entry.source_name.clone()).unwrap_or_else(|| "Unknown".to_string()),
+            total: sources.iter().map(|source| source.total).sum(),
+            sources,
+        }
+    }
+}
+
+/// Kill cam phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KillCamPhase {
+    #[default]
+    Inactive,
+    /// Waiting for `start_delay`
+    Delay,
+    /// Replay playing
+    Replaying,
+    /// Replay over, recap still shown
+    Recap,
+}
+
+/// Runtime kill cam state
+#[derive(Resource, Debug, Default)]
+pub struct KillCamState {
+    pub phase: KillCamPhase,
+    /// Real seconds spent in the current phase
+    pub phase_timer: f32,
+    pub recap: Option<DamageRecap>,
+}
+
+impl KillCamState {
+    pub fn is_active(&self) -> bool {
+        self.phase != KillCamPhase::Inactive
+    }
+
+    /// Entity whose death is being shown
+    pub fn victim(&self) -> Option<Entity> {
+        self.recap.as_ref().filter(|_| self.is_active()).map(|recap| recap.victim)
+    }
+}
+
+/// Recap panel root
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct KillCamRecapRoot;
+
+/// Recap panel text
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct KillCamRecapText;
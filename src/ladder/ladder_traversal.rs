@@ -0,0 +1,718 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::ai::AiPath;
+use crate::character::{CharacterController, Player};
+use crate::input::InputState;
+use super::types::*;
+use super::player_ladder::PlayerLadderSystem;
+use super::ladder_system::LadderSystem;
+
+/// Entry/exit end of a ladder
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum LadderEnd {
+    #[default]
+    Bottom,
+    Top,
+}
+
+/// Mount/exit anchors for a ladder, in the ladder's local space
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct LadderAnchors {
+    /// Where the climber stands on the ladder at the bottom rung
+    pub bottom_anchor: Vec3,
+    /// Where the climber hangs on the ladder at the top rung
+    pub top_anchor: Vec3,
+    /// Where the climber is placed after exiting at the top
+    pub top_exit: Vec3,
+    /// Where the climber is placed after exiting at the bottom
+    pub bottom_exit: Vec3,
+    pub rung_spacing: f32,
+    pub snap_to_rungs: bool,
+    pub allow_side_exit: bool,
+    pub side_exit_distance: f32,
+    pub allow_slide: bool,
+}
+
+impl Default for LadderAnchors {
+    fn default() -> Self {
+        Self {
+            bottom_anchor: Vec3::new(0.0, 0.0, 0.4),
+            top_anchor: Vec3::new(0.0, 3.0, 0.4),
+            top_exit: Vec3::new(0.0, 4.0, -0.6),
+            bottom_exit: Vec3::new(0.0, 0.0, 0.9),
+            rung_spacing: 0.3,
+            snap_to_rungs: true,
+            allow_side_exit: true,
+            side_exit_distance: 0.8,
+            allow_slide: true,
+        }
+    }
+}
+
+impl LadderAnchors {
+    pub fn anchor_world(&self, ladder: &GlobalTransform, end: LadderEnd) -> Vec3 {
+        match end {
+            LadderEnd::Bottom => ladder.transform_point(self.bottom_anchor),
+            LadderEnd::Top => ladder.transform_point(self.top_anchor),
+        }
+    }
+
+    pub fn exit_world(&self, ladder: &GlobalTransform, end: LadderEnd) -> Vec3 {
+        match end {
+            LadderEnd::Bottom => ladder.transform_point(self.bottom_exit),
+            LadderEnd::Top => ladder.transform_point(self.top_exit),
+        }
+    }
+
+    /// Returns the end of the ladder closest to a world position
+    pub fn closest_end(&self, ladder: &GlobalTransform, position: Vec3) -> LadderEnd {
+        let to_bottom = self.anchor_world(ladder, LadderEnd::Bottom).distance_squared(position);
+        let to_top = self.anchor_world(ladder, LadderEnd::Top).distance_squared(position);
+        if to_top < to_bottom { LadderEnd::Top } else { LadderEnd::Bottom }
+    }
+
+    /// Snaps a world position to the nearest rung along the ladder
+    pub fn snap_to_rung(&self, ladder: &GlobalTransform, position: Vec3) -> Vec3 {
+        let bottom = self.anchor_world(ladder, LadderEnd::Bottom);
+        let top = self.anchor_world(ladder, LadderEnd::Top);
+        let axis = top - bottom;
+        let length = axis.length();
+        if length <= f32::EPSILON {
+            return bottom;
+        }
+        let along = (position - bottom).dot(axis / length).clamp(0.0, length);
+        let snapped = if self.snap_to_rungs && self.rung_spacing > 0.0 {
+            ((along / self.rung_spacing).round() * self.rung_spacing).min(length)
+        } else {
+            along
+        };
+        bottom + axis / length * snapped
+    }
+}
+
+/// Tracks who is currently using a ladder so others can be blocked
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct LadderOccupancy {
+    pub occupant: Option<Entity>,
+    pub block_when_occupied: bool,
+}
+
+impl Default for LadderOccupancy {
+    fn default() -> Self {
+        Self {
+            occupant: None,
+            block_when_occupied: true,
+        }
+    }
+}
+
+/// Motion warp used to align a climber with a ladder anchor or exit point
+#[derive(Debug, Clone, Copy, Reflect, Default)]
+pub struct LadderWarp {
+    pub active: bool,
+    pub from_position: Vec3,
+    pub to_position: Vec3,
+    pub from_rotation: Quat,
+    pub to_rotation: Quat,
+    pub progress: f32,
+    pub duration: f32,
+}
+
+impl LadderWarp {
+    pub fn start(&mut self, transform: &Transform, to_position: Vec3, to_rotation: Quat, duration: f32) {
+        self.active = true;
+        self.from_position = transform.translation;
+        self.to_position = to_position;
+        self.from_rotation = transform.rotation;
+        self.to_rotation = to_rotation;
+        self.progress = 0.0;
+        self.duration = duration.max(0.01);
+    }
+
+    /// Advances the warp, returns true when it finished this frame
+    pub fn tick(&mut self, transform: &mut Transform, delta: f32) -> bool {
+        if !self.active {
+            return false;
+        }
+        self.progress = (self.progress + delta / self.duration).min(1.0);
+        // Smoothstep for ease in/out
+        let t = self.progress * self.progress * (3.0 - 2.0 * self.progress);
+        transform.translation = self.from_position.lerp(self.to_position, t);
+        transform.rotation = self.from_rotation.slerp(self.to_rotation, t);
+        if self.progress >= 1.0 {
+            self.active = false;
+            return true;
+        }
+        false
+    }
+}
+
+/// Pending exit requested while on a ladder
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Default)]
+pub enum LadderExitKind {
+    #[default]
+    JumpOff,
+    Top,
+    Bottom,
+    SideLeft,
+    SideRight,
+}
+
+/// Component for precise mount alignment, slide-down and side exits (attached to climbers)
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct LadderTraversal {
+    // Alignment Settings
+    pub mount_warp_duration: f32,
+    pub exit_warp_duration: f32,
+
+    // Slide Settings
+    pub slide_speed: f32,
+    pub slide_acceleration: f32,
+    pub landing_check_distance: f32,
+
+    // Side Exit Settings
+    pub side_exit_input_threshold: f32,
+
+    // Debug State
+    pub warp: LadderWarp,
+    pub mounted_end: LadderEnd,
+    pub is_sliding: bool,
+    pub slide_velocity: f32,
+    pub pending_exit: Option<LadderExitKind>,
+}
+
+impl Default for LadderTraversal {
+    fn default() -> Self {
+        Self {
+            mount_warp_duration: 0.35,
+            exit_warp_duration: 0.4,
+            slide_speed: 8.0,
+            slide_acceleration: 20.0,
+            landing_check_distance: 0.3,
+            side_exit_input_threshold: 0.8,
+            warp: LadderWarp::default(),
+            mounted_end: LadderEnd::Bottom,
+            is_sliding: false,
+            slide_velocity: 0.0,
+            pending_exit: None,
+        }
+    }
+}
+
+/// Component for NPCs that can use ladders while following a path.
+/// A ladder is used when the path routes through an `AiNavLink` on the ladder entity.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct AiLadderClimber {
+    pub enabled: bool,
+    /// How close the NPC must be to a ladder anchor to start using it
+    pub use_radius: f32,
+    pub climb_speed: f32,
+    pub mount_warp_duration: f32,
+
+    // Debug State
+    pub current_ladder: Option<Entity>,
+    pub entry_end: LadderEnd,
+    pub warp: LadderWarp,
+    pub climbing: bool,
+}
+
+impl Default for AiLadderClimber {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            use_radius: 1.0,
+            climb_speed: 2.0,
+            mount_warp_duration: 0.35,
+            current_ladder: None,
+            entry_end: LadderEnd::Bottom,
+            warp: LadderWarp::default(),
+            climbing: false,
+        }
+    }
+}
+
+/// Event for when a slide-down ends on the ground
+#[derive(Event, Debug, Reflect)]
+pub struct LadderSlideLandedEvent {
+    pub entity: Entity,
+    pub ladder_entity: Entity,
+    pub impact_speed: f32,
+}
+
+/// Event for when a climber is refused because the ladder is occupied
+#[derive(Event, Debug, Reflect)]
+pub struct LadderBlockedEvent {
+    pub entity: Entity,
+    pub ladder_entity: Entity,
+    pub occupant: Entity,
+}
+
+/// Rotation that faces the ladder surface
+fn facing_ladder(ladder: &GlobalTransform) -> Quat {
+    Transform::IDENTITY.looking_to(-ladder.forward().as_vec3(), Vec3::Y).rotation
+}
+
+/// System to claim ladders for climbers that mounted them, and to turn back
+/// climbers approaching or mounting a ladder someone else holds
+pub fn enforce_ladder_occupancy(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut PlayerLadderSystem, &mut LadderMovementTracker), With<Player>>,
+    mut ladder_query: Query<&mut LadderOccupancy, With<LadderSystem>>,
+) {
+    for (entity, mut player_ladder, mut tracker) in player_query.iter_mut() {
+        let Some(ladder_entity) = player_ladder.current_ladder_system else {
+            continue;
+        };
+        let Ok(mut occupancy) = ladder_query.get_mut(ladder_entity) else {
+            continue;
+        };
+
+        match occupancy.occupant {
+            Some(occupant) if occupant != entity => {
+                let climbing = tracker.current_state.is_on_ladder() && tracker.current_state != LadderMovementState::Mounting;
+                if occupancy.block_when_occupied && !climbing {
+                    player_ladder.ladder_found = false;
+                    player_ladder.current_ladder_system = None;
+                    tracker.current_state = LadderMovementState::None;
+
+                    commands.trigger(LadderBlockedEvent {
+                        entity,
+                        ladder_entity,
+                        occupant,
+                    });
+                }
+            }
+            // Standing near a ladder doesn't hold it
+            _ if tracker.current_state.is_on_ladder() => {
+                occupancy.occupant = Some(entity);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// System to free ladders whose occupant stopped climbing
+pub fn release_ladder_occupancy(
+    mut ladder_query: Query<(Entity, &mut LadderOccupancy)>,
+    player_query: Query<(&PlayerLadderSystem, &LadderMovementTracker)>,
+    ai_query: Query<&AiLadderClimber>,
+) {
+    for (ladder_entity, mut occupancy) in ladder_query.iter_mut() {
+        let Some(occupant) = occupancy.occupant else {
+            continue;
+        };
+
+        let still_using = player_query
+            .get(occupant)
+            .map(|(player, tracker)| {
+                player.ladder_found
+                    && player.current_ladder_system == Some(ladder_entity)
+                    && tracker.current_state.is_on_ladder()
+            })
+            .unwrap_or(false)
+            || ai_query
+                .get(occupant)
+                .map(|ai| ai.current_ladder == Some(ladder_entity))
+                .unwrap_or(false);
+
+        if !still_using {
+            occupancy.occupant = None;
+        }
+    }
+}
+
+/// System to motion-warp the player to the closest ladder anchor on mount and to the exit point on dismount
+pub fn align_ladder_mount(
+    time: Res<Time>,
+    mut query: Query<(
+        &PlayerLadderSystem,
+        &mut LadderTraversal,
+        &mut LadderMovementTracker,
+        &mut LadderAnimation,
+        &mut Transform,
+    ), With<Player>>,
+    ladder_query: Query<(&GlobalTransform, &LadderAnchors), With<LadderSystem>>,
+) {
+    let delta = time.delta_secs();
+
+    for (
+        player_ladder,
+        mut traversal,
+        mut tracker,
+        mut animation,
+        mut transform,
+    ) in query.iter_mut() {
+        // Start the mount warp once handle_ladder_mount has flagged it
+        if animation.is_mounting && !traversal.warp.active && tracker.current_state == LadderMovementState::Mounting {
+            if let Some((ladder_gt, anchors)) = player_ladder.current_ladder_system.and_then(|ladder| ladder_query.get(ladder).ok()) {
+                let end = anchors.closest_end(ladder_gt, transform.translation);
+                let target = anchors.anchor_world(ladder_gt, end);
+                traversal.mounted_end = end;
+                animation.mount_target_position = target;
+                let duration = traversal.mount_warp_duration;
+                traversal.warp.start(&transform, target, facing_ladder(ladder_gt), duration);
+            }
+        }
+
+        // Start the exit warp once handle_ladder_dismount has flagged it
+        if animation.is_dismounting && !traversal.warp.active {
+            let exit = traversal.pending_exit.take().unwrap_or_default();
+            let ladder = player_ladder.current_ladder_system.or(player_ladder.previous_ladder_system);
+            let mut target = animation.dismount_target_position;
+            let mut rotation = transform.rotation;
+
+            if let Some((ladder_gt, anchors)) = ladder.and_then(|ladder| ladder_query.get(ladder).ok()) {
+                let right = ladder_gt.right().as_vec3();
+                match exit {
+                    LadderExitKind::Top => {
+                        target = anchors.exit_world(ladder_gt, LadderEnd::Top);
+                        rotation = facing_ladder(ladder_gt);
+                    }
+                    LadderExitKind::Bottom => {
+                        target = anchors.exit_world(ladder_gt, LadderEnd::Bottom);
+                    }
+                    LadderExitKind::SideLeft => {
+                        target = transform.translation - right * anchors.side_exit_distance;
+                    }
+                    LadderExitKind::SideRight => {
+                        target = transform.translation + right * anchors.side_exit_distance;
+                    }
+                    LadderExitKind::JumpOff => {}
+                }
+            }
+
+            animation.dismount_target_position = target;
+            let duration = traversal.exit_warp_duration;
+            traversal.warp.start(&transform, target, rotation, duration);
+        }
+
+        if traversal.warp.tick(&mut transform, delta) {
+            if tracker.current_state == LadderMovementState::Mounting {
+                tracker.current_state = if traversal.mounted_end == LadderEnd::Top {
+                    LadderMovementState::ClimbingDown
+                } else {
+                    LadderMovementState::ClimbingUp
+                };
+                tracker.state_timer = 0.0;
+                animation.is_mounting = false;
+                animation.mount_progress = 0.0;
+            }
+            if animation.is_dismounting {
+                animation.is_dismounting = false;
+                animation.dismount_progress = 0.0;
+            }
+        }
+    }
+}
+
+/// System to request top/bottom/side exits based on ladder position and input
+pub fn handle_ladder_exits(
+    input_state: Res<InputState>,
+    mut query: Query<(
+        &PlayerLadderSystem,
+        &mut LadderTraversal,
+        &mut LadderMovementTracker,
+        &Transform,
+    ), With<Player>>,
+    ladder_query: Query<(&GlobalTransform, &LadderAnchors), With<LadderSystem>>,
+) {
+    for (player_ladder, mut traversal, mut tracker, transform) in query.iter_mut() {
+        if !player_ladder.ladder_found || traversal.warp.active || traversal.is_sliding {
+            continue;
+        }
+
+        let climbing = matches!(
+            tracker.current_state,
+            LadderMovementState::ClimbingUp | LadderMovementState::ClimbingDown | LadderMovementState::ClimbingHorizontal
+        );
+        if !climbing {
+            continue;
+        }
+
+        let Some((ladder_gt, anchors)) = player_ladder.current_ladder_system.and_then(|ladder| ladder_query.get(ladder).ok()) else {
+            continue;
+        };
+
+        let top = anchors.anchor_world(ladder_gt, LadderEnd::Top);
+        let bottom = anchors.anchor_world(ladder_gt, LadderEnd::Bottom);
+        let vertical = input_state.movement.y;
+        let horizontal = input_state.movement.x;
+
+        let exit = if vertical > 0.1 && transform.translation.y >= top.y {
+            Some(LadderExitKind::Top)
+        } else if vertical < -0.1 && transform.translation.y <= bottom.y {
+            Some(LadderExitKind::Bottom)
+        } else if anchors.allow_side_exit &&
+                  !player_ladder.use_ladder_horizontal_movement &&
+                  horizontal.abs() > traversal.side_exit_input_threshold {
+            if horizontal > 0.0 { Some(LadderExitKind::SideRight) } else { Some(LadderExitKind::SideLeft) }
+        } else {
+            None
+        };
+
+        if let Some(exit) = exit {
+            traversal.pending_exit = Some(exit);
+            tracker.current_state = LadderMovementState::Dismounting;
+            tracker.state_timer = 0.0;
+        }
+    }
+}
+
+/// System to slide down the ladder quickly when crouch is held, landing with an impact
+pub fn handle_ladder_slide(
+    mut commands: Commands,
+    time: Res<Time>,
+    input_state: Res<InputState>,
+    spatial_query: SpatialQuery,
+    mut query: Query<(
+        Entity,
+        &PlayerLadderSystem,
+        &mut LadderTraversal,
+        &mut LadderMovementTracker,
+        &mut Transform,
+    ), With<Player>>,
+    ladder_query: Query<(&GlobalTransform, &LadderAnchors), With<LadderSystem>>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, player_ladder, mut traversal, mut tracker, mut transform) in query.iter_mut() {
+        let Some(ladder_entity) = player_ladder.current_ladder_system else {
+            traversal.is_sliding = false;
+            continue;
+        };
+        let Ok((ladder_gt, anchors)) = ladder_query.get(ladder_entity) else {
+            continue;
+        };
+
+        if !player_ladder.ladder_found || traversal.warp.active || !anchors.allow_slide {
+            traversal.is_sliding = false;
+            continue;
+        }
+
+        if !traversal.is_sliding {
+            let can_start = matches!(
+                tracker.current_state,
+                LadderMovementState::ClimbingUp | LadderMovementState::ClimbingDown
+            );
+            if can_start && input_state.crouch_pressed && input_state.movement.y < -0.1 {
+                traversal.is_sliding = true;
+                traversal.slide_velocity = 0.0;
+                tracker.current_state = LadderMovementState::ClimbingDown;
+            }
+            continue;
+        }
+
+        traversal.slide_velocity = (traversal.slide_velocity + traversal.slide_acceleration * delta).min(traversal.slide_speed);
+        transform.translation.y -= traversal.slide_velocity * delta;
+
+        let bottom = anchors.anchor_world(ladder_gt, LadderEnd::Bottom);
+        let filter = SpatialQueryFilter::from_excluded_entities([entity, ladder_entity]);
+        let ground_hit = spatial_query.cast_ray(
+            transform.translation + Vec3::Y * 0.1,
+            Dir3::NEG_Y,
+            traversal.landing_check_distance + 0.1,
+            true,
+            &filter,
+        );
+
+        if transform.translation.y <= bottom.y || ground_hit.is_some() {
+            transform.translation.y = transform.translation.y.max(bottom.y);
+            commands.trigger(LadderSlideLandedEvent {
+                entity,
+                ladder_entity,
+                impact_speed: traversal.slide_velocity,
+            });
+
+            traversal.is_sliding = false;
+            traversal.slide_velocity = 0.0;
+            traversal.pending_exit = Some(LadderExitKind::Bottom);
+            tracker.current_state = LadderMovementState::Dismounting;
+            tracker.state_timer = 0.0;
+        }
+    }
+}
+
+type LadderClimbingAi<'a> = (
+    Entity,
+    &'a mut AiLadderClimber,
+    Option<&'a mut AiPath>,
+    &'a mut CharacterController,
+    &'a mut Transform,
+    Option<&'a mut InputState>,
+);
+
+/// System to let NPCs climb the ladders their path is linked through
+pub fn update_ai_ladder_usage(
+    time: Res<Time>,
+    mut ai_query: Query<LadderClimbingAi, Without<Player>>,
+    mut ladder_query: Query<(Entity, &GlobalTransform, &LadderAnchors, &LadderSystem, Option<&mut LadderOccupancy>)>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut climber, path, mut character, mut transform, input) in ai_query.iter_mut() {
+        if !climber.enabled || character.is_dead {
+            continue;
+        }
+
+        // Mount and exit warps run before anything else
+        let warp_finished = climber.warp.tick(&mut transform, delta);
+        if climber.warp.active {
+            continue;
+        }
+
+        // Currently using a ladder
+        if let Some(ladder_entity) = climber.current_ladder {
+            let Ok((_, ladder_gt, anchors, _, _)) = ladder_query.get(ladder_entity) else {
+                climber.current_ladder = None;
+                climber.climbing = false;
+                character.zero_gravity_mode = false;
+                continue;
+            };
+
+            if warp_finished {
+                climber.climbing = true;
+            }
+
+            if let Some(mut input) = input {
+                input.movement = Vec2::ZERO;
+            }
+
+            let exit_end = if climber.entry_end == LadderEnd::Bottom { LadderEnd::Top } else { LadderEnd::Bottom };
+            let target = anchors.anchor_world(ladder_gt, exit_end);
+            let to_target = target - transform.translation;
+
+            if climber.climbing && to_target.length() > 0.05 {
+                let step = to_target.normalize_or_zero() * climber.climb_speed * delta;
+                transform.translation += step.clamp_length_max(to_target.length());
+            } else if climber.climbing {
+                // Reached the far end: warp to the exit and release the ladder
+                let exit = anchors.exit_world(ladder_gt, exit_end);
+                let duration = climber.mount_warp_duration;
+                let rotation = transform.rotation;
+                climber.warp.start(&transform, exit, rotation, duration);
+                climber.climbing = false;
+                climber.current_ladder = None;
+                character.zero_gravity_mode = false;
+
+                // The link is crossed; walk on from the far waypoint
+                if let Some(mut path) = path {
+                    if path.current_link() == Some(ladder_entity) && path.current_index + 1 < path.points.len() {
+                        path.current_index += 1;
+                    }
+                }
+            }
+            continue;
+        }
+
+        // Only ladders the path is routed through are used
+        let Some(ladder_entity) = path.as_ref().and_then(|path| path.current_link()) else {
+            continue;
+        };
+        let Ok((_, ladder_gt, anchors, ladder, occupancy)) = ladder_query.get_mut(ladder_entity) else {
+            continue;
+        };
+        if !ladder.ladder_active {
+            continue;
+        }
+        if let Some(occupancy) = occupancy.as_ref() {
+            if occupancy.block_when_occupied && occupancy.occupant.is_some_and(|occupant| occupant != entity) {
+                continue;
+            }
+        }
+
+        let position = transform.translation;
+        let entry_end = anchors.closest_end(ladder_gt, position);
+        let entry = anchors.anchor_world(ladder_gt, entry_end);
+        if entry.distance(position) > climber.use_radius {
+            continue;
+        }
+        if let Some(mut occupancy) = occupancy {
+            occupancy.occupant = Some(entity);
+        }
+        let rotation = facing_ladder(ladder_gt);
+
+        climber.current_ladder = Some(ladder_entity);
+        climber.entry_end = entry_end;
+        climber.climbing = false;
+        let duration = climber.mount_warp_duration;
+        climber.warp.start(&transform, entry, rotation, duration);
+        character.zero_gravity_mode = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ladder_claimed_only_once_mounted() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Update, (enforce_ladder_occupancy, release_ladder_occupancy).chain());
+
+        let ladder = app.world_mut().spawn((LadderSystem::default(), LadderOccupancy::default())).id();
+        let near_ladder = || PlayerLadderSystem { ladder_found: true, current_ladder_system: Some(ladder), ..default() };
+        let tracker = |current_state| LadderMovementTracker { current_state, ..default() };
+        let first = app.world_mut().spawn((Player, near_ladder(), tracker(LadderMovementState::Approaching))).id();
+
+        // Walking up to the ladder doesn't hold it
+        app.update();
+        assert_eq!(app.world().get::<LadderOccupancy>(ladder).unwrap().occupant, None);
+
+        app.world_mut().get_mut::<LadderMovementTracker>(first).unwrap().current_state = LadderMovementState::ClimbingUp;
+        app.update();
+        assert_eq!(app.world().get::<LadderOccupancy>(ladder).unwrap().occupant, Some(first));
+
+        // A second climber is turned back while the first is on it
+        let second = app.world_mut().spawn((Player, near_ladder(), tracker(LadderMovementState::Mounting))).id();
+        app.update();
+        assert_eq!(app.world().get::<LadderMovementTracker>(second).unwrap().current_state, LadderMovementState::None);
+        assert_eq!(app.world().get::<PlayerLadderSystem>(second).unwrap().current_ladder_system, None);
+
+        app.world_mut().get_mut::<LadderMovementTracker>(first).unwrap().current_state = LadderMovementState::None;
+        app.update();
+        assert_eq!(app.world().get::<LadderOccupancy>(ladder).unwrap().occupant, None);
+    }
+
+    #[test]
+    fn test_ai_climbs_only_linked_ladders() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).add_systems(Update, update_ai_ladder_usage);
+
+        let ladder_transform = GlobalTransform::IDENTITY;
+        let ladder = app.world_mut().spawn((
+            ladder_transform,
+            LadderAnchors::default(),
+            LadderSystem::default(),
+            LadderOccupancy::default(),
+        )).id();
+        let bottom = LadderAnchors::default().anchor_world(&ladder_transform, LadderEnd::Bottom);
+        let top = LadderAnchors::default().anchor_world(&ladder_transform, LadderEnd::Top);
+
+        let npc = |path: AiPath| (
+            AiLadderClimber::default(),
+            path,
+            CharacterController::default(),
+            Transform::from_translation(bottom),
+        );
+        let linked = app.world_mut().spawn(npc(AiPath {
+            points: vec![bottom, top],
+            current_index: 1,
+            links: vec![(1, ladder)],
+        })).id();
+        let walking = app.world_mut().spawn(npc(AiPath {
+            points: vec![bottom, top],
+            current_index: 1,
+            links: Vec::new(),
+        })).id();
+        app.update();
+
+        assert_eq!(app.world().get::<AiLadderClimber>(linked).unwrap().current_ladder, Some(ladder));
+        assert_eq!(app.world().get::<LadderOccupancy>(ladder).unwrap().occupant, Some(linked));
+        assert_eq!(app.world().get::<AiLadderClimber>(walking).unwrap().current_ladder, None);
+    }
+}
@@ -2,17 +2,26 @@ pub mod types;
 pub mod ladder_system;
 pub mod player_ladder;
 pub mod systems;
+pub mod ladder_traversal;
+pub mod moving_ladders;
 
 use bevy::prelude::*;
 use types::*;
 use ladder_system::*;
 use player_ladder::*;
 use systems::*;
+use ladder_traversal::*;
+use moving_ladders::*;
 
 pub use types::LadderMovementState;
 pub use ladder_system::LadderSystem;
 pub use player_ladder::PlayerLadderSystem;
 pub use systems::*;
+pub use ladder_traversal::{
+    LadderAnchors, LadderEnd, LadderOccupancy, LadderWarp, LadderExitKind,
+    LadderTraversal, AiLadderClimber, LadderSlideLandedEvent, LadderBlockedEvent,
+};
+pub use moving_ladders::LadderSurfaceAnchor;
 
 pub struct LadderPlugin;
 
@@ -28,15 +37,29 @@ impl Plugin for LadderPlugin {
             .register_type::<LadderMovement>()
             .register_type::<LadderExitDetection>()
             .register_type::<LadderFootstep>()
+            .register_type::<LadderAnchors>()
+            .register_type::<LadderOccupancy>()
+            .register_type::<LadderTraversal>()
+            .register_type::<AiLadderClimber>()
+            .register_type::<LadderSurfaceAnchor>()
             .add_systems(Update, (
                 handle_ladder_input,
+                handle_ladder_exits,
+                handle_ladder_slide,
                 update_ladder_state,
                 update_ladder_movement,
             ).chain())
+            .add_systems(FixedUpdate, anchor_climbers_to_ladders
+                .after(crate::physics::follow_surface_anchors)
+                .before(detect_ladder))
             .add_systems(FixedUpdate, (
                 detect_ladder,
+                enforce_ladder_occupancy,
                 handle_ladder_mount,
                 handle_ladder_dismount,
+                align_ladder_mount,
+                release_ladder_occupancy,
+                update_ai_ladder_usage,
             ).chain());
     }
 }
@@ -0,0 +1,87 @@
+//! Ladders on moving and rotating objects.
+//!
+//! While the player climbs a ladder, they get a `SurfaceAnchor` to it (see
+//! `crate::physics::surface_anchor`), so a ladder on a ship or a lift carries
+//! them along. Climbing off keeps the ladder's momentum, and a ladder that
+//! shakes the player off makes them jump off it.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::character::Player;
+use crate::physics::{release_surface_anchor, SurfaceAnchor, SurfaceAnchorSettings, SurfaceDetachedEvent};
+use crate::utils::QueueReader;
+use super::types::*;
+use super::player_ladder::PlayerLadderSystem;
+use super::ladder_system::LadderSystem;
+use super::ladder_traversal::{LadderExitKind, LadderTraversal};
+
+/// Marks a `SurfaceAnchor` placed by ladder climbing
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct LadderSurfaceAnchor;
+
+fn is_climbing_ladder(tracker: &LadderMovementTracker) -> bool {
+    matches!(
+        tracker.current_state,
+        LadderMovementState::ClimbingUp | LadderMovementState::ClimbingDown | LadderMovementState::ClimbingHorizontal
+    )
+}
+
+type LadderClimber<'a> = (
+    Entity,
+    &'a PlayerLadderSystem,
+    &'a mut LadderTraversal,
+    &'a mut LadderMovementTracker,
+    &'a Transform,
+    Option<&'a SurfaceAnchor>,
+    Option<&'a mut LinearVelocity>,
+    Has<LadderSurfaceAnchor>,
+);
+
+/// System to anchor ladder climbers to their ladder, and make them jump off
+/// when it shakes them off
+pub fn anchor_climbers_to_ladders(
+    mut commands: Commands,
+    settings: Res<SurfaceAnchorSettings>,
+    mut detached: QueueReader<SurfaceDetachedEvent>,
+    mut query: Query<LadderClimber, With<Player>>,
+    ladder_query: Query<&GlobalTransform, With<LadderSystem>>,
+) {
+    let mut shaken_off = Vec::new();
+    for event in detached.read() {
+        let Ok((entity, _, mut traversal, mut tracker, _, _, _, anchored)) = query.get_mut(event.entity) else { continue };
+        if !anchored {
+            continue;
+        }
+        shaken_off.push(entity);
+        commands.entity(entity).remove::<LadderSurfaceAnchor>();
+        if is_climbing_ladder(&tracker) {
+            traversal.pending_exit = Some(LadderExitKind::JumpOff);
+            tracker.current_state = LadderMovementState::Dismounting;
+            tracker.state_timer = 0.0;
+        }
+    }
+
+    for (entity, player_ladder, _, tracker, transform, anchor, mut velocity, anchored) in query.iter_mut() {
+        if shaken_off.contains(&entity) {
+            continue;
+        }
+        let ladder = player_ladder
+            .current_ladder_system
+            .filter(|_| player_ladder.ladder_found && is_climbing_ladder(&tracker));
+        match (ladder, anchor) {
+            (Some(ladder), Some(anchor)) if anchored && anchor.surface == ladder => {}
+            (Some(ladder), _) => {
+                if let Ok(ladder_transform) = ladder_query.get(ladder) {
+                    let anchor = SurfaceAnchor::new(ladder, ladder_transform, transform.translation, ladder_transform.forward().as_vec3());
+                    commands.entity(entity).insert((anchor, LadderSurfaceAnchor));
+                }
+            }
+            (None, Some(anchor)) if anchored => {
+                release_surface_anchor(&mut commands, entity, anchor, velocity.as_deref_mut(), &settings);
+                commands.entity(entity).remove::<LadderSurfaceAnchor>();
+            }
+            (None, _) => {}
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use crate::character::{CharacterController, Player};
 use crate::input::InputState;
-use crate::footsteps::{FootstepController, FootstepEventQueue, FootstepEvent};
+use crate::footsteps::{FootstepController, FootstepEventQueue, FootstepEvent, FootstepGait, FootstepNoiseSettings};
 use super::types::*;
 use super::player_ladder::PlayerLadderSystem;
 use super::ladder_system::LadderSystem;
@@ -72,6 +72,7 @@ pub fn update_ladder_state(
         &mut FootstepController,
     ), With<Player>>,
     mut event_queue: ResMut<FootstepEventQueue>,
+    noise_settings: Res<FootstepNoiseSettings>,
 ) {
     for (
         entity,
@@ -131,12 +132,13 @@ pub fn update_ladder_state(
                     // Trigger "Ladder" footstep
                     footstep.last_foot_left = !footstep.last_foot_left;
 
-                    event_queue.0.push(FootstepEvent {
+                    event_queue.send(FootstepEvent {
                         entity, 
                         surface_id: "Ladder".to_string(),
                         position: transform.translation,
+                        normal: Vec3::Y,
                         volume: 0.8, 
-                        noise_radius: footstep.noise_radius,
+                        noise_radius: noise_settings.noise_radius(footstep.noise_radius, FootstepGait::Walk, "Ladder", 0.0),
                         is_left: footstep.last_foot_left,
                     });
                 }
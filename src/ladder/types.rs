@@ -49,6 +49,13 @@ pub enum LadderMovementState {
     Dismounting,
 }
 
+impl LadderMovementState {
+    /// Whether the climber is mounted on the ladder, from mounting to dismounting
+    pub fn is_on_ladder(self) -> bool {
+        !matches!(self, Self::None | Self::Approaching)
+    }
+}
+
 /// Component to track ladder movement state
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
@@ -4,10 +4,19 @@ use bevy::prelude::*;
 pub mod types;
 pub mod systems;
 pub mod ui;
+pub mod streaming;
+pub mod scene_export;
 
 use types::*;
 use systems::*;
 use ui::*;
+use streaming::*;
+use crate::utils::EventQueuePlugin;
+
+pub use scene_export::{
+    LevelSceneMember, SceneExportSettings, SceneExportCommand, SceneExportStatus,
+    SceneExportCommandQueue, SceneExportStatusQueue, SceneExportPlugin,
+};
 
 pub struct LevelManagerPlugin;
 
@@ -20,13 +29,24 @@ impl Plugin for LevelManagerPlugin {
             .register_type::<TravelStationDestination>()
             .register_type::<LevelManagerGlobalState>()
             .register_type::<CurrentLevelInfo>()
+            .register_type::<StreamingChunk>()
+            .register_type::<StreamingTriggerVolume>()
+            .register_type::<StreamingSource>()
+            .register_type::<StreamingChunkMember>()
+            .register_type::<PersistentEntity>()
+            .register_type::<ChunkDependent>()
+            .register_type::<LevelStreamingSettings>()
+            .register_type::<LevelStreamingState>()
 
             // Resources
             .init_resource::<LevelManagerGlobalState>()
             .init_resource::<CurrentLevelInfo>()
             .init_resource::<PendingLevelChange>()
-            .init_resource::<RequestLevelChangeEventQueue>()
-            .init_resource::<TravelStationDiscoveredEventQueue>()
+            .add_plugins(EventQueuePlugin::<RequestLevelChangeEvent>::default())
+            .add_plugins(EventQueuePlugin::<TravelStationDiscoveredEvent>::default())
+            .init_resource::<LevelStreamingSettings>()
+            .init_resource::<LevelStreamingState>()
+            .add_plugins(EventQueuePlugin::<ChunkStreamingEvent>::default())
 
             // Events
             // Events (Managed via Queues)
@@ -34,13 +54,22 @@ impl Plugin for LevelManagerPlugin {
             // .add_event::<TravelStationDiscoveredEvent>()
 
             // Systems
-            .add_systems(Startup, setup_travel_ui)
+            .add_systems(Startup, setup_travel_ui.in_set(crate::headless::PresentationSet))
             .add_systems(Update, (
                 handle_level_change,
                 spawn_player_at_level_manager,
                 handle_travel_station_discovery,
-                update_travel_ui,
+                update_travel_ui.in_set(crate::headless::PresentationSet),
                 handle_travel_button_interactions,
-            ));
+                close_travel_ui_on_cancel,
+            ))
+            .add_systems(Update, (
+                update_streaming_triggers,
+                update_chunk_streaming,
+                finish_chunk_loading,
+                handoff_persistent_entities,
+                update_chunk_dependents,
+                draw_streaming_chunks.in_set(crate::headless::PresentationSet),
+            ).chain());
     }
 }
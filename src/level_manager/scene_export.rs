@@ -0,0 +1,265 @@
+//! Level scene export
+//!
+//! Writes the level content of the running world (interactables, puzzles, AI,
+//! vehicles and anything marked [`LevelSceneMember`], with their descendants)
+//! to a `.scn.ron` file, and spawns such a file back. Only reflected components
+//! of this crate plus `Transform`, `Visibility`, `Name` and `ChildOf` are
+//! written: meshes, materials and physics state are rebuilt by the game.
+//!
+//! ```rust,ignore
+//! fn save_level(mut scenes: ResMut<SceneExportCommandQueue>) {
+//!     scenes.send(SceneExportCommand::Export { path: "arena.scn.ron".into() });
+//! }
+//! ```
+
+use bevy::ecs::entity::{EntityHashMap, EntityHashSet};
+use bevy::ecs::query::QueryFilter;
+use bevy::prelude::*;
+use bevy::scene::serde::SceneDeserializer;
+use bevy::scene::{DynamicScene, DynamicSceneBuilder, SceneFilter};
+use serde::de::DeserializeSeed;
+use std::any::TypeId;
+use std::path::PathBuf;
+use crate::ai::AiController;
+use crate::character::Player;
+use crate::interaction::Interactable;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueCursor};
+
+/// Type path prefix of the components owned by this crate
+const CRATE_TYPE_PATH: &str = concat!(env!("CARGO_CRATE_NAME"), "::");
+
+/// Marks an entity (and its descendants) as level content to export
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct LevelSceneMember;
+
+/// Scene export settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct SceneExportSettings {
+    /// Relative paths are resolved against this directory
+    pub directory: String,
+    /// Crate components left out of exports, by type path suffix (e.g. "Highlighted")
+    pub skipped_components: Vec<String>,
+}
+
+impl Default for SceneExportSettings {
+    fn default() -> Self {
+        Self {
+            directory: "levels".to_string(),
+            skipped_components: vec!["Highlighted".to_string()],
+        }
+    }
+}
+
+/// Scene export control
+#[derive(Debug, Clone)]
+pub enum SceneExportCommand {
+    /// Writes the level content to a `.scn.ron` file
+    Export { path: String },
+    /// Spawns a `.scn.ron` file; `replace` despawns the current level content first
+    Import { path: String, replace: bool },
+}
+
+/// Outcome of a scene export command
+#[derive(Debug, Clone)]
+pub enum SceneExportStatus {
+    Exported { path: String, entities: usize },
+    Imported { path: String, entities: usize },
+    Failed { error: String },
+}
+
+pub type SceneExportCommandQueue = EventQueue<SceneExportCommand>;
+pub type SceneExportStatusQueue = EventQueue<SceneExportStatus>;
+
+/// Level export to and import from `.scn.ron` files
+pub struct SceneExportPlugin;
+
+impl Plugin for SceneExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<LevelSceneMember>()
+            .register_type::<SceneExportSettings>()
+            .init_resource::<SceneExportSettings>()
+            .add_plugins(EventQueuePlugin::<SceneExportCommand>::default())
+            .add_plugins(EventQueuePlugin::<SceneExportStatus>::default())
+            .add_systems(Update, process_scene_export_commands.in_set(crate::GameControllerSet::Persistence));
+    }
+}
+
+/// System to run export and import commands
+pub fn process_scene_export_commands(world: &mut World, mut cursor: Local<QueueCursor<SceneExportCommand>>) {
+    let commands: Vec<SceneExportCommand> = cursor
+        .read(world.resource::<SceneExportCommandQueue>())
+        .cloned()
+        .collect();
+
+    for command in commands {
+        let status = match command {
+            SceneExportCommand::Export { path } => {
+                let path = resolve_path(world.resource::<SceneExportSettings>(), &path);
+                export_level(world, &path).map(|entities| SceneExportStatus::Exported {
+                    path: path.display().to_string(),
+                    entities,
+                })
+            }
+            SceneExportCommand::Import { path, replace } => {
+                let path = resolve_path(world.resource::<SceneExportSettings>(), &path);
+                import_level(world, &path, replace).map(|entities| SceneExportStatus::Imported {
+                    path: path.display().to_string(),
+                    entities,
+                })
+            }
+        };
+
+        let status = status.unwrap_or_else(|error| {
+            warn!("{}", error);
+            SceneExportStatus::Failed { error }
+        });
+        world.resource_mut::<SceneExportStatusQueue>().send(status);
+    }
+}
+
+fn resolve_path(settings: &SceneExportSettings, path: &str) -> PathBuf {
+    let path = PathBuf::from(path);
+    if path.is_relative() {
+        PathBuf::from(&settings.directory).join(path)
+    } else {
+        path
+    }
+}
+
+fn collect<F: QueryFilter>(world: &mut World, into: &mut Vec<Entity>) {
+    let mut query = world.query_filtered::<Entity, (F, Without<Player>)>();
+    into.extend(query.iter(world));
+}
+
+/// Level content roots and their descendants, each entity once
+fn level_entities(world: &mut World) -> Vec<Entity> {
+    let mut roots = Vec::new();
+    collect::<Or<(With<LevelSceneMember>, With<Interactable>, With<AiController>)>>(world, &mut roots);
+    #[cfg(feature = "puzzle")]
+    {
+        use crate::puzzle::*;
+        collect::<Or<(
+            With<PuzzleSystem>,
+            With<PuzzleButton>,
+            With<PuzzleLever>,
+            With<PuzzlePressurePlate>,
+            With<PuzzleLock>,
+            With<PuzzleSequence>,
+            With<PuzzlePiano>,
+            With<PuzzleObjectPlacement>,
+        )>>(world, &mut roots);
+    }
+    #[cfg(feature = "vehicles")]
+    collect::<With<crate::vehicles::Vehicle>>(world, &mut roots);
+
+    let mut seen = EntityHashSet::default();
+    let mut entities = Vec::new();
+    while let Some(entity) = roots.pop() {
+        if !seen.insert(entity) {
+            continue;
+        }
+        entities.push(entity);
+        if let Some(children) = world.get::<Children>(entity) {
+            roots.extend(children.iter());
+        }
+    }
+    entities
+}
+
+fn component_filter(world: &World) -> SceneFilter {
+    let settings = world.resource::<SceneExportSettings>();
+    let registry = world.resource::<AppTypeRegistry>().read();
+
+    let mut filter = SceneFilter::deny_all()
+        .allow::<Transform>()
+        .allow::<Visibility>()
+        .allow::<Name>()
+        .allow::<ChildOf>();
+    for registration in registry.iter() {
+        let path = registration.type_info().type_path();
+        if registration.data::<ReflectComponent>().is_some()
+            && path.starts_with(CRATE_TYPE_PATH)
+            && !settings.skipped_components.iter().any(|skipped| path.ends_with(skipped.as_str()))
+        {
+            filter = filter.allow_by_id(registration.type_id());
+        }
+    }
+    filter
+}
+
+fn export_level(world: &mut World, path: &PathBuf) -> Result<usize, String> {
+    let entities = level_entities(world);
+    let exported: EntityHashSet = entities.iter().copied().collect();
+
+    let mut scene = DynamicSceneBuilder::from_world(world)
+        .with_component_filter(component_filter(world))
+        .deny_all_resources()
+        .extract_entities(entities.into_iter())
+        .build();
+
+    // Roots keep no link to parents outside the level
+    for entity in scene.entities.iter_mut() {
+        let parent_exported = world
+            .get::<ChildOf>(entity.entity)
+            .is_some_and(|child_of| exported.contains(&child_of.parent()));
+        if !parent_exported {
+            entity.components.retain(|component| {
+                component
+                    .get_represented_type_info()
+                    .is_none_or(|info| info.type_id() != TypeId::of::<ChildOf>())
+            });
+        }
+    }
+
+    let text = {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        scene
+            .serialize(&registry)
+            .map_err(|e| format!("Failed to serialize level scene: {}", e))?
+    };
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create level directory: {}", e))?;
+    }
+    std::fs::write(path, text)
+        .map_err(|e| format!("Failed to write level scene {}: {}", path.display(), e))?;
+    Ok(scene.entities.len())
+}
+
+fn import_level(world: &mut World, path: &PathBuf, replace: bool) -> Result<usize, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read level scene {}: {}", path.display(), e))?;
+
+    let scene: DynamicScene = {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let mut deserializer = ron::de::Deserializer::from_str(&text)
+            .map_err(|e| format!("Invalid level scene {}: {}", path.display(), e))?;
+        SceneDeserializer { type_registry: &registry }
+            .deserialize(&mut deserializer)
+            .map_err(|e| format!("Invalid level scene {}: {}", path.display(), e))?
+    };
+
+    if replace {
+        for entity in level_entities(world) {
+            if let Ok(entity) = world.get_entity_mut(entity) {
+                entity.despawn();
+            }
+        }
+    }
+
+    let mut entity_map = EntityHashMap::default();
+    scene
+        .write_to_world(world, &mut entity_map)
+        .map_err(|e| format!("Failed to spawn level scene {}: {}", path.display(), e))?;
+
+    // Imported roots are exported again with the level
+    for entity in entity_map.values() {
+        if world.get::<ChildOf>(*entity).is_none() {
+            world.entity_mut(*entity).insert(LevelSceneMember);
+        }
+    }
+    Ok(entity_map.len())
+}
@@ -0,0 +1,575 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use crate::ai::AiController;
+use crate::character::Player;
+use crate::game_manager::types::PlayerManager;
+use crate::loading_screen::LoadingScreenState;
+use crate::utils::EventQueue;
+
+// ============================================================================
+// COMPONENTS
+// ============================================================================
+
+/// Load state of a streaming chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum ChunkLoadState {
+    #[default]
+    Unloaded,
+    /// Scene asset is loading in the background
+    Loading,
+    /// Scene asset is loaded and being instantiated
+    Spawning,
+    Loaded,
+}
+
+/// A streamable sub-scene
+/// The entity transform is the chunk centre; content is spawned as a child root
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct StreamingChunk {
+    /// Unique chunk id, referenced by trigger volumes and dependents
+    pub id: String,
+
+    /// Scene asset path (e.g. "levels/town.glb#Scene0"); empty for chunks authored in place
+    pub scene_path: String,
+
+    /// Load when a streaming source comes closer than this
+    pub load_radius: f32,
+
+    /// Unload when every streaming source is farther than this
+    pub unload_radius: f32,
+
+    /// Only load/unload through trigger volumes
+    pub trigger_only: bool,
+
+    /// Never unload once loaded
+    pub always_loaded: bool,
+
+    /// Seconds to wait for the scene instance before declaring the chunk ready
+    pub spawn_timeout: f32,
+
+    /// Show gizmo for load/unload radii
+    pub show_gizmo: bool,
+
+    // Debug State
+    pub state: ChunkLoadState,
+    pub requested_by_trigger: bool,
+    pub content_root: Option<Entity>,
+    #[reflect(ignore)]
+    pub scene_handle: Option<Handle<Scene>>,
+    pub state_timer: f32,
+}
+
+impl Default for StreamingChunk {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            scene_path: String::new(),
+            load_radius: 80.0,
+            unload_radius: 100.0,
+            trigger_only: false,
+            always_loaded: false,
+            spawn_timeout: 2.0,
+            show_gizmo: false,
+            state: ChunkLoadState::Unloaded,
+            requested_by_trigger: false,
+            content_root: None,
+            scene_handle: None,
+            state_timer: 0.0,
+        }
+    }
+}
+
+impl StreamingChunk {
+    pub fn is_ready(&self) -> bool {
+        self.state == ChunkLoadState::Loaded
+    }
+}
+
+/// Box volume that loads or unloads chunks while a streaming source is inside
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct StreamingTriggerVolume {
+    pub half_extents: Vec3,
+    pub load_chunks: Vec<String>,
+    pub unload_chunks: Vec<String>,
+}
+
+impl Default for StreamingTriggerVolume {
+    fn default() -> Self {
+        Self {
+            half_extents: Vec3::splat(5.0),
+            load_chunks: Vec::new(),
+            unload_chunks: Vec::new(),
+        }
+    }
+}
+
+/// Extra entity (besides the current player) that keeps chunks loaded around it
+#[derive(Component, Debug, Reflect, Default)]
+#[reflect(Component)]
+pub struct StreamingSource;
+
+/// Marks content owned by a chunk; despawned when the chunk unloads
+#[derive(Component, Debug, Clone, Reflect, Default)]
+#[reflect(Component)]
+pub struct StreamingChunkMember {
+    pub chunk_id: String,
+}
+
+/// Entity that survives chunk unloads (player, companions, vehicles)
+/// Its membership is handed off to whichever chunk it currently stands in
+#[derive(Component, Debug, Reflect, Default)]
+#[reflect(Component)]
+pub struct PersistentEntity {
+    pub current_chunk: Option<String>,
+}
+
+/// Spawners and AI that should only run while a chunk is loaded
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ChunkDependent {
+    pub chunk_id: String,
+    /// Pause the AI controller while the chunk is not ready
+    pub pause_ai: bool,
+
+    // Debug State
+    pub active: bool,
+}
+
+impl Default for ChunkDependent {
+    fn default() -> Self {
+        Self {
+            chunk_id: String::new(),
+            pause_ai: true,
+            active: false,
+        }
+    }
+}
+
+// ============================================================================
+// RESOURCES
+// ============================================================================
+
+/// Level streaming settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct LevelStreamingSettings {
+    pub enabled: bool,
+    /// Maximum chunks loading at the same time
+    pub max_concurrent_loads: usize,
+    /// Seconds between distance evaluations
+    pub update_interval: f32,
+    pub update_timer: f32,
+}
+
+impl Default for LevelStreamingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_concurrent_loads: 2,
+            update_interval: 0.5,
+            update_timer: 0.0,
+        }
+    }
+}
+
+/// Ids of the chunks currently ready
+#[derive(Resource, Default, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct LevelStreamingState {
+    pub ready_chunks: Vec<String>,
+}
+
+impl LevelStreamingState {
+    pub fn is_chunk_ready(&self, chunk_id: &str) -> bool {
+        self.ready_chunks.iter().any(|id| id == chunk_id)
+    }
+}
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChunkStreamingEventKind {
+    LoadStarted,
+    Ready,
+    Unloaded,
+    /// A persistent entity moved into this chunk
+    EntityHandedOff { entity: Entity, from: Option<String> },
+}
+
+/// Chunk streaming event
+#[derive(Debug, Clone)]
+pub struct ChunkStreamingEvent {
+    pub chunk_id: String,
+    pub kind: ChunkStreamingEventKind,
+}
+
+pub type ChunkStreamingEventQueue = EventQueue<ChunkStreamingEvent>;
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+type StreamingSourceCandidate = Or<(With<StreamingSource>, With<Player>)>;
+
+/// Entities chunks stream around: streaming sources and the current player
+#[derive(SystemParam)]
+pub struct StreamingSources<'w, 's> {
+    pub player_manager: Res<'w, PlayerManager>,
+    pub source_query: Query<'w, 's, (Entity, &'static GlobalTransform), StreamingSourceCandidate>,
+    pub has_source: Query<'w, 's, (), With<StreamingSource>>,
+}
+
+impl StreamingSources<'_, '_> {
+    fn positions(&self) -> Vec<Vec3> {
+        let current_player = self.player_manager.get_current_player();
+        self.source_query
+            .iter()
+            .filter(|(entity, _)| Some(*entity) == current_player || self.has_source.contains(*entity))
+            .map(|(_, transform)| transform.translation())
+            .collect()
+    }
+}
+
+/// Streaming settings, progress and the events it reports
+#[derive(SystemParam)]
+pub struct StreamingStatus<'w> {
+    pub settings: ResMut<'w, LevelStreamingSettings>,
+    pub state: ResMut<'w, LevelStreamingState>,
+    pub event_queue: ResMut<'w, ChunkStreamingEventQueue>,
+}
+
+/// Loads chunk scenes, holding the loading screen for them while it is up
+#[derive(SystemParam)]
+pub struct ChunkSceneLoader<'w> {
+    pub asset_server: Res<'w, AssetServer>,
+    pub loading_screen: ResMut<'w, LoadingScreenState>,
+}
+
+/// System to request loads/unloads from trigger volumes
+pub fn update_streaming_triggers(
+    sources: StreamingSources,
+    trigger_query: Query<(&GlobalTransform, &StreamingTriggerVolume)>,
+    mut chunk_query: Query<&mut StreamingChunk>,
+) {
+    let sources = sources.positions();
+
+    for (trigger_transform, trigger) in trigger_query.iter() {
+        let inverse = trigger_transform.affine().inverse();
+        let occupied = sources.iter().any(|position| {
+            inverse.transform_point3(*position).abs().cmple(trigger.half_extents).all()
+        });
+        if !occupied {
+            continue;
+        }
+
+        for mut chunk in chunk_query.iter_mut() {
+            if trigger.load_chunks.contains(&chunk.id) && !chunk.requested_by_trigger {
+                chunk.requested_by_trigger = true;
+            } else if trigger.unload_chunks.contains(&chunk.id) && chunk.requested_by_trigger {
+                chunk.requested_by_trigger = false;
+            }
+        }
+    }
+}
+
+/// System to start loading near chunks and unload far ones
+pub fn update_chunk_streaming(
+    mut commands: Commands,
+    time: Res<Time>,
+    status: StreamingStatus,
+    sources: StreamingSources,
+    mut chunk_query: Query<(&GlobalTransform, &mut StreamingChunk)>,
+    member_query: Query<(Entity, &StreamingChunkMember), Without<PersistentEntity>>,
+    loader: ChunkSceneLoader,
+) {
+    let StreamingStatus { mut settings, mut state, mut event_queue } = status;
+    let ChunkSceneLoader { asset_server, mut loading_screen } = loader;
+    if !settings.enabled {
+        return;
+    }
+
+    settings.update_timer -= time.delta_secs();
+    if settings.update_timer > 0.0 {
+        return;
+    }
+    settings.update_timer = settings.update_interval;
+
+    let sources = sources.positions();
+    let mut loading = chunk_query
+        .iter()
+        .filter(|(_, chunk)| matches!(chunk.state, ChunkLoadState::Loading | ChunkLoadState::Spawning))
+        .count();
+
+    for (transform, mut chunk) in chunk_query.iter_mut() {
+        let center = transform.translation();
+        let distance = sources
+            .iter()
+            .map(|position| position.distance(center))
+            .fold(f32::MAX, f32::min);
+
+        let wants_loaded = chunk.requested_by_trigger
+            || (!chunk.trigger_only && distance <= chunk.load_radius);
+        let wants_unloaded = !chunk.always_loaded
+            && !chunk.requested_by_trigger
+            && (chunk.trigger_only || distance > chunk.unload_radius);
+
+        match chunk.state {
+            ChunkLoadState::Unloaded if wants_loaded => {
+                if loading >= settings.max_concurrent_loads {
+                    continue;
+                }
+                loading += 1;
+
+                if !chunk.scene_path.is_empty() {
+                    let handle: Handle<Scene> = asset_server.load(chunk.scene_path.clone());
+                    // Chunks requested while a loading screen is up hold it until they arrive
+                    if loading_screen.is_active() {
+                        loading_screen.track_asset(handle.clone());
+                    }
+                    chunk.scene_handle = Some(handle);
+                }
+                chunk.state = ChunkLoadState::Loading;
+                chunk.state_timer = 0.0;
+                info!("Streaming: loading chunk {}", chunk.id);
+
+                event_queue.send(ChunkStreamingEvent {
+                    chunk_id: chunk.id.clone(),
+                    kind: ChunkStreamingEventKind::LoadStarted,
+                });
+            }
+            ChunkLoadState::Loaded | ChunkLoadState::Loading | ChunkLoadState::Spawning if wants_unloaded => {
+                if let Some(root) = chunk.content_root.take() {
+                    commands.entity(root).despawn();
+                }
+                for (entity, member) in member_query.iter() {
+                    if member.chunk_id == chunk.id {
+                        commands.entity(entity).despawn();
+                    }
+                }
+
+                chunk.scene_handle = None;
+                chunk.state = ChunkLoadState::Unloaded;
+                state.ready_chunks.retain(|id| *id != chunk.id);
+                info!("Streaming: unloaded chunk {}", chunk.id);
+
+                event_queue.send(ChunkStreamingEvent {
+                    chunk_id: chunk.id.clone(),
+                    kind: ChunkStreamingEventKind::Unloaded,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// System to poll async loads, instantiate scenes and report readiness
+pub fn finish_chunk_loading(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut state: ResMut<LevelStreamingState>,
+    mut event_queue: ResMut<ChunkStreamingEventQueue>,
+    mut chunk_query: Query<(Entity, &mut StreamingChunk)>,
+    children_query: Query<&Children>,
+) {
+    for (chunk_entity, mut chunk) in chunk_query.iter_mut() {
+        chunk.state_timer += time.delta_secs();
+
+        match chunk.state {
+            ChunkLoadState::Loading => {
+                let handle = chunk.scene_handle.clone();
+                if let Some(handle) = handle.as_ref() {
+                    if !asset_server.is_loaded_with_dependencies(handle) {
+                        continue;
+                    }
+                }
+
+                let mut root = commands.spawn((
+                    Transform::default(),
+                    Visibility::default(),
+                    StreamingChunkMember { chunk_id: chunk.id.clone() },
+                    Name::new(format!("Chunk Content {}", chunk.id)),
+                ));
+                if let Some(handle) = handle {
+                    root.insert(SceneRoot(handle));
+                }
+                let root = root.id();
+                commands.entity(chunk_entity).add_child(root);
+
+                chunk.content_root = Some(root);
+                chunk.state = ChunkLoadState::Spawning;
+                chunk.state_timer = 0.0;
+            }
+            ChunkLoadState::Spawning => {
+                // The scene is instantiated once the content root has children
+                let spawned = chunk.scene_handle.is_none()
+                    || chunk
+                        .content_root
+                        .is_some_and(|root| children_query.get(root).is_ok_and(|children| !children.is_empty()));
+                if !spawned && chunk.state_timer < chunk.spawn_timeout {
+                    continue;
+                }
+
+                chunk.state = ChunkLoadState::Loaded;
+                if !state.is_chunk_ready(&chunk.id) {
+                    state.ready_chunks.push(chunk.id.clone());
+                }
+                info!("Streaming: chunk {} ready", chunk.id);
+
+                event_queue.send(ChunkStreamingEvent {
+                    chunk_id: chunk.id.clone(),
+                    kind: ChunkStreamingEventKind::Ready,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// System to hand persistent entities over to the chunk they stand in
+pub fn handoff_persistent_entities(
+    mut event_queue: ResMut<ChunkStreamingEventQueue>,
+    mut persistent_query: Query<(Entity, &GlobalTransform, &mut PersistentEntity)>,
+    chunk_query: Query<(&GlobalTransform, &StreamingChunk)>,
+) {
+    for (entity, transform, mut persistent) in persistent_query.iter_mut() {
+        let position = transform.translation();
+
+        // Closest loaded chunk whose load radius contains the entity
+        let current = chunk_query
+            .iter()
+            .filter(|(_, chunk)| chunk.is_ready())
+            .map(|(chunk_transform, chunk)| (chunk_transform.translation().distance(position), chunk))
+            .filter(|(distance, chunk)| *distance <= chunk.load_radius)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, chunk)| chunk.id.clone());
+
+        if current.is_none() || current == persistent.current_chunk {
+            continue;
+        }
+
+        let from = persistent.current_chunk.take();
+        persistent.current_chunk = current.clone();
+
+        if let Some(chunk_id) = current {
+            event_queue.send(ChunkStreamingEvent {
+                chunk_id,
+                kind: ChunkStreamingEventKind::EntityHandedOff { entity, from },
+            });
+        }
+    }
+}
+
+/// System to activate spawners and AI only while their chunk is ready
+pub fn update_chunk_dependents(
+    state: Res<LevelStreamingState>,
+    mut dependent_query: Query<(&mut ChunkDependent, Option<&mut AiController>)>,
+) {
+    for (mut dependent, ai) in dependent_query.iter_mut() {
+        let ready = state.is_chunk_ready(&dependent.chunk_id);
+        if dependent.active == ready {
+            continue;
+        }
+        dependent.active = ready;
+
+        if dependent.pause_ai {
+            if let Some(mut ai) = ai {
+                ai.is_paused = !ready;
+            }
+        }
+    }
+}
+
+/// System to draw chunk radii
+pub fn draw_streaming_chunks(
+    mut gizmos: Gizmos,
+    chunk_query: Query<(&GlobalTransform, &StreamingChunk)>,
+) {
+    for (transform, chunk) in chunk_query.iter() {
+        if !chunk.show_gizmo {
+            continue;
+        }
+
+        let color = match chunk.state {
+            ChunkLoadState::Unloaded => Color::srgb(0.5, 0.5, 0.5),
+            ChunkLoadState::Loading | ChunkLoadState::Spawning => Color::srgb(1.0, 0.8, 0.0),
+            ChunkLoadState::Loaded => Color::srgb(0.0, 1.0, 0.0),
+        };
+        let isometry = Isometry3d::from_translation(transform.translation());
+        gizmos.sphere(isometry, chunk.load_radius, color);
+        gizmos.sphere(isometry, chunk.unload_radius, color.with_alpha(0.3));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::EventQueuePlugin;
+
+    #[test]
+    fn test_chunks_stream_around_source_and_hand_off() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_plugins(EventQueuePlugin::<ChunkStreamingEvent>::default())
+            .insert_resource(LevelStreamingSettings { update_interval: 0.0, ..default() })
+            .init_resource::<LevelStreamingState>()
+            .init_resource::<PlayerManager>()
+            .init_resource::<LoadingScreenState>()
+            .add_systems(Update, (
+                update_streaming_triggers,
+                update_chunk_streaming,
+                finish_chunk_loading,
+                handoff_persistent_entities,
+                update_chunk_dependents,
+            ).chain());
+
+        let town = app.world_mut().spawn((
+            GlobalTransform::IDENTITY,
+            StreamingChunk { id: "town".into(), ..default() },
+        )).id();
+        let farm = app.world_mut().spawn((
+            GlobalTransform::from_xyz(500.0, 0.0, 0.0),
+            StreamingChunk { id: "farm".into(), ..default() },
+        )).id();
+        let prop = app.world_mut().spawn(StreamingChunkMember { chunk_id: "town".into() }).id();
+        let guard = app.world_mut().spawn((
+            ChunkDependent { chunk_id: "town".into(), ..default() },
+            AiController { is_paused: true, ..default() },
+        )).id();
+        let source = app.world_mut().spawn((
+            GlobalTransform::IDENTITY,
+            StreamingSource,
+            PersistentEntity::default(),
+        )).id();
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        assert!(app.world().get::<StreamingChunk>(town).unwrap().is_ready());
+        assert_eq!(app.world().get::<StreamingChunk>(farm).unwrap().state, ChunkLoadState::Unloaded);
+        assert!(app.world().resource::<LevelStreamingState>().is_chunk_ready("town"));
+        assert_eq!(app.world().get::<PersistentEntity>(source).unwrap().current_chunk.as_deref(), Some("town"));
+        assert!(!app.world().get::<AiController>(guard).unwrap().is_paused);
+
+        // Walking to the farm unloads the town and its content, the source follows
+        app.world_mut().entity_mut(source).insert(GlobalTransform::from_xyz(500.0, 0.0, 0.0));
+        for _ in 0..3 {
+            app.update();
+        }
+
+        assert_eq!(app.world().get::<StreamingChunk>(town).unwrap().state, ChunkLoadState::Unloaded);
+        assert!(app.world().get_entity(prop).is_err());
+        assert!(app.world().get::<AiController>(guard).unwrap().is_paused);
+        assert!(app.world().get::<StreamingChunk>(farm).unwrap().is_ready());
+        assert!(app.world().get_entity(source).is_ok());
+        assert!(app.world().resource::<ChunkStreamingEventQueue>().iter().any(|event| {
+            event.chunk_id == "farm"
+                && event.kind == ChunkStreamingEventKind::EntityHandedOff { entity: source, from: Some("town".into()) }
+        }));
+    }
+}
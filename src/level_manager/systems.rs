@@ -1,30 +1,43 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use crate::level_manager::types::*;
 use crate::game_manager::types::PlayerManager;
+use crate::loading_screen::{LoadingReason, LoadingScreenState};
+use crate::utils::QueueReader;
 
 // ============================================================================
 // SYSTEMS
 // ============================================================================
 
+/// Pending and current level, and the loading screen shown between them
+#[derive(SystemParam)]
+pub struct LevelChangeState<'w> {
+    pub pending_change: ResMut<'w, PendingLevelChange>,
+    pub current_level: ResMut<'w, CurrentLevelInfo>,
+    pub loading_screen: ResMut<'w, LoadingScreenState>,
+}
+
 /// System to handle level change requests
 pub fn handle_level_change(
-    mut commands: Commands,
-    mut request_queue: ResMut<RequestLevelChangeEventQueue>, // Changed to Queue
-    mut pending_change: ResMut<PendingLevelChange>,
+    mut request_queue: QueueReader<RequestLevelChangeEvent>,
+    level_state: LevelChangeState,
     time: Res<Time>,
-    mut current_level: ResMut<CurrentLevelInfo>,
     level_managers: Query<(&LevelManager, &Transform)>,
     player_manager: Res<PlayerManager>,
     mut transform_query: Query<&mut Transform>,
 ) {
+    let LevelChangeState { mut pending_change, mut current_level, mut loading_screen } = level_state;
     // Process new requests (Drain queue)
-    for event in request_queue.0.drain(..) {
+    for event in request_queue.read().cloned() {
         info!("Level change requested: Scene {} ID {}", event.target_scene, event.target_level_manager_id);
         
         pending_change.active = true;
         pending_change.target_scene = event.target_scene;
         pending_change.target_id = event.target_level_manager_id;
         pending_change.timer = event.delay;
+
+        loading_screen.begin(LoadingReason::LevelChange);
+        loading_screen.add_task("level_change", 1.0);
     }
 
     // Process pending change
@@ -66,6 +79,7 @@ pub fn handle_level_change(
             }
 
             pending_change.active = false;
+            loading_screen.complete_task("level_change");
         }
     }
 }
@@ -138,7 +152,7 @@ pub fn handle_travel_station_discovery(
             global_state.discovered_stations.push(destination.clone());
             
             // Queue event
-            event_queue.0.push(TravelStationDiscoveredEvent {
+            event_queue.send(TravelStationDiscoveredEvent {
                 station_name: station.station_name.clone(),
                 scene: station.current_scene_number,
                 id: station.current_level_manager_id,
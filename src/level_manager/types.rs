@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::EventQueue;
 
 // ============================================================================
 // COMPONENTS
@@ -123,8 +124,7 @@ pub struct RequestLevelChangeEvent {
     pub delay: f32,
 }
 
-#[derive(Resource, Default)]
-pub struct RequestLevelChangeEventQueue(pub Vec<RequestLevelChangeEvent>);
+pub type RequestLevelChangeEventQueue = EventQueue<RequestLevelChangeEvent>;
 
 /// Event when a station is discovered
 #[derive(Event, Debug, Clone)]
@@ -134,5 +134,4 @@ pub struct TravelStationDiscoveredEvent {
     pub id: i32,
 }
 
-#[derive(Resource, Default)]
-pub struct TravelStationDiscoveredEventQueue(pub Vec<TravelStationDiscoveredEvent>);
+pub type TravelStationDiscoveredEventQueue = EventQueue<TravelStationDiscoveredEvent>;
@@ -2,6 +2,8 @@ use bevy::prelude::*;
 use bevy::ui::{Node, Val, UiRect, Display, FlexDirection, AlignItems, JustifyContent};
 // use bevy::hierarchy::DespawnRecursiveExt; // Disabled
 use crate::level_manager::types::*;
+use crate::input::{FocusCancel, FocusScope, UiNavigationEvent};
+use crate::utils::QueueReader;
 
 // ============================================================================
 // UI COMPONENTS
@@ -40,6 +42,7 @@ pub fn setup_travel_ui(mut commands: Commands) {
             BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
             GlobalZIndex(100),
             TravelStationMenu,
+            FocusScope { priority: 0, cancel: FocusCancel::None },
         ))
         .with_children(|parent| {
             // Header
@@ -171,7 +174,7 @@ pub fn handle_travel_button_interactions(
             info!("Travel Button Pressed: Target ID {}", button.target_id);
             
             // Send request
-            request_queue.0.push(RequestLevelChangeEvent {
+            request_queue.send(RequestLevelChangeEvent {
                 target_scene: button.target_scene,
                 target_level_manager_id: button.target_id,
                 delay: 1.0, 
@@ -186,3 +189,21 @@ pub fn handle_travel_button_interactions(
         }
     }
 }
+
+/// Leave the station when the menu is cancelled from the gamepad
+pub fn close_travel_ui_on_cancel(
+    mut navigation_events: QueueReader<UiNavigationEvent>,
+    menu_query: Query<(), With<TravelStationMenu>>,
+    mut stations: Query<&mut TravelStation>,
+) {
+    for event in navigation_events.read() {
+        let UiNavigationEvent::Cancelled { scope } = event else { continue };
+        if menu_query.contains(*scope) {
+            for mut station in stations.iter_mut() {
+                if station.using_station {
+                    station.using_station = false;
+                }
+            }
+        }
+    }
+}
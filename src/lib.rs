@@ -22,15 +22,31 @@
 //! fn main() {
 //!     App::new()
 //!         .add_plugins(DefaultPlugins)
-//!         .add_plugins(GameControllerPlugin)
+//!         .add_plugins(GameControllerPlugin::default())
 //!         .run();
 //! }
 //! ```
 
+use bevy::ecs::schedule::ScheduleLabel;
 use bevy::prelude::*;
+use simulation::SimulationAppExt;
 
 pub mod abilities;
 pub mod actions;
+pub mod audio;
+#[cfg(feature = "appearance")]
+pub mod appearance;
+#[cfg(feature = "blueprints")]
+pub mod blueprints;
+#[cfg(feature = "boss")]
+pub mod boss;
+#[cfg(feature = "building")]
+pub mod building;
+pub mod bundles;
+#[cfg(feature = "debug_inspector")]
+pub mod debug_inspector;
+#[cfg(feature = "debug_ui")]
+pub mod debug_ui;
 pub mod events;
 pub mod experience;
 pub mod footsteps;
@@ -39,33 +55,76 @@ pub mod game_manager;
 pub mod ai;
 pub mod camera;
 pub mod character;
+#[cfg(feature = "climb")]
 pub mod climb;
 pub mod combat;
+pub mod config;
 pub mod currency;
 pub mod devices;
+#[cfg(feature = "dialog")]
 pub mod dialog;
+#[cfg(feature = "emotes")]
+pub mod emotes;
+#[cfg(feature = "fishing")]
+pub mod fishing;
+#[cfg(feature = "gore")]
+pub mod gore;
 pub mod input;
 pub mod interaction;
 pub mod inventory;
+#[cfg(feature = "kill_cam")]
+pub mod kill_cam;
+#[cfg(feature = "ladder")]
 pub mod ladder;
+#[cfg(feature = "map")]
 pub mod map;
+#[cfg(feature = "nameplates")]
+pub mod nameplates;
+#[cfg(feature = "networking")]
+pub mod networking;
 pub mod pickups;
 pub mod others;
 pub mod physics;
 pub mod player;
+#[cfg(feature = "puzzle")]
 pub mod puzzle;
+#[cfg(feature = "quest")]
 pub mod quest;
+#[cfg(feature = "replay")]
+pub mod replay;
+#[cfg(feature = "respawn")]
+pub mod respawn;
 pub mod save;
+#[cfg(feature = "skills")]
 pub mod skills;
 pub mod stats;
+#[cfg(feature = "stealth")]
 pub mod stealth;
+#[cfg(feature = "survival")]
+pub mod survival;
+pub mod tags;
+#[cfg(feature = "tutorial")]
 pub mod tutorial;
 pub mod utils;
+#[cfg(feature = "vehicles")]
 pub mod vehicles;
+#[cfg(feature = "vendor")]
 pub mod vendor;
 pub mod weapons;
+#[cfg(feature = "world_bounds")]
+pub mod world_bounds;
+#[cfg(feature = "zipline")]
+pub mod zipline;
 pub mod head_track;
+pub mod headless;
+pub mod simulation;
+pub mod highlight;
+pub mod hud;
+pub mod indicators;
+pub mod vfx;
 pub mod level_manager;
+pub mod loading_screen;
+#[cfg(feature = "point_and_click")]
 pub mod point_and_click;
 
 pub mod prelude {
@@ -73,6 +132,19 @@ pub mod prelude {
 
     pub use crate::abilities::*;
     pub use crate::actions::*;
+    #[cfg(feature = "appearance")]
+    pub use crate::appearance::*;
+    #[cfg(feature = "blueprints")]
+    pub use crate::blueprints::*;
+    #[cfg(feature = "boss")]
+    pub use crate::boss::*;
+    #[cfg(feature = "building")]
+    pub use crate::building::*;
+    pub use crate::bundles::*;
+    #[cfg(feature = "debug_inspector")]
+    pub use crate::debug_inspector::*;
+    #[cfg(feature = "debug_ui")]
+    pub use crate::debug_ui::*;
     pub use crate::events::*;
     pub use crate::experience::*;
     pub use crate::footsteps::*;
@@ -81,42 +153,360 @@ pub mod prelude {
     pub use crate::ai::*;
     pub use crate::camera::*;
     pub use crate::character::*;
+    #[cfg(feature = "climb")]
     pub use crate::climb::*;
     pub use crate::combat::*;
+    pub use crate::config::*;
     pub use crate::currency::*;
     pub use crate::devices;
+    #[cfg(feature = "dialog")]
     pub use crate::dialog::*;
+    #[cfg(feature = "emotes")]
+    pub use crate::emotes::*;
+    #[cfg(feature = "fishing")]
+    pub use crate::fishing::*;
+    #[cfg(feature = "gore")]
+    pub use crate::gore::*;
     pub use crate::input::*;
     pub use crate::interaction;
     pub use crate::inventory::*;
+    #[cfg(feature = "kill_cam")]
+    pub use crate::kill_cam::*;
+    #[cfg(feature = "ladder")]
     pub use crate::ladder::*;
+    #[cfg(feature = "map")]
     pub use crate::map::*;
+    #[cfg(feature = "nameplates")]
+    pub use crate::nameplates::*;
+    #[cfg(feature = "networking")]
+    pub use crate::networking::*;
     pub use crate::pickups::*;
     pub use crate::others::*;
+    #[cfg(feature = "point_and_click")]
     pub use crate::point_and_click::*;
     pub use crate::physics::*;
     pub use crate::player::*;
+    #[cfg(feature = "puzzle")]
     pub use crate::puzzle::*;
+    #[cfg(feature = "quest")]
     pub use crate::quest::*;
+    #[cfg(feature = "replay")]
+    pub use crate::replay::*;
+    #[cfg(feature = "respawn")]
+    pub use crate::respawn::*;
     pub use crate::save::*;
+    #[cfg(feature = "skills")]
     pub use crate::skills::*;
     pub use crate::stats::*;
+    #[cfg(feature = "stealth")]
     pub use crate::stealth::*;
+    #[cfg(feature = "survival")]
+    pub use crate::survival::*;
+    pub use crate::tags::*;
+    #[cfg(feature = "tutorial")]
     pub use crate::tutorial::*;
     pub use crate::utils::*;
+    #[cfg(feature = "vehicles")]
     pub use crate::vehicles::*;
+    #[cfg(feature = "vendor")]
     pub use crate::vendor::*;
     pub use crate::weapons::*;
+    #[cfg(feature = "world_bounds")]
+    pub use crate::world_bounds::*;
+    #[cfg(feature = "zipline")]
+    pub use crate::zipline::*;
     pub use crate::head_track::*;
-    pub use crate::GameControllerPlugin;
+    pub use crate::headless::*;
+    pub use crate::simulation::*;
+    pub use crate::highlight::*;
+    pub use crate::hud::*;
+    pub use crate::indicators::*;
+    pub use crate::vfx::*;
+    pub use crate::loading_screen::*;
+    pub use crate::audio::*;
+    pub use crate::{GameControllerPlugin, GameControllerSet, Subsystem};
     pub use bevy::prelude::*;
 }
 
-/// The main plugin for the game controller systems
-pub struct GameControllerPlugin;
+/// Frame phases of the crate's `Update` systems, run in declaration order.
+///
+/// Each module nests its own sets inside one phase (`InputSet`, `AiSet`,
+/// `CharacterSet`, `InteractionSet`, `WeaponsSet`, `CombatSet`, `CameraSet`,
+/// `SaveSet`), so downstream systems
+/// can be ordered against a whole phase or a single step:
+///
+/// ```rust,ignore
+/// app.add_systems(Update, my_system.after(CombatSet::DamageResolution).before(GameControllerSet::Camera));
+/// ```
+///
+/// Modules without a set run unordered in `Update`. With
+/// [`GameControllerPlugin::fixed_simulation`], `Ai` through `Combat` run in
+/// `FixedUpdate` instead (see the `simulation` module).
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameControllerSet {
+    /// Device input mapped to actions and `InputState`
+    Input,
+    /// AI perception, decisions and navigation
+    Ai,
+    /// Character movement, rotation and animation
+    Movement,
+    /// Interactable detection, validation and prompts
+    Interaction,
+    /// Weapons, melee and damage resolution
+    Combat,
+    /// Camera rig, follow and effects
+    Camera,
+    /// Auto-save and save/load requests, once the frame's state is settled
+    Persistence,
+}
+
+/// Optional subsystem that `GameControllerPlugin` can leave out.
+///
+/// Every variant also has a cargo feature of the same (snake_case) name;
+/// disabling the feature removes the module from the build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subsystem {
+    Appearance,
+    Blueprints,
+    Boss,
+    Building,
+    Climb,
+    DebugInspector,
+    Dialog,
+    Emotes,
+    Fishing,
+    Gore,
+    KillCam,
+    Ladder,
+    Map,
+    Nameplates,
+    PointAndClick,
+    Puzzle,
+    Quest,
+    Replay,
+    Respawn,
+    Skills,
+    Stealth,
+    Survival,
+    Tutorial,
+    Vehicles,
+    Vendor,
+    WorldBounds,
+    Zipline,
+}
+
+impl Subsystem {
+    /// Subsystems that must stay enabled while this one is.
+    /// Mirrors the feature dependencies in `Cargo.toml`.
+    pub fn dependencies(&self) -> &'static [Subsystem] {
+        match self {
+            Subsystem::Climb => &[Subsystem::Ladder],
+            Subsystem::KillCam => &[Subsystem::Replay],
+            Subsystem::Quest => &[Subsystem::Map],
+            _ => &[],
+        }
+    }
+
+    /// Whether the subsystem's cargo feature is enabled
+    pub fn is_compiled(&self) -> bool {
+        match self {
+            Subsystem::Appearance => cfg!(feature = "appearance"),
+            Subsystem::Blueprints => cfg!(feature = "blueprints"),
+            Subsystem::Boss => cfg!(feature = "boss"),
+            Subsystem::Building => cfg!(feature = "building"),
+            Subsystem::Climb => cfg!(feature = "climb"),
+            Subsystem::DebugInspector => cfg!(feature = "debug_inspector"),
+            Subsystem::Dialog => cfg!(feature = "dialog"),
+            Subsystem::Emotes => cfg!(feature = "emotes"),
+            Subsystem::Fishing => cfg!(feature = "fishing"),
+            Subsystem::Gore => cfg!(feature = "gore"),
+            Subsystem::KillCam => cfg!(feature = "kill_cam"),
+            Subsystem::Ladder => cfg!(feature = "ladder"),
+            Subsystem::Map => cfg!(feature = "map"),
+            Subsystem::Nameplates => cfg!(feature = "nameplates"),
+            Subsystem::PointAndClick => cfg!(feature = "point_and_click"),
+            Subsystem::Puzzle => cfg!(feature = "puzzle"),
+            Subsystem::Quest => cfg!(feature = "quest"),
+            Subsystem::Replay => cfg!(feature = "replay"),
+            Subsystem::Respawn => cfg!(feature = "respawn"),
+            Subsystem::Skills => cfg!(feature = "skills"),
+            Subsystem::Stealth => cfg!(feature = "stealth"),
+            Subsystem::Survival => cfg!(feature = "survival"),
+            Subsystem::Tutorial => cfg!(feature = "tutorial"),
+            Subsystem::Vehicles => cfg!(feature = "vehicles"),
+            Subsystem::Vendor => cfg!(feature = "vendor"),
+            Subsystem::WorldBounds => cfg!(feature = "world_bounds"),
+            Subsystem::Zipline => cfg!(feature = "zipline"),
+        }
+    }
+
+    /// UI-only subsystems, skipped in headless mode
+    pub fn requires_rendering(&self) -> bool {
+        matches!(self, Subsystem::DebugInspector | Subsystem::KillCam | Subsystem::Nameplates)
+    }
+}
+
+/// The main plugin for the game controller systems.
+///
+/// Adds every compiled subsystem by default; leave some out with the builder:
+///
+/// ```rust,ignore
+/// app.add_plugins(GameControllerPlugin::default().without_vehicles().without_vendor());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GameControllerPlugin {
+    disabled: Vec<Subsystem>,
+    headless: bool,
+    config: Option<String>,
+    /// Fixed steps per second when the simulation runs in `FixedUpdate`
+    fixed_simulation: Option<f64>,
+}
+
+impl GameControllerPlugin {
+    pub fn without(mut self, subsystem: Subsystem) -> Self {
+        if !self.disabled.contains(&subsystem) {
+            self.disabled.push(subsystem);
+        }
+        self
+    }
+
+    pub fn without_appearance(self) -> Self { self.without(Subsystem::Appearance) }
+    pub fn without_blueprints(self) -> Self { self.without(Subsystem::Blueprints) }
+    pub fn without_boss(self) -> Self { self.without(Subsystem::Boss) }
+    pub fn without_building(self) -> Self { self.without(Subsystem::Building) }
+    pub fn without_climb(self) -> Self { self.without(Subsystem::Climb) }
+    pub fn without_debug_inspector(self) -> Self { self.without(Subsystem::DebugInspector) }
+    pub fn without_dialog(self) -> Self { self.without(Subsystem::Dialog) }
+    pub fn without_emotes(self) -> Self { self.without(Subsystem::Emotes) }
+    pub fn without_fishing(self) -> Self { self.without(Subsystem::Fishing) }
+    pub fn without_gore(self) -> Self { self.without(Subsystem::Gore) }
+    pub fn without_kill_cam(self) -> Self { self.without(Subsystem::KillCam) }
+    pub fn without_ladder(self) -> Self { self.without(Subsystem::Ladder) }
+    pub fn without_map(self) -> Self { self.without(Subsystem::Map) }
+    pub fn without_nameplates(self) -> Self { self.without(Subsystem::Nameplates) }
+    pub fn without_point_and_click(self) -> Self { self.without(Subsystem::PointAndClick) }
+    pub fn without_puzzle(self) -> Self { self.without(Subsystem::Puzzle) }
+    pub fn without_quest(self) -> Self { self.without(Subsystem::Quest) }
+    pub fn without_replay(self) -> Self { self.without(Subsystem::Replay) }
+    pub fn without_respawn(self) -> Self { self.without(Subsystem::Respawn) }
+    pub fn without_skills(self) -> Self { self.without(Subsystem::Skills) }
+    pub fn without_stealth(self) -> Self { self.without(Subsystem::Stealth) }
+    pub fn without_survival(self) -> Self { self.without(Subsystem::Survival) }
+    pub fn without_tutorial(self) -> Self { self.without(Subsystem::Tutorial) }
+    pub fn without_vehicles(self) -> Self { self.without(Subsystem::Vehicles) }
+    pub fn without_vendor(self) -> Self { self.without(Subsystem::Vendor) }
+    pub fn without_world_bounds(self) -> Self { self.without(Subsystem::WorldBounds) }
+    pub fn without_zipline(self) -> Self { self.without(Subsystem::Zipline) }
+
+    /// Runs without window, renderer or UI (see the `headless` module)
+    pub fn headless(mut self) -> Self {
+        self.headless = true;
+        self
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.headless
+    }
+
+    /// Runs the simulation phases in `FixedUpdate` at 64 Hz (see the `simulation` module)
+    pub fn fixed_simulation(self) -> Self {
+        self.fixed_simulation_hz(64.0)
+    }
+
+    /// Runs the simulation phases in `FixedUpdate` at `hz` steps per second
+    pub fn fixed_simulation_hz(mut self, hz: f64) -> Self {
+        self.fixed_simulation = Some(hz);
+        self
+    }
+
+    pub fn is_fixed_simulation(&self) -> bool {
+        self.fixed_simulation.is_some()
+    }
+
+    /// Loads a [`config::GameControllerConfig`] asset (e.g. `"game.controller.ron"`)
+    /// at startup and applies it
+    pub fn with_config(mut self, path: impl Into<String>) -> Self {
+        self.config = Some(path.into());
+        self
+    }
+
+    /// Compiled in and not disabled through the builder
+    pub fn is_enabled(&self, subsystem: Subsystem) -> bool {
+        subsystem.is_compiled()
+            && !self.disabled.contains(&subsystem)
+            && !(self.headless && subsystem.requires_rendering())
+    }
+
+    /// Checks that no enabled subsystem depends on a disabled one
+    pub fn validate(&self) -> Result<(), String> {
+        let mut missing = Vec::new();
+        for subsystem in self.disabled.iter() {
+            for dependent in self.enabled_dependents(*subsystem) {
+                missing.push(format!("{:?} requires {:?}", dependent, subsystem));
+            }
+        }
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Invalid GameControllerPlugin configuration: {}", missing.join(", ")))
+        }
+    }
+
+    fn enabled_dependents(&self, subsystem: Subsystem) -> Vec<Subsystem> {
+        ALL_SUBSYSTEMS
+            .iter()
+            .copied()
+            .filter(|dependent| self.is_enabled(*dependent) && dependent.dependencies().contains(&subsystem))
+            .collect()
+    }
+}
+
+const ALL_SUBSYSTEMS: [Subsystem; 27] = [
+    Subsystem::Appearance,
+    Subsystem::Blueprints,
+    Subsystem::Boss,
+    Subsystem::Building,
+    Subsystem::Climb,
+    Subsystem::DebugInspector,
+    Subsystem::Dialog,
+    Subsystem::Emotes,
+    Subsystem::Fishing,
+    Subsystem::Gore,
+    Subsystem::KillCam,
+    Subsystem::Ladder,
+    Subsystem::Map,
+    Subsystem::Nameplates,
+    Subsystem::PointAndClick,
+    Subsystem::Puzzle,
+    Subsystem::Quest,
+    Subsystem::Replay,
+    Subsystem::Respawn,
+    Subsystem::Skills,
+    Subsystem::Stealth,
+    Subsystem::Survival,
+    Subsystem::Tutorial,
+    Subsystem::Vehicles,
+    Subsystem::Vendor,
+    Subsystem::WorldBounds,
+    Subsystem::Zipline,
+];
 
 impl Plugin for GameControllerPlugin {
     fn build(&self, app: &mut App) {
+        if let Err(error) = self.validate() {
+            panic!("{}", error);
+        }
+
+        // Engine prerequisites must exist before the sub-plugins build
+        if self.headless {
+            app.add_plugins(headless::HeadlessPlugin);
+        }
+        app.add_plugins(simulation::SimulationPlugin {
+            mode: if self.is_fixed_simulation() { simulation::SimulationMode::Fixed } else { simulation::SimulationMode::Variable },
+            timestep_hz: self.fixed_simulation.unwrap_or(64.0),
+        });
+
         app
             // Add sub-plugins
             .add_plugins(abilities::AbilitiesPlugin)
@@ -129,35 +519,208 @@ impl Plugin for GameControllerPlugin {
             .add_plugins(ai::AiPlugin)
             .add_plugins(camera::CameraPlugin)
             .add_plugins(character::CharacterPlugin)
-            .add_plugins(climb::ClimbPlugin)
+            .add_plugins(config::GameControllerConfigPlugin)
             .add_plugins(combat::CombatPlugin)
             .add_plugins(currency::CurrencyPlugin)
             .add_plugins(devices::DevicesPlugin)
-            .add_plugins(dialog::DialogPlugin)
             .add_plugins(input::InputPlugin)
             .add_plugins(interaction::InteractionPlugin)
             .add_plugins(inventory::InventoryPlugin)
-            .add_plugins(ladder::LadderPlugin)
-            .add_plugins(map::MapPlugin)
             .add_plugins(pickups::PickupsPlugin)
             .add_plugins(others::OthersPlugin)
             .add_plugins(physics::PhysicsPlugin)
             .add_plugins(player::PlayerPlugin)
-            .add_plugins(puzzle::PuzzlePlugin)
-            .add_plugins(quest::QuestPlugin)
             .add_plugins(save::SavePlugin)
-            .add_plugins(skills::SkillsPlugin)
             .add_plugins(stats::StatsPlugin)
-            .add_plugins(stealth::StealthPlugin)
-            .add_plugins(tutorial::TutorialPlugin)
-            .add_plugins(vehicles::VehiclesPlugin)
-            .add_plugins(vendor::VendorPlugin)
+            .add_plugins(tags::TagsPlugin)
             .add_plugins(weapons::WeaponsPlugin)
             .add_plugins(head_track::HeadTrackPlugin)
+            .add_plugins(highlight::HighlightPlugin)
+            .add_plugins(hud::HudPlugin)
+            .add_plugins(indicators::OffScreenIndicatorPlugin)
+            .add_plugins(vfx::VfxPlugin)
             .add_plugins(level_manager::LevelManagerPlugin)
-            .add_plugins(point_and_click::PointAndClickPlugin)
+            .add_plugins(level_manager::SceneExportPlugin)
+            .add_plugins(loading_screen::LoadingScreenPlugin)
+            .add_plugins(audio::AudioCuePlugin);
+
+        if let Some(path) = &self.config {
+            app.insert_resource(config::ActiveGameControllerConfig::from_path(path.clone()));
+        }
+
+        // Optional sub-plugins
+        #[cfg(feature = "appearance")]
+        if self.is_enabled(Subsystem::Appearance) {
+            app.add_plugins(appearance::AppearancePlugin);
+        }
+
+        #[cfg(feature = "blueprints")]
+        if self.is_enabled(Subsystem::Blueprints) {
+            app.add_plugins(blueprints::BlueprintsPlugin);
+        }
+
+        #[cfg(feature = "boss")]
+        if self.is_enabled(Subsystem::Boss) {
+            app.add_plugins(boss::BossPlugin);
+        }
+
+        #[cfg(feature = "building")]
+        if self.is_enabled(Subsystem::Building) {
+            app.add_plugins(building::BuildingPlugin);
+        }
+
+        #[cfg(feature = "climb")]
+        if self.is_enabled(Subsystem::Climb) {
+            app.add_plugins(climb::ClimbPlugin);
+        }
+
+        #[cfg(feature = "debug_inspector")]
+        if self.is_enabled(Subsystem::DebugInspector) {
+            app.add_plugins(debug_inspector::DebugInspectorPlugin);
+        }
+
+        #[cfg(feature = "dialog")]
+        if self.is_enabled(Subsystem::Dialog) {
+            app.add_plugins(dialog::DialogPlugin);
+        }
+
+        #[cfg(feature = "emotes")]
+        if self.is_enabled(Subsystem::Emotes) {
+            app.add_plugins(emotes::EmotesPlugin);
+        }
+
+        #[cfg(feature = "fishing")]
+        if self.is_enabled(Subsystem::Fishing) {
+            app.add_plugins(fishing::FishingPlugin);
+        }
+
+        #[cfg(feature = "gore")]
+        if self.is_enabled(Subsystem::Gore) {
+            app.add_plugins(gore::GorePlugin);
+        }
+
+        #[cfg(feature = "ladder")]
+        if self.is_enabled(Subsystem::Ladder) {
+            app.add_plugins(ladder::LadderPlugin);
+        }
+
+        #[cfg(feature = "map")]
+        if self.is_enabled(Subsystem::Map) {
+            app.add_plugins(map::MapPlugin);
+        }
+
+        #[cfg(feature = "nameplates")]
+        if self.is_enabled(Subsystem::Nameplates) {
+            app.add_plugins(nameplates::NameplatePlugin);
+        }
+
+        #[cfg(feature = "point_and_click")]
+        if self.is_enabled(Subsystem::PointAndClick) {
+            app.add_plugins(point_and_click::PointAndClickPlugin);
+        }
+
+        #[cfg(feature = "puzzle")]
+        if self.is_enabled(Subsystem::Puzzle) {
+            app.add_plugins(puzzle::PuzzlePlugin);
+        }
+
+        #[cfg(feature = "quest")]
+        if self.is_enabled(Subsystem::Quest) {
+            app.add_plugins(quest::QuestPlugin);
+        }
+
+        #[cfg(feature = "replay")]
+        if self.is_enabled(Subsystem::Replay) {
+            app.add_plugins(replay::ReplayPlugin);
+        }
+
+        #[cfg(feature = "kill_cam")]
+        if self.is_enabled(Subsystem::KillCam) {
+            app.add_plugins(kill_cam::KillCamPlugin);
+        }
+
+        #[cfg(feature = "respawn")]
+        if self.is_enabled(Subsystem::Respawn) {
+            app.add_plugins(respawn::RespawnPlugin);
+        }
+
+        #[cfg(feature = "skills")]
+        if self.is_enabled(Subsystem::Skills) {
+            app.add_plugins(skills::SkillsPlugin);
+        }
+
+        #[cfg(feature = "stealth")]
+        if self.is_enabled(Subsystem::Stealth) {
+            app.add_plugins(stealth::StealthPlugin);
+        }
+
+        #[cfg(feature = "survival")]
+        if self.is_enabled(Subsystem::Survival) {
+            app.add_plugins(survival::SurvivalPlugin);
+        }
+
+        #[cfg(feature = "tutorial")]
+        if self.is_enabled(Subsystem::Tutorial) {
+            app.add_plugins(tutorial::TutorialPlugin);
+        }
+
+        #[cfg(feature = "vehicles")]
+        if self.is_enabled(Subsystem::Vehicles) {
+            app.add_plugins(vehicles::VehiclesPlugin);
+        }
+
+        #[cfg(feature = "vendor")]
+        if self.is_enabled(Subsystem::Vendor) {
+            app.add_plugins(vendor::VendorPlugin);
+        }
+
+        #[cfg(feature = "world_bounds")]
+        if self.is_enabled(Subsystem::WorldBounds) {
+            app.add_plugins(world_bounds::WorldBoundsPlugin);
+        }
+
+        #[cfg(feature = "zipline")]
+        if self.is_enabled(Subsystem::Zipline) {
+            app.add_plugins(zipline::ZiplinePlugin);
+        }
+
+        #[cfg(feature = "networking")]
+        app.add_plugins(networking::NetworkingPlugin);
+
+        #[cfg(feature = "debug_ui")]
+        if !self.headless {
+            app.add_plugins(debug_ui::DebugUiPlugin);
+        }
+
+        app
+            .configure_sets(Update, (
+                GameControllerSet::Input,
+                GameControllerSet::Ai,
+                GameControllerSet::Movement,
+                GameControllerSet::Interaction,
+                GameControllerSet::Combat,
+                GameControllerSet::Camera,
+                GameControllerSet::Persistence,
+            ).chain());
+
+        let simulation = app.simulation_schedule();
+        if simulation != Update.intern() {
+            app.configure_sets(simulation, (
+                GameControllerSet::Ai,
+                GameControllerSet::Movement,
+                GameControllerSet::Interaction,
+                GameControllerSet::Combat,
+            ).chain());
+        }
+
+        app
+            // Weapon hits are resolved in the same frame
+            .configure_sets(simulation, weapons::WeaponsSet::Effects.before(combat::CombatSet::DamageResolution))
             // Add resources
             .init_resource::<utils::GameTime>()
+            .register_type::<utils::TimeOfDay>()
+            .init_resource::<utils::TimeOfDay>()
+            .add_systems(Update, utils::advance_time_of_day.before(GameControllerSet::Ai))
             // Add startup systems
             .add_systems(Startup, setup_allinone);
     }
@@ -179,7 +742,145 @@ mod tests {
     #[test]
     fn test_plugin_build() {
         let mut app = App::new();
-        app.add_plugins(GameControllerPlugin);
+        app.add_plugins(GameControllerPlugin::default());
         // Plugin should build without panicking
     }
+
+    #[test]
+    fn test_documented_subsystem_selections_build() {
+        // The selection shown in the README and the `GameControllerPlugin` docs
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, GameControllerPlugin::default().without_vehicles().without_vendor().headless()));
+
+        // Leaving out anything no other subsystem needs is always valid
+        for subsystem in ALL_SUBSYSTEMS {
+            let needed = ALL_SUBSYSTEMS.iter().any(|dependent| dependent.dependencies().contains(&subsystem));
+            if !needed {
+                assert!(GameControllerPlugin::default().without(subsystem).validate().is_ok(), "{:?}", subsystem);
+            }
+        }
+        assert!(GameControllerPlugin::default().without_ladder().validate().is_err() || !Subsystem::Climb.is_compiled());
+    }
+
+    #[test]
+    fn test_disabled_subsystems_stay_out_of_a_running_app() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, GameControllerPlugin::default().without_vendor().without_world_bounds().headless()));
+
+        // Nothing left in the app depends on the missing subsystems' resources
+        for _ in 0..3 {
+            app.update();
+        }
+
+        #[cfg(feature = "vendor")]
+        assert!(!app.world().contains_resource::<vendor::HaggleSettings>());
+        #[cfg(feature = "world_bounds")]
+        assert!(!app.world().contains_resource::<world_bounds::WorldBounds>());
+        #[cfg(feature = "respawn")]
+        assert!(app.world().contains_resource::<respawn::RespawnSettings>());
+    }
+
+    #[test]
+    fn test_vehicle_dependents_run_without_vehicles() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, GameControllerPlugin::default().without_vehicles().headless()));
+
+        // World bounds, blueprints and replay only gate their vehicle code
+        for _ in 0..3 {
+            app.update();
+        }
+
+        #[cfg(feature = "vehicles")]
+        assert!(!app.world().contains_resource::<vehicles::VehicleOwnershipSettings>());
+        #[cfg(feature = "world_bounds")]
+        assert!(app.world().contains_resource::<world_bounds::WorldBounds>());
+        #[cfg(feature = "replay")]
+        assert!(app.world().contains_resource::<replay::ReplayRecorder>());
+        #[cfg(feature = "blueprints")]
+        assert!(app.world().contains_resource::<blueprints::BlueprintRegistry>());
+    }
+
+    #[test]
+    fn test_headless_plugin_build() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, GameControllerPlugin::default().headless()));
+        assert!(app.world().contains_resource::<headless::HeadlessMode>());
+
+        // Every schedule runs, debug drawing included, without a renderer
+        for _ in 0..5 {
+            app.update();
+        }
+    }
+
+    #[test]
+    fn test_fixed_simulation_plugin_build() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, GameControllerPlugin::default().headless().fixed_simulation()));
+        assert_eq!(app.world().get_resource::<simulation::SimulationMode>(), Some(&simulation::SimulationMode::Fixed));
+        assert_eq!(app.simulation_schedule(), FixedUpdate.intern());
+
+        for _ in 0..5 {
+            app.update();
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct PhaseLog(Vec<&'static str>);
+
+    fn log_phase(phase: &'static str) -> impl FnMut(ResMut<PhaseLog>) {
+        move |mut log: ResMut<PhaseLog>| log.0.push(phase)
+    }
+
+    #[test]
+    fn test_game_controller_sets_run_in_order() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, GameControllerPlugin::default().headless()))
+            .init_resource::<PhaseLog>()
+            // Registered backwards on purpose; only the sets order them
+            .add_systems(Update, (
+                log_phase("camera").in_set(GameControllerSet::Camera),
+                log_phase("after damage").after(combat::CombatSet::DamageResolution).before(GameControllerSet::Camera),
+                log_phase("combat").in_set(combat::CombatSet::DamageResolution),
+                log_phase("movement").in_set(GameControllerSet::Movement),
+                log_phase("ai").in_set(GameControllerSet::Ai),
+                log_phase("input").in_set(GameControllerSet::Input),
+            ));
+
+        app.update();
+        assert_eq!(app.world().resource::<PhaseLog>().0, ["input", "ai", "movement", "combat", "after damage", "camera"]);
+    }
+
+    #[test]
+    fn test_interaction_and_save_phases_run_in_order() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, GameControllerPlugin::default().headless()))
+            .init_resource::<PhaseLog>()
+            .add_systems(Update, (
+                log_phase("load").in_set(save::SaveSet::Requests),
+                log_phase("auto save").in_set(save::SaveSet::AutoSave),
+                log_phase("camera").in_set(GameControllerSet::Camera),
+                log_phase("prompts").in_set(interaction::InteractionSet::Ui),
+                log_phase("combat").in_set(GameControllerSet::Combat),
+                log_phase("interact").in_set(interaction::InteractionSet::Processing),
+                log_phase("detect").in_set(interaction::InteractionSet::Detection),
+                log_phase("movement").in_set(GameControllerSet::Movement),
+            ));
+
+        app.update();
+        assert_eq!(
+            app.world().resource::<PhaseLog>().0,
+            ["movement", "detect", "interact", "prompts", "combat", "camera", "auto save", "load"],
+        );
+    }
+
+    #[test]
+    fn test_scene_components_registered() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, GameControllerPlugin::default().headless()));
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        assert!(registry.get_type_data::<ReflectComponent>(std::any::TypeId::of::<events::types::PreviousCollisions>()).is_some());
+        assert!(registry.get_type_data::<ReflectComponent>(std::any::TypeId::of::<input::PlayerInputSettings>()).is_some());
+        assert!(registry.get_type_data::<ReflectComponent>(std::any::TypeId::of::<inventory::Inventory>()).is_some());
+        assert!(registry.get_type_data::<ReflectComponent>(std::any::TypeId::of::<level_manager::LevelSceneMember>()).is_some());
+    }
 }
@@ -0,0 +1,31 @@
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    LoadingReason, LoadingPhase, LoadingTask, LoadingScreenSettings, LoadingScreenState,
+    LoadingScreenEvent, LoadingScreenEventQueue,
+};
+
+pub struct LoadingScreenPlugin;
+
+impl Plugin for LoadingScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<LoadingScreenSettings>()
+            .init_resource::<LoadingScreenState>()
+            .add_plugins(EventQueuePlugin::<LoadingScreenEvent>::default())
+            .register_type::<LoadingScreenSettings>()
+            .register_type::<LoadingScreenState>()
+            .add_systems(Startup, setup_loading_screen_ui.in_set(crate::headless::PresentationSet))
+            .add_systems(Update, (
+                update_loading_progress,
+                update_loading_screen_phase,
+                update_loading_screen_ui.in_set(crate::headless::PresentationSet),
+            ).chain());
+    }
+}
@@ -0,0 +1,303 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::asset::LoadState;
+use crate::input::InputContextStack;
+use crate::input::types::InputContext;
+use super::types::*;
+
+/// System to spawn the (hidden) loading screen UI
+pub fn setup_loading_screen_ui(mut commands: Commands, settings: Res<LoadingScreenSettings>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                display: Display::None,
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::FlexEnd,
+                padding: UiRect::all(Val::Px(40.0)),
+                ..default()
+            },
+            BackgroundColor(settings.background_color),
+            GlobalZIndex(200),
+            LoadingScreenRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                ImageNode::default(),
+                Visibility::Hidden,
+                LoadingScreenArtwork,
+            ));
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::bottom(Val::Px(16.0)),
+                    ..default()
+                },
+                LoadingScreenHintText,
+            ));
+
+            // Progress bar
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Percent(60.0),
+                        height: Val::Px(8.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.15)),
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.9, 0.9, 0.9)),
+                        LoadingScreenProgressFill,
+                    ));
+                });
+        });
+}
+
+/// System to drop finished assets and aggregate overall progress
+pub fn update_loading_progress(
+    asset_server: Res<AssetServer>,
+    mut state: ResMut<LoadingScreenState>,
+) {
+    if !state.is_active() {
+        return;
+    }
+
+    state.tracked_assets.retain(|handle| {
+        if asset_server.is_loaded_with_dependencies(handle.id()) {
+            return false;
+        }
+        if matches!(asset_server.get_load_state(handle.id()), Some(LoadState::Failed(_))) {
+            warn!("Loading screen: asset {:?} failed to load", handle.path());
+            return false;
+        }
+        true
+    });
+
+    // Assets count as one implicit task
+    let mut total_weight = 0.0;
+    let mut done_weight = 0.0;
+    for task in state.tasks.iter() {
+        total_weight += task.weight;
+        done_weight += task.weight * task.progress;
+    }
+    if state.assets_total > 0 {
+        total_weight += 1.0;
+        done_weight += (state.assets_total - state.tracked_assets.len()) as f32 / state.assets_total as f32;
+    }
+
+    state.progress = if total_weight > 0.0 { done_weight / total_weight } else { 1.0 };
+}
+
+/// System to drive the loading screen phases, minimum display time and input swallow
+pub fn update_loading_screen_phase(
+    time: Res<Time>,
+    settings: Res<LoadingScreenSettings>,
+    mut state: ResMut<LoadingScreenState>,
+    mut event_queue: ResMut<LoadingScreenEventQueue>,
+    mut context_stack: ResMut<InputContextStack>,
+) {
+    if !state.is_active() {
+        return;
+    }
+
+    let delta = time.delta_secs();
+    state.elapsed += delta;
+    state.phase_timer += delta;
+
+    // Never let the bar go backwards when new tasks are added
+    let target = state.progress.max(state.displayed_progress);
+    state.displayed_progress = state.displayed_progress + (target - state.displayed_progress) * (delta * 8.0).min(1.0);
+
+    if settings.swallow_input && !state.input_context_pushed {
+        context_stack.stack.push(InputContext::Menu);
+        state.input_context_pushed = true;
+    }
+
+    if !settings.hints.is_empty() {
+        state.hint_timer += delta;
+        if state.hint_timer >= settings.hint_rotation_interval {
+            state.hint_timer = 0.0;
+            state.hint_index = (state.hint_index + 1) % settings.hints.len();
+        }
+    }
+
+    match state.phase {
+        LoadingPhase::FadingIn => {
+            if state.phase_timer >= settings.fade_in_duration {
+                state.phase = LoadingPhase::Loading;
+                state.phase_timer = 0.0;
+                let reason = state.reason;
+                event_queue.send(LoadingScreenEvent::Shown { reason });
+            }
+        }
+        LoadingPhase::Loading => {
+            if state.all_tasks_complete() && state.elapsed >= settings.min_display_time {
+                state.displayed_progress = 1.0;
+                state.phase = LoadingPhase::FadingOut;
+                state.phase_timer = 0.0;
+            }
+        }
+        LoadingPhase::FadingOut => {
+            if state.phase_timer < settings.fade_out_duration {
+                return;
+            }
+
+            state.phase = LoadingPhase::Hidden;
+            state.phase_timer = 0.0;
+
+            if state.input_context_pushed {
+                if let Some(index) = context_stack.stack.iter().rposition(|context| *context == InputContext::Menu) {
+                    context_stack.stack.remove(index);
+                }
+                state.input_context_pushed = false;
+            }
+
+            let reason = state.reason;
+            event_queue.send(LoadingScreenEvent::Finished { reason });
+            info!("Loading screen finished ({:?})", reason);
+        }
+        LoadingPhase::Hidden => {}
+    }
+}
+
+/// Widgets of the loading screen UI
+#[derive(SystemParam)]
+pub struct LoadingScreenWidgets<'w, 's> {
+    pub root_query: Query<'w, 's, (&'static mut Node, &'static mut BackgroundColor), With<LoadingScreenRoot>>,
+    pub fill_query: Query<'w, 's, &'static mut Node, (With<LoadingScreenProgressFill>, Without<LoadingScreenRoot>)>,
+    pub hint_query: Query<'w, 's, (&'static mut Text, &'static mut TextColor), With<LoadingScreenHintText>>,
+    pub artwork_query: Query<'w, 's, (&'static mut ImageNode, &'static mut Visibility), With<LoadingScreenArtwork>>,
+}
+
+/// System to sync the loading screen UI with its state
+pub fn update_loading_screen_ui(
+    asset_server: Res<AssetServer>,
+    settings: Res<LoadingScreenSettings>,
+    state: Res<LoadingScreenState>,
+    widgets: LoadingScreenWidgets,
+    mut last_artwork: Local<Option<usize>>,
+) {
+    let LoadingScreenWidgets { mut root_query, mut fill_query, mut hint_query, mut artwork_query } = widgets;
+    let alpha = match state.phase {
+        LoadingPhase::Hidden => 0.0,
+        LoadingPhase::FadingIn => (state.phase_timer / settings.fade_in_duration.max(0.001)).min(1.0),
+        LoadingPhase::Loading => 1.0,
+        LoadingPhase::FadingOut => 1.0 - (state.phase_timer / settings.fade_out_duration.max(0.001)).min(1.0),
+    };
+
+    for (mut node, mut background) in root_query.iter_mut() {
+        let display = if state.is_active() { Display::Flex } else { Display::None };
+        if node.display != display {
+            node.display = display;
+        }
+        background.0 = settings.background_color.with_alpha(alpha);
+    }
+
+    if !state.is_active() {
+        *last_artwork = None;
+        return;
+    }
+
+    for mut node in fill_query.iter_mut() {
+        node.width = Val::Percent(state.displayed_progress * 100.0);
+    }
+
+    for (mut text, mut color) in hint_query.iter_mut() {
+        let hint = settings.hints.get(state.hint_index).cloned().unwrap_or_default();
+        if text.0 != hint {
+            text.0 = hint;
+        }
+        color.0 = Color::WHITE.with_alpha(alpha);
+    }
+
+    // Artwork follows the hint rotation
+    let artwork_index = (!settings.artwork.is_empty()).then(|| state.hint_index % settings.artwork.len());
+    if *last_artwork != artwork_index {
+        *last_artwork = artwork_index;
+        for (mut image, mut visibility) in artwork_query.iter_mut() {
+            match artwork_index {
+                Some(index) => {
+                    image.image = asset_server.load(settings.artwork[index].clone());
+                    *visibility = Visibility::Inherited;
+                }
+                None => *visibility = Visibility::Hidden,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn test_loading_screen_waits_for_tasks_and_min_time() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_plugins(EventQueuePlugin::<LoadingScreenEvent>::default())
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<LoadingScreenSettings>()
+            .init_resource::<LoadingScreenState>()
+            .init_resource::<InputContextStack>()
+            .add_systems(Update, (update_loading_progress, update_loading_screen_phase).chain());
+
+        let base_depth = app.world().resource::<InputContextStack>().stack.len();
+        {
+            let mut state = app.world_mut().resource_mut::<LoadingScreenState>();
+            state.begin(LoadingReason::SaveLoad);
+            state.add_task("world", 3.0);
+            state.add_task("npcs", 1.0);
+        }
+
+        for _ in 0..4 {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<LoadingScreenState>().phase, LoadingPhase::Loading);
+        assert!(app.world().resource::<LoadingScreenEventQueue>().iter()
+            .any(|event| *event == LoadingScreenEvent::Shown { reason: LoadingReason::SaveLoad }));
+        assert_eq!(app.world().resource::<InputContextStack>().stack.last(), Some(&InputContext::Menu));
+
+        // Progress is weighted by task
+        app.world_mut().resource_mut::<LoadingScreenState>().complete_task("world");
+        app.update();
+        assert_eq!(app.world().resource::<LoadingScreenState>().progress, 0.75);
+
+        // Finished tasks still wait out the minimum display time before fading
+        app.world_mut().resource_mut::<LoadingScreenState>().complete_task("npcs");
+        app.update();
+        assert_eq!(app.world().resource::<LoadingScreenState>().phase, LoadingPhase::Loading);
+
+        for _ in 0..20 {
+            app.update();
+        }
+        let state = app.world().resource::<LoadingScreenState>();
+        assert_eq!(state.phase, LoadingPhase::Hidden);
+        assert!(!state.input_context_pushed);
+        assert_eq!(app.world().resource::<InputContextStack>().stack.len(), base_depth);
+    }
+}
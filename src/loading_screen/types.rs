@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+use crate::utils::EventQueue;
+
+/// Why the loading screen is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum LoadingReason {
+    #[default]
+    LevelChange,
+    SaveLoad,
+    Streaming,
+    Custom,
+}
+
+/// Loading screen phase
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum LoadingPhase {
+    #[default]
+    Hidden,
+    FadingIn,
+    Loading,
+    /// Everything is loaded, fading back to gameplay
+    FadingOut,
+}
+
+/// A unit of work reported to the loading screen
+#[derive(Debug, Clone, Reflect)]
+pub struct LoadingTask {
+    pub name: String,
+    /// Relative share of the total progress bar
+    pub weight: f32,
+    pub progress: f32,
+}
+
+/// Loading screen settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct LoadingScreenSettings {
+    pub enabled: bool,
+    /// The screen stays up at least this long, even if loading is instant
+    pub min_display_time: f32,
+    pub fade_in_duration: f32,
+    pub fade_out_duration: f32,
+    pub hint_rotation_interval: f32,
+    pub hints: Vec<String>,
+    /// Image asset paths cycled as background artwork
+    pub artwork: Vec<String>,
+    pub background_color: Color,
+    /// Push the menu input context while loading
+    pub swallow_input: bool,
+}
+
+impl Default for LoadingScreenSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_display_time: 1.0,
+            fade_in_duration: 0.25,
+            fade_out_duration: 0.5,
+            hint_rotation_interval: 5.0,
+            hints: Vec::new(),
+            artwork: Vec::new(),
+            background_color: Color::srgb(0.05, 0.05, 0.07),
+            swallow_input: true,
+        }
+    }
+}
+
+/// Loading screen runtime state and progress aggregation
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct LoadingScreenState {
+    pub phase: LoadingPhase,
+    pub reason: LoadingReason,
+    pub tasks: Vec<LoadingTask>,
+    #[reflect(ignore)]
+    pub tracked_assets: Vec<UntypedHandle>,
+    pub assets_total: usize,
+
+    // Debug State
+    pub progress: f32,
+    pub displayed_progress: f32,
+    pub elapsed: f32,
+    pub phase_timer: f32,
+    pub hint_index: usize,
+    pub hint_timer: f32,
+    pub input_context_pushed: bool,
+}
+
+impl LoadingScreenState {
+    pub fn is_active(&self) -> bool {
+        self.phase != LoadingPhase::Hidden
+    }
+
+    /// Shows the loading screen (no-op if already showing)
+    pub fn begin(&mut self, reason: LoadingReason) {
+        if self.is_active() && self.phase != LoadingPhase::FadingOut {
+            return;
+        }
+
+        self.phase = LoadingPhase::FadingIn;
+        self.reason = reason;
+        self.tasks.clear();
+        self.tracked_assets.clear();
+        self.assets_total = 0;
+        self.progress = 0.0;
+        self.displayed_progress = 0.0;
+        self.elapsed = 0.0;
+        self.phase_timer = 0.0;
+        self.hint_timer = 0.0;
+    }
+
+    /// Registers a named task; call `set_task_progress`/`complete_task` as it advances
+    pub fn add_task(&mut self, name: impl Into<String>, weight: f32) {
+        let name = name.into();
+        if self.tasks.iter().any(|task| task.name == name) {
+            return;
+        }
+        self.tasks.push(LoadingTask {
+            name,
+            weight: weight.max(0.0),
+            progress: 0.0,
+        });
+    }
+
+    pub fn set_task_progress(&mut self, name: &str, progress: f32) {
+        if let Some(task) = self.tasks.iter_mut().find(|task| task.name == name) {
+            task.progress = progress.clamp(0.0, 1.0);
+        }
+    }
+
+    pub fn complete_task(&mut self, name: &str) {
+        self.set_task_progress(name, 1.0);
+    }
+
+    /// Tracks an asset; its dependency load state feeds the progress bar
+    pub fn track_asset(&mut self, handle: impl Into<UntypedHandle>) {
+        self.tracked_assets.push(handle.into());
+        self.assets_total += 1;
+    }
+
+    pub fn all_tasks_complete(&self) -> bool {
+        self.tracked_assets.is_empty() && self.tasks.iter().all(|task| task.progress >= 1.0)
+    }
+}
+
+/// Loading screen lifecycle event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadingScreenEvent {
+    Shown { reason: LoadingReason },
+    /// Loading is done and the fade-out has finished; gameplay may resume
+    Finished { reason: LoadingReason },
+}
+
+/// Custom queue for loading screen events
+pub type LoadingScreenEventQueue = EventQueue<LoadingScreenEvent>;
+
+/// Root node of the loading screen UI
+#[derive(Component, Debug, Default)]
+pub struct LoadingScreenRoot;
+
+/// Progress bar fill node
+#[derive(Component, Debug, Default)]
+pub struct LoadingScreenProgressFill;
+
+/// Hint text node
+#[derive(Component, Debug, Default)]
+pub struct LoadingScreenHintText;
+
+/// Artwork image node
+#[derive(Component, Debug, Default)]
+pub struct LoadingScreenArtwork;
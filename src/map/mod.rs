@@ -34,17 +34,19 @@ impl Plugin for MapPlugin {
             .init_resource::<MapGlobalState>()
 
             // Systems
-            .add_systems(Startup, setup_map_ui)
+            .add_systems(Startup, setup_map_ui.in_set(crate::headless::PresentationSet))
             .add_systems(Update, (
                 update_map_object_information,
-                // Ensure visibility is checked BEFORE positioning
-                (update_visible_map_elements, update_minimap_positions).chain(),
-                update_compass,
                 handle_quick_travel,
                 update_objective_icons,
                 check_map_zones,
                 handle_map_system_input,
+            ))
+            .add_systems(Update, (
+                // Ensure visibility is checked BEFORE positioning
+                (update_visible_map_elements, update_minimap_positions).chain(),
+                update_compass,
                 update_map_visibility,
-            ));
+            ).in_set(crate::headless::PresentationSet));
     }
 }
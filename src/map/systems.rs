@@ -2,6 +2,7 @@ use bevy::prelude::*;
 use bevy::ui::{Node, Val, UiRect, Display, FlexDirection, AlignItems, JustifyContent, PositionType};
 use avian3d::prelude::*;
 use crate::map::types::*;
+use crate::input::{InputContext, InputContextStack};
 
 // ============================================================================
 // MAP SYSTEMS
@@ -170,10 +171,13 @@ pub fn handle_map_system_input(
     input: Res<ButtonInput<KeyCode>>,
     mut settings: ResMut<MapSettings>,
     mut global_state: ResMut<MapGlobalState>,
+    mut context_stack: ResMut<InputContextStack>,
 ) {
     if input.just_pressed(KeyCode::KeyM) {
         global_state.map_menu_opened = !global_state.map_menu_opened;
         settings.full_map_enabled = global_state.map_menu_opened;
+        // Blocks the character until the map closes
+        context_stack.set_active(InputContext::Map, global_state.map_menu_opened);
         info!("Toggled Map: {}", global_state.map_menu_opened);
     }
 }
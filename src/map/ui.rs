@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bevy::ui::{Node, Val, UiRect, Display, FlexDirection, AlignItems, JustifyContent, PositionType};
 use crate::map::types::*;
+use crate::hud::{HudElement, HudPriority};
 
 // ============================================================================
 // UI COMPONENTS
@@ -29,6 +30,7 @@ pub fn setup_map_ui(mut commands: Commands) {
         },
         // PickingBehavior::IGNORE, // Removed as it causes errors
         MapRoot,
+        HudElement::new("map", HudPriority::Detail),
     ))
     .with_children(|parent| {
         // Minimap (Top Right)
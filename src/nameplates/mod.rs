@@ -0,0 +1,47 @@
+//! NPC nameplates
+//!
+//! Screen-projected name, level, health bar and cast bar over entities with a
+//! [`Nameplate`] (every AI by default). The name is tinted by the NPC's faction
+//! relation to the player, the health bar flashes on hits and the cast bar tracks
+//! channeled abilities. Plates are culled by distance and line of sight and follow
+//! a [`NameplateVisibility`] policy: always, in combat or on hover.
+
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use bevy::transform::TransformSystems;
+use types::*;
+use systems::*;
+
+pub use types::{
+    NameplateVisibility, NameplateSettings, Nameplate, NameplateUi, NameplateLabel,
+    NameplateHealthBar, NameplateHealthFill, NameplateCastBar, NameplateCastFill,
+};
+
+pub struct NameplatePlugin;
+
+impl Plugin for NameplatePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<NameplateSettings>()
+            .register_type::<NameplateSettings>()
+            .register_type::<Nameplate>()
+            .register_type::<NameplateUi>()
+            .register_type::<NameplateLabel>()
+            .register_type::<NameplateHealthBar>()
+            .register_type::<NameplateHealthFill>()
+            .register_type::<NameplateCastBar>()
+            .register_type::<NameplateCastFill>()
+            .add_systems(Update, (
+                attach_ai_nameplates,
+                spawn_nameplate_ui,
+                update_nameplate_states,
+            ).chain())
+            // After cameras and characters have moved for this frame
+            .add_systems(PostUpdate, (
+                position_nameplates,
+                update_nameplate_contents,
+            ).chain().after(TransformSystems::Propagate));
+    }
+}
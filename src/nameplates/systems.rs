@@ -0,0 +1,423 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use avian3d::prelude::*;
+use super::types::*;
+use crate::abilities::AbilityInfo;
+use crate::ai::{AiBehaviorState, AiController, CharacterFaction, FactionRelation, FactionSystem};
+use crate::character::Player;
+use crate::combat::{DamageResultEvent, Health};
+use crate::utils::QueueReader;
+
+/// Gives new AI a nameplate
+pub fn attach_ai_nameplates(
+    mut commands: Commands,
+    settings: Res<NameplateSettings>,
+    query: Query<Entity, (Added<AiController>, Without<Nameplate>)>,
+) {
+    if !settings.auto_attach_ai {
+        return;
+    }
+    for entity in query.iter() {
+        commands.entity(entity).insert(Nameplate::default());
+    }
+}
+
+/// Spawns the UI of nameplates that have none and removes UI of despawned owners
+pub fn spawn_nameplate_ui(
+    mut commands: Commands,
+    settings: Res<NameplateSettings>,
+    mut nameplate_query: Query<(Entity, &mut Nameplate)>,
+    ui_query: Query<(Entity, &NameplateUi)>,
+) {
+    for (ui_entity, ui) in ui_query.iter() {
+        if !nameplate_query.contains(ui.owner) {
+            commands.entity(ui_entity).despawn();
+        }
+    }
+
+    for (owner, mut nameplate) in nameplate_query.iter_mut() {
+        if nameplate.ui_root.is_some() {
+            continue;
+        }
+
+        let label = commands.spawn((
+            Text::new(""),
+            TextFont { font_size: settings.font_size, ..default() },
+            TextColor(settings.neutral_color),
+            TextLayout::new_with_justify(Justify::Center),
+            NameplateLabel,
+        )).id();
+        let health_fill = commands.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(settings.health_color),
+            NameplateHealthFill,
+        )).id();
+        let health_bar = commands.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            NameplateHealthBar,
+        )).add_child(health_fill).id();
+        let cast_fill = commands.spawn((
+            Node {
+                width: Val::Percent(0.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            BackgroundColor(settings.cast_color),
+            NameplateCastFill,
+        )).id();
+        let cast_bar = commands.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Px(4.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            Visibility::Hidden,
+            NameplateCastBar,
+        )).add_child(cast_fill).id();
+
+        let root = commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Px(settings.width),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                row_gap: Val::Px(2.0),
+                ..default()
+            },
+            Visibility::Hidden,
+            GlobalZIndex(20),
+            NameplateUi {
+                owner,
+                label,
+                health_bar,
+                health_fill,
+                cast_bar,
+                cast_fill,
+            },
+        )).add_children(&[label, health_bar, cast_bar]).id();
+
+        nameplate.ui_root = Some(root);
+    }
+}
+
+/// Raycasts hiding nameplates behind geometry, ignoring the player and the NPC's own parts
+#[derive(SystemParam)]
+pub struct NameplateOcclusion<'w, 's> {
+    pub spatial_query: SpatialQuery<'w, 's>,
+    pub player_query: Query<'w, 's, Entity, With<Player>>,
+    pub parent_query: Query<'w, 's, &'static ChildOf>,
+}
+
+/// Combat timers, damage flash and occlusion checks
+pub fn update_nameplate_states(
+    time: Res<Time>,
+    settings: Res<NameplateSettings>,
+    mut damage_results: QueueReader<DamageResultEvent>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut nameplate_query: Query<(Entity, &mut Nameplate, &GlobalTransform, Option<&AiController>)>,
+    occlusion: NameplateOcclusion,
+) {
+    let NameplateOcclusion { spatial_query, player_query, parent_query } = occlusion;
+    let delta = time.delta_secs();
+
+    for result in damage_results.read() {
+        if result.final_amount <= 0.0 {
+            continue;
+        }
+        if let Ok((_, mut nameplate, _, _)) = nameplate_query.get_mut(result.target) {
+            nameplate.flash_timer = settings.flash_duration;
+            nameplate.combat_timer = settings.combat_linger;
+        }
+    }
+
+    let camera_position = camera_query
+        .iter()
+        .find(|(camera, _)| camera.is_active)
+        .map(|(_, transform)| transform.translation());
+    let filter = SpatialQueryFilter::from_excluded_entities(player_query.iter());
+
+    for (entity, mut nameplate, transform, ai) in nameplate_query.iter_mut() {
+        nameplate.flash_timer = (nameplate.flash_timer - delta).max(0.0);
+        nameplate.combat_timer = (nameplate.combat_timer - delta).max(0.0);
+
+        let fighting = ai.is_some_and(|ai| {
+            ai.target.is_some()
+                && matches!(ai.state, AiBehaviorState::Chase | AiBehaviorState::Attack | AiBehaviorState::Combat)
+        });
+        if fighting {
+            nameplate.combat_timer = settings.combat_linger;
+        }
+
+        if !settings.occlusion_culling {
+            nameplate.occluded = false;
+            continue;
+        }
+        nameplate.occlusion_timer -= delta;
+        if nameplate.occlusion_timer > 0.0 {
+            continue;
+        }
+        nameplate.occlusion_timer = settings.occlusion_interval;
+
+        let Some(camera_position) = camera_position else { continue };
+        let anchor = transform.translation() + nameplate.offset * 0.8;
+        let to_anchor = anchor - camera_position;
+        let Ok(direction) = Dir3::new(to_anchor) else { continue };
+
+        // Anything but the NPC itself (or one of its parts) in between hides the plate
+        nameplate.occluded = spatial_query
+            .cast_ray(camera_position, direction, to_anchor.length(), true, &filter)
+            .is_some_and(|hit| {
+                hit.entity != entity
+                    && parent_query.iter_ancestors(hit.entity).all(|ancestor| ancestor != entity)
+            });
+    }
+}
+
+/// Projects nameplates to the screen and applies distance, occlusion and visibility policies
+pub fn position_nameplates(
+    settings: Res<NameplateSettings>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    nameplate_query: Query<(&Nameplate, &GlobalTransform, Option<&Health>)>,
+    mut ui_query: Query<(&NameplateUi, &mut Node, &mut Visibility)>,
+) {
+    let camera = camera_query.iter().find(|(camera, _)| camera.is_active);
+    let cursor = window_query.iter().next().and_then(|window| window.cursor_position());
+
+    for (ui, mut node, mut visibility) in ui_query.iter_mut() {
+        let Some((camera, camera_transform)) = camera else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        let Ok((nameplate, transform, health)) = nameplate_query.get(ui.owner) else {
+            continue;
+        };
+
+        let anchor = transform.translation() + nameplate.offset;
+        let dead = health.is_some_and(|health| health.is_dead);
+        let in_range = anchor.distance(camera_transform.translation()) <= settings.max_distance;
+        let screen_position = camera.world_to_viewport(camera_transform, anchor).ok();
+
+        let hovered = match (screen_position, cursor) {
+            (Some(position), Some(cursor)) => position.distance(cursor) <= settings.hover_radius,
+            _ => false,
+        };
+        let policy_allows = match nameplate.visibility.unwrap_or(settings.default_visibility) {
+            NameplateVisibility::Always => true,
+            NameplateVisibility::InCombat => nameplate.combat_timer > 0.0,
+            NameplateVisibility::OnHover => hovered,
+            NameplateVisibility::Never => false,
+        };
+
+        let shown = settings.enabled && !dead && in_range && !nameplate.occluded && policy_allows;
+        match screen_position.filter(|_| shown) {
+            Some(position) => {
+                node.left = Val::Px(position.x - settings.width * 0.5);
+                node.top = Val::Px(position.y);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+/// Character a nameplate shows
+type NameplateOwner<'a> = (
+    &'a Nameplate,
+    Option<&'a Name>,
+    Option<&'a Health>,
+    Option<&'a CharacterFaction>,
+    Option<&'a AiController>,
+    Option<&'a Children>,
+);
+
+type NameplateFill = Or<(With<NameplateHealthFill>, With<NameplateCastFill>)>;
+type NameplateBar = (Or<(With<NameplateHealthBar>, With<NameplateCastBar>)>, Without<NameplateUi>);
+
+/// Label and bars inside a nameplate
+#[derive(SystemParam)]
+pub struct NameplateWidgets<'w, 's> {
+    pub label_query: Query<'w, 's, (&'static mut Text, &'static mut TextColor), With<NameplateLabel>>,
+    pub fill_query: Query<'w, 's, (&'static mut Node, &'static mut BackgroundColor), NameplateFill>,
+    pub bar_query: Query<'w, 's, &'static mut Visibility, NameplateBar>,
+}
+
+/// Name, level, faction color, health and cast bars
+pub fn update_nameplate_contents(
+    settings: Res<NameplateSettings>,
+    faction_system: Res<FactionSystem>,
+    player_query: Query<(Entity, Option<&CharacterFaction>), With<Player>>,
+    nameplate_query: Query<NameplateOwner>,
+    ability_query: Query<&AbilityInfo>,
+    ui_query: Query<(&NameplateUi, &Visibility)>,
+    widgets: NameplateWidgets,
+) {
+    let NameplateWidgets { mut label_query, mut fill_query, mut bar_query } = widgets;
+    let players: Vec<Entity> = player_query.iter().map(|(entity, _)| entity).collect();
+    let player_faction = player_query.iter().find_map(|(_, faction)| faction.map(|faction| faction.name.as_str()));
+
+    for (ui, visibility) in ui_query.iter() {
+        if *visibility == Visibility::Hidden {
+            continue;
+        }
+        let Ok((nameplate, name, health, faction, ai, children)) = nameplate_query.get(ui.owner) else {
+            continue;
+        };
+
+        // Faction color relative to the player; whoever is hunting the player is an enemy
+        let targets_player = ai.and_then(|ai| ai.target).is_some_and(|target| players.contains(&target));
+        let relation = if targets_player {
+            FactionRelation::Enemy
+        } else {
+            match (player_faction, faction) {
+                (Some(player_faction), Some(faction)) => faction_system.get_relation(player_faction, &faction.name),
+                _ => FactionRelation::Neutral,
+            }
+        };
+        let color = match relation {
+            FactionRelation::Friend => settings.friend_color,
+            FactionRelation::Neutral => settings.neutral_color,
+            FactionRelation::Enemy => settings.enemy_color,
+        };
+
+        if let Ok((mut text, mut text_color)) = label_query.get_mut(ui.label) {
+            let display_name = nameplate
+                .name
+                .clone()
+                .or_else(|| name.map(|name| name.to_string()))
+                .unwrap_or_default();
+            let label = match nameplate.level {
+                Some(level) => format!("[{}] {}", level, display_name),
+                None => display_name,
+            };
+            if text.0 != label {
+                text.0 = label;
+            }
+            text_color.0 = color;
+        }
+
+        let show_health = nameplate.show_health && health.is_some();
+        if let Ok(mut bar_visibility) = bar_query.get_mut(ui.health_bar) {
+            *bar_visibility = if show_health { Visibility::Inherited } else { Visibility::Hidden };
+        }
+        if let (Some(health), Ok((mut node, mut background))) = (health, fill_query.get_mut(ui.health_fill)) {
+            let fraction = (health.current / health.maximum.max(0.001)).clamp(0.0, 1.0);
+            node.width = Val::Percent(fraction * 100.0);
+            background.0 = if nameplate.flash_timer > 0.0 { settings.flash_color } else { settings.health_color };
+        }
+
+        // Channeled ability: active with a running time limit, on the NPC or one of its children
+        let channel_progress = nameplate.show_cast_bar.then(|| {
+            std::iter::once(ui.owner)
+                .chain(children.into_iter().flat_map(|children| children.iter()))
+                .filter_map(|entity| ability_query.get(entity).ok())
+                .find(|ability| ability.active && ability.time_limit_in_process && ability.time_limit > 0.0)
+                .map(|ability| 1.0 - ability.time_limit_timer / ability.time_limit)
+        }).flatten();
+
+        if let Ok(mut bar_visibility) = bar_query.get_mut(ui.cast_bar) {
+            *bar_visibility = if channel_progress.is_some() { Visibility::Inherited } else { Visibility::Hidden };
+        }
+        if let (Some(progress), Ok((mut node, _))) = (channel_progress, fill_query.get_mut(ui.cast_fill)) {
+            node.width = Val::Percent(progress.clamp(0.0, 1.0) * 100.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::{DamageResultQueue, DamageType};
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn test_nameplates_attach_flash_and_occlude() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin, PhysicsPlugins::default()))
+            .add_plugins(EventQueuePlugin::<DamageResultEvent>::default())
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<NameplateSettings>()
+            .init_resource::<FactionSystem>()
+            .add_systems(Update, (
+                attach_ai_nameplates,
+                spawn_nameplate_ui,
+                update_nameplate_states,
+                update_nameplate_contents,
+            ).chain());
+
+        app.world_mut().spawn((Camera3d::default(), Transform::default()));
+        let player = app.world_mut().spawn((Player, Transform::default())).id();
+        let bandit = app.world_mut().spawn((
+            Name::new("Bandit"),
+            AiController { target: Some(player), ..default() },
+            Health { current: 50.0, ..default() },
+            Transform::from_xyz(0.0, 0.0, 10.0),
+        )).id();
+        let wall = app.world_mut().spawn((
+            RigidBody::Static,
+            Collider::cuboid(4.0, 4.0, 0.5),
+            Transform::from_xyz(0.0, 1.0, 5.0),
+        )).id();
+
+        app.update();
+        let root = app.world().get::<Nameplate>(bandit).unwrap().ui_root.expect("AI should get a nameplate");
+        let ui = app.world().get::<NameplateUi>(root).unwrap();
+        let (label, health_fill) = (ui.label, ui.health_fill);
+
+        // Positioning needs a rendered viewport; show the plate as if on screen
+        *app.world_mut().get_mut::<Visibility>(root).unwrap() = Visibility::Inherited;
+        app.world_mut().resource_mut::<DamageResultQueue>().send(DamageResultEvent {
+            target: bandit,
+            part: None,
+            source: Some(player),
+            original_amount: 10.0,
+            final_amount: 10.0,
+            damage_type: DamageType::Melee,
+            shielded_amount: 0.0,
+            is_crit: false,
+            is_block: false,
+        });
+        app.update();
+
+        let settings = NameplateSettings::default();
+        let nameplate = app.world().get::<Nameplate>(bandit).unwrap();
+        assert!(nameplate.combat_timer > 4.0);
+        assert_eq!(app.world().get::<Text>(label).unwrap().0, "Bandit");
+        assert_eq!(app.world().get::<TextColor>(label).unwrap().0, settings.enemy_color);
+        assert_eq!(app.world().get::<Node>(health_fill).unwrap().width, Val::Percent(50.0));
+        assert_eq!(app.world().get::<BackgroundColor>(health_fill).unwrap().0, settings.flash_color);
+
+        app.update();
+        assert_eq!(app.world().get::<BackgroundColor>(health_fill).unwrap().0, settings.health_color);
+
+        // The wall between the camera and the bandit hides the plate until it is gone
+        for _ in 0..4 {
+            app.update();
+        }
+        assert!(app.world().get::<Nameplate>(bandit).unwrap().occluded);
+
+        app.world_mut().despawn(wall);
+        for _ in 0..4 {
+            app.update();
+        }
+        assert!(!app.world().get::<Nameplate>(bandit).unwrap().occluded);
+
+        app.world_mut().despawn(bandit);
+        app.update();
+        assert!(app.world().get_entity(root).is_err());
+    }
+}
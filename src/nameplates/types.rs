@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+
+/// When a nameplate is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum NameplateVisibility {
+    #[default]
+    Always,
+    /// While the NPC is fighting or was recently hit
+    InCombat,
+    /// While the cursor is over the NPC
+    OnHover,
+    Never,
+}
+
+/// Global nameplate settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct NameplateSettings {
+    pub enabled: bool,
+    /// Give every AI a nameplate when it spawns
+    pub auto_attach_ai: bool,
+    /// Used by nameplates without their own policy
+    pub default_visibility: NameplateVisibility,
+    /// Nameplates farther than this from the camera are hidden
+    pub max_distance: f32,
+    /// Hide nameplates of NPCs behind walls
+    pub occlusion_culling: bool,
+    /// Seconds between occlusion raycasts of one nameplate
+    pub occlusion_interval: f32,
+    /// Seconds an NPC stays "in combat" after being hit
+    pub combat_linger: f32,
+    pub flash_duration: f32,
+    /// Screen distance (pixels) from the cursor that counts as hovering
+    pub hover_radius: f32,
+    pub width: f32,
+    pub font_size: f32,
+    pub friend_color: Color,
+    pub neutral_color: Color,
+    pub enemy_color: Color,
+    pub health_color: Color,
+    pub flash_color: Color,
+    pub cast_color: Color,
+}
+
+impl Default for NameplateSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            auto_attach_ai: true,
+            default_visibility: NameplateVisibility::Always,
+            max_distance: 35.0,
+            occlusion_culling: true,
+            occlusion_interval: 0.2,
+            combat_linger: 5.0,
+            flash_duration: 0.15,
+            hover_radius: 60.0,
+            width: 90.0,
+            font_size: 13.0,
+            friend_color: Color::srgb(0.3, 0.9, 0.4),
+            neutral_color: Color::srgb(0.95, 0.85, 0.3),
+            enemy_color: Color::srgb(0.95, 0.3, 0.25),
+            health_color: Color::srgb(0.8, 0.15, 0.15),
+            flash_color: Color::WHITE,
+            cast_color: Color::srgb(0.95, 0.7, 0.2),
+        }
+    }
+}
+
+/// Nameplate shown over an entity
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Nameplate {
+    /// Shown name; falls back to the entity's `Name`
+    pub name: Option<String>,
+    pub level: Option<u32>,
+    /// Anchor offset above the entity origin
+    pub offset: Vec3,
+    /// Overrides `NameplateSettings::default_visibility`
+    pub visibility: Option<NameplateVisibility>,
+    pub show_health: bool,
+    pub show_cast_bar: bool,
+
+    // Debug State
+    pub ui_root: Option<Entity>,
+    pub combat_timer: f32,
+    pub flash_timer: f32,
+    pub occluded: bool,
+    pub occlusion_timer: f32,
+}
+
+impl Default for Nameplate {
+    fn default() -> Self {
+        Self {
+            name: None,
+            level: None,
+            offset: Vec3::new(0.0, 2.2, 0.0),
+            visibility: None,
+            show_health: true,
+            show_cast_bar: true,
+            ui_root: None,
+            combat_timer: 0.0,
+            flash_timer: 0.0,
+            occluded: false,
+            occlusion_timer: 0.0,
+        }
+    }
+}
+
+/// Screen-space root of a nameplate
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct NameplateUi {
+    pub owner: Entity,
+    pub label: Entity,
+    pub health_bar: Entity,
+    pub health_fill: Entity,
+    pub cast_bar: Entity,
+    pub cast_fill: Entity,
+}
+
+/// Name and level label
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct NameplateLabel;
+
+/// Health bar background
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct NameplateHealthBar;
+
+/// Health bar fill
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct NameplateHealthFill;
+
+/// Cast bar background
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct NameplateCastBar;
+
+/// Cast bar fill
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct NameplateCastFill;
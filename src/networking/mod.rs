@@ -0,0 +1,71 @@
+//! Networking groundwork (feature `networking`)
+//!
+//! Backend-agnostic state replication: the server assigns [`NetworkId`]s to
+//! [`ReplicatedEntity`]s and streams [`Replicated`] component states, weapon fire
+//! and input acks; clients mirror those entities and predict their own character.
+//! A transport (bevy_replicon, renet...) plugs in through [`NetworkAdapter`].
+
+pub mod types;
+pub mod replicated;
+pub mod systems;
+
+use bevy::prelude::*;
+use crate::combat::Health;
+use crate::inventory::Inventory;
+use types::*;
+use systems::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    ClientId, SERVER_CLIENT_ID, NetworkRole, NetworkId, ReplicatedEntity, Replicated, ReplicationBaseline,
+    TransformState, HealthState, ReplicatedItem, InventoryState, ReplicationMessage, MessageTarget,
+    IncomingMessage, NetworkAdapter, NetworkConnection, NetworkSettings, NetworkState, NetworkEntityMap,
+    ReplicationInbox, ReplicationOutbox, PredictedInput, PredictedMove, PredictedCharacter,
+    NetworkInputAck, NetworkEvent, NetworkEventQueue,
+};
+pub use systems::{apply_replicated_component, replicate_component};
+
+pub struct NetworkingPlugin;
+
+impl Plugin for NetworkingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<NetworkConnection>()
+            .init_resource::<NetworkSettings>()
+            .init_resource::<NetworkState>()
+            .init_resource::<NetworkEntityMap>()
+            .init_resource::<ReplicationInbox>()
+            .init_resource::<ReplicationOutbox>()
+            .add_plugins(EventQueuePlugin::<NetworkEvent>::default())
+            .register_type::<NetworkSettings>()
+            .register_type::<NetworkState>()
+            .register_type::<NetworkId>()
+            .register_type::<ReplicatedEntity>()
+            .register_type::<PredictedCharacter>()
+            .register_type::<NetworkInputAck>()
+            // Incoming state lands before gameplay, outgoing state leaves after it
+            .add_systems(PreUpdate, (
+                update_network_state,
+                receive_network_messages,
+                apply_spawn_messages,
+                apply_replicated_component::<Transform>,
+                apply_replicated_component::<Health>,
+                apply_replicated_component::<Inventory>,
+                reconcile_predicted_characters,
+                apply_client_inputs,
+                apply_remote_weapon_fire,
+            ).chain())
+            .add_systems(Update, decay_prediction_offsets)
+            .add_systems(PostUpdate, (
+                assign_network_ids,
+                replicate_despawns,
+                replicate_component::<Transform>,
+                replicate_component::<Health>,
+                replicate_component::<Inventory>,
+                replicate_weapon_fire,
+                record_predicted_inputs,
+                acknowledge_client_inputs,
+                flush_network_messages,
+            ).chain());
+    }
+}
@@ -0,0 +1,135 @@
+//! Built-in `Replicated` implementations
+
+use bevy::prelude::*;
+use crate::combat::Health;
+use crate::inventory::{Inventory, InventoryItem};
+use super::types::*;
+
+impl Replicated for Transform {
+    const KIND: &'static str = "transform";
+    const PREDICTED: bool = true;
+
+    type State = TransformState;
+
+    fn capture(&self) -> Self::State {
+        TransformState {
+            translation: self.translation,
+            rotation: self.rotation,
+        }
+    }
+
+    fn apply(&mut self, state: Self::State) {
+        self.translation = state.translation;
+        self.rotation = state.rotation;
+    }
+}
+
+impl Replicated for Health {
+    const KIND: &'static str = "health";
+
+    type State = HealthState;
+
+    fn capture(&self) -> Self::State {
+        HealthState {
+            current: self.current,
+            maximum: self.maximum,
+            is_dead: self.is_dead,
+        }
+    }
+
+    fn apply(&mut self, state: Self::State) {
+        self.current = state.current;
+        self.maximum = state.maximum;
+        self.is_dead = state.is_dead;
+    }
+}
+
+impl From<&InventoryItem> for ReplicatedItem {
+    fn from(item: &InventoryItem) -> Self {
+        Self {
+            item_id: item.item_id.clone(),
+            name: item.name.clone(),
+            quantity: item.quantity,
+            max_stack: item.max_stack,
+            weight: item.weight,
+            item_type: item.item_type,
+            icon_path: item.icon_path.clone(),
+            value: item.value,
+            category: item.category.clone(),
+            is_infinite: item.is_infinite,
+        }
+    }
+}
+
+impl From<ReplicatedItem> for InventoryItem {
+    fn from(item: ReplicatedItem) -> Self {
+        Self {
+            item_id: item.item_id,
+            name: item.name,
+            quantity: item.quantity,
+            max_stack: item.max_stack,
+            weight: item.weight,
+            item_type: item.item_type,
+            icon_path: item.icon_path,
+            value: item.value,
+            category: item.category,
+            min_level: 0,
+            info: String::new(),
+            is_infinite: item.is_infinite,
+        }
+    }
+}
+
+impl Replicated for Inventory {
+    const KIND: &'static str = "inventory";
+
+    type State = InventoryState;
+
+    fn capture(&self) -> Self::State {
+        InventoryState {
+            slot_count: self.items.len(),
+            slots: self
+                .items
+                .iter()
+                .enumerate()
+                .map(|(index, slot)| (index, slot.as_ref().map(ReplicatedItem::from)))
+                .collect(),
+        }
+    }
+
+    fn apply(&mut self, state: Self::State) {
+        self.items = vec![None; state.slot_count];
+        self.apply_delta(state);
+    }
+
+    /// Only the slots whose contents changed
+    fn delta(previous: &Self::State, current: &Self::State) -> Self::State {
+        let slots = current
+            .slots
+            .iter()
+            .filter(|(index, item)| {
+                previous
+                    .slots
+                    .iter()
+                    .find(|(previous_index, _)| previous_index == index)
+                    .map_or(true, |(_, previous_item)| previous_item != item)
+            })
+            .cloned()
+            .collect();
+
+        InventoryState {
+            slot_count: current.slot_count,
+            slots,
+        }
+    }
+
+    fn apply_delta(&mut self, delta: Self::State) {
+        self.items.resize(delta.slot_count, None);
+        for (index, item) in delta.slots {
+            if let Some(slot) = self.items.get_mut(index) {
+                *slot = item.map(InventoryItem::from);
+            }
+        }
+        self.recalculate_weight();
+    }
+}
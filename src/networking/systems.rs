@@ -0,0 +1,652 @@
+use bevy::prelude::*;
+use bevy::platform::collections::HashMap;
+use crate::input::InputState;
+use crate::weapons::WeaponManager;
+use super::types::*;
+
+/// System to sync the role with the backend, advance the network tick and accept new clients
+pub fn update_network_state(
+    time: Res<Time>,
+    settings: Res<NetworkSettings>,
+    mut connection: ResMut<NetworkConnection>,
+    mut state: ResMut<NetworkState>,
+    mut event_queue: ResMut<NetworkEventQueue>,
+) {
+    state.role = connection.role();
+    state.local_client_id = connection.local_client_id();
+    state.is_tick_frame = false;
+
+    if state.role == NetworkRole::Offline {
+        state.tick_accumulator = 0.0;
+        return;
+    }
+
+    let tick_interval = 1.0 / settings.tick_rate.max(1.0);
+    state.tick_accumulator += time.delta_secs();
+    if state.tick_accumulator >= tick_interval {
+        // Drop backlog instead of bursting several ticks after a hitch
+        state.tick_accumulator = (state.tick_accumulator - tick_interval).min(tick_interval);
+        state.tick = state.tick.wrapping_add(1);
+        state.is_tick_frame = true;
+    }
+
+    if !state.role.is_authority() {
+        return;
+    }
+
+    let Some(adapter) = connection.adapter.as_mut() else {
+        return;
+    };
+    for client_id in adapter.new_connections() {
+        info!("Network: client {} connected", client_id);
+        state.full_state_requested = true;
+        event_queue.send(NetworkEvent::ClientConnected { client_id });
+    }
+}
+
+/// System to pull this frame's messages from the backend
+pub fn receive_network_messages(
+    mut connection: ResMut<NetworkConnection>,
+    mut inbox: ResMut<ReplicationInbox>,
+) {
+    inbox.0.clear();
+    if let Some(adapter) = connection.adapter.as_mut() {
+        inbox.0 = adapter.receive();
+    }
+}
+
+/// System to spawn/despawn local mirrors of replicated entities (client side)
+pub fn apply_spawn_messages(
+    mut commands: Commands,
+    state: Res<NetworkState>,
+    inbox: Res<ReplicationInbox>,
+    mut entity_map: ResMut<NetworkEntityMap>,
+    mut event_queue: ResMut<NetworkEventQueue>,
+) {
+    if !state.role.is_client() {
+        return;
+    }
+
+    for incoming in inbox.0.iter() {
+        match &incoming.message {
+            ReplicationMessage::Spawn { network_id, prefab, owner } => {
+                if entity_map.get(*network_id).is_some() {
+                    continue;
+                }
+
+                let entity = commands.spawn((
+                    *network_id,
+                    ReplicatedEntity {
+                        prefab: prefab.clone(),
+                        owner: *owner,
+                    },
+                    Transform::default(),
+                    Visibility::default(),
+                    Name::new(format!("Replicated {}", prefab)),
+                )).id();
+
+                entity_map.entities.insert(*network_id, entity);
+                event_queue.send(NetworkEvent::EntitySpawned {
+                    entity,
+                    network_id: *network_id,
+                    prefab: prefab.clone(),
+                    owner: *owner,
+                });
+            }
+            ReplicationMessage::Despawn { network_id } => {
+                let Some(entity) = entity_map.entities.remove(network_id) else {
+                    continue;
+                };
+                commands.entity(entity).despawn();
+                event_queue.send(NetworkEvent::EntityDespawned {
+                    entity,
+                    network_id: *network_id,
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// System to apply incoming `T` states (client side)
+pub fn apply_replicated_component<T: Replicated>(
+    state: Res<NetworkState>,
+    inbox: Res<ReplicationInbox>,
+    entity_map: Res<NetworkEntityMap>,
+    mut query: Query<(&mut T, Option<&PredictedCharacter>, &ReplicatedEntity)>,
+) {
+    if !state.role.is_client() {
+        return;
+    }
+
+    for incoming in inbox.0.iter() {
+        let ReplicationMessage::Component { network_id, kind, full, payload, .. } = &incoming.message else {
+            continue;
+        };
+        if kind != T::KIND {
+            continue;
+        }
+
+        let Some(entity) = entity_map.get(*network_id) else {
+            continue;
+        };
+        let Ok((mut component, predicted, replicated)) = query.get_mut(entity) else {
+            continue;
+        };
+
+        // Our own predicted character is corrected through input acks instead
+        let locally_predicted = predicted.is_some_and(|predicted| predicted.enabled)
+            && replicated.owner == Some(state.local_client_id);
+        if T::PREDICTED && locally_predicted {
+            continue;
+        }
+
+        let decoded: T::State = match serde_json::from_slice(payload) {
+            Ok(decoded) => decoded,
+            Err(error) => {
+                warn!("Network: failed to decode {} for {:?}: {}", T::KIND, network_id, error);
+                continue;
+            }
+        };
+
+        if *full {
+            component.apply(decoded);
+        } else {
+            component.apply_delta(decoded);
+        }
+    }
+}
+
+/// System to apply authoritative input acks to predicted characters (client side)
+pub fn reconcile_predicted_characters(
+    state: Res<NetworkState>,
+    inbox: Res<ReplicationInbox>,
+    entity_map: Res<NetworkEntityMap>,
+    mut event_queue: ResMut<NetworkEventQueue>,
+    mut query: Query<(&mut Transform, &mut PredictedCharacter)>,
+) {
+    if !state.role.is_client() {
+        return;
+    }
+
+    for incoming in inbox.0.iter() {
+        let ReplicationMessage::InputAck { network_id, input_tick, position } = &incoming.message else {
+            continue;
+        };
+        let Some(entity) = entity_map.get(*network_id) else {
+            continue;
+        };
+        let Ok((mut transform, mut predicted)) = query.get_mut(entity) else {
+            continue;
+        };
+        if !predicted.enabled || *input_tick <= predicted.last_acknowledged_tick {
+            continue;
+        }
+        predicted.last_acknowledged_tick = *input_tick;
+
+        let Some(acknowledged) = predicted.history.iter().find(|entry| entry.input.tick == *input_tick).copied() else {
+            continue;
+        };
+        predicted.history.retain(|entry| entry.input.tick > *input_tick);
+
+        let error = *position - acknowledged.position;
+        predicted.last_error = error.length();
+        if predicted.last_error <= predicted.correction_threshold {
+            continue;
+        }
+
+        // Moves after the acked one were simulated from the wrong origin:
+        // shift them (and the character) by the error instead of re-simulating.
+        transform.translation += error;
+        for entry in predicted.history.iter_mut() {
+            entry.position += error;
+        }
+        if predicted.last_error < predicted.snap_threshold {
+            predicted.correction_offset -= error;
+        } else {
+            predicted.correction_offset = Vec3::ZERO;
+        }
+        predicted.corrections += 1;
+
+        event_queue.send(NetworkEvent::PredictionCorrected {
+            entity,
+            tick: *input_tick,
+            error,
+        });
+    }
+}
+
+/// System to decay the visual correction offset of predicted characters
+pub fn decay_prediction_offsets(time: Res<Time>, mut query: Query<&mut PredictedCharacter>) {
+    for mut predicted in query.iter_mut() {
+        if predicted.correction_offset == Vec3::ZERO {
+            continue;
+        }
+        let t = (time.delta_secs() * predicted.correction_smoothing).min(1.0);
+        predicted.correction_offset = predicted.correction_offset.lerp(Vec3::ZERO, t);
+        if predicted.correction_offset.length_squared() < 1e-6 {
+            predicted.correction_offset = Vec3::ZERO;
+        }
+    }
+}
+
+/// System to feed client inputs into the server-side characters they own
+pub fn apply_client_inputs(
+    state: Res<NetworkState>,
+    inbox: Res<ReplicationInbox>,
+    entity_map: Res<NetworkEntityMap>,
+    mut query: Query<(&ReplicatedEntity, &mut InputState, Option<&mut NetworkInputAck>)>,
+    mut commands: Commands,
+) {
+    if !state.role.is_authority() {
+        return;
+    }
+
+    for incoming in inbox.0.iter() {
+        let ReplicationMessage::ClientInput { network_id, input } = &incoming.message else {
+            continue;
+        };
+        let Some(entity) = entity_map.get(*network_id) else {
+            continue;
+        };
+        let Ok((replicated, mut input_state, ack)) = query.get_mut(entity) else {
+            continue;
+        };
+        if replicated.owner != Some(incoming.sender) {
+            warn!("Network: client {} sent input for {:?} it does not own", incoming.sender, network_id);
+            continue;
+        }
+
+        input_state.movement = input.movement;
+        input_state.look = input.look;
+        input_state.jump_pressed = input.jump;
+        input_state.crouch_pressed = input.crouch;
+        input_state.sprint_pressed = input.sprint;
+        input_state.fire_pressed = input.fire;
+
+        match ack {
+            Some(mut ack) => {
+                if input.tick > ack.input_tick {
+                    ack.input_tick = input.tick;
+                    ack.pending = true;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(NetworkInputAck {
+                    input_tick: input.tick,
+                    pending: true,
+                });
+            }
+        }
+    }
+}
+
+/// System to turn remote weapon fire messages into local events (client side)
+pub fn apply_remote_weapon_fire(
+    state: Res<NetworkState>,
+    inbox: Res<ReplicationInbox>,
+    entity_map: Res<NetworkEntityMap>,
+    mut event_queue: ResMut<NetworkEventQueue>,
+) {
+    if !state.role.is_client() {
+        return;
+    }
+
+    for incoming in inbox.0.iter() {
+        let ReplicationMessage::WeaponFired { network_id, weapon_index, origin, direction, .. } = &incoming.message else {
+            continue;
+        };
+        let Some(entity) = entity_map.get(*network_id) else {
+            continue;
+        };
+        event_queue.send(NetworkEvent::RemoteWeaponFired {
+            entity,
+            weapon_index: *weapon_index,
+            origin: *origin,
+            direction: *direction,
+        });
+    }
+}
+
+/// System to give new replicated entities a network id and announce them (server side)
+pub fn assign_network_ids(
+    mut commands: Commands,
+    mut state: ResMut<NetworkState>,
+    mut entity_map: ResMut<NetworkEntityMap>,
+    mut outbox: ResMut<ReplicationOutbox>,
+    new_query: Query<(Entity, &ReplicatedEntity), Without<NetworkId>>,
+    existing_query: Query<(&NetworkId, &ReplicatedEntity)>,
+) {
+    if !state.role.is_authority() {
+        return;
+    }
+
+    // Late joiners need every existing entity
+    if state.full_state_requested && state.is_tick_frame {
+        for (network_id, replicated) in existing_query.iter() {
+            outbox.0.push((MessageTarget::AllClients, ReplicationMessage::Spawn {
+                network_id: *network_id,
+                prefab: replicated.prefab.clone(),
+                owner: replicated.owner,
+            }));
+        }
+    }
+
+    for (entity, replicated) in new_query.iter() {
+        state.next_network_id += 1;
+        let network_id = NetworkId(state.next_network_id);
+
+        commands.entity(entity).insert(network_id);
+        entity_map.entities.insert(network_id, entity);
+        outbox.0.push((MessageTarget::AllClients, ReplicationMessage::Spawn {
+            network_id,
+            prefab: replicated.prefab.clone(),
+            owner: replicated.owner,
+        }));
+    }
+}
+
+/// System to announce despawned replicated entities (server side)
+pub fn replicate_despawns(
+    state: Res<NetworkState>,
+    mut removed: RemovedComponents<ReplicatedEntity>,
+    mut entity_map: ResMut<NetworkEntityMap>,
+    mut outbox: ResMut<ReplicationOutbox>,
+) {
+    if !state.role.is_authority() {
+        removed.clear();
+        return;
+    }
+
+    for entity in removed.read() {
+        let Some(network_id) = entity_map.network_id_of(entity) else {
+            continue;
+        };
+        entity_map.entities.remove(&network_id);
+        outbox.0.push((MessageTarget::AllClients, ReplicationMessage::Despawn { network_id }));
+    }
+}
+
+type ReplicatedParts<'a, T> = (Entity, &'a NetworkId, &'a T, Option<&'a mut ReplicationBaseline<T>>);
+
+/// System to send changed `T` states on network ticks (server side)
+pub fn replicate_component<T: Replicated>(
+    mut commands: Commands,
+    state: Res<NetworkState>,
+    settings: Res<NetworkSettings>,
+    mut outbox: ResMut<ReplicationOutbox>,
+    mut query: Query<ReplicatedParts<T>, With<ReplicatedEntity>>,
+) {
+    if !state.role.is_authority() || !state.is_tick_frame || !settings.replicates(T::KIND) {
+        return;
+    }
+
+    let full_tick = state.is_full_state_tick(&settings);
+
+    for (entity, network_id, component, baseline) in query.iter_mut() {
+        let current = component.capture();
+
+        let (full, outgoing) = match baseline {
+            Some(mut baseline) => {
+                if !full_tick && baseline.state == current {
+                    continue;
+                }
+                let outgoing = if full_tick { current.clone() } else { T::delta(&baseline.state, &current) };
+                baseline.state = current;
+                baseline.tick = state.tick;
+                (full_tick, outgoing)
+            }
+            None => {
+                commands.entity(entity).insert(ReplicationBaseline::<T> {
+                    state: current.clone(),
+                    tick: state.tick,
+                });
+                (true, current)
+            }
+        };
+
+        let payload = match serde_json::to_vec(&outgoing) {
+            Ok(payload) => payload,
+            Err(error) => {
+                warn!("Network: failed to encode {} for {:?}: {}", T::KIND, network_id, error);
+                continue;
+            }
+        };
+
+        outbox.0.push((MessageTarget::AllClients, ReplicationMessage::Component {
+            network_id: *network_id,
+            tick: state.tick,
+            kind: T::KIND.to_string(),
+            full,
+            payload,
+        }));
+    }
+}
+
+/// System to broadcast weapon fire of replicated entities (server side)
+pub fn replicate_weapon_fire(
+    state: Res<NetworkState>,
+    settings: Res<NetworkSettings>,
+    mut outbox: ResMut<ReplicationOutbox>,
+    query: Query<(Entity, &NetworkId, &ReplicatedEntity, &WeaponManager, &GlobalTransform)>,
+    mut last_fired: Local<HashMap<Entity, f32>>,
+) {
+    if !state.role.is_authority() || !settings.replicate_weapon_fire {
+        return;
+    }
+
+    for (entity, network_id, replicated, manager, transform) in query.iter() {
+        let previous = last_fired.insert(entity, manager.last_time_fired);
+        if previous.is_none_or(|previous| previous == manager.last_time_fired) {
+            continue;
+        }
+
+        // The owner already played its own shot locally
+        let target = match replicated.owner {
+            Some(owner) => MessageTarget::AllClientsExcept(owner),
+            None => MessageTarget::AllClients,
+        };
+        outbox.0.push((target, ReplicationMessage::WeaponFired {
+            network_id: *network_id,
+            tick: state.tick,
+            weapon_index: manager.current_index,
+            origin: transform.translation(),
+            direction: *transform.forward(),
+        }));
+    }
+
+    last_fired.retain(|entity, _| query.contains(*entity));
+}
+
+/// System to record local inputs for prediction and send them to the server (client side)
+pub fn record_predicted_inputs(
+    state: Res<NetworkState>,
+    mut outbox: ResMut<ReplicationOutbox>,
+    mut query: Query<(&NetworkId, &ReplicatedEntity, &InputState, &Transform, &mut PredictedCharacter)>,
+) {
+    if !state.role.is_client() || !state.is_tick_frame {
+        return;
+    }
+
+    for (network_id, replicated, input_state, transform, mut predicted) in query.iter_mut() {
+        if !predicted.enabled || replicated.owner != Some(state.local_client_id) {
+            continue;
+        }
+
+        let input = PredictedInput {
+            tick: state.tick,
+            movement: input_state.movement,
+            look: input_state.look,
+            jump: input_state.jump_pressed,
+            crouch: input_state.crouch_pressed,
+            sprint: input_state.sprint_pressed,
+            fire: input_state.fire_pressed,
+        };
+
+        predicted.history.push_back(PredictedMove {
+            input,
+            position: transform.translation,
+        });
+        while predicted.history.len() > predicted.max_history {
+            predicted.history.pop_front();
+        }
+
+        outbox.0.push((MessageTarget::Server, ReplicationMessage::ClientInput {
+            network_id: *network_id,
+            input,
+        }));
+    }
+}
+
+/// System to tell owning clients where their characters ended up (server side)
+pub fn acknowledge_client_inputs(
+    state: Res<NetworkState>,
+    mut outbox: ResMut<ReplicationOutbox>,
+    mut query: Query<(&NetworkId, &ReplicatedEntity, &Transform, &mut NetworkInputAck)>,
+) {
+    if !state.role.is_authority() || !state.is_tick_frame {
+        return;
+    }
+
+    for (network_id, replicated, transform, mut ack) in query.iter_mut() {
+        if !ack.pending {
+            continue;
+        }
+        let Some(owner) = replicated.owner else {
+            continue;
+        };
+        ack.pending = false;
+
+        outbox.0.push((MessageTarget::Client(owner), ReplicationMessage::InputAck {
+            network_id: *network_id,
+            input_tick: ack.input_tick,
+            position: transform.translation,
+        }));
+    }
+}
+
+/// System to hand queued messages to the backend
+pub fn flush_network_messages(
+    mut connection: ResMut<NetworkConnection>,
+    mut state: ResMut<NetworkState>,
+    mut outbox: ResMut<ReplicationOutbox>,
+) {
+    if state.is_tick_frame {
+        state.full_state_requested = false;
+    }
+
+    let Some(adapter) = connection.adapter.as_mut() else {
+        outbox.0.clear();
+        return;
+    };
+    for (target, message) in outbox.0.drain(..) {
+        adapter.send(target, &message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use crate::combat::Health;
+    use crate::networking::NetworkingPlugin;
+
+    #[derive(Default)]
+    struct Wire {
+        to_server: Vec<IncomingMessage>,
+        to_client: Vec<IncomingMessage>,
+        connected: bool,
+    }
+
+    /// In-memory transport between one server and one client
+    struct Loopback {
+        wire: Arc<Mutex<Wire>>,
+        role: NetworkRole,
+        client_id: ClientId,
+    }
+
+    impl NetworkAdapter for Loopback {
+        fn role(&self) -> NetworkRole {
+            self.role
+        }
+
+        fn local_client_id(&self) -> ClientId {
+            self.client_id
+        }
+
+        fn send(&mut self, target: MessageTarget, message: &ReplicationMessage) {
+            let incoming = IncomingMessage { sender: self.client_id, message: message.clone() };
+            let mut wire = self.wire.lock().unwrap();
+            match target {
+                MessageTarget::Server => wire.to_server.push(incoming),
+                MessageTarget::AllClients | MessageTarget::Client(1) => wire.to_client.push(incoming),
+                MessageTarget::AllClientsExcept(client) if client != 1 => wire.to_client.push(incoming),
+                _ => {}
+            }
+        }
+
+        fn receive(&mut self) -> Vec<IncomingMessage> {
+            let mut wire = self.wire.lock().unwrap();
+            if self.role.is_authority() {
+                std::mem::take(&mut wire.to_server)
+            } else {
+                std::mem::take(&mut wire.to_client)
+            }
+        }
+
+        fn new_connections(&mut self) -> Vec<ClientId> {
+            let mut wire = self.wire.lock().unwrap();
+            if std::mem::replace(&mut wire.connected, true) { Vec::new() } else { vec![1] }
+        }
+    }
+
+    fn networked_app(adapter: Loopback) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(NetworkingPlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(50)));
+        app.world_mut().resource_mut::<NetworkConnection>().connect(adapter);
+        app
+    }
+
+    fn round(server: &mut App, client: &mut App) {
+        for _ in 0..3 {
+            server.update();
+            client.update();
+        }
+    }
+
+    #[test]
+    fn test_server_state_replicates_to_client() {
+        let wire = Arc::new(Mutex::new(Wire::default()));
+        let mut server = networked_app(Loopback { wire: wire.clone(), role: NetworkRole::Server, client_id: SERVER_CLIENT_ID });
+        let mut client = networked_app(Loopback { wire, role: NetworkRole::Client, client_id: 1 });
+
+        let barrel = server.world_mut().spawn((
+            ReplicatedEntity { prefab: "barrel".into(), owner: None },
+            Transform::from_xyz(1.0, 2.0, 3.0),
+            Health::default(),
+        )).id();
+        round(&mut server, &mut client);
+
+        let network_id = *server.world().get::<NetworkId>(barrel).unwrap();
+        let mirror = client.world().resource::<NetworkEntityMap>().get(network_id).unwrap();
+        assert_eq!(client.world().get::<ReplicatedEntity>(mirror).unwrap().prefab, "barrel");
+        assert_eq!(client.world().get::<Transform>(mirror).unwrap().translation, Vec3::new(1.0, 2.0, 3.0));
+
+        // Game code builds the local components from the prefab, later states update them
+        client.world_mut().entity_mut(mirror).insert(Health::default());
+        server.world_mut().get_mut::<Transform>(barrel).unwrap().translation = Vec3::new(4.0, 5.0, 6.0);
+        server.world_mut().get_mut::<Health>(barrel).unwrap().current = 40.0;
+        round(&mut server, &mut client);
+        assert_eq!(client.world().get::<Transform>(mirror).unwrap().translation, Vec3::new(4.0, 5.0, 6.0));
+        assert_eq!(client.world().get::<Health>(mirror).unwrap().current, 40.0);
+
+        server.world_mut().despawn(barrel);
+        round(&mut server, &mut client);
+        assert!(client.world().get_entity(mirror).is_err());
+        assert!(client.world().resource::<NetworkEntityMap>().get(network_id).is_none());
+    }
+}
@@ -0,0 +1,395 @@
+use std::collections::VecDeque;
+use bevy::prelude::*;
+use bevy::ecs::component::Mutable;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use crate::utils::EventQueue;
+
+/// Connection identifier handed out by the backend
+pub type ClientId = u64;
+
+/// Client id used by the server itself (and by listen servers for their local player)
+pub const SERVER_CLIENT_ID: ClientId = 0;
+
+/// Role of this app in the session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum NetworkRole {
+    /// No session; replication systems do nothing
+    #[default]
+    Offline,
+    /// Dedicated server
+    Server,
+    /// Server with a local player
+    ListenServer,
+    Client,
+}
+
+impl NetworkRole {
+    pub fn is_authority(&self) -> bool {
+        matches!(self, NetworkRole::Server | NetworkRole::ListenServer)
+    }
+
+    pub fn is_client(&self) -> bool {
+        matches!(self, NetworkRole::Client)
+    }
+}
+
+/// Stable id of a replicated entity, shared by server and clients
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+#[reflect(Component)]
+pub struct NetworkId(pub u64);
+
+/// Marks an entity for replication (server side)
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ReplicatedEntity {
+    /// Name clients use to build the entity's visuals and local components
+    pub prefab: String,
+    /// Owning client (None = server owned)
+    pub owner: Option<ClientId>,
+}
+
+impl Default for ReplicatedEntity {
+    fn default() -> Self {
+        Self {
+            prefab: String::new(),
+            owner: None,
+        }
+    }
+}
+
+/// Component state that can be sent over the wire.
+///
+/// Implemented here for `Transform`, `Health` and `Inventory`; game code can
+/// implement it for its own components and add
+/// `replicate_component::<T>` / `apply_replicated_component::<T>` to its app.
+pub trait Replicated: Component<Mutability = Mutable> + Sized {
+    /// Unique name of the component on the wire
+    const KIND: &'static str;
+    /// Locally predicted components are not overwritten on predicted entities
+    const PREDICTED: bool = false;
+
+    type State: Serialize + DeserializeOwned + Clone + PartialEq + Send + Sync + 'static;
+
+    fn capture(&self) -> Self::State;
+
+    fn apply(&mut self, state: Self::State);
+
+    /// Smallest state to send given what the peers already have; full state by default
+    fn delta(_previous: &Self::State, current: &Self::State) -> Self::State {
+        current.clone()
+    }
+
+    fn apply_delta(&mut self, delta: Self::State) {
+        self.apply(delta);
+    }
+}
+
+/// Last state sent for a replicated component (server side)
+#[derive(Component, Debug)]
+pub struct ReplicationBaseline<T: Replicated> {
+    pub state: T::State,
+    pub tick: u32,
+}
+
+/// Transform replication state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransformState {
+    pub translation: Vec3,
+    pub rotation: Quat,
+}
+
+/// Health replication state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthState {
+    pub current: f32,
+    pub maximum: f32,
+    pub is_dead: bool,
+}
+
+/// Inventory slot on the wire
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplicatedItem {
+    pub item_id: String,
+    pub name: String,
+    pub quantity: i32,
+    pub max_stack: i32,
+    pub weight: f32,
+    pub item_type: crate::inventory::ItemType,
+    pub icon_path: String,
+    pub value: f32,
+    pub category: String,
+    pub is_infinite: bool,
+}
+
+/// Inventory replication state: `(slot, contents)` pairs.
+/// Full captures list every slot, deltas only the slots that changed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InventoryState {
+    pub slot_count: usize,
+    pub slots: Vec<(usize, Option<ReplicatedItem>)>,
+}
+
+/// Message exchanged between server and clients
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReplicationMessage {
+    Spawn {
+        network_id: NetworkId,
+        prefab: String,
+        owner: Option<ClientId>,
+    },
+    Despawn {
+        network_id: NetworkId,
+    },
+    /// Serialized `Replicated::State`
+    Component {
+        network_id: NetworkId,
+        tick: u32,
+        kind: String,
+        /// False when `payload` is a delta
+        full: bool,
+        payload: Vec<u8>,
+    },
+    WeaponFired {
+        network_id: NetworkId,
+        tick: u32,
+        weapon_index: usize,
+        origin: Vec3,
+        direction: Vec3,
+    },
+    /// Client input for its predicted character
+    ClientInput {
+        network_id: NetworkId,
+        input: PredictedInput,
+    },
+    /// Authoritative position after the server applied a client input
+    InputAck {
+        network_id: NetworkId,
+        input_tick: u32,
+        position: Vec3,
+    },
+}
+
+/// Who a message is addressed to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageTarget {
+    Server,
+    AllClients,
+    Client(ClientId),
+    AllClientsExcept(ClientId),
+}
+
+/// Message received from the backend
+#[derive(Debug, Clone)]
+pub struct IncomingMessage {
+    pub sender: ClientId,
+    pub message: ReplicationMessage,
+}
+
+/// Transport backend (bevy_replicon, renet, Steam...).
+///
+/// The adapter only moves messages; ordering/reliability is up to the backend.
+/// Component updates tolerate loss, spawn/despawn/inventory should be reliable.
+pub trait NetworkAdapter: Send + Sync + 'static {
+    fn role(&self) -> NetworkRole;
+
+    fn local_client_id(&self) -> ClientId;
+
+    fn send(&mut self, target: MessageTarget, message: &ReplicationMessage);
+
+    fn receive(&mut self) -> Vec<IncomingMessage>;
+
+    /// Clients connected since the last call (server side)
+    fn new_connections(&mut self) -> Vec<ClientId> {
+        Vec::new()
+    }
+}
+
+/// Active network backend
+#[derive(Resource, Default)]
+pub struct NetworkConnection {
+    pub adapter: Option<Box<dyn NetworkAdapter>>,
+}
+
+impl NetworkConnection {
+    pub fn connect(&mut self, adapter: impl NetworkAdapter) {
+        self.adapter = Some(Box::new(adapter));
+    }
+
+    pub fn disconnect(&mut self) {
+        self.adapter = None;
+    }
+
+    pub fn role(&self) -> NetworkRole {
+        self.adapter.as_ref().map(|adapter| adapter.role()).unwrap_or_default()
+    }
+
+    pub fn local_client_id(&self) -> ClientId {
+        self.adapter.as_ref().map(|adapter| adapter.local_client_id()).unwrap_or(SERVER_CLIENT_ID)
+    }
+}
+
+/// Replication settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct NetworkSettings {
+    /// Network ticks per second
+    pub tick_rate: f32,
+    /// Send a full state instead of a delta every N ticks
+    pub full_state_interval: u32,
+    pub replicate_transforms: bool,
+    pub replicate_health: bool,
+    pub replicate_inventory: bool,
+    pub replicate_weapon_fire: bool,
+}
+
+impl NetworkSettings {
+    pub fn replicates(&self, kind: &str) -> bool {
+        match kind {
+            "transform" => self.replicate_transforms,
+            "health" => self.replicate_health,
+            "inventory" => self.replicate_inventory,
+            _ => true,
+        }
+    }
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            tick_rate: 30.0,
+            full_state_interval: 60,
+            replicate_transforms: true,
+            replicate_health: true,
+            replicate_inventory: true,
+            replicate_weapon_fire: true,
+        }
+    }
+}
+
+/// Network tick and per-frame session state
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct NetworkState {
+    pub role: NetworkRole,
+    pub local_client_id: ClientId,
+    pub tick: u32,
+    /// True on frames where a network tick happens
+    pub is_tick_frame: bool,
+    pub tick_accumulator: f32,
+    pub next_network_id: u64,
+    /// Send full states on the next tick (e.g. a client just joined)
+    pub full_state_requested: bool,
+}
+
+impl NetworkState {
+    pub fn is_full_state_tick(&self, settings: &NetworkSettings) -> bool {
+        self.full_state_requested
+            || (settings.full_state_interval > 0 && self.tick % settings.full_state_interval == 0)
+    }
+}
+
+/// Network id to local entity map
+#[derive(Resource, Debug, Default)]
+pub struct NetworkEntityMap {
+    pub entities: bevy::platform::collections::HashMap<NetworkId, Entity>,
+}
+
+impl NetworkEntityMap {
+    pub fn get(&self, network_id: NetworkId) -> Option<Entity> {
+        self.entities.get(&network_id).copied()
+    }
+
+    pub fn network_id_of(&self, entity: Entity) -> Option<NetworkId> {
+        self.entities.iter().find(|(_, e)| **e == entity).map(|(id, _)| *id)
+    }
+}
+
+/// Messages received this frame
+#[derive(Resource, Default)]
+pub struct ReplicationInbox(pub Vec<IncomingMessage>);
+
+/// Messages to send at the end of the frame
+#[derive(Resource, Default)]
+pub struct ReplicationOutbox(pub Vec<(MessageTarget, ReplicationMessage)>);
+
+/// Input sample recorded for client-side prediction
+#[derive(Debug, Clone, Copy, PartialEq, Reflect, Serialize, Deserialize, Default)]
+pub struct PredictedInput {
+    pub tick: u32,
+    pub movement: Vec2,
+    pub look: Vec2,
+    pub jump: bool,
+    pub crouch: bool,
+    pub sprint: bool,
+    pub fire: bool,
+}
+
+/// Locally simulated move waiting for server acknowledgement
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct PredictedMove {
+    pub input: PredictedInput,
+    /// Position after simulating this input locally
+    pub position: Vec3,
+}
+
+/// Character simulated locally ahead of the server
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct PredictedCharacter {
+    pub enabled: bool,
+    pub max_history: usize,
+    /// Position error above which the client is corrected
+    pub correction_threshold: f32,
+    /// Errors above this snap instantly instead of blending
+    pub snap_threshold: f32,
+    /// Speed at which the visual correction offset decays
+    pub correction_smoothing: f32,
+
+    // Debug State
+    pub history: VecDeque<PredictedMove>,
+    pub last_acknowledged_tick: u32,
+    pub correction_offset: Vec3,
+    pub last_error: f32,
+    pub corrections: u32,
+}
+
+impl Default for PredictedCharacter {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_history: 128,
+            correction_threshold: 0.05,
+            snap_threshold: 3.0,
+            correction_smoothing: 10.0,
+            history: VecDeque::new(),
+            last_acknowledged_tick: 0,
+            correction_offset: Vec3::ZERO,
+            last_error: 0.0,
+            corrections: 0,
+        }
+    }
+}
+
+/// Last client input the server applied to this entity
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct NetworkInputAck {
+    pub input_tick: u32,
+    pub pending: bool,
+}
+
+/// Network event data
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetworkEvent {
+    ClientConnected { client_id: ClientId },
+    /// A replicated entity appeared on this client; build its visuals from `prefab`
+    EntitySpawned { entity: Entity, network_id: NetworkId, prefab: String, owner: Option<ClientId> },
+    EntityDespawned { entity: Entity, network_id: NetworkId },
+    /// A remote entity fired; play effects only, damage is server side
+    RemoteWeaponFired { entity: Entity, weapon_index: usize, origin: Vec3, direction: Vec3 },
+    /// The server disagreed with a predicted position
+    PredictionCorrected { entity: Entity, tick: u32, error: Vec3 },
+}
+
+/// Custom queue for network events
+pub type NetworkEventQueue = EventQueue<NetworkEvent>;
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Trigger animation events on enter/exit.
 ///
@@ -27,15 +28,19 @@ pub struct AnimatorTriggerEnterEvent {
     pub other: Entity,
 }
 
+pub type AnimatorTriggerEnterEventQueue = EventQueue<AnimatorTriggerEnterEvent>;
+
 #[derive(Event, Debug)]
 pub struct AnimatorTriggerExitEvent {
     pub entity: Entity,
     pub other: Entity,
 }
 
+pub type AnimatorTriggerExitEventQueue = EventQueue<AnimatorTriggerExitEvent>;
+
 pub fn update_animator_trigger_enter_exit_event(
-    mut enter_events: EventReader<AnimatorTriggerEnterEvent>,
-    mut exit_events: EventReader<AnimatorTriggerExitEvent>,
+    mut enter_events: QueueReader<AnimatorTriggerEnterEvent>,
+    mut exit_events: QueueReader<AnimatorTriggerExitEvent>,
     query: Query<&AnimatorTriggerEnterExitEvent>,
 ) {
     for event in enter_events.read() {
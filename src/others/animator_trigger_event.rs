@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Triggers an animation event on demand.
 ///
@@ -22,8 +23,10 @@ pub struct AnimatorTriggerEventRequest {
     pub entity: Entity,
 }
 
+pub type AnimatorTriggerEventRequestQueue = EventQueue<AnimatorTriggerEventRequest>;
+
 pub fn update_animator_trigger_event(
-    mut events: EventReader<AnimatorTriggerEventRequest>,
+    mut events: QueueReader<AnimatorTriggerEventRequest>,
     query: Query<&AnimatorTriggerEvent>,
 ) {
     for event in events.read() {
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Displays console log lines on screen.
 ///
@@ -24,8 +25,10 @@ pub struct ConsoleLogEvent {
     pub message: String,
 }
 
+pub type ConsoleLogEventQueue = EventQueue<ConsoleLogEvent>;
+
 pub fn update_console_log_on_screen_system(
-    mut events: EventReader<ConsoleLogEvent>,
+    mut events: QueueReader<ConsoleLogEvent>,
     mut query: Query<&mut ConsoleLogOnScreenSystem>,
 ) {
     for event in events.read() {
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::EventQueue;
 
 /// Emits events when a raycast hits an object.
 ///
@@ -25,6 +26,8 @@ pub struct RaycastObjectFoundEvent {
     pub target: Entity,
 }
 
+pub type RaycastObjectFoundEventQueue = EventQueue<RaycastObjectFoundEvent>;
+
 pub fn update_event_object_found_on_raycast_system(
     _query: Query<&EventObjectFoundOnRaycastSystem>,
 ) {
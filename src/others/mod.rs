@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::EventQueuePlugin;
 
 pub mod add_force_to_object_system;
 pub mod animator_trigger_enter_exit_event;
@@ -65,18 +66,20 @@ pub use add_force_to_object_system::AddForceToObjectSystem;
 pub use animator_trigger_enter_exit_event::{
     AnimatorTriggerEnterExitEvent,
     AnimatorTriggerEnterEvent,
+    AnimatorTriggerEnterEventQueue,
     AnimatorTriggerExitEvent,
+    AnimatorTriggerExitEventQueue,
 };
-pub use animator_trigger_event::{AnimatorTriggerEvent, AnimatorTriggerEventRequest};
+pub use animator_trigger_event::{AnimatorTriggerEvent, AnimatorTriggerEventRequest, AnimatorTriggerEventRequestQueue};
 pub use audio_source_info::AudioSourceInfo;
 pub use bezier_spline::BezierSpline;
 pub use change_object_colors::ChangeObjectColors;
 pub use check_collision_type::{CheckCollisionType, CollisionType};
-pub use console_log_on_screen_system::{ConsoleLogEvent, ConsoleLogOnScreenSystem};
+pub use console_log_on_screen_system::{ConsoleLogEvent, ConsoleLogEventQueue, ConsoleLogOnScreenSystem};
 pub use console_mode::ConsoleMode;
 pub use destroy_game_object::DestroyGameObject;
 pub use dissolve_object::DissolveObject;
-pub use event_object_found_on_raycast_system::{EventObjectFoundOnRaycastSystem, RaycastObjectFoundEvent};
+pub use event_object_found_on_raycast_system::{EventObjectFoundOnRaycastSystem, RaycastObjectFoundEvent, RaycastObjectFoundEventQueue};
 pub use fade_object::FadeObject;
 pub use features_manager::FeaturesManager;
 pub use flying_turret_system::FlyingTurretSystem;
@@ -95,26 +98,26 @@ pub use open_initial_pop_up_window::OpenInitialPopUpWindow;
 pub use pause_animation_system::PauseAnimationSystem;
 pub use place_object_in_camera_editor_position_system::PlaceObjectInCameraEditorPositionSystem;
 pub use player_options_editor_system::PlayerOptionsEditorSystem;
-pub use play_sound_on_collision::{PlaySoundOnCollision, CollisionSoundEvent};
-pub use play_sound_on_trigger_enter::{PlaySoundOnTriggerEnter, TriggerSoundEvent};
+pub use play_sound_on_collision::{PlaySoundOnCollision, CollisionSoundEvent, CollisionSoundEventQueue};
+pub use play_sound_on_trigger_enter::{PlaySoundOnTriggerEnter, TriggerSoundEvent, TriggerSoundEventQueue};
 pub use put_gear::PutGear;
 pub use rail_mechanism::RailMechanism;
 pub use replace_material_system::ReplaceMaterialSystem;
 pub use rotate_objects::RotateObjects;
 pub use rotatory_gear::RotatoryGear;
 pub use scan_element_info::ScanElementInfo;
-pub use scanner_system::{ScannerPingEvent, ScannerSystem};
+pub use scanner_system::{ScannerPingEvent, ScannerPingEventQueue, ScannerSystem};
 pub use set_fixed_rotation::SetFixedRotation;
 pub use set_game_object_active_state::SetGameObjectActiveState;
 pub use set_object_parent_system::SetObjectParentSystem;
 pub use set_object_scale_system::SetObjectScaleSystem;
 pub use set_rigidbody_state_system::SetRigidbodyStateSystem;
 pub use show_game_info_hud::ShowGameInfoHud;
-pub use show_message_on_hud_system::{ShowHudMessageEvent, ShowMessageOnHudSystem};
-pub use simple_action_button::{SimpleActionButton, SimpleActionButtonEvent};
+pub use show_message_on_hud_system::{ShowHudMessageEvent, ShowHudMessageEventQueue, ShowMessageOnHudSystem};
+pub use simple_action_button::{SimpleActionButton, SimpleActionButtonEvent, SimpleActionButtonEventQueue};
 pub use simple_animation_system::SimpleAnimationSystem;
 pub use simple_audio_play::SimpleAudioPlay;
-pub use simple_event_system::{SimpleEvent, SimpleEventSystem};
+pub use simple_event_system::{SimpleEvent, SimpleEventQueue, SimpleEventSystem};
 pub use simple_fps_counter::SimpleFpsCounter;
 pub use simple_lamp::SimpleLamp;
 pub use simple_lens_flare_system::SimpleLensFlareSystem;
@@ -124,25 +127,86 @@ pub use spawn_object::SpawnObject;
 pub use spline_walker::SplineWalker;
 pub use tag_layer_system::TagLayerSystem;
 pub use task_counter_system::TaskCounterSystem;
-pub use ui_mouse_hover_event::{UIMouseHoverEvent, UIMouseHoverChangedEvent};
+pub use ui_mouse_hover_event::{UIMouseHoverEvent, UIMouseHoverChangedEvent, UIMouseHoverChangedEventQueue};
 
 pub struct OthersPlugin;
 
 impl Plugin for OthersPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<FeaturesManager>()
-            .add_event::<AnimatorTriggerEnterEvent>()
-            .add_event::<AnimatorTriggerExitEvent>()
-            .add_event::<AnimatorTriggerEventRequest>()
-            .add_event::<ConsoleLogEvent>()
-            .add_event::<RaycastObjectFoundEvent>()
-            .add_event::<CollisionSoundEvent>()
-            .add_event::<TriggerSoundEvent>()
-            .add_event::<ShowHudMessageEvent>()
-            .add_event::<SimpleActionButtonEvent>()
-            .add_event::<SimpleEvent>()
-            .add_event::<UIMouseHoverChangedEvent>()
-            .add_event::<ScannerPingEvent>()
+        app
+            .register_type::<FollowObjectPositionSystem>()
+            .register_type::<IgnoreCollisionHelper>()
+            .register_type::<ChangeObjectColors>()
+            .register_type::<SimpleSetQuaternionRotation>()
+            .register_type::<HideBodyPartOnCharacterSystem>()
+            .register_type::<RotateObjects>()
+            .register_type::<HeadExplodeExample>()
+            .register_type::<PlaySoundOnTriggerEnter>()
+            .register_type::<PutGear>()
+            .register_type::<PlayerOptionsEditorSystem>()
+            .register_type::<IgnoreCollisionSystem>()
+            .register_type::<AnimatorTriggerEnterExitEvent>()
+            .register_type::<TagLayerSystem>()
+            .register_type::<SetRigidbodyStateSystem>()
+            .register_type::<ShowMessageOnHudSystem>()
+            .register_type::<SimpleAnimationSystem>()
+            .register_type::<BezierSpline>()
+            .register_type::<PlaceObjectInCameraEditorPositionSystem>()
+            .register_type::<FadeObject>()
+            .register_type::<SimpleActionButton>()
+            .register_type::<SimpleLensFlareSystem>()
+            .register_type::<ScannerSystem>()
+            .register_type::<ConsoleMode>()
+            .register_type::<SetObjectScaleSystem>()
+            .register_type::<MoveObjectSmoothlySystem>()
+            .register_type::<SimpleFpsCounter>()
+            .register_type::<CheckCollisionType>()
+            .register_type::<RotatoryGear>()
+            .register_type::<SpawnObject>()
+            .register_type::<SimpleScannerSystem>()
+            .register_type::<SimpleAudioPlay>()
+            .register_type::<FollowObjectPositionUpdateSystem>()
+            .register_type::<DissolveObject>()
+            .register_type::<RailMechanism>()
+            .register_type::<PlaySoundOnCollision>()
+            .register_type::<SetObjectParentSystem>()
+            .register_type::<FlyingTurretSystem>()
+            .register_type::<MechanismPart>()
+            .register_type::<SimpleEventSystem>()
+            .register_type::<EventObjectFoundOnRaycastSystem>()
+            .register_type::<ScanElementInfo>()
+            .register_type::<SplineWalker>()
+            .register_type::<DestroyGameObject>()
+            .register_type::<AudioSourceInfo>()
+            .register_type::<TaskCounterSystem>()
+            .register_type::<AnimatorTriggerEvent>()
+            .register_type::<UIMouseHoverEvent>()
+            .register_type::<OpenInitialPopUpWindow>()
+            .register_type::<ReplaceMaterialSystem>()
+            .register_type::<ShowGameInfoHud>()
+            .register_type::<SimpleLamp>()
+            .register_type::<SetFixedRotation>()
+            .register_type::<OnEnableCheckSystem>()
+            .register_type::<SetGameObjectActiveState>()
+            .register_type::<AddForceToObjectSystem>()
+            .register_type::<PauseAnimationSystem>()
+            .register_type::<ConsoleLogOnScreenSystem>()
+            .register_type::<InitialPopUpWindow>()
+            .init_resource::<FeaturesManager>()
+            .add_plugins((
+                EventQueuePlugin::<AnimatorTriggerEnterEvent>::default(),
+                EventQueuePlugin::<AnimatorTriggerExitEvent>::default(),
+                EventQueuePlugin::<AnimatorTriggerEventRequest>::default(),
+                EventQueuePlugin::<ConsoleLogEvent>::default(),
+                EventQueuePlugin::<RaycastObjectFoundEvent>::default(),
+                EventQueuePlugin::<CollisionSoundEvent>::default(),
+                EventQueuePlugin::<TriggerSoundEvent>::default(),
+                EventQueuePlugin::<ShowHudMessageEvent>::default(),
+                EventQueuePlugin::<SimpleActionButtonEvent>::default(),
+                EventQueuePlugin::<SimpleEvent>::default(),
+                EventQueuePlugin::<UIMouseHoverChangedEvent>::default(),
+                EventQueuePlugin::<ScannerPingEvent>::default(),
+            ))
             .add_systems(Update, (
                 add_force_to_object_system::update_add_force_to_object_system,
                 animator_trigger_enter_exit_event::update_animator_trigger_enter_exit_event,
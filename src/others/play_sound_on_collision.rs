@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Plays a sound when collision occurs.
 ///
@@ -24,8 +25,10 @@ pub struct CollisionSoundEvent {
     pub entity: Entity,
 }
 
+pub type CollisionSoundEventQueue = EventQueue<CollisionSoundEvent>;
+
 pub fn update_play_sound_on_collision(
-    mut events: EventReader<CollisionSoundEvent>,
+    mut events: QueueReader<CollisionSoundEvent>,
     query: Query<&PlaySoundOnCollision>,
 ) {
     for event in events.read() {
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Plays a sound when a trigger is entered.
 ///
@@ -24,8 +25,10 @@ pub struct TriggerSoundEvent {
     pub entity: Entity,
 }
 
+pub type TriggerSoundEventQueue = EventQueue<TriggerSoundEvent>;
+
 pub fn update_play_sound_on_trigger_enter(
-    mut events: EventReader<TriggerSoundEvent>,
+    mut events: QueueReader<TriggerSoundEvent>,
     query: Query<&PlaySoundOnTriggerEnter>,
 ) {
     for event in events.read() {
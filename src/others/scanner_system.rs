@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Scanner system component.
 ///
@@ -24,8 +25,10 @@ pub struct ScannerPingEvent {
     pub entity: Entity,
 }
 
+pub type ScannerPingEventQueue = EventQueue<ScannerPingEvent>;
+
 pub fn update_scanner_system(
-    mut events: EventReader<ScannerPingEvent>,
+    mut events: QueueReader<ScannerPingEvent>,
     query: Query<&ScannerSystem>,
 ) {
     for event in events.read() {
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Shows temporary messages on HUD.
 ///
@@ -22,8 +23,10 @@ pub struct ShowHudMessageEvent {
     pub message: String,
 }
 
+pub type ShowHudMessageEventQueue = EventQueue<ShowHudMessageEvent>;
+
 pub fn update_show_message_on_hud_system(
-    mut events: EventReader<ShowHudMessageEvent>,
+    mut events: QueueReader<ShowHudMessageEvent>,
     mut query: Query<&mut ShowMessageOnHudSystem>,
 ) {
     for event in events.read() {
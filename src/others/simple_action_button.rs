@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::EventQueue;
 
 /// Simple action button data.
 ///
@@ -21,3 +22,5 @@ impl Default for SimpleActionButton {
 pub struct SimpleActionButtonEvent {
     pub action_name: String,
 }
+
+pub type SimpleActionButtonEventQueue = EventQueue<SimpleActionButtonEvent>;
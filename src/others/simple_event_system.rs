@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Simple event system component.
 ///
@@ -22,8 +23,10 @@ pub struct SimpleEvent {
     pub name: String,
 }
 
+pub type SimpleEventQueue = EventQueue<SimpleEvent>;
+
 pub fn update_simple_event_system(
-    mut events: EventReader<SimpleEvent>,
+    mut events: QueueReader<SimpleEvent>,
     query: Query<&SimpleEventSystem>,
 ) {
     for event in events.read() {
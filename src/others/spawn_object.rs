@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::level_manager::streaming::ChunkDependent;
 
 /// Spawns an object placeholder.
 ///
@@ -23,12 +24,16 @@ impl Default for SpawnObject {
 
 pub fn update_spawn_object(
     mut commands: Commands,
-    mut query: Query<&mut SpawnObject>,
+    mut query: Query<(&mut SpawnObject, Option<&ChunkDependent>)>,
 ) {
-    for mut spawn in query.iter_mut() {
+    for (mut spawn, dependent) in query.iter_mut() {
         if spawn.spawn_once && spawn.spawned {
             continue;
         }
+        // Wait for the owning streaming chunk to be ready
+        if dependent.is_some_and(|dependent| !dependent.active) {
+            continue;
+        }
         commands.spawn((
             SpatialBundle::default(),
             Name::new(format!("Spawned {}", spawn.prefab_name)),
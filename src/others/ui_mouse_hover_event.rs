@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::EventQueue;
 
 /// UI mouse hover event marker.
 ///
@@ -20,3 +21,5 @@ pub struct UIMouseHoverChangedEvent {
     pub entity: Entity,
     pub hovered: bool,
 }
+
+pub type UIMouseHoverChangedEventQueue = EventQueue<UIMouseHoverChangedEvent>;
@@ -2,18 +2,73 @@ use bevy::prelude::*;
 use avian3d::prelude::*;
 // use avian3d::external_force::ExternalForce;
 
+pub mod buoyancy;
+pub mod force_volumes;
+pub mod physics_lod;
+pub mod surface_anchor;
+
+pub use buoyancy::*;
+pub use force_volumes::*;
+pub use physics_lod::*;
+pub use surface_anchor::*;
+
 pub struct PhysicsPlugin;
 
 impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<GravitySettings>()
+            .add_plugins(EventQueuePlugin::<SplashEvent>::default())
+            .register_type::<BuoyancyVolume>()
+            .register_type::<Buoyant>()
+            .register_type::<RagdollBuoyancy>()
+            .init_resource::<GlobalWindSettings>()
+            .register_type::<ForceVolume>()
+            .register_type::<ForceVolumeReceiver>()
+            .register_type::<GlobalWindSettings>()
+            .init_resource::<PhysicsLodSettings>()
+            .init_resource::<PhysicsLodMetrics>()
+            .add_plugins(EventQueuePlugin::<PhysicsWakeEvent>::default())
+            .init_resource::<PhysicsLodWakeList>()
+            .register_type::<PhysicsLodProp>()
+            .register_type::<PhysicsLodObserver>()
+            .register_type::<PhysicsLodSettings>()
+            .register_type::<CustomGravity>()
+            .register_type::<GravityAlignment>()
+            .register_type::<GroundDetection>()
+            .register_type::<GroundDetectionSettings>()
+            .register_type::<PhysicsLodMetrics>()
+            .init_resource::<SurfaceAnchorSettings>()
+            .add_plugins(EventQueuePlugin::<SurfaceDetachedEvent>::default())
+            .register_type::<SurfaceAnchor>()
+            .register_type::<SurfaceAnchorSettings>()
             .add_systems(FixedUpdate, (
             apply_custom_gravity,
             detect_ground,
             handle_slopes,
             align_gravity_to_surface,
-        ).chain());
+        ).chain())
+            .add_systems(FixedUpdate, (
+                apply_ragdoll_buoyancy,
+                update_buoyant_submersion,
+                apply_buoyancy_forces,
+            ).chain())
+            .add_systems(Update, play_splash_effects.in_set(crate::headless::PresentationSet))
+            .add_systems(FixedUpdate, (
+                sync_global_wind,
+                apply_force_volumes_to_bodies,
+                apply_force_volumes_to_projectiles,
+                apply_force_volumes_to_paragliders,
+            ).chain())
+            .add_systems(FixedUpdate, follow_surface_anchors)
+            .add_systems(Update, draw_force_volumes.in_set(crate::headless::PresentationSet))
+            .add_systems(Update, (
+                update_physics_lod_settling,
+                evaluate_physics_lod,
+                handle_physics_wake_events,
+                process_physics_lod_wakes,
+                update_physics_lod_metrics,
+            ).chain());
     }
 }
 
@@ -175,6 +230,7 @@ fn detect_ground(
 }
 
 use crate::character::types::CharacterMovementState;
+use crate::utils::EventQueuePlugin;
 
 // ... imports remain ...
 
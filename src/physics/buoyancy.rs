@@ -0,0 +1,334 @@
+//! Buoyancy volumes
+//!
+//! Fluid volumes applying buoyancy and drag to rigid bodies, floating debris/pickups
+//! and ragdolled characters, emitting splash events for VFX and audio.
+//! [`play_splash_effects`] turns each splash into a `VfxKey::Splash` burst and
+//! the volume's `splash_sound`; games can read the [`SplashEventQueue`] for more.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::audio::{AudioChannel, AudioCue, AudioCueQueue};
+use crate::player::ragdoll::{Ragdoll, RagdollState};
+use crate::utils::{EventQueue, QueueReader};
+use crate::vfx::{VfxKey, VfxRequest, VfxRequestQueue};
+
+/// Fluid volume (box shaped, in the entity's local space) that makes bodies float
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct BuoyancyVolume {
+    pub active: bool,
+    pub half_extents: Vec3,
+    /// Fluid density in kg/m³ (water is ~1000)
+    pub fluid_density: f32,
+    pub linear_drag: f32,
+    pub angular_drag: f32,
+    /// Current applied to submerged bodies (rivers, waterfalls)
+    pub flow_velocity: Vec3,
+    /// Minimum vertical speed for entering/leaving the surface to emit a splash
+    pub splash_min_speed: f32,
+    /// Sound asset played on splashes
+    pub splash_sound: Option<String>,
+}
+
+impl Default for BuoyancyVolume {
+    fn default() -> Self {
+        Self {
+            active: true,
+            half_extents: Vec3::new(10.0, 2.0, 10.0),
+            fluid_density: 1000.0,
+            linear_drag: 1.5,
+            angular_drag: 1.0,
+            flow_velocity: Vec3::ZERO,
+            splash_min_speed: 1.5,
+            splash_sound: None,
+        }
+    }
+}
+
+impl BuoyancyVolume {
+    /// World-space surface height and whether the point lies inside the volume footprint
+    pub fn surface_at(&self, volume_transform: &GlobalTransform, point: Vec3) -> Option<f32> {
+        let local = volume_transform.affine().inverse().transform_point3(point);
+        if local.x.abs() > self.half_extents.x || local.z.abs() > self.half_extents.z || local.y < -self.half_extents.y {
+            return None;
+        }
+        Some(volume_transform.transform_point(Vec3::new(local.x, self.half_extents.y, local.z)).y)
+    }
+}
+
+/// Marks a rigid body as affected by buoyancy volumes
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Buoyant {
+    pub enabled: bool,
+    /// Body density in kg/m³; lower than the fluid floats, higher sinks
+    pub density: f32,
+    /// Half height of the body used to compute the submerged fraction
+    pub half_height: f32,
+    pub drag_multiplier: f32,
+
+    // Debug State
+    pub submerged_fraction: f32,
+    pub current_volume: Option<Entity>,
+    pub surface_height: f32,
+}
+
+impl Default for Buoyant {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            density: 500.0,
+            half_height: 0.5,
+            drag_multiplier: 1.0,
+            submerged_fraction: 0.0,
+            current_volume: None,
+            surface_height: 0.0,
+        }
+    }
+}
+
+impl Buoyant {
+    /// Preset for light floating debris and dropped pickups
+    pub fn debris() -> Self {
+        Self {
+            density: 400.0,
+            half_height: 0.2,
+            ..default()
+        }
+    }
+}
+
+/// Gives a ragdolled character buoyant body parts (drowned bodies float face down)
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct RagdollBuoyancy {
+    pub enabled: bool,
+    pub body_density: f32,
+    pub part_half_height: f32,
+    pub applied: bool,
+}
+
+impl Default for RagdollBuoyancy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            body_density: 985.0,
+            part_half_height: 0.15,
+            applied: false,
+        }
+    }
+}
+
+/// Splash type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum SplashKind {
+    Enter,
+    Exit,
+}
+
+/// Splash event data, consumed by VFX/audio
+#[derive(Debug, Clone)]
+pub struct SplashEvent {
+    pub entity: Entity,
+    pub volume: Entity,
+    pub position: Vec3,
+    pub impact_speed: f32,
+    pub kind: SplashKind,
+}
+
+/// Custom queue for splash events
+pub type SplashEventQueue = EventQueue<SplashEvent>;
+
+/// System to find the fluid volume each buoyant body is in and how submerged it is
+pub fn update_buoyant_submersion(
+    mut splash_queue: ResMut<SplashEventQueue>,
+    mut body_query: Query<(Entity, &GlobalTransform, &mut Buoyant, Option<&LinearVelocity>)>,
+    volume_query: Query<(Entity, &GlobalTransform, &BuoyancyVolume)>,
+) {
+    for (entity, body_transform, mut buoyant, velocity) in body_query.iter_mut() {
+        if !buoyant.enabled {
+            continue;
+        }
+
+        let position = body_transform.translation();
+        let mut found = None;
+        for (volume_entity, volume_transform, volume) in volume_query.iter() {
+            if !volume.active {
+                continue;
+            }
+            if let Some(surface) = volume.surface_at(volume_transform, position) {
+                found = Some((volume_entity, surface, volume.splash_min_speed));
+                break;
+            }
+        }
+
+        let previous_fraction = buoyant.submerged_fraction;
+        let previous_volume = buoyant.current_volume;
+        let vertical_speed = velocity.map(|velocity| velocity.y.abs()).unwrap_or(0.0);
+
+        match found {
+            Some((volume_entity, surface, splash_min_speed)) => {
+                let bottom = position.y - buoyant.half_height;
+                let height = (buoyant.half_height * 2.0).max(0.001);
+                buoyant.submerged_fraction = ((surface - bottom) / height).clamp(0.0, 1.0);
+                buoyant.current_volume = Some(volume_entity);
+                buoyant.surface_height = surface;
+
+                let crossed_in = previous_fraction <= 0.0 && buoyant.submerged_fraction > 0.0;
+                let crossed_out = previous_fraction > 0.0 && buoyant.submerged_fraction <= 0.0;
+                if (crossed_in || crossed_out) && vertical_speed >= splash_min_speed {
+                    splash_queue.send(SplashEvent {
+                        entity,
+                        volume: volume_entity,
+                        position: Vec3::new(position.x, surface, position.z),
+                        impact_speed: vertical_speed,
+                        kind: if crossed_in { SplashKind::Enter } else { SplashKind::Exit },
+                    });
+                }
+            }
+            None => {
+                if let (Some(volume_entity), true) = (previous_volume, previous_fraction > 0.0) {
+                    splash_queue.send(SplashEvent {
+                        entity,
+                        volume: volume_entity,
+                        position,
+                        impact_speed: vertical_speed,
+                        kind: SplashKind::Exit,
+                    });
+                }
+                buoyant.submerged_fraction = 0.0;
+                buoyant.current_volume = None;
+            }
+        }
+    }
+}
+
+/// System to apply buoyancy, drag and flow to submerged bodies
+pub fn apply_buoyancy_forces(
+    time: Res<Time>,
+    gravity: Res<Gravity>,
+    mut body_query: Query<(&Buoyant, &mut LinearVelocity, Option<&mut AngularVelocity>)>,
+    volume_query: Query<&BuoyancyVolume>,
+) {
+    let delta = time.delta_secs();
+    let gravity_strength = gravity.0.length();
+
+    for (buoyant, mut velocity, angular_velocity) in body_query.iter_mut() {
+        if !buoyant.enabled || buoyant.submerged_fraction <= 0.0 {
+            continue;
+        }
+        let Some(volume) = buoyant.current_volume.and_then(|volume| volume_query.get(volume).ok()) else {
+            continue;
+        };
+
+        // Archimedes: upward acceleration = g * (fluid density / body density) * submerged fraction
+        let density_ratio = volume.fluid_density / buoyant.density.max(1.0);
+        let buoyancy = gravity_strength * density_ratio * buoyant.submerged_fraction;
+        velocity.0 += Vec3::Y * buoyancy * delta;
+
+        // Drag toward the fluid flow velocity
+        let drag = (volume.linear_drag * buoyant.drag_multiplier * buoyant.submerged_fraction * delta).clamp(0.0, 1.0);
+        velocity.0 = velocity.0.lerp(volume.flow_velocity, drag);
+
+        if let Some(mut angular_velocity) = angular_velocity {
+            let angular_drag = (volume.angular_drag * buoyant.drag_multiplier * buoyant.submerged_fraction * delta).clamp(0.0, 1.0);
+            angular_velocity.0 *= 1.0 - angular_drag;
+        }
+    }
+}
+
+/// System to make ragdoll body parts buoyant while the character is ragdolled
+pub fn apply_ragdoll_buoyancy(
+    mut commands: Commands,
+    mut query: Query<(&Ragdoll, &mut RagdollBuoyancy)>,
+) {
+    for (ragdoll, mut ragdoll_buoyancy) in query.iter_mut() {
+        let ragdolled = ragdoll.current_state == RagdollState::Ragdolled;
+
+        if ragdolled && ragdoll_buoyancy.enabled && !ragdoll_buoyancy.applied {
+            for part in ragdoll.body_parts.iter().copied().chain(ragdoll.root_bone) {
+                commands.entity(part).try_insert(Buoyant {
+                    density: ragdoll_buoyancy.body_density,
+                    half_height: ragdoll_buoyancy.part_half_height,
+                    ..default()
+                });
+            }
+            ragdoll_buoyancy.applied = true;
+        } else if !ragdolled && ragdoll_buoyancy.applied {
+            for part in ragdoll.body_parts.iter().copied().chain(ragdoll.root_bone) {
+                commands.entity(part).try_remove::<Buoyant>();
+            }
+            ragdoll_buoyancy.applied = false;
+        }
+    }
+}
+
+/// System to play the effect and sound of each splash, bigger for faster impacts
+pub fn play_splash_effects(
+    mut splashes: QueueReader<SplashEvent>,
+    mut vfx_queue: ResMut<VfxRequestQueue>,
+    mut audio_cues: ResMut<AudioCueQueue>,
+    volume_query: Query<&BuoyancyVolume>,
+) {
+    for splash in splashes.read() {
+        let Ok(volume) = volume_query.get(splash.volume) else { continue };
+        let strength = splash.impact_speed / volume.splash_min_speed.max(0.1);
+
+        vfx_queue.send(VfxRequest {
+            key: VfxKey::Splash,
+            position: splash.position,
+            direction: Vec3::Y,
+            scale: strength.clamp(0.5, 3.0),
+            color: None,
+        });
+
+        if let Some(sound) = &volume.splash_sound {
+            let mut cue = AudioCue::sound(sound.clone(), AudioChannel::Sfx);
+            cue.volume = (strength * 0.5).clamp(0.3, 1.0);
+            audio_cues.send(cue);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::EventQueuePlugin;
+
+    #[test]
+    fn test_splash_plays_effect_and_sound() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins((
+                EventQueuePlugin::<SplashEvent>::default(),
+                EventQueuePlugin::<VfxRequest>::default(),
+                EventQueuePlugin::<AudioCue>::default(),
+            ))
+            .add_systems(Update, play_splash_effects);
+
+        let volume = app.world_mut().spawn(BuoyancyVolume {
+            splash_sound: Some("sfx/splash.ogg".to_string()),
+            ..default()
+        }).id();
+        let body = app.world_mut().spawn_empty().id();
+        app.world_mut().resource_mut::<SplashEventQueue>().send(SplashEvent {
+            entity: body,
+            volume,
+            position: Vec3::new(1.0, 0.0, 2.0),
+            impact_speed: 6.0,
+            kind: SplashKind::Enter,
+        });
+        app.update();
+
+        let vfx: Vec<_> = app.world().resource::<VfxRequestQueue>().iter().cloned().collect();
+        assert_eq!(vfx.len(), 1);
+        assert_eq!(vfx[0].key, VfxKey::Splash);
+        assert_eq!(vfx[0].position, Vec3::new(1.0, 0.0, 2.0));
+        let cues: Vec<_> = app.world().resource::<AudioCueQueue>().iter().map(|cue| cue.sound.clone()).collect();
+        assert_eq!(cues, vec![Some("sfx/splash.ogg".to_string())]);
+
+        // Read once
+        app.update();
+        assert_eq!(app.world().resource::<VfxRequestQueue>().iter().count(), 1);
+    }
+}
@@ -0,0 +1,456 @@
+//! Force volumes
+//!
+//! Wind tunnels, fans, geysers and conveyor surfaces applying continuous or pulsed
+//! forces to characters, rigid bodies, projectiles, grabbed objects and paragliders.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::grab::Grabber;
+use crate::player::extra_movements::paraglider::Paraglider;
+use crate::weapons::{BallisticsEnvironment, Projectile};
+
+/// Shape of a force volume, in the entity's local space
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum ForceVolumeShape {
+    Box { half_extents: Vec3 },
+    Sphere { radius: f32 },
+}
+
+impl Default for ForceVolumeShape {
+    fn default() -> Self {
+        Self::Box { half_extents: Vec3::splat(2.0) }
+    }
+}
+
+/// How the volume pushes things
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum ForceVolumeKind {
+    /// Constant acceleration along the direction
+    #[default]
+    Wind,
+    /// Acceleration that fades out with distance from the volume origin
+    Fan,
+    /// Upward bursts, typically combined with a pulse
+    Geyser,
+    /// Drags bodies toward the direction at `strength` m/s (belts, moving walkways)
+    Conveyor,
+}
+
+/// On/off pulsing for a force volume
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct ForcePulse {
+    pub interval: f32,
+    pub active_duration: f32,
+    pub offset: f32,
+}
+
+/// Noise-based gusting applied on top of the base strength
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct ForceGust {
+    /// Fraction of the base strength added/removed by gusts
+    pub amplitude: f32,
+    pub frequency: f32,
+}
+
+/// Force volume component
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ForceVolume {
+    pub active: bool,
+    pub kind: ForceVolumeKind,
+    pub shape: ForceVolumeShape,
+    /// Local-space direction of the force
+    pub direction: Vec3,
+    /// Acceleration in m/s² (or belt speed in m/s for conveyors)
+    pub strength: f32,
+    pub pulse: Option<ForcePulse>,
+    pub gust: Option<ForceGust>,
+
+    // Affected Targets
+    pub affect_characters: bool,
+    pub affect_rigid_bodies: bool,
+    pub affect_projectiles: bool,
+    pub affect_paragliders: bool,
+    pub grabbed_object_multiplier: f32,
+    pub projectile_multiplier: f32,
+    pub paraglider_multiplier: f32,
+
+    // Debug
+    pub draw_debug: bool,
+    pub debug_color: Color,
+}
+
+impl Default for ForceVolume {
+    fn default() -> Self {
+        Self {
+            active: true,
+            kind: ForceVolumeKind::Wind,
+            shape: ForceVolumeShape::default(),
+            direction: Vec3::Z,
+            strength: 10.0,
+            pulse: None,
+            gust: None,
+            affect_characters: true,
+            affect_rigid_bodies: true,
+            affect_projectiles: true,
+            affect_paragliders: true,
+            grabbed_object_multiplier: 0.25,
+            projectile_multiplier: 1.0,
+            paraglider_multiplier: 1.5,
+            draw_debug: false,
+            debug_color: Color::srgb(0.3, 0.8, 1.0),
+        }
+    }
+}
+
+impl ForceVolume {
+    /// Returns the falloff factor (0 outside, 1 at full strength) for a world position
+    pub fn influence_at(&self, volume_transform: &GlobalTransform, point: Vec3) -> f32 {
+        let local = volume_transform.affine().inverse().transform_point3(point);
+        let (inside, normalized_distance) = match self.shape {
+            ForceVolumeShape::Box { half_extents } => {
+                let inside = local.abs().cmple(half_extents).all();
+                let direction = self.direction.normalize_or_zero();
+                let depth = half_extents.dot(direction.abs()).max(0.001);
+                // Distance travelled along the force direction, from the entry face
+                (inside, ((local.dot(direction) + depth) / (2.0 * depth)).clamp(0.0, 1.0))
+            }
+            ForceVolumeShape::Sphere { radius } => {
+                let distance = local.length();
+                (distance <= radius, (distance / radius.max(0.001)).clamp(0.0, 1.0))
+            }
+        };
+
+        if !inside {
+            return 0.0;
+        }
+
+        match self.kind {
+            ForceVolumeKind::Fan => 1.0 - normalized_distance,
+            _ => 1.0,
+        }
+    }
+
+    /// Current strength multiplier from pulsing and gusts
+    pub fn temporal_factor(&self, elapsed: f32, seed: f32) -> f32 {
+        if let Some(pulse) = self.pulse {
+            let phase = (elapsed + pulse.offset).rem_euclid(pulse.interval.max(0.001));
+            if phase > pulse.active_duration {
+                return 0.0;
+            }
+        }
+
+        match self.gust {
+            Some(gust) => (1.0 + gust.amplitude * gust_noise(elapsed * gust.frequency + seed)).max(0.0),
+            None => 1.0,
+        }
+    }
+
+    pub fn world_direction(&self, volume_transform: &GlobalTransform) -> Vec3 {
+        (volume_transform.rotation() * self.direction).normalize_or_zero()
+    }
+}
+
+/// Cheap smooth noise in [-1, 1] built from layered sines
+pub fn gust_noise(t: f32) -> f32 {
+    (t.sin() * 0.5 + (t * 2.31 + 1.7).sin() * 0.3 + (t * 5.13 + 4.2).sin() * 0.2).clamp(-1.0, 1.0)
+}
+
+/// Per-volume phase offset so neighbouring volumes don't gust in lockstep
+fn volume_seed(entity: Entity) -> f32 {
+    (entity.to_bits() % 1024) as f32 * 0.37
+}
+
+/// Per-body response to force volumes
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ForceVolumeReceiver {
+    pub enabled: bool,
+    pub multiplier: f32,
+    pub is_character: bool,
+}
+
+impl Default for ForceVolumeReceiver {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            multiplier: 1.0,
+            is_character: false,
+        }
+    }
+}
+
+/// Global wind settings, mirrored into the ballistics environment
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct GlobalWindSettings {
+    pub enabled: bool,
+    pub base_wind: Vec3,
+    pub gust: Option<ForceGust>,
+}
+
+impl Default for GlobalWindSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            base_wind: Vec3::ZERO,
+            gust: None,
+        }
+    }
+}
+
+/// Sums the acceleration of all force volumes at a point
+fn accumulate_force(
+    volumes: &Query<(Entity, &GlobalTransform, &ForceVolume)>,
+    point: Vec3,
+    elapsed: f32,
+    filter: impl Fn(&ForceVolume) -> bool,
+) -> (Vec3, Option<Vec3>) {
+    let mut acceleration = Vec3::ZERO;
+    let mut conveyor_velocity = None;
+
+    for (entity, volume_transform, volume) in volumes.iter() {
+        if !volume.active || !filter(volume) {
+            continue;
+        }
+        let influence = volume.influence_at(volume_transform, point);
+        if influence <= 0.0 {
+            continue;
+        }
+        let factor = volume.temporal_factor(elapsed, volume_seed(entity));
+        let direction = volume.world_direction(volume_transform);
+
+        match volume.kind {
+            ForceVolumeKind::Conveyor => {
+                conveyor_velocity = Some(conveyor_velocity.unwrap_or(Vec3::ZERO) + direction * volume.strength * factor);
+            }
+            ForceVolumeKind::Geyser => {
+                // Geysers always push against gravity regardless of the authored direction
+                acceleration += Vec3::Y * volume.strength * influence * factor;
+            }
+            _ => {
+                acceleration += direction * volume.strength * influence * factor;
+            }
+        }
+    }
+
+    (acceleration, conveyor_velocity)
+}
+
+type ForceVolumeBody<'a> = (
+    Entity,
+    &'a GlobalTransform,
+    &'a mut LinearVelocity,
+    Option<&'a RigidBody>,
+    Option<&'a ForceVolumeReceiver>,
+);
+
+/// System to apply force volumes to rigid bodies, characters and grabbed objects
+pub fn apply_force_volumes_to_bodies(
+    time: Res<Time>,
+    volume_query: Query<(Entity, &GlobalTransform, &ForceVolume)>,
+    mut body_query: Query<ForceVolumeBody>,
+    grabber_query: Query<&Grabber>,
+) {
+    if volume_query.is_empty() {
+        return;
+    }
+
+    let delta = time.delta_secs();
+    let elapsed = time.elapsed_secs();
+    let held: Vec<Entity> = grabber_query.iter().filter_map(|grabber| grabber.held_object).collect();
+
+    for (entity, transform, mut velocity, rigid_body, receiver) in body_query.iter_mut() {
+        if receiver.is_some_and(|receiver| !receiver.enabled) {
+            continue;
+        }
+        // Without a receiver only dynamic bodies are pushed
+        if receiver.is_none() && rigid_body != Some(&RigidBody::Dynamic) {
+            continue;
+        }
+
+        let is_character = receiver.is_some_and(|receiver| receiver.is_character);
+        let (acceleration, conveyor_velocity) = accumulate_force(&volume_query, transform.translation(), elapsed, |volume| {
+            if is_character { volume.affect_characters } else { volume.affect_rigid_bodies }
+        });
+
+        let mut multiplier = receiver.map(|receiver| receiver.multiplier).unwrap_or(1.0);
+        if held.contains(&entity) {
+            let grabbed_multiplier = volume_query
+                .iter()
+                .find(|(_, volume_transform, volume)| volume.influence_at(volume_transform, transform.translation()) > 0.0)
+                .map(|(_, _, volume)| volume.grabbed_object_multiplier)
+                .unwrap_or(1.0);
+            multiplier *= grabbed_multiplier;
+        }
+
+        velocity.0 += acceleration * multiplier * delta;
+
+        if let Some(conveyor_velocity) = conveyor_velocity {
+            // Match belt speed horizontally, keep vertical motion
+            let target = Vec3::new(conveyor_velocity.x, velocity.y, conveyor_velocity.z);
+            velocity.0 = velocity.0.lerp(target, (8.0 * multiplier * delta).clamp(0.0, 1.0));
+        }
+    }
+}
+
+/// System to push projectiles flying through force volumes
+pub fn apply_force_volumes_to_projectiles(
+    time: Res<Time>,
+    volume_query: Query<(Entity, &GlobalTransform, &ForceVolume)>,
+    mut projectile_query: Query<(&Transform, &mut Projectile)>,
+) {
+    if volume_query.is_empty() {
+        return;
+    }
+
+    let delta = time.delta_secs();
+    let elapsed = time.elapsed_secs();
+
+    for (transform, mut projectile) in projectile_query.iter_mut() {
+        let mut acceleration = Vec3::ZERO;
+        for (entity, volume_transform, volume) in volume_query.iter() {
+            if !volume.active || !volume.affect_projectiles || volume.kind == ForceVolumeKind::Conveyor {
+                continue;
+            }
+            let influence = volume.influence_at(volume_transform, transform.translation);
+            if influence <= 0.0 {
+                continue;
+            }
+            let factor = volume.temporal_factor(elapsed, volume_seed(entity));
+            acceleration += volume.world_direction(volume_transform) * volume.strength * influence * factor * volume.projectile_multiplier;
+        }
+
+        // Heavier projectiles are pushed less
+        projectile.velocity += acceleration / projectile.mass.max(0.001).sqrt().max(1.0) * delta;
+    }
+}
+
+/// System to carry paragliders on updrafts and wind
+pub fn apply_force_volumes_to_paragliders(
+    time: Res<Time>,
+    volume_query: Query<(Entity, &GlobalTransform, &ForceVolume)>,
+    mut glider_query: Query<(&GlobalTransform, &mut Paraglider)>,
+) {
+    if volume_query.is_empty() {
+        return;
+    }
+
+    let delta = time.delta_secs();
+    let elapsed = time.elapsed_secs();
+
+    for (transform, mut paraglider) in glider_query.iter_mut() {
+        if !paraglider.is_gliding {
+            continue;
+        }
+
+        let mut acceleration = Vec3::ZERO;
+        for (entity, volume_transform, volume) in volume_query.iter() {
+            if !volume.active || !volume.affect_paragliders || volume.kind == ForceVolumeKind::Conveyor {
+                continue;
+            }
+            let influence = volume.influence_at(volume_transform, transform.translation());
+            if influence <= 0.0 {
+                continue;
+            }
+            let factor = volume.temporal_factor(elapsed, volume_seed(entity));
+            let direction = if volume.kind == ForceVolumeKind::Geyser { Vec3::Y } else { volume.world_direction(volume_transform) };
+            acceleration += direction * volume.strength * influence * factor * volume.paraglider_multiplier;
+        }
+
+        paraglider.velocity += acceleration * delta;
+    }
+}
+
+/// System to mirror the global wind (with gusts) into the ballistics environment
+pub fn sync_global_wind(
+    time: Res<Time>,
+    settings: Res<GlobalWindSettings>,
+    mut ballistics: ResMut<BallisticsEnvironment>,
+) {
+    if !settings.enabled {
+        return;
+    }
+
+    let factor = match settings.gust {
+        Some(gust) => (1.0 + gust.amplitude * gust_noise(time.elapsed_secs() * gust.frequency)).max(0.0),
+        None => 1.0,
+    };
+    ballistics.wind = settings.base_wind * factor;
+}
+
+/// System to draw force volume bounds and direction
+pub fn draw_force_volumes(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    volume_query: Query<(Entity, &GlobalTransform, &ForceVolume)>,
+) {
+    for (entity, transform, volume) in volume_query.iter() {
+        if !volume.draw_debug {
+            continue;
+        }
+
+        let factor = if volume.active { volume.temporal_factor(time.elapsed_secs(), volume_seed(entity)) } else { 0.0 };
+        let color = volume.debug_color.with_alpha(0.25 + 0.75 * factor.min(1.0));
+        let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+
+        match volume.shape {
+            ForceVolumeShape::Box { half_extents } => {
+                gizmos.cuboid(
+                    Transform::from_translation(translation)
+                        .with_rotation(rotation)
+                        .with_scale(scale * half_extents * 2.0),
+                    color,
+                );
+            }
+            ForceVolumeShape::Sphere { radius } => {
+                gizmos.sphere(Isometry3d::new(translation, rotation), radius * scale.max_element(), color);
+            }
+        }
+
+        let direction = if volume.kind == ForceVolumeKind::Geyser { Vec3::Y } else { volume.world_direction(transform) };
+        gizmos.arrow(translation, translation + direction * (1.0 + factor), color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn test_force_volumes_push_bodies_inside() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .add_systems(Update, apply_force_volumes_to_bodies);
+
+        app.world_mut().spawn((GlobalTransform::IDENTITY, ForceVolume::default()));
+        app.world_mut().spawn((
+            GlobalTransform::from_xyz(20.0, 0.0, 0.0),
+            ForceVolume { kind: ForceVolumeKind::Conveyor, direction: Vec3::X, strength: 3.0, ..default() },
+        ));
+
+        let inside = app.world_mut().spawn((GlobalTransform::from_xyz(0.0, 0.0, 1.0), LinearVelocity::ZERO, RigidBody::Dynamic)).id();
+        let outside = app.world_mut().spawn((GlobalTransform::from_xyz(0.0, 0.0, 10.0), LinearVelocity::ZERO, RigidBody::Dynamic)).id();
+        let static_body = app.world_mut().spawn((GlobalTransform::IDENTITY, LinearVelocity::ZERO, RigidBody::Static)).id();
+        let character = app.world_mut().spawn((
+            GlobalTransform::from_xyz(20.0, 0.0, 0.0),
+            LinearVelocity::ZERO,
+            ForceVolumeReceiver { is_character: true, ..default() },
+        )).id();
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let velocity = |entity: Entity| app.world().get::<LinearVelocity>(entity).unwrap().0;
+        assert!(velocity(inside).z > 0.0);
+        assert_eq!(velocity(inside).x, 0.0);
+        assert_eq!(velocity(outside), Vec3::ZERO);
+        assert_eq!(velocity(static_body), Vec3::ZERO);
+        // Conveyors drag characters toward the belt speed without overshooting it
+        assert!(velocity(character).x > 0.0 && velocity(character).x <= 3.0);
+    }
+}
@@ -0,0 +1,427 @@
+//! Physics LOD
+//!
+//! Puts far, settled props to sleep (or turns them static) and brings them back
+//! when an observer approaches or an explosion happens nearby. Reactivation is
+//! deferred and budgeted per frame so walking into a cluttered room doesn't spike.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::character::Player;
+use crate::utils::{EventQueue, QueueReader};
+
+/// How a prop is deactivated when it leaves the active range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum PhysicsLodMode {
+    /// Disable the rigid body, keeping its collider for queries and collisions
+    #[default]
+    Sleep,
+    /// Swap the rigid body to static and restore it on wake
+    Static,
+}
+
+/// How much a prop matters; scales its LOD distances
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum PhysicsLodImportance {
+    Low,
+    #[default]
+    Normal,
+    High,
+    /// Never deactivated (quest items, puzzle pieces...)
+    Critical,
+}
+
+impl PhysicsLodImportance {
+    pub fn distance_scale(&self) -> Option<f32> {
+        match self {
+            PhysicsLodImportance::Low => Some(0.5),
+            PhysicsLodImportance::Normal => Some(1.0),
+            PhysicsLodImportance::High => Some(2.0),
+            PhysicsLodImportance::Critical => None,
+        }
+    }
+}
+
+/// Current LOD state of a prop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum PhysicsLodState {
+    #[default]
+    Active,
+    Sleeping,
+    PendingWake,
+}
+
+/// Marks a physics prop as managed by the LOD system
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct PhysicsLodProp {
+    pub enabled: bool,
+    pub importance: PhysicsLodImportance,
+    pub mode: PhysicsLodMode,
+
+    // Debug State
+    pub state: PhysicsLodState,
+    pub original_body: Option<RigidBody>,
+    pub distance_to_observer: f32,
+    pub settled_timer: f32,
+}
+
+impl Default for PhysicsLodProp {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            importance: PhysicsLodImportance::Normal,
+            mode: PhysicsLodMode::Sleep,
+            state: PhysicsLodState::Active,
+            original_body: None,
+            distance_to_observer: 0.0,
+            settled_timer: 0.0,
+        }
+    }
+}
+
+impl PhysicsLodProp {
+    pub fn is_sleeping(&self) -> bool {
+        self.state != PhysicsLodState::Active
+    }
+}
+
+/// Extra observer (besides players) that keeps props around it awake
+#[derive(Component, Debug, Reflect, Default)]
+#[reflect(Component)]
+pub struct PhysicsLodObserver {
+    /// Multiplies the global distances for this observer
+    pub range_multiplier: f32,
+}
+
+/// Global LOD settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct PhysicsLodSettings {
+    pub enabled: bool,
+    /// Props farther than this from every observer go to sleep
+    pub sleep_distance: f32,
+    /// Props closer than this to any observer wake up (lower than sleep distance for hysteresis)
+    pub wake_distance: f32,
+    /// Props must be slower than this for `settle_time` before they may sleep
+    pub settle_speed: f32,
+    pub settle_time: f32,
+    /// Seconds between distance evaluations
+    pub evaluation_interval: f32,
+    /// Maximum props reactivated per frame
+    pub max_wakes_per_frame: usize,
+
+    // Debug State
+    pub evaluation_timer: f32,
+}
+
+impl Default for PhysicsLodSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sleep_distance: 60.0,
+            wake_distance: 50.0,
+            settle_speed: 0.2,
+            settle_time: 1.0,
+            evaluation_interval: 0.25,
+            max_wakes_per_frame: 16,
+            evaluation_timer: 0.0,
+        }
+    }
+}
+
+/// Profiling counters for the physics LOD system
+#[derive(Resource, Debug, Reflect, Default)]
+#[reflect(Resource)]
+pub struct PhysicsLodMetrics {
+    pub managed_props: usize,
+    pub active_props: usize,
+    pub sleeping_props: usize,
+    pub pending_wakes: usize,
+    pub slept_last_evaluation: usize,
+    pub woken_last_frame: usize,
+    pub total_sleeps: u64,
+    pub total_wakes: u64,
+}
+
+/// Request to wake every managed prop within a radius (explosions, scripted events)
+#[derive(Debug, Clone, Copy)]
+pub struct PhysicsWakeEvent {
+    pub position: Vec3,
+    pub radius: f32,
+}
+
+/// Custom queue for physics wake events
+pub type PhysicsWakeEventQueue = EventQueue<PhysicsWakeEvent>;
+
+/// Wake requests waiting for the per-frame budget; explosions go to the front
+#[derive(Resource, Default)]
+pub struct PhysicsLodWakeList(pub Vec<Entity>);
+
+/// System to track how long each prop has been at rest
+pub fn update_physics_lod_settling(
+    time: Res<Time>,
+    settings: Res<PhysicsLodSettings>,
+    mut prop_query: Query<(&mut PhysicsLodProp, Option<&LinearVelocity>, Option<&AngularVelocity>)>,
+) {
+    let delta = time.delta_secs();
+
+    for (mut prop, linear, angular) in prop_query.iter_mut() {
+        if prop.is_sleeping() {
+            continue;
+        }
+
+        let speed = linear.map(|velocity| velocity.length()).unwrap_or(0.0)
+            + angular.map(|velocity| velocity.length()).unwrap_or(0.0);
+        if speed <= settings.settle_speed {
+            prop.settled_timer += delta;
+        } else {
+            prop.settled_timer = 0.0;
+        }
+    }
+}
+
+type LodObserver = Or<(With<Player>, With<PhysicsLodObserver>)>;
+
+/// System to evaluate observer distances and deactivate far props
+pub fn evaluate_physics_lod(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut settings: ResMut<PhysicsLodSettings>,
+    mut metrics: ResMut<PhysicsLodMetrics>,
+    mut wake_list: ResMut<PhysicsLodWakeList>,
+    observer_query: Query<(&GlobalTransform, Option<&PhysicsLodObserver>), LodObserver>,
+    mut prop_query: Query<(Entity, &GlobalTransform, &mut PhysicsLodProp, &RigidBody)>,
+) {
+    if !settings.enabled {
+        return;
+    }
+
+    settings.evaluation_timer -= time.delta_secs();
+    if settings.evaluation_timer > 0.0 {
+        return;
+    }
+    settings.evaluation_timer = settings.evaluation_interval;
+
+    let observers: Vec<(Vec3, f32)> = observer_query
+        .iter()
+        .map(|(transform, observer)| {
+            let range = observer.map(|observer| observer.range_multiplier).unwrap_or(1.0);
+            (transform.translation(), range.max(0.01))
+        })
+        .collect();
+
+    let mut slept = 0;
+
+    for (entity, transform, mut prop, rigid_body) in prop_query.iter_mut() {
+        if !prop.enabled {
+            continue;
+        }
+
+        let Some(scale) = prop.importance.distance_scale() else {
+            continue;
+        };
+
+        // Distance to the closest observer, normalized by that observer's range
+        let position = transform.translation();
+        let distance = observers
+            .iter()
+            .map(|(observer, range)| observer.distance(position) / range)
+            .fold(f32::MAX, f32::min);
+        prop.distance_to_observer = distance;
+
+        match prop.state {
+            PhysicsLodState::Active => {
+                if *rigid_body != RigidBody::Dynamic
+                    || distance < settings.sleep_distance * scale
+                    || prop.settled_timer < settings.settle_time
+                {
+                    continue;
+                }
+
+                match prop.mode {
+                    PhysicsLodMode::Sleep => {
+                        commands.entity(entity).insert(RigidBodyDisabled);
+                    }
+                    PhysicsLodMode::Static => {
+                        prop.original_body = Some(*rigid_body);
+                        commands.entity(entity).insert(RigidBody::Static);
+                    }
+                }
+                prop.state = PhysicsLodState::Sleeping;
+                slept += 1;
+            }
+            PhysicsLodState::Sleeping => {
+                if distance <= settings.wake_distance * scale {
+                    prop.state = PhysicsLodState::PendingWake;
+                    wake_list.0.push(entity);
+                }
+            }
+            PhysicsLodState::PendingWake => {}
+        }
+    }
+
+    metrics.slept_last_evaluation = slept;
+    metrics.total_sleeps += slept as u64;
+}
+
+/// System to turn wake events into high priority wake requests
+pub fn handle_physics_wake_events(
+    mut wake_events: QueueReader<PhysicsWakeEvent>,
+    mut wake_list: ResMut<PhysicsLodWakeList>,
+    mut prop_query: Query<(Entity, &GlobalTransform, &mut PhysicsLodProp)>,
+) {
+    if wake_events.is_empty() {
+        return;
+    }
+
+    let mut urgent = Vec::new();
+    for event in wake_events.read() {
+        for (entity, transform, mut prop) in prop_query.iter_mut() {
+            if !prop.is_sleeping() || transform.translation().distance(event.position) > event.radius {
+                continue;
+            }
+            if prop.state == PhysicsLodState::PendingWake {
+                wake_list.0.retain(|pending| *pending != entity);
+            }
+            prop.state = PhysicsLodState::PendingWake;
+            urgent.push(entity);
+        }
+    }
+
+    // Explosions jump the queue
+    urgent.append(&mut wake_list.0);
+    wake_list.0 = urgent;
+}
+
+/// System to reactivate pending props within the per-frame budget
+pub fn process_physics_lod_wakes(
+    mut commands: Commands,
+    settings: Res<PhysicsLodSettings>,
+    mut metrics: ResMut<PhysicsLodMetrics>,
+    mut wake_list: ResMut<PhysicsLodWakeList>,
+    mut prop_query: Query<&mut PhysicsLodProp>,
+) {
+    let count = wake_list.0.len().min(settings.max_wakes_per_frame);
+    let mut woken = 0;
+
+    for entity in wake_list.0.drain(..count) {
+        let Ok(mut prop) = prop_query.get_mut(entity) else {
+            continue;
+        };
+        if prop.state != PhysicsLodState::PendingWake {
+            continue;
+        }
+
+        match prop.mode {
+            PhysicsLodMode::Sleep => {
+                commands.entity(entity).remove::<RigidBodyDisabled>();
+            }
+            PhysicsLodMode::Static => {
+                let body = prop.original_body.take().unwrap_or(RigidBody::Dynamic);
+                commands.entity(entity).insert(body);
+            }
+        }
+        prop.state = PhysicsLodState::Active;
+        prop.settled_timer = 0.0;
+        woken += 1;
+    }
+
+    metrics.woken_last_frame = woken;
+    metrics.total_wakes += woken as u64;
+}
+
+/// System to refresh the LOD counters
+pub fn update_physics_lod_metrics(
+    mut metrics: ResMut<PhysicsLodMetrics>,
+    wake_list: Res<PhysicsLodWakeList>,
+    prop_query: Query<&PhysicsLodProp>,
+) {
+    let mut active = 0;
+    let mut sleeping = 0;
+    for prop in prop_query.iter() {
+        if prop.is_sleeping() {
+            sleeping += 1;
+        } else {
+            active += 1;
+        }
+    }
+
+    metrics.managed_props = active + sleeping;
+    metrics.active_props = active;
+    metrics.sleeping_props = sleeping;
+    metrics.pending_wakes = wake_list.0.len();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn test_far_props_sleep_and_wake_within_budget() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(EventQueuePlugin::<PhysicsWakeEvent>::default())
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .insert_resource(PhysicsLodSettings {
+                settle_time: 0.5,
+                evaluation_interval: 0.0,
+                max_wakes_per_frame: 1,
+                ..default()
+            })
+            .init_resource::<PhysicsLodMetrics>()
+            .init_resource::<PhysicsLodWakeList>()
+            .add_systems(Update, (
+                update_physics_lod_settling,
+                evaluate_physics_lod,
+                handle_physics_wake_events,
+                process_physics_lod_wakes,
+                update_physics_lod_metrics,
+            ).chain());
+
+        app.world_mut().spawn((Player, GlobalTransform::IDENTITY));
+        let near = app.world_mut().spawn((GlobalTransform::from_xyz(10.0, 0.0, 0.0), RigidBody::Dynamic, PhysicsLodProp::default())).id();
+        let far_a = app.world_mut().spawn((GlobalTransform::from_xyz(100.0, 0.0, 0.0), RigidBody::Dynamic, PhysicsLodProp::default())).id();
+        let far_b = app.world_mut().spawn((
+            GlobalTransform::from_xyz(101.0, 0.0, 0.0),
+            RigidBody::Dynamic,
+            PhysicsLodProp { mode: PhysicsLodMode::Static, ..default() },
+        )).id();
+        let critical = app.world_mut().spawn((
+            GlobalTransform::from_xyz(100.0, 0.0, 1.0),
+            RigidBody::Dynamic,
+            PhysicsLodProp { importance: PhysicsLodImportance::Critical, ..default() },
+        )).id();
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let state = |app: &App, entity: Entity| app.world().get::<PhysicsLodProp>(entity).unwrap().state;
+        assert_eq!(state(&app, near), PhysicsLodState::Active);
+        assert_eq!(state(&app, critical), PhysicsLodState::Active);
+        assert_eq!(state(&app, far_a), PhysicsLodState::Sleeping);
+        assert!(app.world().entity(far_a).contains::<RigidBodyDisabled>());
+        assert_eq!(state(&app, far_b), PhysicsLodState::Sleeping);
+        assert_eq!(app.world().get::<RigidBody>(far_b), Some(&RigidBody::Static));
+        assert_eq!(app.world().resource::<PhysicsLodMetrics>().sleeping_props, 2);
+
+        // An explosion wakes both, one per frame
+        app.world_mut().resource_mut::<PhysicsWakeEventQueue>().send(PhysicsWakeEvent {
+            position: Vec3::new(100.0, 0.0, 0.0),
+            radius: 5.0,
+        });
+        app.update();
+        assert_eq!(app.world().resource::<PhysicsLodMetrics>().woken_last_frame, 1);
+        assert_eq!(app.world().resource::<PhysicsLodMetrics>().pending_wakes, 1);
+        app.update();
+
+        assert_eq!(state(&app, far_a), PhysicsLodState::Active);
+        assert!(!app.world().entity(far_a).contains::<RigidBodyDisabled>());
+        assert_eq!(state(&app, far_b), PhysicsLodState::Active);
+        assert_eq!(app.world().get::<RigidBody>(far_b), Some(&RigidBody::Dynamic));
+        assert_eq!(app.world().resource::<PhysicsLodMetrics>().total_wakes, 2);
+    }
+}
@@ -0,0 +1,236 @@
+//! Holding on to moving and rotating surfaces.
+//!
+//! A character hanging from a ledge, free climbing or on a ladder gets a
+//! [`SurfaceAnchor`] to the entity it holds. The grip is kept in that
+//! entity's space, so when a platform moves or a wheel turns the character is
+//! carried along with it. Only the yaw of the surface turns the character;
+//! pitch and roll move it around the pivot but leave it upright. The camera
+//! picks up the same yaw so the view doesn't swing away from the climber.
+//!
+//! While carried, the anchor tracks the velocity of the grip. It is handed
+//! over to the character's `LinearVelocity` on release, so letting go of a
+//! moving platform keeps its momentum. Motion past the thresholds in
+//! [`SurfaceAnchorSettings`] shakes the character off, with a
+//! [`SurfaceDetachedEvent`].
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::utils::EventQueue;
+
+/// Limits on how violently a held surface may move
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct SurfaceAnchorSettings {
+    /// Acceleration of the grip that shakes the character off, in m/s²
+    pub max_acceleration: f32,
+    /// Turn rate of the surface that shakes the character off, in rad/s
+    pub max_angular_speed: f32,
+    /// Whether the surface velocity is kept when letting go
+    pub inherit_velocity: bool,
+}
+
+impl Default for SurfaceAnchorSettings {
+    fn default() -> Self {
+        Self {
+            max_acceleration: 60.0,
+            max_angular_speed: 4.0,
+            inherit_velocity: true,
+        }
+    }
+}
+
+/// Keeps a character attached to the surface it holds
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct SurfaceAnchor {
+    pub surface: Entity,
+    /// Grip point (ledge, rung) in the surface's space
+    pub local_point: Vec3,
+    /// Surface normal at the grip, in the surface's space
+    pub local_normal: Vec3,
+    /// Pose of the surface on the previous step
+    pub previous_pose: Option<Transform>,
+    /// Pose of the surface on the last step
+    pub last_pose: Option<Transform>,
+    /// Velocity of the grip; `None` until it has been measured
+    pub velocity: Option<Vec3>,
+    pub angular_velocity: Vec3,
+    /// Yaw the surface turned that the camera hasn't followed yet, in radians
+    pub pending_yaw: f32,
+}
+
+impl SurfaceAnchor {
+    /// Anchors to `surface`, gripping it at the world `point` with `normal`
+    pub fn new(surface: Entity, surface_transform: &GlobalTransform, point: Vec3, normal: Vec3) -> Self {
+        let pose = surface_transform.compute_transform();
+        let inverse = pose.rotation.inverse();
+        Self {
+            surface,
+            local_point: inverse * (point - pose.translation),
+            local_normal: inverse * normal,
+            previous_pose: None,
+            last_pose: Some(pose),
+            velocity: None,
+            angular_velocity: Vec3::ZERO,
+            pending_yaw: 0.0,
+        }
+    }
+
+    /// Grip point in world space, with the surface at `pose`
+    pub fn world_point(&self, pose: &Transform) -> Vec3 {
+        pose.translation + pose.rotation * self.local_point
+    }
+
+    pub fn world_normal(&self, pose: &Transform) -> Vec3 {
+        (pose.rotation * self.local_normal).normalize_or_zero()
+    }
+
+    /// Moves the grip so it holds the world `point` from now on
+    pub fn regrip(&mut self, point: Vec3, normal: Vec3) {
+        let Some(pose) = self.last_pose else { return };
+        let inverse = pose.rotation.inverse();
+        self.local_point = inverse * (point - pose.translation);
+        self.local_normal = inverse * normal;
+    }
+
+    /// Rotation of the surface during the last step
+    pub fn last_turn(&self) -> Quat {
+        match (self.previous_pose, self.last_pose) {
+            (Some(previous), Some(last)) => last.rotation * previous.rotation.inverse(),
+            _ => Quat::IDENTITY,
+        }
+    }
+
+    /// Where a world point riding on the surface went during the last step
+    pub fn carry_point(&self, point: Vec3) -> Vec3 {
+        match (self.previous_pose, self.last_pose) {
+            (Some(previous), Some(last)) => last.translation + self.last_turn() * (point - previous.translation),
+            _ => point,
+        }
+    }
+
+    /// Where a world direction riding on the surface turned during the last step
+    pub fn carry_direction(&self, direction: Vec3) -> Vec3 {
+        self.last_turn() * direction
+    }
+
+    /// Turns a character facing by the yaw of the last step, keeping it upright
+    pub fn carry_rotation(&self, rotation: Quat) -> Quat {
+        let (yaw, _, _) = self.last_turn().to_euler(EulerRot::YXZ);
+        Quat::from_rotation_y(yaw) * rotation
+    }
+
+    /// Follows the surface to `pose`, carrying `transform` with it. Returns
+    /// false when the motion is too violent to hold on.
+    pub fn follow(&mut self, pose: Transform, transform: &mut Transform, dt: f32, settings: &SurfaceAnchorSettings) -> bool {
+        self.previous_pose = self.last_pose;
+        self.last_pose = Some(pose);
+        if self.previous_pose.is_none() || dt <= 0.0 {
+            return true;
+        }
+
+        let turn = self.last_turn();
+        let position = self.carry_point(transform.translation);
+        let velocity = (position - transform.translation) / dt;
+        self.angular_velocity = turn.to_scaled_axis() / dt;
+        let acceleration = self.velocity.map_or(0.0, |last| (velocity - last).length() / dt);
+        self.velocity = Some(velocity);
+
+        if acceleration > settings.max_acceleration || self.angular_velocity.length() > settings.max_angular_speed {
+            return false;
+        }
+
+        let (yaw, _, _) = turn.to_euler(EulerRot::YXZ);
+        transform.translation = position;
+        transform.rotation = self.carry_rotation(transform.rotation);
+        self.pending_yaw += yaw;
+        true
+    }
+}
+
+/// A character lost its grip on a surface that moved too violently
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceDetachedEvent {
+    pub entity: Entity,
+    pub surface: Entity,
+    /// Velocity inherited from the surface
+    pub velocity: Vec3,
+}
+
+pub type SurfaceDetachedQueue = EventQueue<SurfaceDetachedEvent>;
+
+/// Hands the anchor's velocity over to the character when it lets go
+pub fn release_surface_anchor(
+    commands: &mut Commands,
+    entity: Entity,
+    anchor: &SurfaceAnchor,
+    velocity: Option<&mut LinearVelocity>,
+    settings: &SurfaceAnchorSettings,
+) {
+    if let (true, Some(velocity), Some(inherited)) = (settings.inherit_velocity, velocity, anchor.velocity) {
+        velocity.0 += inherited;
+    }
+    commands.entity(entity).remove::<SurfaceAnchor>();
+}
+
+/// System to carry anchored characters along with their surfaces
+pub fn follow_surface_anchors(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<SurfaceAnchorSettings>,
+    mut detached: ResMut<SurfaceDetachedQueue>,
+    mut query: Query<(Entity, &mut SurfaceAnchor, &mut Transform, Option<&mut LinearVelocity>)>,
+    surface_query: Query<&GlobalTransform>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut anchor, mut transform, mut velocity) in query.iter_mut() {
+        let held = match surface_query.get(anchor.surface) {
+            Ok(surface) => anchor.follow(surface.compute_transform(), &mut transform, dt, &settings),
+            Err(_) => false,
+        };
+        if held {
+            continue;
+        }
+
+        release_surface_anchor(&mut commands, entity, &anchor, velocity.as_deref_mut(), &settings);
+        detached.send(SurfaceDetachedEvent {
+            entity,
+            surface: anchor.surface,
+            velocity: anchor.velocity.unwrap_or(Vec3::ZERO),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_surface_anchor_carries_and_detaches() {
+        use crate::physics::{SurfaceAnchor, SurfaceAnchorSettings};
+        let settings = SurfaceAnchorSettings::default();
+        let surface = World::new().spawn_empty().id();
+        let start = Transform::from_xyz(10.0, 0.0, 0.0);
+        let mut anchor = SurfaceAnchor::new(surface, &GlobalTransform::from(start), Vec3::new(10.0, 2.0, 1.0), Vec3::Z);
+        assert_eq!(anchor.local_point, Vec3::new(0.0, 2.0, 1.0));
+
+        // A quarter turn about the surface's origin carries the climber around it, upright
+        let mut climber = Transform::from_xyz(10.0, 1.0, 1.5);
+        let turned = start.with_rotation(Quat::from_rotation_y(std::f32::consts::FRAC_PI_2));
+        assert!(anchor.follow(turned, &mut climber, 1.0, &settings));
+        assert!(climber.translation.distance(Vec3::new(11.5, 1.0, 0.0)) < 1e-4);
+        assert!((anchor.pending_yaw - std::f32::consts::FRAC_PI_2).abs() < 1e-4);
+        assert!(climber.up().dot(Vec3::Y) > 0.999);
+        assert!(anchor.world_normal(&turned).distance(Vec3::X) < 1e-4);
+
+        // Steady motion holds, a sudden jolt shakes the climber off
+        let mut climber = Transform::from_xyz(10.0, 1.0, 1.5);
+        let mut anchor = SurfaceAnchor::new(surface, &GlobalTransform::from(start), climber.translation, Vec3::Z);
+        let dt = 1.0 / 60.0;
+        assert!(anchor.follow(start.with_translation(Vec3::new(10.05, 0.0, 0.0)), &mut climber, dt, &settings));
+        assert!(anchor.follow(start.with_translation(Vec3::new(10.1, 0.0, 0.0)), &mut climber, dt, &settings));
+        assert!(anchor.velocity.unwrap().distance(Vec3::new(3.0, 0.0, 0.0)) < 1e-2);
+        assert!(!anchor.follow(start.with_translation(Vec3::new(10.1, 0.5, 0.0)), &mut climber, dt, &settings));
+    }
+}
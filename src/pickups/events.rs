@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::EventQueue;
 
 /// Event triggered when a pickup interaction occurs.
 #[derive(Debug, Clone, Copy)]
@@ -8,5 +9,4 @@ pub struct PickupEvent {
 }
 
 /// Custom queue for pickup events (mirrors InteractionEventQueue pattern).
-#[derive(Resource, Default)]
-pub struct PickupEventQueue(pub Vec<PickupEvent>);
+pub type PickupEventQueue = EventQueue<PickupEvent>;
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use crate::utils::EventQueuePlugin;
 
 pub mod chest_system;
 pub mod crate_system;
@@ -81,7 +82,44 @@ pub struct PickupsPlugin;
 
 impl Plugin for PickupsPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<PickupEventQueue>()
+        app
+            .register_type::<AmmoPickup>()
+            .register_type::<PickupTypeSettings>()
+            .register_type::<ExperiencePickup>()
+            .register_type::<HealthPickup>()
+            .register_type::<InventoryPickup>()
+            .register_type::<GrabObjectsStrengthPickup>()
+            .register_type::<CrateSystem>()
+            .register_type::<ExperienceMultiplierPickup>()
+            .register_type::<MeleeWeaponPickup>()
+            .register_type::<PickUpsScreenInfo>()
+            .register_type::<ShieldPickup>()
+            .register_type::<InventoryWeightBagPickup>()
+            .register_type::<ExplosiveBarrel>()
+            .register_type::<MeleeShieldPickup>()
+            .register_type::<InventoryExtraSpacePickup>()
+            .register_type::<DropPickUpSystem>()
+            .register_type::<MoneyPickup>()
+            .register_type::<PickUpManager>()
+            .register_type::<PowerPickup>()
+            .register_type::<EnergyPickup>()
+            .register_type::<MapPickup>()
+            .register_type::<PickUpIconInfo>()
+            .register_type::<OxygenPickup>()
+            .register_type::<SkillPointPickup>()
+            .register_type::<VehicleFuelPickup>()
+            .register_type::<PickUpIcon>()
+            .register_type::<PickUpElementInfo>()
+            .register_type::<PickUpObject>()
+            .register_type::<StaminaPickup>()
+            .register_type::<JetpackFuelPickup>()
+            .register_type::<WeaponAttachmentPickup>()
+            .register_type::<PlayerPickupIconManager>()
+            .register_type::<WeaponPickup>()
+            .register_type::<ChestSystem>()
+            .register_type::<MeleeWeaponConsumablePickup>()
+            .register_type::<GeneralPickup>()
+            .add_plugins(EventQueuePlugin::<PickupEvent>::default())
             .add_systems(Update, (
                 chest_system::update_chest_system,
                 drop_pickup_system::update_drop_pickup_system,
@@ -3,7 +3,7 @@ use bevy::prelude::*;
 /// Pickup type enumeration.
 ///
 ///
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, serde::Serialize, serde::Deserialize)]
 pub enum PickupKind {
     Ammo,
     Energy,
@@ -2,13 +2,13 @@ use bevy::prelude::*;
 
 use rand::Rng;
 
-use crate::currency::{AddCurrencyEventQueue, AddCurrencyEvent, CurrencyType};
+use crate::currency::{transaction_id, AddCurrencyEventQueue, AddCurrencyEvent, CurrencyType};
 use crate::experience::types::{ExperienceObtainedQueue, ExperienceObtainedEvent, PlayerExperience, ExperienceSettings};
 use crate::inventory::{Inventory, types::{InventoryItem, ItemType}};
 use crate::abilities::OxygenSystem;
 use crate::weapons::{Weapon, WeaponManager};
 
-use super::events::PickupEventQueue;
+use super::events::PickupEvent;
 use super::melee_weapon_consumable_pickup::MeleeWeaponConsumablePickup;
 use super::melee_shield_pickup::MeleeShieldPickup;
 use super::melee_weapon_pickup::MeleeWeaponPickup;
@@ -17,10 +17,11 @@ use super::money_pickup::MoneyPickup;
 use super::experience_pickup::ExperiencePickup;
 use super::experience_multiplier_pickup::ExperienceMultiplierPickup;
 use super::weapon_pickup::WeaponPickup;
+use crate::utils::QueueReader;
 
 pub fn process_pickup_events(
     mut commands: Commands,
-    mut events: ResMut<PickupEventQueue>,
+    mut events: QueueReader<PickupEvent>,
     mut weapon_manager_query: Query<&mut WeaponManager>,
     mut weapon_query: Query<(Entity, &mut Weapon, Option<&mut Visibility>)>,
     mut inventory_query: Query<&mut Inventory>,
@@ -38,7 +39,7 @@ pub fn process_pickup_events(
     experience_pickup_query: Query<&ExperiencePickup>,
     experience_multiplier_pickup_query: Query<&ExperienceMultiplierPickup>,
 ) {
-    for event in events.0.drain(..) {
+    for event in events.read().copied() {
         let mut picked = false;
 
         if let Ok(pickup) = weapon_pickup_query.get(event.target) {
@@ -79,7 +80,7 @@ pub fn process_pickup_events(
 
         if !picked {
             if let Ok(pickup) = money_pickup_query.get(event.target) {
-                picked = handle_money_pickup(event.source, pickup, &mut currency_events);
+                picked = handle_money_pickup(event.source, event.target, pickup, &mut currency_events);
             }
         }
 
@@ -304,6 +305,7 @@ fn handle_oxygen_pickup(
 
 fn handle_money_pickup(
     player: Entity,
+    pickup_entity: Entity,
     pickup: &MoneyPickup,
     currency_events: &mut ResMut<AddCurrencyEventQueue>,
 ) -> bool {
@@ -317,10 +319,12 @@ fn handle_money_pickup(
     };
 
     let currency_type = parse_currency_type(&pickup.currency_type);
-    currency_events.0.push(AddCurrencyEvent {
+    currency_events.send(AddCurrencyEvent {
         entity: player,
         amount,
         currency_type,
+        // A pickup pays out once, even if it is picked twice before it despawns
+        transaction_id: Some(transaction_id(("pickup", pickup_entity))),
     });
 
     true
@@ -349,7 +353,7 @@ fn handle_experience_pickup(
         pickup.amount
     };
 
-    experience_queue.0.push(ExperienceObtainedEvent {
+    experience_queue.send(ExperienceObtainedEvent {
         entity: player,
         amount,
         source_position: None,
@@ -4,6 +4,7 @@
 
 use bevy::prelude::*;
 use crate::input::InputState;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 pub struct FlyPlugin;
 
@@ -11,8 +12,8 @@ impl Plugin for FlyPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<Fly>()
-            .init_resource::<ToggleFlyModeQueue>()
-            .init_resource::<FlyTurboQueue>()
+            .add_plugins(EventQueuePlugin::<ToggleFlyModeEvent>::default())
+            .add_plugins(EventQueuePlugin::<FlyTurboEvent>::default())
             .add_systems(Update, (
                 handle_fly_mode_events,
                 update_fly_physics,
@@ -56,8 +57,7 @@ pub struct ToggleFlyModeEvent {
 }
 
 /// Queue for toggle events
-#[derive(Resource, Default)]
-pub struct ToggleFlyModeQueue(pub Vec<ToggleFlyModeEvent>);
+pub type ToggleFlyModeQueue = EventQueue<ToggleFlyModeEvent>;
 
 /// Event data to toggle turbo speed
 #[derive(Debug, Clone, Copy)]
@@ -67,16 +67,15 @@ pub struct FlyTurboEvent {
 }
 
 /// Queue for turbo events
-#[derive(Resource, Default)]
-pub struct FlyTurboQueue(pub Vec<FlyTurboEvent>);
+pub type FlyTurboQueue = EventQueue<FlyTurboEvent>;
 
 /// System to handle flight mode settings
 pub fn handle_fly_mode_events(
-    mut toggle_queue: ResMut<ToggleFlyModeQueue>,
-    mut turbo_queue: ResMut<FlyTurboQueue>,
+    mut toggle_queue: QueueReader<ToggleFlyModeEvent>,
+    mut turbo_queue: QueueReader<FlyTurboEvent>,
     mut query: Query<&mut Fly>,
 ) {
-    for event in toggle_queue.0.drain(..) {
+    for event in toggle_queue.read().copied() {
         if let Ok(mut fly) = query.get_mut(event.entity) {
             fly.active = !fly.active;
             fly.velocity = Vec3::ZERO; // Reset velocity on toggle
@@ -84,7 +83,7 @@ pub fn handle_fly_mode_events(
         }
     }
 
-    for event in turbo_queue.0.drain(..) {
+    for event in turbo_queue.read().copied() {
         if let Ok(mut fly) = query.get_mut(event.entity) {
             fly.turbo_active = event.active;
             info!("Fly System: Turbo active: {} for {:?}", fly.turbo_active, event.entity);
@@ -3,6 +3,7 @@
 //! Monitors time in air and triggers a free fall state if the player falls for too long.
 
 use bevy::prelude::*;
+use crate::utils::{EventQueue, EventQueuePlugin};
 
 pub struct FreeFallPlugin;
 
@@ -10,8 +11,8 @@ impl Plugin for FreeFallPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<FreeFall>()
-            .init_resource::<FreeFallEnterQueue>()
-            .init_resource::<FreeFallExitQueue>()
+            .add_plugins(EventQueuePlugin::<FreeFallEnterEvent>::default())
+            .add_plugins(EventQueuePlugin::<FreeFallExitEvent>::default())
             .add_systems(Update, (
                 update_free_fall_logic,
             ).chain());
@@ -47,8 +48,7 @@ pub struct FreeFallEnterEvent {
     pub entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct FreeFallEnterQueue(pub Vec<FreeFallEnterEvent>);
+pub type FreeFallEnterQueue = EventQueue<FreeFallEnterEvent>;
 
 /// Event data triggered when free fall ends
 #[derive(Debug, Clone, Copy)]
@@ -56,8 +56,7 @@ pub struct FreeFallExitEvent {
     pub entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct FreeFallExitQueue(pub Vec<FreeFallExitEvent>);
+pub type FreeFallExitQueue = EventQueue<FreeFallExitEvent>;
 
 /// System to monitor air time and trigger free fall
 pub fn update_free_fall_logic(
@@ -77,7 +76,7 @@ pub fn update_free_fall_logic(
             if free_fall.active {
                 free_fall.active = false;
                 free_fall.is_falling = false;
-                exit_queue.0.push(FreeFallExitEvent { entity });
+                exit_queue.send(FreeFallExitEvent { entity });
                 info!("Free Fall: Ended (Grounded)");
             }
             free_fall.last_grounded_time = time.elapsed_secs();
@@ -86,7 +85,7 @@ pub fn update_free_fall_logic(
 
             if !free_fall.active && time_in_air > free_fall.min_time_to_activate {
                 free_fall.active = true;
-                enter_queue.0.push(FreeFallEnterEvent { entity });
+                enter_queue.send(FreeFallEnterEvent { entity });
                 info!("Free Fall: Activated!");
             }
         }
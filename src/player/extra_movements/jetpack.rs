@@ -4,6 +4,7 @@
 
 use bevy::prelude::*;
 use crate::input::InputState;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 pub struct JetpackPlugin;
 
@@ -11,9 +12,9 @@ impl Plugin for JetpackPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<Jetpack>()
-            .init_resource::<EquipJetpackQueue>()
-            .init_resource::<ToggleJetpackQueue>()
-            .init_resource::<JetpackTurboQueue>()
+            .add_plugins(EventQueuePlugin::<EquipJetpackEvent>::default())
+            .add_plugins(EventQueuePlugin::<ToggleJetpackEvent>::default())
+            .add_plugins(EventQueuePlugin::<JetpackTurboEvent>::default())
             .add_systems(Update, (
                 handle_jetpack_events,
                 update_jetpack_physics,
@@ -73,8 +74,7 @@ pub struct EquipJetpackEvent {
     pub equip: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct EquipJetpackQueue(pub Vec<EquipJetpackEvent>);
+pub type EquipJetpackQueue = EventQueue<EquipJetpackEvent>;
 
 /// Event data to toggle active thrusting state
 #[derive(Debug, Clone, Copy)]
@@ -83,8 +83,7 @@ pub struct ToggleJetpackEvent {
     pub active: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct ToggleJetpackQueue(pub Vec<ToggleJetpackEvent>);
+pub type ToggleJetpackQueue = EventQueue<ToggleJetpackEvent>;
 
 /// Event data to toggle turbo mode
 #[derive(Debug, Clone, Copy)]
@@ -93,18 +92,17 @@ pub struct JetpackTurboEvent {
     pub active: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct JetpackTurboQueue(pub Vec<JetpackTurboEvent>);
+pub type JetpackTurboQueue = EventQueue<JetpackTurboEvent>;
 
 /// System to handle jetpack state events
 pub fn handle_jetpack_events(
-    mut equip_queue: ResMut<EquipJetpackQueue>,
-    mut toggle_queue: ResMut<ToggleJetpackQueue>,
-    mut turbo_queue: ResMut<JetpackTurboQueue>,
+    mut equip_queue: QueueReader<EquipJetpackEvent>,
+    mut toggle_queue: QueueReader<ToggleJetpackEvent>,
+    mut turbo_queue: QueueReader<JetpackTurboEvent>,
     mut query: Query<&mut Jetpack>,
     time: Res<Time>,
 ) {
-    for event in equip_queue.0.drain(..) {
+    for event in equip_queue.read().copied() {
         if let Ok(mut jetpack) = query.get_mut(event.entity) {
             jetpack.equipped = event.equip;
             if !jetpack.equipped {
@@ -114,7 +112,7 @@ pub fn handle_jetpack_events(
         }
     }
 
-    for event in toggle_queue.0.drain(..) {
+    for event in toggle_queue.read().copied() {
         if let Ok(mut jetpack) = query.get_mut(event.entity) {
             if jetpack.equipped && jetpack.current_fuel > 0.0 {
                 jetpack.active = event.active;
@@ -130,7 +128,7 @@ pub fn handle_jetpack_events(
         }
     }
     
-    for event in turbo_queue.0.drain(..) {
+    for event in turbo_queue.read().copied() {
         if let Ok(mut jetpack) = query.get_mut(event.entity) {
             jetpack.turbo_active = event.active;
             info!("Jetpack: Turbo state set to {} for {:?}", jetpack.turbo_active, event.entity);
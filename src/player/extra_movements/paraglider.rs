@@ -4,6 +4,7 @@
 
 use bevy::prelude::*;
 use crate::input::InputState;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 pub struct ParagliderPlugin;
 
@@ -11,7 +12,7 @@ impl Plugin for ParagliderPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<Paraglider>()
-            .init_resource::<ToggleParagliderQueue>()
+            .add_plugins(EventQueuePlugin::<ToggleParagliderEvent>::default())
             .add_systems(Update, (
                 handle_paraglider_input,
                 update_paraglider_physics,
@@ -55,17 +56,16 @@ pub struct ToggleParagliderEvent {
     pub active: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct ToggleParagliderQueue(pub Vec<ToggleParagliderEvent>);
+pub type ToggleParagliderQueue = EventQueue<ToggleParagliderEvent>;
 
 /// System to handle input and toggle gliding
 pub fn handle_paraglider_input(
     mut query: Query<(&mut Paraglider, &GlobalTransform)>,
-    mut toggle_queue: ResMut<ToggleParagliderQueue>,
+    mut toggle_queue: QueueReader<ToggleParagliderEvent>,
     input_state: Res<InputState>,
     time: Res<Time>,
 ) {
-    for event in toggle_queue.0.drain(..) {
+    for event in toggle_queue.read().copied() {
         if let Ok((mut paraglider, _)) = query.get_mut(event.entity) {
             paraglider.active = event.active;
             if !paraglider.active {
@@ -3,7 +3,8 @@
 //! Manages mechanics for rolling upon landing to mitigate fall impact.
 
 use bevy::prelude::*;
-use crate::input::InputState;
+use crate::input::{InputAction, InputState};
+use crate::utils::{EventQueue, EventQueuePlugin};
 
 pub struct RollOnLandingPlugin;
 
@@ -11,8 +12,8 @@ impl Plugin for RollOnLandingPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<RollOnLanding>()
-            .init_resource::<PrepareRollOnLandingQueue>()
-            .init_resource::<RollOnLandingExecuteQueue>()
+            .add_plugins(EventQueuePlugin::<PrepareRollOnLandingEvent>::default())
+            .add_plugins(EventQueuePlugin::<RollOnLandingExecuteEvent>::default())
             .add_systems(Update, (
                 handle_roll_input,
                 update_roll_landing_check,
@@ -56,8 +57,7 @@ pub struct PrepareRollOnLandingEvent {
     pub entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct PrepareRollOnLandingQueue(pub Vec<PrepareRollOnLandingEvent>);
+pub type PrepareRollOnLandingQueue = EventQueue<PrepareRollOnLandingEvent>;
 
 /// Event data when the roll actually executes
 #[derive(Debug, Clone, Copy)]
@@ -65,8 +65,7 @@ pub struct RollOnLandingExecuteEvent {
     pub entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct RollOnLandingExecuteQueue(pub Vec<RollOnLandingExecuteEvent>);
+pub type RollOnLandingExecuteQueue = EventQueue<RollOnLandingExecuteEvent>;
 
 /// System to handle input and arm the system
 pub fn handle_roll_input(
@@ -80,11 +79,11 @@ pub fn handle_roll_input(
             continue;
         }
 
-        if input_state.crouch_pressed { 
+        if input_state.intends(InputAction::Crouch) { 
              if !roll.prepared {
                  roll.prepared = true;
                  roll.last_input_time = time.elapsed_secs();
-                 prepare_queue.0.push(PrepareRollOnLandingEvent { entity: Entity::PLACEHOLDER }); // In real use, need entity ID
+                 prepare_queue.send(PrepareRollOnLandingEvent { entity: Entity::PLACEHOLDER }); // In real use, need entity ID
                  info!("Roll On Landing: Prepared");
              }
         }
@@ -116,7 +115,7 @@ pub fn update_roll_landing_check(
             roll.prepared = false;
             roll.executing = true;
             roll.execution_start_time = time.elapsed_secs();
-            execute_queue.0.push(RollOnLandingExecuteEvent { entity });
+            execute_queue.send(RollOnLandingExecuteEvent { entity });
             info!("Roll On Landing: Executing roll!");
         }
     }
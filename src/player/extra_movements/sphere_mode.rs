@@ -4,6 +4,7 @@
 
 use bevy::prelude::*;
 use crate::input::InputState;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 pub struct SphereModePlugin;
 
@@ -11,7 +12,7 @@ impl Plugin for SphereModePlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<SphereMode>()
-            .init_resource::<ToggleSphereModeQueue>()
+            .add_plugins(EventQueuePlugin::<ToggleSphereModeEvent>::default())
             .add_systems(Update, (
                 handle_sphere_mode_input,
                 update_sphere_physics,
@@ -50,15 +51,14 @@ pub struct ToggleSphereModeEvent {
     pub entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct ToggleSphereModeQueue(pub Vec<ToggleSphereModeEvent>);
+pub type ToggleSphereModeQueue = EventQueue<ToggleSphereModeEvent>;
 
 /// System to handle input and toggle sphere mode
 pub fn handle_sphere_mode_input(
     mut query: Query<&mut SphereMode>,
-    mut toggle_queue: ResMut<ToggleSphereModeQueue>,
+    mut toggle_queue: QueueReader<ToggleSphereModeEvent>,
 ) {
-    for event in toggle_queue.0.drain(..) {
+    for event in toggle_queue.read().copied() {
         if let Ok(mut sphere) = query.get_mut(event.entity) {
             sphere.active = !sphere.active;
             info!("Sphere Mode: Active state set to {} for {:?}", sphere.active, event.entity);
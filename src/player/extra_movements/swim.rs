@@ -24,17 +24,37 @@ impl Plugin for SwimPlugin {
 #[reflect(Component)]
 pub struct WaterZone {
     pub surface_height: f32, // Y coordinate of the water surface
-    // Volume dimensions could be added here, or handled by a Collider
+    pub radius: f32, // Horizontal reach from the zone origin
 }
 
 impl Default for WaterZone {
     fn default() -> Self {
         Self {
             surface_height: 0.0,
+            radius: 5.0,
         }
     }
 }
 
+/// Absolute surface height of the first water zone containing `point`
+pub fn water_surface_at<'a>(
+    zones: impl IntoIterator<Item = (&'a WaterZone, &'a GlobalTransform)>,
+    point: Vec3,
+) -> Option<f32> {
+    zones.into_iter().find_map(|(zone, zone_tf)| {
+        let zone_pos = zone_tf.translation();
+        (point.distance(zone_pos) < zone.radius).then_some(zone.surface_height + zone_pos.y)
+    })
+}
+
+/// Whether `point` is below the surface of a water zone
+pub fn is_underwater<'a>(
+    zones: impl IntoIterator<Item = (&'a WaterZone, &'a GlobalTransform)>,
+    point: Vec3,
+) -> bool {
+    water_surface_at(zones, point).is_some_and(|surface| point.y < surface)
+}
+
 /// Component to configure and manage swimming state on the player
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
@@ -79,21 +99,9 @@ pub fn handle_water_zone_interactions(
 ) {
     for (mut swim, player_tf) in player_query.iter_mut() {
         let player_pos = player_tf.translation();
-        let mut in_water = false;
-        let mut water_surface = 0.0;
-
-        // Simple distance/AABB check placeholder
-        for (zone, zone_tf) in zone_query.iter() {
-            let zone_pos = zone_tf.translation();
-            // Assuming a simple radius check or box check for now
-            if player_pos.distance(zone_pos) < 5.0 { // Placeholder Radius
-                in_water = true;
-                water_surface = zone.surface_height + zone_pos.y; // Absolute height
-                break;
-            }
-        }
 
-        if in_water {
+        // Simple radius check placeholder
+        if let Some(water_surface) = water_surface_at(zone_query.iter(), player_pos) {
             if !swim.active {
                 swim.active = true;
                 info!("Swim System: Entered water.");
@@ -4,7 +4,7 @@
 
 use bevy::prelude::*;
 use avian3d::prelude::*;
-use crate::input::InputState;
+use crate::input::{InputAction, InputState};
 use crate::physics::GroundDetection;
 
 pub struct WallRunPlugin;
@@ -224,7 +224,7 @@ pub fn update_wall_run_physics(
         }
 
         // Determine speed based on sprint input
-        let speed = if input_state.sprint_pressed {
+        let speed = if input_state.intends(InputAction::Sprint) {
             wall_run.wall_sprint_speed
         } else {
             wall_run.wall_run_speed
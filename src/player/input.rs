@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 use crate::character::types::*;
-use crate::input::InputState;
+use crate::input::{InputAction, InputState};
 use crate::actions::types::{PlayerActionSystem, ActionSystem};
 
 pub fn handle_player_input(
@@ -49,15 +49,15 @@ pub fn handle_player_input(
         state.lerped_move_dir = state.lerped_move_dir.lerp(move_dir, lerp_speed * time.delta_secs());
 
         state.is_running = true; 
-        state.is_sprinting = input.sprint_pressed;
+        state.is_sprinting = input.intends(InputAction::Sprint);
         
         // Crouch sliding check
-        if input.crouch_pressed && !state.is_crouching && state.is_sprinting && controller.crouch_sliding_enabled {
+        if input.intends(InputAction::Crouch) && !state.is_crouching && state.is_sprinting && controller.crouch_sliding_enabled {
             state.crouch_sliding_active = true;
             state.crouch_sliding_timer = controller.crouch_sliding_duration;
         }
         
-        state.is_crouching = input.crouch_pressed;
+        state.is_crouching = input.intends(InputAction::Crouch);
         state.wants_to_jump = input.jump_pressed;
         state.jump_held = input.jump_pressed; // Simple hold tracking
     }
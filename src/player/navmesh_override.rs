@@ -3,6 +3,7 @@
 //! Allows overriding player control with external NavMesh targets (e.g. for cutscenes or AI).
 
 use bevy::prelude::*;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 pub struct NavMeshOverridePlugin;
 
@@ -10,9 +11,9 @@ impl Plugin for NavMeshOverridePlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<NavMeshOverride>()
-            .init_resource::<EnableNavMeshOverrideQueue>()
-            .init_resource::<DisableNavMeshOverrideQueue>()
-            .init_resource::<SetNavMeshTargetQueue>()
+            .add_plugins(EventQueuePlugin::<EnableNavMeshOverrideEvent>::default())
+            .add_plugins(EventQueuePlugin::<DisableNavMeshOverrideEvent>::default())
+            .add_plugins(EventQueuePlugin::<SetNavMeshTargetEvent>::default())
             .add_systems(Update, (
                 handle_navmesh_override_events,
                 update_navmesh_override,
@@ -57,8 +58,7 @@ pub struct EnableNavMeshOverrideEvent {
     pub entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct EnableNavMeshOverrideQueue(pub Vec<EnableNavMeshOverrideEvent>);
+pub type EnableNavMeshOverrideQueue = EventQueue<EnableNavMeshOverrideEvent>;
 
 /// Event to disable NavMesh override
 #[derive(Debug, Clone, Copy)]
@@ -66,8 +66,7 @@ pub struct DisableNavMeshOverrideEvent {
     pub entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct DisableNavMeshOverrideQueue(pub Vec<DisableNavMeshOverrideEvent>);
+pub type DisableNavMeshOverrideQueue = EventQueue<DisableNavMeshOverrideEvent>;
 
 /// Event to set a new NavMesh target
 #[derive(Debug, Clone, Copy)]
@@ -77,24 +76,23 @@ pub struct SetNavMeshTargetEvent {
     pub target_entity: Option<Entity>,
 }
 
-#[derive(Resource, Default)]
-pub struct SetNavMeshTargetQueue(pub Vec<SetNavMeshTargetEvent>);
+pub type SetNavMeshTargetQueue = EventQueue<SetNavMeshTargetEvent>;
 
 /// System to handle override events
 pub fn handle_navmesh_override_events(
-    mut enable_queue: ResMut<EnableNavMeshOverrideQueue>,
-    mut disable_queue: ResMut<DisableNavMeshOverrideQueue>,
-    mut target_queue: ResMut<SetNavMeshTargetQueue>,
+    mut enable_queue: QueueReader<EnableNavMeshOverrideEvent>,
+    mut disable_queue: QueueReader<DisableNavMeshOverrideEvent>,
+    mut target_queue: QueueReader<SetNavMeshTargetEvent>,
     mut query: Query<&mut NavMeshOverride>,
 ) {
-    for event in enable_queue.0.drain(..) {
+    for event in enable_queue.read().copied() {
         if let Ok(mut nav_override) = query.get_mut(event.entity) {
             nav_override.active = true;
             info!("NavMesh Override: Enabled for {:?}", event.entity);
         }
     }
 
-    for event in disable_queue.0.drain(..) {
+    for event in disable_queue.read().copied() {
         if let Ok(mut nav_override) = query.get_mut(event.entity) {
             nav_override.active = false;
             nav_override.target_entity = None;
@@ -104,7 +102,7 @@ pub fn handle_navmesh_override_events(
         }
     }
 
-    for event in target_queue.0.drain(..) {
+    for event in target_queue.read().copied() {
         if let Ok(mut nav_override) = query.get_mut(event.entity) {
             if !nav_override.active {
                 warn!("NavMesh Override: Received target for {:?} but override is inactive", event.entity);
@@ -3,6 +3,7 @@
 //! Manages high-level player modes (e.g., Weapons, Powers) and control states (e.g., Regular, Driving).
 
 use bevy::prelude::*;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 pub struct PlayerModesPlugin;
 
@@ -12,9 +13,9 @@ impl Plugin for PlayerModesPlugin {
             .register_type::<PlayerModesSystem>()
             .register_type::<PlayerMode>()
             .register_type::<PlayerControlState>()
-            .init_resource::<SetPlayerModeQueue>()
-            .init_resource::<SetControlStateQueue>()
-            .init_resource::<PlayerModeChangedQueue>()
+            .add_plugins(EventQueuePlugin::<SetPlayerModeEvent>::default())
+            .add_plugins(EventQueuePlugin::<SetControlStateEvent>::default())
+            .add_plugins(EventQueuePlugin::<PlayerModeChangedEvent>::default())
             .add_systems(Update, (
                 handle_mode_changes,
                 handle_control_state_changes,
@@ -100,8 +101,7 @@ pub struct SetPlayerModeEvent {
     pub mode_name: String,
 }
 
-#[derive(Resource, Default)]
-pub struct SetPlayerModeQueue(pub Vec<SetPlayerModeEvent>);
+pub type SetPlayerModeQueue = EventQueue<SetPlayerModeEvent>;
 
 /// Event to set the active control state
 #[derive(Debug, Clone)]
@@ -110,8 +110,7 @@ pub struct SetControlStateEvent {
     pub state_name: String,
 }
 
-#[derive(Resource, Default)]
-pub struct SetControlStateQueue(pub Vec<SetControlStateEvent>);
+pub type SetControlStateQueue = EventQueue<SetControlStateEvent>;
 
 /// Event fired when player mode changes
 #[derive(Debug, Clone)]
@@ -121,16 +120,15 @@ pub struct PlayerModeChangedEvent {
     pub is_control_state: bool, // true if it's a control state change, false if it's a player mode change
 }
 
-#[derive(Resource, Default)]
-pub struct PlayerModeChangedQueue(pub Vec<PlayerModeChangedEvent>);
+pub type PlayerModeChangedQueue = EventQueue<PlayerModeChangedEvent>;
 
 /// System to handle player mode changes
 pub fn handle_mode_changes(
-    mut events_queue: ResMut<SetPlayerModeQueue>,
+    mut events_queue: QueueReader<SetPlayerModeEvent>,
     mut change_events_queue: ResMut<PlayerModeChangedQueue>,
     mut query: Query<&mut PlayerModesSystem>,
 ) {
-    for event in events_queue.0.drain(..) {
+    for event in events_queue.read().cloned() {
         if let Ok(mut modes_system) = query.get_mut(event.player_entity) {
             if !modes_system.change_mode_enabled {
                 continue;
@@ -154,7 +152,7 @@ pub fn handle_mode_changes(
                 modes_system.player_modes[index].is_current_state = true;
                 modes_system.current_players_mode_name = modes_system.player_modes[index].name.clone();
                 
-                 change_events_queue.0.push(PlayerModeChangedEvent {
+                 change_events_queue.send(PlayerModeChangedEvent {
                     player_entity: event.player_entity,
                     new_mode_name: event.mode_name.clone(),
                     is_control_state: false,
@@ -170,11 +168,11 @@ pub fn handle_mode_changes(
 
 /// System to handle control state changes
 pub fn handle_control_state_changes(
-    mut events_queue: ResMut<SetControlStateQueue>,
+    mut events_queue: QueueReader<SetControlStateEvent>,
     mut change_events_queue: ResMut<PlayerModeChangedQueue>,
     mut query: Query<&mut PlayerModesSystem>,
 ) {
-    for event in events_queue.0.drain(..) {
+    for event in events_queue.read().cloned() {
         if let Ok(mut modes_system) = query.get_mut(event.player_entity) {
             // Find if state exists and is enabled
              let state_index = modes_system.player_control_states.iter().position(|s| s.name == event.state_name);
@@ -194,7 +192,7 @@ pub fn handle_control_state_changes(
                 modes_system.player_control_states[index].is_current_state = true;
                 modes_system.current_control_state_name = modes_system.player_control_states[index].name.clone();
                 
-                change_events_queue.0.push(PlayerModeChangedEvent {
+                change_events_queue.send(PlayerModeChangedEvent {
                     player_entity: event.player_entity,
                     new_mode_name: event.state_name.clone(),
                     is_control_state: true,
@@ -3,6 +3,7 @@
 //! Manages player states with priorities and durations.
 
 use bevy::prelude::*;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 pub struct PlayerStatePlugin;
 
@@ -11,8 +12,8 @@ impl Plugin for PlayerStatePlugin {
         app
             .register_type::<PlayerStateSystem>()
             .register_type::<PlayerStateInfo>()
-            .init_resource::<SetPlayerStateQueue>()
-            .init_resource::<PlayerStateChangedQueue>()
+            .add_plugins(EventQueuePlugin::<SetPlayerStateEvent>::default())
+            .add_plugins(EventQueuePlugin::<PlayerStateChangedEvent>::default())
             .add_systems(Update, (
                 handle_state_change_events,
                 update_state_timers,
@@ -74,8 +75,7 @@ pub struct SetPlayerStateEvent {
     pub state_name: String,
 }
 
-#[derive(Resource, Default)]
-pub struct SetPlayerStateQueue(pub Vec<SetPlayerStateEvent>);
+pub type SetPlayerStateQueue = EventQueue<SetPlayerStateEvent>;
 
 #[derive(Debug, Clone)]
 pub struct PlayerStateChangedEvent {
@@ -84,16 +84,15 @@ pub struct PlayerStateChangedEvent {
     pub active: bool,
 }
 
-#[derive(Resource, Default)]
-pub struct PlayerStateChangedQueue(pub Vec<PlayerStateChangedEvent>);
+pub type PlayerStateChangedQueue = EventQueue<PlayerStateChangedEvent>;
 
 /// System to handle state change events
 pub fn handle_state_change_events(
-    mut events_queue: ResMut<SetPlayerStateQueue>,
+    mut events_queue: QueueReader<SetPlayerStateEvent>,
     mut change_events_queue: ResMut<PlayerStateChangedQueue>,
     mut query: Query<&mut PlayerStateSystem>,
 ) {
-    for event in events_queue.0.drain(..) {
+    for event in events_queue.read().cloned() {
         if let Ok(mut state_system) = query.get_mut(event.player_entity) {
             if !state_system.player_states_enabled {
                 continue;
@@ -133,7 +132,7 @@ pub fn handle_state_change_events(
                         state.state_active = false;
                         state.current_duration_timer = 0.0;
                         
-                        change_events_queue.0.push(PlayerStateChangedEvent {
+                        change_events_queue.send(PlayerStateChangedEvent {
                             player_entity: event.player_entity,
                             state_name: state.name.clone(),
                             active: false,
@@ -148,7 +147,7 @@ pub fn handle_state_change_events(
                 state.current_duration_timer = 0.0;
                 state_system.current_state_name = state_name_clone.clone();
                 
-                change_events_queue.0.push(PlayerStateChangedEvent {
+                change_events_queue.send(PlayerStateChangedEvent {
                     player_entity: event.player_entity,
                     state_name: state_name_clone,
                     active: true,
@@ -183,7 +182,7 @@ pub fn update_state_timers(
                     state.current_duration_timer = 0.0;
                     expired_states.push(state.name.clone());
                     
-                    change_events_queue.0.push(PlayerStateChangedEvent {
+                    change_events_queue.send(PlayerStateChangedEvent {
                         player_entity,
                         state_name: state.name.clone(),
                         active: false,
@@ -3,7 +3,8 @@
 //! Manages UI icons visually representing active player states.
 
 use bevy::prelude::*;
-use crate::player::player_state::{PlayerStateChangedEvent, PlayerStateChangedQueue};
+use crate::player::player_state::PlayerStateChangedEvent;
+use crate::utils::QueueReader;
 
 pub struct PlayerStateIconPlugin;
 
@@ -62,14 +63,14 @@ impl Default for PlayerStateIconSystem {
 
 /// System to handle visibility of icons based on state changes
 pub fn handle_state_icon_changes(
-    mut events_queue: ResMut<PlayerStateChangedQueue>,
+    mut events_queue: QueueReader<PlayerStateChangedEvent>,
     mut query: Query<&mut PlayerStateIconSystem>,
     mut visibility_query: Query<&mut Visibility>,
     time: Res<Time>,
 ) {
     // Note: This logic assumes this is the ONLY consumer of this queue. 
     // Queues drain events, removing them.
-    for event in events_queue.0.drain(..) {
+    for event in events_queue.read().cloned() {
         for mut icon_system in query.iter_mut() {
             if !icon_system.icons_enabled {
                 continue;
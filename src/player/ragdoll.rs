@@ -3,6 +3,7 @@
 //! Manages physics-based player deaths and falls (ragdolling).
 
 use bevy::prelude::*;
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
 
 pub struct RagdollPlugin;
 
@@ -11,8 +12,8 @@ impl Plugin for RagdollPlugin {
         app
             .register_type::<Ragdoll>()
             .register_type::<RagdollState>()
-            .init_resource::<ActivateRagdollQueue>()
-            .init_resource::<DeactivateRagdollQueue>()
+            .add_plugins(EventQueuePlugin::<ActivateRagdollEvent>::default())
+            .add_plugins(EventQueuePlugin::<DeactivateRagdollEvent>::default())
             .add_systems(Update, (
                 handle_ragdoll_activation,
                 update_ragdoll_state,
@@ -67,8 +68,7 @@ pub struct ActivateRagdollEvent {
     pub force_magnitude: f32,
 }
 
-#[derive(Resource, Default)]
-pub struct ActivateRagdollQueue(pub Vec<ActivateRagdollEvent>);
+pub type ActivateRagdollQueue = EventQueue<ActivateRagdollEvent>;
 
 /// Event to deactivate ragdoll and return to animation
 #[derive(Debug, Clone, Copy)]
@@ -76,16 +76,15 @@ pub struct DeactivateRagdollEvent {
     pub entity: Entity,
 }
 
-#[derive(Resource, Default)]
-pub struct DeactivateRagdollQueue(pub Vec<DeactivateRagdollEvent>);
+pub type DeactivateRagdollQueue = EventQueue<DeactivateRagdollEvent>;
 
 /// System to handle activation events
 pub fn handle_ragdoll_activation(
-    mut activate_queue: ResMut<ActivateRagdollQueue>,
-    mut deactivate_queue: ResMut<DeactivateRagdollQueue>,
+    mut activate_queue: QueueReader<ActivateRagdollEvent>,
+    mut deactivate_queue: QueueReader<DeactivateRagdollEvent>,
     mut query: Query<&mut Ragdoll>,
 ) {
-    for event in activate_queue.0.drain(..) {
+    for event in activate_queue.read().copied() {
         if let Ok(mut ragdoll) = query.get_mut(event.entity) {
              if ragdoll.current_state != RagdollState::Ragdolled {
                 ragdoll.current_state = RagdollState::Ragdolled;
@@ -96,7 +95,7 @@ pub fn handle_ragdoll_activation(
         }
     }
 
-    for event in deactivate_queue.0.drain(..) {
+    for event in deactivate_queue.read().copied() {
         if let Ok(mut ragdoll) = query.get_mut(event.entity) {
             if ragdoll.current_state == RagdollState::Ragdolled {
                 // Transition to blending state
@@ -13,9 +13,18 @@ impl Plugin for PointAndClickPlugin {
         app.register_type::<PointAndClickElement>()
            .register_type::<PointAndClickController>()
            .register_type::<PointAndClickElementType>()
+           .register_type::<PointAndClickCamera>()
+           .register_type::<PointAndClickCursorSettings>()
+           .register_type::<PointAndClickHoverState>()
+           .init_resource::<PointAndClickCursorSettings>()
+           .init_resource::<PointAndClickHoverState>()
            .add_systems(Update, (
+               update_point_and_click_hover,
                handle_mouse_click,
+               plan_point_and_click_orders,
                move_agent,
-           ));
+               trigger_point_and_click_interactions,
+               edge_scroll_camera,
+           ).chain());
     }
-}
+}
\ No newline at end of file
@@ -1,60 +1,159 @@
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
 use avian3d::prelude::*;
+use crate::ai::{find_nav_path, AiNavGraph};
+use crate::game_manager::types::CursorState;
+use crate::interaction::{InteractionEvent, InteractionEventQueue};
 use super::types::*;
 
+/// Casts a ray from the cursor into the world
+fn cursor_ray_hit(
+    window_query: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform)>,
+    spatial_query: &SpatialQuery,
+) -> Option<(Entity, Vec3)> {
+    let window = window_query.iter().next()?;
+    let cursor_position = window.cursor_position()?;
+    let (camera, camera_transform) = camera_query.iter().next()?;
+
+    // Create a ray from the camera into the world
+    let ray = camera.viewport_to_world(camera_transform, cursor_position).ok()?;
+
+    // Cast ray using Avian3d
+    let max_distance = 1000.0;
+    let filter = SpatialQueryFilter::default();
+
+    spatial_query
+        .cast_ray(ray.origin, ray.direction, max_distance, true, &filter)
+        .map(|hit| (hit.entity, ray.origin + ray.direction * hit.distance))
+}
+
+/// System to track the element under the cursor and swap the cursor icon
+pub fn update_point_and_click_hover(
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    spatial_query: SpatialQuery,
+    elements_query: Query<&PointAndClickElement>,
+    cursor_settings: Res<PointAndClickCursorSettings>,
+    mut hover_state: ResMut<PointAndClickHoverState>,
+    mut cursor_state: ResMut<CursorState>,
+) {
+    let hit = cursor_ray_hit(&window_query, &camera_query, &spatial_query);
+
+    let hovered = hit.and_then(|(entity, _)| {
+        elements_query
+            .get(entity)
+            .ok()
+            .filter(|element| element.enabled)
+            .map(|element| (entity, element.element_type))
+    });
+
+    let element_type = hovered.map(|(_, element_type)| element_type);
+    if hover_state.hovered != hovered.map(|(entity, _)| entity) || hover_state.element_type != element_type {
+        hover_state.hovered = hovered.map(|(entity, _)| entity);
+        hover_state.element_type = element_type;
+
+        if cursor_settings.enabled {
+            cursor_state.icon_override = Some(cursor_settings.cursor_for(element_type));
+        }
+    }
+    hover_state.hit_point = hit.map(|(_, point)| point);
+}
+
 /// System to handle mouse clicks for navigation
 pub fn handle_mouse_click(
     buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
     window_query: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
     spatial_query: SpatialQuery,
     mut controller_query: Query<&mut PointAndClickController>,
     elements_query: Query<(&GlobalTransform, &PointAndClickElement)>,
 ) {
+    // Right click or escape cancels everything
+    if buttons.just_pressed(MouseButton::Right) || keys.just_pressed(KeyCode::Escape) {
+        for mut controller in controller_query.iter_mut() {
+            if controller.enabled {
+                controller.cancel_orders();
+            }
+        }
+        return;
+    }
+
     if !buttons.just_pressed(MouseButton::Left) {
         return;
     }
 
-    let Ok(window) = window_query.iter().next().ok_or(()) else { return };
-    let Some(cursor_position) = window.cursor_position() else { return };
+    let Some((hit_entity, hit_point)) = cursor_ray_hit(&window_query, &camera_query, &spatial_query) else {
+        return;
+    };
 
-    let Ok((camera, camera_transform)) = camera_query.iter().next().ok_or(()) else { return };
+    let mut order = PointAndClickOrder {
+        destination: hit_point,
+        target: None,
+    };
 
-    // Create a ray from the camera into the world
-    let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_position) else { return };
+    if let Ok((element_xf, element)) = elements_query.get(hit_entity) {
+        if element.enabled {
+            let offset = element.interaction_offset;
+            let world_offset = element_xf.rotation() * offset;
+            order.destination = element_xf.translation() + world_offset;
+            order.target = Some(hit_entity);
+            info!("Clicked Element: {:?}, moving to {:?}", element.element_type, order.destination);
+        }
+    } else {
+        info!("Clicked Ground at: {:?}", order.destination);
+    }
 
-    // Cast ray using Avian3d
-    let max_distance = 1000.0;
-    let filter = SpatialQueryFilter::default();
+    let shift_held = keys.pressed(KeyCode::ShiftLeft) || keys.pressed(KeyCode::ShiftRight);
+
+    for mut controller in controller_query.iter_mut() {
+        if !controller.enabled {
+            continue;
+        }
 
-    if let Some(hit) = spatial_query.cast_ray(
-        ray.origin,
-        ray.direction,
-        max_distance,
-        true,
-        &filter
-    ) {
-        let hit_point = ray.origin + ray.direction * hit.distance;
-        
-        let mut target_pos = hit_point;
-        
-        if let Ok((element_xf, element)) = elements_query.get(hit.entity) {
-            if element.enabled {
-                let offset = element.interaction_offset;
-                let world_offset = element_xf.rotation() * offset;
-                target_pos = element_xf.translation() + world_offset;
-                info!("Clicked Element: {:?}, moving to {:?}", element.element_type, target_pos);
+        // Shift flips the configured semantics
+        let queue = match controller.click_mode {
+            PointAndClickClickMode::Replace => shift_held,
+            PointAndClickClickMode::Queue => !shift_held,
+        };
+
+        if queue && controller.current_order.is_some() {
+            if controller.queued_orders.len() < controller.max_queued_orders {
+                controller.queued_orders.push(order);
             }
         } else {
-             info!("Clicked Ground at: {:?}", target_pos);
+            controller.cancel_orders();
+            controller.queued_orders.push(order);
         }
+    }
+}
 
-        for mut controller in controller_query.iter_mut() {
-            if controller.enabled {
-                controller.current_target = Some(target_pos);
-            }
+/// System to start the next queued order and plan its path
+pub fn plan_point_and_click_orders(
+    graph: Res<AiNavGraph>,
+    mut agent_query: Query<(&Transform, &mut PointAndClickController)>,
+) {
+    for (transform, mut controller) in agent_query.iter_mut() {
+        if controller.current_order.is_some() || controller.queued_orders.is_empty() {
+            continue;
+        }
+
+        let order = controller.queued_orders.remove(0);
+
+        let mut path = if controller.use_pathfinding && !graph.nodes.is_empty() {
+            find_nav_path(&graph, transform.translation, order.destination, controller.waypoint_connection_radius)
+        } else {
+            Vec::new()
+        };
+        if path.is_empty() {
+            path.push(order.destination);
         }
+
+        controller.current_target = path.first().copied();
+        controller.path = path;
+        controller.current_order = Some(order);
+        controller.order_arrived = false;
     }
 }
 
@@ -62,6 +161,7 @@ pub fn handle_mouse_click(
 pub fn move_agent(
     time: Res<Time>,
     mut agent_query: Query<(&mut Transform, &mut PointAndClickController)>,
+    elements_query: Query<(&GlobalTransform, &PointAndClickElement)>,
 ) {
     let dt = time.delta_secs();
 
@@ -69,34 +169,176 @@ pub fn move_agent(
         let Some(target) = controller.current_target else { continue };
 
         let current_pos = transform.translation;
-        // Ignore Y for distance check (2D plane movement logic usually)
-        
+
+        // Stop as soon as the clicked element is within reach
+        let in_range = controller
+            .current_order
+            .and_then(|order| order.target)
+            .and_then(|target| elements_query.get(target).ok())
+            .is_some_and(|(element_xf, element)| {
+                let delta = element_xf.translation() - current_pos;
+                Vec2::new(delta.x, delta.z).length() <= element.interaction_range
+            });
+
         let delta = target - current_pos;
         let distance = delta.length();
-        
-        if distance < controller.stopping_distance {
-            // Reached target
-            controller.current_target = None;
+
+        if in_range || distance < controller.stopping_distance {
+            if !in_range && !controller.path.is_empty() {
+                controller.path.remove(0);
+            }
+
+            // Reached a waypoint; continue along the path or finish the order
+            if let (false, Some(next)) = (in_range, controller.path.first().copied()) {
+                controller.current_target = Some(next);
+            } else {
+                controller.path.clear();
+                controller.current_target = None;
+                controller.order_arrived = controller.current_order.is_some();
+            }
             continue;
         }
 
         let direction = delta.normalize_or_zero();
-        
+
         // Move
         transform.translation += direction * controller.move_speed * dt;
-        
+
         // Rotate to face target
         if direction.length_squared() > 0.001 {
-             let target_rot = Quat::from_rotation_arc(Vec3::Z, direction); // Assuming Z-forward model?
-             // Actually, usually models are -Z or +Z forward. 
-             // Let's assume standard Bevy forward (-Z).
-             // Quat::from_rotation_arc(Vec3::NEG_Z, direction)
-             
-             // Or simpler: look_at. But look_at modifies translation too if not careful with Up.
-             // Let's use rotate_towards logic if available, or just look_at for now.
-             
-             let flat_target = Vec3::new(target.x, current_pos.y, target.z);
-             transform.look_at(flat_target, Vec3::Y);
+            let flat_target = Vec3::new(target.x, current_pos.y, target.z);
+            let target_rotation = transform.looking_at(flat_target, Vec3::Y).rotation;
+            let t = (controller.rotation_speed * dt).clamp(0.0, 1.0);
+            transform.rotation = transform.rotation.slerp(target_rotation, t);
         }
     }
 }
+
+/// System to trigger the clicked element's interaction once the agent has arrived
+pub fn trigger_point_and_click_interactions(
+    mut agent_query: Query<(Entity, &mut PointAndClickController)>,
+    elements_query: Query<&PointAndClickElement>,
+    mut interaction_events: ResMut<InteractionEventQueue>,
+) {
+    for (agent, mut controller) in agent_query.iter_mut() {
+        if !controller.order_arrived {
+            continue;
+        }
+
+        if let Some(target) = controller.current_order.and_then(|order| order.target) {
+            if let Ok(element) = elements_query.get(target) {
+                if element.enabled && element.auto_interact {
+                    interaction_events.send(InteractionEvent {
+                        source: agent,
+                        target,
+                        interaction_type: element.interaction_type,
+                    });
+                }
+            }
+        }
+
+        controller.current_order = None;
+        controller.order_arrived = false;
+    }
+}
+
+/// System to scroll point and click cameras when the cursor touches the screen edges
+pub fn edge_scroll_camera(
+    time: Res<Time>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut Transform, &PointAndClickCamera)>,
+) {
+    let Some(window) = window_query.iter().next() else { return };
+    let Some(cursor) = window.cursor_position() else { return };
+    let size = Vec2::new(window.width(), window.height());
+    let dt = time.delta_secs();
+
+    for (mut transform, settings) in camera_query.iter_mut() {
+        if !settings.edge_scroll_enabled {
+            continue;
+        }
+
+        let mut input = Vec2::ZERO;
+        if cursor.x <= settings.edge_margin {
+            input.x -= 1.0;
+        } else if cursor.x >= size.x - settings.edge_margin {
+            input.x += 1.0;
+        }
+        // Screen Y grows downwards
+        if cursor.y <= settings.edge_margin {
+            input.y += 1.0;
+        } else if cursor.y >= size.y - settings.edge_margin {
+            input.y -= 1.0;
+        }
+        if input == Vec2::ZERO {
+            continue;
+        }
+
+        // Scroll on the ground plane relative to the camera heading
+        let forward = Vec3::new(transform.forward().x, 0.0, transform.forward().z).normalize_or_zero();
+        let right = Vec3::new(transform.right().x, 0.0, transform.right().z).normalize_or_zero();
+        let movement = (right * input.x + forward * input.y).normalize_or_zero() * settings.scroll_speed * dt;
+        transform.translation += movement;
+
+        if let Some(min) = settings.min_bounds {
+            transform.translation.x = transform.translation.x.max(min.x);
+            transform.translation.z = transform.translation.z.max(min.y);
+        }
+        if let Some(max) = settings.max_bounds {
+            transform.translation.x = transform.translation.x.min(max.x);
+            transform.translation.z = transform.translation.z.min(max.y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn test_queued_orders_walk_then_interact() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(EventQueuePlugin::<InteractionEvent>::default())
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<AiNavGraph>()
+            .add_systems(Update, (plan_point_and_click_orders, move_agent, trigger_point_and_click_interactions).chain());
+
+        let element = app.world_mut().spawn((GlobalTransform::from_xyz(5.0, 0.0, 0.0), PointAndClickElement::default())).id();
+        let agent = app.world_mut().spawn((
+            Transform::default(),
+            PointAndClickController {
+                queued_orders: vec![
+                    PointAndClickOrder { destination: Vec3::new(0.0, 0.0, -3.0), target: None },
+                    PointAndClickOrder { destination: Vec3::new(5.0, 0.0, 1.0), target: Some(element) },
+                ],
+                ..default()
+            },
+        )).id();
+
+        // The ground order runs first
+        app.update();
+        let controller = app.world().get::<PointAndClickController>(agent).unwrap();
+        assert_eq!(controller.current_order.map(|order| order.target), Some(None));
+        assert_eq!(controller.queued_orders.len(), 1);
+
+        let mut interacted = false;
+        for _ in 0..60 {
+            app.update();
+            if app.world().resource::<InteractionEventQueue>().iter().any(|event| event.source == agent && event.target == element) {
+                interacted = true;
+                break;
+            }
+        }
+        assert!(interacted);
+
+        // The interaction fires as soon as the element is in reach, not at the exact destination
+        let position = app.world().get::<Transform>(agent).unwrap().translation;
+        assert!(Vec2::new(position.x - 5.0, position.z).length() <= 1.5);
+        let controller = app.world().get::<PointAndClickController>(agent).unwrap();
+        assert!(controller.current_order.is_none() && controller.queued_orders.is_empty());
+    }
+}
@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use bevy::window::{CursorIcon, SystemCursorIcon};
+use crate::interaction::InteractionType;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
 pub enum PointAndClickElementType {
@@ -16,6 +18,11 @@ pub struct PointAndClickElement {
     pub element_type: PointAndClickElementType,
     pub interaction_offset: Vec3,
     pub enabled: bool,
+    /// Distance from the element at which the agent stops and interacts
+    pub interaction_range: f32,
+    pub interaction_type: InteractionType,
+    /// Interact automatically on arrival
+    pub auto_interact: bool,
 }
 
 impl Default for PointAndClickElement {
@@ -24,6 +31,9 @@ impl Default for PointAndClickElement {
             element_type: PointAndClickElementType::Device,
             interaction_offset: Vec3::new(0.0, 0.0, 1.0), // Default 1 unit forward
             enabled: true,
+            interaction_range: 1.5,
+            interaction_type: InteractionType::Use,
+            auto_interact: true,
         }
     }
 }
@@ -37,6 +47,17 @@ pub struct PointAndClickController {
     pub move_speed: f32,
     pub stopping_distance: f32,
     pub rotation_speed: f32,
+    /// Follow the AI waypoint graph instead of walking straight to the destination
+    pub use_pathfinding: bool,
+    pub waypoint_connection_radius: f32,
+    pub click_mode: PointAndClickClickMode,
+    pub max_queued_orders: usize,
+
+    // Debug State
+    pub current_order: Option<PointAndClickOrder>,
+    pub queued_orders: Vec<PointAndClickOrder>,
+    pub path: Vec<Vec3>,
+    pub order_arrived: bool,
 }
 
 impl Default for PointAndClickController {
@@ -47,6 +68,114 @@ impl Default for PointAndClickController {
             move_speed: 5.0,
             stopping_distance: 0.1,
             rotation_speed: 10.0,
+            use_pathfinding: true,
+            waypoint_connection_radius: 10.0,
+            click_mode: PointAndClickClickMode::Replace,
+            max_queued_orders: 8,
+            current_order: None,
+            queued_orders: Vec::new(),
+            path: Vec::new(),
+            order_arrived: false,
+        }
+    }
+}
+
+impl PointAndClickController {
+    /// Drops the current order, the queue and any path in progress
+    pub fn cancel_orders(&mut self) {
+        self.current_order = None;
+        self.queued_orders.clear();
+        self.path.clear();
+        self.current_target = None;
+        self.order_arrived = false;
+    }
+}
+
+/// What a new click does while the agent is busy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum PointAndClickClickMode {
+    /// A new click cancels the current order (holding shift queues instead)
+    #[default]
+    Replace,
+    /// A new click is queued after the current order (holding shift replaces instead)
+    Queue,
+}
+
+/// A move (and optional interact) order issued by a click
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct PointAndClickOrder {
+    pub destination: Vec3,
+    pub target: Option<Entity>,
+}
+
+/// Cursor shown while hovering each kind of element
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct PointAndClickCursorSettings {
+    pub enabled: bool,
+    pub default_cursor: SystemCursorIcon,
+    pub device_cursor: SystemCursorIcon,
+    pub vehicle_cursor: SystemCursorIcon,
+    pub friend_cursor: SystemCursorIcon,
+    pub enemy_cursor: SystemCursorIcon,
+}
+
+impl Default for PointAndClickCursorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_cursor: SystemCursorIcon::Default,
+            device_cursor: SystemCursorIcon::Pointer,
+            vehicle_cursor: SystemCursorIcon::Grab,
+            friend_cursor: SystemCursorIcon::Help,
+            enemy_cursor: SystemCursorIcon::Crosshair,
+        }
+    }
+}
+
+impl PointAndClickCursorSettings {
+    pub fn cursor_for(&self, element_type: Option<PointAndClickElementType>) -> CursorIcon {
+        let icon = match element_type {
+            None => self.default_cursor,
+            Some(PointAndClickElementType::Device) => self.device_cursor,
+            Some(PointAndClickElementType::Vehicle) => self.vehicle_cursor,
+            Some(PointAndClickElementType::Friend) => self.friend_cursor,
+            Some(PointAndClickElementType::Enemy) => self.enemy_cursor,
+        };
+        CursorIcon::System(icon)
+    }
+}
+
+/// Element currently under the cursor
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PointAndClickHoverState {
+    pub hovered: Option<Entity>,
+    pub element_type: Option<PointAndClickElementType>,
+    pub hit_point: Option<Vec3>,
+}
+
+/// Camera that scrolls when the cursor touches the screen edges
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct PointAndClickCamera {
+    pub edge_scroll_enabled: bool,
+    /// Edge thickness in logical pixels
+    pub edge_margin: f32,
+    pub scroll_speed: f32,
+    /// Optional XZ limits for the camera position
+    pub min_bounds: Option<Vec2>,
+    pub max_bounds: Option<Vec2>,
+}
+
+impl Default for PointAndClickCamera {
+    fn default() -> Self {
+        Self {
+            edge_scroll_enabled: true,
+            edge_margin: 12.0,
+            scroll_speed: 15.0,
+            min_bounds: None,
+            max_bounds: None,
         }
     }
 }
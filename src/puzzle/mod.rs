@@ -39,6 +39,7 @@
 //! - **Audio System**: For feedback sounds
 
 use bevy::prelude::*;
+use crate::utils::EventQueuePlugin;
 
 pub mod types;
 pub mod systems;
@@ -108,10 +109,12 @@ impl Plugin for PuzzlePlugin {
             .register_type::<PuzzleDebug>()
             .register_type::<PuzzleInteractable>()
             // Resources
-            .init_resource::<PuzzleEventQueue>()
-            .init_resource::<PuzzleSolvedEventQueue>()
-            .init_resource::<PuzzleFailedEventQueue>()
-            .init_resource::<PuzzleResetEventQueue>()
+            .add_plugins((
+                EventQueuePlugin::<PuzzleEvent>::default(),
+                EventQueuePlugin::<PuzzleSolvedEvent>::default(),
+                EventQueuePlugin::<PuzzleFailedEvent>::default(),
+                EventQueuePlugin::<PuzzleResetEvent>::default(),
+            ))
             .init_resource::<PuzzleDebugSettings>()
             .init_resource::<PuzzleUIState>()
             // Systems
@@ -128,10 +131,10 @@ impl Plugin for PuzzlePlugin {
             .add_systems(Update, (
                 update_puzzle_timers,
                 process_puzzle_events,
-                update_puzzle_ui,
-                debug_draw_puzzle_gizmos,
+                update_puzzle_ui.in_set(crate::headless::PresentationSet),
+                debug_draw_puzzle_gizmos.in_set(crate::headless::PresentationSet),
                 handle_puzzle_interactions,
             ).chain())
-            .add_systems(Startup, setup_puzzle_ui);
+            .add_systems(Startup, setup_puzzle_ui.in_set(crate::headless::PresentationSet));
     }
 }
@@ -1,8 +1,9 @@
 use bevy::prelude::*;
 use crate::input::{InputState, InputAction, InputBuffer};
-use crate::interaction::{Interactable, InteractionType, InteractionEventQueue, InteractionEvent};
+use crate::interaction::{Interactable, InteractionType, InteractionEvent};
 use avian3d::prelude::*;
 use super::types::*;
+use crate::utils::QueueReader;
 
 // ============================================================================
 // Systems
@@ -174,7 +175,7 @@ pub fn update_puzzle_buttons(
             transform.translation.y -= button.press_amount;
 
             // Trigger event
-            events.0.push(PuzzleEvent::ButtonPressed(PuzzleButtonPressedEvent {
+            events.send(PuzzleEvent::ButtonPressed(PuzzleButtonPressedEvent {
                     button_entity: Entity::PLACEHOLDER, // Will be set by caller
                 button_name: button.name.clone(),
             }));
@@ -240,7 +241,7 @@ pub fn update_puzzle_levers(
                 }
 
                 // Trigger event
-                events.0.push(PuzzleEvent::LeverMoved(PuzzleLeverMovedEvent {
+                events.send(PuzzleEvent::LeverMoved(PuzzleLeverMovedEvent {
                     lever_entity: Entity::PLACEHOLDER, // Will be set by caller
                     lever_name: lever.name.clone(),
                     new_state: lever.state,
@@ -287,7 +288,7 @@ pub fn update_puzzle_pressure_plates(
             }
 
             // Trigger event
-            events.0.push(PuzzleEvent::PressurePlatePressed(PuzzlePressurePlatePressedEvent {
+            events.send(PuzzleEvent::PressurePlatePressed(PuzzlePressurePlatePressedEvent {
                 plate_entity: Entity::PLACEHOLDER, // Will be set by caller
                 plate_name: plate.name.clone(),
                 weight: plate.current_weight,
@@ -345,7 +346,7 @@ pub fn update_puzzle_locks(
                 }
 
                 // Trigger event
-                events.0.push(PuzzleEvent::Solved(PuzzleSolvedEvent {
+                events.send(PuzzleEvent::Solved(PuzzleSolvedEvent {
                     puzzle_entity: Entity::PLACEHOLDER, // Will be set by caller
                     puzzle_name: lock.name.clone(),
                     time_spent: 0.0,
@@ -366,7 +367,7 @@ pub fn update_puzzle_locks(
                 }
 
                 // Trigger event
-                events.0.push(PuzzleEvent::Solved(PuzzleSolvedEvent {
+                events.send(PuzzleEvent::Solved(PuzzleSolvedEvent {
                     puzzle_entity: Entity::PLACEHOLDER, // Will be set by caller
                     puzzle_name: lock.name.clone(),
                     time_spent: 0.0,
@@ -409,7 +410,7 @@ pub fn update_puzzle_sequences(
                     progress.progress = progress.current_step as f32 / progress.total_steps as f32;
 
                     // Trigger event
-                    events.0.push(PuzzleEvent::SequenceItemPressed(PuzzleSequenceItemPressedEvent {
+                    events.send(PuzzleEvent::SequenceItemPressed(PuzzleSequenceItemPressedEvent {
                         item_entity: Entity::PLACEHOLDER, // Will be set by caller
                         item_name: item.name.clone(),
                         order_index: item.order_index,
@@ -422,7 +423,7 @@ pub fn update_puzzle_sequences(
                         progress.state = PuzzleState::Solved;
 
                         // Trigger solved event
-                        events.0.push(PuzzleEvent::Solved(PuzzleSolvedEvent {
+                        events.send(PuzzleEvent::Solved(PuzzleSolvedEvent {
                             puzzle_entity: Entity::PLACEHOLDER, // Will be set by caller
                             puzzle_name: sequence.name.clone(),
                             time_spent: progress.time_spent,
@@ -449,7 +450,7 @@ pub fn update_puzzle_sequences(
                         }
 
                         // Trigger event
-                        events.0.push(PuzzleEvent::SequenceItemPressed(PuzzleSequenceItemPressedEvent {
+                        events.send(PuzzleEvent::SequenceItemPressed(PuzzleSequenceItemPressedEvent {
                             item_entity: Entity::PLACEHOLDER, // Will be set by caller
                             item_name: item.name.clone(),
                             order_index: item.order_index,
@@ -493,7 +494,7 @@ pub fn update_puzzle_pianos(
                     key.current_rotation = new_rotation;
 
                     // Trigger event
-                    events.0.push(PuzzleEvent::PianoKeyPressed(PuzzlePianoKeyPressedEvent {
+                    events.send(PuzzleEvent::PianoKeyPressed(PuzzlePianoKeyPressedEvent {
                         key_entity: Entity::PLACEHOLDER, // Will be set by caller
                         key_name: key.name.clone(),
                         note: key.name.clone(),
@@ -558,7 +559,7 @@ pub fn update_puzzle_object_placements(
                 progress.progress = progress.current_step as f32 / progress.total_steps as f32;
 
                 // Trigger event
-                events.0.push(PuzzleEvent::ObjectPlaced(PuzzleObjectPlacedEvent {
+                events.send(PuzzleEvent::ObjectPlaced(PuzzleObjectPlacedEvent {
                     placement_entity: Entity::PLACEHOLDER, // Will be set by caller
                     placement_name: placement.name.clone(),
                     object_entity: placement.object_to_place.unwrap_or(Entity::PLACEHOLDER),
@@ -569,7 +570,7 @@ pub fn update_puzzle_object_placements(
                     progress.state = PuzzleState::Solved;
 
                     // Trigger solved event
-                    events.0.push(PuzzleEvent::Solved(PuzzleSolvedEvent {
+                    events.send(PuzzleEvent::Solved(PuzzleSolvedEvent {
                         puzzle_entity: Entity::PLACEHOLDER, // Will be set by caller
                         puzzle_name: placement.name.clone(),
                         time_spent: progress.time_spent,
@@ -653,7 +654,7 @@ pub fn update_puzzle_timers(
 
             // Trigger timeout event
             if timer.use_event_on_timeout {
-                events.0.push(PuzzleEvent::TimerTimeout(PuzzleTimerTimeoutEvent {
+                events.send(PuzzleEvent::TimerTimeout(PuzzleTimerTimeoutEvent {
                     puzzle_entity: Entity::PLACEHOLDER, // Will be set by caller
                     puzzle_name: String::new(), // Would be set by caller
                     time_spent: progress.time_spent,
@@ -664,7 +665,7 @@ pub fn update_puzzle_timers(
             progress.state = PuzzleState::Failed;
 
             // Trigger failed event
-            events.0.push(PuzzleEvent::Failed(PuzzleFailedEvent {
+            events.send(PuzzleEvent::Failed(PuzzleFailedEvent {
                 puzzle_entity: Entity::PLACEHOLDER, // Will be set by caller
                 puzzle_name: String::new(), // Would be set by caller
                 reason: "Time limit reached".to_string(),
@@ -678,25 +679,25 @@ pub fn update_puzzle_timers(
 
 /// System to process puzzle events
 pub fn process_puzzle_events(
-    mut events: ResMut<PuzzleEventQueue>,
+    mut events: QueueReader<PuzzleEvent>,
     mut solved_events: ResMut<PuzzleSolvedEventQueue>,
     mut failed_events: ResMut<PuzzleFailedEventQueue>,
     mut reset_events: ResMut<PuzzleResetEventQueue>,
 ) {
-    for event in events.0.drain(..) {
-        match event {
+    for event in events.read() {
+        match event.clone() {
             PuzzleEvent::Solved(e) => {
                 info!("Puzzle solved: {} (time: {:.1}s, resets: {})",
                     e.puzzle_name, e.time_spent, e.reset_count);
-                solved_events.0.push(e);
+                solved_events.send(e);
             }
             PuzzleEvent::Failed(e) => {
                 info!("Puzzle failed: {} - {}", e.puzzle_name, e.reason);
-                failed_events.0.push(e);
+                failed_events.send(e);
             }
             PuzzleEvent::Reset(e) => {
                 info!("Puzzle reset: {}", e.puzzle_name);
-                reset_events.0.push(e);
+                reset_events.send(e);
             }
             PuzzleEvent::ButtonPressed(e) => {
                 info!("Button pressed: {}", e.button_name);
@@ -1037,7 +1038,7 @@ pub fn update_progress_text(
 /// System to handle puzzle interactions
 /// This system integrates with the Interaction System's events
 pub fn handle_puzzle_interactions(
-    mut interaction_events: ResMut<InteractionEventQueue>,
+    mut interaction_events: QueueReader<InteractionEvent>,
     mut puzzle_events: ResMut<PuzzleEventQueue>,
     puzzle_interactables: Query<&PuzzleInteractable>,
     mut puzzle_buttons: Query<&mut PuzzleButton>,
@@ -1053,7 +1054,7 @@ pub fn handle_puzzle_interactions(
     mut puzzle_draggables: Query<&mut PuzzleDraggable>,
 ) {
     // Process interaction events
-    for interaction_event in interaction_events.0.drain(..) {
+    for interaction_event in interaction_events.read().copied() {
         if let Ok(puzzle_interactable) = puzzle_interactables.get(interaction_event.target) {
             if !puzzle_interactable.active {
                 continue;
@@ -1067,7 +1068,7 @@ pub fn handle_puzzle_interactions(
                         button.cooldown_timer = button.cooldown;
 
                         // Trigger event
-                        puzzle_events.0.push(PuzzleEvent::ButtonPressed(PuzzleButtonPressedEvent {
+                        puzzle_events.send(PuzzleEvent::ButtonPressed(PuzzleButtonPressedEvent {
                             button_entity: interaction_event.target,
                             button_name: button.name.clone(),
                         }));
@@ -1087,7 +1088,7 @@ pub fn handle_puzzle_interactions(
                         lever.state = new_state;
 
                         // Trigger event
-                        puzzle_events.0.push(PuzzleEvent::LeverMoved(PuzzleLeverMovedEvent {
+                        puzzle_events.send(PuzzleEvent::LeverMoved(PuzzleLeverMovedEvent {
                             lever_entity: interaction_event.target,
                             lever_name: lever.name.clone(),
                             new_state: lever.state,
@@ -1104,7 +1105,7 @@ pub fn handle_puzzle_interactions(
                         plate.press_timer = plate.press_duration;
 
                         // Trigger event
-                        puzzle_events.0.push(PuzzleEvent::PressurePlatePressed(PuzzlePressurePlatePressedEvent {
+                        puzzle_events.send(PuzzleEvent::PressurePlatePressed(PuzzlePressurePlatePressedEvent {
                             plate_entity: interaction_event.target,
                             plate_name: plate.name.clone(),
                             weight: plate.current_weight,
@@ -1134,7 +1135,7 @@ pub fn handle_puzzle_interactions(
                         key.is_pressed = true;
 
                         // Trigger event
-                        puzzle_events.0.push(PuzzleEvent::PianoKeyPressed(PuzzlePianoKeyPressedEvent {
+                        puzzle_events.send(PuzzleEvent::PianoKeyPressed(PuzzlePianoKeyPressedEvent {
                             key_entity: interaction_event.target,
                             key_name: key.name.clone(),
                             note: key.name.clone(),
@@ -1146,7 +1147,7 @@ pub fn handle_puzzle_interactions(
                         placement.object_inside_trigger = true;
 
                         // Trigger event
-                        puzzle_events.0.push(PuzzleEvent::ObjectPlaced(PuzzleObjectPlacedEvent {
+                        puzzle_events.send(PuzzleEvent::ObjectPlaced(PuzzleObjectPlacedEvent {
                             placement_entity: interaction_event.target,
                             placement_name: placement.name.clone(),
                             object_entity: placement.object_to_place.unwrap_or(Entity::PLACEHOLDER),
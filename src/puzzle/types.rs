@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use crate::interaction::InteractionType;
+use crate::utils::EventQueue;
 
 // ============================================================================
 // Puzzle System Core Components
@@ -883,18 +884,17 @@ pub enum PuzzleEvent {
     HintShown(PuzzleHintShownEvent),
 }
 
-/// Resource to manage puzzle events
-#[derive(Resource, Default)]
-pub struct PuzzleEventQueue(pub Vec<PuzzleEvent>);
+/// Queue for all puzzle events
+pub type PuzzleEventQueue = EventQueue<PuzzleEvent>;
 
-#[derive(Resource, Default)]
-pub struct PuzzleSolvedEventQueue(pub Vec<PuzzleSolvedEvent>);
+/// Queue for solved puzzles, fed by `process_puzzle_events`
+pub type PuzzleSolvedEventQueue = EventQueue<PuzzleSolvedEvent>;
 
-#[derive(Resource, Default)]
-pub struct PuzzleFailedEventQueue(pub Vec<PuzzleFailedEvent>);
+/// Queue for failed puzzles, fed by `process_puzzle_events`
+pub type PuzzleFailedEventQueue = EventQueue<PuzzleFailedEvent>;
 
-#[derive(Resource, Default)]
-pub struct PuzzleResetEventQueue(pub Vec<PuzzleResetEvent>);
+/// Queue for reset puzzles, fed by `process_puzzle_events`
+pub type PuzzleResetEventQueue = EventQueue<PuzzleResetEvent>;
 
 /// Resource for puzzle debug settings
 #[derive(Resource, Debug)]
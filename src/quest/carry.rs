@@ -0,0 +1,456 @@
+//! Quest items carried physically
+//!
+//! Items registered in [`QuestCarryItems`] get special treatment while a
+//! character carries them through
+//! [`CarryPhysicallyObjectFromInventory`](crate::inventory::CarryPhysicallyObjectFromInventory):
+//! the carrier plays a custom carry action, can be kept from sprinting and
+//! using weapons, may drop the item when hit (optionally failing the
+//! objective), and reports the item's position through
+//! [`QuestItemPositionEvent`] for escort and delivery objectives.
+//!
+//! ```rust,ignore
+//! fn setup(mut carry_items: ResMut<QuestCarryItems>) {
+//!     carry_items.register("ancient_relic", QuestCarryItem {
+//!         quest_id: 3,
+//!         objective_index: 1,
+//!         carry_action: "Carry Heavy".to_string(),
+//!         fail_objective_on_drop: true,
+//!         delivery_point: Some(Vec3::new(40.0, 0.0, -12.0)),
+//!         ..default()
+//!     });
+//! }
+//! ```
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+use super::{mark_objective_completed, QuestEvent, QuestEventQueue, QuestLog, QuestStatus};
+use crate::actions::types::{
+    ActivateCustomActionEvent, ActivateCustomActionEventQueue, StopCustomActionEvent, StopCustomActionEventQueue,
+};
+use crate::combat::types::DamageEvent;
+use crate::input::InputState;
+use crate::inventory::{CarryPhysicallyObjectFromInventory, DropInventoryItemEvent, DropInventoryItemEventQueue};
+use crate::utils::{EventQueue, QueueReader};
+
+/// How a quest item behaves while carried
+#[derive(Debug, Clone, Reflect)]
+pub struct QuestCarryItem {
+    pub quest_id: u32,
+    pub objective_index: usize,
+    /// Custom action played on the carrier while carrying; empty for none
+    pub carry_action: String,
+    pub allow_sprint: bool,
+    pub allow_weapons: bool,
+    pub drop_on_damage: bool,
+    /// Smallest hit that knocks the item loose
+    pub drop_damage_threshold: f32,
+    /// Fail the objective (and its quest) when the item is knocked loose
+    pub fail_objective_on_drop: bool,
+    /// Seconds between [`QuestItemPositionEvent`]s
+    pub position_update_interval: f32,
+    /// Where carrying the item completes the objective
+    pub delivery_point: Option<Vec3>,
+    pub delivery_radius: f32,
+}
+
+impl Default for QuestCarryItem {
+    fn default() -> Self {
+        Self {
+            quest_id: 0,
+            objective_index: 0,
+            carry_action: String::new(),
+            allow_sprint: false,
+            allow_weapons: false,
+            drop_on_damage: true,
+            drop_damage_threshold: 10.0,
+            fail_objective_on_drop: false,
+            position_update_interval: 0.5,
+            delivery_point: None,
+            delivery_radius: 2.0,
+        }
+    }
+}
+
+/// Quest carry settings by inventory item id
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct QuestCarryItems {
+    pub items: HashMap<String, QuestCarryItem>,
+}
+
+impl QuestCarryItems {
+    pub fn register(&mut self, item_id: impl Into<String>, item: QuestCarryItem) {
+        self.items.insert(item_id.into(), item);
+    }
+
+    pub fn get(&self, item_id: &str) -> Option<&QuestCarryItem> {
+        self.items.get(item_id)
+    }
+}
+
+/// Added to a character while it carries a quest item
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct QuestItemCarrier {
+    pub item_id: String,
+    pub settings: QuestCarryItem,
+    pub update_timer: f32,
+}
+
+/// Periodic position of a carried quest item
+#[derive(Debug, Clone, Reflect)]
+pub struct QuestItemPositionEvent {
+    pub carrier: Entity,
+    pub item_id: String,
+    pub quest_id: u32,
+    pub objective_index: usize,
+    pub position: Vec3,
+}
+
+pub type QuestItemPositionEventQueue = EventQueue<QuestItemPositionEvent>;
+
+type QuestCarryCandidate = Or<(With<CarryPhysicallyObjectFromInventory>, With<QuestItemCarrier>)>;
+
+/// System to mark characters carrying a registered quest item and start or stop their carry action
+pub fn track_quest_item_carriers(
+    mut commands: Commands,
+    carry_items: Res<QuestCarryItems>,
+    carriers: Query<
+        (Entity, Option<&CarryPhysicallyObjectFromInventory>, Option<&QuestItemCarrier>),
+        QuestCarryCandidate,
+    >,
+    mut activate_action_queue: ResMut<ActivateCustomActionEventQueue>,
+    mut stop_action_queue: ResMut<StopCustomActionEventQueue>,
+) {
+    for (entity, carry, carrier) in carriers.iter() {
+        let carried = carry
+            .filter(|carry| carry.carry)
+            .and_then(|carry| carry.item.as_ref())
+            .and_then(|item| carry_items.get(&item.item_id).map(|settings| (item.item_id.as_str(), settings)));
+
+        match (carried, carrier) {
+            (Some((item_id, _)), Some(carrier)) if carrier.item_id == item_id => {}
+            (Some((item_id, settings)), current) => {
+                if let Some(current) = current {
+                    stop_carry_action(&mut stop_action_queue, entity, &current.settings);
+                }
+                if !settings.carry_action.is_empty() {
+                    activate_action_queue.send(ActivateCustomActionEvent {
+                        player_entity: entity,
+                        action_name: settings.carry_action.clone(),
+                    });
+                }
+                commands.entity(entity).insert(QuestItemCarrier {
+                    item_id: item_id.to_string(),
+                    settings: settings.clone(),
+                    update_timer: 0.0,
+                });
+            }
+            (None, Some(current)) => {
+                stop_carry_action(&mut stop_action_queue, entity, &current.settings);
+                commands.entity(entity).remove::<QuestItemCarrier>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+fn stop_carry_action(queue: &mut StopCustomActionEventQueue, entity: Entity, settings: &QuestCarryItem) {
+    if !settings.carry_action.is_empty() {
+        queue.send(StopCustomActionEvent {
+            player_entity: entity,
+            action_name: settings.carry_action.clone(),
+        });
+    }
+}
+
+/// System to keep quest item carriers from sprinting and using weapons
+pub fn restrict_quest_item_carriers(mut query: Query<(&QuestItemCarrier, &mut InputState)>) {
+    for (carrier, mut input) in query.iter_mut() {
+        if !carrier.settings.allow_sprint {
+            input.sprint_pressed = false;
+        }
+
+        if !carrier.settings.allow_weapons {
+            input.aim_pressed = false;
+            input.fire_pressed = false;
+            input.fire_just_pressed = false;
+            input.reload_pressed = false;
+            input.attack_pressed = false;
+            input.attack_held = false;
+            input.attack_released = false;
+            input.block_pressed = false;
+            input.next_weapon_pressed = false;
+            input.prev_weapon_pressed = false;
+            input.select_weapon = None;
+        }
+    }
+}
+
+/// System to knock quest items loose from carriers that take a hard enough hit
+pub fn drop_quest_items_on_damage(
+    mut damage_events: QueueReader<DamageEvent>,
+    mut carriers: Query<(&QuestItemCarrier, &mut CarryPhysicallyObjectFromInventory, Option<&mut QuestLog>)>,
+    mut drop_events: ResMut<DropInventoryItemEventQueue>,
+    mut quest_events: ResMut<QuestEventQueue>,
+) {
+    for event in damage_events.read() {
+        let Ok((carrier, mut carry, log)) = carriers.get_mut(event.target) else { continue };
+        let settings = &carrier.settings;
+        if !carry.carry || !settings.drop_on_damage || event.amount < settings.drop_damage_threshold {
+            continue;
+        }
+
+        carry.carry = false;
+        carry.item = None;
+        drop_events.send(DropInventoryItemEvent {
+            owner: event.target,
+            item_id: carrier.item_id.clone(),
+            ..default()
+        });
+
+        if settings.fail_objective_on_drop {
+            if let Some(mut log) = log {
+                if mark_objective_failed(&mut log, settings.quest_id, settings.objective_index) {
+                    quest_events.send(QuestEvent::Failed(settings.quest_id));
+                }
+            }
+        }
+    }
+}
+
+/// System to report the position of carried quest items every `position_update_interval`
+pub fn send_quest_item_positions(
+    time: Res<Time>,
+    mut carriers: Query<(Entity, &mut QuestItemCarrier, &GlobalTransform)>,
+    mut position_events: ResMut<QuestItemPositionEventQueue>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut carrier, transform) in carriers.iter_mut() {
+        carrier.update_timer -= delta;
+        if carrier.update_timer > 0.0 {
+            continue;
+        }
+        carrier.update_timer = carrier.settings.position_update_interval.max(0.0);
+
+        position_events.send(QuestItemPositionEvent {
+            carrier: entity,
+            item_id: carrier.item_id.clone(),
+            quest_id: carrier.settings.quest_id,
+            objective_index: carrier.settings.objective_index,
+            position: transform.translation(),
+        });
+    }
+}
+
+/// System to complete delivery objectives once a carried item is reported near its delivery point
+pub fn complete_quest_item_deliveries(
+    mut position_events: QueueReader<QuestItemPositionEvent>,
+    carry_items: Res<QuestCarryItems>,
+    mut quest_logs: Query<&mut QuestLog>,
+    mut quest_events: ResMut<QuestEventQueue>,
+) {
+    for event in position_events.read() {
+        let Some(settings) = carry_items.get(&event.item_id) else { continue };
+        let Some(delivery_point) = settings.delivery_point else { continue };
+        if event.position.distance(delivery_point) > settings.delivery_radius {
+            continue;
+        }
+
+        let Ok(mut log) = quest_logs.get_mut(event.carrier) else { continue };
+        if mark_objective_completed(&mut log, event.quest_id, event.objective_index) {
+            quest_events.send(QuestEvent::ObjectiveCompleted(event.quest_id, event.objective_index));
+        }
+    }
+}
+
+fn mark_objective_failed(log: &mut QuestLog, quest_id: u32, objective_index: usize) -> bool {
+    let Some(quest) = log
+        .active_quests
+        .iter_mut()
+        .find(|quest| quest.id == quest_id && quest.status == QuestStatus::InProgress)
+    else {
+        return false;
+    };
+    let Some(objective) = quest.objectives.get_mut(objective_index) else { return false };
+    if objective.status == QuestStatus::Completed {
+        return false;
+    }
+
+    objective.status = QuestStatus::Failed;
+    quest.status = QuestStatus::Failed;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::types::{DamageEventQueue, DamageType};
+    use crate::inventory::{InventoryItem, ItemType};
+    use crate::quest::{Objective, Quest};
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[derive(Resource, Default)]
+    struct Log {
+        actions: Vec<String>,
+        drops: Vec<String>,
+        quests: Vec<String>,
+        positions: Vec<Vec3>,
+    }
+
+    fn log_events(
+        mut log: ResMut<Log>,
+        mut started: QueueReader<ActivateCustomActionEvent>,
+        mut stopped: QueueReader<StopCustomActionEvent>,
+        mut drops: QueueReader<DropInventoryItemEvent>,
+        mut quests: QueueReader<QuestEvent>,
+        mut positions: QueueReader<QuestItemPositionEvent>,
+    ) {
+        let log = &mut *log;
+        log.actions.extend(started.read().map(|event| format!("start {}", event.action_name)));
+        log.actions.extend(stopped.read().map(|event| format!("stop {}", event.action_name)));
+        log.drops.extend(drops.read().map(|event| event.item_id.clone()));
+        log.quests.extend(quests.read().map(|event| format!("{:?}", event)));
+        log.positions.extend(positions.read().map(|event| event.position));
+    }
+
+    fn item(item_id: &str) -> InventoryItem {
+        InventoryItem {
+            item_id: item_id.to_string(),
+            name: item_id.to_string(),
+            quantity: 1,
+            max_stack: 1,
+            weight: 20.0,
+            item_type: ItemType::KeyItem,
+            icon_path: String::new(),
+            value: 0.0,
+            category: String::new(),
+            min_level: 0,
+            info: String::new(),
+            is_infinite: false,
+        }
+    }
+
+    fn objective(name: &str) -> Objective {
+        Objective { name: name.to_string(), description: String::new(), status: QuestStatus::InProgress }
+    }
+
+    fn hit(app: &mut App, target: Entity, amount: f32) {
+        app.world_mut().resource_mut::<DamageEventQueue>().send(DamageEvent {
+            amount,
+            damage_type: DamageType::Melee,
+            source: None,
+            target,
+            position: None,
+            direction: None,
+            ignore_shield: false,
+        });
+        app.update();
+    }
+
+    #[test]
+    fn test_carried_quest_items_restrict_deliver_and_drop() {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            EventQueuePlugin::<QuestEvent>::default(),
+            EventQueuePlugin::<QuestItemPositionEvent>::default(),
+            EventQueuePlugin::<ActivateCustomActionEvent>::default(),
+            EventQueuePlugin::<StopCustomActionEvent>::default(),
+            EventQueuePlugin::<DamageEvent>::default(),
+            EventQueuePlugin::<DropInventoryItemEvent>::default(),
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+        .init_resource::<QuestCarryItems>()
+        .init_resource::<Log>()
+        .add_systems(Update, (
+            restrict_quest_item_carriers,
+            track_quest_item_carriers,
+            drop_quest_items_on_damage,
+            send_quest_item_positions,
+            complete_quest_item_deliveries,
+            log_events,
+        ).chain());
+
+        {
+            let mut carry_items = app.world_mut().resource_mut::<QuestCarryItems>();
+            carry_items.register("relic", QuestCarryItem {
+                quest_id: 3,
+                objective_index: 1,
+                carry_action: "Carry Heavy".to_string(),
+                delivery_point: Some(Vec3::new(10.0, 0.0, 0.0)),
+                ..default()
+            });
+            carry_items.register("idol", QuestCarryItem {
+                quest_id: 3,
+                objective_index: 0,
+                fail_objective_on_drop: true,
+                ..default()
+            });
+        }
+        let player = app
+            .world_mut()
+            .spawn((
+                GlobalTransform::default(),
+                InputState::default(),
+                CarryPhysicallyObjectFromInventory { item: Some(item("relic")), carry: true, ..default() },
+                QuestLog {
+                    active_quests: vec![Quest {
+                        id: 3,
+                        name: "Relics".to_string(),
+                        description: String::new(),
+                        objectives: vec![objective("Keep the idol"), objective("Bring the relic")],
+                        status: QuestStatus::InProgress,
+                        rewards_description: String::new(),
+                    }],
+                    completed_quests: Vec::new(),
+                },
+            ))
+            .id();
+
+        app.update();
+        assert_eq!(app.world().get::<QuestItemCarrier>(player).unwrap().item_id, "relic");
+        assert_eq!(app.world().resource::<Log>().positions, vec![Vec3::ZERO]);
+
+        {
+            let mut input = app.world_mut().get_mut::<InputState>(player).unwrap();
+            input.sprint_pressed = true;
+            input.fire_pressed = true;
+        }
+        app.update();
+        let input = app.world().get::<InputState>(player).unwrap();
+        assert!(!input.sprint_pressed && !input.fire_pressed);
+
+        // Reported near the delivery point within the update interval
+        *app.world_mut().get_mut::<GlobalTransform>(player).unwrap() = GlobalTransform::from_translation(Vec3::new(9.5, 0.0, 0.0));
+        for _ in 0..6 {
+            app.update();
+        }
+        let quest = &app.world().get::<QuestLog>(player).unwrap().active_quests[0];
+        assert_eq!(quest.objectives[1].status, QuestStatus::Completed);
+        assert_eq!(app.world().resource::<Log>().positions.last(), Some(&Vec3::new(9.5, 0.0, 0.0)));
+
+        app.world_mut().get_mut::<CarryPhysicallyObjectFromInventory>(player).unwrap().item = Some(item("idol"));
+        app.update();
+        assert_eq!(app.world().get::<QuestItemCarrier>(player).unwrap().item_id, "idol");
+
+        // A light hit keeps the idol, a hard one knocks it loose and fails the quest
+        hit(&mut app, player, 5.0);
+        assert!(app.world().get::<CarryPhysicallyObjectFromInventory>(player).unwrap().carry);
+        hit(&mut app, player, 15.0);
+        assert!(!app.world().get::<CarryPhysicallyObjectFromInventory>(player).unwrap().carry);
+        let quest = &app.world().get::<QuestLog>(player).unwrap().active_quests[0];
+        assert_eq!((quest.objectives[0].status, quest.status), (QuestStatus::Failed, QuestStatus::Failed));
+        app.update();
+        assert!(app.world().get::<QuestItemCarrier>(player).is_none());
+
+        let log = app.world().resource::<Log>();
+        assert_eq!(log.actions, ["start Carry Heavy", "stop Carry Heavy"]);
+        assert_eq!(log.drops, ["idol"]);
+        assert_eq!(log.quests, ["ObjectiveCompleted(3, 1)", "Failed(3)"]);
+    }
+}
@@ -1,5 +1,13 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use crate::hud::{HudElement, HudPriority};
+use crate::utils::{EventQueue, EventQueuePlugin, QueueReader};
+
+pub mod carry;
+pub mod photo;
+
+pub use carry::{QuestCarryItem, QuestCarryItems, QuestItemCarrier, QuestItemPositionEvent, QuestItemPositionEventQueue};
+pub use photo::{PhotoObjective, PhotoJournal, JournalPhoto};
 
 /// The status of a quest or an objective.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Reflect)]
@@ -46,9 +54,8 @@ pub enum QuestEvent {
     Failed(u32),
 }
 
-/// Custom queue for quest events (Workaround for Bevy 0.18 EventReader issues)
-#[derive(Resource, Default)]
-pub struct QuestEventQueue(pub Vec<QuestEvent>);
+/// Queue for quest events
+pub type QuestEventQueue = EventQueue<QuestEvent>;
 
 /// Component for entities that can give quests (NPCs, boards, etc.).
 #[derive(Component, Debug, Clone, Reflect)]
@@ -91,12 +98,18 @@ pub struct QuestPlugin;
 
 impl Plugin for QuestPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<QuestEventQueue>()
+        app.add_plugins(EventQueuePlugin::<QuestEvent>::default())
+            .add_plugins(EventQueuePlugin::<QuestItemPositionEvent>::default())
+            .init_resource::<QuestCarryItems>()
             .register_type::<QuestLog>()
             .register_type::<QuestStation>()
             .register_type::<ObjectiveTrigger>()
             .register_type::<QuestTrackerRoot>()
             .register_type::<QuestTrackerText>()
+            .register_type::<QuestCarryItems>()
+            .register_type::<QuestItemCarrier>()
+            .register_type::<PhotoObjective>()
+            .register_type::<PhotoJournal>()
             .add_systems(Startup, setup_quest_tracker_ui)
             .add_systems(Update, (
                 handle_quest_events,
@@ -107,19 +120,30 @@ impl Plugin for QuestPlugin {
                 update_quest_tracker_ui,
                 sync_quest_station_markers,
                 sync_objective_trigger_markers,
-            ));
+                sync_objective_trigger_highlights,
+                photo::complete_photo_objectives,
+            ))
+            .add_systems(Update, carry::restrict_quest_item_carriers
+                .after(crate::input::InputSet::Gather)
+                .before(crate::input::InputSet::Apply))
+            .add_systems(Update, (
+                carry::track_quest_item_carriers,
+                carry::drop_quest_items_on_damage,
+                carry::send_quest_item_positions,
+                carry::complete_quest_item_deliveries,
+            ).chain());
     }
 }
 
 /// System to handle interactions with QuestStations.
 fn handle_quest_interactions(
     mut commands: Commands,
-    mut interaction_events: ResMut<crate::interaction::InteractionEventQueue>,
+    mut interaction_events: QueueReader<crate::interaction::InteractionEvent>,
     quest_stations: Query<&QuestStation>,
     mut quest_logs: Query<(Entity, &mut QuestLog)>,
     mut quest_events: ResMut<QuestEventQueue>,
 ) {
-    for event in interaction_events.0.iter() {
+    for event in interaction_events.read() {
         if let Ok(station) = quest_stations.get(event.target) {
             // Find the quest log for the source (interactor)
             if let Ok((_log_entity, mut log)) = quest_logs.get_mut(event.source) {
@@ -134,7 +158,7 @@ fn handle_quest_interactions(
                     info!("Quest '{}' accepted!", station.quest.name);
 
                     // Trigger quest started event
-                    quest_events.0.push(QuestEvent::Started(station.quest.id));
+                    quest_events.send(QuestEvent::Started(station.quest.id));
                 } else {
                     info!("Player already has quest '{}' (active or complete)", station.quest.name);
                 }
@@ -148,12 +172,12 @@ fn handle_quest_interactions(
 
 /// System to handle interactions with ObjectiveTriggers.
 fn handle_objective_trigger_interactions(
-    mut interaction_events: ResMut<crate::interaction::InteractionEventQueue>,
+    mut interaction_events: QueueReader<crate::interaction::InteractionEvent>,
     trigger_query: Query<&ObjectiveTrigger>,
     mut quest_logs: Query<&mut QuestLog>,
     mut quest_events: ResMut<QuestEventQueue>,
 ) {
-    for event in interaction_events.0.iter() {
+    for event in interaction_events.read() {
         let Ok(trigger) = trigger_query.get(event.target) else { continue };
         if !trigger.is_active || !trigger.activate_on_interaction {
             continue;
@@ -161,7 +185,7 @@ fn handle_objective_trigger_interactions(
 
         let Ok(mut log) = quest_logs.get_mut(event.source) else { continue };
         if mark_objective_completed(&mut log, trigger.quest_id, trigger.objective_index) {
-            quest_events.0.push(QuestEvent::ObjectiveCompleted(trigger.quest_id, trigger.objective_index));
+            quest_events.send(QuestEvent::ObjectiveCompleted(trigger.quest_id, trigger.objective_index));
         }
     }
 }
@@ -191,7 +215,7 @@ fn handle_objective_trigger_enter(
 
         let Ok(mut log) = quest_logs.get_mut(player_entity) else { continue };
         if mark_objective_completed(&mut log, trigger.quest_id, trigger.objective_index) {
-            quest_events.0.push(QuestEvent::ObjectiveCompleted(trigger.quest_id, trigger.objective_index));
+            quest_events.send(QuestEvent::ObjectiveCompleted(trigger.quest_id, trigger.objective_index));
             if trigger.single_use {
                 trigger.is_active = false;
             }
@@ -208,10 +232,9 @@ fn handle_objective_trigger_enter(
 
 /// System to handle quest-related events and update the QuestLog.
 fn handle_quest_events(
-    mut events: ResMut<QuestEventQueue>,
+    mut events: QueueReader<QuestEvent>,
 ) {
-    // Process events and then clear the queue
-    for event in events.0.iter() {
+    for event in events.read() {
         match event {
             QuestEvent::Started(id) => {
                 info!("Quest started event: {}", id);
@@ -227,9 +250,6 @@ fn handle_quest_events(
             }
         }
     }
-    
-    // Clear the queue after processing - similar to how Bevy events work
-    events.0.clear();
 }
 
 /// System to automatically update quest status based on objective progress.
@@ -293,6 +313,7 @@ fn setup_quest_tracker_ui(mut commands: Commands) {
             },
             BackgroundColor(Color::srgba(0.05, 0.05, 0.05, 0.7)),
             QuestTrackerRoot,
+            HudElement::new("quest_tracker", HudPriority::Detail).fading(),
         ))
         .with_children(|parent| {
             parent.spawn((
@@ -331,9 +352,11 @@ fn update_quest_tracker_ui(
     text.0 = lines.join("\n");
 }
 
+type ChangedQuestStation = Or<(Added<QuestStation>, Changed<QuestStation>)>;
+
 fn sync_quest_station_markers(
     mut commands: Commands,
-    query: Query<(Entity, &QuestStation), Or<(Added<QuestStation>, Changed<QuestStation>)>>,
+    query: Query<(Entity, &QuestStation), ChangedQuestStation>,
 ) {
     for (entity, station) in query.iter() {
         if !station.show_on_map {
@@ -354,9 +377,11 @@ fn sync_quest_station_markers(
     }
 }
 
+type ChangedObjectiveTrigger = Or<(Added<ObjectiveTrigger>, Changed<ObjectiveTrigger>)>;
+
 fn sync_objective_trigger_markers(
     mut commands: Commands,
-    query: Query<(Entity, &ObjectiveTrigger), Or<(Added<ObjectiveTrigger>, Changed<ObjectiveTrigger>)>>,
+    query: Query<(Entity, &ObjectiveTrigger), ChangedObjectiveTrigger>,
 ) {
     for (entity, trigger) in query.iter() {
         if !trigger.show_on_map {
@@ -376,3 +401,38 @@ fn sync_objective_trigger_markers(
         });
     }
 }
+
+/// System to highlight the triggers of objectives the player still has to complete.
+fn sync_objective_trigger_highlights(
+    mut highlights: ResMut<crate::highlight::HighlightCommandQueue>,
+    trigger_query: Query<(Entity, &ObjectiveTrigger)>,
+    quest_logs: Query<&QuestLog>,
+    mut highlighted: Local<Vec<Entity>>,
+) {
+    let pending = |trigger: &ObjectiveTrigger| {
+        quest_logs.iter().any(|log| {
+            log.active_quests.iter().any(|quest| {
+                quest.id == trigger.quest_id
+                    && quest.status == QuestStatus::InProgress
+                    && quest
+                        .objectives
+                        .get(trigger.objective_index)
+                        .is_some_and(|objective| objective.status != QuestStatus::Completed)
+            })
+        })
+    };
+
+    let targets: Vec<Entity> = trigger_query
+        .iter()
+        .filter(|(_, trigger)| trigger.is_active && pending(trigger))
+        .map(|(entity, _)| entity)
+        .collect();
+
+    for entity in highlighted.iter().filter(|entity| !targets.contains(entity)) {
+        highlights.release(*entity, crate::highlight::HighlightKind::QuestTarget);
+    }
+    for entity in targets.iter().filter(|entity| !highlighted.contains(entity)) {
+        highlights.request(*entity, crate::highlight::HighlightKind::QuestTarget);
+    }
+    *highlighted = targets;
+}
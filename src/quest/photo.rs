@@ -0,0 +1,173 @@
+//! Photo objectives
+//!
+//! A [`PhotoObjective`] completes a quest objective when the player takes a
+//! screenshot (`TakeScreenshotEvent`, e.g. from photo mode) that frames every
+//! listed [`CameraPerspective`](crate::camera::captures::CameraPerspective)
+//! within its distance and angle. Listing several subjects asks for a
+//! composition: all of them in the same shot. Every photo the player takes is
+//! kept in their [`PhotoJournal`], tagged with the objectives it completed.
+//!
+//! ```rust,ignore
+//! commands.spawn((Transform::from_xyz(4.0, 2.0, -8.0), CameraPerspective { name: "Lighthouse".into(), ..default() }));
+//! commands.spawn((Transform::from_xyz(6.0, 0.0, -9.0), CameraPerspective { name: "Wreck".into(), ..default() }));
+//! commands.spawn(PhotoObjective::new(5, 0, ["Lighthouse", "Wreck"]));
+//! ```
+
+use bevy::prelude::*;
+
+use super::{mark_objective_completed, QuestEvent, QuestEventQueue, QuestLog};
+use crate::camera::captures::{CaptureSlot, PhotoTakenEvent};
+use crate::utils::QueueReader;
+
+/// Completes an objective when a photo frames all of `subjects`
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct PhotoObjective {
+    pub quest_id: u32,
+    pub objective_index: usize,
+    /// `CameraPerspective` names that must all be in the same shot
+    pub subjects: Vec<String>,
+    pub is_active: bool,
+}
+
+impl PhotoObjective {
+    pub fn new(quest_id: u32, objective_index: usize, subjects: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            quest_id,
+            objective_index,
+            subjects: subjects.into_iter().map(Into::into).collect(),
+            is_active: true,
+        }
+    }
+
+    pub fn is_satisfied_by(&self, photo: &PhotoTakenEvent) -> bool {
+        !self.subjects.is_empty() && self.subjects.iter().all(|subject| photo.frames(subject))
+    }
+}
+
+/// A photo kept in the journal
+#[derive(Debug, Clone, Reflect)]
+pub struct JournalPhoto {
+    pub slot: CaptureSlot,
+    /// Saved screenshot, when the request gave a path
+    pub path: Option<String>,
+    pub subjects: Vec<String>,
+    /// (quest id, objective index) pairs this photo completed
+    pub objectives: Vec<(u32, usize)>,
+}
+
+/// The player's photo gallery
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct PhotoJournal {
+    pub photos: Vec<JournalPhoto>,
+    /// Oldest photos that completed no objective make room beyond this
+    pub max_photos: usize,
+}
+
+impl Default for PhotoJournal {
+    fn default() -> Self {
+        Self { photos: Vec::new(), max_photos: 64 }
+    }
+}
+
+impl PhotoJournal {
+    pub fn add(&mut self, photo: JournalPhoto) {
+        self.photos.push(photo);
+        while self.photos.len() > self.max_photos {
+            // Objective photos stay as proof
+            let Some(index) = self.photos.iter().position(|photo| photo.objectives.is_empty()) else { break };
+            self.photos.remove(index);
+        }
+    }
+
+    /// Photos that completed the objective
+    pub fn for_objective(&self, quest_id: u32, objective_index: usize) -> impl Iterator<Item = &JournalPhoto> {
+        self.photos.iter().filter(move |photo| photo.objectives.contains(&(quest_id, objective_index)))
+    }
+}
+
+/// System to complete photo objectives and file every photo in the player's journal
+pub fn complete_photo_objectives(
+    mut commands: Commands,
+    mut photos: QueueReader<PhotoTakenEvent>,
+    mut player_query: Query<(Entity, &mut QuestLog, Option<&mut PhotoJournal>), With<crate::character::Player>>,
+    mut objective_query: Query<&mut PhotoObjective>,
+    mut quest_events: ResMut<QuestEventQueue>,
+) {
+    let Some((player, mut log, mut journal)) = player_query.iter_mut().next() else {
+        photos.clear();
+        return;
+    };
+
+    let mut new_journal = None;
+    for photo in photos.read() {
+        let mut completed = Vec::new();
+        for mut objective in objective_query.iter_mut() {
+            if !objective.is_active || !objective.is_satisfied_by(photo) {
+                continue;
+            }
+            if mark_objective_completed(&mut log, objective.quest_id, objective.objective_index) {
+                quest_events.send(QuestEvent::ObjectiveCompleted(objective.quest_id, objective.objective_index));
+                completed.push((objective.quest_id, objective.objective_index));
+                objective.is_active = false;
+            }
+        }
+
+        let entry = JournalPhoto {
+            slot: photo.slot.clone(),
+            path: photo.path.clone(),
+            subjects: photo.subjects.iter().map(|subject| subject.name.clone()).collect(),
+            objectives: completed,
+        };
+        match journal.as_deref_mut() {
+            Some(journal) => journal.add(entry),
+            None => new_journal.get_or_insert_with(PhotoJournal::default).add(entry),
+        }
+    }
+
+    if let Some(journal) = new_journal {
+        commands.entity(player).insert(journal);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_photo_objective_composition() {
+        use crate::camera::captures::{CaptureSlot, PhotoSubject, PhotoTakenEvent};
+
+        let mut world = World::new();
+        let photo = |names: &[&str], world: &mut World| PhotoTakenEvent {
+            slot: CaptureSlot { name: "Photo".into(), date: String::new(), camera_pos: Vec3::ZERO, camera_rot: Quat::IDENTITY, fov: 60.0 },
+            path: None,
+            subjects: names
+                .iter()
+                .map(|name| PhotoSubject { entity: world.spawn_empty().id(), name: name.to_string(), distance: 5.0, angle: 3.0 })
+                .collect(),
+        };
+
+        // Both subjects have to be in the same shot
+        let objective = PhotoObjective::new(5, 0, ["Lighthouse", "Wreck"]);
+        assert!(!objective.is_satisfied_by(&photo(&["Lighthouse"], &mut world)));
+        assert!(objective.is_satisfied_by(&photo(&["Wreck", "Gull", "Lighthouse"], &mut world)));
+        assert!(!PhotoObjective::new(5, 1, Vec::<String>::new()).is_satisfied_by(&photo(&["Wreck"], &mut world)));
+
+        let mut journal = PhotoJournal { max_photos: 2, ..default() };
+        let entry = |objectives: Vec<(u32, usize)>| JournalPhoto {
+            slot: photo(&[], &mut World::new()).slot,
+            path: None,
+            subjects: Vec::new(),
+            objectives,
+        };
+        journal.add(entry(vec![(5, 0)]));
+        journal.add(entry(Vec::new()));
+        journal.add(entry(Vec::new()));
+        // The objective photo stays
+        assert_eq!(journal.photos.len(), 2);
+        assert_eq!(journal.for_objective(5, 0).count(), 1);
+    }
+}
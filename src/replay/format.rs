@@ -0,0 +1,156 @@
+//! Replay file format
+//!
+//! Little-endian binary:
+//!
+//! - `b"BARP"` magic and a `u16` format version
+//! - `u32` length + JSON metadata (sample rate, entity names, gameplay events)
+//! - `u32` frame count, then per frame: `f32` time, `u32` pose count and per pose
+//!   `u32` id, 3 × `f32` translation, 4 × `i16` normalized rotation (22 bytes)
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use super::types::{Replay, ReplayEntityInfo, ReplayEvent, ReplayFrame, ReplayTransform};
+
+const MAGIC: &[u8; 4] = b"BARP";
+const VERSION: u16 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct ReplayMetadata {
+    sample_rate: f32,
+    entities: Vec<ReplayEntityInfo>,
+    events: Vec<ReplayEvent>,
+}
+
+impl Replay {
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        let metadata = ReplayMetadata {
+            sample_rate: self.sample_rate,
+            entities: self.entities.clone(),
+            events: self.events.iter().cloned().collect(),
+        };
+        let metadata = serde_json::to_vec(&metadata)
+            .map_err(|e| format!("Failed to serialize replay metadata: {}", e))?;
+
+        let pose_count: usize = self.frames.iter().map(|frame| frame.transforms.len()).sum();
+        let mut bytes = Vec::with_capacity(
+            14 + metadata.len() + self.frames.len() * 8 + pose_count * 22,
+        );
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(metadata.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&metadata);
+
+        bytes.extend_from_slice(&(self.frames.len() as u32).to_le_bytes());
+        for frame in &self.frames {
+            bytes.extend_from_slice(&frame.time.to_le_bytes());
+            bytes.extend_from_slice(&(frame.transforms.len() as u32).to_le_bytes());
+            for pose in &frame.transforms {
+                bytes.extend_from_slice(&pose.id.to_le_bytes());
+                for value in pose.translation.to_array() {
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+                for value in pose.rotation.normalize().to_array() {
+                    bytes.extend_from_slice(&quantize(value).to_le_bytes());
+                }
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = ByteReader { bytes, position: 0 };
+
+        if reader.take(4)? != MAGIC {
+            return Err("Not a replay file".to_string());
+        }
+        let version = reader.read_u16()?;
+        if version != VERSION {
+            return Err(format!("Unsupported replay version {}", version));
+        }
+
+        let metadata_len = reader.read_u32()? as usize;
+        let metadata: ReplayMetadata = serde_json::from_slice(reader.take(metadata_len)?)
+            .map_err(|e| format!("Failed to parse replay metadata: {}", e))?;
+
+        let frame_count = reader.read_u32()? as usize;
+        let mut frames = VecDeque::with_capacity(frame_count.min(reader.remaining() / 8));
+        for _ in 0..frame_count {
+            let time = reader.read_f32()?;
+            let pose_count = reader.read_u32()? as usize;
+            let mut transforms = Vec::with_capacity(pose_count.min(reader.remaining() / 22));
+            for _ in 0..pose_count {
+                let id = reader.read_u32()?;
+                let translation = Vec3::new(reader.read_f32()?, reader.read_f32()?, reader.read_f32()?);
+                let rotation = Quat::from_xyzw(
+                    dequantize(reader.read_i16()?),
+                    dequantize(reader.read_i16()?),
+                    dequantize(reader.read_i16()?),
+                    dequantize(reader.read_i16()?),
+                )
+                .normalize();
+                transforms.push(ReplayTransform { id, translation, rotation });
+            }
+            frames.push_back(ReplayFrame { time, transforms });
+        }
+
+        Ok(Self {
+            sample_rate: metadata.sample_rate,
+            entities: metadata.entities,
+            frames,
+            events: metadata.events.into(),
+        })
+    }
+}
+
+fn quantize(value: f32) -> i16 {
+    (value.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16
+}
+
+fn dequantize(value: i16) -> f32 {
+    value as f32 / i16::MAX as f32
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if len > self.remaining() {
+            return Err("Replay file is truncated".to_string());
+        }
+        let slice = &self.bytes[self.position..self.position + len];
+        self.position += len;
+        Ok(slice)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.take(N)?);
+        Ok(array)
+    }
+
+    fn read_u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_i16(&mut self) -> Result<i16, String> {
+        Ok(i16::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_f32(&mut self) -> Result<f32, String> {
+        Ok(f32::from_le_bytes(self.read_array()?))
+    }
+}
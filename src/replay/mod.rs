@@ -0,0 +1,82 @@
+//! Replay recording and playback
+//!
+//! Records the transforms of [`ReplayRecordable`] entities (players, AI and vehicles
+//! by default) at [`ReplaySettings::sample_rate`], together with damage, deaths,
+//! ability activations and vehicle state changes, into a rolling buffer. The buffer
+//! can be saved to a compact binary file and played back later, for kill cams, bug
+//! reports and trailers.
+//!
+//! Everything is driven through [`ReplayCommand`]s:
+//!
+//! ```rust,ignore
+//! fn save_and_watch(mut replay: ResMut<ReplayCommandQueue>) {
+//!     replay.send(ReplayCommand::Save { path: "bug_1234.replay".into() });
+//!     replay.send(ReplayCommand::Play);
+//!     replay.send(ReplayCommand::SeekBy(-10.0));
+//! }
+//! ```
+//!
+//! Playback pauses virtual time, so gameplay freezes while recorded poses are applied.
+//! While playing: Space pauses, Left/Right scrub, Up/Down change speed, Escape stops;
+//! hold the right mouse button to look around and fly with WASD/QE.
+
+pub mod types;
+pub mod format;
+pub mod systems;
+
+use bevy::prelude::*;
+use bevy::transform::TransformSystems;
+use types::*;
+use systems::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    ReplayRecordable, ReplayId, ReplayEntityInfo, ReplayTransform, ReplayFrame, ReplayEventKind,
+    ReplayEvent, Replay, ReplaySettings, ReplayRecorder, ReplayPlayback, ReplayCamera, ReplayGhost,
+    ReplayCommand, ReplayCommandQueue, ReplayStatusEvent, ReplayStatusEventQueue,
+    ReplayPlaybackEventQueue,
+};
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ReplaySettings>()
+            .init_resource::<ReplayRecorder>()
+            .init_resource::<ReplayPlayback>()
+            .add_plugins((
+                EventQueuePlugin::<ReplayCommand>::default(),
+                EventQueuePlugin::<ReplayStatusEvent>::default(),
+                EventQueuePlugin::<ReplayEvent>::default(),
+            ))
+            .register_type::<ReplaySettings>()
+            .register_type::<ReplayRecordable>()
+            .register_type::<ReplayId>()
+            .register_type::<ReplayCamera>()
+            .register_type::<ReplayGhost>()
+            .add_systems(Startup, start_recording_on_startup)
+            .add_systems(Update, (
+                mark_replay_recordables,
+                (
+                    handle_replay_input,
+                    process_replay_commands,
+                ).chain(),
+            ))
+            // Playback overrides whatever gameplay wrote this frame
+            .add_systems(PostUpdate, (
+                advance_replay_playback,
+                update_replay_camera,
+            ).chain().before(TransformSystems::Propagate))
+            .add_systems(PostUpdate, (
+                assign_replay_ids,
+                record_replay_events,
+                record_replay_frames,
+            ).chain().after(TransformSystems::Propagate));
+
+        #[cfg(feature = "vehicles")]
+        app.add_systems(PostUpdate, record_vehicle_replay_events
+            .after(record_replay_events)
+            .before(record_replay_frames));
+    }
+}
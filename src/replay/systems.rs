@@ -0,0 +1,696 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::input::mouse::AccumulatedMouseMotion;
+use bevy::platform::collections::HashMap;
+use std::path::PathBuf;
+use super::types::*;
+use crate::abilities::AbilityInfo;
+use crate::ai::AiController;
+use crate::character::Player;
+use crate::combat::{DamageResultEvent, DeathEvent};
+use crate::utils::QueueReader;
+#[cfg(feature = "vehicles")]
+use crate::vehicles::Vehicle;
+
+/// Minimum and maximum playback speed
+const SPEED_RANGE: (f32, f32) = (0.125, 8.0);
+
+pub fn start_recording_on_startup(
+    settings: Res<ReplaySettings>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut status: ResMut<ReplayStatusEventQueue>,
+) {
+    if settings.record_on_start {
+        recorder.recording = true;
+        recorder.started_at = 0.0;
+        status.send(ReplayStatusEvent::RecordingStarted);
+    }
+}
+
+/// Tags players, AI and vehicles for recording
+pub fn mark_replay_recordables(
+    mut commands: Commands,
+    settings: Res<ReplaySettings>,
+    players: Query<Entity, (Added<Player>, Without<ReplayRecordable>)>,
+    ai: Query<Entity, (Added<AiController>, Without<ReplayRecordable>)>,
+    #[cfg(feature = "vehicles")] vehicles: Query<Entity, (Added<Vehicle>, Without<ReplayRecordable>)>,
+) {
+    let mut mark = |entity: Entity| {
+        commands.entity(entity).insert(ReplayRecordable);
+    };
+
+    if settings.auto_record_players {
+        players.iter().for_each(&mut mark);
+    }
+    if settings.auto_record_ai {
+        ai.iter().for_each(&mut mark);
+    }
+    #[cfg(feature = "vehicles")]
+    if settings.auto_record_vehicles {
+        vehicles.iter().for_each(&mut mark);
+    }
+}
+
+type UnassignedRecordable = (With<ReplayRecordable>, Without<ReplayId>);
+
+pub fn assign_replay_ids(
+    mut commands: Commands,
+    mut recorder: ResMut<ReplayRecorder>,
+    query: Query<(Entity, Option<&Name>), UnassignedRecordable>,
+) {
+    for (entity, name) in query.iter() {
+        let id = recorder.next_id;
+        recorder.next_id += 1;
+
+        let name = name.map(|name| name.to_string()).unwrap_or_else(|| format!("{}", entity));
+        recorder.replay.entities.push(ReplayEntityInfo { id, name });
+        commands.entity(entity).insert(ReplayId(id));
+    }
+}
+
+/// Samples recorded transforms at `ReplaySettings::sample_rate`
+pub fn record_replay_frames(
+    time: Res<Time>,
+    settings: Res<ReplaySettings>,
+    playback: Res<ReplayPlayback>,
+    mut recorder: ResMut<ReplayRecorder>,
+    query: Query<(&ReplayId, &GlobalTransform)>,
+) {
+    if !recorder.recording || playback.active {
+        return;
+    }
+
+    recorder.sample_timer += time.delta_secs();
+    let interval = 1.0 / settings.sample_rate.max(1.0);
+    if recorder.sample_timer < interval && !recorder.replay.frames.is_empty() {
+        return;
+    }
+    recorder.sample_timer = recorder.sample_timer.rem_euclid(interval);
+
+    let now = time.elapsed_secs() - recorder.started_at;
+    let transforms = query
+        .iter()
+        .map(|(id, transform)| {
+            let (_, rotation, translation) = transform.to_scale_rotation_translation();
+            ReplayTransform { id: id.0, translation, rotation }
+        })
+        .collect();
+
+    recorder.replay.sample_rate = settings.sample_rate;
+    recorder.replay.frames.push_back(ReplayFrame { time: now, transforms });
+
+    if settings.buffer_duration > 0.0 {
+        recorder.replay.trim_before(now - settings.buffer_duration);
+    }
+}
+
+/// Combat queues recorded into replays
+#[derive(SystemParam)]
+pub struct RecordedCombatEvents<'w, 's> {
+    pub damage_results: QueueReader<'w, 's, DamageResultEvent>,
+    pub deaths: QueueReader<'w, 's, DeathEvent>,
+}
+
+/// Records damage, deaths and ability activations
+pub fn record_replay_events(
+    time: Res<Time>,
+    playback: Res<ReplayPlayback>,
+    mut recorder: ResMut<ReplayRecorder>,
+    combat: RecordedCombatEvents,
+    abilities: Query<(Entity, &AbilityInfo, Option<&ChildOf>), Changed<AbilityInfo>>,
+    ids: Query<&ReplayId>,
+    mut ability_states: Local<HashMap<Entity, bool>>,
+) {
+    let RecordedCombatEvents { mut damage_results, mut deaths } = combat;
+    let recording = recorder.recording && !playback.active;
+    let now = time.elapsed_secs() - recorder.started_at;
+    let id_of = |entity: Entity| ids.get(entity).ok().map(|id| id.0);
+    let mut events = Vec::new();
+
+    for result in damage_results.read() {
+        let Some(target) = id_of(result.target) else { continue };
+        events.push(ReplayEventKind::Damage {
+            source: result.source.and_then(id_of),
+            target,
+            amount: result.final_amount,
+            shielded: result.shielded_amount,
+            damage_type: format!("{:?}", result.damage_type),
+            is_crit: result.is_crit,
+            is_block: result.is_block,
+        });
+    }
+
+    for death in deaths.read() {
+        if let Some(entity) = id_of(death.entity) {
+            events.push(ReplayEventKind::Death { entity });
+        }
+    }
+
+    for (entity, ability, parent) in abilities.iter() {
+        let was_active = ability_states.insert(entity, ability.active).unwrap_or(false);
+        if !ability.active || was_active {
+            continue;
+        }
+        // Abilities usually live on a child of the character using them
+        let owner = id_of(entity).or_else(|| parent.and_then(|parent| id_of(parent.parent())));
+        if let Some(owner) = owner {
+            events.push(ReplayEventKind::AbilityUsed { entity: owner, ability: ability.name.clone() });
+        }
+    }
+
+    if recording {
+        recorder
+            .replay
+            .events
+            .extend(events.into_iter().map(|kind| ReplayEvent { time: now, kind }));
+    }
+}
+
+/// Records vehicles starting/stopping driving, boosting or changing gear
+#[cfg(feature = "vehicles")]
+pub fn record_vehicle_replay_events(
+    time: Res<Time>,
+    playback: Res<ReplayPlayback>,
+    mut recorder: ResMut<ReplayRecorder>,
+    vehicles: Query<(Entity, &Vehicle), Changed<Vehicle>>,
+    ids: Query<&ReplayId>,
+    mut vehicle_states: Local<HashMap<Entity, (usize, bool, bool)>>,
+) {
+    let recording = recorder.recording && !playback.active;
+    let now = time.elapsed_secs() - recorder.started_at;
+    let mut events = Vec::new();
+
+    for (entity, vehicle) in vehicles.iter() {
+        let state = (vehicle.current_gear, vehicle.is_driving, vehicle.is_boosting);
+        if vehicle_states.insert(entity, state) == Some(state) {
+            continue;
+        }
+        if let Ok(&ReplayId(id)) = ids.get(entity) {
+            events.push(ReplayEventKind::VehicleState {
+                vehicle: id,
+                speed: vehicle.current_speed,
+                gear: vehicle.current_gear,
+                driving: vehicle.is_driving,
+                boosting: vehicle.is_boosting,
+            });
+        }
+    }
+
+    if recording {
+        recorder
+            .replay
+            .events
+            .extend(events.into_iter().map(|kind| ReplayEvent { time: now, kind }));
+    }
+}
+
+/// Playback keys: pause, scrub, speed and stop
+pub fn handle_replay_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<ReplaySettings>,
+    playback: Res<ReplayPlayback>,
+    mut commands: ResMut<ReplayCommandQueue>,
+) {
+    if !playback.active {
+        return;
+    }
+
+    if keyboard.just_pressed(settings.pause_key) {
+        commands.send(ReplayCommand::TogglePause);
+    }
+    if keyboard.just_pressed(settings.seek_back_key) {
+        commands.send(ReplayCommand::SeekBy(-settings.seek_step));
+    }
+    if keyboard.just_pressed(settings.seek_forward_key) {
+        commands.send(ReplayCommand::SeekBy(settings.seek_step));
+    }
+    if keyboard.just_pressed(settings.faster_key) {
+        commands.send(ReplayCommand::SetSpeed(playback.speed * 2.0));
+    }
+    if keyboard.just_pressed(settings.slower_key) {
+        commands.send(ReplayCommand::SetSpeed(playback.speed * 0.5));
+    }
+    if keyboard.just_pressed(settings.stop_key) {
+        commands.send(ReplayCommand::Stop);
+    }
+}
+
+/// Replay settings, the recording and its playback
+#[derive(SystemParam)]
+pub struct ReplaySession<'w> {
+    pub settings: Res<'w, ReplaySettings>,
+    pub recorder: ResMut<'w, ReplayRecorder>,
+    pub playback: ResMut<'w, ReplayPlayback>,
+}
+
+pub fn process_replay_commands(
+    mut commands: Commands,
+    mut replay_commands: QueueReader<ReplayCommand>,
+    mut status: ResMut<ReplayStatusEventQueue>,
+    session: ReplaySession,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut cameras: Query<(Entity, &mut Camera, &GlobalTransform), Without<ReplayCamera>>,
+    mut recorded: Query<(Entity, &ReplayId, &mut Transform)>,
+) {
+    let ReplaySession { settings, mut recorder, mut playback } = session;
+    for command in replay_commands.read() {
+        match command {
+            ReplayCommand::StartRecording => {
+                recorder.replay.frames.clear();
+                recorder.replay.events.clear();
+                recorder.sample_timer = 0.0;
+                recorder.started_at = virtual_time.elapsed_secs();
+                recorder.recording = true;
+                status.send(ReplayStatusEvent::RecordingStarted);
+            }
+            ReplayCommand::StopRecording => {
+                recorder.recording = false;
+                status.send(ReplayStatusEvent::RecordingStopped);
+            }
+            ReplayCommand::Save { path } => {
+                let result = save_replay(&recorder.replay, &resolve_path(&settings, path));
+                status.send(match result {
+                    Ok(path) => ReplayStatusEvent::Saved { path },
+                    Err(error) => ReplayStatusEvent::Failed { error },
+                });
+            }
+            ReplayCommand::Load { path } => {
+                match load_replay(&resolve_path(&settings, path)) {
+                    Ok(replay) => {
+                        playback.loaded = Some(replay);
+                        status.send(ReplayStatusEvent::Loaded { path: path.clone() });
+                    }
+                    Err(error) => status.send(ReplayStatusEvent::Failed { error }),
+                }
+            }
+            ReplayCommand::Unload => {
+                playback.loaded = None;
+            }
+            ReplayCommand::Play => {
+                if playback.active {
+                    playback.paused = false;
+                    continue;
+                }
+
+                let replay = playback.loaded.clone().unwrap_or_else(|| recorder.replay.clone());
+                if replay.frames.is_empty() {
+                    status.send(ReplayStatusEvent::Failed { error: "Replay has no frames".to_string() });
+                    continue;
+                }
+
+                // Recordings of this session drive the real entities, loaded files use ghosts
+                playback.live_entities.clear();
+                if playback.loaded.is_none() {
+                    for (entity, id, transform) in recorded.iter() {
+                        playback.live_entities.insert(id.0, (entity, *transform));
+                    }
+                }
+
+                let mut camera_transform = Transform::default();
+                playback.disabled_cameras.clear();
+                for (entity, mut camera, global) in cameras.iter_mut() {
+                    if camera.is_active {
+                        camera_transform = global.compute_transform();
+                        camera.is_active = false;
+                        playback.disabled_cameras.push(entity);
+                    }
+                }
+                let (yaw, pitch, _) = camera_transform.rotation.to_euler(EulerRot::YXZ);
+                let camera = commands.spawn((
+                    Name::new("Replay Camera"),
+                    Camera3d::default(),
+                    Camera { order: 10, ..default() },
+                    camera_transform,
+                    ReplayCamera { follow: None, follow_offset: Vec3::ZERO, yaw, pitch },
+                )).id();
+
+                playback.time = replay.start_time();
+                playback.replay = replay;
+                playback.camera = Some(camera);
+                playback.paused = false;
+                playback.finished = false;
+                playback.active = true;
+                playback.time_was_paused = virtual_time.is_paused();
+                virtual_time.pause();
+                status.send(ReplayStatusEvent::PlaybackStarted);
+            }
+            ReplayCommand::Stop => {
+                if !playback.active {
+                    continue;
+                }
+
+                for (_, (entity, transform)) in playback.live_entities.drain() {
+                    if let Ok((_, _, mut current)) = recorded.get_mut(entity) {
+                        *current = transform;
+                    }
+                }
+                for (_, ghost) in playback.ghosts.drain() {
+                    commands.entity(ghost).despawn();
+                }
+                if let Some(camera) = playback.camera.take() {
+                    commands.entity(camera).despawn();
+                }
+                for entity in playback.disabled_cameras.drain(..) {
+                    if let Ok((_, mut camera, _)) = cameras.get_mut(entity) {
+                        camera.is_active = true;
+                    }
+                }
+                if !playback.time_was_paused {
+                    virtual_time.unpause();
+                }
+                playback.active = false;
+                status.send(ReplayStatusEvent::PlaybackStopped);
+            }
+            ReplayCommand::TogglePause => {
+                playback.paused = !playback.paused;
+            }
+            ReplayCommand::Seek(time) => {
+                let (start, end) = (playback.replay.start_time(), playback.replay.end_time());
+                playback.time = time.clamp(start, end);
+                playback.finished = false;
+            }
+            ReplayCommand::SeekBy(offset) => {
+                let (start, end) = (playback.replay.start_time(), playback.replay.end_time());
+                playback.time = (playback.time + offset).clamp(start, end);
+                playback.finished = false;
+            }
+            ReplayCommand::SetSpeed(speed) => {
+                playback.speed = speed.clamp(SPEED_RANGE.0, SPEED_RANGE.1);
+            }
+            ReplayCommand::SetCameraFollow { id, offset } => {
+                if let Some(camera) = playback.camera {
+                    commands.entity(camera).entry::<ReplayCamera>().and_modify({
+                        let (id, offset) = (*id, *offset);
+                        move |mut camera| {
+                            camera.follow = id;
+                            camera.follow_offset = offset;
+                        }
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn resolve_path(settings: &ReplaySettings, path: &str) -> PathBuf {
+    let path = PathBuf::from(path);
+    if path.is_relative() {
+        PathBuf::from(&settings.directory).join(path)
+    } else {
+        path
+    }
+}
+
+fn save_replay(replay: &Replay, path: &PathBuf) -> Result<String, String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create replay directory: {}", e))?;
+    }
+    let bytes = replay.to_bytes()?;
+    std::fs::write(path, bytes)
+        .map_err(|e| format!("Failed to write replay {}: {}", path.display(), e))?;
+    Ok(path.display().to_string())
+}
+
+fn load_replay(path: &PathBuf) -> Result<Replay, String> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| format!("Failed to read replay {}: {}", path.display(), e))?;
+    Replay::from_bytes(&bytes)
+}
+
+/// Entities posed during playback and the hierarchy their poses resolve against
+#[derive(SystemParam)]
+pub struct ReplayPoseTargets<'w, 's> {
+    pub transforms: Query<'w, 's, &'static mut Transform, Without<ReplayCamera>>,
+    pub ghosts: Query<'w, 's, &'static mut Visibility, With<ReplayGhost>>,
+    pub child_of: Query<'w, 's, &'static ChildOf>,
+    pub ids: Query<'w, 's, &'static ReplayId>,
+    pub globals: Query<'w, 's, &'static GlobalTransform>,
+}
+
+/// Mesh assets for ghosts, missing in headless apps
+#[derive(SystemParam)]
+pub struct GhostAssets<'w> {
+    pub meshes: Option<ResMut<'w, Assets<Mesh>>>,
+    pub materials: Option<ResMut<'w, Assets<StandardMaterial>>>,
+}
+
+/// Moves the playhead on real time and poses recorded entities
+pub fn advance_replay_playback(
+    mut commands: Commands,
+    real_time: Res<Time<Real>>,
+    mut playback: ResMut<ReplayPlayback>,
+    mut status: ResMut<ReplayStatusEventQueue>,
+    mut playback_events: ResMut<ReplayPlaybackEventQueue>,
+    targets: ReplayPoseTargets,
+    ghost_assets: GhostAssets,
+) {
+    let ReplayPoseTargets { mut transforms, mut ghosts, child_of, ids, globals } = targets;
+    let GhostAssets { mut meshes, mut materials } = ghost_assets;
+    if !playback.active {
+        return;
+    }
+
+    let previous_time = playback.time;
+    if !playback.paused && !playback.finished {
+        let end = playback.replay.end_time();
+        playback.time += real_time.delta_secs() * playback.speed;
+        if playback.time >= end {
+            if playback.looping {
+                playback.time = playback.replay.start_time();
+            } else {
+                playback.time = end;
+                playback.finished = true;
+                status.send(ReplayStatusEvent::PlaybackFinished);
+            }
+        }
+        if playback.time > previous_time {
+            playback_events.send_batch(
+                playback.replay.events_between(previous_time, playback.time).cloned(),
+            );
+        }
+    }
+
+    let poses: HashMap<u32, ReplayTransform> = playback
+        .replay
+        .sample(playback.time)
+        .into_iter()
+        .map(|pose| (pose.id, pose))
+        .collect();
+
+    let playback = &mut *playback;
+
+    for (id, pose) in poses.iter() {
+        let world = Transform::from_translation(pose.translation).with_rotation(pose.rotation);
+
+        if let Some((entity, original)) = playback.live_entities.get(id) {
+            let Ok(mut transform) = transforms.get_mut(*entity) else { continue };
+            // Poses are recorded in world space
+            let parent_world = child_of.get(*entity).ok().and_then(|child_of| {
+                let parent = child_of.parent();
+                ids.get(parent)
+                    .ok()
+                    .and_then(|parent_id| poses.get(&parent_id.0))
+                    .map(|parent_pose| Transform::from_translation(parent_pose.translation).with_rotation(parent_pose.rotation))
+                    .or_else(|| globals.get(parent).ok().map(GlobalTransform::compute_transform))
+            });
+            let local = match parent_world {
+                Some(parent_world) => GlobalTransform::from(world).reparented_to(&GlobalTransform::from(parent_world)),
+                None => world,
+            };
+            *transform = local.with_scale(original.scale);
+            continue;
+        }
+
+        match playback.ghosts.get(id) {
+            Some(ghost) => {
+                if let Ok(mut transform) = transforms.get_mut(*ghost) {
+                    *transform = world;
+                }
+                if let Ok(mut visibility) = ghosts.get_mut(*ghost) {
+                    *visibility = Visibility::Inherited;
+                }
+            }
+            None => {
+                let name = playback.replay.entity_name(*id).unwrap_or("Entity").to_string();
+                let mut ghost = commands.spawn((
+                    Name::new(format!("Replay Ghost ({})", name)),
+                    ReplayGhost { id: *id },
+                    world,
+                    Visibility::Inherited,
+                ));
+                if let (Some(meshes), Some(materials)) = (meshes.as_mut(), materials.as_mut()) {
+                    ghost.insert((
+                        Mesh3d(meshes.add(Capsule3d::new(0.4, 1.0))),
+                        MeshMaterial3d(materials.add(StandardMaterial {
+                            base_color: Color::srgba(0.6, 0.8, 1.0, 0.6),
+                            alpha_mode: AlphaMode::Blend,
+                            ..default()
+                        })),
+                    ));
+                }
+                playback.ghosts.insert(*id, ghost.id());
+            }
+        }
+    }
+
+    // Hide ghosts of entities absent at this point of the recording
+    for (id, ghost) in playback.ghosts.iter() {
+        if !poses.contains_key(id) {
+            if let Ok(mut visibility) = ghosts.get_mut(*ghost) {
+                *visibility = Visibility::Hidden;
+            }
+        }
+    }
+}
+
+/// Free-fly camera (hold right mouse to look, WASD/QE to move) or ride-along view
+pub fn update_replay_camera(
+    real_time: Res<Time<Real>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    settings: Res<ReplaySettings>,
+    playback: Res<ReplayPlayback>,
+    mut query: Query<(&mut Transform, &mut ReplayCamera)>,
+) {
+    if !playback.active {
+        return;
+    }
+
+    let delta = real_time.delta_secs();
+    for (mut transform, mut camera) in query.iter_mut() {
+        if let Some(id) = camera.follow {
+            if let Some(pose) = playback.replay.sample(playback.time).into_iter().find(|pose| pose.id == id) {
+                transform.translation = pose.translation + pose.rotation * camera.follow_offset;
+                transform.rotation = pose.rotation;
+                continue;
+            }
+        }
+
+        if mouse_buttons.pressed(MouseButton::Right) {
+            camera.yaw -= mouse_motion.delta.x * settings.free_camera_sensitivity;
+            camera.pitch = (camera.pitch - mouse_motion.delta.y * settings.free_camera_sensitivity)
+                .clamp(-1.54, 1.54);
+        }
+        transform.rotation = Quat::from_euler(EulerRot::YXZ, camera.yaw, camera.pitch, 0.0);
+
+        let mut direction = Vec3::ZERO;
+        if keyboard.pressed(KeyCode::KeyW) {
+            direction += *transform.forward();
+        }
+        if keyboard.pressed(KeyCode::KeyS) {
+            direction -= *transform.forward();
+        }
+        if keyboard.pressed(KeyCode::KeyD) {
+            direction += *transform.right();
+        }
+        if keyboard.pressed(KeyCode::KeyA) {
+            direction -= *transform.right();
+        }
+        if keyboard.pressed(KeyCode::KeyE) {
+            direction += Vec3::Y;
+        }
+        if keyboard.pressed(KeyCode::KeyQ) {
+            direction -= Vec3::Y;
+        }
+
+        let boost = if keyboard.pressed(KeyCode::ShiftLeft) { 3.0 } else { 1.0 };
+        transform.translation += direction.normalize_or_zero() * settings.free_camera_speed * boost * delta;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::DeathEventQueue;
+    use crate::replay::ReplayPlugin;
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    /// Moves recordables along X at 10 m/s of virtual time
+    fn drive_recordables(time: Res<Time>, mut query: Query<&mut GlobalTransform, With<ReplayRecordable>>) {
+        for mut global in query.iter_mut() {
+            *global = GlobalTransform::from_translation(Vec3::X * time.elapsed_secs() * 10.0);
+        }
+    }
+
+    fn recorded_x(app: &App) -> f32 {
+        let playback = app.world().resource::<ReplayPlayback>();
+        playback.time * 10.0
+    }
+
+    #[test]
+    fn test_record_then_play_back_live_entity() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<AccumulatedMouseMotion>()
+            .add_plugins((
+                EventQueuePlugin::<DamageResultEvent>::default(),
+                EventQueuePlugin::<DeathEvent>::default(),
+                ReplayPlugin,
+            ))
+            .add_systems(Update, drive_recordables);
+
+        let runner = app.world_mut().spawn((
+            Name::new("Runner"),
+            ReplayRecordable,
+            Transform::from_xyz(0.0, 0.0, 5.0),
+            GlobalTransform::default(),
+        )).id();
+
+        for frame in 0..8 {
+            if frame == 4 {
+                app.world_mut().resource_mut::<DeathEventQueue>().send(DeathEvent { entity: runner });
+            }
+            app.update();
+        }
+
+        let (death_time, end_time) = {
+            let recorder = app.world().resource::<ReplayRecorder>();
+            assert!(recorder.recording);
+            assert_eq!(recorder.replay.entity_name(0), Some("Runner"));
+            assert!(recorder.replay.frames.len() >= 7);
+            let death = recorder.replay.events.iter().find(|event| event.kind == ReplayEventKind::Death { entity: 0 });
+            (death.expect("death should be recorded").time, recorder.replay.end_time())
+        };
+
+        // Playback poses the real entity and freezes gameplay time
+        app.world_mut().resource_mut::<ReplayCommandQueue>().send(ReplayCommand::Play);
+        app.update();
+        {
+            let playback = app.world().resource::<ReplayPlayback>();
+            assert!(playback.active);
+            assert!(playback.camera.is_some());
+        }
+        assert!(app.world().resource::<Time<Virtual>>().is_paused());
+        let transform = *app.world().get::<Transform>(runner).unwrap();
+        assert!((transform.translation.x - recorded_x(&app)).abs() < 1e-3);
+        assert_eq!(transform.translation.z, 0.0);
+
+        // Scrubbing across the death replays it on the playback queue
+        app.world_mut().resource_mut::<ReplayCommandQueue>().send(ReplayCommand::Seek(death_time - 0.05));
+        app.update();
+        assert!(app.world().resource::<ReplayPlaybackEventQueue>().iter().any(|event| event.kind == ReplayEventKind::Death { entity: 0 }));
+        let x = app.world().get::<Transform>(runner).unwrap().translation.x;
+        assert!((x - recorded_x(&app)).abs() < 1e-3);
+
+        // Seeking clamps to the recording
+        app.world_mut().resource_mut::<ReplayCommandQueue>().send(ReplayCommand::Seek(end_time + 100.0));
+        app.update();
+        assert!(app.world().resource::<ReplayPlayback>().finished);
+        assert_eq!(app.world().resource::<ReplayPlayback>().time, end_time);
+
+        // Stopping restores the entity and the clock
+        app.world_mut().resource_mut::<ReplayCommandQueue>().send(ReplayCommand::Stop);
+        app.update();
+        assert!(!app.world().resource::<ReplayPlayback>().active);
+        assert!(!app.world().resource::<Time<Virtual>>().is_paused());
+        assert_eq!(app.world().get::<Transform>(runner).unwrap().translation, Vec3::new(0.0, 0.0, 5.0));
+        assert!(app.world_mut().query::<&ReplayCamera>().iter(app.world()).next().is_none());
+    }
+}
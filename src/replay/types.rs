@@ -0,0 +1,323 @@
+use bevy::prelude::*;
+use bevy::platform::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use crate::utils::EventQueue;
+
+/// Marks an entity whose transform is recorded
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct ReplayRecordable;
+
+/// Id of a recorded entity, stable for the whole session
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct ReplayId(pub u32);
+
+/// Recorded entity description
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayEntityInfo {
+    pub id: u32,
+    pub name: String,
+}
+
+/// One entity pose in a frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayTransform {
+    pub id: u32,
+    pub translation: Vec3,
+    pub rotation: Quat,
+}
+
+/// Poses sampled at one instant
+#[derive(Debug, Clone, Default)]
+pub struct ReplayFrame {
+    /// Seconds since the recording started
+    pub time: f32,
+    pub transforms: Vec<ReplayTransform>,
+}
+
+/// Gameplay event kept alongside the poses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ReplayEventKind {
+    Damage {
+        source: Option<u32>,
+        target: u32,
+        amount: f32,
+        shielded: f32,
+        damage_type: String,
+        is_crit: bool,
+        is_block: bool,
+    },
+    Death { entity: u32 },
+    AbilityUsed { entity: u32, ability: String },
+    /// Recorded when a vehicle starts/stops driving, boosting or changes gear
+    VehicleState {
+        vehicle: u32,
+        speed: f32,
+        gear: usize,
+        driving: bool,
+        boosting: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    pub time: f32,
+    pub kind: ReplayEventKind,
+}
+
+/// A recorded session
+#[derive(Debug, Clone)]
+pub struct Replay {
+    pub sample_rate: f32,
+    pub entities: Vec<ReplayEntityInfo>,
+    pub frames: VecDeque<ReplayFrame>,
+    pub events: VecDeque<ReplayEvent>,
+}
+
+impl Default for Replay {
+    fn default() -> Self {
+        Self {
+            sample_rate: 20.0,
+            entities: Vec::new(),
+            frames: VecDeque::new(),
+            events: VecDeque::new(),
+        }
+    }
+}
+
+impl Replay {
+    pub fn start_time(&self) -> f32 {
+        self.frames.front().map(|frame| frame.time).unwrap_or(0.0)
+    }
+
+    pub fn end_time(&self) -> f32 {
+        self.frames.back().map(|frame| frame.time).unwrap_or(0.0)
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.end_time() - self.start_time()
+    }
+
+    pub fn entity_name(&self, id: u32) -> Option<&str> {
+        self.entities.iter().find(|entity| entity.id == id).map(|entity| entity.name.as_str())
+    }
+
+    /// Drops frames and events older than `time`
+    pub fn trim_before(&mut self, time: f32) {
+        while self.frames.front().is_some_and(|frame| frame.time < time) {
+            self.frames.pop_front();
+        }
+        while self.events.front().is_some_and(|event| event.time < time) {
+            self.events.pop_front();
+        }
+    }
+
+    /// Poses at `time`, interpolated between the surrounding frames
+    pub fn sample(&self, time: f32) -> Vec<ReplayTransform> {
+        if self.frames.is_empty() {
+            return Vec::new();
+        }
+
+        let next_index = self.frames.partition_point(|frame| frame.time <= time);
+        if next_index == 0 {
+            return self.frames[0].transforms.clone();
+        }
+        if next_index >= self.frames.len() {
+            return self.frames[self.frames.len() - 1].transforms.clone();
+        }
+
+        let previous = &self.frames[next_index - 1];
+        let next = &self.frames[next_index];
+        let span = (next.time - previous.time).max(f32::EPSILON);
+        let t = ((time - previous.time) / span).clamp(0.0, 1.0);
+
+        previous
+            .transforms
+            .iter()
+            .map(|from| match next.transforms.iter().find(|to| to.id == from.id) {
+                Some(to) => ReplayTransform {
+                    id: from.id,
+                    translation: from.translation.lerp(to.translation, t),
+                    rotation: from.rotation.slerp(to.rotation, t),
+                },
+                None => *from,
+            })
+            .collect()
+    }
+
+    /// Events with `from < time <= to`
+    pub fn events_between(&self, from: f32, to: f32) -> impl Iterator<Item = &ReplayEvent> {
+        self.events.iter().filter(move |event| event.time > from && event.time <= to)
+    }
+}
+
+/// Replay settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct ReplaySettings {
+    /// Pose samples per second
+    pub sample_rate: f32,
+    /// Seconds kept in the recording buffer (0 = unlimited)
+    pub buffer_duration: f32,
+    pub record_on_start: bool,
+    pub auto_record_players: bool,
+    pub auto_record_ai: bool,
+    /// Only with the `vehicles` feature
+    pub auto_record_vehicles: bool,
+    /// Folder used by relative `Save`/`Load` paths
+    pub directory: String,
+    pub free_camera_speed: f32,
+    pub free_camera_sensitivity: f32,
+    /// Seconds skipped by the seek keys
+    pub seek_step: f32,
+    // Playback keys (only read while a replay is playing)
+    pub pause_key: KeyCode,
+    pub seek_back_key: KeyCode,
+    pub seek_forward_key: KeyCode,
+    pub faster_key: KeyCode,
+    pub slower_key: KeyCode,
+    pub stop_key: KeyCode,
+}
+
+impl Default for ReplaySettings {
+    fn default() -> Self {
+        Self {
+            sample_rate: 20.0,
+            buffer_duration: 60.0,
+            record_on_start: true,
+            auto_record_players: true,
+            auto_record_ai: true,
+            auto_record_vehicles: true,
+            directory: "replays".to_string(),
+            free_camera_speed: 8.0,
+            free_camera_sensitivity: 0.003,
+            seek_step: 5.0,
+            pause_key: KeyCode::Space,
+            seek_back_key: KeyCode::ArrowLeft,
+            seek_forward_key: KeyCode::ArrowRight,
+            faster_key: KeyCode::ArrowUp,
+            slower_key: KeyCode::ArrowDown,
+            stop_key: KeyCode::Escape,
+        }
+    }
+}
+
+/// Recording state
+#[derive(Resource, Debug, Default)]
+pub struct ReplayRecorder {
+    pub recording: bool,
+    /// Rolling buffer of the current session
+    pub replay: Replay,
+    /// Virtual time at which recording started
+    pub started_at: f32,
+    pub sample_timer: f32,
+    pub next_id: u32,
+}
+
+/// Playback state
+#[derive(Resource, Debug)]
+pub struct ReplayPlayback {
+    pub active: bool,
+    /// Replay loaded from disk; the recording buffer is played when `None`
+    pub loaded: Option<Replay>,
+    /// Snapshot being played
+    pub replay: Replay,
+    /// Playhead, in replay time
+    pub time: f32,
+    pub speed: f32,
+    pub paused: bool,
+    pub looping: bool,
+    pub finished: bool,
+    pub camera: Option<Entity>,
+    /// Live entities moved by the playback, with their pose before it started
+    pub live_entities: HashMap<u32, (Entity, Transform)>,
+    /// Stand-ins for recorded entities that no longer exist
+    pub ghosts: HashMap<u32, Entity>,
+    pub disabled_cameras: Vec<Entity>,
+    pub time_was_paused: bool,
+}
+
+impl Default for ReplayPlayback {
+    fn default() -> Self {
+        Self {
+            active: false,
+            loaded: None,
+            replay: Replay::default(),
+            time: 0.0,
+            speed: 1.0,
+            paused: false,
+            looping: false,
+            finished: false,
+            camera: None,
+            live_entities: HashMap::new(),
+            ghosts: HashMap::new(),
+            disabled_cameras: Vec::new(),
+            time_was_paused: false,
+        }
+    }
+}
+
+/// Replay camera spawned for playback
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct ReplayCamera {
+    /// Ride along with a recorded entity instead of flying freely
+    pub follow: Option<u32>,
+    /// Offset in the followed entity's local space
+    pub follow_offset: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+/// Stand-in spawned for a recorded entity missing from the world
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ReplayGhost {
+    pub id: u32,
+}
+
+/// Replay control
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayCommand {
+    StartRecording,
+    StopRecording,
+    /// Writes the recording buffer; relative paths go to `ReplaySettings::directory`
+    Save { path: String },
+    Load { path: String },
+    /// Discards a loaded replay so `Play` uses the recording buffer again
+    Unload,
+    Play,
+    Stop,
+    TogglePause,
+    /// Absolute playhead position, in replay time
+    Seek(f32),
+    SeekBy(f32),
+    SetSpeed(f32),
+    /// Free camera (`None`) or ride along with a recorded entity
+    SetCameraFollow { id: Option<u32>, offset: Vec3 },
+}
+
+/// Queue for replay commands
+pub type ReplayCommandQueue = EventQueue<ReplayCommand>;
+
+/// Replay state change
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayStatusEvent {
+    RecordingStarted,
+    RecordingStopped,
+    Saved { path: String },
+    Loaded { path: String },
+    PlaybackStarted,
+    PlaybackFinished,
+    PlaybackStopped,
+    Failed { error: String },
+}
+
+/// Queue for replay status events
+pub type ReplayStatusEventQueue = EventQueue<ReplayStatusEvent>;
+
+/// Queue for recorded events crossed by the playhead
+pub type ReplayPlaybackEventQueue = EventQueue<ReplayEvent>;
@@ -0,0 +1,38 @@
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    SpawnPoint, SpawnPointKind, RespawnLocationPreference, StatRestorePolicy, AmmoRestorePolicy,
+    RespawnSettings, RespawnPhase, Respawnable, RespawnAmmoSnapshot, RespawnFadeOverlay,
+    RespawnEvent, RespawnEventQueue,
+};
+
+pub struct RespawnPlugin;
+
+impl Plugin for RespawnPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<RespawnSettings>()
+            .add_plugins(EventQueuePlugin::<RespawnEvent>::default())
+            .register_type::<SpawnPoint>()
+            .register_type::<Respawnable>()
+            .register_type::<RespawnSettings>()
+            .register_type::<RespawnFadeOverlay>()
+            .register_type::<RespawnAmmoSnapshot>()
+            .add_systems(Startup, setup_respawn_fade_overlay)
+            .add_systems(Update, (
+                snapshot_weapon_ammo,
+                activate_checkpoints,
+                claim_home_spawn_points,
+                detect_respawnable_deaths,
+                update_respawn_flow,
+                update_respawn_fade_overlay,
+                draw_spawn_points.in_set(crate::headless::PresentationSet),
+            ).chain());
+    }
+}
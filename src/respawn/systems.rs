@@ -0,0 +1,439 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::character::CharacterController;
+use crate::combat::Health;
+use crate::game_manager::types::GameManagerSettings;
+use crate::interaction::InteractionEvent;
+use crate::player::ragdoll::{DeactivateRagdollEvent, DeactivateRagdollQueue};
+use crate::save::SaveManager;
+use crate::stats::{DerivedStat, StatsSystem};
+use crate::utils::QueueReader;
+use crate::weapons::{Weapon, WeaponManager};
+use super::types::*;
+
+/// System to spawn the full screen overlay used by the respawn fade
+pub fn setup_respawn_fade_overlay(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
+        GlobalZIndex(120),
+        RespawnFadeOverlay,
+    ));
+}
+
+/// System to remember the reserve ammo each weapon starts with
+pub fn snapshot_weapon_ammo(
+    mut commands: Commands,
+    weapon_query: Query<(Entity, &Weapon), Added<Weapon>>,
+) {
+    for (entity, weapon) in weapon_query.iter() {
+        commands.entity(entity).insert(RespawnAmmoSnapshot {
+            reserve_ammo: weapon.reserve_ammo,
+        });
+    }
+}
+
+/// System to activate checkpoints the entity walks past
+pub fn activate_checkpoints(
+    mut event_queue: ResMut<RespawnEventQueue>,
+    mut respawnable_query: Query<(Entity, &GlobalTransform, &mut Respawnable)>,
+    spawn_point_query: Query<(Entity, &GlobalTransform, &SpawnPoint)>,
+) {
+    for (entity, transform, mut respawnable) in respawnable_query.iter_mut() {
+        if !respawnable.enabled || respawnable.phase != RespawnPhase::Alive {
+            continue;
+        }
+
+        let position = transform.translation();
+        for (point_entity, point_transform, point) in spawn_point_query.iter() {
+            if !point.active || point.kind != SpawnPointKind::Checkpoint || !point.allows_team(respawnable.team) {
+                continue;
+            }
+            if respawnable.last_checkpoint == Some(point_entity) {
+                continue;
+            }
+            if respawnable.last_checkpoint.is_some() && point.checkpoint_order < respawnable.last_checkpoint_order {
+                continue;
+            }
+            if point_transform.translation().distance(position) > point.activation_radius {
+                continue;
+            }
+
+            respawnable.last_checkpoint = Some(point_entity);
+            respawnable.last_checkpoint_order = point.checkpoint_order;
+            info!("Checkpoint {} reached by {:?}", point.checkpoint_order, entity);
+
+            event_queue.send(RespawnEvent::CheckpointReached {
+                entity,
+                spawn_point: point_entity,
+            });
+        }
+    }
+}
+
+/// System to claim beds/homes through the interaction system
+pub fn claim_home_spawn_points(
+    mut interaction_events: QueueReader<InteractionEvent>,
+    mut event_queue: ResMut<RespawnEventQueue>,
+    mut respawnable_query: Query<&mut Respawnable>,
+    spawn_point_query: Query<&SpawnPoint>,
+) {
+    for event in interaction_events.read() {
+        let Ok(point) = spawn_point_query.get(event.target) else {
+            continue;
+        };
+        if !point.active || point.kind != SpawnPointKind::Home {
+            continue;
+        }
+        let Ok(mut respawnable) = respawnable_query.get_mut(event.source) else {
+            continue;
+        };
+        if !point.allows_team(respawnable.team) || respawnable.home_point == Some(event.target) {
+            continue;
+        }
+
+        respawnable.home_point = Some(event.target);
+        event_queue.send(RespawnEvent::HomeSet {
+            entity: event.source,
+            spawn_point: event.target,
+        });
+    }
+}
+
+/// System to start the respawn flow (or end the run) when a respawnable entity dies
+pub fn detect_respawnable_deaths(
+    settings: Res<RespawnSettings>,
+    game_settings: Res<GameManagerSettings>,
+    mut save_manager: ResMut<SaveManager>,
+    mut event_queue: ResMut<RespawnEventQueue>,
+    mut respawnable_query: Query<(Entity, &mut Respawnable, &Health, Option<&mut CharacterController>)>,
+) {
+    for (entity, mut respawnable, health, controller) in respawnable_query.iter_mut() {
+        if !respawnable.enabled || respawnable.phase != RespawnPhase::Alive || !health.is_dead {
+            continue;
+        }
+
+        if let Some(mut controller) = controller {
+            controller.is_dead = true;
+        }
+
+        respawnable.death_count += 1;
+        event_queue.send(RespawnEvent::Died {
+            entity,
+            death_count: respawnable.death_count,
+        });
+
+        if let Some(lives) = respawnable.lives.as_mut() {
+            *lives = lives.saturating_sub(1);
+        }
+        let out_of_lives = respawnable.lives == Some(0);
+
+        if game_settings.permadeath || out_of_lives || !settings.enabled {
+            respawnable.phase = RespawnPhase::GameOver;
+            event_queue.send(RespawnEvent::GameOver { entity });
+            info!("{:?} is out of the game (permadeath: {})", entity, game_settings.permadeath);
+
+            if game_settings.permadeath && game_settings.permadeath_wipe_saves {
+                for slot in 0..save_manager.max_save_slots {
+                    // Empty slots report an error; nothing to wipe there
+                    let _ = save_manager.delete_save(slot);
+                }
+            }
+            continue;
+        }
+
+        respawnable.phase = RespawnPhase::Waiting;
+        respawnable.phase_timer = 0.0;
+    }
+}
+
+/// Picks the spawn point for a respawning entity according to the preference
+fn choose_spawn_point(
+    respawnable: &Respawnable,
+    preference: RespawnLocationPreference,
+    position: Vec3,
+    spawn_point_query: &Query<(Entity, &GlobalTransform, &SpawnPoint)>,
+) -> Option<(Entity, Transform)> {
+    let valid = |entity: Entity| {
+        spawn_point_query
+            .get(entity)
+            .ok()
+            .filter(|(_, _, point)| point.active && point.allows_team(respawnable.team))
+            .map(|(entity, transform, _)| (entity, transform.compute_transform()))
+    };
+
+    let nearest = || {
+        spawn_point_query
+            .iter()
+            .filter(|(_, _, point)| point.active && point.allows_team(respawnable.team) && point.kind != SpawnPointKind::Home)
+            .min_by(|a, b| {
+                a.1.translation().distance(position).total_cmp(&b.1.translation().distance(position))
+            })
+            .map(|(entity, transform, _)| (entity, transform.compute_transform()))
+    };
+
+    let default_point = || {
+        spawn_point_query
+            .iter()
+            .filter(|(_, _, point)| point.active && point.allows_team(respawnable.team) && point.kind == SpawnPointKind::Default)
+            .max_by_key(|(_, _, point)| point.priority)
+            .map(|(entity, transform, _)| (entity, transform.compute_transform()))
+    };
+
+    // Each preference falls back to the next sensible option
+    let preferred = match preference {
+        RespawnLocationPreference::LastCheckpoint => respawnable.last_checkpoint.and_then(valid),
+        RespawnLocationPreference::Home => respawnable
+            .home_point
+            .and_then(valid)
+            .or_else(|| respawnable.last_checkpoint.and_then(valid)),
+        RespawnLocationPreference::NearestSpawnPoint => nearest(),
+        RespawnLocationPreference::DefaultSpawnPoint => None,
+    };
+
+    preferred.or_else(default_point).or_else(nearest)
+}
+
+type RespawningCharacter<'a> = (
+    Entity,
+    &'a mut Respawnable,
+    &'a mut Transform,
+    &'a mut Health,
+    Option<&'a mut StatsSystem>,
+    Option<&'a WeaponManager>,
+    Option<&'a mut CharacterController>,
+    Option<&'a mut LinearVelocity>,
+);
+
+/// System to run the delay, fade-out, teleport and restore, fade-in respawn sequence
+pub fn update_respawn_flow(
+    time: Res<Time>,
+    settings: Res<RespawnSettings>,
+    mut event_queue: ResMut<RespawnEventQueue>,
+    mut ragdoll_queue: ResMut<DeactivateRagdollQueue>,
+    mut respawnable_query: Query<RespawningCharacter>,
+    spawn_point_query: Query<(Entity, &GlobalTransform, &SpawnPoint)>,
+    mut weapon_query: Query<(&mut Weapon, Option<&RespawnAmmoSnapshot>)>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut respawnable, mut transform, mut health, stats, weapon_manager, controller, velocity) in respawnable_query.iter_mut() {
+        if !respawnable.is_respawning() {
+            continue;
+        }
+
+        respawnable.phase_timer += delta;
+
+        match respawnable.phase {
+            RespawnPhase::Waiting => {
+                if respawnable.phase_timer >= settings.respawn_delay && !respawnable.held {
+                    respawnable.phase = RespawnPhase::FadingOut;
+                    respawnable.phase_timer = 0.0;
+                }
+            }
+            RespawnPhase::FadingOut => {
+                if respawnable.phase_timer < settings.fade_out_duration {
+                    continue;
+                }
+
+                // Teleport
+                let preference = respawnable.location_override.unwrap_or(settings.location);
+                let spawn = choose_spawn_point(&respawnable, preference, transform.translation, &spawn_point_query);
+                if let Some((_, spawn_transform)) = spawn {
+                    transform.translation = spawn_transform.translation;
+                    transform.rotation = spawn_transform.rotation;
+                } else {
+                    warn!("No spawn point available for {:?}; respawning in place", entity);
+                }
+                if let Some(mut velocity) = velocity {
+                    velocity.0 = Vec3::ZERO;
+                }
+
+                // Restore stats
+                health.current = settings.health_policy.apply(health.current, health.maximum);
+                health.is_dead = false;
+                health.temporal_invincibility_timer = settings.spawn_protection;
+
+                if let Some(mut stats) = stats {
+                    let max_stamina = stats.get_derived_stat(DerivedStat::MaxStamina).copied().unwrap_or(100.0);
+                    let stamina = stats.get_derived_stat(DerivedStat::CurrentStamina).copied().unwrap_or(max_stamina);
+                    stats.set_derived_stat(DerivedStat::CurrentStamina, settings.stamina_policy.apply(stamina, max_stamina));
+                }
+
+                if let Some(weapon_manager) = weapon_manager {
+                    for weapon_entity in weapon_manager.weapons_list.iter() {
+                        let Ok((mut weapon, snapshot)) = weapon_query.get_mut(*weapon_entity) else {
+                            continue;
+                        };
+                        match settings.ammo_policy {
+                            AmmoRestorePolicy::Keep => {}
+                            AmmoRestorePolicy::RefillMagazines => {
+                                weapon.current_ammo = weapon.ammo_capacity;
+                            }
+                            AmmoRestorePolicy::RefillAll => {
+                                weapon.current_ammo = weapon.ammo_capacity;
+                                if let Some(snapshot) = snapshot {
+                                    weapon.reserve_ammo = weapon.reserve_ammo.max(snapshot.reserve_ammo);
+                                }
+                            }
+                        }
+                    }
+                }
+
+                if let Some(mut controller) = controller {
+                    controller.is_dead = false;
+                }
+                ragdoll_queue.send(DeactivateRagdollEvent { entity });
+
+                respawnable.phase = RespawnPhase::FadingIn;
+                respawnable.phase_timer = 0.0;
+
+                event_queue.send(RespawnEvent::Respawned {
+                    entity,
+                    spawn_point: spawn.map(|(spawn_entity, _)| spawn_entity),
+                    position: transform.translation,
+                });
+            }
+            RespawnPhase::FadingIn => {
+                if respawnable.phase_timer >= settings.fade_in_duration {
+                    respawnable.phase = RespawnPhase::Alive;
+                    respawnable.phase_timer = 0.0;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// System to drive the respawn fade overlay
+pub fn update_respawn_fade_overlay(
+    settings: Res<RespawnSettings>,
+    respawnable_query: Query<&Respawnable>,
+    mut overlay_query: Query<&mut BackgroundColor, With<RespawnFadeOverlay>>,
+) {
+    let alpha = respawnable_query
+        .iter()
+        .filter(|respawnable| respawnable.show_fade)
+        .map(|respawnable| match respawnable.phase {
+            RespawnPhase::Alive | RespawnPhase::Waiting | RespawnPhase::GameOver => 0.0,
+            RespawnPhase::FadingOut => respawnable.phase_timer / settings.fade_out_duration.max(0.001),
+            RespawnPhase::FadingIn => 1.0 - respawnable.phase_timer / settings.fade_in_duration.max(0.001),
+        })
+        .fold(0.0f32, f32::max)
+        .clamp(0.0, 1.0);
+
+    for mut background in overlay_query.iter_mut() {
+        let color = settings.fade_color.with_alpha(alpha);
+        if background.0 != color {
+            background.0 = color;
+        }
+    }
+}
+
+/// System to draw spawn point gizmos
+pub fn draw_spawn_points(
+    mut gizmos: Gizmos,
+    spawn_point_query: Query<(&GlobalTransform, &SpawnPoint)>,
+) {
+    for (transform, point) in spawn_point_query.iter() {
+        if !point.show_gizmo {
+            continue;
+        }
+
+        let color = match point.kind {
+            SpawnPointKind::Default => Color::srgb(0.2, 0.6, 1.0),
+            SpawnPointKind::Checkpoint => Color::srgb(1.0, 0.85, 0.1),
+            SpawnPointKind::Home => Color::srgb(0.3, 1.0, 0.4),
+        };
+        let color = if point.active { color } else { color.with_alpha(0.3) };
+        let position = transform.translation();
+
+        gizmos.circle(
+            Isometry3d::new(position, Quat::from_rotation_arc(Vec3::Z, Vec3::Y)),
+            point.activation_radius,
+            color,
+        );
+        gizmos.arrow(position, position + transform.forward() * 1.0, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn test_respawn_at_checkpoint_until_out_of_lives() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins((
+                EventQueuePlugin::<RespawnEvent>::default(),
+                EventQueuePlugin::<DeactivateRagdollEvent>::default(),
+            ))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .insert_resource(RespawnSettings {
+                respawn_delay: 0.2,
+                fade_out_duration: 0.1,
+                fade_in_duration: 0.1,
+                ..default()
+            })
+            .init_resource::<GameManagerSettings>()
+            .init_resource::<SaveManager>()
+            .add_systems(Update, (activate_checkpoints, detect_respawnable_deaths, update_respawn_flow).chain());
+
+        app.world_mut().spawn((GlobalTransform::IDENTITY, SpawnPoint::default()));
+        let checkpoint = app.world_mut().spawn((
+            GlobalTransform::from_xyz(10.0, 0.0, 0.0),
+            SpawnPoint { kind: SpawnPointKind::Checkpoint, checkpoint_order: 1, ..default() },
+        )).id();
+        let player = app.world_mut().spawn((
+            Transform::from_xyz(10.0, 0.0, 0.0),
+            GlobalTransform::from_xyz(10.0, 0.0, 0.0),
+            Respawnable { lives: Some(2), ..default() },
+            Health::default(),
+        )).id();
+
+        app.update();
+        assert_eq!(app.world().get::<Respawnable>(player).unwrap().last_checkpoint, Some(checkpoint));
+
+        let kill = |app: &mut App| {
+            let mut entity = app.world_mut().entity_mut(player);
+            entity.insert(Transform::from_xyz(50.0, -3.0, 0.0));
+            let mut health = entity.get_mut::<Health>().unwrap();
+            health.current = 0.0;
+            health.is_dead = true;
+        };
+
+        kill(&mut app);
+        app.update();
+        assert_eq!(app.world().get::<Respawnable>(player).unwrap().phase, RespawnPhase::Waiting);
+        for _ in 0..20 {
+            app.update();
+            if app.world().get::<Respawnable>(player).unwrap().phase == RespawnPhase::Alive {
+                break;
+            }
+        }
+
+        // Back at the checkpoint rather than the default spawn, healed and protected
+        assert_eq!(app.world().get::<Respawnable>(player).unwrap().phase, RespawnPhase::Alive);
+        assert_eq!(app.world().get::<Transform>(player).unwrap().translation, Vec3::new(10.0, 0.0, 0.0));
+        let health = app.world().get::<Health>(player).unwrap();
+        assert!(!health.is_dead && health.current == health.maximum);
+        assert!(health.temporal_invincibility_timer > 0.0);
+
+        // The last life ends the run
+        kill(&mut app);
+        app.update();
+        let respawnable = app.world().get::<Respawnable>(player).unwrap();
+        assert_eq!(respawnable.phase, RespawnPhase::GameOver);
+        assert_eq!(respawnable.death_count, 2);
+        assert!(app.world().resource::<RespawnEventQueue>().iter().any(|event| *event == RespawnEvent::GameOver { entity: player }));
+    }
+}
@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+use crate::utils::EventQueue;
+
+/// What kind of spawn point this is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum SpawnPointKind {
+    /// Level start / fallback spawn
+    #[default]
+    Default,
+    /// Activated by walking past it
+    Checkpoint,
+    /// Bed, camp or home; claimed by interacting with it
+    Home,
+}
+
+/// Spawn point component
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct SpawnPoint {
+    pub active: bool,
+    pub kind: SpawnPointKind,
+    /// Team allowed to use this point (None = everyone)
+    pub team: Option<u32>,
+    /// Checkpoints only move forward: a lower order never replaces a higher one
+    pub checkpoint_order: u32,
+    /// Distance at which checkpoints are reached
+    pub activation_radius: f32,
+    /// Higher priority wins among equally valid default points
+    pub priority: i32,
+    pub show_gizmo: bool,
+}
+
+impl Default for SpawnPoint {
+    fn default() -> Self {
+        Self {
+            active: true,
+            kind: SpawnPointKind::Default,
+            team: None,
+            checkpoint_order: 0,
+            activation_radius: 3.0,
+            priority: 0,
+            show_gizmo: true,
+        }
+    }
+}
+
+impl SpawnPoint {
+    pub fn allows_team(&self, team: Option<u32>) -> bool {
+        self.team.is_none() || self.team == team
+    }
+}
+
+/// Where a dead entity should come back
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum RespawnLocationPreference {
+    #[default]
+    LastCheckpoint,
+    Home,
+    NearestSpawnPoint,
+    DefaultSpawnPoint,
+}
+
+/// How a resource (health, stamina) is restored on respawn
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum StatRestorePolicy {
+    Full,
+    /// Fraction of the maximum
+    Fraction(f32),
+    /// Keep whatever the stat was before dying (clamped to at least 1)
+    Keep,
+}
+
+impl Default for StatRestorePolicy {
+    fn default() -> Self {
+        Self::Full
+    }
+}
+
+impl StatRestorePolicy {
+    pub fn apply(&self, current: f32, maximum: f32) -> f32 {
+        match self {
+            StatRestorePolicy::Full => maximum,
+            StatRestorePolicy::Fraction(fraction) => (maximum * fraction).max(1.0),
+            StatRestorePolicy::Keep => current.max(1.0),
+        }
+    }
+}
+
+/// How ammo is restored on respawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum AmmoRestorePolicy {
+    /// Leave ammo as it was
+    Keep,
+    /// Refill the magazine of every weapon
+    #[default]
+    RefillMagazines,
+    /// Refill magazines and reserves back to what they held when the entity spawned
+    RefillAll,
+}
+
+/// Global respawn settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct RespawnSettings {
+    pub enabled: bool,
+    pub respawn_delay: f32,
+    pub fade_out_duration: f32,
+    pub fade_in_duration: f32,
+    pub fade_color: Color,
+    pub location: RespawnLocationPreference,
+    pub health_policy: StatRestorePolicy,
+    pub stamina_policy: StatRestorePolicy,
+    pub ammo_policy: AmmoRestorePolicy,
+    /// Temporal invincibility granted after respawning
+    pub spawn_protection: f32,
+}
+
+impl Default for RespawnSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            respawn_delay: 3.0,
+            fade_out_duration: 0.75,
+            fade_in_duration: 0.75,
+            fade_color: Color::BLACK,
+            location: RespawnLocationPreference::LastCheckpoint,
+            health_policy: StatRestorePolicy::Full,
+            stamina_policy: StatRestorePolicy::Full,
+            ammo_policy: AmmoRestorePolicy::RefillMagazines,
+            spawn_protection: 2.0,
+        }
+    }
+}
+
+/// Respawn phase of an entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum RespawnPhase {
+    #[default]
+    Alive,
+    /// Dead, waiting for the respawn delay
+    Waiting,
+    FadingOut,
+    FadingIn,
+    /// Out of lives or permadeath; will not respawn
+    GameOver,
+}
+
+/// Entity that comes back after dying
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Respawnable {
+    pub enabled: bool,
+    pub team: Option<u32>,
+    /// Remaining lives (None = infinite)
+    pub lives: Option<u32>,
+    /// Override the global location preference
+    pub location_override: Option<RespawnLocationPreference>,
+    /// Drive the respawn fade overlay
+    pub show_fade: bool,
+    /// Stay in the Waiting phase past the delay (e.g. while a kill cam plays)
+    pub held: bool,
+
+    // Debug State
+    pub phase: RespawnPhase,
+    pub phase_timer: f32,
+    pub last_checkpoint: Option<Entity>,
+    pub last_checkpoint_order: u32,
+    pub home_point: Option<Entity>,
+    pub death_count: u32,
+}
+
+impl Default for Respawnable {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            team: None,
+            lives: None,
+            location_override: None,
+            show_fade: true,
+            held: false,
+            phase: RespawnPhase::Alive,
+            phase_timer: 0.0,
+            last_checkpoint: None,
+            last_checkpoint_order: 0,
+            home_point: None,
+            death_count: 0,
+        }
+    }
+}
+
+impl Respawnable {
+    pub fn is_respawning(&self) -> bool {
+        matches!(self.phase, RespawnPhase::Waiting | RespawnPhase::FadingOut | RespawnPhase::FadingIn)
+    }
+}
+
+/// Ammo a weapon held when first seen, used by `AmmoRestorePolicy::RefillAll`
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct RespawnAmmoSnapshot {
+    pub reserve_ammo: i32,
+}
+
+/// Full screen overlay used for the respawn fade
+#[derive(Component, Debug, Reflect, Default)]
+#[reflect(Component)]
+pub struct RespawnFadeOverlay;
+
+/// Respawn event data
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RespawnEvent {
+    CheckpointReached { entity: Entity, spawn_point: Entity },
+    HomeSet { entity: Entity, spawn_point: Entity },
+    Died { entity: Entity, death_count: u32 },
+    Respawned { entity: Entity, spawn_point: Option<Entity>, position: Vec3 },
+    /// Out of lives or permadeath
+    GameOver { entity: Entity },
+}
+
+/// Custom queue for respawn events
+pub type RespawnEventQueue = EventQueue<RespawnEvent>;
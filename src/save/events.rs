@@ -1,12 +1,17 @@
 use bevy::prelude::*;
+use crate::utils::EventQueue;
 
 #[derive(Debug, Clone, Event)]
 pub struct RequestSaveEvent {
     pub slot: usize,
 }
 
+pub type RequestSaveEventQueue = EventQueue<RequestSaveEvent>;
+
 #[derive(Debug, Clone, Event)]
 pub struct RequestLoadEvent {
     pub slot: usize,
 }
 
+pub type RequestLoadEventQueue = EventQueue<RequestLoadEvent>;
+
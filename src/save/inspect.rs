@@ -0,0 +1,405 @@
+//! Save inspection and repair
+//!
+//! Saves that break in the wild (an old format, a hand edit, a module that
+//! stored a reference to something since removed) can be looked at and fixed
+//! without loading them into the game. Everything works on the raw JSON of a
+//! slot, so a save that no longer deserializes can still be read:
+//!
+//! ```rust,ignore
+//! let mut save = manager.read_save_value(3)?;
+//! for issue in tools.validate(&save) {
+//!     warn!("slot 3: {}", issue);
+//! }
+//! tools.migrate(&mut save)?;
+//! tools.remove_orphans(&mut save);
+//! manager.write_save_value(3, &save)?;
+//! ```
+//!
+//! Modules whose saved data refers to other saved data register a reference
+//! check that drops the dangling references, and format changes register a
+//! migration from the version they replace:
+//!
+//! ```rust,ignore
+//! app.add_save_migration(1, |save| { save["custom_data"]["map"] = json!({}); });
+//! ```
+//!
+//! The same tools are console commands: `save_list`, `save_dump`,
+//! `save_validate`, `save_repair` and `save_migrate`.
+
+use bevy::prelude::*;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use super::resources::SaveManager;
+use super::types::{SaveData, SAVE_FORMAT_VERSION};
+use crate::input::{console_arg, ConsoleArgs, ConsoleResult};
+
+/// Brings a save from one format version to the next
+pub type SaveMigration = Box<dyn Fn(&mut Value) + Send + Sync>;
+
+/// Removes dangling references from a save, describing each removal
+pub type SaveReferenceCheck = Box<dyn Fn(&mut Value) -> Vec<String> + Send + Sync>;
+
+/// Problem found in a save
+#[derive(Debug, Clone, PartialEq)]
+pub enum SaveIssue {
+    Unreadable(String),
+    /// Older format; `save_migrate` updates it
+    OutdatedVersion { found: u32 },
+    /// Written by a newer build
+    NewerVersion { found: u32 },
+    MissingField(String),
+    UnknownField(String),
+    /// Doesn't match `SaveData`
+    Schema(String),
+    /// Dangling reference a reference check would remove
+    OrphanReference(String),
+}
+
+impl fmt::Display for SaveIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreadable(error) => write!(f, "unreadable: {}", error),
+            Self::OutdatedVersion { found } => write!(f, "format version {} is older than {}", found, SAVE_FORMAT_VERSION),
+            Self::NewerVersion { found } => write!(f, "format version {} is newer than {}", found, SAVE_FORMAT_VERSION),
+            Self::MissingField(field) => write!(f, "missing field '{}'", field),
+            Self::UnknownField(field) => write!(f, "unknown field '{}'", field),
+            Self::Schema(error) => write!(f, "does not match the save format: {}", error),
+            Self::OrphanReference(reference) => write!(f, "orphaned reference: {}", reference),
+        }
+    }
+}
+
+/// A slot as found on disk
+#[derive(Debug, Clone)]
+pub struct SaveSlotSummary {
+    pub slot: usize,
+    pub path: PathBuf,
+    pub size: u64,
+    /// `None` when the file isn't valid JSON
+    pub format_version: Option<u32>,
+    pub save_date: Option<String>,
+    pub play_time: Option<f32>,
+}
+
+/// Format version of a save; saves from before versioning are 0
+pub fn save_format_version(save: &Value) -> u32 {
+    save.get("format_version").and_then(Value::as_u64).map_or(0, |version| version as u32)
+}
+
+/// The part of a save at a dotted path (`custom_data.building`)
+pub fn save_section<'a>(save: &'a Value, section: &str) -> Option<&'a Value> {
+    section.split('.').filter(|key| !key.is_empty()).try_fold(save, |value, key| match value {
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|index| items.get(index)),
+        _ => value.get(key),
+    })
+}
+
+/// Migrations and reference checks for inspecting and repairing saves
+#[derive(Resource)]
+pub struct SaveTools {
+    /// By the version they migrate from
+    pub migrations: BTreeMap<u32, SaveMigration>,
+    pub reference_checks: Vec<(String, SaveReferenceCheck)>,
+}
+
+impl Default for SaveTools {
+    fn default() -> Self {
+        let mut tools = Self {
+            migrations: BTreeMap::new(),
+            reference_checks: Vec::new(),
+        };
+        // Saves from before versioning lack the fields added since
+        tools.migrations.insert(0, Box::new(fill_missing_fields));
+        tools.reference_checks.push(("vehicle".to_string(), Box::new(remove_orphan_vehicle)));
+        tools.reference_checks.push(("checkpoint".to_string(), Box::new(remove_orphan_checkpoint)));
+        tools
+    }
+}
+
+impl SaveTools {
+    /// Everything wrong with `save`, without changing it
+    pub fn validate(&self, save: &Value) -> Vec<SaveIssue> {
+        let Some(fields) = save.as_object() else {
+            return vec![SaveIssue::Unreadable("not a JSON object".to_string())];
+        };
+
+        let mut issues = Vec::new();
+        let version = save_format_version(save);
+        if version < SAVE_FORMAT_VERSION {
+            issues.push(SaveIssue::OutdatedVersion { found: version });
+        } else if version > SAVE_FORMAT_VERSION {
+            issues.push(SaveIssue::NewerVersion { found: version });
+        }
+
+        let current = default_save_value();
+        if let Some(expected) = current.as_object() {
+            issues.extend(expected.keys().filter(|key| !fields.contains_key(*key)).map(|key| SaveIssue::MissingField(key.clone())));
+            issues.extend(fields.keys().filter(|key| !expected.contains_key(*key)).map(|key| SaveIssue::UnknownField(key.clone())));
+        }
+        if let Err(error) = serde_json::from_value::<SaveData>(save.clone()) {
+            issues.push(SaveIssue::Schema(error.to_string()));
+        }
+
+        let mut repaired = save.clone();
+        issues.extend(self.remove_orphans(&mut repaired).into_iter().map(SaveIssue::OrphanReference));
+        issues
+    }
+
+    /// Runs every reference check on `save`; returns what was removed
+    pub fn remove_orphans(&self, save: &mut Value) -> Vec<String> {
+        self.reference_checks
+            .iter()
+            .flat_map(|(name, check)| check(save).into_iter().map(move |removed| format!("{}: {}", name, removed)))
+            .collect()
+    }
+
+    /// Brings `save` to `SAVE_FORMAT_VERSION`; returns the version it had
+    pub fn migrate(&self, save: &mut Value) -> Result<u32, String> {
+        if !save.is_object() {
+            return Err("Save is not a JSON object".to_string());
+        }
+        let found = save_format_version(save);
+        if found > SAVE_FORMAT_VERSION {
+            return Err(format!("Save format {} is newer than {}", found, SAVE_FORMAT_VERSION));
+        }
+
+        for version in found..SAVE_FORMAT_VERSION {
+            let migration = self.migrations.get(&version).ok_or_else(|| format!("No migration from format {}", version))?;
+            migration(save);
+            save["format_version"] = Value::from(version + 1);
+        }
+        Ok(found)
+    }
+}
+
+fn default_save_value() -> Value {
+    serde_json::to_value(SaveData::default()).unwrap_or(Value::Null)
+}
+
+/// Adds the fields of the current format a save lacks, with their defaults
+pub fn fill_missing_fields(save: &mut Value) {
+    let (Some(fields), Value::Object(defaults)) = (save.as_object_mut(), default_save_value()) else { return };
+    for (key, value) in defaults {
+        fields.entry(key).or_insert(value);
+    }
+}
+
+fn remove_orphan_vehicle(save: &mut Value) -> Vec<String> {
+    let driving = save.get("is_driving").and_then(Value::as_bool).unwrap_or(false);
+    match save.get_mut("current_vehicle") {
+        Some(vehicle) if !driving && !vehicle.is_null() => {
+            let removed = format!("vehicle {} while not driving", vehicle);
+            *vehicle = Value::Null;
+            vec![removed]
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn remove_orphan_checkpoint(save: &mut Value) -> Vec<String> {
+    let checkpoint = save.get("is_checkpoint").and_then(Value::as_bool).unwrap_or(false);
+    match save.get_mut("checkpoint_id") {
+        Some(id) if !checkpoint && !id.is_null() => {
+            let removed = format!("checkpoint {} on a regular save", id);
+            *id = Value::Null;
+            vec![removed]
+        }
+        _ => Vec::new(),
+    }
+}
+
+impl SaveManager {
+    /// Every slot with a file, readable or not
+    pub fn slot_summaries(&self) -> Vec<SaveSlotSummary> {
+        (0..self.max_save_slots)
+            .filter_map(|slot| {
+                let path = self.save_path(slot);
+                let size = fs::metadata(&path).ok()?.len();
+                let save = self.read_save_value(slot).ok();
+                Some(SaveSlotSummary {
+                    slot,
+                    size,
+                    format_version: save.as_ref().map(save_format_version),
+                    save_date: save.as_ref().and_then(|save| save.get("save_date")?.as_str().map(str::to_string)),
+                    play_time: save.as_ref().and_then(|save| save.get("play_time")?.as_f64()).map(|time| time as f32),
+                    path,
+                })
+            })
+            .collect()
+    }
+
+    /// The raw JSON of a slot
+    pub fn read_save_value(&self, slot: usize) -> Result<Value, String> {
+        let text = fs::read_to_string(self.save_path(slot))
+            .map_err(|e| format!("Failed to read save file for slot {}: {}", slot, e))?;
+        serde_json::from_str(&text).map_err(|e| format!("Save file for slot {} is not valid JSON: {}", slot, e))
+    }
+
+    /// Replaces a slot's file, keeping the previous one as `.bak`
+    pub fn write_save_value(&mut self, slot: usize, save: &Value) -> Result<(), String> {
+        let path = self.save_path(slot);
+        if path.exists() {
+            fs::copy(&path, path.with_extension("json.bak"))
+                .map_err(|e| format!("Failed to back up save file: {}", e))?;
+        }
+        let text = serde_json::to_string_pretty(save).map_err(|e| format!("Failed to serialize save data: {}", e))?;
+        fs::write(&path, text).map_err(|e| format!("Failed to write save file: {}", e))?;
+
+        // The cached slot info may describe the old file
+        self.save_slots_cache.remove(&slot);
+        if self.current_save_slot == slot {
+            self.current_save_data = None;
+        }
+        Ok(())
+    }
+}
+
+pub trait SaveAppExt {
+    /// Registers the migration of saves in format `from_version` to the next one
+    fn add_save_migration(&mut self, from_version: u32, migration: impl Fn(&mut Value) + Send + Sync + 'static) -> &mut Self;
+
+    /// Registers a reference check, run by `save_repair` and reported by `save_validate`
+    fn add_save_reference_check(
+        &mut self,
+        name: impl Into<String>,
+        check: impl Fn(&mut Value) -> Vec<String> + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl SaveAppExt for App {
+    fn add_save_migration(&mut self, from_version: u32, migration: impl Fn(&mut Value) + Send + Sync + 'static) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<SaveTools>()
+            .migrations
+            .insert(from_version, Box::new(migration));
+        self
+    }
+
+    fn add_save_reference_check(
+        &mut self,
+        name: impl Into<String>,
+        check: impl Fn(&mut Value) -> Vec<String> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_init::<SaveTools>()
+            .reference_checks
+            .push((name.into(), Box::new(check)));
+        self
+    }
+}
+
+// ============================================================================
+// CONSOLE COMMANDS
+// ============================================================================
+
+/// Console `save_list`
+pub fn save_list_command(In(_args): In<ConsoleArgs>, manager: Res<SaveManager>) -> ConsoleResult {
+    let lines: Vec<String> = manager
+        .slot_summaries()
+        .into_iter()
+        .map(|summary| match summary.format_version {
+            Some(version) => format!(
+                "slot {}: format {}, {}, {:.0} s played, {} bytes",
+                summary.slot,
+                version,
+                summary.save_date.as_deref().unwrap_or("no date"),
+                summary.play_time.unwrap_or(0.0),
+                summary.size,
+            ),
+            None => format!("slot {}: unreadable, {} bytes", summary.slot, summary.size),
+        })
+        .collect();
+    if lines.is_empty() {
+        return Ok(format!("No saves in {}", manager.save_directory.display()));
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Console `save_dump <slot> [section]`
+pub fn save_dump_command(In(args): In<ConsoleArgs>, manager: Res<SaveManager>) -> ConsoleResult {
+    let slot: usize = console_arg(&args, 0, "slot")?;
+    let save = manager.read_save_value(slot)?;
+    let Some(section) = args.get(1) else {
+        // The sections to pick from
+        let mut sections: Vec<String> = save.as_object().map(|fields| fields.keys().cloned().collect()).unwrap_or_default();
+        if let Some(custom_data) = save.get("custom_data").and_then(Value::as_object) {
+            sections.extend(custom_data.keys().map(|key| format!("custom_data.{}", key)));
+        }
+        return Ok(sections.join("\n"));
+    };
+    let value = save_section(&save, section).ok_or_else(|| format!("No section '{}' in slot {}", section, slot))?;
+    serde_json::to_string_pretty(value).map_err(|e| e.to_string())
+}
+
+/// Console `save_validate <slot>`
+pub fn save_validate_command(In(args): In<ConsoleArgs>, manager: Res<SaveManager>, tools: Res<SaveTools>) -> ConsoleResult {
+    let slot: usize = console_arg(&args, 0, "slot")?;
+    let issues = match manager.read_save_value(slot) {
+        Ok(save) => tools.validate(&save),
+        Err(error) => vec![SaveIssue::Unreadable(error)],
+    };
+    if issues.is_empty() {
+        return Ok(format!("Slot {} is valid", slot));
+    }
+    Ok(issues.iter().map(|issue| format!("slot {}: {}", slot, issue)).collect::<Vec<_>>().join("\n"))
+}
+
+/// Console `save_repair <slot>`
+pub fn save_repair_command(In(args): In<ConsoleArgs>, mut manager: ResMut<SaveManager>, tools: Res<SaveTools>) -> ConsoleResult {
+    let slot: usize = console_arg(&args, 0, "slot")?;
+    let mut save = manager.read_save_value(slot)?;
+    let removed = tools.remove_orphans(&mut save);
+    if removed.is_empty() {
+        return Ok(format!("Nothing to repair in slot {}", slot));
+    }
+    manager.write_save_value(slot, &save)?;
+    Ok(format!("Removed from slot {}:\n{}", slot, removed.join("\n")))
+}
+
+/// Console `save_migrate <slot>`
+pub fn save_migrate_command(In(args): In<ConsoleArgs>, mut manager: ResMut<SaveManager>, tools: Res<SaveTools>) -> ConsoleResult {
+    let slot: usize = console_arg(&args, 0, "slot")?;
+    let mut save = manager.read_save_value(slot)?;
+    let found = tools.migrate(&mut save)?;
+    if found == SAVE_FORMAT_VERSION {
+        return Ok(format!("Slot {} is already format {}", slot, found));
+    }
+    manager.write_save_value(slot, &save)?;
+    Ok(format!("Migrated slot {} from format {} to {}", slot, found, SAVE_FORMAT_VERSION))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::save::{SAVE_FORMAT_VERSION, SaveData};
+
+    #[test]
+    fn test_save_inspection_and_repair() {
+        let tools = SaveTools::default();
+        let mut save = serde_json::json!({
+            "player_position": [1.0, 2.0, 3.0],
+            "player_health": 80.0,
+            "is_driving": false,
+            "current_vehicle": "Buggy",
+            "legacy_flag": true,
+        });
+
+        let issues = tools.validate(&save);
+        assert!(issues.contains(&SaveIssue::OutdatedVersion { found: 0 }));
+        assert!(issues.contains(&SaveIssue::MissingField("inventory_items".to_string())));
+        assert!(issues.contains(&SaveIssue::UnknownField("legacy_flag".to_string())));
+        assert!(issues.iter().any(|issue| matches!(issue, SaveIssue::OrphanReference(_))));
+
+        assert_eq!(tools.migrate(&mut save), Ok(0));
+        assert_eq!(save_format_version(&save), SAVE_FORMAT_VERSION);
+        assert_eq!(save["player_health"], 80.0);
+        assert_eq!(tools.remove_orphans(&mut save).len(), 1);
+        assert!(save["current_vehicle"].is_null());
+        assert_eq!(tools.validate(&save), vec![SaveIssue::UnknownField("legacy_flag".to_string())]);
+        assert!(serde_json::from_value::<SaveData>(save.clone()).is_ok());
+        assert_eq!(save_section(&save, "game_progress.chapter"), Some(&serde_json::json!(1)));
+    }
+}
@@ -2,33 +2,67 @@ pub mod types;
 pub mod resources;
 pub mod systems;
 pub mod events;
+pub mod inspect;
 
 use bevy::prelude::*;
 use types::*;
 use resources::*;
 use systems::*;
 use events::*;
+use inspect::*;
+use crate::input::{ConsoleAppExt, ConsoleCommand};
+use crate::utils::EventQueuePlugin;
 
 pub use types::{
-    SaveData, SavedInventoryItem, EquipmentData, GameProgress, CameraOrientation, 
-    SaveSlotInfo, SavePlaceholderHealth, SavePlaceholderInventory, InventoryItemData
+    SaveSet, SaveData, SavedInventoryItem, EquipmentData, GameProgress, CameraOrientation, 
+    SaveSlotInfo, SaveCustomData, ChangedPlayerSaveData, SavePlaceholderHealth, SavePlaceholderInventory, InventoryItemData, SAVE_FORMAT_VERSION,
 };
 pub use resources::SaveManager;
 pub use systems::auto_save_system;
-pub use events::{RequestSaveEvent, RequestLoadEvent};
+pub use events::{RequestSaveEvent, RequestSaveEventQueue, RequestLoadEvent, RequestLoadEventQueue};
+pub use inspect::{
+    SaveAppExt, SaveIssue, SaveMigration, SaveReferenceCheck, SaveSlotSummary, SaveTools,
+    fill_missing_fields, save_format_version, save_section,
+};
 
 pub struct SavePlugin;
 
 impl Plugin for SavePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SaveManager>()
-            .add_event::<RequestSaveEvent>()
-            .add_event::<RequestLoadEvent>()
+            .init_resource::<SaveTools>()
+            .add_plugins((
+                EventQueuePlugin::<RequestSaveEvent>::default(),
+                EventQueuePlugin::<RequestLoadEvent>::default(),
+            ))
             .add_systems(Startup, systems::init_save_manager)
+            .configure_sets(Update, (
+                SaveSet::AutoSave,
+                SaveSet::Requests,
+            ).chain().in_set(crate::GameControllerSet::Persistence))
             .add_systems(Update, (
-                auto_save_system,
-                systems::handle_save_requests,
-                systems::handle_load_requests,
-            ));
+                auto_save_system.in_set(SaveSet::AutoSave),
+                (
+                    systems::handle_save_requests,
+                    systems::handle_load_requests,
+                ).chain().in_set(SaveSet::Requests),
+            ))
+            .add_console_command(ConsoleCommand::new("save_list", "Lists the save files"), save_list_command)
+            .add_console_command(
+                ConsoleCommand::new("save_dump", "Prints a save's sections, or one of them").with_usage("<slot> [section]"),
+                save_dump_command,
+            )
+            .add_console_command(
+                ConsoleCommand::new("save_validate", "Checks a save against the current format").with_usage("<slot>"),
+                save_validate_command,
+            )
+            .add_console_command(
+                ConsoleCommand::new("save_repair", "Removes orphaned references from a save").with_usage("<slot>"),
+                save_repair_command,
+            )
+            .add_console_command(
+                ConsoleCommand::new("save_migrate", "Updates a save to the current format").with_usage("<slot>"),
+                save_migrate_command,
+            );
     }
 }
@@ -4,7 +4,7 @@ use std::fs::{self, File};
 use std::io::{Read, Write};
 use std::path::PathBuf;
 use chrono::{DateTime, Utc};
-use super::types::{SaveData, SaveSlotInfo, EquipmentData, GameProgress};
+use super::types::{SaveData, SaveSlotInfo};
 
 /// Save manager resource
 /// Manages save slots, auto-save settings, and save operations
@@ -71,7 +71,7 @@ impl SaveManager {
             return Err(format!("Slot {} exceeds maximum slots {}", slot, self.max_save_slots));
         }
 
-        let save_path = self.get_save_path(slot);
+        let save_path = self.save_path(slot);
         let json_data = serde_json::to_string_pretty(&data)
             .map_err(|e| format!("Failed to serialize save data: {}", e))?;
 
@@ -104,7 +104,7 @@ impl SaveManager {
 
     /// Load game from specified slot
     pub fn load_game(&mut self, slot: usize) -> Result<SaveData, String> {
-        let save_path = self.get_save_path(slot);
+        let save_path = self.save_path(slot);
 
         if !save_path.exists() {
             return Err(format!("Save file for slot {} does not exist", slot));
@@ -128,7 +128,7 @@ impl SaveManager {
 
     /// Delete save from specified slot
     pub fn delete_save(&mut self, slot: usize) -> Result<(), String> {
-        let save_path = self.get_save_path(slot);
+        let save_path = self.save_path(slot);
 
         if save_path.exists() {
             fs::remove_file(&save_path)
@@ -181,7 +181,7 @@ impl SaveManager {
     }
 
     /// Get save path for specific slot
-    fn get_save_path(&self, slot: usize) -> PathBuf {
+    pub fn save_path(&self, slot: usize) -> PathBuf {
         self.save_directory
             .join(format!("{}_{}.json", self.save_file_name, slot))
     }
@@ -191,7 +191,7 @@ impl SaveManager {
         self.save_slots_cache.clear();
 
         for slot in 0..self.max_save_slots {
-            let save_path = self.get_save_path(slot);
+            let save_path = self.save_path(slot);
 
             if save_path.exists() {
                 match self.load_game(slot) {
@@ -280,36 +280,7 @@ impl SaveManager {
 
     /// New game (reset to default state)
     pub fn new_game(&mut self) -> SaveData {
-        let default_data = SaveData {
-            player_position: Vec3::new(0.0, 0.0, 0.0),
-            player_rotation: Quat::IDENTITY,
-            player_health: 100.0,
-            player_stamina: 100.0,
-            inventory_items: Vec::new(),
-            equipment: EquipmentData {
-                weapon: None,
-                armor: None,
-                accessory: None,
-                custom_slots: HashMap::new(),
-            },
-            game_progress: GameProgress {
-                chapter: 1,
-                quest_progress: HashMap::new(),
-                unlocked_abilities: Vec::new(),
-                discovered_areas: Vec::new(),
-                custom_progress: HashMap::new(),
-            },
-            scene_index: 0,
-            play_time: 0.0,
-            save_date: Utc::now(),
-            save_slot: 0,
-            is_checkpoint: false,
-            checkpoint_id: None,
-            camera_orientation: None,
-            is_driving: false,
-            current_vehicle: None,
-            custom_data: HashMap::new(),
-        };
+        let default_data = SaveData::default();
 
         self.current_save_data = Some(default_data.clone());
         self.current_save_slot = 0;
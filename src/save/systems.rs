@@ -2,12 +2,14 @@ use bevy::prelude::*;
 use std::collections::HashMap;
 use chrono::Utc;
 use super::resources::SaveManager;
-use super::types::{SaveData, SavedInventoryItem, EquipmentData, GameProgress, SavePlaceholderHealth, SavePlaceholderInventory};
+use super::types::{SaveData, SAVE_FORMAT_VERSION, SavedInventoryItem, EquipmentData, GameProgress, SaveCustomData, SavePlaceholderHealth, SavePlaceholderInventory};
 use super::events::{RequestSaveEvent, RequestLoadEvent};
 use crate::character::Player;
 use crate::combat::Health;
 use crate::inventory::{Inventory, InventoryItem, ItemType};
+use crate::loading_screen::{LoadingReason, LoadingScreenState};
 use crate::stats::{StatsSystem, DerivedStat};
+use crate::utils::QueueReader;
 
 /// Auto-save system that runs periodically
 pub fn auto_save_system(
@@ -27,6 +29,7 @@ pub fn auto_save_system(
         // Collect current game state
         if let Some((transform, health, inventory)) = query.iter().next() {
             let data = SaveData {
+                format_version: SAVE_FORMAT_VERSION,
                 player_position: transform.translation,
                 player_rotation: transform.rotation,
                 player_health: health.current,
@@ -79,12 +82,12 @@ pub fn init_save_manager(
 }
 
 pub fn handle_save_requests(
-    mut events: EventReader<RequestSaveEvent>,
+    mut events: QueueReader<RequestSaveEvent>,
     mut save_manager: ResMut<SaveManager>,
-    player_query: Query<(&Transform, &Health, Option<&StatsSystem>, Option<&Inventory>), With<Player>>,
+    player_query: Query<(&Transform, &Health, Option<&StatsSystem>, Option<&Inventory>, Option<&SaveCustomData>), With<Player>>,
 ) {
     for event in events.read() {
-        let Some((transform, health, stats, inventory)) = player_query.iter().next() else { continue };
+        let Some((transform, health, stats, inventory, custom_data)) = player_query.iter().next() else { continue };
         let player_stamina = stats
             .and_then(|s| s.get_derived_stat(DerivedStat::CurrentStamina).copied())
             .unwrap_or(0.0);
@@ -102,6 +105,7 @@ pub fn handle_save_requests(
             .unwrap_or_default();
 
         let data = SaveData {
+            format_version: SAVE_FORMAT_VERSION,
             player_position: transform.translation,
             player_rotation: transform.rotation,
             player_health: health.current,
@@ -129,7 +133,7 @@ pub fn handle_save_requests(
             camera_orientation: None,
             is_driving: false,
             current_vehicle: None,
-            custom_data: HashMap::new(),
+            custom_data: custom_data.map(|custom_data| custom_data.0.clone()).unwrap_or_default(),
         };
 
         if let Err(err) = save_manager.save_game(event.slot, data) {
@@ -139,13 +143,23 @@ pub fn handle_save_requests(
 }
 
 pub fn handle_load_requests(
-    mut events: EventReader<RequestLoadEvent>,
+    mut commands: Commands,
+    mut events: QueueReader<RequestLoadEvent>,
     mut save_manager: ResMut<SaveManager>,
-    mut player_query: Query<(&mut Transform, &mut Health, Option<&mut StatsSystem>, Option<&mut Inventory>), With<Player>>,
+    mut player_query: Query<(Entity, &mut Transform, &mut Health, Option<&mut StatsSystem>, Option<&mut Inventory>), With<Player>>,
+    mut loading_screen: ResMut<LoadingScreenState>,
 ) {
     for event in events.read() {
         let Ok(data) = save_manager.load_game(event.slot) else { continue };
-        let Some((mut transform, mut health, stats, inventory)) = player_query.iter_mut().next() else { continue };
+
+        // World state is applied synchronously; the screen covers the snap and any streaming that follows
+        loading_screen.begin(LoadingReason::SaveLoad);
+        loading_screen.add_task("apply_save", 1.0);
+
+        let Some((player, mut transform, mut health, stats, inventory)) = player_query.iter_mut().next() else {
+            loading_screen.complete_task("apply_save");
+            continue;
+        };
 
         transform.translation = data.player_position;
         transform.rotation = data.player_rotation;
@@ -175,5 +189,9 @@ pub fn handle_load_requests(
             }
             inventory.recalculate_weight();
         }
+
+        commands.entity(player).insert(SaveCustomData(data.custom_data));
+
+        loading_screen.complete_task("apply_save");
     }
 }
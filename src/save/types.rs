@@ -4,10 +4,25 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use chrono::{DateTime, Utc};
 
+/// Save phases, run in order inside `GameControllerSet::Persistence`
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SaveSet {
+    /// Auto-save timer
+    AutoSave,
+    /// `RequestSaveEvent` and `RequestLoadEvent` handling
+    Requests,
+}
+
+/// Version of the `SaveData` layout written by this build; see `save::inspect`
+pub const SAVE_FORMAT_VERSION: u32 = 1;
+
 /// Save data structure
 /// Contains all game state information that needs to be persisted
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SaveData {
+    /// Layout version; saves from before versioning read as 0
+    #[serde(default)]
+    pub format_version: u32,
     /// Player position in the world
     pub player_position: Vec3,
     /// Player rotation
@@ -44,6 +59,43 @@ pub struct SaveData {
     pub custom_data: HashMap<String, serde_json::Value>,
 }
 
+impl Default for SaveData {
+    /// A new game
+    fn default() -> Self {
+        Self {
+            format_version: SAVE_FORMAT_VERSION,
+            player_position: Vec3::ZERO,
+            player_rotation: Quat::IDENTITY,
+            player_health: 100.0,
+            player_stamina: 100.0,
+            inventory_items: Vec::new(),
+            equipment: EquipmentData {
+                weapon: None,
+                armor: None,
+                accessory: None,
+                custom_slots: HashMap::new(),
+            },
+            game_progress: GameProgress {
+                chapter: 1,
+                quest_progress: HashMap::new(),
+                unlocked_abilities: Vec::new(),
+                discovered_areas: Vec::new(),
+                custom_progress: HashMap::new(),
+            },
+            scene_index: 0,
+            play_time: 0.0,
+            save_date: Utc::now(),
+            save_slot: 0,
+            is_checkpoint: false,
+            checkpoint_id: None,
+            camera_orientation: None,
+            is_driving: false,
+            current_vehicle: None,
+            custom_data: HashMap::new(),
+        }
+    }
+}
+
 /// Inventory item data for saving
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SavedInventoryItem {
@@ -96,6 +148,14 @@ pub struct SaveSlotInfo {
     pub is_valid: bool,
 }
 
+/// Module data saved with the player under `SaveData::custom_data`;
+/// restored onto the player when a save is loaded
+#[derive(Component, Debug, Default, Clone)]
+pub struct SaveCustomData(pub HashMap<String, serde_json::Value>);
+
+/// Players whose `SaveCustomData` was just written, e.g. by loading a save
+pub type ChangedPlayerSaveData = (With<crate::character::Player>, Changed<SaveCustomData>);
+
 // Placeholder components for auto-save system
 // These should be integrated with actual game components
 #[derive(Component, Debug)]
@@ -0,0 +1,218 @@
+//! Fixed timestep simulation
+//!
+//! By default the crate's gameplay systems run in `Update`, so their timers
+//! (weapon cooldowns, ability timers, AI perception...) advance by the frame
+//! time. With [`GameControllerPlugin::fixed_simulation`](crate::GameControllerPlugin::fixed_simulation)
+//! the simulation phases (`GameControllerSet::Ai`, `Movement`, `Interaction`
+//! and `Combat`, and the abilities) run in `FixedUpdate` instead, stepping by
+//! the same delta whatever the frame rate, so replays and lockstep multiplayer
+//! can reproduce a session.
+//!
+//! Input gathering, the camera, saving and the UI stay in `Update`:
+//! - Player input gathered over frames that ran no fixed step is latched, so
+//!   the next step still sees every press.
+//! - Event queues only rotate after a frame that ran a fixed step, so
+//!   `FixedUpdate` readers don't miss events sent from `Update`.
+//! - Rigid bodies get `TransformInterpolation`, so rendering moves them
+//!   smoothly between steps.
+//!
+//! Plugins register their simulation systems in
+//! [`SimulationAppExt::simulation_schedule`]; order your own systems against
+//! the simulation phases in that schedule too.
+
+use bevy::ecs::intern::Interned;
+use bevy::ecs::schedule::ScheduleLabel;
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use std::collections::HashMap;
+use crate::input::{InputDrivenPlayer, InputState};
+
+/// Schedule the simulation phases run in
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub enum SimulationMode {
+    /// `Update`, stepped by the frame time
+    #[default]
+    Variable,
+    /// `FixedUpdate`, stepped by `Time<Fixed>`
+    Fixed,
+}
+
+/// Fixed steps run since the last `PreUpdate` (in `First`: during the previous frame)
+#[derive(Resource, Debug, Default)]
+pub struct FixedStepCounter {
+    pub steps: u32,
+}
+
+/// Player input gathered since the last fixed step
+#[derive(Resource, Debug, Default)]
+pub struct LatchedInput {
+    pub global: Option<InputState>,
+    pub players: HashMap<Entity, InputState>,
+    /// A fixed step has read the latched input; the next frame starts over
+    pub consumed: bool,
+}
+
+pub trait SimulationAppExt {
+    /// `FixedUpdate` in fixed simulation mode, `Update` otherwise
+    fn simulation_schedule(&self) -> Interned<dyn ScheduleLabel>;
+}
+
+impl SimulationAppExt for App {
+    fn simulation_schedule(&self) -> Interned<dyn ScheduleLabel> {
+        match self.world().get_resource::<SimulationMode>() {
+            Some(SimulationMode::Fixed) => FixedUpdate.intern(),
+            _ => Update.intern(),
+        }
+    }
+}
+
+/// Run condition: frame-based bookkeeping (event queue rotation) is due.
+/// Always true in variable mode; in fixed mode, only after a frame that ran a fixed step.
+pub fn simulation_frame_advanced(counter: Option<Res<FixedStepCounter>>) -> bool {
+    counter.is_none_or(|counter| counter.steps > 0)
+}
+
+/// Selects the simulation schedule; added by `GameControllerPlugin` before the sub-plugins
+pub struct SimulationPlugin {
+    pub mode: SimulationMode,
+    /// Fixed steps per second in fixed mode
+    pub timestep_hz: f64,
+}
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.mode)
+            .register_type::<SimulationMode>();
+
+        if self.mode != SimulationMode::Fixed {
+            return;
+        }
+
+        app.insert_resource(Time::<Fixed>::from_hz(self.timestep_hz))
+            .init_resource::<FixedStepCounter>()
+            .init_resource::<LatchedInput>()
+            .configure_sets(FixedUpdate, crate::headless::PresentationSet.run_if(crate::headless::presentation_enabled))
+            .add_systems(PreUpdate, reset_fixed_step_counter)
+            .add_systems(FixedFirst, count_fixed_step)
+            .add_systems(FixedUpdate, apply_latched_input.before(crate::GameControllerSet::Ai))
+            .add_systems(Update, latch_player_input
+                .after(crate::input::InputSet::Apply)
+                .in_set(crate::GameControllerSet::Input))
+            .add_systems(PostUpdate, interpolate_rigid_bodies);
+    }
+}
+
+/// System to start counting this frame's fixed steps
+pub fn reset_fixed_step_counter(mut counter: ResMut<FixedStepCounter>) {
+    counter.steps = 0;
+}
+
+/// System to count fixed steps
+pub fn count_fixed_step(mut counter: ResMut<FixedStepCounter>) {
+    counter.steps += 1;
+}
+
+/// System to merge this frame's player input into the input the next fixed step reads
+pub fn latch_player_input(
+    mut latched: ResMut<LatchedInput>,
+    input_state: Res<InputState>,
+    player_query: Query<(Entity, &InputState), InputDrivenPlayer>,
+) {
+    if latched.consumed {
+        latched.global = None;
+        latched.players.clear();
+        latched.consumed = false;
+    }
+
+    match latched.global.as_mut() {
+        Some(global) => global.latch(&input_state),
+        None => latched.global = Some(input_state.clone()),
+    }
+
+    for (entity, input) in player_query.iter() {
+        latched
+            .players
+            .entry(entity)
+            .and_modify(|state| state.latch(input))
+            .or_insert_with(|| input.clone());
+    }
+    latched.players.retain(|entity, _| player_query.contains(*entity));
+}
+
+/// System to hand the latched input to the first fixed step after it was gathered.
+/// Later steps in the same frame keep only the held buttons and the movement axis,
+/// so one press jumps, fires or interacts once
+pub fn apply_latched_input(
+    mut latched: ResMut<LatchedInput>,
+    mut input_state: ResMut<InputState>,
+    mut player_query: Query<&mut InputState, InputDrivenPlayer>,
+) {
+    if latched.consumed {
+        input_state.clear_edges();
+        for mut input in player_query.iter_mut() {
+            input.clear_edges();
+        }
+        return;
+    }
+    latched.consumed = true;
+
+    if let Some(global) = &latched.global {
+        *input_state = global.clone();
+    }
+    for (entity, state) in latched.players.iter() {
+        if let Ok(mut input) = player_query.get_mut(*entity) {
+            *input = state.clone();
+        }
+    }
+}
+
+/// System to smooth rigid bodies between fixed steps
+pub fn interpolate_rigid_bodies(
+    mut commands: Commands,
+    query: Query<Entity, (Added<RigidBody>, Without<TransformInterpolation>)>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(TransformInterpolation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::time::TimeUpdateStrategy;
+
+    #[derive(Resource, Default)]
+    struct Jumps(u32);
+
+    fn press_jump_once(mut input: ResMut<InputState>, mut frame: Local<u32>) {
+        input.jump_pressed = *frame == 0;
+        *frame += 1;
+    }
+
+    fn count_jumps(input: Res<InputState>, mut jumps: ResMut<Jumps>) {
+        if input.jump_pressed {
+            jumps.0 += 1;
+        }
+    }
+
+    #[test]
+    fn test_latched_press_fires_once_per_frame() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, SimulationPlugin { mode: SimulationMode::Fixed, timestep_hz: 64.0 }))
+            .insert_resource(TimeUpdateStrategy::FixedTimesteps(2))
+            .init_resource::<InputState>()
+            .init_resource::<Jumps>()
+            .add_systems(Update, press_jump_once.before(latch_player_input))
+            .add_systems(FixedUpdate, count_jumps.after(apply_latched_input));
+
+        // The press lands on a frame without fixed steps, then two steps run
+        app.update();
+        assert_eq!(app.world().resource::<Jumps>().0, 0);
+        app.update();
+        assert_eq!(app.world().resource::<FixedStepCounter>().steps, 2);
+        assert_eq!(app.world().resource::<Jumps>().0, 1);
+        app.update();
+        assert_eq!(app.world().resource::<Jumps>().0, 1);
+    }
+}
@@ -11,6 +11,7 @@ use skill::*;
 use skill_tree::*;
 use skills_system::*;
 use systems::*;
+use crate::utils::EventQueuePlugin;
 
 pub use types::{SkillType, SkillEvent, SkillSystemEvent};
 pub use skill::{Skill, SkillLevel};
@@ -34,15 +35,15 @@ impl Plugin for SkillsPlugin {
            .register_type::<SkillsSystem>();
 
         // Add events
-        app.init_resource::<SkillSystemEventQueue>()
+        app.add_plugins(EventQueuePlugin::<SkillSystemEvent>::default())
            .register_type::<SkillSystemEvent>();
+           .register_type::<SkillEvent>()
 
         // Add systems
-        app.add_systems(Startup, ui::setup_skill_tree_ui)
+        app.add_systems(Startup, ui::setup_skill_tree_ui.in_set(crate::headless::PresentationSet))
            .add_systems(Update, (
             skills_system_update,
-            ui::toggle_skill_tree_ui,
-            ui::update_skill_tree_ui,
+            (ui::toggle_skill_tree_ui, ui::update_skill_tree_ui).in_set(crate::headless::PresentationSet),
         ));
     }
 }
@@ -3,6 +3,7 @@ use super::types::*;
 
 /// Skill level
 #[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
 pub struct SkillLevel {
     /// Description of skill level
     pub description: String,
@@ -22,6 +23,7 @@ pub struct SkillLevel {
 
 /// Skill
 #[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
 pub struct Skill {
     /// Skill name
     pub name: String,
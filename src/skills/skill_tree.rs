@@ -3,6 +3,7 @@ use super::skill::Skill;
 
 /// Skill category
 #[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
 pub struct SkillCategory {
     /// Category name
     pub name: String,
@@ -75,6 +76,7 @@ impl SkillCategory {
 
 /// Skill tree
 #[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
 pub struct SkillTree {
     /// Skill categories
     pub categories: Vec<SkillCategory>,
@@ -410,6 +412,7 @@ impl Default for SkillTree {
 
 /// Skill template for save/load
 #[derive(Debug, Clone, Component, Reflect)]
+#[reflect(Component)]
 pub struct SkillTemplate {
     /// Categories in template
     pub categories: Vec<SkillTemplateCategory>,
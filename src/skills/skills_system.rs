@@ -4,6 +4,7 @@ use super::types::SkillSlot;
 
 /// Skills system component
 #[derive(Debug, Component, Reflect)]
+#[reflect(Component)]
 pub struct SkillsSystem {
     /// Is skills system active
     pub active: bool,
@@ -5,6 +5,7 @@ use super::types::*;
 use crate::experience::types::PlayerExperience;
 use crate::stats::types::{StatModifier, ModifierType, AddModifierEventQueue, AddModifierEvent, RemoveModifierEventQueue, RemoveModifierEvent};
 use crate::abilities::types::{SetAbilityEnabledEventQueue, SetAbilityEnabledEvent};
+use crate::utils::QueueReader;
 
 /// Helper to apply effects of a skill to an entity
 fn apply_effects_for_skill(
@@ -32,7 +33,7 @@ fn apply_effects_for_skill(
     for effect in effects_to_apply {
         match effect {
             SkillEffect::StatModifier { stat, amount, is_percentage } => {
-                stat_events.0.push(AddModifierEvent {
+                stat_events.send(AddModifierEvent {
                     modifier: StatModifier::new(
                         &format!("{}: {}", source_type, skill_name),
                         if amount >= 0.0 { ModifierType::Buff } else { ModifierType::Debuff },
@@ -44,7 +45,7 @@ fn apply_effects_for_skill(
                 });
             }
             SkillEffect::UnlockAbility(ability_name) => {
-                ability_events.0.push(SetAbilityEnabledEvent {
+                ability_events.send(SetAbilityEnabledEvent {
                     player_entity: entity,
                     ability_name,
                     enabled: true,
@@ -67,7 +68,7 @@ fn remove_effects_for_skill(
     let modifier_name = format!("{}: {}", source_type, skill_name);
     
     // Remove stat modifiers
-    stat_remove_events.0.push(RemoveModifierEvent {
+    stat_remove_events.send(RemoveModifierEvent {
         modifier_name,
     });
 
@@ -85,7 +86,7 @@ fn remove_effects_for_skill(
 
     for effect in effects_to_remove {
         if let SkillEffect::UnlockAbility(ability_name) = effect {
-            ability_events.0.push(SetAbilityEnabledEvent {
+            ability_events.send(SetAbilityEnabledEvent {
                 player_entity: entity,
                 ability_name,
                 enabled: false,
@@ -97,7 +98,7 @@ fn remove_effects_for_skill(
 /// Skills system update
 pub fn skills_system_update(
     mut query: Query<(Entity, &mut SkillsSystem)>,
-    mut event_queue: ResMut<SkillSystemEventQueue>,
+    mut event_queue: QueueReader<SkillSystemEvent>,
     mut experience_query: Query<&mut PlayerExperience>,
     mut stat_events: ResMut<AddModifierEventQueue>,
     mut stat_remove_events: ResMut<RemoveModifierEventQueue>,
@@ -140,7 +141,7 @@ pub fn skills_system_update(
             for (skill_name, effect) in effects_to_apply {
                 match effect {
                     SkillEffect::StatModifier { stat, amount, is_percentage } => {
-                        stat_events.0.push(AddModifierEvent {
+                        stat_events.send(AddModifierEvent {
                             modifier: StatModifier::new(
                                 &format!("Skill Initialize: {}", skill_name),
                                 if amount >= 0.0 { ModifierType::Buff } else { ModifierType::Debuff },
@@ -152,7 +153,7 @@ pub fn skills_system_update(
                         });
                     }
                     SkillEffect::UnlockAbility(ability_name) => {
-                        ability_events.0.push(SetAbilityEnabledEvent {
+                        ability_events.send(SetAbilityEnabledEvent {
                             player_entity: entity,
                             ability_name,
                             enabled: true,
@@ -168,7 +169,7 @@ pub fn skills_system_update(
     }
 
     // Process purchase requests
-    for event in event_queue.0.drain(..) {
+    for event in event_queue.read().cloned() {
         if let SkillSystemEvent::PurchaseSkillRequest { player_entity, category_index, skill_index } = event {
             if let Ok((entity, mut skills_system)) = query.get_mut(player_entity) {
                 if !skills_system.active { continue; }
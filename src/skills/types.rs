@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use crate::stats::types::DerivedStat;
+use crate::utils::EventQueue;
 
 /// Skill type - determines how skill affects character
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
@@ -14,6 +15,7 @@ pub enum SkillType {
 
 /// Skill event
 #[derive(Debug, Clone, Component, Reflect, Default)]
+#[reflect(Component)]
 pub enum SkillEvent {
     #[default]
     None,
@@ -99,5 +101,4 @@ pub enum SkillSystemEvent {
     SkillUnequipped { entity: Entity, skill_name: String, slot_index: usize },
 }
 
-#[derive(Resource, Default)]
-pub struct SkillSystemEventQueue(pub Vec<SkillSystemEvent>);
+pub type SkillSystemEventQueue = EventQueue<SkillSystemEvent>;
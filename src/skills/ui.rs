@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use super::skills_system::SkillsSystem;
 use super::types::{SkillSystemEventQueue, SkillSystemEvent};
+use crate::input::FocusScope;
 
 // Markers for UI elements
 #[derive(Component)]
@@ -30,6 +31,7 @@ pub fn setup_skill_tree_ui(mut commands: Commands) {
         },
         BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.95)),
         SkillTreeRoot,
+        FocusScope::default(),
         Visibility::Hidden,
     )).with_children(|parent| {
         // Header
@@ -203,7 +205,7 @@ pub fn update_skill_tree_ui(
     for (interaction, button, _) in skill_button_query.iter() {
         if *interaction == Interaction::Pressed {
             // Send Purchase Request
-            event_queue.0.push(SkillSystemEvent::PurchaseSkillRequest {
+            event_queue.send(SkillSystemEvent::PurchaseSkillRequest {
                 player_entity,
                 category_index: button.category_index,
                 skill_index: button.skill_index,
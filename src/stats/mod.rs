@@ -7,6 +7,7 @@ use bevy::prelude::*;
 use types::*;
 use stats_system::*;
 use systems::*;
+use crate::utils::EventQueuePlugin;
 
 // Re-export specific types for cleaner imports
 pub use types::{
@@ -25,15 +26,15 @@ impl Plugin for StatsPlugin {
         app
             // Register types
             .register_type::<StatsSystem>()
-            .init_resource::<AddModifierEventQueue>()
-            .init_resource::<RemoveModifierEventQueue>()
+            .add_plugins(EventQueuePlugin::<AddModifierEvent>::default())
+            .add_plugins(EventQueuePlugin::<RemoveModifierEvent>::default())
             // Add systems
             .add_systems(Update, (
                 update_stats,
                 handle_stat_changes,
                 handle_modifier_events,
-                ui::update_stats_hud,
+                ui::update_stats_hud.in_set(crate::headless::PresentationSet),
             ))
-            .add_systems(Startup, ui::setup_stats_hud);
+            .add_systems(Startup, ui::setup_stats_hud.in_set(crate::headless::PresentationSet));
     }
 }
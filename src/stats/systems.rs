@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use super::stats_system::StatsSystem;
-use super::types::{AddModifierEventQueue, RemoveModifierEventQueue};
+use super::types::{AddModifierEvent, RemoveModifierEvent};
+use crate::utils::QueueReader;
 
 /// System to update stats (modifiers, regeneration, etc.)
 pub fn update_stats(
@@ -26,22 +27,22 @@ pub fn handle_stat_changes() {
 /// System to handle modifier events
 pub fn handle_modifier_events(
     mut stats_query: Query<&mut StatsSystem>,
-    mut add_queue: ResMut<AddModifierEventQueue>,
-    mut remove_queue: ResMut<RemoveModifierEventQueue>,
+    mut add_queue: QueueReader<AddModifierEvent>,
+    mut remove_queue: QueueReader<RemoveModifierEvent>,
 ) {
     // Note: This logic assumes modifiers are applied to ALL StatsSystems.
     // In a multi-character game, we'd need entity targeting in the events.
     // For now, let's assume it's for the player or all entities.
     
     // Process add events
-    for event in add_queue.0.drain(..) {
+    for event in add_queue.read().cloned() {
         for mut stats in stats_query.iter_mut() {
             stats.add_modifier(event.modifier.clone());
         }
     }
 
     // Process remove events
-    for event in remove_queue.0.drain(..) {
+    for event in remove_queue.read().cloned() {
         for mut stats in stats_query.iter_mut() {
             stats.remove_modifier(&event.modifier_name);
         }
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use super::stats_system::StatsSystem;
+use crate::utils::EventQueue;
 
 /// Core attributes that define a character's base capabilities.
 ///
@@ -433,19 +434,17 @@ pub struct CoreAttributeChangedEvent {
 }
 
 /// Event for adding a modifier
-#[derive(Event)]
+#[derive(Event, Debug, Clone)]
 pub struct AddModifierEvent {
     pub modifier: StatModifier,
 }
 
-#[derive(Resource, Default)]
-pub struct AddModifierEventQueue(pub Vec<AddModifierEvent>);
+pub type AddModifierEventQueue = EventQueue<AddModifierEvent>;
 
 /// Event for removing a modifier
-#[derive(Event)]
+#[derive(Event, Debug, Clone)]
 pub struct RemoveModifierEvent {
     pub modifier_name: String,
 }
 
-#[derive(Resource, Default)]
-pub struct RemoveModifierEventQueue(pub Vec<RemoveModifierEvent>);
+pub type RemoveModifierEventQueue = EventQueue<RemoveModifierEvent>;
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use super::stats_system::StatsSystem;
 use super::types::DerivedStat;
+use crate::hud::{HudElement, HudPriority};
 
 // Markers for UI elements
 #[derive(Component)]
@@ -39,6 +40,7 @@ pub fn setup_stats_hud(mut commands: Commands) {
             ..default()
         },
         StatsHudRoot,
+        HudElement::new("stats", HudPriority::Essential),
     )).with_children(|parent| {
         // Health Bar Container
         parent.spawn(Node {
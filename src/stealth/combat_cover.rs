@@ -0,0 +1,417 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::camera::{CameraController, CameraSide};
+use crate::character::CharacterMovementState;
+use crate::combat::Health;
+use crate::input::{InputAction, InputState};
+use crate::utils::EventQueue;
+use crate::weapons::BlindFire;
+use super::types::*;
+use super::components::*;
+
+/// Lets a character snap to walls with `TakeCover` and fight from them
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CombatCover {
+    /// Farthest wall `TakeCover` snaps to
+    pub snap_distance: f32,
+    /// Gap kept between the character and the cover face
+    pub wall_offset: f32,
+    /// How strongly movement is pulled back to `wall_offset`
+    pub magnet_strength: f32,
+    /// How far to the side the face must continue before an edge is reached
+    pub edge_probe_distance: f32,
+    /// Probe height over the character's origin; covers shorter than this are low
+    pub low_cover_height: f32,
+    /// Faces steeper than this (degrees from vertical) can't be used as cover
+    pub max_face_angle: f32,
+    /// Spread multiplier of weapons fired from cover without aiming
+    pub blind_fire_spread_multiplier: f32,
+    /// Cover health fraction under which `CoverEvent::Compromised` is sent
+    pub compromised_health_fraction: f32,
+    /// Put the camera over the shoulder the character moves toward
+    pub swap_camera_shoulder: bool,
+}
+
+impl Default for CombatCover {
+    fn default() -> Self {
+        Self {
+            snap_distance: 1.5,
+            wall_offset: 0.4,
+            magnet_strength: 4.0,
+            edge_probe_distance: 0.5,
+            low_cover_height: 1.2,
+            max_face_angle: 30.0,
+            blind_fire_spread_multiplier: 3.0,
+            compromised_health_fraction: 0.3,
+            swap_camera_shoulder: true,
+        }
+    }
+}
+
+/// How a character in cover is aiming out of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum CoverPopOut {
+    #[default]
+    None,
+    /// Standing up over low cover
+    Over,
+    /// Leaning out past an edge, toward `side`
+    Corner { side: CameraSide },
+}
+
+/// Cover a character is snapped to. Sides are as seen facing the cover.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct InCover {
+    pub cover: Entity,
+    /// Face normal, pointing from the cover to the character
+    pub normal: Vec3,
+    pub point: Vec3,
+    pub is_low: bool,
+    /// Edge the character stands at, if any
+    pub edge: Option<CameraSide>,
+    /// Last direction moved along the cover
+    pub facing_side: CameraSide,
+    pub pop_out: CoverPopOut,
+    pub compromised: bool,
+}
+
+impl Default for InCover {
+    fn default() -> Self {
+        Self {
+            cover: Entity::PLACEHOLDER,
+            normal: Vec3::Z,
+            point: Vec3::ZERO,
+            is_low: false,
+            edge: None,
+            facing_side: CameraSide::Right,
+            pop_out: CoverPopOut::None,
+            compromised: false,
+        }
+    }
+}
+
+impl InCover {
+    /// Direction along the face toward `side`
+    pub fn side_direction(&self, side: CameraSide) -> Vec3 {
+        let right = (-self.normal).cross(Vec3::Y).normalize_or_zero();
+        match side {
+            CameraSide::Right => right,
+            CameraSide::Left => -right,
+        }
+    }
+
+    /// Keeps the part of `move_dir` along the face, dropping any push past an edge
+    pub fn constrain_movement(&self, move_dir: Vec3) -> Vec3 {
+        let right = self.side_direction(CameraSide::Right);
+        let along = move_dir.dot(right);
+        let blocked = match self.edge {
+            Some(CameraSide::Right) => along > 0.0,
+            Some(CameraSide::Left) => along < 0.0,
+            None => false,
+        };
+        if blocked { Vec3::ZERO } else { right * along }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CoverEvent {
+    Entered { entity: Entity, cover: Entity },
+    Left { entity: Entity, cover: Entity },
+    /// The cover is below `CombatCover::compromised_health_fraction`
+    Compromised { entity: Entity, cover: Entity },
+    /// The cover died or was despawned; the character is out of cover
+    Destroyed { entity: Entity, cover: Entity },
+}
+
+pub type CoverEventQueue = EventQueue<CoverEvent>;
+
+fn flat(direction: Vec3) -> Vec3 {
+    Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero()
+}
+
+fn probe_face(
+    spatial_query: &SpatialQuery,
+    origin: Vec3,
+    toward: Vec3,
+    distance: f32,
+    entity: Entity,
+) -> Option<RayHitData> {
+    let direction = Dir3::new(toward).ok()?;
+    spatial_query.cast_ray(
+        origin,
+        direction,
+        distance,
+        true,
+        &SpatialQueryFilter::from_excluded_entities([entity]),
+    )
+}
+
+fn set_cover_detection(detection: Option<Mut<CoverDetection>>, cover: Option<&InCover>) {
+    let Some(mut detection) = detection else { return };
+    detection.is_in_cover = cover.is_some();
+    detection.current_cover = cover.map(|cover| cover.cover);
+    if let Some(cover) = cover {
+        detection.cover_normal = cover.normal;
+        detection.cover_direction = -cover.normal;
+        detection.cover_type = if cover.is_low { CoverType::Low } else { CoverType::High };
+    }
+}
+
+fn end_pop_out(state: Option<Mut<StealthState>>) {
+    let Some(mut state) = state else { return };
+    state.is_peeking = false;
+    state.is_corner_leaning = false;
+    state.hide_state = if state.is_hidden { HideState::CrouchHide } else { HideState::Visible };
+}
+
+type CoverTaker<'a> = (
+    Entity,
+    &'a CombatCover,
+    &'a mut Transform,
+    Option<&'a InCover>,
+    Option<&'a mut CoverDetection>,
+    Option<&'a mut StealthState>,
+);
+
+/// Snaps to the wall in front on `TakeCover`, or leaves cover
+pub fn handle_take_cover_input(
+    mut commands: Commands,
+    input_state: Res<InputState>,
+    spatial_query: SpatialQuery,
+    mut events: ResMut<CoverEventQueue>,
+    mut query: Query<CoverTaker>,
+) {
+    if !input_state.is_action_just_pressed(InputAction::TakeCover) {
+        return;
+    }
+
+    for (entity, settings, mut transform, in_cover, detection, state) in query.iter_mut() {
+        if let Some(in_cover) = in_cover {
+            commands.entity(entity).remove::<(InCover, BlindFire)>();
+            events.send(CoverEvent::Left { entity, cover: in_cover.cover });
+            set_cover_detection(detection, None);
+            end_pop_out(state);
+            continue;
+        }
+
+        let forward = flat(transform.forward().as_vec3());
+        let Some(hit) = probe_face(&spatial_query, transform.translation, forward, settings.snap_distance, entity) else {
+            continue;
+        };
+        if hit.normal.y.abs() > settings.max_face_angle.to_radians().sin() {
+            continue;
+        }
+
+        let normal = flat(hit.normal);
+        let point = transform.translation + forward * hit.distance;
+        let head = transform.translation + Vec3::Y * settings.low_cover_height;
+        let is_low = probe_face(&spatial_query, head, -normal, settings.snap_distance, entity).is_none();
+
+        transform.translation = Vec3::new(point.x, transform.translation.y, point.z) + normal * settings.wall_offset;
+        transform.look_to(-normal, Vec3::Y);
+
+        let cover = InCover {
+            cover: hit.entity,
+            normal,
+            point,
+            is_low,
+            ..default()
+        };
+        set_cover_detection(detection, Some(&cover));
+        commands.entity(entity).insert((
+            cover,
+            BlindFire { spread_multiplier: settings.blind_fire_spread_multiplier },
+        ));
+        events.send(CoverEvent::Entered { entity, cover: hit.entity });
+    }
+}
+
+type CoveredCharacter<'a> = (
+    Entity,
+    &'a CombatCover,
+    &'a mut InCover,
+    Option<&'a mut CoverDetection>,
+    Option<&'a mut StealthState>,
+);
+
+/// Drops characters out of cover that was destroyed and warns about cover about to break
+pub fn track_cover_destruction(
+    mut commands: Commands,
+    mut events: ResMut<CoverEventQueue>,
+    mut query: Query<CoveredCharacter>,
+    cover_query: Query<Option<&Health>>,
+) {
+    for (entity, settings, mut in_cover, detection, state) in query.iter_mut() {
+        let health = cover_query.get(in_cover.cover);
+        let destroyed = match health {
+            Ok(health) => health.is_some_and(|health| health.is_dead),
+            Err(_) => true,
+        };
+
+        if destroyed {
+            commands.entity(entity).remove::<(InCover, BlindFire)>();
+            events.send(CoverEvent::Destroyed { entity, cover: in_cover.cover });
+            set_cover_detection(detection, None);
+            end_pop_out(state);
+            continue;
+        }
+
+        let Ok(Some(health)) = health else { continue };
+        if !in_cover.compromised
+            && health.maximum > 0.0
+            && health.current / health.maximum < settings.compromised_health_fraction
+        {
+            in_cover.compromised = true;
+            events.send(CoverEvent::Compromised { entity, cover: in_cover.cover });
+        }
+    }
+}
+
+type CoverMover<'a> = (
+    Entity,
+    &'a CombatCover,
+    &'a mut InCover,
+    &'a mut CharacterMovementState,
+    &'a Transform,
+    Option<&'a mut CoverDetection>,
+    Option<&'a mut StealthState>,
+);
+
+/// Keeps movement along the cover face, pulled to the wall and stopped at edges
+pub fn update_cover_movement(
+    mut commands: Commands,
+    spatial_query: SpatialQuery,
+    mut events: ResMut<CoverEventQueue>,
+    mut query: Query<CoverMover>,
+) {
+    for (entity, settings, mut in_cover, mut movement, transform, detection, state) in query.iter_mut() {
+        let position = transform.translation;
+        let reach = settings.wall_offset + settings.snap_distance;
+
+        // Follow the face as it bends; losing it means the cover is gone from behind us
+        let Some(hit) = probe_face(&spatial_query, position, -in_cover.normal, reach, entity) else {
+            commands.entity(entity).remove::<(InCover, BlindFire)>();
+            events.send(CoverEvent::Left { entity, cover: in_cover.cover });
+            set_cover_detection(detection, None);
+            end_pop_out(state);
+            continue;
+        };
+        let wall_distance = hit.distance;
+        in_cover.normal = flat(hit.normal).try_normalize().unwrap_or(in_cover.normal);
+        in_cover.point = position - in_cover.normal * wall_distance;
+
+        in_cover.edge = [CameraSide::Left, CameraSide::Right].into_iter().find(|&side| {
+            let origin = position + in_cover.side_direction(side) * settings.edge_probe_distance;
+            probe_face(&spatial_query, origin, -in_cover.normal, reach, entity).is_none()
+        });
+
+        let along = in_cover.constrain_movement(movement.raw_move_dir);
+        if along.length_squared() > 0.01 {
+            let right = in_cover.side_direction(CameraSide::Right);
+            in_cover.facing_side = if along.dot(right) > 0.0 { CameraSide::Right } else { CameraSide::Left };
+        }
+
+        // Aiming out of cover holds the character in place
+        let (along, lerped) = if in_cover.pop_out == CoverPopOut::None {
+            (along, in_cover.constrain_movement(movement.lerped_move_dir))
+        } else {
+            (Vec3::ZERO, Vec3::ZERO)
+        };
+        let pull = -in_cover.normal * (wall_distance - settings.wall_offset) * settings.magnet_strength;
+        movement.raw_move_dir = along;
+        movement.lerped_move_dir = lerped + pull;
+        movement.is_sprinting = false;
+        movement.is_crouching = in_cover.is_low && in_cover.pop_out != CoverPopOut::Over;
+    }
+}
+
+/// Aiming from cover pops out: over low cover, or around an edge using the corner lean.
+/// Firing without aiming stays behind cover as blind fire.
+pub fn update_cover_pop_out(
+    mut commands: Commands,
+    mut input_state: ResMut<InputState>,
+    mut query: Query<(Entity, &CombatCover, &mut InCover, Option<&mut StealthState>)>,
+) {
+    for (entity, settings, mut in_cover, state) in query.iter_mut() {
+        let pop_out = if !input_state.intends(InputAction::Aim) {
+            CoverPopOut::None
+        } else if let Some(side) = in_cover.edge {
+            CoverPopOut::Corner { side }
+        } else if in_cover.is_low {
+            CoverPopOut::Over
+        } else {
+            CoverPopOut::None
+        };
+
+        // The camera's lean collision does the leaning out
+        if let CoverPopOut::Corner { side } = pop_out {
+            input_state.lean_left = side == CameraSide::Left;
+            input_state.lean_right = side == CameraSide::Right;
+        }
+
+        if pop_out == in_cover.pop_out {
+            continue;
+        }
+        in_cover.pop_out = pop_out;
+
+        if pop_out == CoverPopOut::None {
+            commands.entity(entity).insert(BlindFire { spread_multiplier: settings.blind_fire_spread_multiplier });
+            end_pop_out(state);
+            continue;
+        }
+
+        commands.entity(entity).remove::<BlindFire>();
+        if let Some(mut state) = state {
+            let corner = matches!(pop_out, CoverPopOut::Corner { .. });
+            state.is_peeking = !corner;
+            state.is_corner_leaning = corner;
+            state.hide_state = if corner { HideState::CornerLean } else { HideState::Peek };
+        }
+    }
+}
+
+/// Puts the camera over the shoulder the character moves toward, or leans out to
+pub fn swap_cover_camera_shoulder(
+    mut camera_query: Query<&mut CameraController>,
+    cover_query: Query<(&CombatCover, &InCover)>,
+) {
+    for mut camera in camera_query.iter_mut() {
+        let Some(target) = camera.follow_target else { continue };
+        let Ok((settings, in_cover)) = cover_query.get(target) else { continue };
+        if !settings.swap_camera_shoulder {
+            continue;
+        }
+
+        let side = match in_cover.pop_out {
+            CoverPopOut::Corner { side } => side,
+            _ => in_cover.facing_side,
+        };
+        if camera.current_side != side {
+            camera.current_side = side;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::CameraSide;
+
+    #[test]
+    fn test_cover_movement_along_face() {
+        // Cover facing +Z: the character faces -Z, so right is +X
+        let mut cover = InCover { normal: Vec3::Z, ..Default::default() };
+        assert!(cover.side_direction(CameraSide::Right).abs_diff_eq(Vec3::X, 1e-5));
+
+        // Pushing into the wall goes nowhere, diagonal input keeps its sideways part
+        assert_eq!(cover.constrain_movement(Vec3::NEG_Z), Vec3::ZERO);
+        assert!(cover.constrain_movement(Vec3::new(-0.6, 0.0, -0.8)).abs_diff_eq(Vec3::new(-0.6, 0.0, 0.0), 1e-5));
+
+        // At the left edge only moving back to the right is allowed
+        cover.edge = Some(CameraSide::Left);
+        assert_eq!(cover.constrain_movement(Vec3::NEG_X), Vec3::ZERO);
+        assert!(cover.constrain_movement(Vec3::X).abs_diff_eq(Vec3::X, 1e-5));
+    }
+}
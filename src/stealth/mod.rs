@@ -1,15 +1,19 @@
 pub mod types;
 pub mod components;
 pub mod systems;
+pub mod combat_cover;
 
 use bevy::prelude::*;
 use types::*;
 use components::*;
 use systems::*;
+use combat_cover::*;
+use crate::utils::EventQueuePlugin;
 
 pub use types::{HideState, CoverType, CoverObject};
 pub use components::{StealthController, StealthState, CoverDetection, VisibilityMeter};
 pub use systems::*;
+pub use combat_cover::{CombatCover, InCover, CoverPopOut, CoverEvent, CoverEventQueue};
 
 pub struct StealthPlugin;
 
@@ -20,6 +24,9 @@ impl Plugin for StealthPlugin {
             .register_type::<StealthState>()
             .register_type::<CoverDetection>()
             .register_type::<VisibilityMeter>()
+            .register_type::<CombatCover>()
+            .register_type::<InCover>()
+            .add_plugins(EventQueuePlugin::<CoverEvent>::default())
             .add_systems(Update, (
                 handle_stealth_input,
                 update_stealth_state,
@@ -29,7 +36,18 @@ impl Plugin for StealthPlugin {
                 detect_cover_objects,
                 check_line_of_sight,
                 update_hide_states,
-            ));
+            ))
+            // Cover rewrites the player's movement and aim before the character and camera use them
+            .add_systems(Update, (
+                handle_take_cover_input,
+                track_cover_destruction,
+                update_cover_movement,
+                update_cover_pop_out,
+                swap_cover_camera_shoulder,
+            ).chain()
+                .after(crate::GameControllerSet::Input)
+                .after(crate::player::input::handle_player_input)
+                .before(crate::GameControllerSet::Movement));
     }
 }
 
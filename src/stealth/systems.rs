@@ -2,7 +2,9 @@ use bevy::prelude::*;
 use avian3d::prelude::*;
 use crate::character::CharacterMovementState;
 use crate::ai::AiController;
+use crate::footsteps::{FootstepEvent, FootstepNoiseSettings};
 use crate::input::{InputState, InputAction};
+use crate::utils::QueueReader;
 use super::types::*;
 use super::components::*;
 
@@ -464,9 +466,18 @@ pub fn update_hide_states(
 /// Update visibility meter
 pub fn update_visibility_meter(
     time: Res<Time>,
-    mut query: Query<(&StealthController, &mut StealthState, &mut VisibilityMeter, &CharacterMovementState)>,
+    noise_settings: Res<FootstepNoiseSettings>,
+    mut footsteps: QueueReader<FootstepEvent>,
+    mut query: Query<(&StealthController, &mut StealthState, &mut VisibilityMeter)>,
 ) {
-    for (_stealth, state, mut visibility, movement) in query.iter_mut() {
+    // Each footstep raises the sound level to its loudness, which then decays
+    for event in footsteps.read() {
+        if let Ok((_, _, mut visibility)) = query.get_mut(event.entity) {
+            visibility.sound_level = visibility.sound_level.max(noise_settings.loudness(event.noise_radius).min(1.0));
+        }
+    }
+
+    for (_stealth, state, mut visibility) in query.iter_mut() {
         // Update visibility based on hide state
         if state.is_hidden {
             visibility.current_visibility = 0.0;
@@ -476,17 +487,6 @@ pub fn update_visibility_meter(
             visibility.is_visible_to_ai = true;
         }
         
-        // Update sound level based on movement
-        if movement.is_sprinting {
-            visibility.sound_level = 1.0;
-        } else if movement.is_running {
-            visibility.sound_level = 0.7;
-        } else if movement.raw_move_dir.length() > 0.0 {
-            visibility.sound_level = 0.3;
-        } else {
-            visibility.sound_level = 0.0;
-        }
-        
         // Decay sound level over time
         if visibility.sound_level > 0.0 {
             visibility.sound_level -= time.delta_secs() * visibility.sound_decay_rate;
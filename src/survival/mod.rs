@@ -0,0 +1,58 @@
+//! Survival needs
+//!
+//! Characters with [`SurvivalNeeds`] get hungry and thirsty as game time goes
+//! by, and their body temperature drifts toward the surroundings whenever
+//! these are outside the comfort range. [`TemperatureZone`]s warm or chill the
+//! ambient temperature locally: campfires, snow fields, hot springs...
+//!
+//! Food and drinks are plain inventory items listed in [`SurvivalConsumables`];
+//! using them through the regular use-inventory-object flow feeds the needs.
+//! Low values start [`SurvivalCondition`]s that put stat debuffs on the
+//! character's `StatsSystem`, and critical ones (starving, dehydrated,
+//! freezing) deal damage every second until dealt with.
+//!
+//! ```rust,ignore
+//! commands.entity(player).insert(SurvivalNeeds { insulation: 5.0, ..default() });
+//! commands.spawn((Transform::from_xyz(4.0, 0.0, 2.0), TemperatureZone::campfire(4.0)));
+//! commands.spawn((Transform::from_xyz(0.0, 0.0, 80.0), TemperatureZone::snow_area(Vec3::new(40.0, 10.0, 40.0))));
+//! consumables.register("roasted_fish", SurvivalRestore::food(35.0).with_warmth(0.5));
+//! ```
+
+pub mod types;
+pub mod systems;
+pub mod ui;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use ui::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    SurvivalNeeds, SurvivalCondition, SurvivalDebuff, SurvivalRestore, SurvivalConsumables,
+    TemperatureZoneShape, TemperatureZone, SurvivalSettings, SurvivalEvent, SurvivalEventQueue,
+    NORMAL_BODY_TEMPERATURE,
+};
+
+pub struct SurvivalPlugin;
+
+impl Plugin for SurvivalPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SurvivalSettings>()
+            .init_resource::<SurvivalConsumables>()
+            .add_plugins(EventQueuePlugin::<SurvivalEvent>::default())
+            .register_type::<SurvivalSettings>()
+            .register_type::<SurvivalConsumables>()
+            .register_type::<SurvivalNeeds>()
+            .register_type::<TemperatureZone>()
+            .add_systems(Startup, setup_survival_hud.in_set(crate::headless::PresentationSet))
+            .add_systems(Update, (
+                update_ambient_temperature,
+                drain_survival_needs,
+                consume_survival_items,
+                update_survival_conditions,
+            ).chain())
+            .add_systems(Update, update_survival_hud.in_set(crate::headless::PresentationSet));
+    }
+}
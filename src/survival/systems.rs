@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+use crate::combat::{DamageEvent, DamageEventQueue, DamageType};
+use crate::inventory::InventoryObjectUsedEvent;
+use crate::stats::StatsSystem;
+use crate::utils::{GameTime, QueueReader};
+use super::types::*;
+
+/// System to update the temperature around each character from the zones it stands in
+pub fn update_ambient_temperature(
+    settings: Res<SurvivalSettings>,
+    zone_query: Query<(&GlobalTransform, &TemperatureZone)>,
+    mut needs_query: Query<(&GlobalTransform, &mut SurvivalNeeds)>,
+) {
+    for (transform, mut needs) in needs_query.iter_mut() {
+        let position = transform.translation();
+        let offset: f32 = zone_query
+            .iter()
+            .map(|(zone_transform, zone)| zone.offset_at(zone_transform, position))
+            .sum();
+        needs.ambient_temperature = settings.ambient_temperature + offset;
+    }
+}
+
+/// System to drain hunger and thirst and move body temperature toward the surroundings
+pub fn drain_survival_needs(
+    time: Res<Time>,
+    game_time: Res<GameTime>,
+    settings: Res<SurvivalSettings>,
+    mut needs_query: Query<&mut SurvivalNeeds>,
+) {
+    if !settings.enabled || game_time.paused {
+        return;
+    }
+    let dt = time.delta_secs();
+    let minutes = dt * settings.time_scale;
+
+    for mut needs in needs_query.iter_mut() {
+        let thirst_multiplier = if needs.has_condition(SurvivalCondition::Overheated) {
+            settings.overheated_thirst_multiplier
+        } else {
+            1.0
+        };
+        needs.hunger = (needs.hunger - needs.hunger_per_minute * minutes).max(0.0);
+        needs.thirst = (needs.thirst - needs.thirst_per_minute * thirst_multiplier * minutes).max(0.0);
+
+        // Clothing widens the comfort range on the cold side
+        let (comfort_min, comfort_max) = (settings.comfort_range.0 - needs.insulation, settings.comfort_range.1);
+        let ambient = needs.ambient_temperature;
+        if ambient < comfort_min {
+            needs.body_temperature -= (comfort_min - ambient) * settings.temperature_change_rate * dt;
+        } else if ambient > comfort_max {
+            needs.body_temperature += (ambient - comfort_max) * settings.temperature_change_rate * dt;
+        } else {
+            let recovery = settings.temperature_recovery_rate * dt;
+            let difference = NORMAL_BODY_TEMPERATURE - needs.body_temperature;
+            needs.body_temperature += difference.clamp(-recovery, recovery);
+        }
+        needs.body_temperature = needs.body_temperature.clamp(25.0, 45.0);
+    }
+}
+
+/// System to apply food and drinks used through the inventory
+pub fn consume_survival_items(
+    consumables: Res<SurvivalConsumables>,
+    mut used_events: QueueReader<InventoryObjectUsedEvent>,
+    mut events: ResMut<SurvivalEventQueue>,
+    mut needs_query: Query<&mut SurvivalNeeds>,
+) {
+    for event in used_events.read() {
+        let Some(restore) = consumables.get(&event.item.item_id) else { continue };
+        let Ok(mut needs) = needs_query.get_mut(event.owner) else { continue };
+        needs.restore(restore, event.item.quantity);
+        events.send(SurvivalEvent::Consumed {
+            entity: event.owner,
+            item_id: event.item.item_id.clone(),
+            restore: *restore,
+        });
+    }
+}
+
+/// System to apply debuffs for the current conditions and hurt characters in critical ones
+pub fn update_survival_conditions(
+    time: Res<Time>,
+    game_time: Res<GameTime>,
+    settings: Res<SurvivalSettings>,
+    mut events: ResMut<SurvivalEventQueue>,
+    mut damage_queue: ResMut<DamageEventQueue>,
+    mut needs_query: Query<(Entity, &mut SurvivalNeeds, Option<&mut StatsSystem>)>,
+) {
+    for (entity, mut needs, mut stats) in needs_query.iter_mut() {
+        let conditions = if settings.enabled { needs.evaluate_conditions(&settings) } else { Vec::new() };
+
+        let ended: Vec<_> = needs.conditions.iter().copied().filter(|c| !conditions.contains(c)).collect();
+        let started: Vec<_> = conditions.iter().copied().filter(|c| !needs.conditions.contains(c)).collect();
+        for condition in ended {
+            if let Some(stats) = stats.as_mut() {
+                stats.remove_modifier(&condition.modifier_name());
+            }
+            events.send(SurvivalEvent::ConditionEnded { entity, condition });
+        }
+        for condition in started {
+            if let Some(stats) = stats.as_mut() {
+                for debuff in settings.debuffs.iter().filter(|debuff| debuff.condition == condition) {
+                    stats.add_modifier(debuff.to_modifier());
+                }
+            }
+            events.send(SurvivalEvent::ConditionStarted { entity, condition });
+        }
+        needs.conditions = conditions;
+
+        let critical = needs.conditions.iter().filter(|condition| condition.is_critical()).count();
+        if critical == 0 || game_time.paused {
+            needs.damage_timer = 0.0;
+            continue;
+        }
+        // Damage ticks once a second rather than every frame
+        needs.damage_timer += time.delta_secs();
+        if needs.damage_timer >= 1.0 {
+            needs.damage_timer -= 1.0;
+            damage_queue.send(DamageEvent {
+                amount: settings.damage_per_second * critical as f32,
+                damage_type: DamageType::Environmental,
+                source: None,
+                target: entity,
+                position: None,
+                direction: None,
+                ignore_shield: true,
+            });
+        }
+    }
+}
@@ -0,0 +1,372 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::stats::{DerivedStat, StatModifier};
+use crate::utils::EventQueue;
+
+/// Normal body temperature, in °C
+pub const NORMAL_BODY_TEMPERATURE: f32 = 37.0;
+
+/// Hunger, thirst and body temperature of a character.
+///
+/// Hunger and thirst count down from their maximum (full = fed); body
+/// temperature drifts toward the surroundings outside the comfort range.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct SurvivalNeeds {
+    pub hunger: f32,
+    pub max_hunger: f32,
+    pub thirst: f32,
+    pub max_thirst: f32,
+    /// Body temperature, in °C
+    pub body_temperature: f32,
+    /// Hunger lost per minute of game time
+    pub hunger_per_minute: f32,
+    /// Thirst lost per minute of game time
+    pub thirst_per_minute: f32,
+    /// Degrees of cold that clothing keeps out; lowers the comfort range
+    pub insulation: f32,
+    /// Temperature around the character, updated from the temperature zones
+    pub ambient_temperature: f32,
+    /// Conditions currently applied (debuffs and damage)
+    pub conditions: Vec<SurvivalCondition>,
+    /// Time accumulated toward the next damage tick
+    pub damage_timer: f32,
+}
+
+impl Default for SurvivalNeeds {
+    fn default() -> Self {
+        Self {
+            hunger: 100.0,
+            max_hunger: 100.0,
+            thirst: 100.0,
+            max_thirst: 100.0,
+            body_temperature: NORMAL_BODY_TEMPERATURE,
+            hunger_per_minute: 2.5,
+            thirst_per_minute: 4.0,
+            insulation: 0.0,
+            ambient_temperature: 18.0,
+            conditions: Vec::new(),
+            damage_timer: 0.0,
+        }
+    }
+}
+
+impl SurvivalNeeds {
+    pub fn hunger_ratio(&self) -> f32 {
+        if self.max_hunger > 0.0 { (self.hunger / self.max_hunger).clamp(0.0, 1.0) } else { 0.0 }
+    }
+
+    pub fn thirst_ratio(&self) -> f32 {
+        if self.max_thirst > 0.0 { (self.thirst / self.max_thirst).clamp(0.0, 1.0) } else { 0.0 }
+    }
+
+    /// Applies `quantity` servings of a consumable
+    pub fn restore(&mut self, restore: &SurvivalRestore, quantity: i32) {
+        let quantity = quantity.max(1) as f32;
+        self.hunger = (self.hunger + restore.hunger * quantity).clamp(0.0, self.max_hunger);
+        self.thirst = (self.thirst + restore.thirst * quantity).clamp(0.0, self.max_thirst);
+        self.body_temperature += restore.warmth * quantity;
+    }
+
+    /// Conditions the current values call for, given the thresholds in `settings`
+    pub fn evaluate_conditions(&self, settings: &SurvivalSettings) -> Vec<SurvivalCondition> {
+        let mut conditions = Vec::new();
+
+        let hunger = self.hunger_ratio();
+        if hunger <= settings.critical_threshold {
+            conditions.push(SurvivalCondition::Starving);
+        } else if hunger <= settings.low_threshold {
+            conditions.push(SurvivalCondition::Hungry);
+        }
+
+        let thirst = self.thirst_ratio();
+        if thirst <= settings.critical_threshold {
+            conditions.push(SurvivalCondition::Dehydrated);
+        } else if thirst <= settings.low_threshold {
+            conditions.push(SurvivalCondition::Thirsty);
+        }
+
+        if self.body_temperature <= settings.freezing_temperature {
+            conditions.push(SurvivalCondition::Freezing);
+        } else if self.body_temperature <= settings.cold_temperature {
+            conditions.push(SurvivalCondition::Cold);
+        } else if self.body_temperature >= settings.overheated_temperature {
+            conditions.push(SurvivalCondition::Overheated);
+        }
+
+        conditions
+    }
+
+    pub fn has_condition(&self, condition: SurvivalCondition) -> bool {
+        self.conditions.contains(&condition)
+    }
+}
+
+/// Threshold state of a character's needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum SurvivalCondition {
+    Hungry,
+    Starving,
+    Thirsty,
+    Dehydrated,
+    Cold,
+    Freezing,
+    Overheated,
+}
+
+impl SurvivalCondition {
+    /// Whether the condition hurts the character over time
+    pub fn is_critical(&self) -> bool {
+        matches!(self, Self::Starving | Self::Dehydrated | Self::Freezing)
+    }
+
+    /// Name of the stat modifiers applied for this condition
+    pub fn modifier_name(&self) -> String {
+        format!("survival_{:?}", self).to_lowercase()
+    }
+}
+
+/// Stat debuff applied while a condition lasts
+#[derive(Debug, Clone, Reflect)]
+pub struct SurvivalDebuff {
+    pub condition: SurvivalCondition,
+    pub stat: DerivedStat,
+    /// Percentage taken off the stat
+    pub percentage: f32,
+}
+
+impl SurvivalDebuff {
+    pub fn new(condition: SurvivalCondition, stat: DerivedStat, percentage: f32) -> Self {
+        Self { condition, stat, percentage }
+    }
+
+    /// Permanent modifier, removed when the condition ends
+    pub fn to_modifier(&self) -> StatModifier {
+        StatModifier::percentage_debuff(&self.condition.modifier_name(), self.stat, -self.percentage.abs(), 0.0)
+    }
+}
+
+/// What a consumable gives back per serving
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect)]
+pub struct SurvivalRestore {
+    pub hunger: f32,
+    pub thirst: f32,
+    /// Degrees of body temperature (negative cools down)
+    pub warmth: f32,
+}
+
+impl SurvivalRestore {
+    pub fn food(hunger: f32) -> Self {
+        Self { hunger, ..default() }
+    }
+
+    pub fn drink(thirst: f32) -> Self {
+        Self { thirst, ..default() }
+    }
+
+    pub fn with_warmth(mut self, warmth: f32) -> Self {
+        self.warmth = warmth;
+        self
+    }
+}
+
+/// Inventory items that feed survival needs when used, by item id.
+///
+/// Applied on `InventoryObjectUsedEvent`, so food and drinks go through the
+/// regular use-inventory-object flow (context menu, quick access slots...).
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct SurvivalConsumables {
+    pub items: HashMap<String, SurvivalRestore>,
+}
+
+impl Default for SurvivalConsumables {
+    fn default() -> Self {
+        let mut items = HashMap::new();
+        items.insert("apple".to_string(), SurvivalRestore { hunger: 12.0, thirst: 4.0, warmth: 0.0 });
+        items.insert("bread".to_string(), SurvivalRestore::food(25.0));
+        items.insert("cooked_meat".to_string(), SurvivalRestore::food(40.0));
+        items.insert("water_bottle".to_string(), SurvivalRestore::drink(40.0));
+        items.insert("hot_soup".to_string(), SurvivalRestore { hunger: 30.0, thirst: 15.0, warmth: 1.5 });
+        items.insert("hot_tea".to_string(), SurvivalRestore::drink(20.0).with_warmth(1.0));
+        items.insert("iced_water".to_string(), SurvivalRestore::drink(30.0).with_warmth(-1.0));
+        Self { items }
+    }
+}
+
+impl SurvivalConsumables {
+    pub fn get(&self, item_id: &str) -> Option<&SurvivalRestore> {
+        self.items.get(item_id)
+    }
+
+    pub fn register(&mut self, item_id: &str, restore: SurvivalRestore) {
+        self.items.insert(item_id.to_string(), restore);
+    }
+}
+
+/// Shape of a temperature zone, around its transform
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum TemperatureZoneShape {
+    Sphere { radius: f32 },
+    Box { half_extents: Vec3 },
+}
+
+/// Area that warms or chills the characters inside it (campfires, snow fields...)
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct TemperatureZone {
+    pub shape: TemperatureZoneShape,
+    /// Degrees added to the ambient temperature
+    pub offset: f32,
+    /// Fade the offset out toward the edge of a sphere
+    pub falloff: bool,
+    pub enabled: bool,
+}
+
+impl Default for TemperatureZone {
+    fn default() -> Self {
+        Self {
+            shape: TemperatureZoneShape::Sphere { radius: 5.0 },
+            offset: 20.0,
+            falloff: true,
+            enabled: true,
+        }
+    }
+}
+
+impl TemperatureZone {
+    /// Warm sphere fading out from the fire
+    pub fn campfire(radius: f32) -> Self {
+        Self { shape: TemperatureZoneShape::Sphere { radius }, offset: 30.0, falloff: true, enabled: true }
+    }
+
+    /// Cold box with the same chill throughout
+    pub fn snow_area(half_extents: Vec3) -> Self {
+        Self { shape: TemperatureZoneShape::Box { half_extents }, offset: -25.0, falloff: false, enabled: true }
+    }
+
+    /// Degrees this zone adds at `point` (0 outside)
+    pub fn offset_at(&self, transform: &GlobalTransform, point: Vec3) -> f32 {
+        if !self.enabled {
+            return 0.0;
+        }
+        match self.shape {
+            TemperatureZoneShape::Sphere { radius } => {
+                let distance = transform.translation().distance(point);
+                if distance > radius {
+                    0.0
+                } else if self.falloff && radius > 0.0 {
+                    self.offset * (1.0 - distance / radius)
+                } else {
+                    self.offset
+                }
+            }
+            TemperatureZoneShape::Box { half_extents } => {
+                let local = transform.affine().inverse().transform_point3(point);
+                if local.abs().cmple(half_extents).all() { self.offset } else { 0.0 }
+            }
+        }
+    }
+}
+
+/// Survival tuning
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct SurvivalSettings {
+    pub enabled: bool,
+    /// Game minutes per real second
+    pub time_scale: f32,
+    /// Temperature outside any zone, in °C
+    pub ambient_temperature: f32,
+    /// Ambient range in which the body returns to normal temperature
+    pub comfort_range: (f32, f32),
+    /// Body degrees gained or lost per second, per degree outside the comfort range
+    pub temperature_change_rate: f32,
+    /// Body degrees per second back toward normal inside the comfort range
+    pub temperature_recovery_rate: f32,
+    /// Hunger/thirst ratio at which the Hungry/Thirsty debuffs start
+    pub low_threshold: f32,
+    /// Hunger/thirst ratio at which starving/dehydration damage starts
+    pub critical_threshold: f32,
+    pub cold_temperature: f32,
+    pub freezing_temperature: f32,
+    pub overheated_temperature: f32,
+    /// Thirst drains this much faster while overheated
+    pub overheated_thirst_multiplier: f32,
+    /// Damage per second for each critical condition
+    pub damage_per_second: f32,
+    pub debuffs: Vec<SurvivalDebuff>,
+}
+
+impl Default for SurvivalSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            time_scale: 1.0 / 60.0,
+            ambient_temperature: 18.0,
+            comfort_range: (10.0, 30.0),
+            temperature_change_rate: 0.004,
+            temperature_recovery_rate: 0.05,
+            low_threshold: 0.3,
+            critical_threshold: 0.05,
+            cold_temperature: 35.5,
+            freezing_temperature: 34.0,
+            overheated_temperature: 38.5,
+            overheated_thirst_multiplier: 2.0,
+            damage_per_second: 2.0,
+            debuffs: vec![
+                SurvivalDebuff::new(SurvivalCondition::Hungry, DerivedStat::MaxStamina, 15.0),
+                SurvivalDebuff::new(SurvivalCondition::Starving, DerivedStat::MaxStamina, 40.0),
+                SurvivalDebuff::new(SurvivalCondition::Starving, DerivedStat::AttackPower, 25.0),
+                SurvivalDebuff::new(SurvivalCondition::Thirsty, DerivedStat::AttackSpeed, 10.0),
+                SurvivalDebuff::new(SurvivalCondition::Dehydrated, DerivedStat::MovementSpeed, 25.0),
+                SurvivalDebuff::new(SurvivalCondition::Cold, DerivedStat::MovementSpeed, 10.0),
+                SurvivalDebuff::new(SurvivalCondition::Freezing, DerivedStat::MovementSpeed, 30.0),
+                SurvivalDebuff::new(SurvivalCondition::Freezing, DerivedStat::AttackSpeed, 20.0),
+                SurvivalDebuff::new(SurvivalCondition::Overheated, DerivedStat::MaxStamina, 20.0),
+            ],
+        }
+    }
+}
+
+/// Survival notifications
+#[derive(Debug, Clone, PartialEq)]
+pub enum SurvivalEvent {
+    ConditionStarted { entity: Entity, condition: SurvivalCondition },
+    ConditionEnded { entity: Entity, condition: SurvivalCondition },
+    Consumed { entity: Entity, item_id: String, restore: SurvivalRestore },
+}
+
+/// Queue for survival events
+pub type SurvivalEventQueue = EventQueue<SurvivalEvent>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_survival_conditions_and_zones() {
+        let settings = SurvivalSettings::default();
+        let mut needs = SurvivalNeeds { hunger: 20.0, thirst: 2.0, body_temperature: 35.0, ..Default::default() };
+        let conditions = needs.evaluate_conditions(&settings);
+        assert!(conditions.contains(&SurvivalCondition::Hungry));
+        assert!(conditions.contains(&SurvivalCondition::Dehydrated));
+        assert!(conditions.contains(&SurvivalCondition::Cold));
+
+        let soup = *SurvivalConsumables::default().get("hot_soup").unwrap();
+        needs.restore(&soup, 2);
+        assert_eq!(needs.hunger, 80.0);
+        assert_eq!(needs.body_temperature, 38.0);
+        assert!(needs.evaluate_conditions(&settings).is_empty());
+
+        let campfire = TemperatureZone::campfire(4.0);
+        let at_origin = GlobalTransform::default();
+        assert_eq!(campfire.offset_at(&at_origin, Vec3::ZERO), 30.0);
+        assert_eq!(campfire.offset_at(&at_origin, Vec3::new(2.0, 0.0, 0.0)), 15.0);
+        assert_eq!(campfire.offset_at(&at_origin, Vec3::new(5.0, 0.0, 0.0)), 0.0);
+        let snow = TemperatureZone::snow_area(Vec3::splat(10.0));
+        assert_eq!(snow.offset_at(&GlobalTransform::from_translation(Vec3::X * 20.0), Vec3::X * 25.0), -25.0);
+        assert_eq!(snow.offset_at(&GlobalTransform::from_translation(Vec3::X * 20.0), Vec3::ZERO), 0.0);
+    }
+}
@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+use crate::character::Player;
+use super::types::*;
+
+/// Survival gauges (hunger, thirst, body temperature)
+#[derive(Component)]
+pub struct SurvivalHudRoot;
+
+/// Which need a gauge shows
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SurvivalGauge {
+    Hunger,
+    Thirst,
+    Temperature,
+}
+
+/// Fill of a survival gauge
+#[derive(Component)]
+pub struct SurvivalGaugeFill(pub SurvivalGauge);
+
+/// System to setup the survival gauges (hidden until a player has needs)
+pub fn setup_survival_hud(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(20.0),
+                bottom: Val::Px(90.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            SurvivalHudRoot,
+            Visibility::Hidden,
+        ))
+        .with_children(|root| {
+            for (gauge, label) in [
+                (SurvivalGauge::Hunger, "Food"),
+                (SurvivalGauge::Thirst, "Water"),
+                (SurvivalGauge::Temperature, "Temp"),
+            ] {
+                root.spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(6.0),
+                    ..default()
+                })
+                .with_children(|row| {
+                    row.spawn((
+                        Node { width: Val::Px(44.0), ..default() },
+                        Text::new(label),
+                        TextFont { font_size: 12.0, ..default() },
+                        TextColor(Color::WHITE),
+                    ));
+                    row.spawn((
+                        Node {
+                            width: Val::Px(120.0),
+                            height: Val::Px(8.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.1, 0.1, 0.12, 0.85)),
+                    ))
+                    .with_children(|bar| {
+                        bar.spawn((
+                            Node {
+                                width: Val::Percent(100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::WHITE),
+                            SurvivalGaugeFill(gauge),
+                        ));
+                    });
+                });
+            }
+        });
+}
+
+/// Fill and color of a gauge for `needs`
+fn gauge_fill(gauge: SurvivalGauge, needs: &SurvivalNeeds, settings: &SurvivalSettings) -> (f32, Color) {
+    match gauge {
+        SurvivalGauge::Hunger | SurvivalGauge::Thirst => {
+            let ratio = if gauge == SurvivalGauge::Hunger { needs.hunger_ratio() } else { needs.thirst_ratio() };
+            let color = if ratio <= settings.critical_threshold {
+                Color::srgb(0.9, 0.15, 0.15)
+            } else if ratio <= settings.low_threshold {
+                Color::srgb(0.95, 0.6, 0.15)
+            } else if gauge == SurvivalGauge::Hunger {
+                Color::srgb(0.75, 0.6, 0.3)
+            } else {
+                Color::srgb(0.3, 0.6, 0.95)
+            };
+            (ratio, color)
+        }
+        SurvivalGauge::Temperature => {
+            // Freezing on the left, overheated on the right, normal in the middle
+            let low = settings.freezing_temperature - 1.0;
+            let high = 2.0 * NORMAL_BODY_TEMPERATURE - low;
+            let ratio = ((needs.body_temperature - low) / (high - low)).clamp(0.0, 1.0);
+            let color = if needs.body_temperature <= settings.cold_temperature {
+                Color::srgb(0.4, 0.7, 1.0)
+            } else if needs.body_temperature >= settings.overheated_temperature {
+                Color::srgb(1.0, 0.4, 0.2)
+            } else {
+                Color::srgb(0.4, 0.85, 0.4)
+            };
+            (ratio, color)
+        }
+    }
+}
+
+/// System to show the local player's needs
+pub fn update_survival_hud(
+    settings: Res<SurvivalSettings>,
+    needs_query: Query<&SurvivalNeeds, With<Player>>,
+    mut root_query: Query<&mut Visibility, With<SurvivalHudRoot>>,
+    mut fill_query: Query<(&SurvivalGaugeFill, &mut Node, &mut BackgroundColor)>,
+) {
+    let Ok(mut visibility) = root_query.single_mut() else { return };
+    let Some(needs) = needs_query.iter().next().filter(|_| settings.enabled) else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    visibility.set_if_neq(Visibility::Visible);
+
+    for (fill, mut node, mut background) in fill_query.iter_mut() {
+        let (ratio, color) = gauge_fill(fill.0, needs, &settings);
+        node.width = Val::Percent(ratio * 100.0);
+        background.0 = color;
+    }
+}
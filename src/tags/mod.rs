@@ -0,0 +1,25 @@
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+
+pub use types::{GameTag, TagIndex, AutoTagSettings};
+
+pub struct TagsPlugin;
+
+impl Plugin for TagsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<TagIndex>()
+            .init_resource::<AutoTagSettings>()
+            .register_type::<GameTag>()
+            .register_type::<AutoTagSettings>()
+            // Index before gameplay so Update systems see this frame's tags
+            .add_systems(PreUpdate, (
+                auto_tag_players,
+                update_tag_index,
+            ).chain());
+    }
+}
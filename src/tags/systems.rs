@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use crate::character::Player;
+use crate::others::TagLayerSystem;
+use super::types::*;
+
+/// System to tag players so tag filters (triggers, quests...) find them without setup
+pub fn auto_tag_players(
+    mut commands: Commands,
+    settings: Res<AutoTagSettings>,
+    mut query: Query<(Entity, Option<&mut GameTag>), Added<Player>>,
+) {
+    let Some(player_tag) = settings.player_tag.as_ref() else {
+        return;
+    };
+
+    for (entity, game_tag) in query.iter_mut() {
+        match game_tag {
+            Some(mut game_tag) => {
+                if !game_tag.has(player_tag) {
+                    game_tag.add(player_tag.clone());
+                }
+            }
+            None => {
+                commands.entity(entity).insert(GameTag::single(player_tag.clone()));
+            }
+        }
+    }
+}
+
+type ChangedTags = Or<(Changed<GameTag>, Changed<TagLayerSystem>)>;
+
+/// System to keep the tag index in sync with `GameTag` and `TagLayerSystem`
+pub fn update_tag_index(
+    mut index: ResMut<TagIndex>,
+    changed_tags: Query<Entity, ChangedTags>,
+    sources: Query<(Option<&GameTag>, Option<&TagLayerSystem>)>,
+    mut removed_game_tags: RemovedComponents<GameTag>,
+    mut removed_layer_tags: RemovedComponents<TagLayerSystem>,
+) {
+    let removed: Vec<Entity> = removed_game_tags.read().chain(removed_layer_tags.read()).collect();
+
+    for entity in changed_tags.iter().chain(removed) {
+        let Ok((game_tag, layer_tags)) = sources.get(entity) else {
+            // Despawned
+            index.remove_entity(entity);
+            continue;
+        };
+
+        let mut tags: Vec<String> = Vec::new();
+        let all_tags = game_tag
+            .into_iter()
+            .flat_map(|game_tag| game_tag.tags.iter())
+            .chain(layer_tags.into_iter().flat_map(|layer_tags| layer_tags.tags.iter()));
+        for tag in all_tags {
+            if !tag.is_empty() && !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+        index.set_tags(entity, tags);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tags::TagsPlugin;
+
+    #[test]
+    fn test_tag_index_follows_components() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TagsPlugin));
+
+        let player = app.world_mut().spawn(Player).id();
+        let boss = app.world_mut().spawn((
+            GameTag::new(["Enemy", "Flammable"]),
+            TagLayerSystem { tags: vec!["Enemy".into(), "Boss".into()], ..default() },
+        )).id();
+        app.update();
+
+        let index = app.world().resource::<TagIndex>();
+        assert_eq!(index.first("Player"), Some(player));
+        assert_eq!(index.tags_of(boss), ["Enemy", "Flammable", "Boss"]);
+        assert_eq!(index.entities_with_all(&["Enemy".into(), "Boss".into()]), vec![boss]);
+
+        // Editing, removing and despawning all reach the index
+        app.world_mut().get_mut::<GameTag>(boss).unwrap().remove("Flammable");
+        app.update();
+        assert!(!app.world().resource::<TagIndex>().has_tag(boss, "Flammable"));
+
+        app.world_mut().entity_mut(boss).remove::<TagLayerSystem>();
+        app.update();
+        assert_eq!(app.world().resource::<TagIndex>().tags_of(boss), ["Enemy"]);
+
+        app.world_mut().despawn(boss);
+        app.update();
+        assert_eq!(app.world().resource::<TagIndex>().count("Enemy"), 0);
+    }
+}
@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+use bevy::platform::collections::{HashMap, HashSet};
+
+/// Gameplay tags of an entity ("Player", "Enemy", "Door_A"...)
+#[derive(Component, Debug, Default, Clone, Reflect)]
+#[reflect(Component)]
+pub struct GameTag {
+    pub tags: Vec<String>,
+}
+
+impl GameTag {
+    pub fn new(tags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let mut game_tag = Self::default();
+        for tag in tags {
+            game_tag.add(tag);
+        }
+        game_tag
+    }
+
+    pub fn single(tag: impl Into<String>) -> Self {
+        Self { tags: vec![tag.into()] }
+    }
+
+    pub fn has(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    pub fn has_any(&self, tags: &[String]) -> bool {
+        tags.iter().any(|tag| self.has(tag))
+    }
+
+    /// Adds a tag, ignoring duplicates
+    pub fn add(&mut self, tag: impl Into<String>) {
+        let tag = tag.into();
+        if !self.has(&tag) {
+            self.tags.push(tag);
+        }
+    }
+
+    pub fn remove(&mut self, tag: &str) {
+        self.tags.retain(|t| t != tag);
+    }
+}
+
+/// Tag to entities index, rebuilt from `GameTag` and `TagLayerSystem` changes
+/// at the start of every frame
+#[derive(Resource, Debug, Default)]
+pub struct TagIndex {
+    pub by_tag: HashMap<String, HashSet<Entity>>,
+    pub by_entity: HashMap<Entity, Vec<String>>,
+}
+
+impl TagIndex {
+    /// All entities with `tag`
+    pub fn entities_with<'a>(&'a self, tag: &str) -> impl Iterator<Item = Entity> + 'a {
+        self.by_tag.get(tag).into_iter().flat_map(|entities| entities.iter().copied())
+    }
+
+    /// Any entity with `tag` (unordered)
+    pub fn first(&self, tag: &str) -> Option<Entity> {
+        self.entities_with(tag).next()
+    }
+
+    pub fn count(&self, tag: &str) -> usize {
+        self.by_tag.get(tag).map_or(0, |entities| entities.len())
+    }
+
+    pub fn has_tag(&self, entity: Entity, tag: &str) -> bool {
+        self.by_tag.get(tag).is_some_and(|entities| entities.contains(&entity))
+    }
+
+    pub fn has_any_tag(&self, entity: Entity, tags: &[String]) -> bool {
+        tags.iter().any(|tag| self.has_tag(entity, tag))
+    }
+
+    pub fn has_all_tags(&self, entity: Entity, tags: &[String]) -> bool {
+        tags.iter().all(|tag| self.has_tag(entity, tag))
+    }
+
+    /// Entities carrying every tag in `tags`
+    pub fn entities_with_all(&self, tags: &[String]) -> Vec<Entity> {
+        let Some((first, rest)) = tags.split_first() else {
+            return Vec::new();
+        };
+        self.entities_with(first)
+            .filter(|entity| rest.iter().all(|tag| self.has_tag(*entity, tag)))
+            .collect()
+    }
+
+    /// Entities carrying at least one tag in `tags`
+    pub fn entities_with_any(&self, tags: &[String]) -> Vec<Entity> {
+        let mut entities: HashSet<Entity> = HashSet::default();
+        for tag in tags {
+            entities.extend(self.entities_with(tag));
+        }
+        entities.into_iter().collect()
+    }
+
+    pub fn tags_of(&self, entity: Entity) -> &[String] {
+        self.by_entity.get(&entity).map_or(&[], |tags| tags.as_slice())
+    }
+
+    /// Replaces the indexed tags of `entity`
+    pub fn set_tags(&mut self, entity: Entity, tags: Vec<String>) {
+        self.remove_entity(entity);
+        if tags.is_empty() {
+            return;
+        }
+        for tag in tags.iter() {
+            self.by_tag.entry(tag.clone()).or_default().insert(entity);
+        }
+        self.by_entity.insert(entity, tags);
+    }
+
+    pub fn remove_entity(&mut self, entity: Entity) {
+        let Some(tags) = self.by_entity.remove(&entity) else {
+            return;
+        };
+        for tag in tags {
+            if let Some(entities) = self.by_tag.get_mut(&tag) {
+                entities.remove(&entity);
+                if entities.is_empty() {
+                    self.by_tag.remove(&tag);
+                }
+            }
+        }
+    }
+}
+
+/// Tags automatically given to entities with well-known components
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct AutoTagSettings {
+    pub player_tag: Option<String>,
+}
+
+impl Default for AutoTagSettings {
+    fn default() -> Self {
+        Self {
+            player_tag: Some("Player".to_string()),
+        }
+    }
+}
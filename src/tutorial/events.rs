@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use crate::utils::EventQueue;
 
 /// Events for controlling the tutorial system.
 #[derive(Event, Debug, Clone, Serialize, Deserialize, Reflect)]
@@ -10,6 +11,5 @@ pub enum TutorialEvent {
     Close,
 }
 
-/// Custom queue for tutorial events (Workaround for Bevy 0.18 EventReader issues)
-#[derive(Resource, Default)]
-pub struct TutorialEventQueue(pub Vec<TutorialEvent>);
+/// Queue for tutorial events
+pub type TutorialEventQueue = EventQueue<TutorialEvent>;
@@ -10,6 +10,7 @@ use components::*;
 use events::*;
 use resources::*;
 use systems::*;
+use crate::utils::EventQueuePlugin;
 
 pub use types::{Tutorial, TutorialPanel};
 pub use components::{TutorialLog, TutorialRoot, TutorialTitleText, TutorialDescriptionText, TutorialPanelImage, TutorialButton};
@@ -22,11 +23,11 @@ pub struct TutorialPlugin;
 impl Plugin for TutorialPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<TutorialManager>()
-            .init_resource::<TutorialEventQueue>()
+            .add_plugins(EventQueuePlugin::<TutorialEvent>::default())
             .register_type::<TutorialLog>()
             .add_systems(Update, (
                 handle_tutorial_events,
-                update_tutorial_ui,
+                update_tutorial_ui.in_set(crate::headless::PresentationSet),
                 handle_tutorial_buttons,
                 manage_tutorial_game_state,
             ));
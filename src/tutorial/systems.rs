@@ -1,16 +1,19 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use super::components::*;
 use super::events::{TutorialEvent, TutorialEventQueue};
 use super::resources::TutorialManager;
 use super::types::TutorialPanel;
+use crate::utils::QueueReader;
+use crate::input::{FocusCancel, FocusScope, GlyphProvider, InputMap, UiCancelButton};
 
 /// System to handle tutorial-related events.
 pub fn handle_tutorial_events(
-    mut events: ResMut<TutorialEventQueue>,
+    mut events: QueueReader<TutorialEvent>,
     mut manager: ResMut<TutorialManager>,
     mut tutorials_log: Query<&mut TutorialLog>,
 ) {
-    let events_to_process: Vec<TutorialEvent> = events.0.drain(..).collect();
+    let events_to_process: Vec<TutorialEvent> = events.read().cloned().collect();
     for event in events_to_process {
         match event {
             TutorialEvent::Open(id) => {
@@ -66,29 +69,43 @@ pub fn handle_tutorial_events(
     }
 }
 
+/// Text and image widgets of the tutorial panel
+#[derive(SystemParam)]
+pub struct TutorialPanelWidgets<'w, 's> {
+    pub title_query: Query<'w, 's, &'static mut Text, (With<TutorialTitleText>, Without<TutorialDescriptionText>)>,
+    pub desc_query: Query<'w, 's, &'static mut Text, (With<TutorialDescriptionText>, Without<TutorialTitleText>)>,
+    pub image_query: Query<'w, 's, &'static mut ImageNode, With<TutorialPanelImage>>,
+}
+
 /// System to update the tutorial UI based on the manager's state.
 pub fn update_tutorial_ui(
     mut commands: Commands,
     manager: Res<TutorialManager>,
     asset_server: Res<AssetServer>,
+    glyphs: Res<GlyphProvider>,
+    input_map: Res<InputMap>,
     root_query: Query<Entity, With<TutorialRoot>>,
-    mut title_query: Query<&mut Text, (With<TutorialTitleText>, Without<TutorialDescriptionText>)>,
-    mut desc_query: Query<&mut Text, (With<TutorialDescriptionText>, Without<TutorialTitleText>)>,
-    mut image_query: Query<&mut ImageNode, With<TutorialPanelImage>>,
+    widgets: TutorialPanelWidgets,
 ) {
+    let TutorialPanelWidgets { mut title_query, mut desc_query, mut image_query } = widgets;
     if let Some(tutorial_id) = manager.active_tutorial_id {
         if let Some(tutorial) = manager.tutorials.get(&tutorial_id) {
             if let Some(panel) = tutorial.panels.get(manager.current_panel_index) {
+                // `{Action}` tokens show the active device's buttons
+                let description = glyphs.format_prompt(&panel.description, &input_map);
+
                 // If UI doesn't exist, create it
                 if root_query.is_empty() {
-                    setup_tutorial_ui(&mut commands, &asset_server, panel);
+                    setup_tutorial_ui(&mut commands, &asset_server, panel, &description);
                 } else {
                     // Update existing UI
                     for mut text in title_query.iter_mut() {
                         text.0 = panel.title.clone();
                     }
                     for mut text in desc_query.iter_mut() {
-                        text.0 = panel.description.clone();
+                        if text.0 != description {
+                            text.0 = description.clone();
+                        }
                     }
                     if let Some(image_path) = &panel.image_path {
                         for mut ui_image in image_query.iter_mut() {
@@ -106,7 +123,7 @@ pub fn update_tutorial_ui(
     }
 }
 
-fn setup_tutorial_ui(commands: &mut Commands, _asset_server: &Res<AssetServer>, panel: &TutorialPanel) {
+fn setup_tutorial_ui(commands: &mut Commands, _asset_server: &Res<AssetServer>, panel: &TutorialPanel, description: &str) {
     commands
         .spawn((
             Node {
@@ -119,6 +136,7 @@ fn setup_tutorial_ui(commands: &mut Commands, _asset_server: &Res<AssetServer>,
             },
             BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
             TutorialRoot,
+            FocusScope { priority: 30, cancel: FocusCancel::None },
         ))
         .with_children(|parent| {
             parent
@@ -159,7 +177,7 @@ fn setup_tutorial_ui(commands: &mut Commands, _asset_server: &Res<AssetServer>,
 
                     // Description
                     parent.spawn((
-                        Text::new(&panel.description),
+                        Text::new(description),
                         TextFont {
                             font_size: 20.0,
                             ..default()
@@ -185,7 +203,8 @@ fn setup_tutorial_ui(commands: &mut Commands, _asset_server: &Res<AssetServer>,
 }
 
 fn spawn_button(parent: &mut ChildSpawnerCommands, label: &str, event: TutorialEvent) {
-    parent
+    let is_close = matches!(event, TutorialEvent::Close);
+    let mut button = parent
         .spawn((
             Button,
             Node {
@@ -197,7 +216,11 @@ fn spawn_button(parent: &mut ChildSpawnerCommands, label: &str, event: TutorialE
             },
             BackgroundColor(Color::srgb(0.2, 0.2, 0.2)),
             TutorialButton(event),
-        ))
+        ));
+    if is_close {
+        button.insert(UiCancelButton);
+    }
+    button
         .with_children(|parent| {
             parent.spawn((
                 Text::new(label),
@@ -221,7 +244,7 @@ pub fn handle_tutorial_buttons(
     for (interaction, button_data, mut color) in interaction_query.iter_mut() {
         match *interaction {
             Interaction::Pressed => {
-                events.0.push(button_data.0.clone());
+                events.send(button_data.0.clone());
                 *color = BackgroundColor(Color::srgb(0.4, 0.4, 0.4));
             }
             Interaction::Hovered => {
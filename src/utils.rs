@@ -11,6 +11,57 @@ pub struct GameTime {
     pub paused: bool,
 }
 
+/// Clock of the game world, for systems that follow the time of day (vendor hours...)
+///
+/// Stays put unless `hours_per_second` is set; a day/night cycle can write
+/// `hour` directly instead.
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct TimeOfDay {
+    /// Hour of the day (0-24)
+    pub hour: f32,
+    pub day: u32,
+    /// In-game hours per real second (0 = driven by the game)
+    pub hours_per_second: f32,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self {
+            hour: 12.0,
+            day: 0,
+            hours_per_second: 0.0,
+        }
+    }
+}
+
+impl TimeOfDay {
+    pub fn advance(&mut self, hours: f32) {
+        self.hour += hours;
+        while self.hour >= 24.0 {
+            self.hour -= 24.0;
+            self.day += 1;
+        }
+    }
+
+    /// Whether the clock is in `[from, to)`, wrapping past midnight when `from > to`
+    pub fn is_between(&self, from: f32, to: f32) -> bool {
+        if from <= to {
+            self.hour >= from && self.hour < to
+        } else {
+            self.hour >= from || self.hour < to
+        }
+    }
+}
+
+/// System to run the world clock
+pub fn advance_time_of_day(time: Res<Time>, game_time: Res<GameTime>, mut time_of_day: ResMut<TimeOfDay>) {
+    if time_of_day.hours_per_second > 0.0 && !game_time.paused {
+        let hours = time_of_day.hours_per_second * time.delta_secs();
+        time_of_day.advance(hours);
+    }
+}
+
 /// Math utilities
 pub mod math {
     use bevy::prelude::*;
@@ -52,3 +103,29 @@ pub mod layers {
     pub const ENVIRONMENT: u32 = 1 << 2;
     pub const INTERACTABLE: u32 = 1 << 3;
 }
+
+/// Entity pooling
+pub mod pool;
+pub use pool::{EntityPool, Poolable, PoolPlugin, PoolStatistics, PoolStats, PooledInactive};
+pub mod queue;
+pub use queue::{
+    EventQueue, EventQueueMessagesPlugin, EventQueuePlugin, QueueCursor, QueueReader, QueuedMessage,
+    forward_event_queue_messages, rotate_event_queue,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_time_of_day_wraps() {
+        let mut clock = TimeOfDay { hour: 23.0, ..Default::default() };
+        assert!(clock.is_between(20.0, 4.0));
+        assert!(!clock.is_between(8.0, 20.0));
+        clock.advance(2.0);
+        assert_eq!(clock.day, 1);
+        assert!((clock.hour - 1.0).abs() < 1e-5);
+        assert!(clock.is_between(20.0, 4.0));
+    }
+}
@@ -0,0 +1,278 @@
+//! Entity pooling
+//!
+//! Typed pools that recycle short-lived entities (projectiles, tracers, decals,
+//! damage numbers...) instead of spawning and despawning them every frame.
+//!
+//! A pooled entity is "inactive" while it sits in the pool: its `T` component is
+//! removed (so gameplay systems stop seeing it), it is hidden and tagged with
+//! [`PooledInactive`]. [`EntityPool::acquire`] re-inserts the caller's bundle on a
+//! recycled entity, or spawns a new one when the pool is empty.
+
+use std::marker::PhantomData;
+use bevy::prelude::*;
+use bevy::ecs::entity::Entities;
+use bevy::platform::collections::HashMap;
+
+/// Component types that can be pooled
+pub trait Poolable: Component + Sized {
+    /// Called when an entity is returned to the pool; clear per-use state here
+    /// (detach from parents, disable physics...). `T` itself is already removed.
+    fn on_release(_entity: &mut EntityCommands) {}
+}
+
+/// Marks an entity waiting in a pool
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct PooledInactive;
+
+/// Per-pool counters
+#[derive(Debug, Clone, Default, Reflect)]
+pub struct PoolStats {
+    /// Entities spawned because the pool was empty (warm-up included)
+    pub created: u64,
+    /// Acquisitions served by a recycled entity
+    pub reused: u64,
+    pub released: u64,
+    /// Releases despawned because the pool was full
+    pub overflowed: u64,
+    /// Pooled entities despawned by something else (e.g. a parent)
+    pub lost: u64,
+    pub active: usize,
+    pub peak_active: usize,
+    pub available: usize,
+}
+
+/// Pool of entities for the `T` component
+#[derive(Resource)]
+pub struct EntityPool<T: Poolable> {
+    pub available: Vec<Entity>,
+    /// Maximum entities kept in the pool; extra releases are despawned
+    pub max_size: usize,
+    pub warm_up_count: usize,
+    /// Spawner used to warm the pool up at startup
+    pub warm_up_spawner: Option<fn(&mut Commands) -> Entity>,
+    pub stats: PoolStats,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Poolable> EntityPool<T> {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            available: Vec::new(),
+            max_size,
+            warm_up_count: 0,
+            warm_up_spawner: None,
+            stats: PoolStats::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a recycled entity with `bundle` inserted, or spawns a new one
+    pub fn acquire(&mut self, commands: &mut Commands, bundle: impl Bundle) -> Entity {
+        let entity = match self.available.pop() {
+            Some(entity) => {
+                commands
+                    .entity(entity)
+                    .remove::<PooledInactive>()
+                    .insert((Visibility::Inherited, bundle));
+                self.stats.reused += 1;
+                entity
+            }
+            None => {
+                self.stats.created += 1;
+                commands.spawn(bundle).id()
+            }
+        };
+
+        self.stats.active += 1;
+        self.stats.peak_active = self.stats.peak_active.max(self.stats.active);
+        self.stats.available = self.available.len();
+        entity
+    }
+
+    /// Returns an entity to the pool (or despawns it when the pool is full)
+    pub fn release(&mut self, commands: &mut Commands, entity: Entity) {
+        self.stats.released += 1;
+        self.stats.active = self.stats.active.saturating_sub(1);
+
+        if self.available.len() >= self.max_size || self.available.contains(&entity) {
+            self.stats.overflowed += 1;
+            commands.entity(entity).despawn();
+            return;
+        }
+
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.remove::<T>().insert((PooledInactive, Visibility::Hidden));
+        T::on_release(&mut entity_commands);
+
+        self.available.push(entity);
+        self.stats.available = self.available.len();
+    }
+
+    /// Pre-spawns inactive entities
+    pub fn warm_up(&mut self, commands: &mut Commands, count: usize, spawner: fn(&mut Commands) -> Entity) {
+        for _ in 0..count.min(self.max_size.saturating_sub(self.available.len())) {
+            let entity = spawner(commands);
+            let mut entity_commands = commands.entity(entity);
+            entity_commands.remove::<T>().insert((PooledInactive, Visibility::Hidden));
+            T::on_release(&mut entity_commands);
+
+            self.available.push(entity);
+            self.stats.created += 1;
+        }
+        self.stats.available = self.available.len();
+    }
+}
+
+/// Pool statistics for every registered pool, keyed by component name
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PoolStatistics {
+    pub pools: HashMap<String, PoolStats>,
+}
+
+/// Registers an [`EntityPool`] for `T`
+pub struct PoolPlugin<T: Poolable> {
+    pub max_size: usize,
+    pub warm_up_count: usize,
+    pub warm_up_spawner: Option<fn(&mut Commands) -> Entity>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Poolable> PoolPlugin<T> {
+    pub fn new(max_size: usize) -> Self {
+        Self {
+            max_size,
+            warm_up_count: 0,
+            warm_up_spawner: None,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn with_warm_up(mut self, count: usize, spawner: fn(&mut Commands) -> Entity) -> Self {
+        self.warm_up_count = count;
+        self.warm_up_spawner = Some(spawner);
+        self
+    }
+}
+
+impl<T: Poolable> Plugin for PoolPlugin<T> {
+    fn build(&self, app: &mut App) {
+        let mut pool = EntityPool::<T>::new(self.max_size);
+        pool.warm_up_count = self.warm_up_count;
+        pool.warm_up_spawner = self.warm_up_spawner;
+
+        app
+            .insert_resource(pool)
+            .init_resource::<PoolStatistics>()
+            .register_type::<PooledInactive>()
+            .register_type::<PoolStatistics>()
+            .add_systems(Startup, warm_up_pool::<T>)
+            .add_systems(PreUpdate, (
+                prune_pool::<T>,
+                update_pool_statistics::<T>,
+            ).chain());
+    }
+}
+
+/// System to pre-spawn pooled entities
+pub fn warm_up_pool<T: Poolable>(mut commands: Commands, mut pool: ResMut<EntityPool<T>>) {
+    let Some(spawner) = pool.warm_up_spawner else {
+        return;
+    };
+    let count = pool.warm_up_count;
+    pool.warm_up(&mut commands, count, spawner);
+}
+
+/// System to forget pooled entities that were despawned behind the pool's back
+pub fn prune_pool<T: Poolable>(entities: &Entities, mut pool: ResMut<EntityPool<T>>) {
+    let before = pool.available.len();
+    pool.available.retain(|entity| entities.contains(*entity));
+
+    let lost = before - pool.available.len();
+    if lost > 0 {
+        pool.stats.lost += lost as u64;
+        pool.stats.available = pool.available.len();
+    }
+}
+
+/// System to publish pool counters for profiling
+pub fn update_pool_statistics<T: Poolable>(
+    pool: Res<EntityPool<T>>,
+    mut statistics: ResMut<PoolStatistics>,
+) {
+    if !pool.is_changed() {
+        return;
+    }
+
+    let name = std::any::type_name::<T>().rsplit("::").next().unwrap_or_default();
+    statistics.pools.insert(name.to_string(), pool.stats.clone());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component)]
+    struct Shell;
+
+    impl Poolable for Shell {}
+
+    #[derive(Resource, Default)]
+    struct Orders {
+        acquire: usize,
+        release: Vec<Entity>,
+        acquired: Vec<Entity>,
+    }
+
+    fn spawn_shell(commands: &mut Commands) -> Entity {
+        commands.spawn(Shell).id()
+    }
+
+    fn run_orders(mut commands: Commands, mut pool: ResMut<EntityPool<Shell>>, mut orders: ResMut<Orders>) {
+        for _ in 0..std::mem::take(&mut orders.acquire) {
+            let entity = pool.acquire(&mut commands, Shell);
+            orders.acquired.push(entity);
+        }
+        for entity in std::mem::take(&mut orders.release) {
+            pool.release(&mut commands, entity);
+        }
+    }
+
+    #[test]
+    fn test_pool_recycles_and_caps_entities() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(PoolPlugin::<Shell>::new(2).with_warm_up(1, spawn_shell))
+            .init_resource::<Orders>()
+            .add_systems(Update, run_orders);
+
+        app.update();
+        let warm = app.world().resource::<EntityPool<Shell>>().available[0];
+        assert!(app.world().entity(warm).contains::<PooledInactive>());
+        assert!(!app.world().entity(warm).contains::<Shell>());
+
+        // The warmed entity is handed out first, the rest are spawned
+        app.world_mut().resource_mut::<Orders>().acquire = 3;
+        app.update();
+        let acquired = app.world().resource::<Orders>().acquired.clone();
+        assert_eq!(acquired[0], warm);
+        assert!(app.world().entity(warm).contains::<Shell>());
+        assert!(!app.world().entity(warm).contains::<PooledInactive>());
+        let stats = &app.world().resource::<EntityPool<Shell>>().stats;
+        assert_eq!((stats.created, stats.reused, stats.active), (3, 1, 3));
+
+        // Only two fit back in the pool
+        app.world_mut().resource_mut::<Orders>().release = acquired.clone();
+        app.update();
+        assert!(app.world().get_entity(acquired[2]).is_err());
+        assert_eq!(app.world().resource::<EntityPool<Shell>>().available, acquired[..2].to_vec());
+
+        // Entities despawned behind the pool's back are pruned and counted
+        app.world_mut().despawn(acquired[0]);
+        app.update();
+        let statistics = app.world().resource::<PoolStatistics>();
+        let stats = &statistics.pools["Shell"];
+        assert_eq!((stats.overflowed, stats.lost, stats.available), (1, 1, 1));
+    }
+}
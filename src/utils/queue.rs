@@ -0,0 +1,356 @@
+//! Event queues
+//!
+//! [`EventQueue<T>`] is the common buffered event channel between systems.
+//! Producers [`send`](EventQueue::send) into it and every consumer reads through
+//! its own [`QueueReader`], so several systems can react to the same event.
+//!
+//! ## Delivery guarantees
+//!
+//! - Events are double-buffered. An event sent during frame N can be read for
+//!   the rest of frame N and during all of frame N+1; the `First` schedule of
+//!   frame N+2 drops it.
+//! - Each reader sees every event exactly once and in send order, whether it
+//!   runs before or after the sender, as long as it runs at least once per frame.
+//!   Readers in `FixedUpdate` can miss events when a frame runs no fixed step,
+//!   except in fixed simulation mode, where queues only rotate after a frame
+//!   that ran one (see [`crate::simulation`]).
+//! - Reading never removes events, so readers cannot starve each other. Only a
+//!   queue with a single consumer should be [`drain`](EventQueue::drain)ed.
+//!
+//! ```rust,ignore
+//! app.add_plugins(EventQueuePlugin::<DamageEvent>::default());
+//!
+//! fn deal_damage(mut damage: ResMut<EventQueue<DamageEvent>>) {
+//!     damage.send(DamageEvent { .. });
+//! }
+//!
+//! fn show_hits(mut damage: QueueReader<DamageEvent>) {
+//!     for event in damage.read() { .. }
+//! }
+//! ```
+//!
+//! ## Bevy messages
+//!
+//! Code written against Bevy's `MessageReader` / `MessageWriter` can reach a
+//! queue through [`EventQueueMessagesPlugin`]. It mirrors the queue into
+//! `Messages<QueuedMessage<T>>` and forwards messages written there back into
+//! the queue, so readers on either side see every event once. Events cross over
+//! in `Last`, so the other side reads them in the next frame.
+//!
+//! ```rust,ignore
+//! app.add_plugins(EventQueueMessagesPlugin::<DamageEvent>::default());
+//!
+//! fn log_damage(mut damage: MessageReader<QueuedMessage<DamageEvent>>) {
+//!     for event in damage.read() {
+//!         info!("{} damage to {:?}", event.amount, event.target);
+//!     }
+//! }
+//! ```
+
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use bevy::prelude::*;
+use bevy::ecs::message::MessageCursor;
+use bevy::ecs::system::SystemParam;
+
+/// Double-buffered queue of `T` events
+#[derive(Resource)]
+pub struct EventQueue<T: Send + Sync + 'static> {
+    previous: Vec<T>,
+    current: Vec<T>,
+    /// Id of the first event in `previous`
+    start_id: usize,
+}
+
+impl<T: Send + Sync + 'static> Default for EventQueue<T> {
+    fn default() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+            start_id: 0,
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> EventQueue<T> {
+    pub fn send(&mut self, event: T) {
+        self.current.push(event);
+    }
+
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = T>) {
+        self.current.extend(events);
+    }
+
+    /// Buffered events from this frame and the previous one
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Every buffered event, oldest first, without marking anything as read
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.previous.iter().chain(self.current.iter())
+    }
+
+    /// Removes all buffered events. Readers that had not seen them miss them.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.start_id += self.len();
+        self.previous.drain(..).chain(self.current.drain(..))
+    }
+
+    /// Frame boundary: this frame's events become the previous frame's, older ones are dropped
+    pub fn rotate(&mut self) {
+        self.start_id += self.previous.len();
+        std::mem::swap(&mut self.previous, &mut self.current);
+        self.current.clear();
+    }
+
+    fn end_id(&self) -> usize {
+        self.start_id + self.len()
+    }
+}
+
+/// Read position of one consumer in an [`EventQueue`]
+pub struct QueueCursor<T> {
+    next_id: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for QueueCursor<T> {
+    fn default() -> Self {
+        Self {
+            next_id: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> QueueCursor<T> {
+    /// Events not yet seen through this cursor, oldest first
+    pub fn read<'a>(&mut self, queue: &'a EventQueue<T>) -> impl Iterator<Item = &'a T> {
+        let skip = self.next_id.saturating_sub(queue.start_id).min(queue.len());
+        self.next_id = queue.end_id();
+        queue.iter().skip(skip)
+    }
+
+    pub fn unread(&self, queue: &EventQueue<T>) -> usize {
+        queue.end_id() - self.next_id.max(queue.start_id).min(queue.end_id())
+    }
+
+    /// Marks every buffered event as read
+    pub fn clear(&mut self, queue: &EventQueue<T>) {
+        self.next_id = queue.end_id();
+    }
+}
+
+/// Per-system reader of an [`EventQueue`]
+#[derive(SystemParam)]
+pub struct QueueReader<'w, 's, T: Send + Sync + 'static> {
+    queue: Res<'w, EventQueue<T>>,
+    cursor: Local<'s, QueueCursor<T>>,
+}
+
+impl<'w, 's, T: Send + Sync + 'static> QueueReader<'w, 's, T> {
+    /// Events this system has not read yet, oldest first
+    pub fn read(&mut self) -> impl Iterator<Item = &T> {
+        self.cursor.read(&self.queue)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cursor.unread(&self.queue)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Skips every unread event
+    pub fn clear(&mut self) {
+        self.cursor.clear(&self.queue);
+    }
+}
+
+/// Registers an [`EventQueue`] for `T` and rotates it at the start of every frame
+/// (in fixed simulation mode, of every frame following a fixed step).
+/// Adding it more than once for the same `T` is harmless.
+pub struct EventQueuePlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for EventQueuePlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Send + Sync + 'static> Plugin for EventQueuePlugin<T> {
+    fn build(&self, app: &mut App) {
+        if app.world().contains_resource::<EventQueue<T>>() {
+            return;
+        }
+
+        app
+            .init_resource::<EventQueue<T>>()
+            .add_systems(First, rotate_event_queue::<T>.run_if(crate::simulation::simulation_frame_advanced));
+    }
+
+    fn is_unique(&self) -> bool {
+        false
+    }
+}
+
+/// System to advance an [`EventQueue`] to the next frame
+pub fn rotate_event_queue<T: Send + Sync + 'static>(mut queue: ResMut<EventQueue<T>>) {
+    queue.rotate();
+}
+
+/// An [`EventQueue`] event seen as a Bevy message (see [`EventQueueMessagesPlugin`])
+#[derive(Message, Debug, Clone)]
+pub struct QueuedMessage<T: Send + Sync + 'static>(pub T);
+
+impl<T: Send + Sync + 'static> Deref for QueuedMessage<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Send + Sync + 'static> DerefMut for QueuedMessage<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// Bridges the [`EventQueue`] of `T` and Bevy's `Messages<QueuedMessage<T>>`,
+/// for `MessageReader` / `MessageWriter` users. Registers both sides if needed.
+pub struct EventQueueMessagesPlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for EventQueueMessagesPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Plugin for EventQueueMessagesPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app
+            .add_plugins(EventQueuePlugin::<T>::default())
+            .add_message::<QueuedMessage<T>>()
+            .add_systems(Last, forward_event_queue_messages::<T>);
+    }
+}
+
+/// System to exchange new events between an [`EventQueue`] and its messages.
+/// Each side only receives what the other side sent, so nothing echoes back.
+pub fn forward_event_queue_messages<T: Clone + Send + Sync + 'static>(
+    mut queue: ResMut<EventQueue<T>>,
+    mut messages: ResMut<Messages<QueuedMessage<T>>>,
+    mut queue_cursor: Local<QueueCursor<T>>,
+    mut message_cursor: Local<MessageCursor<QueuedMessage<T>>>,
+) {
+    let from_messages: Vec<T> = message_cursor.read(&messages).map(|message| message.0.clone()).collect();
+    let from_queue: Vec<T> = queue_cursor.read(&queue).cloned().collect();
+
+    messages.write_batch(from_queue.into_iter().map(QueuedMessage));
+    message_cursor.clear(&messages);
+    queue.send_batch(from_messages);
+    queue_cursor.clear(&queue);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Ping(u32);
+
+    #[derive(Resource, Default)]
+    struct Seen {
+        by_queue: Vec<u32>,
+        by_messages: Vec<u32>,
+    }
+
+    fn send_from_queue(mut queue: ResMut<EventQueue<Ping>>, mut frame: Local<u32>) {
+        *frame += 1;
+        if *frame == 1 {
+            queue.send(Ping(1));
+        }
+    }
+
+    fn send_from_messages(mut pings: MessageWriter<QueuedMessage<Ping>>, mut frame: Local<u32>) {
+        *frame += 1;
+        if *frame == 1 {
+            pings.write(QueuedMessage(Ping(2)));
+        }
+    }
+
+    fn read_queue(mut pings: QueueReader<Ping>, mut seen: ResMut<Seen>) {
+        seen.by_queue.extend(pings.read().map(|ping| ping.0));
+    }
+
+    fn read_messages(mut pings: MessageReader<QueuedMessage<Ping>>, mut seen: ResMut<Seen>) {
+        seen.by_messages.extend(pings.read().map(|ping| ping.0));
+    }
+
+    #[derive(Resource, Default)]
+    struct Readers {
+        before: Vec<u32>,
+        after: Vec<u32>,
+    }
+
+    fn send_each_frame(mut queue: ResMut<EventQueue<Ping>>, mut frame: Local<u32>) {
+        *frame += 1;
+        if *frame <= 2 {
+            queue.send(Ping(*frame));
+        }
+    }
+
+    fn read_before(mut pings: QueueReader<Ping>, mut readers: ResMut<Readers>) {
+        readers.before.extend(pings.read().map(|ping| ping.0));
+    }
+
+    fn read_after(mut pings: QueueReader<Ping>, mut readers: ResMut<Readers>) {
+        readers.after.extend(pings.read().map(|ping| ping.0));
+    }
+
+    #[test]
+    fn test_readers_on_both_sides_of_sender_see_each_event_once() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, EventQueuePlugin::<Ping>::default()))
+            .init_resource::<Readers>()
+            .add_systems(PreUpdate, read_before)
+            .add_systems(Update, send_each_frame)
+            .add_systems(PostUpdate, read_after);
+
+        app.update();
+        app.update();
+        app.update();
+        // Frame 2's event is kept for one more frame, then dropped
+        assert_eq!(app.world().resource::<EventQueue<Ping>>().len(), 1);
+        app.update();
+        assert!(app.world().resource::<EventQueue<Ping>>().is_empty());
+
+        let readers = app.world().resource::<Readers>();
+        assert_eq!(readers.before, vec![1, 2]);
+        assert_eq!(readers.after, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_queue_and_messages_see_each_other_once() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(EventQueueMessagesPlugin::<Ping>::default())
+            .init_resource::<Seen>()
+            .add_systems(Update, (send_from_queue, send_from_messages, read_queue, read_messages).chain());
+
+        for _ in 0..4 {
+            app.update();
+        }
+
+        let seen = app.world().resource::<Seen>();
+        assert_eq!(seen.by_queue, vec![1, 2]);
+        assert_eq!(seen.by_messages, vec![2, 1]);
+    }
+}
@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use bevy::transform::TransformSystems;
+use crate::input::{ConsoleAppExt, ConsoleCommand};
 
 pub mod types;
 pub mod systems;
@@ -16,6 +18,8 @@ pub mod hoverboard_animation;
 pub mod gravity;
 pub mod vehicle_ai_navmesh;
 pub mod waypoints;
+pub mod surface;
+pub mod ownership;
 
 pub use types::*;
 pub use spawn::*;
@@ -43,8 +47,16 @@ pub use gravity::update_vehicle_gravity;
 pub use vehicle_ai_navmesh::VehicleAINavMesh;
 pub use waypoints::WaypointCircuit;
 pub use waypoints::WaypointProgressTracker;
+pub use surface::{HydroplaningZone, SurfaceConditions, VehicleSurfaceHandling};
+pub use ownership::{
+    GarageRequest, GarageRequestQueue, OwnedVehicleLocation, OwnedVehicleRecord, OwnedVehicles, OwnedVehiclesSaveData,
+    PurchaseVehicleEvent, PurchaseVehicleEventQueue, VehicleCustomization, VehicleDealer, VehicleGarage, VehicleListing,
+    VehicleOwnership, VehicleOwnershipEvent, VehicleOwnershipEventQueue, VehicleOwnershipFailure, VehicleOwnershipSettings,
+    spawn_owned_vehicle,
+};
 
 use systems::*;
+use crate::utils::EventQueuePlugin;
 
 pub struct VehiclesPlugin;
 
@@ -52,6 +64,10 @@ impl Plugin for VehiclesPlugin {
     fn build(&self, app: &mut App) {
         app
             .register_type::<Vehicle>()
+            .add_console_command(
+                ConsoleCommand::new("spawn_vehicle", "Spawns a vehicle in front of the player").with_usage("<type>"),
+                spawn_vehicle_command,
+            )
             .register_type::<VehicleSeat>()
             .register_type::<VehicleDriver>()
             .register_type::<VehicleWheel>()
@@ -66,6 +82,8 @@ impl Plugin for VehiclesPlugin {
             .register_type::<VehicleHudHealth>()
             .register_type::<VehicleHudFuel>()
             .register_type::<VehicleHudAmmo>()
+            .register_type::<VehicleHudLeadMarker>()
+            .register_type::<VehicleLeadIndicator>()
             .register_type::<VehicleIKTargets>()
             .register_type::<VehiclePassengerStability>()
             .register_type::<HoverBoardWaypoints>()
@@ -92,10 +110,31 @@ impl Plugin for VehiclesPlugin {
             .register_type::<VehicleAINavMesh>()
             .register_type::<WaypointCircuit>()
             .register_type::<WaypointProgressTracker>()
+            .register_type::<PassengerState>()
+            .register_type::<VehicleWeaponType>()
+            .register_type::<VehicleAudio>()
+            .register_type::<SurfaceConditions>()
+            .register_type::<HydroplaningZone>()
+            .register_type::<VehicleSurfaceHandling>()
+            .init_resource::<SurfaceConditions>()
+            .register_type::<VehicleOwnership>()
+            .register_type::<VehicleCustomization>()
+            .register_type::<VehicleDealer>()
+            .register_type::<VehicleGarage>()
+            .register_type::<VehicleOwnershipSettings>()
+            .init_resource::<VehicleOwnershipSettings>()
+            .init_resource::<OwnedVehicles>()
+            .add_plugins((
+                EventQueuePlugin::<PurchaseVehicleEvent>::default(),
+                EventQueuePlugin::<GarageRequest>::default(),
+                EventQueuePlugin::<VehicleOwnershipEvent>::default(),
+            ))
             .add_systems(Update, (
                 input::vehicle_input_system,
                 sync::character_vehicle_sync_system,
+                surface::update_vehicle_surface_handling.before(physics::update_vehicles_physics),
                 physics::update_vehicles_physics,
+                surface::apply_vehicle_wind.after(physics::update_vehicles_physics),
                 physics::update_passenger_stability,
                 interaction::handle_vehicle_interaction,
             ))
@@ -108,14 +147,19 @@ impl Plugin for VehiclesPlugin {
             .add_systems(Update, (
                 weapons::update_vehicle_weapon_aiming,
                 weapons::update_vehicle_weapon_firing,
+                weapons::update_vehicle_lead_indicator,
+                weapons::draw_vehicle_lead_arcs
+                    .after(weapons::update_vehicle_lead_indicator)
+                    .in_set(crate::headless::PresentationSet),
             ))
+            .add_systems(PostUpdate, weapons::update_vehicle_lead_marker.in_set(crate::headless::PresentationSet).after(TransformSystems::Propagate))
             .add_systems(Update, (
                 seating::manage_vehicle_passengers,
                 effects::update_skidmarks,
                 chassis::update_vehicle_chassis,
                 gravity::update_vehicle_gravity,
                 audio::update_vehicle_audio,
-                hud::update_vehicle_hud,
+                hud::update_vehicle_hud.in_set(crate::headless::PresentationSet),
                 hoverboard_waypoints::update_hoverboard_waypoints,
                 ik_driving_system::update_ik_driving,
                 launch_trajectory::update_launch_trajectory,
@@ -126,6 +170,19 @@ impl Plugin for VehiclesPlugin {
                 hoverboard_animation::update_hoverboard_animation,
                 vehicle_ai_navmesh::update_vehicle_ai_navmesh,
                 waypoints::update_waypoint_progress_tracker,
+            ))
+            .add_systems(Update, (
+                ownership::restore_owned_vehicles_from_save,
+                ownership::handle_vehicle_purchases,
+                ownership::use_vehicle_garages,
+                ownership::handle_garage_requests,
+                ownership::sync_owned_vehicle_records,
+                ownership::spawn_owned_vehicles,
+                ownership::store_owned_vehicles_in_save,
+            ).chain())
+            .add_systems(Update, (
+                ownership::apply_vehicle_paint,
+                ownership::detect_vehicle_theft,
             ));
     }
 }
@@ -0,0 +1,773 @@
+//! Owned vehicles, garages and their persistence.
+//!
+//! Vehicles the player owns are kept as [`OwnedVehicleRecord`]s in the
+//! [`OwnedVehicles`] resource: type, name, damage, fuel, paint, attachments,
+//! and where the vehicle is (parked in a garage or out in the world). Records
+//! of vehicles out in the world are refreshed from the spawned vehicle every
+//! `sync_interval` seconds and written to the player's `SaveCustomData` under
+//! `VehicleOwnershipSettings::save_key`. Loading a save respawns them.
+//!
+//! - A [`VehicleDealer`] (usually next to a `Vendor`) sells vehicles for
+//!   currency through `PurchaseVehicleEvent`, and parks them in its garage or
+//!   delivers them next to it.
+//! - A [`VehicleGarage`] stores an owned vehicle parked near it and brings
+//!   stored ones back out, through `GarageRequest`s or by interacting with it.
+//! - Vehicles with an owner faction in [`VehicleOwnership`] are stolen when
+//!   someone outside that faction takes the wheel. Members of the faction
+//!   nearby chase the thief, and the faction may turn hostile.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::ai::{AiBehaviorState, AiController, CharacterFaction, FactionRelation, FactionSystem};
+use crate::currency::{Currency, TransactionKind, TransactionLedger, TransactionOutcome, TransactionRecord};
+use crate::character::Player;
+use crate::interaction::InteractionEvent;
+use crate::save::{ChangedPlayerSaveData, SaveCustomData};
+use crate::utils::{EventQueue, QueueReader};
+use super::spawn::VehicleConfig;
+use super::types::*;
+
+/// Tuning of vehicle ownership, persistence and theft
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct VehicleOwnershipSettings {
+    /// Seconds between refreshes of the records of vehicles out in the world
+    pub sync_interval: f32,
+    /// Key of the owned vehicles in `SaveCustomData`
+    pub save_key: String,
+    /// How far from a stolen vehicle members of its faction notice the theft
+    pub witness_radius: f32,
+    /// Whether a theft turns the owner faction hostile to the thief's
+    pub theft_makes_hostile: bool,
+}
+
+impl Default for VehicleOwnershipSettings {
+    fn default() -> Self {
+        Self {
+            sync_interval: 1.0,
+            save_key: "vehicles".to_string(),
+            witness_radius: 25.0,
+            theft_makes_hostile: true,
+        }
+    }
+}
+
+/// Paint and attachments carried with a vehicle through garages and saves
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct VehicleCustomization {
+    pub paint: Color,
+    /// Ids of the fitted attachments, for the game to interpret
+    pub attachments: Vec<String>,
+}
+
+impl Default for VehicleCustomization {
+    fn default() -> Self {
+        Self {
+            paint: Color::from(LinearRgba::new(0.8, 0.2, 0.2, 1.0)),
+            attachments: Vec::new(),
+        }
+    }
+}
+
+/// Who a vehicle belongs to
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct VehicleOwnership {
+    /// Id of the `OwnedVehicles` record of a vehicle the player owns
+    pub record: Option<u64>,
+    /// Faction that owns the vehicle; taking its wheel from outside the faction is theft
+    pub faction: Option<String>,
+    /// Who stole the vehicle
+    pub stolen_by: Option<Entity>,
+}
+
+/// Where an owned vehicle is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OwnedVehicleLocation {
+    /// Stored in the garage with this id
+    Garage(String),
+    /// Out in the world, last seen at this pose
+    World { translation: Vec3, rotation: Quat },
+}
+
+/// Owned vehicle as kept in `OwnedVehicles` and stored in saves
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OwnedVehicleRecord {
+    pub id: u64,
+    pub vehicle_type: VehicleType,
+    pub name: String,
+    pub health: f32,
+    pub max_health: f32,
+    pub fuel: f32,
+    pub max_fuel: f32,
+    /// sRGBA
+    pub paint: [f32; 4],
+    pub attachments: Vec<String>,
+    pub location: OwnedVehicleLocation,
+}
+
+impl OwnedVehicleRecord {
+    /// A new vehicle in perfect shape with a full tank
+    pub fn new(id: u64, vehicle_type: VehicleType, name: impl Into<String>, location: OwnedVehicleLocation) -> Self {
+        let stats = VehicleStats::default();
+        let mut record = Self {
+            id,
+            vehicle_type,
+            name: name.into(),
+            health: stats.max_health,
+            max_health: stats.max_health,
+            fuel: stats.max_fuel,
+            max_fuel: stats.max_fuel,
+            paint: [0.0; 4],
+            attachments: Vec::new(),
+            location,
+        };
+        record.set_customization(&VehicleCustomization::default());
+        record
+    }
+
+    /// Destroyed vehicles stay on record but are never spawned again
+    pub fn is_wrecked(&self) -> bool {
+        self.health <= 0.0
+    }
+
+    pub fn is_in_garage(&self, garage: &str) -> bool {
+        matches!(&self.location, OwnedVehicleLocation::Garage(id) if id == garage)
+    }
+
+    pub fn customization(&self) -> VehicleCustomization {
+        let [red, green, blue, alpha] = self.paint;
+        VehicleCustomization {
+            paint: Color::srgba(red, green, blue, alpha),
+            attachments: self.attachments.clone(),
+        }
+    }
+
+    pub fn set_customization(&mut self, customization: &VehicleCustomization) {
+        let paint = customization.paint.to_srgba();
+        self.paint = [paint.red, paint.green, paint.blue, paint.alpha];
+        self.attachments = customization.attachments.clone();
+    }
+
+    /// Copies damage, fuel, customization and pose from the spawned vehicle
+    pub fn capture(&mut self, stats: &VehicleStats, customization: Option<&VehicleCustomization>, transform: &Transform) {
+        self.health = stats.health;
+        self.max_health = stats.max_health;
+        self.fuel = stats.fuel;
+        self.max_fuel = stats.max_fuel;
+        if let Some(customization) = customization {
+            self.set_customization(customization);
+        }
+        if matches!(self.location, OwnedVehicleLocation::World { .. }) {
+            self.location = OwnedVehicleLocation::World { translation: transform.translation, rotation: transform.rotation };
+        }
+    }
+}
+
+/// Owned vehicles, as stored under `VehicleOwnershipSettings::save_key`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OwnedVehiclesSaveData {
+    pub next_id: u64,
+    pub records: Vec<OwnedVehicleRecord>,
+}
+
+/// Vehicles the player owns
+#[derive(Resource, Debug, Default)]
+pub struct OwnedVehicles {
+    pub next_id: u64,
+    pub records: Vec<OwnedVehicleRecord>,
+    /// Spawned vehicles by record id
+    pub spawned: HashMap<u64, Entity>,
+}
+
+impl OwnedVehicles {
+    /// Adds a new vehicle and returns its record id
+    pub fn add(&mut self, vehicle_type: VehicleType, name: impl Into<String>, location: OwnedVehicleLocation) -> u64 {
+        self.next_id += 1;
+        self.records.push(OwnedVehicleRecord::new(self.next_id, vehicle_type, name, location));
+        self.next_id
+    }
+
+    pub fn get(&self, id: u64) -> Option<&OwnedVehicleRecord> {
+        self.records.iter().find(|record| record.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: u64) -> Option<&mut OwnedVehicleRecord> {
+        self.records.iter_mut().find(|record| record.id == id)
+    }
+
+    /// Vehicles stored in the garage with this id
+    pub fn in_garage<'a>(&'a self, garage: &'a str) -> impl Iterator<Item = &'a OwnedVehicleRecord> {
+        self.records.iter().filter(move |record| record.is_in_garage(garage))
+    }
+
+    pub fn save_data(&self) -> OwnedVehiclesSaveData {
+        OwnedVehiclesSaveData { next_id: self.next_id, records: self.records.clone() }
+    }
+}
+
+/// Vehicle on sale at a `VehicleDealer`
+#[derive(Debug, Clone, Reflect)]
+pub struct VehicleListing {
+    pub vehicle_type: VehicleType,
+    pub name: String,
+    pub price: f32,
+    pub customization: VehicleCustomization,
+}
+
+impl VehicleListing {
+    /// The preset of `vehicle_type` at `price`
+    pub fn new(vehicle_type: VehicleType, price: f32) -> Self {
+        let preset = VehicleConfig::preset(vehicle_type.clone(), Vec3::ZERO);
+        Self {
+            vehicle_type,
+            name: preset.name,
+            price,
+            customization: VehicleCustomization { paint: preset.color, attachments: Vec::new() },
+        }
+    }
+}
+
+/// Sells vehicles through `PurchaseVehicleEvent`
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct VehicleDealer {
+    pub listings: Vec<VehicleListing>,
+    /// Id of the garage bought vehicles are parked in; delivered next to the
+    /// dealer without one, or when it is full
+    pub garage: Option<String>,
+    /// Where vehicles are delivered, in the dealer's space
+    pub delivery_offset: Vec3,
+}
+
+impl Default for VehicleDealer {
+    fn default() -> Self {
+        Self {
+            listings: Vec::new(),
+            garage: None,
+            delivery_offset: Vec3::new(0.0, 1.0, 6.0),
+        }
+    }
+}
+
+/// Stores owned vehicles; interacting with it stores the vehicle parked
+/// nearest, or brings out the first one stored
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct VehicleGarage {
+    pub id: String,
+    /// Vehicles it holds at most
+    pub capacity: usize,
+    /// How close a vehicle must be parked to be stored
+    pub store_radius: f32,
+    /// Where retrieved vehicles appear, in the garage's space
+    pub spawn_offset: Vec3,
+}
+
+impl Default for VehicleGarage {
+    fn default() -> Self {
+        Self {
+            id: "garage".to_string(),
+            capacity: 4,
+            store_radius: 8.0,
+            spawn_offset: Vec3::new(0.0, 1.0, 6.0),
+        }
+    }
+}
+
+/// Request to buy a vehicle from a `VehicleDealer`
+#[derive(Debug, Clone)]
+pub struct PurchaseVehicleEvent {
+    pub dealer: Entity,
+    pub buyer: Entity,
+    /// Index in `VehicleDealer::listings`
+    pub listing_index: usize,
+    /// As `PurchaseItemEvent::transaction_id`
+    pub transaction_id: Option<u64>,
+}
+
+pub type PurchaseVehicleEventQueue = EventQueue<PurchaseVehicleEvent>;
+
+/// Requests to a `VehicleGarage`, for menus and gameplay code
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GarageRequest {
+    /// Store an owned vehicle parked near the garage
+    Store { garage: Entity, vehicle: Entity },
+    /// Bring out a stored vehicle; the first one stored without an `id`
+    Retrieve { garage: Entity, id: Option<u64> },
+}
+
+pub type GarageRequestQueue = EventQueue<GarageRequest>;
+
+/// Why a purchase or garage request was refused
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum VehicleOwnershipFailure {
+    NotEnoughMoney,
+    ListingNotFound,
+    ShopClosed,
+    GarageFull,
+    /// The vehicle isn't one of the player's
+    NotOwned,
+    /// Someone is still in the vehicle
+    Occupied,
+    /// The vehicle isn't parked close enough to the garage
+    TooFar,
+    NothingStored,
+    Wrecked,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VehicleOwnershipEvent {
+    Purchased { buyer: Entity, dealer: Entity, id: u64, price: f32 },
+    Stored { garage: Entity, id: u64 },
+    /// The vehicle spawns in front of the garage
+    Retrieved { garage: Entity, id: u64 },
+    /// `entity` is the buyer of a purchase or the garage of a request
+    Failed { entity: Entity, reason: VehicleOwnershipFailure },
+    Stolen { vehicle: Entity, thief: Entity, faction: String },
+}
+
+pub type VehicleOwnershipEventQueue = EventQueue<VehicleOwnershipEvent>;
+
+/// Spawns the vehicle of `record` at `transform`, with its damage, fuel and customization
+pub fn spawn_owned_vehicle(
+    commands: &mut Commands,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    record: &OwnedVehicleRecord,
+    transform: Transform,
+) -> Entity {
+    let customization = record.customization();
+    let mut config = VehicleConfig::preset(record.vehicle_type.clone(), transform.translation);
+    config.name = record.name.clone();
+    config.color = customization.paint;
+    let vehicle = config.build(commands, meshes, materials);
+    commands.entity(vehicle).insert((
+        transform,
+        VehicleStats {
+            health: record.health.min(record.max_health),
+            max_health: record.max_health,
+            fuel: record.fuel.min(record.max_fuel),
+            max_fuel: record.max_fuel,
+            ..default()
+        },
+        customization,
+        VehicleOwnership { record: Some(record.id), ..default() },
+    ));
+    vehicle
+}
+
+/// Dealers and the garages they park sold vehicles in
+#[derive(SystemParam)]
+pub struct VehicleDealers<'w, 's> {
+    pub dealer_query: Query<'w, 's, (&'static VehicleDealer, &'static GlobalTransform)>,
+    pub garage_query: Query<'w, 's, &'static VehicleGarage>,
+}
+
+/// System to sell vehicles at dealers
+pub fn handle_vehicle_purchases(
+    mut purchases: QueueReader<PurchaseVehicleEvent>,
+    mut owned: ResMut<OwnedVehicles>,
+    mut events: ResMut<VehicleOwnershipEventQueue>,
+    ledger: TransactionLedger,
+    dealers: VehicleDealers,
+    mut currency_query: Query<&mut Currency>,
+    #[cfg(feature = "vendor")] schedule_query: Query<&crate::vendor::VendorSchedule>,
+) {
+    let TransactionLedger { time, mut guard, mut log } = ledger;
+    let VehicleDealers { dealer_query, garage_query } = dealers;
+    for event in purchases.read().cloned() {
+        let Ok((dealer, dealer_transform)) = dealer_query.get(event.dealer) else { continue };
+        let listing = dealer.listings.get(event.listing_index);
+        let mut record = TransactionRecord {
+            id: 0,
+            time: time.elapsed_secs_f64(),
+            kind: TransactionKind::Purchase,
+            customer: event.buyer,
+            vendor: Some(event.dealer),
+            item: listing.map(|listing| listing.name.clone()),
+            quantity: 1,
+            money: 0.0,
+            outcome: TransactionOutcome::Completed,
+        };
+        if !guard.admit(event.transaction_id, (event.buyer, event.dealer, event.listing_index), record.time) {
+            record.outcome = TransactionOutcome::Duplicate;
+            log.record(record);
+            continue;
+        }
+
+        let result = 'purchase: {
+            #[cfg(feature = "vendor")]
+            if schedule_query.get(event.dealer).is_ok_and(|schedule| !schedule.is_open) {
+                break 'purchase Err(VehicleOwnershipFailure::ShopClosed);
+            }
+            let Some(listing) = listing else {
+                break 'purchase Err(VehicleOwnershipFailure::ListingNotFound);
+            };
+            let Ok(mut currency) = currency_query.get_mut(event.buyer) else {
+                break 'purchase Err(VehicleOwnershipFailure::NotEnoughMoney);
+            };
+            if currency.amount < listing.price {
+                break 'purchase Err(VehicleOwnershipFailure::NotEnoughMoney);
+            }
+            currency.amount -= listing.price;
+            Ok(listing)
+        };
+
+        match result {
+            Ok(listing) => {
+                let garage = dealer.garage.clone().filter(|garage| {
+                    garage_query
+                        .iter()
+                        .find(|candidate| candidate.id == *garage)
+                        .is_some_and(|candidate| owned.in_garage(garage).count() < candidate.capacity)
+                });
+                let location = match garage {
+                    Some(garage) => OwnedVehicleLocation::Garage(garage),
+                    None => OwnedVehicleLocation::World {
+                        translation: dealer_transform.transform_point(dealer.delivery_offset),
+                        rotation: dealer_transform.compute_transform().rotation,
+                    },
+                };
+                let id = owned.add(listing.vehicle_type.clone(), listing.name.clone(), location);
+                if let Some(vehicle) = owned.get_mut(id) {
+                    vehicle.set_customization(&listing.customization);
+                }
+                record.money = -listing.price;
+                log.record(record);
+                events.send(VehicleOwnershipEvent::Purchased { buyer: event.buyer, dealer: event.dealer, id, price: listing.price });
+            }
+            Err(reason) => {
+                record.outcome = TransactionOutcome::Failed(format!("{:?}", reason));
+                log.record(record);
+                events.send(VehicleOwnershipEvent::Failed { entity: event.buyer, reason });
+            }
+        }
+    }
+}
+
+/// System to store or retrieve vehicles when the player uses a garage
+pub fn use_vehicle_garages(
+    mut interaction_events: QueueReader<InteractionEvent>,
+    mut requests: ResMut<GarageRequestQueue>,
+    owned: Res<OwnedVehicles>,
+    garage_query: Query<(&VehicleGarage, &GlobalTransform)>,
+    vehicle_query: Query<&GlobalTransform, With<VehicleOwnership>>,
+) {
+    for event in interaction_events.read() {
+        let Ok((garage, garage_transform)) = garage_query.get(event.target) else { continue };
+        let parked = owned
+            .spawned
+            .values()
+            .filter_map(|vehicle| {
+                let distance = vehicle_query.get(*vehicle).ok()?.translation().distance(garage_transform.translation());
+                (distance <= garage.store_radius).then_some((*vehicle, distance))
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(vehicle, _)| vehicle);
+
+        requests.send(match parked {
+            Some(vehicle) => GarageRequest::Store { garage: event.target, vehicle },
+            None => GarageRequest::Retrieve { garage: event.target, id: None },
+        });
+    }
+}
+
+/// Vehicle data stored with it in a garage
+type GarageableVehicle<'a> = (
+    &'a GlobalTransform,
+    &'a Transform,
+    &'a VehicleStats,
+    &'a VehicleOwnership,
+    Option<&'a VehicleCustomization>,
+    Option<&'a VehicleSeatingManager>,
+);
+
+/// System to store vehicles in garages and take them out again
+pub fn handle_garage_requests(
+    mut commands: Commands,
+    mut requests: QueueReader<GarageRequest>,
+    mut owned: ResMut<OwnedVehicles>,
+    mut events: ResMut<VehicleOwnershipEventQueue>,
+    garage_query: Query<(&VehicleGarage, &GlobalTransform)>,
+    vehicle_query: Query<GarageableVehicle>,
+    seat_query: Query<&VehicleSeat>,
+) {
+    for request in requests.read().copied() {
+        match request {
+            GarageRequest::Store { garage: garage_entity, vehicle } => {
+                let Ok((garage, garage_transform)) = garage_query.get(garage_entity) else { continue };
+                let result = 'store: {
+                    let Ok((vehicle_transform, transform, stats, ownership, customization, seating)) = vehicle_query.get(vehicle) else {
+                        break 'store Err(VehicleOwnershipFailure::NotOwned);
+                    };
+                    let Some(id) = ownership.record.filter(|id| owned.spawned.get(id) == Some(&vehicle)) else {
+                        break 'store Err(VehicleOwnershipFailure::NotOwned);
+                    };
+                    if vehicle_transform.translation().distance(garage_transform.translation()) > garage.store_radius {
+                        break 'store Err(VehicleOwnershipFailure::TooFar);
+                    }
+                    let occupied = seating.is_some_and(|seating| {
+                        seating.seats.iter().any(|seat| seat_query.get(*seat).is_ok_and(|seat| seat.occupied_by.is_some()))
+                    });
+                    if occupied {
+                        break 'store Err(VehicleOwnershipFailure::Occupied);
+                    }
+                    if owned.in_garage(&garage.id).count() >= garage.capacity {
+                        break 'store Err(VehicleOwnershipFailure::GarageFull);
+                    }
+                    let Some(record) = owned.get_mut(id) else {
+                        break 'store Err(VehicleOwnershipFailure::NotOwned);
+                    };
+                    record.capture(stats, customization, transform);
+                    record.location = OwnedVehicleLocation::Garage(garage.id.clone());
+                    owned.spawned.remove(&id);
+                    commands.entity(vehicle).despawn();
+                    Ok(id)
+                };
+                events.send(match result {
+                    Ok(id) => VehicleOwnershipEvent::Stored { garage: garage_entity, id },
+                    Err(reason) => VehicleOwnershipEvent::Failed { entity: garage_entity, reason },
+                });
+            }
+            GarageRequest::Retrieve { garage: garage_entity, id } => {
+                let Ok((garage, garage_transform)) = garage_query.get(garage_entity) else { continue };
+                let result = 'retrieve: {
+                    let id = id.or_else(|| owned.in_garage(&garage.id).find(|record| !record.is_wrecked()).map(|record| record.id));
+                    let Some(record) = id.and_then(|id| owned.get_mut(id)).filter(|record| record.is_in_garage(&garage.id)) else {
+                        break 'retrieve Err(VehicleOwnershipFailure::NothingStored);
+                    };
+                    if record.is_wrecked() {
+                        break 'retrieve Err(VehicleOwnershipFailure::Wrecked);
+                    }
+                    // Spawned by `spawn_owned_vehicles`
+                    record.location = OwnedVehicleLocation::World {
+                        translation: garage_transform.transform_point(garage.spawn_offset),
+                        rotation: garage_transform.compute_transform().rotation,
+                    };
+                    Ok(record.id)
+                };
+                events.send(match result {
+                    Ok(id) => VehicleOwnershipEvent::Retrieved { garage: garage_entity, id },
+                    Err(reason) => VehicleOwnershipEvent::Failed { entity: garage_entity, reason },
+                });
+            }
+        }
+    }
+}
+
+/// System to refresh the records of owned vehicles out in the world
+pub fn sync_owned_vehicle_records(
+    time: Res<Time>,
+    settings: Res<VehicleOwnershipSettings>,
+    mut since_sync: Local<f32>,
+    mut owned: ResMut<OwnedVehicles>,
+    vehicle_query: Query<(&Transform, &VehicleStats, Option<&VehicleCustomization>)>,
+) {
+    *since_sync += time.delta_secs();
+    if *since_sync < settings.sync_interval {
+        return;
+    }
+    *since_sync = 0.0;
+
+    // Parked vehicles don't change; only mark the records changed when one did
+    let mut changed = false;
+    let vehicles = owned.bypass_change_detection();
+    let spawned: Vec<(u64, Entity)> = vehicles.spawned.iter().map(|(id, vehicle)| (*id, *vehicle)).collect();
+    for (id, vehicle) in spawned {
+        let Some(record) = vehicles.records.iter_mut().find(|record| record.id == id) else {
+            vehicles.spawned.remove(&id);
+            continue;
+        };
+        let before = record.clone();
+        match vehicle_query.get(vehicle) {
+            Ok((transform, stats, customization)) => record.capture(stats, customization, transform),
+            // Despawned without going through a garage: destroyed
+            Err(_) => {
+                record.health = 0.0;
+                vehicles.spawned.remove(&id);
+            }
+        }
+        changed |= *record != before;
+    }
+    if changed {
+        owned.set_changed();
+    }
+}
+
+/// System to spawn owned vehicles that are out in the world but not spawned:
+/// new deliveries, retrieved vehicles and vehicles from a loaded save
+pub fn spawn_owned_vehicles(
+    mut commands: Commands,
+    mut owned: ResMut<OwnedVehicles>,
+    mut meshes: Option<ResMut<Assets<Mesh>>>,
+    mut materials: Option<ResMut<Assets<StandardMaterial>>>,
+) {
+    let (Some(meshes), Some(materials)) = (meshes.as_mut(), materials.as_mut()) else { return };
+    let pending: Vec<OwnedVehicleRecord> = owned
+        .records
+        .iter()
+        .filter(|record| !record.is_wrecked() && !owned.spawned.contains_key(&record.id))
+        .filter(|record| matches!(record.location, OwnedVehicleLocation::World { .. }))
+        .cloned()
+        .collect();
+    for record in pending {
+        let OwnedVehicleLocation::World { translation, rotation } = record.location else { continue };
+        let transform = Transform::from_translation(translation).with_rotation(rotation);
+        let vehicle = spawn_owned_vehicle(&mut commands, meshes, materials, &record, transform);
+        owned.spawned.insert(record.id, vehicle);
+    }
+}
+
+/// System to repaint vehicles when their customization changes
+pub fn apply_vehicle_paint(
+    query: Query<(&VehicleCustomization, &MeshMaterial3d<StandardMaterial>), Changed<VehicleCustomization>>,
+    mut materials: Option<ResMut<Assets<StandardMaterial>>>,
+) {
+    let Some(materials) = materials.as_mut() else { return };
+    for (customization, material) in query.iter() {
+        if let Some(material) = materials.get_mut(&material.0) {
+            if material.base_color != customization.paint {
+                material.base_color = customization.paint;
+            }
+        }
+    }
+}
+
+/// System to react to vehicles taken from their owner faction
+pub fn detect_vehicle_theft(
+    settings: Res<VehicleOwnershipSettings>,
+    mut factions: ResMut<FactionSystem>,
+    mut events: ResMut<VehicleOwnershipEventQueue>,
+    driver_query: Query<(Entity, &ChildOf, Option<&CharacterFaction>), Added<VehicleDriver>>,
+    seat_query: Query<&ChildOf, With<VehicleSeat>>,
+    mut vehicle_query: Query<(&mut VehicleOwnership, &GlobalTransform)>,
+    mut witness_query: Query<(&GlobalTransform, &mut AiController, &CharacterFaction)>,
+) {
+    for (thief, seat, thief_faction) in driver_query.iter() {
+        let Ok(vehicle) = seat_query.get(seat.parent()).map(ChildOf::parent) else { continue };
+        let Ok((mut ownership, vehicle_transform)) = vehicle_query.get_mut(vehicle) else { continue };
+        let Some(faction) = ownership.faction.clone() else { continue };
+        let thief_faction = thief_faction.map(|faction| faction.name.as_str()).unwrap_or("Default");
+        if ownership.stolen_by == Some(thief) || factions.is_friendly(&faction, thief_faction) {
+            continue;
+        }
+
+        ownership.stolen_by = Some(thief);
+        if settings.theft_makes_hostile {
+            factions.set_relation(&faction, thief_faction, FactionRelation::Enemy);
+        }
+        let position = vehicle_transform.translation();
+        for (witness_transform, mut ai, witness_faction) in witness_query.iter_mut() {
+            if ai.state == AiBehaviorState::Dead
+                || !factions.is_friendly(&faction, &witness_faction.name)
+                || witness_transform.translation().distance(position) > settings.witness_radius
+            {
+                continue;
+            }
+            ai.target = Some(thief);
+            ai.target_last_position = Some(position);
+            ai.state = AiBehaviorState::Chase;
+        }
+        info!("{:?} stole a vehicle of {}", thief, faction);
+        events.send(VehicleOwnershipEvent::Stolen { vehicle, thief, faction });
+    }
+}
+
+/// System to keep the owned vehicles in the data written to saves
+pub fn store_owned_vehicles_in_save(
+    mut commands: Commands,
+    settings: Res<VehicleOwnershipSettings>,
+    owned: Res<OwnedVehicles>,
+    mut player_query: Query<(Entity, Option<&mut SaveCustomData>), With<Player>>,
+) {
+    if !owned.is_changed() {
+        return;
+    }
+    let Some((player, custom_data)) = player_query.iter_mut().next() else { return };
+    let Ok(value) = serde_json::to_value(owned.save_data()) else { return };
+
+    match custom_data {
+        Some(mut custom_data) => {
+            if custom_data.0.get(&settings.save_key) != Some(&value) {
+                custom_data.0.insert(settings.save_key.clone(), value);
+            }
+        }
+        None => {
+            let mut custom_data = SaveCustomData::default();
+            custom_data.0.insert(settings.save_key.clone(), value);
+            commands.entity(player).insert(custom_data);
+        }
+    }
+}
+
+/// System to bring back the owned vehicles of a loaded save
+pub fn restore_owned_vehicles_from_save(
+    mut commands: Commands,
+    settings: Res<VehicleOwnershipSettings>,
+    mut owned: ResMut<OwnedVehicles>,
+    save_query: Query<(Entity, &SaveCustomData), ChangedPlayerSaveData>,
+) {
+    for (entity, custom_data) in save_query.iter() {
+        let Some(value) = custom_data.0.get(&settings.save_key) else { continue };
+        let saved: OwnedVehiclesSaveData = match serde_json::from_value(value.clone()) {
+            Ok(saved) => saved,
+            Err(err) => {
+                warn!("Invalid saved vehicles on {:?}: {}", entity, err);
+                continue;
+            }
+        };
+        // Our own write-back
+        if saved == owned.save_data() {
+            continue;
+        }
+
+        for vehicle in owned.spawned.values() {
+            commands.entity(*vehicle).despawn();
+        }
+        owned.spawned.clear();
+        owned.next_id = saved.records.iter().map(|record| record.id).fold(saved.next_id, u64::max);
+        owned.records = saved.records;
+        info!("Restored {} owned vehicles", owned.records.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vehicles::{VehicleStats, VehicleType};
+
+    #[test]
+    fn test_owned_vehicle_records() {
+        let mut owned = OwnedVehicles::default();
+        let stored = owned.add(VehicleType::Car, "Sports Car", OwnedVehicleLocation::Garage("home".to_string()));
+        let out = owned.add(
+            VehicleType::Motorcycle,
+            "Motorcycle",
+            OwnedVehicleLocation::World { translation: Vec3::ZERO, rotation: Quat::IDENTITY },
+        );
+        assert_eq!(owned.in_garage("home").map(|record| record.id).collect::<Vec<_>>(), vec![stored]);
+
+        let stats = VehicleStats { health: 40.0, fuel: 12.5, ..default() };
+        let customization = VehicleCustomization {
+            paint: Color::srgb(0.0, 0.0, 1.0),
+            attachments: vec!["roof_rack".to_string()],
+        };
+        let record = owned.get_mut(out).unwrap();
+        record.capture(&stats, Some(&customization), &Transform::from_xyz(5.0, 0.0, 2.0));
+        assert_eq!((record.health, record.fuel), (40.0, 12.5));
+        assert_eq!(record.location, OwnedVehicleLocation::World { translation: Vec3::new(5.0, 0.0, 2.0), rotation: Quat::IDENTITY });
+        assert_eq!(record.customization(), customization);
+
+        let saved = serde_json::to_value(owned.save_data()).unwrap();
+        let restored: OwnedVehiclesSaveData = serde_json::from_value(saved).unwrap();
+        assert_eq!(restored, owned.save_data());
+        assert_eq!(restored.next_id, out);
+
+        owned.get_mut(stored).unwrap().health = 0.0;
+        assert!(owned.get(stored).unwrap().is_wrecked());
+    }
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 use crate::vehicles::types::*;
-use crate::input::InputState;
+use crate::input::{console_arg, ConsoleArgs, ConsoleResult, InputState};
 use avian3d::prelude::*;
 
 #[derive(Default)]
@@ -27,6 +27,73 @@ impl VehicleConfig {
         }
     }
 
+    /// Default name, body, wheels and seats of `vehicle_type`
+    pub fn preset(vehicle_type: VehicleType, position: Vec3) -> Self {
+        let mut config = Self::new(vehicle_type.clone(), position);
+        match vehicle_type {
+            VehicleType::Car => {
+                config.name = "Sports Car".to_string();
+                config.wheels = vec![
+                    ("FL".to_string(), Vec3::new(-1.0, -0.5, 1.5), true, false, true),
+                    ("FR".to_string(), Vec3::new(1.0, -0.5, 1.5), true, false, false),
+                    ("RL".to_string(), Vec3::new(-1.0, -0.5, -1.5), false, true, true),
+                    ("RR".to_string(), Vec3::new(1.0, -0.5, -1.5), false, true, false),
+                ];
+                config.seats = vec![
+                    ("Driver".to_string(), Vec3::new(-0.5, 0.5, 0.0), true),
+                    ("Passenger".to_string(), Vec3::new(0.5, 0.5, 0.0), false),
+                ];
+            }
+            VehicleType::Motorcycle => {
+                config.name = "Motorcycle".to_string();
+                config.mesh_size = Vec3::new(0.5, 1.0, 2.0);
+                config.wheels = vec![
+                    ("Front".to_string(), Vec3::new(0.0, -0.5, 0.8), true, false, false),
+                    ("Back".to_string(), Vec3::new(0.0, -0.5, -0.8), false, true, false),
+                ];
+                config.seats = vec![
+                    ("Driver".to_string(), Vec3::new(0.0, 0.5, 0.0), true),
+                ];
+            }
+            VehicleType::Sphere => {
+                config.name = "Battle Sphere".to_string();
+                config.mesh_size = Vec3::splat(2.0);
+                config.seats = vec![
+                    ("Driver".to_string(), Vec3::ZERO, true),
+                ];
+            }
+            VehicleType::Aircraft => {
+                config.name = "Combat Plane".to_string();
+                config.mesh_size = Vec3::new(4.0, 1.5, 6.0);
+                config.seats = vec![
+                    ("Pilot".to_string(), Vec3::new(0.0, 0.5, 1.0), true),
+                    ("Gunner".to_string(), Vec3::new(0.0, 0.5, -1.0), false),
+                ];
+            }
+            VehicleType::Hoverboard => {
+                config.name = "Hoverboard".to_string();
+                config.mesh_size = Vec3::new(1.0, 0.2, 2.5);
+                config.color = Color::from(LinearRgba::new(0.2, 0.8, 0.2, 1.0));
+                config.seats = vec![
+                    ("Driver".to_string(), Vec3::new(0.0, 0.2, 0.0), true),
+                ];
+            }
+            _ => {
+                config.wheels = vec![
+                    ("FL".to_string(), Vec3::new(-1.0, -0.5, 1.5), true, false, true),
+                    ("FR".to_string(), Vec3::new(1.0, -0.5, 1.5), true, false, false),
+                    ("RL".to_string(), Vec3::new(-1.0, -0.5, -1.5), false, true, true),
+                    ("RR".to_string(), Vec3::new(1.0, -0.5, -1.5), false, true, false),
+                ];
+                config.seats = vec![
+                    ("Driver".to_string(), Vec3::new(-0.5, 0.5, 0.0), true),
+                ];
+            }
+        }
+
+        config
+    }
+
     pub fn build(
         self,
         commands: &mut Commands,
@@ -154,68 +221,43 @@ pub fn spawn_vehicle(
     position: Vec3,
     vehicle_type: VehicleType,
 ) -> Entity {
-    let mut config = VehicleConfig::new(vehicle_type.clone(), position);
-    
-    match vehicle_type {
-        VehicleType::Car => {
-            config.name = "Sports Car".to_string();
-            config.wheels = vec![
-                ("FL".to_string(), Vec3::new(-1.0, -0.5, 1.5), true, false, true),
-                ("FR".to_string(), Vec3::new(1.0, -0.5, 1.5), true, false, false),
-                ("RL".to_string(), Vec3::new(-1.0, -0.5, -1.5), false, true, true),
-                ("RR".to_string(), Vec3::new(1.0, -0.5, -1.5), false, true, false),
-            ];
-            config.seats = vec![
-                ("Driver".to_string(), Vec3::new(-0.5, 0.5, 0.0), true),
-                ("Passenger".to_string(), Vec3::new(0.5, 0.5, 0.0), false),
-            ];
-        }
-        VehicleType::Motorcycle => {
-            config.name = "Motorcycle".to_string();
-            config.mesh_size = Vec3::new(0.5, 1.0, 2.0);
-            config.wheels = vec![
-                ("Front".to_string(), Vec3::new(0.0, -0.5, 0.8), true, false, false),
-                ("Back".to_string(), Vec3::new(0.0, -0.5, -0.8), false, true, false),
-            ];
-            config.seats = vec![
-                ("Driver".to_string(), Vec3::new(0.0, 0.5, 0.0), true),
-            ];
-        }
-        VehicleType::Sphere => {
-            config.name = "Battle Sphere".to_string();
-            config.mesh_size = Vec3::splat(2.0);
-            config.seats = vec![
-                ("Driver".to_string(), Vec3::ZERO, true),
-            ];
-        }
-        VehicleType::Aircraft => {
-            config.name = "Combat Plane".to_string();
-            config.mesh_size = Vec3::new(4.0, 1.5, 6.0);
-            config.seats = vec![
-                ("Pilot".to_string(), Vec3::new(0.0, 0.5, 1.0), true),
-                ("Gunner".to_string(), Vec3::new(0.0, 0.5, -1.0), false),
-            ];
-        }
-        VehicleType::Hoverboard => {
-            config.name = "Hoverboard".to_string();
-            config.mesh_size = Vec3::new(1.0, 0.2, 2.5);
-            config.color = Color::from(LinearRgba::new(0.2, 0.8, 0.2, 1.0));
-            config.seats = vec![
-                ("Driver".to_string(), Vec3::new(0.0, 0.2, 0.0), true),
-            ];
-        }
-        _ => {
-            config.wheels = vec![
-                ("FL".to_string(), Vec3::new(-1.0, -0.5, 1.5), true, false, true),
-                ("FR".to_string(), Vec3::new(1.0, -0.5, 1.5), true, false, false),
-                ("RL".to_string(), Vec3::new(-1.0, -0.5, -1.5), false, true, true),
-                ("RR".to_string(), Vec3::new(1.0, -0.5, -1.5), false, true, false),
-            ];
-            config.seats = vec![
-                ("Driver".to_string(), Vec3::new(-0.5, 0.5, 0.0), true),
-            ];
-        }
-    }
+    VehicleConfig::preset(vehicle_type, position).build(commands, &mut meshes, &mut materials)
+}
+
+const CONSOLE_VEHICLE_TYPES: [VehicleType; 11] = [
+    VehicleType::Car,
+    VehicleType::Truck,
+    VehicleType::Motorcycle,
+    VehicleType::Boat,
+    VehicleType::Plane,
+    VehicleType::Hovercraft,
+    VehicleType::Aircraft,
+    VehicleType::Flying,
+    VehicleType::Sphere,
+    VehicleType::Turret,
+    VehicleType::Hoverboard,
+];
+
+/// Console command `spawn_vehicle <type>`: spawns a preset vehicle in front of the player
+pub fn spawn_vehicle_command(
+    In(args): In<ConsoleArgs>,
+    mut commands: Commands,
+    meshes: ResMut<Assets<Mesh>>,
+    materials: ResMut<Assets<StandardMaterial>>,
+    player_query: Query<&GlobalTransform, With<crate::character::Player>>,
+) -> ConsoleResult {
+    let name: String = console_arg(&args, 0, "vehicle type")?;
+    let vehicle_type = CONSOLE_VEHICLE_TYPES
+        .iter()
+        .find(|vehicle_type| format!("{:?}", vehicle_type).eq_ignore_ascii_case(&name))
+        .cloned()
+        .ok_or_else(|| {
+            let names: Vec<String> = CONSOLE_VEHICLE_TYPES.iter().map(|vehicle_type| format!("{:?}", vehicle_type).to_lowercase()).collect();
+            format!("Unknown vehicle type '{}', one of: {}", name, names.join(", "))
+        })?;
 
-    config.build(commands, &mut meshes, &mut materials)
+    let player = player_query.iter().next().ok_or("No player")?;
+    let position = player.translation() + player.forward() * 6.0 + Vec3::Y;
+    spawn_vehicle(&mut commands, meshes, materials, position, vehicle_type.clone());
+    Ok(format!("Spawned {:?}", vehicle_type))
 }
@@ -0,0 +1,227 @@
+//! Weather and surface conditions for vehicle handling
+//!
+//! [`SurfaceConditions`] is the shared state a weather system (or the game)
+//! writes: rain and snow intensity and the wind. Vehicle controllers read it
+//! through their [`VehicleSurfaceHandling`]: wet and snowy ground lower tire
+//! grip (engine, brake and steering authority, and the chassis friction), the
+//! wind pushes aircraft, flying vehicles, hovercraft and hoverboards, and
+//! [`HydroplaningZone`]s (puddles, flooded roads) take most of the grip away
+//! from fast ground vehicles. Each vehicle scales these effects with its own
+//! sensitivities, defaulting per vehicle type.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use super::types::{Vehicle, VehicleType};
+
+/// Weather state shared by the vehicle controllers
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct SurfaceConditions {
+    /// Rain intensity (0-1)
+    pub rain: f32,
+    /// Snow cover (0-1)
+    pub snow: f32,
+    /// Wind velocity, in m/s
+    pub wind: Vec3,
+
+    /// Tire grip left in full rain
+    pub wet_grip: f32,
+    /// Tire grip left in full snow
+    pub snow_grip: f32,
+    /// Acceleration per m/s of wind on a fully sensitive vehicle
+    pub wind_force_scale: f32,
+}
+
+impl Default for SurfaceConditions {
+    fn default() -> Self {
+        Self {
+            rain: 0.0,
+            snow: 0.0,
+            wind: Vec3::ZERO,
+            wet_grip: 0.7,
+            snow_grip: 0.45,
+            wind_force_scale: 0.3,
+        }
+    }
+}
+
+impl SurfaceConditions {
+    /// Tire grip multiplier of the current weather (1 = dry)
+    pub fn weather_grip(&self) -> f32 {
+        let wet = 1.0 - (1.0 - self.wet_grip) * self.rain.clamp(0.0, 1.0);
+        let snowy = 1.0 - (1.0 - self.snow_grip) * self.snow.clamp(0.0, 1.0);
+        wet.min(snowy)
+    }
+}
+
+/// Water patch where fast ground vehicles hydroplane
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct HydroplaningZone {
+    /// Half size of the zone box, around the entity transform
+    pub half_extents: Vec3,
+    /// Tire grip left while hydroplaning
+    pub grip: f32,
+    /// Hydroplaning starts above this speed
+    pub min_speed: f32,
+}
+
+impl Default for HydroplaningZone {
+    fn default() -> Self {
+        Self {
+            half_extents: Vec3::new(3.0, 0.5, 3.0),
+            grip: 0.15,
+            min_speed: 12.0,
+        }
+    }
+}
+
+impl HydroplaningZone {
+    pub fn contains(&self, transform: &GlobalTransform, point: Vec3) -> bool {
+        let local = transform.affine().inverse().transform_point3(point);
+        local.abs().cmple(self.half_extents).all()
+    }
+}
+
+/// How a vehicle reacts to the surface conditions; added to vehicles without one
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct VehicleSurfaceHandling {
+    /// How much weather grip loss applies (0 = ignores rain and snow)
+    pub grip_sensitivity: f32,
+    /// How much the wind pushes the vehicle
+    pub wind_sensitivity: f32,
+    /// How much hydroplaning zones apply
+    pub hydroplaning_sensitivity: f32,
+
+    // Debug State
+    /// Current tire grip multiplier
+    pub grip: f32,
+    pub hydroplaning: bool,
+    /// Chassis friction before the grip was applied
+    pub base_friction: Option<f32>,
+}
+
+impl Default for VehicleSurfaceHandling {
+    fn default() -> Self {
+        Self {
+            grip_sensitivity: 1.0,
+            wind_sensitivity: 0.1,
+            hydroplaning_sensitivity: 1.0,
+            grip: 1.0,
+            hydroplaning: false,
+            base_friction: None,
+        }
+    }
+}
+
+impl VehicleSurfaceHandling {
+    /// Defaults of a vehicle type: ground vehicles lose grip, air and hover vehicles catch the wind
+    pub fn for_vehicle_type(vehicle_type: &VehicleType) -> Self {
+        let (grip, wind, hydroplaning) = match vehicle_type {
+            VehicleType::Car | VehicleType::Truck => (1.0, 0.1, 1.0),
+            VehicleType::Motorcycle => (1.2, 0.25, 1.2),
+            VehicleType::Sphere => (0.8, 0.2, 0.5),
+            VehicleType::Hovercraft | VehicleType::Hoverboard => (0.3, 0.8, 0.0),
+            VehicleType::Aircraft | VehicleType::Plane | VehicleType::Flying => (0.0, 1.0, 0.0),
+            VehicleType::Boat | VehicleType::Turret => (0.0, 0.0, 0.0),
+        };
+        Self {
+            grip_sensitivity: grip,
+            wind_sensitivity: wind,
+            hydroplaning_sensitivity: hydroplaning,
+            ..default()
+        }
+    }
+}
+
+type SurfaceVehicle<'a> = (
+    Entity,
+    &'a Vehicle,
+    &'a GlobalTransform,
+    Option<&'a mut VehicleSurfaceHandling>,
+    Option<&'a mut Friction>,
+);
+
+/// System to compute each vehicle's tire grip from the weather and hydroplaning zones
+pub fn update_vehicle_surface_handling(
+    mut commands: Commands,
+    conditions: Res<SurfaceConditions>,
+    zone_query: Query<(&HydroplaningZone, &GlobalTransform)>,
+    mut vehicle_query: Query<SurfaceVehicle>,
+) {
+    let weather_grip = conditions.weather_grip();
+
+    for (entity, vehicle, transform, handling, friction) in vehicle_query.iter_mut() {
+        let Some(mut handling) = handling else {
+            commands.entity(entity).insert(VehicleSurfaceHandling::for_vehicle_type(&vehicle.vehicle_type));
+            continue;
+        };
+
+        let mut grip = 1.0 - (1.0 - weather_grip) * handling.grip_sensitivity;
+
+        let position = transform.translation();
+        let puddle_grip = zone_query
+            .iter()
+            .filter(|(zone, zone_transform)| vehicle.current_speed > zone.min_speed && zone.contains(zone_transform, position))
+            .map(|(zone, _)| zone.grip)
+            .reduce(f32::min);
+        handling.hydroplaning = vehicle.is_on_ground && puddle_grip.is_some() && handling.hydroplaning_sensitivity > 0.0;
+        if let Some(puddle_grip) = puddle_grip.filter(|_| handling.hydroplaning) {
+            grip = grip.min(1.0 - (1.0 - puddle_grip) * handling.hydroplaning_sensitivity);
+        }
+
+        let grip = grip.clamp(0.05, 1.0);
+        if (grip - handling.grip).abs() > f32::EPSILON {
+            handling.grip = grip;
+        }
+
+        if let Some(mut friction) = friction {
+            let base = *handling.base_friction.get_or_insert(friction.dynamic_coefficient);
+            let scaled = base * handling.grip;
+            if (friction.dynamic_coefficient - scaled).abs() > f32::EPSILON {
+                friction.dynamic_coefficient = scaled;
+            }
+        }
+    }
+}
+
+/// System to push wind-sensitive vehicles with the wind
+pub fn apply_vehicle_wind(
+    time: Res<Time>,
+    conditions: Res<SurfaceConditions>,
+    mut vehicle_query: Query<(&Vehicle, &VehicleSurfaceHandling, &mut LinearVelocity)>,
+) {
+    if conditions.wind == Vec3::ZERO {
+        return;
+    }
+    let delta = time.delta_secs();
+
+    for (vehicle, handling, mut velocity) in vehicle_query.iter_mut() {
+        if handling.wind_sensitivity <= 0.0 || matches!(vehicle.vehicle_type, VehicleType::Turret) {
+            continue;
+        }
+        // Grounded vehicles hold the road better
+        let exposure = if vehicle.is_on_ground { 0.3 } else { 1.0 };
+        velocity.0 += conditions.wind * conditions.wind_force_scale * handling.wind_sensitivity * exposure * delta;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vehicles::VehicleType;
+
+    #[test]
+    fn test_surface_conditions_grip() {
+        let mut surface = SurfaceConditions::default();
+        assert_eq!(surface.weather_grip(), 1.0);
+        surface.rain = 1.0;
+        assert!((surface.weather_grip() - surface.wet_grip).abs() < 1e-6);
+        surface.snow = 1.0;
+        assert!((surface.weather_grip() - surface.snow_grip).abs() < 1e-6);
+        let aircraft = VehicleSurfaceHandling::for_vehicle_type(&VehicleType::Aircraft);
+        assert_eq!(aircraft.grip_sensitivity, 0.0);
+        assert_eq!(aircraft.wind_sensitivity, 1.0);
+    }
+}
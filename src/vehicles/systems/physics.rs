@@ -1,16 +1,27 @@
 use bevy::prelude::*;
 use crate::vehicles::types::*;
+use crate::vehicles::surface::VehicleSurfaceHandling;
 use avian3d::prelude::*;
 
+type SimulatedVehicle<'a> = (
+    Entity,
+    &'a mut Vehicle,
+    &'a mut LinearVelocity,
+    &'a mut AngularVelocity,
+    &'a Transform,
+    &'a Children,
+    Option<&'a VehicleSurfaceHandling>,
+);
+
 pub fn update_vehicles_physics(
     time: Res<Time>,
-    mut query: Query<(Entity, &mut Vehicle, &mut LinearVelocity, &mut AngularVelocity, &Transform, &Children)>,
+    mut query: Query<SimulatedVehicle>,
     wheel_query: Query<&VehicleWheel>,
     spatial_query: SpatialQuery,
 ) {
     let delta = time.delta_secs();
 
-    for (entity, mut vehicle, mut velocity, mut angular_vel, transform, children) in query.iter_mut() {
+    for (entity, mut vehicle, mut velocity, mut angular_vel, transform, children, surface) in query.iter_mut() {
         let forward = transform.forward();
         let right = transform.right();
         let up = transform.up();
@@ -45,6 +56,9 @@ pub fn update_vehicles_physics(
         let current_right_speed = velocity.dot(*right);
         vehicle.current_speed = velocity.length();
 
+        // Wet, snowy or flooded ground limits what the tires can transmit
+        let grip = surface.map(|surface| surface.grip).unwrap_or(1.0);
+
         // Determine if reversing
         vehicle.is_reversing = vehicle.motor_input < 0.0 && current_forward_speed < 1.0;
 
@@ -58,9 +72,9 @@ pub fn update_vehicles_physics(
         // Calculate acceleration
         let speed_diff = target_speed - current_forward_speed;
         let acceleration = if vehicle.is_boosting {
-            vehicle.engine_torque * vehicle.boost_multiplier * delta
+            vehicle.engine_torque * vehicle.boost_multiplier * grip * delta
         } else {
-            vehicle.engine_torque * delta
+            vehicle.engine_torque * grip * delta
         };
 
         // Apply motor torque
@@ -71,7 +85,7 @@ pub fn update_vehicles_physics(
 
         // Apply braking
         if vehicle.is_braking || (vehicle.is_reversing && vehicle.motor_input > 0.0) {
-            let brake_force = vehicle.brake_power * delta;
+            let brake_force = vehicle.brake_power * grip * delta;
             velocity.0 -= *forward * brake_force * current_forward_speed.signum();
             velocity.0 -= *right * brake_force * current_right_speed.signum();
         }
@@ -85,14 +99,14 @@ pub fn update_vehicles_physics(
 
         // Apply steering torque
         let steer_effectiveness = (vehicle.current_speed / 10.0).clamp(0.0, 1.0);
-        let steer_torque = -vehicle.steer_input * steer_angle.to_radians() * steer_effectiveness;
+        let steer_torque = -vehicle.steer_input * steer_angle.to_radians() * steer_effectiveness * grip;
 
         if vehicle.is_turned_on && vehicle.is_on_ground {
             // Only apply standard steering if it's a ground vehicle
             if matches!(vehicle.vehicle_type, VehicleType::Car | VehicleType::Truck | VehicleType::Motorcycle | VehicleType::Hovercraft) {
                 angular_vel.y += steer_torque * delta * 2.0;
                 // Extra torque for better handling
-                angular_vel.y += -vehicle.steer_input * 2.0 * delta;
+                angular_vel.y += -vehicle.steer_input * 2.0 * grip * delta;
             }
         }
 
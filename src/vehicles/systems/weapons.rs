@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use crate::vehicles::types::*;
 use crate::input::InputState;
+use crate::combat::Health;
+use crate::weapons::{solve_lead, BallisticArc, BallisticShot, BallisticsEnvironment};
 use avian3d::prelude::*;
 
 pub fn update_vehicle_weapon_aiming(
@@ -148,3 +150,131 @@ pub fn update_vehicle_weapon_firing(
         }
     }
 }
+
+type LeadingVehicle<'a> = (
+    Entity,
+    &'a Vehicle,
+    &'a VehicleWeaponSystem,
+    &'a mut VehicleLeadIndicator,
+    &'a GlobalTransform,
+    Option<&'a LinearVelocity>,
+);
+
+/// Solves the lead of the current vehicle weapon on the target nearest the crosshair
+pub fn update_vehicle_lead_indicator(
+    environment: Option<Res<BallisticsEnvironment>>,
+    mut vehicle_query: Query<LeadingVehicle>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    target_query: Query<(Entity, &GlobalTransform, Option<&LinearVelocity>), With<Health>>,
+    global_transform_query: Query<&GlobalTransform>,
+) {
+    let camera_transform = camera_query
+        .iter()
+        .find(|(camera, _)| camera.is_active)
+        .map(|(_, transform)| transform);
+    let gravity = environment.map(|environment| environment.gravity).unwrap_or(Vec3::new(0.0, -9.81, 0.0));
+
+    for (vehicle_entity, vehicle, weapon_sys, mut indicator, v_gt, vehicle_velocity) in vehicle_query.iter_mut() {
+        let weapon = weapon_sys.weapons.get(weapon_sys.current_weapon_index);
+        let active = indicator.enabled
+            && vehicle.is_driving
+            && weapon_sys.weapons_activated
+            && weapon.is_some_and(|weapon| weapon.projectile_speed > 0.0 && weapon.weapon_type != VehicleWeaponType::Laser);
+        let (Some(weapon), Some(camera_transform), true) = (weapon, camera_transform, active) else {
+            if indicator.solution.is_some() || indicator.target.is_some() {
+                indicator.solution = None;
+                indicator.target = None;
+            }
+            continue;
+        };
+
+        // Fire from the barrel when there is one
+        let origin = weapon_sys
+            .base_y_entity
+            .and_then(|barrel| global_transform_query.get(barrel).ok())
+            .unwrap_or(v_gt)
+            .translation();
+
+        // Target closest to the crosshair
+        let forward = camera_transform.forward();
+        let min_dot = indicator.max_angle.to_radians().cos();
+        let target = target_query
+            .iter()
+            .filter(|(entity, _, _)| *entity != vehicle_entity)
+            .filter_map(|(entity, transform, velocity)| {
+                let offset = transform.translation() - camera_transform.translation();
+                let distance = offset.length();
+                if distance <= 0.1 || distance > indicator.max_distance {
+                    return None;
+                }
+                let dot = forward.dot(offset / distance);
+                (dot >= min_dot).then_some((entity, transform.translation(), velocity, dot))
+            })
+            .max_by(|a, b| a.3.total_cmp(&b.3));
+
+        // Projectiles inherit the vehicle's velocity
+        let solution = target.and_then(|(_, position, velocity, _)| {
+            let relative_velocity = velocity.map(|velocity| velocity.0).unwrap_or(Vec3::ZERO)
+                - vehicle_velocity.map(|velocity| velocity.0).unwrap_or(Vec3::ZERO);
+            let shot = BallisticShot::new(origin, weapon.projectile_speed).with_gravity(gravity);
+            solve_lead(&shot, position, relative_velocity, BallisticArc::Low)
+        });
+
+        let target = target.map(|(entity, ..)| entity);
+        if indicator.target != target || indicator.solution != solution {
+            indicator.target = target;
+            indicator.solution = solution;
+        }
+    }
+}
+
+/// Draws the predicted flight path of each lead solution with `draw_arc`
+pub fn draw_vehicle_lead_arcs(
+    environment: Option<Res<BallisticsEnvironment>>,
+    mut gizmos: Gizmos,
+    vehicle_query: Query<(&VehicleWeaponSystem, &VehicleLeadIndicator, &GlobalTransform)>,
+    global_transform_query: Query<&GlobalTransform>,
+) {
+    let gravity = environment.map(|environment| environment.gravity).unwrap_or(Vec3::new(0.0, -9.81, 0.0));
+
+    for (weapon_sys, indicator, v_gt) in vehicle_query.iter() {
+        let Some(solution) = indicator.solution.filter(|_| indicator.draw_arc) else { continue };
+        let Some(weapon) = weapon_sys.weapons.get(weapon_sys.current_weapon_index) else { continue };
+
+        let origin = weapon_sys
+            .base_y_entity
+            .and_then(|barrel| global_transform_query.get(barrel).ok())
+            .unwrap_or(v_gt)
+            .translation();
+        let shot = BallisticShot::new(origin, weapon.projectile_speed).with_gravity(gravity);
+        let points = shot.trajectory(solution.launch_velocity, solution.time_of_flight, 24);
+        gizmos.linestrip(points, Color::srgba(1.0, 0.6, 0.2, 0.8));
+    }
+}
+
+/// Places the lead marker over the aim point of the driven vehicle's solution
+pub fn update_vehicle_lead_marker(
+    indicator_query: Query<&VehicleLeadIndicator>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut marker_query: Query<(&mut Node, &mut Visibility, &ComputedNode), With<VehicleHudLeadMarker>>,
+) {
+    let screen_position = indicator_query
+        .iter()
+        .find_map(|indicator| indicator.solution.map(|solution| solution.aim_point))
+        .zip(camera_query.iter().find(|(camera, _)| camera.is_active))
+        .and_then(|(aim_point, (camera, camera_transform))| camera.world_to_viewport(camera_transform, aim_point).ok());
+
+    for (mut node, mut visibility, computed) in marker_query.iter_mut() {
+        let Some(position) = screen_position else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        // Center the marker on the point
+        let size = computed.size() * computed.inverse_scale_factor();
+        node.position_type = PositionType::Absolute;
+        node.left = Val::Px(position.x - size.x * 0.5);
+        node.top = Val::Px(position.y - size.y * 0.5);
+        *visibility = Visibility::Inherited;
+    }
+}
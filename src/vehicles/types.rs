@@ -86,7 +86,7 @@ pub struct Vehicle {
     pub using_gravity_control: bool,
 }
 
-#[derive(Debug, Clone, Reflect, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Reflect, Default, serde::Serialize, serde::Deserialize)]
 pub enum VehicleType {
     #[default]
     Car,
@@ -178,10 +178,12 @@ impl Default for Vehicle {
 
 /// Marker for the current driver of a vehicle
 #[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
 pub struct VehicleDriver;
 
 /// Vehicle seat component
 #[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
 pub struct VehicleSeat {
     pub seat_index: usize,
     pub is_driver_seat: bool,
@@ -220,6 +222,7 @@ pub struct VehicleSeatingManager {
 }
 
 #[derive(Component, Debug, Reflect, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
 pub enum PassengerState {
     Driving,
     Passenger,
@@ -227,6 +230,7 @@ pub enum PassengerState {
 }
 
 #[derive(Component, Debug, Reflect, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
 pub enum VehicleWeaponType {
     MachineGun,
     MissileLauncher,
@@ -451,6 +455,36 @@ pub struct VehicleWeaponSystem {
     pub rotation_speed: f32,
 }
 
+/// Lead indicator of the driven vehicle's projectile weapons: solves where to aim
+/// to hit the target nearest the crosshair, shown by a [`VehicleHudLeadMarker`]
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct VehicleLeadIndicator {
+    pub enabled: bool,
+    pub max_distance: f32,
+    /// Angle (degrees) around the camera forward in which targets are picked
+    pub max_angle: f32,
+    /// Draw the predicted flight path
+    pub draw_arc: bool,
+    /// Current target (read-only)
+    pub target: Option<Entity>,
+    /// Current solution (read-only)
+    pub solution: Option<crate::weapons::LeadSolution>,
+}
+
+impl Default for VehicleLeadIndicator {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_distance: 300.0,
+            max_angle: 15.0,
+            draw_arc: false,
+            target: None,
+            solution: None,
+        }
+    }
+}
+
 #[derive(Component, Reflect, Clone, Default)]
 #[reflect(Component)]
 pub struct VehicleDamageReceiver {
@@ -511,6 +545,11 @@ pub struct VehicleHudFuel;
 #[reflect(Component)]
 pub struct VehicleHudAmmo;
 
+/// Marker for the UI node placed over the lead point of the vehicle weapon
+#[derive(Component, Debug, Reflect, Default)]
+#[reflect(Component)]
+pub struct VehicleHudLeadMarker;
+
 /// Vehicle AI controller
 #[derive(Component, Debug, Reflect, Default)]
 #[reflect(Component)]
@@ -0,0 +1,56 @@
+//! Visual effects registry
+//!
+//! Gameplay modules play effects by key through the [`VfxRequestQueue`] instead of
+//! spawning their own meshes. The [`VfxRegistry`] maps each [`VfxKey`] (blood hit,
+//! spark, explosion, splash, heal, level-up or a custom name) to a particle prefab;
+//! particles come from an entity pool and effects far from the camera spawn with
+//! fewer particles or not at all.
+//!
+//! ```rust,ignore
+//! fn on_hit(mut vfx: ResMut<VfxRequestQueue>) {
+//!     vfx.spawn_directed(VfxKey::Spark, hit_point, hit_normal);
+//!     vfx.spawn(VfxKey::Custom("Confetti".into()), chest_position);
+//! }
+//! ```
+
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use crate::utils::{EventQueuePlugin, PoolPlugin};
+
+pub use types::{
+    VfxKey, VfxPrefab, VfxRegistry, VfxSettings, VfxRequest, VfxRequestQueue, VfxParticle,
+    VfxAssets,
+};
+
+pub struct VfxPlugin;
+
+impl Plugin for VfxPlugin {
+    fn build(&self, app: &mut App) {
+        let pool_size = app
+            .world()
+            .get_resource::<VfxSettings>()
+            .map(|settings| settings.pool_size)
+            .unwrap_or_else(|| VfxSettings::default().pool_size);
+
+        app
+            .init_resource::<VfxSettings>()
+            .init_resource::<VfxRegistry>()
+            .init_resource::<VfxAssets>()
+            .add_plugins((
+                EventQueuePlugin::<VfxRequest>::default(),
+                PoolPlugin::<VfxParticle>::new(pool_size),
+            ))
+            .register_type::<VfxSettings>()
+            .register_type::<VfxRegistry>()
+            .register_type::<VfxParticle>()
+            // Requests are made during Update
+            .add_systems(PostUpdate, (
+                spawn_vfx_requests,
+                update_vfx_particles,
+            ).chain());
+    }
+}
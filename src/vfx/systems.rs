@@ -0,0 +1,210 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::Rng;
+use super::types::*;
+use crate::utils::{EntityPool, QueueReader};
+
+/// Cached particle assets and the stores they are created in, missing in headless apps
+#[derive(SystemParam)]
+pub struct VfxRenderAssets<'w> {
+    pub assets: ResMut<'w, VfxAssets>,
+    pub meshes: Option<ResMut<'w, Assets<Mesh>>>,
+    pub materials: Option<ResMut<'w, Assets<StandardMaterial>>>,
+}
+
+/// Spawns particle bursts for queued requests, with fewer particles far from the camera
+pub fn spawn_vfx_requests(
+    mut commands: Commands,
+    mut requests: QueueReader<VfxRequest>,
+    settings: Res<VfxSettings>,
+    registry: Res<VfxRegistry>,
+    mut particle_pool: ResMut<EntityPool<VfxParticle>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    render_assets: VfxRenderAssets,
+) {
+    let VfxRenderAssets { mut assets, meshes, materials } = render_assets;
+    if !settings.enabled {
+        // Drop requests instead of bursting them all when re-enabled
+        requests.clear();
+        return;
+    }
+    let (Some(mut meshes), Some(mut materials)) = (meshes, materials) else { return };
+
+    let mesh = assets
+        .mesh
+        .get_or_insert_with(|| meshes.add(Mesh::from(Sphere::new(1.0))))
+        .clone();
+
+    let cameras: Vec<Vec3> = camera_query
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .map(|(_, transform)| transform.translation())
+        .collect();
+
+    let mut rng = rand::rng();
+    let mut budget = settings.max_particles_per_frame;
+
+    for request in requests.read() {
+        let Some(prefab) = registry.get(&request.key) else {
+            warn!("No VFX prefab registered for {:?}", request.key);
+            continue;
+        };
+
+        let distance = cameras
+            .iter()
+            .map(|camera| camera.distance(request.position))
+            .fold(None, |closest: Option<f32>, distance| Some(closest.map_or(distance, |c| c.min(distance))));
+
+        let detail = match distance {
+            Some(distance) if distance > settings.cull_distance => continue,
+            Some(distance) if distance > settings.lod_distance => settings.lod_particle_fraction,
+            _ => 1.0,
+        };
+
+        let count = ((prefab.particle_count as f32 * detail).ceil() as usize).min(budget);
+        if count == 0 {
+            continue;
+        }
+        budget -= count;
+
+        let color = request.color.unwrap_or(prefab.color);
+        let material_key = (request.key.clone(), request.color.map(color_bits));
+        let material = assets
+            .materials
+            .entry(material_key)
+            .or_insert_with(|| {
+                materials.add(StandardMaterial {
+                    base_color: color,
+                    emissive: color.to_linear() * prefab.emissive,
+                    unlit: prefab.emissive <= 0.0,
+                    ..default()
+                })
+            })
+            .clone();
+
+        let direction = request.direction.try_normalize().unwrap_or(Vec3::Y);
+        let orientation = Quat::from_rotation_arc(Vec3::Z, direction);
+        let cos_spread = prefab.spread.clamp(0.0, std::f32::consts::PI).cos();
+        let size = prefab.particle_size * request.scale;
+
+        for _ in 0..count {
+            // Uniform direction inside the spread cone
+            let cos_theta = rng.random_range(cos_spread.min(1.0)..=1.0);
+            let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+            let phi = rng.random_range(0.0..std::f32::consts::TAU);
+            let local = Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta);
+            let speed = rng.random_range(prefab.speed.x.min(prefab.speed.y)..=prefab.speed.y.max(prefab.speed.x));
+            let lifetime = rng.random_range(prefab.lifetime.x.min(prefab.lifetime.y)..=prefab.lifetime.y.max(prefab.lifetime.x));
+
+            particle_pool.acquire(&mut commands, (
+                Mesh3d(mesh.clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::from_translation(request.position).with_scale(Vec3::splat(size)),
+                GlobalTransform::default(),
+                VfxParticle {
+                    velocity: orientation * local * speed * request.scale,
+                    gravity: prefab.gravity,
+                    age: 0.0,
+                    lifetime: lifetime.max(0.01),
+                    start_scale: size,
+                    end_scale: size * prefab.end_scale,
+                },
+                Name::new("VfxParticle"),
+            ));
+        }
+
+        if budget == 0 {
+            break;
+        }
+    }
+
+    // Over budget: the rest of this frame's requests are dropped
+    requests.clear();
+}
+
+/// Moves and shrinks particles, returning finished ones to the pool
+pub fn update_vfx_particles(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut particle_pool: ResMut<EntityPool<VfxParticle>>,
+    mut query: Query<(Entity, &mut VfxParticle, &mut Transform)>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut particle, mut transform) in query.iter_mut() {
+        particle.age += dt;
+        if particle.age >= particle.lifetime {
+            particle_pool.release(&mut commands, entity);
+            continue;
+        }
+
+        let gravity = particle.gravity;
+        particle.velocity.y -= gravity * dt;
+        transform.translation += particle.velocity * dt;
+
+        let t = particle.age / particle.lifetime;
+        transform.scale = Vec3::splat(particle.start_scale.lerp(particle.end_scale, t));
+    }
+}
+
+/// Quantized color used to share materials between requests of the same tint
+fn color_bits(color: Color) -> [u8; 4] {
+    let color = color.to_srgba();
+    [color.red, color.green, color.blue, color.alpha].map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vfx::VfxPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    fn particle_count(app: &mut App) -> usize {
+        app.world_mut().query::<&VfxParticle>().iter(app.world()).count()
+    }
+
+    #[test]
+    fn test_requests_burst_by_distance_and_recycle_particles() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, VfxPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<Assets<Mesh>>()
+            .init_resource::<Assets<StandardMaterial>>();
+
+        let confetti = VfxKey::Custom("Confetti".into());
+        app.world_mut().resource_mut::<VfxRegistry>().register(confetti.clone(), VfxPrefab {
+            particle_count: 10,
+            lifetime: Vec2::splat(0.25),
+            ..default()
+        });
+        app.world_mut().spawn((Camera3d::default(), GlobalTransform::default()));
+
+        {
+            let mut vfx = app.world_mut().resource_mut::<VfxRequestQueue>();
+            vfx.spawn(confetti.clone(), Vec3::new(0.0, 0.0, 5.0));
+            // Past the LOD distance: ceil(10 * 0.35) particles
+            vfx.spawn(confetti.clone(), Vec3::new(0.0, 0.0, 50.0));
+            // Past the cull distance
+            vfx.spawn(confetti.clone(), Vec3::new(0.0, 0.0, 100.0));
+            vfx.spawn(VfxKey::Custom("Missing".into()), Vec3::ZERO);
+        }
+        app.update();
+
+        assert_eq!(particle_count(&mut app), 14);
+        assert_eq!(app.world().resource::<VfxAssets>().materials.len(), 1);
+
+        // Particles outlive their lifetime and go back to the pool
+        for _ in 0..4 {
+            app.update();
+        }
+        assert_eq!(particle_count(&mut app), 0);
+        let pool = app.world().resource::<EntityPool<VfxParticle>>();
+        assert_eq!((pool.stats.created, pool.stats.active, pool.available.len()), (14, 0, 14));
+
+        app.world_mut().resource_mut::<VfxRequestQueue>().spawn(confetti, Vec3::new(0.0, 0.0, 5.0));
+        app.update();
+        assert_eq!(particle_count(&mut app), 10);
+        let pool = app.world().resource::<EntityPool<VfxParticle>>();
+        assert_eq!((pool.stats.created, pool.stats.reused), (14, 10));
+    }
+}
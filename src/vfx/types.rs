@@ -0,0 +1,224 @@
+use bevy::prelude::*;
+use bevy::platform::collections::HashMap;
+use crate::utils::{EventQueue, Poolable};
+
+/// Name of a registered effect
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+pub enum VfxKey {
+    BloodHit,
+    /// Sparks off hard surfaces
+    Spark,
+    Explosion,
+    /// Water thrown up by bodies entering or leaving a fluid
+    Splash,
+    Heal,
+    LevelUp,
+    /// Game-specific effect registered under a name
+    Custom(String),
+}
+
+/// Particle burst spawned for one effect key
+#[derive(Debug, Clone, Reflect)]
+pub struct VfxPrefab {
+    pub color: Color,
+    /// Emissive strength relative to `color`; 0 for a matte effect
+    pub emissive: f32,
+    /// Particles spawned at full detail
+    pub particle_count: u32,
+    pub particle_size: f32,
+    /// Initial particle speed range
+    pub speed: Vec2,
+    /// Cone half-angle (radians) around the request direction; PI for a sphere
+    pub spread: f32,
+    /// Downward acceleration, negative to make particles rise
+    pub gravity: f32,
+    /// Particle lifetime range in seconds
+    pub lifetime: Vec2,
+    /// Scale multiplier reached at the end of a particle's life
+    pub end_scale: f32,
+}
+
+impl Default for VfxPrefab {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            emissive: 0.0,
+            particle_count: 8,
+            particle_size: 0.05,
+            speed: Vec2::new(1.0, 3.0),
+            spread: 0.8,
+            gravity: 9.81,
+            lifetime: Vec2::new(0.3, 0.6),
+            end_scale: 0.2,
+        }
+    }
+}
+
+/// Effect prefabs by key
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct VfxRegistry {
+    pub prefabs: HashMap<VfxKey, VfxPrefab>,
+}
+
+impl Default for VfxRegistry {
+    fn default() -> Self {
+        let mut prefabs = HashMap::new();
+        prefabs.insert(VfxKey::BloodHit, VfxPrefab {
+            color: Color::srgb(0.55, 0.05, 0.05),
+            particle_count: 10,
+            particle_size: 0.04,
+            speed: Vec2::new(1.0, 2.5),
+            spread: 0.7,
+            ..default()
+        });
+        prefabs.insert(VfxKey::Spark, VfxPrefab {
+            color: Color::srgb(1.0, 0.75, 0.3),
+            emissive: 4.0,
+            particle_count: 12,
+            particle_size: 0.025,
+            speed: Vec2::new(3.0, 6.0),
+            spread: 0.9,
+            lifetime: Vec2::new(0.15, 0.35),
+            ..default()
+        });
+        prefabs.insert(VfxKey::Explosion, VfxPrefab {
+            color: Color::srgb(1.0, 0.45, 0.1),
+            emissive: 6.0,
+            particle_count: 32,
+            particle_size: 0.2,
+            speed: Vec2::new(3.0, 9.0),
+            spread: std::f32::consts::PI,
+            gravity: 2.0,
+            lifetime: Vec2::new(0.4, 0.9),
+            end_scale: 2.0,
+        });
+        prefabs.insert(VfxKey::Splash, VfxPrefab {
+            color: Color::srgba(0.75, 0.88, 1.0, 0.8),
+            particle_count: 16,
+            particle_size: 0.06,
+            speed: Vec2::new(2.0, 4.5),
+            spread: 0.5,
+            lifetime: Vec2::new(0.4, 0.8),
+            end_scale: 0.4,
+            ..default()
+        });
+        prefabs.insert(VfxKey::Heal, VfxPrefab {
+            color: Color::srgb(0.3, 1.0, 0.45),
+            emissive: 2.0,
+            particle_count: 14,
+            particle_size: 0.06,
+            speed: Vec2::new(0.5, 1.2),
+            spread: 0.4,
+            gravity: -1.5,
+            lifetime: Vec2::new(0.8, 1.4),
+            end_scale: 0.0,
+        });
+        prefabs.insert(VfxKey::LevelUp, VfxPrefab {
+            color: Color::srgb(1.0, 0.85, 0.25),
+            emissive: 3.0,
+            particle_count: 24,
+            particle_size: 0.07,
+            speed: Vec2::new(1.0, 2.5),
+            spread: 0.6,
+            gravity: -2.0,
+            lifetime: Vec2::new(1.0, 1.8),
+            end_scale: 0.0,
+        });
+        Self { prefabs }
+    }
+}
+
+impl VfxRegistry {
+    /// Adds or replaces the prefab of `key`
+    pub fn register(&mut self, key: VfxKey, prefab: VfxPrefab) {
+        self.prefabs.insert(key, prefab);
+    }
+
+    pub fn get(&self, key: &VfxKey) -> Option<&VfxPrefab> {
+        self.prefabs.get(key)
+    }
+}
+
+/// Global effect settings
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct VfxSettings {
+    pub enabled: bool,
+    /// Beyond this camera distance effects spawn with reduced detail
+    pub lod_distance: f32,
+    /// Fraction of particles kept beyond `lod_distance`
+    pub lod_particle_fraction: f32,
+    /// Beyond this camera distance effects are skipped
+    pub cull_distance: f32,
+    /// Upper bound of particles spawned per frame
+    pub max_particles_per_frame: usize,
+    /// Particles kept in the pool
+    pub pool_size: usize,
+}
+
+impl Default for VfxSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            lod_distance: 25.0,
+            lod_particle_fraction: 0.35,
+            cull_distance: 80.0,
+            max_particles_per_frame: 256,
+            pool_size: 512,
+        }
+    }
+}
+
+/// Request to play an effect
+#[derive(Debug, Clone, PartialEq)]
+pub struct VfxRequest {
+    pub key: VfxKey,
+    pub position: Vec3,
+    /// Main direction of the burst (e.g. a surface normal)
+    pub direction: Vec3,
+    pub scale: f32,
+    /// Overrides the prefab color
+    pub color: Option<Color>,
+}
+
+/// Queue for effect requests
+pub type VfxRequestQueue = EventQueue<VfxRequest>;
+
+impl EventQueue<VfxRequest> {
+    /// Plays `key` at `position`, bursting upwards
+    pub fn spawn(&mut self, key: VfxKey, position: Vec3) {
+        self.spawn_directed(key, position, Vec3::Y);
+    }
+
+    /// Plays `key` at `position`, bursting along `direction`
+    pub fn spawn_directed(&mut self, key: VfxKey, position: Vec3, direction: Vec3) {
+        self.send(VfxRequest { key, position, direction, scale: 1.0, color: None });
+    }
+
+    pub fn spawn_colored(&mut self, key: VfxKey, position: Vec3, direction: Vec3, color: Color) {
+        self.send(VfxRequest { key, position, direction, scale: 1.0, color: Some(color) });
+    }
+}
+
+/// Live effect particle
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct VfxParticle {
+    pub velocity: Vec3,
+    pub gravity: f32,
+    pub age: f32,
+    pub lifetime: f32,
+    pub start_scale: f32,
+    pub end_scale: f32,
+}
+
+impl Poolable for VfxParticle {}
+
+/// Shared particle mesh and materials
+#[derive(Resource, Debug, Default)]
+pub struct VfxAssets {
+    pub mesh: Option<Handle<Mesh>>,
+    /// Materials by prefab key and color override
+    pub materials: HashMap<(VfxKey, Option<[u8; 4]>), Handle<StandardMaterial>>,
+}
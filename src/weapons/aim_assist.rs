@@ -0,0 +1,490 @@
+//! Aim assist
+//!
+//! Helps players aiming with a gamepad (or any device when
+//! [`AimAssistSettings::gamepad_only`] is off). The device is the one button
+//! prompts show, so picking up the mouse turns assist off straight away:
+//!
+//! - **Magnetism**: look input slows down while the reticle crosses a target
+//! - **Rotational magnetism**: while aiming, moving or turning pulls the view toward the target
+//! - **Bullet magnetism**: hitscan shots inside a small cone, or passing close to the
+//!   target, bend toward it
+//! - **ADS snap**: starting to aim turns the camera toward a nearby target
+//!
+//! Targets are ranked like lock-on candidates ([`lock_on_score`]), the locked
+//! target first, and assist aims at their chest bone ([`AimAssistTarget`]).
+//! The overall strength is the difficulty's strength times the accessibility
+//! multiplier, so players can turn it off or up independently of the
+//! difficulty; each weapon scales it again with its [`WeaponAimAssist`].
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::camera::{lock_on_score, CameraController, CameraState, CameraTargetState};
+use crate::character::Player;
+use crate::combat::Health;
+use crate::input::{GlyphProvider, InputDevice, InputState, PlayerInputSettings};
+use super::types::{Weapon, WeaponAimAssist};
+use super::weapon_manager::WeaponManager;
+
+/// Difficulty preset of the aim assist strength
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum AimAssistDifficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    /// No assist
+    Expert,
+}
+
+impl AimAssistDifficulty {
+    pub fn strength(self) -> f32 {
+        match self {
+            AimAssistDifficulty::Easy => 1.0,
+            AimAssistDifficulty::Normal => 0.75,
+            AimAssistDifficulty::Hard => 0.45,
+            AimAssistDifficulty::Expert => 0.0,
+        }
+    }
+}
+
+/// Accessibility setting applied on top of the difficulty
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum AimAssistLevel {
+    Off,
+    #[default]
+    Standard,
+    Strong,
+}
+
+impl AimAssistLevel {
+    pub fn multiplier(self) -> f32 {
+        match self {
+            AimAssistLevel::Off => 0.0,
+            AimAssistLevel::Standard => 1.0,
+            AimAssistLevel::Strong => 1.75,
+        }
+    }
+}
+
+/// Aim assist configuration
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct AimAssistSettings {
+    pub enabled: bool,
+    /// Only assist players using a gamepad
+    pub gamepad_only: bool,
+    pub difficulty: AimAssistDifficulty,
+    pub accessibility: AimAssistLevel,
+    pub max_distance: f32,
+    /// Angle (degrees) around the reticle in which look input slows down
+    pub magnetism_angle: f32,
+    /// Look speed reduction over a target at full strength (0-1)
+    pub magnetism_slowdown: f32,
+    /// Angle (degrees) in which rotational magnetism pulls the view
+    pub rotation_angle: f32,
+    /// How fast moving or turning pulls the view toward the target while aiming
+    pub rotational_magnetism: f32,
+    /// Cone (degrees) in which hitscan shots bend toward the target
+    pub bullet_magnetism_angle: f32,
+    /// Shots passing this close (meters) to the target bend toward it
+    pub bullet_magnetism_radius: f32,
+    /// Angle (degrees) in which aiming down sights snaps to a target
+    pub snap_angle: f32,
+    pub snap_speed: f32,
+    /// Seconds the snap keeps turning after aiming starts
+    pub snap_duration: f32,
+    pub require_line_of_sight: bool,
+    /// Aim point above the origin of targets without a chest bone
+    pub chest_height: f32,
+    /// Bone names searched for the chest bone of targets
+    pub chest_bone_names: Vec<String>,
+}
+
+impl Default for AimAssistSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            gamepad_only: true,
+            difficulty: AimAssistDifficulty::Normal,
+            accessibility: AimAssistLevel::Standard,
+            max_distance: 60.0,
+            magnetism_angle: 6.0,
+            magnetism_slowdown: 0.5,
+            rotation_angle: 10.0,
+            rotational_magnetism: 3.0,
+            bullet_magnetism_angle: 2.5,
+            bullet_magnetism_radius: 0.6,
+            snap_angle: 12.0,
+            snap_speed: 12.0,
+            snap_duration: 0.25,
+            require_line_of_sight: true,
+            chest_height: 1.3,
+            chest_bone_names: ["Chest", "Spine2", "mixamorig:Spine2", "spine_03", "UpperChest"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        }
+    }
+}
+
+impl AimAssistSettings {
+    /// Overall strength; angles and slowdown scale with it
+    pub fn strength(&self) -> f32 {
+        if !self.enabled {
+            return 0.0;
+        }
+        self.difficulty.strength() * self.accessibility.multiplier()
+    }
+}
+
+/// Where aim assist aims on a target; added to everything with `Health`
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct AimAssistTarget {
+    pub enabled: bool,
+    /// Found by name among the descendants unless set
+    pub chest_bone: Option<Entity>,
+    /// Descendants were searched without finding a chest bone
+    pub searched: bool,
+}
+
+impl Default for AimAssistTarget {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            chest_bone: None,
+            searched: false,
+        }
+    }
+}
+
+/// Aim assist target of the player this frame
+#[derive(Resource, Debug, Default)]
+pub struct AimAssistState {
+    pub player: Option<Entity>,
+    /// Assist applies to the player this frame
+    pub active: bool,
+    pub target: Option<Entity>,
+    pub target_point: Option<Vec3>,
+    /// Angle (degrees) between the view center and the target
+    pub target_angle: f32,
+    /// Tuning of the player's current weapon
+    pub weapon: WeaponAimAssist,
+    /// Movement and look input this frame (0-1), driving rotational magnetism
+    pub turn_input: f32,
+    pub snap_timer: f32,
+    pub was_aiming: bool,
+}
+
+impl AimAssistState {
+    /// Direction of a hitscan shot from `origin` along `direction`, bent toward the target inside the cone
+    pub fn bullet_magnetism(&self, settings: &AimAssistSettings, shooter: Entity, origin: Vec3, direction: Vec3) -> Vec3 {
+        if !self.active || self.player != Some(shooter) {
+            return direction;
+        }
+        let Some(point) = self.target_point else { return direction };
+        let to_target = (point - origin).normalize_or_zero();
+        if to_target == Vec3::ZERO {
+            return direction;
+        }
+
+        let strength = settings.strength() * self.weapon.bullet_magnetism;
+        let cone = settings.bullet_magnetism_angle * strength;
+        let along = (point - origin).dot(direction);
+        let miss = (point - origin - direction * along).length();
+        if direction.angle_between(to_target).to_degrees() <= cone
+            || (along > 0.0 && miss <= settings.bullet_magnetism_radius * strength)
+        {
+            to_target
+        } else {
+            direction
+        }
+    }
+}
+
+type NewAimAssistTarget = (With<Health>, Without<Player>, Without<AimAssistTarget>);
+
+/// System to give targets an `AimAssistTarget` and find their chest bone
+pub fn find_aim_assist_chest_bones(
+    mut commands: Commands,
+    settings: Res<AimAssistSettings>,
+    new_targets: Query<Entity, NewAimAssistTarget>,
+    mut target_query: Query<(Entity, &mut AimAssistTarget)>,
+    children_query: Query<&Children>,
+    name_query: Query<&Name>,
+) {
+    for entity in new_targets.iter() {
+        commands.entity(entity).insert(AimAssistTarget::default());
+    }
+
+    for (entity, mut target) in target_query.iter_mut() {
+        if target.chest_bone.is_some() || target.searched {
+            continue;
+        }
+        // Models spawn their skeleton a few frames late; wait for it
+        if !children_query.contains(entity) {
+            continue;
+        }
+        target.chest_bone = children_query.iter_descendants(entity).find(|descendant| {
+            name_query
+                .get(*descendant)
+                .is_ok_and(|name| settings.chest_bone_names.iter().any(|bone| bone == name.as_str()))
+        });
+        target.searched = target.chest_bone.is_none();
+    }
+}
+
+type AimAssistedPlayer<'a> = (
+    Entity,
+    Option<&'a mut InputState>,
+    Option<&'a PlayerInputSettings>,
+    Option<&'a WeaponManager>,
+);
+
+type AimAssistCandidate<'a> = (Entity, &'a GlobalTransform, &'a Health, Option<&'a AimAssistTarget>);
+
+/// The assisted player and the weapon it holds
+#[derive(SystemParam)]
+pub struct AimAssistShooter<'w, 's> {
+    pub player_query: Query<'w, 's, AimAssistedPlayer<'static>, With<Player>>,
+    pub weapon_query: Query<'w, 's, &'static Weapon>,
+}
+
+/// Targets, their chest bones and the line of sight to them
+#[derive(SystemParam)]
+pub struct AimAssistTargets<'w, 's> {
+    pub spatial_query: SpatialQuery<'w, 's>,
+    pub target_query: Query<'w, 's, AimAssistCandidate<'static>, Without<Player>>,
+    pub bone_query: Query<'w, 's, &'static GlobalTransform>,
+}
+
+/// System to pick the aim assist target and slow down look input over it
+pub fn update_aim_assist(
+    settings: Res<AimAssistSettings>,
+    mut state: ResMut<AimAssistState>,
+    mut global_input: ResMut<InputState>,
+    glyphs: Option<Res<GlyphProvider>>,
+    shooter: AimAssistShooter,
+    camera_query: Query<(&GlobalTransform, Option<&CameraTargetState>), With<CameraController>>,
+    targets: AimAssistTargets,
+) {
+    let AimAssistShooter { mut player_query, weapon_query } = shooter;
+    let AimAssistTargets { spatial_query, target_query, bone_query } = targets;
+    state.active = false;
+    state.target = None;
+    state.target_point = None;
+    state.turn_input = 0.0;
+
+    let Some((player, mut player_input, input_settings, manager)) = player_query.iter_mut().next() else { return };
+    state.player = Some(player);
+    state.weapon = manager
+        .and_then(|manager| manager.weapons_list.get(manager.current_index))
+        .and_then(|weapon| weapon_query.get(*weapon).ok())
+        .map_or_else(WeaponAimAssist::default, |weapon| weapon.aim_assist);
+
+    let strength = settings.strength();
+    // The device in use wins over the configured one
+    let uses_gamepad = match glyphs {
+        Some(glyphs) => glyphs.active_device.is_gamepad(),
+        None => input_settings.is_some_and(|input_settings| matches!(input_settings.device, InputDevice::Gamepad { .. })),
+    };
+    if strength <= 0.0 || !state.weapon.enabled || (settings.gamepad_only && !uses_gamepad) {
+        state.snap_timer = 0.0;
+        return;
+    }
+    let Some((camera, target_state)) = camera_query.iter().next() else { return };
+    state.active = true;
+
+    let origin = camera.translation();
+    let forward = camera.forward();
+    let locked = target_state.and_then(|target_state| target_state.locked_target);
+    let search_angle = settings
+        .magnetism_angle
+        .max(settings.rotation_angle)
+        .max(settings.snap_angle)
+        .max(settings.bullet_magnetism_angle)
+        * strength;
+
+    let mut best: Option<(f32, Entity, Vec3, f32)> = None;
+    for (entity, transform, health, target) in target_query.iter() {
+        if health.is_dead || health.current <= 0.0 || target.is_some_and(|target| !target.enabled) {
+            continue;
+        }
+        let point = target
+            .and_then(|target| target.chest_bone)
+            .and_then(|bone| bone_query.get(bone).ok())
+            .map_or(transform.translation() + Vec3::Y * settings.chest_height, |bone| bone.translation());
+        let to_target = point - origin;
+        let distance = to_target.length();
+        if distance <= f32::EPSILON || distance > settings.max_distance {
+            continue;
+        }
+        let angle = forward.angle_between(to_target / distance).to_degrees();
+        if angle > search_angle {
+            continue;
+        }
+        if settings.require_line_of_sight && !has_line_of_sight(&spatial_query, origin, point, entity, player) {
+            continue;
+        }
+
+        // The locked target wins, the rest are ranked like lock-on candidates
+        let score = if locked == Some(entity) { f32::MIN } else { lock_on_score(distance, angle) };
+        if best.is_none_or(|(best_score, ..)| score < best_score) {
+            best = Some((score, entity, point, angle));
+        }
+    }
+
+    let Some((_, target, point, angle)) = best else { return };
+    state.target = Some(target);
+    state.target_point = Some(point);
+    state.target_angle = angle;
+    let input = player_input.as_deref().unwrap_or(&*global_input);
+    state.turn_input = (input.movement.length() + input.look.length()).min(1.0);
+
+    let magnetism_angle = settings.magnetism_angle * strength;
+    if angle <= magnetism_angle {
+        let falloff = 1.0 - angle / magnetism_angle.max(0.001);
+        let factor = 1.0 - (settings.magnetism_slowdown * strength * state.weapon.slowdown * falloff).clamp(0.0, 0.9);
+        global_input.look *= factor;
+        global_input.aim_turn *= factor;
+        if let Some(input) = player_input.as_mut() {
+            input.look *= factor;
+            input.aim_turn *= factor;
+        }
+    }
+}
+
+fn has_line_of_sight(spatial_query: &SpatialQuery, origin: Vec3, point: Vec3, target: Entity, player: Entity) -> bool {
+    let to_target = point - origin;
+    let distance = to_target.length();
+    let Ok(direction) = Dir3::new(to_target) else { return true };
+
+    match spatial_query.cast_ray(origin, direction, distance, true, &SpatialQueryFilter::from_excluded_entities([player])) {
+        None => true,
+        // Colliders on the target's children stop the ray just short of its origin
+        Some(hit) => hit.entity == target || hit.distance >= distance - 0.5,
+    }
+}
+
+/// System to turn the camera toward the target when aiming down sights starts,
+/// and to pull it along while the player moves or turns
+pub fn apply_aim_assist_snap(
+    time: Res<Time>,
+    settings: Res<AimAssistSettings>,
+    mut state: ResMut<AimAssistState>,
+    mut camera_query: Query<(&mut CameraState, Option<&CameraTargetState>), With<CameraController>>,
+) {
+    let Some((mut camera, target_state)) = camera_query.iter_mut().next() else { return };
+    let aiming = camera.is_aiming;
+    let started_aiming = aiming && !state.was_aiming;
+    state.was_aiming = aiming;
+
+    // Lock-on already turns the camera
+    if !aiming || !state.active || target_state.is_some_and(|target_state| target_state.is_locking) {
+        state.snap_timer = 0.0;
+        return;
+    }
+
+    let strength = settings.strength();
+    if started_aiming && state.target.is_some() && state.target_angle <= settings.snap_angle * strength * state.weapon.snap {
+        state.snap_timer = settings.snap_duration;
+    }
+    let Some(point) = state.target_point else { return };
+    let speed = if state.snap_timer > 0.0 {
+        state.snap_timer -= time.delta_secs();
+        settings.snap_speed * strength
+    } else if state.target_angle <= settings.rotation_angle * strength {
+        settings.rotational_magnetism * strength * state.weapon.rotation * state.turn_input
+    } else {
+        0.0
+    };
+    if speed <= 0.0 {
+        return;
+    }
+
+    let direction = (point - camera.current_pivot).normalize_or_zero();
+    if direction == Vec3::ZERO {
+        return;
+    }
+    let target_yaw = direction.x.atan2(direction.z).to_degrees();
+    let target_pitch = (-direction.y).asin().to_degrees();
+
+    let alpha = 1.0 - (-speed * time.delta_secs()).exp();
+    let yaw_delta = (target_yaw - camera.yaw + 540.0).rem_euclid(360.0) - 180.0;
+    camera.yaw += yaw_delta * alpha;
+    camera.pitch += (target_pitch - camera.pitch) * alpha;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weapons::{WeaponAimAssist, WeaponType};
+
+    #[test]
+    fn test_aim_assist_bullet_magnetism_radius() {
+        let settings = AimAssistSettings::default();
+        let shooter = Entity::from_raw_u32(1).unwrap();
+        let mut state = AimAssistState {
+            player: Some(shooter),
+            active: true,
+            target_point: Some(Vec3::new(0.4, 0.0, -5.0)),
+            ..Default::default()
+        };
+
+        // 0.4 m off at 5 m is outside the cone but inside the radius
+        let bent = state.bullet_magnetism(&settings, shooter, Vec3::ZERO, Vec3::NEG_Z);
+        assert!(bent.x > 0.0);
+
+        state.weapon = WeaponAimAssist::for_weapon_type(WeaponType::Bow);
+        assert_eq!(state.bullet_magnetism(&settings, shooter, Vec3::ZERO, Vec3::NEG_Z), Vec3::NEG_Z);
+    }
+
+    #[test]
+    fn test_aim_assist_slows_look_and_snaps_on_aim() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin, PhysicsPlugins::default()))
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(std::time::Duration::from_millis(100)))
+            .init_resource::<Assets<Mesh>>()
+            .insert_resource(AimAssistSettings { gamepad_only: false, ..default() })
+            .init_resource::<AimAssistState>()
+            .init_resource::<InputState>()
+            .add_systems(Update, (
+                find_aim_assist_chest_bones,
+                update_aim_assist,
+                apply_aim_assist_snap,
+            ).chain());
+
+        app.world_mut().spawn(Player);
+        let camera = app.world_mut().spawn((CameraController::default(), CameraState::default(), Transform::default())).id();
+        // Chest point at (0.5, 0, -10): about 3 degrees off the view center
+        let target = app.world_mut().spawn((Health::default(), Transform::from_xyz(0.5, -1.3, -10.0))).id();
+        for _ in 0..2 {
+            app.update();
+        }
+        assert!(app.world().get::<AimAssistTarget>(target).is_some());
+
+        app.insert_resource(InputState { look: Vec2::X, ..default() });
+        app.update();
+        assert_eq!(app.world().resource::<AimAssistState>().target, Some(target));
+        let look = app.world().resource::<InputState>().look.x;
+        assert!(look > 0.0 && look < 1.0);
+
+        // Starting to aim down sights turns the camera toward the chest
+        let target_yaw = 0.5f32.atan2(-10.0).to_degrees();
+        let before = (target_yaw - app.world().get::<CameraState>(camera).unwrap().yaw + 540.0).rem_euclid(360.0) - 180.0;
+        app.insert_resource(InputState::default());
+        app.world_mut().get_mut::<CameraState>(camera).unwrap().is_aiming = true;
+        app.update();
+        assert!(app.world().resource::<AimAssistState>().snap_timer > 0.0);
+        let after = (target_yaw - app.world().get::<CameraState>(camera).unwrap().yaw + 540.0).rem_euclid(360.0) - 180.0;
+        assert!(after.abs() < before.abs());
+
+        // A wall in between hides the target
+        app.world_mut().spawn((RigidBody::Static, Collider::cuboid(4.0, 4.0, 0.5), Transform::from_xyz(0.0, 0.0, -5.0)));
+        for _ in 0..2 {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<AimAssistState>().target, None);
+    }
+}
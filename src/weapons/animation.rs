@@ -5,7 +5,7 @@
 use bevy::prelude::*;
 use super::types::{Weapon, WeaponAnimationState, WeaponAnimationMode};
 use crate::weapons::weapon_manager::WeaponManager;
-use crate::input::InputState;
+use crate::input::{InputAction, InputState};
 use crate::character::{CharacterAnimationState, CharacterAnimationMode, Player};
 
 /// Automatically add WeaponAnimationState to entities with a Weapon component
@@ -54,7 +54,7 @@ pub fn handle_weapon_animation(
                     is_looping = false;
                 } 
                 // 2. Aim transitions
-                else if input.aim_pressed && anim_state.current_mode != WeaponAnimationMode::AimIn && !manager.aim_mode_input_pressed {
+                else if input.intends(InputAction::Aim) && anim_state.current_mode != WeaponAnimationMode::AimIn && !manager.aim_mode_input_pressed {
                      next_mode = WeaponAnimationMode::AimIn;
                      is_looping = false;
                 }
@@ -1,48 +1,502 @@
 use bevy::prelude::*;
 use super::types::*;
-use super::types::*;
+use crate::abilities::ProjectileCatch;
+use crate::hud::{HudElement, HudPriority};
+use crate::inventory::Equipment;
+use crate::utils::{EventQueue, QueueReader};
 
 #[derive(Debug, Clone, Copy, Event)]
 pub struct ReturnProjectilesEvent {
+    /// `ArmorSurface` or `ProjectileCatch` holding the projectiles
     pub armor_entity: Entity,
     pub direction: Vec3,
+    /// Speed of the returned projectiles; `None` for the standard return speed
+    pub speed: Option<f32>,
 }
 
-#[derive(Resource, Default)]
-pub struct ReturnProjectilesQueue(pub Vec<ReturnProjectilesEvent>);
+pub type ReturnProjectilesQueue = EventQueue<ReturnProjectilesEvent>;
 
-/// System to handle returning "caught" projectiles from an ArmorSurface
+/// System to handle returning "caught" projectiles from an ArmorSurface or ProjectileCatch
 pub fn handle_armor_projectile_return(
-    mut events_queue: ResMut<ReturnProjectilesQueue>,
-    mut armor_query: Query<&mut ArmorSurface>,
-    mut projectile_query: Query<(&mut Projectile, &mut Transform, &CapturedProjectile, &mut Visibility)>,
+    mut events_queue: QueueReader<ReturnProjectilesEvent>,
+    mut armor_query: Query<(Option<&mut ArmorSurface>, Option<&mut ProjectileCatch>)>,
+    mut projectile_query: Query<(&mut Projectile, &mut Transform, &GlobalTransform, &CapturedProjectile, &mut Visibility)>,
     mut commands: Commands,
 ) {
-    for event in events_queue.0.drain(..) {
-        if let Ok(mut armor) = armor_query.get_mut(event.armor_entity) {
-            let projectiles_to_return = std::mem::take(&mut armor.caught_projectiles);
-            
-            for proj_entity in projectiles_to_return {
-                if let Ok((mut projectile, mut transform, _captured, mut visibility)) = projectile_query.get_mut(proj_entity) {
-                    // Reset projectile state
-                    projectile.owner = armor.owner.unwrap_or(proj_entity);
-                    projectile.velocity = event.direction.normalize() * 15.0; // Standard return speed
-                    
-                    // Re-enable projectile
-                    *visibility = Visibility::Visible;
-                    
-                    // Unparent and remove captured marker
-                    commands.entity(proj_entity)
-                        .remove::<ChildOf>()
-                        .remove::<CapturedProjectile>();
-                    
-                    // Point toward direction
-                    transform.look_to(projectile.velocity.normalize(), Vec3::Y);
-                    
-                    // Resume physics if using RigidBody (assuming it was set to kinematic)
-                    // Note: Projectile system handles standard movement
+    for event in events_queue.read().copied() {
+        let Ok((armor, catch)) = armor_query.get_mut(event.armor_entity) else { continue };
+        let (owner, projectiles_to_return) = match (armor, catch) {
+            (Some(mut armor), _) => (armor.owner.unwrap_or(event.armor_entity), std::mem::take(&mut armor.caught_projectiles)),
+            (None, Some(mut catch)) => (event.armor_entity, std::mem::take(&mut catch.stored)),
+            (None, None) => continue,
+        };
+        let speed = event.speed.unwrap_or(15.0); // Standard return speed
+        let Some(direction) = event.direction.try_normalize() else { continue };
+
+        for proj_entity in projectiles_to_return {
+            if let Ok((mut projectile, mut transform, global_transform, _captured, mut visibility)) = projectile_query.get_mut(proj_entity) {
+                // Reset projectile state
+                projectile.owner = owner;
+                projectile.velocity = direction * speed;
+
+                // Re-enable projectile
+                *visibility = Visibility::Visible;
+
+                // Unparent where it was held and remove captured marker
+                transform.translation = global_transform.translation();
+                commands.entity(proj_entity)
+                    .remove::<ChildOf>()
+                    .remove::<CapturedProjectile>();
+
+                // Point toward direction
+                transform.look_to(direction, Vec3::Y);
+            }
+        }
+    }
+}
+
+/// Body slot covered by an armor piece
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
+pub enum ArmorSlot {
+    Head,
+    #[default]
+    Chest,
+    Hands,
+    Legs,
+    Feet,
+}
+
+/// Look of an armor piece at or below a condition threshold
+#[derive(Debug, Clone, Reflect)]
+pub struct ArmorDamageState {
+    /// Condition (0-1) at or below which this state applies
+    pub threshold: f32,
+    /// Material swapped onto the piece's visuals; the original is tinted when `None`
+    pub material: Option<Handle<StandardMaterial>>,
+    pub tint: Color,
+}
+
+/// Armor piece with its own condition
+#[derive(Debug, Clone, Reflect)]
+pub struct ArmorPiece {
+    pub item_id: String,
+    pub name: String,
+    pub slot: ArmorSlot,
+    /// Fraction of physical damage absorbed in perfect condition
+    pub mitigation: f32,
+    /// Part of `mitigation` still applied just before the piece breaks
+    pub min_effectiveness: f32,
+    pub durability: f32,
+    pub max_durability: f32,
+    /// Durability lost per point of damage absorbed
+    pub wear_rate: f32,
+    /// Currency per durability point restored at a vendor
+    pub repair_cost_per_point: f32,
+    /// Damage states, highest threshold first
+    pub damage_states: Vec<ArmorDamageState>,
+    /// Index of the damage state last reported
+    pub shown_state: Option<usize>,
+}
+
+impl ArmorPiece {
+    pub fn new(item_id: &str, name: &str, slot: ArmorSlot, mitigation: f32, max_durability: f32) -> Self {
+        Self {
+            item_id: item_id.to_string(),
+            name: name.to_string(),
+            slot,
+            mitigation,
+            min_effectiveness: 0.4,
+            durability: max_durability,
+            max_durability,
+            wear_rate: 0.5,
+            repair_cost_per_point: 1.0,
+            damage_states: vec![
+                ArmorDamageState { threshold: 0.5, material: None, tint: Color::srgb(0.75, 0.7, 0.65) },
+                ArmorDamageState { threshold: 0.2, material: None, tint: Color::srgb(0.45, 0.38, 0.34) },
+            ],
+            shown_state: None,
+        }
+    }
+
+    /// Remaining durability, 0-1
+    pub fn condition(&self) -> f32 {
+        if self.max_durability <= 0.0 {
+            return 0.0;
+        }
+        (self.durability / self.max_durability).clamp(0.0, 1.0)
+    }
+
+    pub fn is_broken(&self) -> bool {
+        self.durability <= 0.0
+    }
+
+    /// Mitigation at the current condition
+    pub fn effective_mitigation(&self) -> f32 {
+        if self.is_broken() {
+            return 0.0;
+        }
+        let effectiveness = self.min_effectiveness + (1.0 - self.min_effectiveness) * self.condition();
+        (self.mitigation * effectiveness).clamp(0.0, 1.0)
+    }
+
+    /// Index of the damage state matching the current condition
+    pub fn damage_state(&self) -> Option<usize> {
+        let condition = self.condition();
+        self.damage_states
+            .iter()
+            .rposition(|state| condition <= state.threshold)
+    }
+
+    pub fn wear(&mut self, absorbed: f32) {
+        self.durability = (self.durability - absorbed * self.wear_rate).max(0.0);
+    }
+
+    /// Restores up to `amount` durability, returns how much was restored
+    pub fn repair(&mut self, amount: f32) -> f32 {
+        let restored = amount.min(self.max_durability - self.durability).max(0.0);
+        self.durability += restored;
+        restored
+    }
+
+    /// Vendor price of a full repair
+    pub fn repair_cost(&self) -> f32 {
+        (self.max_durability - self.durability).max(0.0) * self.repair_cost_per_point
+    }
+}
+
+/// Mesh showing a worn armor piece; swaps material with the piece's damage state
+/// and hides while the slot is empty
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ArmorVisual {
+    /// Character wearing the piece
+    pub owner: Entity,
+    pub slot: ArmorSlot,
+    #[reflect(ignore)]
+    pub original_material: Option<Handle<StandardMaterial>>,
+    #[reflect(ignore)]
+    pub tinted_materials: std::collections::HashMap<usize, Handle<StandardMaterial>>,
+}
+
+impl ArmorVisual {
+    pub fn new(owner: Entity, slot: ArmorSlot) -> Self {
+        Self {
+            owner,
+            slot,
+            original_material: None,
+            tinted_materials: Default::default(),
+        }
+    }
+}
+
+/// Armor condition notifications
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArmorEvent {
+    /// A worn piece reached another damage state (`None` once repaired)
+    StateChanged { owner: Entity, slot: ArmorSlot, state: Option<usize> },
+    /// A worn piece broke and was moved to `Equipment::stored_armor`
+    Broken { owner: Entity, slot: ArmorSlot, item_id: String },
+    Repaired { owner: Entity, item_id: String, amount: f32 },
+}
+
+/// Queue for armor events
+pub type ArmorEventQueue = EventQueue<ArmorEvent>;
+
+/// Repair of worn and stored armor, e.g. from a workbench or repair kit
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArmorRepairRequest {
+    pub owner: Entity,
+    /// Only this slot; every piece when `None`
+    pub slot: Option<ArmorSlot>,
+    /// Durability restored per piece, as a fraction of its maximum
+    pub fraction: f32,
+}
+
+/// Queue for armor repair requests
+pub type ArmorRepairQueue = EventQueue<ArmorRepairRequest>;
+
+/// Armor warnings shown on the HUD
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct ArmorWarningSettings {
+    pub enabled: bool,
+    /// Seconds a warning stays on screen
+    pub duration: f32,
+    /// Warn once a worn piece reaches this damage state index
+    pub warn_from_state: usize,
+}
+
+impl Default for ArmorWarningSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            duration: 3.0,
+            warn_from_state: 1,
+        }
+    }
+}
+
+/// HUD text for armor warnings
+#[derive(Component)]
+pub struct ArmorWarningText {
+    pub timer: f32,
+}
+
+/// System to unequip broken armor and report damage state changes
+pub fn update_armor_condition(
+    mut equipment_query: Query<(Entity, &mut Equipment), Changed<Equipment>>,
+    mut events: ResMut<ArmorEventQueue>,
+) {
+    for (owner, mut equipment) in equipment_query.iter_mut() {
+        let needs_update = equipment
+            .armor_pieces
+            .iter()
+            .any(|piece| piece.is_broken() || piece.damage_state() != piece.shown_state);
+        if !needs_update {
+            continue;
+        }
+
+        let mut index = 0;
+        while index < equipment.armor_pieces.len() {
+            let piece = &mut equipment.armor_pieces[index];
+            if piece.is_broken() {
+                let piece = equipment.armor_pieces.remove(index);
+                events.send(ArmorEvent::Broken { owner, slot: piece.slot, item_id: piece.item_id.clone() });
+                equipment.stored_armor.push(piece);
+                continue;
+            }
+
+            let state = piece.damage_state();
+            if state != piece.shown_state {
+                piece.shown_state = state;
+                events.send(ArmorEvent::StateChanged { owner, slot: piece.slot, state });
+            }
+            index += 1;
+        }
+    }
+}
+
+/// System to repair armor from repair requests
+pub fn handle_armor_repair_requests(
+    mut requests: QueueReader<ArmorRepairRequest>,
+    mut equipment_query: Query<&mut Equipment>,
+    mut events: ResMut<ArmorEventQueue>,
+) {
+    for request in requests.read() {
+        let Ok(mut equipment) = equipment_query.get_mut(request.owner) else { continue };
+        let equipment = &mut *equipment;
+        let fraction = request.fraction.max(0.0);
+        for piece in equipment.armor_pieces.iter_mut().chain(equipment.stored_armor.iter_mut()) {
+            if request.slot.is_some_and(|slot| slot != piece.slot) {
+                continue;
+            }
+            let amount = piece.repair(piece.max_durability * fraction);
+            if amount > 0.0 {
+                events.send(ArmorEvent::Repaired { owner: request.owner, item_id: piece.item_id.clone(), amount });
+            }
+        }
+    }
+}
+
+/// System to show worn armor's damage state on its visuals
+pub fn update_armor_visuals(
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    equipment_query: Query<Ref<Equipment>>,
+    mut visual_query: Query<(&mut ArmorVisual, &mut MeshMaterial3d<StandardMaterial>, &mut Visibility)>,
+) {
+    for (mut visual, mut material, mut visibility) in visual_query.iter_mut() {
+        let Ok(equipment) = equipment_query.get(visual.owner) else { continue };
+        if !equipment.is_changed() && !visual.is_added() {
+            continue;
+        }
+        let visual = visual.into_inner();
+
+        let Some(piece) = equipment.armor_in(visual.slot) else {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        };
+        visibility.set_if_neq(Visibility::Inherited);
+
+        let original = visual.original_material.get_or_insert_with(|| material.0.clone()).clone();
+        let wanted = match piece.damage_state() {
+            None => original,
+            Some(index) => {
+                let state = &piece.damage_states[index];
+                match &state.material {
+                    Some(swap) => swap.clone(),
+                    None => visual
+                        .tinted_materials
+                        .entry(index)
+                        .or_insert_with(|| {
+                            let mut tinted = materials.get(&original).cloned().unwrap_or_default();
+                            let base = tinted.base_color.to_linear();
+                            let tint = state.tint.to_linear();
+                            tinted.base_color = Color::linear_rgba(
+                                base.red * tint.red,
+                                base.green * tint.green,
+                                base.blue * tint.blue,
+                                base.alpha,
+                            );
+                            materials.add(tinted)
+                        })
+                        .clone(),
                 }
             }
+        };
+
+        if material.0 != wanted {
+            material.0 = wanted;
         }
     }
 }
+
+/// System to setup the armor warning text (hidden by default)
+pub fn setup_armor_warning_ui(mut commands: Commands) {
+    commands.spawn((
+        Text::new(""),
+        TextFont { font_size: 20.0, ..default() },
+        TextColor(Color::srgb(1.0, 0.55, 0.2)),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(120.0),
+            left: Val::Percent(50.0),
+            ..default()
+        },
+        Visibility::Hidden,
+        ArmorWarningText { timer: 0.0 },
+        HudElement::new("armor_warning", HudPriority::Standard),
+    ));
+}
+
+/// System to warn the player about badly damaged and broken armor
+pub fn update_armor_warning_ui(
+    mut events: QueueReader<ArmorEvent>,
+    settings: Res<ArmorWarningSettings>,
+    player_query: Query<(), With<crate::character::Player>>,
+    mut text_query: Query<(&mut Text, &mut Visibility, &mut ArmorWarningText)>,
+    time: Res<Time>,
+) {
+    let Ok((mut text, mut visibility, mut warning)) = text_query.single_mut() else { return };
+
+    for event in events.read() {
+        if !settings.enabled {
+            continue;
+        }
+        let message = match event {
+            ArmorEvent::Broken { owner, slot, .. } if player_query.contains(*owner) => {
+                format!("{:?} armor broke!", slot)
+            }
+            ArmorEvent::StateChanged { owner, slot, state: Some(state) }
+                if *state >= settings.warn_from_state && player_query.contains(*owner) =>
+            {
+                format!("{:?} armor is badly damaged", slot)
+            }
+            _ => continue,
+        };
+        text.0 = message;
+        warning.timer = settings.duration;
+    }
+
+    if warning.timer > 0.0 {
+        warning.timer -= time.delta_secs();
+        visibility.set_if_neq(Visibility::Visible);
+    } else {
+        visibility.set_if_neq(Visibility::Hidden);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::Player;
+    use crate::utils::EventQueuePlugin;
+
+    #[derive(Resource, Default)]
+    struct ArmorLog(Vec<ArmorEvent>);
+
+    fn log_armor_events(mut events: QueueReader<ArmorEvent>, mut log: ResMut<ArmorLog>) {
+        log.0.extend(events.read().cloned());
+    }
+
+    fn hit(app: &mut App, owner: Entity, amount: f32) -> f32 {
+        let taken = app.world_mut().get_mut::<Equipment>(owner).unwrap().absorb_damage(amount);
+        app.update();
+        taken
+    }
+
+    fn warning(app: &mut App) -> (String, Visibility) {
+        let (text, visibility) = app
+            .world_mut()
+            .query_filtered::<(&Text, &Visibility), With<ArmorWarningText>>()
+            .single(app.world())
+            .unwrap();
+        (text.0.clone(), *visibility)
+    }
+
+    #[test]
+    fn test_armor_wears_breaks_and_repairs() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins((
+                EventQueuePlugin::<ArmorEvent>::default(),
+                EventQueuePlugin::<ArmorRepairRequest>::default(),
+            ))
+            .init_resource::<Assets<StandardMaterial>>()
+            .init_resource::<ArmorWarningSettings>()
+            .init_resource::<ArmorLog>()
+            .add_systems(Startup, setup_armor_warning_ui)
+            .add_systems(Update, (
+                handle_armor_repair_requests,
+                update_armor_condition,
+                update_armor_visuals,
+                update_armor_warning_ui,
+                log_armor_events,
+            ).chain());
+
+        let mut equipment = Equipment::default();
+        equipment.equip_armor(ArmorPiece::new("plate", "Plate", ArmorSlot::Chest, 0.5, 20.0)).unwrap();
+        let player = app.world_mut().spawn((Player, equipment)).id();
+        let original = app.world_mut().resource_mut::<Assets<StandardMaterial>>().add(StandardMaterial::default());
+        let visual = app.world_mut().spawn((
+            ArmorVisual::new(player, ArmorSlot::Chest),
+            MeshMaterial3d(original.clone()),
+            Visibility::Hidden,
+        )).id();
+        app.update();
+        assert_eq!(app.world().get::<Visibility>(visual), Some(&Visibility::Inherited));
+
+        // Full condition halves the hit; the plate drops to the first damage state
+        assert!((hit(&mut app, player, 40.0) - 20.0).abs() < 1e-4);
+        assert_ne!(app.world().get::<MeshMaterial3d<StandardMaterial>>(visual).unwrap().0, original);
+        assert_eq!(warning(&mut app).1, Visibility::Hidden);
+
+        // Worn armor absorbs less, and the badly damaged state warns the player
+        assert!((hit(&mut app, player, 40.0) - 26.0).abs() < 1e-4);
+        assert_eq!(warning(&mut app), ("Chest armor is badly damaged".to_string(), Visibility::Visible));
+
+        hit(&mut app, player, 40.0);
+        let equipment = app.world().get::<Equipment>(player).unwrap();
+        assert!(equipment.armor_pieces.is_empty());
+        assert!(equipment.stored_armor[0].is_broken());
+        assert_eq!(app.world().get::<Visibility>(visual), Some(&Visibility::Hidden));
+        assert_eq!(warning(&mut app).0, "Chest armor broke!");
+
+        // A full repair makes the stored plate wearable again
+        app.world_mut()
+            .resource_mut::<ArmorRepairQueue>()
+            .send(ArmorRepairRequest { owner: player, slot: None, fraction: 1.0 });
+        app.update();
+        app.world_mut().get_mut::<Equipment>(player).unwrap().equip_stored_armor("plate").unwrap();
+        app.update();
+        assert_eq!(app.world().get::<MeshMaterial3d<StandardMaterial>>(visual).unwrap().0, original);
+        assert_eq!(app.world().get::<Visibility>(visual), Some(&Visibility::Inherited));
+
+        assert_eq!(app.world().resource::<ArmorLog>().0, vec![
+            ArmorEvent::StateChanged { owner: player, slot: ArmorSlot::Chest, state: Some(0) },
+            ArmorEvent::StateChanged { owner: player, slot: ArmorSlot::Chest, state: Some(1) },
+            ArmorEvent::Broken { owner: player, slot: ArmorSlot::Chest, item_id: "plate".to_string() },
+            ArmorEvent::Repaired { owner: player, item_id: "plate".to_string(), amount: 20.0 },
+            ArmorEvent::StateChanged { owner: player, slot: ArmorSlot::Chest, state: None },
+        ]);
+    }
+}
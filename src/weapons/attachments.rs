@@ -18,6 +18,7 @@ use crate::input::InputState;
 use super::types::Weapon;
 use super::weapon_manager::WeaponManager;
 use crate::camera::CameraState;
+use crate::utils::{EventQueue, QueueReader};
 
 /// Component for laser sight attachments
 #[derive(Component, Debug, Reflect, Default)]
@@ -233,20 +234,17 @@ pub struct RemoveAttachment {
     pub place_id: String,
 }
 
-/// Custom queue for attachment events (Workaround for Bevy 0.18 EventReader issues)
-#[derive(Resource, Default)]
-pub struct AttachmentEventQueue {
-    pub toggle_events: Vec<ToggleAttachmentEditor>,
-    pub select_events: Vec<SelectAttachment>,
-    pub remove_events: Vec<RemoveAttachment>,
-}
+/// Queues for attachment editor events
+pub type ToggleAttachmentEditorQueue = EventQueue<ToggleAttachmentEditor>;
+pub type SelectAttachmentQueue = EventQueue<SelectAttachment>;
+pub type RemoveAttachmentQueue = EventQueue<RemoveAttachment>;
 
 /// System to handle attachment editor toggling
 pub fn handle_attachment_editor_toggle(
-    mut event_queue: ResMut<AttachmentEventQueue>,
+    mut toggle_events: QueueReader<ToggleAttachmentEditor>,
     mut query: Query<&mut WeaponAttachmentSystem>,
 ) {
-    for event in event_queue.toggle_events.drain(..) {
+    for event in toggle_events.read() {
         if let Ok(mut attachment_system) = query.get_mut(event.weapon_entity) {
             attachment_system.editing_attachments = event.open;
 
@@ -261,10 +259,10 @@ pub fn handle_attachment_editor_toggle(
 
 /// System to handle attachment selection
 pub fn handle_attachment_selection(
-    mut event_queue: ResMut<AttachmentEventQueue>,
+    mut select_events: QueueReader<SelectAttachment>,
     mut query: Query<&mut WeaponAttachmentSystem>,
 ) {
-    for event in event_queue.select_events.drain(..) {
+    for event in select_events.read() {
         if let Ok(mut attachment_system) = query.get_mut(event.weapon_entity) {
             // Find the attachment place
             let place_index = attachment_system
@@ -333,10 +331,10 @@ pub fn handle_attachment_selection(
 
 /// System to handle attachment removal
 pub fn handle_attachment_removal(
-    mut event_queue: ResMut<AttachmentEventQueue>,
+    mut remove_events: QueueReader<RemoveAttachment>,
     mut query: Query<&mut WeaponAttachmentSystem>,
 ) {
-    for event in event_queue.remove_events.drain(..) {
+    for event in remove_events.read() {
         if let Ok(mut attachment_system) = query.get_mut(event.weapon_entity) {
             // Find the attachment place
             let place_index = attachment_system
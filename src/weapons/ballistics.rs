@@ -1,26 +1,48 @@
 //! Ballistics system for projectile physics
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use avian3d::prelude::*;
-use crate::combat::{DamageEventQueue, DamageEvent, DamageType};
-use super::types::{BallisticsEnvironment, Projectile};
+use crate::combat::{DamageEventQueue, DamageEvent, DamageType, Health};
+use crate::player::extra_movements::swim::{is_underwater, WaterZone};
+use crate::utils::EntityPool;
+use crate::vfx::{VfxKey, VfxRequestQueue};
+use super::types::{BallisticsEnvironment, CapturedProjectile, Projectile};
+
+/// Air and water projectiles fly through
+#[derive(SystemParam)]
+pub struct BallisticsMedium<'w, 's> {
+    pub ballistics_env: Res<'w, BallisticsEnvironment>,
+    pub water_query: Query<'w, 's, (&'static WaterZone, &'static GlobalTransform)>,
+}
+
+/// Damage and impact effects of projectile hits
+#[derive(SystemParam)]
+pub struct ProjectileHitOutputs<'w, 's> {
+    pub damage_events: ResMut<'w, DamageEventQueue>,
+    pub vfx_queue: ResMut<'w, VfxRequestQueue>,
+    pub health_query: Query<'w, 's, (), With<Health>>,
+}
 
 /// Update projectile physics and collision
 pub fn update_projectiles(
     mut commands: Commands,
     time: Res<Time>,
     spatial_query: SpatialQuery,
-    ballistics_env: Res<BallisticsEnvironment>,
-    mut damage_events: ResMut<DamageEventQueue>,
-    mut query: Query<(Entity, &mut Transform, &mut Projectile)>,
+    medium: BallisticsMedium,
+    mut projectile_pool: ResMut<EntityPool<Projectile>>,
+    hits: ProjectileHitOutputs,
+    mut query: Query<(Entity, &mut Transform, &mut Projectile), Without<CapturedProjectile>>,
 ) {
+    let BallisticsMedium { ballistics_env, water_query } = medium;
+    let ProjectileHitOutputs { mut damage_events, mut vfx_queue, health_query } = hits;
     let dt = time.delta_secs();
     if dt <= 0.0 { return; }
 
     for (entity, mut transform, mut projectile) in query.iter_mut() {
         projectile.lifetime -= dt;
         if projectile.lifetime <= 0.0 {
-            commands.entity(entity).despawn();
+            projectile_pool.release(&mut commands, entity);
             continue;
         }
 
@@ -29,11 +51,18 @@ pub fn update_projectiles(
         let pos = transform.translation;
         let vel = projectile.velocity;
 
+        // Water is ~800x denser than air: projectiles stop within a few meters
+        let (density, wind) = if is_underwater(water_query.iter(), pos) {
+            (ballistics_env.water_density, Vec3::ZERO)
+        } else {
+            (ballistics_env.air_density, ballistics_env.wind)
+        };
+
         // Helper closure for acceleration calculation
         // a = g + (F_drag / m)
         // F_drag = 0.5 * density * speed^2 * Cd * Area * direction
         let calc_acceleration = |p: Vec3, v: Vec3| -> Vec3 {
-            let relative_velocity = v - ballistics_env.wind;
+            let relative_velocity = v - wind;
             let speed_sq = relative_velocity.length_squared();
 
             if speed_sq < 0.0001 {
@@ -43,7 +72,7 @@ pub fn update_projectiles(
             let speed = speed_sq.sqrt();
             let direction = relative_velocity / speed; // Normalize
 
-            let drag_magnitude = 0.5 * ballistics_env.air_density * speed_sq * projectile.drag_coeff * projectile.reference_area;
+            let drag_magnitude = 0.5 * density * speed_sq * projectile.drag_coeff * projectile.reference_area;
             let drag_force = direction * -drag_magnitude;
 
             ballistics_env.gravity + (drag_force / projectile.mass)
@@ -106,7 +135,7 @@ pub fn update_projectiles(
                     projectile.penetration_power = remaining_energy - surface_resistance;
 
                     // Visual effect for penetration
-                    spawn_impact_effect(&mut commands, hit_point, "Penetration".to_string());
+                    vfx_queue.spawn_directed(VfxKey::Spark, hit_point, hit.normal);
 
                     // Continue flight from hit point with reduced velocity (simulating drag inside material)
                     projectile.velocity *= 0.8;
@@ -114,7 +143,7 @@ pub fn update_projectiles(
                 } else {
                     // Stop or Ricochet
                     info!("Projectile stopped at {:?}!", hit_point);
-                    damage_events.0.push(DamageEvent {
+                    damage_events.send(DamageEvent {
                         amount: projectile.damage,
                         damage_type: DamageType::Ranged,
                         source: Some(projectile.owner),
@@ -124,8 +153,9 @@ pub fn update_projectiles(
                         ignore_shield: false,
                     });
 
-                    spawn_impact_effect(&mut commands, hit_point, "Impact".to_string());
-                    commands.entity(entity).despawn();
+                    let key = if health_query.contains(hit.entity) { VfxKey::BloodHit } else { VfxKey::Spark };
+                    vfx_queue.spawn_directed(key, hit_point, hit.normal);
+                    projectile_pool.release(&mut commands, entity);
                 }
                 continue; // Skip position update if we handled collision
             }
@@ -139,17 +169,6 @@ pub fn update_projectiles(
     }
 }
 
-// Helper function to spawn impact effects (Visual Pooling placeholder)
-fn spawn_impact_effect(commands: &mut Commands, position: Vec3, effect_type: String) {
-    // In a full implementation, this would use the VisualEffectPool
-    // For now, we spawn a simple marker
-    commands.spawn((
-        Transform::from_translation(position),
-        Name::new(format!("{}_Effect", effect_type)),
-        // Marker component for cleanup system
-    ));
-}
-
 // Helper function to spawn tracers (Visual/Simulation Separation)
 fn spawn_tracer(commands: &mut Commands, start: Vec3, end: Vec3) {
     // In a full implementation, this spawns a BulletTracer entity that interpolates
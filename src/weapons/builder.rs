@@ -103,6 +103,16 @@ impl WeaponBuilder {
         self
     }
 
+    pub fn with_underwater(mut self, usable: bool) -> Self {
+        self.weapon.usable_underwater = usable;
+        self
+    }
+
+    pub fn with_aim_assist(mut self, aim_assist: WeaponAimAssist) -> Self {
+        self.weapon.aim_assist = aim_assist;
+        self
+    }
+
     pub fn with_visuals(mut self, muzzle: bool, shells: bool) -> Self {
         self.weapon.visual_settings.muzzle_flash_enabled = muzzle;
         self.weapon.visual_settings.shell_ejection_enabled = shells;
@@ -0,0 +1,619 @@
+//! Dynamic crosshair
+//!
+//! The player's crosshair follows the current weapon:
+//!
+//! - lines move apart with the weapon spread plus [`Accuracy`] bloom
+//! - hit markers for hits, crits and kills from [`DamageResultEvent`]s
+//! - a reload bar and low-ammo / empty warnings
+//! - a shape per weapon type (circle for shotguns, hidden for unscoped snipers)
+//!
+//! The look comes from the [`CrosshairStyle`] resource, which can be loaded from JSON:
+//!
+//! ```rust,ignore
+//! let style = CrosshairStyle::from_json(r#"{ "color": [0.2, 1.0, 0.4, 0.9], "thickness": 3.0 }"#)?;
+//! app.insert_resource(style);
+//! ```
+//!
+//! [`CrosshairState`] is public so games can draw their own crosshair instead.
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use super::types::{Accuracy, Weapon, WeaponType};
+use super::weapon_manager::WeaponManager;
+use crate::character::Player;
+use crate::combat::{DamageResultEvent, Health};
+use crate::hud::{HudElement, HudPriority};
+use crate::utils::QueueReader;
+
+/// Crosshair drawn for a weapon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Serialize, Deserialize, Default)]
+pub enum CrosshairShape {
+    /// Four lines around the center
+    #[default]
+    Cross,
+    /// Ring sized by the spread
+    Circle,
+    Dot,
+    Hidden,
+}
+
+/// Hit marker kind, by priority
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Reflect)]
+pub enum HitMarkerKind {
+    Hit,
+    Crit,
+    Kill,
+}
+
+/// Crosshair look; colors are sRGBA
+#[derive(Resource, Debug, Clone, Reflect, Serialize, Deserialize)]
+#[reflect(Resource)]
+#[serde(default)]
+pub struct CrosshairStyle {
+    pub enabled: bool,
+    pub color: [f32; 4],
+    pub hit_color: [f32; 4],
+    pub crit_color: [f32; 4],
+    pub kill_color: [f32; 4],
+    pub low_ammo_color: [f32; 4],
+    pub thickness: f32,
+    pub line_length: f32,
+    /// Gap (pixels) at zero spread
+    pub base_gap: f32,
+    /// Extra gap (pixels) per degree of spread
+    pub spread_scale: f32,
+    pub max_gap: f32,
+    /// How fast the gap follows the spread
+    pub expand_speed: f32,
+    pub hit_marker_size: f32,
+    pub hit_marker_duration: f32,
+    /// Magazine fraction at or below which the low-ammo warning shows
+    pub low_ammo_fraction: f32,
+    pub default_shape: CrosshairShape,
+    pub shotgun_shape: CrosshairShape,
+    pub bow_shape: CrosshairShape,
+    pub melee_shape: CrosshairShape,
+    /// Shape while aiming down a sniper sight
+    pub scoped_shape: CrosshairShape,
+    pub hide_unscoped_sniper: bool,
+    /// Shapes by weapon name, over the type defaults
+    pub weapon_overrides: HashMap<String, CrosshairShape>,
+}
+
+impl Default for CrosshairStyle {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            color: [1.0, 1.0, 1.0, 0.9],
+            hit_color: [1.0, 1.0, 1.0, 1.0],
+            crit_color: [1.0, 0.85, 0.1, 1.0],
+            kill_color: [1.0, 0.2, 0.2, 1.0],
+            low_ammo_color: [1.0, 0.55, 0.2, 1.0],
+            thickness: 2.0,
+            line_length: 8.0,
+            base_gap: 4.0,
+            spread_scale: 6.0,
+            max_gap: 60.0,
+            expand_speed: 18.0,
+            hit_marker_size: 22.0,
+            hit_marker_duration: 0.25,
+            low_ammo_fraction: 0.25,
+            default_shape: CrosshairShape::Cross,
+            shotgun_shape: CrosshairShape::Circle,
+            bow_shape: CrosshairShape::Dot,
+            melee_shape: CrosshairShape::Dot,
+            scoped_shape: CrosshairShape::Dot,
+            hide_unscoped_sniper: true,
+            weapon_overrides: HashMap::new(),
+        }
+    }
+}
+
+impl CrosshairStyle {
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|error| format!("Invalid crosshair style: {}", error))
+    }
+
+    /// Shape for `weapon`, which may be aimed down its sight
+    pub fn shape_for(&self, weapon: &Weapon, aiming: bool) -> CrosshairShape {
+        if let Some(shape) = self.weapon_overrides.get(&weapon.weapon_name) {
+            return *shape;
+        }
+        if weapon.sniper_sight_settings.is_some() {
+            if aiming {
+                return self.scoped_shape;
+            }
+            if self.hide_unscoped_sniper {
+                return CrosshairShape::Hidden;
+            }
+        }
+        match weapon.weapon_type {
+            WeaponType::Shotgun => self.shotgun_shape,
+            WeaponType::Bow => self.bow_shape,
+            WeaponType::Melee => self.melee_shape,
+            _ => self.default_shape,
+        }
+    }
+
+    pub fn marker_color(&self, kind: HitMarkerKind) -> Color {
+        srgba(match kind {
+            HitMarkerKind::Hit => self.hit_color,
+            HitMarkerKind::Crit => self.crit_color,
+            HitMarkerKind::Kill => self.kill_color,
+        })
+    }
+}
+
+fn srgba([r, g, b, a]: [f32; 4]) -> Color {
+    Color::srgba(r, g, b, a)
+}
+
+/// Crosshair state of the player's current weapon
+#[derive(Resource, Debug, Default, Reflect)]
+#[reflect(Resource)]
+pub struct CrosshairState {
+    pub shape: CrosshairShape,
+    /// Current gap (pixels) between the center and the lines
+    pub gap: f32,
+    /// Weapon spread plus bloom, in degrees
+    pub spread: f32,
+    pub hit_marker: Option<HitMarkerKind>,
+    pub hit_marker_timer: f32,
+    pub reloading: bool,
+    /// Reload progress (0-1)
+    pub reload_progress: f32,
+    pub low_ammo: bool,
+    pub empty: bool,
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CrosshairRoot;
+
+/// Crosshair line pointing away from the center along `direction` (screen space, y down)
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CrosshairLine {
+    pub direction: Vec2,
+}
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CrosshairCircle;
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CrosshairDot;
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CrosshairHitMarker;
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CrosshairReloadBar;
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CrosshairReloadFill;
+
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct CrosshairAmmoWarning;
+
+/// System to spawn the crosshair UI at the screen center
+pub fn setup_crosshair_ui(mut commands: Commands, style: Res<CrosshairStyle>) {
+    let color = srgba(style.color);
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Percent(50.0),
+                top: Val::Percent(50.0),
+                width: Val::Px(0.0),
+                height: Val::Px(0.0),
+                ..default()
+            },
+            Visibility::Hidden,
+            GlobalZIndex(90),
+            CrosshairRoot,
+            HudElement::new("crosshair", HudPriority::Essential),
+        ))
+        .with_children(|parent| {
+            for direction in [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y] {
+                parent.spawn((
+                    Node { position_type: PositionType::Absolute, ..default() },
+                    BackgroundColor(color),
+                    CrosshairLine { direction },
+                ));
+            }
+            parent.spawn((
+                Node { position_type: PositionType::Absolute, ..default() },
+                BorderColor(color),
+                BorderRadius::MAX,
+                CrosshairCircle,
+            ));
+            parent.spawn((
+                Node { position_type: PositionType::Absolute, ..default() },
+                BackgroundColor(color),
+                BorderRadius::MAX,
+                CrosshairDot,
+            ));
+            parent.spawn((
+                Text::new("X"),
+                TextFont { font_size: style.hit_marker_size, ..default() },
+                TextColor(style.marker_color(HitMarkerKind::Hit)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(-style.hit_marker_size * 0.3),
+                    top: Val::Px(-style.hit_marker_size * 0.6),
+                    ..default()
+                },
+                Visibility::Hidden,
+                CrosshairHitMarker,
+            ));
+            parent
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        left: Val::Px(-30.0),
+                        top: Val::Px(28.0),
+                        width: Val::Px(60.0),
+                        height: Val::Px(4.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.5)),
+                    Visibility::Hidden,
+                    CrosshairReloadBar,
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Node {
+                            width: Val::Percent(0.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(color),
+                        CrosshairReloadFill,
+                    ));
+                });
+            parent.spawn((
+                Text::new(""),
+                TextFont { font_size: 14.0, ..default() },
+                TextColor(srgba(style.low_ammo_color)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(-32.0),
+                    top: Val::Px(36.0),
+                    ..default()
+                },
+                Visibility::Hidden,
+                CrosshairAmmoWarning,
+            ));
+        });
+}
+
+/// System to update the crosshair state from the player's weapon and damage results
+pub fn update_crosshair_state(
+    time: Res<Time>,
+    style: Res<CrosshairStyle>,
+    mut state: ResMut<CrosshairState>,
+    mut results: QueueReader<DamageResultEvent>,
+    player_query: Query<(Entity, &WeaponManager), With<Player>>,
+    weapon_query: Query<(&Weapon, Option<&Accuracy>)>,
+    health_query: Query<&Health>,
+) {
+    let dt = time.delta_secs();
+    let player = player_query.iter().next();
+
+    // Hit markers
+    state.hit_marker_timer -= dt;
+    if state.hit_marker_timer <= 0.0 {
+        state.hit_marker = None;
+    }
+    for result in results.read() {
+        let Some((player_entity, _)) = player else { continue };
+        if result.source != Some(player_entity) || result.target == player_entity {
+            continue;
+        }
+        if result.final_amount <= 0.0 && result.shielded_amount <= 0.0 {
+            continue;
+        }
+        let killed = health_query
+            .get(result.target)
+            .is_ok_and(|health| health.is_dead || health.current <= 0.0);
+        let kind = if killed {
+            HitMarkerKind::Kill
+        } else if result.is_crit {
+            HitMarkerKind::Crit
+        } else {
+            HitMarkerKind::Hit
+        };
+        if state.hit_marker.is_none_or(|current| kind >= current) {
+            state.hit_marker = Some(kind);
+            state.hit_marker_timer = style.hit_marker_duration;
+        }
+    }
+
+    // Weapon
+    let current = player.and_then(|(_, manager)| {
+        let armed = manager.carrying_weapon_in_third_person || manager.carrying_weapon_in_first_person;
+        let aiming = manager.aiming_in_third_person || manager.aiming_in_first_person;
+        let weapon_entity = manager.weapons_list.get(manager.current_index)?;
+        armed.then(|| weapon_query.get(*weapon_entity).ok().map(|weapon| (weapon, aiming))).flatten()
+    });
+    let Some(((weapon, accuracy), aiming)) = current else {
+        state.shape = CrosshairShape::Hidden;
+        state.reloading = false;
+        state.low_ammo = false;
+        state.empty = false;
+        return;
+    };
+
+    state.shape = style.shape_for(weapon, aiming);
+    state.spread = weapon.spread + accuracy.map_or(0.0, |accuracy| accuracy.current_bloom);
+    let target_gap = (style.base_gap + state.spread * style.spread_scale).min(style.max_gap);
+    let alpha = 1.0 - (-style.expand_speed * dt).exp();
+    state.gap += (target_gap - state.gap) * alpha;
+
+    state.reloading = weapon.is_reloading;
+    state.reload_progress = if weapon.is_reloading && weapon.reload_time > 0.0 {
+        (1.0 - weapon.current_reload_timer / weapon.reload_time).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let tracks_ammo = !weapon.infinite_ammo && weapon.ammo_capacity > 0;
+    state.empty = tracks_ammo && weapon.current_ammo <= 0;
+    state.low_ammo = tracks_ammo
+        && weapon.current_ammo as f32 <= weapon.ammo_capacity as f32 * style.low_ammo_fraction;
+}
+
+type CrosshairCirclePart = (With<CrosshairCircle>, Without<CrosshairLine>, Without<CrosshairRoot>);
+type CrosshairDotPart = (With<CrosshairDot>, Without<CrosshairCircle>, Without<CrosshairLine>, Without<CrosshairRoot>);
+type CrosshairMarkerPart = (
+    With<CrosshairHitMarker>,
+    Without<CrosshairDot>,
+    Without<CrosshairCircle>,
+    Without<CrosshairLine>,
+    Without<CrosshairRoot>,
+);
+type CrosshairReloadPart = (
+    With<CrosshairReloadBar>,
+    Without<CrosshairHitMarker>,
+    Without<CrosshairDot>,
+    Without<CrosshairCircle>,
+    Without<CrosshairLine>,
+    Without<CrosshairRoot>,
+);
+type CrosshairFillPart = (With<CrosshairReloadFill>, Without<CrosshairDot>, Without<CrosshairCircle>, Without<CrosshairLine>);
+type CrosshairWarningPart = (
+    With<CrosshairAmmoWarning>,
+    Without<CrosshairReloadBar>,
+    Without<CrosshairHitMarker>,
+    Without<CrosshairDot>,
+    Without<CrosshairCircle>,
+    Without<CrosshairLine>,
+    Without<CrosshairRoot>,
+);
+type CrosshairLineParts<'a> = (&'a CrosshairLine, &'a mut Node, &'a mut Visibility, &'a mut BackgroundColor);
+
+/// Lines, circle and dot making up the reticle
+#[derive(SystemParam)]
+pub struct CrosshairShapes<'w, 's> {
+    pub line_query: Query<'w, 's, CrosshairLineParts<'static>, Without<CrosshairRoot>>,
+    pub circle_query: Query<'w, 's, (&'static mut Node, &'static mut Visibility, &'static mut BorderColor), CrosshairCirclePart>,
+    pub dot_query: Query<'w, 's, (&'static mut Node, &'static mut Visibility, &'static mut BackgroundColor), CrosshairDotPart>,
+}
+
+/// Hit marker, reload bar and ammo warning around the reticle
+#[derive(SystemParam)]
+pub struct CrosshairIndicators<'w, 's> {
+    pub marker_query: Query<'w, 's, (&'static mut Visibility, &'static mut TextColor), CrosshairMarkerPart>,
+    pub reload_query: Query<'w, 's, &'static mut Visibility, CrosshairReloadPart>,
+    pub fill_query: Query<'w, 's, &'static mut Node, CrosshairFillPart>,
+    pub warning_query: Query<'w, 's, (&'static mut Text, &'static mut Visibility), CrosshairWarningPart>,
+}
+
+/// System to lay out the crosshair UI from its state
+pub fn update_crosshair_ui(
+    style: Res<CrosshairStyle>,
+    state: Res<CrosshairState>,
+    mut root_query: Query<&mut Visibility, With<CrosshairRoot>>,
+    shapes: CrosshairShapes,
+    indicators: CrosshairIndicators,
+) {
+    let CrosshairShapes { mut line_query, mut circle_query, mut dot_query } = shapes;
+    let CrosshairIndicators { mut marker_query, mut reload_query, mut fill_query, mut warning_query } = indicators;
+    let Ok(mut root_visibility) = root_query.single_mut() else { return };
+    if !style.enabled {
+        *root_visibility = Visibility::Hidden;
+        return;
+    }
+    *root_visibility = Visibility::Inherited;
+
+    let show = |visible: bool| if visible { Visibility::Inherited } else { Visibility::Hidden };
+    let color = if state.empty { srgba(style.low_ammo_color) } else { srgba(style.color) };
+    let gap = state.gap;
+    let thickness = style.thickness;
+
+    for (line, mut node, mut visibility, mut background) in line_query.iter_mut() {
+        *visibility = show(state.shape == CrosshairShape::Cross);
+        let length = style.line_length;
+        let center = line.direction * (gap + length * 0.5);
+        let (width, height) = if line.direction.x != 0.0 { (length, thickness) } else { (thickness, length) };
+        node.left = Val::Px(center.x - width * 0.5);
+        node.top = Val::Px(center.y - height * 0.5);
+        node.width = Val::Px(width);
+        node.height = Val::Px(height);
+        background.0 = color;
+    }
+
+    if let Ok((mut node, mut visibility, mut border)) = circle_query.single_mut() {
+        *visibility = show(state.shape == CrosshairShape::Circle);
+        let radius = gap + thickness;
+        node.left = Val::Px(-radius);
+        node.top = Val::Px(-radius);
+        node.width = Val::Px(radius * 2.0);
+        node.height = Val::Px(radius * 2.0);
+        node.border = UiRect::all(Val::Px(thickness));
+        border.0 = color;
+    }
+
+    if let Ok((mut node, mut visibility, mut background)) = dot_query.single_mut() {
+        // The cross keeps its center clear
+        *visibility = show(matches!(state.shape, CrosshairShape::Dot | CrosshairShape::Circle));
+        let size = thickness * 2.0;
+        node.left = Val::Px(-size * 0.5);
+        node.top = Val::Px(-size * 0.5);
+        node.width = Val::Px(size);
+        node.height = Val::Px(size);
+        background.0 = color;
+    }
+
+    if let Ok((mut visibility, mut text_color)) = marker_query.single_mut() {
+        *visibility = show(state.hit_marker.is_some());
+        if let Some(kind) = state.hit_marker {
+            text_color.0 = style.marker_color(kind);
+        }
+    }
+
+    if let Ok(mut visibility) = reload_query.single_mut() {
+        *visibility = show(state.reloading);
+    }
+    if let Ok(mut node) = fill_query.single_mut() {
+        node.width = Val::Percent(state.reload_progress * 100.0);
+    }
+
+    if let Ok((mut text, mut visibility)) = warning_query.single_mut() {
+        let message = if state.reloading {
+            ""
+        } else if state.empty {
+            "RELOAD"
+        } else if state.low_ammo {
+            "LOW AMMO"
+        } else {
+            ""
+        };
+        *visibility = show(!message.is_empty());
+        if text.0 != message {
+            text.0 = message.to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::DamageType;
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    fn result(target: Entity, source: Entity, is_crit: bool) -> DamageResultEvent {
+        DamageResultEvent {
+            target,
+            part: None,
+            source: Some(source),
+            original_amount: 25.0,
+            final_amount: 25.0,
+            damage_type: DamageType::Ranged,
+            shielded_amount: 0.0,
+            is_crit,
+            is_block: false,
+        }
+    }
+
+    fn single<C: Component + Clone>(app: &mut App, filter: impl Fn(&World, Entity) -> bool) -> C {
+        let mut query = app.world_mut().query::<(Entity, &C)>();
+        let world = app.world();
+        query.iter(world).find(|(entity, _)| filter(world, *entity)).map(|(_, component)| component.clone()).unwrap()
+    }
+
+    fn visibility_of<T: Component>(app: &mut App) -> Visibility {
+        single::<Visibility>(app, |world, entity| world.get::<T>(entity).is_some())
+    }
+
+    #[test]
+    fn test_crosshair_follows_weapon_markers_and_ammo() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, EventQueuePlugin::<DamageResultEvent>::default()))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<CrosshairStyle>()
+            .init_resource::<CrosshairState>()
+            .add_systems(Startup, setup_crosshair_ui)
+            .add_systems(Update, (update_crosshair_state, update_crosshair_ui).chain());
+
+        let shotgun = app
+            .world_mut()
+            .spawn(Weapon {
+                weapon_name: "Pump".to_string(),
+                weapon_type: WeaponType::Shotgun,
+                ammo_capacity: 8,
+                current_ammo: 2,
+                spread: 3.0,
+                infinite_ammo: false,
+                ..default()
+            })
+            .id();
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                WeaponManager {
+                    weapons_list: vec![shotgun],
+                    current_index: 0,
+                    carrying_weapon_in_third_person: true,
+                    ..default()
+                },
+            ))
+            .id();
+        let grunt = app.world_mut().spawn(Health::default()).id();
+        let boss = app.world_mut().spawn(Health { current: 0.0, ..default() }).id();
+
+        for _ in 0..6 {
+            app.update();
+        }
+        let state = app.world().resource::<CrosshairState>();
+        assert_eq!(state.shape, CrosshairShape::Circle);
+        assert!(state.low_ammo && !state.empty);
+        assert!((state.gap - 22.0).abs() < 0.1, "gap {}", state.gap);
+        assert_eq!(visibility_of::<CrosshairCircle>(&mut app), Visibility::Inherited);
+        assert_eq!(visibility_of::<CrosshairDot>(&mut app), Visibility::Inherited);
+        assert_eq!(visibility_of::<CrosshairLine>(&mut app), Visibility::Hidden);
+        assert_eq!(visibility_of::<CrosshairAmmoWarning>(&mut app), Visibility::Inherited);
+        let warning = single::<Text>(&mut app, |world, entity| world.get::<CrosshairAmmoWarning>(entity).is_some());
+        assert_eq!(warning.0, "LOW AMMO");
+
+        // A crit and a kill in the same frame show the kill marker
+        app.world_mut()
+            .resource_mut::<DamageResultQueue>()
+            .send_batch([result(grunt, player, true), result(boss, player, false)]);
+        app.update();
+        assert_eq!(app.world().resource::<CrosshairState>().hit_marker, Some(HitMarkerKind::Kill));
+        assert_eq!(visibility_of::<CrosshairHitMarker>(&mut app), Visibility::Inherited);
+        let marker_color = single::<TextColor>(&mut app, |world, entity| world.get::<CrosshairHitMarker>(entity).is_some());
+        assert_eq!(marker_color.0, CrosshairStyle::default().marker_color(HitMarkerKind::Kill));
+
+        // The marker fades after its duration and isn't shown again for the same results
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<CrosshairState>().hit_marker, None);
+        assert_eq!(visibility_of::<CrosshairHitMarker>(&mut app), Visibility::Hidden);
+
+        app.world_mut().get_mut::<Weapon>(shotgun).unwrap().current_ammo = 0;
+        app.update();
+        assert!(app.world().resource::<CrosshairState>().empty);
+        let warning = single::<Text>(&mut app, |world, entity| world.get::<CrosshairAmmoWarning>(entity).is_some());
+        assert_eq!(warning.0, "RELOAD");
+
+        app.world_mut().get_mut::<WeaponManager>(player).unwrap().carrying_weapon_in_third_person = false;
+        app.update();
+        assert_eq!(app.world().resource::<CrosshairState>().shape, CrosshairShape::Hidden);
+        assert_eq!(visibility_of::<CrosshairCircle>(&mut app), Visibility::Hidden);
+        assert_eq!(visibility_of::<CrosshairDot>(&mut app), Visibility::Hidden);
+        assert_eq!(visibility_of::<CrosshairAmmoWarning>(&mut app), Visibility::Hidden);
+    }
+}
@@ -0,0 +1,269 @@
+//! Defending against projectiles.
+//!
+//! A character can meet an incoming projectile in three ways, tried in order:
+//!
+//! - A `ProjectileCatch` ability that is active catches it and holds it for
+//!   re-throw (see `ReturnProjectilesQueue`).
+//! - A `ProjectileDeflector` swinging a melee attack deflects it within the
+//!   first `window` seconds of the swing, back at the shooter or along the aim.
+//! - A `ProjectileShield` absorbs it while blocking, if it comes from the front
+//!   and the stamina cost can be paid. Otherwise the guard breaks and it hits
+//!   the block as usual.
+
+use bevy::prelude::*;
+use crate::abilities::{ProjectileCatch, StaminaSystem};
+use crate::combat::{Blocking, MeleeCombat};
+use crate::stats::StatsSystem;
+use crate::utils::{EntityPool, EventQueue};
+use super::types::*;
+
+/// Height above the defender's origin that projectiles are measured from
+const DEFENSE_HEIGHT: f32 = 1.0;
+
+/// Where a deflected projectile goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum DeflectDirection {
+    /// Back at whoever fired it
+    #[default]
+    TowardShooter,
+    /// Along the defender's facing
+    Aim,
+}
+
+/// Melee swings deflect projectiles early in the swing
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct ProjectileDeflector {
+    /// Seconds from the start of a swing during which it deflects
+    pub window: f32,
+    pub radius: f32,
+    pub direction: DeflectDirection,
+    /// Speed of the deflected projectile relative to the incoming one
+    pub speed_multiplier: f32,
+    /// Time since the current swing started
+    pub swing_time: f32,
+    pub swinging: bool,
+}
+
+impl Default for ProjectileDeflector {
+    fn default() -> Self {
+        Self {
+            window: 0.25,
+            radius: 1.6,
+            direction: DeflectDirection::TowardShooter,
+            speed_multiplier: 1.2,
+            swing_time: 0.0,
+            swinging: false,
+        }
+    }
+}
+
+impl ProjectileDeflector {
+    pub fn is_deflecting(&self) -> bool {
+        self.swinging && self.swing_time <= self.window
+    }
+}
+
+/// Blocking absorbs projectiles from the front for stamina
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct ProjectileShield {
+    pub radius: f32,
+    /// Half-angle of the covered arc in front, in degrees
+    pub angle: f32,
+    /// Stamina spent per point of projectile damage
+    pub stamina_per_damage: f32,
+}
+
+impl Default for ProjectileShield {
+    fn default() -> Self {
+        Self { radius: 1.4, angle: 70.0, stamina_per_damage: 0.5 }
+    }
+}
+
+impl ProjectileShield {
+    /// Whether a projectile moving along `velocity` hits the front of a
+    /// defender facing `facing`
+    pub fn covers(&self, facing: Vec3, velocity: Vec3) -> bool {
+        let incoming = -velocity.normalize_or_zero();
+        facing.normalize_or_zero().dot(incoming) >= self.angle.to_radians().cos()
+    }
+}
+
+/// How a projectile was stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectileDefense {
+    Caught,
+    Deflected,
+    Absorbed,
+    /// The shield couldn't pay the stamina and the projectile went through
+    GuardBroken,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProjectileDefenseEvent {
+    pub defender: Entity,
+    pub projectile: Entity,
+    pub defense: ProjectileDefense,
+}
+
+pub type ProjectileDefenseQueue = EventQueue<ProjectileDefenseEvent>;
+
+/// Distance from `point` to the segment from `a` to `b`
+pub fn distance_to_segment(point: Vec3, a: Vec3, b: Vec3) -> f32 {
+    let segment = b - a;
+    let length_squared = segment.length_squared();
+    let t = if length_squared > 0.0 { ((point - a).dot(segment) / length_squared).clamp(0.0, 1.0) } else { 0.0 };
+    point.distance(a + segment * t)
+}
+
+/// Direction of a projectile deflected at `position` by a defender facing `facing`
+pub fn deflect_direction(direction: DeflectDirection, position: Vec3, incoming: Vec3, facing: Vec3, shooter: Option<Vec3>) -> Vec3 {
+    let target = match (direction, shooter) {
+        (DeflectDirection::TowardShooter, Some(shooter)) => (shooter + Vec3::Y * DEFENSE_HEIGHT - position).try_normalize(),
+        (DeflectDirection::Aim, _) => facing.try_normalize(),
+        _ => None,
+    };
+    target.unwrap_or(-incoming.normalize_or_zero())
+}
+
+/// System to time the deflection window of melee swings
+pub fn update_projectile_deflectors(
+    time: Res<Time>,
+    mut query: Query<(&MeleeCombat, &mut ProjectileDeflector)>,
+) {
+    let dt = time.delta_secs();
+    for (combat, mut deflector) in query.iter_mut() {
+        match (combat.is_attacking, deflector.swinging) {
+            (true, false) => {
+                deflector.swinging = true;
+                deflector.swing_time = 0.0;
+            }
+            (true, true) => deflector.swing_time += dt,
+            (false, true) => deflector.swinging = false,
+            (false, false) => {}
+        }
+    }
+}
+
+type ProjectileDefender<'a> = (
+    Entity,
+    &'a GlobalTransform,
+    Option<&'a mut ProjectileCatch>,
+    Option<&'a ProjectileDeflector>,
+    Option<&'a ProjectileShield>,
+    Option<&'a Blocking>,
+    Option<&'a mut StaminaSystem>,
+    Option<&'a mut StatsSystem>,
+);
+type ProjectileDefenderFilter = Or<(With<ProjectileCatch>, With<ProjectileDeflector>, With<ProjectileShield>)>;
+
+/// System to catch, deflect and absorb incoming projectiles
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn handle_projectile_defenses(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectile_pool: ResMut<EntityPool<Projectile>>,
+    mut events: ResMut<ProjectileDefenseQueue>,
+    mut projectile_query: Query<(Entity, &mut Projectile, &mut Transform), Without<CapturedProjectile>>,
+    mut defender_query: Query<ProjectileDefender, ProjectileDefenderFilter>,
+    shooter_query: Query<&GlobalTransform, Without<Projectile>>,
+) {
+    let dt = time.delta_secs();
+    for (entity, mut projectile, mut transform) in projectile_query.iter_mut() {
+        let velocity = projectile.velocity;
+        if velocity.length_squared() < 0.001 {
+            continue;
+        }
+        let position = transform.translation;
+        let next = position + velocity * dt;
+
+        for (defender, defender_transform, catch, deflector, shield, blocking, stamina, stats) in defender_query.iter_mut() {
+            if defender == projectile.owner {
+                continue;
+            }
+            let center = defender_transform.translation() + Vec3::Y * DEFENSE_HEIGHT;
+            if velocity.dot(center - position) <= 0.0 {
+                continue;
+            }
+            let distance = distance_to_segment(center, position, next);
+            let facing = defender_transform.forward().as_vec3();
+
+            let defense = if let Some(mut catch) = catch.filter(|catch| catch.can_catch() && distance <= catch.catch_radius) {
+                catch.stored.push(entity);
+                commands
+                    .entity(entity)
+                    .insert((
+                        CapturedProjectile { original_velocity: velocity, original_owner: Some(projectile.owner) },
+                        Visibility::Hidden,
+                    ))
+                    .set_parent_in_place(defender);
+                ProjectileDefense::Caught
+            } else if let Some(deflector) = deflector.filter(|deflector| deflector.is_deflecting() && distance <= deflector.radius) {
+                let shooter = shooter_query.get(projectile.owner).ok().map(GlobalTransform::translation);
+                let direction = deflect_direction(deflector.direction, position, velocity, facing, shooter);
+                projectile.velocity = direction * velocity.length() * deflector.speed_multiplier;
+                projectile.owner = defender;
+                transform.look_to(direction, Vec3::Y);
+                // A homing projectile would turn straight back
+                commands.entity(entity).remove::<Homing>();
+                ProjectileDefense::Deflected
+            } else if let (Some(shield), Some(blocking)) = (shield, blocking) {
+                if !blocking.is_blocking || distance > shield.radius || !shield.covers(facing, velocity) {
+                    continue;
+                }
+                let cost = projectile.damage * shield.stamina_per_damage;
+                let paid = match stamina {
+                    Some(mut stamina) => stamina.try_spend(cost, stats.map(|stats| stats.into_inner())),
+                    None => true,
+                };
+                if paid {
+                    projectile_pool.release(&mut commands, entity);
+                    ProjectileDefense::Absorbed
+                } else {
+                    ProjectileDefense::GuardBroken
+                }
+            } else {
+                continue;
+            };
+
+            events.send(ProjectileDefenseEvent { defender, projectile: entity, defense });
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abilities::StaminaSystem;
+
+    #[test]
+    fn test_projectile_deflection() {
+        use crate::weapons::{deflect_direction, distance_to_segment, DeflectDirection, ProjectileDeflector, ProjectileShield};
+
+        // A fast projectile passing the defender within one frame still counts
+        assert_eq!(distance_to_segment(Vec3::new(0.0, 0.0, 1.0), Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0)), 1.0);
+        assert_eq!(distance_to_segment(Vec3::ZERO, Vec3::new(3.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0)), 3.0);
+
+        let incoming = Vec3::new(0.0, 0.0, 10.0);
+        let back = deflect_direction(DeflectDirection::TowardShooter, Vec3::new(0.0, 1.0, 0.0), incoming, Vec3::X, Some(Vec3::new(0.0, 0.0, -8.0)));
+        assert!(back.abs_diff_eq(Vec3::NEG_Z, 1e-5));
+        assert_eq!(deflect_direction(DeflectDirection::Aim, Vec3::ZERO, incoming, Vec3::X, None), Vec3::X);
+        assert_eq!(deflect_direction(DeflectDirection::TowardShooter, Vec3::ZERO, incoming, Vec3::X, None), Vec3::NEG_Z);
+
+        let deflector = ProjectileDeflector { swinging: true, swing_time: 0.1, ..default() };
+        assert!(deflector.is_deflecting());
+        assert!(!ProjectileDeflector { swing_time: 0.4, ..deflector.clone() }.is_deflecting());
+
+        // Facing -Z, the shield covers shots coming from the front only
+        let shield = ProjectileShield::default();
+        assert!(shield.covers(Vec3::NEG_Z, incoming));
+        assert!(!shield.covers(Vec3::NEG_Z, -incoming));
+
+        let mut stamina = StaminaSystem { current_stamina: 10.0, use_stats: false, ..default() };
+        assert!(stamina.try_spend(6.0, None));
+        assert!(!stamina.try_spend(6.0, None));
+        assert_eq!(stamina.current_stamina, 4.0);
+    }
+}
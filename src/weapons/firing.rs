@@ -1,11 +1,16 @@
 //! Weapon firing and reloading systems
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use avian3d::prelude::*;
-use crate::input::InputState;
-use crate::combat::{DamageEventQueue, DamageEvent, DamageType};
-use super::types::{Weapon, Accuracy, BallisticsEnvironment, Projectile};
+use crate::camera::{ShakePreset, ShakeQueue};
+use crate::input::{HapticsCurve, HapticsEvent, HapticsQueue, HapticsSettings, InputState};
+use crate::combat::{DamageEventQueue, DamageEvent, DamageType, Submerged, UnderwaterCombatRules};
+use crate::utils::EntityPool;
+use super::types::{Weapon, Accuracy, BallisticsEnvironment, BlindFire, Projectile};
 use super::weapon_manager::WeaponManager;
+use super::aim_assist::{AimAssistSettings, AimAssistState};
+use super::lead::LeadAim;
 
 /// Handle weapon reloading
 pub fn handle_reloading(
@@ -29,17 +34,63 @@ pub fn handle_reloading(
     }
 }
 
+/// What shots spawn, hit and damage through
+#[derive(SystemParam)]
+pub struct ShotContext<'w, 's> {
+    pub commands: Commands<'w, 's>,
+    pub damage_events: ResMut<'w, DamageEventQueue>,
+    pub projectile_pool: ResMut<'w, EntityPool<Projectile>>,
+    pub spatial_query: SpatialQuery<'w, 's>,
+}
+
+/// Rumble and camera kick of a shot
+#[derive(SystemParam)]
+pub struct FireFeedback<'w> {
+    pub haptics: ResMut<'w, HapticsQueue>,
+    pub haptics_settings: Res<'w, HapticsSettings>,
+    pub shakes: ResMut<'w, ShakeQueue>,
+}
+
+/// Aim assist bending hitscan shots
+#[derive(SystemParam)]
+pub struct FireAimAssist<'w> {
+    pub aim_assist: Res<'w, AimAssistState>,
+    pub aim_assist_settings: Res<'w, AimAssistSettings>,
+}
+
+/// Aim of a shot besides the weapon's own spread
+pub struct ShotAim<'a> {
+    /// Bullet magnetism, none for blind fire
+    pub assist: Option<(&'a AimAssistState, &'a AimAssistSettings)>,
+    /// Lead solution replacing the weapon's forward
+    pub lead_direction: Option<Vec3>,
+    pub spread_multiplier: f32,
+}
+
+type FiringCarrier<'a> = (
+    Entity,
+    &'a GlobalTransform,
+    &'a mut WeaponManager,
+    &'a InputState,
+    Has<Submerged>,
+    Option<&'a LeadAim>,
+    Option<&'a BlindFire>,
+);
+
 /// Handle weapon firing
 pub fn handle_weapon_firing(
-    mut commands: Commands,
     time: Res<Time>,
-    mut damage_events: ResMut<DamageEventQueue>,
-    spatial_query: SpatialQuery,
-    mut manager_query: Query<(Entity, &GlobalTransform, &mut WeaponManager, &InputState)>,
+    mut shot: ShotContext,
+    feedback: FireFeedback,
+    underwater_rules: Res<UnderwaterCombatRules>,
+    aim_help: FireAimAssist,
+    mut manager_query: Query<FiringCarrier>,
     mut weapon_query: Query<(&mut Weapon, &mut Accuracy, &GlobalTransform)>,
 ) {
+    let FireFeedback { mut haptics, haptics_settings, mut shakes } = feedback;
+    let FireAimAssist { aim_assist, aim_assist_settings } = aim_help;
     let dt = time.delta_secs();
-    for (player_entity, player_transform, mut manager, input) in manager_query.iter_mut() {
+    for (player_entity, player_transform, mut manager, input, submerged, lead, blind_fire) in manager_query.iter_mut() {
         // Skip if busy
         if manager.reloading_with_animation_active || manager.changing_weapon {
             continue;
@@ -47,6 +98,12 @@ pub fn handle_weapon_firing(
 
         if let Some(&weapon_entity) = manager.weapons_list.get(manager.current_index) {
             if let Ok((mut weapon, mut accuracy, weapon_transform)) = weapon_query.get_mut(weapon_entity) {
+                if !underwater_rules.allows_weapon(&weapon, submerged) {
+                    weapon.burst_settings.is_bursting = false;
+                    manager.shooting_single_weapon = false;
+                    continue;
+                }
+
                 let mut want_to_fire = false;
 
                 // Handle Burst mode (continues even if fire not pressed)
@@ -86,16 +143,33 @@ pub fn handle_weapon_firing(
                 if want_to_fire {
                     if weapon.current_ammo > 0 {
                         fire_weapon(
-                            &mut commands,
+                            &mut shot,
                             &mut weapon,
                             &mut accuracy,
                             weapon_transform,
-                            &mut damage_events,
-                            &spatial_query,
-                            player_entity
+                            player_entity,
+                            ShotAim {
+                                // Blind fire gets no help
+                                assist: blind_fire.is_none().then_some((&*aim_assist, &*aim_assist_settings)),
+                                lead_direction: lead.and_then(|lead| lead.solution).map(|solution| solution.direction),
+                                spread_multiplier: blind_fire.map_or(1.0, |blind_fire| blind_fire.spread_multiplier),
+                            },
                         );
                         manager.shooting_single_weapon = true;
                         manager.last_time_fired = time.elapsed_secs();
+
+                        if haptics_settings.weapon_fire_enabled {
+                            // Shotguns kick harder
+                            let strength = haptics_settings.weapon_fire_strength * (weapon.projectiles_per_shot as f32).sqrt();
+                            haptics.send(
+                                HapticsEvent::new(strength.min(1.0), haptics_settings.weapon_fire_duration)
+                                    .for_player(player_entity)
+                                    .with_curve(HapticsCurve::EaseOut)
+                                    .with_sharpness(0.8),
+                            );
+                        }
+                        let recoil = if weapon.projectiles_per_shot > 1 { ShakePreset::WeaponRecoilHeavy } else { ShakePreset::WeaponRecoilLight };
+                        shakes.shake_for(recoil, player_entity, 1.0);
                         
                         // Set timer for next shot (if bursting, already set above for the first shot, 
                         // but subsequent shots need it here)
@@ -118,14 +192,15 @@ pub fn handle_weapon_firing(
 
 /// Fire weapon logic
 pub fn fire_weapon(
-    commands: &mut Commands,
+    shot: &mut ShotContext,
     weapon: &mut Weapon,
     accuracy: &mut Accuracy,
     transform: &GlobalTransform,
-    damage_events: &mut DamageEventQueue,
-    spatial_query: &SpatialQuery,
     source_entity: Entity,
+    aim: ShotAim,
 ) {
+    let ShotContext { commands, damage_events, projectile_pool, spatial_query } = shot;
+    let ShotAim { assist: aim_assist, lead_direction, spread_multiplier } = aim;
     weapon.current_ammo -= 1;
     // Timer is now managed in handle_weapon_firing for better control over burst/auto logic
 
@@ -154,14 +229,19 @@ pub fn fire_weapon(
         );
     }
 
-    let forward = transform.forward();
+    // A lead solution replaces the weapon's forward and already accounts for the drop
+    let base_rotation = match lead_direction {
+        Some(direction) => Transform::default().looking_to(direction, Vec3::Y).rotation,
+        None => transform.rotation(),
+    };
+    let forward = base_rotation * Vec3::NEG_Z;
     let right = transform.right();
     let up = transform.up();
 
     for _ in 0..weapon.projectiles_per_shot {
         // --- DYNAMIC SPREAD CALCULATION ---
         // Total spread = Weapon Spread (ADS/Movement) + Accuracy Bloom
-        let total_spread_deg = weapon.spread + accuracy.current_bloom;
+        let total_spread_deg = (weapon.spread + accuracy.current_bloom) * spread_multiplier;
         let spread_angle = total_spread_deg.to_radians();
 
         // Gaussian distribution approximation for spread (more weight towards center)
@@ -182,7 +262,7 @@ pub fn fire_weapon(
         // --- ZEROING CALCULATION ---
         // Adjust pitch based on zeroing distance
         // Simple ballistic arc compensation
-        let zeroing_angle = if weapon.zeroing_distance > 0.0 && weapon.projectile_speed > 0.0 {
+        let zeroing_angle = if lead_direction.is_none() && weapon.zeroing_distance > 0.0 && weapon.projectile_speed > 0.0 {
             // Gravity drop approximation: d = 0.5 * g * t^2
             // t = distance / speed
             let time_to_zero = weapon.zeroing_distance / weapon.projectile_speed;
@@ -196,7 +276,7 @@ pub fn fire_weapon(
         let zeroing_rot = Quat::from_rotation_x(zeroing_angle);
 
         // Combine rotations: Base rotation -> Zeroing -> Spread
-        let final_dir = transform.rotation() * zeroing_rot * spread_rot * Vec3::NEG_Z;
+        let final_dir = base_rotation * zeroing_rot * spread_rot * Vec3::NEG_Z;
 
 
         // Hitscan Logic (Legacy support)
@@ -207,6 +287,12 @@ pub fn fire_weapon(
             // Exclude shooter
             let filter = SpatialQueryFilter::from_excluded_entities([source_entity]);
 
+            // Bullet magnetism
+            let final_dir = match aim_assist {
+                Some((state, settings)) => state.bullet_magnetism(settings, source_entity, ray_origin + Vec3::Y * 1.5, final_dir),
+                None => final_dir,
+            };
+
             if let Some(hit) = spatial_query.cast_ray(
                 ray_origin + Vec3::Y * 1.5,
                 Dir3::new(final_dir).unwrap_or(Dir3::NEG_Z),
@@ -216,7 +302,7 @@ pub fn fire_weapon(
             ) {
                  info!("Hit entity {:?} with {}", hit.entity, weapon.weapon_name);
                  let hit_point = ray_origin + Vec3::Y * 1.5 + final_dir * hit.distance;
-                 damage_events.0.push(DamageEvent {
+                 damage_events.send(DamageEvent {
                     amount: weapon.damage,
                     damage_type: DamageType::Ranged,
                     source: Some(source_entity),
@@ -236,7 +322,7 @@ pub fn fire_weapon(
              let spawn_pos = transform.translation() + forward * 1.0;
              let velocity = final_dir * weapon.projectile_speed;
 
-             commands.spawn((
+             projectile_pool.acquire(commands, (
                 Mesh3d(Default::default()),
                 Transform::from_translation(spawn_pos),
                 GlobalTransform::default(),
@@ -1,11 +1,13 @@
 use bevy::prelude::*;
 use super::types::*;
-use crate::input::InputState;
+use crate::input::{InputAction, InputState};
+use crate::game_manager::types::FeatureToggles;
 use avian3d::prelude::*;
 
 /// System that handles procedural weapon IK transforms, swaying, and bobbing.
 pub fn handle_weapon_ik(
     time: Res<Time>,
+    toggles: Res<FeatureToggles>,
     mut query: Query<(
         Entity,
         &Weapon,
@@ -40,7 +42,7 @@ pub fn handle_weapon_ik(
                  &settings.walk_offset 
             } else if manager.aiming_in_third_person || manager.aiming_in_first_person { // Use manager state
                 &settings.aim_offset
-            } else if input.sprint_pressed { // Could also check character state
+            } else if input.intends(InputAction::Sprint) { // Could also check character state
                 &settings.run_offset
             } else {
                 &settings.walk_offset
@@ -49,9 +51,9 @@ pub fn handle_weapon_ik(
             // Fallback to input if no manager found
              if weapon.is_reloading {
                  &settings.walk_offset
-            } else if input.aim_pressed {
+            } else if input.intends(InputAction::Aim) {
                 &settings.aim_offset
-            } else if input.sprint_pressed {
+            } else if input.intends(InputAction::Sprint) {
                 &settings.run_offset
             } else {
                 &settings.walk_offset
@@ -73,11 +75,15 @@ pub fn handle_weapon_ik(
         let target_sway_x = -mouse_delta.x * settings.sway_settings.horizontal_amount;
         let target_sway_y = -mouse_delta.y * settings.sway_settings.vertical_amount;
         
-        let target_sway = Vec3::new(
-            target_sway_x.clamp(-settings.sway_settings.max_offset.x, settings.sway_settings.max_offset.x),
-            target_sway_y.clamp(-settings.sway_settings.max_offset.y, settings.sway_settings.max_offset.y),
-            0.0,
-        );
+        let target_sway = if toggles.weapon_sway {
+            Vec3::new(
+                target_sway_x.clamp(-settings.sway_settings.max_offset.x, settings.sway_settings.max_offset.x),
+                target_sway_y.clamp(-settings.sway_settings.max_offset.y, settings.sway_settings.max_offset.y),
+                0.0,
+            )
+        } else {
+            Vec3::ZERO
+        };
         state.sway_offset = state.sway_offset.lerp(target_sway, sway_speed * dt);
 
         // 4. Procedural Bobbing
@@ -0,0 +1,358 @@
+//! Ballistic lead solver
+//!
+//! Computes where to aim a projectile so it meets a moving target: given the
+//! muzzle position and speed, gravity, drag and wind, [`solve_lead`] returns the
+//! launch direction, the time of flight and the interception point, on the low
+//! (direct) or high (lobbed) arc. Used by AI shooters through [`LeadAim`], by
+//! turrets, and by the vehicle weapon lead indicator.
+//!
+//! Drag is modeled as linear (proportional to velocity), with the projectile's
+//! quadratic drag linearized at muzzle speed, which keeps the flight equations
+//! closed-form:
+//!
+//! `p(t) = p0 + v0 * (1 - e^(-kt)) / k + a * (t - (1 - e^(-kt)) / k) / k`
+//!
+//! where `a` is gravity plus the wind's pull. The solver searches the flight
+//! time at which the launch speed needed to reach the (moving) target equals the
+//! muzzle speed.
+
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use super::types::{BallisticsEnvironment, Weapon};
+use super::weapon_manager::WeaponManager;
+
+/// Samples of the flight time search
+const SOLVER_SAMPLES: usize = 64;
+/// Bisection steps refining a crossing
+const SOLVER_REFINE_STEPS: usize = 24;
+/// Shortest flight time searched (seconds)
+const MIN_FLIGHT_TIME: f32 = 0.001;
+
+/// Which of the two trajectories reaching a target to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum BallisticArc {
+    /// Flattest, fastest trajectory
+    #[default]
+    Low,
+    /// Lobbed trajectory (mortars, grenades over cover)
+    High,
+}
+
+/// Projectile launch parameters for the lead solver
+#[derive(Debug, Clone, Copy, Reflect)]
+pub struct BallisticShot {
+    pub origin: Vec3,
+    /// Muzzle speed (m/s)
+    pub speed: f32,
+    pub gravity: Vec3,
+    /// Linear drag coefficient (1/s); 0 for none
+    pub drag: f32,
+    pub wind: Vec3,
+    /// Longest time of flight searched (seconds)
+    pub max_time: f32,
+}
+
+impl BallisticShot {
+    pub fn new(origin: Vec3, speed: f32) -> Self {
+        Self {
+            origin,
+            speed,
+            gravity: Vec3::new(0.0, -9.81, 0.0),
+            drag: 0.0,
+            wind: Vec3::ZERO,
+            max_time: 10.0,
+        }
+    }
+
+    /// Shot of `weapon`'s projectiles in the current ballistics environment
+    pub fn from_weapon(origin: Vec3, weapon: &Weapon, environment: &BallisticsEnvironment) -> Self {
+        let drag = if weapon.projectile_mass > 0.0 {
+            0.5 * environment.air_density
+                * weapon.projectile_drag_coeff
+                * weapon.projectile_area
+                * weapon.projectile_speed
+                / weapon.projectile_mass
+        } else {
+            0.0
+        };
+
+        Self {
+            gravity: environment.gravity,
+            drag,
+            wind: environment.wind,
+            ..Self::new(origin, weapon.projectile_speed)
+        }
+    }
+
+    pub fn with_gravity(mut self, gravity: Vec3) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    pub fn with_drag(mut self, drag: f32) -> Self {
+        self.drag = drag.max(0.0);
+        self
+    }
+
+    pub fn with_wind(mut self, wind: Vec3) -> Self {
+        self.wind = wind;
+        self
+    }
+
+    pub fn with_max_time(mut self, max_time: f32) -> Self {
+        self.max_time = max_time;
+        self
+    }
+
+    /// Constant acceleration: gravity plus the drag pulling toward the wind
+    fn acceleration(&self) -> Vec3 {
+        self.gravity + self.wind * self.drag
+    }
+
+    /// Weights of the launch velocity and the acceleration in the displacement after `time`
+    fn displacement_factors(&self, time: f32) -> (f32, f32) {
+        if self.drag < 1e-4 {
+            (time, 0.5 * time * time)
+        } else {
+            let velocity_factor = (1.0 - (-self.drag * time).exp()) / self.drag;
+            (velocity_factor, (time - velocity_factor) / self.drag)
+        }
+    }
+
+    /// Projectile position `time` seconds after launch
+    pub fn position_at(&self, launch_velocity: Vec3, time: f32) -> Vec3 {
+        let (velocity_factor, acceleration_factor) = self.displacement_factors(time);
+        self.origin + launch_velocity * velocity_factor + self.acceleration() * acceleration_factor
+    }
+
+    /// `steps + 1` points of the flight path over `duration` seconds
+    pub fn trajectory(&self, launch_velocity: Vec3, duration: f32, steps: usize) -> Vec<Vec3> {
+        let steps = steps.max(1);
+        (0..=steps)
+            .map(|step| self.position_at(launch_velocity, duration * step as f32 / steps as f32))
+            .collect()
+    }
+
+    /// Launch velocity reaching the moving target after exactly `time` seconds
+    fn launch_velocity_for(&self, target_position: Vec3, target_velocity: Vec3, time: f32) -> Vec3 {
+        let (velocity_factor, acceleration_factor) = self.displacement_factors(time);
+        let target = target_position + target_velocity * time;
+        (target - self.origin - self.acceleration() * acceleration_factor) / velocity_factor
+    }
+}
+
+/// Result of [`solve_lead`]
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct LeadSolution {
+    /// Point along the launch direction at the interception distance (where the
+    /// crosshair goes)
+    pub aim_point: Vec3,
+    pub direction: Vec3,
+    pub launch_velocity: Vec3,
+    /// Where the projectile meets the target
+    pub impact_point: Vec3,
+    pub time_of_flight: f32,
+}
+
+/// Solves the launch direction for `shot` to hit a target moving at a constant
+/// velocity. `None` when the target is out of reach within `shot.max_time`.
+pub fn solve_lead(
+    shot: &BallisticShot,
+    target_position: Vec3,
+    target_velocity: Vec3,
+    arc: BallisticArc,
+) -> Option<LeadSolution> {
+    if shot.speed <= 0.0 || shot.max_time <= MIN_FLIGHT_TIME {
+        return None;
+    }
+
+    // Needed launch speed minus muzzle speed: positive when the shot is too slow
+    let excess = |time: f32| shot.launch_velocity_for(target_position, target_velocity, time).length() - shot.speed;
+
+    // Geometric sampling resolves short flights (fast bullets) and long ones (lobs)
+    let ratio = shot.max_time / MIN_FLIGHT_TIME;
+    let sample_time = |index: usize| MIN_FLIGHT_TIME * ratio.powf(index as f32 / SOLVER_SAMPLES as f32);
+
+    let mut crossing = None;
+    let mut previous_time = sample_time(0);
+    let mut previous_excess = excess(previous_time);
+    for index in 1..=SOLVER_SAMPLES {
+        let time = sample_time(index);
+        let current_excess = excess(time);
+        let found = match arc {
+            // First time the shot becomes fast enough
+            BallisticArc::Low => previous_excess > 0.0 && current_excess <= 0.0,
+            // Last time it still is
+            BallisticArc::High => previous_excess <= 0.0 && current_excess > 0.0,
+        };
+        if found {
+            crossing = Some((previous_time, time));
+            if arc == BallisticArc::Low {
+                break;
+            }
+        }
+        previous_time = time;
+        previous_excess = current_excess;
+    }
+
+    let (mut low, mut high) = crossing?;
+    let low_sign = excess(low) > 0.0;
+    for _ in 0..SOLVER_REFINE_STEPS {
+        let middle = (low + high) * 0.5;
+        if (excess(middle) > 0.0) == low_sign {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    let time_of_flight = (low + high) * 0.5;
+    let launch_velocity = shot.launch_velocity_for(target_position, target_velocity, time_of_flight);
+    let direction = launch_velocity.normalize_or_zero();
+    let impact_point = target_position + target_velocity * time_of_flight;
+
+    Some(LeadSolution {
+        aim_point: shot.origin + direction * shot.origin.distance(impact_point),
+        direction,
+        launch_velocity,
+        impact_point,
+        time_of_flight,
+    })
+}
+
+/// Leads the current weapon's projectiles onto a moving target.
+///
+/// Put on a shooter with a [`WeaponManager`]; while a solution exists, shots
+/// leave along [`LeadSolution::direction`] instead of the weapon's forward.
+/// Hitscan weapons are never led.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct LeadAim {
+    pub enabled: bool,
+    pub target: Option<Entity>,
+    pub arc: BallisticArc,
+    /// Offset from the target's origin of the point aimed at
+    pub target_offset: Vec3,
+    /// Latest solution (read-only)
+    pub solution: Option<LeadSolution>,
+}
+
+impl Default for LeadAim {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            target: None,
+            arc: BallisticArc::Low,
+            target_offset: Vec3::new(0.0, 1.0, 0.0),
+            solution: None,
+        }
+    }
+}
+
+/// System to solve the lead of every [`LeadAim`] shooter
+pub fn update_lead_aim(
+    environment: Res<BallisticsEnvironment>,
+    mut shooter_query: Query<(&mut LeadAim, &WeaponManager)>,
+    weapon_query: Query<(&Weapon, &GlobalTransform)>,
+    target_query: Query<(&GlobalTransform, Option<&LinearVelocity>)>,
+) {
+    for (mut lead, manager) in shooter_query.iter_mut() {
+        let solution = lead_solution(&lead, manager, &environment, &weapon_query, &target_query);
+        if lead.solution != solution {
+            lead.solution = solution;
+        }
+    }
+}
+
+fn lead_solution(
+    lead: &LeadAim,
+    manager: &WeaponManager,
+    environment: &BallisticsEnvironment,
+    weapon_query: &Query<(&Weapon, &GlobalTransform)>,
+    target_query: &Query<(&GlobalTransform, Option<&LinearVelocity>)>,
+) -> Option<LeadSolution> {
+    if !lead.enabled {
+        return None;
+    }
+    let (target_transform, target_velocity) = target_query.get(lead.target?).ok()?;
+    let weapon_entity = manager.weapons_list.get(manager.current_index)?;
+    let (weapon, weapon_transform) = weapon_query.get(*weapon_entity).ok()?;
+    if weapon.projectile_speed <= 0.0 {
+        return None;
+    }
+
+    let shot = BallisticShot::from_weapon(weapon_transform.translation(), weapon, environment);
+    solve_lead(
+        &shot,
+        target_transform.translation() + lead.target_offset,
+        target_velocity.map(|velocity| velocity.0).unwrap_or(Vec3::ZERO),
+        lead.arc,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solution(app: &App, shooter: Entity) -> Option<LeadSolution> {
+        app.world().get::<LeadAim>(shooter).unwrap().solution
+    }
+
+    #[test]
+    fn test_shooter_leads_moving_target_until_it_cannot() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<BallisticsEnvironment>()
+            .add_systems(Update, update_lead_aim);
+
+        let weapon = app
+            .world_mut()
+            .spawn((
+                Weapon { projectile_speed: 25.0, ..default() },
+                GlobalTransform::from_translation(Vec3::new(0.0, 1.5, 0.0)),
+            ))
+            .id();
+        let target = app
+            .world_mut()
+            .spawn((GlobalTransform::from_translation(Vec3::new(0.0, 0.0, -40.0)), LinearVelocity(Vec3::new(5.0, 0.0, 0.0))))
+            .id();
+        let shooter = app
+            .world_mut()
+            .spawn((
+                LeadAim { target: Some(target), ..default() },
+                WeaponManager { weapons_list: vec![weapon], current_index: 0, ..default() },
+            ))
+            .id();
+
+        app.update();
+        let low = solution(&app, shooter).expect("target in reach");
+        let shot = BallisticShot::from_weapon(
+            Vec3::new(0.0, 1.5, 0.0),
+            app.world().get::<Weapon>(weapon).unwrap(),
+            app.world().resource::<BallisticsEnvironment>(),
+        );
+        // Fired at muzzle speed, the projectile meets the target where it will be
+        assert!((low.launch_velocity.length() - 25.0).abs() < 0.05, "speed {}", low.launch_velocity.length());
+        let expected_impact = Vec3::new(0.0, 1.0, -40.0) + Vec3::new(5.0, 0.0, 0.0) * low.time_of_flight;
+        assert!(low.impact_point.distance(expected_impact) < 1e-3);
+        assert!(shot.position_at(low.launch_velocity, low.time_of_flight).distance(low.impact_point) < 0.05);
+        assert!(low.direction.x > 0.0 && low.direction.y > 0.0);
+
+        app.world_mut().get_mut::<LeadAim>(shooter).unwrap().arc = BallisticArc::High;
+        app.update();
+        let high = solution(&app, shooter).expect("lobbed arc in reach");
+        assert!(high.time_of_flight > low.time_of_flight && high.direction.y > low.direction.y);
+
+        // Hitscan weapons aren't led
+        app.world_mut().get_mut::<Weapon>(weapon).unwrap().projectile_speed = 0.0;
+        app.update();
+        assert_eq!(solution(&app, shooter), None);
+
+        app.world_mut().get_mut::<Weapon>(weapon).unwrap().projectile_speed = 25.0;
+        app.update();
+        assert!(solution(&app, shooter).is_some());
+        app.world_mut().despawn(target);
+        app.update();
+        assert_eq!(solution(&app, shooter), None);
+    }
+}
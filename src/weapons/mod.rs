@@ -13,6 +13,10 @@
 //! - **Accuracy System**: Dynamic spread/bloom system
 //! - **Visual Tracers**: Bullet tracer visualization
 //! - **Weapon Attachments**: Scopes, silencers, magazines, etc.
+//! - **Aim Assist**: Gamepad target magnetism, bullet magnetism and ADS snap
+//! - **Armor Condition**: Per-piece durability scaling mitigation, damage-state visuals, repairs and break warnings
+//! - **Crosshair**: Spread-driven reticle with hit markers, reload and low-ammo indicators and a JSON style
+//! - **Skins**: Cosmetic material swaps unlocked by quests, achievements or vendors, previewed in the attachment editor
 
 mod types;
 mod accuracy;
@@ -26,14 +30,21 @@ mod projectiles;
 mod grenades;
 mod ik;
 mod armor;
+mod deflection;
 mod vfx;
 mod animation;
 mod builder;
 mod sniper_sight;
 mod bow;
 mod transform_info;
+mod aim_assist;
+mod crosshair;
+mod lead;
+mod skins;
 
 use bevy::prelude::*;
+use crate::utils::{EventQueuePlugin, PoolPlugin};
+use crate::simulation::SimulationAppExt;
 
 // Re-export types for easier access
 pub use types::*;
@@ -48,22 +59,36 @@ pub use projectiles::*;
 pub use grenades::*;
 pub use ik::*;
 pub use armor::*;
+pub use deflection::*;
 pub use vfx::*;
 pub use animation::*;
 pub use builder::*;
 pub use sniper_sight::*;
 pub use bow::*;
 pub use transform_info::*;
+pub use aim_assist::*;
+pub use crosshair::*;
+pub use lead::*;
+pub use skins::*;
 
 pub struct WeaponsPlugin;
 
 impl Plugin for WeaponsPlugin {
     fn build(&self, app: &mut App) {
+        let simulation = app.simulation_schedule();
+
         app
+            .add_plugins((
+                PoolPlugin::<Projectile>::new(256),
+                PoolPlugin::<BulletTracer>::new(256),
+            ))
             .insert_resource(BallisticsEnvironment::default())
-            .insert_resource(AttachmentEventQueue::default())
+            .add_plugins(EventQueuePlugin::<ToggleAttachmentEditor>::default())
+            .add_plugins(EventQueuePlugin::<SelectAttachment>::default())
+            .add_plugins(EventQueuePlugin::<RemoveAttachment>::default())
             .register_type::<BallisticsEnvironment>()
             .register_type::<Accuracy>()
+            .register_type::<BlindFire>()
             .register_type::<BulletTracer>()
             .register_type::<WeaponManager>()
             .register_type::<WeaponPocket>()
@@ -86,28 +111,95 @@ impl Plugin for WeaponsPlugin {
             .register_type::<WeaponIkState>()
             .register_type::<ArmorSurface>()
             .register_type::<CapturedProjectile>()
-            .init_resource::<ReturnProjectilesQueue>()
+            .register_type::<ProjectileDeflector>()
+            .register_type::<ProjectileShield>()
+            .register_type::<ArmorVisual>()
+            .register_type::<ArmorWarningSettings>()
+            .add_plugins(EventQueuePlugin::<ReturnProjectilesEvent>::default())
+            .add_plugins(EventQueuePlugin::<ProjectileDefenseEvent>::default())
+            .init_resource::<ArmorWarningSettings>()
+            .init_resource::<AimAssistSettings>()
+            .init_resource::<AimAssistState>()
+            .register_type::<AimAssistSettings>()
+            .register_type::<AimAssistTarget>()
+            .register_type::<LeadAim>()
+            .init_resource::<CrosshairStyle>()
+            .init_resource::<CrosshairState>()
+            .register_type::<CrosshairStyle>()
+            .register_type::<CrosshairState>()
+            .register_type::<CrosshairLine>()
+            .register_type::<CrosshairRoot>()
+            .register_type::<CrosshairCircle>()
+            .register_type::<CrosshairDot>()
+            .register_type::<CrosshairHitMarker>()
+            .register_type::<CrosshairReloadBar>()
+            .register_type::<CrosshairReloadFill>()
+            .register_type::<CrosshairAmmoWarning>()
+            .register_type::<Weapon>()
+            .register_type::<ExplosionSettings>()
+            .register_type::<Projectile>()
+            .register_type::<WeaponIkSettings>()
+            .init_asset::<WeaponSkin>()
+            .init_asset_loader::<WeaponSkinLoader>()
+            .init_resource::<WeaponSkinLibrary>()
+            .init_resource::<WeaponSkinCollection>()
+            .init_resource::<WeaponSkinSettings>()
+            .register_type::<WeaponSkinSettings>()
+            .register_type::<SkinTarget>()
+            .add_plugins((
+                EventQueuePlugin::<ArmorEvent>::default(),
+                EventQueuePlugin::<ArmorRepairRequest>::default(),
+                EventQueuePlugin::<WeaponSkinRequest>::default(),
+                EventQueuePlugin::<WeaponSkinEvent>::default(),
+            ))
+            .add_systems(Startup, (setup_armor_warning_ui, setup_crosshair_ui, setup_skin_picker_ui).in_set(crate::headless::PresentationSet))
             .add_systems(Update, (
+                handle_skin_picker_buttons,
+                update_skin_picker_ui,
+            ).chain().in_set(crate::headless::PresentationSet))
+            .add_systems(Update, (find_aim_assist_chest_bones, update_aim_assist)
+                .chain()
+                .in_set(crate::input::InputSet::Gather)
+                .after(crate::input::player_input_sync_system))
+            .add_systems(Update, apply_aim_assist_snap
+                .in_set(crate::camera::CameraSet::Rotation)
+                .after(crate::camera::update_camera_rotation))
+            .configure_sets(simulation, (
+                WeaponsSet::Attachments,
+                WeaponsSet::Fire,
+                WeaponsSet::Effects,
+            ).chain().in_set(crate::GameControllerSet::Combat))
+            .add_systems(simulation, (
                 update_weapons,
+                update_lead_aim.before(handle_weapon_firing),
                 handle_weapon_firing,
                 handle_reloading,
-                update_projectiles,
+                (update_projectile_deflectors, handle_projectile_defenses, update_projectiles).chain(),
                 update_weapon_aim,
                 handle_weapon_switching,
                 update_accuracy,
                 update_tracers,
                 handle_weapon_manager_input,
                 update_weapon_manager,
-            ))
-            .add_systems(Update, (
+            ).in_set(WeaponsSet::Fire))
+            .add_systems(simulation, (
                 handle_attachment_editor_toggle,
                 handle_attachment_selection,
                 handle_attachment_removal,
                 update_weapon_stats_from_attachments,
                 handle_weapon_selection_input,
-                update_weapon_selection_ui,
-            ))
-            .add_systems(Update, (
+                update_weapon_selection_ui.in_set(crate::headless::PresentationSet),
+            ).in_set(WeaponsSet::Attachments))
+            .add_systems(simulation, (
+                add_weapon_skin_targets,
+                register_loaded_weapon_skins,
+                restore_weapon_skins_from_save,
+                handle_weapon_skin_requests,
+                unlock_weapon_skins,
+                apply_weapon_skins,
+                store_weapon_skins_in_save,
+            ).chain().after(handle_attachment_editor_toggle).in_set(WeaponsSet::Attachments))
+            .add_systems(simulation, (
                 handle_muzzle_flash,
                 handle_ejected_shells,
                 initialize_weapon_animation,
@@ -123,7 +215,23 @@ impl Plugin for WeaponsPlugin {
                 handle_weapon_ik,
                 handle_armor_collisions,
                 handle_armor_projectile_return,
-            ));
+            ).in_set(WeaponsSet::Effects))
+            // Armor wear is known once damage is resolved
+            .add_systems(simulation, (
+                handle_armor_repair_requests,
+                update_armor_condition,
+                update_armor_visuals,
+                update_armor_warning_ui.in_set(crate::headless::PresentationSet),
+            ).chain()
+                .in_set(crate::GameControllerSet::Combat)
+                .after(crate::combat::CombatSet::DamageResolution))
+            // Hit markers need the damage results
+            .add_systems(simulation, (
+                update_crosshair_state,
+                update_crosshair_ui.in_set(crate::headless::PresentationSet),
+            ).chain()
+                .in_set(crate::GameControllerSet::Combat)
+                .after(crate::combat::CombatSet::DamageResolution));
     }
 }
 
@@ -163,7 +271,7 @@ fn update_weapon_aim(
     mut query: Query<(&crate::input::InputState, &mut types::Weapon)>,
 ) {
     for (input, mut weapon) in query.iter_mut() {
-        if input.aim_pressed {
+        if input.intends(crate::input::InputAction::Aim) {
             weapon.spread = weapon.base_spread * weapon.aim_spread_mult;
         } else {
             weapon.spread = weapon.base_spread;
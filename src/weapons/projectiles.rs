@@ -2,12 +2,14 @@ use bevy::prelude::*;
 use avian3d::prelude::*;
 use super::types::*;
 use crate::combat::{DamageEventQueue, DamageEvent, DamageType, Health};
+use crate::utils::EntityPool;
 
 /// System to handle advanced projectile behaviors
 pub fn handle_advanced_projectiles(
     mut commands: Commands,
     time: Res<Time>,
     mut damage_events: ResMut<DamageEventQueue>,
+    mut projectile_pool: ResMut<EntityPool<Projectile>>,
     spatial_query: SpatialQuery,
     mut projectile_query: Query<(Entity, &mut Projectile, &mut Transform, &GlobalTransform, Option<&mut Homing>, Option<&mut StickToSurface>), Without<CapturedProjectile>>,
     target_query: Query<(Entity, &GlobalTransform), (With<Health>, Without<Projectile>)>,
@@ -26,8 +28,8 @@ pub fn handle_advanced_projectiles(
                         transform.translation = world_pos;
                         transform.rotation = world_rot;
                     } else {
-                        // Parent is gone, destroy projectile
-                        commands.entity(entity).despawn();
+                        // Parent is gone, recycle projectile
+                        projectile_pool.release(&mut commands, entity);
                     }
                 }
                 continue; // Skip movement logic if stuck
@@ -0,0 +1,802 @@
+//! Weapon skins
+//!
+//! Cosmetic material swaps for weapons and equipment. A [`WeaponSkin`] is a
+//! `.skin.ron` asset (or a value registered in code) holding a palette that is
+//! laid over the original materials of everything under a [`SkinTarget`]:
+//! weapons get one keyed by their name, equipment meshes get one keyed by the
+//! item id. Skins never touch stats.
+//!
+//! Each skin says how it is unlocked: from the start, by completing a quest,
+//! by earning an achievement (reported with [`WeaponSkinRequest::Achievement`])
+//! or by buying its token from a vendor (see `ShopItem::weapon_skin`). While
+//! the attachment editor is open, a picker lists the skins that fit the
+//! weapon; hovering one previews it, locked or not, and clicking equips it.
+//! Unlocked and equipped skins are kept in the player's save data.
+//!
+//! ```ron
+//! (
+//!     id: "tiger",
+//!     name: "Tiger Stripes",
+//!     applies_to: ["Assault Rifle", "SMG"],
+//!     palette: (tint: Some((1.0, 0.6, 0.2, 1.0)), roughness: Some(0.4)),
+//!     unlock: Vendor,
+//!     price: 250.0,
+//! )
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use bevy::asset::{io::Reader, AssetLoader, LoadContext};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::character::Player;
+use crate::inventory::{Inventory, InventoryItem, ItemType};
+use crate::save::{ChangedPlayerSaveData, SaveCustomData};
+use crate::utils::{EventQueue, QueueReader};
+use super::attachments::WeaponAttachmentSystem;
+use super::types::Weapon;
+use super::weapon_manager::WeaponManager;
+
+// ============================================================================
+// SKINS
+// ============================================================================
+
+/// Material overrides of a skin; unset fields keep the original value
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SkinPalette {
+    /// Replaces the base color, in sRGBA
+    #[serde(default)]
+    pub base_color: Option<[f32; 4]>,
+    /// Multiplies the base color, in sRGBA
+    #[serde(default)]
+    pub tint: Option<[f32; 4]>,
+    #[serde(default)]
+    pub metallic: Option<f32>,
+    #[serde(default)]
+    pub roughness: Option<f32>,
+    /// Emissive color, in linear RGB
+    #[serde(default)]
+    pub emissive: Option<[f32; 3]>,
+    /// Asset path of a base color texture replacing the original one
+    #[serde(default)]
+    pub texture: Option<String>,
+}
+
+impl SkinPalette {
+    /// Lays the palette over `material`
+    pub fn apply(&self, material: &mut StandardMaterial, texture: Option<Handle<Image>>) {
+        if let Some([r, g, b, a]) = self.base_color {
+            material.base_color = Color::srgba(r, g, b, a);
+        }
+        if let Some([r, g, b, a]) = self.tint {
+            let base = material.base_color.to_srgba();
+            material.base_color = Color::srgba(base.red * r, base.green * g, base.blue * b, base.alpha * a);
+        }
+        if let Some(metallic) = self.metallic {
+            material.metallic = metallic;
+        }
+        if let Some(roughness) = self.roughness {
+            material.perceptual_roughness = roughness;
+        }
+        if let Some([r, g, b]) = self.emissive {
+            material.emissive = LinearRgba::rgb(r, g, b);
+        }
+        if texture.is_some() {
+            material.base_color_texture = texture;
+        }
+    }
+}
+
+/// How a skin is unlocked
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SkinUnlock {
+    /// Available from the start
+    #[default]
+    Default,
+    /// Completing the quest with this id
+    Quest(u32),
+    /// Earning the achievement with this id
+    Achievement(String),
+    /// Buying the skin's token from a vendor
+    Vendor,
+}
+
+impl SkinUnlock {
+    /// Whether the condition is met. `has_token` tells if the skin's vendor
+    /// token is in the player's inventory.
+    pub fn is_met(&self, achievements: &BTreeSet<String>, completed_quests: &[u32], has_token: bool) -> bool {
+        match self {
+            Self::Default => true,
+            Self::Quest(id) => completed_quests.contains(id),
+            Self::Achievement(id) => achievements.contains(id),
+            Self::Vendor => has_token,
+        }
+    }
+}
+
+impl fmt::Display for SkinUnlock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "Default"),
+            Self::Quest(id) => write!(f, "Complete quest {}", id),
+            Self::Achievement(id) => write!(f, "Achievement: {}", id),
+            Self::Vendor => write!(f, "Sold by vendors"),
+        }
+    }
+}
+
+/// A cosmetic skin, read from `.skin.ron` files
+#[derive(Asset, TypePath, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WeaponSkin {
+    pub id: String,
+    pub name: String,
+    /// Weapon names and equipment item ids it fits; empty fits everything
+    #[serde(default)]
+    pub applies_to: Vec<String>,
+    pub palette: SkinPalette,
+    #[serde(default)]
+    pub unlock: SkinUnlock,
+    /// Price of the vendor token
+    #[serde(default)]
+    pub price: f32,
+}
+
+impl WeaponSkin {
+    pub fn new(id: impl Into<String>, name: impl Into<String>, palette: SkinPalette) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            palette,
+            ..default()
+        }
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, String> {
+        ron::from_str(text).map_err(|e| format!("Invalid weapon skin: {}", e))
+    }
+
+    pub fn fits(&self, key: &str) -> bool {
+        self.applies_to.is_empty() || self.applies_to.iter().any(|target| target == key)
+    }
+
+    /// Item id of the token vendors sell for this skin
+    pub fn token_id(&self) -> String {
+        format!("skin:{}", self.id)
+    }
+
+    /// Inventory item a vendor sells to unlock this skin; it is used up on unlock
+    pub fn token(&self) -> InventoryItem {
+        InventoryItem {
+            item_id: self.token_id(),
+            name: format!("{} Skin", self.name),
+            quantity: 1,
+            max_stack: 1,
+            weight: 0.0,
+            item_type: ItemType::KeyItem,
+            icon_path: String::new(),
+            value: self.price,
+            category: "Skins".to_string(),
+            min_level: 0,
+            info: format!("Unlocks the {} weapon skin", self.name),
+            is_infinite: false,
+        }
+    }
+}
+
+/// Reads [`WeaponSkin`] assets from `.skin.ron` files
+#[derive(Default)]
+pub struct WeaponSkinLoader;
+
+#[derive(Debug)]
+pub enum WeaponSkinLoadError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for WeaponSkinLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Could not read weapon skin: {}", error),
+            Self::Ron(error) => write!(f, "Invalid weapon skin: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for WeaponSkinLoadError {}
+
+impl From<std::io::Error> for WeaponSkinLoadError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl AssetLoader for WeaponSkinLoader {
+    type Asset = WeaponSkin;
+    type Settings = ();
+    type Error = WeaponSkinLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        ron::de::from_bytes(&bytes).map_err(WeaponSkinLoadError::Ron)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["skin.ron"]
+    }
+}
+
+/// Every known skin, by id
+#[derive(Resource, Debug, Default)]
+pub struct WeaponSkinLibrary {
+    pub skins: HashMap<String, WeaponSkin>,
+    /// Skin assets still loading; registered once they are
+    pub pending: Vec<Handle<WeaponSkin>>,
+}
+
+impl WeaponSkinLibrary {
+    pub fn register(&mut self, skin: WeaponSkin) {
+        self.skins.insert(skin.id.clone(), skin);
+    }
+
+    /// Loads a `.skin.ron` file and registers it once loaded
+    pub fn load(&mut self, asset_server: &AssetServer, path: impl Into<String>) {
+        self.pending.push(asset_server.load(path.into()));
+    }
+
+    pub fn get(&self, id: &str) -> Option<&WeaponSkin> {
+        self.skins.get(id)
+    }
+
+    /// Skins that fit `key`, sorted by name
+    pub fn fitting(&self, key: &str) -> Vec<&WeaponSkin> {
+        let mut skins: Vec<&WeaponSkin> = self.skins.values().filter(|skin| skin.fits(key)).collect();
+        skins.sort_by(|a, b| a.name.cmp(&b.name));
+        skins
+    }
+}
+
+// ============================================================================
+// COLLECTION
+// ============================================================================
+
+#[derive(Resource, Debug, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct WeaponSkinSettings {
+    /// Key of the collection in the player's `SaveCustomData`
+    pub save_key: String,
+}
+
+impl Default for WeaponSkinSettings {
+    fn default() -> Self {
+        Self { save_key: "weapon_skins".to_string() }
+    }
+}
+
+/// The player's unlocked and equipped skins; this is what gets saved
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WeaponSkinCollection {
+    pub unlocked: BTreeSet<String>,
+    /// Achievements earned so far
+    pub achievements: BTreeSet<String>,
+    /// Skin worn by each weapon name or equipment item id
+    pub equipped: BTreeMap<String, String>,
+}
+
+impl WeaponSkinCollection {
+    pub fn is_unlocked(&self, skin: &str) -> bool {
+        self.unlocked.contains(skin)
+    }
+
+    pub fn equipped(&self, key: &str) -> Option<&str> {
+        self.equipped.get(key).map(String::as_str)
+    }
+
+    /// Unlocks `skin`, returning whether it was locked
+    pub fn unlock(&mut self, skin: &str) -> bool {
+        self.unlocked.insert(skin.to_string())
+    }
+
+    /// Puts `skin` on `key`, or the original look with `None`
+    pub fn equip(&mut self, library: &WeaponSkinLibrary, key: &str, skin: Option<&str>) -> Result<(), SkinRejection> {
+        let Some(skin) = skin else {
+            self.equipped.remove(key);
+            return Ok(());
+        };
+        let Some(definition) = library.get(skin) else {
+            return Err(SkinRejection::Unknown);
+        };
+        if !definition.fits(key) {
+            return Err(SkinRejection::DoesNotFit);
+        }
+        if !self.is_unlocked(skin) {
+            return Err(SkinRejection::Locked);
+        }
+        self.equipped.insert(key.to_string(), skin.to_string());
+        Ok(())
+    }
+}
+
+/// Something that can wear a skin: a weapon, or the root of an equipment mesh
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct SkinTarget {
+    /// Weapon name or equipment item id the equipped skin is looked up by
+    pub key: String,
+    /// Skin shown instead of the equipped one while the editor previews it
+    pub preview: Option<String>,
+    /// Skin on display
+    pub applied: Option<String>,
+    /// Materials of the meshes before any skin
+    #[reflect(ignore)]
+    pub originals: HashMap<Entity, Handle<StandardMaterial>>,
+    /// Skinned materials by mesh and skin
+    #[reflect(ignore)]
+    pub skinned: HashMap<(Entity, String), Handle<StandardMaterial>>,
+}
+
+impl SkinTarget {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self { key: key.into(), ..default() }
+    }
+
+    /// Skin to show: the preview, else the equipped one
+    pub fn wanted(&self, collection: &WeaponSkinCollection) -> Option<String> {
+        self.preview.clone().or_else(|| collection.equipped(&self.key).map(str::to_string))
+    }
+}
+
+// ============================================================================
+// EVENTS
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WeaponSkinRequest {
+    /// Puts a skin on a weapon name or equipment item id; `None` restores the original look
+    Equip { key: String, skin: Option<String> },
+    /// Shows a skin on a target without equipping it, locked or not
+    Preview { target: Entity, skin: Option<String> },
+    /// Unlocks a skin outright, e.g. as a reward
+    Grant(String),
+    /// Records an achievement, unlocking the skins it gates
+    Achievement(String),
+}
+
+pub type WeaponSkinRequestQueue = EventQueue<WeaponSkinRequest>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkinRejection {
+    Unknown,
+    Locked,
+    DoesNotFit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WeaponSkinEvent {
+    Unlocked(String),
+    Equipped { key: String, skin: Option<String> },
+    Rejected { key: String, skin: String, reason: SkinRejection },
+}
+
+pub type WeaponSkinEventQueue = EventQueue<WeaponSkinEvent>;
+
+/// Weapon whose attachment editor is open, if any
+fn edited_weapon<'a>(
+    editors: impl Iterator<Item = (Entity, &'a WeaponAttachmentSystem, Option<&'a WeaponManager>)>,
+) -> Option<Entity> {
+    editors
+        .filter(|(_, attachments, _)| attachments.editing_attachments)
+        .find_map(|(entity, _, manager)| match manager {
+            Some(manager) => manager.get_current_weapon(),
+            None => Some(entity),
+        })
+}
+
+// ============================================================================
+// SYSTEMS
+// ============================================================================
+
+/// System to give weapons a skin target keyed by their name
+pub fn add_weapon_skin_targets(
+    mut commands: Commands,
+    query: Query<(Entity, &Weapon), Without<SkinTarget>>,
+) {
+    for (entity, weapon) in query.iter() {
+        commands.entity(entity).insert(SkinTarget::new(weapon.weapon_name.clone()));
+    }
+}
+
+/// System to register skin assets once they are loaded
+pub fn register_loaded_weapon_skins(
+    mut library: ResMut<WeaponSkinLibrary>,
+    skins: Res<Assets<WeaponSkin>>,
+) {
+    if library.pending.is_empty() {
+        return;
+    }
+    let pending = std::mem::take(&mut library.pending);
+    for handle in pending {
+        match skins.get(&handle) {
+            Some(skin) => library.register(skin.clone()),
+            None => library.pending.push(handle),
+        }
+    }
+}
+
+/// System to handle equip, preview, grant and achievement requests, and end
+/// previews once the attachment editor closes
+pub fn handle_weapon_skin_requests(
+    mut requests: QueueReader<WeaponSkinRequest>,
+    library: Res<WeaponSkinLibrary>,
+    mut collection: ResMut<WeaponSkinCollection>,
+    mut events: ResMut<WeaponSkinEventQueue>,
+    mut target_query: Query<(Entity, &mut SkinTarget)>,
+    editor_query: Query<(Entity, &WeaponAttachmentSystem, Option<&WeaponManager>)>,
+) {
+    for request in requests.read().cloned() {
+        match request {
+            WeaponSkinRequest::Equip { key, skin } => {
+                match collection.equip(&library, &key, skin.as_deref()) {
+                    Ok(()) => events.send(WeaponSkinEvent::Equipped { key, skin }),
+                    Err(reason) => events.send(WeaponSkinEvent::Rejected {
+                        key,
+                        skin: skin.unwrap_or_default(),
+                        reason,
+                    }),
+                }
+            }
+            WeaponSkinRequest::Preview { target, skin } => {
+                let Ok((_, mut target)) = target_query.get_mut(target) else { continue };
+                let skin = skin.filter(|skin| library.get(skin).is_some_and(|definition| definition.fits(&target.key)));
+                if target.preview != skin {
+                    target.preview = skin;
+                }
+            }
+            WeaponSkinRequest::Grant(skin) => {
+                if library.get(&skin).is_some() && collection.unlock(&skin) {
+                    events.send(WeaponSkinEvent::Unlocked(skin));
+                }
+            }
+            WeaponSkinRequest::Achievement(id) => {
+                if !collection.achievements.contains(&id) {
+                    collection.achievements.insert(id);
+                }
+            }
+        }
+    }
+
+    let edited = edited_weapon(editor_query.iter());
+    for (entity, mut target) in target_query.iter_mut() {
+        if target.preview.is_some() && Some(entity) != edited {
+            target.preview = None;
+        }
+    }
+}
+
+/// System to unlock the skins whose condition the player meets, using up
+/// the vendor tokens they bought
+pub fn unlock_weapon_skins(
+    library: Res<WeaponSkinLibrary>,
+    mut collection: ResMut<WeaponSkinCollection>,
+    mut events: ResMut<WeaponSkinEventQueue>,
+    mut player_query: Query<(Entity, Option<&mut Inventory>), With<Player>>,
+    #[cfg(feature = "quest")] quest_query: Query<&crate::quest::QuestLog>,
+) {
+    let Some((player, mut inventory)) = player_query.iter_mut().next() else { return };
+
+    #[cfg(feature = "quest")]
+    let completed_quests: Vec<u32> = quest_query
+        .get(player)
+        .map(|log| log.completed_quests.iter().map(|quest| quest.id).collect())
+        .unwrap_or_default();
+    #[cfg(not(feature = "quest"))]
+    let completed_quests: Vec<u32> = {
+        let _ = player;
+        Vec::new()
+    };
+
+    let mut unlocked = Vec::new();
+    for skin in library.skins.values() {
+        if collection.is_unlocked(&skin.id) {
+            continue;
+        }
+        let token = skin.token_id();
+        let has_token = inventory.as_ref().is_some_and(|inventory| inventory.item_count(&token) > 0);
+        if !skin.unlock.is_met(&collection.achievements, &completed_quests, has_token) {
+            continue;
+        }
+        if skin.unlock == SkinUnlock::Vendor {
+            if let Some(inventory) = inventory.as_mut() {
+                inventory.remove_quantity(&token, 1);
+            }
+        }
+        unlocked.push(skin.id.clone());
+    }
+
+    for skin in unlocked {
+        collection.unlock(&skin);
+        info!("Unlocked weapon skin {}", skin);
+        events.send(WeaponSkinEvent::Unlocked(skin));
+    }
+}
+
+/// System to lay the skin a target should show over the materials of its meshes
+pub fn apply_weapon_skins(
+    asset_server: Res<AssetServer>,
+    library: Res<WeaponSkinLibrary>,
+    collection: Res<WeaponSkinCollection>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut target_query: Query<(Entity, &mut SkinTarget)>,
+    children_query: Query<&Children>,
+    mut mesh_query: Query<&mut MeshMaterial3d<StandardMaterial>>,
+) {
+    for (root, mut target) in target_query.iter_mut() {
+        let target = target.as_mut();
+        let wanted = target.wanted(&collection).and_then(|id| library.get(&id));
+        let wanted_id = wanted.map(|skin| skin.id.clone());
+        if target.applied != wanted_id {
+            target.applied = wanted_id;
+        }
+
+        for mesh in std::iter::once(root).chain(children_query.iter_descendants(root)) {
+            let Ok(mut material) = mesh_query.get_mut(mesh) else { continue };
+
+            // Anything that isn't one of our materials is the mesh's own look
+            if !target.skinned.values().any(|handle| *handle == material.0) {
+                target.originals.insert(mesh, material.0.clone());
+            }
+            let Some(original) = target.originals.get(&mesh).cloned() else { continue };
+
+            let handle = match wanted {
+                None => original,
+                Some(skin) => target
+                    .skinned
+                    .entry((mesh, skin.id.clone()))
+                    .or_insert_with(|| {
+                        let mut skinned = materials.get(&original).cloned().unwrap_or_default();
+                        let texture = skin.palette.texture.as_ref().map(|path| asset_server.load(path.clone()));
+                        skin.palette.apply(&mut skinned, texture);
+                        materials.add(skinned)
+                    })
+                    .clone(),
+            };
+            if material.0 != handle {
+                material.0 = handle;
+            }
+        }
+    }
+}
+
+/// System to keep the skin collection in the data written to saves
+pub fn store_weapon_skins_in_save(
+    mut commands: Commands,
+    settings: Res<WeaponSkinSettings>,
+    collection: Res<WeaponSkinCollection>,
+    mut player_query: Query<(Entity, Option<&mut SaveCustomData>), With<Player>>,
+) {
+    if !collection.is_changed() {
+        return;
+    }
+    let Some((player, custom_data)) = player_query.iter_mut().next() else { return };
+    let Ok(value) = serde_json::to_value(&*collection) else { return };
+
+    match custom_data {
+        Some(mut custom_data) => {
+            if custom_data.0.get(&settings.save_key) != Some(&value) {
+                custom_data.0.insert(settings.save_key.clone(), value);
+            }
+        }
+        None => {
+            let mut custom_data = SaveCustomData::default();
+            custom_data.0.insert(settings.save_key.clone(), value);
+            commands.entity(player).insert(custom_data);
+        }
+    }
+}
+
+/// System to bring back the skin collection of a loaded save
+pub fn restore_weapon_skins_from_save(
+    settings: Res<WeaponSkinSettings>,
+    mut collection: ResMut<WeaponSkinCollection>,
+    save_query: Query<(Entity, &SaveCustomData), ChangedPlayerSaveData>,
+) {
+    for (entity, custom_data) in save_query.iter() {
+        let Some(value) = custom_data.0.get(&settings.save_key) else { continue };
+        let saved: WeaponSkinCollection = match serde_json::from_value(value.clone()) {
+            Ok(saved) => saved,
+            Err(err) => {
+                warn!("Invalid saved weapon skins on {:?}: {}", entity, err);
+                continue;
+            }
+        };
+        // Our own write-back
+        if saved == *collection {
+            continue;
+        }
+        *collection = saved;
+    }
+}
+
+// ============================================================================
+// PICKER
+// ============================================================================
+
+const SKIN_BUTTON_COLOR: Color = Color::srgba(0.1, 0.1, 0.1, 0.85);
+const SKIN_BUTTON_HOVER_COLOR: Color = Color::srgba(0.25, 0.25, 0.25, 0.9);
+const SKIN_EQUIPPED_COLOR: Color = Color::srgb(1.0, 0.85, 0.3);
+const SKIN_LOCKED_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+
+/// Root of the skin picker shown next to the attachment editor
+#[derive(Component)]
+pub struct SkinPickerRoot;
+
+/// A skin entry of the picker; `None` is the original look
+#[derive(Component, Debug, Clone)]
+pub struct SkinPickerButton {
+    pub target: Entity,
+    pub key: String,
+    pub skin: Option<String>,
+}
+
+/// System to setup the skin picker (hidden by default)
+pub fn setup_skin_picker_ui(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(20.0),
+            top: Val::Px(120.0),
+            width: Val::Px(260.0),
+            flex_direction: FlexDirection::Column,
+            padding: UiRect::all(Val::Px(10.0)),
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        SkinPickerRoot,
+        Visibility::Hidden,
+    ));
+}
+
+/// System to list the skins of the edited weapon in the picker
+pub fn update_skin_picker_ui(
+    mut commands: Commands,
+    library: Res<WeaponSkinLibrary>,
+    collection: Res<WeaponSkinCollection>,
+    editor_query: Query<(Entity, &WeaponAttachmentSystem, Option<&WeaponManager>)>,
+    target_query: Query<&SkinTarget>,
+    mut root_query: Query<(Entity, &mut Visibility, Option<&Children>), With<SkinPickerRoot>>,
+    mut shown: Local<Option<Entity>>,
+) {
+    let edited = edited_weapon(editor_query.iter()).filter(|weapon| target_query.contains(*weapon));
+    if edited == *shown && !library.is_changed() && !collection.is_changed() {
+        return;
+    }
+    *shown = edited;
+
+    let Ok((root, mut visibility, children)) = root_query.single_mut() else { return };
+    for child in children.into_iter().flat_map(|children| children.iter()) {
+        commands.entity(child).despawn();
+    }
+    let Some((weapon, target)) = edited.and_then(|weapon| Some((weapon, target_query.get(weapon).ok()?))) else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    visibility.set_if_neq(Visibility::Visible);
+
+    let equipped = collection.equipped(&target.key);
+    commands.entity(root).with_children(|root| {
+        root.spawn((
+            Text::new("Skins"),
+            TextFont { font_size: 18.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+        spawn_skin_button(root, weapon, &target.key, None, "Default".to_string(), equipped.is_none(), true);
+        for skin in library.fitting(&target.key) {
+            let unlocked = collection.is_unlocked(&skin.id);
+            let label = if unlocked { skin.name.clone() } else { format!("{} ({})", skin.name, skin.unlock) };
+            spawn_skin_button(root, weapon, &target.key, Some(skin.id.clone()), label, equipped == Some(skin.id.as_str()), unlocked);
+        }
+    });
+}
+
+fn spawn_skin_button(
+    parent: &mut ChildSpawnerCommands,
+    target: Entity,
+    key: &str,
+    skin: Option<String>,
+    label: String,
+    equipped: bool,
+    unlocked: bool,
+) {
+    let color = match (equipped, unlocked) {
+        (true, _) => SKIN_EQUIPPED_COLOR,
+        (false, true) => Color::WHITE,
+        (false, false) => SKIN_LOCKED_COLOR,
+    };
+    parent.spawn((
+        Button,
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Px(26.0),
+            padding: UiRect::horizontal(Val::Px(8.0)),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(SKIN_BUTTON_COLOR),
+        SkinPickerButton { target, key: key.to_string(), skin },
+    )).with_children(|button| {
+        button.spawn((
+            Text::new(label),
+            TextFont { font_size: 15.0, ..default() },
+            TextColor(color),
+        ));
+    });
+}
+
+/// System to preview hovered skins and equip clicked ones
+pub fn handle_skin_picker_buttons(
+    mut button_query: Query<(&Interaction, &SkinPickerButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut requests: ResMut<WeaponSkinRequestQueue>,
+) {
+    for (interaction, button, mut background) in button_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => {
+                requests.send(WeaponSkinRequest::Equip { key: button.key.clone(), skin: button.skin.clone() });
+            }
+            Interaction::Hovered => {
+                background.0 = SKIN_BUTTON_HOVER_COLOR;
+                requests.send(WeaponSkinRequest::Preview { target: button.target, skin: button.skin.clone() });
+            }
+            Interaction::None => background.0 = SKIN_BUTTON_COLOR,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+
+    #[test]
+    fn test_weapon_skin_unlocks() {
+        let tiger = WeaponSkin::from_ron(
+            "(id: \"tiger\", name: \"Tiger\", applies_to: [\"Rifle\"], palette: (tint: Some((1.0, 0.5, 0.0, 1.0))), unlock: Vendor, price: 250.0)",
+        )
+        .unwrap();
+        assert!(tiger.fits("Rifle") && !tiger.fits("Pistol"));
+        assert_eq!(tiger.token().item_id, "skin:tiger");
+
+        let mut library = WeaponSkinLibrary::default();
+        library.register(tiger.clone());
+        let mut collection = WeaponSkinCollection::default();
+        assert!(!tiger.unlock.is_met(&collection.achievements, &[], false));
+        assert_eq!(collection.equip(&library, "Rifle", Some("tiger")), Err(SkinRejection::Locked));
+        assert!(collection.unlock("tiger"));
+        assert_eq!(collection.equip(&library, "Pistol", Some("tiger")), Err(SkinRejection::DoesNotFit));
+        assert_eq!(collection.equip(&library, "Rifle", Some("camo")), Err(SkinRejection::Unknown));
+        assert!(collection.equip(&library, "Rifle", Some("tiger")).is_ok());
+
+        collection.achievements.insert("sharpshooter".to_string());
+        assert!(SkinUnlock::Achievement("sharpshooter".to_string()).is_met(&collection.achievements, &[], false));
+        assert!(SkinUnlock::Quest(3).is_met(&collection.achievements, &[1, 3], false));
+
+        let mut target = SkinTarget::new("Rifle");
+        assert_eq!(target.wanted(&collection).as_deref(), Some("tiger"));
+        target.preview = Some("gold".to_string());
+        assert_eq!(target.wanted(&collection).as_deref(), Some("gold"));
+
+        let mut material = StandardMaterial { base_color: Color::srgb(0.5, 0.5, 0.5), ..default() };
+        tiger.palette.apply(&mut material, None);
+        assert_eq!(material.base_color, Color::srgb(0.5, 0.25, 0.0));
+
+        let saved = serde_json::to_value(&collection).unwrap();
+        assert_eq!(serde_json::from_value::<WeaponSkinCollection>(saved).unwrap(), collection);
+    }
+}
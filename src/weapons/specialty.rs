@@ -147,7 +147,7 @@ pub fn handle_beam_weapon(
     ) {
         // Apply damage/heal over time
         let hit_pos = ray_origin + *ray_dir * hit.distance;
-        damage_events.0.push(DamageEvent {
+        damage_events.send(DamageEvent {
             amount: settings.damage_per_second * dt,
             damage_type: match settings.beam_type {
                 BeamType::Laser => DamageType::Ranged,
@@ -1,12 +1,14 @@
 //! Visual tracer system for projectiles
 
 use bevy::prelude::*;
+use crate::utils::EntityPool;
 use super::types::BulletTracer;
 
 /// Update visual tracers with interpolation
 pub fn update_tracers(
     mut commands: Commands,
     time: Res<Time>,
+    mut tracer_pool: ResMut<EntityPool<BulletTracer>>,
     mut query: Query<(Entity, &mut Transform, &mut BulletTracer)>,
 ) {
     for (entity, mut transform, mut tracer) in query.iter_mut() {
@@ -15,14 +17,14 @@ pub fn update_tracers(
         let distance = direction.length();
 
         if distance < 0.1 {
-            commands.entity(entity).despawn();
+            tracer_pool.release(&mut commands, entity);
             continue;
         }
 
         let move_amount = tracer.speed * time.delta_secs();
         if move_amount >= distance {
             transform.translation = tracer.target_pos;
-            commands.entity(entity).despawn();
+            tracer_pool.release(&mut commands, entity);
         } else {
             let normalized_dir = direction / distance;
             tracer.current_pos += normalized_dir * move_amount;
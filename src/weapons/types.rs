@@ -4,6 +4,18 @@
 
 use bevy::prelude::*;
 
+/// Weapon phases, run in order inside `GameControllerSet::Combat` and before
+/// `CombatSet::DamageResolution`
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeaponsSet {
+    /// Attachment editing and weapon selection
+    Attachments,
+    /// Timers, firing, reloading, projectiles and switching
+    Fire,
+    /// Muzzle flashes, shells, animation, IK and special projectiles
+    Effects,
+}
+
 /// Weapon component
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
@@ -84,6 +96,12 @@ pub struct Weapon {
     // Specialty Behaviors
     pub specialty_behavior: SpecialtyBehavior,
 
+    // Works underwater regardless of `UnderwaterCombatRules::blocked_weapon_types` (harpoons)
+    pub usable_underwater: bool,
+
+    // Scales the global `AimAssistSettings` for this weapon
+    pub aim_assist: WeaponAimAssist,
+
     // Transform Settings
     pub transform_info: WeaponTransformInfo,
 }
@@ -155,6 +173,8 @@ impl Default for Weapon {
             sniper_sight_settings: None,
             bow_settings: None,
             specialty_behavior: SpecialtyBehavior::None,
+            usable_underwater: false,
+            aim_assist: WeaponAimAssist::default(),
             transform_info: WeaponTransformInfo::default(),
         }
     }
@@ -212,6 +232,7 @@ pub struct SniperSight {
 }
 
 #[derive(Component, Debug, Clone, Reflect, Default, PartialEq)]
+#[reflect(Component)]
 pub struct ExplosionSettings {
     pub force: f32,
     pub radius: f32,
@@ -243,6 +264,54 @@ pub enum WeaponType {
     Thrown,
 }
 
+/// Per-weapon aim assist, multiplying the global `AimAssistSettings`
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct WeaponAimAssist {
+    pub enabled: bool,
+    /// Look slowdown over a target
+    pub slowdown: f32,
+    /// Rotational magnetism toward the target
+    pub rotation: f32,
+    /// Bullet magnetism cone and radius
+    pub bullet_magnetism: f32,
+    /// Angle in which aiming down sights snaps to a target
+    pub snap: f32,
+}
+
+impl Default for WeaponAimAssist {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            slowdown: 1.0,
+            rotation: 1.0,
+            bullet_magnetism: 1.0,
+            snap: 1.0,
+        }
+    }
+}
+
+impl WeaponAimAssist {
+    pub const NONE: Self = Self {
+        enabled: false,
+        slowdown: 0.0,
+        rotation: 0.0,
+        bullet_magnetism: 0.0,
+        snap: 0.0,
+    };
+
+    /// Usual tuning for a weapon type: precise weapons slow down more and pull less
+    pub fn for_weapon_type(weapon_type: WeaponType) -> Self {
+        match weapon_type {
+            WeaponType::Melee => Self::NONE,
+            WeaponType::Pistol => Self::default(),
+            WeaponType::Rifle => Self { rotation: 0.8, bullet_magnetism: 0.75, ..default() },
+            WeaponType::Shotgun => Self { slowdown: 0.75, rotation: 1.25, bullet_magnetism: 1.5, ..default() },
+            WeaponType::Bow => Self { slowdown: 1.25, rotation: 0.5, bullet_magnetism: 0.0, ..default() },
+            WeaponType::Thrown => Self { slowdown: 0.5, rotation: 0.5, bullet_magnetism: 0.0, snap: 0.5, ..default() },
+        }
+    }
+}
+
 /// Firing mode for weapons
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Default)]
 pub enum FiringMode {
@@ -399,6 +468,15 @@ pub struct Projectile {
     pub rotate_to_velocity: bool,
 }
 
+impl crate::utils::Poolable for Projectile {
+    fn on_release(entity: &mut EntityCommands) {
+        // Per-shot behaviours must not leak into the next shot
+        entity.remove::<(Homing, StickToSurface, CapturedProjectile, ExplosionSettings, crate::combat::ReturnToOwner)>();
+        // Thrown projectiles (grenades) carry their own body
+        entity.remove::<(avian3d::prelude::RigidBody, avian3d::prelude::Collider)>();
+    }
+}
+
 /// Visual tracer component for interpolation
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
@@ -409,6 +487,8 @@ pub struct BulletTracer {
     pub speed: f32,
 }
 
+impl crate::utils::Poolable for BulletTracer {}
+
 /// Accuracy component for dynamic spread
 #[derive(Component, Debug, Reflect, Default)]
 #[reflect(Component)]
@@ -424,12 +504,28 @@ pub struct Accuracy {
     pub airborne_multiplier: f32,
 }
 
+/// Put on a shooter firing without aiming down sights, like from behind cover.
+/// Spread is multiplied and aim assist is skipped while it is present.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct BlindFire {
+    pub spread_multiplier: f32,
+}
+
+impl Default for BlindFire {
+    fn default() -> Self {
+        Self { spread_multiplier: 3.0 }
+    }
+}
+
 /// Global Ballistics Environment Resource
 #[derive(Resource, Debug, Reflect)]
 #[reflect(Resource)]
 pub struct BallisticsEnvironment {
     pub gravity: Vec3,
     pub air_density: f32,
+    /// Density used for projectiles inside a `WaterZone`
+    pub water_density: f32,
     pub wind: Vec3,
 }
 
@@ -438,6 +534,7 @@ impl Default for BallisticsEnvironment {
         Self {
             gravity: Vec3::new(0.0, -9.81, 0.0),
             air_density: 1.225,
+            water_density: 1000.0,
             wind: Vec3::ZERO,
         }
     }
@@ -672,6 +769,7 @@ pub struct SwaySettings {
 
 /// Settings for weapon procedural IK positioning
 #[derive(Component, Debug, Clone, Reflect, Default, PartialEq)]
+#[reflect(Component)]
 pub struct WeaponIkSettings {
     pub aim_offset: Transform,
     pub walk_offset: Transform,
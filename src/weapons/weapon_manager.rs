@@ -3,7 +3,7 @@
 //! Manages weapon inventory, switching, pockets, and dual weapons
 
 use bevy::prelude::*;
-use crate::input::InputState;
+use crate::input::{InputAction, InputState};
 use crate::character::{CharacterController, Player};
 use crate::physics::GroundDetection;
 use super::types::{Weapon, WeaponPocket, WeaponListOnPocket, PocketType};
@@ -321,15 +321,14 @@ pub fn handle_weapon_manager_input(
             manager.last_time_draw_weapon = time.elapsed_secs();
         }
 
-        // Aim weapon input
-        if input.aim_pressed {
-            if manager.carrying_weapon_in_third_person || manager.carrying_weapon_in_first_person {
-                //  aiming usually toggles or holds based on settings. 
-                // Here we'll stick to the existing toggle logic but ensure it's responsive.
-                manager.aiming_in_third_person = !manager.aiming_in_third_person;
-                manager.aiming_in_first_person = !manager.aiming_in_first_person; // Mirror for 1P
-                manager.aim_mode_input_pressed = manager.aiming_in_third_person || manager.aiming_in_first_person;
-            }
+        // Aim weapon input; hold or toggle comes from `InputConfig::accessibility`.
+        // Only changes of intent are applied, so actions can still aim the weapon
+        let aiming = input.intends(InputAction::Aim);
+        let carrying = manager.carrying_weapon_in_third_person || manager.carrying_weapon_in_first_person;
+        if carrying && aiming != manager.aim_mode_input_pressed {
+            manager.aiming_in_third_person = aiming;
+            manager.aiming_in_first_person = aiming; // Mirror for 1P
+            manager.aim_mode_input_pressed = aiming;
         }
 
         // Reload weapon input
@@ -339,7 +338,7 @@ pub fn handle_weapon_manager_input(
         }
 
         // Update last time used
-        if input.fire_pressed || input.aim_pressed || input.reload_pressed {
+        if input.fire_pressed || input.intends(InputAction::Aim) || input.reload_pressed {
             manager.last_time_used = time.elapsed_secs();
         }
     }
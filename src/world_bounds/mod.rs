@@ -0,0 +1,36 @@
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+use crate::utils::EventQueuePlugin;
+
+pub use types::{
+    WorldBounds, WorldBoundsBox, KillVolume, KillVolumeEffect,
+    SafeGroundTracker, RecoveryPhase, RecoveryFadeOverlay,
+    OutOfBoundsReason, OutOfBoundsEvent, OutOfBoundsEventQueue,
+};
+pub use systems::is_position_safe;
+
+pub struct WorldBoundsPlugin;
+
+impl Plugin for WorldBoundsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<WorldBounds>()
+            .add_plugins(EventQueuePlugin::<OutOfBoundsEvent>::default())
+            .register_type::<WorldBounds>()
+            .register_type::<KillVolume>()
+            .register_type::<SafeGroundTracker>()
+            .register_type::<RecoveryFadeOverlay>()
+            .add_systems(Startup, setup_recovery_fade_overlay)
+            .add_systems(Update, (
+                track_safe_ground,
+                check_world_bounds,
+                update_out_of_bounds_recovery,
+                update_recovery_fade_overlay,
+                draw_world_bounds.in_set(crate::headless::PresentationSet),
+            ).chain());
+    }
+}
@@ -0,0 +1,445 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::character::Player;
+use crate::combat::{DamageEvent, DamageEventQueue, DamageType, DeathEvent, DeathEventQueue, Health};
+use crate::physics::GroundDetection;
+#[cfg(feature = "vehicles")]
+use crate::vehicles::Vehicle;
+use super::types::*;
+
+/// System to spawn the full screen overlay used by the recovery fade
+pub fn setup_recovery_fade_overlay(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.0)),
+        GlobalZIndex(110),
+        RecoveryFadeOverlay::default(),
+    ));
+}
+
+/// System to remember the last position each tracked entity stood safely on
+#[cfg_attr(not(feature = "vehicles"), allow(unused_variables))]
+pub fn track_safe_ground(
+    time: Res<Time>,
+    bounds: Res<WorldBounds>,
+    mut tracker_query: Query<(Entity, &GlobalTransform, &mut SafeGroundTracker, Option<&GroundDetection>)>,
+    volume_query: Query<(&GlobalTransform, &KillVolume)>,
+    #[cfg(feature = "vehicles")] vehicle_query: Query<&Vehicle>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, transform, mut tracker, ground) in tracker_query.iter_mut() {
+        if !tracker.enabled || tracker.is_recovering() {
+            continue;
+        }
+
+        let grounded = ground.is_some_and(|ground| ground.is_grounded && ground.ground_angle <= tracker.max_safe_slope);
+        // Vehicles are grounded while their wheels touch the ground
+        #[cfg(feature = "vehicles")]
+        let grounded = grounded || vehicle_query.get(entity).is_ok_and(|vehicle| vehicle.is_on_ground);
+        if !grounded {
+            tracker.grounded_time = 0.0;
+            continue;
+        }
+
+        tracker.grounded_time += delta;
+        if tracker.grounded_time < tracker.min_grounded_time {
+            continue;
+        }
+
+        let position = transform.translation();
+        if !is_position_safe(&bounds, &volume_query, position) {
+            continue;
+        }
+
+        let far_enough = tracker
+            .last_safe_position
+            .is_none_or(|last| last.distance(position) >= tracker.min_sample_distance);
+        if far_enough {
+            tracker.last_safe_position = Some(position);
+            tracker.last_safe_rotation = transform.rotation();
+        }
+    }
+}
+
+/// Damage, deaths and reports of bounds violations
+#[derive(SystemParam)]
+pub struct BoundsOutputs<'w> {
+    pub damage_queue: ResMut<'w, DamageEventQueue>,
+    pub death_queue: ResMut<'w, DeathEventQueue>,
+    pub event_queue: ResMut<'w, OutOfBoundsEventQueue>,
+}
+
+type BoundedEntity<'a> = (
+    Entity,
+    &'a GlobalTransform,
+    Option<&'a mut SafeGroundTracker>,
+    Option<&'a mut Health>,
+    Option<&'a RigidBody>,
+    Has<Player>,
+);
+type BoundedEntityFilter = Or<(With<Health>, With<SafeGroundTracker>, With<RigidBody>)>;
+
+/// System to apply kill-Z, world bounds and kill volumes
+pub fn check_world_bounds(
+    mut commands: Commands,
+    time: Res<Time>,
+    bounds: Res<WorldBounds>,
+    outputs: BoundsOutputs,
+    mut entity_query: Query<BoundedEntity, BoundedEntityFilter>,
+    volume_query: Query<(&GlobalTransform, &KillVolume)>,
+    #[cfg(feature = "vehicles")] vehicle_query: Query<(), With<Vehicle>>,
+) {
+    if !bounds.enabled {
+        return;
+    }
+    let BoundsOutputs { mut damage_queue, mut death_queue, mut event_queue } = outputs;
+
+    let delta = time.delta_secs();
+
+    for (entity, transform, tracker, health, rigid_body, is_player) in entity_query.iter_mut() {
+        let position = transform.translation();
+        #[cfg(feature = "vehicles")]
+        let is_vehicle = vehicle_query.contains(entity);
+        #[cfg(not(feature = "vehicles"))]
+        let is_vehicle = false;
+
+        // Kill-Z wins over bounds, bounds over volumes
+        let mut violation: Option<(OutOfBoundsReason, KillVolumeEffect, DamageType)> = None;
+        if bounds.kill_z.is_some_and(|kill_z| position.y < kill_z) {
+            violation = Some((OutOfBoundsReason::BelowKillZ, bounds.kill_z_effect, DamageType::Fall));
+        } else if bounds.bounds.is_some_and(|world_box| !world_box.contains(position)) {
+            violation = Some((OutOfBoundsReason::OutsideWorldBounds, KillVolumeEffect::Recover { damage: 0.0 }, DamageType::Environmental));
+        } else {
+            for (volume_transform, volume) in volume_query.iter() {
+                let affected = if is_player {
+                    volume.affect_players
+                } else if is_vehicle {
+                    volume.affect_vehicles
+                } else {
+                    volume.affect_npcs
+                };
+                if volume.active && affected && volume.contains(volume_transform, position) {
+                    violation = Some((OutOfBoundsReason::KillVolume, volume.effect, volume.damage_type));
+                    break;
+                }
+            }
+        }
+
+        let Some((reason, effect, damage_type)) = violation else {
+            continue;
+        };
+
+        let mut tracker = tracker;
+        if tracker.as_ref().is_some_and(|tracker| tracker.is_recovering()) {
+            continue;
+        }
+        if health.as_ref().is_some_and(|health| health.is_dead) {
+            continue;
+        }
+
+        // Recovery needs somewhere to go back to; otherwise fall back to a kill
+        let effect = match effect {
+            KillVolumeEffect::Recover { .. }
+                if tracker.as_ref().is_none_or(|tracker| !tracker.enabled || tracker.last_safe_position.is_none()) =>
+            {
+                KillVolumeEffect::Kill
+            }
+            effect => effect,
+        };
+
+        match effect {
+            KillVolumeEffect::Kill => {
+                if let Some(mut health) = health {
+                    health.current = 0.0;
+                    health.is_dead = true;
+                    death_queue.send(DeathEvent { entity });
+                } else if rigid_body == Some(&RigidBody::Dynamic) && tracker.is_none() {
+                    if bounds.despawn_props_below_kill_z || reason == OutOfBoundsReason::KillVolume {
+                        commands.entity(entity).despawn();
+                    }
+                } else {
+                    continue;
+                }
+
+                event_queue.send(OutOfBoundsEvent {
+                    entity,
+                    reason,
+                    position,
+                    recovered_to: None,
+                    killed: true,
+                });
+            }
+            KillVolumeEffect::Damage { per_second } => {
+                if health.is_some() {
+                    damage_queue.send(DamageEvent {
+                        amount: per_second * delta,
+                        damage_type,
+                        source: None,
+                        target: entity,
+                        position: Some(position),
+                        direction: None,
+                        ignore_shield: true,
+                    });
+                }
+            }
+            KillVolumeEffect::Recover { damage } => {
+                let Some(tracker) = tracker.as_mut() else {
+                    continue;
+                };
+                tracker.phase = RecoveryPhase::FadingOut;
+                tracker.phase_timer = 0.0;
+                tracker.pending_damage = damage;
+
+                event_queue.send(OutOfBoundsEvent {
+                    entity,
+                    reason,
+                    position,
+                    recovered_to: tracker.last_safe_position,
+                    killed: false,
+                });
+            }
+        }
+    }
+}
+
+type RecoveringEntity<'a> = (
+    Entity,
+    &'a mut SafeGroundTracker,
+    &'a mut Transform,
+    Option<&'a mut LinearVelocity>,
+    Option<&'a mut AngularVelocity>,
+);
+
+/// System to run the fade-out, teleport, fade-in recovery sequence
+pub fn update_out_of_bounds_recovery(
+    time: Res<Time>,
+    bounds: Res<WorldBounds>,
+    mut damage_queue: ResMut<DamageEventQueue>,
+    mut tracker_query: Query<RecoveringEntity>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut tracker, mut transform, linear, angular) in tracker_query.iter_mut() {
+        if !tracker.is_recovering() {
+            continue;
+        }
+
+        tracker.phase_timer += delta;
+
+        // Freeze the body for the whole sequence
+        if let Some(mut linear) = linear {
+            linear.0 = Vec3::ZERO;
+        }
+        if let Some(mut angular) = angular {
+            angular.0 = Vec3::ZERO;
+        }
+
+        match tracker.phase {
+            RecoveryPhase::FadingOut => {
+                if tracker.phase_timer < bounds.fade_out_duration {
+                    continue;
+                }
+
+                if let Some(safe_position) = tracker.last_safe_position {
+                    transform.translation = safe_position + Vec3::Y * tracker.recovery_lift;
+                    transform.rotation = tracker.last_safe_rotation;
+                }
+
+                if tracker.pending_damage > 0.0 {
+                    damage_queue.send(DamageEvent {
+                        amount: tracker.pending_damage,
+                        damage_type: DamageType::Fall,
+                        source: None,
+                        target: entity,
+                        position: None,
+                        direction: None,
+                        ignore_shield: false,
+                    });
+                    tracker.pending_damage = 0.0;
+                }
+
+                tracker.grounded_time = 0.0;
+                tracker.phase = RecoveryPhase::Holding;
+                tracker.phase_timer = 0.0;
+            }
+            RecoveryPhase::Holding => {
+                if tracker.phase_timer >= bounds.fade_hold_duration {
+                    tracker.phase = RecoveryPhase::FadingIn;
+                    tracker.phase_timer = 0.0;
+                }
+            }
+            RecoveryPhase::FadingIn => {
+                if tracker.phase_timer >= bounds.fade_in_duration {
+                    tracker.phase = RecoveryPhase::None;
+                    tracker.phase_timer = 0.0;
+                }
+            }
+            RecoveryPhase::None => {}
+        }
+    }
+}
+
+/// System to drive the fade overlay from the recovering trackers
+pub fn update_recovery_fade_overlay(
+    bounds: Res<WorldBounds>,
+    tracker_query: Query<&SafeGroundTracker>,
+    mut overlay_query: Query<(&mut RecoveryFadeOverlay, &mut BackgroundColor)>,
+) {
+    let alpha = tracker_query
+        .iter()
+        .filter(|tracker| tracker.show_fade)
+        .map(|tracker| match tracker.phase {
+            RecoveryPhase::None => 0.0,
+            RecoveryPhase::FadingOut => tracker.phase_timer / bounds.fade_out_duration.max(0.001),
+            RecoveryPhase::Holding => 1.0,
+            RecoveryPhase::FadingIn => 1.0 - tracker.phase_timer / bounds.fade_in_duration.max(0.001),
+        })
+        .fold(0.0f32, f32::max)
+        .clamp(0.0, 1.0);
+
+    for (mut overlay, mut background) in overlay_query.iter_mut() {
+        if overlay.alpha == alpha {
+            continue;
+        }
+        overlay.alpha = alpha;
+        background.0 = bounds.fade_color.with_alpha(alpha);
+    }
+}
+
+/// System to draw world bounds, kill-Z and kill volumes
+pub fn draw_world_bounds(
+    mut gizmos: Gizmos,
+    bounds: Res<WorldBounds>,
+    volume_query: Query<(&GlobalTransform, &KillVolume)>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+) {
+    if bounds.draw_debug {
+        if let Some(world_box) = bounds.bounds {
+            gizmos.cuboid(
+                Transform::from_translation((world_box.min + world_box.max) * 0.5)
+                    .with_scale(world_box.max - world_box.min),
+                Color::srgb(0.2, 1.0, 0.2),
+            );
+        }
+
+        if let Some(kill_z) = bounds.kill_z {
+            // Kill plane patch centred under the player
+            let center = player_query.iter().next().map(|transform| transform.translation()).unwrap_or(Vec3::ZERO);
+            let half = 25.0;
+            let corners = [
+                Vec3::new(center.x - half, kill_z, center.z - half),
+                Vec3::new(center.x + half, kill_z, center.z - half),
+                Vec3::new(center.x + half, kill_z, center.z + half),
+                Vec3::new(center.x - half, kill_z, center.z + half),
+                Vec3::new(center.x - half, kill_z, center.z - half),
+            ];
+            gizmos.linestrip(corners, Color::srgb(1.0, 0.2, 0.2));
+        }
+    }
+
+    for (transform, volume) in volume_query.iter() {
+        if !volume.draw_debug && !bounds.draw_debug {
+            continue;
+        }
+
+        let color = match volume.effect {
+            KillVolumeEffect::Kill => Color::srgb(1.0, 0.1, 0.1),
+            KillVolumeEffect::Damage { .. } => Color::srgb(1.0, 0.6, 0.1),
+            KillVolumeEffect::Recover { .. } => Color::srgb(0.3, 0.6, 1.0),
+        };
+        let (scale, rotation, translation) = transform.to_scale_rotation_translation();
+        gizmos.cuboid(
+            Transform::from_translation(translation)
+                .with_rotation(rotation)
+                .with_scale(scale * volume.half_extents * 2.0),
+            if volume.active { color } else { color.with_alpha(0.3) },
+        );
+    }
+}
+
+/// Utility function to check whether a position is a valid recovery point
+pub fn is_position_safe(
+    bounds: &WorldBounds,
+    volume_query: &Query<(&GlobalTransform, &KillVolume)>,
+    position: Vec3,
+) -> bool {
+    if bounds.kill_z.is_some_and(|kill_z| position.y < kill_z) {
+        return false;
+    }
+    if bounds.bounds.is_some_and(|world_box| !world_box.contains(position)) {
+        return false;
+    }
+    !volume_query
+        .iter()
+        .any(|(volume_transform, volume)| volume.active && volume.contains(volume_transform, position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn test_kill_z_recovers_player_and_kills_npc() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins((
+                EventQueuePlugin::<DamageEvent>::default(),
+                EventQueuePlugin::<DeathEvent>::default(),
+                EventQueuePlugin::<OutOfBoundsEvent>::default(),
+            ))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<WorldBounds>()
+            .add_systems(Update, (track_safe_ground, check_world_bounds, update_out_of_bounds_recovery).chain());
+
+        let player = app.world_mut().spawn((
+            Player,
+            Transform::default(),
+            GlobalTransform::IDENTITY,
+            SafeGroundTracker::default(),
+            GroundDetection { is_grounded: true, ..default() },
+        )).id();
+        let npc = app.world_mut().spawn((GlobalTransform::from_xyz(5.0, 0.0, 0.0), Health::default())).id();
+
+        for _ in 0..8 {
+            app.update();
+        }
+        assert_eq!(app.world().get::<SafeGroundTracker>(player).unwrap().last_safe_position, Some(Vec3::ZERO));
+
+        app.world_mut().entity_mut(player).insert((
+            GlobalTransform::from_xyz(0.0, -200.0, 0.0),
+            GroundDetection::default(),
+        ));
+        app.world_mut().entity_mut(npc).insert(GlobalTransform::from_xyz(5.0, -200.0, 0.0));
+        app.update();
+
+        assert!(app.world().get::<Health>(npc).unwrap().is_dead);
+        assert!(app.world().resource::<DeathEventQueue>().iter().any(|event| event.entity == npc));
+        assert!(app.world().resource::<OutOfBoundsEventQueue>().iter().any(|event| {
+            event.entity == player && event.recovered_to == Some(Vec3::ZERO) && !event.killed
+        }));
+        assert!(app.world().get::<SafeGroundTracker>(player).unwrap().is_recovering());
+
+        // The fade-out ends with a teleport back to safe ground and the fall damage
+        for _ in 0..10 {
+            app.update();
+            if app.world().get::<SafeGroundTracker>(player).unwrap().phase == RecoveryPhase::Holding {
+                break;
+            }
+        }
+        assert_eq!(app.world().get::<SafeGroundTracker>(player).unwrap().phase, RecoveryPhase::Holding);
+        assert_eq!(app.world().get::<Transform>(player).unwrap().translation, Vec3::new(0.0, 0.5, 0.0));
+        assert!(app.world().resource::<DamageEventQueue>().iter().any(|event| {
+            event.target == player && event.amount == 10.0 && event.damage_type == DamageType::Fall
+        }));
+    }
+}
@@ -0,0 +1,200 @@
+use bevy::prelude::*;
+use crate::combat::DamageType;
+use crate::utils::EventQueue;
+
+/// Global world limits
+#[derive(Resource, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct WorldBounds {
+    pub enabled: bool,
+    /// Anything below this height is handled by `kill_z_effect`
+    pub kill_z: Option<f32>,
+    pub kill_z_effect: KillVolumeEffect,
+    /// Playable box; leaving it triggers out-of-bounds recovery
+    pub bounds: Option<WorldBoundsBox>,
+    /// Despawn props (no health, no tracker) that fall below kill-Z
+    pub despawn_props_below_kill_z: bool,
+
+    // Recovery
+    pub fade_out_duration: f32,
+    pub fade_hold_duration: f32,
+    pub fade_in_duration: f32,
+    pub fade_color: Color,
+
+    // Debug
+    pub draw_debug: bool,
+}
+
+impl Default for WorldBounds {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            kill_z: Some(-100.0),
+            kill_z_effect: KillVolumeEffect::Recover { damage: 10.0 },
+            bounds: None,
+            despawn_props_below_kill_z: true,
+            fade_out_duration: 0.3,
+            fade_hold_duration: 0.2,
+            fade_in_duration: 0.5,
+            fade_color: Color::BLACK,
+            draw_debug: false,
+        }
+    }
+}
+
+/// Axis aligned playable area
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct WorldBoundsBox {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl WorldBoundsBox {
+    pub fn contains(&self, point: Vec3) -> bool {
+        point.cmpge(self.min).all() && point.cmple(self.max).all()
+    }
+}
+
+/// What happens to an entity entering a kill volume (or falling below kill-Z)
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum KillVolumeEffect {
+    /// Instant death, ignoring invulnerability
+    Kill,
+    /// Continuous damage while inside
+    Damage { per_second: f32 },
+    /// Teleport back to the last safe ground position, optionally dealing damage
+    Recover { damage: f32 },
+}
+
+impl Default for KillVolumeEffect {
+    fn default() -> Self {
+        Self::Kill
+    }
+}
+
+/// Box-shaped kill volume (lava, pits, deep water...)
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct KillVolume {
+    pub active: bool,
+    pub half_extents: Vec3,
+    pub effect: KillVolumeEffect,
+    pub damage_type: DamageType,
+    pub affect_players: bool,
+    pub affect_npcs: bool,
+    /// Only with the `vehicles` feature
+    pub affect_vehicles: bool,
+
+    // Debug
+    pub draw_debug: bool,
+}
+
+impl Default for KillVolume {
+    fn default() -> Self {
+        Self {
+            active: true,
+            half_extents: Vec3::splat(5.0),
+            effect: KillVolumeEffect::Kill,
+            damage_type: DamageType::Environmental,
+            affect_players: true,
+            affect_npcs: true,
+            affect_vehicles: true,
+            draw_debug: false,
+        }
+    }
+}
+
+impl KillVolume {
+    pub fn contains(&self, volume_transform: &GlobalTransform, point: Vec3) -> bool {
+        let local = volume_transform.affine().inverse().transform_point3(point);
+        local.abs().cmple(self.half_extents).all()
+    }
+}
+
+/// Recovery phase of an entity being brought back in bounds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum RecoveryPhase {
+    #[default]
+    None,
+    FadingOut,
+    Holding,
+    FadingIn,
+}
+
+/// Remembers where an entity last stood safely so it can be recovered
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct SafeGroundTracker {
+    pub enabled: bool,
+    /// Seconds of continuous grounding before a position counts as safe
+    pub min_grounded_time: f32,
+    /// Minimum distance between two recorded safe positions
+    pub min_sample_distance: f32,
+    /// Maximum ground angle (degrees) considered safe
+    pub max_safe_slope: f32,
+    /// Lift applied when teleporting back to avoid spawning inside the floor
+    pub recovery_lift: f32,
+    /// Drive the screen fade overlay while recovering
+    pub show_fade: bool,
+
+    // Debug State
+    pub last_safe_position: Option<Vec3>,
+    pub last_safe_rotation: Quat,
+    pub grounded_time: f32,
+    pub phase: RecoveryPhase,
+    pub phase_timer: f32,
+    pub pending_damage: f32,
+}
+
+impl Default for SafeGroundTracker {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_grounded_time: 0.5,
+            min_sample_distance: 0.5,
+            max_safe_slope: 35.0,
+            recovery_lift: 0.5,
+            show_fade: true,
+            last_safe_position: None,
+            last_safe_rotation: Quat::IDENTITY,
+            grounded_time: 0.0,
+            phase: RecoveryPhase::None,
+            phase_timer: 0.0,
+            pending_damage: 0.0,
+        }
+    }
+}
+
+impl SafeGroundTracker {
+    pub fn is_recovering(&self) -> bool {
+        self.phase != RecoveryPhase::None
+    }
+}
+
+/// Full screen overlay used for the recovery fade
+#[derive(Component, Debug, Reflect, Default)]
+#[reflect(Component)]
+pub struct RecoveryFadeOverlay {
+    pub alpha: f32,
+}
+
+/// Why an entity was handled by the world safety systems
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum OutOfBoundsReason {
+    BelowKillZ,
+    OutsideWorldBounds,
+    KillVolume,
+}
+
+/// Out-of-bounds event data
+#[derive(Debug, Clone, Copy)]
+pub struct OutOfBoundsEvent {
+    pub entity: Entity,
+    pub reason: OutOfBoundsReason,
+    pub position: Vec3,
+    pub recovered_to: Option<Vec3>,
+    pub killed: bool,
+}
+
+/// Custom queue for out-of-bounds events
+pub type OutOfBoundsEventQueue = EventQueue<OutOfBoundsEvent>;
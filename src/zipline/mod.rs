@@ -0,0 +1,29 @@
+pub mod types;
+pub mod systems;
+
+use bevy::prelude::*;
+use types::*;
+use systems::*;
+
+pub use types::{
+    Zipline, ZiplineRider, ZiplineDismountReason,
+    ZiplineMountedEvent, ZiplineDismountedEvent,
+};
+pub use systems::is_riding_zipline;
+
+pub struct ZiplinePlugin;
+
+impl Plugin for ZiplinePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .register_type::<Zipline>()
+            .register_type::<ZiplineRider>()
+            .add_systems(Update, (
+                sync_zipline_anchors,
+                handle_zipline_mount,
+                update_zipline_ride,
+                restrict_combat_on_zipline,
+                draw_zipline_cables.in_set(crate::headless::PresentationSet),
+            ).chain());
+    }
+}
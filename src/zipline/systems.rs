@@ -0,0 +1,364 @@
+use bevy::prelude::*;
+use avian3d::prelude::*;
+use crate::character::{CharacterController, Player};
+use crate::input::InputState;
+use crate::interaction::InteractionEvent;
+use crate::utils::QueueReader;
+use crate::weapons::WeaponManager;
+use super::types::*;
+
+/// System to keep cable end points in sync with their anchor entities
+pub fn sync_zipline_anchors(
+    mut zipline_query: Query<&mut Zipline>,
+    anchor_query: Query<&GlobalTransform>,
+) {
+    for mut zipline in zipline_query.iter_mut() {
+        if let Some(anchor) = zipline.start_anchor {
+            if let Ok(anchor_transform) = anchor_query.get(anchor) {
+                let position = anchor_transform.translation();
+                if zipline.start != position {
+                    zipline.start = position;
+                }
+            }
+        }
+        if let Some(anchor) = zipline.end_anchor {
+            if let Ok(anchor_transform) = anchor_query.get(anchor) {
+                let position = anchor_transform.translation();
+                if zipline.end != position {
+                    zipline.end = position;
+                }
+            }
+        }
+    }
+}
+
+/// System to draw the sagging cable
+pub fn draw_zipline_cables(
+    mut gizmos: Gizmos,
+    zipline_query: Query<&Zipline>,
+) {
+    for zipline in zipline_query.iter() {
+        if !zipline.draw_cable {
+            continue;
+        }
+
+        let segments = zipline.cable_segments.max(2);
+        let points = (0..=segments).map(|i| zipline.point_at(i as f32 / segments as f32));
+        gizmos.linestrip(points, zipline.cable_color);
+    }
+}
+
+type MountingRider<'a> = (
+    Entity,
+    &'a mut ZiplineRider,
+    &'a CharacterController,
+    &'a Transform,
+    Option<&'a mut LinearVelocity>,
+    Has<Player>,
+);
+
+/// System to mount ziplines, either through the interaction system or by pressing interact near a cable
+pub fn handle_zipline_mount(
+    mut commands: Commands,
+    input_state: Res<InputState>,
+    mut interaction_events: QueueReader<InteractionEvent>,
+    mut rider_query: Query<MountingRider>,
+    zipline_query: Query<(Entity, &Zipline)>,
+) {
+    // Interaction-driven mounts (works for any rider the interaction system targets)
+    let mut requests: Vec<(Entity, Option<Entity>)> = interaction_events
+        .read()
+        .filter(|event| zipline_query.contains(event.target))
+        .map(|event| (event.source, Some(event.target)))
+        .collect();
+
+    // Proximity mounts for the player
+    if input_state.interact_pressed {
+        for (entity, _, _, _, _, is_player) in rider_query.iter() {
+            if is_player && !requests.iter().any(|(source, _)| *source == entity) {
+                requests.push((entity, None));
+            }
+        }
+    }
+
+    for (rider_entity, target) in requests {
+        let Ok((_, mut rider, character, transform, velocity, _)) = rider_query.get_mut(rider_entity) else {
+            continue;
+        };
+
+        if !rider.can_use_ziplines || rider.riding || rider.cooldown_timer > 0.0 || character.is_dead {
+            continue;
+        }
+
+        // Pick the requested zipline or the nearest one in reach
+        let hands = transform.translation - rider.hang_offset;
+        let mut best: Option<(Entity, f32, f32)> = None;
+        for (zipline_entity, zipline) in zipline_query.iter() {
+            if !zipline.active || target.is_some_and(|target| target != zipline_entity) {
+                continue;
+            }
+
+            let parameter = zipline.closest_parameter(hands);
+            let distance = zipline.point_at(parameter).distance(hands);
+            if distance > zipline.mount_radius {
+                continue;
+            }
+            if best.is_none_or(|(_, _, best_distance)| distance < best_distance) {
+                best = Some((zipline_entity, parameter, distance));
+            }
+        }
+
+        let Some((zipline_entity, parameter, _)) = best else {
+            continue;
+        };
+        let Ok((_, zipline)) = zipline_query.get(zipline_entity) else {
+            continue;
+        };
+
+        // Ride toward the lower end unless the cable is one-way
+        let downhill = if zipline.end.y <= zipline.start.y { 1.0 } else { -1.0 };
+        let direction = if zipline.bidirectional { downhill } else { 1.0 };
+
+        rider.riding = true;
+        rider.current_zipline = Some(zipline_entity);
+        rider.parameter = parameter;
+        rider.speed = zipline.min_speed * direction;
+        rider.braking = false;
+
+        if let Some(mut velocity) = velocity {
+            velocity.0 = Vec3::ZERO;
+        }
+
+        commands.trigger(ZiplineMountedEvent {
+            rider: rider_entity,
+            zipline: zipline_entity,
+            parameter,
+        });
+    }
+}
+
+type RidingRider<'a> = (
+    Entity,
+    &'a mut ZiplineRider,
+    &'a mut CharacterController,
+    &'a mut Transform,
+    Option<&'a mut LinearVelocity>,
+    Option<&'a InputState>,
+);
+
+/// System to move riders along the cable, with slope acceleration, braking and dismounts
+pub fn update_zipline_ride(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rider_query: Query<RidingRider>,
+    global_input: Res<InputState>,
+    zipline_query: Query<&Zipline>,
+) {
+    let delta = time.delta_secs();
+
+    for (
+        rider_entity,
+        mut rider,
+        mut character,
+        mut transform,
+        mut velocity,
+        local_input,
+    ) in rider_query.iter_mut() {
+        if rider.cooldown_timer > 0.0 {
+            rider.cooldown_timer -= delta;
+        }
+
+        if !rider.riding {
+            continue;
+        }
+
+        let Some(zipline_entity) = rider.current_zipline else {
+            rider.riding = false;
+            continue;
+        };
+
+        let input = local_input.unwrap_or(&global_input);
+
+        let zipline = zipline_query.get(zipline_entity).ok().filter(|zipline| zipline.active);
+        let reason = match zipline {
+            None => Some(ZiplineDismountReason::ZiplineDisabled),
+            Some(_) if character.is_dead => Some(ZiplineDismountReason::Died),
+            Some(_) if input.jump_pressed => Some(ZiplineDismountReason::Jumped),
+            _ => None,
+        };
+
+        let mut exit_velocity = Vec3::ZERO;
+        let mut reached_end = false;
+
+        if let (Some(zipline), None) = (zipline, reason) {
+            character.zero_gravity_mode = true;
+
+            // Gravity pulls along the tangent, friction and braking slow the rider
+            let tangent = zipline.tangent_at(rider.parameter);
+            let slope_acceleration = -tangent.y * zipline.gravity_acceleration;
+            rider.speed += slope_acceleration * delta;
+            if !zipline.bidirectional {
+                rider.speed = rider.speed.max(0.0);
+            }
+
+            let direction = if rider.speed >= 0.0 { 1.0 } else { -1.0 };
+            rider.braking = input.crouch_pressed || input.block_pressed;
+            let mut deceleration = zipline.friction;
+            if rider.braking {
+                deceleration += zipline.brake_deceleration;
+            }
+            let magnitude = (rider.speed.abs() - deceleration * delta).clamp(0.0, zipline.max_speed);
+            // Braking may stop the rider, otherwise keep a minimum crawl speed
+            let magnitude = if rider.braking { magnitude } else { magnitude.max(zipline.min_speed) };
+            rider.speed = magnitude * direction;
+
+            // Convert world speed into curve parameter speed
+            let curve_speed = zipline.derivative_at(rider.parameter).length().max(0.001);
+            rider.parameter = (rider.parameter + rider.speed / curve_speed * delta).clamp(0.0, 1.0);
+
+            transform.translation = zipline.point_at(rider.parameter) + rider.hang_offset;
+
+            // Face the direction of travel
+            let facing = Vec3::new(tangent.x, 0.0, tangent.z) * direction;
+            if facing.length_squared() > 0.0001 {
+                transform.look_to(facing.normalize(), Vec3::Y);
+            }
+
+            if let Some(velocity) = velocity.as_mut() {
+                velocity.0 = Vec3::ZERO;
+            }
+
+            reached_end = (rider.parameter >= 1.0 && rider.speed > 0.0) || (rider.parameter <= 0.0 && rider.speed < 0.0);
+            exit_velocity = tangent * rider.speed;
+        } else if let Some(zipline) = zipline {
+            exit_velocity = zipline.tangent_at(rider.parameter) * rider.speed;
+        }
+
+        let reason = if reached_end { Some(ZiplineDismountReason::ReachedEnd) } else { reason };
+        let Some(reason) = reason else {
+            continue;
+        };
+
+        match reason {
+            ZiplineDismountReason::Jumped => {
+                exit_velocity += Vec3::Y * rider.jump_dismount_force;
+            }
+            ZiplineDismountReason::ReachedEnd => {
+                if let Some(zipline) = zipline {
+                    transform.translation += zipline.end_dismount_offset;
+                }
+                exit_velocity = Vec3::ZERO;
+            }
+            _ => {}
+        }
+
+        rider.riding = false;
+        rider.current_zipline = None;
+        rider.speed = 0.0;
+        rider.braking = false;
+        rider.cooldown_timer = rider.mount_cooldown;
+        character.zero_gravity_mode = false;
+
+        if let Some(velocity) = velocity.as_mut() {
+            velocity.0 = exit_velocity;
+        }
+
+        commands.trigger(ZiplineDismountedEvent {
+            rider: rider_entity,
+            zipline: zipline_entity,
+            reason,
+            exit_velocity,
+        });
+    }
+}
+
+/// System to block weapon usage while riding a zipline
+pub fn restrict_combat_on_zipline(
+    mut rider_query: Query<(&mut ZiplineRider, &mut WeaponManager)>,
+    zipline_query: Query<&Zipline>,
+) {
+    for (mut rider, mut weapon_manager) in rider_query.iter_mut() {
+        let restrict = rider.riding && rider
+            .current_zipline
+            .and_then(|zipline| zipline_query.get(zipline).ok())
+            .is_some_and(|zipline| zipline.restrict_combat);
+
+        if restrict {
+            // Someone else (a vehicle, a dialog...) already made the player busy
+            if !rider.busy_set_by_zipline && !weapon_manager.player_currently_busy {
+                rider.busy_set_by_zipline = true;
+                rider.weapons_mode_before_ride = weapon_manager.weapons_mode_active;
+                weapon_manager.player_currently_busy = true;
+                weapon_manager.weapons_mode_active = false;
+                weapon_manager.aiming_in_third_person = false;
+                weapon_manager.aiming_in_first_person = false;
+            }
+        } else if rider.busy_set_by_zipline {
+            rider.busy_set_by_zipline = false;
+            weapon_manager.player_currently_busy = false;
+            weapon_manager.weapons_mode_active = rider.weapons_mode_before_ride;
+        }
+    }
+}
+
+/// Utility function to check if a rider is on a zipline
+pub fn is_riding_zipline(rider: &ZiplineRider) -> bool {
+    rider.riding
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::EventQueuePlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    #[test]
+    fn test_zipline_ride_to_end_restricts_combat() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(EventQueuePlugin::<InteractionEvent>::default())
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)))
+            .init_resource::<InputState>()
+            .add_systems(Update, (handle_zipline_mount, update_zipline_ride, restrict_combat_on_zipline).chain());
+
+        let zipline = Zipline::default();
+        let end = zipline.end;
+        app.world_mut().spawn(zipline);
+
+        let rider = ZiplineRider::default();
+        let player = app.world_mut().spawn((
+            Player,
+            Transform::from_translation(-rider.hang_offset),
+            rider,
+            CharacterController::default(),
+            WeaponManager::default(),
+        )).id();
+
+        app.insert_resource(InputState { interact_pressed: true, ..default() });
+        app.update();
+        app.insert_resource(InputState::default());
+        assert!(app.world().get::<ZiplineRider>(player).unwrap().riding);
+        let weapon_manager = app.world().get::<WeaponManager>(player).unwrap();
+        assert!(weapon_manager.player_currently_busy);
+        assert!(!weapon_manager.weapons_mode_active);
+
+        for _ in 0..200 {
+            app.update();
+            if !app.world().get::<ZiplineRider>(player).unwrap().riding {
+                break;
+            }
+        }
+
+        let rider = app.world().get::<ZiplineRider>(player).unwrap();
+        assert!(!rider.riding);
+        assert!(rider.cooldown_timer > 0.0);
+        let expected = end + rider.hang_offset + Zipline::default().end_dismount_offset;
+        assert!(app.world().get::<Transform>(player).unwrap().translation.distance(expected) < 0.01);
+
+        // The ride's busy flag is cleared and the weapons mode restored
+        let weapon_manager = app.world().get::<WeaponManager>(player).unwrap();
+        assert!(!weapon_manager.player_currently_busy);
+        assert!(weapon_manager.weapons_mode_active);
+    }
+}
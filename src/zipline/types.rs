@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+
+/// Zipline cable component (attached to the cable entity)
+///
+/// The cable runs from `start` to `end` in world space. When anchor entities are
+/// set, their global translations override the stored points every frame.
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct Zipline {
+    // Cable Settings
+    pub active: bool,
+    pub start: Vec3,
+    pub end: Vec3,
+    pub start_anchor: Option<Entity>,
+    pub end_anchor: Option<Entity>,
+    /// Maximum downward sag at the middle of the cable
+    pub sag: f32,
+
+    // Ride Settings
+    pub gravity_acceleration: f32,
+    pub friction: f32,
+    pub min_speed: f32,
+    pub max_speed: f32,
+    pub brake_deceleration: f32,
+    pub mount_radius: f32,
+    /// Allows riding from the end anchor back toward the start
+    pub bidirectional: bool,
+    pub end_dismount_offset: Vec3,
+    pub restrict_combat: bool,
+
+    // Rendering Settings
+    pub draw_cable: bool,
+    pub cable_segments: usize,
+    pub cable_color: Color,
+}
+
+impl Default for Zipline {
+    fn default() -> Self {
+        Self {
+            active: true,
+            start: Vec3::ZERO,
+            end: Vec3::new(0.0, -5.0, 20.0),
+            start_anchor: None,
+            end_anchor: None,
+            sag: 0.5,
+            gravity_acceleration: 9.81,
+            friction: 0.5,
+            min_speed: 2.0,
+            max_speed: 18.0,
+            brake_deceleration: 12.0,
+            mount_radius: 1.5,
+            bidirectional: false,
+            end_dismount_offset: Vec3::new(0.0, -1.0, 0.0),
+            restrict_combat: true,
+            draw_cable: true,
+            cable_segments: 24,
+            cable_color: Color::srgb(0.2, 0.2, 0.2),
+        }
+    }
+}
+
+impl Zipline {
+    /// Point on the sagging cable for a normalized parameter `t` in [0, 1]
+    pub fn point_at(&self, t: f32) -> Vec3 {
+        let t = t.clamp(0.0, 1.0);
+        self.start.lerp(self.end, t) - Vec3::Y * self.sag * 4.0 * t * (1.0 - t)
+    }
+
+    /// Unnormalized derivative of the cable curve at `t`
+    pub fn derivative_at(&self, t: f32) -> Vec3 {
+        let t = t.clamp(0.0, 1.0);
+        (self.end - self.start) - Vec3::Y * self.sag * 4.0 * (1.0 - 2.0 * t)
+    }
+
+    /// Unit tangent of the cable at `t`, pointing from start to end
+    pub fn tangent_at(&self, t: f32) -> Vec3 {
+        self.derivative_at(t).normalize_or_zero()
+    }
+
+    /// Returns the curve parameter closest to a world position
+    pub fn closest_parameter(&self, position: Vec3) -> f32 {
+        let segments = self.cable_segments.max(2);
+        let mut best_t = 0.0;
+        let mut best_distance = f32::MAX;
+        for i in 0..=segments {
+            let t = i as f32 / segments as f32;
+            let distance = self.point_at(t).distance_squared(position);
+            if distance < best_distance {
+                best_distance = distance;
+                best_t = t;
+            }
+        }
+        best_t
+    }
+}
+
+/// Zipline rider component (attached to characters that can use ziplines)
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component)]
+pub struct ZiplineRider {
+    pub can_use_ziplines: bool,
+    /// Offset from the cable to the rider's root (hands on the cable)
+    pub hang_offset: Vec3,
+    pub jump_dismount_force: f32,
+    pub mount_cooldown: f32,
+
+    // Debug State
+    pub riding: bool,
+    pub current_zipline: Option<Entity>,
+    pub parameter: f32,
+    /// Signed speed along the cable (positive toward the end anchor)
+    pub speed: f32,
+    pub braking: bool,
+    pub cooldown_timer: f32,
+    pub weapons_mode_before_ride: bool,
+    /// The ride made the player busy, so the ride's end clears it
+    pub busy_set_by_zipline: bool,
+}
+
+impl Default for ZiplineRider {
+    fn default() -> Self {
+        Self {
+            can_use_ziplines: true,
+            hang_offset: Vec3::new(0.0, -1.9, 0.0),
+            jump_dismount_force: 5.0,
+            mount_cooldown: 0.5,
+            riding: false,
+            current_zipline: None,
+            parameter: 0.0,
+            speed: 0.0,
+            braking: false,
+            cooldown_timer: 0.0,
+            weapons_mode_before_ride: true,
+            busy_set_by_zipline: false,
+        }
+    }
+}
+
+/// Reason a zipline ride ended
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum ZiplineDismountReason {
+    ReachedEnd,
+    Jumped,
+    Died,
+    ZiplineDisabled,
+}
+
+/// Event for when a character mounts a zipline
+#[derive(Event, Debug, Reflect)]
+pub struct ZiplineMountedEvent {
+    pub rider: Entity,
+    pub zipline: Entity,
+    pub parameter: f32,
+}
+
+/// Event for when a character leaves a zipline
+#[derive(Event, Debug, Reflect)]
+pub struct ZiplineDismountedEvent {
+    pub rider: Entity,
+    pub zipline: Entity,
+    pub reason: ZiplineDismountReason,
+    pub exit_velocity: Vec3,
+}